@@ -0,0 +1,158 @@
+//! Claude Code 插件同步
+//!
+//! Claude Code 通过 settings.json 的 `enabledPlugins` 决定启用哪些插件，而每个供应商的
+//! settings_config 只保存了它被回填时的快照。切换到较早保存的供应商会把新装的插件
+//! 从 `enabledPlugins` 中抹掉，看起来就像插件"消失"了。
+//!
+//! 写入 Claude Live 配置前在这里合并：
+//! 1. 供应商自身保存的 `enabledPlugins`
+//! 2. 当前 Live 配置中的 `enabledPlugins`（用户最近一次的启用/停用选择优先）
+//! 3. `~/.claude/plugins/installed_plugins.json` 中用户级安装、但尚未出现的插件（启用）
+//!
+//! 可在设置中关闭（`syncClaudePlugins`）。
+
+use std::path::PathBuf;
+
+use serde_json::{Map, Value};
+
+const ENABLED_PLUGINS_KEY: &str = "enabledPlugins";
+
+pub fn installed_plugins_path() -> PathBuf {
+    crate::config::get_claude_config_dir()
+        .join("plugins")
+        .join("installed_plugins.json")
+}
+
+/// 解析 installed_plugins.json，返回用户级安装的插件 ID（`name@marketplace`）
+///
+/// v1 格式每个插件对应一个对象；v2 格式对应安装记录数组，其中 `scope` 为
+/// `project` / `local` 的记录只对特定项目生效，不应写入全局 `enabledPlugins`。
+fn user_scoped_plugins(installed: &Value) -> Vec<String> {
+    let Some(plugins) = installed.get("plugins").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    plugins
+        .iter()
+        .filter(|(_, entry)| match entry {
+            Value::Array(installs) => installs.iter().any(|install| {
+                matches!(
+                    install.get("scope").and_then(Value::as_str),
+                    None | Some("user")
+                )
+            }),
+            Value::Object(_) => true,
+            _ => false,
+        })
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+fn enabled_plugins(settings: Option<&Value>) -> Option<&Map<String, Value>> {
+    settings?.get(ENABLED_PLUGINS_KEY)?.as_object()
+}
+
+/// 合并 `enabledPlugins`，返回新增的插件数量
+fn merge_enabled_plugins(
+    settings: &mut Value,
+    live: Option<&Value>,
+    installed: &[String],
+) -> usize {
+    let Some(obj) = settings.as_object_mut() else {
+        return 0;
+    };
+    let mut merged = obj
+        .get(ENABLED_PLUGINS_KEY)
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    let before = merged.len();
+
+    if let Some(live_plugins) = enabled_plugins(live) {
+        for (id, enabled) in live_plugins {
+            merged.insert(id.clone(), enabled.clone());
+        }
+    }
+    for id in installed {
+        merged.entry(id.clone()).or_insert(Value::Bool(true));
+    }
+
+    let added = merged.len() - before;
+    if !merged.is_empty() {
+        obj.insert(ENABLED_PLUGINS_KEY.to_string(), Value::Object(merged));
+    }
+    added
+}
+
+/// 在写入 Claude Live 配置前同步插件启用列表
+///
+/// `live` 为即将被覆盖的 settings.json 内容。读取失败的 installed_plugins.json
+/// 只记录警告，不影响切换。
+pub fn sync_enabled_plugins(settings: &mut Value, live: Option<&Value>) {
+    if !crate::settings::get_settings().sync_claude_plugins {
+        return;
+    }
+    let path = installed_plugins_path();
+    let installed = if path.exists() {
+        match crate::config::read_json_file::<Value>(&path) {
+            Ok(value) => user_scoped_plugins(&value),
+            Err(e) => {
+                log::warn!("读取 Claude 插件安装记录失败，仅保留已启用插件: {e}");
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    let added = merge_enabled_plugins(settings, live, &installed);
+    if added > 0 {
+        log::info!("Claude 插件同步：已将 {added} 个插件合并到 enabledPlugins");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_user_scoped_plugins_from_both_formats() {
+        let v1 = json!({
+            "version": 1,
+            "plugins": {"review@official": {"version": "1.0.0"}}
+        });
+        assert_eq!(user_scoped_plugins(&v1), ["review@official"]);
+
+        let v2 = json!({
+            "version": 2,
+            "plugins": {
+                "review@official": [{"scope": "user", "version": "1.0.0"}],
+                "lint@team": [{"scope": "project", "projectPath": "/work/app"}]
+            }
+        });
+        assert_eq!(user_scoped_plugins(&v2), ["review@official"]);
+        assert!(user_scoped_plugins(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_live_choices_and_enables_new_installs() {
+        let mut settings = json!({
+            "env": {"ANTHROPIC_BASE_URL": "https://relay.example.com"},
+            "enabledPlugins": {"old@official": true, "review@official": true}
+        });
+        let live = json!({"enabledPlugins": {"review@official": false}});
+        let installed = vec!["review@official".to_string(), "docs@official".to_string()];
+
+        let added = merge_enabled_plugins(&mut settings, Some(&live), &installed);
+
+        assert_eq!(added, 1);
+        assert_eq!(
+            settings["enabledPlugins"],
+            json!({"old@official": true, "review@official": false, "docs@official": true})
+        );
+
+        let mut bare = json!({"env": {}});
+        assert_eq!(merge_enabled_plugins(&mut bare, None, &[]), 0);
+        assert!(bare.get("enabledPlugins").is_none());
+    }
+}
@@ -0,0 +1,164 @@
+//! `cc-switch alias` 子命令：查看与编辑模型别名表
+//!
+//! 别名可以写在供应商配置（如 `provider add --model main`）、代理请求与用量查价中，
+//! 修改后当前供应商的 Live 配置会立即按新模型重写。
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::services::model_alias::{self, ModelAlias};
+
+#[derive(Debug, PartialEq, Eq)]
+enum AliasCommand {
+    List { json: bool },
+    Set { alias: String, model: String },
+    Remove { alias: String },
+}
+
+fn parse_alias_args(args: &[String]) -> Result<AliasCommand, String> {
+    let json = args.iter().any(|arg| arg == "--json");
+    let words: Vec<&str> = args
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| *arg != "--json")
+        .collect();
+    if let Some(flag) = words.iter().find(|word| word.starts_with('-')) {
+        return Err(format!("unknown argument: {flag}"));
+    }
+    match words.as_slice() {
+        [] | ["list"] => Ok(AliasCommand::List { json }),
+        ["set", alias, model] => Ok(AliasCommand::Set {
+            alias: alias.to_string(),
+            model: model.to_string(),
+        }),
+        ["set", ..] => Err("usage: cc-switch alias set <alias> <model>".to_string()),
+        ["rm" | "remove", alias] => Ok(AliasCommand::Remove {
+            alias: alias.to_string(),
+        }),
+        ["rm" | "remove", ..] => Err("usage: cc-switch alias rm <alias>".to_string()),
+        [other, ..] => Err(format!("unknown alias subcommand: {other}")),
+    }
+}
+
+/// 设置别名：已存在（不区分大小写）则更新目标，否则追加到末尾
+fn upsert(aliases: &mut Vec<ModelAlias>, alias: &str, model: &str) {
+    match aliases
+        .iter_mut()
+        .find(|entry| entry.alias.eq_ignore_ascii_case(alias))
+    {
+        Some(entry) => entry.model = model.to_string(),
+        None => aliases.push(ModelAlias {
+            alias: alias.to_string(),
+            model: model.to_string(),
+        }),
+    }
+}
+
+fn render_list(aliases: &[ModelAlias]) -> String {
+    let width = aliases
+        .iter()
+        .map(|entry| entry.alias.chars().count())
+        .max()
+        .unwrap_or(0);
+    aliases
+        .iter()
+        .map(|entry| {
+            let pad = width - entry.alias.chars().count();
+            format!("{}{}  → {}\n", entry.alias, " ".repeat(pad), entry.model)
+        })
+        .collect()
+}
+
+/// `cc-switch alias [list] [--json]`、`alias set <alias> <model>`、`alias rm <alias>`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let command = match parse_alias_args(args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+
+    let mut aliases = crate::settings::model_aliases();
+    match command {
+        AliasCommand::List { json } => {
+            if json {
+                match serde_json::to_string_pretty(&aliases) {
+                    Ok(text) => println!("{text}"),
+                    Err(e) => {
+                        eprintln!("error: {e}");
+                        return EXIT_FAILURE;
+                    }
+                }
+            } else if aliases.is_empty() {
+                eprintln!(
+                    "{}",
+                    match lang {
+                        CliLang::Zh => "尚未配置模型别名",
+                        CliLang::En => "no model aliases configured",
+                    }
+                );
+            } else {
+                print!("{}", render_list(&aliases));
+            }
+            return EXIT_OK;
+        }
+        AliasCommand::Set { alias, model } => upsert(&mut aliases, &alias, &model),
+        AliasCommand::Remove { alias } => {
+            let before = aliases.len();
+            aliases.retain(|entry| !entry.alias.eq_ignore_ascii_case(&alias));
+            if aliases.len() == before {
+                eprintln!("error: unknown alias: {alias}");
+                return EXIT_FAILURE;
+            }
+        }
+    }
+
+    match open_state().and_then(|state| model_alias::save(&state, aliases)) {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            EXIT_FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_subcommands() {
+        assert_eq!(
+            parse_alias_args(&args(&[])).unwrap(),
+            AliasCommand::List { json: false }
+        );
+        assert_eq!(
+            parse_alias_args(&args(&["list", "--json"])).unwrap(),
+            AliasCommand::List { json: true }
+        );
+        assert_eq!(
+            parse_alias_args(&args(&["set", "fast", "gemini-2.0-flash"])).unwrap(),
+            AliasCommand::Set {
+                alias: "fast".to_string(),
+                model: "gemini-2.0-flash".to_string(),
+            }
+        );
+        assert!(parse_alias_args(&args(&["set", "fast"])).is_err());
+        assert!(parse_alias_args(&args(&["rm"])).is_err());
+        assert!(parse_alias_args(&args(&["list", "--all"])).is_err());
+    }
+
+    #[test]
+    fn set_updates_existing_alias_case_insensitively() {
+        let mut aliases = Vec::new();
+        upsert(&mut aliases, "main", "claude-sonnet-4.5");
+        upsert(&mut aliases, "fast", "gemini-2.0-flash");
+        upsert(&mut aliases, "MAIN", "claude-opus-4.1");
+        assert_eq!(aliases.len(), 2);
+        assert_eq!(aliases[0].model, "claude-opus-4.1");
+        assert_eq!(
+            render_list(&aliases),
+            "main  → claude-opus-4.1\nfast  → gemini-2.0-flash\n"
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! `cc-switch backup` 子命令：生成数据库快照备份
+//!
+//! 与 GUI「立即备份」相同，备份写入 `~/.cc-switch/backups/`。
+//! 配合全局 `--progress` 可在 stderr 查看复制进度。
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+
+/// `cc-switch backup [--json]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let mut json = false;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => json = true,
+            other => {
+                eprintln!("error: unknown argument: {other}");
+                return EXIT_USAGE;
+            }
+        }
+    }
+
+    let state = match open_state() {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+    let path = match state.db.backup_database_file() {
+        Ok(Some(path)) => path,
+        Ok(None) => {
+            eprintln!(
+                "{}",
+                match lang {
+                    CliLang::Zh => "数据库文件不存在，无需备份",
+                    CliLang::En => "database file not found, nothing to back up",
+                }
+            );
+            return EXIT_OK;
+        }
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+
+    if json {
+        println!("{}", serde_json::json!({ "path": path }));
+    } else {
+        println!("{}", path.display());
+    }
+    EXIT_OK
+}
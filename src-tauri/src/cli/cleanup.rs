@@ -0,0 +1,196 @@
+//! `cc-switch cleanup` 子命令：列出并归档长期未使用的供应商
+//!
+//! 默认只列出建议归档的供应商；加 `--archive` 才会导出到归档文件并从列表删除。
+
+use std::str::FromStr;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::services::provider_activity::{
+    ProviderActivityService, ProviderLastSeen, DEFAULT_STALE_DAYS,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+struct CleanupArgs {
+    app: Option<String>,
+    days: i64,
+    archive: bool,
+    json: bool,
+}
+
+fn parse_cleanup_args(args: &[String]) -> Result<CleanupArgs, String> {
+    let mut parsed = CleanupArgs {
+        app: None,
+        days: DEFAULT_STALE_DAYS,
+        archive: false,
+        json: false,
+    };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        match flag {
+            "--app" | "-a" => {
+                let value = inline.or_else(|| iter.next().cloned());
+                parsed.app = Some(value.ok_or("--app requires a value")?);
+            }
+            "--days" => {
+                let value = inline
+                    .or_else(|| iter.next().cloned())
+                    .ok_or("--days requires a value")?;
+                parsed.days = value
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|days| *days > 0)
+                    .ok_or_else(|| format!("invalid --days value: {value}"))?;
+            }
+            "--archive" => parsed.archive = true,
+            "--json" => parsed.json = true,
+            other => return Err(format!("unknown argument: {other}")),
+        }
+    }
+    Ok(parsed)
+}
+
+fn render_stale(stale: &[ProviderLastSeen], lang: CliLang) -> String {
+    let id_width = stale
+        .iter()
+        .map(|item| item.provider_id.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for item in stale {
+        let pad = id_width - item.provider_id.chars().count();
+        let idle = item.idle_days.unwrap_or_default();
+        let idle = match lang {
+            CliLang::Zh => format!("闲置 {idle} 天"),
+            CliLang::En => format!("idle {idle}d"),
+        };
+        out.push_str(&format!(
+            "{}{}  {}  {}\n",
+            item.provider_id,
+            " ".repeat(pad),
+            idle,
+            item.provider_name
+        ));
+    }
+    out
+}
+
+/// `cc-switch cleanup [--app <app>] [--days <n>] [--archive] [--json]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse_cleanup_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+    let app_type = match AppType::from_str(parsed.app.as_deref().unwrap_or("claude")) {
+        Ok(app_type) => app_type,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_USAGE;
+        }
+    };
+
+    let state = match open_state() {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+    let stale = match ProviderActivityService::stale(&state, &app_type, parsed.days) {
+        Ok(stale) => stale,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+
+    if parsed.json {
+        match serde_json::to_string_pretty(&stale) {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return EXIT_FAILURE;
+            }
+        }
+    } else if stale.is_empty() {
+        eprintln!(
+            "{}",
+            match lang {
+                CliLang::Zh => format!("没有闲置超过 {} 天的供应商", parsed.days),
+                CliLang::En => format!("no provider idle for {} days or more", parsed.days),
+            }
+        );
+    } else {
+        print!("{}", render_stale(&stale, lang));
+    }
+
+    if !parsed.archive || stale.is_empty() {
+        if !parsed.archive && !stale.is_empty() && !parsed.json {
+            eprintln!(
+                "{}",
+                match lang {
+                    CliLang::Zh => "加 --archive 归档以上供应商",
+                    CliLang::En => "re-run with --archive to archive these providers",
+                }
+            );
+        }
+        return EXIT_OK;
+    }
+
+    let ids: Vec<String> = stale.into_iter().map(|item| item.provider_id).collect();
+    match ProviderActivityService::archive(&state, app_type, &ids) {
+        Ok(result) => {
+            eprintln!(
+                "{}",
+                match lang {
+                    CliLang::Zh => format!(
+                        "已归档 {} 个供应商到 {}",
+                        result.archived.len(),
+                        result.path.display()
+                    ),
+                    CliLang::En => format!(
+                        "archived {} providers to {}",
+                        result.archived.len(),
+                        result.path.display()
+                    ),
+                }
+            );
+            EXIT_OK
+        }
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            EXIT_FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_cleanup_flags() {
+        let parsed =
+            parse_cleanup_args(&args(&["--days=30", "--archive", "--app", "codex"])).unwrap();
+        assert_eq!(
+            parsed,
+            CleanupArgs {
+                app: Some("codex".to_string()),
+                days: 30,
+                archive: true,
+                json: false,
+            }
+        );
+        assert_eq!(parse_cleanup_args(&[]).unwrap().days, DEFAULT_STALE_DAYS);
+        assert!(parse_cleanup_args(&args(&["--days", "0"])).is_err());
+        assert!(parse_cleanup_args(&args(&["stale"])).is_err());
+    }
+}
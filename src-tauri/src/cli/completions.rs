@@ -0,0 +1,565 @@
+//! `cc-switch completions` 子命令：输出 shell 补全脚本
+//!
+//! 脚本本身不写死候选项，按 Tab 时回调隐藏命令 `cc-switch __complete <index> <words...>`
+//! （`index` 为光标所在词的下标，光标处为空词时 `words` 可以不包含它）。候选项在这里
+//! 计算：顶层命令取自 `parse_args` 的分派表，子命令与参数从帮助用法推导，
+//! 应用名、供应商 id / 编号、宏名称实时读取数据库与配置，
+//! 所以 `cc-switch use cla<Tab>` 与供应商补全开箱即用。
+//!
+//! 没有用 clap_complete：命令行不是基于 clap 构建的（各子命令手写解析，项目也不依赖
+//! clap），clap_complete 需要一棵 `clap::Command` 才能生成脚本，而为补全单独维护一棵
+//! 与解析器平行的命令树，只会多出一份容易漂移的声明。帮助用法表是现有的、随参数一同
+//! 维护的唯一声明，补全语法从它推导；供应商等动态候选本来也需要回调二进制，
+//! 静态生成的脚本覆盖不了。
+//!
+//! 按 Tab 时只以只读方式打开数据库查询供应商，不走 `Database::init()`（建表、迁移、
+//! 实例检查），数据库不存在时不给出供应商候选。
+//!
+//! 每行输出一个候选项，`值\t说明` 格式；各 shell 脚本按自身能力展示说明。
+
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use rusqlite::{Connection, OpenFlags};
+
+use super::{CliLang, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::config::get_app_config_dir;
+use crate::services::scenario::MacroService;
+
+/// 支持的 shell
+const SHELLS: &[&str] = &["bash", "zsh", "fish", "powershell"];
+
+/// 从帮助用法表推导出的补全语法：子命令、各命令参数与需要取值的参数
+///
+/// 各子命令的参数解析是手写的，没有可共享的声明表；帮助用法是它们的文档，
+/// 这里直接从中取词，新增参数时只需更新帮助。
+#[derive(Debug, Default)]
+struct Grammar {
+    subcommands: Vec<(&'static str, Vec<&'static str>)>,
+    flags: Vec<(&'static str, Vec<&'static str>)>,
+    valued: Vec<&'static str>,
+}
+
+fn push_unique(
+    table: &mut Vec<(&'static str, Vec<&'static str>)>,
+    key: &'static str,
+    value: &'static str,
+) {
+    match table.iter_mut().find(|(name, _)| *name == key) {
+        Some((_, values)) if values.contains(&value) => {}
+        Some((_, values)) => values.push(value),
+        None => table.push((key, vec![value])),
+    }
+}
+
+impl Grammar {
+    fn from_help(
+        usages: impl Iterator<Item = &'static str>,
+        options: impl Iterator<Item = &'static str>,
+    ) -> Self {
+        let mut grammar = Self::default();
+        for usage in usages {
+            let tokens: Vec<&'static str> = usage.split_whitespace().skip(1).collect();
+            let Some((&command, rest)) = tokens.split_first() else {
+                continue;
+            };
+            if !command.starts_with(|c: char| c.is_ascii_lowercase()) {
+                continue;
+            }
+            // 第二个词为裸词（可带方括号、以 | 分隔）时视为子命令
+            if let Some(first) = rest.first() {
+                let word = first.trim_start_matches('[').trim_end_matches(']');
+                if !word.is_empty()
+                    && word
+                        .split('|')
+                        .all(|sub| sub.starts_with(|c: char| c.is_ascii_lowercase()))
+                    && word
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c == '-' || c == '|')
+                {
+                    for sub in word.split('|') {
+                        push_unique(&mut grammar.subcommands, command, sub);
+                    }
+                }
+            }
+            for (index, raw) in rest.iter().enumerate() {
+                let token = raw.trim_start_matches(['[', '(']);
+                let Some(flag) = flag_name(token) else {
+                    continue;
+                };
+                push_unique(&mut grammar.flags, command, flag);
+                // 参数后紧跟取值占位（且未被括号闭合）时需要取值
+                let closed = token.len() > flag.len();
+                let valued = !closed
+                    && rest
+                        .get(index + 1)
+                        .is_some_and(|next| !next.starts_with(['-', '[', '(', '|']));
+                if valued && !grammar.valued.contains(&flag) {
+                    grammar.valued.push(flag);
+                }
+            }
+        }
+        // 选项表中的别名组，如 `-a, --app <app>`
+        for option in options {
+            let valued = option.contains('<');
+            for flag in option.split([',', ' ']).filter_map(flag_name) {
+                if valued && !grammar.valued.contains(&flag) {
+                    grammar.valued.push(flag);
+                }
+            }
+        }
+        grammar
+    }
+
+    fn lookup(
+        table: &'static [(&'static str, Vec<&'static str>)],
+        command: &str,
+    ) -> &'static [&'static str] {
+        table
+            .iter()
+            .find(|(name, _)| *name == command)
+            .map(|(_, values)| values.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// 形如 `--name` / `-o` 的参数名（去掉尾随的括号与省略号）
+fn flag_name(token: &'static str) -> Option<&'static str> {
+    let name = token.trim_end_matches(|c: char| !(c.is_ascii_alphanumeric() || c == '-'));
+    let rest = name.strip_prefix('-')?;
+    let rest = rest.strip_prefix('-').unwrap_or(rest);
+    (rest.starts_with(|c: char| c.is_ascii_alphabetic())
+        && rest.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+    .then_some(name)
+}
+
+static GRAMMAR: LazyLock<Grammar> =
+    LazyLock::new(|| Grammar::from_help(super::help::usages(), super::help::options()));
+
+/// 需要供应商 id 的子命令：(命令, 子命令)
+const PROVIDER_TARGETS: &[(&str, &str)] = &[
+    ("provider", "use"),
+    ("provider", "reorder"),
+    ("provider", "curl"),
+    ("provider", "duplicate"),
+    ("failover", "add"),
+    ("failover", "rm"),
+    ("failover", "order"),
+];
+
+/// 可补全的供应商：id、名称与列表编号
+#[derive(Debug, Clone)]
+struct ProviderCandidate {
+    id: String,
+    name: String,
+    number: Option<usize>,
+}
+
+/// 补全时读取的动态数据，测试中可替换
+trait CompletionSource {
+    fn providers(&self, app_type: &AppType) -> Vec<ProviderCandidate>;
+    fn macros(&self) -> Vec<String>;
+}
+
+struct LiveSource;
+
+impl CompletionSource for LiveSource {
+    fn providers(&self, app_type: &AppType) -> Vec<ProviderCandidate> {
+        read_providers(app_type).unwrap_or_default()
+    }
+
+    fn macros(&self) -> Vec<String> {
+        MacroService::list()
+            .into_iter()
+            .map(|scenario| scenario.name)
+            .collect()
+    }
+}
+
+/// 只读查询供应商列表；数据库不存在或读取失败时返回 `None`
+fn read_providers(app_type: &AppType) -> Option<Vec<ProviderCandidate>> {
+    let path = get_app_config_dir().join("cc-switch.db");
+    if !path.is_file() {
+        return None;
+    }
+    let conn = Connection::open_with_flags(
+        &path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .ok()?;
+    let mut stmt = conn
+        .prepare("SELECT id, name, sort_index FROM providers WHERE app_type = ?1")
+        .ok()?;
+    let rows = stmt
+        .query_map([app_type.as_str()], |row| {
+            Ok(ProviderCandidate {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                number: row.get::<_, Option<usize>>(2)?.map(|index| index + 1),
+            })
+        })
+        .ok()?;
+    Some(rows.filter_map(Result::ok).collect())
+}
+
+/// 一个候选项：补全值与说明
+type Candidate = (String, String);
+
+fn plain(values: &[&str]) -> Vec<Candidate> {
+    values
+        .iter()
+        .map(|value| (value.to_string(), String::new()))
+        .collect()
+}
+
+fn app_candidates() -> Vec<Candidate> {
+    AppType::all()
+        .map(|app| (app.as_str().to_string(), String::new()))
+        .collect()
+}
+
+/// 供应商候选：id（说明为名称）与编号（说明为 id）；按名称前缀也能匹配到 id
+fn provider_candidates(
+    source: &dyn CompletionSource,
+    app_type: &AppType,
+    current: &str,
+) -> Vec<Candidate> {
+    let lower = current.to_lowercase();
+    let mut candidates = Vec::new();
+    let mut providers = source.providers(app_type);
+    providers.sort_by_key(|provider| provider.number.unwrap_or(usize::MAX));
+    for provider in &providers {
+        if provider.id.starts_with(current) || provider.name.to_lowercase().starts_with(&lower) {
+            candidates.push((provider.id.clone(), provider.name.clone()));
+        }
+    }
+    if current.chars().all(|c| c.is_ascii_digit() || c == '#') {
+        for provider in &providers {
+            let Some(number) = provider.number else {
+                continue;
+            };
+            let number = number.to_string();
+            if number.starts_with(current.trim_start_matches('#')) {
+                candidates.push((number, format!("{} ({})", provider.name, provider.id)));
+            }
+        }
+    }
+    candidates
+}
+
+/// 计算候选项；`preceding` 为光标前的词（不含程序名），`current` 为光标处的部分输入
+fn candidates(
+    source: &dyn CompletionSource,
+    preceding: &[String],
+    current: &str,
+) -> Vec<Candidate> {
+    // 去掉全局参数（`--lang` 连同取值），只保留命令相关的词
+    let mut words: Vec<&str> = Vec::new();
+    let mut iter = preceding.iter().map(String::as_str);
+    while let Some(word) = iter.next() {
+        match word {
+            "--lang" => {
+                iter.next();
+            }
+            "--progress" | "--safe-mode" => {}
+            word if word.starts_with("--lang=") => {}
+            word => words.push(word),
+        }
+    }
+    let previous = preceding.last().map(String::as_str).unwrap_or("");
+    let positionals: Vec<&str> = words
+        .iter()
+        .enumerate()
+        .filter(|(index, word)| {
+            // 取值型参数后面的词不算位置参数
+            !word.starts_with('-')
+                && !index
+                    .checked_sub(1)
+                    .and_then(|prev| words.get(prev))
+                    .is_some_and(|prev| takes_value(prev))
+        })
+        .map(|(_, word)| *word)
+        .collect();
+    let command = positionals.first().copied();
+    let app_type = words
+        .windows(2)
+        .find(|pair| pair[0] == "--app" || pair[0] == "-a")
+        .and_then(|pair| AppType::from_str(pair[1]).ok())
+        .unwrap_or(AppType::Claude);
+
+    let all = match previous {
+        "--lang" => plain(&["zh", "en"]),
+        "--app" | "-a" => app_candidates(),
+        "--format" if command == Some("current") => plain(&["table", "json"]),
+        "--format" => plain(&["csv", "json"]),
+        "--provider" => return provider_candidates(source, &app_type, current),
+        _ if !previous.is_empty() && takes_value(previous) => return Vec::new(),
+        _ if current.starts_with('-') => {
+            let mut flags = plain(Grammar::lookup(&GRAMMAR.flags, command.unwrap_or_default()));
+            flags.extend(plain(super::GLOBAL_FLAGS));
+            flags
+        }
+        _ => match (command, &positionals[positionals.len().min(1)..]) {
+            (None, _) => super::COMMANDS
+                .iter()
+                .map(|(name, _)| (name.to_string(), String::new()))
+                .collect(),
+            (Some("completions"), []) => plain(SHELLS),
+            (Some("run"), []) => source
+                .macros()
+                .into_iter()
+                .map(|name| (name, String::new()))
+                .collect(),
+            (Some("current"), []) => app_candidates(),
+            (Some("use"), []) => {
+                let mut all = app_candidates();
+                all.retain(|(app, _)| app.starts_with(current));
+                all.extend(provider_candidates(source, &app_type, current));
+                return all;
+            }
+            (Some("use"), [app]) => match AppType::from_str(app) {
+                Ok(app_type) => return provider_candidates(source, &app_type, current),
+                Err(_) => Vec::new(),
+            },
+            (Some(command), []) => plain(Grammar::lookup(&GRAMMAR.subcommands, command)),
+            (Some(command), [subcommand, ..])
+                if PROVIDER_TARGETS
+                    .iter()
+                    .any(|(name, sub)| *name == command && sub == subcommand) =>
+            {
+                return provider_candidates(source, &app_type, current);
+            }
+            _ => Vec::new(),
+        },
+    };
+    all.into_iter()
+        .filter(|(value, _)| value.starts_with(current))
+        .collect()
+}
+
+/// 后面跟取值的参数
+fn takes_value(flag: &str) -> bool {
+    GRAMMAR.valued.contains(&flag)
+}
+
+fn script(shell: &str) -> Option<&'static str> {
+    Some(match shell {
+        "bash" => BASH_SCRIPT,
+        "zsh" => ZSH_SCRIPT,
+        "fish" => FISH_SCRIPT,
+        "powershell" | "pwsh" => POWERSHELL_SCRIPT,
+        _ => return None,
+    })
+}
+
+const BASH_SCRIPT: &str = r#"# cc-switch bash completion
+# eval "$(cc-switch completions bash)"
+_cc_switch() {
+    local IFS=$'\n'
+    local output
+    output=$("${COMP_WORDS[0]}" __complete "$((COMP_CWORD - 1))" "${COMP_WORDS[@]:1}" 2>/dev/null) || return
+    COMPREPLY=($(printf '%s\n' "$output" | cut -f1))
+}
+complete -o default -F _cc_switch cc-switch ccswitch
+"#;
+
+const ZSH_SCRIPT: &str = r#"#compdef cc-switch ccswitch
+# eval "$(cc-switch completions zsh)"
+_cc_switch() {
+    local -a lines values descriptions
+    local line
+    lines=("${(@f)$("${words[1]}" __complete "$((CURRENT - 2))" "${(@)words[2,-1]}" 2>/dev/null)}")
+    for line in $lines; do
+        [[ -z $line ]] && continue
+        values+=("${line%%$'\t'*}")
+        if [[ $line == *$'\t'* ]]; then
+            descriptions+=("${line%%$'\t'*} -- ${line#*$'\t'}")
+        else
+            descriptions+=("$line")
+        fi
+    done
+    (( ${#values} )) && compadd -U -l -d descriptions -a values
+}
+if [[ "${funcstack[1]}" == "_cc_switch" ]]; then
+    _cc_switch "$@"
+else
+    compdef _cc_switch cc-switch ccswitch
+fi
+"#;
+
+const FISH_SCRIPT: &str = r#"# cc-switch fish completion
+# cc-switch completions fish | source
+function __cc_switch_complete
+    set -l tokens (commandline -opc)
+    set -l program $tokens[1]
+    set -e tokens[1]
+    $program __complete (count $tokens) $tokens (commandline -ct) 2>/dev/null
+end
+complete -c cc-switch -f -a '(__cc_switch_complete)'
+complete -c ccswitch -f -a '(__cc_switch_complete)'
+"#;
+
+const POWERSHELL_SCRIPT: &str = r#"# cc-switch PowerShell completion
+# cc-switch completions powershell | Out-String | Invoke-Expression
+Register-ArgumentCompleter -Native -CommandName 'cc-switch', 'ccswitch', 'cc-switch.exe' -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $program = $commandAst.CommandElements[0].ToString()
+    $preceding = @($commandAst.CommandElements |
+        Select-Object -Skip 1 |
+        Where-Object { $_.Extent.EndOffset -lt $cursorPosition } |
+        ForEach-Object { $_.ToString() })
+    & $program __complete $preceding.Count @preceding $wordToComplete 2>$null | ForEach-Object {
+        $value, $description = $_ -split "`t", 2
+        if (-not $description) { $description = $value }
+        [System.Management.Automation.CompletionResult]::new($value, $value, 'ParameterValue', $description)
+    }
+}
+"#;
+
+/// `cc-switch completions <bash|zsh|fish|powershell>`
+pub(super) fn run(args: &[String], _lang: CliLang) -> i32 {
+    match args {
+        [shell] => match script(shell) {
+            Some(script) => {
+                print!("{script}");
+                EXIT_OK
+            }
+            None => {
+                eprintln!("error: unsupported shell: {shell} ({})", SHELLS.join("|"));
+                EXIT_USAGE
+            }
+        },
+        _ => {
+            eprintln!("usage: cc-switch completions <{}>", SHELLS.join("|"));
+            EXIT_USAGE
+        }
+    }
+}
+
+/// 隐藏命令 `cc-switch __complete <index> <words...>`，由补全脚本调用
+pub(super) fn run_complete(args: &[String]) -> i32 {
+    let Some((index, words)) = args.split_first() else {
+        return EXIT_USAGE;
+    };
+    let Ok(index) = index.parse::<usize>() else {
+        return EXIT_USAGE;
+    };
+    let index = index.min(words.len());
+    let current = words.get(index).map(String::as_str).unwrap_or("");
+    for (value, description) in candidates(&LiveSource, &words[..index], current) {
+        if description.is_empty() {
+            println!("{value}");
+        } else {
+            println!("{value}\t{description}");
+        }
+    }
+    EXIT_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeSource;
+
+    impl CompletionSource for FakeSource {
+        fn providers(&self, app_type: &AppType) -> Vec<ProviderCandidate> {
+            let provider = |id: &str, name: &str, number| ProviderCandidate {
+                id: id.to_string(),
+                name: name.to_string(),
+                number,
+            };
+            match app_type {
+                AppType::Claude => vec![
+                    provider("relay-b", "Backup Relay", Some(2)),
+                    provider("official", "Claude Official", Some(1)),
+                ],
+                AppType::Codex => vec![provider("openai", "OpenAI", Some(1))],
+                _ => Vec::new(),
+            }
+        }
+
+        fn macros(&self) -> Vec<String> {
+            vec!["work".to_string()]
+        }
+    }
+
+    fn values(preceding: &[&str], current: &str) -> Vec<String> {
+        let preceding: Vec<String> = preceding.iter().map(|w| w.to_string()).collect();
+        candidates(&FakeSource, &preceding, current)
+            .into_iter()
+            .map(|(value, _)| value)
+            .collect()
+    }
+
+    #[test]
+    fn completes_commands_and_subcommands() {
+        assert_eq!(values(&[], "prov"), ["provider"]);
+        assert_eq!(values(&[], "pro"), ["provider", "proxy"]);
+        assert_eq!(values(&["--lang", "zh"], "doc"), ["doctor"]);
+        assert_eq!(values(&["provider"], "re"), ["reorder"]);
+        assert_eq!(values(&["completions"], ""), SHELLS);
+        assert_eq!(values(&["run"], ""), ["work"]);
+        assert_eq!(values(&["doctor"], "--r"), ["--repair"]);
+        assert_eq!(values(&["alias"], ""), ["list", "set", "rm"]);
+        assert_eq!(values(&["proxy"], ""), ["bench"]);
+    }
+
+    #[test]
+    fn grammar_follows_help_usages() {
+        let grammar = &*GRAMMAR;
+        assert_eq!(
+            Grammar::lookup(&grammar.subcommands, "failover"),
+            ["list", "add", "rm", "order"]
+        );
+        assert!(Grammar::lookup(&grammar.flags, "tunnel").contains(&"--restore"));
+        for flag in [
+            "--app",
+            "-a",
+            "--lang",
+            "--format",
+            "-o",
+            "--set",
+            "--key-env",
+            "-p",
+        ] {
+            assert!(takes_value(flag), "{flag}");
+        }
+        for flag in ["--json", "--use", "--here", "--restore", "--dry-run"] {
+            assert!(!takes_value(flag), "{flag}");
+        }
+        // 每个有子命令或参数的命令都是 parse_args 认识的顶层命令
+        for (command, _) in grammar.subcommands.iter().chain(&grammar.flags) {
+            assert!(
+                super::super::COMMANDS
+                    .iter()
+                    .any(|(name, _)| name == command),
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn completes_apps_and_providers_for_use() {
+        // 应用名与名称匹配的供应商一起列出
+        assert_eq!(
+            values(&["use"], "cla"),
+            ["claude", "claude-desktop", "official"]
+        );
+        // 名称前缀匹配到 id，按编号排序
+        assert_eq!(values(&["use", "claude"], "claude o"), ["official"]);
+        assert_eq!(
+            values(&["use", "claude"], ""),
+            ["official", "relay-b", "1", "2"]
+        );
+        assert_eq!(values(&["use", "codex"], "op"), ["openai"]);
+        assert_eq!(
+            values(&["provider", "use", "--app", "codex"], ""),
+            ["openai", "1"]
+        );
+        assert_eq!(values(&["failover", "add"], "rel"), ["relay-b"]);
+        assert_eq!(values(&["provider", "list", "--app"], "ge"), ["gemini"]);
+        assert!(values(&["provider", "add", "--name"], "").is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+//! `cc-switch current` 子命令：输出各应用当前生效的供应商，供脚本读取
+//!
+//! 与 `status` 面向状态栏的单行摘要不同，这里输出固定的列（应用、id、名称、
+//! Base URL、模型），`--json` 时字段缺失输出 `null`。指定的应用没有当前供应商
+//! （或所有应用都没有）时以非零状态退出，脚本可直接据此判断。
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::services::status_file;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CurrentArgs {
+    app: Option<AppType>,
+    json: bool,
+}
+
+fn parse_current_args(args: &[String]) -> Result<CurrentArgs, String> {
+    let mut parsed = CurrentArgs::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        match flag {
+            "--json" => parsed.json = true,
+            "--format" => {
+                let value = inline
+                    .or_else(|| iter.next().cloned())
+                    .ok_or("--format requires a value")?;
+                parsed.json = match value.as_str() {
+                    "json" => true,
+                    "table" => false,
+                    other => return Err(format!("unsupported format: {other} (table|json)")),
+                };
+            }
+            "--app" | "-a" => {
+                let value = inline
+                    .or_else(|| iter.next().cloned())
+                    .ok_or("--app requires a value")?;
+                parsed.app = Some(AppType::from_str(&value).map_err(|e| e.to_string())?);
+            }
+            other if other.starts_with('-') => return Err(format!("unknown argument: {other}")),
+            app if parsed.app.is_none() => {
+                parsed.app = Some(AppType::from_str(app).map_err(|e| e.to_string())?);
+            }
+            other => return Err(format!("unexpected argument: {other}")),
+        }
+    }
+    Ok(parsed)
+}
+
+/// 单个应用的当前供应商；没有时除 `app` 外均为 `null`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CurrentRow {
+    app: String,
+    id: Option<String>,
+    name: Option<String>,
+    base_url: Option<String>,
+    model: Option<String>,
+}
+
+impl CurrentRow {
+    fn is_configured(&self) -> bool {
+        self.id.is_some()
+    }
+}
+
+fn current_row(db: &Database, app_type: &AppType) -> Result<CurrentRow, AppError> {
+    let status = status_file::app_status(db, app_type)?;
+    Ok(CurrentRow {
+        app: app_type.as_str().to_string(),
+        id: status.as_ref().map(|s| s.provider_id.clone()),
+        name: status.as_ref().map(|s| s.provider_name.clone()),
+        base_url: status.as_ref().and_then(|s| s.endpoint.clone()),
+        model: status.and_then(|s| s.model),
+    })
+}
+
+/// 表格输出：每个应用一行，列对齐，缺失的值显示为 `-`
+fn render_table(rows: &[CurrentRow]) -> String {
+    let cells: Vec<[&str; 5]> = rows
+        .iter()
+        .map(|row| {
+            let value = |v: &Option<String>| v.as_deref().unwrap_or("-");
+            [
+                row.app.as_str(),
+                value(&row.id),
+                value(&row.name),
+                value(&row.base_url),
+                value(&row.model),
+            ]
+        })
+        .collect();
+    let widths: Vec<usize> = (0..5)
+        .map(|col| {
+            cells
+                .iter()
+                .map(|cell| cell[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    cells
+        .iter()
+        .map(|cell| {
+            let line: Vec<String> = cell
+                .iter()
+                .zip(&widths)
+                .map(|(value, width)| format!("{value:<width$}"))
+                .collect();
+            format!("{}\n", line.join("  ").trim_end())
+        })
+        .collect()
+}
+
+/// `cc-switch current [<app>] [--json | --format table|json]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse_current_args(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return EXIT_USAGE;
+        }
+    };
+    if let Some(app) = parsed.app.as_ref().filter(|app| app.is_additive_mode()) {
+        eprintln!(
+            "error: {}",
+            match lang {
+                CliLang::Zh => format!(
+                    "{} 为累加模式，没有单一的当前供应商，请使用 provider list --app {}",
+                    app.as_str(),
+                    app.as_str()
+                ),
+                CliLang::En => format!(
+                    "{} uses additive mode and has no single current provider; use provider list --app {}",
+                    app.as_str(),
+                    app.as_str()
+                ),
+            }
+        );
+        return EXIT_FAILURE;
+    }
+
+    let apps: Vec<AppType> = match parsed.app {
+        Some(app) => vec![app],
+        None => AppType::all()
+            .filter(|app| !app.is_additive_mode())
+            .collect(),
+    };
+    let rows = open_state().and_then(|state| {
+        apps.iter()
+            .map(|app| current_row(&state.db, app))
+            .collect::<Result<Vec<_>, _>>()
+    });
+    let rows = match rows {
+        Ok(rows) => rows,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+
+    if parsed.json {
+        // 指定应用时输出单个对象，否则输出数组
+        let text = match rows.as_slice() {
+            [row] if parsed.app.is_some() => serde_json::to_string_pretty(row),
+            _ => serde_json::to_string_pretty(&rows),
+        };
+        match text {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return EXIT_FAILURE;
+            }
+        }
+    } else {
+        print!("{}", render_table(&rows));
+    }
+
+    if rows.iter().any(CurrentRow::is_configured) {
+        EXIT_OK
+    } else {
+        eprintln!(
+            "{}",
+            match lang {
+                CliLang::Zh => "尚未配置当前供应商",
+                CliLang::En => "no current provider configured",
+            }
+        );
+        EXIT_FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_positional_app_and_formats() {
+        let parsed = parse_current_args(&args(&["codex", "--json"])).unwrap();
+        assert_eq!(parsed.app, Some(AppType::Codex));
+        assert!(parsed.json);
+        let parsed = parse_current_args(&args(&["--format=table", "--app", "gemini"])).unwrap();
+        assert_eq!(parsed.app, Some(AppType::Gemini));
+        assert!(!parsed.json);
+        assert!(parse_current_args(&args(&["--format", "yaml"])).is_err());
+        assert!(parse_current_args(&args(&["claude", "codex"])).is_err());
+        assert!(parse_current_args(&args(&["nope"])).is_err());
+    }
+
+    #[test]
+    fn renders_aligned_rows_with_placeholders() {
+        let rows = vec![
+            CurrentRow {
+                app: "claude".to_string(),
+                id: Some("relay".to_string()),
+                name: Some("Relay".to_string()),
+                base_url: Some("https://relay.example.com".to_string()),
+                model: Some("claude-sonnet-4-5".to_string()),
+            },
+            CurrentRow {
+                app: "gemini".to_string(),
+                id: None,
+                name: None,
+                base_url: None,
+                model: None,
+            },
+        ];
+        assert_eq!(
+            render_table(&rows),
+            "claude  relay  Relay  https://relay.example.com  claude-sonnet-4-5\n\
+             gemini  -      -      -\n"
+        );
+        let json = serde_json::to_value(&rows[1]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"app": "gemini", "id": null, "name": null, "baseUrl": null, "model": null})
+        );
+    }
+}
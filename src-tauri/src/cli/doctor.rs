@@ -0,0 +1,168 @@
+//! `cc-switch doctor` 子命令：检查数据库完整性，按需修复
+//!
+//! 修复前会先生成快照备份（与 `cc-switch backup` 相同，写入 `~/.cc-switch/backups/`）。
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::database::{IntegrityIssueKind, IntegrityReport};
+
+fn kind_label(kind: IntegrityIssueKind, lang: CliLang) -> &'static str {
+    match (kind, lang) {
+        (IntegrityIssueKind::Corruption, CliLang::Zh) => "数据库损坏",
+        (IntegrityIssueKind::Corruption, CliLang::En) => "corruption",
+        (IntegrityIssueKind::ForeignKey, CliLang::Zh) => "外键悬空",
+        (IntegrityIssueKind::ForeignKey, CliLang::En) => "dangling foreign key",
+        (IntegrityIssueKind::OrphanedProvider, CliLang::Zh) => "孤立供应商",
+        (IntegrityIssueKind::OrphanedProvider, CliLang::En) => "orphaned provider",
+        (IntegrityIssueKind::DuplicateCurrent, CliLang::Zh) => "多个当前供应商",
+        (IntegrityIssueKind::DuplicateCurrent, CliLang::En) => "duplicate current provider",
+        (IntegrityIssueKind::InvalidJson, CliLang::Zh) => "JSON 损坏",
+        (IntegrityIssueKind::InvalidJson, CliLang::En) => "invalid JSON",
+    }
+}
+
+fn render(report: &IntegrityReport, lang: CliLang) -> String {
+    let mut out = match lang {
+        CliLang::Zh => format!(
+            "数据库版本 v{}（应用支持 v{}），记录的迁移 {} 条\n",
+            report.schema_version,
+            report.supported_version,
+            report.migrations.len()
+        ),
+        CliLang::En => format!(
+            "schema v{} (app supports v{}), {} recorded migrations\n",
+            report.schema_version,
+            report.supported_version,
+            report.migrations.len()
+        ),
+    };
+    if report.is_healthy() {
+        out.push_str(match lang {
+            CliLang::Zh => "未发现问题\n",
+            CliLang::En => "no problems found\n",
+        });
+        return out;
+    }
+    for issue in &report.issues {
+        out.push_str(&format!(
+            "  [{}] {}{}: {}{}\n",
+            kind_label(issue.kind, lang),
+            issue.table,
+            issue
+                .key
+                .as_deref()
+                .map(|key| format!(" {key}"))
+                .unwrap_or_default(),
+            issue.detail,
+            match (issue.repairable, lang) {
+                (true, _) => "",
+                (false, CliLang::Zh) => "（需手动处理）",
+                (false, CliLang::En) => " (manual fix required)",
+            }
+        ));
+    }
+    out
+}
+
+/// `cc-switch doctor [--repair] [--json]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let mut repair = false;
+    let mut json = false;
+    for arg in args {
+        match arg.as_str() {
+            "--repair" | "--fix" => repair = true,
+            "--json" => json = true,
+            other => {
+                eprintln!("error: unknown argument: {other}");
+                return EXIT_USAGE;
+            }
+        }
+    }
+
+    let state = match open_state() {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+    let mut report = match state.db.check_integrity() {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+
+    let mut repaired = None;
+    if repair && report.repairable_count() > 0 {
+        let backup = match state.db.backup_database_file() {
+            Ok(path) => path,
+            Err(e) => {
+                eprintln!("error: {}", error_message(&e, lang));
+                return EXIT_FAILURE;
+            }
+        };
+        let count = match state.db.repair_integrity(&report) {
+            Ok(count) => count,
+            Err(e) => {
+                eprintln!("error: {}", error_message(&e, lang));
+                return EXIT_FAILURE;
+            }
+        };
+        if !json {
+            if let Some(path) = &backup {
+                eprintln!(
+                    "{}",
+                    match lang {
+                        CliLang::Zh => format!("已备份到 {}", path.display()),
+                        CliLang::En => format!("backed up to {}", path.display()),
+                    }
+                );
+            }
+            eprintln!(
+                "{}",
+                match lang {
+                    CliLang::Zh => format!("已修复 {count} 个问题"),
+                    CliLang::En => format!("repaired {count} problem(s)"),
+                }
+            );
+        }
+        repaired = Some(count);
+        report = match state.db.check_integrity() {
+            Ok(report) => report,
+            Err(e) => {
+                eprintln!("error: {}", error_message(&e, lang));
+                return EXIT_FAILURE;
+            }
+        };
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({ "report": report, "repaired": repaired })
+        );
+    } else {
+        print!("{}", render(&report, lang));
+        if !repair && report.repairable_count() > 0 {
+            println!(
+                "{}",
+                match lang {
+                    CliLang::Zh => format!(
+                        "其中 {} 个可自动修复，运行 `cc-switch doctor --repair`（会先备份数据库）",
+                        report.repairable_count()
+                    ),
+                    CliLang::En => format!(
+                        "{} can be repaired automatically: run `cc-switch doctor --repair` (backs up the database first)",
+                        report.repairable_count()
+                    ),
+                }
+            );
+        }
+    }
+    if report.is_healthy() {
+        EXIT_OK
+    } else {
+        EXIT_FAILURE
+    }
+}
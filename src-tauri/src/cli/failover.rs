@@ -0,0 +1,208 @@
+//! `cc-switch failover` 子命令：管理故障转移队列
+//!
+//! 与界面共用 [`FailoverQueueService`]。代理路由每次请求都从数据库读取队列，
+//! 因此命令行的修改对正在运行的代理立即生效。
+
+use std::str::FromStr;
+
+use tauri::async_runtime::block_on;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::database::FailoverQueueItem;
+use crate::error::AppError;
+use crate::services::failover_queue::FailoverQueueService;
+use crate::store::AppState;
+
+#[derive(Debug, PartialEq, Eq)]
+enum FailoverAction {
+    List,
+    Add(String),
+    Remove(String),
+    Order(Vec<String>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct FailoverArgs {
+    app: Option<String>,
+    json: bool,
+    action: FailoverAction,
+}
+
+fn parse_failover_args(args: &[String]) -> Result<FailoverArgs, String> {
+    let mut app = None;
+    let mut json = false;
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--app" | "-a" => app = Some(iter.next().ok_or("--app requires a value")?.clone()),
+            "--json" => json = true,
+            other => match other.strip_prefix("--app=") {
+                Some(value) => app = Some(value.to_string()),
+                None if other.starts_with('-') => return Err(format!("unknown option: {other}")),
+                None => positional.push(other.to_string()),
+            },
+        }
+    }
+
+    let action = match positional.split_first() {
+        None => {
+            return Err("missing subcommand (list | add <id> | rm <id> | order <id>...)".into())
+        }
+        Some((command, rest)) => match (command.as_str(), rest) {
+            ("list", []) => FailoverAction::List,
+            ("add", [id]) => FailoverAction::Add(id.clone()),
+            ("rm" | "remove", [id]) => FailoverAction::Remove(id.clone()),
+            ("order", ids) if !ids.is_empty() => FailoverAction::Order(ids.to_vec()),
+            ("list", _) => return Err("usage: cc-switch failover list [--app <app>]".into()),
+            ("add" | "rm" | "remove", _) => {
+                return Err(format!(
+                    "usage: cc-switch failover {command} <id> [--app <app>]"
+                ))
+            }
+            ("order", _) => {
+                return Err("usage: cc-switch failover order <id> <id>... [--app <app>]".into())
+            }
+            (other, _) => return Err(format!("unknown failover subcommand: {other}")),
+        },
+    };
+    Ok(FailoverArgs { app, json, action })
+}
+
+fn render_queue(queue: &[FailoverQueueItem]) -> String {
+    let id_width = queue
+        .iter()
+        .map(|item| item.provider_id.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for (index, item) in queue.iter().enumerate() {
+        let pad = id_width - item.provider_id.chars().count();
+        out.push_str(&format!(
+            "P{:<2} {}{}  {}",
+            index + 1,
+            item.provider_id,
+            " ".repeat(pad),
+            item.provider_name
+        ));
+        if !item.healthy {
+            out.push_str("  [unhealthy]");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn execute(
+    state: &AppState,
+    app_type: &AppType,
+    action: FailoverAction,
+) -> Result<Vec<FailoverQueueItem>, AppError> {
+    let db = &state.db;
+    match action {
+        FailoverAction::List => FailoverQueueService::list(db, app_type),
+        FailoverAction::Add(id) => FailoverQueueService::add(db, app_type, &id),
+        FailoverAction::Remove(id) => {
+            let auto_failover_enabled =
+                block_on(db.get_proxy_config_for_app(app_type.as_str()))?.auto_failover_enabled;
+            FailoverQueueService::remove(db, app_type, &id, auto_failover_enabled)
+        }
+        FailoverAction::Order(ids) => FailoverQueueService::reorder(db, app_type, &ids),
+    }
+}
+
+/// `cc-switch failover <list|add|rm|order> ...`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse_failover_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+    let app_type = match AppType::from_str(parsed.app.as_deref().unwrap_or("claude")) {
+        Ok(app_type) => app_type,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_USAGE;
+        }
+    };
+
+    let result = open_state().and_then(|state| execute(&state, &app_type, parsed.action));
+    let queue = match result {
+        Ok(queue) => queue,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+    if parsed.json {
+        match serde_json::to_string_pretty(&queue) {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return EXIT_FAILURE;
+            }
+        }
+    } else if queue.is_empty() {
+        eprintln!("failover queue is empty");
+    } else {
+        print!("{}", render_queue(&queue));
+    }
+    EXIT_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_queue_subcommands() {
+        let parsed = parse_failover_args(&args(&["order", "b", "a", "--app=codex"])).unwrap();
+        assert_eq!(parsed.app.as_deref(), Some("codex"));
+        assert_eq!(
+            parsed.action,
+            FailoverAction::Order(vec!["b".to_string(), "a".to_string()])
+        );
+        assert_eq!(
+            parse_failover_args(&args(&["rm", "a"])).unwrap().action,
+            FailoverAction::Remove("a".to_string())
+        );
+        assert!(
+            parse_failover_args(&args(&["list", "--json"]))
+                .unwrap()
+                .json
+        );
+
+        assert!(parse_failover_args(&[]).is_err());
+        assert!(parse_failover_args(&args(&["order"])).is_err());
+        assert!(parse_failover_args(&args(&["add", "a", "b"])).is_err());
+        assert!(parse_failover_args(&args(&["list", "--force"])).is_err());
+    }
+
+    #[test]
+    fn renders_priority_and_health() {
+        let queue = vec![
+            FailoverQueueItem {
+                provider_id: "relay".to_string(),
+                provider_name: "Relay".to_string(),
+                sort_index: Some(0),
+                provider_notes: None,
+                healthy: true,
+            },
+            FailoverQueueItem {
+                provider_id: "backup-1".to_string(),
+                provider_name: "Backup".to_string(),
+                sort_index: Some(3),
+                provider_notes: None,
+                healthy: false,
+            },
+        ];
+        assert_eq!(
+            render_queue(&queue),
+            "P1  relay     Relay\nP2  backup-1  Backup  [unhealthy]\n"
+        );
+    }
+}
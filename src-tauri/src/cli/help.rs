@@ -0,0 +1,348 @@
+//! 结构化、可本地化的命令行帮助
+//!
+//! 文案取自前端共享的语言包（`src/i18n/locales/*.json` 的 `cli.help` 节点），
+//! 界面与命令行使用同一份翻译；缺失的中文条目回退到英文。
+
+use super::{CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+
+/// (参数, 说明 key)
+const OPTIONS: &[(&str, &str)] = &[
+    ("-h, --help", "options.help"),
+    ("-V, --version", "options.version"),
+    ("--lang <zh|en>", "options.lang"),
+    ("--progress", "options.progress"),
+    ("--safe-mode", "options.safeMode"),
+    ("-a, --app <app>", "options.app"),
+];
+
+/// (用法, 说明 key)；shell 补全的子命令与参数也从这里的用法推导
+const COMMANDS: &[(&str, &str)] = &[
+    ("cc-switch", "commands.gui"),
+    ("cc-switch <ccswitch://...>", "commands.deeplink"),
+    (
+        "cc-switch provider list [--app <app>] [--json] [--icons]",
+        "commands.providerList",
+    ),
+    (
+        "cc-switch provider use <id|number> [--app <app>]",
+        "commands.providerUse",
+    ),
+    ("cc-switch use [<app>] <id|number>", "commands.useShortcut"),
+    (
+        "cc-switch provider reorder [<id|number>...] [--app <app>]",
+        "commands.providerReorder",
+    ),
+    (
+        "cc-switch provider add --name <name> --base-url <url> (--key <key> | --key-env <var>) [--model <model>] [--category <category>] [--notes <text>] [--id <id>] [--use] [--app <app>]",
+        "commands.providerAdd",
+    ),
+    (
+        "cc-switch provider curl <id> [--model <model>] [--mask-key] [--app <app>]",
+        "commands.providerCurl",
+    ),
+    (
+        "cc-switch provider duplicate [<app>] <id|number> [--set <path>=<value>]... [--use] [--json]",
+        "commands.providerDuplicate",
+    ),
+    (
+        "cc-switch provider batch <delete|test|failover-add|export> [--category <category>] [--name-contains <text>] [--app <app>] [--dry-run] [--json] [-o <file>]",
+        "commands.providerBatch",
+    ),
+    (
+        "cc-switch provider matrix <base-url> [--json]",
+        "commands.providerMatrix",
+    ),
+    (
+        "cc-switch failover list [--app <app>] [--json]",
+        "commands.failoverList",
+    ),
+    (
+        "cc-switch failover add|rm <id> [--app <app>]",
+        "commands.failoverAddRemove",
+    ),
+    (
+        "cc-switch failover order <id> <id>... [--app <app>]",
+        "commands.failoverOrder",
+    ),
+    (
+        "cc-switch cleanup [--days <n>] [--archive] [--app <app>] [--json]",
+        "commands.cleanup",
+    ),
+    (
+        "cc-switch usage export [--format csv|json] [--from <date>] [--to <date>] [--app <app>] [-o <file>] [--localized]",
+        "commands.usageExport",
+    ),
+    (
+        "cc-switch usage curl <request-id> [--mask-key]",
+        "commands.usageCurl",
+    ),
+    (
+        "cc-switch simulate --provider <id> [--days <n>] [--app <app>] [--json]",
+        "commands.simulate",
+    ),
+    (
+        "cc-switch sessions [list] [--app <tool>] [--cwd <dir> | --here] [--limit <n>] [--json]",
+        "commands.sessionsList",
+    ),
+    (
+        "cc-switch sessions resume <id> [--app <tool>] [--print]",
+        "commands.sessionsResume",
+    ),
+    ("cc-switch backup [--json]", "commands.backup"),
+    (
+        "cc-switch migrate export <file> [--passphrase-env <VAR>] [--json]",
+        "commands.migrateExport",
+    ),
+    (
+        "cc-switch migrate import <file> [--passphrase-env <VAR>] [--dry-run] [--json]",
+        "commands.migrateImport",
+    ),
+    ("cc-switch doctor [--repair] [--json]", "commands.doctor"),
+    (
+        "cc-switch network-changed [--check] [--json]",
+        "commands.networkChanged",
+    ),
+    (
+        "cc-switch status [--app <app>] [--json] [--watch]",
+        "commands.status",
+    ),
+    (
+        "cc-switch current [<app> | --app <app>] [--json | --format table|json]",
+        "commands.current",
+    ),
+    (
+        "cc-switch proxy bench [--concurrency <n>] [--requests <n>] [--upstream-delay-ms <n>] [--json]",
+        "commands.proxyBench",
+    ),
+    (
+        "cc-switch tunnel <user@host> [--remote-port <n>] [--local-port <n>] [-p <ssh-port>] [-i <file>] [--start] [--check] [--apply] [--app <app>]... [--token-env <VAR>]",
+        "commands.tunnel",
+    ),
+    (
+        "cc-switch tunnel --restore [--app <app>]...",
+        "commands.tunnelRestore",
+    ),
+    ("cc-switch alias [list] [--json]", "commands.aliasList"),
+    ("cc-switch alias set <alias> <model>", "commands.aliasSet"),
+    ("cc-switch alias rm <alias>", "commands.aliasRemove"),
+    ("cc-switch run <macro>", "commands.runMacro"),
+    ("cc-switch run --list", "commands.runList"),
+    (
+        "cc-switch completions <bash|zsh|fish|powershell>",
+        "commands.completions",
+    ),
+];
+
+/// (说明 key, 示例命令)
+///
+/// deep link 的 `config` 参数为 base64 编码的 JSON：
+/// - provider: `{"env":{"ANTHROPIC_BASE_URL":"https://api.example.com","ANTHROPIC_AUTH_TOKEN":"sk-xxx"}}`
+/// - mcp: `{"mcpServers":{"fetch":{"command":"uvx","args":["mcp-server-fetch"]}}}`
+const EXAMPLES: &[(&str, &str)] = &[
+    (
+        "examples.importProviderJson",
+        "cc-switch \"ccswitch://v1/import?resource=provider&app=claude&name=My%20Relay&configFormat=json&config=eyJlbnYiOnsiQU5USFJPUElDX0JBU0VfVVJMIjoiaHR0cHM6Ly9hcGkuZXhhbXBsZS5jb20iLCJBTlRIUk9QSUNfQVVUSF9UT0tFTiI6InNrLXh4eCJ9fQ%3D%3D\"",
+    ),
+    (
+        "examples.importProviderFields",
+        "cc-switch \"ccswitch://v1/import?resource=provider&app=codex&name=My%20Relay&endpoint=https%3A%2F%2Fapi.example.com%2Fv1&apiKey=sk-xxx\"",
+    ),
+    (
+        "examples.importMcpJson",
+        "cc-switch \"ccswitch://v1/import?resource=mcp&apps=claude,codex&config=eyJtY3BTZXJ2ZXJzIjp7ImZldGNoIjp7ImNvbW1hbmQiOiJ1dngiLCJhcmdzIjpbIm1jcC1zZXJ2ZXItZmV0Y2giXX19fQ%3D%3D\"",
+    ),
+    (
+        "examples.useOpenCodeProvider",
+        "cc-switch provider use deepseek --app opencode",
+    ),
+    ("examples.useByNumber", "cc-switch use claude 2"),
+    (
+        "examples.addProvider",
+        "cc-switch provider add --app codex --name \"My Relay\" --base-url https://api.example.com/v1 --key-env RELAY_API_KEY --model gpt-5 --use",
+    ),
+    (
+        "examples.duplicateProvider",
+        "cc-switch provider duplicate claude my-relay --set env.ANTHROPIC_MODEL=glm-4.6",
+    ),
+    (
+        "examples.failoverOrder",
+        "cc-switch failover order relay-a relay-b --app claude",
+    ),
+    (
+        "examples.cleanupArchive",
+        "cc-switch cleanup --days 120 --archive",
+    ),
+    (
+        "examples.exportUsageCsv",
+        "cc-switch usage export --from 2026-01-01 --to 2026-01-31 -o usage-2026-01.csv",
+    ),
+    (
+        "examples.simulateProvider",
+        "cc-switch simulate --provider my-relay --days 7",
+    ),
+    (
+        "examples.resumeSessionHere",
+        "cc-switch sessions --here --app claude",
+    ),
+    ("examples.currentJson", "cc-switch current claude --json"),
+    (
+        "examples.proxyBench",
+        "cc-switch proxy bench --concurrency 50 --requests 2000",
+    ),
+    (
+        "examples.providerMatrix",
+        "cc-switch provider matrix https://relay.example.com",
+    ),
+    ("examples.backupProgress", "cc-switch backup --progress"),
+    (
+        "examples.migrateExport",
+        "CCS_PASS=... cc-switch migrate export ~/cc-switch-state.zip --passphrase-env CCS_PASS",
+    ),
+    (
+        "examples.providerCurl",
+        "cc-switch provider curl my-relay --mask-key",
+    ),
+    (
+        "examples.tunnelStart",
+        "cc-switch tunnel dev@build-box --local-port 25721 --start --apply --app claude",
+    ),
+    (
+        "examples.aliasSet",
+        "cc-switch alias set main claude-sonnet-4.5",
+    ),
+    ("examples.runMacro", "cc-switch run work"),
+    (
+        "examples.completionsBash",
+        "echo 'eval \"$(cc-switch completions bash)\"' >> ~/.bashrc",
+    ),
+    ("examples.helpZh", "cc-switch --help --lang zh"),
+];
+
+/// (退出码, 说明 key)
+const EXIT_CODES: &[(i32, &str)] = &[
+    (EXIT_OK, "exitCodes.ok"),
+    (EXIT_FAILURE, "exitCodes.failure"),
+    (EXIT_USAGE, "exitCodes.usage"),
+];
+
+fn lookup(lang: CliLang, key: &str) -> Option<&'static str> {
+    crate::i18n::lookup(lang, &format!("cli.help.{key}"))
+}
+
+/// 按 `cli.help.<key>` 取文案：当前语言 > 英文 > key 本身
+fn t(lang: CliLang, key: &str) -> String {
+    lookup(lang, key)
+        .or_else(|| lookup(CliLang::En, key))
+        .unwrap_or(key)
+        .to_string()
+}
+
+fn push_section(out: &mut String, title: String, rows: &[(String, String)]) {
+    let width = rows
+        .iter()
+        .map(|(left, _)| left.chars().count())
+        .max()
+        .unwrap_or(0);
+    out.push('\n');
+    out.push_str(&title);
+    out.push_str(":\n");
+    for (left, right) in rows {
+        let pad = width - left.chars().count();
+        out.push_str(&format!("  {left}{}  {right}\n", " ".repeat(pad)));
+    }
+}
+
+/// 渲染完整帮助文本
+/// 全部命令用法行（不含本地化说明）
+pub(super) fn usages() -> impl Iterator<Item = &'static str> {
+    COMMANDS.iter().map(|(usage, _)| *usage)
+}
+
+/// 选项表中的参数（如 `-a, --app <app>`）
+pub(super) fn options() -> impl Iterator<Item = &'static str> {
+    OPTIONS.iter().map(|(flags, _)| *flags)
+}
+
+pub(super) fn render(lang: CliLang) -> String {
+    let mut out = format!(
+        "cc-switch {}\n{}\n\n{}:\n  cc-switch [OPTIONS] [COMMAND | DEEP_LINK]\n",
+        env!("CARGO_PKG_VERSION"),
+        t(lang, "description"),
+        t(lang, "usageTitle"),
+    );
+
+    let commands: Vec<(String, String)> = COMMANDS
+        .iter()
+        .map(|(usage, key)| (usage.to_string(), t(lang, key)))
+        .collect();
+    push_section(&mut out, t(lang, "commandsTitle"), &commands);
+
+    let options: Vec<(String, String)> = OPTIONS
+        .iter()
+        .map(|(flags, key)| (flags.to_string(), t(lang, key)))
+        .collect();
+    push_section(&mut out, t(lang, "optionsTitle"), &options);
+
+    out.push('\n');
+    out.push_str(&t(lang, "examplesTitle"));
+    out.push_str(":\n");
+    for (key, command) in EXAMPLES {
+        out.push_str(&format!("  # {}\n  {command}\n\n", t(lang, key)));
+    }
+    out.push_str(&format!("  {}\n", t(lang, "examples.configNote")));
+
+    let exit_codes: Vec<(String, String)> = EXIT_CODES
+        .iter()
+        .map(|(code, key)| (code.to_string(), t(lang, key)))
+        .collect();
+    push_section(&mut out, t(lang, "exitCodesTitle"), &exit_codes);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_keys() -> Vec<&'static str> {
+        let mut keys = vec![
+            "description",
+            "usageTitle",
+            "commandsTitle",
+            "optionsTitle",
+            "examplesTitle",
+            "exitCodesTitle",
+            "examples.configNote",
+        ];
+        keys.extend(OPTIONS.iter().map(|(_, key)| *key));
+        keys.extend(COMMANDS.iter().map(|(_, key)| *key));
+        keys.extend(EXAMPLES.iter().map(|(key, _)| *key));
+        keys.extend(EXIT_CODES.iter().map(|(_, key)| *key));
+        keys
+    }
+
+    #[test]
+    fn every_help_key_exists_in_zh_and_en_catalogs() {
+        for key in all_keys() {
+            assert!(
+                lookup(CliLang::En, key).is_some(),
+                "en missing cli.help.{key}"
+            );
+            assert!(
+                lookup(CliLang::Zh, key).is_some(),
+                "zh missing cli.help.{key}"
+            );
+        }
+    }
+
+    #[test]
+    fn render_includes_examples_and_exit_codes() {
+        let en = render(CliLang::En);
+        assert!(en.contains("Exit codes:"));
+        assert!(en.contains("resource=provider&app=claude"));
+        assert!(en.contains("  2  "));
+
+        let zh = render(CliLang::Zh);
+        assert!(zh.contains("退出码"));
+        assert_ne!(en, zh);
+    }
+}
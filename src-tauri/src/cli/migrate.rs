@@ -0,0 +1,173 @@
+//! `cc-switch migrate` 子命令：整机迁移
+//!
+//! `export` 把数据库、设置、Skills 与代理配置打包成一个归档，`import` 在新电脑上恢复。
+//! 口令只从环境变量读取（`--passphrase-env`），避免出现在 shell 历史里。
+
+use std::path::PathBuf;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::services::migration::{MigrationService, MigrationSummary};
+
+#[derive(Debug, Default, PartialEq)]
+struct MigrateArgs {
+    command: String,
+    file: Option<PathBuf>,
+    passphrase_env: Option<String>,
+    dry_run: bool,
+    json: bool,
+}
+
+fn parse(args: &[String]) -> Result<MigrateArgs, String> {
+    let mut parsed = MigrateArgs::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--passphrase-env" => {
+                parsed.passphrase_env = Some(
+                    iter.next()
+                        .ok_or("--passphrase-env requires a variable name")?
+                        .clone(),
+                );
+            }
+            "--dry-run" => parsed.dry_run = true,
+            "--json" => parsed.json = true,
+            other if other.starts_with('-') => return Err(format!("unknown argument: {other}")),
+            other if parsed.command.is_empty() => parsed.command = other.to_string(),
+            other if parsed.file.is_none() => parsed.file = Some(PathBuf::from(other)),
+            other => return Err(format!("unexpected argument: {other}")),
+        }
+    }
+    if !matches!(parsed.command.as_str(), "export" | "import") {
+        return Err("usage: cc-switch migrate <export|import> <file>".to_string());
+    }
+    if parsed.file.is_none() {
+        return Err(format!("migrate {} requires a file path", parsed.command));
+    }
+    if parsed.dry_run && parsed.command != "import" {
+        return Err("--dry-run only applies to migrate import".to_string());
+    }
+    Ok(parsed)
+}
+
+fn print_summary(summary: &MigrationSummary, action: &str, lang: CliLang) {
+    let headline = match (action, lang) {
+        ("export", CliLang::Zh) => "已导出迁移归档",
+        ("export", CliLang::En) => "Migration archive written",
+        ("inspect", CliLang::Zh) => "迁移归档校验通过（未做任何修改）",
+        ("inspect", CliLang::En) => "Migration archive verified (nothing changed)",
+        (_, CliLang::Zh) => "已恢复应用状态，请重启 CC Switch",
+        (_, CliLang::En) => "Application state restored; restart CC Switch",
+    };
+    println!("{headline}: {}", summary.path);
+    if let Some(device) = &summary.device_name {
+        println!("  device:    {device}");
+    }
+    println!("  created:   {}", summary.created_at);
+    println!("  encrypted: {}", summary.encrypted);
+    let providers = summary
+        .inventory
+        .providers
+        .iter()
+        .map(|(app, count)| format!("{app}={count}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!("  providers: {providers}");
+    println!("  skills:    {}", summary.inventory.skills.len());
+    println!("  mcp:       {}", summary.inventory.mcp_servers.len());
+    if let Some(backup_id) = &summary.backup_id {
+        println!("  backup:    {backup_id}");
+    }
+    if let Some(warning) = &summary.warning {
+        eprintln!("warning: {warning}");
+    }
+}
+
+/// `cc-switch migrate export <file> [--passphrase-env <VAR>] [--json]`
+/// `cc-switch migrate import <file> [--passphrase-env <VAR>] [--dry-run] [--json]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return EXIT_USAGE;
+        }
+    };
+    let passphrase = match &parsed.passphrase_env {
+        Some(var) => match std::env::var(var) {
+            Ok(value) if !value.is_empty() => Some(value),
+            _ => {
+                eprintln!("error: environment variable {var} is not set");
+                return EXIT_USAGE;
+            }
+        },
+        None => None,
+    };
+    let Some(file) = parsed.file.as_deref() else {
+        return EXIT_USAGE;
+    };
+
+    let result = if parsed.dry_run {
+        MigrationService::inspect(file, passphrase.as_deref()).map(|s| (s, "inspect"))
+    } else {
+        open_state().and_then(|state| {
+            if parsed.command == "export" {
+                MigrationService::export(&state.db, file, passphrase.as_deref())
+                    .map(|s| (s, "export"))
+            } else {
+                MigrationService::import(&state.db, file, passphrase.as_deref())
+                    .map(|s| (s, "import"))
+            }
+        })
+    };
+
+    match result {
+        Ok((summary, action)) => {
+            if parsed.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&summary).unwrap_or_default()
+                );
+            } else {
+                print_summary(&summary, action, lang);
+            }
+            EXIT_OK
+        }
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            EXIT_FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_export_and_import() {
+        let parsed = parse(&args(&[
+            "export",
+            "state.ccsm",
+            "--passphrase-env",
+            "CCS_PASS",
+        ]))
+        .unwrap();
+        assert_eq!(parsed.command, "export");
+        assert_eq!(parsed.file, Some(PathBuf::from("state.ccsm")));
+        assert_eq!(parsed.passphrase_env.as_deref(), Some("CCS_PASS"));
+
+        let parsed = parse(&args(&["import", "state.ccsm", "--dry-run", "--json"])).unwrap();
+        assert!(parsed.dry_run && parsed.json);
+    }
+
+    #[test]
+    fn rejects_incomplete_or_unknown_usage() {
+        assert!(parse(&args(&[])).is_err());
+        assert!(parse(&args(&["export"])).is_err());
+        assert!(parse(&args(&["copy", "a.zip"])).is_err());
+        assert!(parse(&args(&["export", "a.zip", "--dry-run"])).is_err());
+        assert!(parse(&args(&["import", "a.zip", "--passphrase-env"])).is_err());
+        assert!(parse(&args(&["import", "a.zip", "b.zip"])).is_err());
+    }
+}
@@ -0,0 +1,394 @@
+//! 命令行入口
+//!
+//! GUI 二进制同时承担少量命令行功能（帮助、版本等）。只有识别出的命令行参数
+//! 才会在这里处理并退出；其余参数（例如 `ccswitch://` deep link URL、系统附加参数）
+//! 原样交给 GUI 启动流程。
+
+mod alias;
+mod backup;
+mod cleanup;
+mod completions;
+mod current;
+mod doctor;
+mod failover;
+mod help;
+mod migrate;
+mod network;
+mod provider;
+mod provider_batch;
+mod provider_matrix;
+mod proxy;
+mod scenario;
+mod sessions;
+mod simulate;
+mod status;
+mod tunnel;
+mod usage;
+
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::store::AppState;
+
+/// 退出码：成功
+pub const EXIT_OK: i32 = 0;
+/// 退出码：执行失败（读写配置、网络等运行时错误）
+pub const EXIT_FAILURE: i32 = 1;
+/// 退出码：参数错误
+pub const EXIT_USAGE: i32 = 2;
+
+/// 命令行输出语言（未指定 `--lang` 时：界面语言设置 > 系统区域 > 英文）
+pub use crate::i18n::Lang as CliLang;
+
+#[derive(Debug, PartialEq, Eq)]
+enum CliAction {
+    Help,
+    Version,
+    /// `provider ...`，携带子命令参数
+    Provider(Vec<String>),
+    /// `usage ...`，携带子命令参数
+    Usage(Vec<String>),
+    /// `failover ...`，管理故障转移队列
+    Failover(Vec<String>),
+    /// `cleanup ...`，列出并归档闲置供应商
+    Cleanup(Vec<String>),
+    /// `backup ...`，备份数据库
+    Backup(Vec<String>),
+    /// `migrate ...`，整机迁移导出 / 导入
+    Migrate(Vec<String>),
+    /// `run ...`，执行场景宏
+    Run(Vec<String>),
+    /// `simulate ...`，模拟切换供应商后的花费
+    Simulate(Vec<String>),
+    /// `sessions ...`，列出并恢复 Claude Code / Codex 等会话
+    Sessions(Vec<String>),
+    /// `doctor ...`，检查并修复数据库完整性
+    Doctor(Vec<String>),
+    /// `network-changed ...`，网络变化后重新检查供应商
+    NetworkChanged(Vec<String>),
+    /// `status ...`，输出当前供应商（状态栏集成）
+    Status(Vec<String>),
+    /// `current ...`，以表格 / JSON 输出当前供应商，供脚本读取
+    Current(Vec<String>),
+    /// `proxy ...`，代理运维操作（压测）
+    Proxy(Vec<String>),
+    /// `tunnel ...`，通过 SSH 端口转发使用远程代理
+    Tunnel(Vec<String>),
+    /// `alias ...`，查看与编辑模型别名
+    Alias(Vec<String>),
+    /// `completions <shell>`，输出补全脚本
+    Completions(Vec<String>),
+    /// `__complete ...`，补全脚本回调，参数原样保留
+    Complete(Vec<String>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ParsedArgs {
+    action: Option<CliAction>,
+    lang: Option<CliLang>,
+    /// `--progress`：在 stderr 绘制长时间操作的进度条
+    progress: bool,
+    /// `--safe-mode`：只加载数据库与核心服务，GUI 与命令行共用
+    safe_mode: bool,
+}
+
+/// 顶层命令：`parse_args` 据此分派，shell 补全也从这里取命令名
+const COMMANDS: &[(&str, fn(Vec<String>) -> CliAction)] = &[
+    ("provider", CliAction::Provider),
+    ("use", |args| CliAction::Provider(use_shortcut(args))),
+    ("usage", CliAction::Usage),
+    ("failover", CliAction::Failover),
+    ("cleanup", CliAction::Cleanup),
+    ("simulate", CliAction::Simulate),
+    ("sessions", CliAction::Sessions),
+    ("backup", CliAction::Backup),
+    ("migrate", CliAction::Migrate),
+    ("doctor", CliAction::Doctor),
+    ("network-changed", CliAction::NetworkChanged),
+    ("status", CliAction::Status),
+    ("current", CliAction::Current),
+    ("proxy", CliAction::Proxy),
+    ("run", CliAction::Run),
+    ("tunnel", CliAction::Tunnel),
+    ("alias", CliAction::Alias),
+    ("completions", CliAction::Completions),
+    ("help", |_| CliAction::Help),
+];
+
+/// 全局参数（可出现在命令前后）
+const GLOBAL_FLAGS: &[&str] = &["--help", "--version", "--lang", "--progress", "--safe-mode"];
+
+fn parse_args(args: &[String]) -> Result<ParsedArgs, String> {
+    // 补全回调的参数是用户正在输入的命令行，不能按全局参数解析
+    if let Some(("__complete", words)) = args.split_first().map(|(f, r)| (f.as_str(), r)) {
+        return Ok(ParsedArgs {
+            action: Some(CliAction::Complete(words.to_vec())),
+            lang: None,
+            progress: false,
+            safe_mode: false,
+        });
+    }
+    let mut help = false;
+    let mut version = false;
+    let mut lang = None;
+    let mut progress = false;
+    let mut safe_mode = false;
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-h" | "--help" => help = true,
+            "-V" | "--version" => version = true,
+            "--progress" => progress = true,
+            "--safe-mode" => safe_mode = true,
+            "--lang" => {
+                let value = iter.next().ok_or("--lang requires a value (zh|en)")?;
+                lang = Some(lang_value(value)?);
+            }
+            other => match other.strip_prefix("--lang=") {
+                Some(value) => lang = Some(lang_value(value)?),
+                None => rest.push(other.to_string()),
+            },
+        }
+    }
+
+    let command = rest
+        .first()
+        .and_then(|name| COMMANDS.iter().find(|(command, _)| command == name));
+    let action = match command {
+        _ if help => Some(CliAction::Help),
+        Some((_, build)) => Some(build(rest.split_off(1))),
+        None if version => Some(CliAction::Version),
+        None => None,
+    };
+    Ok(ParsedArgs {
+        action,
+        lang,
+        progress,
+        safe_mode,
+    })
+}
+
+/// `use [<app>] <id|编号>` 是 `provider use <id|编号> --app <app>` 的简写
+fn use_shortcut(args: Vec<String>) -> Vec<String> {
+    let mut shortcut = vec!["use".to_string()];
+    match args.as_slice() {
+        [app, target] if AppType::from_str(app).is_ok() => {
+            shortcut.extend([target.clone(), "--app".to_string(), app.clone()]);
+        }
+        _ => shortcut.extend(args),
+    }
+    shortcut
+}
+
+fn lang_value(value: &str) -> Result<CliLang, String> {
+    CliLang::parse(value).ok_or_else(|| format!("unsupported --lang value: {value} (zh|en)"))
+}
+
+/// 打开数据库并构建与 GUI 相同的服务状态
+fn open_state() -> Result<AppState, AppError> {
+    let db = Database::init()?;
+    Ok(AppState::new(Arc::new(db)))
+}
+
+/// 本地化错误文案：`Localized` 错误按语言取对应文本，其余错误原样输出
+fn error_message(err: &AppError, lang: CliLang) -> String {
+    err.message(lang)
+}
+
+/// Windows release 版本使用 GUI 子系统，需要手动挂到父进程控制台才能输出
+#[cfg(target_os = "windows")]
+fn attach_parent_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_parent_console() {}
+
+/// 处理命令行参数
+///
+/// 返回 `Some(exit_code)` 表示已作为命令行调用处理完毕，调用方应直接退出；
+/// 返回 `None` 表示不是命令行调用，继续启动 GUI。
+pub fn run<I>(args: I) -> Option<i32>
+where
+    I: IntoIterator<Item = String>,
+{
+    let args: Vec<String> = args.into_iter().collect();
+    let parsed = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            attach_parent_console();
+            eprintln!("error: {message}");
+            eprintln!("Run `cc-switch --help` for usage.");
+            return Some(EXIT_USAGE);
+        }
+    };
+    // 不带命令时同样生效：参数原样交给 GUI 启动流程，由 GUI 读取该开关
+    if parsed.safe_mode || crate::safe_mode::requested_by_env() {
+        crate::safe_mode::enable();
+    }
+    let action = parsed.action?;
+    attach_parent_console();
+
+    let lang = parsed.lang.unwrap_or_else(CliLang::detect);
+    if parsed.progress {
+        crate::progress_events::enable_cli_output();
+    }
+
+    let output = match action {
+        CliAction::Help => help::render(lang),
+        CliAction::Version => format!("cc-switch {}\n", env!("CARGO_PKG_VERSION")),
+        CliAction::Provider(args) => return Some(provider::run(&args, lang)),
+        CliAction::Usage(args) => return Some(usage::run(&args, lang)),
+        CliAction::Failover(args) => return Some(failover::run(&args, lang)),
+        CliAction::Cleanup(args) => return Some(cleanup::run(&args, lang)),
+        CliAction::Backup(args) => return Some(backup::run(&args, lang)),
+        CliAction::Migrate(args) => return Some(migrate::run(&args, lang)),
+        CliAction::Run(args) => return Some(scenario::run(&args, lang)),
+        CliAction::Simulate(args) => return Some(simulate::run(&args, lang)),
+        CliAction::Sessions(args) => return Some(sessions::run(&args, lang)),
+        CliAction::Doctor(args) => return Some(doctor::run(&args, lang)),
+        CliAction::NetworkChanged(args) => return Some(network::run(&args, lang)),
+        CliAction::Status(args) => return Some(status::run(&args, lang)),
+        CliAction::Current(args) => return Some(current::run(&args, lang)),
+        CliAction::Proxy(args) => return Some(proxy::run(&args, lang)),
+        CliAction::Tunnel(args) => return Some(tunnel::run(&args, lang)),
+        CliAction::Alias(args) => return Some(alias::run(&args, lang)),
+        CliAction::Completions(args) => return Some(completions::run(&args, lang)),
+        CliAction::Complete(args) => return Some(completions::run_complete(&args)),
+    };
+    let mut stdout = std::io::stdout().lock();
+    if stdout
+        .write_all(output.as_bytes())
+        .and_then(|_| stdout.flush())
+        .is_err()
+    {
+        return Some(EXIT_FAILURE);
+    }
+    Some(EXIT_OK)
+}
+
+/// 测试用：由字面量构造参数列表
+#[cfg(test)]
+pub(super) fn args(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_flags_are_not_treated_as_commands() {
+        for flag in GLOBAL_FLAGS {
+            let mut argv = args(&[*flag, "status"]);
+            if *flag == "--lang" {
+                argv.insert(1, "en".to_string());
+            }
+            let parsed = parse_args(&argv).unwrap();
+            assert!(parsed.action.is_some(), "{flag}");
+        }
+    }
+
+    #[test]
+    fn unrelated_args_fall_through_to_gui() {
+        let parsed = parse_args(&args(&["ccswitch://v1/import?resource=provider"])).unwrap();
+        assert_eq!(parsed.action, None);
+        assert_eq!(run(Vec::<String>::new()), None);
+    }
+
+    #[test]
+    fn parses_help_with_lang_in_both_forms() {
+        let parsed = parse_args(&args(&["--help", "--lang", "zh"])).unwrap();
+        assert_eq!(parsed.action, Some(CliAction::Help));
+        assert_eq!(parsed.lang, Some(CliLang::Zh));
+
+        let parsed = parse_args(&args(&["--lang=en-US", "-h"])).unwrap();
+        assert_eq!(parsed.lang, Some(CliLang::En));
+        assert!(parse_args(&args(&["--help", "--lang", "fr"])).is_err());
+        assert!(parse_args(&args(&["--help", "--lang"])).is_err());
+    }
+
+    #[test]
+    fn provider_subcommand_keeps_its_own_args() {
+        let parsed = parse_args(&args(&[
+            "--lang", "en", "provider", "list", "--app", "opencode",
+        ]))
+        .unwrap();
+        assert_eq!(
+            parsed.action,
+            Some(CliAction::Provider(args(&["list", "--app", "opencode"])))
+        );
+        let parsed = parse_args(&args(&["provider", "use", "x", "--help"])).unwrap();
+        assert_eq!(parsed.action, Some(CliAction::Help));
+    }
+
+    #[test]
+    fn use_shortcut_maps_to_provider_use() {
+        let parsed = parse_args(&args(&["use", "claude", "2"])).unwrap();
+        assert_eq!(
+            parsed.action,
+            Some(CliAction::Provider(args(&["use", "2", "--app", "claude"])))
+        );
+        let parsed = parse_args(&args(&["use", "relay", "--app", "codex"])).unwrap();
+        assert_eq!(
+            parsed.action,
+            Some(CliAction::Provider(args(&[
+                "use", "relay", "--app", "codex"
+            ])))
+        );
+    }
+
+    #[test]
+    fn complete_callback_keeps_global_flags_as_words() {
+        let parsed = parse_args(&args(&["__complete", "2", "--lang", "zh", "--help"])).unwrap();
+        assert_eq!(
+            parsed.action,
+            Some(CliAction::Complete(args(&["2", "--lang", "zh", "--help"])))
+        );
+        assert_eq!(parsed.lang, None);
+    }
+
+    #[test]
+    fn network_changed_keeps_its_own_args() {
+        let parsed = parse_args(&args(&["network-changed", "--check", "--json"])).unwrap();
+        assert_eq!(
+            parsed.action,
+            Some(CliAction::NetworkChanged(args(&["--check", "--json"])))
+        );
+    }
+
+    #[test]
+    fn migrate_keeps_its_own_args() {
+        let parsed = parse_args(&args(&["migrate", "export", "state.zip", "--json"])).unwrap();
+        assert_eq!(
+            parsed.action,
+            Some(CliAction::Migrate(args(&["export", "state.zip", "--json"])))
+        );
+    }
+
+    #[test]
+    fn progress_flag_is_global() {
+        let parsed = parse_args(&args(&["backup", "--progress"])).unwrap();
+        assert_eq!(parsed.action, Some(CliAction::Backup(Vec::new())));
+        assert!(parsed.progress);
+        assert!(!parse_args(&args(&["backup"])).unwrap().progress);
+    }
+
+    #[test]
+    fn safe_mode_flag_works_with_and_without_command() {
+        let parsed = parse_args(&args(&["--safe-mode"])).unwrap();
+        assert_eq!(parsed.action, None);
+        assert!(parsed.safe_mode);
+        let parsed = parse_args(&args(&["--safe-mode", "provider", "list"])).unwrap();
+        assert_eq!(parsed.action, Some(CliAction::Provider(args(&["list"]))));
+        assert!(parsed.safe_mode);
+    }
+}
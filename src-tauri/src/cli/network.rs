@@ -0,0 +1,93 @@
+//! `cc-switch network-changed` 子命令：网络变化后重新检查供应商
+//!
+//! 默认只写入触发文件，由运行中的 GUI 在下一次轮询（约 15 秒内）领取并执行检查，
+//! 结果在界面弹出摘要；适合挂到 VPN 客户端的连接/断开脚本里。
+//! `--check` 则在当前进程内直接检查并输出结果，不依赖 GUI。
+
+use tauri::async_runtime::block_on;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::services::network_watch;
+use crate::services::self_test::SelfTestService;
+
+/// `cc-switch network-changed [--check] [--json]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let mut check = false;
+    let mut json = false;
+    for arg in args {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--json" => json = true,
+            other => {
+                eprintln!("error: unknown argument: {other}");
+                return EXIT_USAGE;
+            }
+        }
+    }
+
+    if !check {
+        return match network_watch::request_recheck() {
+            Ok(_) => {
+                eprintln!(
+                    "{}",
+                    match lang {
+                        CliLang::Zh => "已通知运行中的 CC Switch 重新检查当前供应商与故障转移队列",
+                        CliLang::En =>
+                            "asked the running CC Switch to re-check current and failover providers",
+                    }
+                );
+                EXIT_OK
+            }
+            Err(e) => {
+                eprintln!("error: {}", error_message(&e, lang));
+                EXIT_FAILURE
+            }
+        };
+    }
+
+    let result = open_state().and_then(|state| {
+        block_on(SelfTestService::check_proxy_targets(
+            &state.db,
+            None,
+            "network_recheck.run",
+        ))
+    });
+    let apps = match result {
+        Ok(apps) => apps,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+    let healthy = apps
+        .iter()
+        .flat_map(|app| &app.entries)
+        .all(|entry| entry.is_healthy());
+
+    if json {
+        match serde_json::to_string_pretty(&apps) {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return EXIT_FAILURE;
+            }
+        }
+    } else if apps.is_empty() {
+        eprintln!(
+            "{}",
+            match lang {
+                CliLang::Zh => "没有需要检查的供应商",
+                CliLang::En => "no active providers to check",
+            }
+        );
+    } else {
+        for app in &apps {
+            println!("{}", app.summary);
+        }
+    }
+    if healthy {
+        EXIT_OK
+    } else {
+        EXIT_FAILURE
+    }
+}
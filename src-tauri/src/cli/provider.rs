@@ -0,0 +1,767 @@
+//! `cc-switch provider` 子命令：列出、切换与添加供应商
+//!
+//! 与界面共用 `ProviderService`，切换流程（回填、Live 写入、代理接管、切换钩子）完全一致。
+//! `add` 通过参数直接创建供应商（供 Ansible 等脚本批量部署），配置结构与
+//! deep link 导入相同，保存前经过与界面相同的校验。
+//! `curl` 输出携带供应商鉴权头与最小请求体的 curl 命令，便于在外部排查问题。
+//! `duplicate` 复制供应商并用 `--set path=value` 覆盖配置字段，快速创建只差模型或端点的变体。
+//! 累加模式应用（OpenCode / OpenClaw / Hermes）没有"当前供应商"，列表中标记的是
+//! 已写入其 Live 配置（如 `~/.config/opencode/opencode.json`）的供应商，`use` 即加入 Live 配置。
+//! 列表编号与首页顺序一致且固定（`sort_index + 1`），`use` 可直接使用编号；
+//! 只有 `reorder` 或界面拖拽会改变编号。
+//! `batch` 按分类 / 名称过滤批量删除、检查、加入故障转移队列或导出，见 `provider_batch`。
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::curl_export::CurlExportService;
+use crate::services::favicon::fallback_glyph;
+use crate::services::{ConfigOverride, ProviderService};
+use crate::store::AppState;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderRow {
+    /// 固定编号（`sort_index + 1`），可用于 `use <编号>`
+    number: Option<usize>,
+    id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    /// 独占模式：是否为当前供应商；累加模式：是否已写入 Live 配置
+    active: bool,
+}
+
+/// `add` 可用的分类；官方与 OMO 供应商依赖预设，只能在界面中添加
+const ADD_CATEGORIES: &[&str] = &[
+    "cn_official",
+    "cloud_provider",
+    "aggregator",
+    "third_party",
+    "custom",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct AddArgs {
+    id: Option<String>,
+    name: Option<String>,
+    base_url: Option<String>,
+    key: Option<String>,
+    /// 从该环境变量读取 API Key，避免密钥出现在进程列表与 shell 历史中
+    key_env: Option<String>,
+    model: Option<String>,
+    category: Option<String>,
+    notes: Option<String>,
+    /// 添加后立即切换到该供应商
+    switch_to: bool,
+}
+
+impl AddArgs {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Default)]
+struct ProviderArgs {
+    app: Option<String>,
+    json: bool,
+    /// `list`：名称前显示图标字符（终端无法显示站点图标，使用带圈首字母）
+    icons: bool,
+    /// `curl`：遮蔽输出中的密钥
+    mask_key: bool,
+    /// `duplicate`：`path=value` / `path:=json` 覆盖项，可重复
+    overrides: Vec<String>,
+    add: AddArgs,
+    positional: Vec<String>,
+}
+
+/// `add` 的取值参数，返回对应字段
+fn add_field<'a>(add: &'a mut AddArgs, flag: &str) -> Option<&'a mut Option<String>> {
+    Some(match flag {
+        "--id" => &mut add.id,
+        "--name" => &mut add.name,
+        "--base-url" => &mut add.base_url,
+        "--key" => &mut add.key,
+        "--key-env" => &mut add.key_env,
+        "--model" => &mut add.model,
+        "--category" => &mut add.category,
+        "--notes" => &mut add.notes,
+        _ => return None,
+    })
+}
+
+fn parse_provider_args(args: &[String]) -> Result<ProviderArgs, String> {
+    let mut parsed = ProviderArgs::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--app" | "-a" => {
+                let value = iter.next().ok_or("--app requires a value")?;
+                parsed.app = Some(value.clone());
+            }
+            "--json" => parsed.json = true,
+            "--icons" => parsed.icons = true,
+            "--use" => parsed.add.switch_to = true,
+            "--mask-key" => parsed.mask_key = true,
+            "--set" => {
+                let value = iter.next().ok_or("--set requires path=value")?;
+                parsed.overrides.push(value.clone());
+            }
+            other => {
+                let (flag, inline) = match other.split_once('=') {
+                    Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+                    _ => (other, None),
+                };
+                if let Some(field) = add_field(&mut parsed.add, flag) {
+                    let value = match inline {
+                        Some(value) => value.to_string(),
+                        None => iter
+                            .next()
+                            .ok_or_else(|| format!("{flag} requires a value"))?
+                            .clone(),
+                    };
+                    *field = Some(value);
+                } else if let Some(value) = other.strip_prefix("--app=") {
+                    parsed.app = Some(value.to_string());
+                } else if let Some(value) = other.strip_prefix("--set=") {
+                    parsed.overrides.push(value.to_string());
+                } else if other.starts_with('-') {
+                    return Err(format!("unknown option: {other}"));
+                } else {
+                    parsed.positional.push(other.to_string());
+                }
+            }
+        }
+    }
+    Ok(parsed)
+}
+
+/// 累加模式应用 Live 配置中已有的供应商 id
+fn live_provider_ids(app_type: &AppType) -> Result<HashSet<String>, AppError> {
+    let providers = match app_type {
+        AppType::OpenCode => crate::opencode_config::get_providers()?,
+        AppType::OpenClaw => crate::openclaw_config::get_providers()?,
+        AppType::Hermes => crate::hermes_config::get_providers()?,
+        _ => return Ok(HashSet::new()),
+    };
+    Ok(providers.keys().cloned().collect())
+}
+
+fn list_rows(state: &AppState, app_type: &AppType) -> Result<Vec<ProviderRow>, AppError> {
+    // 首次列出时为旧数据补齐序号，此后编号保持不变
+    ProviderService::ensure_sort_indices(state, app_type)?;
+    let providers = ProviderService::list(state, app_type.clone())?;
+    let is_active: Box<dyn Fn(&str) -> bool> = if app_type.is_additive_mode() {
+        let live = live_provider_ids(app_type)?;
+        Box::new(move |id| live.contains(id))
+    } else {
+        let current = ProviderService::current(state, app_type.clone())?;
+        Box::new(move |id| id == current)
+    };
+
+    Ok(providers
+        .into_values()
+        .map(|provider| ProviderRow {
+            number: provider.sort_index.map(|index| index + 1),
+            active: is_active(&provider.id),
+            id: provider.id,
+            name: provider.name,
+            category: provider.category,
+        })
+        .collect())
+}
+
+fn render_table(rows: &[ProviderRow], icons: bool) -> String {
+    let id_width = rows
+        .iter()
+        .map(|row| row.id.chars().count())
+        .max()
+        .unwrap_or(0);
+    let number_width = rows
+        .iter()
+        .filter_map(|row| row.number)
+        .max()
+        .map_or(0, |max| max.to_string().len());
+    let mut out = String::new();
+    for row in rows {
+        let marker = if row.active { '*' } else { ' ' };
+        let pad = id_width - row.id.chars().count();
+        let number = row.number.map(|n| n.to_string()).unwrap_or_default();
+        let glyph = if icons {
+            format!("{} ", fallback_glyph(&row.name))
+        } else {
+            String::new()
+        };
+        out.push_str(&format!(
+            "{marker} {number:>number_width$}  {}{}  {glyph}{}",
+            row.id,
+            " ".repeat(pad),
+            row.name
+        ));
+        if let Some(category) = &row.category {
+            out.push_str(&format!("  [{category}]"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+enum ProviderAction<'a> {
+    List,
+    Use(&'a str),
+    Reorder(&'a [String]),
+    Add(Box<Provider>),
+    Curl(&'a str),
+    Duplicate(&'a str, Vec<ConfigOverride>),
+}
+
+fn run_list(state: &AppState, app_type: &AppType, json: bool, icons: bool) -> Result<(), AppError> {
+    let rows = list_rows(state, app_type)?;
+    if json {
+        let text = serde_json::to_string_pretty(&rows)
+            .map_err(|e| AppError::JsonSerialize { source: e })?;
+        println!("{text}");
+    } else {
+        print!("{}", render_table(&rows, icons));
+    }
+    Ok(())
+}
+
+/// `use` 的目标：供应商 id，或列表中的编号
+fn resolve_target(state: &AppState, app_type: &AppType, target: &str) -> Result<String, AppError> {
+    ProviderService::ensure_sort_indices(state, app_type)?;
+    let providers = ProviderService::list(state, app_type.clone())?;
+    ProviderService::resolve_by_number(&providers, target)
+        .map(|provider| provider.id.clone())
+        .ok_or_else(|| {
+            AppError::localized(
+                "provider.cli.not_found",
+                format!("供应商 {target} 不存在"),
+                format!("Provider {target} not found"),
+            )
+        })
+}
+
+fn run_use(state: &AppState, app_type: &AppType, target: &str) -> Result<(), AppError> {
+    let id = resolve_target(state, app_type, target)?;
+    let result = ProviderService::switch(state, app_type.clone(), &id)?;
+    for warning in &result.warnings {
+        eprintln!("warning: {warning}");
+    }
+    if app_type.is_additive_mode() {
+        println!("{id} → {} live config", app_type.as_str());
+    } else {
+        println!("{} → {id}", app_type.as_str());
+    }
+    Ok(())
+}
+
+/// 参数校验与界面表单保持一致：名称必填，非官方供应商需要 http(s) 端点与 API Key
+fn build_add_provider(app_type: &AppType, add: &AddArgs) -> Result<Provider, String> {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+
+    let name = non_empty(&add.name).ok_or("--name is required")?;
+    let category = non_empty(&add.category).unwrap_or_else(|| "custom".to_string());
+    if !ADD_CATEGORIES.contains(&category.as_str()) {
+        return Err(format!(
+            "unsupported --category: {category} ({})",
+            ADD_CATEGORIES.join("|")
+        ));
+    }
+
+    let base_url = non_empty(&add.base_url).ok_or("--base-url is required")?;
+    let parsed_url =
+        url::Url::parse(&base_url).map_err(|e| format!("invalid --base-url {base_url}: {e}"))?;
+    if !matches!(parsed_url.scheme(), "http" | "https") {
+        return Err(format!("--base-url must be an http(s) URL: {base_url}"));
+    }
+
+    let key = match (non_empty(&add.key), non_empty(&add.key_env)) {
+        (Some(_), Some(_)) => return Err("use either --key or --key-env, not both".to_string()),
+        (Some(key), None) => key,
+        (None, Some(var)) => std::env::var(&var)
+            .ok()
+            .map(|key| key.trim().to_string())
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| format!("environment variable {var} is not set or empty"))?,
+        (None, None) => return Err("--key or --key-env is required".to_string()),
+    };
+
+    let request = crate::deeplink::DeepLinkImportRequest {
+        resource: "provider".to_string(),
+        app: Some(app_type.as_str().to_string()),
+        name: Some(name.clone()),
+        endpoint: Some(base_url.trim_end_matches('/').to_string()),
+        api_key: Some(key),
+        model: non_empty(&add.model),
+        notes: non_empty(&add.notes),
+        homepage: crate::deeplink::infer_homepage_from_endpoint(&base_url),
+        ..Default::default()
+    };
+    let mut provider = crate::deeplink::build_provider_from_request(app_type, &request)
+        .map_err(|e| e.to_string())?;
+    provider.category = Some(category);
+    provider.created_at = Some(chrono::Utc::now().timestamp_millis());
+    provider.id = match non_empty(&add.id) {
+        Some(id) => id,
+        None => {
+            let slug: String = name
+                .chars()
+                .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+                .collect::<String>()
+                .to_lowercase();
+            format!("{slug}-{}", chrono::Utc::now().timestamp_millis())
+        }
+    };
+    Ok(provider)
+}
+
+/// 把指定供应商（id 或编号）移到最前，其余保持相对顺序，编号重新连续排列
+fn run_reorder(
+    state: &AppState,
+    app_type: &AppType,
+    targets: &[String],
+    json: bool,
+) -> Result<(), AppError> {
+    let front = targets
+        .iter()
+        .map(|target| resolve_target(state, app_type, target))
+        .collect::<Result<Vec<_>, _>>()?;
+    ProviderService::reorder(state, app_type, &front)?;
+    run_list(state, app_type, json, false)
+}
+
+fn run_curl(
+    state: &AppState,
+    app_type: &AppType,
+    id: &str,
+    model: Option<&str>,
+    mask_key: bool,
+) -> Result<(), AppError> {
+    let provider = state
+        .db
+        .get_provider_by_id(id, app_type.as_str())?
+        .ok_or_else(|| {
+            AppError::localized(
+                "provider.cli.not_found",
+                format!("供应商 {id} 不存在"),
+                format!("Provider {id} not found"),
+            )
+        })?;
+    println!(
+        "{}",
+        CurlExportService::for_provider(app_type, &provider, model, false, mask_key)?
+    );
+    Ok(())
+}
+
+fn run_add(
+    state: &AppState,
+    app_type: &AppType,
+    provider: Provider,
+    switch_to: bool,
+    json: bool,
+) -> Result<(), AppError> {
+    let id = provider.id.clone();
+    if state
+        .db
+        .get_provider_by_id(&id, app_type.as_str())?
+        .is_some()
+    {
+        return Err(AppError::localized(
+            "provider.cli.id_exists",
+            format!("供应商 {id} 已存在"),
+            format!("Provider {id} already exists"),
+        ));
+    }
+    let mut row = ProviderRow {
+        number: None,
+        id: id.clone(),
+        name: provider.name.clone(),
+        category: provider.category.clone(),
+        active: false,
+    };
+    ProviderService::add(state, app_type.clone(), provider, true)?;
+    row.number = state
+        .db
+        .get_provider_by_id(&id, app_type.as_str())?
+        .and_then(|provider| provider.sort_index)
+        .map(|index| index + 1);
+    if switch_to {
+        run_use(state, app_type, &id)?;
+    }
+    if json {
+        let text = serde_json::to_string_pretty(&row)
+            .map_err(|e| AppError::JsonSerialize { source: e })?;
+        println!("{text}");
+    } else {
+        println!("{id}");
+    }
+    Ok(())
+}
+
+fn run_duplicate(
+    state: &AppState,
+    app_type: &AppType,
+    target: &str,
+    overrides: &[ConfigOverride],
+    switch_to: bool,
+    json: bool,
+) -> Result<(), AppError> {
+    let source = resolve_target(state, app_type, target)?;
+    let copy = ProviderService::duplicate(state, app_type.clone(), &source, overrides)?;
+    if switch_to {
+        run_use(state, app_type, &copy.id)?;
+    }
+    if json {
+        let row = ProviderRow {
+            number: copy.sort_index.map(|index| index + 1),
+            id: copy.id,
+            name: copy.name,
+            category: copy.category,
+            active: switch_to,
+        };
+        let text = serde_json::to_string_pretty(&row)
+            .map_err(|e| AppError::JsonSerialize { source: e })?;
+        println!("{text}");
+    } else {
+        println!("{}  {}", copy.id, copy.name);
+    }
+    Ok(())
+}
+
+/// `cc-switch provider <list|use|reorder|add|curl|duplicate|matrix> ...`
+/// `batch` 有独立的参数集，先于常规解析分流；返回去掉 `batch` 后的参数
+fn batch_args(args: &[String]) -> Option<Vec<String>> {
+    let mut skip_value = false;
+    for (index, arg) in args.iter().enumerate() {
+        if skip_value {
+            skip_value = false;
+            continue;
+        }
+        match arg.as_str() {
+            "--app" | "-a" => skip_value = true,
+            flag if flag.starts_with('-') => {}
+            "batch" => {
+                let mut rest = args.to_vec();
+                rest.remove(index);
+                return Some(rest);
+            }
+            _ => return None,
+        }
+    }
+    None
+}
+
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    if let Some(rest) = batch_args(args) {
+        return super::provider_batch::run(&rest, lang);
+    }
+    // matrix 跨所有应用，不接受 --app
+    if let Some(("matrix", rest)) = args
+        .split_first()
+        .map(|(first, rest)| (first.as_str(), rest))
+    {
+        return super::provider_matrix::run(rest, lang);
+    }
+    let parsed = match parse_provider_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+    let app_type = match AppType::from_str(parsed.app.as_deref().unwrap_or("claude")) {
+        Ok(app_type) => app_type,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_USAGE;
+        }
+    };
+
+    let (command, rest) = match parsed.positional.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => {
+            eprintln!(
+                "error: missing subcommand (list | use <id|number> | reorder [<id|number>...] | add | curl <id> | duplicate <id|number> | batch <op> | matrix <base-url>)"
+            );
+            return EXIT_USAGE;
+        }
+    };
+    // curl 只借用 --model 指定请求模型
+    let curl_model = AddArgs {
+        model: parsed.add.model.clone(),
+        ..Default::default()
+    };
+    // duplicate 只借用 --use 在复制后切换
+    let duplicate_use = AddArgs {
+        switch_to: parsed.add.switch_to,
+        ..Default::default()
+    };
+    let add_flags_allowed = command == "add"
+        || (command == "curl" && parsed.add == curl_model)
+        || (command == "duplicate" && parsed.add == duplicate_use);
+    if !add_flags_allowed && !parsed.add.is_empty() {
+        eprintln!("error: --name/--base-url/--key/... are only valid for `provider add`");
+        return EXIT_USAGE;
+    }
+    if parsed.icons && command != "list" {
+        eprintln!("error: --icons is only valid for `provider list`");
+        return EXIT_USAGE;
+    }
+    if parsed.mask_key && command != "curl" {
+        eprintln!("error: --mask-key is only valid for `provider curl`");
+        return EXIT_USAGE;
+    }
+    if !parsed.overrides.is_empty() && command != "duplicate" {
+        eprintln!("error: --set is only valid for `provider duplicate`");
+        return EXIT_USAGE;
+    }
+    // `duplicate <app> <provider>`：应用也可作为第一个位置参数
+    let (app_type, rest) = match (command, rest) {
+        ("duplicate", [app, target]) if parsed.app.is_none() => match AppType::from_str(app) {
+            Ok(app_type) => (app_type, std::slice::from_ref(target)),
+            Err(e) => {
+                eprintln!("error: {}", error_message(&e, lang));
+                return EXIT_USAGE;
+            }
+        },
+        _ => (app_type, rest),
+    };
+    let action = match (command, rest) {
+        ("list", []) => ProviderAction::List,
+        ("use", [id]) => ProviderAction::Use(id.as_str()),
+        ("reorder", targets) => ProviderAction::Reorder(targets),
+        ("curl", [id]) => ProviderAction::Curl(id.as_str()),
+        ("add", []) => match build_add_provider(&app_type, &parsed.add) {
+            Ok(provider) => ProviderAction::Add(Box::new(provider)),
+            Err(message) => {
+                eprintln!("error: {message}");
+                return EXIT_USAGE;
+            }
+        },
+        ("add", _) => {
+            eprintln!("error: usage: cc-switch provider add --name <name> --base-url <url> --key <key> [--app <app>]");
+            return EXIT_USAGE;
+        }
+        ("use", _) => {
+            eprintln!("error: usage: cc-switch provider use <id|number> [--app <app>]");
+            return EXIT_USAGE;
+        }
+        ("duplicate", [id]) => match parsed
+            .overrides
+            .iter()
+            .map(|spec| ConfigOverride::parse(spec))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(overrides) => ProviderAction::Duplicate(id.as_str(), overrides),
+            Err(e) => {
+                eprintln!("error: {}", error_message(&e, lang));
+                return EXIT_USAGE;
+            }
+        },
+        ("duplicate", _) => {
+            eprintln!("error: usage: cc-switch provider duplicate [<app>] <id|number> [--set <path>=<value>]... [--use]");
+            return EXIT_USAGE;
+        }
+        ("curl", _) => {
+            eprintln!("error: usage: cc-switch provider curl <id> [--model <model>] [--mask-key] [--app <app>]");
+            return EXIT_USAGE;
+        }
+        _ => {
+            eprintln!("error: unknown provider subcommand: {command}");
+            return EXIT_USAGE;
+        }
+    };
+
+    let result = open_state().and_then(|state| match action {
+        ProviderAction::List => run_list(&state, &app_type, parsed.json, parsed.icons),
+        ProviderAction::Use(id) => run_use(&state, &app_type, id),
+        ProviderAction::Reorder(targets) => run_reorder(&state, &app_type, targets, parsed.json),
+        ProviderAction::Add(provider) => run_add(
+            &state,
+            &app_type,
+            *provider,
+            parsed.add.switch_to,
+            parsed.json,
+        ),
+        ProviderAction::Curl(id) => run_curl(
+            &state,
+            &app_type,
+            id,
+            parsed.add.model.as_deref(),
+            parsed.mask_key,
+        ),
+        ProviderAction::Duplicate(id, overrides) => run_duplicate(
+            &state,
+            &app_type,
+            id,
+            &overrides,
+            parsed.add.switch_to,
+            parsed.json,
+        ),
+    });
+    match result {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            EXIT_FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_app_flag_and_positionals() {
+        let parsed = parse_provider_args(&args(&["use", "--app", "opencode", "deepseek"])).unwrap();
+        assert_eq!(parsed.app.as_deref(), Some("opencode"));
+        assert_eq!(parsed.positional, vec!["use", "deepseek"]);
+
+        let parsed = parse_provider_args(&args(&["list", "--app=codex", "--json"])).unwrap();
+        assert_eq!(parsed.app.as_deref(), Some("codex"));
+        assert!(parsed.json);
+        assert!(parse_provider_args(&args(&["list", "--bogus"])).is_err());
+    }
+
+    #[test]
+    fn collects_repeated_set_overrides() {
+        let parsed = parse_provider_args(&args(&[
+            "duplicate",
+            "claude",
+            "relay",
+            "--set",
+            "env.ANTHROPIC_MODEL=glm-4.6",
+            "--set=env.ANTHROPIC_BASE_URL=https://eu.example.com",
+            "--use",
+        ]))
+        .unwrap();
+        assert_eq!(parsed.positional, vec!["duplicate", "claude", "relay"]);
+        assert_eq!(
+            parsed.overrides,
+            vec![
+                "env.ANTHROPIC_MODEL=glm-4.6",
+                "env.ANTHROPIC_BASE_URL=https://eu.example.com"
+            ]
+        );
+        assert!(parsed.add.switch_to);
+        assert!(parse_provider_args(&args(&["duplicate", "relay", "--set"])).is_err());
+    }
+
+    #[test]
+    fn routes_batch_before_regular_parsing() {
+        assert_eq!(
+            batch_args(&args(&["--app", "codex", "batch", "delete", "--dry-run"])),
+            Some(args(&["--app", "codex", "delete", "--dry-run"]))
+        );
+        assert_eq!(batch_args(&args(&["list", "batch"])), None);
+        assert_eq!(batch_args(&args(&["--app", "batch", "list"])), None);
+    }
+
+    #[test]
+    fn parses_add_flags_in_both_forms() {
+        let parsed = parse_provider_args(&args(&[
+            "add",
+            "--name",
+            "My Relay",
+            "--base-url=https://relay.example.com/",
+            "--key",
+            "sk-test",
+            "--use",
+        ]))
+        .unwrap();
+        assert_eq!(parsed.positional, vec!["add"]);
+        assert_eq!(parsed.add.name.as_deref(), Some("My Relay"));
+        assert_eq!(
+            parsed.add.base_url.as_deref(),
+            Some("https://relay.example.com/")
+        );
+        assert!(parsed.add.switch_to);
+        assert!(parse_provider_args(&args(&["add", "--name"])).is_err());
+    }
+
+    #[test]
+    fn add_validates_like_the_form() {
+        let add = AddArgs {
+            name: Some("My Relay".to_string()),
+            base_url: Some("https://relay.example.com/".to_string()),
+            key: Some("sk-test".to_string()),
+            model: Some("claude-sonnet-4-5".to_string()),
+            ..Default::default()
+        };
+        let provider = build_add_provider(&AppType::Claude, &add).unwrap();
+        assert!(provider.id.starts_with("myrelay-"));
+        assert_eq!(provider.category.as_deref(), Some("custom"));
+        assert_eq!(
+            provider.settings_config["env"]["ANTHROPIC_BASE_URL"],
+            "https://relay.example.com"
+        );
+        assert_eq!(
+            provider.settings_config["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "sk-test"
+        );
+
+        let invalid = [
+            AddArgs {
+                name: None,
+                ..add.clone()
+            },
+            AddArgs {
+                base_url: Some("ftp://relay.example.com".to_string()),
+                ..add.clone()
+            },
+            AddArgs {
+                key: None,
+                ..add.clone()
+            },
+            AddArgs {
+                category: Some("official".to_string()),
+                ..add.clone()
+            },
+        ];
+        for args in invalid {
+            assert!(build_add_provider(&AppType::Claude, &args).is_err());
+        }
+    }
+
+    #[test]
+    fn table_marks_active_rows() {
+        let rows = vec![
+            ProviderRow {
+                number: Some(1),
+                id: "a".to_string(),
+                name: "Alpha".to_string(),
+                category: None,
+                active: true,
+            },
+            ProviderRow {
+                number: Some(12),
+                id: "beta".to_string(),
+                name: "Beta".to_string(),
+                category: Some("custom".to_string()),
+                active: false,
+            },
+        ];
+        assert_eq!(
+            render_table(&rows, false),
+            "*  1  a     Alpha\n  12  beta  Beta  [custom]\n"
+        );
+        assert!(render_table(&rows, true).starts_with("*  1  a     Ⓐ Alpha\n"));
+    }
+}
@@ -0,0 +1,421 @@
+//! `cc-switch provider batch` 子命令：按过滤条件批量操作供应商
+//!
+//! `--category`（精确匹配，不区分大小写）与 `--name-contains`（不区分大小写）至少指定一个，
+//! 与 `--app` 一起圈定目标；执行前先列出命中的供应商，`--dry-run` 只列出不执行。
+//!
+//! - `delete`：逐个删除；当前正在使用的供应商会删除失败并报告，其余照常处理；
+//! - `test`：连通性检查（与界面批量检查相同，跳过官方供应商），结果写入检查日志；
+//! - `failover-add`：加入故障转移队列，已在队列中的保持原位；
+//! - `export`：输出命中供应商的完整记录 JSON（`-o` 写入文件）。
+//!
+//! 任一供应商处理失败时退出码为 1。
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use serde::Serialize;
+use tauri::async_runtime::block_on;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::failover_queue::FailoverQueueService;
+use crate::services::stream_check::StreamCheckService;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BatchOp {
+    Delete,
+    Test,
+    FailoverAdd,
+    Export,
+}
+
+impl FromStr for BatchOp {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "delete" | "rm" => Ok(Self::Delete),
+            "test" => Ok(Self::Test),
+            "failover-add" => Ok(Self::FailoverAdd),
+            "export" => Ok(Self::Export),
+            other => Err(format!(
+                "unknown batch operation: {other} (delete | test | failover-add | export)"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct BatchFilter {
+    category: Option<String>,
+    name_contains: Option<String>,
+}
+
+impl BatchFilter {
+    fn is_empty(&self) -> bool {
+        self.category.is_none() && self.name_contains.is_none()
+    }
+
+    fn matches(&self, provider: &Provider) -> bool {
+        let category_ok = self.category.as_deref().is_none_or(|category| {
+            provider
+                .category
+                .as_deref()
+                .is_some_and(|value| value.eq_ignore_ascii_case(category))
+        });
+        let name_ok = self.name_contains.as_deref().is_none_or(|needle| {
+            provider
+                .name
+                .to_lowercase()
+                .contains(&needle.to_lowercase())
+        });
+        category_ok && name_ok
+    }
+}
+
+#[derive(Debug)]
+struct BatchArgs {
+    op: BatchOp,
+    app: Option<String>,
+    filter: BatchFilter,
+    dry_run: bool,
+    json: bool,
+    output: Option<PathBuf>,
+}
+
+/// `args` 为 `batch` 之后的参数
+fn parse_batch_args(args: &[String]) -> Result<BatchArgs, String> {
+    let mut op = None;
+    let mut app = None;
+    let mut filter = BatchFilter::default();
+    let mut dry_run = false;
+    let mut json = false;
+    let mut output = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = |name: &str| {
+            inline
+                .clone()
+                .or_else(|| iter.next().cloned())
+                .ok_or_else(|| format!("{name} requires a value"))
+        };
+        match flag {
+            "--app" | "-a" => app = Some(value("--app")?),
+            "--category" => filter.category = Some(value("--category")?),
+            "--name-contains" => filter.name_contains = Some(value("--name-contains")?),
+            "-o" | "--output" => output = Some(PathBuf::from(value("--output")?)),
+            "--dry-run" => dry_run = true,
+            "--json" => json = true,
+            other if other.starts_with('-') => return Err(format!("unknown option: {other}")),
+            other if op.is_none() => op = Some(other.parse::<BatchOp>()?),
+            other => return Err(format!("unexpected argument: {other}")),
+        }
+    }
+
+    let op = op.ok_or("missing batch operation (delete | test | failover-add | export)")?;
+    if filter.is_empty() {
+        return Err("at least one filter is required (--category, --name-contains)".to_string());
+    }
+    if output.is_some() && op != BatchOp::Export {
+        return Err("-o/--output is only valid for `batch export`".to_string());
+    }
+    Ok(BatchArgs {
+        op,
+        app,
+        filter,
+        dry_run,
+        json,
+        output,
+    })
+}
+
+/// 单个供应商的处理结果
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchOutcome {
+    id: String,
+    name: String,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+fn outcome(provider: &Provider, result: Result<Option<String>, String>) -> BatchOutcome {
+    let ok = result.is_ok();
+    BatchOutcome {
+        id: provider.id.clone(),
+        name: provider.name.clone(),
+        ok,
+        message: result.unwrap_or_else(Some),
+    }
+}
+
+fn select(
+    state: &AppState,
+    app_type: &AppType,
+    filter: &BatchFilter,
+) -> Result<Vec<Provider>, AppError> {
+    Ok(ProviderService::list(state, app_type.clone())?
+        .into_values()
+        .filter(|provider| filter.matches(provider))
+        .collect())
+}
+
+fn render_targets(targets: &[Provider]) -> String {
+    targets
+        .iter()
+        .map(|provider| match &provider.category {
+            Some(category) => format!("  {}  {}  [{category}]\n", provider.id, provider.name),
+            None => format!("  {}  {}\n", provider.id, provider.name),
+        })
+        .collect()
+}
+
+fn execute(
+    state: &AppState,
+    app_type: &AppType,
+    op: BatchOp,
+    targets: &[Provider],
+    lang: CliLang,
+) -> Result<Vec<BatchOutcome>, AppError> {
+    let message = |e: AppError| error_message(&e, lang);
+    let outcomes = match op {
+        BatchOp::Delete => targets
+            .iter()
+            .map(|provider| {
+                let result = ProviderService::delete(state, app_type.clone(), &provider.id)
+                    .map(|_| None)
+                    .map_err(message);
+                outcome(provider, result)
+            })
+            .collect(),
+        BatchOp::FailoverAdd => targets
+            .iter()
+            .map(|provider| {
+                let result = FailoverQueueService::add(&state.db, app_type, &provider.id)
+                    .map(|_| None)
+                    .map_err(message);
+                outcome(provider, result)
+            })
+            .collect(),
+        BatchOp::Test => {
+            let config = state.db.get_stream_check_config()?;
+            targets
+                .iter()
+                .map(|provider| {
+                    if provider.category.as_deref() == Some("official") {
+                        return outcome(provider, Ok(Some("skipped: official provider".into())));
+                    }
+                    eprintln!("testing {} ...", provider.name);
+                    let result = block_on(StreamCheckService::check_with_retry(
+                        app_type, provider, &config, None,
+                    ));
+                    let result = match result {
+                        Ok(check) => {
+                            let _ = state.db.save_stream_check_log(
+                                &provider.id,
+                                &provider.name,
+                                app_type.as_str(),
+                                &check,
+                            );
+                            let summary = match check.response_time_ms {
+                                Some(ms) => format!("{} ({ms} ms)", check.message),
+                                None => check.message.clone(),
+                            };
+                            if check.success {
+                                Ok(Some(summary))
+                            } else {
+                                Err(summary)
+                            }
+                        }
+                        Err(e) => Err(message(e)),
+                    };
+                    outcome(provider, result)
+                })
+                .collect()
+        }
+        // export 不逐个处理，由 `export` 一次性输出
+        BatchOp::Export => Vec::new(),
+    };
+    Ok(outcomes)
+}
+
+fn export(targets: &[Provider], output: Option<&PathBuf>) -> Result<(), AppError> {
+    let text =
+        serde_json::to_string_pretty(targets).map_err(|e| AppError::JsonSerialize { source: e })?;
+    match output {
+        Some(path) => {
+            crate::config::write_text_file(path, &format!("{text}\n"))?;
+            eprintln!("exported {} providers to {}", targets.len(), path.display());
+        }
+        None => println!("{text}"),
+    }
+    Ok(())
+}
+
+/// `cc-switch provider batch <delete|test|failover-add|export> (--category <c> | --name-contains <s>)... [--app <app>] [--dry-run] [--json] [-o <file>]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse_batch_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+    let app_type = match AppType::from_str(parsed.app.as_deref().unwrap_or("claude")) {
+        Ok(app_type) => app_type,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_USAGE;
+        }
+    };
+
+    let state = match open_state() {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+    let targets = match select(&state, &app_type, &parsed.filter) {
+        Ok(targets) => targets,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+    if targets.is_empty() {
+        eprintln!(
+            "{}",
+            match lang {
+                CliLang::Zh => "没有匹配的供应商",
+                CliLang::En => "no providers match the filter",
+            }
+        );
+        return EXIT_OK;
+    }
+
+    // 先列出命中的供应商（export 的 stdout 留给 JSON，列表输出到 stderr）
+    eprintln!(
+        "{}",
+        match lang {
+            CliLang::Zh => format!("匹配 {} 个 {} 供应商：", targets.len(), app_type.as_str()),
+            CliLang::En => format!("{} {} provider(s) match:", targets.len(), app_type.as_str()),
+        }
+    );
+    eprint!("{}", render_targets(&targets));
+    if parsed.dry_run {
+        eprintln!(
+            "{}",
+            match lang {
+                CliLang::Zh => "--dry-run：未执行任何操作",
+                CliLang::En => "--dry-run: nothing was changed",
+            }
+        );
+        return EXIT_OK;
+    }
+
+    if parsed.op == BatchOp::Export {
+        return match export(&targets, parsed.output.as_ref()) {
+            Ok(()) => EXIT_OK,
+            Err(e) => {
+                eprintln!("error: {}", error_message(&e, lang));
+                EXIT_FAILURE
+            }
+        };
+    }
+
+    let outcomes = match execute(&state, &app_type, parsed.op, &targets, lang) {
+        Ok(outcomes) => outcomes,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+    if parsed.json {
+        match serde_json::to_string_pretty(&outcomes) {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return EXIT_FAILURE;
+            }
+        }
+    } else {
+        for item in &outcomes {
+            let mark = if item.ok { "ok  " } else { "FAIL" };
+            match &item.message {
+                Some(message) => println!("{mark}  {}  {message}", item.id),
+                None => println!("{mark}  {}", item.id),
+            }
+        }
+    }
+    if outcomes.iter().all(|item| item.ok) {
+        EXIT_OK
+    } else {
+        EXIT_FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+    use serde_json::json;
+
+    fn provider(name: &str, category: Option<&str>) -> Provider {
+        let mut provider =
+            Provider::with_id(name.to_lowercase(), name.to_string(), json!({}), None);
+        provider.category = category.map(str::to_string);
+        provider
+    }
+
+    #[test]
+    fn parses_operation_filters_and_flags() {
+        let parsed = parse_batch_args(&args(&[
+            "delete",
+            "--category",
+            "third_party",
+            "--name-contains=relay",
+            "--app",
+            "codex",
+            "--dry-run",
+        ]))
+        .unwrap();
+        assert_eq!(parsed.op, BatchOp::Delete);
+        assert_eq!(parsed.app.as_deref(), Some("codex"));
+        assert_eq!(parsed.filter.category.as_deref(), Some("third_party"));
+        assert_eq!(parsed.filter.name_contains.as_deref(), Some("relay"));
+        assert!(parsed.dry_run);
+
+        // 没有过滤条件时拒绝执行，避免误删全部供应商
+        assert!(parse_batch_args(&args(&["delete"])).is_err());
+        assert!(parse_batch_args(&args(&["purge", "--category", "custom"])).is_err());
+        assert!(parse_batch_args(&args(&["test", "--category", "custom", "-o", "x"])).is_err());
+        assert!(parse_batch_args(&args(&["export", "--category", "custom", "-o", "x"])).is_ok());
+    }
+
+    #[test]
+    fn filter_combines_category_and_case_insensitive_name() {
+        let filter = BatchFilter {
+            category: Some("third_party".to_string()),
+            name_contains: Some("RELAY".to_string()),
+        };
+        assert!(filter.matches(&provider("Fast Relay", Some("third_party"))));
+        assert!(!filter.matches(&provider("Fast Relay", Some("custom"))));
+        assert!(!filter.matches(&provider("Mirror", Some("third_party"))));
+
+        let by_name = BatchFilter {
+            category: None,
+            name_contains: Some("relay".to_string()),
+        };
+        assert!(by_name.matches(&provider("relay-b", None)));
+    }
+}
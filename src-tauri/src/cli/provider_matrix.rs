@@ -0,0 +1,420 @@
+//! `cc-switch provider matrix` 子命令：对共用同一端点的所有供应商逐一发送测试消息
+//!
+//! 同一个中转站常同时配置给 Claude、Codex、Gemini 等多个应用。给定 Base URL 后，
+//! 找出各应用中引用该端点的供应商（按协议 + 主机 + 端口匹配，Codex 常见的 `/v1`
+//! 等路径差异视为同一端点），按各自应用的协议发送一条测试消息（与界面“发送测试消息”
+//! 相同，会消耗少量 token），输出应用 × 结果矩阵。所有应用都被拒绝鉴权时提示
+//! 共用的密钥可能已失效，只有部分应用被拒绝时指出是哪些应用。
+//!
+//! 任一测试失败时退出码为 1。
+
+use serde::Serialize;
+use tauri::async_runtime::block_on;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::proxy::providers::get_adapter;
+use crate::services::test_message::{TestMessageOptions, TestMessageService};
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct MatrixArgs {
+    base_url: String,
+    json: bool,
+}
+
+fn parse_matrix_args(args: &[String]) -> Result<MatrixArgs, String> {
+    let mut parsed = MatrixArgs::default();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => parsed.json = true,
+            flag if flag.starts_with('-') => return Err(format!("unknown argument: {flag}")),
+            url if parsed.base_url.is_empty() => parsed.base_url = url.to_string(),
+            other => return Err(format!("unexpected argument: {other}")),
+        }
+    }
+    if parsed.base_url.is_empty() {
+        return Err("usage: cc-switch provider matrix <base-url> [--json]".to_string());
+    }
+    if endpoint_origin(&parsed.base_url).is_none() {
+        return Err(format!(
+            "base URL must be an http(s) URL: {}",
+            parsed.base_url
+        ));
+    }
+    Ok(parsed)
+}
+
+/// 端点的 origin（小写协议与主机，非默认端口），用于判断是否为同一中转站
+fn endpoint_origin(url: &str) -> Option<String> {
+    let url = url::Url::parse(url.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = url.host_str()?;
+    Some(match url.port() {
+        Some(port) => format!("{}://{host}:{port}", url.scheme()),
+        None => format!("{}://{host}", url.scheme()),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CellStatus {
+    Ok,
+    Failed,
+    /// 该应用没有引用此端点的供应商
+    Missing,
+}
+
+/// 矩阵中的一格：一个应用下的一个供应商
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MatrixCell {
+    app: String,
+    status: CellStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+impl MatrixCell {
+    fn missing(app_type: &AppType) -> Self {
+        Self {
+            app: app_type.as_str().to_string(),
+            status: CellStatus::Missing,
+            provider_id: None,
+            provider_name: None,
+            http_status: None,
+            model: None,
+            latency_ms: None,
+            message: None,
+        }
+    }
+
+    fn is_auth_rejected(&self) -> bool {
+        matches!(self.http_status, Some(401 | 403))
+    }
+}
+
+/// 可以发送测试消息的应用（累加模式应用的配置结构不同，暂不支持）
+fn matrix_apps() -> Vec<AppType> {
+    AppType::all()
+        .filter(|app| !app.is_additive_mode())
+        .collect()
+}
+
+/// 各应用中 Base URL 与给定端点同源的供应商
+fn referencing_providers(
+    state: &AppState,
+    app_type: &AppType,
+    origin: &str,
+) -> Result<Vec<Provider>, AppError> {
+    let adapter = get_adapter(app_type);
+    Ok(ProviderService::list(state, app_type.clone())?
+        .into_values()
+        .filter(|provider| {
+            adapter
+                .extract_base_url(provider)
+                .ok()
+                .and_then(|url| endpoint_origin(&url))
+                .is_some_and(|candidate| candidate == origin)
+        })
+        .collect())
+}
+
+fn test_provider(app_type: &AppType, provider: &Provider, lang: CliLang) -> MatrixCell {
+    let mut cell = MatrixCell {
+        provider_id: Some(provider.id.clone()),
+        provider_name: Some(provider.name.clone()),
+        status: CellStatus::Failed,
+        ..MatrixCell::missing(app_type)
+    };
+    let result = block_on(TestMessageService::send(
+        app_type,
+        provider,
+        &TestMessageOptions::default(),
+        |_| {},
+    ));
+    match result {
+        Ok(result) => {
+            if result.success {
+                cell.status = CellStatus::Ok;
+            }
+            cell.http_status = result.http_status;
+            cell.model = result.model.or(Some(result.requested_model));
+            cell.latency_ms = Some(result.latency_ms);
+            cell.message = result.error;
+        }
+        Err(e) => cell.message = Some(error_message(&e, lang)),
+    }
+    cell
+}
+
+/// 鉴权失败的诊断结论
+#[derive(Debug, PartialEq, Eq)]
+enum Diagnosis {
+    /// 所有测试都被拒绝鉴权：共用的密钥本身有问题
+    KeyRejectedEverywhere,
+    /// 只有这些应用被拒绝：密钥可用，但这些应用的配置或权限不对
+    KeyRejectedFor(Vec<String>),
+}
+
+fn diagnose(cells: &[MatrixCell]) -> Option<Diagnosis> {
+    let tested: Vec<&MatrixCell> = cells
+        .iter()
+        .filter(|cell| cell.status != CellStatus::Missing)
+        .collect();
+    let mut rejected: Vec<String> = tested
+        .iter()
+        .filter(|cell| cell.is_auth_rejected())
+        .map(|cell| cell.app.clone())
+        .collect();
+    rejected.dedup();
+    if rejected.is_empty() {
+        None
+    } else if tested.iter().all(|cell| cell.is_auth_rejected()) {
+        Some(Diagnosis::KeyRejectedEverywhere)
+    } else {
+        Some(Diagnosis::KeyRejectedFor(rejected))
+    }
+}
+
+fn render_diagnosis(diagnosis: &Diagnosis, lang: CliLang) -> String {
+    match (diagnosis, lang) {
+        (Diagnosis::KeyRejectedEverywhere, CliLang::Zh) => {
+            "所有应用均被拒绝鉴权（HTTP 401/403），共用的密钥可能无效或已过期".to_string()
+        }
+        (Diagnosis::KeyRejectedEverywhere, CliLang::En) => {
+            "every app was rejected (HTTP 401/403): the shared key is likely invalid or expired"
+                .to_string()
+        }
+        (Diagnosis::KeyRejectedFor(apps), CliLang::Zh) => format!(
+            "仅 {} 被拒绝鉴权（HTTP 401/403），请检查这些应用中的密钥或该密钥的权限",
+            apps.join(", ")
+        ),
+        (Diagnosis::KeyRejectedFor(apps), CliLang::En) => format!(
+            "rejected for {} only (HTTP 401/403): check the key configured for these apps or the key's permissions",
+            apps.join(", ")
+        ),
+    }
+}
+
+/// 表格输出：应用、供应商、结果、详情（状态码 · 耗时 · 模型或错误）
+fn render_matrix(cells: &[MatrixCell]) -> String {
+    let rows: Vec<[String; 4]> = cells
+        .iter()
+        .map(|cell| {
+            let provider = match (&cell.provider_id, &cell.provider_name) {
+                (Some(id), Some(name)) if id != name => format!("{id} ({name})"),
+                (Some(id), _) => id.clone(),
+                _ => "-".to_string(),
+            };
+            let result = match cell.status {
+                CellStatus::Ok => "ok",
+                CellStatus::Failed => "FAIL",
+                CellStatus::Missing => "-",
+            };
+            let mut detail = Vec::new();
+            detail.extend(cell.http_status.map(|status| status.to_string()));
+            detail.extend(cell.latency_ms.map(|ms| format!("{ms} ms")));
+            match (&cell.message, cell.status) {
+                (Some(message), _) => detail.push(message.clone()),
+                (None, CellStatus::Ok) => detail.extend(cell.model.clone()),
+                _ => {}
+            }
+            [
+                cell.app.clone(),
+                provider,
+                result.to_string(),
+                detail.join(" · "),
+            ]
+        })
+        .collect();
+    let widths: Vec<usize> = (0..4)
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    rows.iter()
+        .map(|row| {
+            let line: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(value, width)| format!("{value:<width$}"))
+                .collect();
+            format!("{}\n", line.join("  ").trim_end())
+        })
+        .collect()
+}
+
+/// `cc-switch provider matrix <base-url> [--json]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse_matrix_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+    let origin = endpoint_origin(&parsed.base_url).unwrap_or_default();
+
+    let state = match open_state() {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+
+    let mut cells = Vec::new();
+    for app_type in matrix_apps() {
+        let providers = match referencing_providers(&state, &app_type, &origin) {
+            Ok(providers) => providers,
+            Err(e) => {
+                eprintln!("error: {}", error_message(&e, lang));
+                return EXIT_FAILURE;
+            }
+        };
+        if providers.is_empty() {
+            cells.push(MatrixCell::missing(&app_type));
+            continue;
+        }
+        for provider in &providers {
+            eprintln!("testing {} / {} ...", app_type.as_str(), provider.name);
+            cells.push(test_provider(&app_type, provider, lang));
+        }
+    }
+
+    if cells.iter().all(|cell| cell.status == CellStatus::Missing) {
+        eprintln!(
+            "{}",
+            match lang {
+                CliLang::Zh => format!("没有供应商引用 {origin}"),
+                CliLang::En => format!("no providers reference {origin}"),
+            }
+        );
+        return EXIT_OK;
+    }
+
+    let diagnosis = diagnose(&cells);
+    if parsed.json {
+        let value = serde_json::json!({
+            "endpoint": origin,
+            "results": cells,
+            "diagnosis": diagnosis.as_ref().map(|d| render_diagnosis(d, lang)),
+        });
+        match serde_json::to_string_pretty(&value) {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return EXIT_FAILURE;
+            }
+        }
+    } else {
+        print!("{}", render_matrix(&cells));
+        if let Some(diagnosis) = &diagnosis {
+            println!("\n{}", render_diagnosis(diagnosis, lang));
+        }
+    }
+
+    if cells.iter().any(|cell| cell.status == CellStatus::Failed) {
+        EXIT_FAILURE
+    } else {
+        EXIT_OK
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(app: &str, status: CellStatus, http_status: Option<u16>) -> MatrixCell {
+        MatrixCell {
+            app: app.to_string(),
+            status,
+            provider_id: Some("relay".to_string()),
+            provider_name: Some("Relay".to_string()),
+            http_status,
+            model: None,
+            latency_ms: None,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn matches_endpoints_by_origin() {
+        assert_eq!(
+            endpoint_origin("https://Relay.Example.com/v1/").as_deref(),
+            Some("https://relay.example.com")
+        );
+        assert_eq!(
+            endpoint_origin("https://relay.example.com:443").as_deref(),
+            Some("https://relay.example.com")
+        );
+        assert_eq!(
+            endpoint_origin("http://127.0.0.1:8080/api").as_deref(),
+            Some("http://127.0.0.1:8080")
+        );
+        assert_eq!(endpoint_origin("ftp://relay.example.com"), None);
+        assert!(parse_matrix_args(&["relay.example.com".to_string()]).is_err());
+        assert!(parse_matrix_args(&[]).is_err());
+    }
+
+    #[test]
+    fn diagnoses_shared_and_per_app_key_problems() {
+        let everywhere = vec![
+            cell("claude", CellStatus::Failed, Some(401)),
+            cell("codex", CellStatus::Failed, Some(403)),
+            MatrixCell::missing(&AppType::Gemini),
+        ];
+        assert_eq!(
+            diagnose(&everywhere),
+            Some(Diagnosis::KeyRejectedEverywhere)
+        );
+
+        let partial = vec![
+            cell("claude", CellStatus::Ok, Some(200)),
+            cell("codex", CellStatus::Failed, Some(401)),
+        ];
+        assert_eq!(
+            diagnose(&partial),
+            Some(Diagnosis::KeyRejectedFor(vec!["codex".to_string()]))
+        );
+
+        let network = vec![cell("claude", CellStatus::Failed, None)];
+        assert_eq!(diagnose(&network), None);
+    }
+
+    #[test]
+    fn renders_one_row_per_app() {
+        let mut ok = cell("claude", CellStatus::Ok, Some(200));
+        ok.latency_ms = Some(812);
+        ok.model = Some("claude-sonnet-4-5".to_string());
+        let mut failed = cell("codex", CellStatus::Failed, Some(401));
+        failed.provider_name = Some("relay".to_string());
+        failed.message = Some("invalid api key".to_string());
+        let text = render_matrix(&[ok, failed, MatrixCell::missing(&AppType::Gemini)]);
+        assert_eq!(
+            text,
+            "claude  relay (Relay)  ok    200 · 812 ms · claude-sonnet-4-5\n\
+             codex   relay          FAIL  401 · invalid api key\n\
+             gemini  -              -\n"
+        );
+    }
+}
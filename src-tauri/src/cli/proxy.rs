@@ -0,0 +1,158 @@
+//! `cc-switch proxy` 子命令：代理相关的运维操作
+//!
+//! 目前只有 `bench`：在进程内启动一个隔离的代理实例（内存数据库 + 随机端口 +
+//! 本地 mock 上游）压测完整转发链路，不读写用户数据库，也不影响正在运行的代理。
+
+use tauri::async_runtime::block_on;
+
+use super::{CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::proxy::bench::{run_bench, BenchOptions, BenchReport};
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct BenchArgs {
+    concurrency: Option<usize>,
+    requests: Option<usize>,
+    upstream_delay_ms: Option<u64>,
+    json: bool,
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut parsed = BenchArgs::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut number = || {
+            let raw = inline
+                .clone()
+                .or_else(|| iter.next().cloned())
+                .ok_or_else(|| format!("{flag} requires a value"))?;
+            raw.parse::<u64>()
+                .map_err(|_| format!("invalid {flag} value: {raw}"))
+        };
+        match flag {
+            "--concurrency" | "-c" => parsed.concurrency = Some(number()? as usize),
+            "--requests" | "-n" => parsed.requests = Some(number()? as usize),
+            "--upstream-delay-ms" => parsed.upstream_delay_ms = Some(number()?),
+            "--json" => parsed.json = true,
+            other => return Err(format!("unknown argument: {other}")),
+        }
+    }
+    Ok(parsed)
+}
+
+impl BenchArgs {
+    fn options(&self) -> BenchOptions {
+        let defaults = BenchOptions::default();
+        BenchOptions {
+            concurrency: self.concurrency.unwrap_or(defaults.concurrency),
+            requests: self.requests.unwrap_or(defaults.requests),
+            upstream_delay_ms: self.upstream_delay_ms.unwrap_or(defaults.upstream_delay_ms),
+        }
+    }
+}
+
+fn render(report: &BenchReport, lang: CliLang) -> String {
+    let latency = &report.latency_ms;
+    match lang {
+        CliLang::Zh => format!(
+            "{} 个请求（并发 {}）：成功 {}，失败 {}，耗时 {} ms，吞吐 {:.1} req/s\n\
+             延迟 ms：min {:.1}  mean {:.1}  p50 {:.1}  p90 {:.1}  p99 {:.1}  max {:.1}\n",
+            report.requests,
+            report.concurrency,
+            report.succeeded,
+            report.failed,
+            report.total_ms,
+            report.throughput_rps,
+            latency.min,
+            latency.mean,
+            latency.p50,
+            latency.p90,
+            latency.p99,
+            latency.max
+        ),
+        CliLang::En => format!(
+            "{} requests (concurrency {}): {} succeeded, {} failed in {} ms, {:.1} req/s\n\
+             latency ms: min {:.1}  mean {:.1}  p50 {:.1}  p90 {:.1}  p99 {:.1}  max {:.1}\n",
+            report.requests,
+            report.concurrency,
+            report.succeeded,
+            report.failed,
+            report.total_ms,
+            report.throughput_rps,
+            latency.min,
+            latency.mean,
+            latency.p50,
+            latency.p90,
+            latency.p99,
+            latency.max
+        ),
+    }
+}
+
+/// `cc-switch proxy bench [--concurrency <n>] [--requests <n>] [--upstream-delay-ms <n>] [--json]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match args.split_first() {
+        Some((sub, rest)) if sub == "bench" => parse_bench_args(rest),
+        Some((other, _)) => Err(format!("unknown proxy subcommand: {other}")),
+        None => Err("missing proxy subcommand (bench)".to_string()),
+    };
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return EXIT_USAGE;
+        }
+    };
+    let report = match block_on(run_bench(parsed.options())) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return EXIT_FAILURE;
+        }
+    };
+
+    if parsed.json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return EXIT_FAILURE;
+            }
+        }
+    } else {
+        print!("{}", render(&report, lang));
+    }
+    if report.failed > 0 {
+        EXIT_FAILURE
+    } else {
+        EXIT_OK
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_bench_flags_and_falls_back_to_defaults() {
+        let parsed =
+            parse_bench_args(&args(&["--concurrency", "8", "-n", "50", "--json"])).unwrap();
+        assert_eq!(parsed.concurrency, Some(8));
+        assert_eq!(parsed.requests, Some(50));
+        assert!(parsed.json);
+        let options = parsed.options();
+        assert_eq!(options.concurrency, 8);
+        assert_eq!(options.requests, 50);
+        assert_eq!(options.upstream_delay_ms, 0);
+
+        let options = parse_bench_args(&[]).unwrap().options();
+        assert_eq!(options.concurrency, BenchOptions::default().concurrency);
+        assert!(parse_bench_args(&args(&["--requests", "many"])).is_err());
+        assert!(parse_bench_args(&args(&["--concurrency"])).is_err());
+        assert!(parse_bench_args(&args(&["--nope"])).is_err());
+    }
+}
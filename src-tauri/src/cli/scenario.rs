@@ -0,0 +1,117 @@
+//! `cc-switch run` 子命令：执行场景宏
+//!
+//! 宏定义见 [`crate::services::scenario`]。命令行进程执行完即退出，
+//! 启停代理、代理接管等依赖运行中 GUI 的步骤会报错并中止后续步骤。
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::error::AppError;
+use crate::services::scenario::{MacroRuntime, MacroService};
+
+#[derive(Debug, PartialEq, Eq)]
+enum RunArgs {
+    List,
+    Run(String),
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    match args {
+        [] => Ok(RunArgs::List),
+        [flag] if flag == "--list" || flag == "-l" => Ok(RunArgs::List),
+        [name] if !name.starts_with('-') => Ok(RunArgs::Run(name.clone())),
+        [other] => Err(format!("unknown option: {other}")),
+        _ => Err("expected a single macro name".to_string()),
+    }
+}
+
+fn run_list() {
+    let macros = MacroService::list();
+    if macros.is_empty() {
+        eprintln!(
+            "no macros found in {}",
+            MacroService::macros_dir().display()
+        );
+        return;
+    }
+    let width = macros
+        .iter()
+        .map(|m| m.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for scenario in macros {
+        let pad = width - scenario.name.chars().count();
+        let mut line = format!("{}{}", scenario.name, " ".repeat(pad));
+        line.push_str(&format!("  {} steps", scenario.steps.len()));
+        if let Some(hotkey) = &scenario.hotkey {
+            line.push_str(&format!("  [{hotkey}]"));
+        }
+        if let Some(description) = &scenario.description {
+            line.push_str(&format!("  {description}"));
+        }
+        println!("{line}");
+    }
+}
+
+fn run_macro(name: &str) -> Result<bool, AppError> {
+    let scenario = MacroService::load(name)?;
+    let state = open_state()?;
+    let report = MacroService::run(&state, &scenario, MacroRuntime::Cli);
+    for warning in &report.warnings {
+        eprintln!("warning: {warning}");
+    }
+    match &report.error {
+        None => {
+            println!("{name}: {}/{} steps done", report.completed, report.total);
+            Ok(true)
+        }
+        Some(error) => {
+            eprintln!(
+                "error: {name} stopped after {}/{} steps: {error}",
+                report.completed, report.total
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// `cc-switch run [<macro> | --list]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse_run_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+    match parsed {
+        RunArgs::List => {
+            run_list();
+            EXIT_OK
+        }
+        RunArgs::Run(name) => match run_macro(&name) {
+            Ok(true) => EXIT_OK,
+            Ok(false) => EXIT_FAILURE,
+            Err(e) => {
+                eprintln!("error: {}", error_message(&e, lang));
+                EXIT_FAILURE
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_list_and_macro_name() {
+        assert_eq!(parse_run_args(&[]), Ok(RunArgs::List));
+        assert_eq!(parse_run_args(&args(&["--list"])), Ok(RunArgs::List));
+        assert_eq!(
+            parse_run_args(&args(&["work"])),
+            Ok(RunArgs::Run("work".to_string()))
+        );
+        assert!(parse_run_args(&args(&["--force"])).is_err());
+        assert!(parse_run_args(&args(&["a", "b"])).is_err());
+    }
+}
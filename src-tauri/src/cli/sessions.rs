@@ -0,0 +1,256 @@
+//! `cc-switch sessions` 子命令：列出最近的 Claude Code / Codex 等会话并恢复
+//!
+//! 会话发现复用会话管理器（`session_manager`）的扫描逻辑；恢复时先切换到会话的项目目录，
+//! 因为 Claude Code 只能在会话启动时的目录下找到该会话。
+
+use std::process::Command;
+
+use super::{CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::i18n::LocaleFormat;
+use crate::session_manager::{self, SessionMeta, SessionQuery};
+
+/// 未指定 `--limit` 时列出的会话数
+const DEFAULT_LIMIT: usize = 20;
+
+#[derive(Debug, PartialEq, Eq)]
+enum SessionsCommand {
+    List { limit: usize, json: bool },
+    Resume { id: String, print: bool },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct SessionsArgs {
+    command: SessionsCommand,
+    app: Option<String>,
+    cwd: Option<String>,
+}
+
+fn parse_sessions_args(args: &[String]) -> Result<SessionsArgs, String> {
+    let mut positional = Vec::new();
+    let mut app = None;
+    let mut cwd = None;
+    let mut limit = DEFAULT_LIMIT;
+    let mut json = false;
+    let mut print = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| iter.next().cloned())
+                .ok_or_else(|| format!("{flag} requires a value"))
+        };
+        match flag {
+            "--app" | "-a" => app = Some(value()?),
+            "--cwd" => cwd = Some(value()?),
+            "--here" => {
+                let dir = std::env::current_dir().map_err(|e| e.to_string())?;
+                cwd = Some(dir.display().to_string());
+            }
+            "--limit" | "-n" => {
+                let raw = value()?;
+                limit = raw
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|limit| *limit > 0)
+                    .ok_or_else(|| format!("invalid --limit value: {raw}"))?;
+            }
+            "--json" => json = true,
+            "--print" => print = true,
+            other if other.starts_with('-') => {
+                return Err(format!("unknown argument: {other}"));
+            }
+            other => positional.push(other.to_string()),
+        }
+    }
+
+    let command = match positional.first().map(String::as_str) {
+        None | Some("list") if positional.len() <= 1 => SessionsCommand::List { limit, json },
+        Some("resume") => match positional.as_slice() {
+            [_, id] => SessionsCommand::Resume {
+                id: id.clone(),
+                print,
+            },
+            [_] => return Err("missing session id: sessions resume <id>".to_string()),
+            _ => return Err(format!("unexpected argument: {}", positional[2])),
+        },
+        Some(other) if positional.len() == 1 => {
+            return Err(format!("unknown sessions command: {other}"));
+        }
+        _ => return Err(format!("unexpected argument: {}", positional[1])),
+    };
+    Ok(SessionsArgs { command, app, cwd })
+}
+
+fn format_time(ts: Option<i64>) -> String {
+    ts.and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|time| LocaleFormat::current().datetime(&time))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn render_list(sessions: &[SessionMeta]) -> String {
+    let id_width = sessions
+        .iter()
+        .map(|s| s.session_id.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    for session in sessions {
+        let pad = id_width - session.session_id.chars().count();
+        out.push_str(&format!(
+            "{:<9}  {}{}  {}  {}\n",
+            session.provider_id,
+            session.session_id,
+            " ".repeat(pad),
+            format_time(session.last_active_at.or(session.created_at)),
+            session.title.as_deref().unwrap_or("-")
+        ));
+        if let Some(dir) = session.project_dir.as_deref() {
+            out.push_str(&format!("{:<9}  {dir}\n", ""));
+        }
+    }
+    out
+}
+
+/// 在当前终端中执行恢复命令，返回子进程退出码
+fn exec_resume(command: &str) -> i32 {
+    let status = if cfg!(windows) {
+        Command::new("cmd").args(["/C", command]).status()
+    } else {
+        Command::new("sh").args(["-c", command]).status()
+    };
+    match status {
+        Ok(status) => status.code().unwrap_or(EXIT_FAILURE),
+        Err(e) => {
+            eprintln!("error: {e}");
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// `cc-switch sessions [list] [--app <tool>] [--cwd <dir> | --here] [--limit <n>] [--json]`
+/// `cc-switch sessions resume <id> [--app <tool>] [--print]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse_sessions_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+    let query = SessionQuery {
+        provider_id: parsed.app,
+        project_dir: parsed.cwd,
+        limit: match parsed.command {
+            SessionsCommand::List { limit, .. } => Some(limit),
+            SessionsCommand::Resume { .. } => None,
+        },
+    };
+    let sessions = match session_manager::recent_sessions(&query) {
+        Ok(sessions) => sessions,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+
+    match parsed.command {
+        SessionsCommand::List { json: true, .. } => match serde_json::to_string_pretty(&sessions) {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return EXIT_FAILURE;
+            }
+        },
+        SessionsCommand::List { .. } if sessions.is_empty() => eprintln!(
+            "{}",
+            match lang {
+                CliLang::Zh => "没有找到会话",
+                CliLang::En => "no sessions found",
+            }
+        ),
+        SessionsCommand::List { .. } => print!("{}", render_list(&sessions)),
+        SessionsCommand::Resume { id, print } => {
+            let session = match session_manager::find_session(&sessions, &id) {
+                Ok(session) => session,
+                Err(message) => {
+                    eprintln!("error: {message}");
+                    return EXIT_FAILURE;
+                }
+            };
+            let Some(command) = session_manager::shell_resume_command(session) else {
+                eprintln!(
+                    "{}",
+                    match lang {
+                        CliLang::Zh => format!("{} 的会话不支持命令行恢复", session.provider_id),
+                        CliLang::En => format!(
+                            "{} sessions cannot be resumed from the command line",
+                            session.provider_id
+                        ),
+                    }
+                );
+                return EXIT_FAILURE;
+            };
+            if print {
+                println!("{command}");
+            } else {
+                eprintln!("$ {command}");
+                return exec_resume(&command);
+            }
+        }
+    }
+    EXIT_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_list_with_filters() {
+        let parsed =
+            parse_sessions_args(&args(&["--app", "codex", "--limit=5", "--cwd", "/work"])).unwrap();
+        assert_eq!(
+            parsed,
+            SessionsArgs {
+                command: SessionsCommand::List {
+                    limit: 5,
+                    json: false
+                },
+                app: Some("codex".to_string()),
+                cwd: Some("/work".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_sessions_args(&args(&["list", "--json"]))
+                .unwrap()
+                .command,
+            SessionsCommand::List {
+                limit: DEFAULT_LIMIT,
+                json: true
+            }
+        );
+    }
+
+    #[test]
+    fn parses_resume_and_rejects_bad_input() {
+        assert_eq!(
+            parse_sessions_args(&args(&["resume", "abc", "--print"]))
+                .unwrap()
+                .command,
+            SessionsCommand::Resume {
+                id: "abc".to_string(),
+                print: true
+            }
+        );
+        assert!(parse_sessions_args(&args(&["resume"])).is_err());
+        assert!(parse_sessions_args(&args(&["resume", "a", "b"])).is_err());
+        assert!(parse_sessions_args(&args(&["open"])).is_err());
+        assert!(parse_sessions_args(&args(&["--limit", "0"])).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! `cc-switch simulate` 子命令：模拟最近的流量改走另一个供应商时的花费
+
+use std::str::FromStr;
+
+use tauri::async_runtime::block_on;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::i18n::LocaleFormat;
+use crate::services::cost_simulation::{CostSimulation, CostSimulationService};
+
+/// 未指定 `--days` 时的模拟窗口
+const DEFAULT_DAYS: u32 = 7;
+
+#[derive(Debug, PartialEq, Eq)]
+struct SimulateArgs {
+    provider: String,
+    app: Option<String>,
+    days: u32,
+    json: bool,
+}
+
+fn parse_simulate_args(args: &[String]) -> Result<SimulateArgs, String> {
+    let mut provider = None;
+    let mut app = None;
+    let mut days = DEFAULT_DAYS;
+    let mut json = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| iter.next().cloned())
+                .ok_or_else(|| format!("{flag} requires a value"))
+        };
+        match flag {
+            "--provider" | "-p" => provider = Some(value()?),
+            "--app" | "-a" => app = Some(value()?),
+            "--days" => {
+                let raw = value()?;
+                days = raw
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|days| *days > 0)
+                    .ok_or_else(|| format!("invalid --days value: {raw}"))?;
+            }
+            "--json" => json = true,
+            other => return Err(format!("unknown argument: {other}")),
+        }
+    }
+    Ok(SimulateArgs {
+        provider: provider.ok_or("missing --provider <id>")?,
+        app,
+        days,
+        json,
+    })
+}
+
+fn render(result: &CostSimulation, lang: CliLang, fmt: &LocaleFormat) -> String {
+    let count = |value: u64| fmt.integer(value as i64);
+    let mut out = match lang {
+        CliLang::Zh => format!(
+            "最近 {} 天 {} 次请求改走 {}（倍率 {}）\n实际 {}  模拟 {}  差额 {}\n",
+            result.days,
+            count(result.request_count),
+            result.provider_name,
+            result.cost_multiplier,
+            fmt.usd_str(&result.actual_cost_usd),
+            fmt.usd_str(&result.simulated_cost_usd),
+            fmt.usd_str(&result.delta_usd)
+        ),
+        CliLang::En => format!(
+            "{} requests in the last {} days routed to {} (x{})\nactual {}  simulated {}  delta {}\n",
+            count(result.request_count),
+            result.days,
+            result.provider_name,
+            result.cost_multiplier,
+            fmt.usd_str(&result.actual_cost_usd),
+            fmt.usd_str(&result.simulated_cost_usd),
+            fmt.usd_str(&result.delta_usd)
+        ),
+    };
+    let width = result
+        .rows
+        .iter()
+        .map(|row| row.requested_model.chars().count())
+        .max()
+        .unwrap_or(0);
+    for row in &result.rows {
+        let pad = width - row.requested_model.chars().count();
+        out.push_str(&format!(
+            "  {}{}  -> {}  {}  {} -> {}{}\n",
+            row.requested_model,
+            " ".repeat(pad),
+            row.simulated_model,
+            count(row.request_count),
+            fmt.usd_str(&row.actual_cost_usd),
+            fmt.usd_str(&row.simulated_cost_usd),
+            if row.priced { "" } else { "  (no pricing)" }
+        ));
+    }
+    if !result.unpriced_models.is_empty() {
+        out.push_str(&match lang {
+            CliLang::Zh => format!(
+                "以下模型没有定价，按 0 计：{}\n",
+                result.unpriced_models.join(", ")
+            ),
+            CliLang::En => format!(
+                "no pricing for these models, counted as 0: {}\n",
+                result.unpriced_models.join(", ")
+            ),
+        });
+    }
+    out
+}
+
+/// `cc-switch simulate --provider <id> [--days <n>] [--app <app>] [--json]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse_simulate_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+    let app_type = match AppType::from_str(parsed.app.as_deref().unwrap_or("claude")) {
+        Ok(app_type) => app_type,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_USAGE;
+        }
+    };
+
+    let result = open_state().and_then(|state| {
+        block_on(CostSimulationService::simulate(
+            &state.db,
+            app_type.as_str(),
+            &parsed.provider,
+            parsed.days,
+        ))
+    });
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+
+    if parsed.json {
+        match serde_json::to_string_pretty(&result) {
+            Ok(text) => println!("{text}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return EXIT_FAILURE;
+            }
+        }
+    } else {
+        print!("{}", render(&result, lang, &LocaleFormat::current()));
+    }
+    EXIT_OK
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_provider_days_and_app() {
+        let parsed =
+            parse_simulate_args(&args(&["--provider", "relay", "--days=30", "-a", "codex"]))
+                .unwrap();
+        assert_eq!(
+            parsed,
+            SimulateArgs {
+                provider: "relay".to_string(),
+                app: Some("codex".to_string()),
+                days: 30,
+                json: false,
+            }
+        );
+        assert_eq!(
+            parse_simulate_args(&args(&["-p", "relay"])).unwrap().days,
+            DEFAULT_DAYS
+        );
+    }
+
+    #[test]
+    fn rejects_missing_provider_and_bad_days() {
+        assert!(parse_simulate_args(&args(&["--days", "7"])).is_err());
+        assert!(parse_simulate_args(&args(&["-p", "relay", "--days", "0"])).is_err());
+        assert!(parse_simulate_args(&args(&["-p", "relay", "--days", "week"])).is_err());
+        assert!(parse_simulate_args(&args(&["-p", "relay", "--verbose"])).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+//! `cc-switch status` 子命令：输出各应用的当前供应商，供状态栏脚本使用
+//!
+//! 默认从数据库读取一次后退出。`--watch` 持续监视 `~/.cc-switch/status.json`
+//! （需在设置中开启状态文件），每次变化输出一次；配合 `--json` 时每次输出一行
+//! 完整 JSON，便于 `jq` 等工具逐行处理。
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::services::status_file::{self, StatusSnapshot, StatusSource};
+
+/// `--watch` 的轮询间隔
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct StatusArgs {
+    app: Option<AppType>,
+    watch: bool,
+    json: bool,
+}
+
+fn parse_status_args(args: &[String]) -> Result<StatusArgs, String> {
+    let mut parsed = StatusArgs::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        match flag {
+            "--app" | "-a" => {
+                let value = inline
+                    .or_else(|| iter.next().cloned())
+                    .ok_or("--app requires a value")?;
+                parsed.app = Some(AppType::from_str(&value).map_err(|e| e.to_string())?);
+            }
+            "--watch" | "-w" => parsed.watch = true,
+            "--json" => parsed.json = true,
+            other => return Err(format!("unknown argument: {other}")),
+        }
+    }
+    Ok(parsed)
+}
+
+/// 文本输出：指定 `--app` 时只输出 `供应商 · 模型`，适合直接嵌入状态栏
+fn render(snapshot: &StatusSnapshot, app: Option<&AppType>, json: bool, watch: bool) -> String {
+    let mut snapshot = snapshot.clone();
+    if let Some(app) = app {
+        snapshot.apps.retain(|name, _| name == app.as_str());
+    }
+    if json {
+        let text = if watch {
+            serde_json::to_string(&snapshot)
+        } else {
+            serde_json::to_string_pretty(&snapshot)
+        };
+        return text.unwrap_or_default();
+    }
+
+    let format = crate::i18n::LocaleFormat::current();
+    let describe = |status: &status_file::AppStatus| {
+        let mut parts = vec![status.provider_name.clone()];
+        // 实际发往上游的模型与配置不同（模型映射）时显示 `配置 → 实际`
+        let model = match (&status.model, &status.mapped_model) {
+            (Some(model), Some(mapped)) if model != mapped => Some(format!("{model} → {mapped}")),
+            (model, mapped) => model.clone().or_else(|| mapped.clone()),
+        };
+        parts.extend(model);
+        parts.extend(
+            status
+                .today_cost_usd
+                .as_deref()
+                .map(|cost| format.usd_str(cost)),
+        );
+        parts.join(" · ")
+    };
+    if app.is_some() {
+        return snapshot
+            .apps
+            .values()
+            .next()
+            .map(describe)
+            .unwrap_or_default();
+    }
+    snapshot
+        .apps
+        .iter()
+        .map(|(name, status)| match &status.endpoint {
+            Some(endpoint) => format!("{name}: {} ({endpoint})", describe(status)),
+            None => format!("{name}: {}", describe(status)),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn watch(parsed: &StatusArgs, lang: CliLang) -> i32 {
+    let path = status_file::status_path();
+    if !crate::settings::get_settings().status_file_enabled {
+        eprintln!(
+            "{}",
+            match lang {
+                CliLang::Zh => "提示：状态文件未开启，请在 设置 → 通用 中开启后才会持续更新",
+                CliLang::En =>
+                    "hint: the status file is disabled; enable it in Settings → General to receive updates",
+            }
+        );
+    }
+    let mut last = None;
+    loop {
+        if let Ok(text) = std::fs::read_to_string(&path) {
+            if last.as_ref() != Some(&text) {
+                match serde_json::from_str::<StatusSnapshot>(&text) {
+                    Ok(snapshot) => {
+                        println!(
+                            "{}",
+                            render(&snapshot, parsed.app.as_ref(), parsed.json, true)
+                        )
+                    }
+                    Err(e) => log::debug!("[StatusFile] 状态文件解析失败: {e}"),
+                }
+                last = Some(text);
+            }
+        }
+        std::thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+/// `cc-switch status [--app <app>] [--json] [--watch]`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse_status_args(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return EXIT_USAGE;
+        }
+    };
+    if parsed.watch {
+        return watch(&parsed, lang);
+    }
+
+    let snapshot =
+        open_state().and_then(|state| status_file::snapshot(&state.db, StatusSource::Query));
+    match snapshot {
+        Ok(snapshot) => {
+            let text = render(&snapshot, parsed.app.as_ref(), parsed.json, false);
+            if !text.is_empty() {
+                println!("{text}");
+            }
+            EXIT_OK
+        }
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            EXIT_FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+    use crate::services::status_file::AppStatus;
+    use std::collections::BTreeMap;
+
+    fn sample() -> StatusSnapshot {
+        let mut apps = BTreeMap::new();
+        apps.insert(
+            "claude".to_string(),
+            AppStatus {
+                provider_id: "relay".to_string(),
+                provider_name: "Relay".to_string(),
+                model: Some("claude-sonnet-4-5".to_string()),
+                mapped_model: None,
+                endpoint: Some("https://relay.example.com".to_string()),
+                today_cost_usd: None,
+                today_requests: 0,
+            },
+        );
+        apps.insert(
+            "codex".to_string(),
+            AppStatus {
+                provider_id: "openai".to_string(),
+                provider_name: "OpenAI".to_string(),
+                model: None,
+                mapped_model: None,
+                endpoint: None,
+                today_cost_usd: None,
+                today_requests: 0,
+            },
+        );
+        StatusSnapshot {
+            version: status_file::STATUS_FILE_VERSION,
+            updated_at: 0,
+            source: StatusSource::Switch,
+            apps,
+        }
+    }
+
+    #[test]
+    fn parses_status_flags() {
+        let parsed = parse_status_args(&args(&["--app=codex", "--watch", "--json"])).unwrap();
+        assert_eq!(parsed.app, Some(AppType::Codex));
+        assert!(parsed.watch && parsed.json);
+        assert!(parse_status_args(&args(&["--app", "nope"])).is_err());
+        assert!(parse_status_args(&args(&["--bogus"])).is_err());
+    }
+
+    #[test]
+    fn renders_one_line_per_app_or_a_bare_segment() {
+        assert_eq!(
+            render(&sample(), None, false, false),
+            "claude: Relay · claude-sonnet-4-5 (https://relay.example.com)\ncodex: OpenAI"
+        );
+        assert_eq!(
+            render(&sample(), Some(&AppType::Claude), false, false),
+            "Relay · claude-sonnet-4-5"
+        );
+        let line = render(&sample(), Some(&AppType::Codex), true, true);
+        assert!(!line.contains('\n'));
+        assert!(line.contains("\"providerName\":\"OpenAI\""));
+        assert!(!line.contains("Relay"));
+
+        let mut mapped = sample();
+        if let Some(status) = mapped.apps.get_mut("claude") {
+            status.mapped_model = Some("glm-4.6".to_string());
+        }
+        assert_eq!(
+            render(&mapped, Some(&AppType::Claude), false, false),
+            "Relay · claude-sonnet-4-5 → glm-4.6"
+        );
+    }
+}
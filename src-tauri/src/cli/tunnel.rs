@@ -0,0 +1,341 @@
+//! `cc-switch tunnel` 子命令：通过 SSH 端口转发使用远程机器上的代理
+//!
+//! 默认只输出 `ssh -N -L ...` 命令，可交给 autossh、launchd 等自行托管；
+//! `--start` 由本进程启动并守着 ssh，隧道打通后才返回控制权。`--apply` 把本机
+//! 各应用的 Live 配置指向隧道的本地端口，`--restore` 重新写入当前供应商恢复直连。
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use tauri::async_runtime::block_on;
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::services::tunnel::{self, TunnelSpec, DEFAULT_REMOTE_PORT};
+use crate::services::ProviderService;
+
+/// `--start` 等待隧道打通的时长（含输入 SSH 口令的时间）
+const START_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 未指定 `--app` 时 `--apply` / `--restore` 处理的应用
+const DEFAULT_APPS: &[AppType] = &[AppType::Claude, AppType::Codex, AppType::Gemini];
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct TunnelArgs {
+    destination: Option<String>,
+    remote_port: Option<u16>,
+    local_port: Option<u16>,
+    ssh_port: Option<u16>,
+    identity: Option<String>,
+    start: bool,
+    check: bool,
+    apply: bool,
+    restore: bool,
+    apps: Vec<AppType>,
+    /// 远端开启监听鉴权时，从该环境变量读取访问令牌
+    token_env: Option<String>,
+}
+
+fn port_value(flag: &str, value: &str) -> Result<u16, String> {
+    value
+        .parse::<u16>()
+        .ok()
+        .filter(|port| *port != 0)
+        .ok_or_else(|| format!("{flag} must be a port number (1-65535)"))
+}
+
+fn parse_tunnel_args(args: &[String]) -> Result<TunnelArgs, String> {
+    let mut parsed = TunnelArgs::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| iter.next().cloned())
+                .ok_or_else(|| format!("{flag} requires a value"))
+        };
+        match flag {
+            "--remote-port" => parsed.remote_port = Some(port_value(flag, &value()?)?),
+            "--local-port" => parsed.local_port = Some(port_value(flag, &value()?)?),
+            "--ssh-port" | "-p" => parsed.ssh_port = Some(port_value(flag, &value()?)?),
+            "--identity" | "-i" => parsed.identity = Some(value()?),
+            "--token-env" => parsed.token_env = Some(value()?),
+            "--app" | "-a" => {
+                let app = AppType::from_str(&value()?).map_err(|e| e.to_string())?;
+                if !matches!(
+                    app,
+                    AppType::Claude | AppType::Codex | AppType::Gemini | AppType::GrokBuild
+                ) {
+                    return Err(format!("{} does not support the proxy", app.as_str()));
+                }
+                if !parsed.apps.contains(&app) {
+                    parsed.apps.push(app);
+                }
+            }
+            "--start" => parsed.start = true,
+            "--check" => parsed.check = true,
+            "--apply" => parsed.apply = true,
+            "--restore" => parsed.restore = true,
+            other if other.starts_with('-') => return Err(format!("unknown argument: {other}")),
+            other => {
+                if parsed.destination.replace(other.to_string()).is_some() {
+                    return Err(format!("unexpected argument: {other}"));
+                }
+            }
+        }
+    }
+
+    if parsed.restore {
+        if parsed.start || parsed.check || parsed.apply || parsed.destination.is_some() {
+            return Err("--restore only accepts --app".to_string());
+        }
+    } else if parsed.destination.is_none() && (parsed.start || !(parsed.check || parsed.apply)) {
+        // `--check` / `--apply` 只需要本地端口；其余情况需要 SSH 目标
+        return Err("missing SSH destination (user@host)".to_string());
+    }
+    Ok(parsed)
+}
+
+impl TunnelArgs {
+    fn spec(&self) -> Result<Option<TunnelSpec>, String> {
+        let Some(destination) = &self.destination else {
+            return Ok(None);
+        };
+        let mut spec =
+            TunnelSpec::new(destination, self.remote_port.unwrap_or(DEFAULT_REMOTE_PORT))
+                .map_err(|e| e.to_string())?;
+        spec.local_port = self.local_port.unwrap_or(spec.remote_port);
+        spec.ssh_port = self.ssh_port;
+        spec.identity = self.identity.clone();
+        Ok(Some(spec))
+    }
+
+    fn local_port(&self) -> u16 {
+        self.local_port
+            .or(self.remote_port)
+            .unwrap_or(DEFAULT_REMOTE_PORT)
+    }
+
+    fn token(&self) -> Result<Option<String>, String> {
+        let Some(var) = &self.token_env else {
+            return Ok(None);
+        };
+        std::env::var(var)
+            .ok()
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty())
+            .map(Some)
+            .ok_or_else(|| format!("environment variable {var} is not set or empty"))
+    }
+}
+
+/// `cc-switch tunnel <user@host> [--remote-port <n>] [--local-port <n>] [-p <ssh-port>]
+/// [-i <identity>] [--start] [--check] [--apply] [--app <app>]... [--token-env <VAR>]`
+/// 与 `cc-switch tunnel --restore [--app <app>]...`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match parse_tunnel_args(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+    let (spec, token) = match parsed.spec().and_then(|spec| Ok((spec, parsed.token()?))) {
+        Ok(values) => values,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+
+    if parsed.restore {
+        return restore(&parsed.apps, lang);
+    }
+
+    let local_port = parsed.local_port();
+    if !(parsed.start || parsed.check || parsed.apply) {
+        if let Some(spec) = &spec {
+            println!("{}", spec.command_line());
+        }
+        return EXIT_OK;
+    }
+
+    let mut child = None;
+    if parsed.start {
+        let spec = spec.as_ref().expect("validated destination");
+        eprintln!("{}", spec.command_line());
+        let mut process = match tunnel::spawn(spec) {
+            Ok(process) => process,
+            Err(e) => {
+                eprintln!("error: {}", error_message(&e, lang));
+                return EXIT_FAILURE;
+            }
+        };
+        if let Err(e) = block_on(tunnel::wait_until_healthy(
+            &mut process,
+            local_port,
+            START_TIMEOUT,
+        )) {
+            let _ = process.kill();
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+        child = Some(process);
+    } else if let Err(e) = block_on(tunnel::check_health(local_port)) {
+        eprintln!("error: {}", error_message(&e, lang));
+        return EXIT_FAILURE;
+    }
+    eprintln!(
+        "{}",
+        match lang {
+            CliLang::Zh => format!("隧道可用: http://127.0.0.1:{local_port}"),
+            CliLang::En => format!("tunnel is up: http://127.0.0.1:{local_port}"),
+        }
+    );
+
+    if parsed.apply {
+        let origin = format!("http://127.0.0.1:{local_port}");
+        if let Err(code) = apply(&parsed.apps, &origin, token.as_deref(), lang) {
+            if let Some(mut process) = child {
+                let _ = process.kill();
+            }
+            return code;
+        }
+    }
+
+    let Some(mut process) = child else {
+        return EXIT_OK;
+    };
+    eprintln!(
+        "{}",
+        match lang {
+            CliLang::Zh => "按 Ctrl+C 关闭隧道",
+            CliLang::En => "press Ctrl+C to close the tunnel",
+        }
+    );
+    let status = process.wait();
+    if parsed.apply {
+        eprintln!(
+            "{}",
+            match lang {
+                CliLang::Zh => "隧道已关闭；运行 `cc-switch tunnel --restore` 恢复直连",
+                CliLang::En =>
+                    "tunnel closed; run `cc-switch tunnel --restore` to point apps back at their providers",
+            }
+        );
+    }
+    match status {
+        Ok(status) if status.success() => EXIT_OK,
+        _ => EXIT_FAILURE,
+    }
+}
+
+/// 未指定 `--app` 时跳过没有 Live 配置或没有当前供应商的应用
+fn apply(apps: &[AppType], origin: &str, token: Option<&str>, lang: CliLang) -> Result<(), i32> {
+    let state = open_state().map_err(|e| {
+        eprintln!("error: {}", error_message(&e, lang));
+        EXIT_FAILURE
+    })?;
+    let explicit = !apps.is_empty();
+    let apps = if explicit { apps } else { DEFAULT_APPS };
+    for app in apps {
+        match state.proxy_service.point_live_config_at(app, origin, token) {
+            Ok(()) => eprintln!(
+                "{}",
+                match lang {
+                    CliLang::Zh => format!("{} 已指向 {origin}", app.as_str()),
+                    CliLang::En => format!("{} now points at {origin}", app.as_str()),
+                }
+            ),
+            Err(e) if explicit => {
+                eprintln!("error: {}: {e}", app.as_str());
+                return Err(EXIT_FAILURE);
+            }
+            Err(e) => eprintln!("skip {}: {e}", app.as_str()),
+        }
+    }
+    Ok(())
+}
+
+fn restore(apps: &[AppType], lang: CliLang) -> i32 {
+    let state = match open_state() {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            return EXIT_FAILURE;
+        }
+    };
+    let apps = if apps.is_empty() { DEFAULT_APPS } else { apps };
+    let mut code = EXIT_OK;
+    for app in apps {
+        match ProviderService::sync_current_provider_for_app(&state, app.clone()) {
+            Ok(()) => eprintln!(
+                "{}",
+                match lang {
+                    CliLang::Zh => format!("{} 已恢复为当前供应商", app.as_str()),
+                    CliLang::En => format!("{} restored to its current provider", app.as_str()),
+                }
+            ),
+            Err(e) => {
+                eprintln!("error: {}: {}", app.as_str(), error_message(&e, lang));
+                code = EXIT_FAILURE;
+            }
+        }
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_destination_and_ports() {
+        let parsed = parse_tunnel_args(&args(&[
+            "dev@box",
+            "--remote-port=15800",
+            "--local-port",
+            "25800",
+            "-p",
+            "2222",
+            "--apply",
+            "--app",
+            "claude",
+        ]))
+        .unwrap();
+        let spec = parsed.spec().unwrap().unwrap();
+        assert_eq!(spec.destination, "dev@box");
+        assert_eq!(spec.remote_port, 15800);
+        assert_eq!(spec.local_port, 25800);
+        assert_eq!(spec.ssh_port, Some(2222));
+        assert_eq!(parsed.apps, vec![AppType::Claude]);
+        assert!(parsed.apply);
+
+        // 未指定本地端口时与远端一致
+        let parsed = parse_tunnel_args(&args(&["dev@box", "--remote-port", "16000"])).unwrap();
+        assert_eq!(parsed.local_port(), 16000);
+    }
+
+    #[test]
+    fn check_and_apply_work_without_destination() {
+        let parsed = parse_tunnel_args(&args(&["--check", "--local-port", "25721"])).unwrap();
+        assert_eq!(parsed.spec().unwrap(), None);
+        assert_eq!(parsed.local_port(), 25721);
+        assert!(parse_tunnel_args(&args(&["--restore", "--app", "codex"])).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_combinations() {
+        assert!(parse_tunnel_args(&args(&[])).is_err());
+        assert!(parse_tunnel_args(&args(&["--start"])).is_err());
+        assert!(parse_tunnel_args(&args(&["a@b", "c@d"])).is_err());
+        assert!(parse_tunnel_args(&args(&["a@b", "--local-port", "0"])).is_err());
+        assert!(parse_tunnel_args(&args(&["--restore", "--apply"])).is_err());
+        assert!(parse_tunnel_args(&args(&["a@b", "--app", "opencode"])).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+//! `cc-switch usage` 子命令
+//!
+//! - `export`：导出用量明细供财务报表使用。日期按本地时区解释，`--from` 取当天
+//!   00:00:00，`--to` 取当天 23:59:59（含当天）；`--localized` 让 CSV 的时间、Token 数与
+//!   金额按界面语言 / 系统区域格式输出；
+//! - `curl <request-id>`：把一条代理请求日志还原为可直接运行的 curl 命令。
+
+use std::str::FromStr;
+
+use chrono::{Local, NaiveDate, NaiveTime, TimeZone};
+
+use super::{error_message, open_state, CliLang, EXIT_FAILURE, EXIT_OK, EXIT_USAGE};
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::i18n::LocaleFormat;
+use crate::proxy::usage::{UsageExportFormat, UsageLogger};
+use crate::services::curl_export::CurlExportService;
+
+#[derive(Debug, PartialEq, Eq)]
+struct ExportArgs {
+    format: UsageExportFormat,
+    from: Option<i64>,
+    to: Option<i64>,
+    app: Option<String>,
+    output: Option<String>,
+    localized: bool,
+}
+
+/// 本地日期 → Unix 秒；`end_of_day` 为真时取当天最后一秒
+fn local_day_bound(value: &str, end_of_day: bool) -> Result<i64, String> {
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date: {value} (expected YYYY-MM-DD)"))?;
+    let time = if end_of_day {
+        NaiveTime::from_hms_opt(23, 59, 59)
+    } else {
+        NaiveTime::from_hms_opt(0, 0, 0)
+    }
+    .expect("valid time");
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|dt| dt.timestamp())
+        .ok_or_else(|| format!("invalid local date: {value}"))
+}
+
+fn parse_export_args(args: &[String]) -> Result<ExportArgs, String> {
+    let mut format = UsageExportFormat::Csv;
+    let mut from = None;
+    let mut to = None;
+    let mut app = None;
+    let mut output = None;
+    let mut localized = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| iter.next().cloned())
+                .ok_or_else(|| format!("{flag} requires a value"))
+        };
+        match flag {
+            "--format" => {
+                format = UsageExportFormat::from_str(&value()?).map_err(|e| e.to_string())?
+            }
+            "--from" => from = Some(local_day_bound(&value()?, false)?),
+            "--to" => to = Some(local_day_bound(&value()?, true)?),
+            "--app" | "-a" => app = Some(value()?),
+            "--output" | "-o" => output = Some(value()?),
+            "--localized" => localized = true,
+            other => return Err(format!("unknown option: {other}")),
+        }
+    }
+    if localized && format != UsageExportFormat::Csv {
+        return Err("--localized only applies to CSV exports".to_string());
+    }
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err("--from must not be later than --to".to_string());
+        }
+    }
+    Ok(ExportArgs {
+        format,
+        from,
+        to,
+        app,
+        output,
+        localized,
+    })
+}
+
+fn run_export(args: &ExportArgs) -> Result<(), AppError> {
+    let app_type = args.app.as_deref().map(AppType::from_str).transpose()?;
+    let state = open_state()?;
+    let locale = args.localized.then(LocaleFormat::current);
+    let content = UsageLogger::new(&state.db).export_with_locale(
+        args.format,
+        args.from,
+        args.to,
+        app_type.as_ref().map(AppType::as_str),
+        locale.as_ref(),
+    )?;
+    match &args.output {
+        Some(path) => {
+            std::fs::write(path, content).map_err(|e| AppError::io(path, e))?;
+            eprintln!("exported to {path}");
+        }
+        None => print!("{content}"),
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct CurlArgs {
+    request_id: String,
+    mask_key: bool,
+}
+
+fn parse_curl_args(args: &[String]) -> Result<CurlArgs, String> {
+    let mut request_id = None;
+    let mut mask_key = false;
+    for arg in args {
+        match arg.as_str() {
+            "--mask-key" => mask_key = true,
+            other if other.starts_with('-') => return Err(format!("unknown option: {other}")),
+            other if request_id.is_none() => request_id = Some(other.to_string()),
+            _ => return Err("usage: cc-switch usage curl <request-id> [--mask-key]".to_string()),
+        }
+    }
+    Ok(CurlArgs {
+        request_id: request_id.ok_or("usage: cc-switch usage curl <request-id> [--mask-key]")?,
+        mask_key,
+    })
+}
+
+fn run_curl(args: &CurlArgs) -> Result<(), AppError> {
+    let state = open_state()?;
+    println!(
+        "{}",
+        CurlExportService::for_request_log(&state.db, &args.request_id, args.mask_key)?
+    );
+    Ok(())
+}
+
+enum UsageAction {
+    Export(ExportArgs),
+    Curl(CurlArgs),
+}
+
+/// `cc-switch usage <export|curl> ...`
+pub(super) fn run(args: &[String], lang: CliLang) -> i32 {
+    let parsed = match args.split_first() {
+        Some((command, rest)) if command == "export" => {
+            parse_export_args(rest).map(UsageAction::Export)
+        }
+        Some((command, rest)) if command == "curl" => parse_curl_args(rest).map(UsageAction::Curl),
+        Some((command, _)) => Err(format!("unknown usage subcommand: {command}")),
+        None => Err("missing subcommand (export | curl <request-id>)".to_string()),
+    };
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {message}");
+            return EXIT_USAGE;
+        }
+    };
+    let result = match &parsed {
+        UsageAction::Export(args) => run_export(args),
+        UsageAction::Curl(args) => run_curl(args),
+    };
+    match result {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("error: {}", error_message(&e, lang));
+            EXIT_FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args;
+
+    #[test]
+    fn parses_export_options_in_both_forms() {
+        let parsed = parse_export_args(&args(&[
+            "--format=json",
+            "--from",
+            "2026-01-01",
+            "--to=2026-01-31",
+            "-o",
+            "out.json",
+        ]))
+        .unwrap();
+        assert_eq!(parsed.format, UsageExportFormat::Json);
+        assert_eq!(parsed.output.as_deref(), Some("out.json"));
+        let (from, to) = (parsed.from.unwrap(), parsed.to.unwrap());
+        assert_eq!(to - from, 31 * 86_400 - 1);
+        assert!(!parsed.localized);
+        assert!(
+            parse_export_args(&args(&["--localized"]))
+                .unwrap()
+                .localized
+        );
+    }
+
+    #[test]
+    fn rejects_bad_dates_and_inverted_ranges() {
+        assert!(parse_export_args(&args(&["--from", "2026/01/01"])).is_err());
+        assert!(parse_export_args(&args(&["--from", "2026-02-01", "--to", "2026-01-01"])).is_err());
+        assert!(parse_export_args(&args(&["--format", "xml"])).is_err());
+        assert!(parse_export_args(&args(&["--to"])).is_err());
+        assert!(parse_export_args(&args(&["--format", "json", "--localized"])).is_err());
+    }
+
+    #[test]
+    fn parses_curl_request_id_and_mask_flag() {
+        let parsed = parse_curl_args(&args(&["--mask-key", "req-1"])).unwrap();
+        assert_eq!(
+            parsed,
+            CurlArgs {
+                request_id: "req-1".to_string(),
+                mask_key: true,
+            }
+        );
+        assert!(parse_curl_args(&args(&[])).is_err());
+        assert!(parse_curl_args(&args(&["req-1", "req-2"])).is_err());
+        assert!(parse_curl_args(&args(&["req-1", "--json"])).is_err());
+    }
+}
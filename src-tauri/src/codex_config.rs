@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+use crate::codex_config_model::CodexConfig;
 use crate::config::{
     atomic_write, delete_file, get_home_dir, read_json_file, sanitize_provider_name,
     write_json_file, write_text_file,
@@ -20,7 +21,6 @@ pub const CC_SWITCH_CODEX_MODEL_PROVIDER_ID: &str = "custom";
 /// cleaned up without mistaking a user's own local provider for takeover.
 pub const CC_SWITCH_CODEX_OFFICIAL_PROXY_PROVIDER_ID: &str = "cc-switch-official";
 pub const CC_SWITCH_CODEX_MODEL_CATALOG_FILENAME: &str = "cc-switch-model-catalog.json";
-const CODEX_PROXY_AUTH_PLACEHOLDER: &str = "PROXY_MANAGED";
 
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
@@ -77,11 +77,12 @@ const CODEX_WEB_SEARCH_REJECT_MODEL_PREFIXES: &[&str] =
 
 /// Top-level `model` id from a Codex `config.toml`.
 fn codex_top_level_model(config_text: &str) -> Option<String> {
-    let doc = config_text.parse::<toml::Value>().ok()?;
-    doc.get("model")
-        .and_then(|value| value.as_str())
-        .map(|value| value.trim().to_string())
+    CodexConfig::parse(config_text)
+        .ok()?
+        .model()
+        .map(str::trim)
         .filter(|value| !value.is_empty())
+        .map(str::to_string)
 }
 
 /// Whether a native `/responses` provider's gateway is known to reject the Codex
@@ -352,21 +353,9 @@ pub fn extract_codex_api_key(auth: Option<&Value>, config_text: Option<&str>) ->
 /// (`getRecoverableBaseUrlAssignments`) excludes those too, and a leftover
 /// section unrelated to the active provider must not leak into `{{baseUrl}}`.
 pub fn extract_codex_base_url(config_text: &str) -> Option<String> {
-    let doc = config_text.parse::<toml::Value>().ok()?;
-
-    if let Some(active_provider) = doc.get("model_provider").and_then(|v| v.as_str()) {
-        if let Some(base_url) = doc
-            .get("model_providers")
-            .and_then(|providers| providers.get(active_provider))
-            .and_then(|provider| provider.get("base_url"))
-            .and_then(|v| v.as_str())
-        {
-            return Some(base_url.to_string());
-        }
-    }
-
-    doc.get("base_url")
-        .and_then(|v| v.as_str())
+    CodexConfig::parse(config_text)
+        .ok()?
+        .base_url()
         .map(ToString::to_string)
 }
 
@@ -1011,30 +1000,26 @@ fn set_codex_model_catalog_json_field(
     config_text: &str,
     catalog_path: Option<&Path>,
 ) -> Result<String, AppError> {
-    let mut doc = config_text
-        .parse::<DocumentMut>()
+    let mut config = CodexConfig::try_parse(config_text)
         .map_err(|e| AppError::Message(format!("Invalid Codex config.toml: {e}")))?;
 
     match catalog_path {
-        Some(_) => {
-            doc["model_catalog_json"] = toml_edit::value(CC_SWITCH_CODEX_MODEL_CATALOG_FILENAME);
-        }
+        Some(_) => config.set_model_catalog_json(Some(CC_SWITCH_CODEX_MODEL_CATALOG_FILENAME)),
         None => {
-            let should_remove = doc
-                .get("model_catalog_json")
-                .and_then(|item| item.as_str())
+            let should_remove = config
+                .model_catalog_json()
                 .map(|path| {
                     Path::new(path).file_name().and_then(|name| name.to_str())
                         == Some(CC_SWITCH_CODEX_MODEL_CATALOG_FILENAME)
                 })
                 .unwrap_or(false);
             if should_remove {
-                doc.as_table_mut().remove("model_catalog_json");
+                config.set_model_catalog_json(None);
             }
         }
     }
 
-    Ok(doc.to_string())
+    Ok(config.to_string())
 }
 
 /// Pure toggle for the top-level `web_search` field that turns Codex's built-in
@@ -1156,10 +1141,9 @@ pub(crate) fn resolve_cc_switch_catalog_path(
     if config_text.trim().is_empty() {
         return None;
     }
-    let doc = config_text.parse::<DocumentMut>().ok()?;
-    let catalog_path_str = doc
-        .get("model_catalog_json")
-        .and_then(|item| item.as_str())
+    let config = CodexConfig::parse(config_text).ok()?;
+    let catalog_path_str = config
+        .model_catalog_json()
         .map(str::trim)
         .filter(|s| !s.is_empty())?;
 
@@ -1476,7 +1460,7 @@ fn remove_codex_proxy_placeholders_from_providers(providers: &mut toml_edit::Tab
             let should_remove = table
                 .get("experimental_bearer_token")
                 .and_then(|item| item.as_str())
-                == Some(CODEX_PROXY_AUTH_PLACEHOLDER);
+                .is_some_and(crate::proxy::auth::is_proxy_client_token);
             if should_remove {
                 table.remove("experimental_bearer_token");
             }
@@ -1484,7 +1468,7 @@ fn remove_codex_proxy_placeholders_from_providers(providers: &mut toml_edit::Tab
             let should_remove = table
                 .get("experimental_bearer_token")
                 .and_then(|value| value.as_str())
-                == Some(CODEX_PROXY_AUTH_PLACEHOLDER);
+                .is_some_and(crate::proxy::auth::is_proxy_client_token);
             if should_remove {
                 table.remove("experimental_bearer_token");
             }
@@ -1893,60 +1877,19 @@ pub fn restore_codex_settings_for_backfill(
 ///
 /// Empty value removes the field.
 pub fn update_codex_toml_field(toml_str: &str, field: &str, value: &str) -> Result<String, String> {
-    let mut doc = toml_str
-        .parse::<DocumentMut>()
-        .map_err(|e| format!("TOML parse error: {e}"))?;
-
-    let trimmed = value.trim();
+    let mut config =
+        CodexConfig::try_parse(toml_str).map_err(|e| format!("TOML parse error: {e}"))?;
+    let value = Some(value);
 
     match field {
-        "base_url" | "wire_api" => {
-            let model_provider = doc
-                .get("model_provider")
-                .and_then(|item| item.as_str())
-                .map(str::to_string);
-
-            if let Some(provider_key) = model_provider {
-                // Ensure [model_providers] table exists
-                if doc.get("model_providers").is_none() {
-                    doc["model_providers"] = toml_edit::table();
-                }
-
-                if let Some(model_providers) = doc["model_providers"].as_table_mut() {
-                    // Ensure [model_providers.<provider_key>] table exists
-                    if !model_providers.contains_key(&provider_key) {
-                        model_providers[&provider_key] = toml_edit::table();
-                    }
-
-                    if let Some(provider_table) = model_providers[&provider_key].as_table_mut() {
-                        if trimmed.is_empty() {
-                            provider_table.remove(field);
-                        } else {
-                            provider_table[field] = toml_edit::value(trimmed);
-                        }
-                        return Ok(doc.to_string());
-                    }
-                }
-            }
-
-            // Fallback: no model_provider or structure mismatch → top-level field
-            if trimmed.is_empty() {
-                doc.as_table_mut().remove(field);
-            } else {
-                doc[field] = toml_edit::value(trimmed);
-            }
-        }
-        "model" | "model_catalog_json" => {
-            if trimmed.is_empty() {
-                doc.as_table_mut().remove(field);
-            } else {
-                doc[field] = toml_edit::value(trimmed);
-            }
-        }
+        "base_url" => config.set_base_url(value),
+        "wire_api" => config.set_wire_api(value),
+        "model" => config.set_model(value),
+        "model_catalog_json" => config.set_model_catalog_json(value),
         _ => return Err(format!("unsupported field: {field}")),
     }
 
-    Ok(doc.to_string())
+    Ok(config.to_string())
 }
 
 /// Remove `base_url` from the active model_provider section only if it matches `predicate`.
@@ -0,0 +1,423 @@
+//! Codex `config.toml` 结构化编辑模型
+//!
+//! Codex 供应商的配置以 TOML 字符串存储。直接做字符串/局部表操作很容易在
+//! 边角情况下出错（缺表、字段落在顶层还是 `[model_providers.<id>]` 等）。
+//! `CodexConfig` 在 `toml_edit::DocumentMut` 之上提供类型化的读写接口，
+//! 序列化时保留注释、键顺序与格式。通用配置片段的合并 / 剥离、代理接管时的
+//! MCP 表保留等整文档操作也在这里完成，调用方不直接操作 TOML 文档。
+
+use toml_edit::{DocumentMut, Item, Table, TableLike};
+
+use crate::error::AppError;
+
+/// Codex `config.toml` 文档
+#[derive(Debug, Clone, Default)]
+pub struct CodexConfig {
+    doc: DocumentMut,
+}
+
+fn non_empty(value: &str) -> Option<&str> {
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then_some(trimmed)
+}
+
+fn table_str<'a>(table: &'a Table, key: &str) -> Option<&'a str> {
+    table.get(key).and_then(Item::as_str)
+}
+
+/// 写入或删除字符串字段（空值视为删除）
+fn set_table_str(table: &mut Table, key: &str, value: Option<&str>) {
+    match value.and_then(non_empty) {
+        Some(v) => table[key] = toml_edit::value(v),
+        None => {
+            table.remove(key);
+        }
+    }
+}
+
+fn toml_value_is_subset(target: &toml_edit::Value, source: &toml_edit::Value) -> bool {
+    match (target, source) {
+        (toml_edit::Value::String(target), toml_edit::Value::String(source)) => {
+            target.value() == source.value()
+        }
+        (toml_edit::Value::Integer(target), toml_edit::Value::Integer(source)) => {
+            target.value() == source.value()
+        }
+        (toml_edit::Value::Float(target), toml_edit::Value::Float(source)) => {
+            target.value() == source.value()
+        }
+        (toml_edit::Value::Boolean(target), toml_edit::Value::Boolean(source)) => {
+            target.value() == source.value()
+        }
+        (toml_edit::Value::Datetime(target), toml_edit::Value::Datetime(source)) => {
+            target.value() == source.value()
+        }
+        (toml_edit::Value::Array(target), toml_edit::Value::Array(source)) => {
+            toml_array_contains_subset(target, source)
+        }
+        (toml_edit::Value::InlineTable(target), toml_edit::Value::InlineTable(source)) => {
+            source.iter().all(|(key, source_item)| {
+                target
+                    .get(key)
+                    .is_some_and(|target_item| toml_value_is_subset(target_item, source_item))
+            })
+        }
+        _ => false,
+    }
+}
+
+fn toml_array_contains_subset(target: &toml_edit::Array, source: &toml_edit::Array) -> bool {
+    let mut matched = vec![false; target.len()];
+    let target_items: Vec<&toml_edit::Value> = target.iter().collect();
+
+    source.iter().all(|source_item| {
+        if let Some((index, _)) = target_items
+            .iter()
+            .enumerate()
+            .find(|(index, target_item)| {
+                !matched[*index] && toml_value_is_subset(target_item, source_item)
+            })
+        {
+            matched[index] = true;
+            true
+        } else {
+            false
+        }
+    })
+}
+
+fn toml_remove_array_items(target: &mut toml_edit::Array, source: &toml_edit::Array) {
+    for source_item in source.iter() {
+        let index = {
+            let target_items: Vec<&toml_edit::Value> = target.iter().collect();
+            target_items
+                .iter()
+                .enumerate()
+                .find(|(_, target_item)| toml_value_is_subset(target_item, source_item))
+                .map(|(index, _)| index)
+        };
+
+        if let Some(index) = index {
+            target.remove(index);
+        }
+    }
+}
+
+fn toml_item_is_subset(target: &Item, source: &Item) -> bool {
+    if let Some(source_table) = source.as_table_like() {
+        let Some(target_table) = target.as_table_like() else {
+            return false;
+        };
+        return source_table.iter().all(|(key, source_item)| {
+            target_table
+                .get(key)
+                .is_some_and(|target_item| toml_item_is_subset(target_item, source_item))
+        });
+    }
+
+    match (target.as_value(), source.as_value()) {
+        (Some(target_value), Some(source_value)) => {
+            toml_value_is_subset(target_value, source_value)
+        }
+        _ => false,
+    }
+}
+
+fn merge_toml_item(target: &mut Item, source: &Item) {
+    if let Some(source_table) = source.as_table_like() {
+        if let Some(target_table) = target.as_table_like_mut() {
+            merge_toml_table_like(target_table, source_table);
+            return;
+        }
+    }
+
+    *target = source.clone();
+}
+
+fn merge_toml_table_like(target: &mut dyn TableLike, source: &dyn TableLike) {
+    for (key, source_item) in source.iter() {
+        match target.get_mut(key) {
+            Some(target_item) => merge_toml_item(target_item, source_item),
+            None => {
+                target.insert(key, source_item.clone());
+            }
+        }
+    }
+}
+
+fn remove_toml_item(target: &mut Item, source: &Item) {
+    if let Some(source_table) = source.as_table_like() {
+        if let Some(target_table) = target.as_table_like_mut() {
+            remove_toml_table_like(target_table, source_table);
+            if target_table.is_empty() {
+                *target = Item::None;
+            }
+            return;
+        }
+    }
+
+    if let Some(source_value) = source.as_value() {
+        let mut remove_item = false;
+
+        if let Some(target_value) = target.as_value_mut() {
+            match (target_value, source_value) {
+                (toml_edit::Value::Array(target_arr), toml_edit::Value::Array(source_arr)) => {
+                    toml_remove_array_items(target_arr, source_arr);
+                    remove_item = target_arr.is_empty();
+                }
+                (target_value, source_value)
+                    if toml_value_is_subset(target_value, source_value) =>
+                {
+                    remove_item = true;
+                }
+                _ => {}
+            }
+        }
+
+        if remove_item {
+            *target = Item::None;
+        }
+    }
+}
+
+fn remove_toml_table_like(target: &mut dyn TableLike, source: &dyn TableLike) {
+    let keys: Vec<String> = source.iter().map(|(key, _)| key.to_string()).collect();
+
+    for key in keys {
+        let mut remove_key = false;
+        if let (Some(target_item), Some(source_item)) = (target.get_mut(&key), source.get(&key)) {
+            remove_toml_item(target_item, source_item);
+            remove_key = target_item.is_none()
+                || target_item
+                    .as_table_like()
+                    .is_some_and(|table_like| table_like.is_empty());
+        }
+
+        if remove_key {
+            target.remove(&key);
+        }
+    }
+}
+
+impl CodexConfig {
+    /// 解析 TOML 文本（空文本视为空文档）
+    pub fn parse(text: &str) -> Result<Self, AppError> {
+        Self::try_parse(text)
+            .map_err(|e| AppError::Config(format!("Codex config.toml 解析失败: {e}")))
+    }
+
+    /// 解析 TOML 文本并返回原始语法错误，由调用方组织错误信息
+    pub fn try_parse(text: &str) -> Result<Self, toml_edit::TomlError> {
+        text.parse::<DocumentMut>().map(|doc| Self { doc })
+    }
+
+    pub fn model(&self) -> Option<&str> {
+        table_str(self.doc.as_table(), "model")
+    }
+
+    pub fn set_model(&mut self, model: Option<&str>) {
+        set_table_str(self.doc.as_table_mut(), "model", model);
+    }
+
+    fn model_provider_id(&self) -> Option<&str> {
+        table_str(self.doc.as_table(), "model_provider").and_then(non_empty)
+    }
+
+    pub fn model_catalog_json(&self) -> Option<&str> {
+        table_str(self.doc.as_table(), "model_catalog_json")
+    }
+
+    pub fn set_model_catalog_json(&mut self, path: Option<&str>) {
+        set_table_str(self.doc.as_table_mut(), "model_catalog_json", path);
+    }
+
+    fn provider_table(&self, id: &str) -> Option<&Table> {
+        self.doc
+            .get("model_providers")
+            .and_then(Item::as_table)
+            .and_then(|providers| providers.get(id))
+            .and_then(Item::as_table)
+    }
+
+    /// 获取（缺失时创建）`[model_providers.<id>]` 表
+    ///
+    /// 已存在但不是标准表（如 inline table）时返回 `None`，不覆盖用户写法。
+    fn provider_table_mut(&mut self, id: &str) -> Option<&mut Table> {
+        if self.doc.get("model_providers").is_none() {
+            let mut providers = Table::new();
+            providers.set_implicit(true);
+            self.doc["model_providers"] = Item::Table(providers);
+        }
+        let providers = self.doc["model_providers"].as_table_mut()?;
+        if !providers.contains_key(id) {
+            providers[id] = toml_edit::table();
+        }
+        providers[id].as_table_mut()
+    }
+
+    /// 当前生效的 base_url：优先活动供应商表，其次顶层字段。
+    ///
+    /// 不读取非活动的 `[model_providers.*]`，避免残留表泄漏到路由。
+    pub fn base_url(&self) -> Option<&str> {
+        self.active_provider_field("base_url")
+            .or_else(|| table_str(self.doc.as_table(), "base_url"))
+    }
+
+    fn active_provider_field(&self, key: &str) -> Option<&str> {
+        let id = self.model_provider_id()?;
+        self.provider_table(id)
+            .and_then(|table| table_str(table, key))
+    }
+
+    /// 写 base_url：有 `model_provider` 时写入对应供应商表，否则写顶层
+    pub fn set_base_url(&mut self, base_url: Option<&str>) {
+        self.set_routing_field("base_url", base_url);
+    }
+
+    pub fn set_wire_api(&mut self, wire_api: Option<&str>) {
+        self.set_routing_field("wire_api", wire_api);
+    }
+
+    fn set_routing_field(&mut self, key: &str, value: Option<&str>) {
+        if let Some(id) = self.model_provider_id().map(str::to_string) {
+            if let Some(table) = self.provider_table_mut(&id) {
+                set_table_str(table, key, value);
+                return;
+            }
+        }
+        set_table_str(self.doc.as_table_mut(), key, value);
+    }
+
+    /// 深度合并通用配置片段：表逐键合并，其余值以片段为准
+    pub fn merge_snippet(&mut self, snippet: &CodexConfig) {
+        merge_toml_table_like(self.doc.as_table_mut(), snippet.doc.as_table());
+    }
+
+    /// 剥离通用配置片段：删除与片段相同的值和数组元素，以及因此变空的表
+    pub fn remove_snippet(&mut self, snippet: &CodexConfig) {
+        remove_toml_table_like(self.doc.as_table_mut(), snippet.doc.as_table());
+    }
+
+    /// 片段中的每个值都已包含在本文档中（数组按元素包含判断）
+    pub fn contains_snippet(&self, snippet: &CodexConfig) -> bool {
+        toml_item_is_subset(self.doc.as_item(), snippet.doc.as_item())
+    }
+
+    /// 把 `other` 中本文档缺少的 `[mcp_servers.*]` 条目补进来，已有条目保持不变
+    pub fn merge_missing_mcp_servers(&mut self, other: &CodexConfig) {
+        let Some(other_servers) = other.doc.get("mcp_servers") else {
+            return;
+        };
+        if self.doc.get("mcp_servers").is_none() {
+            self.doc["mcp_servers"] = other_servers.clone();
+            return;
+        }
+        let (Some(servers), Some(other_servers)) = (
+            self.doc["mcp_servers"].as_table_like_mut(),
+            other_servers.as_table_like(),
+        ) else {
+            log::warn!("config.toml contains a non-table mcp_servers section; skipping MCP merge");
+            return;
+        };
+        for (server_id, server) in other_servers.iter() {
+            if servers.get(server_id).is_none() {
+                servers.insert(server_id, server.clone());
+            }
+        }
+    }
+
+    /// 只读访问底层文档，用于遍历任意键
+    pub fn as_document(&self) -> &DocumentMut {
+        &self.doc
+    }
+}
+
+impl std::fmt::Display for CodexConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.doc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"# user comment
+model_provider = "custom"
+model = "gpt-5"
+model_reasoning_effort = "high"
+
+[model_providers.custom]
+name = "custom"
+base_url = "https://api.example.com/v1"
+wire_api = "responses"
+requires_openai_auth = true
+
+[model_providers.legacy]
+base_url = "https://legacy.example.com"
+"#;
+
+    #[test]
+    fn reads_typed_fields_from_active_provider() {
+        let config = CodexConfig::parse(SAMPLE).expect("parse");
+        assert_eq!(config.model(), Some("gpt-5"));
+        assert_eq!(config.base_url(), Some("https://api.example.com/v1"));
+        assert_eq!(config.model_catalog_json(), None);
+    }
+
+    #[test]
+    fn edits_preserve_comments_and_other_tables() {
+        let mut config = CodexConfig::parse(SAMPLE).expect("parse");
+        config.set_base_url(Some("http://127.0.0.1:15721/v1"));
+        config.set_model(None);
+
+        let text = config.to_string();
+        assert!(text.starts_with("# user comment"));
+        assert!(text.contains("base_url = \"http://127.0.0.1:15721/v1\""));
+        assert!(text.contains("https://legacy.example.com"));
+        assert!(text.contains("model_reasoning_effort"));
+        assert!(!text.contains("model = "));
+
+        let reparsed = CodexConfig::parse(&text).expect("reparse");
+        assert_eq!(reparsed.base_url(), Some("http://127.0.0.1:15721/v1"));
+    }
+
+    #[test]
+    fn routing_fields_fall_back_to_top_level_without_model_provider() {
+        let mut config = CodexConfig::parse("model = \"gpt-5\"\n").expect("parse");
+        config.set_wire_api(Some("chat"));
+        config.set_base_url(Some("https://api.example.com"));
+        let text = config.to_string();
+        assert!(text.contains("wire_api = \"chat\""));
+        assert!(!text.contains("model_providers"));
+        assert_eq!(config.base_url(), Some("https://api.example.com"));
+    }
+
+    #[test]
+    fn routing_fields_create_missing_provider_table() {
+        let mut config = CodexConfig::parse("model_provider = \"relay\"\n").expect("parse");
+        config.set_base_url(Some("https://relay.example.com/v1"));
+        assert_eq!(config.base_url(), Some("https://relay.example.com/v1"));
+        assert!(config.to_string().contains("[model_providers.relay]"));
+        config.set_base_url(None);
+        assert_eq!(config.base_url(), None);
+    }
+
+    #[test]
+    fn merge_missing_mcp_servers_keeps_existing_entries() {
+        let mut config = CodexConfig::parse("[mcp_servers.a]\ncommand = \"new\"\n").expect("parse");
+        let existing = CodexConfig::parse(
+            "[mcp_servers.a]\ncommand = \"old\"\n\n[mcp_servers.b]\ncommand = \"b\"\n",
+        )
+        .expect("parse");
+        config.merge_missing_mcp_servers(&existing);
+        let text = config.to_string();
+        assert!(text.contains("command = \"new\""));
+        assert!(!text.contains("command = \"old\""));
+        assert!(text.contains("[mcp_servers.b]"));
+
+        let mut empty = CodexConfig::default();
+        empty.merge_missing_mcp_servers(&existing);
+        assert!(empty.to_string().contains("[mcp_servers.b]"));
+    }
+}
@@ -59,6 +59,12 @@ fn validate_common_config_snippet(app_type: &str, snippet: &str) -> Result<(), S
         _ => {}
     }
 
+    // 模型、端点、凭据等供应商专属键不能放进共享片段
+    if let Ok(app) = AppType::from_str(app_type) {
+        crate::services::provider::ensure_common_config_shareable(&app, snippet)
+            .map_err(|e| e.to_string())?;
+    }
+
     Ok(())
 }
 
@@ -391,6 +397,19 @@ mod tests {
             .expect("comment-only codex snippet should be valid");
     }
 
+    #[test]
+    fn validate_common_config_snippet_rejects_provider_scoped_keys() {
+        let err = validate_common_config_snippet(
+            "claude",
+            r#"{"env":{"ANTHROPIC_BASE_URL":"https://relay.example"},"hooks":{}}"#,
+        )
+        .expect_err("provider-scoped keys should be rejected");
+        assert!(err.contains("env.ANTHROPIC_BASE_URL"), "got {err}");
+
+        validate_common_config_snippet("claude", r#"{"permissions":{"allow":["Bash"]}}"#)
+            .expect("common-safe keys should be accepted");
+    }
+
     #[test]
     fn validate_common_config_snippet_rejects_invalid_codex_snippet() {
         let err = validate_common_config_snippet("codex", "[broken")
@@ -424,3 +443,48 @@ pub async fn extract_common_config_snippet(
     crate::services::provider::ProviderService::extract_common_config_snippet(&state, app)
         .map_err(|e| e.to_string())
 }
+
+/// 检查并修复 Gemini `.env` 与 `settings.json` 之间的漂移
+#[tauri::command]
+pub async fn repair_gemini_config(
+    dryRun: Option<bool>,
+) -> Result<crate::gemini_config::GeminiRepairReport, String> {
+    let dry_run = dryRun.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || crate::gemini_config::repair_gemini_live(dry_run))
+        .await
+        .map_err(|e| format!("修复 Gemini 配置失败: {e}"))?
+        .map_err(|e| e.to_string())
+}
+
+/// 分析通用配置中被误共享的供应商专属键（不做修改）
+#[tauri::command]
+pub async fn analyze_common_config_migration(
+    appType: String,
+    state: State<'_, AppState>,
+) -> Result<crate::services::provider::CommonConfigMigrationReport, String> {
+    let app = AppType::from_str(&appType).map_err(|e| e.to_string())?;
+    crate::services::provider::analyze_common_config_migration(state.inner(), app)
+        .map_err(|e| e.to_string())
+}
+
+/// 将通用配置中的供应商专属键迁入各供应商配置
+#[tauri::command]
+pub async fn apply_common_config_migration(
+    appType: String,
+    state: State<'_, AppState>,
+) -> Result<crate::services::provider::CommonConfigMigrationReport, String> {
+    let app = AppType::from_str(&appType).map_err(|e| e.to_string())?;
+    crate::services::provider::apply_common_config_migration(state.inner(), app)
+        .map_err(|e| e.to_string())
+}
+
+/// 撤销最近一次通用配置迁移
+#[tauri::command]
+pub async fn undo_common_config_migration(
+    appType: String,
+    state: State<'_, AppState>,
+) -> Result<crate::services::provider::CommonConfigMigrationReport, String> {
+    let app = AppType::from_str(&appType).map_err(|e| e.to_string())?;
+    crate::services::provider::undo_common_config_migration(state.inner(), app)
+        .map_err(|e| e.to_string())
+}
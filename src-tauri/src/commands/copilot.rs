@@ -2,16 +2,29 @@
 //!
 //! 提供 Copilot OAuth 认证相关的 Tauri 命令，支持多账号管理。
 
+use crate::app_config::AppType;
 use crate::proxy::providers::copilot_auth::{
     CopilotAuthManager, CopilotAuthStatus, CopilotModel, CopilotUsageResponse, GitHubAccount,
     GitHubDeviceCodeResponse,
 };
-use std::sync::Arc;
+use crate::services::copilot_provisioning::{CopilotProvisioningService, ProvisionedProvider};
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock};
 use tauri::State;
 use tokio::sync::RwLock;
 
-/// Copilot 认证状态
-pub struct CopilotAuthState(pub Arc<RwLock<CopilotAuthManager>>);
+/// Copilot 认证状态；首次访问时才从磁盘加载账号
+pub struct CopilotAuthState(pub LazyLock<Arc<RwLock<CopilotAuthManager>>>);
+
+impl Default for CopilotAuthState {
+    fn default() -> Self {
+        Self(LazyLock::new(|| {
+            let manager = CopilotAuthManager::new(crate::config::get_app_config_dir());
+            log::info!("✓ CopilotAuthManager initialized");
+            Arc::new(RwLock::new(manager))
+        }))
+    }
+}
 
 // ==================== 设备码流程 ====================
 
@@ -219,3 +232,34 @@ pub async fn copilot_get_usage_for_account(
         .await
         .map_err(|e| e.to_string())
 }
+
+// ==================== 供应商自动生成 ====================
+
+/// 为已认证账号生成 Claude / Codex 供应商（已存在时更新）
+#[tauri::command(rename_all = "camelCase")]
+pub async fn copilot_provision_providers(
+    account_id: String,
+    model: String,
+    apps: Vec<String>,
+    state: State<'_, crate::store::AppState>,
+    copilot_state: State<'_, CopilotAuthState>,
+) -> Result<Vec<ProvisionedProvider>, String> {
+    let apps = apps
+        .iter()
+        .map(|app| AppType::from_str(app).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    CopilotProvisioningService::provision(&state, &copilot_state, &account_id, &model, &apps)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 按最新模型列表刷新自动生成的供应商，返回更新数量
+#[tauri::command]
+pub async fn copilot_sync_provisioned_providers(
+    state: State<'_, crate::store::AppState>,
+    copilot_state: State<'_, CopilotAuthState>,
+) -> Result<usize, String> {
+    CopilotProvisioningService::sync_models(&state, &copilot_state)
+        .await
+        .map_err(|e| e.to_string())
+}
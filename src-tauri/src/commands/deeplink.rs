@@ -1,6 +1,7 @@
 use crate::deeplink::{
     import_mcp_from_deeplink, import_prompt_from_deeplink, import_provider_from_deeplink,
-    import_skill_from_deeplink, parse_deeplink_url, DeepLinkImportRequest,
+    import_skill_from_deeplink, parse_deeplink_url, parse_pasted_provider, DeepLinkImportRequest,
+    PastedProvider,
 };
 use crate::store::AppState;
 use tauri::State;
@@ -12,6 +13,14 @@ pub fn parse_deeplink(url: String) -> Result<DeepLinkImportRequest, String> {
     parse_deeplink_url(&url).map_err(|e| e.to_string())
 }
 
+/// Recognize a provider in pasted text ("paste to add")
+///
+/// The returned request goes through the same confirmation dialog as deep links.
+#[tauri::command]
+pub fn parse_provider_paste(text: String) -> Result<PastedProvider, String> {
+    parse_pasted_provider(&text).map_err(|e| e.to_string())
+}
+
 /// Merge configuration from Base64/URL into a deep link request
 /// This is used by the frontend to show the complete configuration in the confirmation dialog
 #[tauri::command]
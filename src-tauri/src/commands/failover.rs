@@ -2,8 +2,10 @@
 //!
 //! 管理代理模式下的故障转移队列（基于 providers 表的 in_failover_queue 字段）
 
+use crate::app_config::AppType;
 use crate::database::FailoverQueueItem;
 use crate::provider::Provider;
+use crate::services::failover_queue::FailoverQueueService;
 use crate::store::AppState;
 use std::str::FromStr;
 use tauri::Emitter;
@@ -39,9 +41,9 @@ pub async fn add_to_failover_queue(
     app_type: String,
     provider_id: String,
 ) -> Result<(), String> {
-    state
-        .db
-        .add_to_failover_queue(&app_type, &provider_id)
+    let app = AppType::from_str(&app_type).map_err(|e| e.to_string())?;
+    FailoverQueueService::add(&state.db, &app, &provider_id)
+        .map(|_| ())
         .map_err(|e| e.to_string())
 }
 
@@ -52,12 +54,29 @@ pub async fn remove_from_failover_queue(
     app_type: String,
     provider_id: String,
 ) -> Result<(), String> {
-    state
+    let app = AppType::from_str(&app_type).map_err(|e| e.to_string())?;
+    let auto_failover_enabled = state
         .db
-        .remove_from_failover_queue(&app_type, &provider_id)
+        .get_proxy_config_for_app(&app_type)
+        .await
+        .map(|config| config.auto_failover_enabled)
+        .map_err(|e| e.to_string())?;
+    FailoverQueueService::remove(&state.db, &app, &provider_id, auto_failover_enabled)
+        .map(|_| ())
         .map_err(|e| e.to_string())
 }
 
+/// 调整故障转移队列顺序（同时调整首页列表中队列成员的相对顺序）
+#[tauri::command]
+pub async fn reorder_failover_queue(
+    state: tauri::State<'_, AppState>,
+    app_type: String,
+    provider_ids: Vec<String>,
+) -> Result<Vec<FailoverQueueItem>, String> {
+    let app = AppType::from_str(&app_type).map_err(|e| e.to_string())?;
+    FailoverQueueService::reorder(&state.db, &app, &provider_ids).map_err(|e| e.to_string())
+}
+
 /// 获取指定应用的自动故障转移开关状态（从 proxy_config 表读取）
 #[tauri::command]
 pub async fn get_auto_failover_enabled(
@@ -106,8 +125,8 @@ pub async fn set_auto_failover_enabled(
             .map_err(|e| e.to_string())?;
 
         if queue.is_empty() {
-            let app_enum = crate::app_config::AppType::from_str(&app_type)
-                .map_err(|_| format!("无效的应用类型: {app_type}"))?;
+            let app_enum =
+                AppType::from_str(&app_type).map_err(|_| format!("无效的应用类型: {app_type}"))?;
 
             let current_id = crate::settings::get_effective_current_provider(&state.db, &app_enum)
                 .map_err(|e| e.to_string())?;
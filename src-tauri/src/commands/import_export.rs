@@ -12,6 +12,7 @@ use crate::database::backup::BackupEntry;
 use crate::database::Database;
 use crate::error::AppError;
 use crate::services::provider::ProviderService;
+use crate::services::telemetry::{TelemetryEvent, TelemetryService};
 use crate::store::AppState;
 
 // ─── File import/export ──────────────────────────────────────
@@ -35,6 +36,7 @@ pub async fn export_config_to_file(
     .await
     .map_err(|e| format!("导出配置失败: {e}"))?
     .map_err(|e: AppError| e.to_string())
+    .inspect(|_| TelemetryService::record(TelemetryEvent::ConfigExport))
 }
 
 /// 从 SQL 备份导入数据库
@@ -57,6 +59,7 @@ pub async fn import_config_from_file(
     .await
     .map_err(|e| format!("导入配置失败: {e}"))?
     .map_err(|e: AppError| e.to_string())
+    .inspect(|_| TelemetryService::record(TelemetryEvent::ConfigImport))
 }
 
 #[tauri::command]
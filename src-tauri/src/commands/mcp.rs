@@ -8,6 +8,7 @@ use tauri::State;
 
 use crate::app_config::AppType;
 use crate::claude_mcp;
+use crate::services::telemetry::{TelemetryEvent, TelemetryService};
 use crate::services::McpService;
 use crate::store::AppState;
 
@@ -173,7 +174,9 @@ pub async fn upsert_mcp_server(
     state: State<'_, AppState>,
     server: McpServer,
 ) -> Result<(), String> {
-    McpService::upsert_server(&state, server).map_err(|e| e.to_string())
+    McpService::upsert_server(&state, server).map_err(|e| e.to_string())?;
+    TelemetryService::record(TelemetryEvent::McpUpsert);
+    Ok(())
 }
 
 /// 删除 MCP 服务器
@@ -199,3 +202,39 @@ pub async fn toggle_mcp_app(
 pub async fn import_mcp_from_apps(state: State<'_, AppState>) -> Result<usize, String> {
     McpService::import_from_all_apps(&state).map_err(|e| e.to_string())
 }
+
+use crate::services::mcp_runtime::{self, McpRuntimeCheck, McpStartResult};
+
+/// 检测 stdio MCP 服务器依赖的运行时（npx / uvx / docker 等）
+#[tauri::command]
+pub async fn check_mcp_runtime(spec: serde_json::Value) -> Result<McpRuntimeCheck, String> {
+    tauri::async_runtime::spawn_blocking(move || mcp_runtime::check(&spec))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// 执行可选的准备步骤（预下载 npm 包 / 拉取镜像），返回执行的命令
+#[tauri::command]
+pub async fn prepare_mcp_runtime(spec: serde_json::Value) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || mcp_runtime::prepare(&spec))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// 试启动 MCP 服务器并完成 initialize 握手
+#[tauri::command]
+pub async fn verify_mcp_server(
+    spec: serde_json::Value,
+    timeout_secs: Option<u64>,
+) -> Result<McpStartResult, String> {
+    let timeout = timeout_secs
+        .filter(|secs| *secs > 0)
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(mcp_runtime::DEFAULT_VERIFY_TIMEOUT);
+    tauri::async_runtime::spawn_blocking(move || mcp_runtime::verify_start(&spec, timeout))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
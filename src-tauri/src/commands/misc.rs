@@ -2,6 +2,7 @@
 
 use crate::app_config::AppType;
 use crate::init_status::{InitErrorPayload, SkillsMigrationPayload};
+use crate::services::app_detection::{compare_semver, AppDetectionService};
 use crate::services::ProviderService;
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -50,6 +51,20 @@ pub async fn copy_text_to_clipboard(text: String) -> Result<bool, String> {
     .map_err(|e| format!("剪贴板任务执行失败: {e}"))?
 }
 
+/// 读取系统剪贴板文本（"粘贴即添加"供应商）
+#[tauri::command]
+pub async fn read_clipboard_text() -> Result<String, String> {
+    tokio::task::spawn_blocking(|| {
+        let mut clipboard =
+            arboard::Clipboard::new().map_err(|e| format!("访问系统剪贴板失败: {e}"))?;
+        clipboard
+            .get_text()
+            .map_err(|e| format!("读取系统剪贴板失败: {e}"))
+    })
+    .await
+    .map_err(|e| format!("剪贴板任务执行失败: {e}"))?
+}
+
 /// 检查更新
 #[tauri::command]
 pub async fn check_for_updates(handle: AppHandle) -> Result<bool, String> {
@@ -109,6 +124,14 @@ pub struct ToolVersion {
     env_type: String,
     /// 当 env_type 为 "wsl" 时，返回该工具绑定的 WSL distro（用于按 distro 探测 shells）
     wsl_distro: Option<String>,
+    /// 命令行默认命中的可执行文件（canonicalize 后）；WSL 或未安装时为空
+    path: Option<String>,
+    /// 安装方式（npm / native / homebrew / nvm / ...），见 `AppDetectionService::install_method`
+    install_method: Option<String>,
+    /// cc-switch 要求的最低版本；未登记的工具为空
+    min_supported_version: Option<String>,
+    /// 已安装版本低于最低支持版本
+    below_min_supported: bool,
 }
 
 const VALID_TOOLS: [&str; 7] = [
@@ -752,9 +775,6 @@ async fn get_single_tool_version_impl(
     // 判断该工具的运行环境 & WSL distro（如有）
     let (env_type, wsl_distro) = tool_env_type_and_wsl_distro(tool);
 
-    // 使用全局 HTTP 客户端（已包含代理配置）
-    let client = crate::proxy::http_client::get();
-
     // 1. 获取本地版本
     let probe = if let Some(distro) = wsl_distro.as_deref() {
         try_get_version_wsl(tool, distro, wsl_shell, wsl_shell_flag)
@@ -782,28 +802,23 @@ async fn get_single_tool_version_impl(
     };
 
     // 2. 获取远程最新版本（npm 工具在本地领先 latest 时会按预发布通道补查，见
-    //    fetch_npm_latest_for_tool / npm_prerelease_tags）
-    let local = local_version.as_deref();
-    let latest_version = match tool {
-        "claude" => {
-            fetch_npm_latest_for_tool(&client, "@anthropic-ai/claude-code", tool, local).await
-        }
-        "codex" => fetch_npm_latest_for_tool(&client, "@openai/codex", tool, local).await,
-        "gemini" => fetch_npm_latest_for_tool(&client, "@google/gemini-cli", tool, local).await,
-        "grok" => fetch_npm_latest_for_tool(&client, "@xai-official/grok", tool, local).await,
-        "opencode" => {
-            if let Some(version) =
-                fetch_npm_latest_for_tool(&client, "opencode-ai", tool, local).await
-            {
-                Some(version)
-            } else {
-                fetch_github_latest_version(&client, "anomalyco/opencode").await
-            }
-        }
-        "openclaw" => fetch_npm_latest_for_tool(&client, "openclaw", tool, local).await,
-        "hermes" => fetch_pypi_latest_version(&client, "hermes-agent").await,
+    //    fetch_npm_latest_for_tool / npm_prerelease_tags）；离线模式下跳过
+    let latest_version = match crate::proxy::http_client::get_auxiliary("tool version check") {
+        Ok(client) => fetch_latest_tool_version(&client, tool, local_version.as_deref()).await,
+        Err(_) => None,
+    };
+
+    // 3. 安装位置与方式：只对本机已安装的工具解析（WSL 内的路径对宿主无意义）
+    let real_path = match (&local_version, &wsl_distro) {
+        (Some(_), None) => resolve_path_default(tool),
         _ => None,
     };
+    let install_method = real_path.as_deref().map(|real| {
+        AppDetectionService::install_method(tool, infer_install_source(real), real).to_string()
+    });
+    let below_min_supported = local_version
+        .as_deref()
+        .is_some_and(|version| AppDetectionService::is_below_minimum(tool, version));
 
     ToolVersion {
         name: tool.to_string(),
@@ -813,6 +828,38 @@ async fn get_single_tool_version_impl(
         installed_but_broken,
         env_type,
         wsl_distro,
+        path: real_path.map(|path| path.display().to_string()),
+        install_method,
+        min_supported_version: AppDetectionService::min_supported_version(tool).map(str::to_string),
+        below_min_supported,
+    }
+}
+
+/// 查询工具的远程最新版本
+async fn fetch_latest_tool_version(
+    client: &reqwest::Client,
+    tool: &str,
+    local: Option<&str>,
+) -> Option<String> {
+    match tool {
+        "claude" => {
+            fetch_npm_latest_for_tool(client, "@anthropic-ai/claude-code", tool, local).await
+        }
+        "codex" => fetch_npm_latest_for_tool(client, "@openai/codex", tool, local).await,
+        "gemini" => fetch_npm_latest_for_tool(client, "@google/gemini-cli", tool, local).await,
+        "grok" => fetch_npm_latest_for_tool(client, "@xai-official/grok", tool, local).await,
+        "opencode" => {
+            if let Some(version) =
+                fetch_npm_latest_for_tool(client, "opencode-ai", tool, local).await
+            {
+                Some(version)
+            } else {
+                fetch_github_latest_version(client, "anomalyco/opencode").await
+            }
+        }
+        "openclaw" => fetch_npm_latest_for_tool(client, "openclaw", tool, local).await,
+        "hermes" => fetch_pypi_latest_version(client, "hermes-agent").await,
+        _ => None,
     }
 }
 
@@ -833,62 +880,6 @@ fn npm_prerelease_tags(tool: &str) -> &'static [&'static str] {
     }
 }
 
-/// 解析 "2.1.156" / "2.1.156-beta.1" → (主版本三段, 预发布段)。无法解析返回 None。
-/// 与前端 `src/lib/version.ts` 的 parseVersion 语义对称(跨语言各实现一份)。
-/// patch 用 u64 以容纳 codex 的 `0.1.2505172116` 时间戳式版本而不溢出。
-fn parse_semver(v: &str) -> Option<([u64; 3], Vec<String>)> {
-    // 忽略 `+build` 元数据,再以首个 `-` 切出预发布段。
-    let core_and_pre = v.trim().split('+').next().unwrap_or("");
-    let (core, pre) = match core_and_pre.split_once('-') {
-        Some((c, p)) => (c, Some(p)),
-        None => (core_and_pre, None),
-    };
-    let mut parts = core.split('.');
-    let major = parts.next()?.parse::<u64>().ok()?;
-    let minor = parts.next()?.parse::<u64>().ok()?;
-    let patch = parts.next()?.parse::<u64>().ok()?;
-    if parts.next().is_some() {
-        return None; // 多于三段,非法
-    }
-    let pre_segments = pre
-        .map(|p| p.split('.').map(|s| s.to_string()).collect())
-        .unwrap_or_default();
-    Some(([major, minor, patch], pre_segments))
-}
-
-/// 比较两个版本号(遵循 semver:主版本三段优先;core 相等时有预发布 < 无预发布;
-/// 预发布段逐段比 —— 数字段按数值、数字段 < 非数字段、非数字段按 ASCII、前缀相同
-/// 则段更多者更大)。任一无法解析返回 None,调用方据此保守处理。
-fn compare_semver(a: &str, b: &str) -> Option<std::cmp::Ordering> {
-    use std::cmp::Ordering;
-    let (ac, ap) = parse_semver(a)?;
-    let (bc, bp) = parse_semver(b)?;
-    for i in 0..3 {
-        match ac[i].cmp(&bc[i]) {
-            Ordering::Equal => continue,
-            other => return Some(other),
-        }
-    }
-    match (ap.is_empty(), bp.is_empty()) {
-        (true, true) => return Some(Ordering::Equal),
-        (true, false) => return Some(Ordering::Greater),
-        (false, true) => return Some(Ordering::Less),
-        (false, false) => {}
-    }
-    for (x, y) in ap.iter().zip(bp.iter()) {
-        let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
-            (Ok(xv), Ok(yv)) => xv.cmp(&yv),
-            (Ok(_), Err(_)) => Ordering::Less, // 数字段 < 非数字段
-            (Err(_), Ok(_)) => Ordering::Greater,
-            (Err(_), Err(_)) => x.as_str().cmp(y.as_str()),
-        };
-        if ord != Ordering::Equal {
-            return Some(ord);
-        }
-    }
-    Some(ap.len().cmp(&bp.len()))
-}
-
 /// 从一次 registry 请求得到的完整 dist-tags 出发,挑选要展示的"最新版本"。
 ///
 /// 规则:默认就是 `latest`;仅当本地版本已**严格领先** `latest`(说明用户主动在
@@ -2694,25 +2685,34 @@ pub async fn open_provider_terminal(
     cwd: Option<String>,
 ) -> Result<bool, String> {
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    launch_provider_terminal(state.inner(), &app_type, &providerId, cwd)?;
+    Ok(true)
+}
+
+/// 以指定提供商的配置启动终端（命令与场景宏共用）
+pub(crate) fn launch_provider_terminal(
+    state: &crate::store::AppState,
+    app_type: &AppType,
+    provider_id: &str,
+    cwd: Option<String>,
+) -> Result<(), String> {
     let launch_cwd = resolve_launch_cwd(cwd)?;
 
     // 获取提供商配置
-    let providers = ProviderService::list(state.inner(), app_type.clone())
+    let providers = ProviderService::list(state, app_type.clone())
         .map_err(|e| format!("获取提供商列表失败: {e}"))?;
 
     let provider = providers
-        .get(&providerId)
-        .ok_or_else(|| format!("提供商 {providerId} 不存在"))?;
+        .get(provider_id)
+        .ok_or_else(|| format!("提供商 {provider_id} 不存在"))?;
 
     // 从提供商配置中提取环境变量
     let config = &provider.settings_config;
-    let env_vars = extract_env_vars_from_config(config, &app_type);
+    let env_vars = extract_env_vars_from_config(config, app_type);
 
     // 根据平台启动终端，传入提供商ID用于生成唯一的配置文件名
-    launch_terminal_with_env(env_vars, &providerId, launch_cwd.as_deref())
-        .map_err(|e| format!("启动终端失败: {e}"))?;
-
-    Ok(true)
+    launch_terminal_with_env(env_vars, provider_id, launch_cwd.as_deref())
+        .map_err(|e| format!("启动终端失败: {e}"))
 }
 
 /// 从提供商配置中提取环境变量
@@ -22,12 +22,14 @@ mod profile;
 mod prompt;
 mod provider;
 mod proxy;
+mod secrets;
 mod session_manager;
 mod settings;
 pub mod skill;
 mod stream_check;
 mod subscription;
 mod sync_support;
+mod telemetry;
 mod xai_oauth;
 
 mod lightweight;
@@ -58,11 +60,13 @@ pub use profile::*;
 pub use prompt::*;
 pub use provider::*;
 pub use proxy::*;
+pub use secrets::*;
 pub use session_manager::*;
 pub use settings::*;
 pub use skill::*;
 pub use stream_check::*;
 pub use subscription::*;
+pub use telemetry::*;
 pub use xai_oauth::*;
 
 pub use lightweight::*;
@@ -15,11 +15,11 @@ pub async fn fetch_models_for_config(
     is_full_url: Option<bool>,
     models_url: Option<String>,
     custom_user_agent: Option<String>,
+    client_identity: Option<crate::provider::ClientIdentity>,
 ) -> Result<Vec<FetchedModel>, String> {
-    // 与转发 / 检测路径共用 parse_custom_user_agent：非法 UA 静默忽略（不阻断取模型）。
-    let user_agent = crate::provider::parse_custom_user_agent(custom_user_agent.as_deref())
-        .ok()
-        .flatten();
+    // 与转发 / 检测路径共用 resolve_identity_user_agent：非法 UA 静默忽略（不阻断取模型）。
+    let user_agent =
+        crate::provider::resolve_identity_user_agent(client_identity, custom_user_agent.as_deref());
     model_fetch::fetch_models(
         &base_url,
         &api_key,
@@ -4,7 +4,9 @@ use std::str::FromStr;
 use tauri::State;
 
 use crate::app_config::AppType;
-use crate::prompt::Prompt;
+use crate::prompt::{Prompt, PromptVersion};
+use crate::services::prompt::PromptDiff;
+use crate::services::telemetry::{TelemetryEvent, TelemetryService};
 use crate::services::PromptService;
 use crate::store::AppState;
 
@@ -45,7 +47,9 @@ pub async fn enable_prompt(
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    PromptService::enable_prompt(&state, app_type, &id).map_err(|e| e.to_string())
+    PromptService::enable_prompt(&state, app_type, &id).map_err(|e| e.to_string())?;
+    TelemetryService::record(TelemetryEvent::PromptEnable);
+    Ok(())
 }
 
 #[tauri::command]
@@ -62,3 +66,47 @@ pub async fn get_current_prompt_file_content(app: String) -> Result<Option<Strin
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
     PromptService::get_current_file_content(app_type).map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn get_prompt_versions(
+    app: String,
+    id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<PromptVersion>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    PromptService::list_versions(&state, app_type, &id).map_err(|e| e.to_string())
+}
+
+/// 预览部署某个版本对目标应用提示词文件的改动；`target_app` 为空时部署回原应用
+#[tauri::command]
+pub async fn preview_prompt_version(
+    app: String,
+    id: String,
+    version: i64,
+    target_app: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<PromptDiff, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let target = target_app
+        .map(|t| AppType::from_str(&t))
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    PromptService::preview_version(&state, app_type, &id, version, target)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn deploy_prompt_version(
+    app: String,
+    id: String,
+    version: i64,
+    target_app: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let target = target_app
+        .map(|t| AppType::from_str(&t))
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    PromptService::deploy_version(&state, app_type, &id, version, target).map_err(|e| e.to_string())
+}
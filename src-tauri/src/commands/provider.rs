@@ -5,9 +5,28 @@ use crate::app_config::AppType;
 use crate::commands::copilot::CopilotAuthState;
 use crate::commands::xai_oauth::XaiOAuthState;
 use crate::error::AppError;
-use crate::provider::{ClaudeDesktopMode, Provider};
+use crate::provider::{ClaudeDesktopMode, Provider, ProviderTrust};
+use crate::services::capability_probe::{CapabilityMatrix, CapabilityProbeService};
+use crate::services::curl_export::CurlExportService;
+use crate::services::endpoint_pool::EndpointPoolService;
+use crate::services::favicon::FaviconService;
+use crate::services::prompt_integrity::{PromptIntegrityService, PromptIntegritySignal};
+use crate::services::provider::{
+    DriftResolution, LiveConfigDrift, LiveImportCandidate, LiveImportOutcome,
+};
+use crate::services::provider_activity::{
+    ArchiveResult, ProviderActivityService, ProviderLastSeen, DEFAULT_STALE_DAYS,
+};
+use crate::services::provider_notice::{ProviderNotice, ProviderNoticeService};
+use crate::services::provider_trust::{ProviderTrustService, ResolvedTrust, TrustPolicy};
+use crate::services::telemetry::{TelemetryEvent, TelemetryService};
+use crate::services::test_message::{
+    TestMessageChunk, TestMessageOptions, TestMessageResult, TestMessageService,
+    EVENT_TEST_MESSAGE_CHUNK,
+};
 use crate::services::{
-    EndpointLatency, ProviderService, ProviderSortUpdate, SpeedtestService, SwitchResult,
+    EndpointLatency, ProviderService, ProviderSortUpdate, ProviderSpeedtestOptions,
+    SpeedtestService, SwitchResult,
 };
 use crate::store::AppState;
 use std::str::FromStr;
@@ -29,48 +48,269 @@ pub fn get_providers(
     ProviderService::list(state.inner(), app_type).map_err(|e| e.to_string())
 }
 
+/// 已缓存的供应商图标（id → data URL），不触发网络请求
+#[tauri::command]
+pub fn get_provider_favicons(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<std::collections::HashMap<String, String>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let providers = ProviderService::list(state.inner(), app_type).map_err(|e| e.to_string())?;
+    Ok(FaviconService::for_providers(providers.values()))
+}
+
 #[tauri::command]
 pub fn get_current_provider(state: State<'_, AppState>, app: String) -> Result<String, String> {
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
     ProviderService::current(state.inner(), app_type).map_err(|e| e.to_string())
 }
 
+/// 在阻塞线程池上执行供应商写操作：写操作可能等待其他实例释放跨进程写锁
+async fn run_provider_write<T, F>(app_handle: tauri::AppHandle, op: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&AppState) -> Result<T, AppError> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle
+            .try_state::<AppState>()
+            .ok_or_else(|| "应用状态不可用".to_string())?;
+        op(state.inner()).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| format!("供应商写入任务执行失败: {e}"))?
+}
+
 #[tauri::command]
-pub fn add_provider(
-    state: State<'_, AppState>,
+pub async fn add_provider(
+    app_handle: tauri::AppHandle,
     app: String,
     provider: Provider,
     #[allow(non_snake_case)] addToLive: Option<bool>,
 ) -> Result<bool, String> {
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::add(state.inner(), app_type, provider, addToLive.unwrap_or(true))
-        .map_err(|e| e.to_string())
+    run_provider_write(app_handle, move |state| {
+        ProviderService::add(state, app_type, provider, addToLive.unwrap_or(true))
+    })
+    .await
 }
 
 #[tauri::command]
-pub fn update_provider(
-    state: State<'_, AppState>,
+pub async fn update_provider(
+    app_handle: tauri::AppHandle,
     app: String,
     provider: Provider,
     #[allow(non_snake_case)] originalId: Option<String>,
 ) -> Result<bool, String> {
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::update(state.inner(), app_type, originalId.as_deref(), provider)
-        .map_err(|e| e.to_string())
+    run_provider_write(app_handle, move |state| {
+        ProviderService::update(state, app_type, originalId.as_deref(), provider)
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn delete_provider(
+    app_handle: tauri::AppHandle,
+    app: String,
+    id: String,
+) -> Result<bool, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    run_provider_write(app_handle, move |state| {
+        ProviderService::delete(state, app_type, &id).map(|_| true)
+    })
+    .await
+}
+
+/// 获取供应商最近使用时间（切换 / 经代理请求）
+#[tauri::command]
+pub fn get_provider_last_seen(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<Vec<ProviderLastSeen>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderActivityService::list(state.inner(), &app_type).map_err(|e| e.to_string())
 }
 
+/// 各供应商的信任等级（用户标注优先，其次按信任策略文件判定）
 #[tauri::command]
-pub fn delete_provider(
+pub fn get_provider_trust_levels(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<std::collections::HashMap<String, ResolvedTrust>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderTrustService::list(state.inner(), app_type).map_err(|e| e.to_string())
+}
+
+/// 标注供应商信任等级；`level` 为空时清除标注，回落到策略文件
+#[tauri::command]
+pub fn set_provider_trust(
     state: State<'_, AppState>,
     app: String,
     id: String,
+    level: Option<ProviderTrust>,
 ) -> Result<bool, String> {
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
-    ProviderService::delete(state.inner(), app_type, &id)
+    ProviderTrustService::set_label(state.inner(), app_type, &id, level)
         .map(|_| true)
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_provider_trust_policy() -> TrustPolicy {
+    ProviderTrustService::get_policy()
+}
+
+#[tauri::command]
+pub fn save_provider_trust_policy(policy: TrustPolicy) -> Result<bool, String> {
+    ProviderTrustService::save_policy(&policy)
+        .map(|_| true)
+        .map_err(|e| e.to_string())
+}
+
+/// 列出闲置至少 `days` 天（默认 90）的供应商
+#[tauri::command]
+pub fn get_stale_providers(
+    state: State<'_, AppState>,
+    app: String,
+    days: Option<i64>,
+) -> Result<Vec<ProviderLastSeen>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderActivityService::stale(state.inner(), &app_type, days.unwrap_or(DEFAULT_STALE_DAYS))
+        .map_err(|e| e.to_string())
+}
+
+/// 归档供应商：导出到归档文件后从列表删除
+#[tauri::command]
+pub fn archive_providers(
+    state: State<'_, AppState>,
+    app: String,
+    ids: Vec<String>,
+) -> Result<ArchiveResult, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    ProviderActivityService::archive(state.inner(), app_type, &ids).map_err(|e| e.to_string())
+}
+
+/// 复制为 curl：生成携带供应商鉴权头与最小请求体的 curl 命令
+#[tauri::command]
+pub fn copy_provider_as_curl(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+    model: Option<String>,
+    mask_key: bool,
+) -> Result<String, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let provider = state
+        .db
+        .get_provider_by_id(&id, app_type.as_str())
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("供应商 {id} 不存在"))?;
+    CurlExportService::for_provider(&app_type, &provider, model.as_deref(), false, mask_key)
+        .map_err(|e| e.to_string())
+}
+
+/// 探测供应商能力矩阵并保存；`model` 为空时取供应商配置的默认模型
+#[tauri::command]
+pub async fn probe_provider_capabilities(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+    model: Option<String>,
+) -> Result<CapabilityMatrix, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let provider = state
+        .db
+        .get_provider_by_id(&id, app_type.as_str())
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("供应商 {id} 不存在"))?;
+    CapabilityProbeService::probe(&state.db, &app_type, &provider, model.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 向供应商发送一条测试消息，返回模型、停止原因、用量与耗时等结构化结果
+///
+/// 流式模式下回复文本会以 `provider-test-message-chunk` 事件逐段推送。
+#[tauri::command]
+pub async fn send_test_message_to_provider(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+    options: Option<TestMessageOptions>,
+) -> Result<TestMessageResult, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let provider = state
+        .db
+        .get_provider_by_id(&id, app_type.as_str())
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("供应商 {id} 不存在"))?;
+    let options = options.unwrap_or_default();
+    let request_id = options.request_id.clone().unwrap_or_default();
+    TestMessageService::send(&app_type, &provider, &options, |delta| {
+        let chunk = TestMessageChunk {
+            request_id: request_id.clone(),
+            delta: delta.to_string(),
+        };
+        if let Err(e) = app_handle.emit(EVENT_TEST_MESSAGE_CHUNK, &chunk) {
+            log::debug!("emit {EVENT_TEST_MESSAGE_CHUNK} 失败: {e}");
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// 读取供应商最近一次的能力探测结果
+#[tauri::command]
+pub fn get_provider_capabilities(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<Option<CapabilityMatrix>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let mut matrices = state
+        .db
+        .get_provider_capabilities(app_type.as_str())
+        .map_err(|e| e.to_string())?;
+    Ok(matrices.remove(&id))
+}
+
+/// 上游宣布的弃用 / 下线公告（未关闭的）
+#[tauri::command]
+pub fn get_provider_notices(
+    state: State<'_, AppState>,
+    app: Option<String>,
+) -> Result<Vec<ProviderNotice>, String> {
+    let app_type = app
+        .as_deref()
+        .map(AppType::from_str)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    ProviderNoticeService::list_active(&state.db, app_type.as_ref().map(AppType::as_str))
+        .map_err(|e| e.to_string())
+}
+
+/// 关闭公告；公告内容变化后会再次提醒
+#[tauri::command]
+pub fn dismiss_provider_notice(
+    state: State<'_, AppState>,
+    app: String,
+    id: String,
+) -> Result<(), String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    state
+        .db
+        .dismiss_provider_notice(app_type.as_str(), &id)
+        .map_err(|e| e.to_string())
+}
+
+/// 各供应商的系统提示词注入 / 截断检测结果（代理运行期间采样）
+#[tauri::command]
+pub fn get_prompt_integrity_signals(app: Option<String>) -> Vec<PromptIntegritySignal> {
+    PromptIntegrityService::signals(app.as_deref())
+}
+
 #[tauri::command]
 pub fn remove_provider_from_live_config(
     state: tauri::State<'_, AppState>,
@@ -107,6 +347,8 @@ pub async fn switch_provider(
     id: String,
 ) -> Result<SwitchResult, String> {
     let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let handle = app_handle.clone();
+    let (pool_app, pool_id) = (app_type.clone(), id.clone());
     tauri::async_runtime::spawn_blocking(move || {
         let state = app_handle
             .try_state::<AppState>()
@@ -115,6 +357,10 @@ pub async fn switch_provider(
     })
     .await
     .map_err(|e| format!("供应商切换任务执行失败: {e}"))?
+    .inspect(|_| {
+        TelemetryService::record(TelemetryEvent::ProviderSwitch);
+        EndpointPoolService::spawn_on_switch(&handle, pool_app, pool_id);
+    })
 }
 
 fn import_default_config_internal(state: &AppState, app_type: AppType) -> Result<bool, AppError> {
@@ -199,6 +445,78 @@ pub fn import_default_config(state: State<'_, AppState>, app: String) -> Result<
     import_default_config_internal(&state, app_type).map_err(Into::into)
 }
 
+/// 比对当前供应商与 live 配置，返回外部修改造成的差异
+#[tauri::command]
+pub fn get_live_config_drift(
+    state: State<'_, AppState>,
+    app: String,
+) -> Result<Option<LiveConfigDrift>, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    crate::services::provider::detect_live_drift(state.inner(), &app_type).map_err(Into::into)
+}
+
+/// 处理 live 配置的外部修改：`reimport` 并入当前供应商，`overwrite` 用供应商覆盖 live
+#[tauri::command]
+pub fn resolve_live_config_drift(
+    state: State<'_, AppState>,
+    app: String,
+    resolution: String,
+) -> Result<bool, String> {
+    let app_type = AppType::from_str(&app).map_err(|e| e.to_string())?;
+    let resolution = DriftResolution::from_str(&resolution).map_err(|e| e.to_string())?;
+    crate::services::provider::resolve_live_drift(state.inner(), &app_type, resolution)
+        .map(|_| true)
+        .map_err(Into::into)
+}
+
+/// 扫描本机已有的 CLI 配置（首次使用引导）
+#[tauri::command]
+pub fn scan_live_import_candidates(
+    state: State<'_, AppState>,
+) -> Result<Vec<LiveImportCandidate>, String> {
+    ProviderService::scan_live_import_candidates(state.inner()).map_err(|e| e.to_string())
+}
+
+/// 批量导入已有 CLI 配置为供应商（"Imported from live config"）
+///
+/// `apps` 为空时导入所有可导入的应用；单个应用失败不影响其它应用。
+#[tauri::command]
+pub fn import_live_configs(
+    state: State<'_, AppState>,
+    apps: Option<Vec<String>>,
+) -> Result<Vec<LiveImportOutcome>, String> {
+    let candidates =
+        ProviderService::scan_live_import_candidates(state.inner()).map_err(|e| e.to_string())?;
+
+    let mut outcomes = Vec::new();
+    for candidate in candidates.iter().filter(|c| c.importable()) {
+        if let Some(selected) = apps.as_ref() {
+            if !selected.contains(&candidate.app) {
+                continue;
+            }
+        }
+        let app_type = AppType::from_str(&candidate.app).map_err(|e| e.to_string())?;
+        let outcome = match import_default_config_internal(state.inner(), app_type) {
+            Ok(imported) => LiveImportOutcome {
+                app: candidate.app.clone(),
+                imported,
+                error: None,
+            },
+            Err(e) => {
+                log::warn!("导入 {} 的 live 配置失败: {e}", candidate.app);
+                LiveImportOutcome {
+                    app: candidate.app.clone(),
+                    imported: false,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+        outcomes.push(outcome);
+    }
+
+    Ok(outcomes)
+}
+
 #[tauri::command]
 pub async fn get_claude_desktop_status(
     state: State<'_, AppState>,
@@ -786,14 +1104,21 @@ pub fn read_live_provider_settings(app: String) -> Result<serde_json::Value, Str
     ProviderService::read_live_settings(app_type).map_err(|e| e.to_string())
 }
 
+/// 端点测速；传入 `provider` 时以该供应商的鉴权、请求头与出站代理发起请求
 #[tauri::command]
 pub async fn test_api_endpoints(
+    state: State<'_, AppState>,
     urls: Vec<String>,
     #[allow(non_snake_case)] timeoutSecs: Option<u64>,
+    provider: Option<ProviderSpeedtestOptions>,
 ) -> Result<Vec<EndpointLatency>, String> {
-    SpeedtestService::test_endpoints(urls, timeoutSecs)
-        .await
-        .map_err(|e| e.to_string())
+    match provider {
+        Some(options) => {
+            SpeedtestService::test_provider_endpoints(&state.db, &options, urls, timeoutSecs).await
+        }
+        None => SpeedtestService::test_endpoints(urls, timeoutSecs).await,
+    }
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -3,8 +3,12 @@
 //! 提供前端调用的 API 接口
 
 use crate::error::AppError;
+use crate::proxy::auth::ProxyAuthConfig;
+use crate::proxy::bench::{run_bench, BenchOptions, BenchReport};
+use crate::proxy::route_logging::{self, RouteLogLevel, RouteLogRule};
 use crate::proxy::types::*;
 use crate::proxy::{CircuitBreakerConfig, CircuitBreakerStats};
+use crate::services::telemetry::{TelemetryEvent, TelemetryService};
 use crate::store::AppState;
 use std::str::FromStr;
 
@@ -13,7 +17,19 @@ use std::str::FromStr;
 pub async fn start_proxy_server(
     state: tauri::State<'_, AppState>,
 ) -> Result<ProxyServerInfo, String> {
-    state.proxy_service.start().await
+    let info = state.proxy_service.start().await?;
+    TelemetryService::record(TelemetryEvent::ProxyStart);
+    Ok(info)
+}
+
+/// 代理压测：在隔离实例上对 mock 上游发送合成请求，返回吞吐与延迟统计
+///
+/// 不依赖、也不影响正在运行的代理服务器。
+#[tauri::command]
+pub async fn run_proxy_bench(options: Option<BenchOptions>) -> Result<BenchReport, String> {
+    run_bench(options.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// 停止代理服务器（仅停止服务，不恢复/清理 Live 接管状态）
@@ -83,6 +99,69 @@ pub async fn update_proxy_config(
     state.proxy_service.update_config(&config).await
 }
 
+// ==================== Proxy Auth ====================
+
+/// 获取代理鉴权配置
+#[tauri::command]
+pub async fn get_proxy_auth_config(
+    state: tauri::State<'_, AppState>,
+) -> Result<ProxyAuthConfig, String> {
+    state.proxy_service.get_auth_config()
+}
+
+/// 开关代理鉴权
+#[tauri::command]
+pub async fn set_proxy_auth_enabled(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), String> {
+    state.proxy_service.set_auth_enabled(enabled).await
+}
+
+/// 列出代理访问令牌
+#[tauri::command]
+pub async fn list_proxy_auth_tokens(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ProxyAuthToken>, String> {
+    state.proxy_service.list_auth_tokens()
+}
+
+/// 新建代理访问令牌
+#[tauri::command]
+pub async fn create_proxy_auth_token(
+    state: tauri::State<'_, AppState>,
+    name: String,
+) -> Result<ProxyAuthToken, String> {
+    state.proxy_service.create_auth_token(&name).await
+}
+
+/// 轮换代理访问令牌
+#[tauri::command]
+pub async fn rotate_proxy_auth_token(
+    state: tauri::State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.proxy_service.rotate_auth_token(&id).await
+}
+
+/// 吊销代理访问令牌
+#[tauri::command]
+pub async fn revoke_proxy_auth_token(
+    state: tauri::State<'_, AppState>,
+    id: String,
+) -> Result<(), String> {
+    state.proxy_service.revoke_auth_token(&id).await
+}
+
+/// 按令牌汇总用量
+#[tauri::command]
+pub async fn get_proxy_auth_token_usage(
+    state: tauri::State<'_, AppState>,
+    since: Option<i64>,
+) -> Result<Vec<ProxyAuthTokenUsage>, String> {
+    state.proxy_service.get_auth_token_usage(since)
+}
+
 // ==================== Global & Per-App Config ====================
 
 /// 获取全局代理配置
@@ -453,3 +532,23 @@ pub async fn get_circuit_breaker_stats(
     let _ = (state, provider_id, app_type);
     Ok(None)
 }
+
+/// 获取按路由 / 供应商设置的代理日志级别
+#[tauri::command]
+pub fn get_proxy_log_rules() -> Result<Vec<RouteLogRule>, String> {
+    Ok(route_logging::rules())
+}
+
+/// 设置代理日志级别（即时生效，无需重启代理）
+///
+/// `provider_id` 为空时作用于整个路由；`level` 为空时移除该规则。
+#[tauri::command]
+pub fn set_proxy_log_level(
+    app: String,
+    provider_id: Option<String>,
+    level: Option<RouteLogLevel>,
+) -> Result<Vec<RouteLogRule>, String> {
+    let app_type = crate::app_config::AppType::from_str(&app).map_err(|e| e.to_string())?;
+    route_logging::set_rule(app_type.as_str(), provider_id.as_deref(), level);
+    Ok(route_logging::rules())
+}
@@ -4,10 +4,13 @@ use serde_json::{json, Value};
 use tauri::State;
 
 use crate::commands::sync_support::{
-    attach_warning, post_sync_warning_from_result, run_post_import_sync,
+    attach_warning, post_sync_warning_from_result, preview_backup, restore_selected,
+    run_post_import_sync,
 };
 use crate::error::AppError;
 use crate::services::s3_sync as s3_sync_service;
+use crate::services::selective_restore::{BackupContents, RestoreSelection};
+use crate::services::telemetry::{TelemetryEvent, TelemetryService};
 use crate::settings::{self, S3SyncSettings};
 use crate::store::AppState;
 
@@ -44,6 +47,10 @@ fn resolve_secret_for_request(
     preserve_empty_secret: bool,
 ) -> S3SyncSettings {
     if let Some(existing_settings) = existing {
+        // 加密口令从不下发给前端，空值始终表示沿用现有口令
+        if incoming.encryption_passphrase.is_empty() {
+            incoming.encryption_passphrase = existing_settings.encryption_passphrase.clone();
+        }
         if preserve_empty_secret && incoming.secret_access_key.is_empty() {
             incoming.secret_access_key = existing_settings.secret_access_key;
         }
@@ -102,6 +109,7 @@ pub async fn s3_sync_upload(state: State<'_, AppState>) -> Result<Value, String>
     map_sync_result(result, |error| {
         persist_sync_error(&mut settings, error, "manual")
     })
+    .inspect(|_| TelemetryService::record(TelemetryEvent::S3Upload))
 }
 
 #[tauri::command]
@@ -126,10 +134,37 @@ pub async fn s3_sync_download(state: State<'_, AppState>) -> Result<Value, Strin
         log::warn!("[S3] post-download sync warning: {msg}");
     }
     result = attach_warning(result, warning);
+    TelemetryService::record(TelemetryEvent::S3Download);
 
     Ok(result)
 }
 
+/// 只下载远端数据库并列出其中可恢复的条目
+#[tauri::command]
+pub async fn s3_sync_preview_restore(state: State<'_, AppState>) -> Result<BackupContents, String> {
+    let settings = require_enabled_s3_settings()?;
+    let (manifest, db_sql) = run_with_s3_lock(s3_sync_service::download_database(&settings))
+        .await
+        .map_err(|e| e.to_string())?;
+    preview_backup(state.db.clone(), manifest, db_sql)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 从远端备份中恢复选中的供应商 / MCP 服务器 / 设置，合并到本地
+#[tauri::command]
+pub async fn s3_sync_restore_selected(
+    state: State<'_, AppState>,
+    selection: RestoreSelection,
+) -> Result<Value, String> {
+    let settings = require_enabled_s3_settings()?;
+    let _auto_sync_suppression = crate::services::s3_auto_sync::AutoSyncSuppressionGuard::new();
+    let (_, db_sql) = run_with_s3_lock(s3_sync_service::download_database(&settings))
+        .await
+        .map_err(|e| e.to_string())?;
+    restore_selected(state.db.clone(), db_sql, selection).await
+}
+
 #[tauri::command]
 pub async fn s3_sync_save_settings(
     settings: S3SyncSettings,
@@ -160,6 +195,14 @@ pub async fn s3_sync_fetch_remote_info() -> Result<Value, String> {
     Ok(info.unwrap_or(json!({ "empty": true })))
 }
 
+#[tauri::command]
+pub async fn s3_sync_delete_remote() -> Result<Value, String> {
+    let settings = require_enabled_s3_settings()?;
+    run_with_s3_lock(s3_sync_service::delete_remote(&settings))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
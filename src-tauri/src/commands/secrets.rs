@@ -0,0 +1,36 @@
+//! 密钥存储命令
+
+use tauri::State;
+
+use crate::services::secrets::{self, SecretsMigrationReport};
+use crate::services::secrets_export::{
+    self, PasswordManagerFormat, SecretsExportReport, SecretsImportReport,
+};
+use crate::store::AppState;
+
+/// 把所有供应商的明文 API Key 迁移到系统钥匙串
+#[tauri::command]
+pub fn migrate_secrets_to_keychain(
+    state: State<'_, AppState>,
+) -> Result<SecretsMigrationReport, String> {
+    secrets::migrate_all(state.inner()).map_err(|e| e.to_string())
+}
+
+/// 导出供应商密钥为密码管理器（1Password / Bitwarden）可导入的文件
+#[tauri::command]
+pub fn export_secrets_for_password_manager(
+    state: State<'_, AppState>,
+    format: PasswordManagerFormat,
+    file_path: String,
+) -> Result<SecretsExportReport, String> {
+    secrets_export::export_to_file(state.inner(), format, &file_path).map_err(|e| e.to_string())
+}
+
+/// 从密码管理器导出的 CSV / JSON 内容回写供应商密钥
+#[tauri::command]
+pub fn import_secrets_from_password_manager(
+    state: State<'_, AppState>,
+    content: String,
+) -> Result<SecretsImportReport, String> {
+    secrets_export::import_from_content(state.inner(), &content).map_err(|e| e.to_string())
+}
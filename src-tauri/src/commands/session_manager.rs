@@ -1,5 +1,6 @@
 #![allow(non_snake_case)]
 
+use crate::services::live_reload::{LiveReloadReport, LiveReloadService, ReloadKind};
 use crate::session_manager;
 
 #[tauri::command]
@@ -10,6 +11,17 @@ pub async fn list_sessions() -> Result<Vec<session_manager::SessionMeta>, String
     Ok(sessions)
 }
 
+/// Recent sessions, optionally filtered by tool and project directory
+#[tauri::command]
+pub async fn list_recent_sessions(
+    query: Option<session_manager::SessionQuery>,
+) -> Result<Vec<session_manager::SessionMeta>, String> {
+    let query = query.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || session_manager::recent_sessions(&query))
+        .await
+        .map_err(|e| format!("Failed to scan sessions: {e}"))?
+}
+
 #[tauri::command]
 pub async fn get_session_messages(
     providerId: String,
@@ -83,3 +95,27 @@ pub async fn delete_sessions(
         .await
         .map_err(|e| format!("Failed to delete sessions: {e}"))
 }
+
+/// 刷新 Skill / Prompt 并报告正在运行的 Claude 会话是否已加载
+#[tauri::command]
+pub async fn trigger_live_reload(
+    kind: ReloadKind,
+    name: String,
+) -> Result<LiveReloadReport, String> {
+    tauri::async_runtime::spawn_blocking(move || LiveReloadService::trigger(kind, &name))
+        .await
+        .map_err(|e| format!("Failed to reload: {e}"))?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn check_live_reload(
+    kind: ReloadKind,
+    name: String,
+    triggeredAt: i64,
+) -> Result<LiveReloadReport, String> {
+    tauri::async_runtime::spawn_blocking(move || LiveReloadService::check(kind, &name, triggeredAt))
+        .await
+        .map_err(|e| format!("Failed to check reload: {e}"))?
+        .map_err(|e| e.to_string())
+}
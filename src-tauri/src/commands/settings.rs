@@ -43,6 +43,19 @@ fn merge_settings_for_save(
         }
         _ => {}
     }
+    // 加密口令同样不会下发给前端，空值一律视为"保持现有"
+    if let (Some(incoming_sync), Some(existing_sync)) =
+        (&mut incoming.webdav_sync, &existing.webdav_sync)
+    {
+        if incoming_sync.encryption_passphrase.is_empty() {
+            incoming_sync.encryption_passphrase = existing_sync.encryption_passphrase.clone();
+        }
+    }
+    if let (Some(incoming_sync), Some(existing_sync)) = (&mut incoming.s3_sync, &existing.s3_sync) {
+        if incoming_sync.encryption_passphrase.is_empty() {
+            incoming_sync.encryption_passphrase = existing_sync.encryption_passphrase.clone();
+        }
+    }
     // local_migrations 是纯后端状态（迁移完成标记），前端没有合法的修改场景，
     // 无条件取现有值。若按 incoming 透传：后端清掉 marker（如关闭统一会话
     // 开关）后、前端 query 缓存刷新前的一次全量保存会把旧 marker 重放回来，
@@ -57,6 +70,13 @@ pub async fn get_settings() -> Result<crate::settings::AppSettings, String> {
     Ok(crate::settings::get_settings_for_frontend())
 }
 
+/// 获取由 settings.toml / 环境变量覆盖的设置项
+#[tauri::command]
+pub async fn get_managed_settings() -> Result<Vec<crate::settings_overrides::ManagedSetting>, String>
+{
+    Ok(crate::settings_overrides::managed_settings())
+}
+
 /// 保存设置
 #[tauri::command]
 pub async fn save_settings(
@@ -68,8 +88,31 @@ pub async fn save_settings(
     let unify_codex_changed =
         merged.unify_codex_session_history != existing.unify_codex_session_history;
     let unify_codex_enabled = merged.unify_codex_session_history;
+    let favicons_turned_on =
+        merged.provider_favicons_enabled && !existing.provider_favicons_enabled;
+    crate::services::model_alias::validate(&merged.model_aliases).map_err(String::from)?;
+    let aliases_changed = merged.model_aliases != existing.model_aliases;
     crate::settings::update_settings(merged).map_err(|e| e.to_string())?;
 
+    if aliases_changed {
+        crate::services::model_alias::resync_live(state.inner());
+    }
+    crate::services::status_file::refresh(
+        &state.db,
+        crate::services::status_file::StatusSource::Settings,
+    );
+
+    // 刚开启供应商图标时立即在后台抓取一轮，不必等下一个刷新周期
+    if favicons_turned_on {
+        let db = state.db.clone();
+        tauri::async_runtime::spawn(async move {
+            use crate::services::favicon::{FaviconService, EVENT_PROVIDER_FAVICONS_UPDATED};
+            if FaviconService::refresh_stale(&db).await > 0 {
+                crate::usage_events::emit(EVENT_PROVIDER_FAVICONS_UPDATED, ());
+            }
+        });
+    }
+
     // 统一会话开关变更时立即重写当前官方 Codex 供应商的 live 配置，
     // 不必等下一次切换才生效。
     if unify_codex_changed {
@@ -170,6 +213,12 @@ pub async fn restore_codex_unified_history() -> Result<CodexUnifyHistoryRestoreR
     })
 }
 
+/// 当前是否以安全模式启动（前端据此显示提示横幅）
+#[tauri::command]
+pub fn is_safe_mode() -> bool {
+    crate::safe_mode::is_enabled()
+}
+
 /// 重启应用程序（当 app_config_dir 变更后使用）
 #[tauri::command]
 pub async fn restart_app(app: AppHandle) -> Result<bool, String> {
@@ -626,6 +675,17 @@ pub async fn get_auto_launch_status() -> Result<bool, String> {
     crate::auto_launch::is_auto_launch_enabled().map_err(|e| format!("获取开机自启状态失败: {e}"))
 }
 
+/// 向通知渠道发送测试消息
+#[tauri::command]
+pub async fn test_notification_channel(
+    channel: crate::settings::NotificationChannel,
+) -> Result<bool, String> {
+    crate::services::notifier::NotifierService::send_test(&channel)
+        .await
+        .map(|_| true)
+        .map_err(|e| e.to_string())
+}
+
 /// 获取整流器配置
 #[tauri::command]
 pub async fn get_rectifier_config(
@@ -647,6 +707,92 @@ pub async fn set_rectifier_config(
     Ok(true)
 }
 
+/// 获取请求内重试配置
+#[tauri::command]
+pub async fn get_retry_config(
+    state: tauri::State<'_, crate::AppState>,
+) -> Result<crate::proxy::types::RetryConfig, String> {
+    state.db.get_retry_config().map_err(|e| e.to_string())
+}
+
+/// 设置请求内重试配置
+#[tauri::command]
+pub async fn set_retry_config(
+    state: tauri::State<'_, crate::AppState>,
+    config: crate::proxy::types::RetryConfig,
+) -> Result<bool, String> {
+    state
+        .db
+        .set_retry_config(&config)
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// 获取优雅排空配置
+#[tauri::command]
+pub async fn get_drain_config(
+    state: tauri::State<'_, crate::AppState>,
+) -> Result<crate::proxy::types::DrainConfig, String> {
+    state.db.get_drain_config().map_err(|e| e.to_string())
+}
+
+/// 设置优雅排空配置
+#[tauri::command]
+pub async fn set_drain_config(
+    state: tauri::State<'_, crate::AppState>,
+    config: crate::proxy::types::DrainConfig,
+) -> Result<bool, String> {
+    if config.timeout_secs == 0
+        || config.timeout_secs > crate::proxy::types::DrainConfig::MAX_TIMEOUT_SECS
+    {
+        return Err(format!(
+            "排空超时需在 1-{} 秒之间",
+            crate::proxy::types::DrainConfig::MAX_TIMEOUT_SECS
+        ));
+    }
+    state
+        .db
+        .set_drain_config(&config)
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// 获取自动切走 / 切回看门狗配置
+#[tauri::command]
+pub async fn get_failback_watchdog_config(
+    state: tauri::State<'_, crate::AppState>,
+) -> Result<crate::proxy::types::FailbackWatchdogConfig, String> {
+    state
+        .db
+        .get_failback_watchdog_config()
+        .map_err(|e| e.to_string())
+}
+
+/// 设置自动切走 / 切回看门狗配置
+#[tauri::command]
+pub async fn set_failback_watchdog_config(
+    state: tauri::State<'_, crate::AppState>,
+    config: crate::proxy::types::FailbackWatchdogConfig,
+) -> Result<bool, String> {
+    if !(10..=3600).contains(&config.window_secs) {
+        return Err("统计窗口需在 10-3600 秒之间".to_string());
+    }
+    if config.min_failures == 0 || !(1..=100).contains(&config.error_rate_percent) {
+        return Err("最少错误次数需大于 0，错误率阈值需在 1-100 之间".to_string());
+    }
+    if config.status_codes.is_empty() {
+        return Err("至少需要一个状态码".to_string());
+    }
+    if !(30..=86_400).contains(&config.cooloff_secs) {
+        return Err("冷却时间需在 30-86400 秒之间".to_string());
+    }
+    state
+        .db
+        .set_failback_watchdog_config(&config)
+        .map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
 /// 获取优化器配置
 #[tauri::command]
 pub async fn get_optimizer_config(
@@ -668,6 +814,47 @@ pub async fn set_optimizer_config(
     Ok(true)
 }
 
+/// 获取响应缓存配置
+#[tauri::command]
+pub async fn get_response_cache_config(
+    state: tauri::State<'_, crate::AppState>,
+) -> Result<crate::proxy::types::ResponseCacheConfig, String> {
+    state
+        .db
+        .get_response_cache_config()
+        .map_err(|e| e.to_string())
+}
+
+/// 设置响应缓存配置（关闭时清空已缓存的响应）
+#[tauri::command]
+pub async fn set_response_cache_config(
+    state: tauri::State<'_, crate::AppState>,
+    config: crate::proxy::types::ResponseCacheConfig,
+) -> Result<bool, String> {
+    state
+        .db
+        .set_response_cache_config(&config)
+        .map_err(|e| e.to_string())?;
+    if !config.enabled {
+        crate::proxy::response_cache::clear();
+    }
+    Ok(true)
+}
+
+/// 响应缓存命中统计（自程序启动起）
+#[tauri::command]
+pub async fn get_response_cache_stats(
+) -> Result<crate::proxy::response_cache::ResponseCacheStats, String> {
+    Ok(crate::proxy::response_cache::stats())
+}
+
+/// 清空响应缓存并重置统计
+#[tauri::command]
+pub async fn clear_response_cache() -> Result<bool, String> {
+    crate::proxy::response_cache::clear();
+    Ok(true)
+}
+
 /// 获取 Copilot 优化器配置
 #[tauri::command]
 pub async fn get_copilot_optimizer_config(
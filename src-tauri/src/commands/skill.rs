@@ -7,17 +7,24 @@
 use crate::app_config::{AppType, InstalledSkill, UnmanagedSkill};
 use crate::error::format_skill_error;
 use crate::services::skill::{
-    DiscoverableSkill, ImportSkillSelection, MigrationResult, Skill, SkillBackupEntry, SkillRepo,
-    SkillService, SkillStorageLocation, SkillUninstallResult, SkillUpdateInfo,
-    SkillsShSearchResult,
+    DiscoverableSkill, ImportSkillSelection, MigrationResult, ProjectSkillInstall, Skill,
+    SkillBackupEntry, SkillInstallTarget, SkillRepo, SkillService, SkillStorageLocation,
+    SkillUninstallResult, SkillUpdateInfo, SkillsShSearchResult,
 };
+use crate::services::telemetry::{TelemetryEvent, TelemetryService};
 use crate::store::AppState;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 use tauri::State;
 
-/// SkillService 状态包装
-pub struct SkillServiceState(pub Arc<SkillService>);
+/// SkillService 状态包装；首次访问时才创建
+pub struct SkillServiceState(pub LazyLock<Arc<SkillService>>);
+
+impl Default for SkillServiceState {
+    fn default() -> Self {
+        Self(LazyLock::new(|| Arc::new(SkillService::new())))
+    }
+}
 
 /// 解析 app 参数为 AppType
 fn parse_app_type(app: &str) -> Result<AppType, String> {
@@ -48,10 +55,12 @@ pub fn delete_skill_backup(backup_id: String) -> Result<bool, String> {
 /// 参数：
 /// - skill: 从发现列表获取的技能信息
 /// - current_app: 当前选中的应用，安装后默认启用该应用
+/// - target: 安装目标（默认全局；项目级安装写入 `<project>/.claude/skills` 等目录）
 #[tauri::command]
 pub async fn install_skill_unified(
     skill: DiscoverableSkill,
     current_app: String,
+    target: Option<SkillInstallTarget>,
     service: State<'_, SkillServiceState>,
     app_state: State<'_, AppState>,
 ) -> Result<InstalledSkill, String> {
@@ -59,18 +68,49 @@ pub async fn install_skill_unified(
 
     service
         .0
-        .install(&app_state.db, &skill, &app_type)
+        .install(
+            &app_state.db,
+            &skill,
+            &app_type,
+            &target.unwrap_or_default(),
+        )
         .await
         .map_err(|e| e.to_string())
+        .inspect(|_| TelemetryService::record(TelemetryEvent::SkillInstall))
 }
 
 /// 卸载 Skill（新版统一卸载）
+///
+/// target 为项目时仅从该项目目录移除（current_app 为空则移除该项目下所有应用），
+/// 不影响 SSOT 与全局启用状态。
 #[tauri::command]
 pub fn uninstall_skill_unified(
     id: String,
+    target: Option<SkillInstallTarget>,
+    current_app: Option<String>,
     app_state: State<'_, AppState>,
 ) -> Result<SkillUninstallResult, String> {
-    SkillService::uninstall(&app_state.db, &id).map_err(|e| e.to_string())
+    match target.unwrap_or_default() {
+        SkillInstallTarget::Global => {
+            SkillService::uninstall(&app_state.db, &id).map_err(|e| e.to_string())
+        }
+        SkillInstallTarget::Project { path } => {
+            let app_type = current_app.as_deref().map(parse_app_type).transpose()?;
+            SkillService::uninstall_from_project(&app_state.db, &id, &path, app_type.as_ref())
+                .map_err(|e| e.to_string())?;
+            Ok(SkillUninstallResult { backup_path: None })
+        }
+    }
+}
+
+/// 获取项目级 Skill 安装记录（project_path 为空时返回所有项目）
+#[tauri::command]
+pub fn get_project_skill_installs(
+    project_path: Option<String>,
+    app_state: State<'_, AppState>,
+) -> Result<Vec<ProjectSkillInstall>, String> {
+    SkillService::list_project_installs(&app_state.db, project_path.as_deref())
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -254,7 +294,12 @@ pub async fn install_skill_for_app(
 
     service
         .0
-        .install(&app_state.db, &skill, &app_type)
+        .install(
+            &app_state.db,
+            &skill,
+            &app_type,
+            &SkillInstallTarget::Global,
+        )
         .await
         .map_err(|e| e.to_string())?;
 
@@ -6,9 +6,13 @@
 use crate::app_config::AppType;
 use crate::commands::copilot::CopilotAuthState;
 use crate::error::AppError;
+use crate::progress_events::ProgressReporter;
+use crate::proxy::HttpErrorClass;
+use crate::services::self_test::{SelfTestConfig, SelfTestReport, SelfTestService};
 use crate::services::stream_check::{
     HealthStatus, StreamCheckConfig, StreamCheckResult, StreamCheckService,
 };
+use crate::services::telemetry::{TelemetryEvent, TelemetryService};
 use crate::store::AppState;
 use std::collections::HashSet;
 use tauri::State;
@@ -40,6 +44,7 @@ pub async fn stream_check_provider(
         state
             .db
             .save_stream_check_log(&provider_id, &provider.name, app_type.as_str(), &result);
+    TelemetryService::record(TelemetryEvent::StreamCheck);
 
     Ok(result)
 }
@@ -70,20 +75,20 @@ pub async fn stream_check_all_providers(
         None
     };
 
-    let mut results = Vec::new();
-    for (id, provider) in providers {
-        // Official OAuth providers intentionally have no user-configured probe
-        // target. Never turn their runtime adapter defaults into unauthenticated
-        // network probes against first-party endpoints.
-        if provider.category.as_deref() == Some("official") {
-            continue;
-        }
-        if let Some(ids) = &allowed_ids {
-            if !ids.contains(&id) {
-                continue;
-            }
-        }
+    // Official OAuth providers intentionally have no user-configured probe
+    // target. Never turn their runtime adapter defaults into unauthenticated
+    // network probes against first-party endpoints.
+    let targets: Vec<_> = providers
+        .into_iter()
+        .filter(|(_, provider)| provider.category.as_deref() != Some("official"))
+        .filter(|(id, _)| allowed_ids.as_ref().is_none_or(|ids| ids.contains(id)))
+        .collect();
 
+    let progress = ProgressReporter::start("stream_check.batch");
+    let total = targets.len() as u64;
+    let mut results = Vec::new();
+    for (index, (id, provider)) in targets.into_iter().enumerate() {
+        progress.step("testing", index as u64, total, Some(provider.name.clone()));
         let base_url_override =
             resolve_copilot_base_url_override(&provider, &copilot_state).await?;
         let result =
@@ -98,7 +103,9 @@ pub async fn stream_check_all_providers(
                     model_used: String::new(),
                     tested_at: chrono::Utc::now().timestamp(),
                     retry_count: 0,
-                    error_category: None,
+                    // check_with_retry 只会因 base_url 解析失败报错，属于供应商配置问题
+                    error_category: Some(HttpErrorClass::Transform.as_str().to_string()),
+                    diagnostics: None,
                 });
 
         let _ = state
@@ -107,6 +114,7 @@ pub async fn stream_check_all_providers(
 
         results.push((id, result));
     }
+    progress.finish(None);
 
     Ok(results)
 }
@@ -126,11 +134,67 @@ pub fn save_stream_check_config(
     state.db.save_stream_check_config(&config)
 }
 
+/// 获取夜间自检配置
+#[tauri::command]
+pub fn get_self_test_config(state: State<'_, AppState>) -> Result<SelfTestConfig, AppError> {
+    state.db.get_self_test_config()
+}
+
+/// 保存夜间自检配置
+#[tauri::command]
+pub fn save_self_test_config(
+    state: State<'_, AppState>,
+    config: SelfTestConfig,
+) -> Result<(), AppError> {
+    if config.run_hour > 23 || config.summary_hour > 23 {
+        return Err(AppError::InvalidInput("小时数必须在 0-23 之间".to_string()));
+    }
+    state.db.save_self_test_config(&config)
+}
+
+/// 获取最近一次夜间自检报告
+#[tauri::command]
+pub fn get_self_test_report(
+    state: State<'_, AppState>,
+) -> Result<Option<SelfTestReport>, AppError> {
+    state.db.get_self_test_report()
+}
+
+/// 立即执行一次自检（不受启用开关与时段限制）
+#[tauri::command]
+pub async fn run_self_test_now(
+    state: State<'_, AppState>,
+    copilot_state: State<'_, CopilotAuthState>,
+) -> Result<SelfTestReport, AppError> {
+    let mut report = SelfTestService::run(&state.db, &copilot_state).await?;
+    // 手动执行的结果已直接展示给用户，无需早晨再提醒
+    report.notified = true;
+    state.db.save_self_test_report(&report)?;
+    Ok(report)
+}
+
+/// 领取待提醒的自检摘要：窗口在提醒时间未打开时，由前端启动后补弹
+#[tauri::command]
+pub fn take_pending_self_test_summary(
+    state: State<'_, AppState>,
+) -> Result<Option<SelfTestReport>, AppError> {
+    let config = state.db.get_self_test_config()?;
+    let Some(mut report) = state.db.get_self_test_report()? else {
+        return Ok(None);
+    };
+    if !SelfTestService::is_summary_due(&config, &report, chrono::Local::now()) {
+        return Ok(None);
+    }
+    report.notified = true;
+    state.db.save_self_test_report(&report)?;
+    Ok(Some(report))
+}
+
 /// Copilot 供应商的 base_url 需要从 OAuth 管理器动态解析（按账号或默认端点）。
 /// `is_full_url` 的供应商已是完整地址，无需解析。
-async fn resolve_copilot_base_url_override(
+pub(crate) async fn resolve_copilot_base_url_override(
     provider: &crate::provider::Provider,
-    copilot_state: &State<'_, CopilotAuthState>,
+    copilot_state: &CopilotAuthState,
 ) -> Result<Option<String>, AppError> {
     let is_copilot = is_copilot_provider(provider);
     let is_full_url = provider
@@ -4,6 +4,8 @@ use std::sync::Arc;
 use crate::database::Database;
 use crate::error::AppError;
 use crate::services::provider::ProviderService;
+use crate::services::selective_restore::{self, BackupContents, RestoreSelection};
+use crate::services::sync_protocol::SyncManifest;
 use crate::settings;
 use crate::store::AppState;
 
@@ -14,6 +16,47 @@ pub(crate) fn run_post_import_sync(db: Arc<Database>) -> Result<(), AppError> {
     Ok(())
 }
 
+/// 预览下载的备份内容
+pub(crate) async fn preview_backup(
+    db: Arc<Database>,
+    manifest: SyncManifest,
+    db_sql: Vec<u8>,
+) -> Result<BackupContents, AppError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let backup = selective_restore::open_backup(&db_sql)?;
+        selective_restore::preview(&backup, &db, manifest.device_name, manifest.created_at)
+    })
+    .await
+    .map_err(|e| AppError::Message(e.to_string()))?
+}
+
+/// 把选中的条目合并到本地，再把当前供应商同步到 live（尽力而为，失败作为 warning 返回）
+pub(crate) async fn restore_selected(
+    db: Arc<Database>,
+    db_sql: Vec<u8>,
+    selection: RestoreSelection,
+) -> Result<Value, String> {
+    let db_for_restore = db.clone();
+    let summary = tauri::async_runtime::spawn_blocking(move || {
+        let backup = selective_restore::open_backup(&db_sql)?;
+        selective_restore::apply(&backup, &db_for_restore, &selection)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    let warning = post_sync_warning_from_result(
+        tauri::async_runtime::spawn_blocking(move || run_post_import_sync(db))
+            .await
+            .map_err(|e| e.to_string()),
+    );
+    if let Some(msg) = warning.as_ref() {
+        log::warn!("[Restore] post-restore sync warning: {msg}");
+    }
+    let payload = serde_json::to_value(summary).map_err(|e| e.to_string())?;
+    Ok(attach_warning(payload, warning))
+}
+
 fn post_sync_warning<E: std::fmt::Display>(err: E) -> String {
     AppError::localized(
         "sync.post_operation_sync_failed",
@@ -0,0 +1,26 @@
+//! 本地使用统计命令
+
+use std::path::PathBuf;
+
+use crate::services::telemetry::{TelemetryReport, TelemetryService, TelemetrySnapshot};
+
+/// 获取本地聚合的功能使用计数
+#[tauri::command]
+pub async fn get_telemetry_snapshot() -> Result<TelemetrySnapshot, String> {
+    TelemetryService::snapshot().map_err(|e| e.to_string())
+}
+
+/// 清空本地使用统计
+#[tauri::command]
+pub async fn reset_telemetry() -> Result<bool, String> {
+    TelemetryService::reset().map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// 导出使用统计报告（由用户自行决定是否提交）
+#[tauri::command]
+pub async fn export_telemetry_report(
+    #[allow(non_snake_case)] filePath: String,
+) -> Result<TelemetryReport, String> {
+    TelemetryService::export_report(&PathBuf::from(filePath)).map_err(|e| e.to_string())
+}
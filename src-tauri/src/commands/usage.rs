@@ -1,6 +1,9 @@
 //! 使用统计相关命令
 
 use crate::error::AppError;
+use crate::proxy::usage::{UsageExportFormat, UsageLogger};
+use crate::services::cost_simulation::{CostSimulation, CostSimulationService};
+use crate::services::curl_export::CurlExportService;
 use crate::services::usage_stats::*;
 use crate::store::AppState;
 use rust_decimal::Decimal;
@@ -81,6 +84,19 @@ pub fn get_provider_stats(
     )
 }
 
+/// 按工作负载比较供应商（同模型档位、同请求体量）
+#[tauri::command]
+pub fn get_workload_comparison(
+    state: State<'_, AppState>,
+    start_date: Option<i64>,
+    end_date: Option<i64>,
+    app_type: Option<String>,
+) -> Result<Vec<WorkloadProviderStats>, AppError> {
+    state
+        .db
+        .get_workload_comparison(start_date, end_date, app_type.as_deref())
+}
+
 /// 获取模型统计
 #[tauri::command]
 pub fn get_model_stats(
@@ -120,6 +136,16 @@ pub fn get_request_detail(
     state.db.get_request_detail(&request_id)
 }
 
+/// 将已记录的请求复制为 curl（日志不含请求体，生成最小请求体）
+#[tauri::command]
+pub fn copy_request_as_curl(
+    state: State<'_, AppState>,
+    request_id: String,
+    mask_key: bool,
+) -> Result<String, AppError> {
+    CurlExportService::for_request_log(&state.db, &request_id, mask_key)
+}
+
 /// 获取模型定价列表
 #[tauri::command]
 pub fn get_model_pricing(state: State<'_, AppState>) -> Result<Vec<ModelPricingInfo>, AppError> {
@@ -326,6 +352,40 @@ pub fn get_usage_data_sources(
     crate::services::session_usage::get_data_source_breakdown(&state.db)
 }
 
+/// 导出时间范围内的用量明细
+///
+/// 传入 `file_path` 时写入该文件并返回路径，否则直接返回导出文本。
+#[tauri::command]
+pub fn export_usage(
+    state: State<'_, AppState>,
+    format: UsageExportFormat,
+    start_date: Option<i64>,
+    end_date: Option<i64>,
+    app_type: Option<String>,
+    file_path: Option<String>,
+) -> Result<String, AppError> {
+    let content =
+        UsageLogger::new(&state.db).export(format, start_date, end_date, app_type.as_deref())?;
+    match file_path {
+        Some(path) => {
+            std::fs::write(&path, content).map_err(|e| AppError::io(&path, e))?;
+            Ok(path)
+        }
+        None => Ok(content),
+    }
+}
+
+/// 模拟最近 `days` 天的流量改走指定供应商时的花费
+#[tauri::command]
+pub async fn simulate_provider_cost(
+    state: State<'_, AppState>,
+    app_type: String,
+    provider_id: String,
+    days: u32,
+) -> Result<CostSimulation, AppError> {
+    CostSimulationService::simulate(&state.db, &app_type, &provider_id, days).await
+}
+
 /// 模型定价信息
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -4,9 +4,12 @@ use serde_json::{json, Value};
 use tauri::State;
 
 use crate::commands::sync_support::{
-    attach_warning, post_sync_warning_from_result, run_post_import_sync,
+    attach_warning, post_sync_warning_from_result, preview_backup, restore_selected,
+    run_post_import_sync,
 };
 use crate::error::AppError;
+use crate::services::selective_restore::{BackupContents, RestoreSelection};
+use crate::services::telemetry::{TelemetryEvent, TelemetryService};
 use crate::services::webdav_sync as webdav_sync_service;
 use crate::settings::{self, WebDavSyncSettings};
 use crate::store::AppState;
@@ -49,6 +52,10 @@ fn resolve_password_for_request(
     preserve_empty_password: bool,
 ) -> WebDavSyncSettings {
     if let Some(existing_settings) = existing {
+        // 加密口令从不下发给前端，空值始终表示沿用现有口令
+        if incoming.encryption_passphrase.is_empty() {
+            incoming.encryption_passphrase = existing_settings.encryption_passphrase.clone();
+        }
         if preserve_empty_password && incoming.password.is_empty() {
             incoming.password = existing_settings.password;
         }
@@ -110,6 +117,7 @@ pub async fn webdav_sync_upload(state: State<'_, AppState>) -> Result<Value, Str
     map_sync_result(result, |error| {
         persist_sync_error(&mut settings, error, "manual")
     })
+    .inspect(|_| TelemetryService::record(TelemetryEvent::WebDavUpload))
 }
 
 #[tauri::command]
@@ -134,10 +142,40 @@ pub async fn webdav_sync_download(state: State<'_, AppState>) -> Result<Value, S
         log::warn!("[WebDAV] post-download sync warning: {msg}");
     }
     result = attach_warning(result, warning);
+    TelemetryService::record(TelemetryEvent::WebDavDownload);
 
     Ok(result)
 }
 
+/// 只下载远端数据库并列出其中可恢复的条目
+#[tauri::command]
+pub async fn webdav_sync_preview_restore(
+    state: State<'_, AppState>,
+) -> Result<BackupContents, String> {
+    let settings = require_enabled_webdav_settings()?;
+    let (manifest, db_sql) =
+        run_with_webdav_lock(webdav_sync_service::download_database(&settings))
+            .await
+            .map_err(|e| e.to_string())?;
+    preview_backup(state.db.clone(), manifest, db_sql)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 从远端备份中恢复选中的供应商 / MCP 服务器 / 设置，合并到本地
+#[tauri::command]
+pub async fn webdav_sync_restore_selected(
+    state: State<'_, AppState>,
+    selection: RestoreSelection,
+) -> Result<Value, String> {
+    let settings = require_enabled_webdav_settings()?;
+    let _auto_sync_suppression = crate::services::webdav_auto_sync::AutoSyncSuppressionGuard::new();
+    let (_, db_sql) = run_with_webdav_lock(webdav_sync_service::download_database(&settings))
+        .await
+        .map_err(|e| e.to_string())?;
+    restore_selected(state.db.clone(), db_sql, selection).await
+}
+
 #[tauri::command]
 pub async fn webdav_sync_save_settings(
     settings: WebDavSyncSettings,
@@ -168,6 +206,14 @@ pub async fn webdav_sync_fetch_remote_info() -> Result<Value, String> {
     Ok(info.unwrap_or(json!({ "empty": true })))
 }
 
+#[tauri::command]
+pub async fn webdav_sync_delete_remote() -> Result<Value, String> {
+    let settings = require_enabled_webdav_settings()?;
+    run_with_webdav_lock(webdav_sync_service::delete_remote(&settings))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
@@ -277,14 +277,17 @@ pub fn write_json_file<T: Serialize>(path: &Path, data: &T) -> Result<(), AppErr
         fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
 
-    let value = serde_json::to_value(data).map_err(|e| AppError::JsonSerialize { source: e })?;
-    let sorted_value = sort_json_keys(&value);
-    let json = serde_json::to_string_pretty(&sorted_value)
-        .map_err(|e| AppError::JsonSerialize { source: e })?;
-
+    let json = to_sorted_json_string(data)?;
     atomic_write(path, json.as_bytes())
 }
 
+/// 序列化为键排序后的格式化 JSON（与 `write_json_file` 写出的内容一致）
+pub fn to_sorted_json_string<T: Serialize>(data: &T) -> Result<String, AppError> {
+    let value = serde_json::to_value(data).map_err(|e| AppError::JsonSerialize { source: e })?;
+    serde_json::to_string_pretty(&sort_json_keys(&value))
+        .map_err(|e| AppError::JsonSerialize { source: e })
+}
+
 /// 原子写入文本文件（用于 TOML/纯文本）
 pub fn write_text_file(path: &Path, data: &str) -> Result<(), AppError> {
     if let Some(parent) = path.parent() {
@@ -0,0 +1,329 @@
+//! 配置目录监听模块
+//!
+//! 监听 Claude / Codex / Gemini 的 live 配置文件以及 CC Switch 数据库文件，
+//! 变更经防抖合并后向前端 emit `config-files-changed` 事件，让界面（以及后续
+//! 的 TUI / 代理）响应式刷新，而不是轮询或展示过期的供应商状态。
+//! live 配置变化后还会与当前供应商比对，被外部编辑时额外 emit
+//! `live-config-drift`，由界面询问是并入供应商还是覆盖回去。
+//!
+//! 设计要点：
+//! - 只监听目录本身（非递归），再按文件名过滤：`~/.claude` 等目录下有大量
+//!   会话/缓存文件，递归监听会产生大量无关事件。
+//! - 500ms 防抖：编辑器保存、原子写入（临时文件 + rename）通常会在短时间内
+//!   触发多次事件，合并成一次通知。
+//! - 本进程自己的数据库写入（请求日志、用量汇总等）不通知：SQLite update hook
+//!   记录最近一次自身写入的时间，落在同一防抖窗口内的 `database` 批次直接丢弃，
+//!   只有 CLI 等外部进程的写入才会触发界面刷新。
+//! - 启动失败只记录日志，不影响应用其它功能。
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::app_config::AppType;
+use crate::services::provider::detect_live_drift;
+use crate::store::AppState;
+
+/// 前端监听的事件名
+pub const EVENT_CONFIG_FILES_CHANGED: &str = "config-files-changed";
+
+/// live 配置被外部修改、与当前供应商不一致时的事件名
+pub const EVENT_LIVE_CONFIG_DRIFT: &str = "live-config-drift";
+
+/// 防抖窗口：窗口内没有新事件才 emit。
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(500);
+
+/// 变更来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchScope {
+    Claude,
+    Codex,
+    Gemini,
+    Database,
+}
+
+/// 推送给前端的事件负载
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChangeEvent {
+    pub scopes: Vec<WatchScope>,
+    pub paths: Vec<String>,
+}
+
+impl WatchScope {
+    /// 对应的应用；数据库变更没有对应应用
+    fn app_type(self) -> Option<AppType> {
+        match self {
+            Self::Claude => Some(AppType::Claude),
+            Self::Codex => Some(AppType::Codex),
+            Self::Gemini => Some(AppType::Gemini),
+            Self::Database => None,
+        }
+    }
+}
+
+struct WatchTarget {
+    scope: WatchScope,
+    dir: PathBuf,
+    files: &'static [&'static str],
+}
+
+/// 持有 watcher，防止被 drop 后停止监听
+static WATCHER: OnceLock<Mutex<Option<RecommendedWatcher>>> = OnceLock::new();
+
+/// 本进程最近一次写数据库的时间（UNIX 毫秒），0 表示尚未写入
+static LAST_SELF_DB_WRITE_MS: AtomicU64 = AtomicU64::new(0);
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// 记录本进程写入了数据库（由 SQLite update hook 调用）
+pub fn note_db_self_write() {
+    LAST_SELF_DB_WRITE_MS.store(now_ms(), Ordering::Relaxed);
+}
+
+/// 批次开始前一个防抖窗口内本进程写过数据库，则认为该批数据库事件来自自身
+fn is_self_db_write(batch_started_ms: u64, last_self_write_ms: u64) -> bool {
+    last_self_write_ms != 0
+        && last_self_write_ms + DEBOUNCE_WINDOW.as_millis() as u64 >= batch_started_ms
+}
+
+fn watch_targets() -> Vec<WatchTarget> {
+    vec![
+        WatchTarget {
+            scope: WatchScope::Claude,
+            dir: crate::config::get_claude_config_dir(),
+            files: &["settings.json"],
+        },
+        WatchTarget {
+            scope: WatchScope::Codex,
+            dir: crate::codex_config::get_codex_config_dir(),
+            files: &["config.toml", "auth.json"],
+        },
+        WatchTarget {
+            scope: WatchScope::Gemini,
+            dir: crate::gemini_config::get_gemini_dir(),
+            files: &[".env", "settings.json"],
+        },
+        WatchTarget {
+            scope: WatchScope::Database,
+            dir: crate::config::get_app_config_dir(),
+            files: &["cc-switch.db"],
+        },
+    ]
+}
+
+/// 判断变更路径属于哪个监听目标
+fn classify(targets: &[WatchTarget], path: &Path) -> Option<WatchScope> {
+    let dir = path.parent()?;
+    let name = path.file_name()?.to_str()?;
+    targets
+        .iter()
+        .find(|target| target.dir == dir && target.files.contains(&name))
+        .map(|target| target.scope)
+}
+
+/// 阻塞等待一批变更：收到首个事件后，直到防抖窗口内再无新事件才返回。
+///
+/// 返回首个事件的时间（UNIX 毫秒）与变更路径；通道关闭时返回 None。
+fn collect_batch(rx: &Receiver<PathBuf>, window: Duration) -> Option<(u64, BTreeSet<PathBuf>)> {
+    let mut batch = BTreeSet::new();
+    batch.insert(rx.recv().ok()?);
+    let started_ms = now_ms();
+    loop {
+        match rx.recv_timeout(window) {
+            Ok(path) => {
+                batch.insert(path);
+            }
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => {
+                return Some((started_ms, batch))
+            }
+        }
+    }
+}
+
+fn run_dispatch_loop(app: AppHandle, targets: Vec<WatchTarget>, rx: Receiver<PathBuf>) {
+    while let Some((started_ms, batch)) = collect_batch(&rx, DEBOUNCE_WINDOW) {
+        let self_db_write =
+            is_self_db_write(started_ms, LAST_SELF_DB_WRITE_MS.load(Ordering::Relaxed));
+        let scopes: BTreeSet<WatchScope> = batch
+            .iter()
+            .filter_map(|path| classify(&targets, path))
+            .filter(|scope| !(self_db_write && *scope == WatchScope::Database))
+            .collect();
+        if scopes.is_empty() {
+            continue;
+        }
+
+        let payload = ConfigChangeEvent {
+            scopes: scopes.into_iter().collect(),
+            paths: batch
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+        };
+        log::debug!("[ConfigWatcher] 检测到配置变更: {:?}", payload.scopes);
+        if let Err(e) = app.emit(EVENT_CONFIG_FILES_CHANGED, &payload) {
+            log::warn!("emit {EVENT_CONFIG_FILES_CHANGED} 失败: {e}");
+        }
+        emit_live_drift(&app, &payload.scopes);
+    }
+}
+
+/// 比对变更应用的 live 配置与当前供应商，不一致时通知界面
+fn emit_live_drift(app: &AppHandle, scopes: &[WatchScope]) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    for app_type in scopes.iter().filter_map(|scope| scope.app_type()) {
+        match detect_live_drift(&state, &app_type) {
+            Ok(Some(drift)) => {
+                log::info!(
+                    "[ConfigWatcher] {} live 配置被外部修改，与供应商 '{}' 不一致",
+                    drift.app,
+                    drift.provider_id
+                );
+                if let Err(e) = app.emit(EVENT_LIVE_CONFIG_DRIFT, &drift) {
+                    log::warn!("emit {EVENT_LIVE_CONFIG_DRIFT} 失败: {e}");
+                }
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!(
+                "[ConfigWatcher] 比对 {} live 配置失败: {e}",
+                app_type.as_str()
+            ),
+        }
+    }
+}
+
+/// 启动监听（应用 setup 阶段调用一次，重复调用无害）
+pub fn start(app: AppHandle) {
+    let slot = WATCHER.get_or_init(|| Mutex::new(None));
+    let Ok(mut slot) = slot.lock() else {
+        return;
+    };
+    if slot.is_some() {
+        return;
+    }
+
+    let targets = watch_targets();
+    let (tx, rx) = channel::<PathBuf>();
+    let filter_targets = watch_targets();
+    let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        for path in event.paths {
+            if classify(&filter_targets, &path).is_some() {
+                let _ = tx.send(path);
+            }
+        }
+    });
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            log::warn!("[ConfigWatcher] 创建文件监听失败，配置变更将不会自动刷新: {e}");
+            return;
+        }
+    };
+
+    let mut watched = 0usize;
+    for target in &targets {
+        if !target.dir.is_dir() {
+            continue;
+        }
+        match watcher.watch(&target.dir, RecursiveMode::NonRecursive) {
+            Ok(()) => watched += 1,
+            Err(e) => log::warn!(
+                "[ConfigWatcher] 监听 {} 失败: {e}",
+                target.dir.to_string_lossy()
+            ),
+        }
+    }
+    if watched == 0 {
+        log::info!("[ConfigWatcher] 没有可监听的配置目录，跳过");
+        return;
+    }
+
+    std::thread::spawn(move || run_dispatch_loop(app, targets, rx));
+    *slot = Some(watcher);
+    log::info!("[ConfigWatcher] ✓ 已监听 {watched} 个配置目录");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets() -> Vec<WatchTarget> {
+        vec![
+            WatchTarget {
+                scope: WatchScope::Codex,
+                dir: PathBuf::from("/home/u/.codex"),
+                files: &["config.toml", "auth.json"],
+            },
+            WatchTarget {
+                scope: WatchScope::Database,
+                dir: PathBuf::from("/home/u/.cc-switch"),
+                files: &["cc-switch.db"],
+            },
+        ]
+    }
+
+    #[test]
+    fn classify_matches_only_watched_files() {
+        let targets = targets();
+        assert_eq!(
+            classify(&targets, Path::new("/home/u/.codex/config.toml")),
+            Some(WatchScope::Codex)
+        );
+        assert_eq!(
+            classify(&targets, Path::new("/home/u/.cc-switch/cc-switch.db")),
+            Some(WatchScope::Database)
+        );
+        assert_eq!(
+            classify(&targets, Path::new("/home/u/.codex/history.jsonl")),
+            None
+        );
+        assert_eq!(
+            classify(&targets, Path::new("/home/u/.codex/sessions/config.toml")),
+            None
+        );
+    }
+
+    #[test]
+    fn collect_batch_merges_bursts() {
+        let (tx, rx) = channel();
+        tx.send(PathBuf::from("/a")).unwrap();
+        tx.send(PathBuf::from("/a")).unwrap();
+        tx.send(PathBuf::from("/b")).unwrap();
+
+        let (_, batch) = collect_batch(&rx, Duration::from_millis(10)).expect("batch");
+        assert_eq!(batch.len(), 2);
+
+        drop(tx);
+        assert!(collect_batch(&rx, Duration::from_millis(10)).is_none());
+    }
+
+    #[test]
+    fn database_batches_right_after_own_writes_are_self_writes() {
+        assert!(!is_self_db_write(10_000, 0));
+        assert!(is_self_db_write(10_000, 9_600));
+        assert!(is_self_db_write(10_000, 10_200));
+        assert!(!is_self_db_write(10_000, 9_000));
+    }
+}
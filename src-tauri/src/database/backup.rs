@@ -5,8 +5,9 @@
 use super::{lock_conn, Database};
 use crate::config::get_app_config_dir;
 use crate::error::AppError;
+use crate::progress_events::ProgressReporter;
 use chrono::{Local, Utc};
-use rusqlite::backup::Backup;
+use rusqlite::backup::{Backup, StepResult};
 use rusqlite::types::ValueRef;
 use rusqlite::Connection;
 use std::fs;
@@ -15,22 +16,29 @@ use tempfile::NamedTempFile;
 
 const CC_SWITCH_SQL_EXPORT_HEADER: &str = "-- CC Switch SQLite 导出";
 
+/// 快照备份每步复制的页数，用于上报进度
+const BACKUP_PAGES_PER_STEP: std::os::raw::c_int = 256;
+
 /// Tables whose data rows are skipped when exporting for WebDAV sync.
 const SYNC_SKIP_TABLES: &[&str] = &[
     "proxy_request_logs",
     "stream_check_logs",
     "provider_health",
+    "provider_activity",
+    "provider_notices",
     "proxy_live_backup",
     "usage_daily_rollups",
+    "schema_migrations",
 ];
 
 /// Tables whose local data is preserved (restored from local snapshot) during WebDAV import.
-/// Excludes ephemeral tables like provider_health that can safely rebuild at runtime.
+/// Excludes ephemeral tables like provider_health / provider_activity that can safely rebuild at runtime.
 const SYNC_PRESERVE_TABLES: &[&str] = &[
     "proxy_request_logs",
     "stream_check_logs",
     "proxy_live_backup",
     "usage_daily_rollups",
+    "schema_migrations",
 ];
 
 /// A database backup entry for the UI
@@ -42,6 +50,32 @@ pub struct BackupEntry {
     pub created_at: String, // ISO 8601
 }
 
+/// 分步复制数据库页并上报进度
+fn run_backup_with_progress(
+    backup: &Backup<'_, '_>,
+    progress: &ProgressReporter,
+) -> Result<(), AppError> {
+    loop {
+        match backup
+            .step(BACKUP_PAGES_PER_STEP)
+            .map_err(|e| AppError::Database(e.to_string()))?
+        {
+            StepResult::Done => return Ok(()),
+            StepResult::More => {
+                let state = backup.progress();
+                let total = state.pagecount.max(0) as u64;
+                let done = total.saturating_sub(state.remaining.max(0) as u64);
+                progress.step("copying", done, total, None);
+            }
+            // 源库被其它连接占用时稍后重试
+            StepResult::Busy | StepResult::Locked => {
+                std::thread::sleep(std::time::Duration::from_millis(10))
+            }
+            _ => return Ok(()),
+        }
+    }
+}
+
 impl Database {
     /// 导出为 SQLite 兼容的 SQL 文本（内存字符串，完整导出）
     pub fn export_sql_string(&self) -> Result<String, AppError> {
@@ -91,6 +125,25 @@ impl Database {
         self.import_sql_string_inner(sql_raw, SYNC_PRESERVE_TABLES)
     }
 
+    /// 把 CC Switch 导出的 SQL 载入独立的内存数据库，不触碰主库
+    ///
+    /// 用于预览云端备份内容并从中挑选条目恢复；不注册变更钩子，不会触发自动同步。
+    pub(crate) fn open_sql_export(sql_raw: &str) -> Result<Database, AppError> {
+        let sql_content = sql_raw.trim_start_matches('\u{feff}');
+        Self::validate_cc_switch_sql_export(sql_content)?;
+
+        let conn = Connection::open_in_memory().map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute_batch(sql_content)
+            .map_err(|e| AppError::Database(format!("执行 SQL 导入失败: {e}")))?;
+        Self::create_tables_on_conn(&conn)?;
+        Self::apply_schema_migrations_on_conn(&conn)?;
+        Self::validate_basic_state(&conn)?;
+
+        Ok(Database {
+            conn: std::sync::Mutex::new(conn),
+        })
+    }
+
     fn import_sql_string_inner(
         &self,
         sql_raw: &str,
@@ -276,7 +329,7 @@ impl Database {
                 log::warn!("Periodic stream_check_logs cleanup failed: {e}");
             }
         }
-        match self.rollup_and_prune(30) {
+        match self.rollup_and_prune(super::dao::usage_rollup::DETAIL_RETAIN_DAYS) {
             Ok(deleted) => {
                 reclaimed_rows += deleted;
             }
@@ -318,18 +371,18 @@ impl Database {
             counter += 1;
         }
 
+        let progress = ProgressReporter::start("backup");
         {
             let conn = lock_conn!(self.conn);
             let mut dest_conn =
                 Connection::open(&backup_path).map_err(|e| AppError::Database(e.to_string()))?;
             let backup = Backup::new(&conn, &mut dest_conn)
                 .map_err(|e| AppError::Database(e.to_string()))?;
-            backup
-                .step(-1)
-                .map_err(|e| AppError::Database(e.to_string()))?;
+            run_backup_with_progress(&backup, &progress)?;
         }
 
         Self::cleanup_db_backups(&backup_dir)?;
+        progress.finish(Some(backup_id));
         Ok(Some(backup_path))
     }
 
@@ -577,6 +630,7 @@ impl Database {
             .unwrap_or_default();
 
         // Step 2: Open the backup file and restore it to the main database
+        let progress = ProgressReporter::start("backup.restore");
         let source_conn =
             Connection::open(&backup_path).map_err(|e| AppError::Database(e.to_string()))?;
 
@@ -584,17 +638,17 @@ impl Database {
             let mut main_conn = lock_conn!(self.conn);
             let backup = Backup::new(&source_conn, &mut main_conn)
                 .map_err(|e| AppError::Database(e.to_string()))?;
-            backup
-                .step(-1)
-                .map_err(|e| AppError::Database(e.to_string()))?;
+            run_backup_with_progress(&backup, &progress)?;
         }
 
         // Step 3: Run schema migrations (backup may be from an older version)
+        progress.phase("migrating", None);
         self.create_tables()?;
         self.apply_schema_migrations()?;
         self.ensure_model_pricing_seeded()?;
 
         log::info!("Database restored from backup: {filename}, safety backup: {safety_id}");
+        progress.finish(Some(filename.to_string()));
         Ok(safety_id)
     }
 
@@ -16,6 +16,13 @@ pub struct FailoverQueueItem {
     pub sort_index: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provider_notes: Option<String>,
+    /// 持久化的健康状态；没有记录时视为健康
+    #[serde(default = "default_healthy")]
+    pub healthy: bool,
+}
+
+fn default_healthy() -> bool {
+    true
 }
 
 impl Database {
@@ -25,10 +32,12 @@ impl Database {
 
         let mut stmt = conn
             .prepare(
-                "SELECT id, name, sort_index, notes
-                 FROM providers
-                 WHERE app_type = ?1 AND in_failover_queue = 1
-                 ORDER BY COALESCE(sort_index, 999999), id ASC",
+                "SELECT p.id, p.name, p.sort_index, p.notes, COALESCE(h.is_healthy, 1)
+                 FROM providers p
+                 LEFT JOIN provider_health h
+                   ON h.provider_id = p.id AND h.app_type = p.app_type
+                 WHERE p.app_type = ?1 AND p.in_failover_queue = 1
+                 ORDER BY COALESCE(p.sort_index, 999999), p.id ASC",
             )
             .map_err(|e| AppError::Database(e.to_string()))?;
 
@@ -39,6 +48,7 @@ impl Database {
                     provider_name: row.get(1)?,
                     sort_index: row.get(2)?,
                     provider_notes: row.get(3)?,
+                    healthy: row.get::<_, i64>(4)? != 0,
                 })
             })
             .map_err(|e| AppError::Database(e.to_string()))?
@@ -48,13 +58,14 @@ impl Database {
         Ok(items)
     }
 
-    /// 获取故障转移队列中的供应商（完整 Provider 信息，按顺序）
+    /// 获取故障转移队列中的供应商（完整 Provider 信息，按队列顺序）
     pub fn get_failover_providers(&self, app_type: &str) -> Result<Vec<Provider>, AppError> {
-        let all_providers = self.get_all_providers(app_type)?;
+        let mut all_providers = self.get_all_providers(app_type)?;
 
-        let result: Vec<Provider> = all_providers
-            .into_values()
-            .filter(|p| p.in_failover_queue)
+        let result: Vec<Provider> = self
+            .get_failover_queue(app_type)?
+            .into_iter()
+            .filter_map(|item| all_providers.shift_remove(&item.provider_id))
             .collect();
 
         Ok(result)
@@ -100,6 +111,71 @@ impl Database {
         Ok(())
     }
 
+    /// 调整故障转移队列顺序
+    ///
+    /// 队列顺序即首页列表顺序（`sort_index`）。这里只在队列成员当前占据的位置之间
+    /// 重新排列，非队列供应商的位置保持不变；随后把整个应用的 `sort_index` 归一为 0..n。
+    /// `ordered_ids` 必须恰好是当前队列的全部成员。
+    pub fn reorder_failover_queue(
+        &self,
+        app_type: &str,
+        ordered_ids: &[String],
+    ) -> Result<(), AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let rows: Vec<(String, bool)> = {
+            let mut stmt = tx
+                .prepare(
+                    "SELECT id, in_failover_queue FROM providers
+                     WHERE app_type = ?1
+                     ORDER BY COALESCE(sort_index, 999999), id ASC",
+                )
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            let rows = stmt
+                .query_map([app_type], |row| Ok((row.get(0)?, row.get(1)?)))
+                .map_err(|e| AppError::Database(e.to_string()))?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| AppError::Database(e.to_string()))?;
+            rows
+        };
+
+        let mut members: Vec<&str> = rows
+            .iter()
+            .filter(|(_, in_queue)| *in_queue)
+            .map(|(id, _)| id.as_str())
+            .collect();
+        let mut requested: Vec<&str> = ordered_ids.iter().map(String::as_str).collect();
+        members.sort_unstable();
+        requested.sort_unstable();
+        if members != requested {
+            return Err(AppError::localized(
+                "failover.queue.order_mismatch",
+                "新顺序必须恰好包含当前故障转移队列中的全部供应商",
+                "The new order must list exactly the providers currently in the failover queue",
+            ));
+        }
+
+        let mut queue_order = ordered_ids.iter();
+        for (index, (id, in_queue)) in rows.iter().enumerate() {
+            let id = if *in_queue {
+                queue_order.next().map(String::as_str).unwrap_or(id)
+            } else {
+                id
+            };
+            tx.execute(
+                "UPDATE providers SET sort_index = ?1 WHERE id = ?2 AND app_type = ?3",
+                rusqlite::params![index as i64, id, app_type],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
     /// 清空故障转移队列
     pub fn clear_failover_queue(&self, app_type: &str) -> Result<(), AppError> {
         let conn = lock_conn!(self.conn);
@@ -6,6 +6,9 @@ pub mod failover;
 pub mod mcp;
 pub mod profiles;
 pub mod prompts;
+pub mod provider_activity;
+pub mod provider_capabilities;
+pub mod provider_notices;
 pub mod providers;
 pub mod providers_seed;
 pub mod proxy;
@@ -14,8 +17,11 @@ pub mod skills;
 pub mod stream_check;
 pub mod universal_providers;
 pub mod usage_rollup;
+pub mod usage_sync;
 
 // 所有 DAO 方法都通过 Database impl 提供，无需单独导出
-// 导出 FailoverQueueItem / Profile 供外部使用
+// 导出 FailoverQueueItem / Profile / ProviderActivity 供外部使用
 pub use failover::FailoverQueueItem;
 pub use profiles::Profile;
+pub use provider_activity::ProviderActivity;
+pub use usage_sync::UsageRow;
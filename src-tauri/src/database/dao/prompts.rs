@@ -1,12 +1,12 @@
 //! 提示词数据访问对象
 //!
-//! 提供提示词（Prompt）的 CRUD 操作。
+//! 提供提示词（Prompt）的 CRUD 操作，以及内容版本历史。
 
 use crate::database::{lock_conn, Database};
 use crate::error::AppError;
-use crate::prompt::Prompt;
+use crate::prompt::{Prompt, PromptVersion};
 use indexmap::IndexMap;
-use rusqlite::params;
+use rusqlite::{params, Connection, OptionalExtension};
 
 impl Database {
     /// 获取指定应用类型的所有提示词
@@ -54,6 +54,8 @@ impl Database {
     }
 
     /// 保存提示词
+    ///
+    /// 内容与最新版本不同时自动记录一个新版本。
     pub fn save_prompt(&self, app_type: &str, prompt: &Prompt) -> Result<(), AppError> {
         let conn = lock_conn!(self.conn);
         conn.execute(
@@ -72,6 +74,11 @@ impl Database {
             ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
+        let created_at = prompt
+            .updated_at
+            .or(prompt.created_at)
+            .unwrap_or_else(|| chrono::Utc::now().timestamp());
+        Self::record_prompt_version(&conn, app_type, &prompt.id, &prompt.content, created_at)?;
         Ok(())
     }
 
@@ -83,6 +90,95 @@ impl Database {
             params![id, app_type],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
+        conn.execute(
+            "DELETE FROM prompt_versions WHERE prompt_id = ?1 AND app_type = ?2",
+            params![id, app_type],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 获取提示词的全部历史版本（新版本在前）
+    pub fn get_prompt_versions(
+        &self,
+        app_type: &str,
+        prompt_id: &str,
+    ) -> Result<Vec<PromptVersion>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT version, content, created_at FROM prompt_versions
+                 WHERE app_type = ?1 AND prompt_id = ?2
+                 ORDER BY version DESC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type, prompt_id], |row| {
+                Ok(PromptVersion {
+                    version: row.get(0)?,
+                    content: row.get(1)?,
+                    created_at: row.get(2)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 获取提示词的指定版本
+    pub fn get_prompt_version(
+        &self,
+        app_type: &str,
+        prompt_id: &str,
+        version: i64,
+    ) -> Result<Option<PromptVersion>, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT version, content, created_at FROM prompt_versions
+             WHERE app_type = ?1 AND prompt_id = ?2 AND version = ?3",
+            params![app_type, prompt_id, version],
+            |row| {
+                Ok(PromptVersion {
+                    version: row.get(0)?,
+                    content: row.get(1)?,
+                    created_at: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 内容与最新版本不同时追加新版本
+    fn record_prompt_version(
+        conn: &Connection,
+        app_type: &str,
+        prompt_id: &str,
+        content: &str,
+        created_at: i64,
+    ) -> Result<(), AppError> {
+        let latest: Option<(i64, String)> = conn
+            .query_row(
+                "SELECT version, content FROM prompt_versions
+                 WHERE app_type = ?1 AND prompt_id = ?2
+                 ORDER BY version DESC LIMIT 1",
+                params![app_type, prompt_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let next = match latest {
+            Some((_, ref latest_content)) if latest_content == content => return Ok(()),
+            Some((version, _)) => version + 1,
+            None => 1,
+        };
+        conn.execute(
+            "INSERT INTO prompt_versions (app_type, prompt_id, version, content, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![app_type, prompt_id, next, content, created_at],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(())
     }
 }
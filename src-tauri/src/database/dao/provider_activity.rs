@@ -0,0 +1,82 @@
+//! 供应商最近活跃时间 DAO
+//!
+//! `last_active_at`：最近一次被切换为当前供应商；`last_traffic_at`：最近一次经代理
+//! 服务请求。均为 Unix 秒，只在本机记录，不参与云同步。
+
+use std::collections::HashMap;
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderActivity {
+    pub last_active_at: Option<i64>,
+    pub last_traffic_at: Option<i64>,
+}
+
+impl Database {
+    /// 记录供应商被切换为当前供应商
+    pub fn touch_provider_active(&self, app_type: &str, provider_id: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO provider_activity (provider_id, app_type, last_active_at)
+             SELECT ?1, ?2, ?3 WHERE EXISTS (SELECT 1 FROM providers WHERE id = ?1 AND app_type = ?2)
+             ON CONFLICT(provider_id, app_type) DO UPDATE SET last_active_at = excluded.last_active_at",
+            params![provider_id, app_type, chrono::Utc::now().timestamp()],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 记录供应商经代理服务了一次请求
+    ///
+    /// 由请求日志写入路径调用，调用方已持有连接锁。供应商已被删除时静默跳过。
+    pub(crate) fn touch_provider_traffic_on_conn(
+        conn: &Connection,
+        app_type: &str,
+        provider_id: &str,
+        at: i64,
+    ) -> Result<(), AppError> {
+        conn.execute(
+            "INSERT INTO provider_activity (provider_id, app_type, last_traffic_at)
+             SELECT ?1, ?2, ?3 WHERE EXISTS (SELECT 1 FROM providers WHERE id = ?1 AND app_type = ?2)
+             ON CONFLICT(provider_id, app_type) DO UPDATE SET last_traffic_at =
+                 MAX(COALESCE(last_traffic_at, 0), excluded.last_traffic_at)",
+            params![provider_id, app_type, at],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 按供应商 ID 读取某应用的活跃记录
+    pub fn get_provider_activity(
+        &self,
+        app_type: &str,
+    ) -> Result<HashMap<String, ProviderActivity>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT provider_id, last_active_at, last_traffic_at
+                 FROM provider_activity WHERE app_type = ?1",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    ProviderActivity {
+                        last_active_at: row.get(1)?,
+                        last_traffic_at: row.get(2)?,
+                    },
+                ))
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        rows.collect::<Result<HashMap<_, _>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
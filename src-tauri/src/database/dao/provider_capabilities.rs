@@ -0,0 +1,62 @@
+//! 供应商能力矩阵 DAO
+//!
+//! 矩阵以 JSON 存储，便于后续增加能力项而无需迁移；供应商删除时级联清理。
+
+use std::collections::HashMap;
+
+use rusqlite::params;
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::capability_probe::CapabilityMatrix;
+
+impl Database {
+    /// 保存（覆盖）供应商能力矩阵
+    pub fn save_provider_capabilities(
+        &self,
+        provider_id: &str,
+        app_type: &str,
+        matrix: &CapabilityMatrix,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(matrix)
+            .map_err(|e| AppError::Message(format!("序列化能力矩阵失败: {e}")))?;
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO provider_capabilities (provider_id, app_type, matrix, probed_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(provider_id, app_type) DO UPDATE SET
+                 matrix = excluded.matrix, probed_at = excluded.probed_at",
+            params![provider_id, app_type, json, matrix.probed_at],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 按供应商 ID 读取某应用的能力矩阵；无法解析的旧记录跳过
+    pub fn get_provider_capabilities(
+        &self,
+        app_type: &str,
+    ) -> Result<HashMap<String, CapabilityMatrix>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare("SELECT provider_id, matrix FROM provider_capabilities WHERE app_type = ?1")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut matrices = HashMap::new();
+        for row in rows {
+            let (provider_id, json) = row.map_err(|e| AppError::Database(e.to_string()))?;
+            match serde_json::from_str(&json) {
+                Ok(matrix) => {
+                    matrices.insert(provider_id, matrix);
+                }
+                Err(e) => log::warn!("[Capability] 跳过无法解析的能力矩阵 {provider_id}: {e}"),
+            }
+        }
+        Ok(matrices)
+    }
+}
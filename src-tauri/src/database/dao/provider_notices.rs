@@ -0,0 +1,129 @@
+//! 供应商变更公告 DAO
+//!
+//! 每个供应商只保留最近一次公告；内容变化时重置关闭状态，供应商删除时级联清理。
+
+use rusqlite::{params, OptionalExtension};
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::services::provider_notice::{DeprecationSignal, ProviderNotice};
+
+impl Database {
+    /// 写入公告；新公告或内容变化时返回 true（并重新标记为未关闭），
+    /// 内容不变时只刷新 last_seen_at
+    pub fn upsert_provider_notice(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        signal: &DeprecationSignal,
+        now: i64,
+    ) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let existing: Option<DeprecationSignal> = conn
+            .query_row(
+                "SELECT deprecated, deprecated_at, sunset_at, link, message
+                 FROM provider_notices WHERE provider_id = ?1 AND app_type = ?2",
+                params![provider_id, app_type],
+                |row| {
+                    Ok(DeprecationSignal {
+                        deprecated: row.get::<_, i64>(0)? != 0,
+                        deprecated_at: row.get(1)?,
+                        sunset_at: row.get(2)?,
+                        link: row.get(3)?,
+                        message: row.get(4)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        if existing.as_ref() == Some(signal) {
+            conn.execute(
+                "UPDATE provider_notices SET last_seen_at = ?3
+                 WHERE provider_id = ?1 AND app_type = ?2",
+                params![provider_id, app_type, now],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+            return Ok(false);
+        }
+
+        conn.execute(
+            "INSERT INTO provider_notices (
+                provider_id, app_type, deprecated, deprecated_at, sunset_at, link, message,
+                first_seen_at, last_seen_at, dismissed
+             ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8, 0)
+             ON CONFLICT(provider_id, app_type) DO UPDATE SET
+                 deprecated = excluded.deprecated, deprecated_at = excluded.deprecated_at,
+                 sunset_at = excluded.sunset_at, link = excluded.link, message = excluded.message,
+                 first_seen_at = excluded.first_seen_at, last_seen_at = excluded.last_seen_at,
+                 dismissed = 0",
+            params![
+                provider_id,
+                app_type,
+                signal.deprecated as i64,
+                signal.deprecated_at,
+                signal.sunset_at,
+                signal.link,
+                signal.message,
+                now,
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(true)
+    }
+
+    /// 读取公告（含已关闭的），按首次出现时间倒序
+    pub fn list_provider_notices(
+        &self,
+        app_type: Option<&str>,
+    ) -> Result<Vec<ProviderNotice>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT n.app_type, n.provider_id, COALESCE(p.name, n.provider_id),
+                        n.deprecated, n.deprecated_at, n.sunset_at, n.link, n.message,
+                        n.first_seen_at, n.last_seen_at, n.dismissed
+                 FROM provider_notices n
+                 LEFT JOIN providers p ON n.provider_id = p.id AND n.app_type = p.app_type
+                 WHERE ?1 IS NULL OR n.app_type = ?1
+                 ORDER BY n.first_seen_at DESC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(params![app_type], |row| {
+                Ok(ProviderNotice {
+                    app_type: row.get(0)?,
+                    provider_id: row.get(1)?,
+                    provider_name: row.get(2)?,
+                    signal: DeprecationSignal {
+                        deprecated: row.get::<_, i64>(3)? != 0,
+                        deprecated_at: row.get(4)?,
+                        sunset_at: row.get(5)?,
+                        link: row.get(6)?,
+                        message: row.get(7)?,
+                    },
+                    first_seen_at: row.get(8)?,
+                    last_seen_at: row.get(9)?,
+                    dismissed: row.get::<_, i64>(10)? != 0,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 关闭公告（内容变化前不再提醒）
+    pub fn dismiss_provider_notice(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "UPDATE provider_notices SET dismissed = 1 WHERE provider_id = ?1 AND app_type = ?2",
+            params![provider_id, app_type],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+}
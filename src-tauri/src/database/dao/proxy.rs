@@ -5,6 +5,7 @@
 use std::str::FromStr;
 
 use crate::error::AppError;
+use crate::proxy::auth::ProxyAuthConfig;
 use crate::proxy::types::*;
 use rust_decimal::Decimal;
 
@@ -78,20 +79,26 @@ impl Database {
         };
         // conn 已在 block 结束时释放
 
-        match result {
-            Ok(config) => Ok(config),
+        let mut config = match result {
+            Ok(config) => config,
             Err(rusqlite::Error::QueryReturnedNoRows) => {
                 // 如果不存在，创建默认配置
                 self.init_proxy_config_rows().await?;
-                Ok(GlobalProxyConfig {
+                GlobalProxyConfig {
                     proxy_enabled: false,
                     listen_address: "127.0.0.1".to_string(),
                     listen_port: 15721,
                     enable_logging: true,
-                })
+                }
             }
-            Err(e) => Err(AppError::Database(e.to_string())),
-        }
+            Err(e) => return Err(AppError::Database(e.to_string())),
+        };
+        // settings.toml / CC_SWITCH_PROXY_PORT 等部署覆盖优先于数据库
+        crate::settings_overrides::apply_proxy_listen(
+            &mut config.listen_address,
+            &mut config.listen_port,
+        );
+        Ok(config)
     }
 
     /// 更新全局代理配置（镜像写三行）
@@ -444,15 +451,20 @@ impl Database {
         };
         // conn 已在 block 结束时释放
 
-        match result {
-            Ok(config) => Ok(config),
+        let mut config = match result {
+            Ok(config) => config,
             Err(rusqlite::Error::QueryReturnedNoRows) => {
                 // 如果不存在，初始化默认配置
                 self.init_proxy_config_rows().await?;
-                Ok(ProxyConfig::default())
+                ProxyConfig::default()
             }
-            Err(e) => Err(AppError::Database(e.to_string())),
-        }
+            Err(e) => return Err(AppError::Database(e.to_string())),
+        };
+        crate::settings_overrides::apply_proxy_listen(
+            &mut config.listen_address,
+            &mut config.listen_port,
+        );
+        Ok(config)
     }
 
     /// 更新代理配置（兼容旧接口，更新所有三行的公共字段）
@@ -900,6 +912,115 @@ impl Database {
 
         Ok(())
     }
+
+    // ==================== Proxy Auth ====================
+
+    /// 获取代理鉴权配置
+    pub fn get_proxy_auth_config(&self) -> Result<ProxyAuthConfig, AppError> {
+        match self.get_setting("proxy_auth_config")? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Message(format!("解析配置失败: {e}"))),
+            None => Ok(ProxyAuthConfig::default()),
+        }
+    }
+
+    /// 保存代理鉴权配置
+    pub fn save_proxy_auth_config(&self, config: &ProxyAuthConfig) -> Result<(), AppError> {
+        let json = serde_json::to_string(config)
+            .map_err(|e| AppError::Message(format!("序列化配置失败: {e}")))?;
+        self.set_setting("proxy_auth_config", &json)
+    }
+
+    /// 列出访问令牌（按创建时间升序，第一个为写入客户端配置的令牌）
+    pub fn list_proxy_auth_tokens(&self) -> Result<Vec<ProxyAuthToken>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, token, created_at, last_used_at FROM proxy_auth_tokens
+                 ORDER BY created_at ASC, id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(ProxyAuthToken {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    token: row.get(2)?,
+                    created_at: row.get(3)?,
+                    last_used_at: row.get(4)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    pub fn insert_proxy_auth_token(&self, token: &ProxyAuthToken) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT INTO proxy_auth_tokens (id, name, token, created_at, last_used_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                token.id,
+                token.name,
+                token.token,
+                token.created_at,
+                token.last_used_at
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 更换令牌值（轮换），返回是否存在该令牌
+    pub fn update_proxy_auth_token_secret(&self, id: &str, token: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute(
+                "UPDATE proxy_auth_tokens SET token = ?2 WHERE id = ?1",
+                rusqlite::params![id, token],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
+    pub fn delete_proxy_auth_token(&self, id: &str) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute("DELETE FROM proxy_auth_tokens WHERE id = ?1", [id])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
+    /// 按令牌汇总请求日志（只覆盖明细日志保留期内的数据）
+    pub fn get_proxy_auth_token_usage(
+        &self,
+        since: Option<i64>,
+    ) -> Result<Vec<ProxyAuthTokenUsage>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT auth_token_id, COUNT(*),
+                        COALESCE(SUM(input_tokens + output_tokens + cache_read_tokens + cache_creation_tokens), 0),
+                        COALESCE(SUM(CAST(total_cost_usd AS REAL)), 0)
+                 FROM proxy_request_logs
+                 WHERE auth_token_id IS NOT NULL AND created_at >= ?1
+                 GROUP BY auth_token_id",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([since.unwrap_or(0)], |row| {
+                Ok(ProxyAuthTokenUsage {
+                    token_id: row.get(0)?,
+                    request_count: row.get::<_, i64>(1)? as u64,
+                    total_tokens: row.get::<_, i64>(2)? as u64,
+                    total_cost: format!("{:.6}", row.get::<_, f64>(3)?),
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
 }
 
 #[cfg(test)]
@@ -33,6 +33,19 @@ impl Database {
         }
     }
 
+    /// 获取全部设置项（按键名排序）
+    pub fn get_all_settings(&self) -> Result<Vec<(String, String)>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare("SELECT key, value FROM settings ORDER BY key")
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
     /// 以布尔语义读取 flag：`"true"` 或 `"1"` → true，其它全部 false。
     ///
     /// 用于一次性启动 flag（`official_providers_seeded` / `first_run_notice_shown` 等）。
@@ -56,6 +69,37 @@ impl Database {
         Ok(())
     }
 
+    /// 删除设置项
+    pub fn delete_setting(&self, key: &str) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute("DELETE FROM settings WHERE key = ?1", params![key])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    // --- 供应商级代理覆盖 (Per-provider proxy override) ---
+
+    fn provider_proxy_override_key(app_type: &str) -> String {
+        format!("provider_proxy_override_{app_type}")
+    }
+
+    /// 当前应用的 Live 接管是否由供应商级代理覆盖（`meta.useLocalProxy`）发起
+    pub fn is_provider_proxy_override_active(&self, app_type: &str) -> Result<bool, AppError> {
+        self.get_bool_flag(&Self::provider_proxy_override_key(app_type))
+    }
+
+    /// 标记/清除供应商级代理覆盖发起的接管
+    pub fn set_provider_proxy_override_active(
+        &self,
+        app_type: &str,
+        active: bool,
+    ) -> Result<(), AppError> {
+        self.set_setting(
+            &Self::provider_proxy_override_key(app_type),
+            if active { "true" } else { "false" },
+        )
+    }
+
     // --- 通用配置片段 (Common Config Snippet) ---
 
     /// 获取通用配置片段
@@ -259,6 +303,77 @@ impl Database {
         self.set_setting("rectifier_config", &json)
     }
 
+    // --- 请求内重试配置 ---
+
+    /// 获取请求内重试配置
+    ///
+    /// 返回重试配置，如果不存在则返回默认值（开启，同供应商重试 1 次）
+    pub fn get_retry_config(&self) -> Result<crate::proxy::types::RetryConfig, AppError> {
+        match self.get_setting("retry_config")? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Database(format!("解析重试配置失败: {e}"))),
+            None => Ok(crate::proxy::types::RetryConfig::default()),
+        }
+    }
+
+    /// 更新请求内重试配置
+    pub fn set_retry_config(
+        &self,
+        config: &crate::proxy::types::RetryConfig,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(config)
+            .map_err(|e| AppError::Database(format!("序列化重试配置失败: {e}")))?;
+        self.set_setting("retry_config", &json)
+    }
+
+    // --- 优雅排空配置 ---
+
+    /// 获取优雅排空配置
+    ///
+    /// 返回排空配置，如果不存在则返回默认值（开启，超时 30 秒）
+    pub fn get_drain_config(&self) -> Result<crate::proxy::types::DrainConfig, AppError> {
+        match self.get_setting("drain_config")? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Database(format!("解析排空配置失败: {e}"))),
+            None => Ok(crate::proxy::types::DrainConfig::default()),
+        }
+    }
+
+    /// 更新优雅排空配置
+    pub fn set_drain_config(
+        &self,
+        config: &crate::proxy::types::DrainConfig,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(config)
+            .map_err(|e| AppError::Database(format!("序列化排空配置失败: {e}")))?;
+        self.set_setting("drain_config", &json)
+    }
+
+    // --- 自动切换看门狗配置 ---
+
+    /// 获取自动切走 / 切回看门狗配置
+    ///
+    /// 返回看门狗配置，如果不存在则返回默认值（默认关闭）
+    pub fn get_failback_watchdog_config(
+        &self,
+    ) -> Result<crate::proxy::types::FailbackWatchdogConfig, AppError> {
+        match self.get_setting("failback_watchdog_config")? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Database(format!("解析看门狗配置失败: {e}"))),
+            None => Ok(crate::proxy::types::FailbackWatchdogConfig::default()),
+        }
+    }
+
+    /// 更新自动切走 / 切回看门狗配置
+    pub fn set_failback_watchdog_config(
+        &self,
+        config: &crate::proxy::types::FailbackWatchdogConfig,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(config)
+            .map_err(|e| AppError::Database(format!("序列化看门狗配置失败: {e}")))?;
+        self.set_setting("failback_watchdog_config", &json)
+    }
+
     // --- 优化器配置 ---
 
     /// 获取优化器配置
@@ -282,6 +397,29 @@ impl Database {
         self.set_setting("optimizer_config", &json)
     }
 
+    // --- 响应缓存配置 ---
+
+    /// 获取响应缓存配置，不存在时返回默认值（默认关闭）
+    pub fn get_response_cache_config(
+        &self,
+    ) -> Result<crate::proxy::types::ResponseCacheConfig, AppError> {
+        match self.get_setting("response_cache_config")? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Database(format!("解析响应缓存配置失败: {e}"))),
+            None => Ok(crate::proxy::types::ResponseCacheConfig::default()),
+        }
+    }
+
+    /// 更新响应缓存配置
+    pub fn set_response_cache_config(
+        &self,
+        config: &crate::proxy::types::ResponseCacheConfig,
+    ) -> Result<(), AppError> {
+        let json = serde_json::to_string(config)
+            .map_err(|e| AppError::Database(format!("序列化响应缓存配置失败: {e}")))?;
+        self.set_setting("response_cache_config", &json)
+    }
+
     // --- Copilot 优化器配置 ---
 
     /// 获取 Copilot 优化器配置
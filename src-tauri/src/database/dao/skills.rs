@@ -9,7 +9,7 @@
 use crate::app_config::{InstalledSkill, SkillApps};
 use crate::database::{lock_conn, Database};
 use crate::error::AppError;
-use crate::services::skill::SkillRepo;
+use crate::services::skill::{ProjectSkillInstall, SkillRepo, SkillRepoSource};
 use indexmap::IndexMap;
 use rusqlite::params;
 
@@ -184,6 +184,91 @@ impl Database {
         Ok(affected > 0)
     }
 
+    // ========== 项目级安装记录 ==========
+
+    /// 获取项目级 Skill 安装记录（可按项目路径过滤）
+    pub fn get_project_skill_installs(
+        &self,
+        project_path: Option<&str>,
+    ) -> Result<Vec<ProjectSkillInstall>, AppError> {
+        let conn = lock_conn!(self.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT project_path, skill_id, app_type, installed_at
+                 FROM skill_project_installs
+                 WHERE ?1 IS NULL OR project_path = ?1
+                 ORDER BY project_path ASC, skill_id ASC, app_type ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let install_iter = stmt
+            .query_map(params![project_path], |row| {
+                Ok(ProjectSkillInstall {
+                    project_path: row.get(0)?,
+                    skill_id: row.get(1)?,
+                    app: row.get(2)?,
+                    installed_at: row.get(3)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut installs = Vec::new();
+        for install_res in install_iter {
+            installs.push(install_res.map_err(|e| AppError::Database(e.to_string()))?);
+        }
+        Ok(installs)
+    }
+
+    /// 获取某个 Skill 的全部项目级安装记录
+    pub fn get_project_installs_for_skill(
+        &self,
+        skill_id: &str,
+    ) -> Result<Vec<ProjectSkillInstall>, AppError> {
+        Ok(self
+            .get_project_skill_installs(None)?
+            .into_iter()
+            .filter(|install| install.skill_id == skill_id)
+            .collect())
+    }
+
+    /// 保存项目级 Skill 安装记录
+    pub fn save_project_skill_install(
+        &self,
+        install: &ProjectSkillInstall,
+    ) -> Result<(), AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.execute(
+            "INSERT OR REPLACE INTO skill_project_installs (project_path, skill_id, app_type, installed_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                install.project_path,
+                install.skill_id,
+                install.app,
+                install.installed_at
+            ],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// 删除项目级 Skill 安装记录
+    pub fn delete_project_skill_install(
+        &self,
+        project_path: &str,
+        skill_id: &str,
+        app_type: &str,
+    ) -> Result<bool, AppError> {
+        let conn = lock_conn!(self.conn);
+        let affected = conn
+            .execute(
+                "DELETE FROM skill_project_installs
+                 WHERE project_path = ?1 AND skill_id = ?2 AND app_type = ?3",
+                params![project_path, skill_id, app_type],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(affected > 0)
+    }
+
     // ========== SkillRepo CRUD（保持原有） ==========
 
     /// 获取所有 Skill 仓库
@@ -191,7 +276,8 @@ impl Database {
         let conn = lock_conn!(self.conn);
         let mut stmt = conn
             .prepare(
-                "SELECT owner, name, branch, enabled FROM skill_repos ORDER BY owner ASC, name ASC",
+                "SELECT owner, name, branch, enabled, source, location, access_token
+                 FROM skill_repos ORDER BY owner ASC, name ASC",
             )
             .map_err(|e| AppError::Database(e.to_string()))?;
 
@@ -202,6 +288,9 @@ impl Database {
                     name: row.get(1)?,
                     branch: row.get(2)?,
                     enabled: row.get(3)?,
+                    source: SkillRepoSource::parse(&row.get::<_, String>(4)?),
+                    location: row.get(5)?,
+                    access_token: row.get(6)?,
                 })
             })
             .map_err(|e| AppError::Database(e.to_string()))?;
@@ -217,8 +306,18 @@ impl Database {
     pub fn save_skill_repo(&self, repo: &SkillRepo) -> Result<(), AppError> {
         let conn = lock_conn!(self.conn);
         conn.execute(
-            "INSERT OR REPLACE INTO skill_repos (owner, name, branch, enabled) VALUES (?1, ?2, ?3, ?4)",
-            params![repo.owner, repo.name, repo.branch, repo.enabled],
+            "INSERT OR REPLACE INTO skill_repos
+             (owner, name, branch, enabled, source, location, access_token)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                repo.owner,
+                repo.name,
+                repo.branch,
+                repo.enabled,
+                repo.source.as_str(),
+                repo.location,
+                repo.access_token
+            ],
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
         Ok(())
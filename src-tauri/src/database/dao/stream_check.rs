@@ -2,6 +2,7 @@
 
 use crate::database::{lock_conn, Database};
 use crate::error::AppError;
+use crate::services::self_test::{SelfTestConfig, SelfTestReport};
 use crate::services::stream_check::{StreamCheckConfig, StreamCheckResult};
 
 impl Database {
@@ -71,4 +72,37 @@ impl Database {
             .map_err(|e| AppError::Message(format!("序列化配置失败: {e}")))?;
         self.set_setting("stream_check_config", &json)
     }
+
+    /// 获取夜间自检配置
+    pub fn get_self_test_config(&self) -> Result<SelfTestConfig, AppError> {
+        match self.get_setting("self_test_config")? {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| AppError::Message(format!("解析配置失败: {e}"))),
+            None => Ok(SelfTestConfig::default()),
+        }
+    }
+
+    /// 保存夜间自检配置
+    pub fn save_self_test_config(&self, config: &SelfTestConfig) -> Result<(), AppError> {
+        let json = serde_json::to_string(config)
+            .map_err(|e| AppError::Message(format!("序列化配置失败: {e}")))?;
+        self.set_setting("self_test_config", &json)
+    }
+
+    /// 获取最近一次夜间自检报告
+    pub fn get_self_test_report(&self) -> Result<Option<SelfTestReport>, AppError> {
+        match self.get_setting("self_test_last_report")? {
+            Some(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| AppError::Message(format!("解析自检报告失败: {e}"))),
+            None => Ok(None),
+        }
+    }
+
+    /// 保存夜间自检报告（只保留最近一次）
+    pub fn save_self_test_report(&self, report: &SelfTestReport) -> Result<(), AppError> {
+        let json = serde_json::to_string(report)
+            .map_err(|e| AppError::Message(format!("序列化自检报告失败: {e}")))?;
+        self.set_setting("self_test_last_report", &json)
+    }
 }
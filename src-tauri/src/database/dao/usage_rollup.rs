@@ -8,6 +8,9 @@ use crate::services::sql_helpers::{fresh_input_sql, INPUT_TOKEN_SEMANTICS_FRESH}
 use crate::services::usage_stats::effective_usage_log_filter;
 use chrono::{Duration, Local, TimeZone};
 
+/// 请求明细的保留天数，更早的明细归档进日汇总
+pub(crate) const DETAIL_RETAIN_DAYS: i64 = 30;
+
 /// Compute the rollup/prune cutoff aligned to a local-day boundary.
 ///
 /// Anything strictly older than the returned timestamp will be aggregated into
@@ -55,6 +58,11 @@ fn compute_local_midnight_cutoff(
     Ok(local_dt.timestamp())
 }
 
+/// 当前的明细保留边界：早于该时间戳（秒）的明细会在下次维护时被归档删除
+pub(crate) fn detail_retention_cutoff() -> Result<i64, AppError> {
+    compute_local_midnight_cutoff(Local::now(), DETAIL_RETAIN_DAYS)
+}
+
 impl Database {
     /// Aggregate proxy_request_logs older than `retain_days` into usage_daily_rollups,
     /// then delete the aggregated detail rows.
@@ -0,0 +1,265 @@
+//! Usage sync DAO
+//!
+//! 云同步的用量增量读写：按插入顺序（rowid）水位导出新增的请求明细，
+//! 合并远端明细时按 `request_id` 去重。
+//!
+//! 水位不用 `created_at`：会话日志导入的行带原始消息时间，通常早于上次同步的水位，
+//! 按时间导出会漏掉这些行。
+
+use rusqlite::types::ValueRef;
+use rusqlite::{params_from_iter, Connection};
+use serde_json::{Map, Number, Value};
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+
+/// 一行请求明细：列名 → 值，按列名写回以兼容两端表结构的差异
+pub type UsageRow = Map<String, Value>;
+
+/// 一次导出的明细
+#[derive(Debug, Default)]
+pub struct UsageExport {
+    pub rows: Vec<UsageRow>,
+    /// 导出时表中最大的 rowid（空表为 0），作为下次导出的水位
+    pub high_water: i64,
+}
+
+/// 一次合并的结果
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct UsageMerge {
+    /// 实际新增的行数
+    pub inserted: usize,
+    /// 合并前表中最大的 rowid
+    pub high_water_before: i64,
+    /// 合并后表中最大的 rowid
+    pub high_water_after: i64,
+}
+
+const USAGE_TABLE: &str = "proxy_request_logs";
+
+fn table_columns(conn: &Connection) -> Result<Vec<String>, AppError> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info(\"{USAGE_TABLE}\")"))
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(|e| AppError::Database(e.to_string()))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Database(e.to_string()))?;
+    Ok(columns)
+}
+
+fn high_water(conn: &Connection) -> Result<i64, AppError> {
+    conn.query_row(
+        &format!("SELECT COALESCE(MAX(rowid), 0) FROM {USAGE_TABLE}"),
+        [],
+        |row| row.get(0),
+    )
+    .map_err(|e| AppError::Database(e.to_string()))
+}
+
+fn to_json(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null | ValueRef::Blob(_) => Value::Null,
+        ValueRef::Integer(i) => Value::from(i),
+        ValueRef::Real(f) => Number::from_f64(f).map_or(Value::Null, Value::Number),
+        ValueRef::Text(text) => Value::String(String::from_utf8_lossy(text).into_owned()),
+    }
+}
+
+fn to_sql(value: &Value) -> rusqlite::types::Value {
+    use rusqlite::types::Value as Sql;
+    match value {
+        Value::Null => Sql::Null,
+        Value::Bool(b) => Sql::Integer(i64::from(*b)),
+        Value::Number(n) => n
+            .as_i64()
+            .map(Sql::Integer)
+            .or_else(|| n.as_f64().map(Sql::Real))
+            .unwrap_or(Sql::Null),
+        Value::String(s) => Sql::Text(s.clone()),
+        other => Sql::Text(other.to_string()),
+    }
+}
+
+impl Database {
+    /// 导出 rowid 大于 `after`、`created_at` 不早于 `not_before`（秒）的请求明细，按插入顺序
+    ///
+    /// `after` 超过表中最大 rowid 时（表被清空后 rowid 重新分配，或水位来自按时间记录的
+    /// 旧版本）从头导出，重复的行在合并时按 `request_id` 去重。
+    pub fn usage_rows_after(&self, after: i64, not_before: i64) -> Result<UsageExport, AppError> {
+        let conn = lock_conn!(self.conn);
+        let columns = table_columns(&conn)?;
+        let high_water = high_water(&conn)?;
+        let after = if after > high_water { 0 } else { after };
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT * FROM {USAGE_TABLE} WHERE rowid > ?1 AND created_at >= ?2 ORDER BY rowid"
+            ))
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let mut rows = stmt
+            .query([after, not_before])
+            .map_err(|e| AppError::Database(e.to_string()))?;
+
+        let mut out = Vec::new();
+        while let Some(row) = rows.next().map_err(|e| AppError::Database(e.to_string()))? {
+            let mut item = Map::new();
+            for (idx, column) in columns.iter().enumerate() {
+                let value = row
+                    .get_ref(idx)
+                    .map_err(|e| AppError::Database(e.to_string()))?;
+                item.insert(column.clone(), to_json(value));
+            }
+            out.push(item);
+        }
+        Ok(UsageExport {
+            rows: out,
+            high_water,
+        })
+    }
+
+    /// 合并远端明细
+    ///
+    /// 合并在同一事务内完成并返回前后的最大 rowid，调用方据此判断合并前是否有待上传的行。
+    /// 已存在的 `request_id` 保持不动；早于 `not_before` 的行跳过——本机已把
+    /// 那段时间的明细归档进日汇总，再写入会在下次归档时重复计数。
+    /// 只写入本地表存在的列，旧版本导出的行缺列时由默认值补齐。
+    pub fn merge_usage_rows(
+        &self,
+        rows: &[UsageRow],
+        not_before: i64,
+    ) -> Result<UsageMerge, AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let columns = table_columns(&conn)?;
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let high_water_before = high_water(&tx)?;
+
+        let mut inserted = 0;
+        for row in rows {
+            let created_at = row.get("created_at").and_then(Value::as_i64);
+            if row.get("request_id").and_then(Value::as_str).is_none()
+                || !created_at.is_some_and(|ts| ts >= not_before)
+            {
+                continue;
+            }
+            let present: Vec<&String> = columns.iter().filter(|c| row.contains_key(*c)).collect();
+            let names = present
+                .iter()
+                .map(|c| format!("\"{c}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let placeholders = (1..=present.len())
+                .map(|idx| format!("?{idx}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let values = present.iter().map(|c| to_sql(&row[c.as_str()]));
+            inserted += tx
+                .execute(
+                    &format!(
+                        "INSERT OR IGNORE INTO {USAGE_TABLE} ({names}) VALUES ({placeholders})"
+                    ),
+                    params_from_iter(values),
+                )
+                .map_err(|e| AppError::Database(format!("合并用量明细失败: {e}")))?;
+        }
+
+        let high_water_after = high_water(&tx)?;
+        tx.commit().map_err(|e| AppError::Database(e.to_string()))?;
+        if inserted > 0 {
+            crate::usage_events::notify_log_recorded();
+        }
+        Ok(UsageMerge {
+            inserted,
+            high_water_before,
+            high_water_after,
+        })
+    }
+}
+
+/// 测试用：写入一行最简的请求明细
+#[cfg(test)]
+pub(crate) fn insert_test_log(db: &Database, request_id: &str, created_at: i64) {
+    let conn = db.conn.lock().unwrap();
+    conn.execute(
+        "INSERT INTO proxy_request_logs (
+            request_id, provider_id, app_type, model,
+            input_tokens, output_tokens, total_cost_usd,
+            latency_ms, status_code, created_at
+        ) VALUES (?1, 'p1', 'claude', 'claude-sonnet-4-5', 100, 50, '0.01', 120, 200, ?2)",
+        rusqlite::params![request_id, created_at],
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exports_rows_after_watermark_and_merges_idempotently() {
+        let source = Database::memory().unwrap();
+        insert_test_log(&source, "req-1", 1_000);
+        insert_test_log(&source, "req-2", 2_000);
+        let first = source.usage_rows_after(0, 0).unwrap();
+        assert_eq!(first.rows.len(), 2);
+        assert_eq!(first.rows[0]["total_cost_usd"], "0.01");
+
+        // 会话日志导入的行带原始时间，早于已导出的行，仍按插入顺序导出
+        insert_test_log(&source, "imported", 500);
+        insert_test_log(&source, "req-3", 3_000);
+        let export = source.usage_rows_after(first.high_water, 0).unwrap();
+        let ids: Vec<_> = export
+            .rows
+            .iter()
+            .map(|r| r["request_id"].clone())
+            .collect();
+        assert_eq!(ids, vec!["imported", "req-3"]);
+        assert!(export.high_water > first.high_water);
+
+        let target = Database::memory().unwrap();
+        insert_test_log(&target, "req-3", 3_000);
+        let merge = target.merge_usage_rows(&export.rows, 0).unwrap();
+        assert_eq!(merge.inserted, 1);
+        assert_eq!(merge.high_water_after, merge.high_water_before + 1);
+        assert_eq!(
+            target.merge_usage_rows(&export.rows, 0).unwrap().inserted,
+            0
+        );
+        assert_eq!(target.usage_rows_after(0, 0).unwrap().rows.len(), 2);
+    }
+
+    #[test]
+    fn stale_watermark_beyond_table_exports_from_start() {
+        let db = Database::memory().unwrap();
+        insert_test_log(&db, "req-1", 1_000);
+        // 旧版本按秒记录的水位远大于 rowid
+        let export = db.usage_rows_after(1_700_000_000, 0).unwrap();
+        assert_eq!(export.rows.len(), 1);
+        assert_eq!(
+            db.usage_rows_after(export.high_water, 0)
+                .unwrap()
+                .rows
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn merge_skips_rows_older_than_rollup_horizon() {
+        let source = Database::memory().unwrap();
+        insert_test_log(&source, "old", 500);
+        insert_test_log(&source, "new", 5_000);
+        let mut rows = source.usage_rows_after(0, 0).unwrap().rows;
+        // 来自更旧版本、缺少部分列的行同样可以写入
+        rows[1].remove("data_source");
+
+        let target = Database::memory().unwrap();
+        assert_eq!(target.merge_usage_rows(&rows, 1_000).unwrap().inserted, 1);
+        let merged = target.usage_rows_after(0, 0).unwrap().rows;
+        assert_eq!(merged[0]["request_id"], "new");
+        assert_eq!(merged[0]["data_source"], "proxy");
+        assert_eq!(source.usage_rows_after(0, 1_000).unwrap().rows.len(), 1);
+    }
+}
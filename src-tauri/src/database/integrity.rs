@@ -0,0 +1,380 @@
+//! 数据库完整性检查与修复
+//!
+//! 供 `cc-switch doctor` 使用：检查 SQLite 页级损坏、外键悬空、
+//! 无法识别的供应商、重复的当前供应商以及无法解析的 JSON 列。
+//! 能安全自动处理的问题标记为可修复，其余只报告、由用户手动处理。
+
+use std::str::FromStr;
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+
+use super::{lock_conn, AppliedMigration, Database, SCHEMA_VERSION};
+use crate::app_config::AppType;
+use crate::error::AppError;
+
+/// 问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityIssueKind {
+    /// SQLite 页级损坏（`PRAGMA quick_check` 未通过）
+    Corruption,
+    /// 外键指向不存在的父行
+    ForeignKey,
+    /// 供应商的 app_type 不被当前版本识别
+    OrphanedProvider,
+    /// 同一应用存在多个当前供应商
+    DuplicateCurrent,
+    /// 列中存储的 JSON 无法解析
+    InvalidJson,
+}
+
+/// 自动修复动作
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Repair {
+    DeleteRow {
+        table: &'static str,
+        rowid: i64,
+    },
+    ResetColumn {
+        table: &'static str,
+        column: &'static str,
+        rowid: i64,
+        value: &'static str,
+    },
+    KeepCurrent {
+        app_type: String,
+        rowid: i64,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityIssue {
+    pub kind: IntegrityIssueKind,
+    pub table: String,
+    /// 出问题的行（`app/id`、主键或 rowid）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    pub detail: String,
+    pub repairable: bool,
+    #[serde(skip)]
+    repair: Option<Repair>,
+}
+
+impl IntegrityIssue {
+    fn new(
+        kind: IntegrityIssueKind,
+        table: &str,
+        key: Option<String>,
+        detail: String,
+        repair: Option<Repair>,
+    ) -> Self {
+        Self {
+            kind,
+            table: table.to_string(),
+            key,
+            detail,
+            repairable: repair.is_some(),
+            repair,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    pub schema_version: i32,
+    pub supported_version: i32,
+    pub migrations: Vec<AppliedMigration>,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    pub fn is_healthy(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn repairable_count(&self) -> usize {
+        self.issues.iter().filter(|issue| issue.repairable).count()
+    }
+}
+
+/// JSON 列损坏时的处理方式
+#[derive(Clone, Copy)]
+enum JsonFix {
+    /// 核心配置，自动改写会丢数据，只报告
+    Manual,
+    /// 重置为空值
+    Reset(&'static str),
+    /// 删除整行（可重新生成的缓存数据）
+    DeleteRow,
+}
+
+/// 需要校验的 JSON 列：(表, 行标识表达式, 列, 修复方式)
+const JSON_COLUMNS: &[(&str, &str, &str, JsonFix)] = &[
+    (
+        "providers",
+        "app_type || '/' || id",
+        "settings_config",
+        JsonFix::Manual,
+    ),
+    (
+        "providers",
+        "app_type || '/' || id",
+        "meta",
+        JsonFix::Reset("{}"),
+    ),
+    ("mcp_servers", "id", "server_config", JsonFix::Manual),
+    ("mcp_servers", "id", "tags", JsonFix::Reset("[]")),
+    ("profiles", "id", "payload", JsonFix::Manual),
+    (
+        "provider_capabilities",
+        "app_type || '/' || provider_id",
+        "matrix",
+        JsonFix::DeleteRow,
+    ),
+];
+
+fn db_err(e: rusqlite::Error) -> AppError {
+    AppError::Database(e.to_string())
+}
+
+impl Database {
+    /// 检查数据库完整性（只读）
+    pub fn check_integrity(&self) -> Result<IntegrityReport, AppError> {
+        let migrations = self.applied_migrations()?;
+        let conn = lock_conn!(self.conn);
+        let mut issues = Vec::new();
+        Self::check_corruption(&conn, &mut issues)?;
+        Self::check_foreign_keys(&conn, &mut issues)?;
+        Self::check_providers(&conn, &mut issues)?;
+        Self::check_json_columns(&conn, &mut issues)?;
+        Ok(IntegrityReport {
+            schema_version: Self::get_user_version(&conn)?,
+            supported_version: SCHEMA_VERSION,
+            migrations,
+            issues,
+        })
+    }
+
+    /// 在一个事务内修复报告中可自动修复的问题，返回修复的条数
+    ///
+    /// 调用方应先做快照备份；页级损坏无法在此修复。
+    pub fn repair_integrity(&self, report: &IntegrityReport) -> Result<usize, AppError> {
+        let mut conn = lock_conn!(self.conn);
+        let tx = conn.transaction().map_err(db_err)?;
+        let mut repaired = 0;
+        for repair in report
+            .issues
+            .iter()
+            .filter_map(|issue| issue.repair.as_ref())
+        {
+            let changed = match repair {
+                Repair::DeleteRow { table, rowid } => tx
+                    .execute(&format!("DELETE FROM {table} WHERE rowid = ?1"), [rowid])
+                    .map_err(db_err)?,
+                Repair::ResetColumn {
+                    table,
+                    column,
+                    rowid,
+                    value,
+                } => tx
+                    .execute(
+                        &format!("UPDATE {table} SET {column} = ?1 WHERE rowid = ?2"),
+                        params![value, rowid],
+                    )
+                    .map_err(db_err)?,
+                Repair::KeepCurrent { app_type, rowid } => tx
+                    .execute(
+                        "UPDATE providers SET is_current = 0
+                         WHERE app_type = ?1 AND is_current = 1 AND rowid != ?2",
+                        params![app_type, rowid],
+                    )
+                    .map_err(db_err)?,
+            };
+            if changed > 0 {
+                repaired += 1;
+            }
+        }
+        tx.commit().map_err(db_err)?;
+        Ok(repaired)
+    }
+
+    fn check_corruption(
+        conn: &Connection,
+        issues: &mut Vec<IntegrityIssue>,
+    ) -> Result<(), AppError> {
+        let mut stmt = conn.prepare("PRAGMA quick_check").map_err(db_err)?;
+        let messages = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(db_err)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_err)?;
+        for message in messages.into_iter().filter(|m| m != "ok") {
+            issues.push(IntegrityIssue::new(
+                IntegrityIssueKind::Corruption,
+                "sqlite",
+                None,
+                message,
+                None,
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_foreign_keys(
+        conn: &Connection,
+        issues: &mut Vec<IntegrityIssue>,
+    ) -> Result<(), AppError> {
+        let mut stmt = conn.prepare("PRAGMA foreign_key_check").map_err(db_err)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, Option<i64>>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(db_err)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_err)?;
+        for (table, rowid, parent) in rows {
+            // 只自动删除已知的附属表行，避免误删未知表的数据
+            let repair = rowid.and_then(|rowid| {
+                FK_CHILD_TABLES
+                    .iter()
+                    .copied()
+                    .find(|known| *known == table)
+                    .map(|table| Repair::DeleteRow { table, rowid })
+            });
+            issues.push(IntegrityIssue::new(
+                IntegrityIssueKind::ForeignKey,
+                &table,
+                rowid.map(|rowid| format!("rowid {rowid}")),
+                format!("references a missing row in {parent}"),
+                repair,
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_providers(
+        conn: &Connection,
+        issues: &mut Vec<IntegrityIssue>,
+    ) -> Result<(), AppError> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT rowid, id, app_type, is_current FROM providers
+                 ORDER BY app_type, COALESCE(sort_index, 999999), rowid",
+            )
+            .map_err(db_err)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, bool>(3)?,
+                ))
+            })
+            .map_err(db_err)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_err)?;
+
+        let mut current: Vec<(String, i64, Vec<String>)> = Vec::new();
+        for (rowid, id, app_type, is_current) in rows {
+            // 可能是更新版本写入的应用类型（降级或多版本共用数据目录），只报告不删除
+            if AppType::from_str(&app_type).is_err() {
+                issues.push(IntegrityIssue::new(
+                    IntegrityIssueKind::OrphanedProvider,
+                    "providers",
+                    Some(format!("{app_type}/{id}")),
+                    format!("unknown app type '{app_type}'"),
+                    None,
+                ));
+                continue;
+            }
+            if !is_current {
+                continue;
+            }
+            match current.iter_mut().find(|(app, _, _)| *app == app_type) {
+                Some((_, _, ids)) => ids.push(id),
+                None => current.push((app_type, rowid, vec![id])),
+            }
+        }
+        let duplicates = current.into_iter().filter(|(_, _, ids)| ids.len() > 1);
+        for (app_type, keep_rowid, ids) in duplicates {
+            issues.push(IntegrityIssue::new(
+                IntegrityIssueKind::DuplicateCurrent,
+                "providers",
+                Some(app_type.clone()),
+                format!("{} providers marked current: {}", ids.len(), ids.join(", ")),
+                Some(Repair::KeepCurrent {
+                    app_type,
+                    rowid: keep_rowid,
+                }),
+            ));
+        }
+        Ok(())
+    }
+
+    fn check_json_columns(
+        conn: &Connection,
+        issues: &mut Vec<IntegrityIssue>,
+    ) -> Result<(), AppError> {
+        for &(table, key_expr, column, fix) in JSON_COLUMNS {
+            if !Self::table_exists(conn, table)? || !Self::has_column(conn, table, column)? {
+                continue;
+            }
+            let mut stmt = conn
+                .prepare(&format!("SELECT rowid, {key_expr}, {column} FROM {table}"))
+                .map_err(db_err)?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                })
+                .map_err(db_err)?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(db_err)?;
+            for (rowid, key, value) in rows {
+                let Err(e) =
+                    serde_json::from_str::<serde_json::Value>(value.as_deref().unwrap_or(""))
+                else {
+                    continue;
+                };
+                let repair = match fix {
+                    JsonFix::Manual => None,
+                    JsonFix::Reset(value) => Some(Repair::ResetColumn {
+                        table,
+                        column,
+                        rowid,
+                        value,
+                    }),
+                    JsonFix::DeleteRow => Some(Repair::DeleteRow { table, rowid }),
+                };
+                issues.push(IntegrityIssue::new(
+                    IntegrityIssueKind::InvalidJson,
+                    table,
+                    Some(key),
+                    format!("{column}: {e}"),
+                    repair,
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 带外键的附属表；悬空行没有意义，可以直接删除
+const FK_CHILD_TABLES: &[&str] = &[
+    "provider_endpoints",
+    "provider_health",
+    "provider_capabilities",
+    "provider_activity",
+    "provider_notices",
+];
@@ -15,6 +15,7 @@
 //! ├── schema.rs     - 表结构定义 + Schema 迁移
 //! ├── backup.rs     - SQL 导入导出 + 快照备份
 //! ├── migration.rs  - JSON → SQLite 数据迁移
+//! ├── integrity.rs  - 完整性检查与修复（cc-switch doctor）
 //! └── dao/          - 数据访问对象
 //!     ├── providers.rs
 //!     ├── mcp.rs
@@ -25,6 +26,7 @@
 
 pub(crate) mod backup;
 mod dao;
+mod integrity;
 mod migration;
 mod schema;
 
@@ -40,20 +42,31 @@ pub(crate) use dao::proxy::{
     validate_cost_multiplier, validate_pricing_source, PRICING_SOURCE_REQUEST,
     PRICING_SOURCE_RESPONSE,
 };
+pub(crate) use dao::usage_rollup::detail_retention_cutoff;
+#[cfg(test)]
+pub(crate) use dao::usage_sync::insert_test_log;
 pub use dao::FailoverQueueItem;
 pub use dao::Profile;
+pub use dao::ProviderActivity;
+pub use dao::UsageRow;
+pub use integrity::{IntegrityIssue, IntegrityIssueKind, IntegrityReport};
+pub use schema::AppliedMigration;
 
 use crate::config::get_app_config_dir;
 use crate::error::AppError;
 use rusqlite::{hooks::Action, Connection};
 use serde::Serialize;
 use std::sync::Mutex;
+use std::time::Duration;
 
 // DAO 方法通过 impl Database 提供，无需额外导出
 
 /// 当前 Schema 版本号
 /// 每次修改表结构时递增，并在 schema.rs 中添加相应的迁移逻辑
-pub(crate) const SCHEMA_VERSION: i32 = 16;
+pub(crate) const SCHEMA_VERSION: i32 = 27;
+
+/// 数据库被其他进程锁定时的最长等待时间（SQLite 在此期间自动退避重试）
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// 安全地序列化 JSON，避免 unwrap panic
 pub(crate) fn to_json_string<T: Serialize>(value: &T) -> Result<String, AppError> {
@@ -85,6 +98,7 @@ fn register_db_change_hook(conn: &Connection) {
     conn.update_hook(Some(
         |action: Action, _database: &str, table: &str, _row_id: i64| match action {
             Action::SQLITE_INSERT | Action::SQLITE_UPDATE | Action::SQLITE_DELETE => {
+                crate::config_watcher::note_db_self_write();
                 crate::services::webdav_auto_sync::notify_db_changed(table);
                 crate::services::s3_auto_sync::notify_db_changed(table);
             }
@@ -101,12 +115,20 @@ impl Database {
         let db_path = get_app_config_dir().join("cc-switch.db");
         let db_exists = db_path.exists();
 
+        // 共享机器上拒绝打开其他用户的数据目录，避免文件归属被改写或并发损坏
+        if let Some(parent) = db_path.parent() {
+            crate::os_user::ensure_own_data_dir(parent)?;
+        }
+
         // 确保父目录存在
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
         }
 
         let conn = Connection::open(&db_path).map_err(|e| AppError::Database(e.to_string()))?;
+        // GUI 与 CLI 可能同时打开数据库：遇到其他进程的写事务时等待重试，而不是立即报 busy
+        conn.busy_timeout(BUSY_TIMEOUT)
+            .map_err(|e| AppError::Database(e.to_string()))?;
 
         // 启用外键约束
         conn.execute("PRAGMA foreign_keys = ON;", [])
@@ -149,7 +171,7 @@ impl Database {
         if let Err(e) = db.cleanup_old_stream_check_logs(7) {
             log::warn!("Startup stream_check_logs cleanup failed: {e}");
         }
-        if let Err(e) = db.rollup_and_prune(30) {
+        if let Err(e) = db.rollup_and_prune(dao::usage_rollup::DETAIL_RETAIN_DAYS) {
             log::warn!("Startup rollup_and_prune failed: {e}");
         }
         // Reclaim disk space after cleanup
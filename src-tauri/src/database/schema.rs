@@ -7,6 +7,161 @@ use crate::error::AppError;
 use rusqlite::{params, Connection};
 use serde::Serialize;
 
+/// 一个 Schema 迁移步骤：把 `user_version` 从 `from` 升级到 `from + 1`
+struct Migration {
+    from: i32,
+    description: &'static str,
+    apply: fn(&Connection) -> Result<(), AppError>,
+}
+
+/// 按版本排列的迁移步骤；新增迁移时追加一项并递增 [`SCHEMA_VERSION`]
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 0,
+        description: "补齐缺失列并设置版本",
+        apply: Database::migrate_v0_to_v1,
+    },
+    Migration {
+        from: 1,
+        description: "添加使用统计表和完整字段，重构 skills 表",
+        apply: Database::migrate_v1_to_v2,
+    },
+    Migration {
+        from: 2,
+        description: "Skills 统一管理架构",
+        apply: Database::migrate_v2_to_v3,
+    },
+    Migration {
+        from: 3,
+        description: "OpenCode 支持",
+        apply: Database::migrate_v3_to_v4,
+    },
+    Migration {
+        from: 4,
+        description: "计费模式支持",
+        apply: Database::migrate_v4_to_v5,
+    },
+    Migration {
+        from: 5,
+        description: "使用量聚合表 + Copilot 模板类型统一",
+        apply: Database::migrate_v5_to_v6,
+    },
+    Migration {
+        from: 6,
+        description: "Skills 更新检测支持",
+        apply: Database::migrate_v6_to_v7,
+    },
+    Migration {
+        from: 7,
+        description: "会话日志使用追踪 + 修正模型定价",
+        apply: Database::migrate_v7_to_v8,
+    },
+    Migration {
+        from: 8,
+        description: "全面补充模型定价",
+        apply: Database::migrate_v8_to_v9,
+    },
+    Migration {
+        from: 9,
+        description: "添加 Hermes Agent 支持",
+        apply: Database::migrate_v9_to_v10,
+    },
+    Migration {
+        from: 10,
+        description: "usage_daily_rollups 保留 request_model 维度",
+        apply: Database::migrate_v10_to_v11,
+    },
+    Migration {
+        from: 11,
+        description: "添加项目 Profiles 表",
+        apply: Database::migrate_v11_to_v12,
+    },
+    Migration {
+        from: 12,
+        description: "记录输入 token 缓存语义",
+        apply: Database::migrate_v12_to_v13,
+    },
+    Migration {
+        from: 13,
+        description: "添加 Grok Build 代理配置",
+        apply: Database::migrate_v13_to_v14,
+    },
+    Migration {
+        from: 14,
+        description: "Skills/MCP 添加 Grok Build 支持",
+        apply: Database::migrate_v14_to_v15,
+    },
+    Migration {
+        from: 15,
+        description: "重建 Codex 会话用量",
+        apply: Database::migrate_v15_to_v16,
+    },
+    Migration {
+        from: 16,
+        description: "Skills 项目级安装记录",
+        apply: Database::migrate_v16_to_v17,
+    },
+    Migration {
+        from: 17,
+        description: "Skill 仓库支持本地目录与私有仓库",
+        apply: Database::migrate_v17_to_v18,
+    },
+    Migration {
+        from: 18,
+        description: "记录供应商最近使用时间",
+        apply: Database::migrate_v18_to_v19,
+    },
+    Migration {
+        from: 19,
+        description: "请求日志记录上游重试次数",
+        apply: Database::migrate_v19_to_v20,
+    },
+    Migration {
+        from: 20,
+        description: "供应商能力矩阵",
+        apply: Database::migrate_v20_to_v21,
+    },
+    Migration {
+        from: 21,
+        description: "提示词版本历史",
+        apply: Database::migrate_v21_to_v22,
+    },
+    Migration {
+        from: 22,
+        description: "请求日志标记不受信任的供应商",
+        apply: Database::migrate_v22_to_v23,
+    },
+    Migration {
+        from: 23,
+        description: "本地代理访问令牌",
+        apply: Database::migrate_v23_to_v24,
+    },
+    Migration {
+        from: 24,
+        description: "请求日志工作负载指纹",
+        apply: Database::migrate_v24_to_v25,
+    },
+    Migration {
+        from: 25,
+        description: "供应商弃用公告",
+        apply: Database::migrate_v25_to_v26,
+    },
+    Migration {
+        from: 26,
+        description: "请求日志记录映射后的模型",
+        apply: Database::migrate_v26_to_v27,
+    },
+];
+
+/// 已应用迁移的记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedMigration {
+    pub version: i32,
+    pub description: String,
+    pub applied_at: i64,
+}
+
 #[derive(Serialize)]
 struct LegacySkillMigrationRow {
     directory: String,
@@ -80,6 +235,17 @@ impl Database {
             PRIMARY KEY (id, app_type)
         )", []).map_err(|e| AppError::Database(e.to_string()))?;
 
+        // 4.1 Prompt Versions 表（提示词内容的历史版本，按 app_type + prompt_id 递增编号）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS prompt_versions (
+            app_type TEXT NOT NULL, prompt_id TEXT NOT NULL, version INTEGER NOT NULL,
+            content TEXT NOT NULL, created_at INTEGER NOT NULL,
+            PRIMARY KEY (app_type, prompt_id, version)
+        )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
         // 5. Skills 表（v3.10.0+ 统一结构）
         conn.execute(
             "CREATE TABLE IF NOT EXISTS skills (
@@ -109,7 +275,8 @@ impl Database {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS skill_repos (
             owner TEXT NOT NULL, name TEXT NOT NULL, branch TEXT NOT NULL DEFAULT 'main',
-            enabled BOOLEAN NOT NULL DEFAULT 1, PRIMARY KEY (owner, name)
+            enabled BOOLEAN NOT NULL DEFAULT 1, source TEXT NOT NULL DEFAULT 'github',
+            location TEXT, access_token TEXT, PRIMARY KEY (owner, name)
         )",
             [],
         )
@@ -191,12 +358,52 @@ impl Database {
             FOREIGN KEY (provider_id, app_type) REFERENCES providers(id, app_type) ON DELETE CASCADE
         )", []).map_err(|e| AppError::Database(e.to_string()))?;
 
+        // 9.1 Provider Capabilities 表（能力探测矩阵，JSON）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_capabilities (
+            provider_id TEXT NOT NULL, app_type TEXT NOT NULL, matrix TEXT NOT NULL,
+            probed_at INTEGER NOT NULL,
+            PRIMARY KEY (provider_id, app_type),
+            FOREIGN KEY (provider_id, app_type) REFERENCES providers(id, app_type) ON DELETE CASCADE
+        )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 9.2 Provider Notices 表（上游弃用 / 下线公告）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_notices (
+            provider_id TEXT NOT NULL, app_type TEXT NOT NULL,
+            deprecated INTEGER NOT NULL DEFAULT 0, deprecated_at INTEGER, sunset_at INTEGER,
+            link TEXT, message TEXT,
+            first_seen_at INTEGER NOT NULL, last_seen_at INTEGER NOT NULL,
+            dismissed INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (provider_id, app_type),
+            FOREIGN KEY (provider_id, app_type) REFERENCES providers(id, app_type) ON DELETE CASCADE
+        )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 9.3 Proxy Auth Tokens 表（本地代理监听的访问令牌）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS proxy_auth_tokens (
+            id TEXT PRIMARY KEY, name TEXT NOT NULL, token TEXT NOT NULL UNIQUE,
+            created_at INTEGER NOT NULL, last_used_at INTEGER
+        )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
         // 10. Proxy Request Logs 表
+        // 模型三元组：request_model = 客户端请求的模型，mapped_model = 别名解析 / 模型映射后
+        // 实际发往上游的模型，model = 上游回显的模型（缺失时同 mapped_model）。
         // pricing_model = 写入时实际用于计价的模型名（pricing_model_source 解析结果），
         // 回填按它重算；NULL 表示 v11 之前的历史行，'' 表示未计价的错误行。
         conn.execute("CREATE TABLE IF NOT EXISTS proxy_request_logs (
             request_id TEXT PRIMARY KEY, provider_id TEXT NOT NULL, app_type TEXT NOT NULL, model TEXT NOT NULL,
             request_model TEXT,
+            mapped_model TEXT,
             pricing_model TEXT,
             input_tokens INTEGER NOT NULL DEFAULT 0, output_tokens INTEGER NOT NULL DEFAULT 0,
             cache_read_tokens INTEGER NOT NULL DEFAULT 0, cache_creation_tokens INTEGER NOT NULL DEFAULT 0,
@@ -207,7 +414,11 @@ impl Database {
             duration_ms INTEGER, status_code INTEGER NOT NULL, error_message TEXT, session_id TEXT,
             provider_type TEXT, is_streaming INTEGER NOT NULL DEFAULT 0,
             cost_multiplier TEXT NOT NULL DEFAULT '1.0', created_at INTEGER NOT NULL,
-            data_source TEXT NOT NULL DEFAULT 'proxy'
+            data_source TEXT NOT NULL DEFAULT 'proxy',
+            retry_count INTEGER NOT NULL DEFAULT 0,
+            untrusted INTEGER NOT NULL DEFAULT 0,
+            auth_token_id TEXT,
+            workload TEXT
         )", []).map_err(|e| AppError::Database(e.to_string()))?;
 
         conn.execute("CREATE INDEX IF NOT EXISTS idx_request_logs_provider ON proxy_request_logs(provider_id, app_type)", [])
@@ -230,6 +441,7 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
         Self::create_request_logs_usage_indexes_if_supported(conn)?;
+        Self::create_request_logs_workload_index_if_supported(conn)?;
 
         // 11. Model Pricing 表
         conn.execute(
@@ -323,6 +535,36 @@ impl Database {
         )
         .map_err(|e| AppError::Database(e.to_string()))?;
 
+        // 20. Skill Project Installs 表（项目级 Skill 安装记录，路径为设备本地）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS skill_project_installs (
+                project_path TEXT NOT NULL,
+                skill_id TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                installed_at INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (project_path, skill_id, app_type)
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 21. Provider Activity 表（最近一次切换 / 经代理服务请求的时间，设备本地）
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_activity (
+                provider_id TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                last_active_at INTEGER,
+                last_traffic_at INTEGER,
+                PRIMARY KEY (provider_id, app_type),
+                FOREIGN KEY (provider_id, app_type) REFERENCES providers(id, app_type) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+
+        // 22. Schema Migrations 表（已应用的迁移历史，设备本地）
+        Self::ensure_schema_migrations_table(conn)?;
+
         // 修复跑过未发布开发版的库：current 标记曾是全局 key，现按应用分组
         // （随 v12 定稿为 current_profile_id_<scope>，不单独 bump 版本）
         if conn
@@ -411,6 +653,37 @@ impl Database {
         Self::apply_schema_migrations_on_conn(&conn)
     }
 
+    /// 依次执行从 `version` 到 [`SCHEMA_VERSION`] 的迁移并记录历史（调用方负责 savepoint）
+    fn apply_pending_migrations(conn: &Connection, version: &mut i32) -> Result<(), AppError> {
+        Self::ensure_schema_migrations_table(conn)?;
+        while *version < SCHEMA_VERSION {
+            let Some(migration) = MIGRATIONS.iter().find(|m| m.from == *version) else {
+                return Err(AppError::Database(format!(
+                    "未知的数据库版本 {version}，无法迁移到 {SCHEMA_VERSION}"
+                )));
+            };
+            let target = migration.from + 1;
+            log::info!(
+                "迁移数据库从 v{version} 到 v{target}（{}）",
+                migration.description
+            );
+            (migration.apply)(conn)?;
+            Self::set_user_version(conn, target)?;
+            conn.execute(
+                "INSERT OR REPLACE INTO schema_migrations (version, description, applied_at)
+                 VALUES (?1, ?2, ?3)",
+                params![
+                    target,
+                    migration.description,
+                    chrono::Utc::now().timestamp()
+                ],
+            )
+            .map_err(|e| AppError::Database(format!("记录迁移历史失败: {e}")))?;
+            *version = Self::get_user_version(conn)?;
+        }
+        Ok(())
+    }
+
     /// 在指定连接上应用 Schema 迁移
     pub(crate) fn apply_schema_migrations_on_conn(conn: &Connection) -> Result<(), AppError> {
         conn.execute("SAVEPOINT schema_migration;", [])
@@ -426,101 +699,7 @@ impl Database {
             )));
         }
 
-        let result = (|| {
-            while version < SCHEMA_VERSION {
-                match version {
-                    0 => {
-                        log::info!("检测到 user_version=0，迁移到 1（补齐缺失列并设置版本）");
-                        Self::migrate_v0_to_v1(conn)?;
-                        Self::set_user_version(conn, 1)?;
-                    }
-                    1 => {
-                        log::info!(
-                            "迁移数据库从 v1 到 v2（添加使用统计表和完整字段，重构 skills 表）"
-                        );
-                        Self::migrate_v1_to_v2(conn)?;
-                        Self::set_user_version(conn, 2)?;
-                    }
-                    2 => {
-                        log::info!("迁移数据库从 v2 到 v3（Skills 统一管理架构）");
-                        Self::migrate_v2_to_v3(conn)?;
-                        Self::set_user_version(conn, 3)?;
-                    }
-                    3 => {
-                        log::info!("迁移数据库从 v3 到 v4（OpenCode 支持）");
-                        Self::migrate_v3_to_v4(conn)?;
-                        Self::set_user_version(conn, 4)?;
-                    }
-                    4 => {
-                        log::info!("迁移数据库从 v4 到 v5（计费模式支持）");
-                        Self::migrate_v4_to_v5(conn)?;
-                        Self::set_user_version(conn, 5)?;
-                    }
-                    5 => {
-                        log::info!("迁移数据库从 v5 到 v6（使用量聚合表 + Copilot 模板类型统一）");
-                        Self::migrate_v5_to_v6(conn)?;
-                        Self::set_user_version(conn, 6)?;
-                    }
-                    6 => {
-                        log::info!("迁移数据库从 v6 到 v7（Skills 更新检测支持）");
-                        Self::migrate_v6_to_v7(conn)?;
-                        Self::set_user_version(conn, 7)?;
-                    }
-                    7 => {
-                        log::info!("迁移数据库从 v7 到 v8（会话日志使用追踪 + 修正模型定价）");
-                        Self::migrate_v7_to_v8(conn)?;
-                        Self::set_user_version(conn, 8)?;
-                    }
-                    8 => {
-                        log::info!("迁移数据库从 v8 到 v9（全面补充模型定价）");
-                        Self::migrate_v8_to_v9(conn)?;
-                        Self::set_user_version(conn, 9)?;
-                    }
-                    9 => {
-                        log::info!("迁移数据库从 v9 到 v10（添加 Hermes Agent 支持）");
-                        Self::migrate_v9_to_v10(conn)?;
-                        Self::set_user_version(conn, 10)?;
-                    }
-                    10 => {
-                        log::info!("迁移数据库从 v10 到 v11（usage_daily_rollups 保留 request_model 维度）");
-                        Self::migrate_v10_to_v11(conn)?;
-                        Self::set_user_version(conn, 11)?;
-                    }
-                    11 => {
-                        log::info!("迁移数据库从 v11 到 v12（添加项目 Profiles 表）");
-                        Self::migrate_v11_to_v12(conn)?;
-                        Self::set_user_version(conn, 12)?;
-                    }
-                    12 => {
-                        log::info!("迁移数据库从 v12 到 v13（记录输入 token 缓存语义）");
-                        Self::migrate_v12_to_v13(conn)?;
-                        Self::set_user_version(conn, 13)?;
-                    }
-                    13 => {
-                        log::info!("迁移数据库从 v13 到 v14（添加 Grok Build 代理配置）");
-                        Self::migrate_v13_to_v14(conn)?;
-                        Self::set_user_version(conn, 14)?;
-                    }
-                    14 => {
-                        log::info!("迁移数据库从 v14 到 v15（Skills/MCP 添加 Grok Build 支持）");
-                        Self::migrate_v14_to_v15(conn)?;
-                        Self::set_user_version(conn, 15)?;
-                    }
-                    15 => {
-                        log::info!("迁移数据库从 v15 到 v16（重建 Codex 会话用量）");
-                        Self::migrate_v15_to_v16(conn)?;
-                        Self::set_user_version(conn, 16)?;
-                    }
-                    _ => {
-                        return Err(AppError::Database(format!(
-                            "未知的数据库版本 {version}，无法迁移到 {SCHEMA_VERSION}"
-                        )));
-                    }
-                }
-                version = Self::get_user_version(conn)?;
-            }
-            Ok(())
-        })();
+        let result = Self::apply_pending_migrations(conn, &mut version);
 
         match result {
             Ok(_) => {
@@ -536,6 +715,43 @@ impl Database {
         }
     }
 
+    fn ensure_schema_migrations_table(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER PRIMARY KEY,
+                description TEXT NOT NULL,
+                applied_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .map_err(|e| AppError::Database(format!("创建迁移历史表失败: {e}")))?;
+        Ok(())
+    }
+
+    /// 已应用的迁移历史（按版本升序）；早于迁移记录引入的升级不在其中
+    pub fn applied_migrations(&self) -> Result<Vec<AppliedMigration>, AppError> {
+        let conn = lock_conn!(self.conn);
+        if !Self::table_exists(&conn, "schema_migrations")? {
+            return Ok(Vec::new());
+        }
+        let mut stmt = conn
+            .prepare(
+                "SELECT version, description, applied_at FROM schema_migrations ORDER BY version",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(AppliedMigration {
+                    version: row.get(0)?,
+                    description: row.get(1)?,
+                    applied_at: row.get(2)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
     /// v0 -> v1 迁移：补齐所有缺失列
     fn migrate_v0_to_v1(conn: &Connection) -> Result<(), AppError> {
         // providers 表
@@ -1523,6 +1739,212 @@ impl Database {
         crate::services::session_usage_codex::reset_codex_usage_on_conn(conn, &codex_dir)
     }
 
+    /// v16 -> v17：添加项目级 Skill 安装记录表
+    /// 与 create_tables_on_conn 中的建表语句保持一致（IF NOT EXISTS 保证幂等）
+    fn migrate_v16_to_v17(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS skill_project_installs (
+                project_path TEXT NOT NULL,
+                skill_id TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                installed_at INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (project_path, skill_id, app_type)
+            )",
+            [],
+        )
+        .map_err(|e| {
+            AppError::Database(format!(
+                "v16 -> v17 创建 skill_project_installs 表失败: {e}"
+            ))
+        })?;
+        Ok(())
+    }
+
+    /// v17 -> v18：skill_repos 增加来源类型、本地路径/实例地址、访问令牌
+    fn migrate_v17_to_v18(conn: &Connection) -> Result<(), AppError> {
+        if !Self::table_exists(conn, "skill_repos")? {
+            return Ok(());
+        }
+        Self::add_column_if_missing(
+            conn,
+            "skill_repos",
+            "source",
+            "TEXT NOT NULL DEFAULT 'github'",
+        )?;
+        Self::add_column_if_missing(conn, "skill_repos", "location", "TEXT")?;
+        Self::add_column_if_missing(conn, "skill_repos", "access_token", "TEXT")?;
+        Ok(())
+    }
+
+    /// v18 -> v19：添加供应商最近活跃时间表
+    /// 与 create_tables_on_conn 中的建表语句保持一致（IF NOT EXISTS 保证幂等）
+    fn migrate_v18_to_v19(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_activity (
+                provider_id TEXT NOT NULL,
+                app_type TEXT NOT NULL,
+                last_active_at INTEGER,
+                last_traffic_at INTEGER,
+                PRIMARY KEY (provider_id, app_type),
+                FOREIGN KEY (provider_id, app_type) REFERENCES providers(id, app_type) ON DELETE CASCADE
+            )",
+            [],
+        )
+        .map_err(|e| {
+            AppError::Database(format!("v18 -> v19 创建 provider_activity 表失败: {e}"))
+        })?;
+        Ok(())
+    }
+
+    /// v19 -> v20：proxy_request_logs 增加 retry_count（同供应商重试 + 故障转移次数）
+    fn migrate_v19_to_v20(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "proxy_request_logs")? {
+            Self::add_column_if_missing(
+                conn,
+                "proxy_request_logs",
+                "retry_count",
+                "INTEGER NOT NULL DEFAULT 0",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// v20 -> v21：新增 provider_capabilities 表，保存能力探测矩阵
+    fn migrate_v20_to_v21(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_capabilities (
+            provider_id TEXT NOT NULL, app_type TEXT NOT NULL, matrix TEXT NOT NULL,
+            probed_at INTEGER NOT NULL,
+            PRIMARY KEY (provider_id, app_type),
+            FOREIGN KEY (provider_id, app_type) REFERENCES providers(id, app_type) ON DELETE CASCADE
+        )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// v21 -> v22：新增 prompt_versions 表，并把现有提示词内容记为第 1 版
+    fn migrate_v21_to_v22(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS prompt_versions (
+            app_type TEXT NOT NULL, prompt_id TEXT NOT NULL, version INTEGER NOT NULL,
+            content TEXT NOT NULL, created_at INTEGER NOT NULL,
+            PRIMARY KEY (app_type, prompt_id, version)
+        )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        if Self::table_exists(conn, "prompts")? {
+            conn.execute(
+                "INSERT OR IGNORE INTO prompt_versions
+                    (app_type, prompt_id, version, content, created_at)
+                 SELECT app_type, id, 1, content, COALESCE(updated_at, created_at, 0)
+                 FROM prompts",
+                [],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// v22 -> v23：proxy_request_logs 增加 untrusted（经不受信任的供应商转发）
+    fn migrate_v22_to_v23(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "proxy_request_logs")? {
+            Self::add_column_if_missing(
+                conn,
+                "proxy_request_logs",
+                "untrusted",
+                "INTEGER NOT NULL DEFAULT 0",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// v23 -> v24：新增 proxy_auth_tokens 表，proxy_request_logs 增加 auth_token_id（按令牌归因）
+    fn migrate_v23_to_v24(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS proxy_auth_tokens (
+            id TEXT PRIMARY KEY, name TEXT NOT NULL, token TEXT NOT NULL UNIQUE,
+            created_at INTEGER NOT NULL, last_used_at INTEGER
+        )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        if Self::table_exists(conn, "proxy_request_logs")? {
+            Self::add_column_if_missing(conn, "proxy_request_logs", "auth_token_id", "TEXT")?;
+        }
+        Ok(())
+    }
+
+    /// v24 -> v25：proxy_request_logs 增加 workload（工作负载指纹，同负载比较供应商）
+    fn migrate_v24_to_v25(conn: &Connection) -> Result<(), AppError> {
+        if Self::table_exists(conn, "proxy_request_logs")? {
+            Self::add_column_if_missing(conn, "proxy_request_logs", "workload", "TEXT")?;
+            Self::create_request_logs_workload_index_if_supported(conn)?;
+        }
+        Ok(())
+    }
+
+    /// v25 -> v26：新增 provider_notices 表，记录上游弃用 / 下线公告
+    fn migrate_v25_to_v26(conn: &Connection) -> Result<(), AppError> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS provider_notices (
+            provider_id TEXT NOT NULL, app_type TEXT NOT NULL,
+            deprecated INTEGER NOT NULL DEFAULT 0, deprecated_at INTEGER, sunset_at INTEGER,
+            link TEXT, message TEXT,
+            first_seen_at INTEGER NOT NULL, last_seen_at INTEGER NOT NULL,
+            dismissed INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (provider_id, app_type),
+            FOREIGN KEY (provider_id, app_type) REFERENCES providers(id, app_type) ON DELETE CASCADE
+        )",
+            [],
+        )
+        .map_err(|e| AppError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// v26 -> v27：proxy_request_logs 增加 mapped_model（别名解析 / 模型映射后发往上游的模型）
+    ///
+    /// 历史行无法还原当时的映射：按请求计价的行 pricing_model 就是发出的模型，
+    /// 其余行取上游回显的 model（上游通常原样回显收到的模型名）。
+    fn migrate_v26_to_v27(conn: &Connection) -> Result<(), AppError> {
+        if !Self::table_exists(conn, "proxy_request_logs")? {
+            return Ok(());
+        }
+        Self::add_column_if_missing(conn, "proxy_request_logs", "mapped_model", "TEXT")?;
+        if Self::has_column(conn, "proxy_request_logs", "model")?
+            && Self::has_column(conn, "proxy_request_logs", "pricing_model")?
+        {
+            conn.execute(
+                "UPDATE proxy_request_logs
+                 SET mapped_model = CASE
+                     WHEN pricing_model IS NOT NULL AND pricing_model != '' THEN pricing_model
+                     ELSE model
+                 END
+                 WHERE mapped_model IS NULL",
+                [],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// 旧库在迁移前可能还没有 workload 列，建索引前先确认
+    fn create_request_logs_workload_index_if_supported(conn: &Connection) -> Result<(), AppError> {
+        if Self::has_column(conn, "proxy_request_logs", "workload")?
+            && Self::has_column(conn, "proxy_request_logs", "created_at")?
+        {
+            conn.execute(
+                "CREATE INDEX IF NOT EXISTS idx_request_logs_workload
+                 ON proxy_request_logs(workload, created_at)",
+                [],
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        }
+        Ok(())
+    }
+
     /// 插入默认模型定价数据
     /// 格式: (model_id, display_name, input, output, cache_read, cache_creation)
     /// 注意: model_id 使用短横线格式（如 claude-haiku-4-5），与 API 返回的模型名称标准化后一致
@@ -3069,7 +3491,7 @@ mod tests {
 
         Database::apply_schema_migrations_on_conn(&conn)?;
 
-        assert_eq!(Database::get_user_version(&conn)?, 16);
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
         let counts: (i64, i64, i64, i64) = conn.query_row(
             "SELECT
                 (SELECT COUNT(*) FROM proxy_request_logs WHERE data_source = 'codex_session'),
@@ -3082,4 +3504,279 @@ mod tests {
         assert_eq!(counts, (0, 1, 0, 1));
         Ok(())
     }
+
+    #[test]
+    fn migrate_v16_to_v17_creates_skill_project_installs() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        Database::set_user_version(&conn, 16)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        assert!(Database::table_exists(&conn, "skill_project_installs")?);
+        assert!(Database::has_column(
+            &conn,
+            "skill_project_installs",
+            "project_path"
+        )?);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_v17_to_v18_adds_skill_repo_source_columns() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute(
+            "CREATE TABLE skill_repos (
+                owner TEXT NOT NULL, name TEXT NOT NULL, branch TEXT NOT NULL DEFAULT 'main',
+                enabled BOOLEAN NOT NULL DEFAULT 1, PRIMARY KEY (owner, name)
+            )",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO skill_repos (owner, name) VALUES ('anthropics', 'skills')",
+            [],
+        )?;
+        Database::set_user_version(&conn, 17)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        let source: String =
+            conn.query_row("SELECT source FROM skill_repos", [], |row| row.get(0))?;
+        assert_eq!(source, "github");
+        assert!(Database::has_column(&conn, "skill_repos", "access_token")?);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_v18_to_v19_creates_provider_activity() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        Database::set_user_version(&conn, 18)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        assert!(Database::table_exists(&conn, "provider_activity")?);
+        assert!(Database::has_column(
+            &conn,
+            "provider_activity",
+            "last_traffic_at"
+        )?);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_v19_to_v20_adds_request_log_retry_count() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute(
+            "CREATE TABLE proxy_request_logs (request_id TEXT PRIMARY KEY, status_code INTEGER)",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO proxy_request_logs (request_id, status_code) VALUES ('old', 200)",
+            [],
+        )?;
+        Database::set_user_version(&conn, 19)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        let retry_count: i64 = conn.query_row(
+            "SELECT retry_count FROM proxy_request_logs WHERE request_id = 'old'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(retry_count, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_v20_to_v21_creates_provider_capabilities() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        Database::set_user_version(&conn, 20)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        assert!(Database::table_exists(&conn, "provider_capabilities")?);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_v21_to_v22_seeds_prompt_versions() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute(
+            "CREATE TABLE prompts (id TEXT NOT NULL, app_type TEXT NOT NULL, name TEXT NOT NULL,
+             content TEXT NOT NULL, created_at INTEGER, updated_at INTEGER,
+             PRIMARY KEY (id, app_type))",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO prompts (id, app_type, name, content, updated_at)
+             VALUES ('p1', 'claude', 'P1', 'hello', 42)",
+            [],
+        )?;
+        Database::set_user_version(&conn, 21)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        let (version, content, created_at): (i64, String, i64) = conn.query_row(
+            "SELECT version, content, created_at FROM prompt_versions
+             WHERE app_type = 'claude' AND prompt_id = 'p1'",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        assert_eq!((version, content.as_str(), created_at), (1, "hello", 42));
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_v22_to_v23_adds_request_log_untrusted() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute(
+            "CREATE TABLE proxy_request_logs (request_id TEXT PRIMARY KEY, status_code INTEGER)",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO proxy_request_logs (request_id, status_code) VALUES ('old', 200)",
+            [],
+        )?;
+        Database::set_user_version(&conn, 22)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        let untrusted: i64 = conn.query_row(
+            "SELECT untrusted FROM proxy_request_logs WHERE request_id = 'old'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(untrusted, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_v23_to_v24_adds_proxy_auth_tokens() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute(
+            "CREATE TABLE proxy_request_logs (request_id TEXT PRIMARY KEY, status_code INTEGER)",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO proxy_request_logs (request_id, status_code) VALUES ('old', 200)",
+            [],
+        )?;
+        Database::set_user_version(&conn, 23)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        let token_id: Option<String> = conn.query_row(
+            "SELECT auth_token_id FROM proxy_request_logs WHERE request_id = 'old'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(token_id, None);
+        assert!(Database::table_exists(&conn, "proxy_auth_tokens")?);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_v24_to_v25_adds_request_log_workload() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute(
+            "CREATE TABLE proxy_request_logs (request_id TEXT PRIMARY KEY, status_code INTEGER, created_at INTEGER)",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO proxy_request_logs (request_id, status_code, created_at) VALUES ('old', 200, 1)",
+            [],
+        )?;
+        Database::set_user_version(&conn, 24)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        let workload: Option<String> = conn.query_row(
+            "SELECT workload FROM proxy_request_logs WHERE request_id = 'old'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(workload, None);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_v25_to_v26_creates_provider_notices() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        Database::set_user_version(&conn, 25)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        assert!(Database::table_exists(&conn, "provider_notices")?);
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_v26_to_v27_backfills_mapped_model() -> Result<(), AppError> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute(
+            "CREATE TABLE proxy_request_logs (
+                request_id TEXT PRIMARY KEY, model TEXT NOT NULL, pricing_model TEXT
+            )",
+            [],
+        )?;
+        conn.execute(
+            "INSERT INTO proxy_request_logs (request_id, model, pricing_model) VALUES
+                ('response', 'glm-4.6', 'glm-4.6'),
+                ('request', 'glm-4.6-0520', 'glm-4.6'),
+                ('error', 'claude-sonnet-4-5', ''),
+                ('legacy', 'gpt-5', NULL)",
+            [],
+        )?;
+        Database::set_user_version(&conn, 26)?;
+
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        assert_eq!(Database::get_user_version(&conn)?, SCHEMA_VERSION);
+        let mapped: Vec<(String, String)> = conn
+            .prepare("SELECT request_id, mapped_model FROM proxy_request_logs ORDER BY request_id")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+        assert_eq!(
+            mapped,
+            vec![
+                ("error".to_string(), "claude-sonnet-4-5".to_string()),
+                ("legacy".to_string(), "gpt-5".to_string()),
+                ("request".to_string(), "glm-4.6".to_string()),
+                ("response".to_string(), "glm-4.6".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn migrations_are_contiguous_and_recorded() -> Result<(), AppError> {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.from, index as i32);
+        }
+        assert_eq!(MIGRATIONS.len() as i32, SCHEMA_VERSION);
+
+        let conn = Connection::open_in_memory()?;
+        Database::set_user_version(&conn, 19)?;
+        conn.execute(
+            "CREATE TABLE proxy_request_logs (request_id TEXT PRIMARY KEY)",
+            [],
+        )?;
+        Database::apply_schema_migrations_on_conn(&conn)?;
+
+        let recorded: Vec<i32> = conn
+            .prepare("SELECT version FROM schema_migrations ORDER BY version")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        assert_eq!(recorded, (20..=SCHEMA_VERSION).collect::<Vec<_>>());
+        Ok(())
+    }
 }
@@ -919,3 +919,54 @@ fn ensure_incremental_auto_vacuum_rebuilds_existing_file_db() {
         "file db should persist INCREMENTAL auto_vacuum after VACUUM rebuild"
     );
 }
+
+#[test]
+fn integrity_check_reports_and_repairs_known_problems() {
+    let db = Database::memory().expect("create memory db");
+    {
+        let conn = db.conn.lock().expect("lock conn");
+        conn.execute_batch(
+            "PRAGMA foreign_keys = OFF;
+             INSERT INTO providers (id, app_type, name, settings_config, meta, is_current, sort_index)
+             VALUES ('a', 'claude', 'A', '{}', 'not json', 1, 0),
+                    ('b', 'claude', 'B', '{}', '{}', 1, 1),
+                    ('c', 'claude', 'C', '{broken', '{}', 0, 2),
+                    ('x', 'retired-app', 'X', '{}', '{}', 0, 0);
+             INSERT INTO provider_endpoints (provider_id, app_type, url, added_at)
+             VALUES ('gone', 'claude', 'https://example.com', 0);
+             PRAGMA foreign_keys = ON;",
+        )
+        .expect("seed broken rows");
+    }
+
+    let report = db.check_integrity().expect("check integrity");
+    let kinds: Vec<IntegrityIssueKind> = report.issues.iter().map(|issue| issue.kind).collect();
+    assert!(kinds.contains(&IntegrityIssueKind::ForeignKey));
+    assert!(kinds.contains(&IntegrityIssueKind::OrphanedProvider));
+    assert!(kinds.contains(&IntegrityIssueKind::DuplicateCurrent));
+    assert_eq!(
+        kinds
+            .iter()
+            .filter(|kind| **kind == IntegrityIssueKind::InvalidJson)
+            .count(),
+        2
+    );
+    // settings_config 损坏与未知应用类型只报告，不自动改写
+    assert_eq!(report.repairable_count(), report.issues.len() - 2);
+
+    let repaired = db.repair_integrity(&report).expect("repair");
+    assert_eq!(repaired, report.repairable_count());
+
+    let after = db.check_integrity().expect("re-check integrity");
+    let mut keys: Vec<_> = after
+        .issues
+        .iter()
+        .filter_map(|issue| issue.key.as_deref())
+        .collect();
+    keys.sort();
+    assert_eq!(keys, ["claude/c", "retired-app/x"]);
+    assert_eq!(
+        db.get_current_provider("claude").expect("current"),
+        Some("a".to_string())
+    );
+}
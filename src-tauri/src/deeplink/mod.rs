@@ -7,9 +7,13 @@
 //! - Prompts
 //! - Skills
 //!
+//! `paste` recognizes providers in pasted text (relay welcome messages, JSON,
+//! `export` lines) and turns them into the same import request.
+//!
 
 mod mcp;
 mod parser;
+mod paste;
 mod prompt;
 mod provider;
 mod skill;
@@ -23,9 +27,12 @@ use serde::{Deserialize, Serialize};
 // Re-export public API
 pub use mcp::import_mcp_from_deeplink;
 pub use parser::parse_deeplink_url;
+pub use paste::{parse_pasted_provider, PastedProvider};
 pub use prompt::import_prompt_from_deeplink;
+pub(crate) use provider::build_provider_from_request;
 pub use provider::{import_provider_from_deeplink, parse_and_merge_config};
 pub use skill::import_skill_from_deeplink;
+pub(crate) use utils::infer_homepage_from_endpoint;
 
 /// Deep link import request model
 ///
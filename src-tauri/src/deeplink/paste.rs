@@ -0,0 +1,386 @@
+//! "Paste to add": recognize a provider in clipboard text
+//!
+//! The usual ways relays hand out credentials all become a provider import
+//! request that goes through the regular deep link confirmation dialog:
+//!
+//! - `ccswitch://` deep links
+//! - JSON: Claude `settings.json` (`env` object), env-var objects, or
+//!   `baseUrl` / `apiKey` style fields
+//! - shell lines: `export KEY=VALUE`, `set KEY=VALUE`, `$env:KEY="VALUE"`
+//! - welcome messages such as "接口地址：https://… / API Key：sk-…"
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use base64::Engine;
+use regex::Regex;
+use serde::Serialize;
+
+use super::{parse_deeplink_url, DeepLinkImportRequest};
+use crate::error::AppError;
+
+/// Claude auth style: `ANTHROPIC_AUTH_TOKEN` (Bearer) or `ANTHROPIC_API_KEY` (x-api-key)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PasteAuthStyle {
+    AuthToken,
+    ApiKey,
+}
+
+/// Which format the pasted text was recognized as
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PasteFormat {
+    Deeplink,
+    Json,
+    Env,
+    Text,
+}
+
+/// Recognized provider: an import request ready for the confirmation dialog
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PastedProvider {
+    pub request: DeepLinkImportRequest,
+    pub auth_style: PasteAuthStyle,
+    pub format: PasteFormat,
+}
+
+static URL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"https?://[^\s"'`<>，。、）)]+"#).expect("valid url regex"));
+
+/// Well-known key prefixes (sk- / cr_ / AIza) for free text without labels
+static KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:sk-[A-Za-z0-9_\-]{16,}|cr_[A-Za-z0-9]{16,}|AIza[A-Za-z0-9_\-]{30,})")
+        .expect("valid key regex")
+});
+
+/// `export KEY=VALUE` / `set KEY=VALUE` / `$env:KEY = "VALUE"` / `KEY=VALUE`
+static ENV_LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*(?:export\s+|set\s+|\$env:)?([A-Za-z_][A-Za-z0-9_]*)\s*=\s*(.+?)\s*;?\s*$"#)
+        .expect("valid env regex")
+});
+
+/// Labelled lines such as `接口地址：https://…` or `API Key: sk-…`
+static LABEL_LINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*[-*•]?\s*([^:：=]{1,24}?)\s*[:：]\s*(\S.*?)\s*$").expect("valid label regex")
+});
+
+const URL_KEYS: &[&str] = &[
+    "ANTHROPIC_BASE_URL",
+    "OPENAI_BASE_URL",
+    "GOOGLE_GEMINI_BASE_URL",
+    "GEMINI_BASE_URL",
+    "BASE_URL",
+    "BASEURL",
+    "API_BASE",
+    "API_BASE_URL",
+    "ENDPOINT",
+    "URL",
+];
+
+const KEY_KEYS: &[&str] = &[
+    "ANTHROPIC_AUTH_TOKEN",
+    "ANTHROPIC_API_KEY",
+    "OPENAI_API_KEY",
+    "GEMINI_API_KEY",
+    "GOOGLE_API_KEY",
+    "API_KEY",
+    "APIKEY",
+    "KEY",
+    "TOKEN",
+    "AUTH_TOKEN",
+];
+
+const MODEL_KEYS: &[&str] = &["ANTHROPIC_MODEL", "OPENAI_MODEL", "GEMINI_MODEL", "MODEL"];
+
+fn unquote(value: &str) -> String {
+    value
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'' || c == '`')
+        .trim()
+        .to_string()
+}
+
+/// Normalize key names: `baseUrl` / `base-url` / `Base URL` → `BASE_URL`
+fn normalize_key(key: &str) -> String {
+    let mut out = String::new();
+    let mut prev_lower = false;
+    for ch in key.trim().chars() {
+        if ch.is_ascii_uppercase() && prev_lower {
+            out.push('_');
+        }
+        if ch.is_ascii_alphanumeric() {
+            out.push(ch.to_ascii_uppercase());
+        } else if !out.ends_with('_') && !out.is_empty() {
+            out.push('_');
+        }
+        prev_lower = ch.is_ascii_lowercase() || ch.is_ascii_digit();
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+/// Map a free-text label to one of the canonical keys
+fn label_key(label: &str) -> Option<&'static str> {
+    let lower = label.to_lowercase();
+    if ["地址", "url", "endpoint", "域名", "接口", "base"]
+        .iter()
+        .any(|needle| lower.contains(needle))
+    {
+        return Some("URL");
+    }
+    if ["key", "令牌", "密钥", "token", "秘钥"]
+        .iter()
+        .any(|needle| lower.contains(needle))
+    {
+        return Some("KEY");
+    }
+    if ["模型", "model"]
+        .iter()
+        .any(|needle| lower.contains(needle))
+    {
+        return Some("MODEL");
+    }
+    None
+}
+
+fn collect_json(value: &serde_json::Value, pairs: &mut HashMap<String, String>) {
+    let Some(object) = value.as_object() else {
+        return;
+    };
+    for (key, value) in object {
+        match value {
+            serde_json::Value::String(text) => {
+                pairs
+                    .entry(normalize_key(key))
+                    .or_insert_with(|| text.trim().to_string());
+            }
+            // Nested objects such as Claude's `env` or Codex's `auth`
+            serde_json::Value::Object(_) => collect_json(value, pairs),
+            _ => {}
+        }
+    }
+}
+
+fn collect_lines(text: &str, pairs: &mut HashMap<String, String>) -> bool {
+    let mut env_lines = false;
+    for line in text.lines() {
+        if let Some(caps) = ENV_LINE_RE.captures(line) {
+            let value = unquote(&caps[2]);
+            if !value.is_empty() {
+                pairs.entry(normalize_key(&caps[1])).or_insert(value);
+                env_lines = true;
+            }
+            continue;
+        }
+        if let Some(caps) = LABEL_LINE_RE.captures(line) {
+            if let Some(key) = label_key(&caps[1]) {
+                let value = unquote(&caps[2]);
+                let value = match key {
+                    "URL" => URL_RE.find(&value).map(|m| m.as_str().to_string()),
+                    _ => value.split_whitespace().next().map(str::to_string),
+                };
+                if let Some(value) = value {
+                    pairs.entry(key.to_string()).or_insert(value);
+                }
+            }
+        }
+    }
+    env_lines
+}
+
+fn first_of(pairs: &HashMap<String, String>, keys: &[&str]) -> Option<(String, String)> {
+    keys.iter().find_map(|key| {
+        pairs
+            .get(*key)
+            .filter(|value| !value.is_empty())
+            .map(|value| (key.to_string(), value.clone()))
+    })
+}
+
+/// Detect the target app from variable names, then the key prefix, then the
+/// URL shape; relays are most often Claude-compatible, so that is the default.
+fn detect_app(pairs: &HashMap<String, String>, url: &str, key: &str) -> &'static str {
+    let has_prefix = |prefix: &str| pairs.keys().any(|name| name.starts_with(prefix));
+    if has_prefix("ANTHROPIC_") || has_prefix("CLAUDE_") {
+        return "claude";
+    }
+    if has_prefix("GEMINI_") || has_prefix("GOOGLE_") || key.starts_with("AIza") {
+        return "gemini";
+    }
+    if has_prefix("OPENAI_") || has_prefix("CODEX_") {
+        return "codex";
+    }
+    let lower = url.to_lowercase();
+    if lower.contains("generativelanguage") || lower.contains("/v1beta") {
+        return "gemini";
+    }
+    if lower.contains("openai") || lower.trim_end_matches('/').ends_with("/v1") {
+        return "codex";
+    }
+    "claude"
+}
+
+/// Default name from the host: `https://api.fast-relay.com/v1` → `fast-relay`
+fn name_from_url(url: &str) -> Option<String> {
+    let host = url::Url::parse(url).ok()?.host_str()?.to_string();
+    let labels: Vec<&str> = host
+        .split('.')
+        .filter(|label| !matches!(*label, "api" | "www" | "gateway" | "relay"))
+        .collect();
+    let name = match labels.len() {
+        0 => host.as_str(),
+        1 => labels[0],
+        n => labels[n - 2],
+    };
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Parse pasted text into a provider import request
+pub fn parse_pasted_provider(text: &str) -> Result<PastedProvider, AppError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(not_recognized());
+    }
+
+    if text.starts_with("ccswitch://") {
+        let request = parse_deeplink_url(text)?;
+        if request.resource != "provider" {
+            return Err(not_recognized());
+        }
+        return Ok(PastedProvider {
+            request,
+            auth_style: PasteAuthStyle::AuthToken,
+            format: PasteFormat::Deeplink,
+        });
+    }
+
+    let mut pairs = HashMap::new();
+    let format = match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(value) if value.is_object() => {
+            collect_json(&value, &mut pairs);
+            PasteFormat::Json
+        }
+        _ if collect_lines(text, &mut pairs) => PasteFormat::Env,
+        _ => PasteFormat::Text,
+    };
+
+    let url = first_of(&pairs, URL_KEYS)
+        .map(|(_, url)| url)
+        .or_else(|| URL_RE.find(text).map(|m| m.as_str().to_string()))
+        .map(|url| url.trim_end_matches('/').to_string());
+    let key = first_of(&pairs, KEY_KEYS).or_else(|| {
+        KEY_RE
+            .find(text)
+            .map(|m| ("KEY".to_string(), m.as_str().to_string()))
+    });
+    let (Some(url), Some((key_name, key))) = (url, key) else {
+        return Err(not_recognized());
+    };
+
+    let app = detect_app(&pairs, &url, &key);
+    let auth_style = if app == "claude" && key_name == "ANTHROPIC_API_KEY" {
+        PasteAuthStyle::ApiKey
+    } else {
+        PasteAuthStyle::AuthToken
+    };
+
+    let mut request = DeepLinkImportRequest {
+        version: "v1".to_string(),
+        resource: "provider".to_string(),
+        app: Some(app.to_string()),
+        name: name_from_url(&url),
+        homepage: super::infer_homepage_from_endpoint(&url),
+        endpoint: Some(url.clone()),
+        api_key: Some(key.clone()),
+        model: first_of(&pairs, MODEL_KEYS).map(|(_, model)| model),
+        ..Default::default()
+    };
+    if auth_style == PasteAuthStyle::ApiKey {
+        // Deep link imports write ANTHROPIC_AUTH_TOKEN by default; the inline
+        // env keeps x-api-key auth (see build_claude_settings).
+        let config = serde_json::json!({
+            "env": { "ANTHROPIC_BASE_URL": url, "ANTHROPIC_API_KEY": key }
+        });
+        request.config = Some(base64::engine::general_purpose::STANDARD.encode(config.to_string()));
+        request.config_format = Some("json".to_string());
+    }
+
+    Ok(PastedProvider {
+        request,
+        auth_style,
+        format,
+    })
+}
+
+fn not_recognized() -> AppError {
+    AppError::localized(
+        "provider.paste.not_recognized",
+        "未能从剪贴板内容中识别出接口地址和 API Key",
+        "Couldn't find a base URL and API key in the clipboard text",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_relay_welcome_message() {
+        let pasted = parse_pasted_provider(
+            "🎉 开通成功！\n接口地址：https://api.fast-relay.com\nAPI Key：sk-abcdefghijklmnopqrstuvwx\n祝使用愉快",
+        )
+        .unwrap();
+        assert_eq!(pasted.format, PasteFormat::Text);
+        assert_eq!(pasted.request.app.as_deref(), Some("claude"));
+        assert_eq!(pasted.request.name.as_deref(), Some("fast-relay"));
+        assert_eq!(
+            pasted.request.endpoint.as_deref(),
+            Some("https://api.fast-relay.com")
+        );
+        assert_eq!(
+            pasted.request.api_key.as_deref(),
+            Some("sk-abcdefghijklmnopqrstuvwx")
+        );
+        assert_eq!(pasted.auth_style, PasteAuthStyle::AuthToken);
+    }
+
+    #[test]
+    fn parses_export_lines_with_api_key_auth() {
+        let pasted = parse_pasted_provider(
+            "export ANTHROPIC_BASE_URL=\"https://relay.example.com/\"\nexport ANTHROPIC_API_KEY='sk-test-1234567890abcdef'\nexport ANTHROPIC_MODEL=claude-sonnet-4-5",
+        )
+        .unwrap();
+        assert_eq!(pasted.format, PasteFormat::Env);
+        assert_eq!(pasted.auth_style, PasteAuthStyle::ApiKey);
+        assert_eq!(
+            pasted.request.endpoint.as_deref(),
+            Some("https://relay.example.com")
+        );
+        assert_eq!(pasted.request.model.as_deref(), Some("claude-sonnet-4-5"));
+        assert!(pasted.request.config.is_some());
+    }
+
+    #[test]
+    fn detects_codex_from_json_fields() {
+        let pasted = parse_pasted_provider(
+            r#"{"baseUrl": "https://api.example.com/v1", "apiKey": "sk-codex-abcdefghijklmnop"}"#,
+        )
+        .unwrap();
+        assert_eq!(pasted.format, PasteFormat::Json);
+        assert_eq!(pasted.request.app.as_deref(), Some("codex"));
+
+        let pasted = parse_pasted_provider(
+            r#"{"env": {"ANTHROPIC_BASE_URL": "https://a.example.com", "ANTHROPIC_AUTH_TOKEN": "tok"}}"#,
+        )
+        .unwrap();
+        assert_eq!(pasted.request.app.as_deref(), Some("claude"));
+        assert_eq!(pasted.request.api_key.as_deref(), Some("tok"));
+    }
+
+    #[test]
+    fn rejects_text_without_url_or_key() {
+        assert!(parse_pasted_provider("hello world").is_err());
+        assert!(parse_pasted_provider("https://example.com only a link").is_err());
+    }
+}
@@ -299,8 +299,16 @@ fn build_claude_settings(request: &DeepLinkImportRequest) -> serde_json::Value {
     // are authoritative because they're what the deeplink builder put on the
     // wire — for Claude these are: ANTHROPIC_AUTH_TOKEN, ANTHROPIC_BASE_URL,
     // ANTHROPIC_MODEL, and the haiku/sonnet/opus model aliases.
+    // The inline config decides the auth style: a config that only carries
+    // ANTHROPIC_API_KEY (x-api-key relays) keeps the key under that name.
+    let auth_key =
+        if env.contains_key("ANTHROPIC_API_KEY") && !env.contains_key("ANTHROPIC_AUTH_TOKEN") {
+            "ANTHROPIC_API_KEY"
+        } else {
+            "ANTHROPIC_AUTH_TOKEN"
+        };
     env.insert(
-        "ANTHROPIC_AUTH_TOKEN".to_string(),
+        auth_key.to_string(),
         json!(request.api_key.clone().unwrap_or_default()),
     );
     env.insert(
@@ -668,7 +676,11 @@ fn merge_claude_config(
 
     // Auto-fill API key if not provided in URL
     if request.api_key.as_ref().is_none_or(|s| s.is_empty()) {
-        if let Some(token) = env.get("ANTHROPIC_AUTH_TOKEN").and_then(|v| v.as_str()) {
+        if let Some(token) = env
+            .get("ANTHROPIC_AUTH_TOKEN")
+            .or_else(|| env.get("ANTHROPIC_API_KEY"))
+            .and_then(|v| v.as_str())
+        {
             request.api_key = Some(token.to_string());
         }
     }
@@ -91,6 +91,31 @@ impl AppError {
             en: en.into(),
         }
     }
+
+    /// 语言包 key（仅可本地化错误）
+    pub fn key(&self) -> Option<&'static str> {
+        match self {
+            Self::Localized { key, .. } => Some(key),
+            _ => None,
+        }
+    }
+
+    /// 按指定语言输出错误信息；非本地化错误保持原文
+    pub fn message(&self, lang: crate::i18n::Lang) -> String {
+        match (self, lang) {
+            (Self::Localized { zh, .. }, crate::i18n::Lang::Zh) => zh.clone(),
+            (Self::Localized { en, .. }, crate::i18n::Lang::En) => en.clone(),
+            _ => self.to_string(),
+        }
+    }
+
+    /// 界面输出：按设置中的语言取文案；未选择语言时与 `Display` 一致，输出“中文 (English)”
+    pub fn ui_message(&self) -> String {
+        match crate::i18n::configured() {
+            Some(lang) => self.message(lang),
+            None => self.to_string(),
+        }
+    }
 }
 
 impl<T> From<PoisonError<T>> for AppError {
@@ -107,7 +132,7 @@ impl From<rusqlite::Error> for AppError {
 
 impl From<AppError> for String {
     fn from(err: AppError) -> Self {
-        err.to_string()
+        err.ui_message()
     }
 }
 
@@ -116,7 +141,7 @@ impl serde::Serialize for AppError {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        serializer.serialize_str(&self.ui_message())
     }
 }
 
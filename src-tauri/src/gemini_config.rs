@@ -1,9 +1,10 @@
 use crate::config::{get_home_dir, write_text_file};
 use crate::error::AppError;
+use serde::Serialize;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// 获取 Gemini 配置目录路径（支持设置覆盖）
 pub fn get_gemini_dir() -> PathBuf {
@@ -63,14 +64,8 @@ pub fn parse_env_file(content: &str) -> HashMap<String, String> {
 ///
 /// # 使用场景
 ///
-/// 此函数为未来的严格验证场景预留，当前运行时使用宽松的 `parse_env_file`。
-/// 可用于：
-/// - 配置导入验证
-/// - CLI 工具的严格模式
-/// - 配置文件错误诊断
-///
-/// 已有完整的测试覆盖，可直接使用。
-#[allow(dead_code)]
+/// 运行时读取使用宽松的 `parse_env_file`；此函数用于配置漂移诊断
+/// （[`inspect_gemini_live`]），把无效行报告给用户。
 pub fn parse_env_file_strict(content: &str) -> Result<HashMap<String, String>, AppError> {
     let mut map = HashMap::new();
 
@@ -152,15 +147,11 @@ pub fn read_gemini_env() -> Result<HashMap<String, String>, AppError> {
     Ok(parse_env_file(&content))
 }
 
-/// 写入 Gemini .env 文件（原子操作）
-pub fn write_gemini_env_atomic(map: &HashMap<String, String>) -> Result<(), AppError> {
-    let path = get_gemini_env_path();
-
-    // 确保目录存在
+/// 确保 Gemini 目录存在，并将权限设为 700（仅所有者可读写执行）
+fn ensure_gemini_dir(path: &Path) -> Result<(), AppError> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
 
-        // 设置目录权限为 700（仅所有者可读写执行）
         #[cfg(unix)]
         {
             use std::os::unix::fs::PermissionsExt;
@@ -171,24 +162,350 @@ pub fn write_gemini_env_atomic(map: &HashMap<String, String>) -> Result<(), AppE
             fs::set_permissions(parent, perms).map_err(|e| AppError::io(parent, e))?;
         }
     }
+    Ok(())
+}
 
-    let content = serialize_env_file(map);
-    write_text_file(&path, &content)?;
+/// 写入 .env 内容并设置文件权限为 600（仅所有者可读写）
+fn write_env_content(path: &Path, content: &str) -> Result<(), AppError> {
+    ensure_gemini_dir(path)?;
+    write_text_file(path, content)?;
 
-    // 设置文件权限为 600（仅所有者可读写）
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(&path)
-            .map_err(|e| AppError::io(&path, e))?
+        let mut perms = fs::metadata(path)
+            .map_err(|e| AppError::io(path, e))?
             .permissions();
         perms.set_mode(0o600);
-        fs::set_permissions(&path, perms).map_err(|e| AppError::io(&path, e))?;
+        fs::set_permissions(path, perms).map_err(|e| AppError::io(path, e))?;
+    }
+
+    Ok(())
+}
+
+/// 写入 Gemini .env 文件（原子操作）
+pub fn write_gemini_env_atomic(map: &HashMap<String, String>) -> Result<(), AppError> {
+    write_env_content(&get_gemini_env_path(), &serialize_env_file(map))
+}
+
+/// 写回 .env 后能读出的键值（值会被 trim）
+fn normalized_env(map: &HashMap<String, String>) -> HashMap<String, String> {
+    map.iter()
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+fn read_optional(path: &Path) -> Result<Option<Vec<u8>>, AppError> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(AppError::io(path, e)),
+    }
+}
+
+fn restore_file(path: &Path, original: &Option<Vec<u8>>) -> Result<(), AppError> {
+    match original {
+        Some(bytes) => crate::config::atomic_write(path, bytes),
+        None if path.exists() => fs::remove_file(path).map_err(|e| AppError::io(path, e)),
+        None => Ok(()),
+    }
+}
+
+fn dual_write_failed(detail: impl std::fmt::Display) -> AppError {
+    AppError::localized(
+        "gemini.live.dual_write_failed",
+        format!("写入 Gemini 配置失败，.env 与 settings.json 已回滚: {detail}"),
+        format!("Failed to write Gemini config; .env and settings.json were rolled back: {detail}"),
+    )
+}
+
+/// 校验写入后的文件内容：.env 能完整读回同样的键值，settings.json 能解析为同样的 JSON
+fn verify_gemini_live_files(
+    env_path: &Path,
+    settings_path: &Path,
+    env: Option<&HashMap<String, String>>,
+    settings: Option<&Value>,
+) -> Result<(), AppError> {
+    match env {
+        Some(map) => {
+            let content = fs::read_to_string(env_path).map_err(|e| AppError::io(env_path, e))?;
+            if parse_env_file(&content) != normalized_env(map) {
+                return Err(AppError::Config(
+                    ".env 读回内容与写入不一致（变量名或值包含无法写入 .env 的字符）".to_string(),
+                ));
+            }
+        }
+        None if env_path.exists() => {
+            return Err(AppError::Config(".env 未被删除".to_string()));
+        }
+        None => {}
+    }
+    match settings {
+        Some(expected) => {
+            let actual: Value = crate::config::read_json_file(settings_path)?;
+            if &actual != expected {
+                return Err(AppError::Config(
+                    "settings.json 读回内容与写入不一致".to_string(),
+                ));
+            }
+        }
+        None if settings_path.exists() => {
+            return Err(AppError::Config("settings.json 未被删除".to_string()));
+        }
+        None => {}
     }
+    Ok(())
+}
 
+/// 同时写入 `.env` 与 `settings.json`，`None` 表示删除对应文件
+///
+/// Gemini CLI 同时读取两个文件，只写成功一个会留下不一致的状态（例如 .env 已换成
+/// API Key，settings.json 仍是 OAuth 模式）。流程：
+/// 1. 记录两个文件的原始内容
+/// 2. 分别原子写入（临时文件 + rename）
+/// 3. 重新读取并解析校验
+///
+/// 任一步失败都把两个文件恢复为原始内容后返回错误。
+pub fn write_gemini_live_files(
+    env: Option<&HashMap<String, String>>,
+    settings: Option<&Value>,
+) -> Result<(), AppError> {
+    let env_path = get_gemini_env_path();
+    let settings_path = get_gemini_settings_path();
+    let original_env = read_optional(&env_path)?;
+    let original_settings = read_optional(&settings_path)?;
+
+    let result = (|| -> Result<(), AppError> {
+        match env {
+            Some(map) => write_env_content(&env_path, &serialize_env_file(map))?,
+            None => restore_file(&env_path, &None)?,
+        }
+        match settings {
+            Some(value) => {
+                ensure_gemini_dir(&settings_path)?;
+                let json = crate::config::to_sorted_json_string(value)?;
+                crate::config::atomic_write(&settings_path, json.as_bytes())?;
+            }
+            None => restore_file(&settings_path, &None)?,
+        }
+        verify_gemini_live_files(&env_path, &settings_path, env, settings)
+    })();
+
+    if let Err(e) = result {
+        for (path, original) in [
+            (&env_path, &original_env),
+            (&settings_path, &original_settings),
+        ] {
+            if let Err(restore_err) = restore_file(path, original) {
+                log::error!("回滚 {} 失败: {restore_err}", path.display());
+            }
+        }
+        return Err(dual_write_failed(e));
+    }
     Ok(())
 }
 
+const SELECTED_TYPE_API_KEY: &str = "gemini-api-key";
+const SELECTED_TYPE_OAUTH: &str = "oauth-personal";
+
+/// `.env` 与 `settings.json` 之间的不一致
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum GeminiDrift {
+    /// 写入中断留下的临时文件
+    #[serde(rename_all = "camelCase")]
+    StaleTempFile { path: String },
+    /// settings.json 不是合法 JSON
+    #[serde(rename_all = "camelCase")]
+    InvalidSettingsJson { error: String },
+    /// .env 含有无法解析的行
+    #[serde(rename_all = "camelCase")]
+    InvalidEnv { error: String },
+    /// `security.auth.selectedType` 与 .env 中的凭据不匹配
+    #[serde(rename_all = "camelCase")]
+    AuthTypeMismatch {
+        expected: String,
+        actual: Option<String>,
+    },
+}
+
+/// 检查或修复的结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiRepairReport {
+    pub drifts: Vec<GeminiDrift>,
+    /// 是否已改写配置文件（dry run 时恒为 false）
+    pub repaired: bool,
+    /// 改写前备份的原文件
+    pub backups: Vec<String>,
+}
+
+/// 按 .env 中的凭据推断 selectedType：有 GEMINI_API_KEY 用 API Key 模式，否则 OAuth
+fn expected_selected_type(env: &HashMap<String, String>) -> &'static str {
+    match env.get("GEMINI_API_KEY") {
+        Some(key) if !key.trim().is_empty() => SELECTED_TYPE_API_KEY,
+        _ => SELECTED_TYPE_OAUTH,
+    }
+}
+
+fn selected_type(settings: &Value) -> Option<&str> {
+    settings
+        .pointer("/security/auth/selectedType")
+        .and_then(Value::as_str)
+}
+
+/// 比较两个文件的内容（`None` 表示文件不存在）
+///
+/// 只报告会让 Gemini CLI 用错凭据的组合：有 API Key 但未选 API Key 模式，
+/// 或选了 API Key 模式但 .env 中没有 Key。其他 selectedType（如 vertex-ai）
+/// 由用户自行管理，不视为漂移。
+fn detect_drift(env_content: Option<&str>, settings_content: Option<&str>) -> Vec<GeminiDrift> {
+    let mut drifts = Vec::new();
+
+    if let Some(content) = env_content {
+        if let Err(e) = parse_env_file_strict(content) {
+            drifts.push(GeminiDrift::InvalidEnv {
+                error: e.to_string(),
+            });
+        }
+    }
+    let env = env_content.map(parse_env_file).unwrap_or_default();
+
+    let settings = match settings_content {
+        Some(content) => match serde_json::from_str::<Value>(content) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                drifts.push(GeminiDrift::InvalidSettingsJson {
+                    error: e.to_string(),
+                });
+                None
+            }
+        },
+        None => Some(Value::Object(Default::default())),
+    };
+
+    if let Some(settings) = settings {
+        let expected = expected_selected_type(&env);
+        let actual = selected_type(&settings);
+        let mismatched = match expected {
+            SELECTED_TYPE_API_KEY => actual != Some(SELECTED_TYPE_API_KEY),
+            _ => actual == Some(SELECTED_TYPE_API_KEY),
+        };
+        if mismatched {
+            drifts.push(GeminiDrift::AuthTypeMismatch {
+                expected: expected.to_string(),
+                actual: actual.map(str::to_string),
+            });
+        }
+    }
+
+    drifts
+}
+
+/// 原子写入中断后遗留的 `.env.tmp.*` / `settings.json.tmp.*`
+fn stale_temp_files() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(get_gemini_dir()) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.starts_with(".env.tmp.") || name.starts_with("settings.json.tmp.")
+                })
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn read_optional_string(path: &Path) -> Result<Option<String>, AppError> {
+    Ok(read_optional(path)?.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()))
+}
+
+/// 检查 Gemini 的 `.env` 与 `settings.json` 是否一致
+pub fn inspect_gemini_live() -> Result<Vec<GeminiDrift>, AppError> {
+    let env_content = read_optional_string(&get_gemini_env_path())?;
+    let settings_content = read_optional_string(&get_gemini_settings_path())?;
+    let mut drifts: Vec<GeminiDrift> = stale_temp_files()
+        .into_iter()
+        .map(|path| GeminiDrift::StaleTempFile {
+            path: path.display().to_string(),
+        })
+        .collect();
+    drifts.extend(detect_drift(
+        env_content.as_deref(),
+        settings_content.as_deref(),
+    ));
+    Ok(drifts)
+}
+
+/// 修复 `.env` 与 `settings.json` 之间的漂移
+///
+/// 以 .env 为准：清理临时文件；settings.json 无法解析时从空对象重建；
+/// 按 .env 中的凭据修正 selectedType；.env 中的无效行会被丢弃。
+/// 改写前原文件备份为 `<文件名>.bak.<时间戳>`，两个文件通过
+/// [`write_gemini_live_files`] 一起写入。`dry_run` 时只报告不修改。
+pub fn repair_gemini_live(dry_run: bool) -> Result<GeminiRepairReport, AppError> {
+    let drifts = inspect_gemini_live()?;
+    let mut report = GeminiRepairReport {
+        drifts,
+        ..Default::default()
+    };
+    if dry_run || report.drifts.is_empty() {
+        return Ok(report);
+    }
+
+    for path in stale_temp_files() {
+        fs::remove_file(&path).map_err(|e| AppError::io(&path, e))?;
+    }
+    let needs_rewrite = report
+        .drifts
+        .iter()
+        .any(|drift| !matches!(drift, GeminiDrift::StaleTempFile { .. }));
+    if !needs_rewrite {
+        report.repaired = true;
+        return Ok(report);
+    }
+
+    let env_path = get_gemini_env_path();
+    let settings_path = get_gemini_settings_path();
+    let env_content = read_optional_string(&env_path)?;
+    let env = env_content
+        .as_deref()
+        .map(parse_env_file)
+        .unwrap_or_default();
+    let mut settings = read_optional_string(&settings_path)?
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .filter(Value::is_object)
+        .unwrap_or_else(|| serde_json::json!({}));
+    set_selected_type(&mut settings, expected_selected_type(&env));
+
+    let suffix = chrono::Local::now().format("%Y%m%d%H%M%S");
+    for path in [&env_path, &settings_path] {
+        if path.exists() {
+            let mut backup = path.clone().into_os_string();
+            backup.push(format!(".bak.{suffix}"));
+            let backup = PathBuf::from(backup);
+            fs::copy(path, &backup).map_err(|e| AppError::io(&backup, e))?;
+            report.backups.push(backup.display().to_string());
+        }
+    }
+
+    let env = env_content.is_some().then_some(&env);
+    write_gemini_live_files(env, Some(&settings))?;
+    report.repaired = true;
+    log::info!(
+        "[Gemini] 已修复 {} 处配置漂移，备份: {:?}",
+        report.drifts.len(),
+        report.backups
+    );
+    Ok(report)
+}
+
 /// 从 .env 格式转换为 Provider.settings_config (JSON Value)
 pub fn env_to_json(env_map: &HashMap<String, String>) -> Value {
     let mut json_map = serde_json::Map::new();
@@ -282,33 +599,8 @@ pub fn get_gemini_settings_path() -> PathBuf {
     get_gemini_dir().join("settings.json")
 }
 
-/// 更新 Gemini 目录 settings.json 中的 security.auth.selectedType 字段
-///
-/// 此函数会：
-/// 1. 读取现有的 settings.json（如果存在）
-/// 2. 只更新 `security.auth.selectedType` 字段，保留其他所有字段
-/// 3. 原子性写入文件
-///
-/// # 参数
-/// - `selected_type`: 要设置的 selectedType 值（如 "gemini-api-key" 或 "oauth-personal"）
-fn update_selected_type(selected_type: &str) -> Result<(), AppError> {
-    let settings_path = get_gemini_settings_path();
-
-    // 确保目录存在
-    if let Some(parent) = settings_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
-    }
-
-    // 读取现有的 settings.json（如果存在）
-    let mut settings_content = if settings_path.exists() {
-        let content =
-            fs::read_to_string(&settings_path).map_err(|e| AppError::io(&settings_path, e))?;
-        serde_json::from_str::<Value>(&content).unwrap_or_else(|_| serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
-
-    // 只更新 security.auth.selectedType 字段
+/// 设置 settings.json 内容中的 `security.auth.selectedType`，保留其他字段
+pub fn set_selected_type(settings_content: &mut Value, selected_type: &str) {
     if let Some(obj) = settings_content.as_object_mut() {
         let security = obj
             .entry("security")
@@ -327,16 +619,11 @@ fn update_selected_type(selected_type: &str) -> Result<(), AppError> {
             }
         }
     }
-
-    // 写入文件
-    crate::config::write_json_file(&settings_path, &settings_content)?;
-
-    Ok(())
 }
 
-/// 为 Packycode Gemini 供应商写入 settings.json
+/// 为 API Key 模式（Packycode 等）的供应商设置 settings.json 内容
 ///
-/// 设置 `~/.gemini/settings.json` 中的：
+/// 设置 `~/.gemini/settings.json` 内容中的：
 /// ```json
 /// {
 ///   "security": {
@@ -348,13 +635,13 @@ fn update_selected_type(selected_type: &str) -> Result<(), AppError> {
 /// ```
 ///
 /// 保留文件中的其他所有字段。
-pub fn write_packycode_settings() -> Result<(), AppError> {
-    update_selected_type("gemini-api-key")
+pub fn apply_packycode_settings(settings: &mut Value) {
+    set_selected_type(settings, SELECTED_TYPE_API_KEY);
 }
 
-/// 为 Google 官方 Gemini 供应商写入 settings.json（OAuth 模式）
+/// 为 Google 官方 Gemini 供应商设置 settings.json 内容（OAuth 模式）
 ///
-/// 设置 `~/.gemini/settings.json` 中的：
+/// 设置 `~/.gemini/settings.json` 内容中的：
 /// ```json
 /// {
 ///   "security": {
@@ -366,8 +653,8 @@ pub fn write_packycode_settings() -> Result<(), AppError> {
 /// ```
 ///
 /// 保留文件中的其他所有字段。
-pub fn write_google_oauth_settings() -> Result<(), AppError> {
-    update_selected_type("oauth-personal")
+pub fn apply_google_oauth_settings(settings: &mut Value) {
+    set_selected_type(settings, SELECTED_TYPE_OAUTH);
 }
 
 #[cfg(test)]
@@ -648,4 +935,79 @@ KEY_WITH-DASH=value";
 
         assert!(validate_gemini_settings(&settings).is_err());
     }
+
+    #[test]
+    fn test_detect_drift_auth_type() {
+        let api_key_env = "GEMINI_API_KEY=sk-test\n";
+        let oauth = r#"{"security":{"auth":{"selectedType":"oauth-personal"}}}"#;
+        let api_key = r#"{"security":{"auth":{"selectedType":"gemini-api-key"}}}"#;
+
+        assert!(detect_drift(Some(api_key_env), Some(api_key)).is_empty());
+        assert_eq!(
+            detect_drift(Some(api_key_env), Some(oauth)),
+            vec![GeminiDrift::AuthTypeMismatch {
+                expected: "gemini-api-key".to_string(),
+                actual: Some("oauth-personal".to_string()),
+            }]
+        );
+        // 没有 Key 却选了 API Key 模式
+        assert_eq!(detect_drift(None, Some(api_key)).len(), 1);
+        // 用户自管的其他认证方式与全新安装都不算漂移
+        assert!(detect_drift(
+            None,
+            Some(r#"{"security":{"auth":{"selectedType":"vertex-ai"}}}"#)
+        )
+        .is_empty());
+        assert!(detect_drift(None, None).is_empty());
+    }
+
+    #[test]
+    fn test_detect_drift_invalid_files() {
+        let drifts = detect_drift(Some("GEMINI_API_KEY=sk\nbroken line\n"), Some("{ not json"));
+        assert!(matches!(drifts[0], GeminiDrift::InvalidEnv { .. }));
+        assert!(matches!(drifts[1], GeminiDrift::InvalidSettingsJson { .. }));
+        assert_eq!(drifts.len(), 2);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_dual_write_and_repair() {
+        let temp = tempfile::TempDir::new().expect("temp dir");
+        let original_test_home = std::env::var_os("CC_SWITCH_TEST_HOME");
+        std::env::set_var("CC_SWITCH_TEST_HOME", temp.path());
+
+        let mut env = HashMap::new();
+        env.insert("GEMINI_API_KEY".to_string(), "sk-test".to_string());
+        let settings = serde_json::json!({ "general": { "vimMode": true } });
+        write_gemini_live_files(Some(&env), Some(&settings)).expect("dual write");
+        assert_eq!(read_gemini_env().unwrap(), env);
+
+        // 写入后无法原样读回的 .env（值含换行）会回滚两个文件
+        let mut broken = env.clone();
+        broken.insert("GEMINI_MODEL".to_string(), "a\nb".to_string());
+        let other = serde_json::json!({ "other": true });
+        assert!(write_gemini_live_files(Some(&broken), Some(&other)).is_err());
+        assert_eq!(read_gemini_env().unwrap(), env);
+        let on_disk: Value = crate::config::read_json_file(&get_gemini_settings_path()).unwrap();
+        assert_eq!(on_disk, settings);
+
+        // settings.json 缺少 selectedType → 按 .env 修复，并保留其他字段
+        fs::write(get_gemini_dir().join("settings.json.tmp.1"), "{}").unwrap();
+        let report = repair_gemini_live(true).unwrap();
+        assert_eq!(report.drifts.len(), 2);
+        assert!(!report.repaired);
+
+        let report = repair_gemini_live(false).unwrap();
+        assert!(report.repaired);
+        assert_eq!(report.backups.len(), 2);
+        assert!(inspect_gemini_live().unwrap().is_empty());
+        let on_disk: Value = crate::config::read_json_file(&get_gemini_settings_path()).unwrap();
+        assert_eq!(selected_type(&on_disk), Some("gemini-api-key"));
+        assert_eq!(on_disk["general"]["vimMode"], true);
+
+        match original_test_home {
+            Some(value) => std::env::set_var("CC_SWITCH_TEST_HOME", value),
+            None => std::env::remove_var("CC_SWITCH_TEST_HOME"),
+        }
+    }
 }
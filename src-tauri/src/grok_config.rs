@@ -293,10 +293,15 @@ pub fn update_api_key(config_toml: &str, api_key: &str) -> Result<String, AppErr
     update_selected_model_string(config_toml, "api_key", api_key)
 }
 
+/// 也匹配 `<占位符>-<令牌>`：开启代理鉴权时写入的是代理访问令牌。
 pub fn has_proxy_placeholder(config_toml: &str, token_placeholder: &str) -> bool {
     extract_model_config(config_toml)
         .and_then(|config| config.api_key)
-        .is_some_and(|api_key| api_key == token_placeholder)
+        .is_some_and(|api_key| {
+            api_key
+                .strip_prefix(token_placeholder)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+        })
 }
 
 pub fn base_url_matches(config_toml: &str, predicate: impl FnOnce(&str) -> bool) -> bool {
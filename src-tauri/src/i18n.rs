@@ -0,0 +1,293 @@
+//! 后端文案国际化
+//!
+//! 文案与前端共用语言包（`src/i18n/locales/*.json`）：后端消息放在 `backend` 节点下，
+//! 命令行帮助放在 `cli.help` 节点下。界面、命令行与核心服务因此只需传递 key，
+//! 由各自按当前语言渲染；目前后端提供中文与英文，其余语言回退到英文。
+//!
+//! 语言取自设置中的 `language` 字段（设置加载或更新时通过 [`set_language`] 同步）。
+//! [`crate::error::AppError::Localized`] 的 `Display` 始终是“中文 (English)”双语，
+//! 日志与测试因此不受语言设置影响；只在输出界面（命令行、前端命令返回值）
+//! 通过 [`crate::error::AppError::message`] 取单一语言。
+
+use std::sync::{OnceLock, RwLock};
+
+use chrono::{DateTime, Local, TimeZone};
+
+use serde_json::Value;
+
+use crate::error::AppError;
+
+const EN_CATALOG: &str = include_str!("../../src/i18n/locales/en.json");
+const ZH_CATALOG: &str = include_str!("../../src/i18n/locales/zh.json");
+
+/// 后端文案语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    Zh,
+    En,
+}
+
+impl Lang {
+    /// 解析语言代码；`zh-TW` 等中文变体统一使用简体中文文案
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        if value == "zh" || value.starts_with("zh-") || value.starts_with("zh_") {
+            Some(Self::Zh)
+        } else if value == "en" || value.starts_with("en-") || value.starts_with("en_") {
+            Some(Self::En)
+        } else {
+            None
+        }
+    }
+
+    /// 界面语言设置 > 系统区域 > 英文
+    pub fn detect() -> Self {
+        crate::settings::preload_settings();
+        configured()
+            .or_else(|| sys_locale::get_locale().as_deref().and_then(Self::parse))
+            .unwrap_or(Self::En)
+    }
+}
+
+/// 设置中的界面语言代码（en / zh / zh-TW / ja），独立于设置锁缓存
+static CONFIGURED: RwLock<Option<String>> = RwLock::new(None);
+
+/// 同步设置中的界面语言（由设置模块在加载 / 更新时调用，不持有设置锁）
+pub fn set_language(language: Option<&str>) {
+    let mut guard = CONFIGURED.write().unwrap_or_else(|e| e.into_inner());
+    *guard = language.map(str::to_string);
+}
+
+fn configured_tag() -> Option<String> {
+    CONFIGURED.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// 用户在设置中选择的语言；未选择时为 None（日语等暂无后端文案，按英文处理）
+pub fn configured() -> Option<Lang> {
+    configured_tag().map(|tag| Lang::parse(&tag).unwrap_or(Lang::En))
+}
+
+pub(crate) fn catalog(lang: Lang) -> &'static Value {
+    static EN: OnceLock<Value> = OnceLock::new();
+    static ZH: OnceLock<Value> = OnceLock::new();
+    let (cell, source) = match lang {
+        Lang::En => (&EN, EN_CATALOG),
+        Lang::Zh => (&ZH, ZH_CATALOG),
+    };
+    cell.get_or_init(|| serde_json::from_str(source).unwrap_or(Value::Null))
+}
+
+/// 按点分路径查找语言包中的字符串
+pub(crate) fn lookup(lang: Lang, path: &str) -> Option<&'static str> {
+    path.split('.')
+        .try_fold(catalog(lang), |node, part| node.get(part))?
+        .as_str()
+}
+
+/// 渲染 `backend.<key>`：当前语言 > 英文 > key 本身；`{{name}}` 替换为参数
+pub fn t_in(lang: Lang, key: &str, args: &[(&str, &str)]) -> String {
+    let path = format!("backend.{key}");
+    let template = lookup(lang, &path)
+        .or_else(|| lookup(Lang::En, &path))
+        .unwrap_or(key);
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{{{name}}}}}"), value)
+        })
+}
+
+/// 按当前语言渲染 `backend.<key>`
+pub fn t(key: &str, args: &[(&str, &str)]) -> String {
+    t_in(Lang::detect(), key, args)
+}
+
+/// 由语言包 key 构造可本地化错误，同时携带中英文，供各界面按自身语言输出
+pub fn error(key: &'static str, args: &[(&str, &str)]) -> AppError {
+    AppError::Localized {
+        key,
+        zh: t_in(Lang::Zh, key, args),
+        en: t_in(Lang::En, key, args),
+    }
+}
+
+/// 数字、金额与日期的区域格式（千位分隔符、小数点、货币位置、日期顺序）
+///
+/// 只用于给人看的输出（命令行表格、本地化导出、通知文案）；
+/// JSON 等机器可读输出始终保持原始格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocaleFormat {
+    group: char,
+    decimal: char,
+    /// 货币符号在数字之后（如 `1.234,50 $`）
+    currency_suffix: bool,
+    date: &'static str,
+}
+
+impl LocaleFormat {
+    /// 按 BCP 47 / POSIX 区域代码选择格式；未知区域使用 en-US
+    pub fn for_tag(tag: &str) -> Self {
+        let tag = tag.trim().replace('_', "-").to_lowercase();
+        let tag = tag.split('.').next().unwrap_or_default();
+        let (language, region) = tag.split_once('-').unwrap_or((tag, ""));
+        let (group, decimal, currency_suffix) = match language {
+            "de" | "nl" | "es" | "it" | "pt" | "da" | "id" | "tr" => ('.', ',', true),
+            "fr" | "ru" | "pl" | "sv" | "cs" | "fi" | "nb" | "no" | "uk" => ('\u{a0}', ',', true),
+            _ => (',', '.', false),
+        };
+        let date = match (language, region) {
+            ("en", "" | "us") => "%m/%d/%Y",
+            ("zh" | "sv", _) => "%Y-%m-%d",
+            ("ja" | "ko", _) => "%Y/%m/%d",
+            ("de" | "ru" | "pl" | "cs" | "fi" | "nb" | "no" | "uk" | "tr", _) => "%d.%m.%Y",
+            ("en" | "fr" | "es" | "it" | "pt" | "nl" | "da" | "id", _) => "%d/%m/%Y",
+            _ => "%Y-%m-%d",
+        };
+        Self {
+            group,
+            decimal,
+            currency_suffix,
+            date,
+        }
+    }
+
+    /// 界面语言与系统区域同一语种时采用系统区域（保留 en-GB 等地区差异），
+    /// 否则使用界面语言；都没有时为 en-US
+    pub fn current() -> Self {
+        crate::settings::preload_settings();
+        let system = sys_locale::get_locale();
+        let tag = match (configured_tag(), system) {
+            (Some(language), Some(system))
+                if system.to_lowercase().starts_with(&language.to_lowercase()) =>
+            {
+                system
+            }
+            (Some(language), _) => language,
+            (None, Some(system)) => system,
+            (None, None) => "en-US".to_string(),
+        };
+        Self::for_tag(&tag)
+    }
+
+    /// 整数加千位分隔符
+    pub fn integer(&self, value: i64) -> String {
+        let digits = value.unsigned_abs().to_string();
+        let mut out = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+        if value < 0 {
+            out.push('-');
+        }
+        for (index, ch) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index) % 3 == 0 {
+                out.push(self.group);
+            }
+            out.push(ch);
+        }
+        out
+    }
+
+    /// 定点小数（`digits` 位）
+    pub fn decimal(&self, value: f64, digits: usize) -> String {
+        let raw = format!("{:.*}", digits, value.abs());
+        let (int_part, frac_part) = raw.split_once('.').unwrap_or((&raw, ""));
+        let int_value = int_part.parse::<i64>().unwrap_or(0);
+        let negative = value < 0.0 && raw.chars().any(|c| c.is_ascii_digit() && c != '0');
+        let mut out = String::new();
+        if negative {
+            out.push('-');
+        }
+        out.push_str(&self.integer(int_value));
+        if !frac_part.is_empty() {
+            out.push(self.decimal);
+            out.push_str(frac_part);
+        }
+        out
+    }
+
+    /// 美元金额：两位小数；不足一分的非零金额保留四位，避免显示为 0
+    pub fn usd(&self, value: f64) -> String {
+        let digits = if value != 0.0 && value.abs() < 0.01 {
+            4
+        } else {
+            2
+        };
+        let amount = self.decimal(value, digits);
+        if self.currency_suffix {
+            format!("{amount}\u{a0}$")
+        } else if let Some(amount) = amount.strip_prefix('-') {
+            format!("-${amount}")
+        } else {
+            format!("${amount}")
+        }
+    }
+
+    /// 以十进制字符串存储的美元金额（数据库中的成本字段）；无法解析时原样返回
+    pub fn usd_str(&self, raw: &str) -> String {
+        raw.trim()
+            .parse::<f64>()
+            .map(|value| self.usd(value))
+            .unwrap_or_else(|_| raw.to_string())
+    }
+
+    pub fn date<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        time.format(self.date).to_string()
+    }
+
+    /// 本地时区的日期与 24 小时制时间
+    pub fn datetime<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String {
+        let local = time.with_timezone(&Local);
+        format!("{} {}", self.date(&local), local.format("%H:%M"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_language_codes() {
+        assert_eq!(Lang::parse("zh-TW"), Some(Lang::Zh));
+        assert_eq!(Lang::parse("en_US"), Some(Lang::En));
+        assert_eq!(Lang::parse("ja"), None);
+    }
+
+    #[test]
+    fn renders_backend_keys_with_arguments_and_fallbacks() {
+        assert_eq!(
+            t_in(Lang::En, "skill.notFound", &[("id", "demo")]),
+            "Skill not found: demo"
+        );
+        assert_eq!(
+            t_in(Lang::Zh, "skill.notFound", &[("id", "demo")]),
+            "未找到 Skill: demo"
+        );
+        assert_eq!(t_in(Lang::Zh, "no.such.key", &[]), "no.such.key");
+
+        let err = error("skill.notFound", &[("id", "demo")]);
+        assert_eq!(err.message(Lang::En), "Skill not found: demo");
+        assert_eq!(err.key(), Some("skill.notFound"));
+    }
+
+    #[test]
+    fn formats_numbers_and_dates_per_locale() {
+        let us = LocaleFormat::for_tag("en_US.UTF-8");
+        assert_eq!(us.integer(1_234_567), "1,234,567");
+        assert_eq!(us.integer(-999), "-999");
+        assert_eq!(us.usd(1234.5), "$1,234.50");
+        assert_eq!(us.usd(-0.004), "-$0.0040");
+        assert_eq!(us.usd_str("0"), "$0.00");
+        assert_eq!(us.usd_str("n/a"), "n/a");
+
+        let de = LocaleFormat::for_tag("de-DE");
+        assert_eq!(de.decimal(1234.5, 2), "1.234,50");
+        assert_eq!(de.usd(1234.5), "1.234,50\u{a0}$");
+
+        let time = chrono::Utc.with_ymd_and_hms(2026, 3, 9, 8, 0, 0).unwrap();
+        assert_eq!(us.date(&time), "03/09/2026");
+        assert_eq!(LocaleFormat::for_tag("en-GB").date(&time), "09/03/2026");
+        assert_eq!(de.date(&time), "09.03.2026");
+        assert_eq!(LocaleFormat::for_tag("zh").date(&time), "2026-03-09");
+        assert_eq!(LocaleFormat::for_tag("ja").date(&time), "2026/03/09");
+    }
+}
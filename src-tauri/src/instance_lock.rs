@@ -0,0 +1,282 @@
+//! 跨进程写锁
+//!
+//! GUI 与 `cc-switch` CLI 可能同时运行，两边都会切换供应商、改写 Live 配置。
+//! 这里用配置目录下的 `write.lock` 文件锁（系统级 advisory lock）串行化这些写操作：
+//!
+//! - 锁由操作系统在进程退出时自动释放，不存在崩溃后遗留的"死锁文件"；
+//! - 同一进程内可重入（嵌套调用、不同线程共享），进程内的互斥仍由各服务自己的锁负责；
+//! - 等待超时后返回"另一个实例持有写锁"的错误，而不是冒险并发写入。
+//!
+//! SQLite 层面的并发由 `Database` 设置的 busy timeout 处理。
+//!
+//! 等待锁时会阻塞当前线程，调用方应在阻塞线程池（`spawn_blocking`）上调用。
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config::get_app_config_dir;
+use crate::error::AppError;
+
+const LOCK_FILE: &str = "write.lock";
+
+/// 等待其他实例释放写锁的最长时间
+const LOCK_WAIT: Duration = Duration::from_secs(10);
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+struct Held {
+    file: File,
+    depth: usize,
+}
+
+/// 本进程已持有的锁（按锁文件路径）
+static HELD: LazyLock<Mutex<HashMap<PathBuf, Held>>> = LazyLock::new(Default::default);
+
+/// 写锁守卫，离开作用域时释放
+#[must_use = "写锁在守卫被丢弃时立即释放"]
+pub struct WriteLockGuard {
+    path: PathBuf,
+}
+
+impl Drop for WriteLockGuard {
+    fn drop(&mut self) {
+        let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(entry) = held.get_mut(&self.path) else {
+            return;
+        };
+        entry.depth -= 1;
+        if entry.depth == 0 {
+            if let Some(entry) = held.remove(&self.path) {
+                if let Err(e) = unlock_file(&entry.file) {
+                    log::warn!("[InstanceLock] 释放写锁失败 {}: {e}", self.path.display());
+                }
+            }
+        }
+    }
+}
+
+/// 获取配置写锁；`operation` 写入锁文件，供被阻塞的实例提示是谁在写
+pub fn acquire(operation: &str) -> Result<WriteLockGuard, AppError> {
+    acquire_at(&get_app_config_dir().join(LOCK_FILE), operation, LOCK_WAIT)
+}
+
+fn acquire_at(path: &Path, operation: &str, wait: Duration) -> Result<WriteLockGuard, AppError> {
+    let started = Instant::now();
+    loop {
+        {
+            let mut held = HELD.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(entry) = held.get_mut(path) {
+                entry.depth += 1;
+                return Ok(WriteLockGuard {
+                    path: path.to_path_buf(),
+                });
+            }
+
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+            }
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(path)
+                .map_err(|e| open_error(path, e))?;
+            if try_lock_file(&file).map_err(|e| AppError::io(path, e))? {
+                record_owner(&mut file, operation);
+                held.insert(path.to_path_buf(), Held { file, depth: 1 });
+                return Ok(WriteLockGuard {
+                    path: path.to_path_buf(),
+                });
+            }
+        }
+
+        if started.elapsed() >= wait {
+            return Err(locked_error(path));
+        }
+        std::thread::sleep(RETRY_INTERVAL);
+    }
+}
+
+/// 非阻塞地获取排他锁；被其他句柄持有时返回 `Ok(false)`
+#[cfg(unix)]
+fn try_lock_file(file: &File) -> std::io::Result<bool> {
+    use std::os::unix::io::AsRawFd;
+    // SAFETY: fd 在 file 的生命周期内有效
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(true);
+    }
+    let err = std::io::Error::last_os_error();
+    if err.raw_os_error() == Some(libc::EWOULDBLOCK) {
+        Ok(false)
+    } else {
+        Err(err)
+    }
+}
+
+#[cfg(unix)]
+fn unlock_file(file: &File) -> std::io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+    // SAFETY: fd 在 file 的生命周期内有效
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_UN) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(windows)]
+fn try_lock_file(file: &File) -> std::io::Result<bool> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Foundation::ERROR_LOCK_VIOLATION;
+    use windows_sys::Win32::Storage::FileSystem::{
+        LockFileEx, LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY,
+    };
+    use windows_sys::Win32::System::IO::OVERLAPPED;
+
+    // SAFETY: 句柄在 file 的生命周期内有效，OVERLAPPED 全零即从偏移 0 开始加锁
+    let locked = unsafe {
+        let mut overlapped: OVERLAPPED = std::mem::zeroed();
+        LockFileEx(
+            file.as_raw_handle() as _,
+            LOCKFILE_EXCLUSIVE_LOCK | LOCKFILE_FAIL_IMMEDIATELY,
+            0,
+            u32::MAX,
+            u32::MAX,
+            &mut overlapped,
+        )
+    };
+    if locked != 0 {
+        return Ok(true);
+    }
+    let err = std::io::Error::last_os_error();
+    if err.raw_os_error() == Some(ERROR_LOCK_VIOLATION as i32) {
+        Ok(false)
+    } else {
+        Err(err)
+    }
+}
+
+#[cfg(windows)]
+fn unlock_file(file: &File) -> std::io::Result<()> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Storage::FileSystem::UnlockFile;
+
+    // SAFETY: 句柄在 file 的生命周期内有效
+    if unsafe { UnlockFile(file.as_raw_handle() as _, 0, 0, u32::MAX, u32::MAX) } != 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn try_lock_file(_file: &File) -> std::io::Result<bool> {
+    Ok(true)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn unlock_file(_file: &File) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// 锁文件内容仅用于提示，写入失败不影响加锁
+fn record_owner(file: &mut File, operation: &str) {
+    let owner = format!(
+        "pid={} user={} operation={operation}\n",
+        std::process::id(),
+        crate::os_user::user_name()
+    );
+    let result = file
+        .set_len(0)
+        .and_then(|_| file.write_all(owner.as_bytes()))
+        .and_then(|_| file.flush());
+    if let Err(e) = result {
+        log::debug!("[InstanceLock] 写入锁文件持有者信息失败: {e}");
+    }
+}
+
+/// 锁文件由其他系统用户创建时无权打开：说明数据目录被多个用户共用
+fn open_error(path: &Path, e: std::io::Error) -> AppError {
+    if e.kind() != std::io::ErrorKind::PermissionDenied {
+        return AppError::io(path, e);
+    }
+    match path.parent().map(crate::os_user::check_data_dir) {
+        Some(Err(err)) => err,
+        _ => AppError::io(path, e),
+    }
+}
+
+fn locked_error(path: &Path) -> AppError {
+    // Windows 上被锁定的文件不可读，此时只给出通用提示
+    let owner = std::fs::read_to_string(path)
+        .ok()
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+    match owner {
+        Some(owner) => AppError::localized(
+            "instance.write_lock.busy",
+            format!("另一个 CC Switch 实例正在写入配置（{owner}），请稍后重试"),
+            format!(
+                "Another CC Switch instance holds the config lock ({owner}); try again shortly"
+            ),
+        ),
+        None => AppError::localized(
+            "instance.write_lock.busy",
+            "另一个 CC Switch 实例正在写入配置，请稍后重试",
+            "Another CC Switch instance holds the config lock; try again shortly",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reentrant_within_process_and_released_on_drop() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+
+        let outer = acquire_at(&path, "outer", Duration::ZERO).unwrap();
+        let inner = acquire_at(&path, "inner", Duration::ZERO).unwrap();
+        drop(inner);
+        // 外层仍持有：另一个文件句柄（模拟其他进程）拿不到锁
+        let other = File::open(&path).unwrap();
+        assert!(!try_lock_file(&other).unwrap());
+        drop(outer);
+        assert!(try_lock_file(&other).unwrap());
+    }
+
+    #[test]
+    fn reports_other_instance_after_waiting() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(LOCK_FILE);
+        let mut other = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .unwrap();
+        assert!(try_lock_file(&other).unwrap());
+        record_owner(&mut other, "provider.switch");
+
+        let err = match acquire_at(&path, "provider.update", Duration::from_millis(150)) {
+            Ok(_) => panic!("lock should be held by the other handle"),
+            Err(e) => e,
+        };
+        assert!(matches!(
+            err,
+            AppError::Localized {
+                key: "instance.write_lock.busy",
+                ..
+            }
+        ));
+    }
+}
@@ -4,11 +4,15 @@ mod auto_launch;
 mod claude_desktop_config;
 mod claude_mcp;
 mod claude_plugin;
+mod claude_plugin_sync;
+mod cli;
 mod codex_config;
+mod codex_config_model;
 mod codex_history_migration;
 mod codex_state_db;
 mod commands;
 mod config;
+mod config_watcher;
 mod database;
 mod deeplink;
 mod error;
@@ -16,7 +20,9 @@ mod gemini_config;
 mod gemini_mcp;
 mod grok_config;
 pub mod hermes_config;
+mod i18n;
 mod init_status;
+mod instance_lock;
 mod lightweight;
 #[cfg(target_os = "linux")]
 mod linux_fix;
@@ -24,15 +30,19 @@ mod mcp;
 mod model_capabilities;
 mod openclaw_config;
 mod opencode_config;
+mod os_user;
 mod panic_hook;
+mod progress_events;
 mod prompt;
 mod prompt_files;
 mod provider;
 mod provider_defaults;
 mod proxy;
+mod safe_mode;
 mod services;
 mod session_manager;
 mod settings;
+mod settings_overrides;
 mod store;
 
 mod tray;
@@ -40,7 +50,9 @@ mod usage_events;
 mod usage_script;
 
 pub use app_config::{AppType, InstalledSkill, McpApps, McpServer, MultiAppConfig, SkillApps};
+pub use cli::run as run_cli;
 pub use codex_config::{get_codex_auth_path, get_codex_config_path, write_codex_live_atomic};
+pub use codex_config_model::CodexConfig;
 pub use commands::open_provider_terminal;
 pub use commands::*;
 pub use config::{get_claude_mcp_path, get_claude_settings_path, read_json_file};
@@ -478,10 +490,26 @@ pub fn run() {
                 }
             }
 
+            // 全局快捷键（场景宏）；回调按快捷键 id 查找绑定的宏
+            #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+            {
+                if let Err(e) = app.handle().plugin(
+                    tauri_plugin_global_shortcut::Builder::new()
+                        .with_handler(crate::services::scenario::handle_hotkey)
+                        .build(),
+                ) {
+                    log::warn!("初始化全局快捷键插件失败，已跳过：{e}");
+                }
+            }
+
             // 注入 AppHandle 给 usage_events，让无 AppHandle 持有的写日志路径
             // 也能向前端推送 `usage-log-recorded`。
             // 放在日志系统初始化之后，确保 init 的日志能正常输出。
             usage_events::init(app.handle().clone());
+            progress_events::init(app.handle().clone());
+
+            // 设置文件与数据库互不依赖：后台线程预热设置缓存，与数据库打开（含 schema 迁移）并行
+            let settings_warmup = std::thread::spawn(crate::settings::preload_settings);
 
             // 初始化数据库
             let app_config_dir = crate::config::get_app_config_dir();
@@ -561,8 +589,16 @@ pub fn run() {
                     Err(e) => {
                         log::error!("Failed to init database: {e}");
 
-                        if !show_database_init_error_dialog(app.handle(), &db_path, &e.to_string())
-                        {
+                        let lang = if is_chinese_locale() {
+                            crate::i18n::Lang::Zh
+                        } else {
+                            crate::i18n::Lang::En
+                        };
+                        if !show_database_init_error_dialog(
+                            app.handle(),
+                            &db_path,
+                            &e.message(lang),
+                        ) {
                             log::info!("用户选择退出程序");
                             std::process::exit(1);
                         }
@@ -572,6 +608,10 @@ pub fn run() {
                 }
             };
 
+            if settings_warmup.join().is_err() {
+                log::warn!("预加载设置失败，将在首次访问时重新读取");
+            }
+
             // 数据库可用后立即应用持久化日志级别，避免后续服务初始化
             // 继续使用启动阶段的 Info 回退。损坏配置显式 fail-closed 到 Info。
             match db.get_log_config() {
@@ -1077,33 +1117,24 @@ pub fn run() {
             }
 
             let _tray = tray_builder.build(app)?;
-            crate::services::webdav_auto_sync::start_worker(
-                app_state.db.clone(),
-                app.handle().clone(),
-            );
-            crate::services::s3_auto_sync::start_worker(
-                app_state.db.clone(),
-                app.handle().clone(),
+            // 监听 live 配置与数据库文件，外部修改后通知前端刷新
+            config_watcher::start(app.handle().clone());
+            crate::services::status_file::refresh(
+                &app_state.db,
+                crate::services::status_file::StatusSource::Startup,
             );
             // 将同一个实例注入到全局状态，避免重复创建导致的不一致
             app.manage(app_state);
 
-            // 初始化 SkillService
-            let skill_service = SkillService::new();
-            app.manage(commands::skill::SkillServiceState(Arc::new(skill_service)));
-
-            // 初始化 CopilotAuthManager
-            {
-                use crate::proxy::providers::copilot_auth::CopilotAuthManager;
-                use commands::CopilotAuthState;
-                use tokio::sync::RwLock;
-
-                let app_config_dir = crate::config::get_app_config_dir();
-                let copilot_auth_manager = CopilotAuthManager::new(app_config_dir);
-                app.manage(CopilotAuthState(Arc::new(RwLock::new(copilot_auth_manager))));
-                log::info!("✓ CopilotAuthManager initialized");
+            #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+            if !crate::safe_mode::is_enabled() {
+                crate::services::scenario::register_hotkeys(app.handle());
             }
 
+            // SkillService 与 CopilotAuthManager 在首次使用时才创建
+            app.manage(commands::skill::SkillServiceState::default());
+            app.manage(commands::CopilotAuthState::default());
+
             // 初始化 CodexOAuthManager (ChatGPT Plus/Pro 反代)
             {
                 use crate::proxy::providers::codex_oauth_auth::CodexOAuthManager;
@@ -1131,7 +1162,12 @@ pub fn run() {
             // 初始化全局出站代理 HTTP 客户端
             {
                 let db = &app.state::<AppState>().db;
-                let proxy_url = db.get_global_proxy_url().ok().flatten();
+                // 安全模式按直连初始化，不读取（也不清除）已保存的出站代理
+                let proxy_url = if crate::safe_mode::is_enabled() {
+                    None
+                } else {
+                    db.get_global_proxy_url().ok().flatten()
+                };
 
                 if let Err(e) = crate::proxy::http_client::init(proxy_url.as_deref()) {
                     log::error!(
@@ -1186,6 +1222,19 @@ pub fn run() {
 
                 initialize_common_config_snippets(&state);
 
+                // 安全模式下不启动云同步 worker（同步配置错误可能正是崩溃原因）
+                if crate::safe_mode::is_enabled() {
+                    log::warn!("安全模式：跳过云同步、代理自动恢复、定期备份、夜间自检与会话用量同步");
+                    return;
+                }
+
+                // 云同步 worker 不影响窗口显示，移出 setup 在后台任务中启动
+                crate::services::webdav_auto_sync::start_worker(
+                    state.db.clone(),
+                    app_handle.clone(),
+                );
+                crate::services::s3_auto_sync::start_worker(state.db.clone(), app_handle.clone());
+
                 // 检查 settings 表中的代理状态，自动恢复代理服务
                 restore_proxy_state_on_startup(&state).await;
 
@@ -1194,6 +1243,27 @@ pub fn run() {
                     log::warn!("Periodic backup failed on startup: {e}");
                 }
 
+                // 供应商图标：启动后延迟刷新过期缓存，之后定期刷新（未开启 / 离线模式时跳过）
+                let app_for_favicons = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    use crate::services::favicon::{
+                        FaviconService, EVENT_PROVIDER_FAVICONS_UPDATED, REFRESH_INTERVAL,
+                        STARTUP_DELAY,
+                    };
+                    tokio::time::sleep(STARTUP_DELAY).await;
+                    loop {
+                        let db = app_for_favicons.state::<AppState>().db.clone();
+                        if FaviconService::refresh_stale(&db).await > 0 {
+                            if let Err(e) =
+                                app_for_favicons.emit(EVENT_PROVIDER_FAVICONS_UPDATED, ())
+                            {
+                                log::warn!("[Favicon] 发送图标更新事件失败: {e}");
+                            }
+                        }
+                        tokio::time::sleep(REFRESH_INTERVAL).await;
+                    }
+                });
+
                 // Periodic maintenance timer: run once per day while the app is running
                 let db_for_timer = state.db.clone();
                 tauri::async_runtime::spawn(async move {
@@ -1210,6 +1280,141 @@ pub fn run() {
                     }
                 });
 
+                // 夜间自检：每 5 分钟检查是否进入自检时段 / 摘要提醒时间
+                let app_for_self_test = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    use crate::services::self_test::{SelfTestService, EVENT_SELF_TEST_SUMMARY};
+                    const SELF_TEST_TICK_SECS: u64 = 5 * 60;
+
+                    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                        SELF_TEST_TICK_SECS,
+                    ));
+                    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+                    loop {
+                        interval.tick().await;
+                        let state = app_for_self_test.state::<AppState>();
+                        let config = match state.db.get_self_test_config() {
+                            Ok(config) => config,
+                            Err(e) => {
+                                log::warn!("[SelfTest] 读取自检配置失败: {e}");
+                                continue;
+                            }
+                        };
+                        let mut last = state.db.get_self_test_report().ok().flatten();
+                        let now = chrono::Local::now();
+                        if SelfTestService::is_run_due(&config, last.as_ref(), now) {
+                            let copilot_state = app_for_self_test.state::<crate::commands::CopilotAuthState>();
+                            match SelfTestService::run(&state.db, &copilot_state).await {
+                                Ok(report) => last = Some(report),
+                                Err(e) => log::warn!("[SelfTest] 夜间自检失败: {e}"),
+                            }
+                        }
+                        if let Some(mut report) = last {
+                            if SelfTestService::is_summary_due(&config, &report, now) {
+                                if let Err(e) = app_for_self_test.emit(EVENT_SELF_TEST_SUMMARY, &report) {
+                                    log::warn!("[SelfTest] 发送自检摘要失败: {e}");
+                                    continue;
+                                }
+                                report.notified = true;
+                                if let Err(e) = state.db.save_self_test_report(&report) {
+                                    log::warn!("[SelfTest] 保存自检报告失败: {e}");
+                                }
+                            }
+                        }
+                    }
+                });
+
+                // 网络变化（VPN 开关、切换 Wi-Fi）或 `cc-switch network-changed` 后，
+                // 重新检查当前供应商与故障转移队列
+                let app_for_network = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    use crate::services::network_watch::{
+                        self, NetworkFingerprint, NetworkRecheckReport, NetworkWatcher,
+                        EVENT_NETWORK_RECHECK, NETWORK_WATCH_TICK_SECS,
+                    };
+                    use crate::services::self_test::SelfTestService;
+
+                    let mut watcher = NetworkWatcher::default();
+                    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                        NETWORK_WATCH_TICK_SECS,
+                    ));
+                    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+                    loop {
+                        interval.tick().await;
+                        let now = chrono::Utc::now().timestamp();
+                        let manual = network_watch::take_manual_request(now);
+                        let state = app_for_network.state::<AppState>();
+                        let enabled = state
+                            .db
+                            .get_self_test_config()
+                            .map(|config| config.network_recheck)
+                            .unwrap_or(true);
+                        if !enabled && !manual {
+                            continue;
+                        }
+                        let fingerprint =
+                            match tauri::async_runtime::spawn_blocking(NetworkFingerprint::capture)
+                                .await
+                            {
+                                Ok(fingerprint) => fingerprint,
+                                Err(e) => {
+                                    log::warn!("[Network] 采集网络指纹失败: {e}");
+                                    continue;
+                                }
+                            };
+                        let Some(reason) = watcher.poll(fingerprint.clone(), manual, now) else {
+                            continue;
+                        };
+                        log::info!("[Network] 检测到网络变化 ({reason:?})，重新检查供应商");
+                        let copilot_state =
+                            app_for_network.state::<crate::commands::CopilotAuthState>();
+                        match SelfTestService::check_proxy_targets(
+                            &state.db,
+                            Some(copilot_state.inner()),
+                            "network_recheck.run",
+                        )
+                        .await
+                        {
+                            Ok(apps) => {
+                                let report = NetworkRecheckReport {
+                                    reason,
+                                    fingerprint,
+                                    checked_at: chrono::Utc::now().timestamp(),
+                                    apps,
+                                };
+                                if let Err(e) = app_for_network.emit(EVENT_NETWORK_RECHECK, &report) {
+                                    log::warn!("[Network] 发送重新检查结果失败: {e}");
+                                }
+                            }
+                            Err(e) => log::warn!("[Network] 重新检查失败: {e}"),
+                        }
+                    }
+                });
+
+                // 候选端点池：每分钟检查当前供应商是否到了定期选择端点的时间
+                let app_for_endpoint_pool = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    const ENDPOINT_POOL_TICK_SECS: u64 = 60;
+
+                    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+                        ENDPOINT_POOL_TICK_SECS,
+                    ));
+                    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+                    loop {
+                        interval.tick().await;
+                        let state = app_for_endpoint_pool.state::<AppState>();
+                        crate::services::endpoint_pool::EndpointPoolService::run_due(&state).await;
+                    }
+                });
+
+                // 上游连接保温：开启后代理运行期间定期探测当前供应商
+                crate::proxy::warm_pool::spawn(&app_handle);
+
+                // 自动生成的 Copilot 供应商：定期按账号模型列表更新模型配置
+                crate::services::copilot_provisioning::CopilotProvisioningService::spawn_model_sync(
+                    &app_handle,
+                );
+
                 // Session log usage sync: 启动时同步一次，之后每 60 秒检查
                 let db_for_session_sync = state.db.clone();
                 tauri::async_runtime::spawn(async move {
@@ -1306,12 +1511,31 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::get_providers,
             commands::get_current_provider,
+            commands::get_provider_favicons,
             commands::add_provider,
             commands::update_provider,
             commands::delete_provider,
+            commands::get_provider_last_seen,
+            commands::get_provider_trust_levels,
+            commands::set_provider_trust,
+            commands::get_provider_trust_policy,
+            commands::save_provider_trust_policy,
+            commands::get_stale_providers,
+            commands::archive_providers,
+            commands::copy_provider_as_curl,
+            commands::probe_provider_capabilities,
+            commands::send_test_message_to_provider,
+            commands::get_provider_capabilities,
+            commands::get_provider_notices,
+            commands::dismiss_provider_notice,
+            commands::get_prompt_integrity_signals,
             commands::remove_provider_from_live_config,
             commands::switch_provider,
             commands::import_default_config,
+            commands::scan_live_import_candidates,
+            commands::get_live_config_drift,
+            commands::resolve_live_config_drift,
+            commands::import_live_configs,
             commands::get_claude_desktop_status,
             commands::get_claude_desktop_default_routes,
             commands::import_claude_desktop_providers_from_claude,
@@ -1336,25 +1560,42 @@ pub fn run() {
             commands::set_common_config_snippet,
             commands::update_toml_common_config_snippet,
             commands::extract_common_config_snippet,
+            commands::analyze_common_config_migration,
+            commands::apply_common_config_migration,
+            commands::undo_common_config_migration,
+            commands::repair_gemini_config,
             commands::read_live_provider_settings,
             commands::get_settings,
+            commands::get_managed_settings,
             commands::save_settings,
             commands::has_codex_unify_history_backup,
             commands::restore_codex_unified_history,
             commands::get_rectifier_config,
             commands::set_rectifier_config,
+            commands::get_retry_config,
+            commands::set_retry_config,
+            commands::get_drain_config,
+            commands::set_drain_config,
+            commands::get_failback_watchdog_config,
+            commands::set_failback_watchdog_config,
             commands::get_optimizer_config,
             commands::set_optimizer_config,
+            commands::get_response_cache_config,
+            commands::set_response_cache_config,
+            commands::get_response_cache_stats,
+            commands::clear_response_cache,
             commands::get_copilot_optimizer_config,
             commands::set_copilot_optimizer_config,
             commands::get_log_config,
             commands::set_log_config,
             commands::restart_app,
+            commands::is_safe_mode,
             commands::install_update_and_restart,
             commands::check_app_update_available,
             commands::check_for_updates,
             commands::is_portable_mode,
             commands::copy_text_to_clipboard,
+            commands::read_clipboard_text,
             commands::get_claude_plugin_status,
             commands::read_claude_plugin_config,
             commands::apply_claude_plugin_config,
@@ -1389,6 +1630,9 @@ pub fn run() {
             commands::delete_mcp_server,
             commands::toggle_mcp_app,
             commands::import_mcp_from_apps,
+            commands::check_mcp_runtime,
+            commands::prepare_mcp_runtime,
+            commands::verify_mcp_server,
             // Prompt management
             commands::get_prompts,
             commands::upsert_prompt,
@@ -1396,6 +1640,9 @@ pub fn run() {
             commands::enable_prompt,
             commands::import_prompt_from_file,
             commands::get_current_prompt_file_content,
+            commands::get_prompt_versions,
+            commands::preview_prompt_version,
+            commands::deploy_prompt_version,
             // Profile management (项目配置方案)
             commands::list_profiles,
             commands::create_profile,
@@ -1422,13 +1669,19 @@ pub fn run() {
             commands::webdav_test_connection,
             commands::webdav_sync_upload,
             commands::webdav_sync_download,
+            commands::webdav_sync_preview_restore,
+            commands::webdav_sync_restore_selected,
             commands::webdav_sync_save_settings,
             commands::webdav_sync_fetch_remote_info,
+            commands::webdav_sync_delete_remote,
             commands::s3_test_connection,
             commands::s3_sync_upload,
             commands::s3_sync_download,
+            commands::s3_sync_preview_restore,
+            commands::s3_sync_restore_selected,
             commands::s3_sync_save_settings,
             commands::s3_sync_fetch_remote_info,
+            commands::s3_sync_delete_remote,
             commands::save_file_dialog,
             commands::open_file_dialog,
             commands::open_zip_file_dialog,
@@ -1441,6 +1694,7 @@ pub fn run() {
             // Deep link import
             commands::parse_deeplink,
             commands::merge_deeplink_config,
+            commands::parse_provider_paste,
             commands::import_from_deeplink,
             commands::import_from_deeplink_unified,
             update_tray_menu,
@@ -1448,12 +1702,17 @@ pub fn run() {
             commands::check_env_conflicts,
             commands::delete_env_vars,
             commands::restore_env_backup,
+            // Local usage telemetry (opt-in)
+            commands::get_telemetry_snapshot,
+            commands::reset_telemetry,
+            commands::export_telemetry_report,
             // Skill management (v3.10.0+ unified)
             commands::get_installed_skills,
             commands::get_skill_backups,
             commands::delete_skill_backup,
             commands::install_skill_unified,
             commands::uninstall_skill_unified,
+            commands::get_project_skill_installs,
             commands::restore_skill_backup,
             commands::toggle_skill_app,
             commands::scan_unmanaged_skills,
@@ -1477,6 +1736,7 @@ pub fn run() {
             // Auto launch
             commands::set_auto_launch,
             commands::get_auto_launch_status,
+            commands::test_notification_channel,
             // Proxy server management
             commands::start_proxy_server,
             commands::stop_proxy_server,
@@ -1486,6 +1746,16 @@ pub fn run() {
             commands::get_proxy_status,
             commands::get_proxy_config,
             commands::update_proxy_config,
+            commands::get_proxy_auth_config,
+            commands::set_proxy_auth_enabled,
+            commands::list_proxy_auth_tokens,
+            commands::create_proxy_auth_token,
+            commands::rotate_proxy_auth_token,
+            commands::revoke_proxy_auth_token,
+            commands::get_proxy_auth_token_usage,
+            commands::run_proxy_bench,
+            commands::get_proxy_log_rules,
+            commands::set_proxy_log_level,
             // Global & Per-App Config
             commands::get_global_proxy_config,
             commands::update_global_proxy_config,
@@ -1509,6 +1779,7 @@ pub fn run() {
             commands::get_available_providers_for_failover,
             commands::add_to_failover_queue,
             commands::remove_from_failover_queue,
+            commands::reorder_failover_queue,
             commands::get_auto_failover_enabled,
             commands::set_auto_failover_enabled,
             // Usage statistics
@@ -1516,9 +1787,11 @@ pub fn run() {
             commands::get_usage_summary_by_app,
             commands::get_usage_trends,
             commands::get_provider_stats,
+            commands::get_workload_comparison,
             commands::get_model_stats,
             commands::get_request_logs,
             commands::get_request_detail,
+            commands::copy_request_as_curl,
             commands::get_model_pricing,
             commands::update_model_pricing,
             commands::delete_model_pricing,
@@ -1527,17 +1800,30 @@ pub fn run() {
             commands::sync_session_usage,
             commands::rebuild_codex_usage,
             commands::get_usage_data_sources,
+            commands::export_usage,
+            commands::simulate_provider_cost,
+            commands::migrate_secrets_to_keychain,
+            commands::export_secrets_for_password_manager,
+            commands::import_secrets_from_password_manager,
             // Stream health check
             commands::stream_check_provider,
             commands::stream_check_all_providers,
             commands::get_stream_check_config,
             commands::save_stream_check_config,
+            commands::get_self_test_config,
+            commands::save_self_test_config,
+            commands::get_self_test_report,
+            commands::run_self_test_now,
+            commands::take_pending_self_test_summary,
             // Session manager
             commands::list_sessions,
+            commands::list_recent_sessions,
             commands::get_session_messages,
             commands::delete_session,
             commands::delete_sessions,
             commands::launch_session_terminal,
+            commands::trigger_live_reload,
+            commands::check_live_reload,
             commands::get_tool_versions,
             commands::run_tool_lifecycle_action,
             commands::probe_tool_installations,
@@ -1610,6 +1896,8 @@ pub fn run() {
             commands::copilot_get_models_for_account,
             commands::copilot_get_usage,
             commands::copilot_get_usage_for_account,
+            commands::copilot_provision_providers,
+            commands::copilot_sync_provisioned_providers,
             // OMO commands
             commands::read_omo_local_file,
             commands::get_current_omo_provider_id,
@@ -147,6 +147,81 @@ pub fn remove_provider(id: &str) -> Result<(), AppError> {
     write_opencode_config(&config)
 }
 
+/// 取出供应商片段（`provider.<id>` 的内容）
+///
+/// 兼容旧数据：settings_config 中存的是整份 opencode.json（含 `$schema` 或顶层 `provider`）时，
+/// 取其中 `provider.<id>`；取不到则原样返回。
+pub fn provider_fragment<'a>(id: &str, settings: &'a Value) -> &'a Value {
+    let is_full_config = settings
+        .as_object()
+        .is_some_and(|obj| obj.contains_key("$schema") || obj.contains_key("provider"));
+    if !is_full_config {
+        return settings;
+    }
+    log::warn!(
+        "OpenCode provider '{id}' has full config structure in settings_config, attempting to extract fragment"
+    );
+    settings
+        .get("provider")
+        .and_then(|providers| providers.get(id))
+        .unwrap_or(settings)
+}
+
+/// 校验供应商片段：`{ npm, name?, options?: { baseURL?, apiKey?, ... }, models? }`
+pub fn validate_provider_fragment(fragment: &Value) -> Result<(), AppError> {
+    let obj = fragment.as_object().ok_or_else(|| {
+        AppError::localized(
+            "provider.opencode.settings.not_object",
+            "OpenCode 配置必须是 JSON 对象",
+            "OpenCode configuration must be a JSON object",
+        )
+    })?;
+
+    let npm = obj.get("npm").and_then(Value::as_str).unwrap_or_default();
+    if npm.trim().is_empty() {
+        return Err(AppError::localized(
+            "provider.opencode.npm.missing",
+            "OpenCode 供应商缺少 npm 字段（AI SDK 包名，如 @ai-sdk/openai-compatible）",
+            "OpenCode provider is missing `npm` (AI SDK package, e.g. @ai-sdk/openai-compatible)",
+        ));
+    }
+
+    if let Some(options) = obj.get("options") {
+        let options = options.as_object().ok_or_else(|| {
+            AppError::localized(
+                "provider.opencode.options.not_object",
+                "OpenCode 供应商的 options 必须是 JSON 对象",
+                "OpenCode provider `options` must be a JSON object",
+            )
+        })?;
+        if let Some(base_url) = options.get("baseURL") {
+            let base_url = base_url.as_str().map(str::trim).unwrap_or("-");
+            // `{env:VAR}` 由 OpenCode 运行时展开，不做 URL 校验
+            let valid = base_url.is_empty()
+                || base_url.starts_with("{env:")
+                || url::Url::parse(base_url)
+                    .is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+            if !valid {
+                return Err(AppError::localized(
+                    "provider.opencode.base_url.invalid",
+                    format!("OpenCode 供应商的 options.baseURL 不是有效的 http(s) 地址: {base_url}"),
+                    format!("OpenCode provider `options.baseURL` is not a valid http(s) URL: {base_url}"),
+                ));
+            }
+        }
+    }
+
+    if obj.get("models").is_some_and(|models| !models.is_object()) {
+        return Err(AppError::localized(
+            "provider.opencode.models.not_object",
+            "OpenCode 供应商的 models 必须是 JSON 对象",
+            "OpenCode provider `models` must be a JSON object",
+        ));
+    }
+
+    Ok(())
+}
+
 pub fn get_typed_providers() -> Result<IndexMap<String, OpenCodeProviderConfig>, AppError> {
     let providers = get_providers()?;
     let mut result = IndexMap::new();
@@ -265,3 +340,42 @@ pub fn remove_plugins_by_prefixes(prefixes: &[&str]) -> Result<(), AppError> {
 
     write_opencode_config(&config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_provider_fragment_checks_required_shape() {
+        let valid = json!({
+            "npm": "@ai-sdk/openai-compatible",
+            "options": { "baseURL": "https://api.example.com/v1", "apiKey": "{env:KEY}" },
+            "models": { "gpt-4o": { "name": "GPT-4o" } }
+        });
+        assert!(validate_provider_fragment(&valid).is_ok());
+        assert!(validate_provider_fragment(&json!({ "npm": "@ai-sdk/anthropic" })).is_ok());
+        assert!(validate_provider_fragment(
+            &json!({ "npm": "x", "options": { "baseURL": "{env:BASE_URL}" } })
+        )
+        .is_ok());
+
+        assert!(validate_provider_fragment(&json!({ "options": {} })).is_err());
+        assert!(validate_provider_fragment(&json!({ "npm": "x", "options": [] })).is_err());
+        assert!(validate_provider_fragment(
+            &json!({ "npm": "x", "options": { "baseURL": "api.example.com" } })
+        )
+        .is_err());
+        assert!(validate_provider_fragment(&json!({ "npm": "x", "models": [] })).is_err());
+    }
+
+    #[test]
+    fn provider_fragment_unwraps_full_config() {
+        let fragment = json!({ "npm": "x" });
+        assert_eq!(provider_fragment("p", &fragment), &fragment);
+
+        let full =
+            json!({ "$schema": "https://opencode.ai/config.json", "provider": { "p": fragment } });
+        assert_eq!(provider_fragment("p", &full), &json!({ "npm": "x" }));
+        assert_eq!(provider_fragment("missing", &full), &full);
+    }
+}
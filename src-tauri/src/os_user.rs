@@ -0,0 +1,167 @@
+//! 多用户共享机器（如共享构建服务器）上的隔离
+//!
+//! 数据目录默认位于各自的 `~/.cc-switch`，但 `sudo` 保留了 `HOME`、手动指定的数据目录
+//! 指向他人目录等情况下，多个系统用户会打开同一个 SQLite 文件：文件被 root 接管后原用户
+//! 无法写入，并发写入还可能损坏数据库。数据库与写锁文件不按用户拆分：打开数据库前检查
+//! 数据目录归属，归属他人时直接给出明确错误（界面与命令行都会展示）。绑定挂载或 NFS
+//! 主目录的 uid 可能本就不一致，确认无误时可用环境变量 `CC_SWITCH_ALLOW_FOREIGN_DATA_DIR=1`
+//! 显式放行（仅记录警告）。本地代理的默认端口被其他用户的 CC Switch 占用时，
+//! 改用按用户推导的端口。
+
+use std::path::Path;
+
+use crate::error::AppError;
+use crate::i18n;
+
+/// 放行他人数据目录的环境变量（`1` / `true` / `yes`）
+pub const ALLOW_FOREIGN_DATA_DIR_ENV: &str = "CC_SWITCH_ALLOW_FOREIGN_DATA_DIR";
+
+/// 按用户推导的代理端口范围：默认端口之后的 1000 个端口
+const PER_USER_PORT_SPAN: u32 = 1000;
+
+/// 数据目录中需要检查归属的文件（SQLite 的 WAL / 共享内存文件与跨进程写锁同样会被写入）
+const DATA_FILES: &[&str] = &[
+    "cc-switch.db",
+    "cc-switch.db-wal",
+    "cc-switch.db-shm",
+    "settings.json",
+    "write.lock",
+];
+
+/// 当前进程的有效 uid（非 Unix 平台为 `None`）
+pub fn current_uid() -> Option<u32> {
+    #[cfg(unix)]
+    {
+        // SAFETY: geteuid 没有失败路径，也不访问调用方内存
+        Some(unsafe { libc::geteuid() })
+    }
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+/// 当前系统用户名，用于提示与代理 `/health`
+pub fn user_name() -> String {
+    ["USER", "LOGNAME", "USERNAME"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+        .or_else(|| current_uid().and_then(name_for_uid))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// 从 `/etc/passwd` 查找 uid 对应的用户名；查不到时返回 `None`
+fn name_for_uid(uid: u32) -> Option<String> {
+    let passwd = std::fs::read_to_string("/etc/passwd").ok()?;
+    passwd.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        let name = fields.next()?;
+        let id = fields.nth(1)?.parse::<u32>().ok()?;
+        (id == uid).then(|| name.to_string())
+    })
+}
+
+fn describe_uid(uid: u32) -> String {
+    match name_for_uid(uid) {
+        Some(name) => format!("{name} (uid {uid})"),
+        None => format!("uid {uid}"),
+    }
+}
+
+/// 路径存在且归属其他用户时返回该用户的 uid
+#[cfg(unix)]
+fn foreign_owner(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    let owner = std::fs::metadata(path).ok()?.uid();
+    let current = current_uid()?;
+    (owner != current).then_some(owner)
+}
+
+#[cfg(not(unix))]
+fn foreign_owner(_path: &Path) -> Option<u32> {
+    None
+}
+
+/// 检查数据目录及其中的数据库文件归属当前用户
+///
+/// root 也不例外：以 root 身份写入普通用户的数据目录后，文件归属变为 root，
+/// 原用户再启动时就无法写入数据库。
+pub fn check_data_dir(dir: &Path) -> Result<(), AppError> {
+    let paths = std::iter::once(dir.to_path_buf()).chain(DATA_FILES.iter().map(|f| dir.join(f)));
+    for path in paths {
+        if let Some(owner) = foreign_owner(&path) {
+            return Err(i18n::error(
+                "osUser.foreignDataDir",
+                &[
+                    ("path", &path.display().to_string()),
+                    ("owner", &describe_uid(owner)),
+                    ("user", &user_name()),
+                    ("env", ALLOW_FOREIGN_DATA_DIR_ENV),
+                ],
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 环境变量是否显式放行他人数据目录
+fn foreign_data_dir_allowed() -> bool {
+    std::env::var(ALLOW_FOREIGN_DATA_DIR_ENV)
+        .map(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+/// 打开数据库前的归属检查：归属他人时返回错误，设置了放行环境变量时只记录警告
+pub fn ensure_own_data_dir(dir: &Path) -> Result<(), AppError> {
+    match check_data_dir(dir) {
+        Err(e) if foreign_data_dir_allowed() => {
+            log::warn!("[OsUser] {ALLOW_FOREIGN_DATA_DIR_ENV} 已设置，继续使用他人数据目录: {e}");
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+/// 当前用户的专属代理端口：默认端口被其他用户占用时使用
+///
+/// 按 uid（非 Unix 平台按用户名）确定，同一用户每次得到相同端口，
+/// 接管写入 Live 配置的地址在重启后保持不变。
+pub fn per_user_port(base: u16) -> Option<u16> {
+    let key = current_uid().unwrap_or_else(|| {
+        // FNV-1a：跨版本稳定，不使用随机化的 DefaultHasher
+        user_name().bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+            (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+        })
+    });
+    port_for_key(base, key)
+}
+
+fn port_for_key(base: u16, key: u32) -> Option<u16> {
+    let port = u32::from(base) + 1 + key % PER_USER_PORT_SPAN;
+    u16::try_from(port).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_user_port_stays_above_base_and_is_stable() {
+        assert_eq!(port_for_key(15721, 1000), Some(15722));
+        assert_eq!(port_for_key(15721, 1001), Some(15723));
+        assert_eq!(port_for_key(15721, 999), Some(16721));
+        assert_eq!(port_for_key(65000, 999), None);
+        assert_eq!(per_user_port(15721), per_user_port(15721));
+    }
+
+    #[test]
+    fn own_data_dir_passes_ownership_check() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("cc-switch.db"), b"").unwrap();
+        assert!(check_data_dir(dir.path()).is_ok());
+        // 不存在的目录（首次启动）同样通过
+        assert!(check_data_dir(&dir.path().join("missing")).is_ok());
+    }
+}
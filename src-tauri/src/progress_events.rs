@@ -0,0 +1,260 @@
+//! 长时间操作的进度事件
+//!
+//! Skill 下载、仓库索引、批量测试、云同步、数据库备份等操作通过
+//! [`ProgressReporter`] 上报统一格式的进度（id、阶段、百分比、说明）：
+//! - GUI：emit `operation-progress` 事件，前端据此渲染进度条；
+//! - 命令行：传入 `--progress` 时在 stderr 绘制进度条。
+//!
+//! 设计要点：
+//! - 与 `usage_events` 一样使用全局 AppHandle，服务层无需持有 AppHandle；
+//! - 同一阶段内 100ms 节流，阶段切换与结束事件总是发出；
+//! - reporter 被丢弃时若未调用 [`ProgressReporter::finish`]，自动发出失败事件，
+//!   `?` 提前返回的错误路径不会让前端进度条卡住。
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// 前端监听的事件名
+pub const EVENT_OPERATION_PROGRESS: &str = "operation-progress";
+
+/// 结束阶段：成功
+pub const PHASE_DONE: &str = "done";
+/// 结束阶段：失败
+pub const PHASE_FAILED: &str = "failed";
+
+const THROTTLE: Duration = Duration::from_millis(100);
+const CLI_BAR_WIDTH: usize = 24;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static CLI_OUTPUT: AtomicBool = AtomicBool::new(false);
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 进度事件负载
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationProgress {
+    /// 本次操作的唯一 id（同一操作的所有事件相同）
+    pub id: String,
+    /// 操作类型，如 `skill.install`、`webdav.upload`
+    pub operation: &'static str,
+    /// 当前阶段；结束时为 [`PHASE_DONE`] 或 [`PHASE_FAILED`]
+    pub phase: String,
+    /// 0-100；无法估算时为 None（前端显示不确定进度）
+    pub percent: Option<u8>,
+    pub message: Option<String>,
+}
+
+impl OperationProgress {
+    pub fn is_finished(&self) -> bool {
+        self.phase == PHASE_DONE || self.phase == PHASE_FAILED
+    }
+}
+
+/// 在应用 setup 阶段调用一次，注入 AppHandle。
+pub fn init(handle: AppHandle) {
+    if APP_HANDLE.set(handle).is_err() {
+        log::debug!("progress_events::init 重复调用，已忽略");
+    }
+}
+
+/// 命令行 `--progress`：在 stderr 绘制进度条
+pub fn enable_cli_output() {
+    CLI_OUTPUT.store(true, Ordering::Release);
+}
+
+fn percent_of(done: u64, total: u64) -> Option<u8> {
+    (total > 0).then(|| (done.min(total) * 100 / total) as u8)
+}
+
+fn render_cli_line(event: &OperationProgress) -> String {
+    let mut line = match event.percent {
+        Some(percent) => {
+            let filled = CLI_BAR_WIDTH * percent as usize / 100;
+            format!(
+                "{} [{}{}] {:>3}% {}",
+                event.operation,
+                "#".repeat(filled),
+                "-".repeat(CLI_BAR_WIDTH - filled),
+                percent,
+                event.phase
+            )
+        }
+        None => format!(
+            "{} [{}] {}",
+            event.operation,
+            "~".repeat(CLI_BAR_WIDTH),
+            event.phase
+        ),
+    };
+    if let Some(message) = &event.message {
+        line.push_str(": ");
+        line.push_str(message);
+    }
+    line
+}
+
+fn dispatch(event: &OperationProgress) {
+    #[cfg(test)]
+    TEST_EVENTS.with(|events| events.borrow_mut().push(event.clone()));
+
+    if let Some(handle) = APP_HANDLE.get() {
+        if let Err(e) = handle.emit(EVENT_OPERATION_PROGRESS, event) {
+            log::debug!("emit {EVENT_OPERATION_PROGRESS} 失败: {e}");
+        }
+    }
+    if CLI_OUTPUT.load(Ordering::Acquire) {
+        let mut stderr = std::io::stderr().lock();
+        // \x1b[2K 清掉上一帧残留；结束事件换行保留最终状态
+        let _ = write!(stderr, "\r\x1b[2K{}", render_cli_line(event));
+        if event.is_finished() {
+            let _ = writeln!(stderr);
+        }
+        let _ = stderr.flush();
+    }
+}
+
+/// 单个操作的进度上报器
+///
+/// 可跨线程共享（`&ProgressReporter` 即可上报），并发任务可共用一个 reporter
+/// 以 [`step`](Self::step) 汇报已完成数量。
+pub struct ProgressReporter {
+    id: String,
+    operation: &'static str,
+    /// 上次发出事件的时间与阶段，用于节流
+    last: Mutex<Option<(Instant, String)>>,
+    finished: AtomicBool,
+}
+
+impl ProgressReporter {
+    /// 开始一个操作并发出 `started` 事件
+    pub fn start(operation: &'static str) -> Self {
+        let reporter = Self {
+            id: format!("{operation}-{}", NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+            operation,
+            last: Mutex::new(None),
+            finished: AtomicBool::new(false),
+        };
+        reporter.emit("started", Some(0), None, true);
+        reporter
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// 进入一个无法估算百分比的阶段
+    pub fn phase(&self, phase: &str, message: Option<String>) {
+        self.emit(phase, None, message, false);
+    }
+
+    /// 汇报阶段内的完成量（如已下载字节数、已完成的供应商数）
+    pub fn step(&self, phase: &str, done: u64, total: u64, message: Option<String>) {
+        let force = done >= total;
+        self.emit(phase, percent_of(done, total), message, force);
+    }
+
+    /// 成功结束
+    pub fn finish(&self, message: Option<String>) {
+        if !self.finished.swap(true, Ordering::AcqRel) {
+            self.emit(PHASE_DONE, Some(100), message, true);
+        }
+    }
+
+    /// 失败结束
+    pub fn fail(&self, message: impl std::fmt::Display) {
+        if !self.finished.swap(true, Ordering::AcqRel) {
+            self.emit(PHASE_FAILED, None, Some(message.to_string()), true);
+        }
+    }
+
+    fn emit(&self, phase: &str, percent: Option<u8>, message: Option<String>, force: bool) {
+        {
+            let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+            let now = Instant::now();
+            let throttled = matches!(
+                last.as_ref(),
+                Some((at, last_phase)) if last_phase == phase && now.duration_since(*at) < THROTTLE
+            );
+            if throttled && !force {
+                return;
+            }
+            *last = Some((now, phase.to_string()));
+        }
+        dispatch(&OperationProgress {
+            id: self.id.clone(),
+            operation: self.operation,
+            phase: phase.to_string(),
+            percent,
+            message,
+        });
+    }
+}
+
+impl Drop for ProgressReporter {
+    fn drop(&mut self) {
+        if !self.finished.load(Ordering::Acquire) {
+            self.fail("interrupted");
+        }
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    static TEST_EVENTS: std::cell::RefCell<Vec<OperationProgress>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+#[cfg(test)]
+pub(crate) fn take_test_events() -> Vec<OperationProgress> {
+    TEST_EVENTS.with(|events| std::mem::take(&mut *events.borrow_mut()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reporter_throttles_steps_and_fails_when_dropped() {
+        take_test_events();
+        {
+            let reporter = ProgressReporter::start("test.op");
+            for done in 1..=10 {
+                reporter.step("working", done, 10, None);
+            }
+        }
+        let events = take_test_events();
+        let phases: Vec<_> = events.iter().map(|e| e.phase.as_str()).collect();
+        // 同阶段的中间步骤被节流，最后一步总是发出
+        assert_eq!(phases, ["started", "working", "working", PHASE_FAILED]);
+        assert_eq!(events[2].percent, Some(100));
+        assert_eq!(events[3].message.as_deref(), Some("interrupted"));
+        assert!(events.iter().all(|e| e.id == events[0].id));
+
+        let reporter = ProgressReporter::start("test.op");
+        reporter.finish(None);
+        drop(reporter);
+        let events = take_test_events();
+        assert_eq!(events.last().map(|e| e.phase.as_str()), Some(PHASE_DONE));
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn renders_cli_progress_bar() {
+        let event = OperationProgress {
+            id: "backup-1".into(),
+            operation: "backup",
+            phase: "copying".into(),
+            percent: Some(50),
+            message: Some("120/240 pages".into()),
+        };
+        assert_eq!(
+            render_cli_line(&event),
+            "backup [############------------]  50% copying: 120/240 pages"
+        );
+    }
+}
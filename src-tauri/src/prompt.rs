@@ -14,3 +14,12 @@ pub struct Prompt {
     #[serde(rename = "updatedAt", skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<i64>,
 }
+
+/// 提示词内容的一个历史版本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptVersion {
+    pub version: i64,
+    pub content: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: i64,
+}
@@ -214,7 +214,10 @@ impl Provider {
         // Normalize like the JS-script path (extract_base_url_from_provider) so a
         // future delegation from services/provider/usage.rs is behavior-preserving
         // and `{{baseUrl}}/path` concatenation never produces a double slash.
-        (base_url.trim_end_matches('/').to_string(), api_key)
+        (
+            base_url.trim_end_matches('/').to_string(),
+            crate::services::secrets::resolve_secret_lossy(&api_key),
+        )
     }
 }
 
@@ -380,6 +383,149 @@ pub struct CodexChatReasoningConfig {
     pub output_format: Option<String>,
 }
 
+/// 供应商级 TLS 选项（自建中转使用私有 CA 时）
+///
+/// 同时作用于连通性检查与代理转发；配置后该供应商改走独立的 reqwest 客户端。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderTlsOptions {
+    /// 额外信任的根证书文件（PEM 证书链或单个 DER 证书）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert_path: Option<String>,
+    /// 跳过证书校验（危险：仅用于排查，前端需用户确认警告后才能开启）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insecure_skip_verify: Option<bool>,
+}
+
+impl ProviderTlsOptions {
+    pub fn ca_cert_path(&self) -> Option<&str> {
+        self.ca_cert_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+    }
+
+    pub fn skips_verification(&self) -> bool {
+        self.insecure_skip_verify.unwrap_or(false)
+    }
+
+    /// 与默认 TLS 行为一致时视为未配置
+    pub fn is_default(&self) -> bool {
+        self.ca_cert_path().is_none() && !self.skips_verification()
+    }
+}
+
+/// 供应商级超时覆盖（秒）
+///
+/// 未设置的项沿用应用级代理配置，0 表示不限制。与应用级超时不同，这里的值
+/// 在关闭故障转移时同样生效：慢速推理模型可放宽总超时与静默期，
+/// 不稳定的中转可收紧连接超时以尽快失败。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderTimeouts {
+    /// 建立 TCP/TLS 连接的超时
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_secs: Option<u64>,
+    /// 非流式请求的总超时
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_timeout_secs: Option<u64>,
+    /// 流式响应两个数据块之间的最长静默时间
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_idle_timeout_secs: Option<u64>,
+}
+
+impl ProviderTimeouts {
+    /// 连接超时；0 视为未设置（连接阶段总需要一个上限，交给全局默认值）
+    pub fn connect_timeout(&self) -> Option<std::time::Duration> {
+        self.connect_timeout_secs
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.connect_timeout_secs.is_none()
+            && self.total_timeout_secs.is_none()
+            && self.stream_idle_timeout_secs.is_none()
+    }
+}
+
+/// 自定义鉴权方式
+///
+/// 部分地区中转不接受 `x-api-key` / `Authorization: Bearer`，而是要求把密钥放在
+/// 查询参数或 Cookie 中，或对请求做 HMAC 签名。配置后代理转发与测试请求都改用
+/// 该方式，不再发送适配器默认的鉴权头；托管 OAuth 供应商不受影响。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum CustomAuthScheme {
+    /// 密钥作为查询参数：`?<param>=<api_key>`
+    QueryParam { param: String },
+    /// 密钥作为 Cookie：`Cookie: <name>=<api_key>`
+    Cookie { name: String },
+    /// HMAC-SHA256 签名
+    Hmac(HmacAuthOptions),
+}
+
+/// HMAC 签名结果的编码
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HmacEncoding {
+    #[default]
+    Hex,
+    Base64,
+}
+
+/// HMAC 签名鉴权配置
+///
+/// 签名原文由 `template` 渲染，支持占位符：`{method}`、`{path}`、`{query}`、
+/// `{timestamp}`（unix 秒）、`{nonce}`、`{body_sha256}`（请求体 SHA-256 十六进制）
+/// 与 `{api_key}`。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HmacAuthOptions {
+    /// 签名密钥
+    pub secret: String,
+    /// 签名原文模板，为空时使用 `{method}\n{path}\n{timestamp}\n{body_sha256}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// 携带签名的请求头，默认 `x-signature`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_header: Option<String>,
+    /// 签名值前缀（如 `HMAC-SHA256 `）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_prefix: Option<String>,
+    /// 携带时间戳的请求头，默认 `x-timestamp`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_header: Option<String>,
+    /// 携带随机数的请求头；未设置时不发送
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce_header: Option<String>,
+    /// 携带 API Key 的请求头，默认 `x-api-key`；设为空字符串时不发送
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_header: Option<String>,
+    #[serde(default)]
+    pub encoding: HmacEncoding,
+}
+
+/// 内置模拟供应商（base_url 为 `mock://…`）的行为配置
+///
+/// 请求由本地代理内置的桩上游应答，不消耗 token，用于演示切换、故障转移与用量统计。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockProviderOptions {
+    /// 回复内容：为空时回显最后一条用户消息，一条时固定回复，多条时按顺序轮流回复
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replies: Vec<String>,
+    /// 每次应答前的模拟延迟
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// 模拟失败的概率（0.0 ~ 1.0）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_rate: Option<f64>,
+    /// 模拟失败时返回的状态码，默认 503
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_status: Option<u16>,
+}
+
 /// Local proxy request overrides applied after route/protocol transforms.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct LocalProxyRequestOverrides {
@@ -393,6 +539,143 @@ impl LocalProxyRequestOverrides {
     pub fn is_empty(&self) -> bool {
         self.headers.is_empty() && self.body.is_none()
     }
+
+    /// 渲染后的自定义请求头（值中的模板变量见 [`HeaderTemplateContext`]）
+    pub fn rendered_headers(&self, ctx: &HeaderTemplateContext) -> Vec<(String, String)> {
+        self.headers
+            .iter()
+            .map(|(name, value)| (name.clone(), ctx.render(value)))
+            .collect()
+    }
+}
+
+/// 自定义请求头值的模板变量
+///
+/// 支持 `{{model}}`（实际发往上游的模型，未知时为空）、`{{app_type}}`、
+/// `{{provider_id}}`、`{{provider_name}}`；其他 `{{…}}` 原样保留。
+#[derive(Debug, Clone, Copy)]
+pub struct HeaderTemplateContext<'a> {
+    pub app_type: &'a str,
+    pub model: Option<&'a str>,
+    pub provider_id: &'a str,
+    pub provider_name: &'a str,
+}
+
+impl<'a> HeaderTemplateContext<'a> {
+    pub fn new(app_type: &'a str, provider: &'a Provider, model: Option<&'a str>) -> Self {
+        Self {
+            app_type,
+            model,
+            provider_id: &provider.id,
+            provider_name: &provider.name,
+        }
+    }
+
+    pub fn render(&self, template: &str) -> String {
+        if !template.contains("{{") {
+            return template.to_string();
+        }
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                out.push_str(&rest[start..]);
+                return out;
+            };
+            let value = match after[..end].trim() {
+                "model" => Some(self.model.unwrap_or_default()),
+                "app_type" => Some(self.app_type),
+                "provider_id" => Some(self.provider_id),
+                "provider_name" => Some(self.provider_name),
+                _ => None,
+            };
+            match value {
+                Some(value) => out.push_str(value),
+                None => out.push_str(&rest[start..start + 2 + end + 2]),
+            }
+            rest = &after[end + 2..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// 供应商维护时段（如中转站每天 03:00–03:10 重启）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceWindow {
+    /// 开始时间，`HH:MM`
+    pub start: String,
+    /// 结束时间，`HH:MM`；早于开始时间表示跨越午夜
+    pub end: String,
+    /// 时区：`local`（默认）、`UTC`、`+08:00` / `UTC+8` 等固定偏移，或 `CST` 等常见缩写
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// 生效的星期（1 = 周一 … 7 = 周日），为空表示每天
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<u8>,
+}
+
+/// 首字延迟 SLO（如 p95 TTFB < 3s），代理按滚动窗口评估
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencySlo {
+    /// 百分位（1–100），默认 95
+    #[serde(default = "default_slo_percentile")]
+    pub percentile: u8,
+    /// 首字延迟阈值（毫秒）
+    pub threshold_ms: u64,
+    /// 滚动窗口长度（分钟），默认 15
+    #[serde(default = "default_slo_window_minutes")]
+    pub window_minutes: u32,
+    /// 窗口内样本数达到该值才评估，默认 10
+    #[serde(default = "default_slo_min_samples")]
+    pub min_samples: u32,
+    /// 违反 SLO 期间在故障转移队列中后移
+    #[serde(default)]
+    pub demote: bool,
+}
+
+fn default_slo_percentile() -> u8 {
+    95
+}
+
+fn default_slo_window_minutes() -> u32 {
+    15
+}
+
+fn default_slo_min_samples() -> u32 {
+    10
+}
+
+/// 候选端点池：从当前地址与自定义端点中测速，把最快的可用地址写入配置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointPool {
+    /// 切换到该供应商后在后台测速选择，默认开启
+    #[serde(default = "default_pool_on_switch")]
+    pub on_switch: bool,
+    /// 作为当前供应商时定期重新选择的间隔（分钟），0 表示不定期选择
+    #[serde(default)]
+    pub interval_minutes: u32,
+}
+
+fn default_pool_on_switch() -> bool {
+    true
+}
+
+/// 端点池最近一次选出的端点
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SelectedEndpoint {
+    pub url: String,
+    pub latency_ms: u64,
+    /// 选择时间（毫秒时间戳）
+    pub selected_at: i64,
+    /// 参与测速的候选数
+    pub candidates: usize,
 }
 
 /// 供应商元数据
@@ -444,6 +727,31 @@ pub struct ProviderMeta {
     /// 每月消费限额（USD）
     #[serde(rename = "limitMonthlyUsd", skip_serializing_if = "Option::is_none")]
     pub limit_monthly_usd: Option<String>,
+    /// 每日 token 上限（输入 + 输出）
+    #[serde(rename = "limitDailyTokens", skip_serializing_if = "Option::is_none")]
+    pub limit_daily_tokens: Option<u64>,
+    /// 每月 token 上限（输入 + 输出）
+    #[serde(rename = "limitMonthlyTokens", skip_serializing_if = "Option::is_none")]
+    pub limit_monthly_tokens: Option<u64>,
+    /// 达到预算上限后停止路由到该供应商（故障转移到其他供应商）
+    #[serde(rename = "budgetHardLimit", skip_serializing_if = "Option::is_none")]
+    pub budget_hard_limit: Option<bool>,
+    /// 维护时段：时段内代理优先路由到其他供应商，健康检查不计失败
+    #[serde(
+        default,
+        rename = "maintenanceWindows",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    /// 首字延迟 SLO：违反时提醒，可选在故障转移中后移
+    #[serde(rename = "latencySlo", skip_serializing_if = "Option::is_none")]
+    pub latency_slo: Option<LatencySlo>,
+    /// 候选端点池：切换时 / 定期测速选择最快的端点
+    #[serde(rename = "endpointPool", skip_serializing_if = "Option::is_none")]
+    pub endpoint_pool: Option<EndpointPool>,
+    /// 端点池最近一次选择结果
+    #[serde(rename = "selectedEndpoint", skip_serializing_if = "Option::is_none")]
+    pub selected_endpoint: Option<SelectedEndpoint>,
     /// Claude API 格式（仅 Claude 供应商使用）
     /// - "anthropic": 原生 Anthropic Messages API，直接透传
     /// - "openai_chat": OpenAI Chat Completions 格式，需要转换
@@ -500,12 +808,35 @@ pub struct ProviderMeta {
     /// Custom User-Agent for local proxy routing.
     #[serde(rename = "customUserAgent", skip_serializing_if = "Option::is_none")]
     pub custom_user_agent: Option<String>,
+    /// 发往上游的客户端标识策略（转发、流式检测、测速、获取模型共用）。
+    /// 未设置时：配置了 customUserAgent 视为 custom，否则原样透传。
+    #[serde(rename = "clientIdentity", skip_serializing_if = "Option::is_none")]
+    pub client_identity: Option<ClientIdentity>,
     /// Local proxy request overrides applied to the transformed upstream request.
     #[serde(
         rename = "localProxyRequestOverrides",
         skip_serializing_if = "Option::is_none"
     )]
     pub local_proxy_request_overrides: Option<LocalProxyRequestOverrides>,
+    /// 供应商级 TLS 选项（自定义 CA / 跳过证书校验）
+    #[serde(rename = "tls", skip_serializing_if = "Option::is_none")]
+    pub tls: Option<ProviderTlsOptions>,
+    /// 供应商级超时覆盖（连接 / 总超时 / 流式静默期）
+    #[serde(rename = "timeouts", skip_serializing_if = "Option::is_none")]
+    pub timeouts: Option<ProviderTimeouts>,
+    /// 自定义鉴权方式（查询参数 / Cookie / HMAC 签名）
+    #[serde(rename = "customAuth", skip_serializing_if = "Option::is_none")]
+    pub custom_auth: Option<CustomAuthScheme>,
+    /// 修复中转弄坏的中日韩响应文本（乱码 / 双重转义 / GBK 编码），默认关闭
+    #[serde(rename = "repairResponseText", skip_serializing_if = "Option::is_none")]
+    pub repair_response_text: Option<bool>,
+    /// 内置模拟供应商的应答配置，仅 base_url 为 `mock://…` 时生效
+    #[serde(rename = "mock", skip_serializing_if = "Option::is_none")]
+    pub mock: Option<MockProviderOptions>,
+    /// 供应商级代理覆盖：切换到该供应商时仅为它接管 Live 配置并经本地代理转发，
+    /// 切换到未开启的供应商时恢复直连。应用级接管开启时此字段不生效。
+    #[serde(rename = "useLocalProxy", skip_serializing_if = "Option::is_none")]
+    pub use_local_proxy: Option<bool>,
     /// 累加模式应用中，该 provider 是否已写入 live config。
     /// `None` 表示旧数据/未知状态，`Some(false)` 表示明确仅存在于数据库中。
     #[serde(rename = "liveConfigManaged", skip_serializing_if = "Option::is_none")]
@@ -518,6 +849,65 @@ pub struct ProviderMeta {
     /// 用于多账号支持，关联到特定的 GitHub 账号
     #[serde(rename = "githubAccountId", skip_serializing_if = "Option::is_none")]
     pub github_account_id: Option<String>,
+    /// 由 Copilot 账号自动生成的供应商所选模型；账号模型列表变化时据此自动更新
+    #[serde(rename = "copilotModel", skip_serializing_if = "Option::is_none")]
+    pub copilot_model: Option<String>,
+    /// 用户标注的信任等级；未标注时按信任策略文件（provider-trust.json）判定
+    #[serde(rename = "trustLevel", skip_serializing_if = "Option::is_none")]
+    pub trust_level: Option<ProviderTrust>,
+}
+
+/// 供应商信任等级
+///
+/// 切换到不受信任的供应商、或让代理流量经过它时需要用户确认，
+/// 经其转发的请求在使用日志中单独标记。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderTrust {
+    Trusted,
+    Untrusted,
+}
+
+/// Claude Code CLI 的 User-Agent 指纹（部分中转只放行 CLI 请求）
+pub const CLAUDE_CODE_USER_AGENT: &str = "claude-cli/1.0.119 (external, cli)";
+
+/// 客户端标识策略
+///
+/// 有的中转要求请求带 CLI 标识才放行，有的反而会拦截伪装的 CLI 请求，
+/// 因此按供应商选择发送哪种 User-Agent。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ClientIdentity {
+    /// 原样透传客户端自己的 User-Agent；cc-switch 发起的检测请求不带 User-Agent
+    #[default]
+    Passthrough,
+    /// 伪装为 Claude Code CLI（User-Agent + `x-app: cli`）
+    ClaudeCode,
+    /// 如实标识为 cc-switch
+    CcSwitch,
+    /// 使用 `customUserAgent`
+    Custom,
+}
+
+/// 如实标识时使用的 User-Agent
+pub fn cc_switch_user_agent() -> &'static str {
+    concat!("cc-switch/", env!("CARGO_PKG_VERSION"))
+}
+
+/// 按客户端标识策略解析 User-Agent（单一真理来源）
+///
+/// `identity` 为 None 时兼容旧数据：有自定义 UA 即视为 Custom。
+/// 返回 None 表示不覆盖（透传或自定义 UA 为空/非法）。
+pub fn resolve_identity_user_agent(
+    identity: Option<ClientIdentity>,
+    custom_user_agent: Option<&str>,
+) -> Option<HeaderValue> {
+    match identity.unwrap_or(ClientIdentity::Custom) {
+        ClientIdentity::Passthrough => None,
+        ClientIdentity::ClaudeCode => Some(HeaderValue::from_static(CLAUDE_CODE_USER_AGENT)),
+        ClientIdentity::CcSwitch => Some(HeaderValue::from_static(cc_switch_user_agent())),
+        ClientIdentity::Custom => parse_custom_user_agent(custom_user_agent).ok().flatten(),
+    }
 }
 
 /// 解析 Provider 级自定义 User-Agent 字符串（单一真理来源）。
@@ -551,11 +941,54 @@ impl ProviderMeta {
         self.codex_fast_mode.unwrap_or(false)
     }
 
+    /// 是否开启供应商级代理覆盖（`useLocalProxy`），默认关闭。
+    pub fn uses_local_proxy(&self) -> bool {
+        self.use_local_proxy.unwrap_or(false)
+    }
+
+    /// 生效的 TLS 选项；未配置或与默认行为一致时返回 None
+    pub fn tls_options(&self) -> Option<&ProviderTlsOptions> {
+        self.tls.as_ref().filter(|tls| !tls.is_default())
+    }
+
+    /// 生效的超时覆盖；未配置任何一项时返回 None
+    pub fn timeouts(&self) -> Option<&ProviderTimeouts> {
+        self.timeouts
+            .as_ref()
+            .filter(|timeouts| !timeouts.is_default())
+    }
+
+    /// 自定义鉴权方式；未配置时沿用适配器默认的鉴权头
+    pub fn custom_auth(&self) -> Option<&CustomAuthScheme> {
+        self.custom_auth.as_ref()
+    }
+
+    /// 是否对该供应商的响应执行编码修复（`repairResponseText`），默认关闭。
+    pub fn repairs_response_text(&self) -> bool {
+        self.repair_response_text.unwrap_or(false)
+    }
+
     /// 经校验的 Provider 级自定义 User-Agent。见 [`parse_custom_user_agent`]。
     pub fn custom_user_agent_header(&self) -> Result<Option<HeaderValue>, InvalidHeaderValue> {
         parse_custom_user_agent(self.custom_user_agent.as_deref())
     }
 
+    /// 生效的客户端标识策略
+    pub fn client_identity(&self) -> ClientIdentity {
+        self.client_identity.unwrap_or_else(|| {
+            if self.custom_user_agent_header().ok().flatten().is_some() {
+                ClientIdentity::Custom
+            } else {
+                ClientIdentity::Passthrough
+            }
+        })
+    }
+
+    /// 按客户端标识策略发往上游的 User-Agent。见 [`resolve_identity_user_agent`]。
+    pub fn identity_user_agent(&self) -> Option<HeaderValue> {
+        resolve_identity_user_agent(self.client_identity, self.custom_user_agent.as_deref())
+    }
+
     /// 解析指定托管认证供应商绑定的账号 ID。
     ///
     /// 新版优先读取 authBinding，旧版继续兼容 githubAccountId。
@@ -972,8 +1405,9 @@ pub struct OpenCodeModelLimit {
 #[cfg(test)]
 mod tests {
     use super::{
-        ClaudeModelConfig, CodexModelConfig, GeminiModelConfig, LocalProxyRequestOverrides,
-        OpenCodeProviderConfig, Provider, ProviderManager, ProviderMeta, UniversalProvider,
+        ClaudeModelConfig, CodexModelConfig, GeminiModelConfig, HeaderTemplateContext,
+        LocalProxyRequestOverrides, OpenCodeProviderConfig, Provider, ProviderManager,
+        ProviderMeta, UniversalProvider,
     };
     use serde_json::json;
     use std::collections::HashMap;
@@ -1004,6 +1438,46 @@ mod tests {
         assert!(value.get("pricingModelSource").is_none());
     }
 
+    #[test]
+    fn client_identity_falls_back_to_custom_user_agent() {
+        use super::{ClientIdentity, CLAUDE_CODE_USER_AGENT};
+
+        let mut meta = ProviderMeta::default();
+        assert_eq!(meta.client_identity(), ClientIdentity::Passthrough);
+        assert!(meta.identity_user_agent().is_none());
+
+        meta.custom_user_agent = Some("relay-cli/2.0".to_string());
+        assert_eq!(meta.client_identity(), ClientIdentity::Custom);
+        assert_eq!(meta.identity_user_agent().unwrap(), "relay-cli/2.0");
+
+        meta.client_identity = Some(ClientIdentity::ClaudeCode);
+        assert_eq!(meta.identity_user_agent().unwrap(), CLAUDE_CODE_USER_AGENT);
+
+        meta.client_identity = Some(ClientIdentity::CcSwitch);
+        assert!(meta
+            .identity_user_agent()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("cc-switch/"));
+
+        meta.client_identity = Some(ClientIdentity::Passthrough);
+        assert!(meta.identity_user_agent().is_none());
+    }
+
+    #[test]
+    fn provider_meta_use_local_proxy_defaults_off() {
+        assert!(!ProviderMeta::default().uses_local_proxy());
+
+        let meta: ProviderMeta =
+            serde_json::from_value(json!({ "useLocalProxy": true })).expect("parse meta");
+        assert!(meta.uses_local_proxy());
+        assert_eq!(
+            serde_json::to_value(&meta).expect("serialize ProviderMeta")["useLocalProxy"],
+            json!(true)
+        );
+    }
+
     #[test]
     fn provider_meta_roundtrips_max_output_tokens() {
         let meta = ProviderMeta {
@@ -1567,4 +2041,29 @@ mod tests {
             (String::new(), String::new())
         );
     }
+
+    #[test]
+    fn header_templates_render_known_variables_only() {
+        let provider = Provider::with_id("relay".into(), "Relay".into(), json!({}), None);
+        let ctx = HeaderTemplateContext::new("claude", &provider, Some("claude-sonnet-4-5"));
+        assert_eq!(
+            ctx.render("{{app_type}}/{{ model }}@{{provider_id}}"),
+            "claude/claude-sonnet-4-5@relay"
+        );
+        assert_eq!(
+            ctx.render("{{tenant}}-{{provider_name}}"),
+            "{{tenant}}-Relay"
+        );
+        assert_eq!(ctx.render("open {{model"), "open {{model");
+
+        let overrides = LocalProxyRequestOverrides {
+            headers: HashMap::from([("X-Model".to_string(), "{{model}}".to_string())]),
+            body: None,
+        };
+        let no_model = HeaderTemplateContext::new("codex", &provider, None);
+        assert_eq!(
+            overrides.rendered_headers(&no_model),
+            vec![("X-Model".to_string(), String::new())]
+        );
+    }
 }
@@ -0,0 +1,290 @@
+//! 本地代理监听鉴权
+//!
+//! 代理默认只监听 localhost 且不校验来源，在多人共用的机器上任何进程都能借用
+//! 上游 Key。开启鉴权后：
+//!
+//! - 请求必须携带有效的访问令牌（`Authorization: Bearer`、`x-api-key`、
+//!   `x-goog-api-key` 或 Gemini 的 `?key=`），否则返回 401；
+//! - 令牌形如 `PROXY_MANAGED-<32 位十六进制>`，接管 Live 配置时代替
+//!   `PROXY_MANAGED` 占位符写入客户端配置，因此原有的占位符识别与
+//!   "不得发往上游" 的保护对令牌同样生效；
+//! - 请求日志按令牌记录 `auth_token_id`，用于按令牌统计用量。
+//!
+//! 开关与令牌表由每个代理服务器各持一份（[`ProxyAuth`]，挂在 `ProxyState` 上），
+//! 进程内另起的代理（如压测用的内存库代理）不会影响正在运行的代理。
+//! 写入客户端配置的凭据只由 `ProxyService` 按主数据库刷新（[`reload_client_token`]）。
+//! 数据库变更后两者都需要重新加载。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex, RwLock};
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::IntoResponse,
+    response::Response,
+};
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use super::server::ProxyState;
+use super::types::ProxyAuthToken;
+use super::ProxyError;
+use crate::database::Database;
+use crate::error::AppError;
+
+/// 未开启鉴权时写入客户端配置的占位符（避免客户端提示缺少 key，同时不泄露真实 Token）
+pub const PROXY_TOKEN_PLACEHOLDER: &str = "PROXY_MANAGED";
+
+/// 开启鉴权时自动生成的默认令牌名
+const DEFAULT_TOKEN_NAME: &str = "default";
+
+/// 无需鉴权的路径（存活探测）
+const PUBLIC_PATHS: &[&str] = &["/health", "/healthz"];
+
+/// 会话 → 令牌映射的容量上限
+const MAX_TRACKED_SESSIONS: usize = 4096;
+
+/// 代理鉴权配置（settings 表 `proxy_auth_config`）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyAuthConfig {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+#[derive(Default)]
+struct AuthState {
+    enabled: bool,
+    /// token → id
+    tokens: HashMap<String, String>,
+}
+
+/// 单个代理服务器的鉴权状态
+#[derive(Default)]
+pub struct ProxyAuth {
+    state: RwLock<AuthState>,
+}
+
+/// 写入客户端配置的凭据；`None` 表示使用占位符
+static CLIENT_TOKEN: LazyLock<RwLock<Option<String>>> = LazyLock::new(Default::default);
+
+#[derive(Default)]
+struct SessionTokens {
+    order: VecDeque<String>,
+    map: HashMap<String, String>,
+}
+
+static SESSIONS: LazyLock<Mutex<SessionTokens>> = LazyLock::new(Default::default);
+
+/// 生成新令牌
+pub fn generate_token() -> String {
+    format!(
+        "{PROXY_TOKEN_PLACEHOLDER}-{}",
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// 是否为写入客户端配置的代理凭据（占位符或访问令牌）
+pub fn is_proxy_client_token(value: &str) -> bool {
+    value
+        .strip_prefix(PROXY_TOKEN_PLACEHOLDER)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+}
+
+/// 当前应写入客户端配置的凭据：开启鉴权时为默认令牌，否则为占位符
+pub fn client_token() -> String {
+    CLIENT_TOKEN
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(|| PROXY_TOKEN_PLACEHOLDER.to_string())
+}
+
+/// 按数据库刷新写入客户端配置的凭据（最早创建的令牌）
+pub fn reload_client_token(db: &Database) -> Result<(), AppError> {
+    let (enabled, tokens) = load(db)?;
+    let token = tokens.first().filter(|_| enabled).map(|t| t.token.clone());
+    *CLIENT_TOKEN.write().unwrap_or_else(|e| e.into_inner()) = token;
+    Ok(())
+}
+
+/// 读取开关与令牌；开启鉴权但没有任何令牌时自动生成一个
+fn load(db: &Database) -> Result<(bool, Vec<ProxyAuthToken>), AppError> {
+    let config = db.get_proxy_auth_config()?;
+    let mut tokens = db.list_proxy_auth_tokens()?;
+    if config.enabled && tokens.is_empty() {
+        let token = ProxyAuthToken {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: DEFAULT_TOKEN_NAME.to_string(),
+            token: generate_token(),
+            created_at: chrono::Utc::now().timestamp(),
+            last_used_at: None,
+        };
+        db.insert_proxy_auth_token(&token)?;
+        log::info!("[ProxyAuth] 已生成默认访问令牌");
+        tokens.push(token);
+    }
+    Ok((config.enabled, tokens))
+}
+
+impl ProxyAuth {
+    /// 从该代理服务器的数据库重新加载开关与令牌
+    pub fn reload(&self, db: &Database) -> Result<(), AppError> {
+        let (enabled, tokens) = load(db)?;
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        state.enabled = enabled;
+        state.tokens = tokens.into_iter().map(|t| (t.token, t.id)).collect();
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.state.read().unwrap_or_else(|e| e.into_inner()).enabled
+    }
+
+    fn lookup(&self, token: Option<&str>) -> Option<String> {
+        let token = token?;
+        let state = self.state.read().unwrap_or_else(|e| e.into_inner());
+        state.tokens.get(token).cloned()
+    }
+
+    /// 记录会话使用的令牌，供请求日志归因
+    pub fn bind_session(&self, session_id: &str, headers: &HeaderMap) {
+        if !self.is_enabled() {
+            return;
+        }
+        let Some(token_id) = self.lookup(presented_token(headers)) else {
+            return;
+        };
+        let mut sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+        if sessions
+            .map
+            .insert(session_id.to_string(), token_id)
+            .is_none()
+        {
+            sessions.order.push_back(session_id.to_string());
+            while sessions.order.len() > MAX_TRACKED_SESSIONS {
+                if let Some(oldest) = sessions.order.pop_front() {
+                    sessions.map.remove(&oldest);
+                }
+            }
+        }
+    }
+}
+
+/// 从请求头中取出客户端携带的凭据
+fn presented_token(headers: &HeaderMap) -> Option<&str> {
+    if let Some(value) = headers
+        .get(http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+    {
+        let value = value.trim();
+        let token = value
+            .strip_prefix("Bearer ")
+            .or_else(|| value.strip_prefix("bearer "))
+            .unwrap_or(value);
+        return Some(token.trim());
+    }
+    ["x-api-key", "x-goog-api-key"]
+        .into_iter()
+        .find_map(|name| headers.get(name).and_then(|v| v.to_str().ok()))
+        .map(str::trim)
+}
+
+/// Gemini SDK 也可能把 Key 放在查询参数里
+fn query_token(query: Option<&str>) -> Option<&str> {
+    query?.split('&').find_map(|pair| pair.strip_prefix("key="))
+}
+
+/// 令牌校验中间件（按所在代理服务器的鉴权状态）；未开启鉴权时直接放行
+pub async fn require_client_token(
+    State(state): State<ProxyState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let auth = &state.auth;
+    if !auth.is_enabled() || PUBLIC_PATHS.contains(&request.uri().path()) {
+        return next.run(request).await;
+    }
+    let token = presented_token(request.headers()).or_else(|| query_token(request.uri().query()));
+    if auth.lookup(token).is_none() {
+        log::warn!(
+            "[ProxyAuth] 拒绝未授权的代理请求: {} {}",
+            request.method(),
+            request.uri().path()
+        );
+        return ProxyError::AuthError(
+            "本地代理已开启鉴权，请使用 CC Switch 生成的访问令牌".to_string(),
+        )
+        .into_response();
+    }
+    next.run(request).await
+}
+
+/// 查询会话对应的令牌 ID
+pub fn token_for_session(session_id: &str) -> Option<String> {
+    let sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+    sessions.map.get(session_id).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    #[test]
+    fn recognises_placeholder_and_generated_tokens() {
+        let token = generate_token();
+        assert!(token.starts_with("PROXY_MANAGED-"));
+        assert_eq!(token.len(), "PROXY_MANAGED-".len() + 32);
+        assert!(is_proxy_client_token(&token));
+        assert!(is_proxy_client_token(PROXY_TOKEN_PLACEHOLDER));
+        assert!(!is_proxy_client_token("PROXY_MANAGEDX"));
+        assert!(!is_proxy_client_token("sk-ant-real-key"));
+    }
+
+    #[test]
+    fn each_server_keeps_its_own_auth_state() -> Result<(), AppError> {
+        let live = Database::memory()?;
+        live.save_proxy_auth_config(&ProxyAuthConfig { enabled: true })?;
+        let live_auth = ProxyAuth::default();
+        live_auth.reload(&live)?;
+        let token = live.list_proxy_auth_tokens()?.remove(0).token;
+
+        // 另一个代理（如压测）加载自己的内存库，不影响已运行的代理
+        let other_auth = ProxyAuth::default();
+        other_auth.reload(&Database::memory()?)?;
+        assert!(live_auth.is_enabled());
+        assert!(!other_auth.is_enabled());
+        assert!(live_auth.lookup(Some(&token)).is_some());
+        assert!(other_auth.lookup(Some(&token)).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn extracts_token_from_each_client_style() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static("PROXY_MANAGED-a"));
+        assert_eq!(presented_token(&headers), Some("PROXY_MANAGED-a"));
+
+        headers.insert(
+            http::header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer  PROXY_MANAGED-b"),
+        );
+        assert_eq!(presented_token(&headers), Some("PROXY_MANAGED-b"));
+
+        let mut gemini = HeaderMap::new();
+        gemini.insert(
+            "x-goog-api-key",
+            HeaderValue::from_static("PROXY_MANAGED-c"),
+        );
+        assert_eq!(presented_token(&gemini), Some("PROXY_MANAGED-c"));
+
+        assert_eq!(
+            query_token(Some("alt=sse&key=PROXY_MANAGED-d")),
+            Some("PROXY_MANAGED-d")
+        );
+        assert_eq!(query_token(Some("alt=sse")), None);
+        assert_eq!(presented_token(&HeaderMap::new()), None);
+    }
+}
@@ -0,0 +1,275 @@
+//! 代理压测模式
+//!
+//! 在隔离环境中启动一个独立的代理实例（内存数据库 + 随机端口）和一个本地 mock 上游，
+//! 通过完整的 router / transform / forwarder 链路发送合成的 Anthropic 请求，
+//! 统计吞吐量与延迟分布。不读写用户数据库，也不触碰 Live 配置。
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{routing::post, Json, Router};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::oneshot;
+
+use super::server::ProxyServer;
+use super::types::ProxyConfig;
+use super::ProxyError;
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::provider::Provider;
+
+const BENCH_PROVIDER_ID: &str = "bench-mock";
+const MAX_BENCH_CONCURRENCY: usize = 512;
+const MAX_BENCH_REQUESTS: usize = 100_000;
+
+/// 压测参数
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchOptions {
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    #[serde(default = "default_requests")]
+    pub requests: usize,
+    /// mock 上游在响应前的人为延迟（毫秒），用于模拟真实上游耗时
+    #[serde(default)]
+    pub upstream_delay_ms: u64,
+}
+
+fn default_concurrency() -> usize {
+    20
+}
+
+fn default_requests() -> usize {
+    200
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: default_concurrency(),
+            requests: default_requests(),
+            upstream_delay_ms: 0,
+        }
+    }
+}
+
+/// 延迟分布（毫秒）
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencySummary {
+    pub min: f64,
+    pub mean: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+/// 压测报告
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchReport {
+    pub requests: usize,
+    pub concurrency: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub total_ms: u64,
+    pub throughput_rps: f64,
+    pub latency_ms: LatencySummary,
+}
+
+impl LatencySummary {
+    fn from_samples(samples: &mut [f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_by(|a, b| a.total_cmp(b));
+        let percentile = |p: f64| {
+            let rank = ((p / 100.0) * samples.len() as f64).ceil() as usize;
+            samples[rank.saturating_sub(1).min(samples.len() - 1)]
+        };
+        Self {
+            min: samples[0],
+            mean: samples.iter().sum::<f64>() / samples.len() as f64,
+            p50: percentile(50.0),
+            p90: percentile(90.0),
+            p99: percentile(99.0),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+fn mock_message_response() -> Value {
+    json!({
+        "id": "msg_bench",
+        "type": "message",
+        "role": "assistant",
+        "model": "claude-bench",
+        "content": [{ "type": "text", "text": "ok" }],
+        "stop_reason": "end_turn",
+        "stop_sequence": null,
+        "usage": { "input_tokens": 8, "output_tokens": 1 }
+    })
+}
+
+fn bench_request_body() -> Value {
+    json!({
+        "model": "claude-bench",
+        "max_tokens": 16,
+        "messages": [{ "role": "user", "content": "ping" }]
+    })
+}
+
+/// 启动 mock Anthropic 上游，返回地址与关闭句柄
+async fn start_mock_upstream(
+    delay: Duration,
+) -> Result<(SocketAddr, oneshot::Sender<()>), ProxyError> {
+    let app = Router::new().route(
+        "/v1/messages",
+        post(move || async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            Json(mock_message_response())
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| ProxyError::BindFailed(e.to_string()))?;
+    let addr = listener
+        .local_addr()
+        .map_err(|e| ProxyError::BindFailed(e.to_string()))?;
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app)
+            .with_graceful_shutdown(async {
+                let _ = shutdown_rx.await;
+            })
+            .await;
+    });
+    Ok((addr, shutdown_tx))
+}
+
+fn bench_database(upstream: SocketAddr) -> Result<Arc<Database>, ProxyError> {
+    let db = Database::memory().map_err(|e| ProxyError::DatabaseError(e.to_string()))?;
+    let provider = Provider::with_id(
+        BENCH_PROVIDER_ID.to_string(),
+        "Bench Mock".to_string(),
+        json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": format!("http://{upstream}"),
+                "ANTHROPIC_AUTH_TOKEN": "bench"
+            }
+        }),
+        None,
+    );
+    let app = AppType::Claude.as_str();
+    db.save_provider(app, &provider)
+        .and_then(|_| db.set_current_provider(app, BENCH_PROVIDER_ID))
+        .map_err(|e| ProxyError::DatabaseError(e.to_string()))?;
+    Ok(Arc::new(db))
+}
+
+/// 执行一次压测
+pub async fn run_bench(options: BenchOptions) -> Result<BenchReport, ProxyError> {
+    let concurrency = options.concurrency.clamp(1, MAX_BENCH_CONCURRENCY);
+    let requests = options.requests.clamp(1, MAX_BENCH_REQUESTS);
+
+    let (upstream_addr, upstream_shutdown) =
+        start_mock_upstream(Duration::from_millis(options.upstream_delay_ms)).await?;
+
+    let config = ProxyConfig {
+        listen_port: 0,
+        enable_logging: false,
+        ..ProxyConfig::default()
+    };
+    // 鉴权、进行中请求等状态都挂在实例自己的 ProxyState 上，
+    // 全局代理端口只由 ProxyService 发布，因此与正在运行的代理互不影响
+    let server = ProxyServer::new(config, bench_database(upstream_addr)?, None);
+    let info = server.start().await?;
+
+    let url = format!("http://{}:{}/v1/messages", info.address, info.port);
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .pool_max_idle_per_host(concurrency)
+        .build()
+        .map_err(|e| ProxyError::Internal(e.to_string()))?;
+    let body = bench_request_body();
+
+    let started = Instant::now();
+    let results: Vec<Option<f64>> = stream::iter(0..requests)
+        .map(|_| {
+            let client = client.clone();
+            let url = url.clone();
+            let body = body.clone();
+            async move {
+                let begin = Instant::now();
+                let response = client
+                    .post(&url)
+                    .header("x-api-key", "bench")
+                    .header("anthropic-version", "2023-06-01")
+                    .json(&body)
+                    .send()
+                    .await
+                    .ok()?;
+                if !response.status().is_success() {
+                    return None;
+                }
+                response.bytes().await.ok()?;
+                Some(begin.elapsed().as_secs_f64() * 1000.0)
+            }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+    let elapsed = started.elapsed();
+
+    if let Err(e) = server.stop().await {
+        log::warn!("[Bench] 停止压测代理实例失败: {e}");
+    }
+    let _ = upstream_shutdown.send(());
+
+    let mut samples: Vec<f64> = results.iter().flatten().copied().collect();
+    let succeeded = samples.len();
+    let total_secs = elapsed.as_secs_f64();
+
+    Ok(BenchReport {
+        requests,
+        concurrency,
+        succeeded,
+        failed: requests - succeeded,
+        total_ms: elapsed.as_millis() as u64,
+        throughput_rps: if total_secs > 0.0 {
+            succeeded as f64 / total_secs
+        } else {
+            0.0
+        },
+        latency_ms: LatencySummary::from_samples(&mut samples),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latency_summary_picks_nearest_rank_percentiles() {
+        let mut samples: Vec<f64> = (1..=100).map(|v| v as f64).collect();
+        let summary = LatencySummary::from_samples(&mut samples);
+        assert_eq!(summary.min, 1.0);
+        assert_eq!(summary.p50, 50.0);
+        assert_eq!(summary.p90, 90.0);
+        assert_eq!(summary.p99, 99.0);
+        assert_eq!(summary.max, 100.0);
+        assert!((summary.mean - 50.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn latency_summary_handles_empty_samples() {
+        let summary = LatencySummary::from_samples(&mut []);
+        assert_eq!(summary.max, 0.0);
+    }
+}
@@ -288,7 +288,6 @@ impl CircuitBreaker {
     }
 
     /// 获取当前状态
-    #[allow(dead_code)]
     pub async fn get_state(&self) -> CircuitState {
         *self.state.read().await
     }
@@ -0,0 +1,226 @@
+//! Claude 辅助端点：`/v1/messages/count_tokens` 与 `/v1/models`
+//!
+//! Claude Code 会调用这两个端点估算上下文、列出可选模型。Anthropic 原生上游直接
+//! 透传 count_tokens；需要格式转换的上游（OpenAI Chat / Responses、Copilot 等）没有
+//! 对应接口，改为本地估算。模型列表始终由供应商配置合成，不依赖上游。
+
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use super::model_mapper::{self, ModelMapping};
+use super::providers::ProviderAdapter;
+use super::{http_client, ProxyError};
+use crate::provider::Provider;
+use crate::services::prompt_integrity::estimate_prompt_tokens;
+
+pub const COUNT_TOKENS_ENDPOINT: &str = "/v1/messages/count_tokens";
+
+/// 图片 / 文档块的估算值：约等于 Anthropic 对 1092×1092 图片的计费
+const MEDIA_BLOCK_TOKENS: u64 = 1_600;
+
+const COUNT_TOKENS_TIMEOUT: Duration = Duration::from_secs(15);
+
+const DEFAULT_ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// 把图片 / 文档块替换为空文本，返回替换的块数
+fn strip_media(content: &mut Value) -> u64 {
+    let Some(blocks) = content.as_array_mut() else {
+        return 0;
+    };
+    let mut stripped = 0;
+    for block in blocks {
+        match block.get("type").and_then(Value::as_str) {
+            Some("image" | "document") => {
+                *block = json!({"type": "text", "text": ""});
+                stripped += 1;
+            }
+            Some("tool_result") => {
+                if let Some(inner) = block.get_mut("content") {
+                    stripped += strip_media(inner);
+                }
+            }
+            _ => {}
+        }
+    }
+    stripped
+}
+
+/// 本地估算 count_tokens 的响应体
+pub fn estimate_count_tokens(body: &Value) -> Value {
+    let mut body = body.clone();
+    let mut media = 0;
+    if let Some(messages) = body.get_mut("messages").and_then(Value::as_array_mut) {
+        for message in messages {
+            if let Some(content) = message.get_mut("content") {
+                media += strip_media(content);
+            }
+        }
+    }
+    let text = estimate_prompt_tokens(&body).unwrap_or(0);
+    json!({ "input_tokens": text + media * MEDIA_BLOCK_TOKENS })
+}
+
+/// 把 count_tokens 透传给 Anthropic 原生上游（应用与正式请求相同的模型映射）
+pub async fn forward_count_tokens(
+    adapter: &dyn ProviderAdapter,
+    provider: &Provider,
+    headers: &http::HeaderMap,
+    body: &Value,
+) -> Result<Value, ProxyError> {
+    let base_url = adapter.extract_base_url(provider)?;
+    let url = adapter.build_url(&base_url, COUNT_TOKENS_ENDPOINT);
+    let auth = adapter
+        .extract_auth(provider)
+        .ok_or_else(|| ProxyError::AuthError("供应商缺少 API Key".to_string()))?;
+    let (mapped, _, _) = model_mapper::apply_model_mapping(body.clone(), provider);
+    let mapped = model_mapper::strip_one_m_suffix_for_upstream_from_body(mapped);
+
+    let mut request = http_client::get()
+        .post(&url)
+        .timeout(COUNT_TOKENS_TIMEOUT)
+        .json(&mapped);
+    for (name, value) in adapter.get_auth_headers(&auth)? {
+        request = request.header(name, value);
+    }
+    for name in ["anthropic-version", "anthropic-beta"] {
+        if let Some(value) = headers.get(name) {
+            request = request.header(name, value.clone());
+        }
+    }
+    if !headers.contains_key("anthropic-version") {
+        request = request.header("anthropic-version", DEFAULT_ANTHROPIC_VERSION);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ProxyError::ForwardFailed(e.to_string()))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(ProxyError::UpstreamError {
+            status: status.as_u16(),
+            body: response.text().await.ok(),
+        });
+    }
+    let value: Value = response
+        .json()
+        .await
+        .map_err(|e| ProxyError::TransformError(format!("count_tokens 响应解析失败: {e}")))?;
+    if value.get("input_tokens").and_then(Value::as_u64).is_none() {
+        return Err(ProxyError::TransformError(
+            "count_tokens 响应缺少 input_tokens".to_string(),
+        ));
+    }
+    Ok(value)
+}
+
+/// 由供应商的模型配置合成 Anthropic 格式的 `/v1/models` 响应
+///
+/// 依次列出主模型、各档位默认模型与子代理模型（去重）；未配置任何模型时返回空列表，
+/// 客户端会回落到内置的 Claude 模型。
+pub fn model_list_response(provider: &Provider) -> Value {
+    let mapping = ModelMapping::from_provider(provider);
+    let mut ids: Vec<String> = Vec::new();
+    for model in [
+        mapping.default_model,
+        mapping.opus_model,
+        mapping.sonnet_model,
+        mapping.haiku_model,
+        mapping.fable_model,
+        mapping.subagent_model,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        let model = model_mapper::strip_one_m_suffix_for_upstream(&model).to_string();
+        if !ids.contains(&model) {
+            ids.push(model);
+        }
+    }
+
+    let created_at = provider
+        .created_at
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let data: Vec<Value> = ids
+        .iter()
+        .map(|id| {
+            json!({
+                "type": "model",
+                "id": id,
+                "display_name": id,
+                "created_at": created_at,
+            })
+        })
+        .collect();
+
+    json!({
+        "data": data,
+        "has_more": false,
+        "first_id": ids.first(),
+        "last_id": ids.last(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_text_and_media_blocks() {
+        let body = json!({
+            "model": "claude-sonnet-4-5",
+            "messages": [{
+                "role": "user",
+                "content": [
+                    {"type": "text", "text": "abcdefgh"},
+                    {"type": "image", "source": {"type": "base64", "data": "AAAA".repeat(10_000)}},
+                    {"type": "tool_result", "tool_use_id": "t1", "content": [
+                        {"type": "document", "source": {"type": "base64", "data": "BBBB"}}
+                    ]}
+                ]
+            }]
+        });
+        let text_only = json!({
+            "messages": [{"role": "user", "content": [{"type": "text", "text": "abcdefgh"}]}]
+        });
+        let base = estimate_prompt_tokens(&text_only).unwrap();
+        let estimated = estimate_count_tokens(&body)["input_tokens"]
+            .as_u64()
+            .unwrap();
+        // base64 数据不计入，按块计费
+        assert_eq!(estimated, base + 2 * MEDIA_BLOCK_TOKENS);
+    }
+
+    #[test]
+    fn model_list_is_built_from_provider_models() {
+        let mut provider = Provider::with_id(
+            "glm".to_string(),
+            "GLM".to_string(),
+            json!({"env": {
+                "ANTHROPIC_MODEL": "glm-4.6",
+                "ANTHROPIC_DEFAULT_SONNET_MODEL": "glm-4.6",
+                "ANTHROPIC_DEFAULT_HAIKU_MODEL": "glm-4.5-air[1m]",
+            }}),
+            None,
+        );
+        provider.created_at = Some(1_700_000_000_000);
+        let list = model_list_response(&provider);
+        let ids: Vec<&str> = list["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["glm-4.6", "glm-4.5-air"]);
+        assert_eq!(list["first_id"], "glm-4.6");
+        assert_eq!(list["last_id"], "glm-4.5-air");
+        assert_eq!(list["data"][0]["type"], "model");
+        assert_eq!(list["data"][0]["created_at"], "2023-11-14T22:13:20Z");
+
+        let empty = Provider::with_id("x".into(), "X".into(), json!({}), None);
+        assert_eq!(model_list_response(&empty)["data"], json!([]));
+    }
+}
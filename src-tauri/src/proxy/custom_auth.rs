@@ -0,0 +1,372 @@
+//! 自定义鉴权：查询参数密钥、Cookie 与 HMAC 签名
+//!
+//! 适配器的 `get_auth_headers` 只能产出固定的鉴权头，而查询参数需要改写 URL、
+//! HMAC 签名需要最终的 URL 与请求体，因此自定义鉴权在请求完全组装好之后统一
+//! 应用。代理转发与测试请求（测试消息、能力探测、复制为 curl）共用这里的实现，
+//! 保证两边发出的请求一致。
+
+use base64::Engine;
+use http::{HeaderName, HeaderValue};
+
+use crate::provider::{CustomAuthScheme, HmacAuthOptions, HmacEncoding};
+use crate::proxy::error::ProxyError;
+
+const DEFAULT_SIGNING_TEMPLATE: &str = "{method}\n{path}\n{timestamp}\n{body_sha256}";
+const DEFAULT_SIGNATURE_HEADER: &str = "x-signature";
+const DEFAULT_TIMESTAMP_HEADER: &str = "x-timestamp";
+const DEFAULT_KEY_HEADER: &str = "x-api-key";
+
+/// 应用自定义鉴权后的请求：新的 URL 与需要追加的请求头
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorizedRequest {
+    pub url: String,
+    pub headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+/// 签名所需的请求上下文
+pub struct SigningInput<'a> {
+    pub method: &'a str,
+    pub url: &'a str,
+    pub body: &'a [u8],
+    /// unix 秒
+    pub timestamp: i64,
+    pub nonce: &'a str,
+}
+
+fn header_name(name: &str) -> Result<HeaderName, String> {
+    HeaderName::from_bytes(name.trim().as_bytes())
+        .map_err(|_| format!("invalid header name: {name}"))
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, ProxyError> {
+    HeaderValue::from_str(value)
+        .map_err(|e| ProxyError::AuthError(format!("invalid auth header value: {e}")))
+}
+
+fn configured<'a>(value: Option<&'a str>, default: &'a str) -> &'a str {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(default)
+}
+
+/// 校验配置；保存供应商时调用，避免运行时才发现头名等非法
+pub fn validate(scheme: &CustomAuthScheme) -> Result<(), String> {
+    match scheme {
+        CustomAuthScheme::QueryParam { param } => {
+            if param.trim().is_empty() {
+                return Err("query parameter name is empty".to_string());
+            }
+        }
+        CustomAuthScheme::Cookie { name } => {
+            let name = name.trim();
+            if name.is_empty() || name.contains(['=', ';', ' ']) {
+                return Err(format!("invalid cookie name: {name}"));
+            }
+        }
+        CustomAuthScheme::Hmac(options) => {
+            if options.secret.is_empty() {
+                return Err("HMAC secret is empty".to_string());
+            }
+            for name in [
+                options.signature_header.as_deref(),
+                options.timestamp_header.as_deref(),
+                options.nonce_header.as_deref(),
+                options.key_header.as_deref(),
+            ]
+            .into_iter()
+            .flatten()
+            .filter(|name| !name.trim().is_empty())
+            {
+                header_name(name)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn append_query_param(url: &str, param: &str, value: &str) -> String {
+    let encoded: String = url::form_urlencoded::Serializer::new(String::new())
+        .append_pair(param.trim(), value)
+        .finish();
+    let (base, fragment) = match url.split_once('#') {
+        Some((base, fragment)) => (base, Some(fragment)),
+        None => (url, None),
+    };
+    let separator = if !base.contains('?') {
+        "?"
+    } else if base.ends_with('?') || base.ends_with('&') {
+        ""
+    } else {
+        "&"
+    };
+    match fragment {
+        Some(fragment) => format!("{base}{separator}{encoded}#{fragment}"),
+        None => format!("{base}{separator}{encoded}"),
+    }
+}
+
+/// URL 的路径与查询串（不含 `?`）
+fn path_and_query(url: &str) -> (String, String) {
+    let uri = url.parse::<http::Uri>().ok();
+    let path = uri
+        .as_ref()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| "/".to_string());
+    let query = uri
+        .as_ref()
+        .and_then(|uri| uri.query())
+        .unwrap_or_default()
+        .to_string();
+    (path, query)
+}
+
+fn sign(options: &HmacAuthOptions, api_key: &str, input: &SigningInput<'_>) -> String {
+    use hmac::{Hmac, Mac};
+    use sha2::{Digest, Sha256};
+    type HmacSha256 = Hmac<Sha256>;
+
+    let (path, query) = path_and_query(input.url);
+    let payload = configured(options.template.as_deref(), DEFAULT_SIGNING_TEMPLATE)
+        .replace("{method}", &input.method.to_ascii_uppercase())
+        .replace("{path}", &path)
+        .replace("{query}", &query)
+        .replace("{timestamp}", &input.timestamp.to_string())
+        .replace("{nonce}", input.nonce)
+        .replace(
+            "{body_sha256}",
+            &format!("{:x}", Sha256::digest(input.body)),
+        )
+        .replace("{api_key}", api_key);
+
+    let mut mac =
+        HmacSha256::new_from_slice(options.secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(payload.as_bytes());
+    let digest = mac.finalize().into_bytes();
+    let signature = match options.encoding {
+        HmacEncoding::Hex => format!("{digest:x}"),
+        HmacEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(digest),
+    };
+    format!(
+        "{}{signature}",
+        options.signature_prefix.as_deref().unwrap_or_default()
+    )
+}
+
+/// 按自定义鉴权方式改写请求
+pub fn authorize(
+    scheme: &CustomAuthScheme,
+    api_key: &str,
+    input: &SigningInput<'_>,
+) -> Result<AuthorizedRequest, ProxyError> {
+    let invalid = |e: String| ProxyError::ConfigError(format!("自定义鉴权配置无效: {e}"));
+    match scheme {
+        CustomAuthScheme::QueryParam { param } => {
+            validate(scheme).map_err(invalid)?;
+            Ok(AuthorizedRequest {
+                url: append_query_param(input.url, param, api_key),
+                headers: Vec::new(),
+            })
+        }
+        CustomAuthScheme::Cookie { name } => {
+            validate(scheme).map_err(invalid)?;
+            Ok(AuthorizedRequest {
+                url: input.url.to_string(),
+                headers: vec![(
+                    http::header::COOKIE,
+                    header_value(&format!("{}={api_key}", name.trim()))?,
+                )],
+            })
+        }
+        CustomAuthScheme::Hmac(options) => {
+            validate(scheme).map_err(invalid)?;
+            let name = |value: Option<&str>, default: &str| {
+                header_name(configured(value, default)).map_err(invalid)
+            };
+            let mut headers = vec![
+                (
+                    name(
+                        options.timestamp_header.as_deref(),
+                        DEFAULT_TIMESTAMP_HEADER,
+                    )?,
+                    header_value(&input.timestamp.to_string())?,
+                ),
+                (
+                    name(
+                        options.signature_header.as_deref(),
+                        DEFAULT_SIGNATURE_HEADER,
+                    )?,
+                    header_value(&sign(options, api_key, input))?,
+                ),
+            ];
+            if let Some(nonce_header) = options
+                .nonce_header
+                .as_deref()
+                .filter(|name| !name.trim().is_empty())
+            {
+                headers.push((
+                    header_name(nonce_header).map_err(invalid)?,
+                    header_value(input.nonce)?,
+                ));
+            }
+            // 显式设为空字符串表示不单独发送 API Key（只用签名鉴权）
+            let key_header = match options.key_header.as_deref() {
+                Some(name) if name.trim().is_empty() => None,
+                other => Some(configured(other, DEFAULT_KEY_HEADER)),
+            };
+            if let Some(key_header) = key_header.filter(|_| !api_key.is_empty()) {
+                headers.push((
+                    header_name(key_header).map_err(invalid)?,
+                    header_value(api_key)?,
+                ));
+            }
+            Ok(AuthorizedRequest {
+                url: input.url.to_string(),
+                headers,
+            })
+        }
+    }
+}
+
+/// 以当前时间与随机 nonce 应用自定义鉴权
+pub fn authorize_now(
+    scheme: &CustomAuthScheme,
+    api_key: &str,
+    method: &str,
+    url: &str,
+    body: &[u8],
+) -> Result<AuthorizedRequest, ProxyError> {
+    let nonce = uuid::Uuid::new_v4().simple().to_string();
+    authorize(
+        scheme,
+        api_key,
+        &SigningInput {
+            method,
+            url,
+            body,
+            timestamp: chrono::Utc::now().timestamp(),
+            nonce: &nonce,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input<'a>(url: &'a str, body: &'a [u8]) -> SigningInput<'a> {
+        SigningInput {
+            method: "post",
+            url,
+            body,
+            timestamp: 1_700_000_000,
+            nonce: "n1",
+        }
+    }
+
+    fn hmac_options() -> HmacAuthOptions {
+        HmacAuthOptions {
+            secret: "secret".to_string(),
+            template: None,
+            signature_header: None,
+            signature_prefix: None,
+            timestamp_header: None,
+            nonce_header: None,
+            key_header: None,
+            encoding: HmacEncoding::Hex,
+        }
+    }
+
+    #[test]
+    fn query_param_is_appended_and_encoded() {
+        let scheme = CustomAuthScheme::QueryParam {
+            param: "key".to_string(),
+        };
+        let out = authorize(
+            &scheme,
+            "sk a&b",
+            &input("https://relay.example/v1/messages?beta=true", b"{}"),
+        )
+        .unwrap();
+        assert_eq!(
+            out.url,
+            "https://relay.example/v1/messages?beta=true&key=sk+a%26b"
+        );
+        assert!(out.headers.is_empty());
+
+        let out = authorize(&scheme, "sk", &input("https://relay.example/v1", b"")).unwrap();
+        assert_eq!(out.url, "https://relay.example/v1?key=sk");
+    }
+
+    #[test]
+    fn cookie_scheme_sets_cookie_header() {
+        let scheme = CustomAuthScheme::Cookie {
+            name: "session".to_string(),
+        };
+        let out = authorize(&scheme, "abc", &input("https://relay.example/v1", b"")).unwrap();
+        assert_eq!(out.headers.len(), 1);
+        assert_eq!(out.headers[0].0, http::header::COOKIE);
+        assert_eq!(out.headers[0].1, "session=abc");
+
+        assert!(validate(&CustomAuthScheme::Cookie {
+            name: "a=b".to_string()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn hmac_signs_default_template() {
+        use hmac::{Hmac, Mac};
+        use sha2::{Digest, Sha256};
+
+        let body = br#"{"model":"m"}"#;
+        let scheme = CustomAuthScheme::Hmac(hmac_options());
+        let out = authorize(
+            &scheme,
+            "sk-1",
+            &input("https://relay.example/v1/messages?x=1", body),
+        )
+        .unwrap();
+
+        let payload = format!("POST\n/v1/messages\n1700000000\n{:x}", Sha256::digest(body));
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(payload.as_bytes());
+        let expected = format!("{:x}", mac.finalize().into_bytes());
+
+        let header = |name: &str| {
+            out.headers
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.to_str().unwrap().to_string())
+        };
+        assert_eq!(header("x-timestamp").as_deref(), Some("1700000000"));
+        assert_eq!(header("x-signature"), Some(expected));
+        assert_eq!(header("x-api-key").as_deref(), Some("sk-1"));
+        assert_eq!(out.url, "https://relay.example/v1/messages?x=1");
+    }
+
+    #[test]
+    fn hmac_honours_custom_headers_prefix_and_encoding() {
+        let scheme = CustomAuthScheme::Hmac(HmacAuthOptions {
+            template: Some("{method}&{query}&{nonce}".to_string()),
+            signature_header: Some("Authorization".to_string()),
+            signature_prefix: Some("HMAC-SHA256 ".to_string()),
+            nonce_header: Some("x-nonce".to_string()),
+            key_header: Some(String::new()),
+            encoding: HmacEncoding::Base64,
+            ..hmac_options()
+        });
+        let out = authorize(&scheme, "sk-1", &input("https://relay.example/a?b=c", b"")).unwrap();
+        let names: Vec<_> = out.headers.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["x-timestamp", "authorization", "x-nonce"]);
+        let signature = out.headers[1].1.to_str().unwrap();
+        assert!(signature.starts_with("HMAC-SHA256 "));
+        assert!(signature.ends_with('='));
+
+        assert!(validate(&CustomAuthScheme::Hmac(HmacAuthOptions {
+            secret: String::new(),
+            ..hmac_options()
+        }))
+        .is_err());
+    }
+}
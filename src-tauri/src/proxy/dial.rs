@@ -0,0 +1,176 @@
+//! 出站连接的地址排序与 Happy Eyeballs 拨号
+//!
+//! 部分用户的网络 IPv6 路由不通但 DNS 仍返回 AAAA 记录，按解析顺序逐个连接会
+//! 先在 IPv6 地址上耗满超时才回退到 IPv4。这里统一处理两条出站路径：
+//! - reqwest 客户端：通过 [`PreferenceResolver`] 按偏好重排解析结果
+//!   （hyper 连接器自带 Happy Eyeballs，会在首选协议族迟迟不通时并行尝试另一族）
+//! - raw TCP 路径（保留 header 大小写）：[`connect_tcp`] 自行实现错峰并发拨号
+//!
+//! 偏好在每次解析/拨号时读取设置，修改后无需重建客户端。
+
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use tokio::net::TcpStream;
+
+use crate::settings::IpFamilyPreference;
+
+/// 相邻两次拨号尝试的错峰间隔（RFC 8305 建议 250ms）
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// 按协议族偏好重排地址
+///
+/// - `Auto`：保持系统解析顺序
+/// - 指定协议族：该族地址在前（各族内部保持原顺序）；`fallback` 为 false 时丢弃
+///   其它协议族，除非首选族没有任何地址
+pub fn order_addrs(
+    addrs: Vec<SocketAddr>,
+    preference: IpFamilyPreference,
+    fallback: bool,
+) -> Vec<SocketAddr> {
+    let prefers_v6 = match preference {
+        IpFamilyPreference::Auto => return addrs,
+        IpFamilyPreference::Ipv4 => false,
+        IpFamilyPreference::Ipv6 => true,
+    };
+    let (preferred, others): (Vec<_>, Vec<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == prefers_v6);
+    if preferred.is_empty() {
+        return others;
+    }
+    if !fallback {
+        return preferred;
+    }
+    preferred.into_iter().chain(others).collect()
+}
+
+async fn resolve_ordered(host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+    // URI 中的 IPv6 字面量带方括号（`[::1]`），解析前去掉
+    let host = host
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host);
+    let (preference, happy_eyeballs) = crate::settings::network_connection_preference();
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port)).await?.collect();
+    Ok(order_addrs(addrs, preference, happy_eyeballs))
+}
+
+/// reqwest DNS 解析器：系统解析后按设置中的协议族偏好重排
+pub struct PreferenceResolver;
+
+impl reqwest::dns::Resolve for PreferenceResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs = resolve_ordered(&host, 0).await?;
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// 建立 TCP 连接：按偏好排序地址，启用 Happy Eyeballs 时错峰并发拨号，
+/// 取最先成功的连接；否则按顺序逐个尝试。
+pub async fn connect_tcp(host: &str, port: u16) -> io::Result<TcpStream> {
+    let (_, happy_eyeballs) = crate::settings::network_connection_preference();
+    let addrs = resolve_ordered(host, port).await?;
+    if happy_eyeballs {
+        race_connect(addrs, CONNECTION_ATTEMPT_DELAY).await
+    } else {
+        sequential_connect(addrs).await
+    }
+}
+
+fn no_addresses() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "no addresses resolved")
+}
+
+async fn sequential_connect(addrs: Vec<SocketAddr>) -> io::Result<TcpStream> {
+    let mut last_error = None;
+    for addr in addrs {
+        match TcpStream::connect(addr).await {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(no_addresses))
+}
+
+/// 第 i 个地址在 `i * delay` 后开始拨号，先成功者胜出，其余尝试随之取消
+async fn race_connect(addrs: Vec<SocketAddr>, delay: Duration) -> io::Result<TcpStream> {
+    let mut attempts: FuturesUnordered<_> = addrs
+        .into_iter()
+        .enumerate()
+        .map(|(index, addr)| async move {
+            if index > 0 {
+                tokio::time::sleep(delay * index as u32).await;
+            }
+            TcpStream::connect(addr).await
+        })
+        .collect();
+
+    let mut last_error = None;
+    while let Some(result) = attempts.next().await {
+        match result {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(no_addresses))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addrs() -> Vec<SocketAddr> {
+        vec![
+            "[2001:db8::1]:443".parse().unwrap(),
+            "192.0.2.1:443".parse().unwrap(),
+            "[2001:db8::2]:443".parse().unwrap(),
+            "192.0.2.2:443".parse().unwrap(),
+        ]
+    }
+
+    #[test]
+    fn orders_preferred_family_first() {
+        let ordered = order_addrs(addrs(), IpFamilyPreference::Ipv4, true);
+        assert!(ordered[0].is_ipv4() && ordered[1].is_ipv4());
+        assert_eq!(ordered.len(), 4);
+        assert_eq!(
+            order_addrs(addrs(), IpFamilyPreference::Auto, false),
+            addrs()
+        );
+    }
+
+    #[test]
+    fn strict_mode_drops_other_family_unless_empty() {
+        let ordered = order_addrs(addrs(), IpFamilyPreference::Ipv6, false);
+        assert_eq!(ordered.len(), 2);
+        assert!(ordered.iter().all(SocketAddr::is_ipv6));
+
+        let v4_only: Vec<SocketAddr> = vec!["192.0.2.1:443".parse().unwrap()];
+        assert_eq!(
+            order_addrs(v4_only.clone(), IpFamilyPreference::Ipv6, false),
+            v4_only
+        );
+    }
+
+    #[tokio::test]
+    async fn race_skips_dead_address() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live = listener.local_addr().unwrap();
+        // 先占一个端口再释放，得到一个（大概率）拒绝连接的地址
+        let dead = {
+            let probe = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            probe.local_addr().unwrap()
+        };
+        let stream = race_connect(vec![dead, live], Duration::from_millis(10))
+            .await
+            .expect("falls back to live address");
+        assert_eq!(stream.peer_addr().unwrap(), live);
+    }
+}
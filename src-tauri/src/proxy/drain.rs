@@ -0,0 +1,208 @@
+//! 进行中请求跟踪与优雅排空
+//!
+//! 每个客户端请求在转发期间持有一个 [`InFlightGuard`]，确定最终供应商后绑定到
+//! `(app_type, provider_id)`。切换供应商只影响之后进入的请求——已经开始的请求
+//! 继续在原供应商上完成；状态接口据此把「仍在旧供应商上的请求」标记为排空中。
+//!
+//! 代理停止时先停止接受新连接，并让已有连接在当前响应结束后关闭，再通过
+//! [`InFlightTracker::wait_idle`] 等待进行中的请求全部完成或达到排空超时。
+//!
+//! 跟踪器挂在 `ProxyState` 上，每个代理服务器各自一份，互不影响。
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+/// 连接级停机阶段：运行中 → 排空（不再接受新请求）→ 强制关闭
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrainPhase {
+    Running,
+    Draining,
+    Closed,
+}
+
+/// 按应用与供应商分组的进行中请求（字段命名与 `ProxyStatus` 一致）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InFlightGroup {
+    pub app_type: String,
+    /// 尚未选定供应商（仍在首次尝试 / 故障转移中）时为 None
+    pub provider_id: Option<String>,
+    pub provider_name: Option<String>,
+    pub count: usize,
+    /// 组内最早开始的请求已持续的秒数
+    pub oldest_secs: u64,
+    /// 供应商已不是该应用的当前目标：这些请求正在旧供应商上排空
+    #[serde(default)]
+    pub draining: bool,
+}
+
+struct Entry {
+    app_type: String,
+    provider: Option<(String, String)>,
+    started_at: Instant,
+}
+
+#[derive(Default)]
+pub struct InFlightTracker {
+    next_id: AtomicU64,
+    entries: Mutex<BTreeMap<u64, Entry>>,
+    idle: Notify,
+}
+
+impl InFlightTracker {
+    /// 登记一个新请求；guard drop 时自动注销
+    pub fn begin(self: &Arc<Self>, app_type: &str) -> InFlightGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(
+                id,
+                Entry {
+                    app_type: app_type.to_string(),
+                    provider: None,
+                    started_at: Instant::now(),
+                },
+            );
+        }
+        InFlightGuard {
+            tracker: self.clone(),
+            id,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|e| e.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 按 (app_type, provider) 分组；`current` 为 app_type -> 当前目标供应商 id
+    pub fn snapshot(&self, current: &BTreeMap<String, String>) -> Vec<InFlightGroup> {
+        let Ok(entries) = self.entries.lock() else {
+            return Vec::new();
+        };
+        let mut groups: BTreeMap<(String, Option<String>), InFlightGroup> = BTreeMap::new();
+        for entry in entries.values() {
+            let provider_id = entry.provider.as_ref().map(|(id, _)| id.clone());
+            let group = groups
+                .entry((entry.app_type.clone(), provider_id.clone()))
+                .or_insert_with(|| InFlightGroup {
+                    app_type: entry.app_type.clone(),
+                    draining: provider_id
+                        .as_ref()
+                        .is_some_and(|id| current.get(&entry.app_type).is_some_and(|c| c != id)),
+                    provider_id,
+                    provider_name: entry.provider.as_ref().map(|(_, name)| name.clone()),
+                    count: 0,
+                    oldest_secs: 0,
+                });
+            group.count += 1;
+            group.oldest_secs = group.oldest_secs.max(entry.started_at.elapsed().as_secs());
+        }
+        groups.into_values().collect()
+    }
+
+    /// 等待所有进行中的请求完成；超时返回 false
+    pub async fn wait_idle(&self, timeout: Duration) -> bool {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let notified = self.idle.notified();
+            if self.is_empty() {
+                return true;
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return self.is_empty();
+            }
+        }
+    }
+
+    fn bind(&self, id: u64, provider_id: &str, provider_name: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            if let Some(entry) = entries.get_mut(&id) {
+                entry.provider = Some((provider_id.to_string(), provider_name.to_string()));
+            }
+        }
+    }
+
+    fn end(&self, id: u64) {
+        let now_empty = self
+            .entries
+            .lock()
+            .map(|mut entries| {
+                entries.remove(&id);
+                entries.is_empty()
+            })
+            .unwrap_or(false);
+        if now_empty {
+            self.idle.notify_waiters();
+        }
+    }
+}
+
+/// 进行中请求的 RAII 登记：随响应流转，流式 body 结束时才 drop
+pub struct InFlightGuard {
+    tracker: Arc<InFlightTracker>,
+    id: u64,
+}
+
+impl InFlightGuard {
+    /// 记录实际处理该请求的供应商（故障转移后为最终供应商）
+    pub fn bind_provider(&self, provider_id: &str, provider_name: &str) {
+        self.tracker.bind(self.id, provider_id, provider_name);
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.tracker.end(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_flags_requests_left_on_previous_provider() {
+        let tracker = Arc::new(InFlightTracker::default());
+        let old = tracker.begin("claude");
+        old.bind_provider("p-old", "Old");
+        let new = tracker.begin("claude");
+        new.bind_provider("p-new", "New");
+        let _pending = tracker.begin("codex");
+
+        let current = BTreeMap::from([("claude".to_string(), "p-new".to_string())]);
+        let groups = tracker.snapshot(&current);
+        assert_eq!(groups.len(), 3);
+        let draining: Vec<_> = groups.iter().filter(|g| g.draining).collect();
+        assert_eq!(draining.len(), 1);
+        assert_eq!(draining[0].provider_id.as_deref(), Some("p-old"));
+        assert_eq!(draining[0].provider_name.as_deref(), Some("Old"));
+
+        drop(old);
+        assert_eq!(tracker.len(), 2);
+        assert!(tracker.snapshot(&current).iter().all(|g| !g.draining));
+    }
+
+    #[tokio::test]
+    async fn wait_idle_returns_when_last_request_finishes() {
+        let tracker = Arc::new(InFlightTracker::default());
+        assert!(tracker.wait_idle(Duration::from_millis(10)).await);
+
+        let guard = tracker.begin("claude");
+        assert!(!tracker.wait_idle(Duration::from_millis(20)).await);
+
+        let waiter = {
+            let tracker = tracker.clone();
+            tokio::spawn(async move { tracker.wait_idle(Duration::from_secs(5)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(guard);
+        assert!(waiter.await.unwrap());
+    }
+}
@@ -3,6 +3,7 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use thiserror::Error;
 
@@ -26,6 +27,14 @@ pub enum ProxyError {
     #[error("请求转发失败: {0}")]
     ForwardFailed(String),
 
+    /// 建立连接阶段失败（DNS / TCP / TLS / 上游代理隧道），请求尚未发出
+    #[error("上游连接失败: {0}")]
+    ConnectFailed(String),
+
+    /// 建立连接阶段超时，请求尚未发出
+    #[error("连接超时: {0}")]
+    ConnectTimeout(String),
+
     #[error("无可用的Provider")]
     NoAvailableProvider,
 
@@ -35,6 +44,9 @@ pub enum ProxyError {
     #[error("未配置供应商")]
     NoProvidersConfigured,
 
+    #[error("所有可用供应商均已达到预算上限")]
+    BudgetExhausted,
+
     #[allow(dead_code)]
     #[error("Provider不健康: {0}")]
     ProviderUnhealthy(String),
@@ -76,6 +88,41 @@ pub enum ProxyError {
     Internal(String),
 }
 
+impl ProxyError {
+    /// 统一错误分类；代理自身生命周期/内部错误（未涉及上游）返回 None
+    pub fn class(&self) -> Option<HttpErrorClass> {
+        match self {
+            ProxyError::UpstreamError { status, .. } => {
+                Some(HttpErrorClass::from_status(*status).unwrap_or(HttpErrorClass::Upstream))
+            }
+            ProxyError::Timeout(_)
+            | ProxyError::ForwardFailed(_)
+            | ProxyError::ConnectFailed(_)
+            | ProxyError::ConnectTimeout(_)
+            | ProxyError::StreamIdleTimeout(_) => Some(HttpErrorClass::Network),
+            ProxyError::ProviderUnhealthy(_) => Some(HttpErrorClass::Upstream),
+            ProxyError::AuthError(_) => Some(HttpErrorClass::Auth),
+            // Provider 级配置/格式转换问题，换一个 Provider 可能就能成功
+            ProxyError::TransformError(_) | ProxyError::ConfigError(_) => {
+                Some(HttpErrorClass::Transform)
+            }
+            ProxyError::InvalidRequest(_) => Some(HttpErrorClass::Client),
+            ProxyError::AlreadyRunning
+            | ProxyError::NotRunning
+            | ProxyError::BindFailed(_)
+            | ProxyError::StopTimeout
+            | ProxyError::StopFailed(_)
+            | ProxyError::NoAvailableProvider
+            | ProxyError::AllProvidersCircuitOpen
+            | ProxyError::NoProvidersConfigured
+            | ProxyError::BudgetExhausted
+            | ProxyError::MaxRetriesExceeded
+            | ProxyError::DatabaseError(_)
+            | ProxyError::Internal(_) => None,
+        }
+    }
+}
+
 impl IntoResponse for ProxyError {
     fn into_response(self) -> Response {
         let (status, body) = match &self {
@@ -124,7 +171,9 @@ impl IntoResponse for ProxyError {
                     ProxyError::StopFailed(_) => {
                         (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
                     }
-                    ProxyError::ForwardFailed(_) => (StatusCode::BAD_GATEWAY, self.to_string()),
+                    ProxyError::ForwardFailed(_) | ProxyError::ConnectFailed(_) => {
+                        (StatusCode::BAD_GATEWAY, self.to_string())
+                    }
                     ProxyError::NoAvailableProvider => {
                         (StatusCode::SERVICE_UNAVAILABLE, self.to_string())
                     }
@@ -134,6 +183,9 @@ impl IntoResponse for ProxyError {
                     ProxyError::NoProvidersConfigured => {
                         (StatusCode::SERVICE_UNAVAILABLE, self.to_string())
                     }
+                    ProxyError::BudgetExhausted => {
+                        (StatusCode::TOO_MANY_REQUESTS, self.to_string())
+                    }
                     ProxyError::ProviderUnhealthy(_) => {
                         (StatusCode::SERVICE_UNAVAILABLE, self.to_string())
                     }
@@ -148,7 +200,9 @@ impl IntoResponse for ProxyError {
                         (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
                     }
                     ProxyError::InvalidRequest(_) => (StatusCode::BAD_REQUEST, self.to_string()),
-                    ProxyError::Timeout(_) => (StatusCode::GATEWAY_TIMEOUT, self.to_string()),
+                    ProxyError::Timeout(_) | ProxyError::ConnectTimeout(_) => {
+                        (StatusCode::GATEWAY_TIMEOUT, self.to_string())
+                    }
                     ProxyError::StreamIdleTimeout(_) => {
                         (StatusCode::GATEWAY_TIMEOUT, self.to_string())
                     }
@@ -174,6 +228,75 @@ impl IntoResponse for ProxyError {
     }
 }
 
+/// 统一 HTTP 错误分类
+///
+/// 代理转发、供应商连通性检查与端点测速共用同一套分类，前端/命令行据此给出一致的
+/// 本地化说明（`errorClass.<class>`），故障转移据此决定是否换供应商。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HttpErrorClass {
+    /// 鉴权失败（401/403/407，密钥无效或无权限）
+    Auth,
+    /// 配额/限流（402/429）
+    Quota,
+    /// 网络层失败（DNS、连接、TLS、超时、408）
+    Network,
+    /// 上游服务端错误（5xx）或供应商特有的拒绝（404/409 等）
+    Upstream,
+    /// 请求格式转换或供应商配置问题
+    Transform,
+    /// 请求本身不合法（400/422 等），换供应商也无法修复
+    Client,
+}
+
+impl HttpErrorClass {
+    /// 按 HTTP 状态码分类；非错误状态码返回 None
+    ///
+    /// 客户端请求自身有问题的状态码无论换哪个 provider 都会被拒绝：
+    ///   400 Bad Request / 422 Unprocessable Entity   ← 请求体格式或语义错误
+    ///   405 Method Not Allowed / 406 Not Acceptable  ← 方法或 Accept 错误
+    ///   413 Payload Too Large / 414 URI Too Long     ← 客户端构造超限
+    ///   415 Unsupported Media Type                    ← Content-Type 错误
+    ///   501 Not Implemented                           ← 上游协议确实不支持
+    pub fn from_status(status: u16) -> Option<Self> {
+        match status {
+            0..=399 => None,
+            401 | 403 | 407 => Some(Self::Auth),
+            402 | 429 => Some(Self::Quota),
+            408 => Some(Self::Network),
+            400 | 405 | 406 | 413 | 414 | 415 | 422 | 501 => Some(Self::Client),
+            _ => Some(Self::Upstream),
+        }
+    }
+
+    /// 按 reqwest 错误分类（带状态码的按状态码，其余视为网络层失败）
+    pub fn from_reqwest_error(error: &reqwest::Error) -> Self {
+        if let Some(class) = error.status().and_then(|s| Self::from_status(s.as_u16())) {
+            return class;
+        }
+        if error.is_decode() || error.is_body() {
+            return Self::Upstream;
+        }
+        Self::Network
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auth => "auth",
+            Self::Quota => "quota",
+            Self::Network => "network",
+            Self::Upstream => "upstream",
+            Self::Transform => "transform",
+            Self::Client => "client",
+        }
+    }
+
+    /// 是否属于供应商侧故障：换一家 provider 可能成功，且应计入其健康度
+    pub fn is_provider_fault(&self) -> bool {
+        !matches!(self, Self::Client)
+    }
+}
+
 /// 错误分类
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ErrorCategory {
@@ -204,3 +327,56 @@ pub fn categorize_error(error: &reqwest::Error) -> ErrorCategory {
         ErrorCategory::Retryable
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_codes_map_to_classes() {
+        assert_eq!(HttpErrorClass::from_status(200), None);
+        assert_eq!(HttpErrorClass::from_status(401), Some(HttpErrorClass::Auth));
+        assert_eq!(
+            HttpErrorClass::from_status(429),
+            Some(HttpErrorClass::Quota)
+        );
+        assert_eq!(
+            HttpErrorClass::from_status(408),
+            Some(HttpErrorClass::Network)
+        );
+        assert_eq!(
+            HttpErrorClass::from_status(422),
+            Some(HttpErrorClass::Client)
+        );
+        assert_eq!(
+            HttpErrorClass::from_status(501),
+            Some(HttpErrorClass::Client)
+        );
+        assert_eq!(
+            HttpErrorClass::from_status(404),
+            Some(HttpErrorClass::Upstream)
+        );
+        assert_eq!(
+            HttpErrorClass::from_status(503),
+            Some(HttpErrorClass::Upstream)
+        );
+    }
+
+    #[test]
+    fn proxy_errors_expose_their_class() {
+        let upstream = ProxyError::UpstreamError {
+            status: 429,
+            body: None,
+        };
+        assert_eq!(upstream.class(), Some(HttpErrorClass::Quota));
+        assert_eq!(
+            ProxyError::Timeout("t".into()).class(),
+            Some(HttpErrorClass::Network)
+        );
+        assert_eq!(ProxyError::NoAvailableProvider.class(), None);
+        assert_eq!(
+            serde_json::to_value(HttpErrorClass::Upstream).unwrap(),
+            json!("upstream")
+        );
+    }
+}
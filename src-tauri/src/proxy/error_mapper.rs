@@ -26,10 +26,12 @@ pub fn map_proxy_error_to_status(error: &ProxyError) -> u16 {
         ProxyError::UpstreamError { status, .. } => *status,
 
         // 超时错误：504 Gateway Timeout
-        ProxyError::Timeout(_) | ProxyError::StreamIdleTimeout(_) => 504,
+        ProxyError::Timeout(_)
+        | ProxyError::ConnectTimeout(_)
+        | ProxyError::StreamIdleTimeout(_) => 504,
 
         // 转发失败/连接失败：502 Bad Gateway
-        ProxyError::ForwardFailed(_) => 502,
+        ProxyError::ForwardFailed(_) | ProxyError::ConnectFailed(_) => 502,
 
         // 无可用 Provider：503 Service Unavailable
         ProxyError::NoAvailableProvider => 503,
@@ -40,6 +42,9 @@ pub fn map_proxy_error_to_status(error: &ProxyError) -> u16 {
         // 未配置供应商：503 Service Unavailable
         ProxyError::NoProvidersConfigured => 503,
 
+        // 所有供应商已达预算硬上限：429 Too Many Requests
+        ProxyError::BudgetExhausted => 429,
+
         // 重试耗尽：503 Service Unavailable
         ProxyError::MaxRetriesExceeded => 503,
 
@@ -75,9 +80,12 @@ pub fn get_error_message(error: &ProxyError) -> String {
         }
         ProxyError::Timeout(msg) => format!("请求超时: {msg}"),
         ProxyError::ForwardFailed(msg) => format!("转发失败: {msg}"),
+        ProxyError::ConnectFailed(msg) => format!("连接失败: {msg}"),
+        ProxyError::ConnectTimeout(msg) => format!("连接超时: {msg}"),
         ProxyError::NoAvailableProvider => "无可用 Provider".to_string(),
         ProxyError::AllProvidersCircuitOpen => "所有供应商已熔断，无可用渠道".to_string(),
         ProxyError::NoProvidersConfigured => "未配置供应商".to_string(),
+        ProxyError::BudgetExhausted => "所有可用供应商均已达到预算上限".to_string(),
         ProxyError::MaxRetriesExceeded => "所有 Provider 都失败，重试耗尽".to_string(),
         ProxyError::ProviderUnhealthy(msg) => format!("Provider 不健康: {msg}"),
         ProxyError::DatabaseError(msg) => format!("数据库错误: {msg}"),
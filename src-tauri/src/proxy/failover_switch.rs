@@ -7,6 +7,7 @@
 
 use crate::database::Database;
 use crate::error::AppError;
+use crate::services::status_file::{self, StatusSource};
 use std::collections::HashSet;
 use std::sync::Arc;
 use tauri::{Emitter, Manager};
@@ -109,6 +110,7 @@ impl FailoverSwitchManager {
                 if !switched {
                     return Ok(false);
                 }
+                status_file::refresh(&self.db, StatusSource::Failover);
 
                 if let Ok(new_menu) = crate::tray::create_tray_menu(app, app_state.inner()) {
                     if let Some(tray) = app.tray_by_id(crate::tray::TRAY_ID) {
@@ -6,29 +6,36 @@ use super::hyper_client::ProxyResponse;
 use super::{
     body_filter::filter_private_params_with_whitelist,
     content_encoding::{decompress_body, get_content_encoding},
+    drain::{InFlightGuard, InFlightTracker},
     error::*,
     failover_switch::FailoverSwitchManager,
     json_canonical::{canonicalize_value, short_value_hash},
     log_codes::fwd as log_fwd,
+    metrics::ProxyMetrics,
     provider_router::ProviderRouter,
     providers::{
         codex_chat_history::CodexChatHistoryStore, gemini_shadow::GeminiShadowStore, get_adapter,
         AuthInfo, AuthStrategy, ProviderAdapter, ProviderType,
     },
+    route_logging::RouteLogLevel,
     thinking_budget_rectifier::{rectify_thinking_budget, should_rectify_thinking_budget},
     thinking_rectifier::{
         normalize_thinking_type, rectify_anthropic_request, should_rectify_thinking_signature,
     },
-    types::{CopilotOptimizerConfig, OptimizerConfig, ProxyStatus, RectifierConfig},
+    types::{CopilotOptimizerConfig, OptimizerConfig, ProxyStatus, RectifierConfig, RetryConfig},
     ProxyError,
 };
 use crate::commands::{CodexOAuthState, CopilotAuthState, XaiOAuthState};
 use crate::proxy::providers::codex_oauth_auth::CodexOAuthManager;
 use crate::proxy::providers::copilot_auth::CopilotAuthManager;
 use crate::proxy::providers::xai_oauth_auth::XaiOAuthManager;
+use crate::services::provider_notice::ProviderNoticeService;
 use crate::{
     app_config::AppType,
-    provider::{LocalProxyRequestOverrides, Provider},
+    provider::{
+        ClientIdentity, HeaderTemplateContext, LocalProxyRequestOverrides, Provider,
+        CLAUDE_CODE_USER_AGENT,
+    },
 };
 use bytes::Bytes;
 use futures::StreamExt;
@@ -85,17 +92,32 @@ pub struct ForwardError {
 /// 在 `create_logged_passthrough_stream` 内还会继续 yield 字节流，导致 UI 的
 /// `active_connections` 计数过早归零。RAII guard 让"减量"由 Rust 类型系统驱动，
 /// 不需要每条出口路径都手动调用。
+///
+/// 同时持有 [`InFlightGuard`]，供停止代理时的优雅排空与切换后的状态展示使用。
 pub(crate) struct ActiveConnectionGuard {
     status: Arc<RwLock<ProxyStatus>>,
+    in_flight: InFlightGuard,
 }
 
 impl ActiveConnectionGuard {
-    pub(crate) async fn acquire(status: Arc<RwLock<ProxyStatus>>) -> Self {
+    pub(crate) async fn acquire(
+        status: Arc<RwLock<ProxyStatus>>,
+        in_flight: &Arc<InFlightTracker>,
+        app_type: &str,
+    ) -> Self {
         {
             let mut s = status.write().await;
             s.active_connections = s.active_connections.saturating_add(1);
         }
-        Self { status }
+        Self {
+            status,
+            in_flight: in_flight.begin(app_type),
+        }
+    }
+
+    /// 记录最终处理请求的供应商
+    pub(crate) fn bind_provider(&self, provider: &Provider) {
+        self.in_flight.bind_provider(&provider.id, &provider.name);
     }
 }
 
@@ -122,6 +144,10 @@ pub struct RequestForwarder {
     codex_chat_history: Arc<CodexChatHistoryStore>,
     /// 故障转移切换管理器
     failover_manager: Arc<FailoverSwitchManager>,
+    /// 请求指标
+    metrics: Arc<ProxyMetrics>,
+    /// 所属代理服务器的进行中请求跟踪器
+    in_flight: Arc<InFlightTracker>,
     /// AppHandle，用于发射事件和更新托盘
     app_handle: Option<tauri::AppHandle>,
     /// 请求开始时的"当前供应商 ID"（用于判断是否需要同步 UI/托盘）
@@ -146,6 +172,8 @@ pub struct RequestForwarder {
     /// `max_attempts = max_retries + 1`，所以 max_retries=0 表示仅尝试一家、
     /// max_retries=3（默认）表示最多 4 家。loop 同时受 providers.len() 自然限制。
     max_attempts: usize,
+    /// 请求内重试配置（同一供应商的瞬时故障重试）
+    retry_config: RetryConfig,
 }
 
 impl RequestForwarder {
@@ -194,6 +222,17 @@ impl RequestForwarder {
             && super::media_sanitizer::is_unsupported_image_error(error)
     }
 
+    /// 供应商的非流式总超时：供应商级 `totalTimeoutSecs` 优先，否则为应用级配置
+    fn non_streaming_timeout_for(&self, provider: &Provider) -> std::time::Duration {
+        provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.timeouts())
+            .and_then(|timeouts| timeouts.total_timeout_secs)
+            .map(std::time::Duration::from_secs)
+            .unwrap_or(self.non_streaming_timeout)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         router: Arc<ProviderRouter>,
@@ -203,6 +242,8 @@ impl RequestForwarder {
         gemini_shadow: Arc<GeminiShadowStore>,
         codex_chat_history: Arc<CodexChatHistoryStore>,
         failover_manager: Arc<FailoverSwitchManager>,
+        metrics: Arc<ProxyMetrics>,
+        in_flight: Arc<InFlightTracker>,
         app_handle: Option<tauri::AppHandle>,
         current_provider_id_at_start: String,
         session_id: String,
@@ -212,6 +253,7 @@ impl RequestForwarder {
         rectifier_config: RectifierConfig,
         optimizer_config: OptimizerConfig,
         copilot_optimizer_config: CopilotOptimizerConfig,
+        retry_config: RetryConfig,
         max_retries: u32,
     ) -> Self {
         // max_retries 是「失败后重试次数」语义，attempt 上限 = retries + 1。
@@ -224,6 +266,8 @@ impl RequestForwarder {
             gemini_shadow,
             codex_chat_history,
             failover_manager,
+            metrics,
+            in_flight,
             app_handle,
             current_provider_id_at_start,
             session_id,
@@ -236,6 +280,7 @@ impl RequestForwarder {
                 streaming_first_byte_timeout,
             ),
             max_attempts,
+            retry_config,
         }
     }
 
@@ -245,6 +290,11 @@ impl RequestForwarder {
         app_type: &str,
         used_half_open_permit: bool,
     ) {
+        if self.session_client_provided {
+            self.router
+                .bind_session(app_type, &self.session_id, provider_id);
+        }
+
         if used_half_open_permit {
             if let Err(e) = self
                 .router
@@ -293,7 +343,10 @@ impl RequestForwarder {
         // Provider 错误：本家上游/网络确实出问题，下一家 provider 可能可用 → 继续故障转移。
         // 客户端错误：整流后请求仍违法，下一家也修不好 → 直接返回。
         let is_provider_error = match &retry_err {
-            ProxyError::Timeout(_) | ProxyError::ForwardFailed(_) => true,
+            ProxyError::Timeout(_)
+            | ProxyError::ForwardFailed(_)
+            | ProxyError::ConnectFailed(_)
+            | ProxyError::ConnectTimeout(_) => true,
             ProxyError::UpstreamError { status, .. } => *status >= 500,
             _ => false,
         };
@@ -343,6 +396,9 @@ impl RequestForwarder {
     /// `active_connections` / 刷新 `last_request_at`，无论 inner 走哪条出口路径，
     /// 出口处都会把 `active_connections` 回收。Per-attempt 维度（成功/失败/熔断
     /// 等）仍由 inner 内自行更新 `success_requests` / `failed_requests`。
+    ///
+    /// `retry_count` 累加本次请求的上游重试次数（同供应商重试 + 故障转移），
+    /// 调用方传入请求上下文中写入用量日志的字段。
     #[allow(clippy::too_many_arguments)]
     pub async fn forward_with_retry(
         &self,
@@ -353,8 +409,12 @@ impl RequestForwarder {
         headers: axum::http::HeaderMap,
         extensions: Extensions,
         providers: Vec<Provider>,
+        retry_count: &mut u32,
     ) -> Result<ForwardResult, ForwardError> {
-        let guard = ActiveConnectionGuard::acquire(self.status.clone()).await;
+        let guard =
+            ActiveConnectionGuard::acquire(self.status.clone(), &self.in_flight, app_type.as_str())
+                .await;
+        let started_at = std::time::Instant::now();
         {
             let mut s = self.status.write().await;
             s.total_requests = s.total_requests.saturating_add(1);
@@ -362,13 +422,34 @@ impl RequestForwarder {
         }
         let result = self
             .forward_with_retry_inner(
-                app_type, method, endpoint, body, headers, extensions, providers,
+                app_type,
+                method,
+                endpoint,
+                body,
+                headers,
+                extensions,
+                providers,
+                retry_count,
             )
             .await;
+        let final_provider = match &result {
+            Ok(fr) => Some((&fr.provider, true)),
+            Err(fe) => fe.provider.as_ref().map(|provider| (provider, false)),
+        };
+        if let Some((provider, success)) = final_provider {
+            self.metrics.record(
+                app_type.as_str(),
+                &provider.id,
+                &provider.name,
+                success,
+                started_at.elapsed(),
+            );
+        }
         // 把 guard 注入到 Ok 结果，让它随响应一起流转到 response_processor，
         // 在流式 body 的 future 内才真正 drop。
         // Err 路径：guard 在函数 scope 内随返回值落地时自动 drop。
         result.map(|mut fr| {
+            guard.bind_provider(&fr.provider);
             fr.connection_guard = Some(guard);
             fr
         })
@@ -383,6 +464,7 @@ impl RequestForwarder {
     /// * `body` - 请求体
     /// * `headers` - 请求头
     /// * `providers` - 已选择的 Provider 列表（由 RequestContext 提供，避免重复调用 select_providers）
+    /// * `retry_count` - 上游重试次数累加器
     #[allow(clippy::too_many_arguments)]
     async fn forward_with_retry_inner(
         &self,
@@ -393,6 +475,7 @@ impl RequestForwarder {
         headers: axum::http::HeaderMap,
         extensions: Extensions,
         providers: Vec<Provider>,
+        retry_count: &mut u32,
     ) -> Result<ForwardResult, ForwardError> {
         // 获取适配器
         let adapter = get_adapter(app_type);
@@ -408,6 +491,8 @@ impl RequestForwarder {
         let mut last_error = None;
         let mut last_provider = None;
         let mut attempted_providers = 0usize;
+        let started_at = std::time::Instant::now();
+        let request_is_streaming = is_streaming_request(endpoint, &body, &headers);
 
         // 单 Provider 场景下跳过熔断器检查（故障转移关闭时）
         let bypass_circuit_breaker = providers.len() == 1;
@@ -464,6 +549,9 @@ impl RequestForwarder {
                 };
 
             attempted_providers += 1;
+            if attempted_providers > 1 {
+                *retry_count += 1;
+            }
 
             // 更新状态中的当前 Provider 信息（per-attempt 维度的标识）
             //
@@ -476,9 +564,9 @@ impl RequestForwarder {
                 status.current_provider_id = Some(provider.id.clone());
             }
 
-            // 转发请求（每个 Provider 只尝试一次，重试由客户端控制）
+            // 转发请求：瞬时故障先按退避重试同一 Provider，仍失败再走下方的故障转移
             match self
-                .forward(
+                .forward_with_transient_retry(
                     app_type,
                     &method,
                     provider,
@@ -487,6 +575,9 @@ impl RequestForwarder {
                     &headers,
                     &extensions,
                     adapter.as_ref(),
+                    request_is_streaming,
+                    started_at,
+                    retry_count,
                 )
                 .await
             {
@@ -1107,6 +1198,64 @@ impl RequestForwarder {
         })
     }
 
+    /// 转发单个请求，对瞬时故障按指数退避重试同一 Provider
+    ///
+    /// 只重试上游尚未产生响应的瞬时故障（见 [`is_transient_upstream_error`]）。
+    /// 连接阶段的失败（[`failed_before_reaching_upstream`]）任何方法都可重放；
+    /// 其余情况须为幂等方法（[`RetryConfig::permits_replay`]）——POST 补全即使
+    /// 收到 502 也可能已被上游处理，重放会重复计费。次数与总耗时受 [`RetryConfig`]
+    /// 约束；其余错误原样返回给故障转移逻辑。
+    #[allow(clippy::too_many_arguments)]
+    async fn forward_with_transient_retry(
+        &self,
+        app_type: &AppType,
+        method: &http::Method,
+        provider: &Provider,
+        endpoint: &str,
+        body: &Value,
+        headers: &axum::http::HeaderMap,
+        extensions: &Extensions,
+        adapter: &dyn ProviderAdapter,
+        request_is_streaming: bool,
+        started_at: std::time::Instant,
+        retry_count: &mut u32,
+    ) -> Result<(ProxyResponse, Option<String>, Option<String>), ProxyError> {
+        let mut retry = 0u32;
+        loop {
+            let result = self
+                .forward(
+                    app_type, method, provider, endpoint, body, headers, extensions, adapter,
+                )
+                .await;
+            let error = match result {
+                Err(error)
+                    if is_transient_upstream_error(&error, request_is_streaming)
+                        && self
+                            .retry_config
+                            .permits_replay(method, !failed_before_reaching_upstream(&error)) =>
+                {
+                    error
+                }
+                other => return other,
+            };
+            retry += 1;
+            if !self.retry_config.allows_retry(retry, started_at.elapsed()) {
+                return Err(error);
+            }
+            let backoff = self.retry_config.jittered_backoff(retry);
+            log::info!(
+                "[{}] [{}] Provider {} 瞬时故障，{}ms 后重试 ({retry}/{}): {error}",
+                app_type.as_str(),
+                log_fwd::TRANSIENT_RETRY,
+                provider.name,
+                backoff.as_millis(),
+                self.retry_config.same_provider_retries
+            );
+            *retry_count += 1;
+            tokio::time::sleep(backoff).await;
+        }
+    }
+
     /// 转发单个请求（使用适配器）
     ///
     /// 成功时返回 `(response, claude_api_format, outbound_model)`，其中
@@ -1611,6 +1760,9 @@ impl RequestForwarder {
         // 获取认证头（提前准备，用于内联替换），同时保留仅用于日志脱敏的
         // 精确认证材料。实际日志永远不输出这些值。
         let mut log_secrets: Vec<String> = Vec::new();
+        // 自定义鉴权（查询参数 / Cookie / HMAC）需要最终 URL 与请求体，
+        // 先记下密钥，待请求组装完成后再应用；托管 OAuth 供应商不适用
+        let mut custom_auth: Option<(&crate::provider::CustomAuthScheme, String)> = None;
         let mut auth_headers = if let Some(mut auth) = adapter.extract_auth(provider) {
             // GitHub Copilot 特殊处理：从 CopilotAuthManager 获取真实 token
             if auth.strategy == AuthStrategy::GitHubCopilot {
@@ -1760,7 +1912,23 @@ impl RequestForwarder {
                 }
             }
 
-            adapter.get_auth_headers(&auth)?
+            let scheme = provider
+                .meta
+                .as_ref()
+                .and_then(|meta| meta.custom_auth())
+                .filter(|_| !auth.strategy.is_oauth());
+            if let Some(scheme) = scheme {
+                if let crate::provider::CustomAuthScheme::Hmac(options) = scheme {
+                    if !options.secret.is_empty() {
+                        log_secrets.push(options.secret.clone());
+                    }
+                }
+                custom_auth = Some((scheme, auth.api_key.clone()));
+                // 不发送适配器默认的鉴权头（客户端自带的鉴权头同样被丢弃）
+                Vec::new()
+            } else {
+                adapter.get_auth_headers(&auth)?
+            }
         } else {
             Vec::new()
         };
@@ -1779,16 +1947,25 @@ impl RequestForwarder {
                 Vec::new()
             };
 
-        // 自定义 User-Agent：与 stream_check / model_fetch 共用 parse_custom_user_agent，
-        // 运行时静默忽略非法值（前端在输入处给非阻断提示，不在保存时阻断）。
+        // 客户端标识：与 stream_check / model_fetch / 测速共用 resolve_identity_user_agent，
+        // 自定义 UA 非法时运行时静默忽略（前端在输入处给非阻断提示，不在保存时阻断）。
         // Copilot 指纹 UA 不可覆盖。
+        let client_identity = if is_copilot {
+            ClientIdentity::Passthrough
+        } else {
+            provider
+                .meta
+                .as_ref()
+                .map(|meta| meta.client_identity())
+                .unwrap_or_default()
+        };
         let custom_user_agent = if is_copilot {
             None
         } else {
             provider
                 .meta
                 .as_ref()
-                .and_then(|meta| meta.custom_user_agent_header().ok().flatten())
+                .and_then(|meta| meta.identity_user_agent())
         };
         // Codex→Anthropic emulation: when there is no custom UA, override Codex's
         // codex_cli_rs UA with the Claude Code UA.
@@ -1797,6 +1974,9 @@ impl RequestForwarder {
         } else {
             custom_user_agent
         };
+        // 伪装 Claude Code 时统一注入 `x-app: cli`（替换客户端自带的值）
+        let inject_claude_code_x_app =
+            codex_impersonate_claude_code || client_identity == ClientIdentity::ClaudeCode;
 
         // --- Copilot 优化器：动态 header 注入 ---
         if let Some((ref classification, ref det_request_id, ref interaction_id)) =
@@ -1980,8 +2160,8 @@ impl RequestForwarder {
                 continue;
             }
 
-            // --- x-app — during Codex→Anthropic emulation, `cli` is injected uniformly below ---
-            if codex_impersonate_claude_code && key_str.eq_ignore_ascii_case("x-app") {
+            // --- x-app — during Claude Code emulation, `cli` is injected uniformly below ---
+            if inject_claude_code_x_app && key_str.eq_ignore_ascii_case("x-app") {
                 continue;
             }
 
@@ -2097,8 +2277,8 @@ impl RequestForwarder {
             );
         }
 
-        // Codex→Anthropic emulation: inject Claude Code's x-app: cli
-        if codex_impersonate_claude_code {
+        // Claude Code emulation: inject Claude Code's x-app: cli
+        if inject_claude_code_x_app {
             ordered_headers.append("x-app", http::HeaderValue::from_static("cli"));
         }
 
@@ -2160,9 +2340,27 @@ impl RequestForwarder {
                 .meta
                 .as_ref()
                 .and_then(|meta| meta.local_proxy_request_overrides.as_ref()),
+            &HeaderTemplateContext::new(app_type.as_str(), provider, outbound_model.as_deref()),
             is_copilot,
         );
 
+        let url = match custom_auth {
+            Some((scheme, api_key)) => {
+                let authorized = super::custom_auth::authorize_now(
+                    scheme,
+                    &api_key,
+                    method.as_str(),
+                    &url,
+                    &body_bytes,
+                )?;
+                for (name, value) in authorized.headers {
+                    ordered_headers.insert(name, value);
+                }
+                authorized.url
+            }
+            None => url,
+        };
+
         reject_proxy_placeholder_for_managed_account_upstream(&url, &ordered_headers)?;
 
         // 日志目标 URL 的脱敏分两种情形：
@@ -2182,18 +2380,37 @@ impl RequestForwarder {
             .get("model")
             .and_then(|v| v.as_str())
             .unwrap_or("<none>");
-        log::info!("[{tag}] >>> 请求目标: {target_for_log} (model={request_model})");
-        log::debug!(
-            "[{tag}] >>> 请求体已准备: bytes={}, hash={} (content omitted)",
-            body_bytes.len(),
-            short_value_hash(Some(&filtered_body))
-        );
+        let log_level = super::route_logging::level_for(app_type.as_str(), &provider.id);
+        if log_level > RouteLogLevel::Silent {
+            log::info!("[{tag}] >>> 请求目标: {target_for_log} (model={request_model})");
+        }
+        if log_level >= RouteLogLevel::Headers {
+            log::info!(
+                "[{tag}] >>> 请求头: {}",
+                super::route_logging::format_headers_redacted(&ordered_headers)
+            );
+        }
+        if log_level == RouteLogLevel::FullBodyRedacted {
+            log::info!(
+                "[{tag}] >>> 请求体: {}",
+                super::route_logging::redact_body(&filtered_body, &log_secrets)
+            );
+        } else if log_level > RouteLogLevel::Silent {
+            log::debug!(
+                "[{tag}] >>> 请求体已准备: bytes={}, hash={} (content omitted)",
+                body_bytes.len(),
+                short_value_hash(Some(&filtered_body))
+            );
+        }
 
-        // 确定超时
-        let timeout = if self.non_streaming_timeout.is_zero() {
+        // 确定超时（供应商级覆盖优先于应用级配置）
+        let provider_timeouts = provider.meta.as_ref().and_then(|meta| meta.timeouts());
+        let non_streaming_timeout = self.non_streaming_timeout_for(provider);
+        let connect_timeout = provider_timeouts.and_then(|timeouts| timeouts.connect_timeout());
+        let timeout = if non_streaming_timeout.is_zero() {
             std::time::Duration::from_secs(600) // 默认 600 秒
         } else {
-            self.non_streaming_timeout
+            non_streaming_timeout
         };
 
         // 获取全局代理 URL
@@ -2212,21 +2429,38 @@ impl RequestForwarder {
             is_copilot,
         );
 
-        // 发送请求
-        let response = if is_socks_proxy || !preserve_exact_header_case {
+        // 供应商级 TLS 选项（自定义 CA / 跳过校验）只在 reqwest 客户端上生效，
+        // 配置后放弃 raw write 的 header 大小写保留，改走独立客户端。
+        // 连接超时两条路径都支持，不影响路径选择。
+        let tls_options = provider.meta.as_ref().and_then(|meta| meta.tls_options());
+        let tls_client = tls_options
+            .map(|tls| super::http_client::get_for_provider(Some(tls), connect_timeout))
+            .transpose()
+            .map_err(ProxyError::ConfigError)?;
+
+        // 发送请求（`mock://` 供应商由内置模拟上游直接应答）
+        let response = if super::mock_upstream::is_mock_url(&base_url) {
+            super::mock_upstream::respond(provider, &url, &filtered_body, request_is_streaming)
+                .await
+        } else if is_socks_proxy || !preserve_exact_header_case || tls_client.is_some() {
             // OpenAI / Copilot / Codex 类后端不依赖原始 header 大小写；走 reqwest
             // 连接池，避免 raw TCP/TLS path 每次请求都重新握手。SOCKS5 也只能走 reqwest。
             log::debug!(
-                "[Forwarder] Using pooled reqwest client (preserve_exact_header_case={preserve_exact_header_case}, socks_proxy={is_socks_proxy})"
+                "[Forwarder] Using pooled reqwest client (preserve_exact_header_case={preserve_exact_header_case}, socks_proxy={is_socks_proxy}, provider_tls={})",
+                tls_client.is_some()
             );
-            let client = super::http_client::get();
+            let client = match tls_client {
+                Some(client) => client,
+                None => super::http_client::get_for_provider(None, connect_timeout)
+                    .map_err(ProxyError::ConfigError)?,
+            };
             let mut request = client.request(method.clone(), &url);
             if request_is_streaming {
                 // reqwest 的 timeout 是整请求超时；流式请求交给 response_processor
                 // 的首包/静默期超时控制，避免长流被总时长误杀。
                 request = request.timeout(std::time::Duration::from_secs(24 * 60 * 60));
-            } else if !self.non_streaming_timeout.is_zero() {
-                request = request.timeout(self.non_streaming_timeout);
+            } else if !non_streaming_timeout.is_zero() {
+                request = request.timeout(non_streaming_timeout);
             }
             for (key, value) in &ordered_headers {
                 request = request.header(key, value);
@@ -2265,6 +2499,7 @@ impl RequestForwarder {
                 extensions.clone(),
                 body_bytes,
                 timeout,
+                connect_timeout,
                 upstream_proxy_url.as_deref(),
             )
             .await?
@@ -2273,9 +2508,30 @@ impl RequestForwarder {
         // 检查响应状态
         let status = response.status();
 
+        // 上游宣告的弃用 / 下线信息按供应商记录，提醒用户提前迁移
+        if let Some(signal) = ProviderNoticeService::parse_headers(response.headers()) {
+            if let Some(app_state) = self
+                .app_handle
+                .as_ref()
+                .and_then(|app| app.try_state::<crate::store::AppState>())
+            {
+                ProviderNoticeService::observe(
+                    &app_state.db,
+                    app_type.as_str(),
+                    &provider.id,
+                    &provider.name,
+                    signal,
+                );
+            }
+        }
+
         if status.is_success() {
             let mut response = self
-                .prepare_success_response_for_failover(response, request_is_streaming)
+                .prepare_success_response_for_failover(
+                    response,
+                    request_is_streaming,
+                    non_streaming_timeout,
+                )
                 .await?;
             // Streaming requests normally return SSE. If a compatible gateway
             // explicitly returns JSON instead, buffer and validate it inside the retry
@@ -2301,6 +2557,13 @@ impl RequestForwarder {
                     response = self.validate_responses_stream_start(response).await?;
                 }
             }
+            if provider
+                .meta
+                .as_ref()
+                .is_some_and(|meta| meta.repairs_response_text())
+            {
+                response = super::text_repair::repair_response(response, &provider.id).await?;
+            }
             Ok((response, resolved_claude_api_format, outbound_model))
         } else {
             let status_code = status.as_u16();
@@ -2334,18 +2597,18 @@ impl RequestForwarder {
         &self,
         response: ProxyResponse,
         request_is_streaming: bool,
+        body_timeout: std::time::Duration,
     ) -> Result<ProxyResponse, ProxyError> {
         if request_is_streaming {
             return self.prime_streaming_response(response).await;
         }
 
-        if self.non_streaming_timeout.is_zero() {
+        if body_timeout.is_zero() {
             return Ok(response);
         }
 
         let status = response.status();
         let headers = response.headers().clone();
-        let body_timeout = self.non_streaming_timeout;
         let body = tokio::time::timeout(body_timeout, response.bytes())
             .await
             .map_err(|_| {
@@ -2658,35 +2921,13 @@ impl RequestForwarder {
             return ErrorCategory::NonRetryable;
         }
 
-        match error {
-            // 网络和上游错误：都应该尝试下一个供应商
-            ProxyError::Timeout(_) => ErrorCategory::Retryable,
-            ProxyError::ForwardFailed(_) => ErrorCategory::Retryable,
-            ProxyError::ProviderUnhealthy(_) => ErrorCategory::Retryable,
-            // 上游 HTTP 错误：按状态码分桶。
-            //
-            // 客户端请求自身有问题的状态码无论换哪个 provider 都会被拒绝，
-            // 继续轮询只会放大错误率、污染熔断器健康度、浪费配额：
-            //   400 Bad Request / 422 Unprocessable Entity   ← 请求体格式或语义错误
-            //   405 Method Not Allowed / 406 Not Acceptable  ← 方法或 Accept 错误
-            //   413 Payload Too Large / 414 URI Too Long     ← 客户端构造超限
-            //   415 Unsupported Media Type                    ← Content-Type 错误
-            //   501 Not Implemented                           ← 上游协议确实不支持
-            //
-            // 其他 4xx（401/403/404/408/409/429/451 等）和全部 5xx 都保留
-            // Retryable —— 换一家 provider 可能持有不同的 key、配额、地域或模型映射。
-            ProxyError::UpstreamError { status, .. } => match *status {
-                400 | 405 | 406 | 413 | 414 | 415 | 422 | 501 => ErrorCategory::NonRetryable,
-                _ => ErrorCategory::Retryable,
-            },
-            // Provider 级配置/转换问题：换一个 Provider 可能就能成功
-            ProxyError::ConfigError(_) => ErrorCategory::Retryable,
-            ProxyError::TransformError(_) => ErrorCategory::Retryable,
-            ProxyError::AuthError(_) => ErrorCategory::Retryable,
-            ProxyError::StreamIdleTimeout(_) => ErrorCategory::Retryable,
-            // 无可用供应商：所有供应商都试过了，无法重试
-            ProxyError::NoAvailableProvider => ErrorCategory::NonRetryable,
-            // 其他错误（数据库/内部错误等）：不是换供应商能解决的问题
+        // 按统一错误分类分桶：客户端请求自身有问题（Client）无论换哪个 provider
+        // 都会被拒绝，继续轮询只会放大错误率、污染熔断器健康度、浪费配额；
+        // 其余分类（鉴权/配额/网络/上游/转换）换一家 provider 可能持有不同的
+        // key、配额、地域或模型映射，应尝试下一个供应商。
+        // 代理自身错误（无可用供应商、数据库/内部错误等）没有分类，不是换供应商能解决的问题。
+        match error.class() {
+            Some(class) if class.is_provider_fault() => ErrorCategory::Retryable,
             _ => ErrorCategory::NonRetryable,
         }
     }
@@ -2711,6 +2952,33 @@ fn is_bedrock_provider(provider: &Provider) -> bool {
         .unwrap_or(false)
 }
 
+/// 是否为可安全重试同一 Provider 的瞬时故障
+///
+/// 这些错误都发生在上游产生任何响应内容之前，客户端尚未收到字节，重放请求是幂等的：
+/// - 连接失败 / 连接超时（`ConnectFailed` / `ConnectTimeout`）
+/// - 发送失败、流式响应在首包前中断（`ForwardFailed`）
+/// - 网关类 502/503/504
+/// - 流式请求的首包超时（非流式的整请求超时可能已在上游完成生成，不重试）
+fn is_transient_upstream_error(error: &ProxyError, request_is_streaming: bool) -> bool {
+    match error {
+        ProxyError::ForwardFailed(_)
+        | ProxyError::ConnectFailed(_)
+        | ProxyError::ConnectTimeout(_) => true,
+        ProxyError::UpstreamError { status, .. } => matches!(status, 502..=504),
+        ProxyError::Timeout(_) => request_is_streaming,
+        _ => false,
+    }
+}
+
+/// 错误是否发生在请求发出之前（连接 / DNS / TLS / 上游代理隧道失败、连接超时），
+/// 此时上游不可能处理过该请求
+fn failed_before_reaching_upstream(error: &ProxyError) -> bool {
+    matches!(
+        error,
+        ProxyError::ConnectFailed(_) | ProxyError::ConnectTimeout(_)
+    )
+}
+
 fn build_retryable_failure_log(
     provider_name: &str,
     attempted_providers: usize,
@@ -2774,6 +3042,12 @@ fn summarize_proxy_error(error: &ProxyError) -> String {
         ProxyError::ForwardFailed(message) => {
             format!("请求转发失败: {}", summarize_text_for_log(message, 180))
         }
+        ProxyError::ConnectFailed(message) => {
+            format!("上游连接失败: {}", summarize_text_for_log(message, 180))
+        }
+        ProxyError::ConnectTimeout(message) => {
+            format!("连接超时: {}", summarize_text_for_log(message, 180))
+        }
         ProxyError::TransformError(message) => {
             format!("响应转换失败: {}", summarize_text_for_log(message, 180))
         }
@@ -2852,9 +3126,6 @@ fn rewrite_codex_responses_endpoint_to_chat(endpoint: &str) -> (String, Option<S
     (rewritten, passthrough_query)
 }
 
-/// Claude Code client fingerprint (used for Codex→Anthropic emulation to pass a
-/// gateway's "Claude Code only" check).
-const CLAUDE_CODE_USER_AGENT: &str = "claude-cli/1.0.119 (external, cli)";
 const CLAUDE_CODE_SYSTEM_IDENTITY: &str =
     "You are Claude Code, Anthropic's official CLI for Claude.";
 
@@ -3293,10 +3564,12 @@ fn should_force_identity_encoding(
 }
 
 fn map_reqwest_send_error(error: reqwest::Error) -> ProxyError {
-    if error.is_timeout() {
+    if error.is_connect() && error.is_timeout() {
+        ProxyError::ConnectTimeout(error.without_url().to_string())
+    } else if error.is_timeout() {
         ProxyError::Timeout(format!("上游请求超时: {}", error.without_url()))
     } else if error.is_connect() {
-        ProxyError::ForwardFailed(format!("上游连接失败: {}", error.without_url()))
+        ProxyError::ConnectFailed(error.without_url().to_string())
     } else {
         ProxyError::ForwardFailed(format!("上游请求发送失败: {}", error.without_url()))
     }
@@ -3372,17 +3645,19 @@ fn merge_json_override_inner(target: &mut Value, patch: &Value, is_top_level: bo
 fn apply_local_proxy_header_overrides(
     headers: &mut http::HeaderMap,
     overrides: Option<&LocalProxyRequestOverrides>,
+    template: &HeaderTemplateContext,
     is_copilot: bool,
 ) {
     if is_copilot {
         return;
     }
 
-    let Some(header_overrides) = overrides.map(|overrides| &overrides.headers) else {
+    let Some(header_overrides) = overrides.map(|overrides| overrides.rendered_headers(template))
+    else {
         return;
     };
 
-    for (raw_name, raw_value) in header_overrides {
+    for (raw_name, raw_value) in &header_overrides {
         let header_name = raw_name.trim().to_ascii_lowercase();
         if header_name.is_empty() {
             log::warn!("[LocalProxyOverrides] Ignoring header override with empty name");
@@ -3414,7 +3689,7 @@ fn apply_local_proxy_header_overrides(
     }
 }
 
-fn is_protected_local_proxy_override_header(name: &http::HeaderName) -> bool {
+pub(crate) fn is_protected_local_proxy_override_header(name: &http::HeaderName) -> bool {
     matches!(
         name.as_str(),
         "host"
@@ -3611,6 +3886,8 @@ mod tests {
             gemini_shadow: Arc::new(GeminiShadowStore::new()),
             codex_chat_history: Arc::new(CodexChatHistoryStore::default()),
             failover_manager: Arc::new(FailoverSwitchManager::new(db)),
+            metrics: Arc::new(ProxyMetrics::default()),
+            in_flight: Arc::new(InFlightTracker::default()),
             app_handle: None,
             current_provider_id_at_start: String::new(),
             session_id: String::new(),
@@ -3621,9 +3898,63 @@ mod tests {
             non_streaming_timeout,
             streaming_first_byte_timeout,
             max_attempts: 1,
+            retry_config: RetryConfig::default(),
         }
     }
 
+    #[test]
+    fn connect_phase_failures_never_reached_upstream() {
+        for message in [
+            "connection refused",
+            "TCP connect failed: dns error",
+            "TLS handshake failed: bad certificate",
+            "Proxy TLS handshake failed: bad certificate",
+        ] {
+            let error = ProxyError::ConnectFailed(message.to_string());
+            assert!(failed_before_reaching_upstream(&error), "{message}");
+        }
+        assert!(failed_before_reaching_upstream(
+            &ProxyError::ConnectTimeout("10s".to_string())
+        ));
+        // 阶段由错误类型决定，与文案无关
+        assert!(!failed_before_reaching_upstream(&ProxyError::Timeout(
+            "连接超时: 10s".to_string()
+        )));
+        assert!(!failed_before_reaching_upstream(
+            &ProxyError::ForwardFailed("上游请求发送失败: broken pipe".to_string())
+        ));
+        assert!(!failed_before_reaching_upstream(
+            &ProxyError::UpstreamError {
+                status: 502,
+                body: None
+            }
+        ));
+    }
+
+    #[test]
+    fn transient_retry_only_covers_pre_response_failures() {
+        let connect = ProxyError::ConnectFailed("connection refused".to_string());
+        assert!(is_transient_upstream_error(&connect, false));
+        for status in [502, 503, 504] {
+            let error = ProxyError::UpstreamError { status, body: None };
+            assert!(is_transient_upstream_error(&error, false));
+        }
+        for status in [400, 429, 500] {
+            let error = ProxyError::UpstreamError { status, body: None };
+            assert!(!is_transient_upstream_error(&error, true));
+        }
+        let timeout = ProxyError::Timeout("流式响应首包超时".to_string());
+        assert!(is_transient_upstream_error(&timeout, true));
+        // 非流式整请求超时时上游可能已完成生成，不重放
+        assert!(!is_transient_upstream_error(&timeout, false));
+        let connect_timeout = ProxyError::ConnectTimeout("10s".to_string());
+        assert!(is_transient_upstream_error(&connect_timeout, false));
+        assert!(!is_transient_upstream_error(
+            &ProxyError::AuthError("bad key".to_string()),
+            true
+        ));
+    }
+
     #[test]
     fn single_provider_retryable_log_uses_single_provider_code() {
         let error = ProxyError::UpstreamError {
@@ -3813,11 +4144,14 @@ mod tests {
                 ("Authorization".to_string(), "Bearer bad".to_string()),
                 ("Content-Type".to_string(), "text/plain".to_string()),
                 ("X-Bad".to_string(), "bad\nvalue".to_string()),
+                ("X-Route".to_string(), "{{app_type}}:{{model}}".to_string()),
             ]),
             body: None,
         };
+        let provider = test_provider_with_type(None);
+        let template = HeaderTemplateContext::new("claude", &provider, Some("claude-haiku"));
 
-        apply_local_proxy_header_overrides(&mut headers, Some(&overrides), false);
+        apply_local_proxy_header_overrides(&mut headers, Some(&overrides), &template, false);
 
         assert_eq!(
             headers
@@ -3842,6 +4176,10 @@ mod tests {
             Some("ok")
         );
         assert!(headers.get("x-bad").is_none());
+        assert_eq!(
+            headers.get("x-route").and_then(|value| value.to_str().ok()),
+            Some("claude:claude-haiku")
+        );
     }
 
     #[test]
@@ -3856,7 +4194,10 @@ mod tests {
             body: None,
         };
 
-        apply_local_proxy_header_overrides(&mut headers, Some(&overrides), true);
+        let provider = test_provider_with_type(None);
+        let template = HeaderTemplateContext::new("claude", &provider, None);
+
+        apply_local_proxy_header_overrides(&mut headers, Some(&overrides), &template, true);
 
         assert_eq!(
             headers
@@ -3866,6 +4207,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn provider_total_timeout_overrides_app_timeout() {
+        let forwarder = test_forwarder(Duration::from_secs(600), Duration::from_secs(1));
+        let mut provider = test_provider_with_type(None);
+        assert_eq!(
+            forwarder.non_streaming_timeout_for(&provider),
+            Duration::from_secs(600)
+        );
+
+        provider.meta = Some(crate::provider::ProviderMeta {
+            timeouts: Some(crate::provider::ProviderTimeouts {
+                total_timeout_secs: Some(1800),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        assert_eq!(
+            forwarder.non_streaming_timeout_for(&provider),
+            Duration::from_secs(1800)
+        );
+    }
+
     #[tokio::test]
     async fn non_streaming_success_is_buffered_before_marking_provider_successful() {
         let forwarder = test_forwarder(Duration::from_secs(1), Duration::from_secs(1));
@@ -3879,7 +4242,7 @@ mod tests {
         );
 
         let prepared = forwarder
-            .prepare_success_response_for_failover(response, false)
+            .prepare_success_response_for_failover(response, false, forwarder.non_streaming_timeout)
             .await
             .expect("response should be buffered");
 
@@ -3901,7 +4264,7 @@ mod tests {
         );
 
         let err = match forwarder
-            .prepare_success_response_for_failover(response, false)
+            .prepare_success_response_for_failover(response, false, forwarder.non_streaming_timeout)
             .await
         {
             Ok(_) => panic!("body read errors should fail the attempt"),
@@ -3924,7 +4287,7 @@ mod tests {
         );
 
         let prepared = forwarder
-            .prepare_success_response_for_failover(response, true)
+            .prepare_success_response_for_failover(response, true, forwarder.non_streaming_timeout)
             .await
             .expect("stream should be primed");
 
@@ -3946,7 +4309,7 @@ mod tests {
         );
 
         let err = match forwarder
-            .prepare_success_response_for_failover(response, true)
+            .prepare_success_response_for_failover(response, true, forwarder.non_streaming_timeout)
             .await
         {
             Ok(_) => panic!("first chunk errors should fail the attempt"),
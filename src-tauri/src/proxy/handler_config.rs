@@ -40,7 +40,9 @@ pub struct UsageParserConfig {
 // ============================================================================
 
 pub fn claude_stream_usage_event_filter(data: &str) -> bool {
-    data.contains("\"message_start\"") || data.contains("\"message_delta\"")
+    data.contains("\"message_start\"")
+        || data.contains("\"message_delta\"")
+        || data.contains("invocationMetrics")
 }
 
 fn openai_stream_usage_event_filter(data: &str) -> bool {
@@ -7,8 +7,12 @@ use crate::provider::Provider;
 use crate::proxy::{
     extract_session_id,
     forwarder::RequestForwarder,
+    route_logging::RouteLogLevel,
     server::ProxyState,
-    types::{AppProxyConfig, CopilotOptimizerConfig, OptimizerConfig, RectifierConfig},
+    types::{
+        AppProxyConfig, CopilotOptimizerConfig, OptimizerConfig, RectifierConfig,
+        ResponseCacheConfig, RetryConfig,
+    },
     ProxyError,
 };
 use axum::http::HeaderMap;
@@ -53,6 +57,8 @@ pub struct RequestContext {
     /// usage 归因的兜底顺序：上游响应回显 → outbound_model → request_model。
     /// 不能直接用 request_model 兜底：接管场景下它是映射前的客户端别名。
     pub outbound_model: Option<String>,
+    /// 本次请求的上游重试次数（同供应商重试 + 故障转移，forward 结束后回填），写入用量日志
+    pub retry_count: u32,
     /// 日志标签（如 "Claude"、"Codex"、"Gemini"）
     pub tag: &'static str,
     /// 应用类型字符串（如 "claude"、"codex"、"gemini"）
@@ -70,6 +76,12 @@ pub struct RequestContext {
     pub optimizer_config: OptimizerConfig,
     /// Copilot 优化器配置
     pub copilot_optimizer_config: CopilotOptimizerConfig,
+    /// 请求内重试配置
+    pub retry_config: RetryConfig,
+    /// 响应缓存配置
+    pub response_cache_config: ResponseCacheConfig,
+    /// Claude 请求提示词的估算 token 数，用于检测中转注入 / 截断系统提示词
+    pub prompt_estimate: Option<u64>,
 }
 
 impl RequestContext {
@@ -106,6 +118,8 @@ impl RequestContext {
         let rectifier_config = state.db.get_rectifier_config().unwrap_or_default();
         let optimizer_config = state.db.get_optimizer_config().unwrap_or_default();
         let copilot_optimizer_config = state.db.get_copilot_optimizer_config().unwrap_or_default();
+        let retry_config = state.db.get_retry_config().unwrap_or_default();
+        let response_cache_config = state.db.get_response_cache_config().unwrap_or_default();
 
         let current_provider_id =
             crate::settings::get_current_provider(&app_type).unwrap_or_default();
@@ -120,6 +134,7 @@ impl RequestContext {
         // 提取 Session ID
         let session_result = extract_session_id(headers, body, app_type_str);
         let session_id = session_result.session_id.clone();
+        state.auth.bind_session(&session_id, headers);
 
         log::debug!(
             "[{}] Session ID: {} (from {:?}, client_provided: {})",
@@ -131,9 +146,14 @@ impl RequestContext {
 
         // 使用共享的 ProviderRouter 选择 Provider（熔断器状态跨请求保持）
         // 注意：只在这里调用一次，结果传递给 forwarder，避免重复消耗 HalfOpen 名额
-        let providers = state
+        let mut providers = state
             .provider_router
-            .select_providers(app_type_str)
+            .select_providers_for_session(
+                app_type_str,
+                session_result
+                    .client_provided
+                    .then_some(session_id.as_str()),
+            )
             .await
             .map_err(|e| match e {
                 crate::error::AppError::AllProvidersCircuitOpen => {
@@ -142,6 +162,26 @@ impl RequestContext {
                 crate::error::AppError::NoProvidersConfigured => ProxyError::NoProvidersConfigured,
                 _ => ProxyError::DatabaseError(e.to_string()),
             })?;
+        // 已达预算硬上限的供应商不参与路由
+        let removed = crate::services::provider_budget::ProviderBudgetService::remove_exhausted(
+            &state.db,
+            app_type_str,
+            &mut providers,
+        );
+        if removed > 0 && providers.is_empty() {
+            return Err(ProxyError::BudgetExhausted);
+        }
+        // 能力矩阵明确不支持本次请求所需特性（流式/工具/图片）的供应商后移
+        crate::services::capability_probe::prefer_capable(
+            &state.db,
+            app_type_str,
+            body,
+            &mut providers,
+        );
+        // 处于维护时段的供应商排到最后，仅在其他供应商都失败时尝试
+        crate::services::maintenance::defer_in_maintenance(&mut providers, chrono::Utc::now());
+        // 正在违反首字延迟 SLO（且开启后移）的供应商同样延后
+        crate::services::latency_slo::defer_violating(app_type_str, &mut providers);
 
         let provider = providers
             .first()
@@ -165,6 +205,7 @@ impl RequestContext {
             current_provider_id,
             request_model,
             outbound_model: None,
+            retry_count: 0,
             tag,
             app_type_str,
             app_type,
@@ -173,6 +214,11 @@ impl RequestContext {
             rectifier_config,
             optimizer_config,
             copilot_optimizer_config,
+            retry_config,
+            response_cache_config,
+            prompt_estimate: (app_type_str == "claude")
+                .then(|| crate::services::prompt_integrity::estimate_prompt_tokens(body))
+                .flatten(),
         })
     }
 
@@ -231,6 +277,8 @@ impl RequestContext {
             state.gemini_shadow.clone(),
             state.codex_chat_history.clone(),
             state.failover_manager.clone(),
+            state.metrics.clone(),
+            state.in_flight.clone(),
             state.app_handle.clone(),
             self.current_provider_id.clone(),
             self.session_id.clone(),
@@ -240,6 +288,7 @@ impl RequestContext {
             self.rectifier_config.clone(),
             self.optimizer_config.clone(),
             self.copilot_optimizer_config.clone(),
+            self.retry_config.clone(),
             max_retries,
         )
     }
@@ -251,6 +300,18 @@ impl RequestContext {
         self.providers.clone()
     }
 
+    /// 用量归因的映射后模型：forward 成功时为实际发出的模型；
+    /// 失败时按别名表与供应商模型映射推算（Claude Desktop 的路由映射不在此列）
+    pub fn mapped_model(&self) -> String {
+        if let Some(model) = &self.outbound_model {
+            return model.clone();
+        }
+        if self.request_model.is_empty() || matches!(self.app_type, AppType::ClaudeDesktop) {
+            return self.request_model.clone();
+        }
+        crate::proxy::model_mapper::map_model_name(&self.request_model, &self.provider)
+    }
+
     /// 计算请求延迟（毫秒）
     #[inline]
     pub fn latency_ms(&self) -> u64 {
@@ -262,9 +323,10 @@ impl RequestContext {
     /// 配置生效规则：
     /// - 故障转移开启：返回配置的值（0 表示禁用超时检查）
     /// - 故障转移关闭：返回 0（禁用超时检查）
+    /// - 实际处理请求的供应商设置了 `streamIdleTimeoutSecs` 时，静默期超时以它为准
     #[inline]
     pub fn streaming_timeout_config(&self) -> StreamingTimeoutConfig {
-        if self.app_config.auto_failover_enabled {
+        let mut config = if self.app_config.auto_failover_enabled {
             // 故障转移开启：使用配置的值（0 = 禁用超时）
             StreamingTimeoutConfig {
                 first_byte_timeout: self.app_config.streaming_first_byte_timeout as u64,
@@ -276,7 +338,23 @@ impl RequestContext {
                 first_byte_timeout: 0,
                 idle_timeout: 0,
             }
+        };
+        if let Some(idle_timeout) = self
+            .provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.timeouts())
+            .and_then(|timeouts| timeouts.stream_idle_timeout_secs)
+        {
+            config.idle_timeout = idle_timeout;
         }
+        config
+    }
+
+    /// 实际处理请求的供应商生效的日志详细程度
+    #[inline]
+    pub fn route_log_level(&self) -> RouteLogLevel {
+        super::route_logging::level_for(self.app_type_str, &self.provider.id)
     }
 }
 
@@ -8,6 +8,7 @@
 //! - Claude 的格式转换逻辑保留在此文件（用于 OpenRouter 旧接口回退）
 
 use super::{
+    claude_endpoints,
     content_encoding::{decompress_body, get_content_encoding, is_supported_content_encoding},
     error_mapper::{get_error_message, map_proxy_error_to_status},
     forwarder::ActiveConnectionGuard,
@@ -16,6 +17,7 @@ use super::{
         CODEX_PARSER_CONFIG, GEMINI_PARSER_CONFIG, OPENAI_PARSER_CONFIG,
     },
     handler_context::RequestContext,
+    metrics::MetricsGauges,
     providers::{
         codex_chat_common::extract_reasoning_field_text,
         codex_chat_history::record_responses_sse_stream,
@@ -31,6 +33,7 @@ use super::{
         transform, transform_codex_anthropic, transform_codex_chat,
         transform_codex_responses_namespace, transform_gemini, transform_responses,
     },
+    response_cache,
     response_processor::{
         create_logged_passthrough_stream, create_usage_collector, process_response,
         read_decoded_body, strip_entity_headers_for_rebuilt_body,
@@ -60,10 +63,77 @@ pub async fn health_check() -> (StatusCode, Json<Value>) {
         Json(json!({
             "status": "healthy",
             "timestamp": chrono::Utc::now().to_rfc3339(),
+            // 共享机器上用于判断占用默认端口的是哪个用户的代理
+            "user": crate::os_user::user_name(),
         })),
     )
 }
 
+/// 运行状况：运行时间、各应用当前目标供应商与熔断状态（便于外部监控探活）
+pub async fn healthz(State(state): State<ProxyState>) -> Json<Value> {
+    let gauges = collect_gauges(&state).await;
+    let circuits: Vec<Value> = gauges
+        .circuits
+        .iter()
+        .map(|(app, provider_id, circuit)| {
+            json!({ "app": app, "providerId": provider_id, "state": circuit })
+        })
+        .collect();
+    let open_circuits = gauges
+        .circuits
+        .iter()
+        .filter(|(_, _, circuit)| *circuit != super::CircuitState::Closed)
+        .count();
+    Json(json!({
+        "status": if open_circuits == 0 { "ok" } else { "degraded" },
+        "uptimeSeconds": gauges.uptime_seconds,
+        "activeConnections": gauges.active_connections,
+        "failoverCount": gauges.failover_count,
+        "activeTargets": gauges.active_targets,
+        "circuits": circuits,
+    }))
+}
+
+/// Prometheus 指标
+pub async fn metrics(State(state): State<ProxyState>) -> impl IntoResponse {
+    let gauges = collect_gauges(&state).await;
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        state.metrics.render_prometheus(&gauges),
+    )
+}
+
+async fn collect_gauges(state: &ProxyState) -> MetricsGauges {
+    let status = state.status.read().await.clone();
+    let uptime_seconds = state
+        .start_time
+        .read()
+        .await
+        .map(|start| start.elapsed().as_secs())
+        .unwrap_or(0);
+    let active_targets = state
+        .current_providers
+        .read()
+        .await
+        .iter()
+        .map(|(app_type, (provider_id, provider_name))| ActiveTarget {
+            app_type: app_type.clone(),
+            provider_id: provider_id.clone(),
+            provider_name: provider_name.clone(),
+        })
+        .collect();
+    MetricsGauges {
+        uptime_seconds,
+        active_connections: status.active_connections,
+        failover_count: status.failover_count,
+        active_targets,
+        circuits: state.provider_router.circuit_states().await,
+    }
+}
+
 /// 获取服务状态
 pub async fn get_status(State(state): State<ProxyState>) -> Result<Json<ProxyStatus>, ProxyError> {
     let status = state.status.read().await.clone();
@@ -72,6 +142,10 @@ pub async fn get_status(State(state): State<ProxyState>) -> Result<Json<ProxySta
 
 /// GET /v1/models — Codex model list (reachability check)
 ///
+/// Claude clients (identified by the `anthropic-version` header) get a model
+/// list synthesized from the current Claude provider instead, see
+/// [`claude_endpoints::model_list_response`].
+///
 /// Codex CLI probes this endpoint at startup and deserializes the response as a
 /// catalog with a top-level `models` field.  Return the cc-switch–managed model
 /// catalog file directly so the format always matches what the current version
@@ -80,7 +154,14 @@ pub async fn get_status(State(state): State<ProxyState>) -> Result<Json<ProxySta
 /// Only serves the catalog when the live config.toml still references the
 /// cc-switch–owned `model_catalog_json`, using the same path ownership rules as
 /// Codex live-setting import.
-pub async fn handle_models() -> Result<Json<Value>, ProxyError> {
+pub async fn handle_models(
+    State(state): State<ProxyState>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<Value>, ProxyError> {
+    if headers.contains_key("anthropic-version") {
+        return handle_claude_models(State(state)).await;
+    }
+
     let generated_path = crate::codex_config::get_codex_model_catalog_path();
     let active_catalog_path = match crate::codex_config::read_codex_config_text() {
         Ok(config_text) => {
@@ -109,6 +190,64 @@ pub async fn handle_models() -> Result<Json<Value>, ProxyError> {
 // Claude API 处理器（包含格式转换逻辑）
 // ============================================================================
 
+/// GET /claude/v1/models — 由当前 Claude 供应商的模型配置合成
+pub async fn handle_claude_models(
+    State(state): State<ProxyState>,
+) -> Result<Json<Value>, ProxyError> {
+    let providers = state
+        .provider_router
+        .select_providers("claude")
+        .await
+        .map_err(|e| ProxyError::DatabaseError(e.to_string()))?;
+    let provider = providers.first().ok_or(ProxyError::NoAvailableProvider)?;
+    Ok(Json(claude_endpoints::model_list_response(provider)))
+}
+
+/// POST /v1/messages/count_tokens
+///
+/// Anthropic 原生上游直接透传；需要格式转换的上游没有该接口，或透传失败时
+/// 回落到本地估算，保证 Claude Code 的上下文统计不会因 404 中断。
+/// 不计入用量统计，也不参与故障转移与熔断。
+pub async fn handle_count_tokens(
+    State(state): State<ProxyState>,
+    request: axum::extract::Request,
+) -> Result<Json<Value>, ProxyError> {
+    let (parts, body) = request.into_parts();
+    let body_bytes = body
+        .collect()
+        .await
+        .map_err(|e| ProxyError::Internal(format!("Failed to read request body: {e}")))?
+        .to_bytes();
+    let body: Value = serde_json::from_slice(&body_bytes)
+        .map_err(|e| ProxyError::InvalidRequest(format!("Failed to parse request body: {e}")))?;
+
+    let providers = state
+        .provider_router
+        .select_providers("claude")
+        .await
+        .map_err(|e| ProxyError::DatabaseError(e.to_string()))?;
+    if let Some(provider) = providers.first() {
+        let adapter = get_adapter(&AppType::Claude);
+        if !adapter.needs_transform(provider) {
+            match claude_endpoints::forward_count_tokens(
+                adapter.as_ref(),
+                provider,
+                &parts.headers,
+                &body,
+            )
+            .await
+            {
+                Ok(response) => return Ok(Json(response)),
+                Err(e) => log::debug!(
+                    "[Claude] count_tokens passthrough failed for {}, using local estimate: {e}",
+                    provider.id
+                ),
+            }
+        }
+    }
+    Ok(Json(claude_endpoints::estimate_count_tokens(&body)))
+}
+
 /// 处理 /v1/messages 请求（Claude API）
 ///
 /// Claude 处理器包含独特的格式转换逻辑：
@@ -190,6 +329,11 @@ async fn handle_messages_for_app(
         .and_then(|s| s.as_bool())
         .unwrap_or(false);
 
+    let cache_slot = response_cache::prepare(&ctx, endpoint, &body, is_stream);
+    if let Some(response) = cache_slot.as_ref().and_then(|slot| slot.lookup(tag)) {
+        return Ok(response);
+    }
+
     // 转发请求
     let forwarder = ctx.create_forwarder(&state);
     let mut result = match forwarder
@@ -201,6 +345,7 @@ async fn handle_messages_for_app(
             headers,
             extensions,
             ctx.get_providers(),
+            &mut ctx.retry_count,
         )
         .await
     {
@@ -228,9 +373,9 @@ async fn handle_messages_for_app(
     let adapter = get_adapter(&app_type);
     let needs_transform = adapter.needs_transform(&ctx.provider);
 
-    // Claude 特有：格式转换处理
-    if needs_transform {
-        return handle_claude_transform(
+    let response = if needs_transform {
+        // Claude 特有：格式转换处理
+        handle_claude_transform(
             response,
             &ctx,
             &state,
@@ -239,18 +384,19 @@ async fn handle_messages_for_app(
             &api_format,
             connection_guard,
         )
-        .await;
-    }
-
-    // 通用响应处理（透传模式）
-    process_response(
-        response,
-        &ctx,
-        &state,
-        &CLAUDE_PARSER_CONFIG,
-        connection_guard,
-    )
-    .await
+        .await?
+    } else {
+        // 通用响应处理（透传模式）
+        process_response(
+            response,
+            &ctx,
+            &state,
+            &CLAUDE_PARSER_CONFIG,
+            connection_guard,
+        )
+        .await?
+    };
+    response_cache::finish(cache_slot, response).await
 }
 
 fn validate_claude_desktop_gateway_auth(
@@ -294,6 +440,7 @@ struct ClaudeUsageLog {
     latency_ms: u64,
     status_code: u16,
     is_streaming: bool,
+    retry_count: u32,
 }
 
 fn prepare_claude_usage_log(
@@ -327,6 +474,7 @@ fn prepare_claude_usage_log(
         latency_ms: ctx.latency_ms(),
         status_code,
         is_streaming,
+        retry_count: ctx.retry_count,
     })
 }
 
@@ -344,6 +492,7 @@ async fn write_claude_usage_log(state: &ProxyState, log: ClaudeUsageLog) {
         log.is_streaming,
         log.status_code,
         Some(log.session_id),
+        log.retry_count,
     )
     .await;
 }
@@ -436,6 +585,7 @@ async fn handle_claude_transform(
             let status_code = status.as_u16();
             let start_time = ctx.start_time;
             let session_id = ctx.session_id.clone();
+            let retry_count = ctx.retry_count;
             // 用 ctx 的 app_type：Claude Desktop 网关也走此转换路径，硬编码
             // "claude" 会把 claude-desktop 的行错记到 claude 名下
             let app_type_str = ctx.app_type_str;
@@ -471,6 +621,7 @@ async fn handle_claude_transform(
                                 true,
                                 status_code,
                                 Some(session_id),
+                                retry_count,
                             )
                             .await;
                         });
@@ -491,6 +642,7 @@ async fn handle_claude_transform(
             "Claude/OpenRouter",
             usage_collector,
             timeout_config,
+            ctx.route_log_level(),
             connection_guard,
         );
 
@@ -516,7 +668,7 @@ async fn handle_claude_transform(
             std::time::Duration::ZERO
         };
     let (mut response_headers, _status, body_bytes) =
-        read_decoded_body(response, ctx.tag, body_timeout).await?;
+        read_decoded_body(response, ctx, body_timeout).await?;
 
     let body_str = String::from_utf8_lossy(&body_bytes);
 
@@ -722,6 +874,11 @@ pub async fn handle_chat_completions(
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
 
+    let cache_slot = response_cache::prepare(&ctx, &endpoint, &body, is_stream);
+    if let Some(response) = cache_slot.as_ref().and_then(|slot| slot.lookup(ctx.tag)) {
+        return Ok(response);
+    }
+
     let forwarder = ctx.create_forwarder(&state);
     let mut result = match forwarder
         .forward_with_retry(
@@ -732,6 +889,7 @@ pub async fn handle_chat_completions(
             headers,
             extensions,
             ctx.get_providers(),
+            &mut ctx.retry_count,
         )
         .await
     {
@@ -750,14 +908,15 @@ pub async fn handle_chat_completions(
     ctx.provider = result.provider;
     let response = result.response;
 
-    process_response(
+    let response = process_response(
         response,
         &ctx,
         &state,
         &OPENAI_PARSER_CONFIG,
         connection_guard,
     )
-    .await
+    .await?;
+    response_cache::finish(cache_slot, response).await
 }
 
 /// 处理 /v1/responses 请求（OpenAI Responses API - Codex CLI 透传）
@@ -817,6 +976,11 @@ async fn handle_responses_for_app(
     // function-call names (see the namespace-restore dispatch below).
     let namespace_restore_map = transform_codex_responses_namespace::namespace_restore_map(&body);
 
+    let cache_slot = response_cache::prepare(&ctx, &endpoint, &body, is_stream);
+    if let Some(response) = cache_slot.as_ref().and_then(|slot| slot.lookup(tag)) {
+        return Ok(response);
+    }
+
     let forwarder = ctx.create_forwarder(&state);
     let mut result = match forwarder
         .forward_with_retry(
@@ -827,6 +991,7 @@ async fn handle_responses_for_app(
             headers,
             extensions,
             ctx.get_providers(),
+            &mut ctx.retry_count,
         )
         .await
     {
@@ -845,8 +1010,11 @@ async fn handle_responses_for_app(
     ctx.provider = result.provider;
     let response = result.response;
 
-    if super::providers::should_convert_codex_responses_to_anthropic(&ctx.provider, &endpoint) {
-        return handle_codex_anthropic_to_responses_transform(
+    let response = if super::providers::should_convert_codex_responses_to_anthropic(
+        &ctx.provider,
+        &endpoint,
+    ) {
+        handle_codex_anthropic_to_responses_transform(
             response,
             &ctx,
             &state,
@@ -854,11 +1022,9 @@ async fn handle_responses_for_app(
             connection_guard,
             codex_tool_context,
         )
-        .await;
-    }
-
-    if super::providers::should_convert_codex_responses_to_chat(&ctx.provider, &endpoint) {
-        return handle_codex_chat_to_responses_transform(
+        .await?
+    } else if super::providers::should_convert_codex_responses_to_chat(&ctx.provider, &endpoint) {
+        handle_codex_chat_to_responses_transform(
             response,
             &ctx,
             &state,
@@ -866,35 +1032,34 @@ async fn handle_responses_for_app(
             connection_guard,
             codex_tool_context,
         )
-        .await;
-    }
-
-    // Native Responses passthrough to a strict gateway (xAI): the request-side
-    // flatten (in the forwarder) turned Codex `namespace` tools into flat
-    // function tools, so the upstream returns flat function-call names. Restore
-    // them to `{name, namespace}` so the Codex client matches them against its
-    // namespaced tool registry.
-    if super::providers::provider_needs_responses_namespace_flatten(&ctx.provider)
+        .await?
+    } else if super::providers::provider_needs_responses_namespace_flatten(&ctx.provider)
         && !namespace_restore_map.is_empty()
     {
-        return handle_codex_responses_namespace_restore(
+        // Native Responses passthrough to a strict gateway (xAI): the request-side
+        // flatten (in the forwarder) turned Codex `namespace` tools into flat
+        // function tools, so the upstream returns flat function-call names. Restore
+        // them to `{name, namespace}` so the Codex client matches them against its
+        // namespaced tool registry.
+        handle_codex_responses_namespace_restore(
             response,
             &ctx,
             &state,
             connection_guard,
             namespace_restore_map,
         )
-        .await;
-    }
-
-    process_response(
-        response,
-        &ctx,
-        &state,
-        &CODEX_PARSER_CONFIG,
-        connection_guard,
-    )
-    .await
+        .await?
+    } else {
+        process_response(
+            response,
+            &ctx,
+            &state,
+            &CODEX_PARSER_CONFIG,
+            connection_guard,
+        )
+        .await?
+    };
+    response_cache::finish(cache_slot, response).await
 }
 
 /// 处理 /v1/responses/compact 请求（OpenAI Responses Compact API - Codex CLI 透传）
@@ -961,6 +1126,7 @@ async fn handle_responses_compact_for_app(
             headers,
             extensions,
             ctx.get_providers(),
+            &mut ctx.retry_count,
         )
         .await
     {
@@ -1072,6 +1238,7 @@ async fn handle_codex_responses_namespace_restore(
             ctx.tag,
             usage_collector,
             ctx.streaming_timeout_config(),
+            ctx.route_log_level(),
             connection_guard,
         );
 
@@ -1092,7 +1259,7 @@ async fn handle_codex_responses_namespace_restore(
             std::time::Duration::ZERO
         };
     let (mut response_headers, status, body_bytes) =
-        read_decoded_body(response, ctx.tag, body_timeout).await?;
+        read_decoded_body(response, ctx, body_timeout).await?;
     strip_hop_by_hop_response_headers(&mut response_headers);
 
     // Restore names when the body parses as JSON; otherwise pass the bytes
@@ -1124,6 +1291,7 @@ async fn handle_codex_responses_namespace_restore(
                     let state = state.clone();
                     let provider_id = ctx.provider.id.clone();
                     let session_id = ctx.session_id.clone();
+                    let retry_count = ctx.retry_count;
                     let latency_ms = ctx.latency_ms();
                     async move {
                         log_usage(
@@ -1139,6 +1307,7 @@ async fn handle_codex_responses_namespace_restore(
                             false,
                             status.as_u16(),
                             Some(session_id),
+                            retry_count,
                         )
                         .await;
                     }
@@ -1208,6 +1377,7 @@ async fn handle_codex_chat_to_responses_transform(
             let app_type_str = ctx.app_type_str;
             let start_time = ctx.start_time;
             let session_id = ctx.session_id.clone();
+            let retry_count = ctx.retry_count;
 
             Some(SseUsageCollector::new(
                 start_time,
@@ -1251,6 +1421,7 @@ async fn handle_codex_chat_to_responses_transform(
                             true,
                             status.as_u16(),
                             Some(session_id),
+                            retry_count,
                         )
                         .await;
                     });
@@ -1265,6 +1436,7 @@ async fn handle_codex_chat_to_responses_transform(
             ctx.tag,
             usage_collector,
             ctx.streaming_timeout_config(),
+            ctx.route_log_level(),
             connection_guard,
         );
 
@@ -1290,7 +1462,7 @@ async fn handle_codex_chat_to_responses_transform(
             std::time::Duration::ZERO
         };
     let (mut response_headers, status, body_bytes) =
-        read_decoded_body(response, ctx.tag, body_timeout).await?;
+        read_decoded_body(response, ctx, body_timeout).await?;
     let body_str = String::from_utf8_lossy(&body_bytes);
     let chat_response: Value = match serde_json::from_slice(&body_bytes) {
         Ok(value) => value,
@@ -1357,6 +1529,7 @@ async fn handle_codex_chat_to_responses_transform(
             let state = state.clone();
             let provider_id = ctx.provider.id.clone();
             let session_id = ctx.session_id.clone();
+            let retry_count = ctx.retry_count;
             let latency_ms = ctx.latency_ms();
             async move {
                 log_usage(
@@ -1372,6 +1545,7 @@ async fn handle_codex_chat_to_responses_transform(
                     false,
                     status.as_u16(),
                     Some(session_id),
+                    retry_count,
                 )
                 .await;
             }
@@ -1450,7 +1624,7 @@ async fn handle_codex_anthropic_to_responses_transform(
             std::time::Duration::ZERO
         };
     let (mut response_headers, status, body_bytes) =
-        read_decoded_body(response, ctx.tag, body_timeout).await?;
+        read_decoded_body(response, ctx, body_timeout).await?;
     let body_str = String::from_utf8_lossy(&body_bytes);
     let anthropic_response: Value = match serde_json::from_slice(&body_bytes) {
         Ok(value) => value,
@@ -1522,6 +1696,7 @@ async fn handle_codex_anthropic_to_responses_transform(
             let state = state.clone();
             let provider_id = ctx.provider.id.clone();
             let session_id = ctx.session_id.clone();
+            let retry_count = ctx.retry_count;
             let latency_ms = ctx.latency_ms();
             async move {
                 log_usage(
@@ -1537,6 +1712,7 @@ async fn handle_codex_anthropic_to_responses_transform(
                     false,
                     status.as_u16(),
                     Some(session_id),
+                    retry_count,
                 )
                 .await;
             }
@@ -1587,6 +1763,7 @@ fn build_codex_anthropic_sse_response(
         let app_type_str = ctx.app_type_str;
         let start_time = ctx.start_time;
         let session_id = ctx.session_id.clone();
+        let retry_count = ctx.retry_count;
 
         Some(SseUsageCollector::new(
             start_time,
@@ -1624,6 +1801,7 @@ fn build_codex_anthropic_sse_response(
                         true,
                         status.as_u16(),
                         Some(session_id),
+                        retry_count,
                     )
                     .await;
                 });
@@ -1638,6 +1816,7 @@ fn build_codex_anthropic_sse_response(
         ctx.tag,
         usage_collector,
         ctx.streaming_timeout_config(),
+        ctx.route_log_level(),
         connection_guard,
     );
 
@@ -1673,7 +1852,7 @@ async fn handle_codex_chat_error_response(
             std::time::Duration::ZERO
         };
     let (mut response_headers, _status, body_bytes) =
-        read_decoded_body(response, ctx.tag, body_timeout).await?;
+        read_decoded_body(response, ctx, body_timeout).await?;
 
     // 非 JSON 上游错误体（Cloudflare HTML、纯文本 "Unauthorized" 等）若丢成 None，
     // 客户端就看不到原始诊断信息；包成 Value::String 走转换函数的字符串分支。
@@ -1878,11 +2057,14 @@ fn codex_proxy_error_json(
 
 fn codex_proxy_error_code(error: &ProxyError) -> &'static str {
     match error {
-        ProxyError::ForwardFailed(_) => "cc_switch_forward_failed",
-        ProxyError::Timeout(_) | ProxyError::StreamIdleTimeout(_) => "cc_switch_timeout",
+        ProxyError::ForwardFailed(_) | ProxyError::ConnectFailed(_) => "cc_switch_forward_failed",
+        ProxyError::Timeout(_)
+        | ProxyError::ConnectTimeout(_)
+        | ProxyError::StreamIdleTimeout(_) => "cc_switch_timeout",
         ProxyError::NoAvailableProvider => "cc_switch_no_available_provider",
         ProxyError::AllProvidersCircuitOpen => "cc_switch_all_providers_circuit_open",
         ProxyError::NoProvidersConfigured => "cc_switch_no_providers_configured",
+        ProxyError::BudgetExhausted => "cc_switch_budget_exhausted",
         ProxyError::MaxRetriesExceeded => "cc_switch_max_retries_exceeded",
         ProxyError::ProviderUnhealthy(_) => "cc_switch_provider_unhealthy",
         ProxyError::ConfigError(_) => "cc_switch_config_error",
@@ -1959,6 +2141,11 @@ pub async fn handle_gemini(
         .and_then(|v| v.as_bool())
         .unwrap_or(false);
 
+    let cache_slot = response_cache::prepare(&ctx, endpoint, &body, is_stream);
+    if let Some(response) = cache_slot.as_ref().and_then(|slot| slot.lookup(ctx.tag)) {
+        return Ok(response);
+    }
+
     let forwarder = ctx.create_forwarder(&state);
     let mut result = match forwarder
         .forward_with_retry(
@@ -1969,6 +2156,7 @@ pub async fn handle_gemini(
             headers,
             extensions,
             ctx.get_providers(),
+            &mut ctx.retry_count,
         )
         .await
     {
@@ -1987,14 +2175,15 @@ pub async fn handle_gemini(
     ctx.provider = result.provider;
     let response = result.response;
 
-    process_response(
+    let response = process_response(
         response,
         &ctx,
         &state,
         &GEMINI_PARSER_CONFIG,
         connection_guard,
     )
-    .await
+    .await?;
+    response_cache::finish(cache_slot, response).await
 }
 
 fn should_use_claude_transform_streaming(
@@ -2584,12 +2773,14 @@ fn log_forward_error(
         ctx.provider.id.clone(),
         ctx.app_type_str.to_string(),
         ctx.request_model.clone(),
+        ctx.mapped_model(),
         status_code,
         error_message,
         ctx.latency_ms(),
         is_streaming,
         Some(ctx.session_id.clone()),
         None,
+        ctx.retry_count,
     ) {
         log::warn!("记录失败请求日志失败: {e}");
     }
@@ -2613,6 +2804,7 @@ async fn log_usage(
     is_streaming: bool,
     status_code: u16,
     session_id: Option<String>,
+    retry_count: u32,
 ) {
     use super::usage::logger::UsageLogger;
 
@@ -2639,6 +2831,7 @@ async fn log_usage(
         app_type.to_string(),
         model.to_string(),
         request_model.to_string(),
+        outbound_model.to_string(),
         pricing_model.to_string(),
         usage,
         multiplier,
@@ -2648,6 +2841,7 @@ async fn log_usage(
         session_id,
         None, // provider_type
         is_streaming,
+        retry_count,
     ) {
         log::warn!("[USG-001] 记录使用量失败: {e}");
     }
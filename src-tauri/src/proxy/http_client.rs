@@ -1,21 +1,32 @@
 //! 全局 HTTP 客户端模块
 //!
 //! 提供支持全局代理配置的 HTTP 客户端。
-//! 所有需要发送 HTTP 请求的模块都应使用此模块提供的客户端。
+//! 所有需要发送 HTTP 请求的模块都应使用此模块提供的客户端：
+//! 访问供应商端点用 [`get`] / [`get_with_tls`]，其余请求用 [`get_auxiliary`]
+//! （离线模式下统一在此拦截）。
 
 use once_cell::sync::OnceCell;
 use reqwest::Client;
+use std::collections::HashMap;
 use std::env;
 use std::net::IpAddr;
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
 use std::time::Duration;
 
+use crate::provider::ProviderTlsOptions;
+
 /// 全局 HTTP 客户端实例
 static GLOBAL_CLIENT: OnceCell<RwLock<Client>> = OnceCell::new();
 
 /// 当前代理 URL（用于日志和状态查询）
 static CURRENT_PROXY_URL: OnceCell<RwLock<Option<String>>> = OnceCell::new();
 
+/// 供应商级客户端缓存，key 为 (TLS 选项, 连接超时, 代理 URL)
+///
+/// 代理地址变更后旧 key 不再命中，自然按新代理重建。
+type ProviderClientKey = (ProviderTlsOptions, Option<Duration>, Option<String>);
+static PROVIDER_CLIENTS: OnceCell<Mutex<HashMap<ProviderClientKey, Client>>> = OnceCell::new();
+
 /// CC Switch 代理服务器当前监听的端口
 static CC_SWITCH_PROXY_PORT: OnceCell<RwLock<u16>> = OnceCell::new();
 
@@ -35,7 +46,7 @@ pub fn set_proxy_port(port: u16) {
 }
 
 /// 获取 CC Switch 代理服务器的监听端口
-fn get_proxy_port() -> u16 {
+pub(crate) fn get_proxy_port() -> u16 {
     CC_SWITCH_PROXY_PORT
         .get()
         .and_then(|lock| lock.read().ok())
@@ -45,32 +56,35 @@ fn get_proxy_port() -> u16 {
 
 /// 初始化全局 HTTP 客户端
 ///
-/// 应在应用启动时调用一次。
+/// 应在应用启动时调用一次。这里只校验并记录代理地址，真正的 `Client`
+/// （加载 TLS 根证书、探测系统代理等）推迟到首次 [`get`] 时构建，
+/// 不发起网络请求的启动路径无需为此付出开销。
 ///
 /// # Arguments
 /// * `proxy_url` - 代理 URL，如 `http://127.0.0.1:7890` 或 `socks5://127.0.0.1:1080`
 ///   传入 None 或空字符串表示直连
 pub fn init(proxy_url: Option<&str>) -> Result<(), String> {
     let effective_url = proxy_url.filter(|s| !s.trim().is_empty());
-    let client = build_client(effective_url)?;
+    if let Some(url) = effective_url {
+        parse_proxy(url)?;
+    }
 
-    // 尝试初始化全局客户端，如果已存在则记录警告并使用 apply_proxy 更新
-    if GLOBAL_CLIENT.set(RwLock::new(client.clone())).is_err() {
+    // 已初始化则记录警告并使用 apply_proxy 更新
+    if CURRENT_PROXY_URL
+        .set(RwLock::new(effective_url.map(|s| s.to_string())))
+        .is_err()
+    {
         log::warn!(
             "[GlobalProxy] [GP-003] Already initialized, updating instead: {}",
             effective_url
                 .map(mask_url)
                 .unwrap_or_else(|| "direct connection".to_string())
         );
-        // 已初始化，改用 apply_proxy 更新
         return apply_proxy(proxy_url);
     }
 
-    // 初始化代理 URL 记录
-    let _ = CURRENT_PROXY_URL.set(RwLock::new(effective_url.map(|s| s.to_string())));
-
     log::info!(
-        "[GlobalProxy] Initialized: {}",
+        "[GlobalProxy] Initialized (client built on first use): {}",
         effective_url
             .map(mask_url)
             .unwrap_or_else(|| "direct connection".to_string())
@@ -79,6 +93,39 @@ pub fn init(proxy_url: Option<&str>) -> Result<(), String> {
     Ok(())
 }
 
+/// 获取（必要时首次构建）全局客户端；尚未 `init` 时返回 None
+fn global_client() -> Option<&'static RwLock<Client>> {
+    if let Some(lock) = GLOBAL_CLIENT.get() {
+        return Some(lock);
+    }
+    CURRENT_PROXY_URL.get()?;
+
+    Some(GLOBAL_CLIENT.get_or_init(|| {
+        let proxy_url = get_current_proxy_url();
+        let client = build_client(proxy_url.as_deref()).unwrap_or_else(|e| {
+            log::warn!("[GlobalProxy] Failed to build client, falling back to direct: {e}");
+            build_client(None).unwrap_or_default()
+        });
+        log::debug!("[GlobalProxy] Client built on first use");
+        RwLock::new(client)
+    }))
+}
+
+/// 在已初始化状态下替换客户端（客户端尚未构建时直接写入新实例）
+fn replace_client(new_client: Client) -> Result<(), String> {
+    if let Some(lock) = GLOBAL_CLIENT.get() {
+        let mut client = lock.write().map_err(|e| {
+            log::error!("[GlobalProxy] [GP-001] Failed to acquire write lock: {e}");
+            "Failed to update proxy: lock poisoned".to_string()
+        })?;
+        *client = new_client;
+    } else if GLOBAL_CLIENT.set(RwLock::new(new_client.clone())).is_err() {
+        // 与首次 get() 并发构建时，以新配置为准
+        return replace_client(new_client);
+    }
+    Ok(())
+}
+
 /// 验证代理配置（不应用）
 ///
 /// 只验证代理 URL 是否有效，不实际更新全局客户端。
@@ -107,17 +154,11 @@ pub fn apply_proxy(proxy_url: Option<&str>) -> Result<(), String> {
     let effective_url = proxy_url.filter(|s| !s.trim().is_empty());
     let new_client = build_client(effective_url)?;
 
-    // 更新客户端
-    if let Some(lock) = GLOBAL_CLIENT.get() {
-        let mut client = lock.write().map_err(|e| {
-            log::error!("[GlobalProxy] [GP-001] Failed to acquire write lock: {e}");
-            "Failed to update proxy: lock poisoned".to_string()
-        })?;
-        *client = new_client;
-    } else {
-        // 如果还没初始化，则初始化
+    // 如果还没初始化，则初始化；否则更新客户端
+    if CURRENT_PROXY_URL.get().is_none() {
         return init(proxy_url);
     }
+    replace_client(new_client)?;
 
     // 更新代理 URL 记录
     if let Some(lock) = CURRENT_PROXY_URL.get() {
@@ -151,17 +192,11 @@ pub fn update_proxy(proxy_url: Option<&str>) -> Result<(), String> {
     let effective_url = proxy_url.filter(|s| !s.trim().is_empty());
     let new_client = build_client(effective_url)?;
 
-    // 更新客户端
-    if let Some(lock) = GLOBAL_CLIENT.get() {
-        let mut client = lock.write().map_err(|e| {
-            log::error!("[GlobalProxy] [GP-001] Failed to acquire write lock: {e}");
-            "Failed to update proxy: lock poisoned".to_string()
-        })?;
-        *client = new_client;
-    } else {
-        // 如果还没初始化，则初始化
+    // 如果还没初始化，则初始化；否则更新客户端
+    if CURRENT_PROXY_URL.get().is_none() {
         return init(proxy_url);
     }
+    replace_client(new_client)?;
 
     // 更新代理 URL 记录
     if let Some(lock) = CURRENT_PROXY_URL.get() {
@@ -186,8 +221,7 @@ pub fn update_proxy(proxy_url: Option<&str>) -> Result<(), String> {
 ///
 /// 返回配置了代理的客户端（如果已配置代理），否则返回跟随系统代理的客户端。
 pub fn get() -> Client {
-    GLOBAL_CLIENT
-        .get()
+    global_client()
         .and_then(|lock| lock.read().ok())
         .map(|c| c.clone())
         .unwrap_or_else(|| {
@@ -196,6 +230,92 @@ pub fn get() -> Client {
         })
 }
 
+/// 获取用于非供应商请求的 HTTP 客户端（技能/模板市场、版本检查、云同步、webhook 等）
+///
+/// 离线模式下拒绝构建并返回错误：受限网络中只允许访问用户显式配置的供应商端点，
+/// 供应商流量（代理转发、连通性检查、测速、用量查询、OAuth）请使用 [`get`]。
+/// `feature` 仅用于日志与错误信息。
+pub fn get_auxiliary(feature: &str) -> Result<Client, String> {
+    if crate::settings::offline_mode() {
+        log::info!("[GlobalProxy] Offline mode: blocked outbound request for {feature}");
+        return Err(format!(
+            "Offline mode is enabled; outbound request for {feature} is blocked"
+        ));
+    }
+    Ok(get())
+}
+
+/// 获取带供应商级 TLS 选项与连接超时的 HTTP 客户端
+///
+/// 两者都未配置时直接返回全局客户端。否则与全局客户端使用相同的代理配置，
+/// 额外信任 `caCertPath` 中的根证书、在 `insecureSkipVerify` 开启时跳过证书校验、
+/// 覆盖连接超时；按配置缓存，复用连接池（reqwest 的连接超时只能设在客户端上）。
+pub fn get_for_provider(
+    tls: Option<&ProviderTlsOptions>,
+    connect_timeout: Option<Duration>,
+) -> Result<Client, String> {
+    get_for_provider_via(tls, connect_timeout, None)
+}
+
+/// 同 [`get_for_provider`]，`proxy_url` 非空时改走该代理（供应商自带的 HTTPS_PROXY 等）
+pub fn get_for_provider_via(
+    tls: Option<&ProviderTlsOptions>,
+    connect_timeout: Option<Duration>,
+    proxy_url: Option<&str>,
+) -> Result<Client, String> {
+    let tls = tls.cloned().unwrap_or_default();
+    let proxy_override = proxy_url
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(str::to_string);
+    if tls.is_default() && connect_timeout.is_none() && proxy_override.is_none() {
+        return Ok(get());
+    }
+
+    let proxy_url = proxy_override.or_else(get_current_proxy_url);
+    let key = (tls.clone(), connect_timeout, proxy_url.clone());
+    let cache = PROVIDER_CLIENTS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(client) = cache.lock().ok().and_then(|map| map.get(&key).cloned()) {
+        return Ok(client);
+    }
+
+    let mut builder = client_builder(proxy_url.as_deref())?;
+    if let Some(timeout) = connect_timeout {
+        builder = builder.connect_timeout(timeout);
+    }
+    if let Some(path) = tls.ca_cert_path() {
+        for cert in load_ca_certificates(path)? {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    if tls.skips_verification() {
+        log::warn!("[GlobalProxy] 供应商已开启跳过 TLS 证书校验，连接可能被中间人劫持");
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    let client = builder
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {e}"))?;
+
+    if let Ok(mut map) = cache.lock() {
+        map.insert(key, client.clone());
+    }
+    Ok(client)
+}
+
+/// 读取自定义根证书：优先按 PEM 证书链解析，失败再按单个 DER 证书解析
+fn load_ca_certificates(path: &str) -> Result<Vec<reqwest::Certificate>, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Failed to read CA certificate '{path}': {e}"))?;
+    if let Ok(certs) = reqwest::Certificate::from_pem_bundle(&bytes) {
+        if !certs.is_empty() {
+            return Ok(certs);
+        }
+    }
+    reqwest::Certificate::from_der(&bytes)
+        .map(|cert| vec![cert])
+        .map_err(|e| format!("Invalid CA certificate '{path}': {e}"))
+}
+
 /// 获取当前代理 URL
 ///
 /// 返回当前配置的代理 URL，None 表示直连。
@@ -212,8 +332,32 @@ pub fn is_proxy_enabled() -> bool {
     get_current_proxy_url().is_some()
 }
 
+/// 校验代理 URL 格式与 scheme（不构建客户端，开销很小）
+fn parse_proxy(url: &str) -> Result<reqwest::Proxy, String> {
+    let parsed = url::Url::parse(url)
+        .map_err(|e| format!("Invalid proxy URL '{}': {}", mask_url(url), e))?;
+
+    let scheme = parsed.scheme();
+    if !["http", "https", "socks5", "socks5h"].contains(&scheme) {
+        return Err(format!(
+            "Invalid proxy scheme '{}' in URL '{}'. Supported: http, https, socks5, socks5h",
+            scheme,
+            mask_url(url)
+        ));
+    }
+
+    reqwest::Proxy::all(url).map_err(|e| format!("Invalid proxy URL '{}': {}", mask_url(url), e))
+}
+
 /// 构建 HTTP 客户端
 fn build_client(proxy_url: Option<&str>) -> Result<Client, String> {
+    client_builder(proxy_url)?
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {e}"))
+}
+
+/// 全局客户端与供应商级 TLS 客户端共用的基础配置
+fn client_builder(proxy_url: Option<&str>) -> Result<reqwest::ClientBuilder, String> {
     let mut builder = Client::builder()
         .timeout(Duration::from_secs(600))
         .connect_timeout(Duration::from_secs(30))
@@ -224,26 +368,13 @@ fn build_client(proxy_url: Option<&str>) -> Result<Client, String> {
         .no_gzip()
         .no_brotli()
         .no_deflate()
-        .no_zstd();
+        .no_zstd()
+        // 按设置中的 IPv4/IPv6 偏好重排解析结果，避免 IPv6 不通时长时间等待
+        .dns_resolver(std::sync::Arc::new(super::dial::PreferenceResolver));
 
     // 有代理地址则使用代理，否则跟随系统代理
     if let Some(url) = proxy_url {
-        // 先验证 URL 格式和 scheme
-        let parsed = url::Url::parse(url)
-            .map_err(|e| format!("Invalid proxy URL '{}': {}", mask_url(url), e))?;
-
-        let scheme = parsed.scheme();
-        if !["http", "https", "socks5", "socks5h"].contains(&scheme) {
-            return Err(format!(
-                "Invalid proxy scheme '{}' in URL '{}'. Supported: http, https, socks5, socks5h",
-                scheme,
-                mask_url(url)
-            ));
-        }
-
-        let proxy = reqwest::Proxy::all(url)
-            .map_err(|e| format!("Invalid proxy URL '{}': {}", mask_url(url), e))?;
-        builder = builder.proxy(proxy);
+        builder = builder.proxy(parse_proxy(url)?);
         log::debug!("[GlobalProxy] Proxy configured: {}", mask_url(url));
     } else {
         // 未设置全局代理时，让 reqwest 自动检测系统代理（环境变量）
@@ -258,9 +389,7 @@ fn build_client(proxy_url: Option<&str>) -> Result<Client, String> {
         }
     }
 
-    builder
-        .build()
-        .map_err(|e| format!("Failed to build HTTP client: {e}"))
+    Ok(builder)
 }
 
 fn system_proxy_points_to_loopback() -> bool {
@@ -447,4 +576,29 @@ mod tests {
             std::env::remove_var(key);
         }
     }
+
+    #[test]
+    fn custom_ca_rejects_missing_or_invalid_files() {
+        assert!(load_ca_certificates("/nonexistent/cc-switch-ca.pem").is_err());
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("ca.pem");
+        std::fs::write(&path, "not a certificate").expect("write");
+        assert!(load_ca_certificates(path.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn default_tls_options_are_ignored() {
+        let tls = ProviderTlsOptions {
+            ca_cert_path: Some("  ".to_string()),
+            insecure_skip_verify: Some(false),
+        };
+        assert!(tls.is_default());
+        let tls = ProviderTlsOptions {
+            insecure_skip_verify: Some(true),
+            ..Default::default()
+        };
+        assert!(!tls.is_default());
+        assert!(get_with_tls(&tls).is_ok());
+    }
 }
@@ -244,6 +244,10 @@ impl ProxyResponse {
 ///
 /// `log_display` is a caller-supplied, already-sanitized string used only for
 /// logging; this layer never derives a log value from the raw `uri`.
+///
+/// `connect_timeout`: optional per-provider limit for establishing the TCP
+/// connection (or proxy tunnel) on the raw write path. The shared hyper-util
+/// fallback client keeps its own connector settings.
 #[allow(clippy::too_many_arguments)]
 pub async fn send_request(
     uri: http::Uri,
@@ -253,6 +257,7 @@ pub async fn send_request(
     original_extensions: http::Extensions,
     body: Vec<u8>,
     timeout: std::time::Duration,
+    connect_timeout: Option<std::time::Duration>,
     proxy_url: Option<&str>,
 ) -> Result<ProxyResponse, ProxyError> {
     // Extract our own OriginalHeaderCases if available
@@ -277,7 +282,15 @@ pub async fn send_request(
         // Primary path: use raw write + hyper handshake for exact header casing
         let result = tokio::time::timeout(
             timeout,
-            send_raw_request(&uri, &method, &headers, original_cases, &body, proxy_url),
+            send_raw_request(
+                &uri,
+                &method,
+                &headers,
+                original_cases,
+                &body,
+                connect_timeout,
+                proxy_url,
+            ),
         )
         .await
         .map_err(|_| ProxyError::Timeout(format!("请求超时: {}s", timeout.as_secs())))?;
@@ -289,6 +302,11 @@ pub async fn send_request(
                     // Don't bypass configured proxy with direct connect fallback
                     return Err(e);
                 }
+                if matches!(e, ProxyError::ConnectTimeout(_)) {
+                    // Connect timeout: retrying through the fallback client would
+                    // defeat the provider's fail-fast setting
+                    return Err(e);
+                }
                 log::warn!("[HyperClient] Raw write failed, falling back to hyper-util: {e}");
                 // Fall through to hyper-util Client
             }
@@ -309,11 +327,32 @@ pub async fn send_request(
     let resp = tokio::time::timeout(timeout, client.request(req))
         .await
         .map_err(|_| ProxyError::Timeout(format!("请求超时: {}s", timeout.as_secs())))?
-        .map_err(|e| ProxyError::ForwardFailed(format!("上游请求失败: {e}")))?;
+        .map_err(|e| {
+            if e.is_connect() {
+                ProxyError::ConnectFailed(e.to_string())
+            } else {
+                ProxyError::ForwardFailed(format!("上游请求失败: {e}"))
+            }
+        })?;
 
     Ok(ProxyResponse::Hyper(resp))
 }
 
+/// Send a WebSocket upgrade handshake through the shared direct client.
+///
+/// On a `101 Switching Protocols` response the caller takes the upgraded
+/// connection with `hyper::upgrade::on`. Upstream HTTP/SOCKS proxies are not
+/// used for upgrades.
+pub(crate) async fn send_upgrade(
+    req: http::Request<http_body_util::Full<Bytes>>,
+    timeout: std::time::Duration,
+) -> Result<hyper::Response<hyper::body::Incoming>, ProxyError> {
+    tokio::time::timeout(timeout, global_hyper_client().request(req))
+        .await
+        .map_err(|_| ProxyError::Timeout(format!("握手超时: {}s", timeout.as_secs())))?
+        .map_err(|e| ProxyError::ForwardFailed(format!("上游握手失败: {e}")))
+}
+
 /// TCP or TLS stream returned by `connect_via_proxy`.
 ///
 /// When the proxy URL uses `https://`, the connection to the proxy itself is
@@ -381,6 +420,7 @@ async fn send_raw_request(
     headers: &http::HeaderMap,
     original_cases: &OriginalHeaderCases,
     body: &[u8],
+    connect_timeout: Option<std::time::Duration>,
     proxy_url: Option<&str>,
 ) -> Result<ProxyResponse, ProxyError> {
     use tokio::io::AsyncWriteExt;
@@ -398,24 +438,31 @@ async fn send_raw_request(
     let raw = build_raw_request(method, path_and_query, headers, original_cases, body);
 
     // Establish TCP connection — either direct or through HTTP CONNECT proxy
-    let stream = if let Some(proxy) = proxy_url {
-        connect_via_proxy(proxy, host, port).await?
-    } else {
-        ProxyStream::Tcp(
-            tokio::net::TcpStream::connect((host, port))
+    let connect = async {
+        if let Some(proxy) = proxy_url {
+            connect_via_proxy(proxy, host, port).await
+        } else {
+            super::dial::connect_tcp(host, port)
                 .await
-                .map_err(|e| ProxyError::ForwardFailed(format!("TCP connect failed: {e}")))?,
-        )
+                .map(ProxyStream::Tcp)
+                .map_err(|e| ProxyError::ConnectFailed(format!("TCP connect failed: {e}")))
+        }
+    };
+    let stream = match connect_timeout {
+        Some(limit) => tokio::time::timeout(limit, connect)
+            .await
+            .map_err(|_| ProxyError::ConnectTimeout(format!("{}s", limit.as_secs())))??,
+        None => connect.await?,
     };
 
     if scheme == "https" {
         let tls_connector = global_tls_connector();
         let server_name = rustls::pki_types::ServerName::try_from(host.to_string())
-            .map_err(|e| ProxyError::ForwardFailed(format!("Invalid server name: {e}")))?;
+            .map_err(|e| ProxyError::ConnectFailed(format!("Invalid server name: {e}")))?;
         let mut tls_stream = tls_connector
             .connect(server_name, stream)
             .await
-            .map_err(|e| ProxyError::ForwardFailed(format!("TLS handshake failed: {e}")))?;
+            .map_err(|e| ProxyError::ConnectFailed(format!("TLS handshake failed: {e}")))?;
 
         tls_stream
             .write_all(&raw)
@@ -459,11 +506,11 @@ async fn connect_via_proxy(
     use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 
     let parsed = url::Url::parse(proxy_url)
-        .map_err(|e| ProxyError::ForwardFailed(format!("Invalid proxy URL: {e}")))?;
+        .map_err(|e| ProxyError::ConnectFailed(format!("Invalid proxy URL: {e}")))?;
 
     let proxy_host = parsed
         .host_str()
-        .ok_or_else(|| ProxyError::ForwardFailed("Proxy URL has no host".into()))?;
+        .ok_or_else(|| ProxyError::ConnectFailed("Proxy URL has no host".into()))?;
     let proxy_port = parsed
         .port()
         .unwrap_or(if parsed.scheme() == "https" { 443 } else { 80 });
@@ -479,19 +526,19 @@ async fn connect_via_proxy(
     };
 
     // Connect to the proxy
-    let tcp = tokio::net::TcpStream::connect((proxy_host, proxy_port))
+    let tcp = super::dial::connect_tcp(proxy_host, proxy_port)
         .await
-        .map_err(|e| ProxyError::ForwardFailed(format!("Proxy TCP connect failed: {e}")))?;
+        .map_err(|e| ProxyError::ConnectFailed(format!("Proxy TCP connect failed: {e}")))?;
 
     // Wrap with TLS if the proxy URL uses https://
     let mut stream: ProxyStream = if parsed.scheme() == "https" {
         let tls_connector = global_tls_connector();
         let server_name = rustls::pki_types::ServerName::try_from(proxy_host.to_string())
-            .map_err(|e| ProxyError::ForwardFailed(format!("Invalid proxy server name: {e}")))?;
+            .map_err(|e| ProxyError::ConnectFailed(format!("Invalid proxy server name: {e}")))?;
         let tls_stream = tls_connector
             .connect(server_name, tcp)
             .await
-            .map_err(|e| ProxyError::ForwardFailed(format!("Proxy TLS handshake failed: {e}")))?;
+            .map_err(|e| ProxyError::ConnectFailed(format!("Proxy TLS handshake failed: {e}")))?;
         ProxyStream::Tls(Box::new(tls_stream))
     } else {
         ProxyStream::Tcp(tcp)
@@ -510,11 +557,11 @@ async fn connect_via_proxy(
     stream
         .write_all(connect_req.as_bytes())
         .await
-        .map_err(|e| ProxyError::ForwardFailed(format!("CONNECT write failed: {e}")))?;
+        .map_err(|e| ProxyError::ConnectFailed(format!("CONNECT write failed: {e}")))?;
     stream
         .flush()
         .await
-        .map_err(|e| ProxyError::ForwardFailed(format!("CONNECT flush failed: {e}")))?;
+        .map_err(|e| ProxyError::ConnectFailed(format!("CONNECT flush failed: {e}")))?;
 
     // Read the proxy's response status line
     let mut reader = BufReader::new(&mut stream);
@@ -522,7 +569,7 @@ async fn connect_via_proxy(
     reader
         .read_line(&mut status_line)
         .await
-        .map_err(|e| ProxyError::ForwardFailed(format!("CONNECT read failed: {e}")))?;
+        .map_err(|e| ProxyError::ConnectFailed(format!("CONNECT read failed: {e}")))?;
 
     // Expect "HTTP/1.1 200 ..." or "HTTP/1.0 200 ..."
     if !status_line.contains(" 200 ") {
@@ -532,7 +579,7 @@ async fn connect_via_proxy(
                 status_line.trim()
             )));
         }
-        return Err(ProxyError::ForwardFailed(format!(
+        return Err(ProxyError::ConnectFailed(format!(
             "Proxy CONNECT rejected: {}",
             status_line.trim()
         )));
@@ -544,7 +591,7 @@ async fn connect_via_proxy(
         reader
             .read_line(&mut line)
             .await
-            .map_err(|e| ProxyError::ForwardFailed(format!("CONNECT header read: {e}")))?;
+            .map_err(|e| ProxyError::ConnectFailed(format!("CONNECT header read: {e}")))?;
         if line.trim().is_empty() {
             break;
         }
@@ -566,7 +613,7 @@ async fn connect_via_proxy(
 /// Loads both webpki roots AND native system certificates so that
 /// proxy MITM CAs (e.g. Clash, mitmproxy) installed in the system
 /// keychain are trusted through the CONNECT tunnel.
-fn global_tls_connector() -> &'static tokio_rustls::TlsConnector {
+pub(crate) fn global_tls_connector() -> &'static tokio_rustls::TlsConnector {
     static CONNECTOR: OnceLock<tokio_rustls::TlsConnector> = OnceLock::new();
     CONNECTOR.get_or_init(|| {
         let mut root_store = rustls::RootCertStore::empty();
@@ -28,6 +28,8 @@ pub mod srv {
     pub const TASK_ERROR: &str = "SRV-004";
     pub const ACCEPT_ERR: &str = "SRV-005";
     pub const CONN_ERR: &str = "SRV-006";
+    pub const DRAINING: &str = "SRV-007";
+    pub const DRAIN_TIMEOUT: &str = "SRV-008";
 }
 
 /// 转发器日志码
@@ -35,6 +37,7 @@ pub mod fwd {
     pub const PROVIDER_FAILED_RETRY: &str = "FWD-001";
     pub const ALL_PROVIDERS_FAILED: &str = "FWD-002";
     pub const SINGLE_PROVIDER_FAILED: &str = "FWD-003";
+    pub const TRANSIENT_RETRY: &str = "FWD-004";
 }
 
 /// 故障转移日志码
@@ -0,0 +1,290 @@
+//! 代理运行指标（`/healthz` 与 Prometheus `/metrics`）
+//!
+//! 以客户端请求为粒度统计：每个请求在故障转移结束后，按最终处理它的供应商记一次
+//! 成功/失败与耗时（到上游响应头返回为止，不含流式 body 的传输时间）。
+//! 指标只保存在内存中，代理重启后清零——Prometheus 的 counter 语义允许重置。
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::circuit_breaker::CircuitState;
+use super::types::ActiveTarget;
+
+/// 耗时直方图的桶上界（秒）
+const LATENCY_BUCKETS: [f64; 10] = [0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0];
+
+#[derive(Debug, Default, Clone)]
+struct ProviderMetrics {
+    provider_name: String,
+    success: u64,
+    errors: u64,
+    /// 与 `LATENCY_BUCKETS` 一一对应的非累计计数，渲染时再累加
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    latency_sum_secs: f64,
+}
+
+impl ProviderMetrics {
+    fn count(&self) -> u64 {
+        self.success + self.errors
+    }
+}
+
+/// 渲染时才需要的瞬时状态（由 ProxyState 提供）
+#[derive(Debug, Default)]
+pub struct MetricsGauges {
+    pub uptime_seconds: u64,
+    pub active_connections: usize,
+    pub failover_count: u64,
+    pub active_targets: Vec<ActiveTarget>,
+    /// (app_type, provider_id, 熔断器状态)
+    pub circuits: Vec<(String, String, CircuitState)>,
+}
+
+/// 按 (app_type, provider_id) 聚合的请求指标
+#[derive(Debug, Default)]
+pub struct ProxyMetrics {
+    providers: Mutex<BTreeMap<(String, String), ProviderMetrics>>,
+}
+
+impl ProxyMetrics {
+    pub fn record(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+        provider_name: &str,
+        success: bool,
+        latency: Duration,
+    ) {
+        let mut providers = self.providers.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = providers
+            .entry((app_type.to_string(), provider_id.to_string()))
+            .or_default();
+        entry.provider_name = provider_name.to_string();
+        if success {
+            entry.success += 1;
+        } else {
+            entry.errors += 1;
+        }
+        let secs = latency.as_secs_f64();
+        entry.latency_sum_secs += secs;
+        if let Some(index) = LATENCY_BUCKETS.iter().position(|upper| secs <= *upper) {
+            entry.buckets[index] += 1;
+        }
+    }
+
+    /// 以 Prometheus 文本格式（0.0.4）渲染全部指标
+    pub fn render_prometheus(&self, gauges: &MetricsGauges) -> String {
+        let providers = self
+            .providers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        let mut out = String::new();
+
+        write_header(
+            &mut out,
+            "cc_switch_proxy_up",
+            "gauge",
+            "Whether the cc-switch proxy is running",
+        );
+        let _ = writeln!(out, "cc_switch_proxy_up 1");
+        write_header(
+            &mut out,
+            "cc_switch_proxy_uptime_seconds",
+            "gauge",
+            "Seconds since the proxy started",
+        );
+        let _ = writeln!(
+            out,
+            "cc_switch_proxy_uptime_seconds {}",
+            gauges.uptime_seconds
+        );
+        write_header(
+            &mut out,
+            "cc_switch_proxy_active_connections",
+            "gauge",
+            "Client requests currently in flight",
+        );
+        let _ = writeln!(
+            out,
+            "cc_switch_proxy_active_connections {}",
+            gauges.active_connections
+        );
+        write_header(
+            &mut out,
+            "cc_switch_proxy_failovers_total",
+            "counter",
+            "Number of failovers to another provider",
+        );
+        let _ = writeln!(
+            out,
+            "cc_switch_proxy_failovers_total {}",
+            gauges.failover_count
+        );
+        let cache = super::response_cache::stats();
+        write_header(
+            &mut out,
+            "cc_switch_proxy_response_cache_total",
+            "counter",
+            "Response cache lookups for non-streaming requests",
+        );
+        let _ = writeln!(
+            out,
+            "cc_switch_proxy_response_cache_total{{result=\"hit\"}} {}",
+            cache.hits
+        );
+        let _ = writeln!(
+            out,
+            "cc_switch_proxy_response_cache_total{{result=\"miss\"}} {}",
+            cache.misses
+        );
+
+        write_header(
+            &mut out,
+            "cc_switch_proxy_requests_total",
+            "counter",
+            "Client requests by the provider that finally served them",
+        );
+        for ((app, id), metrics) in &providers {
+            let labels = provider_labels(app, id, &metrics.provider_name);
+            let _ = writeln!(
+                out,
+                "cc_switch_proxy_requests_total{{{labels},outcome=\"success\"}} {}",
+                metrics.success
+            );
+            let _ = writeln!(
+                out,
+                "cc_switch_proxy_requests_total{{{labels},outcome=\"error\"}} {}",
+                metrics.errors
+            );
+        }
+
+        write_header(
+            &mut out,
+            "cc_switch_proxy_request_duration_seconds",
+            "histogram",
+            "Time until upstream response headers were received",
+        );
+        for ((app, id), metrics) in &providers {
+            let labels = provider_labels(app, id, &metrics.provider_name);
+            let mut cumulative = 0;
+            for (upper, count) in LATENCY_BUCKETS.iter().zip(metrics.buckets) {
+                cumulative += count;
+                let _ = writeln!(
+                    out,
+                    "cc_switch_proxy_request_duration_seconds_bucket{{{labels},le=\"{upper}\"}} {cumulative}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "cc_switch_proxy_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}",
+                metrics.count()
+            );
+            let _ = writeln!(
+                out,
+                "cc_switch_proxy_request_duration_seconds_sum{{{labels}}} {}",
+                metrics.latency_sum_secs
+            );
+            let _ = writeln!(
+                out,
+                "cc_switch_proxy_request_duration_seconds_count{{{labels}}} {}",
+                metrics.count()
+            );
+        }
+
+        write_header(
+            &mut out,
+            "cc_switch_proxy_active_provider",
+            "gauge",
+            "Current target provider per app (always 1)",
+        );
+        for target in &gauges.active_targets {
+            let labels =
+                provider_labels(&target.app_type, &target.provider_id, &target.provider_name);
+            let _ = writeln!(out, "cc_switch_proxy_active_provider{{{labels}}} 1");
+        }
+
+        write_header(
+            &mut out,
+            "cc_switch_proxy_circuit_state",
+            "gauge",
+            "Circuit breaker state: 0=closed, 1=half-open, 2=open",
+        );
+        for (app, id, state) in &gauges.circuits {
+            let value = match state {
+                CircuitState::Closed => 0,
+                CircuitState::HalfOpen => 1,
+                CircuitState::Open => 2,
+            };
+            let _ = writeln!(
+                out,
+                "cc_switch_proxy_circuit_state{{app=\"{}\",provider_id=\"{}\"}} {value}",
+                escape_label(app),
+                escape_label(id)
+            );
+        }
+
+        out
+    }
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn provider_labels(app: &str, id: &str, name: &str) -> String {
+    format!(
+        "app=\"{}\",provider_id=\"{}\",provider=\"{}\"",
+        escape_label(app),
+        escape_label(id),
+        escape_label(name)
+    )
+}
+
+/// Prometheus 标签值转义：反斜杠、双引号、换行
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_counters_and_cumulative_histogram() {
+        let metrics = ProxyMetrics::default();
+        metrics.record(
+            "claude",
+            "p1",
+            "Relay \"A\"",
+            true,
+            Duration::from_millis(80),
+        );
+        metrics.record("claude", "p1", "Relay \"A\"", false, Duration::from_secs(3));
+        let text = metrics.render_prometheus(&MetricsGauges {
+            circuits: vec![("claude".into(), "p1".into(), CircuitState::Open)],
+            ..Default::default()
+        });
+
+        let labels = r#"app="claude",provider_id="p1",provider="Relay \"A\"""#;
+        assert!(text.contains(&format!(
+            "cc_switch_proxy_requests_total{{{labels},outcome=\"error\"}} 1"
+        )));
+        assert!(text.contains(&format!(
+            "cc_switch_proxy_request_duration_seconds_bucket{{{labels},le=\"0.1\"}} 1"
+        )));
+        assert!(text.contains(&format!(
+            "cc_switch_proxy_request_duration_seconds_bucket{{{labels},le=\"5\"}} 2"
+        )));
+        assert!(text.contains(&format!(
+            "cc_switch_proxy_request_duration_seconds_count{{{labels}}} 2"
+        )));
+        assert!(text.contains(r#"cc_switch_proxy_circuit_state{app="claude",provider_id="p1"} 2"#));
+    }
+}
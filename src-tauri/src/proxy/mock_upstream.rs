@@ -0,0 +1,592 @@
+//! 内置模拟上游（`mock://` 供应商）
+//!
+//! base_url 以 `mock://` 开头的供应商不会发出网络请求，而是由这里按上游协议
+//! （Anthropic Messages / OpenAI Chat Completions / OpenAI Responses / Gemini）
+//! 直接生成应答。应答经过与真实上游相同的格式转换、故障转移与用量统计流程，
+//! 便于在不消耗 token 的情况下演示和调试这些功能。
+//!
+//! 行为由 `meta.mock` 配置：回复内容（回显 / 固定 / 轮流）、模拟延迟与失败率。
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderValue, StatusCode};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+use super::hyper_client::ProxyResponse;
+use crate::provider::{MockProviderOptions, Provider};
+
+pub const MOCK_URL_SCHEME: &str = "mock://";
+
+/// 轮流回复的进度，按供应商 ID 记录
+static SCRIPT_CURSORS: Lazy<Mutex<HashMap<String, usize>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn is_mock_url(url: &str) -> bool {
+    url.trim_start()
+        .get(..MOCK_URL_SCHEME.len())
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case(MOCK_URL_SCHEME))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+    Anthropic,
+    OpenAiChat,
+    OpenAiResponses,
+    Gemini,
+}
+
+impl Protocol {
+    fn detect(url: &str) -> Self {
+        let path = url.split('?').next().unwrap_or(url);
+        if path.contains("generateContent") {
+            Self::Gemini
+        } else if path.ends_with("/chat/completions") {
+            Self::OpenAiChat
+        } else if path.ends_with("/responses") {
+            Self::OpenAiResponses
+        } else {
+            Self::Anthropic
+        }
+    }
+}
+
+/// 生成模拟应答
+pub async fn respond(
+    provider: &Provider,
+    url: &str,
+    body: &Value,
+    streaming: bool,
+) -> ProxyResponse {
+    let options = provider
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.mock.clone())
+        .unwrap_or_default();
+
+    if let Some(latency) = options.latency_ms.filter(|ms| *ms > 0) {
+        tokio::time::sleep(std::time::Duration::from_millis(latency)).await;
+    }
+
+    if should_fail(options.failure_rate) {
+        let status = options
+            .failure_status
+            .and_then(|code| StatusCode::from_u16(code).ok())
+            .filter(|status| !status.is_success())
+            .unwrap_or(StatusCode::SERVICE_UNAVAILABLE);
+        log::info!(
+            "[Mock] provider={} 模拟失败: HTTP {}",
+            provider.id,
+            status.as_u16()
+        );
+        let error = json!({
+            "type": "error",
+            "error": {
+                "type": "mock_failure",
+                "message": "Simulated failure from the built-in mock provider"
+            }
+        });
+        return json_response(status, &error);
+    }
+
+    let protocol = Protocol::detect(url);
+    let text = reply_text(&provider.id, &options, protocol, body);
+    let model = body
+        .get("model")
+        .and_then(Value::as_str)
+        .filter(|model| !model.is_empty())
+        .unwrap_or("mock-model");
+    let usage = Usage {
+        input: estimate_tokens(&body.to_string()),
+        output: estimate_tokens(&text),
+    };
+    log::debug!(
+        "[Mock] provider={} protocol={protocol:?} streaming={streaming} output_tokens={}",
+        provider.id,
+        usage.output
+    );
+
+    if streaming {
+        let events = match protocol {
+            Protocol::Anthropic => anthropic_events(model, &text, usage),
+            Protocol::OpenAiChat => openai_chat_events(model, &text, usage),
+            Protocol::OpenAiResponses => responses_events(model, &text, usage),
+            Protocol::Gemini => gemini_events(model, &text, usage),
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("text/event-stream"),
+        );
+        let stream = futures::stream::iter(events.into_iter().map(|event| Ok(Bytes::from(event))));
+        return ProxyResponse::streamed(StatusCode::OK, headers, stream);
+    }
+
+    let response = match protocol {
+        Protocol::Anthropic => anthropic_message(model, &text, usage),
+        Protocol::OpenAiChat => openai_chat_completion(model, &text, usage),
+        Protocol::OpenAiResponses => responses_object(model, &text, usage),
+        Protocol::Gemini => gemini_response(model, &text, usage),
+    };
+    json_response(StatusCode::OK, &response)
+}
+
+fn should_fail(rate: Option<f64>) -> bool {
+    let rate = rate.unwrap_or(0.0);
+    if rate.is_nan() || rate <= 0.0 {
+        return false;
+    }
+    let roll = (uuid::Uuid::new_v4().as_u128() % 10_000) as f64 / 10_000.0;
+    roll < rate
+}
+
+fn json_response(status: StatusCode, value: &Value) -> ProxyResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    ProxyResponse::buffered(status, headers, Bytes::from(value.to_string()))
+}
+
+fn reply_text(
+    provider_id: &str,
+    options: &MockProviderOptions,
+    protocol: Protocol,
+    body: &Value,
+) -> String {
+    match options.replies.as_slice() {
+        [] => match last_user_text(protocol, body) {
+            Some(text) => format!("Echo: {text}"),
+            None => "Echo: (empty)".to_string(),
+        },
+        [reply] => reply.clone(),
+        replies => {
+            let mut cursors = SCRIPT_CURSORS.lock().unwrap_or_else(|e| e.into_inner());
+            let cursor = cursors.entry(provider_id.to_string()).or_insert(0);
+            let reply = replies[*cursor % replies.len()].clone();
+            *cursor = cursor.wrapping_add(1);
+            reply
+        }
+    }
+}
+
+/// 提取最后一条用户消息中的文本
+fn last_user_text(protocol: Protocol, body: &Value) -> Option<String> {
+    let messages = match protocol {
+        Protocol::Gemini => body.get("contents"),
+        Protocol::OpenAiResponses => match body.get("input") {
+            Some(Value::String(text)) => return Some(text.clone()),
+            input => input,
+        },
+        Protocol::Anthropic | Protocol::OpenAiChat => body.get("messages"),
+    };
+    let message = messages?
+        .as_array()?
+        .iter()
+        .rev()
+        .find(|message| message.get("role").and_then(Value::as_str) == Some("user"))?;
+    let content = message.get("content").or_else(|| message.get("parts"))?;
+    let mut parts = Vec::new();
+    collect_text(content, &mut parts);
+    let text = parts.join("\n");
+    (!text.trim().is_empty()).then_some(text)
+}
+
+fn collect_text(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::String(text) => out.push(text.clone()),
+        Value::Array(items) => items.iter().for_each(|item| collect_text(item, out)),
+        Value::Object(map) => {
+            if let Some(Value::String(text)) = map.get("text") {
+                out.push(text.clone());
+            }
+        }
+        _ => {}
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Usage {
+    input: u64,
+    output: u64,
+}
+
+/// 粗略估算 token 数（约 4 字符 / token），仅用于演示用量统计
+fn estimate_tokens(text: &str) -> u64 {
+    (text.chars().count() as u64).div_ceil(4).max(1)
+}
+
+/// 把回复拆成若干增量，模拟逐段输出
+fn chunks(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars
+        .chunks(16)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+fn response_id(prefix: &str) -> String {
+    format!("{prefix}_mock_{}", uuid::Uuid::new_v4().simple())
+}
+
+fn sse(event: Option<&str>, data: &Value) -> String {
+    match event {
+        Some(event) => format!("event: {event}\ndata: {data}\n\n"),
+        None => format!("data: {data}\n\n"),
+    }
+}
+
+fn anthropic_message(model: &str, text: &str, usage: Usage) -> Value {
+    json!({
+        "id": response_id("msg"),
+        "type": "message",
+        "role": "assistant",
+        "model": model,
+        "content": [{"type": "text", "text": text}],
+        "stop_reason": "end_turn",
+        "stop_sequence": null,
+        "usage": {"input_tokens": usage.input, "output_tokens": usage.output}
+    })
+}
+
+fn anthropic_events(model: &str, text: &str, usage: Usage) -> Vec<String> {
+    let mut events = vec![
+        sse(
+            Some("message_start"),
+            &json!({
+                "type": "message_start",
+                "message": {
+                    "id": response_id("msg"),
+                    "type": "message",
+                    "role": "assistant",
+                    "model": model,
+                    "content": [],
+                    "stop_reason": null,
+                    "stop_sequence": null,
+                    "usage": {"input_tokens": usage.input, "output_tokens": 1}
+                }
+            }),
+        ),
+        sse(
+            Some("content_block_start"),
+            &json!({
+                "type": "content_block_start",
+                "index": 0,
+                "content_block": {"type": "text", "text": ""}
+            }),
+        ),
+    ];
+    events.extend(chunks(text).into_iter().map(|chunk| {
+        sse(
+            Some("content_block_delta"),
+            &json!({
+                "type": "content_block_delta",
+                "index": 0,
+                "delta": {"type": "text_delta", "text": chunk}
+            }),
+        )
+    }));
+    events.push(sse(
+        Some("content_block_stop"),
+        &json!({"type": "content_block_stop", "index": 0}),
+    ));
+    events.push(sse(
+        Some("message_delta"),
+        &json!({
+            "type": "message_delta",
+            "delta": {"stop_reason": "end_turn", "stop_sequence": null},
+            "usage": {"input_tokens": usage.input, "output_tokens": usage.output}
+        }),
+    ));
+    events.push(sse(Some("message_stop"), &json!({"type": "message_stop"})));
+    events
+}
+
+fn openai_usage(usage: Usage) -> Value {
+    json!({
+        "prompt_tokens": usage.input,
+        "completion_tokens": usage.output,
+        "total_tokens": usage.input + usage.output
+    })
+}
+
+fn openai_chat_completion(model: &str, text: &str, usage: Usage) -> Value {
+    json!({
+        "id": response_id("chatcmpl"),
+        "object": "chat.completion",
+        "created": chrono::Utc::now().timestamp(),
+        "model": model,
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": text},
+            "finish_reason": "stop"
+        }],
+        "usage": openai_usage(usage)
+    })
+}
+
+fn openai_chat_events(model: &str, text: &str, usage: Usage) -> Vec<String> {
+    let id = response_id("chatcmpl");
+    let created = chrono::Utc::now().timestamp();
+    let chunk = |choices: Value, usage: Option<Value>| {
+        let mut value = json!({
+            "id": id,
+            "object": "chat.completion.chunk",
+            "created": created,
+            "model": model,
+            "choices": choices
+        });
+        if let Some(usage) = usage {
+            value["usage"] = usage;
+        }
+        sse(None, &value)
+    };
+
+    let mut events: Vec<String> = chunks(text)
+        .into_iter()
+        .enumerate()
+        .map(|(index, content)| {
+            let delta = if index == 0 {
+                json!({"role": "assistant", "content": content})
+            } else {
+                json!({"content": content})
+            };
+            chunk(
+                json!([{"index": 0, "delta": delta, "finish_reason": null}]),
+                None,
+            )
+        })
+        .collect();
+    events.push(chunk(
+        json!([{"index": 0, "delta": {}, "finish_reason": "stop"}]),
+        None,
+    ));
+    events.push(chunk(json!([]), Some(openai_usage(usage))));
+    events.push("data: [DONE]\n\n".to_string());
+    events
+}
+
+fn responses_object(model: &str, text: &str, usage: Usage) -> Value {
+    json!({
+        "id": response_id("resp"),
+        "object": "response",
+        "created_at": chrono::Utc::now().timestamp(),
+        "status": "completed",
+        "model": model,
+        "output": [responses_message(text)],
+        "usage": {
+            "input_tokens": usage.input,
+            "output_tokens": usage.output,
+            "total_tokens": usage.input + usage.output
+        }
+    })
+}
+
+fn responses_message(text: &str) -> Value {
+    json!({
+        "type": "message",
+        "id": "msg_mock_0",
+        "status": "completed",
+        "role": "assistant",
+        "content": [{"type": "output_text", "text": text, "annotations": []}]
+    })
+}
+
+fn responses_events(model: &str, text: &str, usage: Usage) -> Vec<String> {
+    let completed = responses_object(model, text, usage);
+    let mut created = completed.clone();
+    created["status"] = json!("in_progress");
+    created["output"] = json!([]);
+    created["usage"] = Value::Null;
+
+    let mut events = vec![
+        sse(
+            Some("response.created"),
+            &json!({"type": "response.created", "response": created}),
+        ),
+        sse(
+            Some("response.output_item.added"),
+            &json!({
+                "type": "response.output_item.added",
+                "output_index": 0,
+                "item": {
+                    "type": "message",
+                    "id": "msg_mock_0",
+                    "status": "in_progress",
+                    "role": "assistant",
+                    "content": []
+                }
+            }),
+        ),
+    ];
+    events.extend(chunks(text).into_iter().map(|delta| {
+        sse(
+            Some("response.output_text.delta"),
+            &json!({
+                "type": "response.output_text.delta",
+                "item_id": "msg_mock_0",
+                "output_index": 0,
+                "content_index": 0,
+                "delta": delta
+            }),
+        )
+    }));
+    events.push(sse(
+        Some("response.output_item.done"),
+        &json!({
+            "type": "response.output_item.done",
+            "output_index": 0,
+            "item": responses_message(text)
+        }),
+    ));
+    events.push(sse(
+        Some("response.completed"),
+        &json!({"type": "response.completed", "response": completed}),
+    ));
+    events
+}
+
+fn gemini_chunk(model: &str, text: &str, finish: bool, usage: Usage) -> Value {
+    let mut candidate = json!({
+        "content": {"role": "model", "parts": [{"text": text}]},
+        "index": 0
+    });
+    if finish {
+        candidate["finishReason"] = json!("STOP");
+    }
+    json!({
+        "candidates": [candidate],
+        "usageMetadata": {
+            "promptTokenCount": usage.input,
+            "candidatesTokenCount": usage.output,
+            "totalTokenCount": usage.input + usage.output
+        },
+        "modelVersion": model
+    })
+}
+
+fn gemini_response(model: &str, text: &str, usage: Usage) -> Value {
+    gemini_chunk(model, text, true, usage)
+}
+
+fn gemini_events(model: &str, text: &str, usage: Usage) -> Vec<String> {
+    let parts = chunks(text);
+    let last = parts.len() - 1;
+    parts
+        .into_iter()
+        .enumerate()
+        .map(|(index, part)| sse(None, &gemini_chunk(model, &part, index == last, usage)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_provider(options: MockProviderOptions) -> Provider {
+        let mut provider = Provider::with_id(
+            "mock".to_string(),
+            "Mock".to_string(),
+            json!({"env": {"ANTHROPIC_BASE_URL": "mock://echo"}}),
+            None,
+        );
+        provider.meta = Some(crate::provider::ProviderMeta {
+            mock: Some(options),
+            ..Default::default()
+        });
+        provider
+    }
+
+    #[test]
+    fn detects_mock_urls_and_protocols() {
+        assert!(is_mock_url("mock://echo"));
+        assert!(is_mock_url("MOCK://demo/v1"));
+        assert!(!is_mock_url("https://mock.example.com"));
+
+        assert_eq!(
+            Protocol::detect("mock://x/v1/messages?beta=true"),
+            Protocol::Anthropic
+        );
+        assert_eq!(
+            Protocol::detect("mock://x/v1/chat/completions"),
+            Protocol::OpenAiChat
+        );
+        assert_eq!(
+            Protocol::detect("mock://x/v1/responses"),
+            Protocol::OpenAiResponses
+        );
+        assert_eq!(
+            Protocol::detect("mock://x/v1beta/models/gemini-2.5-pro:streamGenerateContent?alt=sse"),
+            Protocol::Gemini
+        );
+    }
+
+    #[tokio::test]
+    async fn echoes_last_user_message_with_usage() {
+        let provider = mock_provider(MockProviderOptions::default());
+        let body = json!({
+            "model": "claude-sonnet-4",
+            "messages": [
+                {"role": "user", "content": "first"},
+                {"role": "assistant", "content": "ok"},
+                {"role": "user", "content": [{"type": "text", "text": "hello mock"}]}
+            ]
+        });
+        let response = respond(&provider, "mock://echo/v1/messages", &body, false).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let value: Value = serde_json::from_slice(&response.bytes().await.unwrap()).unwrap();
+        assert_eq!(value["content"][0]["text"], "Echo: hello mock");
+        assert_eq!(value["model"], "claude-sonnet-4");
+        assert!(value["usage"]["input_tokens"].as_u64().unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn cycles_scripted_replies_and_simulates_failures() {
+        let provider = mock_provider(MockProviderOptions {
+            replies: vec!["one".to_string(), "two".to_string()],
+            ..Default::default()
+        });
+        let body = json!({"model": "gpt-5", "messages": []});
+        let mut texts = Vec::new();
+        for _ in 0..3 {
+            let response = respond(&provider, "mock://s/v1/chat/completions", &body, false).await;
+            let value: Value = serde_json::from_slice(&response.bytes().await.unwrap()).unwrap();
+            texts.push(
+                value["choices"][0]["message"]["content"]
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+            );
+        }
+        assert_eq!(texts, ["one", "two", "one"]);
+
+        let failing = mock_provider(MockProviderOptions {
+            failure_rate: Some(1.0),
+            failure_status: Some(429),
+            ..Default::default()
+        });
+        let response = respond(&failing, "mock://f/v1/messages", &body, true).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn streams_protocol_specific_events() {
+        let provider = mock_provider(MockProviderOptions {
+            replies: vec!["streamed reply".to_string()],
+            ..Default::default()
+        });
+        let body = json!({"model": "gpt-5", "input": "hi"});
+        let response = respond(&provider, "mock://s/v1/responses", &body, true).await;
+        assert!(response.is_sse());
+        let text = String::from_utf8(response.bytes().await.unwrap().to_vec()).unwrap();
+        assert!(text.starts_with("event: response.created\n"));
+        assert!(text.contains("\"delta\":\"streamed reply\""));
+        assert!(text.contains("event: response.completed\n"));
+    }
+}
@@ -2,11 +2,17 @@
 //!
 //! 提供本地HTTP代理服务，支持多Provider故障转移和请求透传
 
+pub mod auth;
+pub mod bench;
 pub mod body_filter;
 pub mod cache_injector;
 pub mod circuit_breaker;
+pub mod claude_endpoints;
 pub(crate) mod content_encoding;
 pub mod copilot_optimizer;
+pub mod custom_auth;
+pub mod dial;
+pub mod drain;
 pub mod error;
 pub mod error_mapper;
 pub(crate) mod failover_switch;
@@ -21,21 +27,29 @@ pub mod hyper_client;
 pub(crate) mod json_canonical;
 pub mod log_codes;
 pub mod media_sanitizer;
+pub mod metrics;
+pub mod mock_upstream;
 pub mod model_mapper;
 pub mod provider_router;
 pub mod providers;
+pub mod response_cache;
 pub mod response_handler;
 pub mod response_processor;
+pub mod route_logging;
 pub(crate) mod server;
 pub mod session;
+pub mod session_affinity;
 pub(crate) mod sse;
 pub(crate) mod switch_lock;
+pub(crate) mod text_repair;
 pub mod thinking_budget_rectifier;
 pub mod thinking_optimizer;
 pub mod thinking_rectifier;
 pub(crate) mod tool_media;
 pub(crate) mod types;
 pub mod usage;
+pub mod warm_pool;
+pub(crate) mod websocket;
 
 // 公开导出给外部使用（commands, services等模块需要）
 #[allow(unused_imports)]
@@ -43,7 +57,8 @@ pub use circuit_breaker::{
     CircuitBreaker, CircuitBreakerConfig, CircuitBreakerStats, CircuitState,
 };
 #[allow(unused_imports)]
-pub use error::ProxyError;
+pub use error::{HttpErrorClass, ProxyError};
+pub(crate) use forwarder::is_protected_local_proxy_override_header;
 #[allow(unused_imports)]
 pub use provider_router::ProviderRouter;
 #[allow(unused_imports)]
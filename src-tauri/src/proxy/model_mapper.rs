@@ -4,6 +4,7 @@
 
 use crate::claude_desktop_config::ONE_M_CONTEXT_MARKER;
 use crate::provider::Provider;
+use crate::services::model_alias;
 use serde_json::Value;
 
 /// 模型映射配置
@@ -17,41 +18,23 @@ pub struct ModelMapping {
 }
 
 impl ModelMapping {
-    /// 从 Provider 配置中提取模型映射
+    /// 从 Provider 配置中提取模型映射（配置中的模型别名解析为真实模型名）
     pub fn from_provider(provider: &Provider) -> Self {
         let env = provider.settings_config.get("env");
-
-        Self {
-            haiku_model: env
-                .and_then(|e| e.get("ANTHROPIC_DEFAULT_HAIKU_MODEL"))
-                .and_then(|v| v.as_str())
-                .filter(|s| !s.is_empty())
-                .map(String::from),
-            sonnet_model: env
-                .and_then(|e| e.get("ANTHROPIC_DEFAULT_SONNET_MODEL"))
+        let model = |key: &str| {
+            env.and_then(|e| e.get(key))
                 .and_then(|v| v.as_str())
                 .filter(|s| !s.is_empty())
-                .map(String::from),
-            opus_model: env
-                .and_then(|e| e.get("ANTHROPIC_DEFAULT_OPUS_MODEL"))
-                .and_then(|v| v.as_str())
-                .filter(|s| !s.is_empty())
-                .map(String::from),
-            fable_model: env
-                .and_then(|e| e.get("ANTHROPIC_DEFAULT_FABLE_MODEL"))
-                .and_then(|v| v.as_str())
-                .filter(|s| !s.is_empty())
-                .map(String::from),
-            subagent_model: env
-                .and_then(|e| e.get("CLAUDE_CODE_SUBAGENT_MODEL"))
-                .and_then(|v| v.as_str())
-                .filter(|s| !s.is_empty())
-                .map(String::from),
-            default_model: env
-                .and_then(|e| e.get("ANTHROPIC_MODEL"))
-                .and_then(|v| v.as_str())
-                .filter(|s| !s.is_empty())
-                .map(String::from),
+                .map(model_alias::resolve)
+        };
+
+        Self {
+            haiku_model: model("ANTHROPIC_DEFAULT_HAIKU_MODEL"),
+            sonnet_model: model("ANTHROPIC_DEFAULT_SONNET_MODEL"),
+            opus_model: model("ANTHROPIC_DEFAULT_OPUS_MODEL"),
+            fable_model: model("ANTHROPIC_DEFAULT_FABLE_MODEL"),
+            subagent_model: model("CLAUDE_CODE_SUBAGENT_MODEL"),
+            default_model: model("ANTHROPIC_MODEL"),
         }
     }
 
@@ -113,6 +96,18 @@ impl ModelMapping {
     }
 }
 
+/// 请求模型名经别名解析与供应商模型映射后的结果
+pub fn map_model_name(original: &str, provider: &Provider) -> String {
+    // 请求中的模型别名先解析为真实模型名，再按供应商配置映射
+    let resolved = model_alias::resolve(original);
+    let mapping = ModelMapping::from_provider(provider);
+    if mapping.has_mapping() {
+        mapping.map_model(&resolved)
+    } else {
+        resolved
+    }
+}
+
 /// 对请求体应用模型映射
 ///
 /// 返回 (映射后的请求体, 原始模型名, 映射后模型名)
@@ -120,28 +115,19 @@ pub fn apply_model_mapping(
     mut body: Value,
     provider: &Provider,
 ) -> (Value, Option<String>, Option<String>) {
-    let mapping = ModelMapping::from_provider(provider);
-
-    // 如果没有配置映射，直接返回
-    if !mapping.has_mapping() {
-        let original = body.get("model").and_then(|m| m.as_str()).map(String::from);
-        return (body, original, None);
-    }
-
     // 提取原始模型名
-    let original_model = body.get("model").and_then(|m| m.as_str()).map(String::from);
-
-    if let Some(ref original) = original_model {
-        let mapped = mapping.map_model(original);
+    let Some(original) = body.get("model").and_then(|m| m.as_str()).map(String::from) else {
+        return (body, None, None);
+    };
 
-        if mapped != *original {
-            log::debug!("[ModelMapper] 模型映射: {original} → {mapped}");
-            body["model"] = serde_json::json!(mapped);
-            return (body, Some(original.clone()), Some(mapped));
-        }
+    let mapped = map_model_name(&original, provider);
+    if mapped != original {
+        log::debug!("[ModelMapper] 模型映射: {original} → {mapped}");
+        body["model"] = serde_json::json!(mapped);
+        return (body, Some(original), Some(mapped));
     }
 
-    (body, original_model, None)
+    (body, Some(original), None)
 }
 
 /// Claude Code 通过 `[1M]` 后缀声明 100 万上下文能力；上游 API
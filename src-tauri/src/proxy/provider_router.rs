@@ -6,7 +6,12 @@ use crate::app_config::AppType;
 use crate::database::Database;
 use crate::error::AppError;
 use crate::provider::Provider;
-use crate::proxy::circuit_breaker::{AllowResult, CircuitBreaker, CircuitBreakerConfig};
+use crate::proxy::circuit_breaker::{
+    AllowResult, CircuitBreaker, CircuitBreakerConfig, CircuitState,
+};
+use crate::proxy::session_affinity::SessionAffinity;
+use crate::services::notifier::{localized_text, Notification, NotifierService};
+use crate::settings::NotificationEvent;
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -18,6 +23,8 @@ pub struct ProviderRouter {
     db: Arc<Database>,
     /// 熔断器管理器 - key 格式: "app_type:provider_id"
     circuit_breakers: Arc<RwLock<HashMap<String, Arc<CircuitBreaker>>>>,
+    /// 会话亲和绑定（prompt caching 需要同一会话命中同一供应商）
+    session_affinity: Arc<SessionAffinity>,
 }
 
 impl ProviderRouter {
@@ -26,6 +33,42 @@ impl ProviderRouter {
         Self {
             db,
             circuit_breakers: Arc::new(RwLock::new(HashMap::new())),
+            session_affinity: Arc::new(SessionAffinity::new()),
+        }
+    }
+
+    /// 按会话选择供应商：在 `select_providers` 的结果上，把会话绑定的供应商提到最前
+    ///
+    /// 绑定的供应商已熔断或不在队列中时保持原顺序。`session_id` 为 None
+    /// （客户端未提供会话）或设置中关闭了会话亲和时等同于 `select_providers`。
+    pub async fn select_providers_for_session(
+        &self,
+        app_type: &str,
+        session_id: Option<&str>,
+    ) -> Result<Vec<Provider>, AppError> {
+        let mut providers = self.select_providers(app_type).await?;
+        if let (Some(session_id), Some(ttl)) =
+            (session_id, crate::settings::proxy_session_affinity_ttl())
+        {
+            if providers.len() > 1
+                && self
+                    .session_affinity
+                    .prefer(app_type, session_id, ttl, &mut providers)
+            {
+                log::debug!(
+                    "[{app_type}] 会话亲和命中: session={session_id}, provider={}",
+                    providers[0].id
+                );
+            }
+        }
+        Ok(providers)
+    }
+
+    /// 记录会话最近一次成功使用的供应商
+    pub fn bind_session(&self, app_type: &str, session_id: &str, provider_id: &str) {
+        if let Some(ttl) = crate::settings::proxy_session_affinity_ttl() {
+            self.session_affinity
+                .bind(app_type, session_id, provider_id, ttl);
         }
     }
 
@@ -34,6 +77,7 @@ impl ProviderRouter {
     /// 返回按优先级排序的可用供应商列表：
     /// - 故障转移关闭时：仅返回当前供应商
     /// - 故障转移开启时：仅使用故障转移队列，按队列顺序依次尝试（P1 → P2 → ...）
+    /// - 接管由供应商级代理覆盖发起时：队列中仅保留开启了 `useLocalProxy` 的供应商
     pub async fn select_providers(&self, app_type: &str) -> Result<Vec<Provider>, AppError> {
         let mut result = Vec::new();
         let mut total_providers = 0usize;
@@ -62,10 +106,24 @@ impl ProviderRouter {
 
             total_providers = ordered_ids.len();
 
+            // 接管由供应商级代理覆盖发起时，未开启覆盖的供应商应直连上游，不参与代理路由
+            let override_only = self
+                .db
+                .is_provider_proxy_override_active(app_type)
+                .unwrap_or(false);
+
             for provider_id in ordered_ids {
                 let Some(provider) = all_providers.get(&provider_id).cloned() else {
                     continue;
                 };
+                if override_only
+                    && !provider
+                        .meta
+                        .as_ref()
+                        .is_some_and(|meta| meta.uses_local_proxy())
+                {
+                    continue;
+                }
 
                 let circuit_key = format!("{app_type}:{}", provider.id);
                 let breaker = self.get_or_create_circuit_breaker(&circuit_key).await;
@@ -105,6 +163,14 @@ impl ProviderRouter {
             }
         }
 
+        // 钥匙串引用在转发前解析；解析失败的供应商保留引用，请求会以鉴权失败告终并触发故障转移
+        for provider in &mut result {
+            match crate::services::secrets::resolve_settings(&provider.settings_config) {
+                Ok(settings) => provider.settings_config = settings,
+                Err(e) => log::warn!("[{app_type}] 供应商 {} 密钥解析失败: {e}", provider.id),
+            }
+        }
+
         Ok(result)
     }
 
@@ -131,6 +197,14 @@ impl ProviderRouter {
         success: bool,
         error_msg: Option<String>,
     ) -> Result<(), AppError> {
+        // 维护时段内的失败是预期内的，不计入熔断器与健康度
+        if !success && self.is_in_maintenance(provider_id, app_type) {
+            log::debug!("[{app_type}] 供应商 {provider_id} 处于维护时段，忽略本次失败");
+            self.release_permit_neutral(provider_id, app_type, used_half_open_permit)
+                .await;
+            return Ok(());
+        }
+
         // 1. 按应用独立获取熔断器配置
         let failure_threshold = match self.db.get_proxy_config_for_app(app_type).await {
             Ok(app_config) => app_config.circuit_failure_threshold,
@@ -144,7 +218,11 @@ impl ProviderRouter {
         if success {
             breaker.record_success(used_half_open_permit).await;
         } else {
+            let was_open = breaker.get_state().await == CircuitState::Open;
             breaker.record_failure(used_half_open_permit).await;
+            if !was_open && breaker.get_state().await == CircuitState::Open {
+                self.notify_provider_down(provider_id, app_type, error_msg.as_deref());
+            }
         }
 
         // 3. 更新数据库健康状态（使用配置的阈值）
@@ -161,6 +239,37 @@ impl ProviderRouter {
         Ok(())
     }
 
+    /// 熔断器刚打开时推送"供应商不可用"通知
+    fn notify_provider_down(&self, provider_id: &str, app_type: &str, error: Option<&str>) {
+        let name = self
+            .db
+            .get_provider_by_id(provider_id, app_type)
+            .ok()
+            .flatten()
+            .map(|provider| provider.name)
+            .unwrap_or_else(|| provider_id.to_string());
+        let error = error.unwrap_or_default();
+        let notification = Notification::new(
+            NotificationEvent::ProviderDown,
+            localized_text("供应商不可用", "Provider down"),
+            localized_text(
+                format!("{name}（{app_type}）已熔断，请求将转交故障转移队列。{error}"),
+                format!("{name} ({app_type}) tripped its circuit breaker; requests fail over to the queue. {error}"),
+            ),
+        )
+        .with_dedup_key(format!("{app_type}:{provider_id}"));
+        NotifierService::dispatch(notification);
+    }
+
+    fn is_in_maintenance(&self, provider_id: &str, app_type: &str) -> bool {
+        match self.db.get_provider_by_id(provider_id, app_type) {
+            Ok(Some(provider)) => {
+                crate::services::maintenance::is_in_maintenance(&provider, chrono::Utc::now())
+            }
+            _ => false,
+        }
+    }
+
     /// 重置熔断器（手动恢复）
     pub async fn reset_circuit_breaker(&self, circuit_key: &str) {
         let breakers = self.circuit_breakers.read().await;
@@ -212,6 +321,29 @@ impl ProviderRouter {
         }
     }
 
+    /// 列出所有已创建熔断器的状态：(app_type, provider_id, state)
+    pub async fn circuit_states(&self) -> Vec<(String, String, CircuitState)> {
+        let breakers: Vec<(String, Arc<CircuitBreaker>)> = self
+            .circuit_breakers
+            .read()
+            .await
+            .iter()
+            .map(|(key, breaker)| (key.clone(), breaker.clone()))
+            .collect();
+        let mut states = Vec::with_capacity(breakers.len());
+        for (key, breaker) in breakers {
+            if let Some((app_type, provider_id)) = key.split_once(':') {
+                states.push((
+                    app_type.to_string(),
+                    provider_id.to_string(),
+                    breaker.get_stats().await.state,
+                ));
+            }
+        }
+        states.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        states
+    }
+
     /// 获取熔断器状态
     #[allow(dead_code)]
     pub async fn get_circuit_breaker_stats(
@@ -395,6 +527,51 @@ mod tests {
         assert_eq!(providers[1].id, "a");
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_reordered_queue_drives_routing_and_keeps_other_positions() {
+        let _home = TempHome::new();
+        let db = Arc::new(Database::memory().unwrap());
+
+        for (id, sort_index) in [("a", 0), ("c", 1), ("b", 2)] {
+            let mut provider =
+                Provider::with_id(id.to_string(), id.to_uppercase(), json!({}), None);
+            provider.sort_index = Some(sort_index);
+            db.save_provider("claude", &provider).unwrap();
+        }
+        db.add_to_failover_queue("claude", "a").unwrap();
+        db.add_to_failover_queue("claude", "b").unwrap();
+
+        let mut config = db.get_proxy_config_for_app("claude").await.unwrap();
+        config.auto_failover_enabled = true;
+        db.update_proxy_config_for_app(config).await.unwrap();
+
+        let queue = crate::services::failover_queue::FailoverQueueService::reorder(
+            &db,
+            &crate::app_config::AppType::Claude,
+            &["b".to_string(), "a".to_string()],
+        )
+        .unwrap();
+        let ids: Vec<&str> = queue.iter().map(|item| item.provider_id.as_str()).collect();
+        assert_eq!(ids, ["b", "a"]);
+
+        // 非队列成员 c 保持在原位置
+        let all = db.get_all_providers("claude").unwrap();
+        assert_eq!(all["b"].sort_index, Some(0));
+        assert_eq!(all["c"].sort_index, Some(1));
+        assert_eq!(all["a"].sort_index, Some(2));
+
+        let router = ProviderRouter::new(db.clone());
+        let providers = router.select_providers("claude").await.unwrap();
+        let routed: Vec<&str> = providers.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(routed, ["b", "a"]);
+
+        // 新顺序必须恰好覆盖队列成员
+        assert!(db
+            .reorder_failover_queue("claude", &["b".to_string(), "c".to_string()])
+            .is_err());
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_failover_enabled_uses_queue_only_even_if_current_not_in_queue() {
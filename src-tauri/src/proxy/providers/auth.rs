@@ -137,6 +137,19 @@ pub enum AuthStrategy {
     XaiOAuth,
 }
 
+impl AuthStrategy {
+    /// 凭据来自 OAuth 流程而非用户填写的 API Key（不适用自定义鉴权方式）
+    pub fn is_oauth(self) -> bool {
+        matches!(
+            self,
+            AuthStrategy::GoogleOAuth
+                | AuthStrategy::GitHubCopilot
+                | AuthStrategy::CodexOAuth
+                | AuthStrategy::XaiOAuth
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
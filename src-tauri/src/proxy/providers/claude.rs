@@ -58,6 +58,10 @@ pub fn get_claude_api_format(provider: &Provider) -> &'static str {
                 _ => "anthropic",
             };
         }
+        // 本地运行时未显式选择格式时走 OpenAI Chat（各运行时都兼容）
+        if meta.provider_type.as_deref() == Some(super::local::LOCAL_PROVIDER_TYPE) {
+            return "openai_chat";
+        }
     }
 
     // 2) Backward compatibility: legacy settings_config.api_format
@@ -501,6 +505,11 @@ impl ClaudeAdapter {
             return ProviderType::GitHubCopilot;
         }
 
+        // 检测本地模型运行时（Ollama / LM Studio / llama.cpp）
+        if super::local::is_local_provider(provider) {
+            return ProviderType::Local;
+        }
+
         // 检测 OpenRouter
         if self.is_openrouter(provider) {
             return ProviderType::OpenRouter;
@@ -712,6 +721,13 @@ impl ProviderAdapter for ClaudeAdapter {
             return Ok(super::XAI_API_BASE_URL.to_string());
         }
 
+        // 本地运行时：兼容直接粘贴的 Ollama 原生地址，未填写时默认 Ollama
+        if super::local::is_local_provider(provider) {
+            return Ok(super::local::configured_base_url(provider)
+                .map(|url| super::local::normalize_base_url(&url))
+                .unwrap_or_else(|| super::local::OLLAMA_DEFAULT_BASE_URL.to_string()));
+        }
+
         // 1. 从 env 中获取
         if let Some(env) = provider.settings_config.get("env") {
             if let Some(url) = env.get("ANTHROPIC_BASE_URL").and_then(|v| v.as_str()) {
@@ -778,6 +794,12 @@ impl ProviderAdapter for ClaudeAdapter {
             ));
         }
 
+        // 本地运行时默认无鉴权：返回 None 时转发层不发送任何鉴权头
+        if provider_type == ProviderType::Local {
+            return super::local::configured_key(provider)
+                .map(|key| AuthInfo::new(key, AuthStrategy::Bearer));
+        }
+
         let key = self.extract_key(provider)?;
 
         match provider_type {
@@ -915,53 +937,7 @@ impl ProviderAdapter for ClaudeAdapter {
             AuthStrategy::XaiOAuth => {
                 vec![(HeaderName::from_static("authorization"), hv(&bearer)?)]
             }
-            AuthStrategy::GitHubCopilot => {
-                // 生成请求追踪 ID
-                let request_id = uuid::Uuid::new_v4().to_string();
-                vec![
-                    (HeaderName::from_static("authorization"), hv(&bearer)?),
-                    (
-                        HeaderName::from_static("editor-version"),
-                        HeaderValue::from_static(super::copilot_auth::COPILOT_EDITOR_VERSION),
-                    ),
-                    (
-                        HeaderName::from_static("editor-plugin-version"),
-                        HeaderValue::from_static(super::copilot_auth::COPILOT_PLUGIN_VERSION),
-                    ),
-                    (
-                        HeaderName::from_static("copilot-integration-id"),
-                        HeaderValue::from_static(super::copilot_auth::COPILOT_INTEGRATION_ID),
-                    ),
-                    (
-                        HeaderName::from_static("user-agent"),
-                        HeaderValue::from_static(super::copilot_auth::COPILOT_USER_AGENT),
-                    ),
-                    (
-                        HeaderName::from_static("x-github-api-version"),
-                        HeaderValue::from_static(super::copilot_auth::COPILOT_API_VERSION),
-                    ),
-                    // 26-04-01新增的copilot关键 headers
-                    (
-                        HeaderName::from_static("openai-intent"),
-                        HeaderValue::from_static("conversation-agent"),
-                    ),
-                    (
-                        HeaderName::from_static("x-initiator"),
-                        HeaderValue::from_static("user"),
-                    ),
-                    (
-                        HeaderName::from_static("x-interaction-type"),
-                        HeaderValue::from_static("conversation-agent"),
-                    ),
-                    // x-interaction-id 由 forwarder 按需注入（仅在有 session 时）
-                    (
-                        HeaderName::from_static("x-vscode-user-agent-library-version"),
-                        HeaderValue::from_static("electron-fetch"),
-                    ),
-                    (HeaderName::from_static("x-request-id"), hv(&request_id)?),
-                    (HeaderName::from_static("x-agent-task-id"), hv(&request_id)?),
-                ]
-            }
+            AuthStrategy::GitHubCopilot => copilot_auth_headers(&bearer)?,
         })
     }
 
@@ -1020,6 +996,61 @@ impl ProviderAdapter for ClaudeAdapter {
     }
 }
 
+/// GitHub Copilot 请求头（Claude 与 Codex 经 Copilot 转发时共用）
+///
+/// `bearer` 为完整的 `Bearer <copilot token>`。
+pub(crate) fn copilot_auth_headers(
+    bearer: &str,
+) -> Result<Vec<(http::HeaderName, http::HeaderValue)>, ProxyError> {
+    use super::adapter::auth_header_value as hv;
+    use http::{HeaderName, HeaderValue};
+    // 生成请求追踪 ID
+    let request_id = uuid::Uuid::new_v4().to_string();
+    Ok(vec![
+        (HeaderName::from_static("authorization"), hv(bearer)?),
+        (
+            HeaderName::from_static("editor-version"),
+            HeaderValue::from_static(super::copilot_auth::COPILOT_EDITOR_VERSION),
+        ),
+        (
+            HeaderName::from_static("editor-plugin-version"),
+            HeaderValue::from_static(super::copilot_auth::COPILOT_PLUGIN_VERSION),
+        ),
+        (
+            HeaderName::from_static("copilot-integration-id"),
+            HeaderValue::from_static(super::copilot_auth::COPILOT_INTEGRATION_ID),
+        ),
+        (
+            HeaderName::from_static("user-agent"),
+            HeaderValue::from_static(super::copilot_auth::COPILOT_USER_AGENT),
+        ),
+        (
+            HeaderName::from_static("x-github-api-version"),
+            HeaderValue::from_static(super::copilot_auth::COPILOT_API_VERSION),
+        ),
+        // 26-04-01新增的copilot关键 headers
+        (
+            HeaderName::from_static("openai-intent"),
+            HeaderValue::from_static("conversation-agent"),
+        ),
+        (
+            HeaderName::from_static("x-initiator"),
+            HeaderValue::from_static("user"),
+        ),
+        (
+            HeaderName::from_static("x-interaction-type"),
+            HeaderValue::from_static("conversation-agent"),
+        ),
+        // x-interaction-id 由 forwarder 按需注入（仅在有 session 时）
+        (
+            HeaderName::from_static("x-vscode-user-agent-library-version"),
+            HeaderValue::from_static("electron-fetch"),
+        ),
+        (HeaderName::from_static("x-request-id"), hv(&request_id)?),
+        (HeaderName::from_static("x-agent-task-id"), hv(&request_id)?),
+    ])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
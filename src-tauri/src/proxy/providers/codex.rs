@@ -739,6 +739,20 @@ impl ProviderAdapter for CodexAdapter {
             ));
         }
 
+        // GitHub Copilot (auto-provisioned Codex providers): the real Copilot token is
+        // resolved per-request by the forwarder via CopilotAuthManager.
+        if provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.provider_type.as_deref())
+            == Some("github_copilot")
+        {
+            return Some(AuthInfo::new(
+                "copilot_placeholder".to_string(),
+                AuthStrategy::GitHubCopilot,
+            ));
+        }
+
         // Anthropic upstream: the auth field is chosen by the user in the UI (meta.apiKeyField).
         //   ANTHROPIC_API_KEY    → x-api-key (AuthStrategy::Anthropic)
         //   ANTHROPIC_AUTH_TOKEN → Authorization: Bearer (default, AuthStrategy::Bearer)
@@ -801,6 +815,9 @@ impl ProviderAdapter for CodexAdapter {
     ) -> Result<Vec<(http::HeaderName, http::HeaderValue)>, ProxyError> {
         use super::adapter::auth_header_value;
         let bearer = format!("Bearer {}", auth.api_key);
+        if auth.strategy == AuthStrategy::GitHubCopilot {
+            return super::claude::copilot_auth_headers(&bearer);
+        }
         // Anthropic gateway: send only x-api-key (anthropic-version is filled in by
         // the forwarder). Mutually exclusive with Bearer to avoid a 401 from the
         // gateway receiving both auth headers at once.
@@ -1575,6 +1592,30 @@ wire_api = "responses"
         assert_eq!(auth.strategy, AuthStrategy::XaiOAuth);
     }
 
+    #[test]
+    fn github_copilot_codex_provider_uses_copilot_auth() {
+        let adapter = CodexAdapter::new();
+        let mut provider = create_provider(json!({
+            "auth": { "OPENAI_API_KEY": "" },
+            "config": "model = \"gpt-5\"",
+        }));
+        provider.meta = Some(crate::provider::ProviderMeta {
+            provider_type: Some("github_copilot".to_string()),
+            ..Default::default()
+        });
+
+        let auth = adapter
+            .extract_auth(&provider)
+            .expect("copilot placeholder");
+        assert_eq!(auth.strategy, AuthStrategy::GitHubCopilot);
+        let headers = adapter
+            .get_auth_headers(&AuthInfo::new("tid".into(), AuthStrategy::GitHubCopilot))
+            .unwrap();
+        assert!(headers
+            .iter()
+            .any(|(name, _)| name.as_str() == "copilot-integration-id"));
+    }
+
     #[test]
     fn xai_oauth_pins_native_responses_catalog_profile() {
         let mut provider = create_provider(json!({ "auth": {}, "config": "" }));
@@ -0,0 +1,174 @@
+//! 本地模型运行时（Ollama / LM Studio / llama.cpp server）
+//!
+//! 这些运行时都提供 OpenAI 兼容的 `/v1/chat/completions`，Claude Code 经由现有的
+//! Anthropic ↔ OpenAI Chat 转换链即可使用本地模型。与云端供应商的差异：
+//!
+//! - 不需要鉴权：未配置 API Key 时不发送任何鉴权头（配置了则按 Bearer 发送，
+//!   兼容 `llama-server --api-key` 与 LM Studio 的鉴权开关）；
+//! - 用户常直接粘贴 Ollama 原生地址（`http://localhost:11434/api` 或 `.../api/chat`），
+//!   这里统一改写为服务根地址，再由转发层拼接 `/v1/chat/completions`；
+//! - 流式差异（Ollama 的 tool call 增量不带 `index` 等）在 `streaming.rs` 中兼容。
+//!
+//! 识别规则：`meta.providerType = "local"`，或 OpenAI Chat 格式、地址为本机且未配置 API Key。
+
+use crate::provider::Provider;
+
+/// `meta.providerType` 取值
+pub const LOCAL_PROVIDER_TYPE: &str = "local";
+
+/// Ollama 默认监听地址
+pub const OLLAMA_DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+/// 需要从 base_url 末尾剥离的原生/完整端点路径（按长度从长到短匹配）
+const STRIPPED_SUFFIXES: &[&str] = &["/v1/chat/completions", "/api/chat", "/api", "/v1"];
+
+/// 是否为本地运行时供应商
+pub fn is_local_provider(provider: &Provider) -> bool {
+    if provider
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.provider_type.as_deref())
+        == Some(LOCAL_PROVIDER_TYPE)
+    {
+        return true;
+    }
+    super::get_claude_api_format(provider) == "openai_chat"
+        && configured_base_url(provider).is_some_and(|url| is_loopback_url(&url))
+        && configured_key(provider).is_none()
+}
+
+/// 地址是否指向本机（localhost / 127.0.0.0/8 / ::1 / 0.0.0.0 / host.docker.internal）
+pub fn is_loopback_url(url: &str) -> bool {
+    let Ok(parsed) = url::Url::parse(url.trim()) else {
+        return false;
+    };
+    match parsed.host() {
+        Some(url::Host::Domain(domain)) => {
+            domain.eq_ignore_ascii_case("localhost")
+                || domain.eq_ignore_ascii_case("host.docker.internal")
+        }
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback() || ip.is_unspecified(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback() || ip.is_unspecified(),
+        None => false,
+    }
+}
+
+/// 把 Ollama 原生地址或完整端点地址改写为服务根地址（不带末尾 `/`）
+pub fn normalize_base_url(url: &str) -> String {
+    let trimmed = url.trim().trim_end_matches('/');
+    let lower = trimmed.to_ascii_lowercase();
+    for suffix in STRIPPED_SUFFIXES {
+        if lower.ends_with(suffix) {
+            return trimmed[..trimmed.len() - suffix.len()].to_string();
+        }
+    }
+    trimmed.to_string()
+}
+
+/// 本地运行时可选的 API Key；不存在时不记录告警
+pub fn configured_key(provider: &Provider) -> Option<String> {
+    let env = provider.settings_config.get("env");
+    [
+        "ANTHROPIC_AUTH_TOKEN",
+        "ANTHROPIC_API_KEY",
+        "OPENAI_API_KEY",
+    ]
+    .iter()
+    .filter_map(|name| env.and_then(|env| env.get(*name)))
+    .chain(provider.settings_config.get("apiKey"))
+    .filter_map(|value| value.as_str())
+    .map(str::trim)
+    .find(|key| !key.is_empty())
+    .map(str::to_string)
+}
+
+/// 用户填写的 base_url（未做改写）
+pub fn configured_base_url(provider: &Provider) -> Option<String> {
+    provider
+        .settings_config
+        .get("env")
+        .and_then(|env| env.get("ANTHROPIC_BASE_URL"))
+        .or_else(|| provider.settings_config.get("base_url"))
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::ProviderMeta;
+    use serde_json::json;
+
+    fn provider(env: serde_json::Value, meta: Option<ProviderMeta>) -> Provider {
+        let mut provider = Provider::with_id(
+            "local".to_string(),
+            "Local".to_string(),
+            json!({ "env": env }),
+            None,
+        );
+        provider.meta = meta;
+        provider
+    }
+
+    #[test]
+    fn normalizes_native_and_full_endpoint_urls() {
+        assert_eq!(
+            normalize_base_url("http://localhost:11434/api/chat"),
+            "http://localhost:11434"
+        );
+        assert_eq!(
+            normalize_base_url("http://localhost:11434/api/"),
+            "http://localhost:11434"
+        );
+        assert_eq!(
+            normalize_base_url("http://127.0.0.1:1234/v1/chat/completions"),
+            "http://127.0.0.1:1234"
+        );
+        assert_eq!(
+            normalize_base_url("http://127.0.0.1:8080"),
+            "http://127.0.0.1:8080"
+        );
+    }
+
+    #[test]
+    fn detects_local_by_meta_or_keyless_loopback_openai_chat() {
+        let meta = |provider_type: Option<&str>, api_format: Option<&str>| ProviderMeta {
+            provider_type: provider_type.map(str::to_string),
+            api_format: api_format.map(str::to_string),
+            ..Default::default()
+        };
+        // 显式标记，地址不限
+        assert!(is_local_provider(&provider(
+            json!({ "ANTHROPIC_BASE_URL": "http://192.168.1.20:11434" }),
+            Some(meta(Some("local"), None)),
+        )));
+        // OpenAI Chat + 本机 + 无 key
+        assert!(is_local_provider(&provider(
+            json!({ "ANTHROPIC_BASE_URL": "http://127.0.0.1:1234/v1" }),
+            Some(meta(None, Some("openai_chat"))),
+        )));
+        // 本机但走 Anthropic 格式（例如本地中转）不算
+        assert!(!is_local_provider(&provider(
+            json!({ "ANTHROPIC_BASE_URL": "http://localhost:3000" }),
+            None,
+        )));
+        // 配置了 key 的本机 OpenAI 兼容服务按普通供应商处理
+        assert!(!is_local_provider(&provider(
+            json!({
+                "ANTHROPIC_BASE_URL": "http://localhost:4000",
+                "ANTHROPIC_AUTH_TOKEN": "sk-litellm"
+            }),
+            Some(meta(None, Some("openai_chat"))),
+        )));
+    }
+
+    #[test]
+    fn loopback_detection_covers_common_hosts() {
+        assert!(is_loopback_url("http://localhost:11434"));
+        assert!(is_loopback_url("http://[::1]:8080/v1"));
+        assert!(is_loopback_url("http://0.0.0.0:1234"));
+        assert!(is_loopback_url("http://host.docker.internal:11434"));
+        assert!(!is_loopback_url("https://api.example.com"));
+        assert!(!is_loopback_url("not a url"));
+    }
+}
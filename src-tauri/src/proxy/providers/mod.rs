@@ -8,6 +8,7 @@
 //! - `claude`: Claude (Anthropic) 适配器
 //! - `codex`: Codex (OpenAI) 适配器
 //! - `gemini`: Gemini (Google) 适配器
+//! - `local`: 本地模型运行时（Ollama / LM Studio / llama.cpp）
 //! - `models`: API 数据模型
 //! - `transform`: 格式转换
 
@@ -24,6 +25,7 @@ pub mod copilot_model_map;
 mod gemini;
 pub(crate) mod gemini_schema;
 pub mod gemini_shadow;
+pub mod local;
 pub mod models;
 pub(crate) mod reasoning_bridge;
 pub mod streaming;
@@ -90,6 +92,8 @@ pub enum ProviderType {
     CodexOAuth,
     /// xAI Grok OAuth（需要 Anthropic ↔ Responses API 转换）
     XaiOAuth,
+    /// 本地模型运行时（Ollama / LM Studio / llama.cpp，无鉴权，需要 Anthropic ↔ OpenAI 转换）
+    Local,
 }
 
 impl ProviderType {
@@ -104,6 +108,7 @@ impl ProviderType {
             ProviderType::GitHubCopilot => true,
             ProviderType::CodexOAuth => true,
             ProviderType::XaiOAuth => true,
+            ProviderType::Local => true,
             ProviderType::OpenRouter => false,
             _ => false,
         }
@@ -122,6 +127,7 @@ impl ProviderType {
             ProviderType::GitHubCopilot => "https://api.githubcopilot.com",
             ProviderType::CodexOAuth => CHATGPT_CODEX_BASE_URL,
             ProviderType::XaiOAuth => XAI_API_BASE_URL,
+            ProviderType::Local => local::OLLAMA_DEFAULT_BASE_URL,
         }
     }
 
@@ -153,6 +159,11 @@ impl ProviderType {
                     }
                 }
 
+                // 检测本地模型运行时
+                if local::is_local_provider(provider) {
+                    return ProviderType::Local;
+                }
+
                 // 检测 base_url 是否为 GitHub Copilot
                 let adapter = ClaudeAdapter::new();
                 if let Ok(base_url) = adapter.extract_base_url(provider) {
@@ -221,6 +232,7 @@ impl ProviderType {
             ProviderType::GitHubCopilot => "github_copilot",
             ProviderType::CodexOAuth => "codex_oauth",
             ProviderType::XaiOAuth => "xai_oauth",
+            ProviderType::Local => "local",
         }
     }
 }
@@ -247,6 +259,7 @@ impl std::str::FromStr for ProviderType {
             }
             "codex_oauth" | "codex-oauth" | "codexoauth" => Ok(ProviderType::CodexOAuth),
             "xai_oauth" | "xai-oauth" | "xaioauth" => Ok(ProviderType::XaiOAuth),
+            "local" => Ok(ProviderType::Local),
             _ => Err(format!("Invalid provider type: {s}")),
         }
     }
@@ -272,7 +285,8 @@ pub fn get_adapter_for_provider_type(provider_type: &ProviderType) -> Box<dyn Pr
         | ProviderType::OpenRouter
         | ProviderType::GitHubCopilot
         | ProviderType::CodexOAuth
-        | ProviderType::XaiOAuth => Box::new(ClaudeAdapter::new()),
+        | ProviderType::XaiOAuth
+        | ProviderType::Local => Box::new(ClaudeAdapter::new()),
         ProviderType::Codex => Box::new(CodexAdapter::new()),
         ProviderType::Gemini | ProviderType::GeminiCli => Box::new(GeminiAdapter::new()),
     }
@@ -309,6 +323,7 @@ mod tests {
         assert!(!ProviderType::GeminiCli.needs_transform());
         assert!(!ProviderType::OpenRouter.needs_transform());
         assert!(ProviderType::GitHubCopilot.needs_transform());
+        assert!(ProviderType::Local.needs_transform());
     }
 
     #[test]
@@ -393,6 +408,10 @@ mod tests {
             "xai_oauth".parse::<ProviderType>().unwrap(),
             ProviderType::XaiOAuth
         );
+        assert_eq!(
+            "local".parse::<ProviderType>().unwrap(),
+            ProviderType::Local
+        );
         assert!("invalid".parse::<ProviderType>().is_err());
     }
 
@@ -406,6 +425,7 @@ mod tests {
         assert_eq!(ProviderType::OpenRouter.as_str(), "openrouter");
         assert_eq!(ProviderType::GitHubCopilot.as_str(), "github_copilot");
         assert_eq!(ProviderType::XaiOAuth.as_str(), "xai_oauth");
+        assert_eq!(ProviderType::Local.as_str(), "local");
     }
 
     #[test]
@@ -467,6 +487,19 @@ mod tests {
         assert_eq!(provider_type, ProviderType::ClaudeAuth);
     }
 
+    #[test]
+    fn test_from_app_type_claude_local_runtime() {
+        let provider = create_provider(json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": "http://localhost:11434/api"
+            },
+            "api_format": "openai_chat"
+        }));
+
+        let provider_type = ProviderType::from_app_type_and_config(&AppType::Claude, &provider);
+        assert_eq!(provider_type, ProviderType::Local);
+    }
+
     #[test]
     fn test_from_app_type_codex() {
         let provider = create_provider(json!({
@@ -42,7 +42,9 @@ struct Delta {
 
 #[derive(Debug, Deserialize, Serialize)]
 struct DeltaToolCall {
-    index: usize,
+    /// Ollama 等本地运行时的 OpenAI 兼容接口会省略 index，见 [`ToolCallStreamState::resolve_slot`]
+    #[serde(default)]
+    index: Option<usize>,
     #[serde(default)]
     id: Option<String>,
     #[serde(rename = "type", default)]
@@ -101,6 +103,211 @@ struct ToolBlockState {
 /// 无限空白 bug 的连续空白字符阈值
 const INFINITE_WHITESPACE_THRESHOLD: usize = 500;
 
+/// 工具调用增量状态机
+///
+/// OpenAI 把一次工具调用拆成多个 `tool_calls` 增量：首个片段带 id/name，
+/// 之后按 index 续传 arguments 片段（任意切分，可能在 JSON token 中间断开）。
+/// Anthropic 则要求先发 `content_block_start`（tool_use，含 id/name），再以
+/// `input_json_delta` 逐段发送参数，最后 `content_block_stop`。这里负责：
+///
+/// - 按 index（缺失时按 id）把片段归到各自的块，多个调用交错续传也不会串；
+/// - id/name 到齐前先缓存参数片段，开块后一次补发；
+/// - 上游对不同调用复用同一 index（id 变化）时另开新块；
+/// - 续传片段里的空 id/name 不覆盖已有值，空参数片段不发事件；
+/// - finish 时补开仍缺 id/name 的块并按顺序关闭全部工具块。
+#[derive(Debug, Default)]
+struct ToolCallStreamState {
+    blocks: Vec<ToolBlockState>,
+    /// 上游 index → blocks 下标
+    slot_by_index: HashMap<usize, usize>,
+    open_indices: HashSet<u32>,
+}
+
+impl ToolCallStreamState {
+    /// 处理一个 tool_call 增量，返回需要发出的 Anthropic 事件
+    fn apply(&mut self, tool_call: &DeltaToolCall, next_content_index: &mut u32) -> Vec<Value> {
+        let slot = self.resolve_slot(tool_call, next_content_index);
+        let state = &mut self.blocks[slot];
+        let mut events = Vec::new();
+
+        // 如果此 tool call 已被中止（无限空白 bug），跳过后续处理
+        if state.aborted {
+            return events;
+        }
+
+        if let Some(id) = tool_call.id.as_deref().filter(|id| !id.is_empty()) {
+            state.id = id.to_string();
+        }
+        if let Some(name) = tool_call
+            .function
+            .as_ref()
+            .and_then(|f| f.name.as_deref())
+            .filter(|name| !name.is_empty())
+        {
+            state.name = name.to_string();
+        }
+
+        if let Some(args) = tool_call
+            .function
+            .as_ref()
+            .and_then(|f| f.arguments.as_deref())
+            .filter(|args| !args.is_empty())
+        {
+            // 无限空白 bug 检测：跟踪连续空白字符
+            for ch in args.chars() {
+                if ch.is_whitespace() {
+                    state.consecutive_whitespace += 1;
+                } else {
+                    state.consecutive_whitespace = 0;
+                }
+            }
+            if state.consecutive_whitespace >= INFINITE_WHITESPACE_THRESHOLD {
+                log::warn!(
+                    "[Copilot] 检测到无限空白 bug (tool: {}), 中止此 tool call 流",
+                    state.name
+                );
+                state.aborted = true;
+            } else {
+                state.pending_args.push_str(args);
+            }
+        }
+
+        if !state.started && !state.id.is_empty() && !state.name.is_empty() {
+            state.started = true;
+            self.open_indices.insert(state.anthropic_index);
+            events.push(tool_use_start_event(
+                state.anthropic_index,
+                &state.id,
+                &state.name,
+            ));
+        }
+        if state.started && !state.pending_args.is_empty() {
+            let args = std::mem::take(&mut state.pending_args);
+            events.push(input_json_delta_event(state.anthropic_index, args));
+        }
+        events
+    }
+
+    /// 收到 finish_reason 时调用：补开缺 id/name 的块，并关闭所有已开的工具块
+    fn finish(&mut self) -> Vec<Value> {
+        let mut events = Vec::new();
+        for (slot, state) in self.blocks.iter_mut().enumerate() {
+            if state.started {
+                continue;
+            }
+            let has_payload =
+                !state.pending_args.is_empty() || !state.id.is_empty() || !state.name.is_empty();
+            if !has_payload {
+                continue;
+            }
+            let id = if state.id.is_empty() {
+                format!("tool_call_{slot}")
+            } else {
+                state.id.clone()
+            };
+            let name = if state.name.is_empty() {
+                "unknown_tool".to_string()
+            } else {
+                state.name.clone()
+            };
+            state.started = true;
+            self.open_indices.insert(state.anthropic_index);
+            events.push(tool_use_start_event(state.anthropic_index, &id, &name));
+            if !state.pending_args.is_empty() {
+                let args = std::mem::take(&mut state.pending_args);
+                events.push(input_json_delta_event(state.anthropic_index, args));
+            }
+        }
+
+        let mut indices: Vec<u32> = self.open_indices.drain().collect();
+        indices.sort_unstable();
+        events.extend(indices.into_iter().map(|index| {
+            json!({
+                "type": "content_block_stop",
+                "index": index
+            })
+        }));
+        events
+    }
+
+    /// tool call 增量对应的块，没有则新建（同时分配 Anthropic content index）
+    ///
+    /// 带 index 时按 index 路由，但同一 index 上出现新的 id 视为新调用；
+    /// 缺少 index 时（Ollama 等本地运行时）按 id 匹配已有块，新 id 新建块，
+    /// 没有 id 的续传片段归入最近的块。
+    fn resolve_slot(&mut self, tool_call: &DeltaToolCall, next_content_index: &mut u32) -> usize {
+        let id = tool_call.id.as_deref().filter(|id| !id.is_empty());
+        let existing = match tool_call.index {
+            Some(index) => self
+                .slot_by_index
+                .get(&index)
+                .copied()
+                .filter(|&slot| match id {
+                    Some(id) => self.blocks[slot].id.is_empty() || self.blocks[slot].id == id,
+                    None => true,
+                }),
+            None => match id {
+                Some(id) => self.blocks.iter().position(|state| state.id == id),
+                None => self.blocks.len().checked_sub(1),
+            },
+        };
+        if let Some(slot) = existing {
+            return slot;
+        }
+
+        let slot = self.blocks.len();
+        self.blocks.push(ToolBlockState {
+            anthropic_index: *next_content_index,
+            id: String::new(),
+            name: String::new(),
+            started: false,
+            pending_args: String::new(),
+            consecutive_whitespace: 0,
+            aborted: false,
+        });
+        *next_content_index += 1;
+        if let Some(index) = tool_call.index {
+            self.slot_by_index.insert(index, slot);
+        }
+        slot
+    }
+}
+
+fn tool_use_start_event(index: u32, id: &str, name: &str) -> Value {
+    json!({
+        "type": "content_block_start",
+        "index": index,
+        "content_block": {
+            "type": "tool_use",
+            "id": id,
+            "name": name
+        }
+    })
+}
+
+fn input_json_delta_event(index: u32, partial_json: String) -> Value {
+    json!({
+        "type": "content_block_delta",
+        "index": index,
+        "delta": {
+            "type": "input_json_delta",
+            "partial_json": partial_json
+        }
+    })
+}
+
+/// 把 Anthropic 事件编码为一条 SSE 消息（event 名取自 type 字段）
+fn encode_sse_event(event: &Value) -> Bytes {
+    let event_name = event
+        .get("type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("message");
+    Bytes::from(format!(
+        "event: {event_name}\ndata: {}\n\n",
+        serde_json::to_string(event).unwrap_or_default()
+    ))
+}
+
 fn build_anthropic_usage_json(usage: &Usage) -> Value {
     // OpenAI prompt_tokens 含缓存，Anthropic input_tokens 不含，需减去 cache_read 与 cache_creation
     // （三桶互斥，恒等 input + cache_read + cache_creation == prompt_tokens）。
@@ -168,8 +375,7 @@ pub fn create_anthropic_sse_stream<E: std::error::Error + Send + 'static>(
         let mut latest_usage: Option<Value> = None;
         let mut current_non_tool_block_type: Option<&'static str> = None;
         let mut current_non_tool_block_index: Option<u32> = None;
-        let mut tool_blocks_by_index: HashMap<usize, ToolBlockState> = HashMap::new();
-        let mut open_tool_block_indices: HashSet<u32> = HashSet::new();
+        let mut tool_calls_state = ToolCallStreamState::default();
 
         tokio::pin!(stream);
 
@@ -372,139 +578,8 @@ pub fn create_anthropic_sse_stream<E: std::error::Error + Send + 'static>(
                                                 current_non_tool_block_type = None;
 
                                                 for tool_call in tool_calls {
-                                                    let (
-                                                        anthropic_index,
-                                                        id,
-                                                        name,
-                                                        should_start,
-                                                        pending_after_start,
-                                                        immediate_delta,
-                                                    ) = {
-                                                        let state = tool_blocks_by_index
-                                                            .entry(tool_call.index)
-                                                            .or_insert_with(|| {
-                                                                let index = next_content_index;
-                                                                next_content_index += 1;
-                                                                ToolBlockState {
-                                                                    anthropic_index: index,
-                                                                    id: String::new(),
-                                                                    name: String::new(),
-                                                                    started: false,
-                                                                    pending_args: String::new(),
-                                                                    consecutive_whitespace: 0,
-                                                                    aborted: false,
-                                                                }
-                                                            });
-
-                                                        // 如果此 tool call 已被中止（无限空白 bug），跳过后续处理
-                                                        if state.aborted {
-                                                            continue;
-                                                        }
-
-                                                        if let Some(id) = &tool_call.id {
-                                                            state.id = id.clone();
-                                                        }
-                                                        if let Some(function) = &tool_call.function {
-                                                            if let Some(name) = &function.name {
-                                                                state.name = name.clone();
-                                                            }
-                                                        }
-
-                                                        let should_start =
-                                                            !state.started
-                                                                && !state.id.is_empty()
-                                                                && !state.name.is_empty();
-                                                        if should_start {
-                                                            state.started = true;
-                                                        }
-                                                        let pending_after_start = if should_start
-                                                            && !state.pending_args.is_empty()
-                                                        {
-                                                            Some(std::mem::take(&mut state.pending_args))
-                                                        } else {
-                                                            None
-                                                        };
-                                                        let args_delta = tool_call
-                                                            .function
-                                                            .as_ref()
-                                                            .and_then(|f| f.arguments.clone());
-                                                        let immediate_delta = if let Some(args) = args_delta {
-                                                            // 无限空白 bug 检测：跟踪连续空白字符
-                                                            for ch in args.chars() {
-                                                                if ch.is_whitespace() {
-                                                                    state.consecutive_whitespace += 1;
-                                                                } else {
-                                                                    state.consecutive_whitespace = 0;
-                                                                }
-                                                            }
-                                                            if state.consecutive_whitespace >= INFINITE_WHITESPACE_THRESHOLD {
-                                                                log::warn!(
-                                                                    "[Copilot] 检测到无限空白 bug (tool: {}), 中止此 tool call 流",
-                                                                    state.name
-                                                                );
-                                                                state.aborted = true;
-                                                                None
-                                                            } else if state.started {
-                                                                Some(args)
-                                                            } else {
-                                                                state.pending_args.push_str(&args);
-                                                                None
-                                                            }
-                                                        } else {
-                                                            None
-                                                        };
-                                                        (
-                                                            state.anthropic_index,
-                                                            state.id.clone(),
-                                                            state.name.clone(),
-                                                            should_start,
-                                                            pending_after_start,
-                                                            immediate_delta,
-                                                        )
-                                                    };
-
-                                                    if should_start {
-                                                        let event = json!({
-                                                            "type": "content_block_start",
-                                                            "index": anthropic_index,
-                                                            "content_block": {
-                                                                "type": "tool_use",
-                                                                "id": id,
-                                                                "name": name
-                                                            }
-                                                        });
-                                                        let sse_data = format!("event: content_block_start\ndata: {}\n\n",
-                                                            serde_json::to_string(&event).unwrap_or_default());
-                                                        yield Ok(Bytes::from(sse_data));
-                                                        open_tool_block_indices.insert(anthropic_index);
-                                                    }
-
-                                                    if let Some(args) = pending_after_start {
-                                                        let event = json!({
-                                                            "type": "content_block_delta",
-                                                            "index": anthropic_index,
-                                                            "delta": {
-                                                                "type": "input_json_delta",
-                                                                "partial_json": args
-                                                            }
-                                                        });
-                                                        let sse_data = format!("event: content_block_delta\ndata: {}\n\n",
-                                                            serde_json::to_string(&event).unwrap_or_default());
-                                                        yield Ok(Bytes::from(sse_data));
-                                                    }
-
-                                                    if let Some(args) = immediate_delta {
-                                                        let event = json!({
-                                                            "type": "content_block_delta",
-                                                            "index": anthropic_index,
-                                                            "delta": {
-                                                                "type": "input_json_delta",
-                                                                "partial_json": args
-                                                            }
-                                                        });
-                                                        let sse_data = format!("event: content_block_delta\ndata: {}\n\n",
-                                                            serde_json::to_string(&event).unwrap_or_default());
-                                                        yield Ok(Bytes::from(sse_data));
+                                                    for event in tool_calls_state.apply(tool_call, &mut next_content_index) {
+                                                        yield Ok(encode_sse_event(&event));
                                                     }
                                                 }
                                             }
@@ -539,82 +614,9 @@ pub fn create_anthropic_sse_stream<E: std::error::Error + Send + 'static>(
                                             }
                                             current_non_tool_block_type = None;
 
-                                            // Late start for blocks that accumulated args before id/name arrived.
-                                            let mut late_tool_starts: Vec<(u32, String, String, String)> =
-                                                Vec::new();
-                                            for (tool_idx, state) in tool_blocks_by_index.iter_mut() {
-                                                if state.started {
-                                                    continue;
-                                                }
-                                                let has_payload = !state.pending_args.is_empty()
-                                                    || !state.id.is_empty()
-                                                    || !state.name.is_empty();
-                                                if !has_payload {
-                                                    continue;
-                                                }
-                                                let fallback_id = if state.id.is_empty() {
-                                                    format!("tool_call_{tool_idx}")
-                                                } else {
-                                                    state.id.clone()
-                                                };
-                                                let fallback_name = if state.name.is_empty() {
-                                                    "unknown_tool".to_string()
-                                                } else {
-                                                    state.name.clone()
-                                                };
-                                                state.started = true;
-                                                let pending = std::mem::take(&mut state.pending_args);
-                                                late_tool_starts.push((
-                                                    state.anthropic_index,
-                                                    fallback_id,
-                                                    fallback_name,
-                                                    pending,
-                                                ));
-                                            }
-                                            late_tool_starts.sort_unstable_by_key(|(index, _, _, _)| *index);
-                                            for (index, id, name, pending) in late_tool_starts {
-                                                let event = json!({
-                                                    "type": "content_block_start",
-                                                    "index": index,
-                                                    "content_block": {
-                                                        "type": "tool_use",
-                                                        "id": id,
-                                                        "name": name
-                                                    }
-                                                });
-                                                let sse_data = format!("event: content_block_start\ndata: {}\n\n",
-                                                    serde_json::to_string(&event).unwrap_or_default());
-                                                yield Ok(Bytes::from(sse_data));
-                                                open_tool_block_indices.insert(index);
-                                                if !pending.is_empty() {
-                                                    let delta_event = json!({
-                                                        "type": "content_block_delta",
-                                                        "index": index,
-                                                        "delta": {
-                                                            "type": "input_json_delta",
-                                                            "partial_json": pending
-                                                        }
-                                                    });
-                                                    let delta_sse = format!("event: content_block_delta\ndata: {}\n\n",
-                                                        serde_json::to_string(&delta_event).unwrap_or_default());
-                                                    yield Ok(Bytes::from(delta_sse));
-                                                }
-                                            }
-
-                                            if !open_tool_block_indices.is_empty() {
-                                                let mut tool_indices: Vec<u32> =
-                                                    open_tool_block_indices.iter().copied().collect();
-                                                tool_indices.sort_unstable();
-                                                for index in tool_indices {
-                                                    let event = json!({
-                                                        "type": "content_block_stop",
-                                                        "index": index
-                                                    });
-                                                    let sse_data = format!("event: content_block_stop\ndata: {}\n\n",
-                                                        serde_json::to_string(&event).unwrap_or_default());
-                                                    yield Ok(Bytes::from(sse_data));
-                                                }
-                                                open_tool_block_indices.clear();
+                                            // 补发 id/name 迟迟未到的工具块，并关闭所有工具块
+                                            for event in tool_calls_state.finish() {
+                                                yield Ok(encode_sse_event(&event));
                                             }
 
                                             // 缓存 message_delta，等到 [DONE] 时发送（以便收集完整的 usage）
@@ -723,9 +725,18 @@ mod tests {
     use std::collections::HashMap;
 
     async fn collect_anthropic_events(input: &str) -> Vec<Value> {
-        let upstream = stream::iter(vec![Ok::<_, std::io::Error>(Bytes::from(
-            input.as_bytes().to_vec(),
-        ))]);
+        collect_anthropic_events_chunked(input, input.len().max(1)).await
+    }
+
+    /// 把上游字节按固定大小切块送入，模拟 TCP 分包（可能切在 SSE 行、JSON 或 UTF-8 中间）
+    async fn collect_anthropic_events_chunked(input: &str, chunk_size: usize) -> Vec<Value> {
+        let upstream = stream::iter(
+            input
+                .as_bytes()
+                .chunks(chunk_size)
+                .map(|chunk| Ok::<_, std::io::Error>(Bytes::from(chunk.to_vec())))
+                .collect::<Vec<_>>(),
+        );
         let converted = create_anthropic_sse_stream(upstream);
         let chunks: Vec<_> = converted.collect().await;
         let merged = chunks
@@ -748,6 +759,44 @@ mod tests {
         event.get("type").and_then(|v| v.as_str())
     }
 
+    /// 按 content index 汇总 tool_use 块：(id, name, 拼接后的 partial_json)
+    fn collect_tool_inputs(events: &[Value]) -> Vec<(u64, String, String, String)> {
+        let mut blocks: Vec<(u64, String, String, String)> = Vec::new();
+        for event in events {
+            let Some(index) = event.get("index").and_then(|v| v.as_u64()) else {
+                continue;
+            };
+            match event_type(event) {
+                Some("content_block_start")
+                    if event
+                        .pointer("/content_block/type")
+                        .and_then(|v| v.as_str())
+                        == Some("tool_use") =>
+                {
+                    let field = |name: &str| {
+                        event
+                            .pointer(&format!("/content_block/{name}"))
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default()
+                            .to_string()
+                    };
+                    blocks.push((index, field("id"), field("name"), String::new()));
+                }
+                Some("content_block_delta") => {
+                    let block = blocks.iter_mut().find(|block| block.0 == index);
+                    let partial = event
+                        .pointer("/delta/partial_json")
+                        .and_then(|v| v.as_str());
+                    if let (Some(block), Some(partial)) = (block, partial) {
+                        block.3.push_str(partial);
+                    }
+                }
+                _ => {}
+            }
+        }
+        blocks
+    }
+
     #[test]
     fn test_map_stop_reason_legacy_and_filtered_values() {
         assert_eq!(
@@ -760,6 +809,47 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_streaming_tool_calls_without_index_get_separate_blocks() {
+        // Ollama 的 OpenAI 兼容流：一次给出完整调用，且不带 index
+        let input = concat!(
+            "data: {\"id\":\"chatcmpl-7\",\"model\":\"qwen3:8b\",\"choices\":[{\"delta\":{\"role\":\"assistant\",\"tool_calls\":[{\"id\":\"call_a\",\"type\":\"function\",\"function\":{\"name\":\"read\",\"arguments\":\"{\\\"path\\\":\\\"a\\\"}\"}},{\"id\":\"call_b\",\"type\":\"function\",\"function\":{\"name\":\"read\",\"arguments\":\"{\\\"path\\\":\\\"b\\\"}\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl-7\",\"model\":\"qwen3:8b\",\"choices\":[{\"delta\":{},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: [DONE]\n\n"
+        );
+
+        let events = collect_anthropic_events(input).await;
+        let tool_starts: Vec<(u64, &str)> = events
+            .iter()
+            .filter(|event| {
+                event_type(event) == Some("content_block_start")
+                    && event
+                        .pointer("/content_block/type")
+                        .and_then(|v| v.as_str())
+                        == Some("tool_use")
+            })
+            .filter_map(|event| {
+                Some((
+                    event.get("index")?.as_u64()?,
+                    event.pointer("/content_block/id")?.as_str()?,
+                ))
+            })
+            .collect();
+        assert_eq!(tool_starts.len(), 2);
+        assert_eq!(tool_starts[0].1, "call_a");
+        assert_eq!(tool_starts[1].1, "call_b");
+        assert_ne!(tool_starts[0].0, tool_starts[1].0);
+
+        let stop = events
+            .iter()
+            .find(|event| event_type(event) == Some("message_delta"))
+            .unwrap();
+        assert_eq!(
+            stop.pointer("/delta/stop_reason").and_then(|v| v.as_str()),
+            Some("tool_use")
+        );
+    }
+
     #[tokio::test]
     async fn test_streaming_tool_calls_routed_by_index() {
         let input = concat!(
@@ -1245,4 +1335,105 @@ mod tests {
             .iter()
             .any(|e| e.get("type").and_then(|v| v.as_str()) == Some("message_stop")));
     }
+
+    #[tokio::test]
+    async fn test_streaming_tool_args_survive_arbitrary_chunk_splits() {
+        // 参数片段本身在 JSON token 中间断开，且字节流再被切成任意大小的块
+        let input = concat!(
+            "data: {\"id\":\"chatcmpl_s\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"content\":\"查一下\"}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_s\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_0\",\"type\":\"function\",\"function\":{\"name\":\"search\",\"arguments\":\"\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_s\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"{\\\"que\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_s\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"ry\\\":\\\"天\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_s\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":1,\"id\":\"call_1\",\"type\":\"function\",\"function\":{\"name\":\"read\",\"arguments\":\"{\\\"path\\\"\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_s\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"气\\\",\\\"limit\\\":3}\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_s\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":1,\"function\":{\"arguments\":\":\\\"/tmp\\\"}\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_s\",\"model\":\"gpt-4o\",\"choices\":[{\"delta\":{},\"finish_reason\":\"tool_calls\"}],\"usage\":{\"prompt_tokens\":9,\"completion_tokens\":7}}\n\n",
+            "data: [DONE]\n\n"
+        );
+
+        let expected = collect_anthropic_events(input).await;
+        let tools = collect_tool_inputs(&expected);
+        assert_eq!(tools.len(), 2);
+        assert_eq!(
+            (tools[0].1.as_str(), tools[0].2.as_str()),
+            ("call_0", "search")
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&tools[0].3).unwrap(),
+            json!({"query": "天气", "limit": 3})
+        );
+        assert_eq!(
+            (tools[1].1.as_str(), tools[1].2.as_str()),
+            ("call_1", "read")
+        );
+        assert_eq!(
+            serde_json::from_str::<Value>(&tools[1].3).unwrap(),
+            json!({"path": "/tmp"})
+        );
+        // 空参数片段不产生事件
+        assert!(!expected.iter().any(|event| {
+            event
+                .pointer("/delta/partial_json")
+                .and_then(|v| v.as_str())
+                == Some("")
+        }));
+
+        let stops = expected
+            .iter()
+            .filter(|event| event_type(event) == Some("content_block_stop"))
+            .count();
+        assert_eq!(stops, 3, "text block + two tool blocks must all be closed");
+
+        for chunk_size in [1, 2, 3, 5, 7, 16, 64] {
+            let events = collect_anthropic_events_chunked(input, chunk_size).await;
+            assert_eq!(events, expected, "chunk_size={chunk_size}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_reused_tool_index_with_new_id_opens_new_block() {
+        // 部分兼容网关对每个调用都用 index 0，只靠 id 区分
+        let input = concat!(
+            "data: {\"id\":\"chatcmpl_r\",\"model\":\"gemini-2.5\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_a\",\"type\":\"function\",\"function\":{\"name\":\"read\",\"arguments\":\"{\\\"path\\\":\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_r\",\"model\":\"gemini-2.5\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"function\":{\"arguments\":\"\\\"a\\\"}\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_r\",\"model\":\"gemini-2.5\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_b\",\"type\":\"function\",\"function\":{\"name\":\"write\",\"arguments\":\"{\\\"path\\\":\\\"b\\\"}\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_r\",\"model\":\"gemini-2.5\",\"choices\":[{\"delta\":{},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: [DONE]\n\n"
+        );
+
+        let events = collect_anthropic_events_chunked(input, 11).await;
+        let tools = collect_tool_inputs(&events);
+        assert_eq!(tools.len(), 2);
+        assert_ne!(tools[0].0, tools[1].0);
+        assert_eq!(
+            (tools[0].1.as_str(), tools[0].3.as_str()),
+            ("call_a", "{\"path\":\"a\"}")
+        );
+        assert_eq!(
+            (tools[1].1.as_str(), tools[1].2.as_str()),
+            ("call_b", "write")
+        );
+        assert_eq!(tools[1].3, "{\"path\":\"b\"}");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_empty_id_and_name_in_continuation_do_not_clobber() {
+        // 续传片段重复携带空的 id/name，不能把已有的 id/name 清空
+        let input = concat!(
+            "data: {\"id\":\"chatcmpl_e\",\"model\":\"glm-5.1\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"call_0\",\"function\":{\"arguments\":\"{\\\"cmd\\\"\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_e\",\"model\":\"glm-5.1\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"\",\"function\":{\"name\":\"\",\"arguments\":\":\\\"ls\\\"}\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_e\",\"model\":\"glm-5.1\",\"choices\":[{\"delta\":{\"tool_calls\":[{\"index\":0,\"id\":\"\",\"function\":{\"name\":\"Bash\"}}]}}]}\n\n",
+            "data: {\"id\":\"chatcmpl_e\",\"model\":\"glm-5.1\",\"choices\":[{\"delta\":{},\"finish_reason\":\"tool_calls\"}]}\n\n",
+            "data: [DONE]\n\n"
+        );
+
+        let events = collect_anthropic_events(input).await;
+        let tools = collect_tool_inputs(&events);
+        assert_eq!(tools.len(), 1);
+        assert_eq!(
+            (tools[0].1.as_str(), tools[0].2.as_str()),
+            ("call_0", "Bash")
+        );
+        assert_eq!(tools[0].3, "{\"cmd\":\"ls\"}");
+    }
 }
@@ -0,0 +1,321 @@
+//! 代理响应缓存
+//!
+//! lint、评测等工具常会原样重发同一个提示词。开启响应缓存后，非流式请求按
+//! "应用 + 供应商 + 端点 + 规范化请求体"（模型、消息与全部参数）计算哈希，
+//! 有效期内的相同请求直接返回上次的成功响应，不再请求上游、不产生费用。
+//!
+//! - 只缓存 2xx 且非 SSE 的响应；流式请求不参与缓存（也不计入命中统计）；
+//! - 规范化时对象键按字典序排列，并去掉 `stream`、`metadata` 等不影响结果的字段；
+//! - 超过条目上限时淘汰最久未使用的条目；缓存与统计只在内存中，重启后清空。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::Response;
+use bytes::Bytes;
+use http_body::Body as _;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use super::handler_context::RequestContext;
+use super::ProxyError;
+
+/// 命中缓存的响应带上该响应头，便于客户端排查
+const CACHE_HEADER: &str = "x-cc-switch-cache";
+
+/// 单个响应体超过该大小时不缓存
+const MAX_CACHEABLE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// 不影响响应内容的顶层字段
+const IGNORED_FIELDS: &[&str] = &["stream", "metadata"];
+
+struct CachedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    stored_at: Instant,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct ResponseCache {
+    entries: HashMap<String, CachedResponse>,
+    /// 单调递增的访问序号，用于 LRU 淘汰
+    clock: u64,
+}
+
+impl ResponseCache {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn get(&mut self, key: &str, ttl: Duration) -> Option<&CachedResponse> {
+        if self
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.stored_at.elapsed() >= ttl)
+        {
+            self.entries.remove(key);
+            return None;
+        }
+        let now = self.tick();
+        let entry = self.entries.get_mut(key)?;
+        entry.last_used = now;
+        Some(entry)
+    }
+
+    fn insert(&mut self, key: String, mut entry: CachedResponse, ttl: Duration, max: usize) {
+        self.entries
+            .retain(|_, cached| cached.stored_at.elapsed() < ttl);
+        while !self.entries.contains_key(&key) && self.entries.len() >= max.max(1) {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        entry.last_used = self.tick();
+        self.entries.insert(key, entry);
+    }
+}
+
+static CACHE: LazyLock<Mutex<ResponseCache>> = LazyLock::new(Default::default);
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// 缓存命中统计
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+}
+
+pub fn stats() -> ResponseCacheStats {
+    ResponseCacheStats {
+        hits: HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+        entries: CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entries
+            .len(),
+    }
+}
+
+/// 清空缓存并重置统计
+pub fn clear() {
+    CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entries
+        .clear();
+    HITS.store(0, Ordering::Relaxed);
+    MISSES.store(0, Ordering::Relaxed);
+}
+
+/// 递归按键排序，得到与字段顺序无关的表示
+fn canonicalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<_> = map.keys().collect();
+            keys.sort();
+            let mut sorted = Map::new();
+            for key in keys {
+                sorted.insert(key.clone(), canonicalize(&map[key]));
+            }
+            Value::Object(sorted)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(canonicalize).collect()),
+        other => other.clone(),
+    }
+}
+
+fn cache_key(app_type: &str, provider_id: &str, endpoint: &str, body: &Value) -> String {
+    let mut normalized = canonicalize(body);
+    if let Some(map) = normalized.as_object_mut() {
+        for field in IGNORED_FIELDS {
+            map.remove(*field);
+        }
+    }
+    let mut hasher = Sha256::new();
+    for part in [app_type, provider_id, endpoint] {
+        hasher.update(part.as_bytes());
+        hasher.update(b"\0");
+    }
+    hasher.update(normalized.to_string().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// 单个请求的缓存位置
+pub struct CacheSlot {
+    key: String,
+    ttl: Duration,
+    max_entries: usize,
+}
+
+/// 为请求准备缓存位置；未开启缓存、流式请求或无请求体时返回 None
+pub fn prepare(
+    ctx: &RequestContext,
+    endpoint: &str,
+    body: &Value,
+    is_stream: bool,
+) -> Option<CacheSlot> {
+    let config = &ctx.response_cache_config;
+    if !config.enabled || config.ttl_seconds == 0 || is_stream || !body.is_object() {
+        return None;
+    }
+    // Gemini 的流式与否体现在 URL 上
+    if endpoint.contains("streamGenerateContent") || endpoint.contains("alt=sse") {
+        return None;
+    }
+    Some(CacheSlot {
+        key: cache_key(ctx.app_type_str, &ctx.provider.id, endpoint, body),
+        ttl: Duration::from_secs(config.ttl_seconds),
+        max_entries: config.max_entries,
+    })
+}
+
+impl CacheSlot {
+    /// 查找缓存并记录命中/未命中
+    pub fn lookup(&self, tag: &str) -> Option<Response> {
+        let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(entry) = cache.get(&self.key, self.ttl) else {
+            MISSES.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        HITS.fetch_add(1, Ordering::Relaxed);
+        log::info!(
+            "[{tag}] 命中响应缓存 ({}s 前缓存, {} bytes)",
+            entry.stored_at.elapsed().as_secs(),
+            entry.body.len()
+        );
+        let mut response = Response::new(axum::body::Body::from(entry.body.clone()));
+        *response.status_mut() = entry.status;
+        *response.headers_mut() = entry.headers.clone();
+        response
+            .headers_mut()
+            .insert(CACHE_HEADER, HeaderValue::from_static("hit"));
+        Some(response)
+    }
+
+    /// 缓存成功的非流式响应，原样返回响应
+    pub async fn store(self, response: Response) -> Result<Response, ProxyError> {
+        let is_sse = response
+            .headers()
+            .get(axum::http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains("text/event-stream"));
+        let size = response.body().size_hint().exact();
+        if !response.status().is_success()
+            || is_sse
+            || !size.is_some_and(|size| size <= MAX_CACHEABLE_BYTES)
+        {
+            return Ok(response);
+        }
+
+        let (parts, body) = response.into_parts();
+        let body = axum::body::to_bytes(body, usize::MAX)
+            .await
+            .map_err(|e| ProxyError::Internal(format!("Failed to buffer response: {e}")))?;
+        CACHE.lock().unwrap_or_else(|e| e.into_inner()).insert(
+            self.key,
+            CachedResponse {
+                status: parts.status,
+                headers: parts.headers.clone(),
+                body: body.clone(),
+                stored_at: Instant::now(),
+                last_used: 0,
+            },
+            self.ttl,
+            self.max_entries,
+        );
+        Ok(Response::from_parts(parts, axum::body::Body::from(body)))
+    }
+}
+
+/// 有缓存位置时缓存响应，否则原样返回
+pub async fn finish(slot: Option<CacheSlot>, response: Response) -> Result<Response, ProxyError> {
+    match slot {
+        Some(slot) => slot.store(response).await,
+        None => Ok(response),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cached(body: &'static str) -> CachedResponse {
+        CachedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+            stored_at: Instant::now(),
+            last_used: 0,
+        }
+    }
+
+    #[test]
+    fn key_ignores_field_order_and_volatile_fields() {
+        let a = json!({
+            "model": "claude-sonnet-4",
+            "max_tokens": 64,
+            "messages": [{ "role": "user", "content": "lint this" }],
+            "metadata": { "user_id": "session-1" },
+        });
+        let b = json!({
+            "messages": [{ "content": "lint this", "role": "user" }],
+            "stream": false,
+            "max_tokens": 64,
+            "model": "claude-sonnet-4",
+            "metadata": { "user_id": "session-2" },
+        });
+        let key = |body| cache_key("claude", "p1", "/v1/messages", body);
+        assert_eq!(key(&a), key(&b));
+
+        let mut c = a.clone();
+        c["max_tokens"] = json!(128);
+        assert_ne!(key(&a), key(&c));
+        assert_ne!(
+            key(&a),
+            cache_key("claude", "p2", "/v1/messages", &a),
+            "不同供应商不共享缓存"
+        );
+    }
+
+    #[test]
+    fn least_recently_used_entry_is_evicted() {
+        let ttl = Duration::from_secs(60);
+        let mut cache = ResponseCache::default();
+        cache.insert("a".into(), cached("a"), ttl, 2);
+        cache.insert("b".into(), cached("b"), ttl, 2);
+        assert!(cache.get("a", ttl).is_some());
+        cache.insert("c".into(), cached("c"), ttl, 2);
+
+        assert!(cache.get("a", ttl).is_some());
+        assert!(cache.get("b", ttl).is_none());
+        assert!(cache.get("c", ttl).is_some());
+    }
+
+    #[test]
+    fn expired_entries_are_dropped() {
+        let mut cache = ResponseCache::default();
+        let mut stale = cached("old");
+        stale.stored_at = Instant::now() - Duration::from_secs(120);
+        cache.entries.insert("k".into(), stale);
+        assert!(cache.get("k", Duration::from_secs(60)).is_none());
+        assert!(cache.entries.is_empty());
+    }
+}
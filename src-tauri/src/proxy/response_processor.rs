@@ -8,12 +8,14 @@ use super::{
     handler_config::{StreamUsageEventFilter, UsageParserConfig},
     handler_context::{RequestContext, StreamingTimeoutConfig},
     hyper_client::ProxyResponse,
+    route_logging::{self, RouteLogLevel},
     server::ProxyState,
-    sse::{strip_sse_field, take_sse_block},
+    sse::{strip_sse_field, SseBlockScanner},
     usage::parser::TokenUsage,
     ProxyError,
 };
 use crate::database::PRICING_SOURCE_REQUEST;
+use crate::services::prompt_integrity::PromptIntegrityService;
 use axum::http::{header::HeaderMap, HeaderName};
 use axum::response::{IntoResponse, Response};
 use bytes::Bytes;
@@ -81,9 +83,11 @@ pub(crate) fn strip_entity_headers_for_rebuilt_body(headers: &mut HeaderMap) {
 /// 传入 `Duration::ZERO` 表示不启用超时（故障转移关闭时）。
 pub(crate) async fn read_decoded_body(
     response: ProxyResponse,
-    tag: &str,
+    ctx: &RequestContext,
     body_timeout: Duration,
 ) -> Result<(HeaderMap, http::StatusCode, Bytes), ProxyError> {
+    let tag = ctx.tag;
+    let log_level = ctx.route_log_level();
     let mut headers = response.headers().clone();
     let status = response.status();
     let raw_bytes = if body_timeout.is_zero() {
@@ -99,12 +103,21 @@ pub(crate) async fn read_decoded_body(
             })??
     };
 
-    log::debug!(
-        "[{tag}] 已接收上游响应体: status={}, bytes={}, headers={}",
-        status.as_u16(),
-        raw_bytes.len(),
-        format_headers(&headers)
-    );
+    if log_level >= RouteLogLevel::Headers {
+        log::info!(
+            "[{tag}] <<< 响应: status={}, bytes={}, headers={}",
+            status.as_u16(),
+            raw_bytes.len(),
+            route_logging::format_headers_redacted(&headers)
+        );
+    } else if log_level > RouteLogLevel::Silent {
+        log::debug!(
+            "[{tag}] 已接收上游响应体: status={}, bytes={}, headers={}",
+            status.as_u16(),
+            raw_bytes.len(),
+            format_headers(&headers)
+        );
+    }
 
     let mut body_bytes = raw_bytes.clone();
     let mut decoded = false;
@@ -128,6 +141,12 @@ pub(crate) async fn read_decoded_body(
     if decoded {
         strip_entity_headers_for_rebuilt_body(&mut headers);
     }
+    if log_level == RouteLogLevel::FullBodyRedacted {
+        log::info!(
+            "[{tag}] <<< 响应体: {}",
+            route_logging::redact_text(&String::from_utf8_lossy(&body_bytes))
+        );
+    }
 
     Ok((headers, status, body_bytes))
 }
@@ -151,12 +170,22 @@ pub async fn handle_streaming(
     connection_guard: Option<ActiveConnectionGuard>,
 ) -> Response {
     let status = response.status();
-    log::debug!(
-        "[{}] 已接收上游流式响应: status={}, headers={}",
-        ctx.tag,
-        status.as_u16(),
-        format_headers(response.headers())
-    );
+    let log_level = ctx.route_log_level();
+    if log_level >= RouteLogLevel::Headers {
+        log::info!(
+            "[{}] <<< 流式响应: status={}, headers={}",
+            ctx.tag,
+            status.as_u16(),
+            route_logging::format_headers_redacted(response.headers())
+        );
+    } else if log_level > RouteLogLevel::Silent {
+        log::debug!(
+            "[{}] 已接收上游流式响应: status={}, headers={}",
+            ctx.tag,
+            status.as_u16(),
+            format_headers(response.headers())
+        );
+    }
     // 检查流式响应是否被压缩（SSE 通常不压缩，如果压缩则 SSE 解析会失败）
     if let Some(encoding) = get_content_encoding(response.headers()) {
         log::warn!(
@@ -191,6 +220,7 @@ pub async fn handle_streaming(
         ctx.tag,
         usage_collector,
         timeout_config,
+        log_level,
         connection_guard,
     );
 
@@ -221,7 +251,7 @@ pub async fn handle_non_streaming(
             Duration::ZERO
         };
     let (mut response_headers, status, body_bytes) =
-        read_decoded_body(response, ctx.tag, body_timeout).await?;
+        read_decoded_body(response, ctx, body_timeout).await?;
     strip_hop_by_hop_response_headers(&mut response_headers);
 
     log::debug!(
@@ -440,6 +470,8 @@ impl SseUsageFinishGuard {
 impl Drop for SseUsageFinishGuard {
     fn drop(&mut self) {
         if let Some(collector) = self.collector.take() {
+            // 客户端提前断开：按已收到的事件记录（message_start 的 input 等已可计费）
+            log::debug!("SSE 流在结束前被丢弃，按已收集事件记录用量");
             if let Ok(handle) = tokio::runtime::Handle::try_current() {
                 handle.spawn(async move {
                     collector.finish().await;
@@ -489,12 +521,19 @@ pub(crate) fn create_usage_collector(
     let stream_parser = parser_config.stream_parser;
     let model_extractor = parser_config.model_extractor;
     let session_id = ctx.session_id.clone();
+    let retry_count = ctx.retry_count;
+    let prompt_check = ctx
+        .prompt_estimate
+        .map(|estimate| (ctx.provider.clone(), estimate));
 
     Some(SseUsageCollector::new(
         start_time,
         parser_config.stream_event_filter,
         move |events, first_token_ms| {
             if let Some(usage) = stream_parser(&events) {
+                if let Some((provider, estimate)) = &prompt_check {
+                    PromptIntegrityService::observe(app_type_str, provider, *estimate, &usage);
+                }
                 let model = model_extractor(&events, &fallback_model);
                 let latency_ms = start_time.elapsed().as_millis() as u64;
 
@@ -518,6 +557,7 @@ pub(crate) fn create_usage_collector(
                         true, // is_streaming
                         status_code,
                         Some(session_id),
+                        retry_count,
                     )
                     .await;
                 });
@@ -544,6 +584,7 @@ pub(crate) fn create_usage_collector(
                         true, // is_streaming
                         status_code,
                         Some(session_id),
+                        retry_count,
                     )
                     .await;
                 });
@@ -563,6 +604,10 @@ fn spawn_log_usage(
     status_code: u16,
     is_streaming: bool,
 ) {
+    if let Some(estimate) = ctx.prompt_estimate {
+        PromptIntegrityService::observe(ctx.app_type_str, &ctx.provider, estimate, &usage);
+    }
+
     // Check enable_logging before spawning the log task
     if let Ok(config) = state.config.try_read() {
         if !config.enable_logging {
@@ -582,6 +627,7 @@ fn spawn_log_usage(
         .unwrap_or_else(|| ctx.request_model.clone());
     let latency_ms = ctx.latency_ms();
     let session_id = ctx.session_id.clone();
+    let retry_count = ctx.retry_count;
 
     tokio::spawn(async move {
         log_usage_internal(
@@ -597,6 +643,7 @@ fn spawn_log_usage(
             is_streaming,
             status_code,
             Some(session_id),
+            retry_count,
         )
         .await;
     });
@@ -617,7 +664,7 @@ pub(crate) fn usage_logging_enabled(state: &ProxyState) -> bool {
 /// 「按代理发出的请求计价、不信任上游回显」，接管场景下发出的请求模型是
 /// 映射后的 Y 而非客户端别名 X，按 X 计价会用错定价表行。
 #[allow(clippy::too_many_arguments)]
-async fn log_usage_internal(
+pub(crate) async fn log_usage_internal(
     state: &ProxyState,
     provider_id: &str,
     app_type: &str,
@@ -630,6 +677,7 @@ async fn log_usage_internal(
     is_streaming: bool,
     status_code: u16,
     session_id: Option<String>,
+    retry_count: u32,
 ) {
     use super::usage::logger::UsageLogger;
 
@@ -660,6 +708,7 @@ async fn log_usage_internal(
         app_type.to_string(),
         model.to_string(),
         request_model.to_string(),
+        outbound_model.to_string(),
         pricing_model.to_string(),
         usage,
         multiplier,
@@ -669,9 +718,21 @@ async fn log_usage_internal(
         session_id,
         None, // provider_type
         is_streaming,
+        retry_count,
     ) {
         log::warn!("[USG-001] 记录使用量失败: {e}");
     }
+
+    // 首字延迟 SLO：流式取首个事件，非流式取响应耗时
+    let ttfb_ms = first_token_ms.or((!is_streaming).then_some(latency_ms));
+    if let Some(ttfb_ms) = ttfb_ms.filter(|_| (200..300).contains(&status_code)) {
+        crate::services::latency_slo::LatencySloService::on_request_completed(
+            &state.db,
+            app_type,
+            provider_id,
+            ttfb_ms,
+        );
+    }
 }
 
 /// 创建带日志记录和超时控制的透传流
@@ -680,16 +741,17 @@ pub fn create_logged_passthrough_stream(
     tag: &'static str,
     usage_collector: Option<SseUsageCollector>,
     timeout_config: StreamingTimeoutConfig,
+    log_level: RouteLogLevel,
     connection_guard: Option<ActiveConnectionGuard>,
 ) -> impl Stream<Item = Result<Bytes, std::io::Error>> + Send {
     async_stream::stream! {
         let _conn_guard = connection_guard;
-        let mut buffer = String::new();
-        let mut utf8_remainder: Vec<u8> = Vec::new();
+        let mut scanner = SseBlockScanner::default();
         let mut collector = usage_collector;
         let mut finish_guard = collector.clone().map(SseUsageFinishGuard::new);
+        let log_events = log_level == RouteLogLevel::FullBodyRedacted;
         let inspect_sse_events =
-            collector.is_some() || log::log_enabled!(log::Level::Debug);
+            collector.is_some() || log_events || log::log_enabled!(log::Level::Debug);
         let mut is_first_chunk = true;
 
         // 超时配置
@@ -741,35 +803,45 @@ pub fn create_logged_passthrough_stream(
                     }
                     is_first_chunk = false;
                     if inspect_sse_events {
-                        crate::proxy::sse::append_utf8_safe(&mut buffer, &mut utf8_remainder, &bytes);
-
-                        // 尝试解析并记录完整的 SSE 事件
-                        while let Some(event_text) = take_sse_block(&mut buffer) {
-                            if !event_text.trim().is_empty() {
-                                // 提取 data 部分；只有 usage collector 存在时才解析 JSON。
-                                for line in event_text.lines() {
-                                    if let Some(data) = strip_sse_field(line, "data") {
-                                        if data.trim() != "[DONE]" {
-                                            let collected = match &collector {
-                                                Some(c) if c.should_collect(data) => {
-                                                    match serde_json::from_str::<Value>(data) {
-                                                        Ok(json_value) => {
-                                                            c.push(json_value).await;
-                                                            true
-                                                        }
-                                                        Err(_) => false,
-                                                    }
-                                                }
-                                                _ => false,
-                                            };
-                                            log::trace!(
-                                                "[{tag}] <<< SSE data: bytes={}, usage_collected={collected} (content omitted)",
-                                                data.len()
-                                            );
-                                        } else {
-                                            log::debug!("[{tag}] <<< SSE: [DONE]");
+                        // 只按字节切分事件块，透传的 chunk 本身原样转发，不做解码/重组
+                        scanner.push(&bytes);
+
+                        while let Some(block) = scanner.next_block() {
+                            let event_text = String::from_utf8_lossy(&block);
+                            if event_text.trim().is_empty() {
+                                continue;
+                            }
+                            // 提取 data 部分；只有 usage collector 存在时才解析 JSON。
+                            for line in event_text.lines() {
+                                let Some(data) = strip_sse_field(line, "data") else {
+                                    continue;
+                                };
+                                if data.trim() == "[DONE]" {
+                                    log::debug!("[{tag}] <<< SSE: [DONE]");
+                                    continue;
+                                }
+                                let collected = match &collector {
+                                    Some(c) if c.should_collect(data) => {
+                                        match serde_json::from_str::<Value>(data) {
+                                            Ok(json_value) => {
+                                                c.push(json_value).await;
+                                                true
+                                            }
+                                            Err(_) => false,
                                         }
                                     }
+                                    _ => false,
+                                };
+                                if log_events {
+                                    log::info!(
+                                        "[{tag}] <<< SSE data: {}",
+                                        route_logging::redact_text(data)
+                                    );
+                                } else {
+                                    log::trace!(
+                                        "[{tag}] <<< SSE data: bytes={}, usage_collected={collected} (content omitted)",
+                                        data.len()
+                                    );
                                 }
                             }
                         }
@@ -789,6 +861,22 @@ pub fn create_logged_passthrough_stream(
             }
         }
 
+        // 上游最后一个事件可能缺少结尾空行（常见于携带 usage 的终止 chunk），
+        // 流结束时把残留缓冲也交给收集器，避免漏记最终用量。
+        // 与逐块处理一致，先经 should_collect 过滤再解析。
+        if let Some(c) = &collector {
+            let tail = String::from_utf8_lossy(scanner.remainder());
+            for data in tail.lines().filter_map(|line| strip_sse_field(line, "data")) {
+                let data = data.trim();
+                if data.is_empty() || data == "[DONE]" || !c.should_collect(data) {
+                    continue;
+                }
+                if let Ok(json_value) = serde_json::from_str::<Value>(data) {
+                    c.push(json_value).await;
+                }
+            }
+        }
+
         if let Some(c) = collector.take() {
             c.finish().await;
         }
@@ -1001,6 +1089,9 @@ mod tests {
             codex_chat_history: Arc::new(CodexChatHistoryStore::default()),
             app_handle: None,
             failover_manager: Arc::new(FailoverSwitchManager::new(db)),
+            metrics: Arc::new(super::super::metrics::ProxyMetrics::default()),
+            auth: Arc::new(crate::proxy::auth::ProxyAuth::default()),
+            in_flight: Arc::new(crate::proxy::drain::InFlightTracker::default()),
         }
     }
 
@@ -1078,6 +1169,7 @@ mod tests {
             false,
             200,
             None,
+            0,
         )
         .await;
 
@@ -1148,6 +1240,7 @@ mod tests {
             false,
             200,
             None,
+            0,
         )
         .await;
 
@@ -1228,6 +1321,7 @@ mod tests {
             false,
             200,
             None,
+            0,
         )
         .await;
 
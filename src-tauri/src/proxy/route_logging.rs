@@ -0,0 +1,256 @@
+//! 按路由 / 供应商调整代理日志详细程度
+//!
+//! 规则只保存在内存中、即时生效，无需重启代理：排查某个异常中转时只放大它的日志，
+//! 不会被其他流量淹没。供应商级规则优先于路由（应用）级；都未设置时为 `Summary`，
+//! 即原有的日志行为。放大后的日志用 info 级别输出，不依赖全局日志级别。
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+use axum::http::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 日志详细程度
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum RouteLogLevel {
+    /// 不输出逐请求日志
+    Silent,
+    /// 请求目标与响应概要（默认）
+    #[default]
+    Summary,
+    /// 额外输出请求/响应头，凭据类头只保留名称
+    Headers,
+    /// 额外输出脱敏后的请求/响应体
+    FullBodyRedacted,
+}
+
+/// 一条日志规则；`provider_id` 为空表示作用于整个路由
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteLogRule {
+    pub app: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_id: Option<String>,
+    pub level: RouteLogLevel,
+}
+
+type RuleKey = (String, Option<String>);
+
+static RULES: LazyLock<RwLock<HashMap<RuleKey, RouteLogLevel>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// 单个字符串值在日志中保留的最大字符数（图片 base64 等大字段会被截断）
+const MAX_LOGGED_STRING_CHARS: usize = 512;
+
+/// 值会被整体隐藏的字段名（小写比较）
+const SENSITIVE_KEYS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "authorization",
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "token",
+    "password",
+    "secret",
+    "client_secret",
+];
+
+/// 凭据类请求/响应头，只输出名称
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "x-api-key",
+    "x-goog-api-key",
+    "api-key",
+    "cookie",
+    "set-cookie",
+];
+
+/// 设置规则；`level` 为 None 时移除规则，恢复上一级或默认
+pub fn set_rule(app: &str, provider_id: Option<&str>, level: Option<RouteLogLevel>) {
+    let key = (
+        app.to_string(),
+        provider_id
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string),
+    );
+    let Ok(mut rules) = RULES.write() else {
+        return;
+    };
+    match level {
+        Some(level) => {
+            log::info!(
+                "[Proxy] 日志级别: {}{} -> {level:?}",
+                key.0,
+                key.1
+                    .as_deref()
+                    .map(|id| format!("/{id}"))
+                    .unwrap_or_default()
+            );
+            rules.insert(key, level);
+        }
+        None => {
+            rules.remove(&key);
+        }
+    }
+}
+
+/// 当前所有规则（按应用、供应商排序）
+pub fn rules() -> Vec<RouteLogRule> {
+    let Ok(rules) = RULES.read() else {
+        return Vec::new();
+    };
+    let mut list: Vec<RouteLogRule> = rules
+        .iter()
+        .map(|((app, provider_id), level)| RouteLogRule {
+            app: app.clone(),
+            provider_id: provider_id.clone(),
+            level: *level,
+        })
+        .collect();
+    list.sort_by(|a, b| (&a.app, &a.provider_id).cmp(&(&b.app, &b.provider_id)));
+    list
+}
+
+/// 某个供应商请求的生效级别：供应商规则 > 路由规则 > 默认
+pub fn level_for(app: &str, provider_id: &str) -> RouteLogLevel {
+    let Ok(rules) = RULES.read() else {
+        return RouteLogLevel::default();
+    };
+    if rules.is_empty() {
+        return RouteLogLevel::default();
+    }
+    rules
+        .get(&(app.to_string(), Some(provider_id.to_string())))
+        .or_else(|| rules.get(&(app.to_string(), None)))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// 输出请求/响应头：凭据类头只保留名称，其余截断后输出
+pub fn format_headers_redacted(headers: &HeaderMap) -> String {
+    let mut entries: Vec<String> = headers
+        .iter()
+        .map(|(name, value)| {
+            let name = name.as_str();
+            if SENSITIVE_HEADERS.contains(&name) {
+                return format!("{name}=[REDACTED]");
+            }
+            format!(
+                "{name}={}",
+                truncate(&String::from_utf8_lossy(value.as_bytes()))
+            )
+        })
+        .collect();
+    entries.sort();
+    format!("[{}]", entries.join(", "))
+}
+
+/// 输出 JSON 体：隐藏凭据字段与已知密钥，截断超长字符串
+pub fn redact_body(body: &Value, known_secrets: &[String]) -> String {
+    let text = redact_value(body).to_string();
+    crate::redact_known_secrets(&text, known_secrets)
+}
+
+/// 输出非 JSON 或 SSE 文本：尝试按 JSON 脱敏，失败则只截断
+pub fn redact_text(text: &str) -> String {
+    match serde_json::from_str::<Value>(text) {
+        Ok(value) => redact_body(&value, &[]),
+        Err(_) => truncate(text),
+    }
+}
+
+fn redact_value(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let lower = key.to_ascii_lowercase();
+                    let value = if SENSITIVE_KEYS.contains(&lower.as_str()) {
+                        Value::String("[REDACTED]".to_string())
+                    } else {
+                        redact_value(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact_value).collect()),
+        Value::String(text) => Value::String(truncate(text)),
+        other => other.clone(),
+    }
+}
+
+fn truncate(text: &str) -> String {
+    let count = text.chars().count();
+    if count <= MAX_LOGGED_STRING_CHARS {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(MAX_LOGGED_STRING_CHARS).collect();
+    format!("{kept}…(+{} chars)", count - MAX_LOGGED_STRING_CHARS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn provider_rule_overrides_route_rule() {
+        set_rule("test-route", None, Some(RouteLogLevel::Silent));
+        set_rule(
+            "test-route",
+            Some("noisy"),
+            Some(RouteLogLevel::FullBodyRedacted),
+        );
+        assert_eq!(
+            level_for("test-route", "noisy"),
+            RouteLogLevel::FullBodyRedacted
+        );
+        assert_eq!(level_for("test-route", "other"), RouteLogLevel::Silent);
+
+        set_rule("test-route", None, None);
+        assert_eq!(level_for("test-route", "other"), RouteLogLevel::Summary);
+        set_rule("test-route", Some("noisy"), None);
+        assert!(rules().iter().all(|rule| rule.app != "test-route"));
+    }
+
+    #[test]
+    fn body_redaction_hides_credentials_and_truncates() {
+        let body = json!({
+            "model": "claude-sonnet",
+            "metadata": { "api_key": "sk-inline" },
+            "messages": [{ "role": "user", "content": "x".repeat(600) }],
+            "note": "uses sk-known-secret-123"
+        });
+        let text = redact_body(&body, &["sk-known-secret-123".to_string()]);
+        assert!(!text.contains("sk-inline"), "{text}");
+        assert!(!text.contains("sk-known-secret-123"), "{text}");
+        assert!(text.contains("(+88 chars)"), "{text}");
+        assert!(text.contains("claude-sonnet"), "{text}");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", "sk-header".parse().unwrap());
+        headers.insert("anthropic-version", "2023-06-01".parse().unwrap());
+        let formatted = format_headers_redacted(&headers);
+        assert!(!formatted.contains("sk-header"), "{formatted}");
+        assert!(
+            formatted.contains("anthropic-version=2023-06-01"),
+            "{formatted}"
+        );
+    }
+
+    #[test]
+    fn level_names_use_kebab_case() {
+        assert_eq!(
+            serde_json::to_value(RouteLogLevel::FullBodyRedacted).unwrap(),
+            json!("full-body-redacted")
+        );
+    }
+}
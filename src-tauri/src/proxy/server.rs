@@ -9,26 +9,113 @@
 //! a direct (non-proxied) CLI request.
 
 use super::{
+    auth,
+    drain::{self, DrainPhase},
     failover_switch::FailoverSwitchManager,
     handlers,
     log_codes::srv as log_srv,
+    metrics::ProxyMetrics,
     provider_router::ProviderRouter,
     providers::{codex_chat_history::CodexChatHistoryStore, gemini_shadow::GeminiShadowStore},
     types::*,
-    ProxyError,
+    websocket, ProxyError,
 };
 use crate::database::Database;
 use axum::{
     extract::DefaultBodyLimit,
+    middleware,
     routing::{any, get, post},
     Router,
 };
 use hyper_util::rt::TokioIo;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::{oneshot, RwLock};
+use tokio::sync::{watch, RwLock};
 use tokio::task::JoinHandle;
 
+/// 本地代理的默认监听端口
+pub const DEFAULT_LISTEN_PORT: u16 = 15721;
+
+/// 询问端口上的 CC Switch 代理属于哪个系统用户；不是 CC Switch 代理时返回 `None`
+async fn port_owner(addr: SocketAddr) -> Option<String> {
+    let host = match addr.ip() {
+        ip if ip.is_unspecified() && ip.is_ipv6() => "[::1]".to_string(),
+        ip if ip.is_unspecified() => "127.0.0.1".to_string(),
+        std::net::IpAddr::V6(ip) => format!("[{ip}]"),
+        std::net::IpAddr::V4(ip) => ip.to_string(),
+    };
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(std::time::Duration::from_secs(2))
+        .build()
+        .ok()?;
+    let body: serde_json::Value = client
+        .get(format!("http://{host}:{}/health", addr.port()))
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+    if body.get("status").and_then(|v| v.as_str()) != Some("healthy") {
+        return None;
+    }
+    // 旧版本的 /health 不带 user 字段
+    Some(
+        body.get("user")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+    )
+}
+
+/// 绑定监听端口
+///
+/// 共享机器上默认端口可能已被其他用户的 CC Switch 占用：此时改用当前用户的专属端口
+/// （见 [`crate::os_user::per_user_port`]），其余占用情况给出明确的错误。
+async fn bind_listener(addr: SocketAddr) -> Result<tokio::net::TcpListener, ProxyError> {
+    let err = match tokio::net::TcpListener::bind(&addr).await {
+        Ok(listener) => return Ok(listener),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => e,
+        Err(e) => return Err(ProxyError::BindFailed(e.to_string())),
+    };
+
+    let port = addr.port();
+    let current = crate::os_user::user_name();
+    match port_owner(addr).await {
+        Some(owner) if owner == current => Err(ProxyError::BindFailed(format!(
+            "端口 {port} 已被当前用户的另一个 CC Switch 代理占用"
+        ))),
+        Some(owner) => {
+            let owner = if owner.is_empty() {
+                "其他用户".to_string()
+            } else {
+                format!("用户 {owner}")
+            };
+            let fallback = crate::os_user::per_user_port(port)
+                .filter(|_| port == DEFAULT_LISTEN_PORT)
+                .ok_or_else(|| {
+                    ProxyError::BindFailed(format!(
+                        "端口 {port} 已被{owner}的 CC Switch 代理占用，请在代理设置中换一个端口"
+                    ))
+                })?;
+            log::warn!(
+                "默认端口 {port} 已被{owner}的 CC Switch 代理占用，改用当前用户的端口 {fallback}"
+            );
+            tokio::net::TcpListener::bind(SocketAddr::new(addr.ip(), fallback))
+                .await
+                .map_err(|e| {
+                    ProxyError::BindFailed(format!(
+                        "默认端口 {port} 已被{owner}占用，当前用户的端口 {fallback} 也无法使用（{e}），请在代理设置中指定端口"
+                    ))
+                })
+        }
+        None => Err(ProxyError::BindFailed(format!(
+            "端口 {port} 已被其他程序占用（{err}），请在代理设置中换一个端口"
+        ))),
+    }
+}
+
 /// 代理服务器状态（共享）
 #[derive(Clone)]
 pub struct ProxyState {
@@ -48,13 +135,20 @@ pub struct ProxyState {
     pub app_handle: Option<tauri::AppHandle>,
     /// 故障转移切换管理器
     pub failover_manager: Arc<FailoverSwitchManager>,
+    /// 请求指标（`/metrics`）
+    pub metrics: Arc<ProxyMetrics>,
+    /// 本服务器的鉴权开关与访问令牌（按自身数据库加载）
+    pub auth: Arc<auth::ProxyAuth>,
+    /// 本服务器的进行中请求（停止时据此排空）
+    pub in_flight: Arc<drain::InFlightTracker>,
 }
 
 /// 代理HTTP服务器
 pub struct ProxyServer {
     config: ProxyConfig,
     state: ProxyState,
-    shutdown_tx: Arc<RwLock<Option<oneshot::Sender<()>>>>,
+    /// 停机阶段广播：accept 循环与每个连接任务都订阅
+    shutdown_tx: Arc<RwLock<Option<watch::Sender<DrainPhase>>>>,
     /// 服务器任务句柄，用于等待服务器实际关闭
     server_handle: Arc<RwLock<Option<JoinHandle<()>>>>,
 }
@@ -81,6 +175,9 @@ impl ProxyServer {
             codex_chat_history: Arc::new(CodexChatHistoryStore::default()),
             app_handle,
             failover_manager,
+            metrics: Arc::new(ProxyMetrics::default()),
+            auth: Arc::new(auth::ProxyAuth::default()),
+            in_flight: Arc::new(drain::InFlightTracker::default()),
         };
 
         Self {
@@ -103,15 +200,18 @@ impl ProxyServer {
                 .map_err(|e| ProxyError::BindFailed(format!("无效的地址: {e}")))?;
 
         // 创建关闭通道
-        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let (shutdown_tx, shutdown_rx) = watch::channel(DrainPhase::Running);
+
+        // 加载代理鉴权状态（开关与访问令牌）
+        if let Err(e) = self.state.auth.reload(&self.state.db) {
+            log::warn!("加载代理鉴权配置失败: {e}");
+        }
 
         // 构建路由
         let app = self.build_router();
 
         // 绑定监听器
-        let listener = tokio::net::TcpListener::bind(&addr)
-            .await
-            .map_err(|e| ProxyError::BindFailed(e.to_string()))?;
+        let listener = bind_listener(addr).await?;
         let local_addr = listener
             .local_addr()
             .map_err(|e| ProxyError::BindFailed(e.to_string()))?;
@@ -119,9 +219,6 @@ impl ProxyServer {
 
         log::info!("[{}] 代理服务器启动于 {local_addr}", log_srv::STARTED);
 
-        // 更新全局代理端口，用于系统代理检测
-        crate::proxy::http_client::set_proxy_port(actual_port);
-
         // 保存关闭句柄
         *self.shutdown_tx.write().await = Some(shutdown_tx);
 
@@ -152,7 +249,13 @@ impl ProxyServer {
                             }
                         };
 
+                        // SSE 事件通常很小，关闭 Nagle 避免逐事件的转发延迟
+                        if let Err(e) = stream.set_nodelay(true) {
+                            log::debug!("[ProxyServer] set_nodelay failed (non-fatal): {e}");
+                        }
+
                         let app = app.clone();
+                        let mut phase_rx = shutdown_rx.clone();
                         tokio::spawn(async move {
                             // Peek raw TCP bytes to capture original header casing
                             // before hyper parses (and lowercases) the header names.
@@ -191,17 +294,34 @@ impl ProxyServer {
                                 }
                             });
 
-                            if let Err(e) = hyper::server::conn::http1::Builder::new()
+                            let conn = hyper::server::conn::http1::Builder::new()
                                 .preserve_header_case(true)
                                 .serve_connection(TokioIo::new(stream), service)
-                                .await
-                            {
+                                // WebSocket / Realtime 透传需要 HTTP/1.1 Upgrade
+                                .with_upgrades();
+                            tokio::pin!(conn);
+
+                            // 排空阶段：当前响应（含流式 body）结束后关闭连接，不再处理
+                            // keep-alive 上的新请求；强制关闭阶段直接丢弃连接。
+                            let result = loop {
+                                tokio::select! {
+                                    result = conn.as_mut() => break result,
+                                    changed = phase_rx.changed() => {
+                                        let phase = *phase_rx.borrow_and_update();
+                                        if changed.is_err() || phase == DrainPhase::Closed {
+                                            break Ok(());
+                                        }
+                                        conn.as_mut().graceful_shutdown();
+                                    }
+                                }
+                            };
+                            if let Err(e) = result {
                                 // Connection reset / broken pipe 等在代理场景下很常见，debug 级别
                                 log::debug!("[{SRV}] connection error: {e}", SRV = log_srv::CONN_ERR);
                             }
                         });
                     }
-                    _ = &mut shutdown_rx => {
+                    _ = shutdown_rx.changed() => {
                         break;
                     }
                 }
@@ -223,14 +343,40 @@ impl ProxyServer {
     }
 
     pub async fn stop(&self) -> Result<(), ProxyError> {
-        // 1. 发送关闭信号
-        if let Some(tx) = self.shutdown_tx.write().await.take() {
-            let _ = tx.send(());
-        } else {
+        // 1. 发送排空信号：停止接受新连接，已有连接在当前响应结束后关闭
+        let Some(tx) = self.shutdown_tx.write().await.take() else {
             return Err(ProxyError::NotRunning);
+        };
+        let _ = tx.send(DrainPhase::Draining);
+
+        // 2. 等待进行中的请求在原供应商上完成（受排空超时限制），随后强制关闭剩余连接
+        let drain_timeout = self
+            .state
+            .db
+            .get_drain_config()
+            .unwrap_or_default()
+            .timeout();
+        let tracker = self.state.in_flight.clone();
+        if !tracker.is_empty() && !drain_timeout.is_zero() {
+            log::info!(
+                "[{}] 等待 {} 个进行中的请求完成（最长 {}s）",
+                log_srv::DRAINING,
+                tracker.len(),
+                drain_timeout.as_secs()
+            );
+            self.state.status.write().await.draining = true;
+            if !tracker.wait_idle(drain_timeout).await {
+                log::warn!(
+                    "[{}] 排空超时，强制关闭 {} 个仍在进行的请求",
+                    log_srv::DRAIN_TIMEOUT,
+                    tracker.len()
+                );
+            }
+            self.state.status.write().await.draining = false;
         }
+        let _ = tx.send(DrainPhase::Closed);
 
-        // 2. 等待服务器任务结束（带 5 秒超时保护）
+        // 3. 等待服务器任务结束（带 5 秒超时保护）
         if let Some(handle) = self.server_handle.write().await.take() {
             match tokio::time::timeout(std::time::Duration::from_secs(5), handle).await {
                 Ok(Ok(())) => {
@@ -272,6 +418,11 @@ impl ProxyServer {
                 provider_name: provider_name.clone(),
             })
             .collect();
+        let current: std::collections::BTreeMap<String, String> = current_providers
+            .iter()
+            .map(|(app_type, (provider_id, _))| (app_type.clone(), provider_id.clone()))
+            .collect();
+        status.in_flight = self.state.in_flight.snapshot(&current);
 
         status
     }
@@ -293,9 +444,21 @@ impl ProxyServer {
             // 健康检查
             .route("/health", get(handlers::health_check))
             .route("/status", get(handlers::get_status))
+            .route("/healthz", get(handlers::healthz))
+            .route("/metrics", get(handlers::metrics))
             // Claude API (支持带前缀和不带前缀两种格式)
             .route("/v1/messages", post(handlers::handle_messages))
             .route("/claude/v1/messages", post(handlers::handle_messages))
+            // count_tokens / models：原生上游透传，转换型上游本地估算 / 合成
+            .route(
+                "/v1/messages/count_tokens",
+                post(handlers::handle_count_tokens),
+            )
+            .route(
+                "/claude/v1/messages/count_tokens",
+                post(handlers::handle_count_tokens),
+            )
+            .route("/claude/v1/models", get(handlers::handle_claude_models))
             // Claude Desktop 3P 本地 gateway（独立 provider namespace）
             .route(
                 "/claude-desktop/v1/models",
@@ -323,10 +486,23 @@ impl ProxyServer {
             .route("/models", get(handlers::handle_models))
             .route("/v1/models", get(handlers::handle_models))
             // OpenAI Responses API (Codex CLI，支持带前缀和不带前缀)
-            .route("/responses", post(handlers::handle_responses))
-            .route("/v1/responses", post(handlers::handle_responses))
+            .route(
+                "/responses",
+                post(handlers::handle_responses).get(websocket::handle_codex_websocket),
+            )
+            .route(
+                "/v1/responses",
+                post(handlers::handle_responses).get(websocket::handle_codex_websocket),
+            )
             .route("/v1/v1/responses", post(handlers::handle_responses))
-            .route("/codex/v1/responses", post(handlers::handle_responses))
+            .route(
+                "/codex/v1/responses",
+                post(handlers::handle_responses).get(websocket::handle_codex_websocket),
+            )
+            // OpenAI Realtime API（WebSocket 透传）
+            .route("/realtime", get(websocket::handle_codex_websocket))
+            .route("/v1/realtime", get(websocket::handle_codex_websocket))
+            .route("/codex/v1/realtime", get(websocket::handle_codex_websocket))
             // Grok Build uses the Responses protocol but has an independent
             // provider namespace and failover queue.
             .route(
@@ -364,11 +540,32 @@ impl ProxyServer {
             .route("/gemini/v1beta/*path", any(handlers::handle_gemini))
             // Gemini 的 GA 版本也叫 /v1，给原 SDK 留一条出口
             .route("/gemini/v1/*path", any(handlers::handle_gemini))
+            // 开启代理鉴权时校验访问令牌（健康检查除外）
+            .layer(middleware::from_fn_with_state(
+                self.state.clone(),
+                auth::require_client_token,
+            ))
             // 提高默认请求体大小限制（避免 413 Payload Too Large）
             .layer(DefaultBodyLimit::max(200 * 1024 * 1024))
             .with_state(self.state.clone())
     }
 
+    /// 当前仍在非目标供应商上完成的请求数（`current` 为 app_type -> 当前目标供应商 id）
+    pub fn draining_count(&self, current: &std::collections::BTreeMap<String, String>) -> usize {
+        self.state
+            .in_flight
+            .snapshot(current)
+            .iter()
+            .filter(|group| group.draining)
+            .map(|group| group.count)
+            .sum()
+    }
+
+    /// 数据库中的鉴权开关或令牌变更后重新加载
+    pub fn reload_auth(&self) -> Result<(), crate::error::AppError> {
+        self.state.auth.reload(&self.state.db)
+    }
+
     /// 在不重启服务的情况下更新运行时配置
     pub async fn apply_runtime_config(&self, config: &ProxyConfig) {
         *self.state.config.write().await = config.clone();
@@ -0,0 +1,127 @@
+//! 会话亲和（Sticky Session）
+//!
+//! Anthropic 等上游的 prompt caching 只在同一供应商上生效：故障转移队列中的请求
+//! 若在多个供应商之间来回切换，缓存会反复失效。这里记录"会话 → 最近成功的供应商"，
+//! 后续同一会话的请求（含重试）优先路由到该供应商。
+//!
+//! - 仅使用客户端提供的 Session ID（生成的 UUID 每个请求都不同，没有亲和意义）
+//! - 首选供应商熔断/不在可用列表中时按原队列顺序故障转移，成功后重新绑定
+//! - 绑定在 TTL 内未被使用即失效；设置中可关闭（`proxySessionAffinity = false`）
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::provider::Provider;
+
+/// 超过该条目数时在写入前清理过期绑定
+const PRUNE_THRESHOLD: usize = 1024;
+
+#[derive(Debug, Clone)]
+struct AffinityEntry {
+    provider_id: String,
+    last_used: Instant,
+}
+
+/// 会话 → 供应商绑定表
+#[derive(Debug, Default)]
+pub struct SessionAffinity {
+    entries: Mutex<HashMap<String, AffinityEntry>>,
+}
+
+fn affinity_key(app_type: &str, session_id: &str) -> String {
+    format!("{app_type}:{session_id}")
+}
+
+impl SessionAffinity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 将会话绑定的供应商移到列表最前（若其仍可用），返回是否命中
+    pub fn prefer(
+        &self,
+        app_type: &str,
+        session_id: &str,
+        ttl: Duration,
+        providers: &mut [Provider],
+    ) -> bool {
+        let key = affinity_key(app_type, session_id);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(entry) = entries.get_mut(&key) else {
+            return false;
+        };
+        if entry.last_used.elapsed() >= ttl {
+            entries.remove(&key);
+            return false;
+        }
+
+        let Some(index) = providers.iter().position(|p| p.id == entry.provider_id) else {
+            return false;
+        };
+        entry.last_used = Instant::now();
+        providers[..=index].rotate_right(1);
+        true
+    }
+
+    /// 记录会话最近一次成功使用的供应商
+    pub fn bind(&self, app_type: &str, session_id: &str, provider_id: &str, ttl: Duration) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= PRUNE_THRESHOLD {
+            entries.retain(|_, entry| entry.last_used.elapsed() < ttl);
+        }
+        entries.insert(
+            affinity_key(app_type, session_id),
+            AffinityEntry {
+                provider_id: provider_id.to_string(),
+                last_used: Instant::now(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn providers(ids: &[&str]) -> Vec<Provider> {
+        ids.iter()
+            .map(|id| Provider::with_id(id.to_string(), id.to_string(), json!({}), None))
+            .collect()
+    }
+
+    fn ids(providers: &[Provider]) -> Vec<&str> {
+        providers.iter().map(|p| p.id.as_str()).collect()
+    }
+
+    #[test]
+    fn bound_provider_moves_to_front_preserving_rest_order() {
+        let affinity = SessionAffinity::new();
+        let ttl = Duration::from_secs(60);
+        affinity.bind("claude", "s1", "c", ttl);
+
+        let mut list = providers(&["a", "b", "c", "d"]);
+        assert!(affinity.prefer("claude", "s1", ttl, &mut list));
+        assert_eq!(ids(&list), vec!["c", "a", "b", "d"]);
+
+        // 其他会话 / 其他应用不受影响
+        let mut list = providers(&["a", "b", "c"]);
+        assert!(!affinity.prefer("claude", "s2", ttl, &mut list));
+        assert!(!affinity.prefer("codex", "s1", ttl, &mut list));
+        assert_eq!(ids(&list), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn unavailable_or_expired_binding_keeps_queue_order() {
+        let affinity = SessionAffinity::new();
+        affinity.bind("claude", "s1", "gone", Duration::from_secs(60));
+        let mut list = providers(&["a", "b"]);
+        assert!(!affinity.prefer("claude", "s1", Duration::from_secs(60), &mut list));
+        assert_eq!(ids(&list), vec!["a", "b"]);
+
+        affinity.bind("claude", "s1", "b", Duration::from_secs(60));
+        assert!(!affinity.prefer("claude", "s1", Duration::ZERO, &mut list));
+        assert_eq!(ids(&list), vec!["a", "b"]);
+    }
+}
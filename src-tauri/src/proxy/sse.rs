@@ -1,7 +1,9 @@
+use bytes::{Bytes, BytesMut};
+
 #[inline]
 pub(crate) fn strip_sse_field<'a>(line: &'a str, field: &str) -> Option<&'a str> {
-    line.strip_prefix(&format!("{field}: "))
-        .or_else(|| line.strip_prefix(&format!("{field}:")))
+    let value = line.strip_prefix(field)?.strip_prefix(':')?;
+    Some(value.strip_prefix(' ').unwrap_or(value))
 }
 
 #[inline]
@@ -22,6 +24,59 @@ pub(crate) fn take_sse_block(buffer: &mut String) -> Option<String> {
     Some(block)
 }
 
+/// Byte-level SSE block splitter for the passthrough hot path.
+///
+/// Unlike [`take_sse_block`], chunks are never decoded into a `String`: raw
+/// bytes are appended to a `BytesMut` and complete blocks are split off as
+/// `Bytes` views sharing the same allocation. Delimiters are ASCII, so every
+/// complete block is on a UTF-8 boundary and callers can borrow `&str` from it
+/// without copying.
+#[derive(Debug, Default)]
+pub(crate) struct SseBlockScanner {
+    buffer: BytesMut,
+    /// Bytes before this offset are known not to start a delimiter.
+    scanned: usize,
+}
+
+impl SseBlockScanner {
+    pub(crate) fn push(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Split off the next complete block (without its delimiter).
+    pub(crate) fn next_block(&mut self) -> Option<Bytes> {
+        let found = (self.scanned..self.buffer.len()).find_map(|pos| {
+            let rest = &self.buffer[pos..];
+            if rest.starts_with(b"\r\n\r\n") {
+                Some((pos, 4))
+            } else if rest.starts_with(b"\n\n") {
+                Some((pos, 2))
+            } else {
+                None
+            }
+        });
+
+        match found {
+            Some((pos, len)) => {
+                let mut block = self.buffer.split_to(pos + len);
+                block.truncate(pos);
+                self.scanned = 0;
+                Some(block.freeze())
+            }
+            None => {
+                // A delimiter may straddle the next chunk; rescan the last 3 bytes.
+                self.scanned = self.buffer.len().saturating_sub(3);
+                None
+            }
+        }
+    }
+
+    /// Bytes received after the last complete block.
+    pub(crate) fn remainder(&self) -> &[u8] {
+        &self.buffer
+    }
+}
+
 /// Append raw bytes to a UTF-8 `String` buffer, correctly handling multi-byte
 /// characters that are split across chunk boundaries.
 ///
@@ -87,7 +142,7 @@ pub(crate) fn append_utf8_safe(buffer: &mut String, remainder: &mut Vec<u8>, new
 
 #[cfg(test)]
 mod tests {
-    use super::{append_utf8_safe, strip_sse_field, take_sse_block};
+    use super::{append_utf8_safe, strip_sse_field, take_sse_block, SseBlockScanner};
 
     #[test]
     fn strip_sse_field_accepts_optional_space() {
@@ -132,6 +187,37 @@ mod tests {
         assert_eq!(buffer, "rest");
     }
 
+    #[test]
+    fn scanner_splits_blocks_across_chunks() {
+        let mut scanner = SseBlockScanner::default();
+        scanner.push(b"data: {\"a\":1}\r\n\r");
+        assert_eq!(scanner.next_block(), None);
+
+        scanner.push(b"\ndata: {\"b\":2}\n\ndata: tail");
+        assert_eq!(
+            scanner.next_block().as_deref(),
+            Some(&b"data: {\"a\":1}"[..])
+        );
+        assert_eq!(
+            scanner.next_block().as_deref(),
+            Some(&b"data: {\"b\":2}"[..])
+        );
+        assert_eq!(scanner.next_block(), None);
+        assert_eq!(scanner.remainder(), b"data: tail");
+    }
+
+    #[test]
+    fn scanner_keeps_multibyte_characters_intact() {
+        let payload = "data: 你好\n\n".as_bytes();
+        let mut scanner = SseBlockScanner::default();
+        scanner.push(&payload[..7]);
+        assert_eq!(scanner.next_block(), None);
+        scanner.push(&payload[7..]);
+
+        let block = scanner.next_block().expect("block");
+        assert_eq!(std::str::from_utf8(&block), Ok("data: 你好"));
+    }
+
     // ------------------------------------------------------------------
     // append_utf8_safe tests
     // ------------------------------------------------------------------
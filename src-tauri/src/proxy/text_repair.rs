@@ -0,0 +1,343 @@
+//! 响应文本编码修复（供应商级开关 `repairResponseText`）
+//!
+//! 部分中转会弄坏上游返回的中日韩文本，常见三种：
+//! - 乱码：UTF-8 字节被当作 Latin-1 / Windows-1252 解码后再以 UTF-8 输出（`ä½ å¥½` → `你好`）
+//! - 双重转义：JSON 解析后的字符串里仍是字面量 `你好`
+//! - 整个响应体以 GBK 编码返回
+//!
+//! 修复在转发层、格式转换之前进行，作用于 JSON 响应体与 SSE 事件 `data:` 中的字符串值。
+//! 为避免误伤正常内容，只有修复结果包含 CJK 字符时才会替换；每个响应修复后记录一条日志。
+
+use std::pin::Pin;
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use http::header::{CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE};
+use serde_json::Value;
+
+use super::error::ProxyError;
+use super::hyper_client::ProxyResponse;
+use super::sse::{strip_sse_field, SseBlockScanner};
+
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x3000..=0x30FF     // CJK 标点、平假名、片假名
+            | 0x3400..=0x4DBF // 扩展 A
+            | 0x4E00..=0x9FFF // 基本汉字
+            | 0xAC00..=0xD7AF // 韩文音节
+            | 0xF900..=0xFAFF // 兼容汉字
+            | 0xFF00..=0xFFEF // 全角符号
+    )
+}
+
+/// Windows-1252 在 0x80..=0x9F 区间映射到的字符（反向查表）
+const CP1252_HIGH: [(char, u8); 27] = [
+    ('€', 0x80),
+    ('‚', 0x82),
+    ('ƒ', 0x83),
+    ('„', 0x84),
+    ('…', 0x85),
+    ('†', 0x86),
+    ('‡', 0x87),
+    ('ˆ', 0x88),
+    ('‰', 0x89),
+    ('Š', 0x8A),
+    ('‹', 0x8B),
+    ('Œ', 0x8C),
+    ('Ž', 0x8E),
+    ('‘', 0x91),
+    ('’', 0x92),
+    ('“', 0x93),
+    ('”', 0x94),
+    ('•', 0x95),
+    ('–', 0x96),
+    ('—', 0x97),
+    ('˜', 0x98),
+    ('™', 0x99),
+    ('š', 0x9A),
+    ('›', 0x9B),
+    ('œ', 0x9C),
+    ('ž', 0x9E),
+    ('Ÿ', 0x9F),
+];
+
+/// 把字符还原为它在 Latin-1 / Windows-1252 中的单字节
+fn single_byte(c: char) -> Option<u8> {
+    match c as u32 {
+        code @ 0..=0xFF => Some(code as u8),
+        _ => CP1252_HIGH
+            .iter()
+            .find(|(mapped, _)| *mapped == c)
+            .map(|(_, byte)| *byte),
+    }
+}
+
+/// 修复"UTF-8 被当作单字节编码解码"的乱码
+fn repair_mojibake(text: &str) -> Option<String> {
+    if text.is_ascii() || text.chars().any(is_cjk) {
+        return None;
+    }
+    let bytes: Vec<u8> = text.chars().map(single_byte).collect::<Option<_>>()?;
+    let repaired = String::from_utf8(bytes).ok()?;
+    repaired.chars().any(is_cjk).then_some(repaired)
+}
+
+fn parse_hex4(chars: &[char]) -> Option<u32> {
+    if chars.len() < 4 {
+        return None;
+    }
+    chars[..4]
+        .iter()
+        .try_fold(0u32, |acc, c| Some(acc * 16 + c.to_digit(16)?))
+}
+
+/// 解码字符串中字面量的 `\uXXXX`（含代理对）
+fn decode_unicode_escapes(text: &str) -> Option<String> {
+    if !text.contains("\\u") {
+        return None;
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut decoded_cjk = false;
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '\\' && chars.get(i + 1) == Some(&'u') {
+            if let Some(high) = parse_hex4(&chars[i + 2..]) {
+                let (code, consumed) = if (0xD800..0xDC00).contains(&high)
+                    && chars.get(i + 6) == Some(&'\\')
+                    && chars.get(i + 7) == Some(&'u')
+                {
+                    match parse_hex4(&chars[i + 8..]) {
+                        Some(low) if (0xDC00..0xE000).contains(&low) => {
+                            (0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00), 12)
+                        }
+                        _ => (high, 6),
+                    }
+                } else {
+                    (high, 6)
+                };
+                if let Some(c) = char::from_u32(code) {
+                    decoded_cjk |= is_cjk(c);
+                    out.push(c);
+                    i += consumed;
+                    continue;
+                }
+            }
+        }
+        out.push(chars[i]);
+        i += 1;
+    }
+    decoded_cjk.then_some(out)
+}
+
+/// 修复单个字符串；无需修复时返回 None
+pub fn repair_text(text: &str) -> Option<String> {
+    match decode_unicode_escapes(text) {
+        Some(decoded) => Some(repair_mojibake(&decoded).unwrap_or(decoded)),
+        None => repair_mojibake(text),
+    }
+}
+
+/// 递归修复 JSON 中的所有字符串值，返回修复的数量
+pub fn repair_value(value: &mut Value) -> usize {
+    match value {
+        Value::String(text) => match repair_text(text) {
+            Some(repaired) => {
+                *text = repaired;
+                1
+            }
+            None => 0,
+        },
+        Value::Array(items) => items.iter_mut().map(repair_value).sum(),
+        Value::Object(map) => map.values_mut().map(repair_value).sum(),
+        _ => 0,
+    }
+}
+
+/// 非 UTF-8 内容按 GBK（GB18030）解码；解码出错时放弃
+fn decode_text(bytes: &[u8]) -> Option<(String, bool)> {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Some((text.to_string(), false));
+    }
+    let (text, had_errors) = encoding_rs::GB18030.decode_without_bom_handling(bytes);
+    (!had_errors).then(|| (text.into_owned(), true))
+}
+
+/// 修复完整的 JSON 响应体，返回新 body 与修复数量（转码计为一次修复）
+pub fn repair_json_body(body: &[u8]) -> Option<(Bytes, usize)> {
+    let (text, transcoded) = decode_text(body)?;
+    let mut value: Value = serde_json::from_str(&text).ok()?;
+    let repaired = repair_value(&mut value) + usize::from(transcoded);
+    if repaired == 0 {
+        return None;
+    }
+    let body = serde_json::to_vec(&value).ok()?;
+    Some((Bytes::from(body), repaired))
+}
+
+/// 修复一个 SSE 事件块（不含结尾空行）中 JSON 格式的 `data:` 行
+fn repair_sse_block(block: &[u8]) -> Option<(String, usize)> {
+    let (text, transcoded) = decode_text(block)?;
+    let mut repaired = usize::from(transcoded);
+    let lines: Vec<String> = text
+        .split('\n')
+        .map(|line| {
+            let content = line.strip_suffix('\r').unwrap_or(line);
+            let Some(data) = strip_sse_field(content, "data") else {
+                return line.to_string();
+            };
+            let Ok(mut value) = serde_json::from_str::<Value>(data) else {
+                return line.to_string();
+            };
+            let count = repair_value(&mut value);
+            if count == 0 {
+                return line.to_string();
+            }
+            repaired += count;
+            let ending = if line.ends_with('\r') { "\r" } else { "" };
+            format!("data: {value}{ending}")
+        })
+        .collect();
+    (repaired > 0).then(|| (lines.join("\n"), repaired))
+}
+
+type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send>>;
+
+/// 按事件修复 SSE 流；流结束时汇总记录日志
+fn repair_sse_stream(stream: ByteStream, provider: String) -> ByteStream {
+    Box::pin(async_stream::stream! {
+        let mut stream = stream;
+        let mut scanner = SseBlockScanner::default();
+        let mut repaired = 0usize;
+        while let Some(chunk) = stream.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    yield Err(e);
+                    break;
+                }
+            };
+            scanner.push(&chunk);
+            while let Some(block) = scanner.next_block() {
+                match repair_sse_block(&block) {
+                    Some((text, count)) => {
+                        repaired += count;
+                        yield Ok(Bytes::from(format!("{text}\n\n")));
+                    }
+                    None => {
+                        let mut out = block.to_vec();
+                        out.extend_from_slice(b"\n\n");
+                        yield Ok(Bytes::from(out));
+                    }
+                }
+            }
+        }
+        let rest = scanner.remainder();
+        if !rest.is_empty() {
+            yield Ok(Bytes::copy_from_slice(rest));
+        }
+        if repaired > 0 {
+            log::info!("[TextRepair] provider={provider} 流式响应修复了 {repaired} 处编码异常的文本");
+        }
+    })
+}
+
+/// 对成功响应执行编码修复
+///
+/// 压缩过的响应体无法在此解析，原样放行。非流式 JSON 会被整包读取。
+pub async fn repair_response(
+    response: ProxyResponse,
+    provider: &str,
+) -> Result<ProxyResponse, ProxyError> {
+    let encoded = response
+        .headers()
+        .get(CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| !value.eq_ignore_ascii_case("identity"));
+    if encoded {
+        log::debug!("[TextRepair] provider={provider} 响应已压缩，跳过编码修复");
+        return Ok(response);
+    }
+
+    let status = response.status();
+    let mut headers = response.headers().clone();
+    if response.is_sse() {
+        headers.remove(CONTENT_LENGTH);
+        let stream = repair_sse_stream(Box::pin(response.bytes_stream()), provider.to_string());
+        return Ok(ProxyResponse::streamed(status, headers, stream));
+    }
+    if !response.is_json() {
+        return Ok(response);
+    }
+
+    let body = response.bytes().await?;
+    match repair_json_body(&body) {
+        Some((repaired_body, count)) => {
+            log::info!("[TextRepair] provider={provider} 响应修复了 {count} 处编码异常的文本");
+            headers.remove(CONTENT_LENGTH);
+            headers.insert(
+                CONTENT_TYPE,
+                http::HeaderValue::from_static("application/json"),
+            );
+            Ok(ProxyResponse::buffered(status, headers, repaired_body))
+        }
+        None => Ok(ProxyResponse::buffered(status, headers, body)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn repairs_latin1_and_cp1252_mojibake() {
+        // "你好" 的 UTF-8 字节按 Latin-1 解码
+        let latin1: String = "你好".bytes().map(char::from).collect();
+        assert_eq!(repair_text(&latin1).as_deref(), Some("你好"));
+
+        // "中文" 含 0x80..=0x9F 字节，按 Windows-1252 解码
+        let (cp1252, _) = encoding_rs::WINDOWS_1252.decode_without_bom_handling("中文".as_bytes());
+        assert_eq!(repair_text(&cp1252).as_deref(), Some("中文"));
+
+        // 正常的西文与中文不受影响
+        assert_eq!(repair_text("café"), None);
+        assert_eq!(repair_text("你好 world"), None);
+    }
+
+    #[test]
+    fn decodes_double_escaped_cjk_only() {
+        assert_eq!(
+            repair_text(r"\u4f60\u597d, world").as_deref(),
+            Some("你好, world")
+        );
+        // 代码里的普通转义保持原样
+        assert_eq!(repair_text(r#"printf("\u0041")"#), None);
+    }
+
+    #[test]
+    fn repairs_json_bodies_and_sse_events() {
+        let body = json!({"content": [{"type": "text", "text": "\\u4f60\\u597d"}]});
+        let (repaired, count) = repair_json_body(body.to_string().as_bytes()).unwrap();
+        assert_eq!(count, 1);
+        let value: Value = serde_json::from_slice(&repaired).unwrap();
+        assert_eq!(value["content"][0]["text"], "你好");
+
+        let (gbk, _, _) = encoding_rs::GBK.encode(r#"{"text":"你好"}"#);
+        let (repaired, _) = repair_json_body(&gbk).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&repaired).unwrap()["text"],
+            "你好"
+        );
+
+        let block = "event: content_block_delta\ndata: {\"delta\":{\"text\":\"\\\\u4f60\"}}";
+        let (text, count) = repair_sse_block(block.as_bytes()).unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            text,
+            "event: content_block_delta\ndata: {\"delta\":{\"text\":\"你\"}}"
+        );
+        assert!(repair_sse_block(b"data: [DONE]").is_none());
+    }
+}
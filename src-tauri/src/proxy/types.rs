@@ -89,6 +89,12 @@ pub struct ProxyStatus {
     /// 当前活跃的代理目标列表
     #[serde(default)]
     pub active_targets: Vec<ActiveTarget>,
+    /// 是否正在停止并排空进行中的请求
+    #[serde(default)]
+    pub draining: bool,
+    /// 进行中的请求（按应用与供应商分组）
+    #[serde(default)]
+    pub in_flight: Vec<super::drain::InFlightGroup>,
 }
 
 /// 活跃的代理目标信息
@@ -118,6 +124,28 @@ pub struct ProxyTakeoverStatus {
     pub openclaw: bool,
 }
 
+/// 本地代理访问令牌
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyAuthToken {
+    pub id: String,
+    pub name: String,
+    pub token: String,
+    pub created_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<i64>,
+}
+
+/// 按令牌汇总的用量
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyAuthTokenUsage {
+    pub token_id: String,
+    pub request_count: u64,
+    pub total_tokens: u64,
+    pub total_cost: String,
+}
+
 /// API 格式类型（预留，当前不需要格式转换）
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
@@ -249,6 +277,214 @@ impl Default for RectifierConfig {
     }
 }
 
+/// 请求内重试配置
+///
+/// 存储在 settings 表中，key = "retry_config"
+/// 仅对幂等请求（GET/HEAD）的瞬时故障生效（连接错误、502/503/504、流式首包前失败）：
+/// 先按带抖动的指数退避重试同一供应商，用完次数或预算后再走故障转移。
+/// POST 补全请求即使上游报 502 也可能已被处理并计费，默认不重放。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryConfig {
+    /// 总开关（默认开启）
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 同一供应商的最大重试次数（不含首次请求）
+    #[serde(default = "default_same_provider_retries")]
+    pub same_provider_retries: u32,
+    /// 退避基数（毫秒）：第 n 次重试前等待 `base * 2^(n-1)`
+    #[serde(default = "default_backoff_base_ms")]
+    pub backoff_base_ms: u64,
+    /// 单次退避上限（毫秒）
+    #[serde(default = "default_backoff_max_ms")]
+    pub backoff_max_ms: u64,
+    /// 重试预算（毫秒）：请求已耗时加上下一次退避超过预算时不再重试同一供应商
+    #[serde(default = "default_retry_budget_ms")]
+    pub budget_ms: u64,
+    /// 是否也重试非幂等请求（POST 等，默认关闭：可能重复计费）
+    #[serde(default)]
+    pub retry_non_idempotent: bool,
+}
+
+fn default_same_provider_retries() -> u32 {
+    1
+}
+
+fn default_backoff_base_ms() -> u64 {
+    250
+}
+
+fn default_backoff_max_ms() -> u64 {
+    2_000
+}
+
+fn default_retry_budget_ms() -> u64 {
+    10_000
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            same_provider_retries: default_same_provider_retries(),
+            backoff_base_ms: default_backoff_base_ms(),
+            backoff_max_ms: default_backoff_max_ms(),
+            budget_ms: default_retry_budget_ms(),
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// 第 `retry`（从 1 开始）次重试前的退避时长
+    pub fn backoff(&self, retry: u32) -> std::time::Duration {
+        let factor = 1u64 << retry.saturating_sub(1).min(16);
+        let millis = self
+            .backoff_base_ms
+            .saturating_mul(factor)
+            .min(self.backoff_max_ms);
+        std::time::Duration::from_millis(millis)
+    }
+
+    /// 第 `retry` 次重试前实际等待的时长：在 `[backoff/2, backoff]` 内随机，
+    /// 避免多个并发请求在同一时刻一起重试
+    pub fn jittered_backoff(&self, retry: u32) -> std::time::Duration {
+        let full = self.backoff(retry).as_millis() as u64;
+        let half = full / 2;
+        // 每个 RandomState 带独立的随机种子，足够做退避抖动，无需引入随机数依赖
+        let seed = std::hash::BuildHasher::hash_one(
+            &std::collections::hash_map::RandomState::new(),
+            retry,
+        );
+        let jitter = seed % (full - half + 1);
+        std::time::Duration::from_millis(half + jitter)
+    }
+
+    /// 该请求方法是否可以重放：GET/HEAD 天然幂等，其余方法需显式开启
+    pub fn permits_method(&self, method: &http::Method) -> bool {
+        self.retry_non_idempotent || matches!(*method, http::Method::GET | http::Method::HEAD)
+    }
+
+    /// 失败的请求能否重放：连接阶段就失败（请求从未到达上游）时任何方法都可以，
+    /// 否则上游可能已处理过该请求，仍按 [`Self::permits_method`] 判断
+    pub fn permits_replay(&self, method: &http::Method, may_have_reached_upstream: bool) -> bool {
+        !may_have_reached_upstream || self.permits_method(method)
+    }
+
+    /// 在已耗时 `elapsed` 的请求上，是否还允许第 `retry` 次同供应商重试
+    pub fn allows_retry(&self, retry: u32, elapsed: std::time::Duration) -> bool {
+        self.enabled
+            && retry <= self.same_provider_retries
+            && elapsed + self.backoff(retry) <= std::time::Duration::from_millis(self.budget_ms)
+    }
+}
+
+/// 优雅排空配置
+///
+/// 存储在 settings 表中，key = "drain_config"。
+/// 代理停止时不再接受新请求，等待进行中的请求（含流式响应）在原供应商上完成，
+/// 超过 `timeout_secs` 仍未结束的连接被强制关闭。切换供应商不会中断进行中的请求。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrainConfig {
+    /// 总开关（默认开启；关闭后停止代理立即断开所有连接）
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 排空超时（秒），范围 1-600
+    #[serde(default = "default_drain_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_drain_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for DrainConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            timeout_secs: default_drain_timeout_secs(),
+        }
+    }
+}
+
+impl DrainConfig {
+    pub const MAX_TIMEOUT_SECS: u64 = 600;
+
+    /// 实际使用的排空时长：关闭时为 0，其余按范围截断
+    pub fn timeout(&self) -> std::time::Duration {
+        if !self.enabled {
+            return std::time::Duration::ZERO;
+        }
+        std::time::Duration::from_secs(self.timeout_secs.clamp(1, Self::MAX_TIMEOUT_SECS))
+    }
+}
+
+/// 自动切走 / 切回看门狗配置
+///
+/// 存储在 settings 表中，key = "failback_watchdog_config"。
+/// 当前供应商在滚动窗口内的鉴权 / 限流错误率超过阈值时，把该应用切到故障转移队列中的
+/// 下一个供应商；冷却期过后自动切回原供应商（原供应商再次出错会重新触发）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailbackWatchdogConfig {
+    /// 总开关（默认关闭）
+    #[serde(default)]
+    pub enabled: bool,
+    /// 统计窗口（秒）
+    #[serde(default = "default_watchdog_window_secs")]
+    pub window_secs: u64,
+    /// 窗口内至少出现多少次匹配错误才会切换
+    #[serde(default = "default_watchdog_min_failures")]
+    pub min_failures: u32,
+    /// 匹配错误占窗口内请求的百分比阈值（1-100）
+    #[serde(default = "default_watchdog_error_rate_percent")]
+    pub error_rate_percent: u8,
+    /// 计入的上游状态码
+    #[serde(default = "default_watchdog_status_codes")]
+    pub status_codes: Vec<u16>,
+    /// 切走后至少等待多久才切回（秒）；同时也是两次自动切换的最小间隔
+    #[serde(default = "default_watchdog_cooloff_secs")]
+    pub cooloff_secs: u64,
+    /// 冷却期后是否自动切回原供应商
+    #[serde(default = "default_true")]
+    pub switch_back: bool,
+}
+
+fn default_watchdog_window_secs() -> u64 {
+    120
+}
+
+fn default_watchdog_min_failures() -> u32 {
+    3
+}
+
+fn default_watchdog_error_rate_percent() -> u8 {
+    50
+}
+
+fn default_watchdog_status_codes() -> Vec<u16> {
+    vec![401, 403, 429]
+}
+
+fn default_watchdog_cooloff_secs() -> u64 {
+    600
+}
+
+impl Default for FailbackWatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_secs: default_watchdog_window_secs(),
+            min_failures: default_watchdog_min_failures(),
+            error_rate_percent: default_watchdog_error_rate_percent(),
+            status_codes: default_watchdog_status_codes(),
+            cooloff_secs: default_watchdog_cooloff_secs(),
+            switch_back: true,
+        }
+    }
+}
+
 /// 请求优化器配置
 ///
 /// 存储在 settings 表中，key = "optimizer_config"
@@ -277,6 +513,42 @@ impl Default for OptimizerConfig {
     }
 }
 
+/// 响应缓存配置
+///
+/// 存储在 settings 表中，key = "response_cache_config"
+/// 仅缓存非流式的成功响应，命中时不再请求上游
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseCacheConfig {
+    /// 总开关（默认关闭）
+    #[serde(default)]
+    pub enabled: bool,
+    /// 缓存有效期（秒）
+    #[serde(default = "default_response_cache_ttl_seconds")]
+    pub ttl_seconds: u64,
+    /// 最多缓存的响应数，超出后淘汰最久未使用的条目
+    #[serde(default = "default_response_cache_max_entries")]
+    pub max_entries: usize,
+}
+
+fn default_response_cache_ttl_seconds() -> u64 {
+    300
+}
+
+fn default_response_cache_max_entries() -> usize {
+    200
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_seconds: default_response_cache_ttl_seconds(),
+            max_entries: default_response_cache_max_entries(),
+        }
+    }
+}
+
 /// Copilot 优化器配置
 ///
 /// 存储在 settings 表中，key = "copilot_optimizer_config"
@@ -381,6 +653,59 @@ impl LogConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_retry_config_backoff_and_budget() {
+        let config = RetryConfig {
+            same_provider_retries: 3,
+            ..RetryConfig::default()
+        };
+        assert_eq!(config.backoff(1).as_millis(), 250);
+        assert_eq!(config.backoff(2).as_millis(), 500);
+        assert_eq!(config.backoff(5).as_millis(), 2_000);
+
+        let elapsed = std::time::Duration::from_millis(500);
+        assert!(config.allows_retry(1, elapsed));
+        assert!(!config.allows_retry(4, elapsed));
+        // 预算耗尽后不再重试
+        assert!(!config.allows_retry(1, std::time::Duration::from_millis(9_900)));
+
+        let disabled = RetryConfig {
+            enabled: false,
+            ..RetryConfig::default()
+        };
+        assert!(!disabled.allows_retry(1, elapsed));
+
+        // 旧版本缺少字段时使用默认值
+        let parsed: RetryConfig = serde_json::from_str(r#"{"enabled":true}"#).unwrap();
+        assert_eq!(parsed, RetryConfig::default());
+    }
+
+    #[test]
+    fn test_retry_config_idempotency_and_jitter() {
+        let config = RetryConfig::default();
+        assert!(config.permits_method(&http::Method::GET));
+        assert!(config.permits_method(&http::Method::HEAD));
+        assert!(!config.permits_method(&http::Method::POST));
+
+        let opted_in = RetryConfig {
+            retry_non_idempotent: true,
+            ..RetryConfig::default()
+        };
+        assert!(opted_in.permits_method(&http::Method::POST));
+
+        // 请求未到达上游时 POST 也可以重放
+        assert!(config.permits_replay(&http::Method::POST, false));
+        assert!(!config.permits_replay(&http::Method::POST, true));
+        assert!(config.permits_replay(&http::Method::GET, true));
+
+        // 抖动后的等待时间不超过退避上限，也不低于其一半
+        for retry in 1..=4 {
+            let full = config.backoff(retry);
+            let waited = config.jittered_backoff(retry);
+            assert!(waited <= full && waited >= full / 2);
+        }
+    }
+
     #[test]
     fn test_rectifier_config_default_enabled() {
         // 验证 RectifierConfig::default() 返回全开启状态
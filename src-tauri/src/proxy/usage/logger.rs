@@ -4,10 +4,13 @@ use super::calculator::{CostBreakdown, CostCalculator, ModelPricing};
 use super::parser::TokenUsage;
 use crate::database::{Database, PRICING_SOURCE_REQUEST, PRICING_SOURCE_RESPONSE};
 use crate::error::AppError;
+use crate::i18n::LocaleFormat;
+use crate::services::provider_budget::ProviderBudgetService;
 use crate::services::sql_helpers::{INPUT_TOKEN_SEMANTICS_FRESH, INPUT_TOKEN_SEMANTICS_TOTAL};
 use crate::services::usage_stats::{find_model_pricing_row, is_placeholder_pricing_model};
 use rusqlite::OptionalExtension;
 use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::str::FromStr;
 
@@ -65,8 +68,12 @@ pub struct RequestLog {
     pub request_id: String,
     pub provider_id: String,
     pub app_type: String,
+    /// 上游响应中报告的模型（未报告时为发往上游的模型）
     pub model: String,
+    /// 客户端请求的模型（可能是别名或 Claude 档位名）
     pub request_model: String,
+    /// 别名解析 / 供应商模型映射后实际发往上游的模型
+    pub mapped_model: String,
     /// 写入时实际用于计价的模型名（pricing_model_source 解析后的结果）。
     /// 落库供回填使用：缺价行补价后必须按写入时的基准重算，而不是
     /// 用 model/request_model 猜——路由接管下三者可能各不相同。
@@ -85,6 +92,65 @@ pub struct RequestLog {
     pub is_streaming: bool,
     /// 成本倍数
     pub cost_multiplier: String,
+    /// 上游重试次数（同供应商瞬时故障重试 + 故障转移）
+    pub retry_count: u32,
+}
+
+/// 用量导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageExportFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for UsageExportFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            other => Err(AppError::InvalidInput(format!(
+                "不支持的导出格式: {other}（可选 csv / json）"
+            ))),
+        }
+    }
+}
+
+/// 导出的单条用量记录
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageExportRecord {
+    /// RFC 3339（UTC）
+    pub timestamp: String,
+    pub request_id: String,
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: Option<String>,
+    /// 客户端请求的模型
+    pub requested_model: Option<String>,
+    /// 实际发往上游（经别名解析 / 模型映射后）的模型
+    pub mapped_model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub total_cost_usd: String,
+    pub status_code: u16,
+    /// 上游响应中报告的模型（CSV 追加在末列，不影响已有列的位置）
+    pub upstream_model: String,
+}
+
+const USAGE_EXPORT_CSV_HEADER: &str = "timestamp,request_id,app_type,provider_id,provider_name,requested_model,mapped_model,input_tokens,output_tokens,cache_read_tokens,cache_creation_tokens,total_cost_usd,status_code,upstream_model";
+
+/// CSV 字段转义：含逗号、引号或换行时加引号，引号双写
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
 }
 
 /// 使用量记录器
@@ -161,6 +227,25 @@ impl<'a> UsageLogger<'a> {
             }
         };
 
+        // 经不受信任的供应商转发的请求单独打标
+        let untrusted = crate::services::provider_trust::is_untrusted_on_conn(
+            &conn,
+            &log.app_type,
+            &log.provider_id,
+        );
+        // 开启代理鉴权时按访问令牌归因
+        let auth_token_id = log
+            .session_id
+            .as_deref()
+            .and_then(crate::proxy::auth::token_for_session);
+
+        // 同负载比较用的工作负载指纹（模型档位 + 请求体量）
+        let workload = super::workload::fingerprint(
+            &log.request_model,
+            &log.usage,
+            input_token_semantics == INPUT_TOKEN_SEMANTICS_TOTAL,
+        );
+
         let insert_verb = if replace_session_log {
             "INSERT OR REPLACE"
         } else {
@@ -169,12 +254,13 @@ impl<'a> UsageLogger<'a> {
         let sql = format!(
             "{insert_verb} INTO proxy_request_logs (
                 request_id, provider_id, app_type, model, request_model, pricing_model,
-                input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
+                mapped_model, input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
                 input_token_semantics,
                 input_cost_usd, output_cost_usd, cache_read_cost_usd, cache_creation_cost_usd, total_cost_usd,
                 latency_ms, first_token_ms, status_code, error_message, session_id,
-                provider_type, is_streaming, cost_multiplier, created_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25)"
+                provider_type, is_streaming, cost_multiplier, created_at, retry_count, untrusted,
+                auth_token_id, workload
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30)"
         );
         let affected_rows = conn
             .execute(
@@ -186,6 +272,7 @@ impl<'a> UsageLogger<'a> {
                     log.model,
                     log.request_model,
                     log.pricing_model,
+                    log.mapped_model,
                     log.usage.input_tokens,
                     log.usage.output_tokens,
                     log.usage.cache_read_tokens,
@@ -205,11 +292,31 @@ impl<'a> UsageLogger<'a> {
                     log.is_streaming as i64,
                     log.cost_multiplier,
                     created_at,
+                    log.retry_count as i64,
+                    untrusted as i64,
+                    auth_token_id,
+                    workload,
                 ],
             )
             .map_err(|e| AppError::Database(format!("记录请求日志失败: {e}")))?;
 
         if affected_rows > 0 {
+            if let Err(e) = crate::database::Database::touch_provider_traffic_on_conn(
+                &conn,
+                &log.app_type,
+                &log.provider_id,
+                created_at,
+            ) {
+                log::debug!("记录供应商最近请求时间失败: {e}");
+            }
+            if let Some(token_id) = auth_token_id.as_deref() {
+                if let Err(e) = conn.execute(
+                    "UPDATE proxy_auth_tokens SET last_used_at = ?2 WHERE id = ?1",
+                    rusqlite::params![token_id, created_at],
+                ) {
+                    log::debug!("记录访问令牌最近使用时间失败: {e}");
+                }
+            }
             if collision {
                 log::warn!(
                     "usage request_id collision: primary={}, fallback={request_id}",
@@ -218,6 +325,16 @@ impl<'a> UsageLogger<'a> {
             }
             crate::usage_events::notify_log_recorded();
         }
+        drop(conn);
+
+        if affected_rows > 0 {
+            crate::services::failback_watchdog::FailbackWatchdog::observe(
+                self.db,
+                &log.app_type,
+                &log.provider_id,
+                log.status_code,
+            );
+        }
 
         Ok(())
     }
@@ -268,12 +385,14 @@ impl<'a> UsageLogger<'a> {
         latency_ms: u64,
     ) -> Result<(), AppError> {
         let request_model = model.clone();
+        let mapped_model = model.clone();
         let log = RequestLog {
             request_id,
             provider_id,
             app_type,
             model,
             request_model,
+            mapped_model,
             // 错误行未经过计价，留空（回填的 has_usage 闸门也不会碰全 0 行）
             pricing_model: String::new(),
             usage: TokenUsage::default(),
@@ -286,6 +405,7 @@ impl<'a> UsageLogger<'a> {
             provider_type: None,
             is_streaming: false,
             cost_multiplier: "1.0".to_string(),
+            retry_count: 0,
         };
 
         self.log_request(&log)
@@ -300,21 +420,24 @@ impl<'a> UsageLogger<'a> {
         request_id: String,
         provider_id: String,
         app_type: String,
-        model: String,
+        request_model: String,
+        mapped_model: String,
         status_code: u16,
         error_message: String,
         latency_ms: u64,
         is_streaming: bool,
         session_id: Option<String>,
         provider_type: Option<String>,
+        retry_count: u32,
     ) -> Result<(), AppError> {
-        let request_model = model.clone();
+        // 失败请求没有上游响应，上游模型按实际发出的模型记录
         let log = RequestLog {
             request_id,
             provider_id,
             app_type,
-            model,
+            model: mapped_model.clone(),
             request_model,
+            mapped_model,
             // 错误行未经过计价，留空（回填的 has_usage 闸门也不会碰全 0 行）
             pricing_model: String::new(),
             usage: TokenUsage::default(),
@@ -327,9 +450,14 @@ impl<'a> UsageLogger<'a> {
             provider_type,
             is_streaming,
             cost_multiplier: "1.0".to_string(),
+            retry_count,
         };
 
-        self.log_request(&log)
+        self.log_request(&log)?;
+        if has_usage {
+            ProviderBudgetService::on_usage_recorded(self.db, &log.app_type, &log.provider_id);
+        }
+        Ok(())
     }
 
     /// 获取模型定价
@@ -450,6 +578,7 @@ impl<'a> UsageLogger<'a> {
         app_type: String,
         model: String,
         request_model: String,
+        mapped_model: String,
         pricing_model: String,
         usage: TokenUsage,
         cost_multiplier: Decimal,
@@ -459,6 +588,7 @@ impl<'a> UsageLogger<'a> {
         session_id: Option<String>,
         provider_type: Option<String>,
         is_streaming: bool,
+        retry_count: u32,
     ) -> Result<(), AppError> {
         let pricing = self.get_model_pricing(&pricing_model)?;
 
@@ -484,6 +614,7 @@ impl<'a> UsageLogger<'a> {
             app_type,
             model,
             request_model,
+            mapped_model,
             pricing_model,
             usage,
             cost,
@@ -495,10 +626,130 @@ impl<'a> UsageLogger<'a> {
             provider_type,
             is_streaming,
             cost_multiplier: cost_multiplier.to_string(),
+            retry_count,
         };
 
         self.log_request(&log)
     }
+
+    /// 查询时间范围内的请求明细（`[start, end]`，Unix 秒），按时间升序
+    ///
+    /// 只导出明细表；超过保留期已被汇总进日聚合表的旧记录不在其中。
+    pub fn export_records(
+        &self,
+        start: Option<i64>,
+        end: Option<i64>,
+        app_type: Option<&str>,
+    ) -> Result<Vec<UsageExportRecord>, AppError> {
+        let conn = crate::database::lock_conn!(self.db.conn);
+        let mut stmt = conn
+            .prepare(
+                "SELECT l.created_at, l.request_id, l.app_type, l.provider_id, p.name,
+                        l.request_model, COALESCE(NULLIF(l.mapped_model, ''), l.model),
+                        l.input_tokens, l.output_tokens,
+                        l.cache_read_tokens, l.cache_creation_tokens, l.total_cost_usd,
+                        l.status_code, l.model
+                 FROM proxy_request_logs l
+                 LEFT JOIN providers p ON l.provider_id = p.id AND l.app_type = p.app_type
+                 WHERE (?1 IS NULL OR l.created_at >= ?1)
+                   AND (?2 IS NULL OR l.created_at <= ?2)
+                   AND (?3 IS NULL OR l.app_type = ?3)
+                 ORDER BY l.created_at ASC, l.request_id ASC",
+            )
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(rusqlite::params![start, end, app_type], |row| {
+                let created_at: i64 = row.get(0)?;
+                Ok(UsageExportRecord {
+                    timestamp: chrono::DateTime::from_timestamp(created_at, 0)
+                        .map(|dt| dt.to_rfc3339())
+                        .unwrap_or_else(|| created_at.to_string()),
+                    request_id: row.get(1)?,
+                    app_type: row.get(2)?,
+                    provider_id: row.get(3)?,
+                    provider_name: row.get(4)?,
+                    requested_model: row.get::<_, Option<String>>(5)?.filter(|m| !m.is_empty()),
+                    mapped_model: row.get(6)?,
+                    input_tokens: row.get::<_, i64>(7)?.max(0) as u64,
+                    output_tokens: row.get::<_, i64>(8)?.max(0) as u64,
+                    cache_read_tokens: row.get::<_, i64>(9)?.max(0) as u64,
+                    cache_creation_tokens: row.get::<_, i64>(10)?.max(0) as u64,
+                    total_cost_usd: row.get(11)?,
+                    status_code: row.get::<_, i64>(12)? as u16,
+                    upstream_model: row.get(13)?,
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+
+    /// 导出时间范围内的用量报表（CSV 或 JSON 文本）
+    pub fn export(
+        &self,
+        format: UsageExportFormat,
+        start: Option<i64>,
+        end: Option<i64>,
+        app_type: Option<&str>,
+    ) -> Result<String, AppError> {
+        self.export_with_locale(format, start, end, app_type, None)
+    }
+
+    /// 导出用量明细；`locale` 非空时 CSV 的时间、Token 数与金额按区域格式输出
+    /// （本地时区日期、千位分隔符、货币符号），便于直接在表格软件中阅读。JSON 始终保持原始值。
+    pub fn export_with_locale(
+        &self,
+        format: UsageExportFormat,
+        start: Option<i64>,
+        end: Option<i64>,
+        app_type: Option<&str>,
+        locale: Option<&LocaleFormat>,
+    ) -> Result<String, AppError> {
+        let records = self.export_records(start, end, app_type)?;
+        match format {
+            UsageExportFormat::Json => serde_json::to_string_pretty(&records)
+                .map_err(|e| AppError::JsonSerialize { source: e }),
+            UsageExportFormat::Csv => {
+                let tokens = |value: u64| match locale {
+                    Some(locale) => locale.integer(value as i64),
+                    None => value.to_string(),
+                };
+                let mut out = String::from(USAGE_EXPORT_CSV_HEADER);
+                out.push('\n');
+                for r in &records {
+                    let (timestamp, cost) = match locale {
+                        Some(locale) => (
+                            chrono::DateTime::parse_from_rfc3339(&r.timestamp)
+                                .map(|time| locale.datetime(&time))
+                                .unwrap_or_else(|_| r.timestamp.clone()),
+                            locale.usd_str(&r.total_cost_usd),
+                        ),
+                        None => (r.timestamp.clone(), r.total_cost_usd.clone()),
+                    };
+                    let fields = [
+                        timestamp,
+                        r.request_id.clone(),
+                        r.app_type.clone(),
+                        r.provider_id.clone(),
+                        r.provider_name.clone().unwrap_or_default(),
+                        r.requested_model.clone().unwrap_or_default(),
+                        r.mapped_model.clone(),
+                        tokens(r.input_tokens),
+                        tokens(r.output_tokens),
+                        tokens(r.cache_read_tokens),
+                        tokens(r.cache_creation_tokens),
+                        cost,
+                        r.status_code.to_string(),
+                        r.upstream_model.clone(),
+                    ];
+                    let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                    out.push_str(&line.join(","));
+                    out.push('\n');
+                }
+                Ok(out)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -512,6 +763,7 @@ mod tests {
             app_type: "codex".to_string(),
             model: "gpt-5.6".to_string(),
             request_model: "gpt-5.6".to_string(),
+            mapped_model: "gpt-5.6".to_string(),
             pricing_model: "gpt-5.6".to_string(),
             usage: TokenUsage {
                 input_tokens,
@@ -530,6 +782,7 @@ mod tests {
             provider_type: Some("codex".to_string()),
             is_streaming: true,
             cost_multiplier: "1".to_string(),
+            retry_count: 0,
         }
     }
 
@@ -566,6 +819,7 @@ mod tests {
             "test-model".to_string(),
             "req-model".to_string(),
             "test-model".to_string(),
+            "test-model".to_string(),
             usage,
             Decimal::from(1),
             100,
@@ -574,19 +828,22 @@ mod tests {
             None,
             Some("claude".to_string()),
             false,
+            2,
         )?;
 
         // 验证记录已插入
         let conn = crate::database::lock_conn!(db.conn);
-        let (count, request_model): (i64, String) = conn
+        let (count, request_model, mapped_model, retry_count): (i64, String, String, i64) = conn
             .query_row(
-                "SELECT COUNT(*), request_model FROM proxy_request_logs WHERE request_id = 'req-123'",
+                "SELECT COUNT(*), request_model, mapped_model, retry_count FROM proxy_request_logs WHERE request_id = 'req-123'",
                 [],
-                |row| Ok((row.get(0)?, row.get(1)?)),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
             )
             .unwrap();
         assert_eq!(count, 1);
         assert_eq!(request_model, "req-model");
+        assert_eq!(mapped_model, "test-model");
+        assert_eq!(retry_count, 2);
         Ok(())
     }
 
@@ -726,6 +983,7 @@ mod tests {
             app_type: "grokbuild".to_string(),
             model: "grok-4.5".to_string(),
             request_model: "grok-4.5".to_string(),
+            mapped_model: "grok-4.5".to_string(),
             pricing_model: String::new(),
             usage: TokenUsage::default(),
             cost: None,
@@ -737,6 +995,7 @@ mod tests {
             provider_type: Some("grokbuild".to_string()),
             is_streaming: false,
             cost_multiplier: "1".to_string(),
+            retry_count: 0,
         };
 
         logger.log_request(&log)?;
@@ -750,4 +1009,59 @@ mod tests {
         assert_eq!(semantics, INPUT_TOKEN_SEMANTICS_TOTAL);
         Ok(())
     }
+
+    #[test]
+    fn export_filters_by_range_and_escapes_csv() -> Result<(), AppError> {
+        let db = Database::memory()?;
+        let logger = UsageLogger::new(&db);
+        let mut log = request_log("req-a", 10);
+        log.request_model = "alias, \"fast\"".to_string();
+        log.model = "gpt-5.6-2026-01-01".to_string();
+        logger.log_request(&log)?;
+        logger.log_request(&request_log("req-b", 20))?;
+        {
+            let conn = crate::database::lock_conn!(db.conn);
+            conn.execute(
+                "UPDATE proxy_request_logs SET created_at = 100 WHERE request_id = 'req-a'",
+                [],
+            )
+            .unwrap();
+            conn.execute(
+                "UPDATE proxy_request_logs SET created_at = 200 WHERE request_id = 'req-b'",
+                [],
+            )
+            .unwrap();
+        }
+
+        let records = logger.export_records(Some(50), Some(150), None)?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].request_id, "req-a");
+        assert_eq!(records[0].mapped_model, "gpt-5.6");
+        assert_eq!(records[0].upstream_model, "gpt-5.6-2026-01-01");
+
+        let csv = logger.export(UsageExportFormat::Csv, None, None, Some("codex"))?;
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], USAGE_EXPORT_CSV_HEADER);
+        assert!(lines[1].starts_with("1970-01-01T00:01:40+00:00,req-a,codex,provider-1,,"));
+        assert!(lines[1].contains(",\"alias, \"\"fast\"\"\",gpt-5.6,10,5,2,0,"));
+
+        let de = LocaleFormat::for_tag("de-DE");
+        let localized = logger.export_with_locale(
+            UsageExportFormat::Csv,
+            None,
+            None,
+            Some("codex"),
+            Some(&de),
+        )?;
+        let row = localized.lines().nth(1).unwrap();
+        assert!(!row.contains("T00:01:40"));
+        assert!(row.contains(",gpt-5.6,10,5,2,0,"));
+
+        let json = logger.export(UsageExportFormat::Json, Some(150), None, None)?;
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value[0]["requestId"], "req-b");
+        assert_eq!(value[0]["requestedModel"], "gpt-5.6");
+        Ok(())
+    }
 }
@@ -5,11 +5,12 @@
 pub mod calculator;
 pub mod logger;
 pub mod parser;
+pub mod workload;
 
 // 仅导出内部使用的类型,避免未使用警告
 #[allow(unused_imports)]
 pub use calculator::{CostBreakdown, CostCalculator, ModelPricing};
 #[allow(unused_imports)]
-pub use logger::{RequestLog, UsageLogger};
+pub use logger::{RequestLog, UsageExportFormat, UsageLogger};
 #[allow(unused_imports)]
 pub use parser::{ApiType, TokenUsage};
@@ -5,6 +5,9 @@
 //! - OpenRouter (OpenAI 格式)
 //! - Codex API (非流式和流式)
 //! - Gemini API (非流式和流式)
+//!
+//! 流式解析兼容 `[DONE]` 终止 chunk、缺少结尾空行的最后事件，以及客户端提前断开时
+//! 仅收到部分事件的情况（按已收到的 usage 记录）。
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -27,6 +30,31 @@ fn openai_cache_write_tokens(usage: &Value) -> u32 {
         .unwrap_or(0) as u32
 }
 
+/// Gemini 输出 tokens：优先 `totalTokenCount - promptTokenCount`（含思考 tokens），
+/// 部分兼容上游省略 totalTokenCount 时回退到 candidates + thoughts。
+fn gemini_output_tokens(usage: &Value, prompt_tokens: u32) -> u32 {
+    if let Some(total) = usage.get("totalTokenCount").and_then(Value::as_u64) {
+        return (total as u32).saturating_sub(prompt_tokens);
+    }
+    let candidates = usage
+        .get("candidatesTokenCount")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let thoughts = usage
+        .get("thoughtsTokenCount")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    (candidates + thoughts) as u32
+}
+
+/// Responses API 的终止事件（completed / incomplete / failed 均携带最终 usage）
+fn codex_terminal_response(event: &Value) -> Option<&Value> {
+    match event.get("type").and_then(Value::as_str)? {
+        "response.completed" | "response.incomplete" | "response.failed" => event.get("response"),
+        _ => None,
+    }
+}
+
 /// Session 日志 request_id 前缀，与 `session_usage.rs` 中的格式保持一致
 pub const SESSION_REQUEST_ID_PREFIX: &str = "session:";
 
@@ -220,6 +248,29 @@ impl TokenUsage {
                             }
                         }
                     }
+                    "message_stop" => {
+                        // Bedrock 透传时 message_stop 携带 invocationMetrics，
+                        // 仅在前面的事件未给出对应计数时补齐
+                        if let Some(metrics) = event.get("amazon-bedrock-invocationMetrics") {
+                            let metric = |key: &str| {
+                                metrics.get(key).and_then(|v| v.as_u64()).map(|v| v as u32)
+                            };
+                            if usage.input_tokens == 0 {
+                                usage.input_tokens = metric("inputTokenCount").unwrap_or(0);
+                            }
+                            if usage.output_tokens == 0 {
+                                usage.output_tokens = metric("outputTokenCount").unwrap_or(0);
+                            }
+                            if usage.cache_read_tokens == 0 {
+                                usage.cache_read_tokens =
+                                    metric("cacheReadInputTokenCount").unwrap_or(0);
+                            }
+                            if usage.cache_creation_tokens == 0 {
+                                usage.cache_creation_tokens =
+                                    metric("cacheWriteInputTokenCount").unwrap_or(0);
+                            }
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -331,17 +382,12 @@ impl TokenUsage {
     pub fn from_codex_stream_events(events: &[Value]) -> Option<Self> {
         log::debug!("[Codex] 解析流式事件，共 {} 个事件", events.len());
         for event in events {
-            if let Some(event_type) = event.get("type").and_then(|v| v.as_str()) {
-                log::debug!("[Codex] 事件类型: {event_type}");
-                if event_type == "response.completed" {
-                    if let Some(response) = event.get("response") {
-                        log::debug!("[Codex] 找到 response.completed 事件，解析 usage");
-                        return Self::from_codex_response_adjusted(response);
-                    }
-                }
+            if let Some(response) = codex_terminal_response(event) {
+                log::debug!("[Codex] 找到终止事件，解析 usage");
+                return Self::from_codex_response_adjusted(response);
             }
         }
-        log::debug!("[Codex] 未找到 response.completed 事件");
+        log::debug!("[Codex] 未找到 response.completed/incomplete/failed 事件");
         None
     }
 
@@ -373,15 +419,12 @@ impl TokenUsage {
     pub fn from_codex_stream_events_auto(events: &[Value]) -> Option<Self> {
         log::debug!("[Codex] 智能解析流式事件，共 {} 个事件", events.len());
 
-        // 先尝试 Codex Responses API 格式 (response.completed 事件)
+        // 先尝试 Codex Responses API 格式 (response.completed 事件)；
+        // 被截断（max_output_tokens）或失败的响应以 incomplete/failed 结束，同样带 usage
         for event in events {
-            if let Some(event_type) = event.get("type").and_then(|v| v.as_str()) {
-                if event_type == "response.completed" {
-                    if let Some(response) = event.get("response") {
-                        log::debug!("[Codex] 找到 response.completed 事件");
-                        return Self::from_codex_response_auto(response);
-                    }
-                }
+            if let Some(response) = codex_terminal_response(event) {
+                log::debug!("[Codex] 找到 Responses 终止事件");
+                return Self::from_codex_response_auto(response);
             }
         }
 
@@ -421,19 +464,45 @@ impl TokenUsage {
     /// 从 OpenAI Chat Completions API 流式响应解析
     pub fn from_openai_stream_events(events: &[Value]) -> Option<Self> {
         log::debug!("[Codex] 解析 OpenAI 流式事件，共 {} 个事件", events.len());
-        // OpenAI 流式响应在最后一个 chunk 中包含 usage
+        // OpenAI 流式响应在最后一个 chunk 中包含 usage；
+        // 部分兼容上游（如 Moonshot）把 usage 放在 choices[0].usage 中
         for event in events.iter().rev() {
-            if let Some(usage) = event.get("usage") {
-                if !usage.is_null() {
+            let choice_usage = event
+                .pointer("/choices/0/usage")
+                .filter(|usage| !usage.is_null());
+            let mut parsed = match event.get("usage") {
+                Some(usage) if !usage.is_null() => {
                     log::debug!("[Codex] 找到 usage: {usage:?}");
-                    let mut parsed = Self::from_openai_response(event)?;
-                    if parsed.message_id.is_none() {
-                        parsed.message_id =
-                            events.iter().find_map(|chunk| response_id(chunk, "id"));
-                    }
-                    return Some(parsed);
+                    Self::from_openai_response(event)?
                 }
+                _ => match choice_usage {
+                    Some(usage) => {
+                        log::debug!("[Codex] 找到 choices[0].usage: {usage:?}");
+                        let mut envelope = serde_json::Map::new();
+                        envelope.insert("usage".to_string(), usage.clone());
+                        for key in ["id", "model"] {
+                            if let Some(value) = event.get(key) {
+                                envelope.insert(key.to_string(), value.clone());
+                            }
+                        }
+                        Self::from_openai_response(&Value::Object(envelope))?
+                    }
+                    None => continue,
+                },
+            };
+            if parsed.message_id.is_none() {
+                parsed.message_id = events.iter().find_map(|chunk| response_id(chunk, "id"));
+            }
+            if parsed.model.is_none() {
+                parsed.model = events.iter().find_map(|chunk| {
+                    chunk
+                        .get("model")
+                        .and_then(Value::as_str)
+                        .filter(|m| !m.is_empty())
+                        .map(str::to_owned)
+                });
             }
+            return Some(parsed);
         }
         log::debug!("[Codex] 未找到 usage 信息");
         None
@@ -449,11 +518,10 @@ impl TokenUsage {
             .map(|s| s.to_string());
 
         let prompt_tokens = usage.get("promptTokenCount")?.as_u64()? as u32;
-        let total_tokens = usage.get("totalTokenCount")?.as_u64()? as u32;
 
         // 输出 tokens = 总 tokens - 输入 tokens
         // 这包含了 candidatesTokenCount + thoughtsTokenCount
-        let output_tokens = total_tokens.saturating_sub(prompt_tokens);
+        let output_tokens = gemini_output_tokens(usage, prompt_tokens);
 
         Some(Self {
             input_tokens: prompt_tokens,
@@ -472,7 +540,7 @@ impl TokenUsage {
     #[allow(dead_code)]
     pub fn from_gemini_stream_chunks(chunks: &[Value]) -> Option<Self> {
         let mut total_input = 0u32;
-        let mut total_tokens = 0u32;
+        let mut total_output = 0u32;
         let mut total_cache_read = 0u32;
         let mut model: Option<String> = None;
         let mut message_id: Option<String> = None;
@@ -485,11 +553,8 @@ impl TokenUsage {
                     .and_then(|v| v.as_u64())
                     .unwrap_or(0) as u32;
 
-                // 总 tokens (包含输入 + 输出 + 思考)
-                total_tokens = usage
-                    .get("totalTokenCount")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0) as u32;
+                // usageMetadata 是累计值，以最后一个 chunk 为准
+                total_output = gemini_output_tokens(usage, total_input);
 
                 // 缓存读取 tokens
                 total_cache_read = usage
@@ -509,9 +574,6 @@ impl TokenUsage {
             }
         }
 
-        // 输出 tokens = 总 tokens - 输入 tokens
-        let total_output = total_tokens.saturating_sub(total_input);
-
         if total_input > 0 || total_output > 0 {
             Some(Self {
                 input_tokens: total_input,
@@ -1237,4 +1299,85 @@ mod tests {
         assert_eq!(usage.output_tokens, 50);
         assert_eq!(usage.model, Some("gpt-4o".to_string()));
     }
+
+    #[test]
+    fn claude_stream_keeps_input_when_client_disconnects_before_message_delta() {
+        let events = vec![json!({
+            "type": "message_start",
+            "message": {
+                "id": "msg_partial",
+                "usage": { "input_tokens": 1200, "output_tokens": 1 }
+            }
+        })];
+        let usage = TokenUsage::from_claude_stream_events(&events).unwrap();
+        assert_eq!(usage.input_tokens, 1200);
+        assert_eq!(usage.output_tokens, 0);
+    }
+
+    #[test]
+    fn claude_stream_falls_back_to_bedrock_invocation_metrics() {
+        let events = vec![
+            json!({"type": "message_start", "message": {"usage": {}}}),
+            json!({
+                "type": "message_stop",
+                "amazon-bedrock-invocationMetrics": {
+                    "inputTokenCount": 40,
+                    "outputTokenCount": 12,
+                    "cacheReadInputTokenCount": 5
+                }
+            }),
+        ];
+        let usage = TokenUsage::from_claude_stream_events(&events).unwrap();
+        assert_eq!(usage.input_tokens, 40);
+        assert_eq!(usage.output_tokens, 12);
+        assert_eq!(usage.cache_read_tokens, 5);
+    }
+
+    #[test]
+    fn codex_stream_reads_usage_from_incomplete_response() {
+        let events = vec![json!({
+            "type": "response.incomplete",
+            "response": {
+                "model": "gpt-5",
+                "usage": { "input_tokens": 300, "output_tokens": 128 }
+            }
+        })];
+        let usage = TokenUsage::from_codex_stream_events_auto(&events).unwrap();
+        assert_eq!(usage.input_tokens, 300);
+        assert_eq!(usage.output_tokens, 128);
+    }
+
+    #[test]
+    fn openai_stream_reads_usage_nested_in_choices() {
+        let events = vec![
+            json!({"id": "chatcmpl-9", "model": "kimi-k2", "choices": [{"delta": {"content": "hi"}}]}),
+            json!({
+                "id": "chatcmpl-9",
+                "choices": [{
+                    "delta": {},
+                    "finish_reason": "stop",
+                    "usage": { "prompt_tokens": 20, "completion_tokens": 7 }
+                }]
+            }),
+        ];
+        let usage = TokenUsage::from_openai_stream_events(&events).unwrap();
+        assert_eq!(usage.input_tokens, 20);
+        assert_eq!(usage.output_tokens, 7);
+        assert_eq!(usage.model.as_deref(), Some("kimi-k2"));
+    }
+
+    #[test]
+    fn gemini_stream_falls_back_to_candidate_and_thought_counts() {
+        let chunks = vec![
+            json!({"usageMetadata": {"promptTokenCount": 10, "candidatesTokenCount": 2}}),
+            json!({"usageMetadata": {
+                "promptTokenCount": 10,
+                "candidatesTokenCount": 6,
+                "thoughtsTokenCount": 4
+            }}),
+        ];
+        let usage = TokenUsage::from_gemini_stream_chunks(&chunks).unwrap();
+        assert_eq!(usage.input_tokens, 10);
+        assert_eq!(usage.output_tokens, 10);
+    }
 }
@@ -0,0 +1,124 @@
+//! 工作负载指纹 - 为同类请求打标，便于在相同负载下横向比较供应商
+//!
+//! 指纹由「模型档位 + 请求体量分桶」组成，如 `sonnet:m`。不同供应商只有在
+//! 处理同一档位、同一量级的请求时，延迟和成本才有可比性；直接比较总体平均值
+//! 会被各自承接的流量结构带偏（例如只跑 haiku 小请求的供应商天然更快更便宜）。
+
+use super::parser::TokenUsage;
+
+/// 请求体量分桶上界（提示词 token 数，不含上界），最后一档无上界
+const SIZE_BUCKETS: &[(&str, u32)] = &[("xs", 2_000), ("s", 8_000), ("m", 32_000), ("l", 128_000)];
+const SIZE_BUCKET_UNBOUNDED: &str = "xl";
+
+/// 模型档位：按请求模型名归类，与具体版本号无关
+pub fn model_class(model: &str) -> &'static str {
+    let lower = model.to_ascii_lowercase();
+    let has = |needle: &str| lower.contains(needle);
+    if has("opus") || has("fable") || has("mythos") {
+        "opus"
+    } else if has("sonnet") {
+        "sonnet"
+    } else if has("haiku") {
+        "haiku"
+    } else if has("gemini") {
+        if has("flash") || has("lite") {
+            "gemini-flash"
+        } else {
+            "gemini-pro"
+        }
+    } else if has("gpt") || has("codex") {
+        if has("mini") || has("nano") {
+            "gpt-mini"
+        } else {
+            "gpt"
+        }
+    } else {
+        "other"
+    }
+}
+
+/// 请求体量分桶
+pub fn size_bucket(prompt_tokens: u32) -> &'static str {
+    SIZE_BUCKETS
+        .iter()
+        .find(|(_, upper)| prompt_tokens < *upper)
+        .map(|(bucket, _)| *bucket)
+        .unwrap_or(SIZE_BUCKET_UNBOUNDED)
+}
+
+/// 计算请求的工作负载指纹
+///
+/// `cache_inclusive` 表示 input_tokens 已包含缓存命中部分（Codex/Gemini 口径）。
+/// 没有任何输入 token 的行（失败请求、上游未回报用量）无法判断体量，返回 None，
+/// 不参与同负载比较。
+pub fn fingerprint(
+    request_model: &str,
+    usage: &TokenUsage,
+    cache_inclusive: bool,
+) -> Option<String> {
+    let prompt_tokens = if cache_inclusive {
+        usage.input_tokens
+    } else {
+        usage
+            .input_tokens
+            .saturating_add(usage.cache_read_tokens)
+            .saturating_add(usage.cache_creation_tokens)
+    };
+    if prompt_tokens == 0 || request_model.trim().is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{}:{}",
+        model_class(request_model),
+        size_bucket(prompt_tokens)
+    ))
+}
+
+/// 把指纹拆回 (模型档位, 体量分桶)
+pub fn split_fingerprint(workload: &str) -> (&str, &str) {
+    workload.split_once(':').unwrap_or((workload, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input: u32, cache_read: u32) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cache_read_tokens: cache_read,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn classifies_models_ignoring_versions() {
+        assert_eq!(model_class("claude-sonnet-4-5-20250929"), "sonnet");
+        assert_eq!(model_class("anthropic/claude-opus-4.1"), "opus");
+        assert_eq!(model_class("gemini-2.5-flash-lite"), "gemini-flash");
+        assert_eq!(model_class("gpt-5-codex"), "gpt");
+        assert_eq!(model_class("gpt-5-mini"), "gpt-mini");
+        assert_eq!(model_class("deepseek-chat"), "other");
+    }
+
+    #[test]
+    fn buckets_prompt_size_including_cache_for_fresh_semantics() {
+        assert_eq!(size_bucket(0), "xs");
+        assert_eq!(size_bucket(7_999), "s");
+        assert_eq!(size_bucket(8_000), "m");
+        assert_eq!(size_bucket(500_000), "xl");
+
+        // Claude 口径：缓存命中不计入 input_tokens，需要加回
+        assert_eq!(
+            fingerprint("claude-sonnet-4-5", &usage(1_000, 30_000), false).as_deref(),
+            Some("sonnet:m")
+        );
+        // Codex 口径：input_tokens 已含缓存
+        assert_eq!(
+            fingerprint("gpt-5", &usage(1_000, 900), true).as_deref(),
+            Some("gpt:xs")
+        );
+        assert_eq!(fingerprint("gpt-5", &usage(0, 0), true), None);
+        assert_eq!(split_fingerprint("sonnet:m"), ("sonnet", "m"));
+    }
+}
@@ -0,0 +1,162 @@
+//! 上游连接保温
+//!
+//! 空闲一段时间后连接池里的连接会被回收，下一次请求要重新走 DNS + TCP + TLS 握手。
+//! 开启 `proxyKeepWarm` 后，代理运行期间定期向各已接管应用的当前供应商发送一次轻量
+//! `HEAD` 请求，使用与转发器相同的连接池客户端，让连接保持可复用。
+//!
+//! 只有走 reqwest 连接池的供应商才会保温：需要保留原始 header 大小写的 Anthropic 直连
+//! 走逐请求握手的 raw write 路径，保温对其无效，因此跳过。
+
+use std::time::Duration;
+
+use tauri::Manager;
+
+use crate::app_config::AppType;
+use crate::provider::Provider;
+use crate::proxy::providers::{get_adapter, get_claude_api_format};
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+/// 保温间隔：需小于连接池的空闲回收时间（reqwest 默认 90 秒）
+const WARM_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 单次保温请求超时
+const WARM_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 转发时是否会走 reqwest 连接池（与转发器的路径选择保持一致）
+fn uses_pooled_client(app_type: &AppType, provider: &Provider, base_url: &str) -> bool {
+    if !matches!(app_type, AppType::Claude | AppType::ClaudeDesktop) {
+        return true;
+    }
+    if crate::proxy::http_client::get_current_proxy_url()
+        .is_some_and(|url| url.starts_with("socks5"))
+    {
+        return true;
+    }
+    let meta = provider.meta.as_ref();
+    meta.and_then(|meta| meta.tls_options()).is_some()
+        || meta.and_then(|meta| meta.provider_type.as_deref()) == Some("github_copilot")
+        || base_url.contains("githubcopilot.com")
+        || get_claude_api_format(provider) != "anthropic"
+}
+
+/// 保温目标：基础地址的 origin；`mock://` 等非 HTTP 地址返回 None
+fn warm_target(base_url: &str) -> Option<String> {
+    let parsed = url::Url::parse(base_url.trim()).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
+    }
+    Some(parsed.origin().ascii_serialization())
+}
+
+/// 对单个应用的当前供应商保温一次
+async fn warm_app(state: &AppState, app_type: &AppType) -> Option<()> {
+    let enabled = state
+        .db
+        .get_proxy_config_for_app(app_type.as_str())
+        .await
+        .is_ok_and(|config| config.enabled);
+    if !enabled {
+        return None;
+    }
+    let current = ProviderService::current(state, app_type.clone()).ok()?;
+    let provider = state
+        .db
+        .get_provider_by_id(&current, app_type.as_str())
+        .ok()??;
+    let base_url = get_adapter(app_type).extract_base_url(&provider).ok()?;
+    if !uses_pooled_client(app_type, &provider, &base_url) {
+        return None;
+    }
+    let target = warm_target(&base_url)?;
+
+    let meta = provider.meta.as_ref();
+    let connect_timeout = meta
+        .and_then(|meta| meta.timeouts())
+        .and_then(|timeouts| timeouts.connect_timeout());
+    let client = crate::proxy::http_client::get_for_provider(
+        meta.and_then(|meta| meta.tls_options()),
+        connect_timeout,
+    )
+    .map_err(|e| log::debug!("[WarmPool] 获取客户端失败: {e}"))
+    .ok()?;
+
+    // 只关心连接本身，任何 HTTP 状态码都说明连接可用
+    match client.head(&target).timeout(WARM_TIMEOUT).send().await {
+        Ok(response) => log::trace!(
+            "[WarmPool] {} → {target} ({})",
+            provider.name,
+            response.status()
+        ),
+        Err(e) => log::debug!("[WarmPool] {} → {target} 保温失败: {e}", provider.name),
+    }
+    Some(())
+}
+
+/// 后台保温任务：设置关闭或代理未运行时空转
+pub fn spawn(app: &tauri::AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(WARM_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            if !crate::settings::proxy_keep_warm() {
+                continue;
+            }
+            let state = app.state::<AppState>();
+            if !state.proxy_service.is_running().await {
+                continue;
+            }
+            for app_type in AppType::all().filter(|app| !app.is_additive_mode()) {
+                warm_app(&state, &app_type).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn warm_target_is_origin_of_http_urls() {
+        assert_eq!(
+            warm_target("https://relay.example:8443/api/v1/").as_deref(),
+            Some("https://relay.example:8443")
+        );
+        assert_eq!(
+            warm_target(" http://127.0.0.1:3000 ").as_deref(),
+            Some("http://127.0.0.1:3000")
+        );
+        assert!(warm_target("mock://ok").is_none());
+        assert!(warm_target("not a url").is_none());
+    }
+
+    #[test]
+    fn anthropic_raw_write_providers_are_skipped() {
+        let anthropic = Provider::with_id("a".into(), "A".into(), json!({}), None);
+        assert!(!uses_pooled_client(
+            &AppType::Claude,
+            &anthropic,
+            "https://api.anthropic.com"
+        ));
+        assert!(uses_pooled_client(
+            &AppType::Codex,
+            &anthropic,
+            "https://api.openai.com"
+        ));
+
+        let mut openai_format = anthropic.clone();
+        openai_format.meta = Some(crate::provider::ProviderMeta {
+            api_format: Some("openai_chat".into()),
+            ..Default::default()
+        });
+        assert!(uses_pooled_client(
+            &AppType::Claude,
+            &openai_format,
+            "https://relay.example"
+        ));
+    }
+}
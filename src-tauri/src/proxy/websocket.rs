@@ -0,0 +1,725 @@
+//! WebSocket / Realtime 透传
+//!
+//! Codex 的 Responses WebSocket 与 OpenAI Realtime 都通过 HTTP/1.1 Upgrade 建立连接，
+//! 普通的请求转发无法处理。这里按路由选出的供应商依次尝试建立上游 `wss://` 连接
+//! （注入供应商鉴权，丢弃客户端携带的占位凭据），握手成功后原样双向转发帧，同时：
+//!
+//! - 解析上游文本消息中的 `response.completed` / `response.done`，每个响应记一条使用量；
+//! - 连接关闭时输出连接时长与双向消息数；整个连接没有产生使用量时也记一条（token 为 0），
+//!   请求日志中能看到这次连接。
+//!
+//! 为了能解析帧，握手时去掉 `Sec-WebSocket-Extensions`（不协商 permessage-deflate）。
+//! 上游连接始终直连，不经过配置的 HTTP/SOCKS 代理。
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::upgrade::OnUpgrade;
+use hyper_util::rt::TokioIo;
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use super::response_processor::{log_usage_internal, usage_logging_enabled};
+use super::server::ProxyState;
+use super::usage::parser::TokenUsage;
+use super::{hyper_client, ProxyError};
+use crate::app_config::AppType;
+use crate::provider::Provider;
+use crate::services::curl_export::{UpstreamTarget, WireFormat};
+
+/// 握手超时
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 单条上游文本消息最多缓存的字节数，超出的消息不解析使用量（音频增量等大消息）
+const MAX_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+
+/// 不透传给上游的握手头：逐跳头、客户端凭据与压缩扩展
+const SKIPPED_HEADERS: &[&str] = &[
+    "host",
+    "content-length",
+    "authorization",
+    "x-api-key",
+    "api-key",
+    "proxy-authorization",
+    "sec-websocket-extensions",
+];
+
+/// 浏览器客户端通过子协议传递密钥（`openai-insecure-api-key.<key>`），由供应商鉴权替代
+const INSECURE_KEY_PROTOCOL_PREFIX: &str = "openai-insecure-api-key.";
+
+/// 处理 Codex 路由上的 WebSocket 升级（`/v1/responses`、`/v1/realtime` 等）
+pub async fn handle_codex_websocket(
+    State(state): State<ProxyState>,
+    request: axum::extract::Request,
+) -> Result<Response, ProxyError> {
+    handle_upgrade(state, request, AppType::Codex).await
+}
+
+/// 请求是否为 WebSocket 升级
+pub(crate) fn is_websocket_upgrade(headers: &HeaderMap) -> bool {
+    let connection_upgrade = headers
+        .get_all(header::CONNECTION)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|token| token.trim().eq_ignore_ascii_case("upgrade"));
+    let websocket = headers
+        .get(header::UPGRADE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim().eq_ignore_ascii_case("websocket"));
+    connection_upgrade && websocket
+}
+
+/// 本地路径对应的上游端点（保留查询串，如 Realtime 的 `?model=`）
+fn upstream_endpoint(path: &str, query: Option<&str>) -> String {
+    let endpoint = if path.trim_end_matches('/').ends_with("/realtime") {
+        "/realtime"
+    } else {
+        "/responses"
+    };
+    match query.filter(|query| !query.is_empty()) {
+        Some(query) => format!("{endpoint}?{query}"),
+        None => endpoint.to_string(),
+    }
+}
+
+/// 查询串中的 `model` 参数（Realtime 通过它指定模型）
+fn query_model(query: Option<&str>) -> Option<String> {
+    url::form_urlencoded::parse(query?.as_bytes())
+        .find(|(key, _)| key == "model")
+        .map(|(_, value)| value.into_owned())
+        .filter(|value| !value.is_empty())
+}
+
+async fn handle_upgrade(
+    state: ProxyState,
+    request: axum::extract::Request,
+    app_type: AppType,
+) -> Result<Response, ProxyError> {
+    if !is_websocket_upgrade(request.headers()) {
+        return Err(ProxyError::InvalidRequest(
+            "该端点的 GET 请求只接受 WebSocket 升级".to_string(),
+        ));
+    }
+    let (mut parts, _body) = request.into_parts();
+    let on_upgrade = parts
+        .extensions
+        .remove::<OnUpgrade>()
+        .ok_or_else(|| ProxyError::Internal("连接不支持协议升级".to_string()))?;
+    let endpoint = upstream_endpoint(parts.uri.path(), parts.uri.query());
+    let model_hint = query_model(parts.uri.query());
+
+    let providers = state
+        .provider_router
+        .select_providers(app_type.as_str())
+        .await
+        .map_err(|e| match e {
+            crate::error::AppError::AllProvidersCircuitOpen => ProxyError::AllProvidersCircuitOpen,
+            crate::error::AppError::NoProvidersConfigured => ProxyError::NoProvidersConfigured,
+            _ => ProxyError::DatabaseError(e.to_string()),
+        })?;
+
+    let started = Instant::now();
+    let mut last_error = None;
+    for provider in providers {
+        let target = match UpstreamTarget::resolve(&app_type, &provider, None) {
+            Ok(target) if target.format == WireFormat::OpenAiResponses => target,
+            Ok(_) => {
+                log::debug!(
+                    "[WS] 供应商 {} 经协议转换接入，不支持 WebSocket，跳过",
+                    provider.name
+                );
+                continue;
+            }
+            Err(e) => {
+                log::debug!("[WS] 供应商 {} 无法建立上游连接: {e}", provider.name);
+                continue;
+            }
+        };
+        let upstream_request = match build_upstream_request(&target, &endpoint, &parts.headers) {
+            Ok(request) => request,
+            Err(e) => {
+                last_error = Some(e);
+                continue;
+            }
+        };
+
+        match hyper_client::send_upgrade(upstream_request, HANDSHAKE_TIMEOUT).await {
+            Ok(response) if response.status() == StatusCode::SWITCHING_PROTOCOLS => {
+                log::info!(
+                    "[WS] {} → {} 握手成功 ({}ms)",
+                    endpoint,
+                    provider.name,
+                    started.elapsed().as_millis()
+                );
+                return Ok(start_tunnel(
+                    state, app_type, provider, model_hint, on_upgrade, response, started,
+                ));
+            }
+            Ok(response) if response.status().is_server_error() => {
+                log::warn!(
+                    "[WS] 供应商 {} 握手失败: {}，尝试下一个",
+                    provider.name,
+                    response.status()
+                );
+                last_error = Some(ProxyError::UpstreamError {
+                    status: response.status().as_u16(),
+                    body: None,
+                });
+            }
+            // 4xx（鉴权失败、不支持 WebSocket 等）原样返回给客户端
+            Ok(response) => return Ok(rejection_response(response).await),
+            Err(e) => {
+                log::warn!("[WS] 供应商 {} 握手失败: {e}", provider.name);
+                last_error = Some(e);
+            }
+        }
+    }
+    Err(last_error.unwrap_or(ProxyError::NoAvailableProvider))
+}
+
+/// 组装上游握手请求：透传客户端的 WebSocket 头，鉴权换成供应商的
+fn build_upstream_request(
+    target: &UpstreamTarget,
+    endpoint: &str,
+    client_headers: &HeaderMap,
+) -> Result<http::Request<Full<Bytes>>, ProxyError> {
+    let (url, auth_headers) = target
+        .headers(false)
+        .and_then(|headers| target.authorize("GET", target.url(endpoint), headers, &[], false))
+        .map_err(|e| ProxyError::AuthError(e.to_string()))?;
+    let url = match url.strip_prefix("wss://") {
+        Some(rest) => format!("https://{rest}"),
+        None => match url.strip_prefix("ws://") {
+            Some(rest) => format!("http://{rest}"),
+            None => url,
+        },
+    };
+    let uri: http::Uri = url
+        .parse()
+        .map_err(|e| ProxyError::ConfigError(format!("上游地址无效 {url}: {e}")))?;
+
+    let mut builder = http::Request::get(uri.clone());
+    for (name, value) in client_headers {
+        let name_str = name.as_str();
+        if SKIPPED_HEADERS.contains(&name_str) {
+            continue;
+        }
+        if name == header::SEC_WEBSOCKET_PROTOCOL {
+            if let Some(value) = strip_insecure_key_protocol(value) {
+                builder = builder.header(name, value);
+            }
+            continue;
+        }
+        builder = builder.header(name, value);
+    }
+    if let Some(host) = uri.authority() {
+        builder = builder.header(header::HOST, host.as_str());
+    }
+    for (name, value) in auth_headers {
+        if name == "content-type" {
+            continue;
+        }
+        builder = builder.header(name, value);
+    }
+    builder
+        .body(Full::new(Bytes::new()))
+        .map_err(|e| ProxyError::Internal(format!("构建握手请求失败: {e}")))
+}
+
+/// 去掉子协议列表中的占位密钥；列表因此为空时返回 None
+fn strip_insecure_key_protocol(value: &HeaderValue) -> Option<HeaderValue> {
+    let protocols: Vec<&str> = value
+        .to_str()
+        .ok()?
+        .split(',')
+        .map(str::trim)
+        .filter(|protocol| {
+            !protocol.is_empty() && !protocol.starts_with(INSECURE_KEY_PROTOCOL_PREFIX)
+        })
+        .collect();
+    if protocols.is_empty() {
+        return None;
+    }
+    HeaderValue::from_str(&protocols.join(", ")).ok()
+}
+
+/// 上游拒绝升级时把状态码与响应体原样交给客户端
+async fn rejection_response(response: hyper::Response<hyper::body::Incoming>) -> Response {
+    let (parts, body) = response.into_parts();
+    let body = body
+        .collect()
+        .await
+        .map(|collected| collected.to_bytes())
+        .unwrap_or_default();
+    let mut response = (parts.status, body).into_response();
+    if let Some(content_type) = parts.headers.get(header::CONTENT_TYPE) {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, content_type.clone());
+    }
+    response
+}
+
+/// 向客户端返回 101，并在后台把两端升级后的连接接起来
+fn start_tunnel(
+    state: ProxyState,
+    app_type: AppType,
+    provider: Provider,
+    model_hint: Option<String>,
+    client_upgrade: OnUpgrade,
+    upstream_response: hyper::Response<hyper::body::Incoming>,
+    started: Instant,
+) -> Response {
+    let mut response = Response::new(axum::body::Body::empty());
+    *response.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+    for (name, value) in upstream_response.headers() {
+        if name == header::CONTENT_LENGTH {
+            continue;
+        }
+        response.headers_mut().append(name, value.clone());
+    }
+
+    tokio::spawn(async move {
+        let upgraded = tokio::try_join!(client_upgrade, hyper::upgrade::on(upstream_response));
+        let (client, upstream) = match upgraded {
+            Ok(pair) => pair,
+            Err(e) => {
+                log::warn!("[WS] 连接升级失败: {e}");
+                return;
+            }
+        };
+        let stats = tunnel(TokioIo::new(client), TokioIo::new(upstream), started).await;
+        record_usage(
+            &state,
+            &app_type,
+            &provider,
+            model_hint.as_deref(),
+            stats,
+            started,
+        )
+        .await;
+    });
+
+    response
+}
+
+/// 一次连接中已完成的响应
+struct CompletedTurn {
+    usage: TokenUsage,
+    latency_ms: u64,
+    first_token_ms: Option<u64>,
+}
+
+/// 连接统计（两个方向共享）
+#[derive(Default)]
+struct TunnelStats {
+    client_messages: u64,
+    upstream_messages: u64,
+    /// 首个上游消息距连接开始的毫秒数
+    first_upstream_ms: Option<u64>,
+    /// 当前轮次：上一个响应结束后客户端发出的第一条消息
+    turn_started: Option<Instant>,
+    turn_first_token_ms: Option<u64>,
+    completed: Vec<CompletedTurn>,
+}
+
+impl TunnelStats {
+    fn on_client_message(&mut self, now: Instant) {
+        self.client_messages += 1;
+        self.turn_started.get_or_insert(now);
+    }
+
+    fn on_upstream_message(&mut self, now: Instant, started: Instant, payload: &[u8]) {
+        self.upstream_messages += 1;
+        self.first_upstream_ms
+            .get_or_insert_with(|| now.duration_since(started).as_millis() as u64);
+        let turn_started = self.turn_started.unwrap_or(started);
+        self.turn_first_token_ms
+            .get_or_insert_with(|| now.duration_since(turn_started).as_millis() as u64);
+        if let Some(usage) = response_usage(payload) {
+            self.completed.push(CompletedTurn {
+                usage,
+                latency_ms: now.duration_since(turn_started).as_millis() as u64,
+                first_token_ms: self.turn_first_token_ms.take(),
+            });
+            self.turn_started = None;
+        }
+    }
+}
+
+/// 上游文本消息若为响应结束事件，提取其中的使用量
+fn response_usage(payload: &[u8]) -> Option<TokenUsage> {
+    if payload.is_empty() {
+        return None;
+    }
+    let event: Value = serde_json::from_slice(payload).ok()?;
+    match event.get("type")?.as_str()? {
+        "response.completed" | "response.done" | "response.incomplete" | "response.failed" => {
+            TokenUsage::from_codex_response_auto(event.get("response")?)
+        }
+        _ => None,
+    }
+}
+
+/// 双向转发，任一方向结束后关闭对端写入，等待另一方向结束
+async fn tunnel<C, U>(client: C, upstream: U, started: Instant) -> TunnelStats
+where
+    C: AsyncRead + AsyncWrite + Unpin,
+    U: AsyncRead + AsyncWrite + Unpin,
+{
+    let stats = Mutex::new(TunnelStats::default());
+    let (client_read, client_write) = tokio::io::split(client);
+    let (upstream_read, upstream_write) = tokio::io::split(upstream);
+
+    let outbound = pump(
+        client_read,
+        upstream_write,
+        FrameScanner::new(false),
+        |_| {
+            if let Ok(mut stats) = stats.lock() {
+                stats.on_client_message(Instant::now());
+            }
+        },
+    );
+    let inbound = pump(
+        upstream_read,
+        client_write,
+        FrameScanner::new(true),
+        |payload| {
+            if let Ok(mut stats) = stats.lock() {
+                stats.on_upstream_message(Instant::now(), started, payload);
+            }
+        },
+    );
+    let (outbound, inbound) = tokio::join!(outbound, inbound);
+    for (direction, result) in [("client→upstream", outbound), ("upstream→client", inbound)] {
+        if let Err(e) = result {
+            log::debug!("[WS] {direction} 连接中断: {e}");
+        }
+    }
+    stats.into_inner().unwrap_or_default()
+}
+
+async fn pump<R, W>(
+    mut reader: R,
+    mut writer: W,
+    mut scanner: FrameScanner,
+    mut on_message: impl FnMut(&[u8]),
+) -> std::io::Result<()>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut buf = vec![0u8; 16 * 1024];
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        scanner.feed(&buf[..n], &mut on_message);
+        writer.write_all(&buf[..n]).await?;
+    }
+    writer.shutdown().await
+}
+
+/// 连接结束后写入使用量日志
+async fn record_usage(
+    state: &ProxyState,
+    app_type: &AppType,
+    provider: &Provider,
+    model_hint: Option<&str>,
+    stats: TunnelStats,
+    started: Instant,
+) {
+    let duration_ms = started.elapsed().as_millis() as u64;
+    log::info!(
+        "[WS] 连接关闭 ({}): 时长 {:.1}s，客户端消息 {}，上游消息 {}，完成响应 {}",
+        provider.name,
+        duration_ms as f64 / 1000.0,
+        stats.client_messages,
+        stats.upstream_messages,
+        stats.completed.len()
+    );
+    if !usage_logging_enabled(state) {
+        return;
+    }
+
+    let fallback_model = model_hint.unwrap_or("unknown");
+    let turns = if stats.completed.is_empty() {
+        vec![CompletedTurn {
+            usage: TokenUsage::default(),
+            latency_ms: duration_ms,
+            first_token_ms: stats.first_upstream_ms,
+        }]
+    } else {
+        stats.completed
+    };
+    for turn in turns {
+        let model = turn
+            .usage
+            .model
+            .clone()
+            .unwrap_or_else(|| fallback_model.to_string());
+        log_usage_internal(
+            state,
+            &provider.id,
+            app_type.as_str(),
+            &model,
+            fallback_model,
+            fallback_model,
+            turn.usage,
+            turn.latency_ms,
+            turn.first_token_ms,
+            true,
+            200,
+            None,
+            0,
+        )
+        .await;
+    }
+}
+
+/// 只识别帧边界的 WebSocket 帧扫描器
+///
+/// 不修改转发的字节，只统计完整数据消息数；`collect_text` 时还会拼出文本消息内容
+/// （含分片消息、跳过穿插的控制帧、去掉掩码）供解析使用量。
+#[derive(Debug, Default)]
+struct FrameScanner {
+    collect_text: bool,
+    header: Vec<u8>,
+    remaining: u64,
+    data_frame: bool,
+    fin: bool,
+    mask: Option<[u8; 4]>,
+    mask_offset: usize,
+    text: bool,
+    message: Vec<u8>,
+    overflow: bool,
+}
+
+impl FrameScanner {
+    fn new(collect_text: bool) -> Self {
+        Self {
+            collect_text,
+            ..Self::default()
+        }
+    }
+
+    /// 喂入一段字节流；每收完一条数据消息调用一次 `on_message`
+    ///
+    /// 文本内容只在 `collect_text` 且未超过上限时传入，否则为空切片。
+    fn feed(&mut self, mut data: &[u8], on_message: &mut impl FnMut(&[u8])) {
+        while !data.is_empty() {
+            if self.remaining > 0 {
+                let take = self.remaining.min(data.len() as u64) as usize;
+                self.consume_payload(&data[..take]);
+                self.remaining -= take as u64;
+                data = &data[take..];
+                if self.remaining == 0 {
+                    self.finish_frame(on_message);
+                }
+                continue;
+            }
+
+            self.header.push(data[0]);
+            data = &data[1..];
+            match header_len(&self.header) {
+                Some(len) if self.header.len() == len => {
+                    self.start_frame();
+                    if self.remaining == 0 {
+                        self.finish_frame(on_message);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn start_frame(&mut self) {
+        let header = std::mem::take(&mut self.header);
+        let opcode = header[0] & 0x0f;
+        self.fin = header[0] & 0x80 != 0;
+        let (len, offset) = match header[1] & 0x7f {
+            126 => (u64::from(u16::from_be_bytes([header[2], header[3]])), 4),
+            127 => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&header[2..10]);
+                (u64::from_be_bytes(bytes), 10)
+            }
+            len => (u64::from(len), 2),
+        };
+        self.mask = (header[1] & 0x80 != 0).then(|| {
+            [
+                header[offset],
+                header[offset + 1],
+                header[offset + 2],
+                header[offset + 3],
+            ]
+        });
+        self.mask_offset = 0;
+        // 0 续帧、1 文本、2 二进制；8 以上为控制帧，可穿插在分片消息之间
+        self.data_frame = opcode <= 2;
+        if opcode == 1 || opcode == 2 {
+            self.text = opcode == 1;
+            self.message.clear();
+            self.overflow = false;
+        }
+        self.remaining = len;
+    }
+
+    fn consume_payload(&mut self, chunk: &[u8]) {
+        if !(self.data_frame && self.collect_text && self.text) || self.overflow {
+            return;
+        }
+        if self.message.len() + chunk.len() > MAX_MESSAGE_BYTES {
+            self.overflow = true;
+            self.message = Vec::new();
+            return;
+        }
+        match self.mask {
+            Some(mask) => {
+                for byte in chunk {
+                    self.message.push(byte ^ mask[self.mask_offset % 4]);
+                    self.mask_offset += 1;
+                }
+            }
+            None => self.message.extend_from_slice(chunk),
+        }
+    }
+
+    fn finish_frame(&mut self, on_message: &mut impl FnMut(&[u8])) {
+        if !(self.data_frame && self.fin) {
+            return;
+        }
+        if self.collect_text && self.text && !self.overflow {
+            on_message(&self.message);
+        } else {
+            on_message(&[]);
+        }
+        self.message.clear();
+    }
+}
+
+/// 帧头长度；前两个字节未到齐时返回 None
+fn header_len(header: &[u8]) -> Option<usize> {
+    let second = *header.get(1)?;
+    let extended = match second & 0x7f {
+        126 => 2,
+        127 => 8,
+        _ => 0,
+    };
+    let mask = if second & 0x80 != 0 { 4 } else { 0 };
+    Some(2 + extended + mask)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(opcode: u8, fin: bool, payload: &[u8], mask: Option<[u8; 4]>) -> Vec<u8> {
+        let mut out = vec![if fin { 0x80 | opcode } else { opcode }];
+        let mask_bit = if mask.is_some() { 0x80 } else { 0 };
+        if payload.len() < 126 {
+            out.push(mask_bit | payload.len() as u8);
+        } else {
+            out.push(mask_bit | 126);
+            out.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+        }
+        match mask {
+            Some(mask) => {
+                out.extend_from_slice(&mask);
+                out.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+            }
+            None => out.extend_from_slice(payload),
+        }
+        out
+    }
+
+    fn scan(scanner: &mut FrameScanner, bytes: &[u8], chunk: usize) -> Vec<Vec<u8>> {
+        let mut messages = Vec::new();
+        for part in bytes.chunks(chunk) {
+            scanner.feed(part, &mut |payload: &[u8]| messages.push(payload.to_vec()));
+        }
+        messages
+    }
+
+    #[test]
+    fn scanner_reassembles_messages_across_chunks() {
+        let long = "x".repeat(300);
+        let mut bytes = frame(1, true, b"{\"type\":\"a\"}", None);
+        bytes.extend(frame(1, true, long.as_bytes(), None));
+        // 分片文本消息中间穿插 ping
+        bytes.extend(frame(1, false, b"hel", None));
+        bytes.extend(frame(9, true, b"", None));
+        bytes.extend(frame(0, true, b"lo", None));
+        bytes.extend(frame(2, true, &[1, 2, 3], None));
+
+        let messages = scan(&mut FrameScanner::new(true), &bytes, 7);
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages[0], b"{\"type\":\"a\"}");
+        assert_eq!(messages[1], long.as_bytes());
+        assert_eq!(messages[2], b"hello");
+        // 二进制消息只计数
+        assert!(messages[3].is_empty());
+    }
+
+    #[test]
+    fn scanner_unmasks_client_frames_and_counts_without_collecting() {
+        let bytes = frame(1, true, b"{\"a\":1}", Some([1, 2, 3, 4]));
+        assert_eq!(
+            scan(&mut FrameScanner::new(true), &bytes, 3),
+            vec![b"{\"a\":1}".to_vec()]
+        );
+        assert_eq!(
+            scan(&mut FrameScanner::new(false), &bytes, 64),
+            vec![Vec::<u8>::new()]
+        );
+    }
+
+    #[test]
+    fn completed_responses_become_usage_turns() {
+        let started = Instant::now();
+        let mut stats = TunnelStats::default();
+        stats.on_client_message(started);
+        stats.on_upstream_message(started, started, br#"{"type":"response.created"}"#);
+        stats.on_upstream_message(
+            started + Duration::from_millis(50),
+            started,
+            br#"{"type":"response.done","response":{"model":"gpt-realtime","usage":{"input_tokens":10,"output_tokens":4}}}"#,
+        );
+        assert_eq!(stats.completed.len(), 1);
+        assert_eq!(stats.completed[0].usage.input_tokens, 10);
+        assert_eq!(stats.completed[0].latency_ms, 50);
+        assert_eq!(stats.completed[0].first_token_ms, Some(0));
+        assert!(stats.turn_started.is_none());
+    }
+
+    #[test]
+    fn detects_upgrade_and_maps_endpoints() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONNECTION, "keep-alive, Upgrade".parse().unwrap());
+        headers.insert(header::UPGRADE, "websocket".parse().unwrap());
+        assert!(is_websocket_upgrade(&headers));
+        headers.remove(header::UPGRADE);
+        assert!(!is_websocket_upgrade(&headers));
+
+        assert_eq!(upstream_endpoint("/v1/responses", None), "/responses");
+        assert_eq!(
+            upstream_endpoint("/v1/realtime", Some("model=gpt-realtime")),
+            "/realtime?model=gpt-realtime"
+        );
+        assert_eq!(
+            query_model(Some("model=gpt-realtime")).as_deref(),
+            Some("gpt-realtime")
+        );
+
+        let protocols = HeaderValue::from_static("realtime, openai-insecure-api-key.sk-dummy");
+        assert_eq!(strip_insecure_key_protocol(&protocols).unwrap(), "realtime");
+    }
+}
@@ -0,0 +1,32 @@
+//! 安全模式启动
+//!
+//! `--safe-mode`（或环境变量 `CC_SWITCH_SAFE_MODE=1`）启动时只加载数据库与核心服务：
+//! 不自动恢复代理接管、不启动云同步 worker 与各类定时任务、不注册全局快捷键，
+//! 全局出站代理按直连初始化。用于代理或同步配置错误导致应用一启动就崩溃时，
+//! 进入界面修正配置；之后正常启动即可恢复。命令行同样接受该参数。
+//!
+//! 上次异常退出残留的 Live 接管仍会恢复，这一步只读写本地文件。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 环境变量开关（macOS 从访达启动时无法附加命令行参数）
+pub const SAFE_MODE_ENV: &str = "CC_SWITCH_SAFE_MODE";
+
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+pub fn enable() {
+    if !SAFE_MODE.swap(true, Ordering::AcqRel) {
+        log::warn!("安全模式：跳过代理自动启动、后台同步与定时任务");
+    }
+}
+
+pub fn is_enabled() -> bool {
+    SAFE_MODE.load(Ordering::Acquire)
+}
+
+/// 环境变量是否要求安全模式（`1` / `true` / `yes`）
+pub fn requested_by_env() -> bool {
+    std::env::var(SAFE_MODE_ENV)
+        .map(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
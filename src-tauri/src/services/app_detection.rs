@@ -0,0 +1,177 @@
+//! 应用（CLI 工具）检测：最低支持版本与安装方式
+//!
+//! 可执行文件的定位（PATH、npm 全局、nvm/volta/homebrew 等平台目录）与
+//! `--version` 探测在 `commands::misc` 中完成；这里集中放与平台无关的判定策略：
+//!
+//! - 每个工具的最低支持版本，低于该版本时 cc-switch 写入的配置可能不被识别；
+//! - 由可执行文件路径推断安装方式（npm / 原生安装器 / homebrew / ...），
+//!   供界面展示并决定升级命令走向。
+
+use std::cmp::Ordering;
+use std::path::Path;
+
+/// 各工具的最低支持版本（低于此版本的配置格式与 cc-switch 写入的不兼容）
+const MIN_SUPPORTED_VERSIONS: &[(&str, &str)] = &[
+    // ANTHROPIC_DEFAULT_*_MODEL 等 env 键自 1.0 起才被读取
+    ("claude", "1.0.0"),
+    // model_providers / wire_api 配置由 Rust 版 codex 提供
+    ("codex", "0.20.0"),
+    // GOOGLE_GEMINI_BASE_URL 自定义端点
+    ("gemini", "0.2.0"),
+    // provider.options.baseURL 配置结构
+    ("opencode", "0.3.0"),
+];
+
+pub struct AppDetectionService;
+
+impl AppDetectionService {
+    /// 工具的最低支持版本；未登记的工具不做限制
+    pub fn min_supported_version(tool: &str) -> Option<&'static str> {
+        MIN_SUPPORTED_VERSIONS
+            .iter()
+            .find(|(name, _)| *name == tool)
+            .map(|(_, version)| *version)
+    }
+
+    /// 已安装版本是否低于最低支持版本。版本无法解析时不告警，避免误报。
+    pub fn is_below_minimum(tool: &str, version: &str) -> bool {
+        Self::min_supported_version(tool)
+            .and_then(|min| compare_semver(version, min))
+            .is_some_and(|ord| ord == Ordering::Less)
+    }
+
+    /// 由路径推断的安装方式。`source` 为按入口路径前缀推断的来源
+    /// （nvm/homebrew/volta/...），`real` 为 canonicalize 后的真身路径。
+    ///
+    /// 入口落在系统目录时再看真身：原生安装器（Claude Code 的
+    /// `~/.local/share/claude/versions/`）与 npm 全局包（`node_modules`）
+    /// 仅凭入口路径无法区分。
+    pub fn install_method(tool: &str, source: &'static str, real: &Path) -> &'static str {
+        let real = real
+            .to_string_lossy()
+            .replace('\\', "/")
+            .to_ascii_lowercase();
+        if tool == "claude"
+            && (real.contains("/.local/share/claude/") || real.contains("/claude/versions/"))
+        {
+            return "native";
+        }
+        if source != "system" {
+            return source;
+        }
+        if real.contains("/node_modules/") {
+            "npm"
+        } else if real.contains("/cellar/") {
+            "homebrew"
+        } else {
+            "system"
+        }
+    }
+}
+
+/// 解析 "2.1.156" / "2.1.156-beta.1" → (主版本三段, 预发布段)。无法解析返回 None。
+/// 与前端 `src/lib/version.ts` 的 parseVersion 语义对称(跨语言各实现一份)。
+/// patch 用 u64 以容纳 codex 的 `0.1.2505172116` 时间戳式版本而不溢出。
+fn parse_semver(v: &str) -> Option<([u64; 3], Vec<String>)> {
+    // 忽略 `+build` 元数据,再以首个 `-` 切出预发布段。
+    let core_and_pre = v.trim().split('+').next().unwrap_or("");
+    let (core, pre) = match core_and_pre.split_once('-') {
+        Some((c, p)) => (c, Some(p)),
+        None => (core_and_pre, None),
+    };
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse::<u64>().ok()?;
+    let minor = parts.next()?.parse::<u64>().ok()?;
+    let patch = parts.next()?.parse::<u64>().ok()?;
+    if parts.next().is_some() {
+        return None; // 多于三段,非法
+    }
+    let pre_segments = pre
+        .map(|p| p.split('.').map(|s| s.to_string()).collect())
+        .unwrap_or_default();
+    Some(([major, minor, patch], pre_segments))
+}
+
+/// 比较两个版本号(遵循 semver:主版本三段优先;core 相等时有预发布 < 无预发布;
+/// 预发布段逐段比 —— 数字段按数值、数字段 < 非数字段、非数字段按 ASCII、前缀相同
+/// 则段更多者更大)。任一无法解析返回 None,调用方据此保守处理。
+pub(crate) fn compare_semver(a: &str, b: &str) -> Option<Ordering> {
+    let (ac, ap) = parse_semver(a)?;
+    let (bc, bp) = parse_semver(b)?;
+    for i in 0..3 {
+        match ac[i].cmp(&bc[i]) {
+            Ordering::Equal => continue,
+            other => return Some(other),
+        }
+    }
+    match (ap.is_empty(), bp.is_empty()) {
+        (true, true) => return Some(Ordering::Equal),
+        (true, false) => return Some(Ordering::Greater),
+        (false, true) => return Some(Ordering::Less),
+        (false, false) => {}
+    }
+    for (x, y) in ap.iter().zip(bp.iter()) {
+        let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(xv), Ok(yv)) => xv.cmp(&yv),
+            (Ok(_), Err(_)) => Ordering::Less, // 数字段 < 非数字段
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.as_str().cmp(y.as_str()),
+        };
+        if ord != Ordering::Equal {
+            return Some(ord);
+        }
+    }
+    Some(ap.len().cmp(&bp.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn below_minimum_only_for_registered_tools_with_parsable_versions() {
+        assert!(AppDetectionService::is_below_minimum("claude", "0.2.125"));
+        assert!(!AppDetectionService::is_below_minimum("claude", "1.0.0"));
+        assert!(!AppDetectionService::is_below_minimum("claude", "2.1.3"));
+        assert!(AppDetectionService::is_below_minimum("codex", "0.19.9"));
+        // 未登记的工具与无法解析的版本都不告警
+        assert!(!AppDetectionService::is_below_minimum("hermes", "0.0.1"));
+        assert!(!AppDetectionService::is_below_minimum("claude", "unknown"));
+    }
+
+    #[test]
+    fn install_method_looks_through_system_entry_points() {
+        assert_eq!(
+            AppDetectionService::install_method(
+                "claude",
+                "system",
+                Path::new("/Users/me/.local/share/claude/versions/2.0.1"),
+            ),
+            "native"
+        );
+        assert_eq!(
+            AppDetectionService::install_method(
+                "codex",
+                "system",
+                Path::new("/usr/local/lib/node_modules/@openai/codex/bin/codex.js"),
+            ),
+            "npm"
+        );
+        assert_eq!(
+            AppDetectionService::install_method(
+                "gemini",
+                "nvm",
+                Path::new("/Users/me/.nvm/versions/node/v22/lib/node_modules/x.js"),
+            ),
+            "nvm"
+        );
+        assert_eq!(
+            AppDetectionService::install_method(
+                "opencode",
+                "system",
+                Path::new("/usr/bin/opencode")
+            ),
+            "system"
+        );
+    }
+}
@@ -0,0 +1,507 @@
+//! 供应商能力探测：用少量廉价的定向请求得出能力矩阵
+//!
+//! 每项能力只发一个最小请求（`max_tokens` 很小、单条 "ping"），按状态码判定：
+//! - 2xx → 支持；
+//! - 400 / 404 / 413 / 422 → 不支持（上游明确拒绝了该特性）；
+//! - 其他（限流、5xx、网络错误）→ 未知，不据此做路由决策。
+//!
+//! 基线请求不通时直接报错、不落库，避免把鉴权失败误记为"全部不支持"。
+//! 矩阵按供应商 + 应用持久化，代理在故障转移排序时会参考它（见 [`prefer_capable`]）。
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::curl_export::{UpstreamTarget, WireFormat};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(30);
+const CONTEXT_1M_BETA: &str = "context-1m-2025-08-07";
+/// 1x1 透明 PNG
+const PROBE_PNG_BASE64: &str =
+    "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mP8/5+hHgAHggJ/PchI7wAAAABJRU5ErkJggg==";
+/// 最大输出 token 探测阶梯，取第一个被接受的值
+const MAX_OUTPUT_LADDER: [u32; 6] = [128_000, 64_000, 32_000, 16_384, 8_192, 4_096];
+
+/// 单项能力的探测结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CapabilitySupport {
+    Supported,
+    Unsupported,
+    #[default]
+    Unknown,
+}
+
+impl CapabilitySupport {
+    fn from_status(status: Option<u16>) -> Self {
+        match status {
+            Some(200..=299) => Self::Supported,
+            Some(400 | 404 | 413 | 422) => Self::Unsupported,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// 供应商能力矩阵
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityMatrix {
+    /// 探测时使用的模型
+    pub model: String,
+    #[serde(default)]
+    pub streaming: CapabilitySupport,
+    #[serde(default)]
+    pub tools: CapabilitySupport,
+    #[serde(default)]
+    pub vision: CapabilitySupport,
+    #[serde(default)]
+    pub cache_control: CapabilitySupport,
+    #[serde(default)]
+    pub context_1m: CapabilitySupport,
+    /// 阶梯中被接受的最大 `max_tokens`；全部被拒时为 None
+    #[serde(default)]
+    pub max_output_tokens: Option<u32>,
+    pub probed_at: i64,
+}
+
+impl CapabilityMatrix {
+    /// 矩阵明确标记为不支持请求所需的某项能力
+    pub fn rejects(&self, needs: &RequestNeeds) -> bool {
+        (needs.streaming && self.streaming == CapabilitySupport::Unsupported)
+            || (needs.tools && self.tools == CapabilitySupport::Unsupported)
+            || (needs.vision && self.vision == CapabilitySupport::Unsupported)
+    }
+}
+
+/// 从请求体推断出的能力需求
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestNeeds {
+    pub streaming: bool,
+    pub tools: bool,
+    pub vision: bool,
+}
+
+impl RequestNeeds {
+    /// 兼容 Anthropic / OpenAI Chat / Responses / Gemini 的请求体结构
+    pub fn from_body(body: &Value) -> Self {
+        let tools = body
+            .get("tools")
+            .and_then(Value::as_array)
+            .is_some_and(|tools| !tools.is_empty());
+        Self {
+            streaming: body.get("stream").and_then(Value::as_bool).unwrap_or(false),
+            tools,
+            vision: contains_image(body),
+        }
+    }
+
+    fn any(&self) -> bool {
+        self.streaming || self.tools || self.vision
+    }
+}
+
+fn contains_image(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => {
+            if map.contains_key("inlineData") || map.contains_key("inline_data") {
+                return true;
+            }
+            if matches!(
+                map.get("type").and_then(Value::as_str),
+                Some("image" | "image_url" | "input_image")
+            ) {
+                return true;
+            }
+            map.iter()
+                .filter(|(key, _)| !matches!(key.as_str(), "tools" | "system"))
+                .any(|(_, value)| contains_image(value))
+        }
+        Value::Array(items) => items.iter().any(contains_image),
+        _ => false,
+    }
+}
+
+/// 路由参考：把明确不支持本次请求所需能力的供应商稳定地移到队尾
+///
+/// 只调整顺序、不剔除——矩阵可能基于其他模型探测，或者已经过时，
+/// 真正能否处理仍以上游响应为准。
+pub fn prefer_capable(db: &Database, app_type: &str, body: &Value, providers: &mut Vec<Provider>) {
+    if providers.len() < 2 {
+        return;
+    }
+    let needs = RequestNeeds::from_body(body);
+    if !needs.any() {
+        return;
+    }
+    let matrices = match db.get_provider_capabilities(app_type) {
+        Ok(matrices) if !matrices.is_empty() => matrices,
+        Ok(_) => return,
+        Err(e) => {
+            log::warn!("[Capability] 读取能力矩阵失败: {e}");
+            return;
+        }
+    };
+    reorder_by_capability(&matrices, &needs, providers);
+}
+
+fn reorder_by_capability(
+    matrices: &HashMap<String, CapabilityMatrix>,
+    needs: &RequestNeeds,
+    providers: &mut Vec<Provider>,
+) {
+    let (capable, lacking): (Vec<_>, Vec<_>) = providers.drain(..).partition(|provider| {
+        !matrices
+            .get(&provider.id)
+            .is_some_and(|matrix| matrix.rejects(needs))
+    });
+    if !lacking.is_empty() {
+        log::debug!(
+            "[Capability] {} 个供应商不支持本次请求所需能力，已后移: {:?}",
+            lacking.len(),
+            lacking.iter().map(|p| p.id.as_str()).collect::<Vec<_>>()
+        );
+    }
+    providers.extend(capable);
+    providers.extend(lacking);
+}
+
+pub struct CapabilityProbeService;
+
+impl CapabilityProbeService {
+    /// 探测供应商能力并持久化
+    pub async fn probe(
+        db: &Database,
+        app_type: &AppType,
+        provider: &Provider,
+        model: Option<&str>,
+    ) -> Result<CapabilityMatrix, AppError> {
+        let target = UpstreamTarget::resolve(app_type, provider, model)?;
+        let meta = provider.meta.as_ref();
+        let client = crate::proxy::http_client::get_for_provider(
+            meta.and_then(|meta| meta.tls_options()),
+            meta.and_then(|meta| meta.timeouts())
+                .and_then(|timeouts| timeouts.connect_timeout()),
+        )
+        .map_err(AppError::Message)?;
+        let probe = Prober {
+            client,
+            target: &target,
+        };
+
+        // 基线：不带任何特性的最小请求必须成功
+        let (endpoint, body) = target.minimal_request(false);
+        match probe.send(&endpoint, &body, &[]).await {
+            Some(status) if (200..300).contains(&status) => {}
+            status => {
+                return Err(AppError::localized(
+                    "capability.baseline_failed",
+                    format!(
+                        "基线请求失败（{}），无法探测能力",
+                        status.map_or("network error".to_string(), |s| format!("HTTP {s}"))
+                    ),
+                    format!(
+                        "Baseline request failed ({}), cannot probe capabilities",
+                        status.map_or("network error".to_string(), |s| format!("HTTP {s}"))
+                    ),
+                ));
+            }
+        }
+
+        let streaming = probe.streaming().await;
+        let stream_ladder = streaming == CapabilitySupport::Supported;
+        let matrix = CapabilityMatrix {
+            model: target.model.clone(),
+            streaming,
+            tools: probe.with_body(add_tool).await,
+            vision: probe.with_body(add_image).await,
+            cache_control: probe.cache_control().await,
+            context_1m: probe.context_1m().await,
+            max_output_tokens: probe.max_output_tokens(stream_ladder).await,
+            probed_at: chrono::Utc::now().timestamp(),
+        };
+        db.save_provider_capabilities(&provider.id, app_type.as_str(), &matrix)?;
+        Ok(matrix)
+    }
+}
+
+struct Prober<'a> {
+    client: Client,
+    target: &'a UpstreamTarget,
+}
+
+impl Prober<'_> {
+    /// 发送请求，只关心状态码；网络错误返回 None
+    async fn send(&self, endpoint: &str, body: &Value, extra: &[(&str, &str)]) -> Option<u16> {
+        let resp = self.send_raw(endpoint, body, extra).await?;
+        Some(resp.status().as_u16())
+    }
+
+    async fn send_raw(
+        &self,
+        endpoint: &str,
+        body: &Value,
+        extra: &[(&str, &str)],
+    ) -> Option<reqwest::Response> {
+        let prepared = self.target.prepare(endpoint, body, false).ok()?;
+        let mut req = self
+            .client
+            .post(&prepared.url)
+            .timeout(PROBE_TIMEOUT)
+            .body(prepared.body);
+        for (name, value) in prepared.headers {
+            req = req.header(name, value);
+        }
+        for (name, value) in extra {
+            req = req.header(*name, *value);
+        }
+        match req.send().await {
+            Ok(resp) => Some(resp),
+            Err(e) => {
+                log::debug!("[Capability] 探测请求失败: {e}");
+                None
+            }
+        }
+    }
+
+    /// 流式：2xx 且响应确为 SSE 才算支持（有的中转会忽略 stream 字段）
+    async fn streaming(&self) -> CapabilitySupport {
+        let (endpoint, body) = self.target.minimal_request(true);
+        let Some(resp) = self.send_raw(&endpoint, &body, &[]).await else {
+            return CapabilitySupport::Unknown;
+        };
+        let status = resp.status().as_u16();
+        let is_sse = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains("text/event-stream"));
+        match CapabilitySupport::from_status(Some(status)) {
+            CapabilitySupport::Supported if !is_sse => CapabilitySupport::Unsupported,
+            support => support,
+        }
+    }
+
+    async fn with_body(&self, apply: fn(WireFormat, &mut Value)) -> CapabilitySupport {
+        let (endpoint, mut body) = self.target.minimal_request(false);
+        apply(self.target.format, &mut body);
+        CapabilitySupport::from_status(self.send(&endpoint, &body, &[]).await)
+    }
+
+    /// Anthropic 专有特性，其他协议记为未知
+    async fn cache_control(&self) -> CapabilitySupport {
+        if self.target.format != WireFormat::Anthropic {
+            return CapabilitySupport::Unknown;
+        }
+        let (endpoint, mut body) = self.target.minimal_request(false);
+        body["system"] = json!([{
+            "type": "text",
+            "text": "You are a connectivity probe.",
+            "cache_control": { "type": "ephemeral" },
+        }]);
+        CapabilitySupport::from_status(self.send(&endpoint, &body, &[]).await)
+    }
+
+    async fn context_1m(&self) -> CapabilitySupport {
+        if self.target.format != WireFormat::Anthropic {
+            return CapabilitySupport::Unknown;
+        }
+        let (endpoint, body) = self.target.minimal_request(false);
+        CapabilitySupport::from_status(
+            self.send(&endpoint, &body, &[("anthropic-beta", CONTEXT_1M_BETA)])
+                .await,
+        )
+    }
+
+    /// 大 `max_tokens` 的非流式请求会被部分上游拒绝，能流式时走流式
+    async fn max_output_tokens(&self, stream: bool) -> Option<u32> {
+        for limit in MAX_OUTPUT_LADDER {
+            let (endpoint, mut body) = self.target.minimal_request(stream);
+            set_max_tokens(self.target.format, &mut body, limit);
+            match CapabilitySupport::from_status(self.send(&endpoint, &body, &[]).await) {
+                CapabilitySupport::Supported => return Some(limit),
+                CapabilitySupport::Unsupported => continue,
+                // 限流或网络错误时不再继续爬阶梯
+                CapabilitySupport::Unknown => return None,
+            }
+        }
+        None
+    }
+}
+
+fn set_max_tokens(format: WireFormat, body: &mut Value, limit: u32) {
+    match format {
+        WireFormat::Anthropic | WireFormat::OpenAiChat => body["max_tokens"] = json!(limit),
+        WireFormat::OpenAiResponses => body["max_output_tokens"] = json!(limit),
+        WireFormat::Gemini => body["generationConfig"]["maxOutputTokens"] = json!(limit),
+    }
+}
+
+fn add_tool(format: WireFormat, body: &mut Value) {
+    let name = "get_time";
+    let description = "Get the current time";
+    let parameters = json!({
+        "type": "object",
+        "properties": { "timezone": { "type": "string" } },
+    });
+    body["tools"] = match format {
+        WireFormat::Anthropic => json!([{
+            "name": name,
+            "description": description,
+            "input_schema": parameters,
+        }]),
+        WireFormat::OpenAiChat => json!([{
+            "type": "function",
+            "function": { "name": name, "description": description, "parameters": parameters },
+        }]),
+        WireFormat::OpenAiResponses => json!([{
+            "type": "function",
+            "name": name,
+            "description": description,
+            "parameters": parameters,
+        }]),
+        WireFormat::Gemini => json!([{
+            "functionDeclarations": [{
+                "name": name,
+                "description": description,
+                "parameters": parameters,
+            }],
+        }]),
+    };
+}
+
+fn add_image(format: WireFormat, body: &mut Value) {
+    let data_url = format!("data:image/png;base64,{PROBE_PNG_BASE64}");
+    match format {
+        WireFormat::Anthropic => {
+            body["messages"][0]["content"] = json!([
+                {
+                    "type": "image",
+                    "source": { "type": "base64", "media_type": "image/png", "data": PROBE_PNG_BASE64 },
+                },
+                { "type": "text", "text": "ping" },
+            ]);
+        }
+        WireFormat::OpenAiChat => {
+            body["messages"][0]["content"] = json!([
+                { "type": "image_url", "image_url": { "url": data_url } },
+                { "type": "text", "text": "ping" },
+            ]);
+        }
+        WireFormat::OpenAiResponses => {
+            body["input"] = json!([{
+                "role": "user",
+                "content": [
+                    { "type": "input_image", "image_url": data_url },
+                    { "type": "input_text", "text": "ping" },
+                ],
+            }]);
+        }
+        WireFormat::Gemini => {
+            body["contents"][0]["parts"] = json!([
+                { "inlineData": { "mimeType": "image/png", "data": PROBE_PNG_BASE64 } },
+                { "text": "ping" },
+            ]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(id: &str) -> Provider {
+        Provider::with_id(id.to_string(), id.to_string(), json!({}), None)
+    }
+
+    #[test]
+    fn request_needs_detects_images_across_formats() {
+        let anthropic = json!({
+            "stream": true,
+            "messages": [{ "role": "user", "content": [
+                { "type": "image", "source": { "type": "base64", "data": "x" } },
+            ]}],
+        });
+        assert_eq!(
+            RequestNeeds::from_body(&anthropic),
+            RequestNeeds {
+                streaming: true,
+                tools: false,
+                vision: true
+            }
+        );
+
+        let gemini = json!({
+            "contents": [{ "parts": [{ "inlineData": { "data": "x" } }] }],
+            "tools": [{ "functionDeclarations": [] }],
+        });
+        let needs = RequestNeeds::from_body(&gemini);
+        assert!(needs.vision && needs.tools && !needs.streaming);
+
+        // 工具 schema 中的 "image" 字样不应被误判为图片输入
+        let tools_only = json!({
+            "messages": [{ "role": "user", "content": "hi" }],
+            "tools": [{ "name": "x", "input_schema": { "type": "image" } }],
+        });
+        assert!(!RequestNeeds::from_body(&tools_only).vision);
+    }
+
+    #[test]
+    fn reorder_moves_unsupported_providers_to_the_end_stably() {
+        let mut providers = vec![provider("a"), provider("b"), provider("c"), provider("d")];
+        let matrices = HashMap::from([
+            (
+                "a".to_string(),
+                CapabilityMatrix {
+                    vision: CapabilitySupport::Unsupported,
+                    ..Default::default()
+                },
+            ),
+            (
+                "c".to_string(),
+                CapabilityMatrix {
+                    vision: CapabilitySupport::Unsupported,
+                    tools: CapabilitySupport::Supported,
+                    ..Default::default()
+                },
+            ),
+            // 未知不影响顺序
+            ("b".to_string(), CapabilityMatrix::default()),
+        ]);
+        let needs = RequestNeeds {
+            vision: true,
+            ..Default::default()
+        };
+
+        reorder_by_capability(&matrices, &needs, &mut providers);
+
+        let ids: Vec<_> = providers.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, ["b", "d", "a", "c"]);
+    }
+
+    #[test]
+    fn status_classification() {
+        assert_eq!(
+            CapabilitySupport::from_status(Some(200)),
+            CapabilitySupport::Supported
+        );
+        assert_eq!(
+            CapabilitySupport::from_status(Some(400)),
+            CapabilitySupport::Unsupported
+        );
+        assert_eq!(
+            CapabilitySupport::from_status(Some(429)),
+            CapabilitySupport::Unknown
+        );
+        assert_eq!(
+            CapabilitySupport::from_status(None),
+            CapabilitySupport::Unknown
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! 连通性失败时的分层诊断（简易 traceroute）
+//!
+//! 可达性探测在网络层失败时，只能拿到 reqwest 笼统的 "Connection failed"。
+//! 这里按 DNS 解析 → TCP 连接 → TLS 握手 → HTTP HEAD 逐层重放一次，
+//! 报告具体卡在哪一层以及各层耗时。
+//!
+//! 前三层直连目标主机；配置了全局出站代理时真实请求并不走这条路径，
+//! 结果中会附上代理地址提示，HTTP 层仍使用供应商的实际客户端。
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+/// 诊断层级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagStage {
+    Dns,
+    Tcp,
+    Tls,
+    Http,
+}
+
+/// 单层诊断结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagStep {
+    pub stage: DiagStage,
+    pub ok: bool,
+    pub duration_ms: u64,
+    /// 成功时为解析到的地址 / 协商的 TLS 版本 / HTTP 状态码，失败时为错误信息
+    pub detail: String,
+}
+
+/// 分层诊断报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionDiagnosis {
+    pub host: String,
+    pub port: u16,
+    pub steps: Vec<DiagStep>,
+    /// 全局出站代理（已脱敏）；存在时前三层结果不代表真实请求路径
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub via_proxy: Option<String>,
+}
+
+impl ConnectionDiagnosis {
+    pub fn failed_step(&self) -> Option<&DiagStep> {
+        self.steps.iter().find(|step| !step.ok)
+    }
+
+    /// 一句话说明失败的层级，替代笼统的连接失败信息
+    pub fn summary(&self) -> Option<String> {
+        let step = self.failed_step()?;
+        let what = match step.stage {
+            DiagStage::Dns => format!("DNS resolution for {} failed", self.host),
+            DiagStage::Tcp => format!("TCP connect to {}:{} failed", self.host, self.port),
+            DiagStage::Tls => format!("TLS handshake with {} failed", self.host),
+            DiagStage::Http => "HTTP HEAD request failed".to_string(),
+        };
+        Some(format!(
+            "{what} after {} ms: {}",
+            step.duration_ms, step.detail
+        ))
+    }
+}
+
+struct Probe {
+    steps: Vec<DiagStep>,
+}
+
+impl Probe {
+    fn record(
+        &mut self,
+        stage: DiagStage,
+        started: Instant,
+        result: Result<String, String>,
+    ) -> bool {
+        let ok = result.is_ok();
+        self.steps.push(DiagStep {
+            stage,
+            ok,
+            duration_ms: started.elapsed().as_millis() as u64,
+            detail: result.unwrap_or_else(|e| e),
+        });
+        ok
+    }
+}
+
+/// 解析 URL 中的主机与端口（IPv6 去掉方括号）
+fn split_target(url: &str) -> Result<(String, u16, bool), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("invalid URL: {e}"))?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "URL has no host".to_string())?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = parsed
+        .port_or_known_default()
+        .ok_or_else(|| format!("unsupported scheme: {}", parsed.scheme()))?;
+    Ok((host, port, parsed.scheme() == "https"))
+}
+
+async fn with_timeout<T, E: std::fmt::Display>(
+    timeout: Duration,
+    fut: impl std::future::Future<Output = Result<T, E>>,
+) -> Result<T, String> {
+    match tokio::time::timeout(timeout, fut).await {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(_) => Err(format!("timed out after {} s", timeout.as_secs())),
+    }
+}
+
+/// 对 `url` 逐层诊断；`timeout` 为每一层的超时
+pub async fn diagnose(client: &Client, url: &str, timeout: Duration) -> ConnectionDiagnosis {
+    let via_proxy = crate::proxy::http_client::get_current_proxy_url()
+        .map(|proxy| crate::proxy::http_client::mask_url(&proxy));
+    let mut probe = Probe { steps: Vec::new() };
+
+    let (host, port, https) = match split_target(url) {
+        Ok(target) => target,
+        Err(e) => {
+            probe.record(DiagStage::Dns, Instant::now(), Err(e));
+            return ConnectionDiagnosis {
+                host: String::new(),
+                port: 0,
+                steps: probe.steps,
+                via_proxy,
+            };
+        }
+    };
+    let report = |steps| ConnectionDiagnosis {
+        host: host.clone(),
+        port,
+        steps,
+        via_proxy: via_proxy.clone(),
+    };
+
+    // 1. DNS
+    let started = Instant::now();
+    let resolved = with_timeout(timeout, tokio::net::lookup_host((host.as_str(), port)))
+        .await
+        .map(|addrs| addrs.collect::<Vec<SocketAddr>>());
+    let addrs = match resolved {
+        Ok(addrs) if !addrs.is_empty() => addrs,
+        Ok(_) => {
+            probe.record(
+                DiagStage::Dns,
+                started,
+                Err("no addresses returned".to_string()),
+            );
+            return report(probe.steps);
+        }
+        Err(e) => {
+            probe.record(DiagStage::Dns, started, Err(e));
+            return report(probe.steps);
+        }
+    };
+    let ips = addrs
+        .iter()
+        .map(|addr| addr.ip().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    probe.record(DiagStage::Dns, started, Ok(ips));
+
+    // 2. TCP：依次尝试解析到的地址，报告最后一个错误
+    let started = Instant::now();
+    let mut stream = None;
+    let mut last_error = String::new();
+    for addr in &addrs {
+        match with_timeout(timeout, tokio::net::TcpStream::connect(addr)).await {
+            Ok(connected) => {
+                stream = Some((connected, *addr));
+                break;
+            }
+            Err(e) => last_error = format!("{addr}: {e}"),
+        }
+    }
+    let Some((stream, addr)) = stream else {
+        probe.record(DiagStage::Tcp, started, Err(last_error));
+        return report(probe.steps);
+    };
+    probe.record(DiagStage::Tcp, started, Ok(addr.to_string()));
+
+    // 3. TLS
+    if https {
+        let started = Instant::now();
+        let result = match rustls::pki_types::ServerName::try_from(host.clone()) {
+            Ok(server_name) => with_timeout(
+                timeout,
+                crate::proxy::hyper_client::global_tls_connector().connect(server_name, stream),
+            )
+            .await
+            .map(|tls| {
+                tls.get_ref()
+                    .1
+                    .protocol_version()
+                    .map(|version| format!("{version:?}"))
+                    .unwrap_or_else(|| "established".to_string())
+            }),
+            Err(e) => Err(format!("invalid server name: {e}")),
+        };
+        if !probe.record(DiagStage::Tls, started, result) {
+            return report(probe.steps);
+        }
+    } else {
+        drop(stream);
+    }
+
+    // 4. HTTP HEAD（使用供应商的真实客户端，包含代理 / 自定义 TLS 设置）
+    let started = Instant::now();
+    let result = client
+        .head(url)
+        .timeout(timeout)
+        .send()
+        .await
+        .map(|resp| format!("HTTP {}", resp.status().as_u16()))
+        .map_err(|e| e.to_string());
+    probe.record(DiagStage::Http, started, result);
+    report(probe.steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_host_and_default_port() {
+        assert_eq!(
+            split_target("https://api.example.com/v1").unwrap(),
+            ("api.example.com".to_string(), 443, true)
+        );
+        assert_eq!(
+            split_target("http://[::1]:8080").unwrap(),
+            ("::1".to_string(), 8080, false)
+        );
+        assert!(split_target("not a url").is_err());
+    }
+
+    #[test]
+    fn summary_names_the_failed_stage() {
+        let diagnosis = ConnectionDiagnosis {
+            host: "relay.example".to_string(),
+            port: 443,
+            steps: vec![
+                DiagStep {
+                    stage: DiagStage::Dns,
+                    ok: true,
+                    duration_ms: 12,
+                    detail: "203.0.113.7".to_string(),
+                },
+                DiagStep {
+                    stage: DiagStage::Tcp,
+                    ok: true,
+                    duration_ms: 40,
+                    detail: "203.0.113.7:443".to_string(),
+                },
+                DiagStep {
+                    stage: DiagStage::Tls,
+                    ok: false,
+                    duration_ms: 310,
+                    detail: "invalid peer certificate: Expired".to_string(),
+                },
+            ],
+            via_proxy: None,
+        };
+        assert_eq!(
+            diagnosis.summary().as_deref(),
+            Some("TLS handshake with relay.example failed after 310 ms: invalid peer certificate: Expired")
+        );
+    }
+
+    #[tokio::test]
+    async fn refused_port_fails_at_tcp() {
+        // 先占用再释放端口，得到一个大概率无人监听的本地端口
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let client = Client::new();
+        let diagnosis = diagnose(
+            &client,
+            &format!("http://127.0.0.1:{port}"),
+            Duration::from_secs(2),
+        )
+        .await;
+        assert_eq!(
+            diagnosis.failed_step().map(|step| step.stage),
+            Some(DiagStage::Tcp)
+        );
+    }
+}
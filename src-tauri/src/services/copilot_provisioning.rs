@@ -0,0 +1,478 @@
+//! GitHub Copilot 供应商自动生成
+//!
+//! 账号认证成功后，按选定的 Copilot 模型直接生成可用的 Claude / Codex 供应商（经本地代理转发），
+//! 并通过 authBinding 绑定到该账号，无需再手动填写表单。
+//!
+//! 生成的供应商在 `meta.copilotModel` 中记录所选模型。后台定期拉取账号的模型列表：
+//! 所选模型下线时改用同系列的最新模型，Claude 的 Haiku / Sonnet / Opus 默认模型也跟随
+//! 列表中各系列的最新版本更新。
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::Manager;
+
+use crate::app_config::AppType;
+use crate::codex_config_model::CodexConfig;
+use crate::commands::CopilotAuthState;
+use crate::error::AppError;
+use crate::provider::{AuthBinding, AuthBindingSource, Provider, ProviderMeta};
+use crate::proxy::providers::copilot_auth::{CopilotModel, GitHubAccount};
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+/// Copilot API 地址（企业版等动态端点由转发器按账号解析）
+const COPILOT_BASE_URL: &str = "https://api.githubcopilot.com";
+
+/// 后台同步模型列表的间隔
+const SYNC_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 启动后首次同步前的等待时间，避开启动高峰
+const SYNC_INITIAL_DELAY: Duration = Duration::from_secs(120);
+
+/// 生成 / 更新的供应商
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisionedProvider {
+    pub app_type: String,
+    pub provider_id: String,
+    pub name: String,
+    /// 是否为新建（否则为更新已生成的供应商）
+    pub created: bool,
+}
+
+/// 同一账号在各应用下使用固定 id，重复生成时更新而不是新增
+fn provider_id(account_id: &str) -> String {
+    let slug: String = account_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("copilot-{slug}")
+}
+
+/// 模型系列：去掉以数字开头的版本段，如 `claude-sonnet-4.5` → `claude-sonnet`
+fn model_family(model: &str) -> String {
+    model
+        .split('-')
+        .filter(|part| !part.starts_with(|c: char| c.is_ascii_digit()))
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// 模型选择器中可见的模型；全部不可见时退回完整列表
+fn pickable(models: &[CopilotModel]) -> Vec<&CopilotModel> {
+    let enabled: Vec<_> = models.iter().filter(|m| m.model_picker_enabled).collect();
+    if enabled.is_empty() {
+        models.iter().collect()
+    } else {
+        enabled
+    }
+}
+
+/// 某系列的最新模型（按 id 字典序取最大）
+fn latest_in_family(models: &[&CopilotModel], family: &str) -> Option<String> {
+    models
+        .iter()
+        .filter(|model| model_family(&model.id) == family)
+        .map(|model| model.id.clone())
+        .max()
+}
+
+/// 解析所选模型：仍在列表中则保留，否则改用同系列最新模型
+fn resolve_model(models: &[CopilotModel], preferred: &str) -> Option<String> {
+    let candidates = pickable(models);
+    if candidates.iter().any(|model| model.id == preferred) {
+        return Some(preferred.to_string());
+    }
+    latest_in_family(&candidates, &model_family(preferred))
+}
+
+fn claude_settings(model: &str, models: &[CopilotModel]) -> Value {
+    let candidates = pickable(models);
+    let tier = |family: &str| latest_in_family(&candidates, family).unwrap_or(model.to_string());
+    json!({
+        "env": {
+            "ANTHROPIC_BASE_URL": COPILOT_BASE_URL,
+            "ANTHROPIC_MODEL": model,
+            "ANTHROPIC_DEFAULT_HAIKU_MODEL": tier("claude-haiku"),
+            "ANTHROPIC_DEFAULT_SONNET_MODEL": tier("claude-sonnet"),
+            "ANTHROPIC_DEFAULT_OPUS_MODEL": tier("claude-opus"),
+        }
+    })
+}
+
+fn codex_settings(name: &str, model: &str) -> Value {
+    let toml_string = |value: &str| Value::String(value.to_string()).to_string();
+    let config = format!(
+        "model_provider = \"custom\"\n\
+         model = {}\n\
+         model_reasoning_effort = \"high\"\n\
+         disable_response_storage = true\n\
+         \n\
+         [model_providers.custom]\n\
+         name = {}\n\
+         base_url = {}\n\
+         wire_api = \"responses\"\n\
+         requires_openai_auth = true",
+        toml_string(model),
+        toml_string(name),
+        toml_string(COPILOT_BASE_URL),
+    );
+    json!({
+        "auth": { "OPENAI_API_KEY": "" },
+        "config": config,
+    })
+}
+
+fn settings_for(app_type: &AppType, name: &str, model: &str, models: &[CopilotModel]) -> Value {
+    match app_type {
+        AppType::Codex => codex_settings(name, model),
+        _ => claude_settings(model, models),
+    }
+}
+
+fn provisioned_meta(app_type: &AppType, account_id: &str, model: &str) -> ProviderMeta {
+    ProviderMeta {
+        provider_type: Some("github_copilot".to_string()),
+        api_format: matches!(app_type, AppType::Claude).then(|| "openai_chat".to_string()),
+        auth_binding: Some(AuthBinding {
+            source: AuthBindingSource::ManagedAccount,
+            auth_provider: Some("github_copilot".to_string()),
+            account_id: Some(account_id.to_string()),
+        }),
+        copilot_model: Some(model.to_string()),
+        ..Default::default()
+    }
+}
+
+fn build_provider(
+    app_type: &AppType,
+    account: &GitHubAccount,
+    model: &str,
+    models: &[CopilotModel],
+) -> Provider {
+    let name = format!("GitHub Copilot ({})", account.login);
+    let mut provider = Provider::with_id(
+        provider_id(&account.id),
+        name.clone(),
+        settings_for(app_type, &name, model, models),
+        Some("https://github.com/features/copilot".to_string()),
+    );
+    provider.category = Some("third_party".to_string());
+    provider.icon = Some("github".to_string());
+    provider.icon_color = Some("#000000".to_string());
+    provider.meta = Some(provisioned_meta(app_type, &account.id, model));
+    provider
+}
+
+/// 按模型列表刷新已生成的供应商；无变化时返回 None
+fn refresh_provider(
+    app_type: &AppType,
+    provider: &Provider,
+    models: &[CopilotModel],
+) -> Option<Provider> {
+    let meta = provider.meta.as_ref()?;
+    let selected = meta.copilot_model.as_deref()?;
+    let model = resolve_model(models, selected)?;
+    let settings = settings_for(app_type, &provider.name, &model, models);
+
+    let unchanged = match app_type {
+        AppType::Codex => model == selected,
+        _ => settings["env"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .all(|(key, value)| provider.settings_config["env"].get(key) == Some(value)),
+    };
+    if unchanged {
+        return None;
+    }
+
+    let mut updated = provider.clone();
+    match app_type {
+        AppType::Codex => {
+            // 只替换 model，保留用户对 config.toml 的其他修改
+            let config = provider.settings_config["config"]
+                .as_str()
+                .unwrap_or_default();
+            match CodexConfig::parse(config) {
+                Ok(mut config) => {
+                    config.set_model(Some(model.as_str()));
+                    updated.settings_config["config"] = Value::String(config.to_string());
+                }
+                Err(_) => updated.settings_config = settings,
+            }
+        }
+        _ => {
+            if let Some(env) = updated
+                .settings_config
+                .get_mut("env")
+                .and_then(Value::as_object_mut)
+            {
+                if let Some(new_env) = settings.get("env").and_then(Value::as_object) {
+                    for (key, value) in new_env {
+                        env.insert(key.clone(), value.clone());
+                    }
+                }
+            } else {
+                updated.settings_config = settings;
+            }
+        }
+    }
+    if let Some(meta) = updated.meta.as_mut() {
+        meta.copilot_model = Some(model);
+    }
+    Some(updated)
+}
+
+pub struct CopilotProvisioningService;
+
+impl CopilotProvisioningService {
+    /// 为指定账号与模型生成（或更新）Claude / Codex 供应商
+    pub async fn provision(
+        state: &AppState,
+        copilot: &CopilotAuthState,
+        account_id: &str,
+        model: &str,
+        apps: &[AppType],
+    ) -> Result<Vec<ProvisionedProvider>, AppError> {
+        if apps.is_empty() {
+            return Err(AppError::InvalidInput("请至少选择一个应用".into()));
+        }
+        if let Some(app) = apps
+            .iter()
+            .find(|app| !matches!(app, AppType::Claude | AppType::Codex))
+        {
+            return Err(AppError::InvalidInput(format!(
+                "Copilot 供应商暂不支持 {}",
+                app.as_str()
+            )));
+        }
+
+        let (account, models) = {
+            let auth = copilot.0.read().await;
+            let account = auth.get_account(account_id).await.ok_or_else(|| {
+                AppError::InvalidInput(format!("未找到 GitHub 账号: {account_id}"))
+            })?;
+            let models = auth
+                .fetch_models_for_account(account_id)
+                .await
+                .map_err(|e| AppError::Message(format!("获取 Copilot 模型列表失败: {e}")))?;
+            (account, models)
+        };
+        if !models.iter().any(|m| m.id == model) {
+            return Err(AppError::InvalidInput(format!(
+                "账号 {} 不可用模型 {model}",
+                account.login
+            )));
+        }
+
+        let mut result = Vec::new();
+        for app_type in apps {
+            let mut provider = build_provider(app_type, &account, model, &models);
+            let existing = state
+                .db
+                .get_provider_by_id(&provider.id, app_type.as_str())?;
+            let created = existing.is_none();
+            match existing {
+                Some(existing) => {
+                    // 保留用户的排序、备注等，只重写连接与模型配置
+                    provider.sort_index = existing.sort_index;
+                    provider.created_at = existing.created_at;
+                    provider.notes = existing.notes;
+                    provider.in_failover_queue = existing.in_failover_queue;
+                    ProviderService::update(state, app_type.clone(), None, provider.clone())?;
+                }
+                None => {
+                    ProviderService::add(state, app_type.clone(), provider.clone(), false)?;
+                }
+            }
+            log::info!(
+                "[Copilot] 已{}供应商 {} ({}, model={model})",
+                if created { "生成" } else { "更新" },
+                provider.id,
+                app_type.as_str()
+            );
+            result.push(ProvisionedProvider {
+                app_type: app_type.as_str().to_string(),
+                provider_id: provider.id,
+                name: provider.name,
+                created,
+            });
+        }
+        Ok(result)
+    }
+
+    /// 按各账号最新的模型列表刷新已生成的供应商，返回更新数量
+    pub async fn sync_models(
+        state: &AppState,
+        copilot: &CopilotAuthState,
+    ) -> Result<usize, AppError> {
+        let mut models_by_account: HashMap<String, Option<Vec<CopilotModel>>> = HashMap::new();
+        let mut updated = 0;
+        for app_type in [AppType::Claude, AppType::Codex] {
+            for provider in state.db.get_all_providers(app_type.as_str())?.into_values() {
+                let Some(account_id) = provider
+                    .meta
+                    .as_ref()
+                    .filter(|meta| meta.copilot_model.is_some())
+                    .and_then(|meta| meta.managed_account_id_for("github_copilot"))
+                else {
+                    continue;
+                };
+                if !models_by_account.contains_key(&account_id) {
+                    let models = copilot
+                        .0
+                        .read()
+                        .await
+                        .fetch_models_for_account(&account_id)
+                        .await
+                        .map_err(|e| {
+                            log::warn!("[Copilot] 同步账号 {account_id} 模型列表失败: {e}")
+                        })
+                        .ok()
+                        .filter(|models| !models.is_empty());
+                    models_by_account.insert(account_id.clone(), models);
+                }
+                let Some(models) = models_by_account[&account_id].as_deref() else {
+                    continue;
+                };
+                if let Some(next) = refresh_provider(&app_type, &provider, models) {
+                    log::info!(
+                        "[Copilot] 模型列表变化，更新供应商 {} ({}): {:?} → {:?}",
+                        provider.id,
+                        app_type.as_str(),
+                        provider
+                            .meta
+                            .as_ref()
+                            .and_then(|m| m.copilot_model.as_deref()),
+                        next.meta.as_ref().and_then(|m| m.copilot_model.as_deref()),
+                    );
+                    ProviderService::update(state, app_type.clone(), None, next)?;
+                    updated += 1;
+                }
+            }
+        }
+        Ok(updated)
+    }
+
+    /// 后台定期同步模型列表
+    pub fn spawn_model_sync(app: &tauri::AppHandle) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(SYNC_INITIAL_DELAY).await;
+            let mut interval = tokio::time::interval(SYNC_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                let state = app.state::<AppState>();
+                let copilot = app.state::<CopilotAuthState>();
+                match Self::sync_models(&state, &copilot).await {
+                    Ok(0) => {}
+                    Ok(count) => log::info!("[Copilot] 已按模型列表更新 {count} 个供应商"),
+                    Err(e) => log::warn!("[Copilot] 同步自动生成的供应商失败: {e}"),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str, enabled: bool) -> CopilotModel {
+        CopilotModel {
+            id: id.to_string(),
+            name: id.to_string(),
+            vendor: "Anthropic".to_string(),
+            model_picker_enabled: enabled,
+        }
+    }
+
+    #[test]
+    fn resolves_removed_model_to_latest_in_family() {
+        let models = vec![
+            model("claude-sonnet-4.5", true),
+            model("claude-sonnet-5", true),
+            model("claude-haiku-4.5", true),
+            model("gpt-5", false),
+        ];
+        assert_eq!(model_family("claude-sonnet-4.5"), "claude-sonnet");
+        assert_eq!(
+            resolve_model(&models, "claude-sonnet-4.5").as_deref(),
+            Some("claude-sonnet-4.5")
+        );
+        assert_eq!(
+            resolve_model(&models, "claude-sonnet-4").as_deref(),
+            Some("claude-sonnet-5")
+        );
+        assert_eq!(resolve_model(&models, "o3-mini"), None);
+        assert_eq!(
+            provider_id("ghe.example.com:42"),
+            "copilot-ghe-example-com-42"
+        );
+    }
+
+    #[test]
+    fn refresh_updates_claude_tiers_and_keeps_other_env() {
+        let account = GitHubAccount {
+            id: "42".into(),
+            login: "octocat".into(),
+            avatar_url: None,
+            authenticated_at: 0,
+            github_domain: "github.com".into(),
+        };
+        let old_models = vec![model("claude-sonnet-4.5", true)];
+        let mut provider =
+            build_provider(&AppType::Claude, &account, "claude-sonnet-4.5", &old_models);
+        provider.settings_config["env"]["DISABLE_TELEMETRY"] = json!("1");
+        assert_eq!(
+            provider.settings_config["env"]["ANTHROPIC_DEFAULT_HAIKU_MODEL"],
+            "claude-sonnet-4.5"
+        );
+        assert!(refresh_provider(&AppType::Claude, &provider, &old_models).is_none());
+
+        let new_models = vec![
+            model("claude-sonnet-5", true),
+            model("claude-haiku-4.5", true),
+        ];
+        let next = refresh_provider(&AppType::Claude, &provider, &new_models).unwrap();
+        let env = &next.settings_config["env"];
+        assert_eq!(env["ANTHROPIC_MODEL"], "claude-sonnet-5");
+        assert_eq!(env["ANTHROPIC_DEFAULT_HAIKU_MODEL"], "claude-haiku-4.5");
+        assert_eq!(env["DISABLE_TELEMETRY"], "1");
+        let meta = next.meta.unwrap();
+        assert_eq!(meta.copilot_model.as_deref(), Some("claude-sonnet-5"));
+        assert_eq!(
+            meta.managed_account_id_for("github_copilot").as_deref(),
+            Some("42")
+        );
+    }
+
+    #[test]
+    fn refresh_codex_only_rewrites_model() {
+        let account = GitHubAccount {
+            id: "42".into(),
+            login: "octocat".into(),
+            avatar_url: None,
+            authenticated_at: 0,
+            github_domain: "github.com".into(),
+        };
+        let mut provider = build_provider(&AppType::Codex, &account, "gpt-5", &[]);
+        let config = format!(
+            "{}\nmodel_verbosity = \"low\"",
+            provider.settings_config["config"].as_str().unwrap()
+        );
+        provider.settings_config["config"] = json!(config);
+
+        let models = vec![model("gpt-5.1", true)];
+        let next = refresh_provider(&AppType::Codex, &provider, &models).unwrap();
+        let config = next.settings_config["config"].as_str().unwrap();
+        assert!(config.contains("model = \"gpt-5.1\""));
+        assert!(config.contains("model_verbosity = \"low\""));
+        assert!(refresh_provider(&AppType::Codex, &next, &models).is_none());
+    }
+}
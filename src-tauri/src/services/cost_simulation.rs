@@ -0,0 +1,300 @@
+//! 切换前的成本模拟
+//!
+//! 取最近 N 天的请求日志，假设同样的流量全部走目标供应商：按目标供应商的模型映射
+//! 换算出实际会请求的模型，再用定价表与目标供应商的成本倍率重新计价，
+//! 与日志中记录的实际花费逐模型对比。
+//!
+//! 输入 token 统一按 fresh input 语义（见 [`fresh_input_sql`]）重新计价；
+//! 定价表中找不到的模型计为 0，并在结果中单独列出，避免把“无定价”误读为“免费”。
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::database::{lock_conn, Database};
+use crate::error::AppError;
+use crate::proxy::model_mapper::ModelMapping;
+use crate::proxy::usage::{CostCalculator, TokenUsage, UsageLogger};
+use crate::services::sql_helpers::fresh_input_sql;
+use crate::services::usage_stats::find_model_pricing;
+
+/// 单个（请求模型 → 实际模型）组合的对比
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostSimulationRow {
+    /// 客户端请求的模型
+    pub requested_model: String,
+    /// 日志中实际计价的模型
+    pub actual_model: String,
+    /// 走目标供应商时会请求的模型
+    pub simulated_model: String,
+    pub request_count: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub actual_cost_usd: String,
+    pub simulated_cost_usd: String,
+    /// 模拟模型在定价表中有价格
+    pub priced: bool,
+}
+
+/// 成本模拟结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostSimulation {
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub days: u32,
+    /// 目标供应商生效的成本倍率
+    pub cost_multiplier: String,
+    pub request_count: u64,
+    pub actual_cost_usd: String,
+    pub simulated_cost_usd: String,
+    /// 模拟 - 实际；负数表示切换后更便宜
+    pub delta_usd: String,
+    pub rows: Vec<CostSimulationRow>,
+    /// 定价表中找不到的模拟模型
+    pub unpriced_models: Vec<String>,
+}
+
+/// 聚合前的单条日志
+struct LoggedRequest {
+    requested_model: String,
+    actual_model: String,
+    usage: TokenUsage,
+    actual_cost: Decimal,
+}
+
+pub struct CostSimulationService;
+
+impl CostSimulationService {
+    /// 模拟最近 `days` 天 `app_type` 的流量走 `provider_id` 时的花费
+    pub async fn simulate(
+        db: &Database,
+        app_type: &str,
+        provider_id: &str,
+        days: u32,
+    ) -> Result<CostSimulation, AppError> {
+        if days == 0 {
+            return Err(AppError::localized(
+                "usage.simulation.invalid_days",
+                "模拟天数必须大于 0",
+                "Simulation window must be at least one day",
+            ));
+        }
+        let provider = db
+            .get_provider_by_id(provider_id, app_type)?
+            .ok_or_else(|| {
+                AppError::localized(
+                    "provider.not_found",
+                    format!("供应商不存在: {provider_id}"),
+                    format!("Provider not found: {provider_id}"),
+                )
+            })?;
+        let (multiplier, _) = UsageLogger::new(db)
+            .resolve_pricing_config(provider_id, app_type)
+            .await;
+        let since = chrono::Utc::now().timestamp() - i64::from(days) * 86_400;
+        let requests = Self::load_requests(db, app_type, since)?;
+        let mapping = ModelMapping::from_provider(&provider);
+
+        let conn = lock_conn!(db.conn);
+        let mut rows: BTreeMap<(String, String), CostSimulationRow> = BTreeMap::new();
+        let mut totals = (Decimal::ZERO, Decimal::ZERO);
+        let mut unpriced = Vec::new();
+        let mut pricing_cache = BTreeMap::new();
+        for request in &requests {
+            let simulated_model = mapping.map_model(&request.requested_model);
+            let pricing = pricing_cache
+                .entry(simulated_model.clone())
+                .or_insert_with(|| find_model_pricing(&conn, &simulated_model));
+            let simulated_cost = pricing
+                .as_ref()
+                .map(|p| CostCalculator::calculate(&request.usage, p, multiplier).total_cost)
+                .unwrap_or(Decimal::ZERO);
+            if pricing.is_none() && !unpriced.contains(&simulated_model) {
+                unpriced.push(simulated_model.clone());
+            }
+
+            let row = rows
+                .entry((
+                    request.requested_model.clone(),
+                    request.actual_model.clone(),
+                ))
+                .or_insert_with(|| CostSimulationRow {
+                    requested_model: request.requested_model.clone(),
+                    actual_model: request.actual_model.clone(),
+                    simulated_model: simulated_model.clone(),
+                    request_count: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cache_read_tokens: 0,
+                    cache_creation_tokens: 0,
+                    actual_cost_usd: "0".to_string(),
+                    simulated_cost_usd: "0".to_string(),
+                    priced: pricing.is_some(),
+                });
+            row.request_count += 1;
+            row.input_tokens += u64::from(request.usage.input_tokens);
+            row.output_tokens += u64::from(request.usage.output_tokens);
+            row.cache_read_tokens += u64::from(request.usage.cache_read_tokens);
+            row.cache_creation_tokens += u64::from(request.usage.cache_creation_tokens);
+            row.actual_cost_usd = add_usd(&row.actual_cost_usd, request.actual_cost);
+            row.simulated_cost_usd = add_usd(&row.simulated_cost_usd, simulated_cost);
+            totals.0 += request.actual_cost;
+            totals.1 += simulated_cost;
+        }
+
+        let mut rows: Vec<_> = rows.into_values().collect();
+        rows.sort_by(|a, b| {
+            parse_usd(&b.actual_cost_usd)
+                .cmp(&parse_usd(&a.actual_cost_usd))
+                .then_with(|| b.request_count.cmp(&a.request_count))
+        });
+        unpriced.sort();
+        Ok(CostSimulation {
+            app_type: app_type.to_string(),
+            provider_id: provider.id,
+            provider_name: provider.name,
+            days,
+            cost_multiplier: multiplier.normalize().to_string(),
+            request_count: requests.len() as u64,
+            actual_cost_usd: format_usd(totals.0),
+            simulated_cost_usd: format_usd(totals.1),
+            delta_usd: format_usd(totals.1 - totals.0),
+            rows,
+            unpriced_models: unpriced,
+        })
+    }
+
+    /// 读取 `since` 之后有 token 用量的请求；请求模型缺失时退回实际模型
+    fn load_requests(
+        db: &Database,
+        app_type: &str,
+        since: i64,
+    ) -> Result<Vec<LoggedRequest>, AppError> {
+        let conn = lock_conn!(db.conn);
+        let sql = format!(
+            "SELECT COALESCE(NULLIF(request_model, ''), model), model,
+                    {fresh_input}, output_tokens, cache_read_tokens, cache_creation_tokens,
+                    total_cost_usd
+             FROM proxy_request_logs
+             WHERE app_type = ?1 AND created_at >= ?2
+               AND (input_tokens > 0 OR output_tokens > 0
+                    OR cache_read_tokens > 0 OR cache_creation_tokens > 0)",
+            fresh_input = fresh_input_sql("")
+        );
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        let rows = stmt
+            .query_map(rusqlite::params![app_type, since], |row| {
+                let tokens = |idx: usize| -> rusqlite::Result<u32> {
+                    Ok(row.get::<_, i64>(idx)?.clamp(0, i64::from(u32::MAX)) as u32)
+                };
+                Ok(LoggedRequest {
+                    requested_model: row.get(0)?,
+                    actual_model: row.get(1)?,
+                    usage: TokenUsage {
+                        input_tokens: tokens(2)?,
+                        output_tokens: tokens(3)?,
+                        cache_read_tokens: tokens(4)?,
+                        cache_creation_tokens: tokens(5)?,
+                        model: None,
+                        message_id: None,
+                    },
+                    actual_cost: parse_usd(&row.get::<_, String>(6)?),
+                })
+            })
+            .map_err(|e| AppError::Database(e.to_string()))?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| AppError::Database(e.to_string()))
+    }
+}
+
+fn parse_usd(value: &str) -> Decimal {
+    Decimal::from_str(value.trim()).unwrap_or(Decimal::ZERO)
+}
+
+fn format_usd(value: Decimal) -> String {
+    value.round_dp(6).normalize().to_string()
+}
+
+fn add_usd(current: &str, amount: Decimal) -> String {
+    format_usd(parse_usd(current) + amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::{Provider, ProviderMeta};
+    use serde_json::json;
+
+    fn insert_log(db: &Database, request_id: &str, request_model: &str, cost: &str) {
+        let conn = lock_conn!(db.conn);
+        conn.execute(
+            "INSERT INTO proxy_request_logs (
+                request_id, provider_id, app_type, model, request_model,
+                input_tokens, output_tokens, cache_read_tokens, cache_creation_tokens,
+                input_cost_usd, output_cost_usd, cache_read_cost_usd, cache_creation_cost_usd,
+                total_cost_usd, latency_ms, status_code, created_at
+            ) VALUES (?1, 'official', 'claude', ?2, ?2, 1000000, 1000000, 0, 0,
+                      '0', '0', '0', '0', ?3, 100, 200, ?4)",
+            rusqlite::params![
+                request_id,
+                request_model,
+                cost,
+                chrono::Utc::now().timestamp()
+            ],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn reprices_traffic_with_target_mapping_and_multiplier() {
+        let db = Database::memory().unwrap();
+        let mut provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({"env": {"ANTHROPIC_DEFAULT_SONNET_MODEL": "glm-4.6"}}),
+            None,
+        );
+        provider.meta = Some(ProviderMeta {
+            cost_multiplier: Some("2".to_string()),
+            ..Default::default()
+        });
+        db.save_provider("claude", &provider).unwrap();
+        insert_log(&db, "r1", "claude-sonnet-4-5", "18");
+        insert_log(&db, "r2", "claude-sonnet-4-5", "18");
+        insert_log(&db, "r3", "mystery-model", "1");
+
+        let result = CostSimulationService::simulate(&db, "claude", "relay", 7)
+            .await
+            .unwrap();
+        assert_eq!(result.request_count, 3);
+        assert_eq!(result.cost_multiplier, "2");
+        assert_eq!(result.actual_cost_usd, "37");
+        // glm-4.6：(0.28 + 1.11) × 2 × 2 请求
+        assert_eq!(result.simulated_cost_usd, "5.56");
+        assert_eq!(result.delta_usd, "-31.44");
+        assert_eq!(result.rows[0].simulated_model, "glm-4.6");
+        assert_eq!(result.rows[0].request_count, 2);
+        assert_eq!(result.unpriced_models, vec!["mystery-model".to_string()]);
+        assert!(!result.rows[1].priced);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_provider_and_empty_window() {
+        let db = Database::memory().unwrap();
+        assert!(CostSimulationService::simulate(&db, "claude", "missing", 7)
+            .await
+            .is_err());
+        assert!(CostSimulationService::simulate(&db, "claude", "missing", 0)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,546 @@
+//! 复制为 curl：为供应商或已记录的代理请求生成可直接运行的 curl 命令
+//!
+//! - URL 与鉴权头复用代理适配器（`build_url` / `get_auth_headers`），与真实转发一致；
+//! - 供应商配置了自定义鉴权（查询参数 / Cookie / HMAC 签名）时同样复用 `proxy::custom_auth`；
+//! - 请求日志不保存请求体，生成的是携带该请求模型与流式标志的最小请求体；
+//! - `mask_key` 为真时密钥只保留首尾各 4 位，便于贴到 issue 或群聊中。
+//!
+//! Copilot / Codex OAuth / xAI OAuth 等托管供应商的令牌由 forwarder 动态换取，
+//! 数据库中没有可直接使用的凭据，不支持导出。
+
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::provider::{CustomAuthScheme, Provider};
+use crate::proxy::custom_auth::authorize_now;
+use crate::proxy::providers::local::is_local_provider;
+use crate::proxy::providers::{
+    codex_provider_upstream_model, get_adapter, get_claude_api_format,
+    should_convert_codex_responses_to_anthropic, should_convert_codex_responses_to_chat, AuthInfo,
+    AuthStrategy, ProviderAdapter,
+};
+use crate::services::secrets::resolve_settings;
+
+const PROBE_PROMPT: &str = "ping";
+const PROBE_MAX_TOKENS: u32 = 16;
+
+/// 上游实际使用的协议
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WireFormat {
+    Anthropic,
+    OpenAiChat,
+    OpenAiResponses,
+    Gemini,
+}
+
+fn wire_format(app_type: &AppType, provider: &Provider) -> Result<WireFormat, AppError> {
+    match app_type {
+        AppType::Claude | AppType::ClaudeDesktop => Ok(match get_claude_api_format(provider) {
+            "openai_chat" => WireFormat::OpenAiChat,
+            "openai_responses" => WireFormat::OpenAiResponses,
+            "gemini_native" => WireFormat::Gemini,
+            _ => WireFormat::Anthropic,
+        }),
+        AppType::Codex | AppType::GrokBuild => {
+            if should_convert_codex_responses_to_anthropic(provider, "/responses") {
+                Ok(WireFormat::Anthropic)
+            } else if should_convert_codex_responses_to_chat(provider, "/responses") {
+                Ok(WireFormat::OpenAiChat)
+            } else {
+                Ok(WireFormat::OpenAiResponses)
+            }
+        }
+        AppType::Gemini => Ok(WireFormat::Gemini),
+        AppType::OpenCode | AppType::OpenClaw | AppType::Hermes => Err(AppError::localized(
+            "curl.unsupported_app",
+            format!("{} 暂不支持复制为 curl", app_type.as_str()),
+            format!("Copy as curl is not supported for {}", app_type.as_str()),
+        )),
+    }
+}
+
+/// 供应商配置中显式指定的模型
+pub(crate) fn configured_model(app_type: &AppType, provider: &Provider) -> Option<String> {
+    let env = |key: &str| {
+        provider
+            .settings_config
+            .pointer(&format!("/env/{key}"))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    match app_type {
+        AppType::Claude | AppType::ClaudeDesktop => {
+            env("ANTHROPIC_MODEL").or_else(|| env("ANTHROPIC_DEFAULT_SONNET_MODEL"))
+        }
+        AppType::Codex | AppType::GrokBuild => codex_provider_upstream_model(provider),
+        AppType::Gemini => env("GEMINI_MODEL"),
+        _ => None,
+    }
+}
+
+/// 供应商配置中的默认模型；未配置时使用各协议的常见模型名
+fn default_model(app_type: &AppType, provider: &Provider, format: WireFormat) -> String {
+    configured_model(app_type, provider).unwrap_or_else(|| {
+        match format {
+            WireFormat::Anthropic => "claude-sonnet-4-5",
+            WireFormat::OpenAiChat | WireFormat::OpenAiResponses => "gpt-5",
+            WireFormat::Gemini => "gemini-2.5-flash",
+        }
+        .to_string()
+    })
+}
+
+/// 各协议的端点与单轮对话请求体
+fn endpoint_and_body(
+    app_type: &AppType,
+    format: WireFormat,
+    model: &str,
+    prompt: &str,
+    max_tokens: u32,
+    stream: bool,
+) -> (String, Value) {
+    // Codex 适配器会为纯 origin 自动补 /v1，Claude 适配器不会
+    let v1 = if matches!(app_type, AppType::Codex | AppType::GrokBuild) {
+        ""
+    } else {
+        "/v1"
+    };
+    match format {
+        WireFormat::Anthropic => {
+            let mut body = json!({
+                "model": model,
+                "max_tokens": max_tokens,
+                "messages": [{ "role": "user", "content": prompt }],
+            });
+            if stream {
+                body["stream"] = json!(true);
+            }
+            ("/v1/messages".to_string(), body)
+        }
+        WireFormat::OpenAiChat => {
+            let mut body = json!({
+                "model": model,
+                "max_tokens": max_tokens,
+                "messages": [{ "role": "user", "content": prompt }],
+            });
+            if stream {
+                body["stream"] = json!(true);
+            }
+            (format!("{v1}/chat/completions"), body)
+        }
+        WireFormat::OpenAiResponses => {
+            let mut body = json!({
+                "model": model,
+                "max_output_tokens": max_tokens,
+                "input": prompt,
+            });
+            if stream {
+                body["stream"] = json!(true);
+            }
+            (format!("{v1}/responses"), body)
+        }
+        WireFormat::Gemini => {
+            let endpoint = if stream {
+                format!("/v1beta/models/{model}:streamGenerateContent?alt=sse")
+            } else {
+                format!("/v1beta/models/{model}:generateContent")
+            };
+            let body = json!({
+                "contents": [{ "role": "user", "parts": [{ "text": prompt }] }],
+                "generationConfig": { "maxOutputTokens": max_tokens },
+            });
+            (endpoint, body)
+        }
+    }
+}
+
+/// POSIX shell 单引号转义
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn mask_secret(value: &str, auth: &AuthInfo) -> String {
+    let mut masked = value.replace(&auth.api_key, &auth.masked_key());
+    if let (Some(token), Some(masked_token)) = (&auth.access_token, auth.masked_access_token()) {
+        masked = masked.replace(token, &masked_token);
+    }
+    masked
+}
+
+fn render_curl(url: &str, headers: &[(String, String)], body: &[u8], stream: bool) -> String {
+    let mut lines = vec![format!(
+        "curl{} {}",
+        if stream { " -N" } else { "" },
+        shell_quote(url)
+    )];
+    for (name, value) in headers {
+        lines.push(format!("  -H {}", shell_quote(&format!("{name}: {value}"))));
+    }
+    lines.push(format!(
+        "  -d {}",
+        shell_quote(&String::from_utf8_lossy(body))
+    ));
+    lines.join(" \\\n")
+}
+
+/// 已解析的上游目标：协议、URL、鉴权与模型
+///
+/// 复制为 curl 与能力探测（`capability_probe`）共用，保证两者打到的地址与
+/// 携带的鉴权头和真实转发一致。
+pub(crate) struct UpstreamTarget {
+    pub(crate) app_type: AppType,
+    pub(crate) format: WireFormat,
+    pub(crate) model: String,
+    base_url: String,
+    adapter: Box<dyn ProviderAdapter>,
+    auth: Option<AuthInfo>,
+    custom_auth: Option<CustomAuthScheme>,
+    user_agent: Option<String>,
+}
+
+/// 组装完成的请求：已应用自定义鉴权
+pub(crate) struct PreparedRequest {
+    pub(crate) url: String,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl UpstreamTarget {
+    /// 解析供应商的上游目标；`model` 为空时取供应商配置的默认模型
+    pub(crate) fn resolve(
+        app_type: &AppType,
+        provider: &Provider,
+        model: Option<&str>,
+    ) -> Result<Self, AppError> {
+        let format = wire_format(app_type, provider)?;
+        // 钥匙串中的密钥引用需先解析为明文
+        let mut provider = provider.clone();
+        provider.settings_config = resolve_settings(&provider.settings_config)?;
+
+        let adapter = get_adapter(app_type);
+        let base_url = adapter
+            .extract_base_url(&provider)
+            .map_err(|e| AppError::Message(format!("Failed to extract base_url: {e}")))?;
+        // 本地运行时（Ollama 等）允许不配置 API Key
+        let auth = adapter.extract_auth(&provider);
+        if auth.is_none() && !is_local_provider(&provider) {
+            return Err(AppError::localized(
+                "curl.missing_key",
+                format!("供应商 {} 未配置 API Key", provider.name),
+                format!("Provider {} has no API key configured", provider.name),
+            ));
+        }
+        if auth.as_ref().is_some_and(|auth| {
+            matches!(
+                auth.strategy,
+                AuthStrategy::GitHubCopilot | AuthStrategy::CodexOAuth | AuthStrategy::XaiOAuth
+            )
+        }) {
+            return Err(AppError::localized(
+                "curl.managed_auth",
+                "托管 OAuth 供应商的令牌由代理动态获取，无法直接构造请求",
+                "Managed OAuth providers use short-lived tokens fetched by the proxy and cannot be requested directly",
+            ));
+        }
+
+        let model = model
+            .map(str::trim)
+            .filter(|model| !model.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| default_model(app_type, &provider, format));
+        let user_agent = provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.identity_user_agent())
+            .and_then(|ua| ua.to_str().ok().map(str::to_string));
+        let custom_auth = provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.custom_auth())
+            .filter(|_| auth.as_ref().is_some_and(|auth| !auth.strategy.is_oauth()))
+            .cloned();
+
+        Ok(Self {
+            app_type: app_type.clone(),
+            format,
+            model,
+            base_url,
+            adapter,
+            auth,
+            custom_auth,
+            user_agent,
+        })
+    }
+
+    pub(crate) fn url(&self, endpoint: &str) -> String {
+        self.adapter.build_url(&self.base_url, endpoint)
+    }
+
+    /// 以另一个候选地址拼接端点（端点测速逐个测试候选地址）
+    pub(crate) fn url_with_base(&self, base_url: &str, endpoint: &str) -> String {
+        self.adapter
+            .build_url(base_url.trim().trim_end_matches('/'), endpoint)
+    }
+
+    /// 协议要求的固定头 + 鉴权头 + 自定义 UA
+    ///
+    /// 配置了自定义鉴权时不含鉴权头，需经 [`Self::prepare`] 应用。
+    pub(crate) fn headers(&self, mask_key: bool) -> Result<Vec<(String, String)>, AppError> {
+        let mut headers = vec![("content-type".to_string(), "application/json".to_string())];
+        if self.format == WireFormat::Anthropic {
+            headers.push(("anthropic-version".to_string(), "2023-06-01".to_string()));
+        }
+        if let Some(auth) = self.auth.as_ref().filter(|_| self.custom_auth.is_none()) {
+            for (name, value) in self
+                .adapter
+                .get_auth_headers(auth)
+                .map_err(|e| AppError::Message(e.to_string()))?
+            {
+                let value = value.to_str().unwrap_or_default().to_string();
+                let value = if mask_key {
+                    mask_secret(&value, auth)
+                } else {
+                    value
+                };
+                headers.push((name.as_str().to_string(), value));
+            }
+        }
+        if let Some(ua) = &self.user_agent {
+            headers.push(("user-agent".to_string(), ua.clone()));
+        }
+        Ok(headers)
+    }
+
+    /// 组装 POST 请求：URL、请求头与序列化后的请求体，并应用自定义鉴权
+    pub(crate) fn prepare(
+        &self,
+        endpoint: &str,
+        body: &Value,
+        mask_key: bool,
+    ) -> Result<PreparedRequest, AppError> {
+        let body = serde_json::to_vec(body)
+            .map_err(|e| AppError::Message(format!("Failed to serialize request body: {e}")))?;
+        let (url, headers) = self.authorize(
+            "POST",
+            self.url(endpoint),
+            self.headers(mask_key)?,
+            &body,
+            mask_key,
+        )?;
+        Ok(PreparedRequest { url, headers, body })
+    }
+
+    /// 应用自定义鉴权（查询参数 / Cookie / HMAC 签名需要最终的 URL 与请求体）；
+    /// 未配置时原样返回
+    pub(crate) fn authorize(
+        &self,
+        method: &str,
+        url: String,
+        mut headers: Vec<(String, String)>,
+        body: &[u8],
+        mask_key: bool,
+    ) -> Result<(String, Vec<(String, String)>), AppError> {
+        let (Some(scheme), Some(auth)) = (&self.custom_auth, &self.auth) else {
+            return Ok((url, headers));
+        };
+        let authorized = authorize_now(scheme, &auth.api_key, method, &url, body)
+            .map_err(|e| AppError::Message(e.to_string()))?;
+        let mask = |value: &str| {
+            if mask_key {
+                mask_secret(value, auth)
+            } else {
+                value.to_string()
+            }
+        };
+        for (name, value) in authorized.headers {
+            headers.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name.as_str()));
+            headers.push((
+                name.as_str().to_string(),
+                mask(value.to_str().unwrap_or_default()),
+            ));
+        }
+        Ok((mask(&authorized.url), headers))
+    }
+
+    /// 最小请求：端点与请求体
+    pub(crate) fn minimal_request(&self, stream: bool) -> (String, Value) {
+        self.message_request(PROBE_PROMPT, PROBE_MAX_TOKENS, stream)
+    }
+
+    /// 发送单条用户消息的请求：端点与请求体
+    pub(crate) fn message_request(
+        &self,
+        prompt: &str,
+        max_tokens: u32,
+        stream: bool,
+    ) -> (String, Value) {
+        endpoint_and_body(
+            &self.app_type,
+            self.format,
+            &self.model,
+            prompt,
+            max_tokens,
+            stream,
+        )
+    }
+}
+
+pub struct CurlExportService;
+
+impl CurlExportService {
+    /// 为供应商生成 curl 命令；`model` 为空时取供应商配置的默认模型
+    pub fn for_provider(
+        app_type: &AppType,
+        provider: &Provider,
+        model: Option<&str>,
+        stream: bool,
+        mask_key: bool,
+    ) -> Result<String, AppError> {
+        let target = UpstreamTarget::resolve(app_type, provider, model)?;
+        let (endpoint, body) = target.minimal_request(stream);
+        let request = target.prepare(&endpoint, &body, mask_key)?;
+        Ok(render_curl(
+            &request.url,
+            &request.headers,
+            &request.body,
+            stream,
+        ))
+    }
+
+    /// 为已记录的代理请求生成 curl 命令（使用该请求的供应商、上游模型与流式标志）
+    pub fn for_request_log(
+        db: &Database,
+        request_id: &str,
+        mask_key: bool,
+    ) -> Result<String, AppError> {
+        let detail = db.get_request_detail(request_id)?.ok_or_else(|| {
+            AppError::localized(
+                "curl.request_not_found",
+                format!("请求 {request_id} 不存在"),
+                format!("Request {request_id} not found"),
+            )
+        })?;
+        let app_type: AppType = detail.app_type.parse()?;
+        let provider = db
+            .get_provider_by_id(&detail.provider_id, app_type.as_str())?
+            .ok_or_else(|| {
+                AppError::localized(
+                    "curl.provider_not_found",
+                    format!("供应商 {} 已不存在", detail.provider_id),
+                    format!("Provider {} no longer exists", detail.provider_id),
+                )
+            })?;
+        Self::for_provider(
+            &app_type,
+            &provider,
+            Some(&detail.model),
+            detail.is_streaming,
+            mask_key,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(settings_config: Value) -> Provider {
+        Provider {
+            id: "relay".to_string(),
+            name: "Relay".to_string(),
+            settings_config,
+            website_url: None,
+            category: Some("custom".to_string()),
+            created_at: None,
+            sort_index: None,
+            notes: None,
+            meta: None,
+            icon: None,
+            icon_color: None,
+            in_failover_queue: false,
+        }
+    }
+
+    #[test]
+    fn claude_provider_renders_messages_request_with_masked_key() {
+        let provider = provider(json!({ "env": {
+            "ANTHROPIC_BASE_URL": "https://relay.example.com",
+            "ANTHROPIC_AUTH_TOKEN": "sk-1234567890abcdef",
+            "ANTHROPIC_MODEL": "claude-opus-4-1",
+        }}));
+        let curl = CurlExportService::for_provider(&AppType::Claude, &provider, None, false, true)
+            .unwrap();
+        assert_eq!(
+            curl,
+            "curl 'https://relay.example.com/v1/messages' \\\n  \
+             -H 'content-type: application/json' \\\n  \
+             -H 'anthropic-version: 2023-06-01' \\\n  \
+             -H 'authorization: Bearer sk-1...cdef' \\\n  \
+             -d '{\"model\":\"claude-opus-4-1\",\"max_tokens\":16,\"messages\":[{\"role\":\"user\",\"content\":\"ping\"}]}'"
+        );
+
+        let unmasked =
+            CurlExportService::for_provider(&AppType::Claude, &provider, None, true, false)
+                .unwrap();
+        assert!(unmasked.starts_with("curl -N "));
+        assert!(unmasked.contains("Bearer sk-1234567890abcdef"));
+        assert!(unmasked.contains("\"stream\":true"));
+    }
+
+    #[test]
+    fn gemini_provider_uses_model_in_path() {
+        let provider = provider(json!({ "env": {
+            "GOOGLE_GEMINI_BASE_URL": "https://gemini.example.com",
+            "GEMINI_API_KEY": "AIza-test-key-123",
+        }}));
+        let curl = CurlExportService::for_provider(
+            &AppType::Gemini,
+            &provider,
+            Some("gemini-2.5-pro"),
+            false,
+            false,
+        )
+        .unwrap();
+        assert!(curl.starts_with(
+            "curl 'https://gemini.example.com/v1beta/models/gemini-2.5-pro:generateContent'"
+        ));
+    }
+
+    #[test]
+    fn custom_auth_replaces_default_auth_header() {
+        let mut provider = provider(json!({ "env": {
+            "ANTHROPIC_BASE_URL": "https://relay.example.com",
+            "ANTHROPIC_AUTH_TOKEN": "sk-1234567890abcdef",
+        }}));
+        provider.meta = Some(crate::provider::ProviderMeta {
+            custom_auth: Some(CustomAuthScheme::QueryParam {
+                param: "key".to_string(),
+            }),
+            ..Default::default()
+        });
+        let curl = CurlExportService::for_provider(&AppType::Claude, &provider, None, false, true)
+            .unwrap();
+        assert!(curl.starts_with("curl 'https://relay.example.com/v1/messages?key=sk-1...cdef'"));
+        assert!(!curl.contains("authorization"));
+
+        provider.meta = Some(crate::provider::ProviderMeta {
+            custom_auth: Some(CustomAuthScheme::Cookie {
+                name: "token".to_string(),
+            }),
+            ..Default::default()
+        });
+        let curl = CurlExportService::for_provider(&AppType::Claude, &provider, None, false, false)
+            .unwrap();
+        assert!(curl.contains("-H 'cookie: token=sk-1234567890abcdef'"));
+        assert!(!curl.contains("authorization"));
+    }
+
+    #[test]
+    fn shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
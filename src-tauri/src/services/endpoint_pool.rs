@@ -0,0 +1,339 @@
+//! 候选端点池：自动选择最快的可用端点
+//!
+//! 部分中转提供多个区域地址。供应商开启 `endpointPool` 后，候选地址为当前配置的地址加上
+//! 自定义端点列表；切换到该供应商后（以及作为当前供应商时按间隔定期）以供应商身份测速，
+//! 把最快的可用地址写入供应商配置并同步到 live，选择结果记录在 meta 的 `selectedEndpoint`。
+//!
+//! 返回 5xx、超时或连接失败的地址视为不可用；全部不可用时保持原配置不变。
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::Manager;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::{Provider, SelectedEndpoint};
+use crate::proxy::providers::get_adapter;
+use crate::services::speedtest::{EndpointLatency, ProviderSpeedtestOptions, SpeedtestService};
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+/// 前端监听的事件名：端点池选出了新的端点
+pub const EVENT_PROVIDER_ENDPOINT_SELECTED: &str = "provider-endpoint-selected";
+
+/// 端点切换事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderEndpointSelected {
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub previous_url: String,
+    pub url: String,
+    pub latency_ms: u64,
+}
+
+fn normalize(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+/// 候选地址：当前地址在前，其余自定义端点按添加时间排列，去重
+fn candidate_urls(provider: &Provider, current: &str) -> Vec<String> {
+    let mut endpoints: Vec<_> = provider
+        .meta
+        .as_ref()
+        .map(|meta| meta.custom_endpoints.values().collect())
+        .unwrap_or_default();
+    endpoints.sort_by_key(|endpoint| endpoint.added_at);
+
+    let mut urls = vec![normalize(current)];
+    for endpoint in endpoints {
+        let url = normalize(&endpoint.url);
+        if !url.is_empty() && !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls.retain(|url| !url.is_empty());
+    urls
+}
+
+/// 最快的可用端点：有响应且不是 5xx
+fn pick_best(results: &[EndpointLatency]) -> Option<&EndpointLatency> {
+    results
+        .iter()
+        .filter(|result| result.error.is_none())
+        .filter(|result| result.status.is_some_and(|status| status < 500))
+        .filter(|result| result.latency.is_some())
+        .min_by_key(|result| result.latency)
+}
+
+/// 把基础地址写回供应商配置；不支持的应用返回错误
+fn set_base_url(app_type: &AppType, settings: &mut Value, url: &str) -> Result<(), AppError> {
+    let env_key = match app_type {
+        AppType::Claude | AppType::ClaudeDesktop => "ANTHROPIC_BASE_URL",
+        AppType::Gemini => "GOOGLE_GEMINI_BASE_URL",
+        AppType::Codex => {
+            let config = settings
+                .get("config")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let updated = crate::codex_config::update_codex_toml_field(config, "base_url", url)
+                .map_err(AppError::Message)?;
+            settings["config"] = json!(updated);
+            return Ok(());
+        }
+        _ => {
+            return Err(AppError::localized(
+                "provider.endpoint_pool.unsupported_app",
+                format!("{} 暂不支持候选端点池", app_type.as_str()),
+                format!("Endpoint pools are not supported for {}", app_type.as_str()),
+            ))
+        }
+    };
+    match settings.get_mut("env").and_then(Value::as_object_mut) {
+        Some(env) => {
+            env.insert(env_key.to_string(), json!(url));
+        }
+        None => settings["env"] = json!({ env_key: url }),
+    }
+    Ok(())
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+pub struct EndpointPoolService;
+
+impl EndpointPoolService {
+    /// 对供应商的候选端点测速并应用最快的可用端点
+    ///
+    /// 未开启端点池或候选不足两个时返回 `Ok(None)`；全部不可用时同样返回 None，配置不变。
+    pub async fn select(
+        state: &AppState,
+        app_type: &AppType,
+        provider_id: &str,
+    ) -> Result<Option<SelectedEndpoint>, AppError> {
+        let Some(provider) = state
+            .db
+            .get_provider_by_id(provider_id, app_type.as_str())?
+        else {
+            return Ok(None);
+        };
+        if provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.endpoint_pool.as_ref())
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let current = get_adapter(app_type)
+            .extract_base_url(&provider)
+            .map_err(|e| AppError::Message(format!("Failed to extract base_url: {e}")))?;
+        let urls = candidate_urls(&provider, &current);
+        if urls.len() < 2 {
+            return Ok(None);
+        }
+
+        let options = ProviderSpeedtestOptions {
+            app: app_type.as_str().to_string(),
+            provider_id: provider_id.to_string(),
+            completion: false,
+        };
+        let results =
+            SpeedtestService::test_provider_endpoints(&state.db, &options, urls.clone(), None)
+                .await?;
+        let Some(best) = pick_best(&results) else {
+            log::warn!(
+                "[EndpointPool] {} 的 {} 个候选端点均不可用，保持 {current}",
+                provider.name,
+                urls.len()
+            );
+            return Ok(None);
+        };
+        let selected = SelectedEndpoint {
+            url: best.url.clone(),
+            latency_ms: best.latency.unwrap_or_default() as u64,
+            selected_at: now_millis(),
+            candidates: urls.len(),
+        };
+
+        // 测速期间供应商可能被编辑，重新读取后再写回
+        let Some(mut provider) = state
+            .db
+            .get_provider_by_id(provider_id, app_type.as_str())?
+        else {
+            return Ok(None);
+        };
+        let changed = normalize(&current) != selected.url;
+        if changed {
+            set_base_url(app_type, &mut provider.settings_config, &selected.url)?;
+        }
+        let meta = provider.meta.get_or_insert_with(Default::default);
+        meta.selected_endpoint = Some(selected.clone());
+        if let Some(endpoint) = meta.custom_endpoints.get_mut(&selected.url) {
+            endpoint.last_used = Some(selected.selected_at);
+        }
+        state.db.save_provider(app_type.as_str(), &provider)?;
+
+        if !changed {
+            log::debug!(
+                "[EndpointPool] {} 当前端点仍最快: {} ({}ms)",
+                provider.name,
+                selected.url,
+                selected.latency_ms
+            );
+            return Ok(Some(selected));
+        }
+
+        log::info!(
+            "[EndpointPool] {} 切换端点 {current} → {} ({}ms，{} 个候选)",
+            provider.name,
+            selected.url,
+            selected.latency_ms,
+            selected.candidates
+        );
+        if ProviderService::current(state, app_type.clone())? == provider_id {
+            ProviderService::sync_current_provider_for_app(state, app_type.clone())?;
+        }
+        crate::usage_events::emit(
+            EVENT_PROVIDER_ENDPOINT_SELECTED,
+            ProviderEndpointSelected {
+                app_type: app_type.as_str().to_string(),
+                provider_id: provider_id.to_string(),
+                provider_name: provider.name.clone(),
+                previous_url: normalize(&current),
+                url: selected.url.clone(),
+                latency_ms: selected.latency_ms,
+            },
+        );
+        Ok(Some(selected))
+    }
+
+    /// 切换供应商成功后在后台选择端点（不阻塞切换本身）
+    pub fn spawn_on_switch(app: &tauri::AppHandle, app_type: AppType, provider_id: String) {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let state = app.state::<AppState>();
+            let enabled = state
+                .db
+                .get_provider_by_id(&provider_id, app_type.as_str())
+                .ok()
+                .flatten()
+                .and_then(|provider| provider.meta?.endpoint_pool)
+                .is_some_and(|pool| pool.on_switch);
+            if !enabled {
+                return;
+            }
+            if let Err(e) = Self::select(&state, &app_type, &provider_id).await {
+                log::warn!("[EndpointPool] 切换后选择端点失败: {e}");
+            }
+        });
+    }
+
+    /// 定时任务：当前供应商开启定期选择且已到间隔时重新选择
+    pub async fn run_due(state: &AppState) {
+        let now = now_millis();
+        for app_type in AppType::all().filter(|app| !app.is_additive_mode()) {
+            let Ok(current) = ProviderService::current(state, app_type.clone()) else {
+                continue;
+            };
+            let Ok(Some(provider)) = state.db.get_provider_by_id(&current, app_type.as_str())
+            else {
+                continue;
+            };
+            let Some(meta) = provider.meta.as_ref() else {
+                continue;
+            };
+            let Some(pool) = meta
+                .endpoint_pool
+                .as_ref()
+                .filter(|p| p.interval_minutes > 0)
+            else {
+                continue;
+            };
+            let interval_ms = i64::from(pool.interval_minutes) * 60_000;
+            let last = meta
+                .selected_endpoint
+                .as_ref()
+                .map_or(0, |selected| selected.selected_at);
+            if now - last < interval_ms {
+                continue;
+            }
+            if let Err(e) = Self::select(state, &app_type, &current).await {
+                log::warn!("[EndpointPool] {} 定期选择端点失败: {e}", app_type.as_str());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::CustomEndpoint;
+
+    fn latency(url: &str, latency: Option<u128>, status: Option<u16>) -> EndpointLatency {
+        EndpointLatency {
+            url: url.to_string(),
+            latency,
+            status,
+            error: latency.is_none().then(|| "连接失败".to_string()),
+            error_class: None,
+        }
+    }
+
+    #[test]
+    fn best_endpoint_skips_failures_and_server_errors() {
+        let results = vec![
+            latency("https://a", Some(300), Some(200)),
+            latency("https://b", Some(80), Some(502)),
+            latency("https://c", None, None),
+            latency("https://d", Some(120), Some(401)),
+        ];
+        assert_eq!(
+            pick_best(&results).map(|r| r.url.as_str()),
+            Some("https://d")
+        );
+        assert!(pick_best(&results[1..3]).is_none());
+    }
+
+    #[test]
+    fn candidates_start_with_current_and_dedupe() {
+        let mut provider = Provider::with_id("p".into(), "P".into(), json!({}), None);
+        let mut meta = crate::provider::ProviderMeta::default();
+        for (url, added_at) in [("https://hk.example/", 2), ("https://us.example", 1)] {
+            meta.custom_endpoints.insert(
+                url.to_string(),
+                CustomEndpoint {
+                    url: url.to_string(),
+                    added_at,
+                    last_used: None,
+                },
+            );
+        }
+        provider.meta = Some(meta);
+        assert_eq!(
+            candidate_urls(&provider, "https://hk.example"),
+            ["https://hk.example", "https://us.example"]
+        );
+    }
+
+    #[test]
+    fn base_url_written_per_app() {
+        let mut claude = json!({ "env": { "ANTHROPIC_AUTH_TOKEN": "sk" } });
+        set_base_url(&AppType::Claude, &mut claude, "https://hk.example").unwrap();
+        assert_eq!(claude["env"]["ANTHROPIC_BASE_URL"], "https://hk.example");
+
+        let mut codex = json!({
+            "config": "model_provider = \"r\"\n\n[model_providers.r]\nbase_url = \"https://old/v1\"\n"
+        });
+        set_base_url(&AppType::Codex, &mut codex, "https://hk.example/v1").unwrap();
+        assert!(codex["config"]
+            .as_str()
+            .unwrap()
+            .contains("base_url = \"https://hk.example/v1\""));
+
+        assert!(set_base_url(&AppType::OpenCode, &mut json!({}), "https://x").is_err());
+    }
+}
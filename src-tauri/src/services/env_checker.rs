@@ -151,6 +151,54 @@ fn check_shell_configs(keywords: &[&str]) -> Result<Vec<EnvConflict>, String> {
     Ok(conflicts)
 }
 
+/// 命令行运行时（npx / uvx / docker 等）的可用性
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeStatus {
+    pub command: String,
+    pub available: bool,
+    pub version: Option<String>,
+}
+
+/// 检查运行时命令是否在 PATH 中，并尝试读取 `--version`
+pub fn check_runtime(command: &str) -> RuntimeStatus {
+    let available = crate::claude_mcp::validate_command_in_path(command).unwrap_or(false);
+    let version = available.then(|| runtime_version(command)).flatten();
+    RuntimeStatus {
+        command: command.to_string(),
+        available,
+        version,
+    }
+}
+
+fn runtime_version(command: &str) -> Option<String> {
+    // Windows 上 npx 等是 .cmd 脚本，需经 cmd 启动
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        use std::os::windows::process::CommandExt;
+        let mut cmd = std::process::Command::new("cmd");
+        cmd.args(["/C", command, "--version"])
+            .creation_flags(0x08000000); // CREATE_NO_WINDOW
+        cmd
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut cmd = {
+        let mut cmd = std::process::Command::new(command);
+        cmd.arg("--version");
+        cmd
+    };
+    cmd.stdin(std::process::Stdio::null());
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    text.lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,387 @@
+//! 自动切走 / 切回看门狗
+//!
+//! Claude Code 等客户端经本地代理请求时，每次请求都会写入用量日志，看门狗据此跟踪
+//! 当前供应商的上游错误（默认 401 / 403 / 429）：
+//!
+//! - 滚动窗口内匹配错误的次数与比例都达到阈值时，把该应用热切换到故障转移队列中的
+//!   下一个健康供应商（更新 live 配置、托盘与界面，与代理故障转移切换走同一路径）；
+//! - 切走后经过冷却期，在下一次请求时自动切回原供应商；原供应商仍然出错会再次触发，
+//!   两次自动切换之间同样至少间隔一个冷却期，避免来回抖动；
+//! - 每次切换按 `auto-switch` 事件推送外部通知，并向前端发送 `provider-watchdog-switch` 事件。
+//!
+//! 配置见 [`FailbackWatchdogConfig`]（默认关闭）。窗口只保存在内存中，代理重启后重新累计。
+
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::Manager;
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::proxy::failover_switch::FailoverSwitchManager;
+use crate::proxy::types::FailbackWatchdogConfig;
+use crate::services::notifier::{localized_text, Notification, NotifierService};
+use crate::settings::NotificationEvent;
+
+/// 前端监听的事件名
+pub const EVENT_PROVIDER_WATCHDOG_SWITCH: &str = "provider-watchdog-switch";
+
+/// 单个窗口最多保留的样本数，避免高流量时无限增长
+const MAX_SAMPLES: usize = 1000;
+
+/// 自动切换原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchdogSwitchReason {
+    /// 当前供应商错误率超限，切到下一个供应商
+    Failing,
+    /// 冷却期结束，切回原供应商
+    CooloffElapsed,
+}
+
+/// 自动切换事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderWatchdogSwitch {
+    pub app_type: String,
+    pub from_provider_id: String,
+    pub from_provider_name: String,
+    pub to_provider_id: String,
+    pub to_provider_name: String,
+    pub reason: WatchdogSwitchReason,
+    /// 触发时窗口内的匹配错误数 / 请求数（切回时为 0）
+    pub failures: usize,
+    pub requests: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Decision {
+    Keep,
+    SwitchAway { failures: usize, requests: usize },
+    SwitchBack(String),
+}
+
+/// 单个应用的看门狗状态
+#[derive(Debug, Default)]
+struct AppWatch {
+    /// (时间, 供应商, 是否为匹配错误)；只保留当前供应商的样本
+    samples: VecDeque<(Instant, String, bool)>,
+    /// 自动切走前的供应商与切走时间
+    failed_over_from: Option<(String, Instant)>,
+    last_switch: Option<Instant>,
+}
+
+impl AppWatch {
+    fn observe(
+        &mut self,
+        config: &FailbackWatchdogConfig,
+        now: Instant,
+        current_id: &str,
+        provider_id: &str,
+        status_code: u16,
+    ) -> Decision {
+        let window = Duration::from_secs(config.window_secs.max(1));
+        let cooloff = Duration::from_secs(config.cooloff_secs);
+
+        // 用户手动切回（或切到其他供应商）后不再自动切回
+        if self
+            .failed_over_from
+            .as_ref()
+            .is_some_and(|(original, _)| original == current_id)
+        {
+            self.failed_over_from = None;
+        }
+
+        self.samples
+            .retain(|(at, id, _)| id == current_id && now.duration_since(*at) <= window);
+        if provider_id == current_id {
+            self.samples.push_back((
+                now,
+                provider_id.to_string(),
+                config.status_codes.contains(&status_code),
+            ));
+            while self.samples.len() > MAX_SAMPLES {
+                self.samples.pop_front();
+            }
+        }
+
+        if let Some((original, switched_at)) = &self.failed_over_from {
+            if config.switch_back && now.duration_since(*switched_at) >= cooloff {
+                return Decision::SwitchBack(original.clone());
+            }
+        }
+
+        let cooled = self
+            .last_switch
+            .is_none_or(|at| now.duration_since(at) >= cooloff);
+        let requests = self.samples.len();
+        let failures = self.samples.iter().filter(|(_, _, failed)| *failed).count();
+        if cooled
+            && failures >= config.min_failures.max(1) as usize
+            && failures * 100 >= usize::from(config.error_rate_percent) * requests
+        {
+            return Decision::SwitchAway { failures, requests };
+        }
+        Decision::Keep
+    }
+
+    fn mark_switched(&mut self, failed_over_from: Option<String>, now: Instant) {
+        self.samples.clear();
+        self.failed_over_from = failed_over_from.map(|id| (id, now));
+        self.last_switch = Some(now);
+    }
+}
+
+/// app_type -> 看门狗状态
+static WATCHES: LazyLock<Mutex<HashMap<String, AppWatch>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 故障转移队列中当前供应商之后的第一个健康供应商（到队尾后从头找）
+fn next_in_queue(db: &Database, app_type: &str, current_id: &str) -> Option<(String, String)> {
+    let queue = db.get_failover_queue(app_type).ok()?;
+    let start = queue
+        .iter()
+        .position(|item| item.provider_id == current_id)
+        .map_or(0, |index| index + 1);
+    queue
+        .iter()
+        .cycle()
+        .skip(start)
+        .take(queue.len())
+        .find(|item| item.healthy && item.provider_id != current_id)
+        .map(|item| (item.provider_id.clone(), item.provider_name.clone()))
+}
+
+fn provider_name(db: &Database, app_type: &str, provider_id: &str) -> String {
+    db.get_provider_by_id(provider_id, app_type)
+        .ok()
+        .flatten()
+        .map(|provider| provider.name)
+        .unwrap_or_else(|| provider_id.to_string())
+}
+
+pub struct FailbackWatchdog;
+
+impl FailbackWatchdog {
+    /// 代理写入一条请求日志后调用：计入样本，需要时异步切换供应商
+    pub fn observe(db: &Database, app_type: &str, provider_id: &str, status_code: u16) {
+        let config = match db.get_failback_watchdog_config() {
+            Ok(config) if config.enabled => config,
+            Ok(_) => return,
+            Err(e) => {
+                log::debug!("[Watchdog] 读取看门狗配置失败: {e}");
+                return;
+            }
+        };
+        let Ok(app) = AppType::from_str(app_type) else {
+            return;
+        };
+        let Ok(Some(current_id)) = crate::settings::get_effective_current_provider(db, &app) else {
+            return;
+        };
+
+        let event = {
+            let Ok(mut watches) = WATCHES.lock() else {
+                return;
+            };
+            let watch = watches.entry(app_type.to_string()).or_default();
+            let now = Instant::now();
+            let (target, reason, failures, requests) = match watch.observe(
+                &config,
+                now,
+                &current_id,
+                provider_id,
+                status_code,
+            ) {
+                Decision::Keep => return,
+                Decision::SwitchAway { failures, requests } => {
+                    let Some(target) = next_in_queue(db, app_type, &current_id) else {
+                        log::warn!(
+                            "[Watchdog] {app_type} 当前供应商 {current_id} 持续出错，但故障转移队列中没有可切换的健康供应商"
+                        );
+                        watch.mark_switched(None, now);
+                        return;
+                    };
+                    watch.mark_switched(Some(current_id.clone()), now);
+                    (target, WatchdogSwitchReason::Failing, failures, requests)
+                }
+                Decision::SwitchBack(original) => {
+                    watch.mark_switched(None, now);
+                    let name = provider_name(db, app_type, &original);
+                    ((original, name), WatchdogSwitchReason::CooloffElapsed, 0, 0)
+                }
+            };
+            ProviderWatchdogSwitch {
+                app_type: app_type.to_string(),
+                from_provider_name: provider_name(db, app_type, &current_id),
+                from_provider_id: current_id,
+                to_provider_id: target.0,
+                to_provider_name: target.1,
+                reason,
+                failures,
+                requests,
+            }
+        };
+
+        let Some(handle) = crate::usage_events::app_handle() else {
+            log::debug!("[Watchdog] 应用尚未初始化，跳过自动切换");
+            return;
+        };
+        tauri::async_runtime::spawn(async move {
+            let Some(db) = handle
+                .try_state::<crate::store::AppState>()
+                .map(|state| state.db.clone())
+            else {
+                return;
+            };
+            let manager = FailoverSwitchManager::new(db);
+            match manager
+                .try_switch(
+                    Some(&handle),
+                    &event.app_type,
+                    &event.to_provider_id,
+                    &event.to_provider_name,
+                )
+                .await
+            {
+                Ok(true) => Self::announce(event),
+                Ok(false) => log::debug!(
+                    "[Watchdog] {} 未切换到 {}（代理未接管或切换进行中）",
+                    event.app_type,
+                    event.to_provider_name
+                ),
+                Err(e) => log::warn!("[Watchdog] 自动切换失败: {e}"),
+            }
+        });
+    }
+
+    fn announce(event: ProviderWatchdogSwitch) {
+        let (title, body) = match event.reason {
+            WatchdogSwitchReason::Failing => {
+                log::warn!(
+                    "[Watchdog] {} 供应商 {} 在 {} 次请求中出错 {} 次，已切换到 {}",
+                    event.app_type,
+                    event.from_provider_name,
+                    event.requests,
+                    event.failures,
+                    event.to_provider_name
+                );
+                (
+                    localized_text("已自动切换供应商", "Provider switched automatically"),
+                    localized_text(
+                        format!(
+                            "{}（{}）持续返回鉴权 / 限流错误，已切换到 {}",
+                            event.from_provider_name, event.app_type, event.to_provider_name
+                        ),
+                        format!(
+                            "{} ({}) kept returning auth / rate-limit errors; switched to {}",
+                            event.from_provider_name, event.app_type, event.to_provider_name
+                        ),
+                    ),
+                )
+            }
+            WatchdogSwitchReason::CooloffElapsed => {
+                log::info!(
+                    "[Watchdog] {} 冷却期结束，已切回 {}",
+                    event.app_type,
+                    event.to_provider_name
+                );
+                (
+                    localized_text("已切回原供应商", "Switched back to provider"),
+                    localized_text(
+                        format!(
+                            "冷却期结束，{} 已切回 {}",
+                            event.app_type, event.to_provider_name
+                        ),
+                        format!(
+                            "Cool-off elapsed; {} is back on {}",
+                            event.app_type, event.to_provider_name
+                        ),
+                    ),
+                )
+            }
+        };
+        NotifierService::dispatch(
+            Notification::new(NotificationEvent::AutoSwitch, title, body)
+                .with_dedup_key(format!("{}:{}", event.app_type, event.to_provider_id)),
+        );
+        crate::usage_events::emit(EVENT_PROVIDER_WATCHDOG_SWITCH, event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> FailbackWatchdogConfig {
+        FailbackWatchdogConfig {
+            enabled: true,
+            window_secs: 60,
+            min_failures: 3,
+            error_rate_percent: 50,
+            cooloff_secs: 300,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn switches_away_once_errors_cross_both_thresholds() {
+        let config = config();
+        let mut watch = AppWatch::default();
+        let now = Instant::now();
+        assert_eq!(watch.observe(&config, now, "a", "a", 429), Decision::Keep);
+        assert_eq!(watch.observe(&config, now, "a", "a", 200), Decision::Keep);
+        // 其他供应商（请求内故障转移）的结果不计入
+        assert_eq!(watch.observe(&config, now, "a", "b", 401), Decision::Keep);
+        // 500 不在状态码列表中
+        assert_eq!(watch.observe(&config, now, "a", "a", 500), Decision::Keep);
+        assert_eq!(watch.observe(&config, now, "a", "a", 401), Decision::Keep);
+        assert_eq!(
+            watch.observe(&config, now, "a", "a", 429),
+            Decision::SwitchAway {
+                failures: 3,
+                requests: 5
+            }
+        );
+
+        // 窗口外的错误过期
+        let mut watch = AppWatch::default();
+        for _ in 0..2 {
+            watch.observe(&config, now, "a", "a", 429);
+        }
+        let later = now + Duration::from_secs(61);
+        assert_eq!(watch.observe(&config, later, "a", "a", 429), Decision::Keep);
+    }
+
+    #[test]
+    fn switches_back_after_cooloff_unless_user_already_did() {
+        let config = config();
+        let now = Instant::now();
+        let mut watch = AppWatch::default();
+        watch.mark_switched(Some("a".to_string()), now);
+
+        let early = now + Duration::from_secs(10);
+        assert_eq!(watch.observe(&config, early, "b", "b", 200), Decision::Keep);
+        let after = now + Duration::from_secs(301);
+        assert_eq!(
+            watch.observe(&config, after, "b", "b", 200),
+            Decision::SwitchBack("a".to_string())
+        );
+
+        // 冷却期内新供应商出错也不会再次切换
+        let mut watch = AppWatch::default();
+        watch.mark_switched(Some("a".to_string()), now);
+        for _ in 0..3 {
+            assert_eq!(watch.observe(&config, early, "b", "b", 429), Decision::Keep);
+        }
+
+        // 用户手动切回后清除待切回状态
+        let mut watch = AppWatch::default();
+        watch.mark_switched(Some("a".to_string()), now);
+        assert_eq!(watch.observe(&config, after, "a", "a", 200), Decision::Keep);
+        assert!(watch.failed_over_from.is_none());
+    }
+}
@@ -0,0 +1,139 @@
+//! 故障转移队列管理
+//!
+//! 界面与命令行共用的队列增删与排序入口。队列顺序即首页列表顺序（`sort_index`），
+//! 代理路由按同一顺序依次尝试（见 `ProviderRouter::select_providers`）。
+//! 移除成员时保证队列中仍至少有一个健康的供应商，避免开启故障转移后无路可走。
+
+use crate::app_config::AppType;
+use crate::database::{Database, FailoverQueueItem};
+use crate::error::AppError;
+
+pub struct FailoverQueueService;
+
+impl FailoverQueueService {
+    /// 按路由顺序列出队列
+    pub fn list(db: &Database, app_type: &AppType) -> Result<Vec<FailoverQueueItem>, AppError> {
+        db.get_failover_queue(app_type.as_str())
+    }
+
+    /// 加入队列；已在队列中时不做任何事
+    pub fn add(
+        db: &Database,
+        app_type: &AppType,
+        provider_id: &str,
+    ) -> Result<Vec<FailoverQueueItem>, AppError> {
+        if db
+            .get_provider_by_id(provider_id, app_type.as_str())?
+            .is_none()
+        {
+            return Err(provider_not_found(provider_id));
+        }
+        db.add_to_failover_queue(app_type.as_str(), provider_id)?;
+        log::info!(
+            "[Failover] 已将供应商 {provider_id} 加入 {} 故障转移队列",
+            app_type.as_str()
+        );
+        Self::list(db, app_type)
+    }
+
+    /// 移出队列
+    ///
+    /// `auto_failover_enabled` 为应用当前的自动故障转移开关：开启时不允许清空队列。
+    pub fn remove(
+        db: &Database,
+        app_type: &AppType,
+        provider_id: &str,
+        auto_failover_enabled: bool,
+    ) -> Result<Vec<FailoverQueueItem>, AppError> {
+        let queue = Self::list(db, app_type)?;
+        if !queue.iter().any(|item| item.provider_id == provider_id) {
+            return Err(AppError::localized(
+                "failover.queue.not_member",
+                format!("供应商 {provider_id} 不在故障转移队列中"),
+                format!("Provider {provider_id} is not in the failover queue"),
+            ));
+        }
+        let remaining: Vec<&FailoverQueueItem> = queue
+            .iter()
+            .filter(|item| item.provider_id != provider_id)
+            .collect();
+        ensure_healthy_remaining(&remaining, auto_failover_enabled)?;
+
+        db.remove_from_failover_queue(app_type.as_str(), provider_id)?;
+        Self::list(db, app_type)
+    }
+
+    /// 调整队列顺序；`ordered_ids` 必须恰好是当前队列的全部成员
+    pub fn reorder(
+        db: &Database,
+        app_type: &AppType,
+        ordered_ids: &[String],
+    ) -> Result<Vec<FailoverQueueItem>, AppError> {
+        db.reorder_failover_queue(app_type.as_str(), ordered_ids)?;
+        log::info!(
+            "[Failover] {} 故障转移队列顺序已更新: {}",
+            app_type.as_str(),
+            ordered_ids.join(" → ")
+        );
+        Self::list(db, app_type)
+    }
+}
+
+fn provider_not_found(provider_id: &str) -> AppError {
+    AppError::localized(
+        "provider.not_found",
+        format!("供应商不存在: {provider_id}"),
+        format!("Provider not found: {provider_id}"),
+    )
+}
+
+/// 移除后队列必须仍有健康成员；队列被清空只在自动故障转移关闭时允许
+fn ensure_healthy_remaining(
+    remaining: &[&FailoverQueueItem],
+    auto_failover_enabled: bool,
+) -> Result<(), AppError> {
+    if remaining.is_empty() {
+        if auto_failover_enabled {
+            return Err(AppError::localized(
+                "failover.queue.last_member",
+                "自动故障转移已开启，不能移除队列中的最后一个供应商",
+                "Automatic failover is on; the last provider in the queue cannot be removed",
+            ));
+        }
+        return Ok(());
+    }
+    if !remaining.iter().any(|item| item.healthy) {
+        return Err(AppError::localized(
+            "failover.queue.no_healthy_remaining",
+            "移除后队列中将没有健康的供应商",
+            "No healthy provider would remain in the failover queue",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, healthy: bool) -> FailoverQueueItem {
+        FailoverQueueItem {
+            provider_id: id.to_string(),
+            provider_name: id.to_string(),
+            sort_index: None,
+            provider_notes: None,
+            healthy,
+        }
+    }
+
+    #[test]
+    fn removal_keeps_a_healthy_member() {
+        let healthy = item("a", true);
+        let unhealthy = item("b", false);
+
+        assert!(ensure_healthy_remaining(&[&healthy, &unhealthy], true).is_ok());
+        assert!(ensure_healthy_remaining(&[&unhealthy], false).is_err());
+        assert!(ensure_healthy_remaining(&[], false).is_ok());
+        assert!(ensure_healthy_remaining(&[], true).is_err());
+    }
+}
@@ -0,0 +1,420 @@
+//! 供应商图标（favicon）缓存
+//!
+//! 没有手动选择图标的供应商，按官网地址（`websiteUrl`）抓取站点图标并缓存到
+//! `~/.cc-switch/favicons/<host>.<ext>`，界面据此显示，供应商很多时也便于快速辨认。
+//!
+//! - 后台节流刷新：启动后延迟执行、之后每 6 小时一轮，逐个站点请求并间隔
+//!   [`REQUEST_GAP`]；缓存 7 天内不重复抓取，抓取失败的站点 1 天内不再重试。
+//! - 离线优先：离线模式或网络失败时保留旧缓存，界面回退到内置图标 / 首字母，
+//!   命令行回退到 [`fallback_glyph`]。
+//! - 体积限制：图标不超过 [`MAX_ICON_BYTES`]，只接受按文件头识别出的图片格式。
+//!
+//! 需在设置中开启（默认关闭，不主动访问供应商官网）。
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Duration, SystemTime};
+
+use base64::Engine;
+use regex::Regex;
+
+use crate::app_config::AppType;
+use crate::config::get_app_config_dir;
+use crate::database::Database;
+use crate::provider::Provider;
+
+/// 前端监听：有图标更新时刷新
+pub const EVENT_PROVIDER_FAVICONS_UPDATED: &str = "provider-favicons-updated";
+
+/// 启动后延迟，避免与启动时的代理恢复、用量同步争抢网络
+pub const STARTUP_DELAY: Duration = Duration::from_secs(30);
+/// 后台刷新周期
+pub const REFRESH_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// 相邻两次站点请求的间隔
+const REQUEST_GAP: Duration = Duration::from_secs(1);
+const FETCH_TIMEOUT: Duration = Duration::from_secs(8);
+const FRESH_FOR: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const RETRY_MISS_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 单个图标的大小上限
+pub const MAX_ICON_BYTES: usize = 128 * 1024;
+/// 解析 `<link rel="icon">` 时读取的首页大小上限
+const MAX_PAGE_BYTES: usize = 512 * 1024;
+
+const CACHE_DIR: &str = "favicons";
+const MISS_EXT: &str = "miss";
+
+/// 识别出的图片格式：(扩展名, MIME)
+const FORMATS: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("ico", "image/x-icon"),
+    ("jpg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("svg", "image/svg+xml"),
+];
+
+static LINK_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<link\b[^>]*>").expect("valid regex"));
+static ATTR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)\b(rel|href)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#).expect("valid regex")
+});
+
+pub struct FaviconService;
+
+impl FaviconService {
+    fn cache_dir() -> PathBuf {
+        get_app_config_dir().join(CACHE_DIR)
+    }
+
+    /// 官网地址对应的缓存键（小写主机名，去掉 `www.`）；非 http(s) 地址返回 None
+    pub fn host_key(website_url: &str) -> Option<String> {
+        let url = url::Url::parse(website_url.trim()).ok()?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return None;
+        }
+        let host = url.host_str()?.to_lowercase();
+        let host = host.strip_prefix("www.").unwrap_or(&host);
+        host.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+            .then(|| host.to_string())
+    }
+
+    /// 已缓存的图标文件
+    fn cached_file(dir: &Path, host: &str) -> Option<(PathBuf, &'static str)> {
+        FORMATS.iter().find_map(|(ext, mime)| {
+            let path = dir.join(format!("{host}.{ext}"));
+            path.is_file().then_some((path, *mime))
+        })
+    }
+
+    /// 已缓存图标的 data URL；没有缓存时返回 None（不触发网络请求）
+    pub fn data_url(website_url: &str) -> Option<String> {
+        let host = Self::host_key(website_url)?;
+        let (path, mime) = Self::cached_file(&Self::cache_dir(), &host)?;
+        let bytes = std::fs::read(path).ok()?;
+        Some(format!(
+            "data:{mime};base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        ))
+    }
+
+    /// 指定应用各供应商（id → data URL）的已缓存图标
+    pub fn for_providers<'a>(
+        providers: impl IntoIterator<Item = &'a Provider>,
+    ) -> HashMap<String, String> {
+        providers
+            .into_iter()
+            .filter_map(|provider| {
+                let url = provider.website_url.as_deref()?;
+                Some((provider.id.clone(), Self::data_url(url)?))
+            })
+            .collect()
+    }
+
+    /// 需要抓取图标的站点：未手动选择图标、且填写了官网地址的供应商
+    fn wanted_hosts(db: &Database) -> BTreeSet<String> {
+        let mut hosts = BTreeSet::new();
+        for app_type in AppType::all() {
+            let Ok(providers) = db.get_all_providers(app_type.as_str()) else {
+                continue;
+            };
+            hosts.extend(providers.values().filter_map(|provider| {
+                if provider
+                    .icon
+                    .as_deref()
+                    .is_some_and(|icon| !icon.is_empty())
+                {
+                    return None;
+                }
+                Self::host_key(provider.website_url.as_deref()?)
+            }));
+        }
+        hosts
+    }
+
+    fn is_fresh(path: &Path, max_age: Duration) -> bool {
+        std::fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age < max_age)
+    }
+
+    /// 该站点本轮是否需要抓取
+    fn is_due(dir: &Path, host: &str) -> bool {
+        if let Some((path, _)) = Self::cached_file(dir, host) {
+            return !Self::is_fresh(&path, FRESH_FOR);
+        }
+        !Self::is_fresh(&dir.join(format!("{host}.{MISS_EXT}")), RETRY_MISS_AFTER)
+    }
+
+    /// 刷新过期的图标缓存，返回更新的图标数；未开启、离线模式下直接返回 0
+    pub async fn refresh_stale(db: &Database) -> usize {
+        if !crate::settings::get_settings().provider_favicons_enabled {
+            return 0;
+        }
+        let client = match crate::proxy::http_client::get_auxiliary("provider favicons") {
+            Ok(client) => client,
+            Err(e) => {
+                log::debug!("[Favicon] 跳过刷新: {e}");
+                return 0;
+            }
+        };
+        let dir = Self::cache_dir();
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("[Favicon] 创建缓存目录失败: {e}");
+            return 0;
+        }
+
+        let due: Vec<String> = Self::wanted_hosts(db)
+            .into_iter()
+            .filter(|host| Self::is_due(&dir, host))
+            .collect();
+        let mut updated = 0;
+        for (index, host) in due.iter().enumerate() {
+            if index > 0 {
+                tokio::time::sleep(REQUEST_GAP).await;
+            }
+            match fetch_icon(&client, host).await {
+                Ok((bytes, ext)) => match store(&dir, host, &bytes, ext) {
+                    Ok(()) => updated += 1,
+                    Err(e) => log::warn!("[Favicon] 写入 {host} 图标失败: {e}"),
+                },
+                Err(e) => {
+                    log::debug!("[Favicon] 获取 {host} 图标失败: {e}");
+                    // 保留旧缓存；从未成功过的站点记录失败时间，1 天内不再重试
+                    if Self::cached_file(&dir, host).is_some() {
+                        touch(&dir, host);
+                    } else {
+                        let _ = std::fs::write(dir.join(format!("{host}.{MISS_EXT}")), b"");
+                    }
+                }
+            }
+        }
+        if !due.is_empty() {
+            log::info!("[Favicon] 图标刷新完成: {updated}/{} 个站点", due.len());
+        }
+        updated
+    }
+}
+
+/// 先按首页 `<link rel="icon">` 声明的地址，再回退到 `/favicon.ico`
+async fn fetch_icon(
+    client: &reqwest::Client,
+    host: &str,
+) -> Result<(Vec<u8>, &'static str), String> {
+    let origin = url::Url::parse(&format!("https://{host}/")).map_err(|e| e.to_string())?;
+    let mut candidates = match read_limited(client, origin.as_str(), MAX_PAGE_BYTES).await {
+        Ok(page) => icon_links(&origin, &String::from_utf8_lossy(&page)),
+        Err(e) => {
+            log::debug!("[Favicon] 读取 {host} 首页失败: {e}");
+            Vec::new()
+        }
+    };
+    candidates.push(origin.join("/favicon.ico").map_err(|e| e.to_string())?);
+
+    let mut last_err = String::from("no icon candidates");
+    for candidate in candidates {
+        match read_limited(client, candidate.as_str(), MAX_ICON_BYTES).await {
+            Ok(bytes) => match sniff(&bytes) {
+                Some(ext) => return Ok((bytes, ext)),
+                None => last_err = format!("{candidate}: not an image"),
+            },
+            Err(e) => last_err = e,
+        }
+    }
+    Err(last_err)
+}
+
+/// GET 并限制响应体大小，超出上限视为失败
+async fn read_limited(
+    client: &reqwest::Client,
+    url: &str,
+    limit: usize,
+) -> Result<Vec<u8>, String> {
+    let mut response = client
+        .get(url)
+        .timeout(FETCH_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("{url}: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("{url}: HTTP {}", response.status()));
+    }
+    if response
+        .content_length()
+        .is_some_and(|length| length as usize > limit)
+    {
+        return Err(format!("{url}: larger than {limit} bytes"));
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| format!("{url}: {e}"))? {
+        if body.len() + chunk.len() > limit {
+            return Err(format!("{url}: larger than {limit} bytes"));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// 首页中声明的图标地址（`rel` 含 `icon`），按出现顺序
+fn icon_links(base: &url::Url, html: &str) -> Vec<url::Url> {
+    LINK_TAG
+        .find_iter(html)
+        .filter_map(|tag| {
+            let mut rel = None;
+            let mut href = None;
+            for attr in ATTR.captures_iter(tag.as_str()) {
+                let value = attr
+                    .get(2)
+                    .or_else(|| attr.get(3))
+                    .or_else(|| attr.get(4))
+                    .map(|value| value.as_str().trim());
+                match attr[1].to_lowercase().as_str() {
+                    "rel" => rel = value,
+                    _ => href = value,
+                }
+            }
+            let is_icon = rel?.split_ascii_whitespace().any(|token| {
+                token.eq_ignore_ascii_case("icon") || token.eq_ignore_ascii_case("apple-touch-icon")
+            });
+            let href =
+                href.filter(|href| is_icon && !href.is_empty() && !href.starts_with("data:"))?;
+            base.join(href)
+                .ok()
+                .filter(|url| matches!(url.scheme(), "http" | "https"))
+        })
+        .collect()
+}
+
+/// 按文件头识别图片格式，返回扩展名
+fn sniff(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("png")
+    } else if bytes.starts_with(&[0, 0, 1, 0]) {
+        Some("ico")
+    } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        Some("jpg")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("gif")
+    } else if bytes.len() > 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some("webp")
+    } else {
+        let head = String::from_utf8_lossy(&bytes[..bytes.len().min(1024)]).to_lowercase();
+        let head = head.trim_start_matches('\u{feff}').trim_start();
+        (head.starts_with('<') && head.contains("<svg")).then_some("svg")
+    }
+}
+
+/// 原子写入新图标，并清理同一站点其它格式的旧缓存与失败记录
+fn store(dir: &Path, host: &str, bytes: &[u8], ext: &str) -> Result<(), crate::error::AppError> {
+    crate::config::atomic_write(&dir.join(format!("{host}.{ext}")), bytes)?;
+    for other in FORMATS
+        .iter()
+        .map(|(other, _)| *other)
+        .filter(|other| *other != ext)
+        .chain([MISS_EXT])
+    {
+        let _ = std::fs::remove_file(dir.join(format!("{host}.{other}")));
+    }
+    Ok(())
+}
+
+/// 刷新缓存文件的修改时间，让失败的站点在下个周期之前不再重试
+fn touch(dir: &Path, host: &str) {
+    if let Some((path, _)) = FaviconService::cached_file(dir, host) {
+        if let Ok(file) = std::fs::File::options().append(true).open(path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+    }
+}
+
+/// 命令行中的图标回退：名称首字符的带圈形式（`Ⓐ`、`①`），其它文字保留原字符
+pub fn fallback_glyph(name: &str) -> char {
+    let Some(first) = name.chars().find(|c| c.is_alphanumeric()) else {
+        return '•';
+    };
+    let upper = first.to_ascii_uppercase();
+    match upper {
+        'A'..='Z' => char::from_u32(0x24B6 + (upper as u32 - 'A' as u32)).unwrap_or(upper),
+        '0' => '⓪',
+        '1'..='9' => char::from_u32(0x2460 + (upper as u32 - '1' as u32)).unwrap_or(upper),
+        _ => first,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_keys_are_normalized_and_safe() {
+        assert_eq!(
+            FaviconService::host_key("https://WWW.Example.com/path?q=1").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            FaviconService::host_key("http://relay.example.com:8080").as_deref(),
+            Some("relay.example.com")
+        );
+        assert_eq!(FaviconService::host_key("file:///etc/passwd"), None);
+        assert_eq!(FaviconService::host_key("not a url"), None);
+    }
+
+    #[test]
+    fn finds_declared_icons_and_sniffs_formats() {
+        let base = url::Url::parse("https://example.com/").unwrap();
+        let html = r#"<head>
+            <link rel="stylesheet" href="/site.css">
+            <LINK REL='shortcut icon' HREF='/static/icon.png'>
+            <link href="https://cdn.example.com/apple.png" rel="apple-touch-icon">
+            <link rel="icon" href="data:image/png;base64,AAAA">
+        </head>"#;
+        let links: Vec<String> = icon_links(&base, html)
+            .into_iter()
+            .map(|url| url.to_string())
+            .collect();
+        assert_eq!(
+            links,
+            [
+                "https://example.com/static/icon.png",
+                "https://cdn.example.com/apple.png"
+            ]
+        );
+
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\nrest"), Some("png"));
+        assert_eq!(sniff(&[0, 0, 1, 0, 1]), Some("ico"));
+        assert_eq!(sniff(b"  <?xml version=\"1.0\"?><svg></svg>"), Some("svg"));
+        assert_eq!(sniff(b"<!doctype html><html>"), None);
+    }
+
+    #[test]
+    fn stale_and_missing_hosts_are_due() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(FaviconService::is_due(dir.path(), "example.com"));
+
+        store(dir.path(), "example.com", b"\x89PNG\r\n\x1a\n", "png").unwrap();
+        assert!(!FaviconService::is_due(dir.path(), "example.com"));
+
+        std::fs::write(dir.path().join("down.example.com.miss"), b"").unwrap();
+        assert!(!FaviconService::is_due(dir.path(), "down.example.com"));
+
+        // 新格式替换旧缓存与失败记录
+        store(dir.path(), "down.example.com", &[0, 0, 1, 0], "ico").unwrap();
+        assert!(!dir.path().join("down.example.com.miss").exists());
+        assert_eq!(
+            FaviconService::cached_file(dir.path(), "down.example.com").map(|(_, mime)| mime),
+            Some("image/x-icon")
+        );
+    }
+
+    #[test]
+    fn glyph_fallbacks() {
+        assert_eq!(fallback_glyph("deepseek"), 'Ⓓ');
+        assert_eq!(fallback_glyph("302.AI"), '③');
+        assert_eq!(fallback_glyph("智谱"), '智');
+        assert_eq!(fallback_glyph("--"), '•');
+    }
+}
@@ -0,0 +1,234 @@
+//! 首字延迟 SLO：按供应商评估滚动窗口内的 TTFB 百分位
+//!
+//! SLO 存放在供应商 meta 的 `latencySlo` 中（如 p95 < 3000ms，窗口 15 分钟）。
+//!
+//! - 代理每记录一次成功请求，把首字延迟（流式取首个事件，非流式取响应耗时）计入窗口；
+//! - 样本数足够后计算百分位，由达标变为违反、或由违反恢复时向前端发送
+//!   `provider-latency-slo` 事件（状态不变时不重复提醒）；
+//! - 开启 `demote` 的供应商在违反期间排到故障转移队列末尾，恢复后自动回到原位。
+//!
+//! 窗口只保存在内存中，代理重启后重新累计。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::database::Database;
+use crate::provider::{LatencySlo, Provider};
+
+/// 前端监听的事件名
+pub const EVENT_PROVIDER_LATENCY_SLO: &str = "provider-latency-slo";
+
+/// 单个窗口最多保留的样本数，避免高流量时无限增长
+const MAX_SAMPLES: usize = 2000;
+
+#[derive(Debug, Default)]
+struct SloWindow {
+    samples: VecDeque<(Instant, u64)>,
+    violated: bool,
+}
+
+impl SloWindow {
+    /// 计入一个样本；SLO 状态发生变化时返回 `(是否违反, 观测到的百分位)`
+    fn record(&mut self, slo: &LatencySlo, now: Instant, ttfb_ms: u64) -> Option<(bool, u64)> {
+        let window = Duration::from_secs(u64::from(slo.window_minutes.max(1)) * 60);
+        self.samples.push_back((now, ttfb_ms));
+        while let Some((at, _)) = self.samples.front() {
+            if now.duration_since(*at) > window || self.samples.len() > MAX_SAMPLES {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+        if self.samples.len() < slo.min_samples.max(1) as usize {
+            return None;
+        }
+        let values: Vec<u64> = self.samples.iter().map(|(_, ms)| *ms).collect();
+        let observed = percentile(&values, slo.percentile)?;
+        let violated = observed > slo.threshold_ms;
+        if violated == self.violated {
+            return None;
+        }
+        self.violated = violated;
+        Some((violated, observed))
+    }
+}
+
+/// (app_type, provider_id) -> 滚动窗口
+static WINDOWS: LazyLock<Mutex<HashMap<(String, String), SloWindow>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// SLO 状态变化事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderLatencySloAlert {
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    /// true 表示开始违反，false 表示已恢复
+    pub violated: bool,
+    pub percentile: u8,
+    pub threshold_ms: u64,
+    pub observed_ms: u64,
+    pub window_minutes: u32,
+    pub demoted: bool,
+}
+
+/// 最近秩法求百分位；`values` 为空时返回 None
+pub fn percentile(values: &[u64], percentile: u8) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let p = f64::from(percentile.clamp(1, 100)) / 100.0;
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.saturating_sub(1)).copied()
+}
+
+fn slo_of(provider: &Provider) -> Option<&LatencySlo> {
+    provider
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.latency_slo.as_ref())
+        .filter(|slo| slo.threshold_ms > 0)
+}
+
+pub struct LatencySloService;
+
+impl LatencySloService {
+    /// 代理记录一次成功请求后调用：计入首字延迟，SLO 状态变化时发出提醒
+    pub fn on_request_completed(db: &Database, app_type: &str, provider_id: &str, ttfb_ms: u64) {
+        let provider = match db.get_provider_by_id(provider_id, app_type) {
+            Ok(Some(provider)) => provider,
+            Ok(None) => return,
+            Err(e) => {
+                log::debug!("[SLO] 读取供应商失败: {e}");
+                return;
+            }
+        };
+        let Some(slo) = slo_of(&provider) else {
+            return;
+        };
+
+        let change = {
+            let Ok(mut windows) = WINDOWS.lock() else {
+                return;
+            };
+            windows
+                .entry((app_type.to_string(), provider_id.to_string()))
+                .or_default()
+                .record(slo, Instant::now(), ttfb_ms)
+        };
+        let Some((violated, observed_ms)) = change else {
+            return;
+        };
+
+        if violated {
+            log::warn!(
+                "[SLO] 供应商 {} 首字延迟 p{} = {observed_ms}ms，超过 {}ms",
+                provider.name,
+                slo.percentile,
+                slo.threshold_ms
+            );
+        } else {
+            log::info!(
+                "[SLO] 供应商 {} 首字延迟 p{} = {observed_ms}ms，已恢复",
+                provider.name,
+                slo.percentile
+            );
+        }
+        crate::usage_events::emit(
+            EVENT_PROVIDER_LATENCY_SLO,
+            ProviderLatencySloAlert {
+                app_type: app_type.to_string(),
+                provider_id: provider_id.to_string(),
+                provider_name: provider.name.clone(),
+                violated,
+                percentile: slo.percentile,
+                threshold_ms: slo.threshold_ms,
+                observed_ms,
+                window_minutes: slo.window_minutes,
+                demoted: violated && slo.demote,
+            },
+        );
+    }
+
+    /// 当前是否处于违反状态
+    pub fn is_violated(app_type: &str, provider_id: &str) -> bool {
+        WINDOWS
+            .lock()
+            .ok()
+            .and_then(|windows| {
+                windows
+                    .get(&(app_type.to_string(), provider_id.to_string()))
+                    .map(|window| window.violated)
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// 路由：开启 `demote` 且正在违反 SLO 的供应商排到最后，返回被后移的数量
+pub fn defer_violating(app_type: &str, providers: &mut Vec<Provider>) -> usize {
+    let (healthy, deferred): (Vec<_>, Vec<_>) = providers.drain(..).partition(|provider| {
+        !slo_of(provider).is_some_and(|slo| slo.demote)
+            || !LatencySloService::is_violated(app_type, &provider.id)
+    });
+    for provider in &deferred {
+        log::info!(
+            "[SLO] 供应商 {} 正在违反首字延迟 SLO，延后尝试",
+            provider.name
+        );
+    }
+    let count = deferred.len();
+    providers.extend(healthy);
+    providers.extend(deferred);
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slo(threshold_ms: u64, min_samples: u32) -> LatencySlo {
+        LatencySlo {
+            percentile: 95,
+            threshold_ms,
+            window_minutes: 1,
+            min_samples,
+            demote: true,
+        }
+    }
+
+    #[test]
+    fn percentile_uses_nearest_rank() {
+        let values: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&values, 95), Some(95));
+        assert_eq!(percentile(&values, 100), Some(100));
+        assert_eq!(percentile(&[42], 50), Some(42));
+        assert_eq!(percentile(&[], 95), None);
+    }
+
+    #[test]
+    fn window_reports_only_state_changes() {
+        let slo = slo(1000, 3);
+        let mut window = SloWindow::default();
+        let start = Instant::now();
+
+        // 样本不足时不评估
+        assert_eq!(window.record(&slo, start, 5000), None);
+        assert_eq!(window.record(&slo, start, 5000), None);
+        assert_eq!(window.record(&slo, start, 5000), Some((true, 5000)));
+        // 持续违反不重复提醒
+        assert_eq!(window.record(&slo, start, 5000), None);
+
+        // 旧样本滑出窗口后恢复
+        let later = start + Duration::from_secs(120);
+        assert_eq!(window.record(&slo, later, 200), None);
+        assert_eq!(window.record(&slo, later, 300), None);
+        assert_eq!(window.record(&slo, later, 400), Some((false, 400)));
+        assert_eq!(window.samples.len(), 3);
+    }
+}
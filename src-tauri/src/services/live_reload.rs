@@ -0,0 +1,367 @@
+//! 将新安装 / 更新的 Skill、Prompt 推送到正在运行的 Claude 会话
+//!
+//! 文件写入 `~/.claude` 之后，已经在运行的 Claude Code 会话未必能看到变化，
+//! 用户常遇到"装好了却用不了"的情况。这里提供一个显式的刷新动作：
+//!
+//! 1. 触发：更新目标文件的修改时间，让 CLI 的文件监听重新加载
+//!    （Claude Code 会热加载 `skills/` 目录，CLAUDE.md 与 `commands/` 仍需重启会话）；
+//! 2. 检查：扫描最近活跃的会话记录，逐个判断会话是否已经拿到新内容，
+//!    无法热加载时给出 `claude --resume` 重启命令。
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::config::get_claude_config_dir;
+use crate::error::AppError;
+use crate::prompt_files::prompt_file_path;
+use crate::services::skill::SkillService;
+use crate::session_manager::{self, SessionMeta};
+
+/// 最近多长时间内有写入的会话视为"正在运行"
+const ACTIVE_WINDOW: Duration = Duration::from_secs(30 * 60);
+
+/// 检查会话是否已加载时读取的记录尾部大小
+const TAIL_BYTES: u64 = 256 * 1024;
+
+/// 刷新对象的类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReloadKind {
+    Skill,
+    Command,
+    Prompt,
+}
+
+impl ReloadKind {
+    /// Claude Code 是否会在运行中自动重新加载该类文件
+    fn hot_reloads(self) -> bool {
+        matches!(self, Self::Skill)
+    }
+}
+
+/// 单个会话的加载状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionReloadStatus {
+    /// 会话在文件写入之后才启动，已包含新内容
+    Loaded,
+    /// 会话记录中已出现新内容，热加载生效
+    PickedUp,
+    /// 已触发热加载，会话的下一轮对话才会读取
+    Pending,
+    /// 该类文件不支持热加载，需要重启会话
+    RestartRequired,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionReloadState {
+    pub session_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_dir: Option<String>,
+    pub status: SessionReloadStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resume_command: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveReloadReport {
+    pub kind: ReloadKind,
+    pub name: String,
+    pub path: String,
+    /// 触发刷新的时间（毫秒时间戳），再次检查时原样传回
+    pub triggered_at: i64,
+    pub hot_reload: bool,
+    pub sessions: Vec<SessionReloadState>,
+}
+
+pub struct LiveReloadService;
+
+impl LiveReloadService {
+    /// 触发刷新并立即检查一次正在运行的会话
+    pub fn trigger(kind: ReloadKind, name: &str) -> Result<LiveReloadReport, AppError> {
+        let path = target_path(kind, name)?;
+        let file = touch_target(&path)?;
+        let triggered_at = now_ms();
+        log::info!(
+            "[LiveReload] 已刷新 {:?} {} ({})",
+            kind,
+            name,
+            file.display()
+        );
+        Ok(Self::build_report(kind, name, &path, triggered_at))
+    }
+
+    /// 重新检查会话是否已拿到 `triggered_at` 时刷新的内容
+    pub fn check(
+        kind: ReloadKind,
+        name: &str,
+        triggered_at: i64,
+    ) -> Result<LiveReloadReport, AppError> {
+        let path = target_path(kind, name)?;
+        if !path.exists() {
+            return Err(AppError::InvalidInput(format!(
+                "文件不存在: {}",
+                path.display()
+            )));
+        }
+        Ok(Self::build_report(kind, name, &path, triggered_at))
+    }
+
+    fn build_report(
+        kind: ReloadKind,
+        name: &str,
+        path: &Path,
+        triggered_at: i64,
+    ) -> LiveReloadReport {
+        let cutoff = triggered_at - ACTIVE_WINDOW.as_millis() as i64;
+        let needle = mention_needle(kind, name);
+        let sessions = session_manager::scan_sessions_for("claude")
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|session| session.last_active_at.is_some_and(|ts| ts >= cutoff))
+            .map(|session| {
+                let status = session_status(kind, &session, triggered_at, needle.as_deref());
+                SessionReloadState {
+                    resume_command: (status == SessionReloadStatus::RestartRequired)
+                        .then(|| session.resume_command.clone())
+                        .flatten(),
+                    session_id: session.session_id,
+                    title: session.title,
+                    project_dir: session.project_dir,
+                    status,
+                }
+            })
+            .collect();
+
+        LiveReloadReport {
+            kind,
+            name: name.to_string(),
+            path: path.to_string_lossy().to_string(),
+            triggered_at,
+            hot_reload: kind.hot_reloads(),
+            sessions,
+        }
+    }
+}
+
+/// 解析刷新对象在 Claude 配置目录中的路径
+fn target_path(kind: ReloadKind, name: &str) -> Result<PathBuf, AppError> {
+    let name = name.trim();
+    if kind != ReloadKind::Prompt && (name.is_empty() || name.contains(['/', '\\']) || name == "..")
+    {
+        return Err(AppError::InvalidInput(format!("无效的名称: {name}")));
+    }
+
+    match kind {
+        ReloadKind::Skill => Ok(SkillService::get_app_skills_dir(&AppType::Claude)
+            .map_err(|e| AppError::Message(e.to_string()))?
+            .join(name)),
+        ReloadKind::Command => {
+            let file = if name.ends_with(".md") {
+                name.to_string()
+            } else {
+                format!("{name}.md")
+            };
+            Ok(get_claude_config_dir().join("commands").join(file))
+        }
+        ReloadKind::Prompt => prompt_file_path(&AppType::Claude),
+    }
+}
+
+/// 更新修改时间以触发 CLI 的文件监听，返回实际被更新的文件
+///
+/// Skill 以目录形式安装，监听的是其中的 SKILL.md。
+fn touch_target(path: &Path) -> Result<PathBuf, AppError> {
+    let file = if path.is_dir() {
+        path.join("SKILL.md")
+    } else {
+        path.to_path_buf()
+    };
+    if !file.is_file() {
+        return Err(AppError::InvalidInput(format!(
+            "文件不存在: {}",
+            file.display()
+        )));
+    }
+
+    File::options()
+        .append(true)
+        .open(&file)
+        .and_then(|handle| handle.set_modified(SystemTime::now()))
+        .map_err(|e| AppError::io(&file, e))?;
+    Ok(file)
+}
+
+/// 会话记录中用于确认已加载的特征字符串
+///
+/// Claude Code 会在对话中附带可用 Skill 列表，刷新之后的记录里出现
+/// `skills/<name>` 或带引号的名称，即可认为新 Skill 已进入该会话。
+fn mention_needle(kind: ReloadKind, name: &str) -> Option<String> {
+    match kind {
+        ReloadKind::Skill => Some(name.trim().to_string()),
+        ReloadKind::Command | ReloadKind::Prompt => None,
+    }
+}
+
+fn session_status(
+    kind: ReloadKind,
+    session: &SessionMeta,
+    triggered_at: i64,
+    needle: Option<&str>,
+) -> SessionReloadStatus {
+    if session.created_at.is_some_and(|ts| ts >= triggered_at) {
+        return SessionReloadStatus::Loaded;
+    }
+    if !kind.hot_reloads() {
+        return SessionReloadStatus::RestartRequired;
+    }
+
+    let picked_up = match (needle, session.source_path.as_deref()) {
+        (Some(needle), Some(path)) => mentions_since(Path::new(path), needle, triggered_at),
+        _ => false,
+    };
+    if picked_up {
+        SessionReloadStatus::PickedUp
+    } else {
+        SessionReloadStatus::Pending
+    }
+}
+
+/// 检查会话记录中 `since` 之后的条目是否提到了目标 Skill
+fn mentions_since(path: &Path, needle: &str, since: i64) -> bool {
+    let Ok(tail) = read_tail(path, TAIL_BYTES) else {
+        return false;
+    };
+    let quoted = format!("\"{needle}\"");
+    let scoped = format!("skills/{needle}");
+
+    tail.lines().any(|line| {
+        if !line.contains(&quoted) && !line.contains(&scoped) {
+            return false;
+        }
+        serde_json::from_str::<Value>(line)
+            .ok()
+            .and_then(|value| value.get("timestamp").and_then(timestamp_ms))
+            .is_some_and(|ts| ts >= since)
+    })
+}
+
+fn read_tail(path: &Path, max_bytes: u64) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    let text = String::from_utf8_lossy(&buf).into_owned();
+    // 从中间截断时丢弃第一行残缺内容
+    Ok(if start > 0 {
+        text.split_once('\n')
+            .map(|(_, rest)| rest.to_string())
+            .unwrap_or_default()
+    } else {
+        text
+    })
+}
+
+fn timestamp_ms(value: &Value) -> Option<i64> {
+    if let Some(n) = value.as_i64() {
+        return Some(if n > 1_000_000_000_000 { n } else { n * 1000 });
+    }
+    chrono::DateTime::parse_from_rfc3339(value.as_str()?)
+        .ok()
+        .map(|dt| dt.timestamp_millis())
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn session(created_at: i64, source_path: Option<String>) -> SessionMeta {
+        SessionMeta {
+            provider_id: "claude".to_string(),
+            session_id: "s1".to_string(),
+            title: None,
+            summary: None,
+            project_dir: None,
+            created_at: Some(created_at),
+            last_active_at: Some(created_at),
+            source_path,
+            resume_command: Some("claude --resume s1".to_string()),
+        }
+    }
+
+    #[test]
+    fn status_depends_on_kind_and_session_start() {
+        let triggered = 1_000_000;
+        let old = session(triggered - 10, None);
+        let fresh = session(triggered + 10, None);
+
+        assert_eq!(
+            session_status(ReloadKind::Prompt, &fresh, triggered, None),
+            SessionReloadStatus::Loaded
+        );
+        assert_eq!(
+            session_status(ReloadKind::Prompt, &old, triggered, None),
+            SessionReloadStatus::RestartRequired
+        );
+        assert_eq!(
+            session_status(ReloadKind::Skill, &old, triggered, Some("pdf")),
+            SessionReloadStatus::Pending
+        );
+    }
+
+    #[test]
+    fn skill_mention_after_trigger_counts_as_picked_up() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        fs::write(
+            &path,
+            concat!(
+                "{\"timestamp\":\"2026-01-01T00:00:00Z\",\"text\":\"skills/pdf/SKILL.md\"}\n",
+                "{\"timestamp\":\"2026-01-01T00:10:00Z\",\"text\":\"hello\"}\n",
+            ),
+        )
+        .unwrap();
+        let before = chrono::DateTime::parse_from_rfc3339("2025-12-31T23:00:00Z")
+            .unwrap()
+            .timestamp_millis();
+        let after = chrono::DateTime::parse_from_rfc3339("2026-01-01T00:05:00Z")
+            .unwrap()
+            .timestamp_millis();
+
+        assert!(mentions_since(&path, "pdf", before));
+        assert!(!mentions_since(&path, "pdf", after));
+
+        let old = session(before - 1, Some(path.to_string_lossy().to_string()));
+        assert_eq!(
+            session_status(ReloadKind::Skill, &old, before, Some("pdf")),
+            SessionReloadStatus::PickedUp
+        );
+    }
+
+    #[test]
+    fn rejects_names_with_path_separators() {
+        assert!(target_path(ReloadKind::Skill, "../evil").is_err());
+        assert!(target_path(ReloadKind::Command, "").is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! 供应商维护时段
+//!
+//! 维护时段存放在供应商 meta 的 `maintenanceWindows` 中，按各自时区解释：
+//!
+//! - 代理路由时把正处于维护时段的供应商挪到故障转移队列末尾，只有其他供应商都失败时才会尝试；
+//! - 维护时段内的请求失败不计入熔断器与健康度；
+//! - 夜间自检在维护时段内的失败只做标注，不算作异常。
+//!
+//! 未安装时区数据库，命名时区只支持固定偏移的常见缩写，不处理夏令时。
+
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveTime, Timelike, Utc};
+
+use crate::provider::{MaintenanceWindow, Provider};
+
+/// 解析后的时区
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WindowZone {
+    Local,
+    Fixed(FixedOffset),
+}
+
+/// 常见时区缩写（固定偏移，单位：小时）
+const ZONE_ABBREVIATIONS: &[(&str, i32)] = &[
+    ("UTC", 0),
+    ("GMT", 0),
+    ("Z", 0),
+    ("CST", 8),
+    ("HKT", 8),
+    ("SGT", 8),
+    ("JST", 9),
+    ("KST", 9),
+    ("CET", 1),
+    ("EST", -5),
+    ("PST", -8),
+];
+
+fn parse_zone(raw: Option<&str>) -> Option<WindowZone> {
+    let raw = raw.map(str::trim).unwrap_or_default();
+    if raw.is_empty() || raw.eq_ignore_ascii_case("local") {
+        return Some(WindowZone::Local);
+    }
+    let upper = raw.to_ascii_uppercase();
+    if upper == "IST" {
+        // 印度标准时间为 +05:30
+        return FixedOffset::east_opt(5 * 3600 + 1800).map(WindowZone::Fixed);
+    }
+    if let Some((_, hours)) = ZONE_ABBREVIATIONS.iter().find(|(name, _)| *name == upper) {
+        return FixedOffset::east_opt(hours * 3600).map(WindowZone::Fixed);
+    }
+    let offset = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper);
+    parse_offset(offset).map(WindowZone::Fixed)
+}
+
+/// `+08:00`、`+0800`、`+8`、`-05:30`
+fn parse_offset(raw: &str) -> Option<FixedOffset> {
+    let (sign, rest) = match raw.as_bytes().first()? {
+        b'+' => (1, &raw[1..]),
+        b'-' => (-1, &raw[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((h, m)) => (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?),
+        None if rest.len() == 4 && rest.is_ascii() => {
+            (rest[..2].parse().ok()?, rest[2..].parse().ok()?)
+        }
+        None => (rest.parse().ok()?, 0),
+    };
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn parse_minutes(raw: &str) -> Option<u32> {
+    let time = NaiveTime::parse_from_str(raw.trim(), "%H:%M").ok()?;
+    Some(time.hour() * 60 + time.minute())
+}
+
+/// 校验维护时段配置，返回首个错误描述
+pub fn validate_window(window: &MaintenanceWindow) -> Result<(), String> {
+    let start =
+        parse_minutes(&window.start).ok_or_else(|| format!("无效的开始时间: {}", window.start))?;
+    let end =
+        parse_minutes(&window.end).ok_or_else(|| format!("无效的结束时间: {}", window.end))?;
+    if start == end {
+        return Err("开始时间与结束时间不能相同".to_string());
+    }
+    if parse_zone(window.timezone.as_deref()).is_none() {
+        return Err(format!(
+            "无法识别的时区: {}",
+            window.timezone.as_deref().unwrap_or_default()
+        ));
+    }
+    if let Some(day) = window.days.iter().find(|day| !(1..=7).contains(*day)) {
+        return Err(format!("无效的星期: {day}"));
+    }
+    Ok(())
+}
+
+/// `now` 是否落在维护时段内；配置无效的时段视为不生效
+pub fn window_contains(window: &MaintenanceWindow, now: DateTime<Utc>) -> bool {
+    let (Some(start), Some(end), Some(zone)) = (
+        parse_minutes(&window.start),
+        parse_minutes(&window.end),
+        parse_zone(window.timezone.as_deref()),
+    ) else {
+        return false;
+    };
+    let (minute_of_day, weekday) = match zone {
+        WindowZone::Local => {
+            let local = now.with_timezone(&Local);
+            (
+                local.hour() * 60 + local.minute(),
+                local.weekday().number_from_monday() as u8,
+            )
+        }
+        WindowZone::Fixed(offset) => {
+            let zoned = now.with_timezone(&offset);
+            (
+                zoned.hour() * 60 + zoned.minute(),
+                zoned.weekday().number_from_monday() as u8,
+            )
+        }
+    };
+    let applies_on = |day: u8| window.days.is_empty() || window.days.contains(&day);
+    let previous_day = if weekday == 1 { 7 } else { weekday - 1 };
+
+    if start < end {
+        applies_on(weekday) && (start..end).contains(&minute_of_day)
+    } else {
+        // 跨越午夜：前半段属于当天，后半段属于前一天开始的时段
+        (minute_of_day >= start && applies_on(weekday))
+            || (minute_of_day < end && applies_on(previous_day))
+    }
+}
+
+/// 供应商当前是否处于维护时段
+pub fn is_in_maintenance(provider: &Provider, now: DateTime<Utc>) -> bool {
+    provider.meta.as_ref().is_some_and(|meta| {
+        meta.maintenance_windows
+            .iter()
+            .any(|window| window_contains(window, now))
+    })
+}
+
+/// 路由：把处于维护时段的供应商挪到末尾（保持相对顺序），返回被挪动的数量
+pub fn defer_in_maintenance(providers: &mut Vec<Provider>, now: DateTime<Utc>) -> usize {
+    let (available, deferred): (Vec<_>, Vec<_>) = providers
+        .drain(..)
+        .partition(|provider| !is_in_maintenance(provider, now));
+    for provider in &deferred {
+        log::info!(
+            "[Maintenance] 供应商 {} 处于维护时段，延后尝试",
+            provider.name
+        );
+    }
+    let count = deferred.len();
+    providers.extend(available);
+    providers.extend(deferred);
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn window(start: &str, end: &str, timezone: Option<&str>, days: Vec<u8>) -> MaintenanceWindow {
+        MaintenanceWindow {
+            start: start.to_string(),
+            end: end.to_string(),
+            timezone: timezone.map(str::to_string),
+            days,
+        }
+    }
+
+    #[test]
+    fn window_respects_fixed_timezone() {
+        // 03:05 CST == 19:05 UTC（前一天）
+        let w = window("03:00", "03:10", Some("CST"), vec![]);
+        let inside = Utc.with_ymd_and_hms(2026, 1, 5, 19, 5, 0).unwrap();
+        let outside = Utc.with_ymd_and_hms(2026, 1, 5, 19, 10, 0).unwrap();
+        assert!(window_contains(&w, inside));
+        assert!(!window_contains(&w, outside));
+        assert!(window_contains(
+            &window("03:00", "03:10", Some("UTC+8"), vec![]),
+            inside
+        ));
+    }
+
+    #[test]
+    fn window_crossing_midnight_uses_start_day() {
+        // 周日 23:30 – 周一 00:30（UTC），只在周日开始
+        let w = window("23:30", "00:30", Some("UTC"), vec![7]);
+        let sunday_late = Utc.with_ymd_and_hms(2026, 1, 4, 23, 45, 0).unwrap();
+        let monday_early = Utc.with_ymd_and_hms(2026, 1, 5, 0, 15, 0).unwrap();
+        let monday_late = Utc.with_ymd_and_hms(2026, 1, 5, 23, 45, 0).unwrap();
+        assert!(window_contains(&w, sunday_late));
+        assert!(window_contains(&w, monday_early));
+        assert!(!window_contains(&w, monday_late));
+    }
+
+    #[test]
+    fn validate_rejects_bad_input() {
+        assert!(validate_window(&window("03:00", "03:10", Some("+08:00"), vec![1, 7])).is_ok());
+        assert!(validate_window(&window("25:00", "03:10", None, vec![])).is_err());
+        assert!(validate_window(&window("03:00", "03:00", None, vec![])).is_err());
+        assert!(validate_window(&window("03:00", "03:10", Some("Mars/Olympus"), vec![])).is_err());
+        assert!(validate_window(&window("03:00", "03:10", None, vec![0])).is_err());
+    }
+}
@@ -0,0 +1,578 @@
+//! MCP 服务器运行时检测与启动校验
+//!
+//! 预设与模板只负责把 JSON 配置合并进各应用，而大多数 stdio 服务器还依赖 `npx`、`uvx`、
+//! `docker` 等运行时。安装流程因此分为三步：
+//!
+//! 1. 检测：解析启动命令（兼容 Windows 的 `cmd /c npx ...` 包装），确认运行时可用；
+//! 2. 准备（可选）：提前拉取 npm 包或 Docker 镜像，避免首次启动时下载过久；
+//! 3. 校验：按配置启动服务器并发送 MCP `initialize` 请求，收到响应才视为可用。
+//!
+//! 校验通过后才在应用配置中启用该服务器，避免把起不来的服务器写进 CLI 配置。
+
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::error::AppError;
+use crate::services::env_checker::{self, RuntimeStatus};
+
+/// 准备步骤（下载包 / 拉取镜像）超时
+const PREPARE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// 启动校验的默认超时：npx / uvx 首次启动需要下载依赖
+pub const DEFAULT_VERIFY_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 服务器依赖的运行时类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpRuntimeKind {
+    Node,
+    Python,
+    Docker,
+    Other,
+}
+
+impl McpRuntimeKind {
+    fn of(program: &str) -> Self {
+        let name = program
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or(program)
+            .to_ascii_lowercase();
+        let name = name
+            .strip_suffix(".cmd")
+            .or_else(|| name.strip_suffix(".exe"))
+            .unwrap_or(&name);
+        match name {
+            "npx" | "npm" | "node" | "pnpm" | "bunx" => Self::Node,
+            "uvx" | "uv" | "python" | "python3" | "pipx" => Self::Python,
+            "docker" | "podman" => Self::Docker,
+            _ => Self::Other,
+        }
+    }
+
+    fn install_url(self) -> Option<&'static str> {
+        match self {
+            Self::Node => Some("https://nodejs.org/"),
+            Self::Python => Some("https://docs.astral.sh/uv/getting-started/installation/"),
+            Self::Docker => Some("https://docs.docker.com/get-docker/"),
+            Self::Other => None,
+        }
+    }
+}
+
+/// 运行时检测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpRuntimeCheck {
+    pub kind: McpRuntimeKind,
+    pub runtime: RuntimeStatus,
+    /// 启动的 npm / PyPI 包名或 Docker 镜像
+    pub package: Option<String>,
+    /// 可选的准备命令（仅用于展示）
+    pub prepare_command: Option<String>,
+    /// 运行时缺失时的安装指引
+    pub install_url: Option<String>,
+}
+
+/// 启动校验结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpStartResult {
+    pub ok: bool,
+    pub server_name: Option<String>,
+    pub server_version: Option<String>,
+    pub error: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+/// 实际启动的程序与参数
+#[derive(Debug, PartialEq, Eq)]
+struct Launch {
+    program: String,
+    args: Vec<String>,
+}
+
+fn launch_of(spec: &Value) -> Result<Launch, AppError> {
+    let kind = spec.get("type").and_then(Value::as_str).unwrap_or("stdio");
+    if kind != "stdio" {
+        return Err(AppError::InvalidInput(format!(
+            "只有 stdio 类型的 MCP 服务器需要本地运行时（当前为 {kind}）"
+        )));
+    }
+    let command = spec
+        .get("command")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|command| !command.is_empty())
+        .ok_or_else(|| AppError::InvalidInput("MCP 服务器缺少 command".into()))?;
+    let mut args: Vec<String> = spec
+        .get("args")
+        .and_then(Value::as_array)
+        .map(|args| {
+            args.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Windows 预设使用 `cmd /c npx ...` 包装，运行时取被包装的命令
+    if command.eq_ignore_ascii_case("cmd")
+        && args
+            .first()
+            .is_some_and(|arg| arg.eq_ignore_ascii_case("/c"))
+        && args.len() >= 2
+    {
+        let program = args.remove(1);
+        args.remove(0);
+        return Ok(Launch { program, args });
+    }
+    Ok(Launch {
+        program: command.to_string(),
+        args,
+    })
+}
+
+/// 第一个非选项参数；`takes_value` 中的选项会连带跳过其取值
+fn first_positional<'a>(args: &'a [String], takes_value: &[&str]) -> Option<&'a str> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if takes_value.contains(&arg.as_str()) {
+            iter.next();
+        } else if !arg.starts_with('-') {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+fn package_of(kind: McpRuntimeKind, launch: &Launch) -> Option<String> {
+    let package = match kind {
+        McpRuntimeKind::Node => first_positional(&launch.args, &["-p", "--package"]),
+        McpRuntimeKind::Python => {
+            // `uvx --from pkg cmd` 以 --from 指定的包为准
+            let from = launch
+                .args
+                .iter()
+                .position(|arg| arg == "--from")
+                .and_then(|index| launch.args.get(index + 1));
+            from.map(String::as_str).or_else(|| {
+                first_positional(&launch.args, &["--with", "--python", "-p", "--index-url"])
+            })
+        }
+        McpRuntimeKind::Docker => {
+            let rest = launch
+                .args
+                .split_first()
+                .filter(|(sub, _)| *sub == "run")?
+                .1;
+            first_positional(
+                rest,
+                &[
+                    "-e",
+                    "--env",
+                    "--env-file",
+                    "-v",
+                    "--volume",
+                    "--mount",
+                    "--name",
+                    "--network",
+                    "-p",
+                    "--publish",
+                    "-w",
+                    "--workdir",
+                    "-u",
+                    "--user",
+                    "--entrypoint",
+                ],
+            )
+        }
+        McpRuntimeKind::Other => None,
+    };
+    package.map(str::to_string)
+}
+
+/// 准备步骤：npm 包预先放入缓存，Docker 镜像预先拉取；uvx 首次启动时自行缓存
+fn prepare_args(
+    kind: McpRuntimeKind,
+    launch: &Launch,
+    package: &str,
+) -> Option<(String, Vec<String>)> {
+    match kind {
+        McpRuntimeKind::Node if launch.program.to_ascii_lowercase().starts_with("npx") => Some((
+            "npm".to_string(),
+            vec!["cache".into(), "add".into(), package.to_string()],
+        )),
+        McpRuntimeKind::Docker => Some((
+            launch.program.clone(),
+            vec!["pull".into(), package.to_string()],
+        )),
+        _ => None,
+    }
+}
+
+/// 构建子进程命令；Windows 上经 `cmd /C` 启动以支持 .cmd 脚本
+fn process(program: &str, args: &[String]) -> Command {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C")
+            .arg(program)
+            .args(args)
+            .creation_flags(CREATE_NO_WINDOW);
+        cmd
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let mut cmd = Command::new(program);
+        cmd.args(args);
+        cmd
+    }
+}
+
+/// 应用服务器配置中的 env 与 cwd
+fn apply_spec_env(cmd: &mut Command, spec: &Value) {
+    if let Some(env) = spec.get("env").and_then(Value::as_object) {
+        for (key, value) in env {
+            if let Some(value) = value.as_str() {
+                cmd.env(key, value);
+            }
+        }
+    }
+    if let Some(cwd) = spec
+        .get("cwd")
+        .and_then(Value::as_str)
+        .filter(|cwd| !cwd.trim().is_empty())
+    {
+        cmd.current_dir(cwd);
+    }
+}
+
+fn tail(lines: &[String], n: usize) -> String {
+    lines[lines.len().saturating_sub(n)..].join("\n")
+}
+
+/// 检测服务器依赖的运行时
+pub fn check(spec: &Value) -> Result<McpRuntimeCheck, AppError> {
+    let launch = launch_of(spec)?;
+    let kind = McpRuntimeKind::of(&launch.program);
+    let package = package_of(kind, &launch);
+    let prepare_command = package
+        .as_deref()
+        .and_then(|package| prepare_args(kind, &launch, package))
+        .map(|(program, args)| format!("{program} {}", args.join(" ")));
+    let runtime = env_checker::check_runtime(&launch.program);
+    Ok(McpRuntimeCheck {
+        install_url: (!runtime.available)
+            .then(|| kind.install_url().map(str::to_string))
+            .flatten(),
+        kind,
+        runtime,
+        package,
+        prepare_command,
+    })
+}
+
+/// 执行准备步骤，返回实际执行的命令；无需准备时返回 None
+pub fn prepare(spec: &Value) -> Result<Option<String>, AppError> {
+    let launch = launch_of(spec)?;
+    let kind = McpRuntimeKind::of(&launch.program);
+    let Some((program, args)) =
+        package_of(kind, &launch).and_then(|package| prepare_args(kind, &launch, &package))
+    else {
+        return Ok(None);
+    };
+    let display = format!("{program} {}", args.join(" "));
+
+    let mut cmd = process(&program, &args);
+    apply_spec_env(&mut cmd, spec);
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Message(format!("启动 {display} 失败: {e}")))?;
+
+    let stderr = child.stderr.take().map(|stderr| {
+        std::thread::spawn(move || {
+            BufReader::new(stderr)
+                .lines()
+                .map_while(Result::ok)
+                .collect::<Vec<_>>()
+        })
+    });
+    let deadline = Instant::now() + PREPARE_TIMEOUT;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AppError::Message(format!(
+                    "{display} 超时（{} 秒）",
+                    PREPARE_TIMEOUT.as_secs()
+                )));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(200)),
+            Err(e) => return Err(AppError::Message(format!("等待 {display} 失败: {e}"))),
+        }
+    };
+    if !status.success() {
+        let detail = stderr
+            .and_then(|handle| handle.join().ok())
+            .map(|lines| tail(&lines, 6))
+            .unwrap_or_default();
+        return Err(AppError::Message(format!("{display} 失败: {detail}")));
+    }
+    log::info!("[MCP] 运行时准备完成: {display}");
+    Ok(Some(display))
+}
+
+/// MCP `initialize` 请求
+fn initialize_request() -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2024-11-05",
+            "capabilities": {},
+            "clientInfo": { "name": "cc-switch", "version": env!("CARGO_PKG_VERSION") },
+        },
+    })
+}
+
+/// 解析 stdout 中的一行：不是 `initialize` 的响应时返回 None
+fn parse_initialize_response(
+    line: &str,
+) -> Option<Result<(Option<String>, Option<String>), String>> {
+    let message: Value = serde_json::from_str(line.trim()).ok()?;
+    if message.get("id").and_then(Value::as_i64) != Some(1) {
+        return None;
+    }
+    if let Some(error) = message.get("error") {
+        let text = error
+            .get("message")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string());
+        return Some(Err(text));
+    }
+    let info = message.get("result")?.get("serverInfo");
+    let field = |key: &str| {
+        info.and_then(|info| info.get(key))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    Some(Ok((field("name"), field("version"))))
+}
+
+/// 启动服务器并完成一次 `initialize` 握手，结束后关闭进程
+pub fn verify_start(spec: &Value, timeout: Duration) -> Result<McpStartResult, AppError> {
+    let launch = launch_of(spec)?;
+    let started = Instant::now();
+    let mut cmd = process(&launch.program, &launch.args);
+    apply_spec_env(&mut cmd, spec);
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Message(format!("启动 MCP 服务器失败: {e}")))?;
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    let stderr_lines = Arc::new(Mutex::new(Vec::<String>::new()));
+    let stderr_reader = child.stderr.take().map(|stderr| {
+        let sink = stderr_lines.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                sink.lock().unwrap_or_else(|e| e.into_inner()).push(line);
+            }
+        })
+    });
+
+    let handshake = child
+        .stdin
+        .as_mut()
+        .map(|stdin| writeln!(stdin, "{}", initialize_request()).and_then(|_| stdin.flush()));
+    let mut outcome = match handshake {
+        Some(Ok(())) => None,
+        Some(Err(e)) => Some(Err(format!("写入 initialize 请求失败: {e}"))),
+        None => Some(Err("无法写入服务器 stdin".to_string())),
+    };
+
+    let deadline = started + timeout;
+    while outcome.is_none() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            outcome = Some(Err(format!("{} 秒内未响应 initialize", timeout.as_secs())));
+            break;
+        }
+        match rx.recv_timeout(remaining) {
+            Ok(line) => outcome = parse_initialize_response(&line),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                // stdout 关闭通常意味着进程已退出，稍等片刻取退出码
+                std::thread::sleep(Duration::from_millis(100));
+                let status = child.try_wait().ok().flatten();
+                outcome = Some(Err(match status.and_then(|status| status.code()) {
+                    Some(code) => format!("服务器进程已退出（exit code: {code}）"),
+                    None => "服务器进程已退出".to_string(),
+                }));
+            }
+        }
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    let result = match outcome.unwrap_or_else(|| Err("未知错误".to_string())) {
+        Ok((server_name, server_version)) => McpStartResult {
+            ok: true,
+            server_name,
+            server_version,
+            error: None,
+            elapsed_ms,
+        },
+        Err(error) => {
+            // 给 stderr 读取线程一点时间收尾，子进程派生的孙进程可能仍占用管道
+            if let Some(reader) = stderr_reader {
+                let wait_until = Instant::now() + Duration::from_millis(500);
+                while !reader.is_finished() && Instant::now() < wait_until {
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+            }
+            let stderr = tail(&stderr_lines.lock().unwrap_or_else(|e| e.into_inner()), 6);
+            McpStartResult {
+                ok: false,
+                server_name: None,
+                server_version: None,
+                error: Some(if stderr.is_empty() {
+                    error
+                } else {
+                    format!("{error}\n{stderr}")
+                }),
+                elapsed_ms,
+            }
+        }
+    };
+    log::info!(
+        "[MCP] 启动校验 {} {}: ok={} ({}ms)",
+        launch.program,
+        launch.args.join(" "),
+        result.ok,
+        result.elapsed_ms
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch(spec: Value) -> Launch {
+        launch_of(&spec).unwrap()
+    }
+
+    #[test]
+    fn unwraps_windows_cmd_wrapper() {
+        let wrapped = launch(json!({
+            "command": "cmd",
+            "args": ["/c", "npx", "-y", "@modelcontextprotocol/server-time"],
+        }));
+        assert_eq!(wrapped.program, "npx");
+        assert_eq!(wrapped.args, ["-y", "@modelcontextprotocol/server-time"]);
+        assert!(launch_of(&json!({ "type": "http", "url": "https://x" })).is_err());
+    }
+
+    #[test]
+    fn detects_runtime_and_package() {
+        let npx =
+            launch(json!({ "command": "npx", "args": ["-y", "@scope/server", "--port", "1"] }));
+        assert_eq!(McpRuntimeKind::of(&npx.program), McpRuntimeKind::Node);
+        assert_eq!(
+            package_of(McpRuntimeKind::Node, &npx).as_deref(),
+            Some("@scope/server")
+        );
+
+        let uvx = launch(json!({ "command": "uvx", "args": ["--from", "mcp-pkg", "mcp-cmd"] }));
+        assert_eq!(
+            package_of(McpRuntimeKind::Python, &uvx).as_deref(),
+            Some("mcp-pkg")
+        );
+
+        let docker = launch(json!({
+            "command": "docker",
+            "args": ["run", "-i", "--rm", "-e", "TOKEN", "ghcr.io/github/github-mcp-server"],
+        }));
+        assert_eq!(
+            McpRuntimeKind::of("C:\\bin\\docker.exe"),
+            McpRuntimeKind::Docker
+        );
+        assert_eq!(
+            package_of(McpRuntimeKind::Docker, &docker).as_deref(),
+            Some("ghcr.io/github/github-mcp-server")
+        );
+        assert_eq!(
+            prepare_args(McpRuntimeKind::Docker, &docker, "img").map(|(_, args)| args),
+            Some(vec!["pull".to_string(), "img".to_string()])
+        );
+    }
+
+    #[test]
+    fn parses_initialize_response() {
+        let ok =
+            r#"{"jsonrpc":"2.0","id":1,"result":{"serverInfo":{"name":"time","version":"0.6.2"}}}"#;
+        assert_eq!(
+            parse_initialize_response(ok),
+            Some(Ok((Some("time".into()), Some("0.6.2".into()))))
+        );
+        let err = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"bad version"}}"#;
+        assert_eq!(
+            parse_initialize_response(err),
+            Some(Err("bad version".into()))
+        );
+        assert_eq!(parse_initialize_response("npm notice ..."), None);
+        assert_eq!(
+            parse_initialize_response(r#"{"jsonrpc":"2.0","method":"notifications/message"}"#),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn verify_start_completes_handshake() {
+        let reply = r#"{"jsonrpc":"2.0","id":1,"result":{"serverInfo":{"name":"demo"}}}"#;
+        let spec = json!({
+            "command": "sh",
+            "args": ["-c", format!("read line; echo '{reply}'; sleep 5")],
+        });
+        let result = verify_start(&spec, Duration::from_secs(5)).unwrap();
+        assert!(result.ok, "{:?}", result.error);
+        assert_eq!(result.server_name.as_deref(), Some("demo"));
+
+        let failing = json!({ "command": "sh", "args": ["-c", "echo boom >&2; exit 3"] });
+        let result = verify_start(&failing, Duration::from_secs(5)).unwrap();
+        assert!(!result.ok);
+        assert!(result.error.unwrap().contains("boom"));
+    }
+}
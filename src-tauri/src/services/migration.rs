@@ -0,0 +1,539 @@
+//! 整机迁移：把完整应用状态打包成单个归档，在新电脑上一次性恢复
+//!
+//! 归档是一个 zip，包含：
+//!
+//! - `manifest.json`：格式、版本、来源设备与各文件的 SHA256
+//! - `db.sql`：完整数据库导出（含供应商、MCP、提示词、代理配置与本机表）
+//! - `settings.json` / `settings.toml`：设备级设置与覆盖文件（存在时）
+//! - `skills.zip`：Skills SSOT 目录
+//! - `inventory.json`：已安装 Skills / MCP 与各应用供应商数量清单，便于导入前确认
+//!
+//! 提供口令时，`db.sql` 与 `settings.json` 这类含 API Key 的文件用与云同步相同的
+//! AES-256-GCM 方案加密（见 [`super::sync_backend::encrypt_blob`]），其余文件保持明文。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Cursor, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tempfile::tempdir;
+use zip::write::SimpleFileOptions;
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::services::provider::ProviderService;
+use crate::services::sync_backend::{decrypt_blob, encrypt_blob};
+use crate::services::sync_protocol::{
+    detect_system_device_name, io_context_localized, localized, sha256_hex, ArtifactMeta,
+    MAX_SYNC_ARTIFACT_BYTES,
+};
+use crate::services::webdav_sync::archive::{
+    backup_current_skills, restore_skills_from_backup, restore_skills_zip, zip_skills_ssot,
+};
+use crate::store::AppState;
+
+pub(crate) const MIGRATION_FORMAT: &str = "cc-switch-migration";
+pub(crate) const MIGRATION_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+const DB_SQL: &str = "db.sql";
+const SETTINGS_JSON: &str = "settings.json";
+const SETTINGS_TOML: &str = "settings.toml";
+const SKILLS_ZIP: &str = "skills.zip";
+const INVENTORY: &str = "inventory.json";
+
+/// 含密钥、需要随口令加密的文件
+const SECRET_ARTIFACTS: &[&str] = &[DB_SQL, SETTINGS_JSON, SETTINGS_TOML];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MigrationManifest {
+    pub format: String,
+    pub version: u32,
+    pub app_version: String,
+    pub schema_version: i32,
+    pub device_name: Option<String>,
+    pub created_at: String,
+    /// 含密钥的文件是否已加密
+    pub encrypted: bool,
+    pub artifacts: BTreeMap<String, ArtifactMeta>,
+}
+
+/// 已安装组件清单（仅用于展示，恢复以 `db.sql` 为准）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationInventory {
+    /// app_type -> 供应商数量
+    pub providers: BTreeMap<String, usize>,
+    pub skills: Vec<InventoryEntry>,
+    pub mcp_servers: Vec<InventoryEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// 导出 / 导入结果摘要
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationSummary {
+    pub path: String,
+    pub encrypted: bool,
+    pub device_name: Option<String>,
+    pub created_at: String,
+    pub files: Vec<String>,
+    pub inventory: MigrationInventory,
+    /// 导入前自动生成的数据库备份 ID（仅导入）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup_id: Option<String>,
+    /// 导入后同步 live 配置失败等非致命问题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+/// 解包后的归档内容（已校验、已解密）
+struct MigrationBundle {
+    manifest: MigrationManifest,
+    files: BTreeMap<String, Vec<u8>>,
+}
+
+impl MigrationBundle {
+    fn inventory(&self) -> MigrationInventory {
+        self.files
+            .get(INVENTORY)
+            .and_then(|raw| serde_json::from_slice(raw).ok())
+            .unwrap_or_default()
+    }
+
+    fn summary(&self, path: &Path) -> MigrationSummary {
+        MigrationSummary {
+            path: path.display().to_string(),
+            encrypted: self.manifest.encrypted,
+            device_name: self.manifest.device_name.clone(),
+            created_at: self.manifest.created_at.clone(),
+            files: self.manifest.artifacts.keys().cloned().collect(),
+            inventory: self.inventory(),
+            backup_id: None,
+            warning: None,
+        }
+    }
+}
+
+pub struct MigrationService;
+
+impl MigrationService {
+    /// 导出完整应用状态到 `path`；`passphrase` 非空时加密含密钥的文件
+    pub fn export(
+        db: &Database,
+        path: &Path,
+        passphrase: Option<&str>,
+    ) -> Result<MigrationSummary, AppError> {
+        let mut files = BTreeMap::new();
+        files.insert(DB_SQL.to_string(), db.export_sql_string()?.into_bytes());
+
+        for (name, source) in [
+            (SETTINGS_JSON, crate::settings::AppSettings::settings_path()),
+            (
+                SETTINGS_TOML,
+                Some(crate::settings_overrides::settings_toml_path()),
+            ),
+        ] {
+            let Some(source) = source.filter(|p| p.exists()) else {
+                continue;
+            };
+            files.insert(
+                name.to_string(),
+                fs::read(&source).map_err(|e| AppError::io(&source, e))?,
+            );
+        }
+
+        let tmp = tempdir().map_err(|e| {
+            io_context_localized(
+                "migration.tmpdir_failed",
+                "创建迁移临时目录失败",
+                "Failed to create temporary directory for migration",
+                e,
+            )
+        })?;
+        let skills_zip_path = tmp.path().join(SKILLS_ZIP);
+        zip_skills_ssot(&skills_zip_path)?;
+        files.insert(
+            SKILLS_ZIP.to_string(),
+            fs::read(&skills_zip_path).map_err(|e| AppError::io(&skills_zip_path, e))?,
+        );
+
+        let inventory = collect_inventory(db)?;
+        files.insert(
+            INVENTORY.to_string(),
+            serde_json::to_vec_pretty(&inventory)
+                .map_err(|e| AppError::JsonSerialize { source: e })?,
+        );
+
+        let bundle = MigrationBundle {
+            manifest: MigrationManifest {
+                format: MIGRATION_FORMAT.to_string(),
+                version: MIGRATION_VERSION,
+                app_version: env!("CARGO_PKG_VERSION").to_string(),
+                schema_version: crate::database::SCHEMA_VERSION,
+                device_name: detect_system_device_name(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+                encrypted: false,
+                artifacts: BTreeMap::new(),
+            },
+            files,
+        };
+        let (archive, bundle) = pack(bundle, passphrase)?;
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+        }
+        crate::config::atomic_write(path, &archive)?;
+        log::info!("[Migration] 已导出迁移归档 {}", path.display());
+        Ok(bundle.summary(path))
+    }
+
+    /// 只读取并校验归档，不修改本机状态
+    pub fn inspect(path: &Path, passphrase: Option<&str>) -> Result<MigrationSummary, AppError> {
+        let raw = fs::read(path).map_err(|e| AppError::io(path, e))?;
+        Ok(unpack(&raw, passphrase)?.summary(path))
+    }
+
+    /// 从归档恢复完整应用状态
+    ///
+    /// 先替换 Skills，再导入数据库（导入前自动备份当前库）；数据库导入失败时回滚 Skills。
+    /// 设置文件最后写入，写入后把当前供应商同步到各应用的 live 配置（失败仅作为警告）。
+    pub fn import(
+        db: &Arc<Database>,
+        path: &Path,
+        passphrase: Option<&str>,
+    ) -> Result<MigrationSummary, AppError> {
+        let raw = fs::read(path).map_err(|e| AppError::io(path, e))?;
+        let bundle = unpack(&raw, passphrase)?;
+        if bundle.manifest.schema_version > crate::database::SCHEMA_VERSION {
+            return Err(localized(
+                "migration.schema_too_new",
+                format!(
+                    "迁移归档来自更新版本的 CC Switch（数据库版本 {}，当前支持 {}），请先升级",
+                    bundle.manifest.schema_version,
+                    crate::database::SCHEMA_VERSION
+                ),
+                format!(
+                    "The migration archive comes from a newer CC Switch (schema {}, this build supports {}). Please upgrade first.",
+                    bundle.manifest.schema_version,
+                    crate::database::SCHEMA_VERSION
+                ),
+            ));
+        }
+
+        let sql = bundle
+            .files
+            .get(DB_SQL)
+            .ok_or_else(|| missing_artifact(DB_SQL))?;
+        let sql = std::str::from_utf8(sql).map_err(|e| {
+            localized(
+                "sync.sql_not_utf8",
+                format!("SQL 非 UTF-8: {e}"),
+                format!("SQL is not valid UTF-8: {e}"),
+            )
+        })?;
+
+        let skills_backup = backup_current_skills()?;
+        if let Some(skills_zip) = bundle.files.get(SKILLS_ZIP) {
+            restore_skills_zip(skills_zip)?;
+        }
+        let backup_id = match db.import_sql_string(sql) {
+            Ok(id) => id,
+            Err(db_err) => {
+                if let Err(rollback_err) = restore_skills_from_backup(&skills_backup) {
+                    return Err(localized(
+                        "sync.db_import_and_rollback_failed",
+                        format!("导入数据库失败: {db_err}; 同时回滚 Skills 失败: {rollback_err}"),
+                        format!(
+                            "Database import failed: {db_err}; skills rollback also failed: {rollback_err}"
+                        ),
+                    ));
+                }
+                return Err(db_err);
+            }
+        };
+
+        for (name, target) in [
+            (SETTINGS_JSON, crate::settings::AppSettings::settings_path()),
+            (
+                SETTINGS_TOML,
+                Some(crate::settings_overrides::settings_toml_path()),
+            ),
+        ] {
+            if let (Some(content), Some(target)) = (bundle.files.get(name), target) {
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
+                }
+                crate::config::atomic_write(&target, content)?;
+            }
+        }
+
+        let mut summary = bundle.summary(path);
+        summary.backup_id = (!backup_id.is_empty()).then_some(backup_id);
+        summary.warning = crate::settings::reload_settings()
+            .and_then(|()| ProviderService::sync_current_to_live(&AppState::new(db.clone())))
+            .err()
+            .map(|e| e.to_string());
+        log::info!("[Migration] 已从 {} 恢复应用状态", path.display());
+        Ok(summary)
+    }
+}
+
+fn collect_inventory(db: &Database) -> Result<MigrationInventory, AppError> {
+    let mut inventory = MigrationInventory::default();
+    for app in AppType::all() {
+        let count = db.get_all_providers(app.as_str())?.len();
+        if count > 0 {
+            inventory.providers.insert(app.as_str().to_string(), count);
+        }
+    }
+    inventory.skills = db
+        .get_all_installed_skills()?
+        .into_values()
+        .map(|skill| InventoryEntry {
+            id: skill.id,
+            name: skill.name,
+            source: match (skill.repo_owner, skill.repo_name) {
+                (Some(owner), Some(name)) => Some(format!("{owner}/{name}")),
+                _ => None,
+            },
+        })
+        .collect();
+    inventory.mcp_servers = db
+        .get_all_mcp_servers()?
+        .into_values()
+        .map(|server| InventoryEntry {
+            id: server.id,
+            name: server.name,
+            source: server.homepage,
+        })
+        .collect();
+    Ok(inventory)
+}
+
+fn missing_artifact(name: &str) -> AppError {
+    localized(
+        "migration.artifact_missing",
+        format!("迁移归档缺少文件: {name}"),
+        format!("Migration archive is missing {name}"),
+    )
+}
+
+/// 加密（可选）并写出 zip；返回归档字节与填好 artifacts 的 bundle
+fn pack(
+    mut bundle: MigrationBundle,
+    passphrase: Option<&str>,
+) -> Result<(Vec<u8>, MigrationBundle), AppError> {
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    bundle.manifest.encrypted = passphrase.is_some();
+
+    let mut stored = BTreeMap::new();
+    for (name, content) in &bundle.files {
+        let content = match passphrase {
+            Some(passphrase) if SECRET_ARTIFACTS.contains(&name.as_str()) => {
+                encrypt_blob(passphrase, content)?
+            }
+            _ => content.clone(),
+        };
+        bundle.manifest.artifacts.insert(
+            name.clone(),
+            ArtifactMeta {
+                sha256: sha256_hex(&content),
+                size: content.len() as u64,
+            },
+        );
+        stored.insert(name.clone(), content);
+    }
+
+    let manifest = serde_json::to_vec_pretty(&bundle.manifest)
+        .map_err(|e| AppError::JsonSerialize { source: e })?;
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in std::iter::once((MANIFEST.to_string(), manifest)).chain(stored) {
+        writer
+            .start_file(name.as_str(), options)
+            .map_err(zip_error)?;
+        writer
+            .write_all(&content)
+            .map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    let archive = writer.finish().map_err(zip_error)?.into_inner();
+    Ok((archive, bundle))
+}
+
+/// 读取 zip、校验 manifest 与哈希并解密
+fn unpack(raw: &[u8], passphrase: Option<&str>) -> Result<MigrationBundle, AppError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(raw)).map_err(|_| invalid_archive())?;
+    let mut read_entry = |name: &str| -> Result<Option<Vec<u8>>, AppError> {
+        let entry = match archive.by_name(name) {
+            Ok(entry) => entry,
+            Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+            Err(_) => return Err(invalid_archive()),
+        };
+        let mut buf = Vec::new();
+        entry
+            .take(MAX_SYNC_ARTIFACT_BYTES + 1)
+            .read_to_end(&mut buf)
+            .map_err(|e| AppError::Message(e.to_string()))?;
+        if buf.len() as u64 > MAX_SYNC_ARTIFACT_BYTES {
+            return Err(localized(
+                "migration.artifact_too_large",
+                format!("迁移归档中的 {name} 过大"),
+                format!("{name} in the migration archive is too large"),
+            ));
+        }
+        Ok(Some(buf))
+    };
+
+    let manifest: MigrationManifest = read_entry(MANIFEST)?
+        .and_then(|raw| serde_json::from_slice(&raw).ok())
+        .ok_or_else(invalid_archive)?;
+    if manifest.format != MIGRATION_FORMAT {
+        return Err(invalid_archive());
+    }
+    if manifest.version > MIGRATION_VERSION {
+        return Err(localized(
+            "migration.version_unsupported",
+            format!("不支持的迁移归档版本: {}", manifest.version),
+            format!(
+                "Unsupported migration archive version: {}",
+                manifest.version
+            ),
+        ));
+    }
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    if manifest.encrypted && passphrase.is_none() {
+        return Err(localized(
+            "migration.passphrase_required",
+            "迁移归档已加密，请提供导出时使用的口令",
+            "The migration archive is encrypted. Provide the passphrase used for export.",
+        ));
+    }
+
+    let mut files = BTreeMap::new();
+    for (name, meta) in &manifest.artifacts {
+        let content = read_entry(name)?.ok_or_else(|| missing_artifact(name))?;
+        if sha256_hex(&content) != meta.sha256 {
+            return Err(localized(
+                "migration.artifact_hash_mismatch",
+                format!("迁移归档中的 {name} 校验失败，文件可能已损坏"),
+                format!("{name} in the migration archive failed verification; the file may be corrupted"),
+            ));
+        }
+        let content = match passphrase {
+            Some(passphrase) if manifest.encrypted && SECRET_ARTIFACTS.contains(&name.as_str()) => {
+                decrypt_blob(passphrase, &content)?
+            }
+            _ => content,
+        };
+        files.insert(name.clone(), content);
+    }
+    if !files.contains_key(DB_SQL) {
+        return Err(missing_artifact(DB_SQL));
+    }
+    Ok(MigrationBundle { manifest, files })
+}
+
+fn invalid_archive() -> AppError {
+    localized(
+        "migration.invalid_archive",
+        "不是有效的 CC Switch 迁移归档",
+        "Not a valid CC Switch migration archive",
+    )
+}
+
+fn zip_error(e: zip::result::ZipError) -> AppError {
+    localized(
+        "migration.zip_failed",
+        format!("写入迁移归档失败: {e}"),
+        format!("Failed to write migration archive: {e}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_bundle() -> MigrationBundle {
+        let mut files = BTreeMap::new();
+        files.insert(
+            DB_SQL.to_string(),
+            b"-- CC Switch SQLite export\nINSERT INTO providers VALUES ('sk-secret');".to_vec(),
+        );
+        files.insert(SETTINGS_JSON.to_string(), br#"{"language":"en"}"#.to_vec());
+        files.insert(SKILLS_ZIP.to_string(), b"PK-not-really".to_vec());
+        MigrationBundle {
+            manifest: MigrationManifest {
+                format: MIGRATION_FORMAT.to_string(),
+                version: MIGRATION_VERSION,
+                app_version: "0.0.0".to_string(),
+                schema_version: crate::database::SCHEMA_VERSION,
+                device_name: Some("old-laptop".to_string()),
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                encrypted: false,
+                artifacts: BTreeMap::new(),
+            },
+            files,
+        }
+    }
+
+    #[test]
+    fn plain_archive_round_trips() -> Result<(), AppError> {
+        let (archive, _) = pack(sample_bundle(), None)?;
+        let bundle = unpack(&archive, None)?;
+        assert!(!bundle.manifest.encrypted);
+        assert_eq!(bundle.manifest.device_name.as_deref(), Some("old-laptop"));
+        assert_eq!(bundle.files, sample_bundle().files);
+        Ok(())
+    }
+
+    #[test]
+    fn encrypted_archive_hides_keys_and_needs_passphrase() -> Result<(), AppError> {
+        let (archive, _) = pack(sample_bundle(), Some("correct horse"))?;
+        assert!(!archive
+            .windows(b"sk-secret".len())
+            .any(|w| w == b"sk-secret"));
+
+        assert!(unpack(&archive, None).is_err());
+        assert!(unpack(&archive, Some("wrong")).is_err());
+        let bundle = unpack(&archive, Some("correct horse"))?;
+        assert!(bundle.manifest.encrypted);
+        assert_eq!(bundle.files, sample_bundle().files);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_tampered_and_foreign_archives() -> Result<(), AppError> {
+        let (_, mut bundle) = pack(sample_bundle(), None)?;
+        bundle.manifest.artifacts.get_mut(DB_SQL).unwrap().sha256 = "0".repeat(64);
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        writer.start_file(MANIFEST, options).map_err(zip_error)?;
+        writer
+            .write_all(&serde_json::to_vec(&bundle.manifest).unwrap())
+            .unwrap();
+        for (name, content) in &bundle.files {
+            writer
+                .start_file(name.as_str(), options)
+                .map_err(zip_error)?;
+            writer.write_all(content).unwrap();
+        }
+        let tampered = writer.finish().map_err(zip_error)?.into_inner();
+        assert!(unpack(&tampered, None).is_err());
+
+        assert!(unpack(b"not a zip", None).is_err());
+        Ok(())
+    }
+}
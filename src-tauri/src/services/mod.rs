@@ -1,19 +1,47 @@
+pub mod app_detection;
 pub mod balance;
+pub mod capability_probe;
 pub mod codex_oauth_models;
 pub mod coding_plan;
 pub mod config;
+pub mod connection_diag;
+pub mod copilot_provisioning;
+pub mod cost_simulation;
+pub mod curl_export;
+pub mod endpoint_pool;
 pub mod env_checker;
 pub mod env_manager;
+pub mod failback_watchdog;
+pub mod failover_queue;
+pub mod favicon;
+pub mod latency_slo;
+pub mod live_reload;
+pub mod maintenance;
 pub mod mcp;
+pub mod mcp_runtime;
+pub mod migration;
+pub mod model_alias;
 pub mod model_fetch;
+pub mod network_watch;
+pub mod notifier;
 pub mod omo;
 pub mod profile;
 pub mod prompt;
+pub mod prompt_integrity;
 pub mod provider;
+pub mod provider_activity;
+pub mod provider_budget;
+pub mod provider_notice;
+pub mod provider_trust;
 pub mod proxy;
 pub mod s3;
 pub mod s3_auto_sync;
 pub mod s3_sync;
+pub mod scenario;
+pub mod secrets;
+pub mod secrets_export;
+pub mod selective_restore;
+pub mod self_test;
 pub mod session_usage;
 pub mod session_usage_codex;
 pub mod session_usage_gemini;
@@ -22,12 +50,19 @@ pub mod session_usage_opencode;
 pub mod skill;
 pub mod speedtest;
 pub mod sql_helpers;
+pub mod status_file;
 pub mod stream_check;
 pub mod subscription;
 pub mod subscription_grok;
+pub mod switch_hooks;
+pub mod sync_backend;
 pub mod sync_protocol;
+pub mod telemetry;
+pub mod test_message;
+pub mod tunnel;
 pub mod usage_cache;
 pub mod usage_stats;
+pub mod usage_sync;
 pub mod webdav;
 pub mod webdav_auto_sync;
 pub mod webdav_sync;
@@ -36,11 +71,11 @@ pub use config::ConfigService;
 pub use mcp::McpService;
 pub use omo::OmoService;
 pub use prompt::PromptService;
-pub use provider::{ProviderService, ProviderSortUpdate, SwitchResult};
+pub use provider::{ConfigOverride, ProviderService, ProviderSortUpdate, SwitchResult};
 pub use proxy::ProxyService;
 #[allow(unused_imports)]
 pub use skill::{DiscoverableSkill, Skill, SkillRepo, SkillService};
-pub use speedtest::{EndpointLatency, SpeedtestService};
+pub use speedtest::{EndpointLatency, ProviderSpeedtestOptions, SpeedtestService};
 pub use usage_cache::UsageCache;
 #[allow(unused_imports)]
 pub use usage_stats::{
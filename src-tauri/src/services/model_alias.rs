@@ -0,0 +1,328 @@
+//! Model alias registry
+//!
+//! 用户在设置中维护一张别名表（如 `fast → gemini-2.0-flash`、`main → claude-sonnet-4.5`），
+//! 供应商配置、命令行参数与代理请求里都可以写别名。别名在模型名离开 cc-switch 时解析：
+//! 写入 Live 配置、代理转发、用量查价；回填 Live 时再换回别名。更换首选模型只需改别名表。
+//!
+//! 别名只解析一层，目标不能是另一个别名；Claude 的 `[1m]` 后缀会保留在解析结果上。
+
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::codex_config_model::CodexConfig;
+use crate::error::AppError;
+use crate::i18n;
+use crate::proxy::model_mapper::strip_one_m_suffix_for_upstream;
+use crate::services::ProviderService;
+pub use crate::settings::ModelAlias;
+use crate::store::AppState;
+
+/// 供应商配置中保存模型名的 env 字段
+const CLAUDE_MODEL_ENV_KEYS: &[&str] = &[
+    "ANTHROPIC_MODEL",
+    "ANTHROPIC_DEFAULT_HAIKU_MODEL",
+    "ANTHROPIC_DEFAULT_SONNET_MODEL",
+    "ANTHROPIC_DEFAULT_OPUS_MODEL",
+    "ANTHROPIC_DEFAULT_FABLE_MODEL",
+    "CLAUDE_CODE_SUBAGENT_MODEL",
+];
+const GEMINI_MODEL_ENV_KEYS: &[&str] = &["GEMINI_MODEL"];
+
+/// 按别名表解析；不是别名时返回 `None`
+pub fn resolve_with(aliases: &[ModelAlias], name: &str) -> Option<String> {
+    let name = name.trim();
+    let base = strip_one_m_suffix_for_upstream(name);
+    let suffix = name[base.len()..].trim();
+    aliases
+        .iter()
+        .find(|entry| entry.alias.trim().eq_ignore_ascii_case(base))
+        .map(|entry| format!("{}{suffix}", entry.model.trim()))
+}
+
+/// 按当前设置解析别名；不是别名时原样返回
+pub fn resolve(name: &str) -> String {
+    let aliases = crate::settings::model_aliases();
+    if aliases.is_empty() {
+        return name.to_string();
+    }
+    resolve_with(&aliases, name).unwrap_or_else(|| name.to_string())
+}
+
+pub fn validate(aliases: &[ModelAlias]) -> Result<(), AppError> {
+    for (index, entry) in aliases.iter().enumerate() {
+        let alias = entry.alias.trim();
+        let model = entry.model.trim();
+        if alias.is_empty() || model.is_empty() || alias.chars().any(char::is_whitespace) {
+            return Err(i18n::error(
+                "modelAlias.invalid",
+                &[("alias", alias), ("model", model)],
+            ));
+        }
+        if aliases[..index]
+            .iter()
+            .any(|other| other.alias.trim().eq_ignore_ascii_case(alias))
+        {
+            return Err(i18n::error("modelAlias.duplicate", &[("alias", alias)]));
+        }
+        let target = strip_one_m_suffix_for_upstream(model);
+        if aliases
+            .iter()
+            .any(|other| other.alias.trim().eq_ignore_ascii_case(target))
+        {
+            return Err(i18n::error(
+                "modelAlias.chain",
+                &[("alias", alias), ("model", model)],
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// 读取配置中的模型字段：(字段名, 值)
+fn model_fields(app_type: &AppType, settings: &Value) -> Vec<(String, String)> {
+    match app_type {
+        AppType::Claude | AppType::Gemini => {
+            let keys = if matches!(app_type, AppType::Claude) {
+                CLAUDE_MODEL_ENV_KEYS
+            } else {
+                GEMINI_MODEL_ENV_KEYS
+            };
+            let Some(env) = settings.get("env").and_then(Value::as_object) else {
+                return Vec::new();
+            };
+            keys.iter()
+                .filter_map(|key| {
+                    let value = env.get(*key)?.as_str()?;
+                    Some((key.to_string(), value.to_string()))
+                })
+                .collect()
+        }
+        AppType::Codex => settings
+            .get("config")
+            .and_then(Value::as_str)
+            .and_then(|text| CodexConfig::parse(text).ok())
+            .and_then(|config| config.model().map(str::to_string))
+            .map(|model| vec![("model".to_string(), model)])
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// 对每个模型字段调用 `rewrite(字段名, 当前值)`，返回 `Some` 时写回
+fn rewrite_model_fields(
+    app_type: &AppType,
+    settings: &mut Value,
+    mut rewrite: impl FnMut(&str, &str) -> Option<String>,
+) {
+    match app_type {
+        AppType::Claude | AppType::Gemini => {
+            let keys = if matches!(app_type, AppType::Claude) {
+                CLAUDE_MODEL_ENV_KEYS
+            } else {
+                GEMINI_MODEL_ENV_KEYS
+            };
+            let Some(env) = settings.get_mut("env").and_then(Value::as_object_mut) else {
+                return;
+            };
+            for key in keys {
+                let Some(current) = env.get(*key).and_then(Value::as_str) else {
+                    continue;
+                };
+                if let Some(next) = rewrite(key, current) {
+                    env.insert(key.to_string(), Value::String(next));
+                }
+            }
+        }
+        AppType::Codex => {
+            let Some(text) = settings.get("config").and_then(Value::as_str) else {
+                return;
+            };
+            let Ok(mut config) = CodexConfig::parse(text) else {
+                return;
+            };
+            let Some(current) = config.model() else {
+                return;
+            };
+            if let Some(next) = rewrite("model", current) {
+                config.set_model(Some(next.as_str()));
+                settings["config"] = Value::String(config.to_string());
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve_settings_with(aliases: &[ModelAlias], app_type: &AppType, settings: &mut Value) {
+    if aliases.is_empty() {
+        return;
+    }
+    rewrite_model_fields(app_type, settings, |_, current| {
+        resolve_with(aliases, current)
+    });
+}
+
+fn restore_with(aliases: &[ModelAlias], app_type: &AppType, original: &Value, updated: &mut Value) {
+    let stored: Vec<(String, String)> = model_fields(app_type, original)
+        .into_iter()
+        .filter(|(_, value)| resolve_with(aliases, value).is_some())
+        .collect();
+    if stored.is_empty() {
+        return;
+    }
+    rewrite_model_fields(app_type, updated, |key, current| {
+        stored
+            .iter()
+            .find(|(stored_key, alias)| {
+                stored_key == key && resolve_with(aliases, alias).as_deref() == Some(current)
+            })
+            .map(|(_, alias)| alias.clone())
+    });
+}
+
+/// 写入 Live 前把模型字段中的别名解析为真实模型名
+pub fn resolve_settings_models(app_type: &AppType, settings: &mut Value) {
+    resolve_settings_with(&crate::settings::model_aliases(), app_type, settings);
+}
+
+/// 回填 Live 时，值仍等于别名解析结果的字段换回别名
+pub fn restore_aliases_for_backfill(app_type: &AppType, original: &Value, updated: &mut Value) {
+    let aliases = crate::settings::model_aliases();
+    if !aliases.is_empty() {
+        restore_with(&aliases, app_type, original, updated);
+    }
+}
+
+/// 别名表变更后重写 Claude / Codex / Gemini 当前供应商的 Live 配置，
+/// 使用别名的模型字段立即指向新模型（代理接管期间只更新恢复备份）
+pub fn resync_live(state: &AppState) {
+    let _write_lock = match crate::instance_lock::acquire("model_alias.resync") {
+        Ok(guard) => guard,
+        Err(err) => {
+            log::warn!("模型别名变更后重写 live 配置失败: {err}");
+            return;
+        }
+    };
+    for app_type in [AppType::Claude, AppType::Codex, AppType::Gemini] {
+        if let Err(err) = ProviderService::sync_current_provider_for_app(state, app_type.clone()) {
+            log::warn!(
+                "模型别名变更后重写 {} live 配置失败: {err}",
+                app_type.as_str()
+            );
+        }
+    }
+}
+
+/// 校验并保存别名表，随后重写 Live 配置
+pub fn save(state: &AppState, aliases: Vec<ModelAlias>) -> Result<(), AppError> {
+    validate(&aliases)?;
+    // 保存与重写在同一把写锁内，其他实例不会在中途看到新别名表配旧 Live 配置
+    let _write_lock = crate::instance_lock::acquire("model_alias.save")?;
+    crate::settings::set_model_aliases(aliases)?;
+    resync_live(state);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn aliases() -> Vec<ModelAlias> {
+        vec![
+            ModelAlias {
+                alias: "main".to_string(),
+                model: "claude-sonnet-4.5".to_string(),
+            },
+            ModelAlias {
+                alias: "fast".to_string(),
+                model: "gemini-2.0-flash".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn resolves_aliases_and_keeps_one_m_suffix() {
+        let aliases = aliases();
+        assert_eq!(
+            resolve_with(&aliases, "Main").as_deref(),
+            Some("claude-sonnet-4.5")
+        );
+        assert_eq!(
+            resolve_with(&aliases, "main[1m]").as_deref(),
+            Some("claude-sonnet-4.5[1m]")
+        );
+        assert_eq!(resolve_with(&aliases, "claude-opus-4"), None);
+    }
+
+    #[test]
+    fn rejects_duplicates_chains_and_blank_entries() {
+        let mut list = aliases();
+        assert!(validate(&list).is_ok());
+
+        list.push(ModelAlias {
+            alias: "MAIN".to_string(),
+            model: "x".to_string(),
+        });
+        assert!(validate(&list).is_err());
+
+        let chain = vec![
+            aliases()[0].clone(),
+            ModelAlias {
+                alias: "best".to_string(),
+                model: "main".to_string(),
+            },
+        ];
+        assert!(validate(&chain).is_err());
+
+        let blank = vec![ModelAlias {
+            alias: "my fast".to_string(),
+            model: "gemini-2.0-flash".to_string(),
+        }];
+        assert!(validate(&blank).is_err());
+    }
+
+    #[test]
+    fn resolves_for_live_and_restores_on_backfill() {
+        let aliases = aliases();
+        let stored = json!({"env": {
+            "ANTHROPIC_MODEL": "main",
+            "ANTHROPIC_DEFAULT_HAIKU_MODEL": "claude-haiku-4.5",
+            "ANTHROPIC_DEFAULT_SONNET_MODEL": "main[1m]",
+        }});
+        let mut live = stored.clone();
+        resolve_settings_with(&aliases, &AppType::Claude, &mut live);
+        assert_eq!(live["env"]["ANTHROPIC_MODEL"], "claude-sonnet-4.5");
+        assert_eq!(
+            live["env"]["ANTHROPIC_DEFAULT_SONNET_MODEL"],
+            "claude-sonnet-4.5[1m]"
+        );
+
+        restore_with(&aliases, &AppType::Claude, &stored, &mut live);
+        assert_eq!(live, stored);
+
+        // 用户在 Live 中改成了别的模型：保留新值
+        let mut edited = json!({"env": {"ANTHROPIC_MODEL": "claude-opus-4"}});
+        restore_with(&aliases, &AppType::Claude, &stored, &mut edited);
+        assert_eq!(edited["env"]["ANTHROPIC_MODEL"], "claude-opus-4");
+    }
+
+    #[test]
+    fn resolves_codex_toml_model() {
+        let aliases = vec![ModelAlias {
+            alias: "main".to_string(),
+            model: "gpt-5".to_string(),
+        }];
+        let stored = json!({
+            "auth": {},
+            "config": "model = \"main\"\nmodel_provider = \"relay\"\n",
+        });
+        let mut live = stored.clone();
+        resolve_settings_with(&aliases, &AppType::Codex, &mut live);
+        assert_eq!(
+            live["config"],
+            "model = \"gpt-5\"\nmodel_provider = \"relay\"\n"
+        );
+        restore_with(&aliases, &AppType::Codex, &stored, &mut live);
+        assert_eq!(live, stored);
+    }
+}
@@ -0,0 +1,294 @@
+//! 网络变化后的批量重新检查
+//!
+//! 开关 VPN、切换 Wi-Fi 后，原本可达的中转经常变得不可达（反之亦然）。
+//! 这里定期采集网络指纹（出口本机地址 + Wi-Fi SSID），变化稳定后对各应用的
+//! 当前供应商与故障转移队列重新做连通性检查：
+//!
+//! - 检查复用 [`SelfTestService::check_proxy_targets`]，结果写入 `stream_check_logs`；
+//! - 完成后发出 `network-recheck` 事件，由前端弹出摘要；
+//! - `cc-switch network-changed` 在配置目录写入触发文件，运行中的 GUI 下次轮询时领取，
+//!   用于系统无法感知的场景（例如只切换了代理软件的出口节点）。
+
+use std::net::UdpSocket;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::get_app_config_dir;
+use crate::error::AppError;
+use crate::services::self_test::SelfTestAppReport;
+
+/// 前端监听的事件名
+pub const EVENT_NETWORK_RECHECK: &str = "network-recheck";
+
+/// 轮询间隔
+pub const NETWORK_WATCH_TICK_SECS: u64 = 15;
+
+/// 两次自动检查的最小间隔，避免网络抖动时反复探测
+const RECHECK_COOLDOWN_SECS: i64 = 60;
+
+/// 手动触发文件超过该时长视为过期（GUI 未运行时写入的请求不再补执行）
+const TRIGGER_MAX_AGE_SECS: i64 = 120;
+
+const TRIGGER_FILE: &str = "network-changed";
+
+/// 网络指纹
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkFingerprint {
+    /// 访问公网时系统选用的本机地址，随 VPN / 网卡切换变化
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_addr: Option<String>,
+    /// 当前连接的 Wi-Fi 名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssid: Option<String>,
+}
+
+impl NetworkFingerprint {
+    /// 采集当前网络指纹（会启动子进程查询 SSID，需在阻塞线程中调用）
+    pub fn capture() -> Self {
+        Self {
+            local_addr: default_route_addr(),
+            ssid: current_ssid(),
+        }
+    }
+
+    /// 离线时什么都取不到；断网不算"网络变化"，等重新联网后再比较
+    fn is_offline(&self) -> bool {
+        self.local_addr.is_none() && self.ssid.is_none()
+    }
+}
+
+/// 触发原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NetworkChangeReason {
+    /// 出口地址变化（VPN 开关、切换网卡）
+    Interface,
+    /// Wi-Fi 网络变化
+    Ssid,
+    /// `cc-switch network-changed` 手动触发
+    Manual,
+}
+
+/// 一次重新检查的结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkRecheckReport {
+    pub reason: NetworkChangeReason,
+    pub fingerprint: NetworkFingerprint,
+    pub checked_at: i64,
+    pub apps: Vec<SelfTestAppReport>,
+}
+
+/// 轮询状态：指纹变化后要求下一次采样保持一致才触发，VPN 连接过程中的
+/// 中间状态只会触发一次检查
+#[derive(Debug, Default)]
+pub struct NetworkWatcher {
+    last: Option<NetworkFingerprint>,
+    pending: Option<NetworkChangeReason>,
+    last_recheck_at: Option<i64>,
+}
+
+impl NetworkWatcher {
+    /// 喂入一次采样，返回是否应立即重新检查
+    pub fn poll(
+        &mut self,
+        fingerprint: NetworkFingerprint,
+        manual: bool,
+        now: i64,
+    ) -> Option<NetworkChangeReason> {
+        if manual {
+            self.pending = None;
+            self.last_recheck_at = Some(now);
+            if !fingerprint.is_offline() {
+                self.last = Some(fingerprint);
+            }
+            return Some(NetworkChangeReason::Manual);
+        }
+        if fingerprint.is_offline() {
+            return None;
+        }
+        let Some(last) = self.last.as_ref() else {
+            // 首次采样只作为基线
+            self.last = Some(fingerprint);
+            return None;
+        };
+        if *last != fingerprint {
+            let reason = if last.local_addr != fingerprint.local_addr {
+                NetworkChangeReason::Interface
+            } else {
+                NetworkChangeReason::Ssid
+            };
+            // 出口地址变化优先于 SSID 变化
+            if self.pending != Some(NetworkChangeReason::Interface) {
+                self.pending = Some(reason);
+            }
+            self.last = Some(fingerprint);
+            return None;
+        }
+        let cooling_down = self
+            .last_recheck_at
+            .is_some_and(|at| now - at < RECHECK_COOLDOWN_SECS);
+        if cooling_down {
+            return None;
+        }
+        let reason = self.pending.take()?;
+        self.last_recheck_at = Some(now);
+        Some(reason)
+    }
+}
+
+/// UDP `connect` 不发送数据，只让系统按路由表选出出口地址
+fn default_route_addr() -> Option<String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("1.1.1.1:53").ok()?;
+    let addr = socket.local_addr().ok()?.ip();
+    (!addr.is_unspecified()).then(|| addr.to_string())
+}
+
+fn current_ssid() -> Option<String> {
+    #[cfg(target_os = "macos")]
+    let output = std::process::Command::new("/usr/sbin/networksetup")
+        .args(["-getairportnetwork", "en0"])
+        .output();
+    #[cfg(target_os = "linux")]
+    let output = std::process::Command::new("nmcli")
+        .args(["-t", "-f", "active,ssid", "dev", "wifi"])
+        .output();
+    #[cfg(target_os = "windows")]
+    let output = {
+        use std::os::windows::process::CommandExt;
+        std::process::Command::new("netsh")
+            .args(["wlan", "show", "interfaces"])
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output()
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+    let output: std::io::Result<std::process::Output> = Err(std::io::ErrorKind::Unsupported.into());
+
+    let output = output.ok().filter(|output| output.status.success())?;
+    parse_ssid(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// 兼容三种系统命令的输出：
+///
+/// - macOS `networksetup`：`Current Wi-Fi Network: Office`
+/// - Linux `nmcli -t`：`yes:Office`
+/// - Windows `netsh`：`    SSID                   : Office`
+fn parse_ssid(output: &str) -> Option<String> {
+    output.lines().find_map(|line| {
+        let line = line.trim();
+        let ssid = if let Some(rest) = line.strip_prefix("yes:") {
+            rest
+        } else if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim();
+            if key != "SSID" && key != "Current Wi-Fi Network" && key != "Current AirPort Network" {
+                return None;
+            }
+            value
+        } else {
+            return None;
+        };
+        let ssid = ssid.trim();
+        (!ssid.is_empty()).then(|| ssid.to_string())
+    })
+}
+
+fn trigger_path() -> PathBuf {
+    get_app_config_dir().join(TRIGGER_FILE)
+}
+
+/// 请求运行中的 GUI 重新检查（写入触发文件，内容为请求时间戳）
+pub fn request_recheck() -> Result<PathBuf, AppError> {
+    let path = trigger_path();
+    crate::config::write_text_file(&path, &chrono::Utc::now().timestamp().to_string())?;
+    Ok(path)
+}
+
+/// 领取手动触发请求；过期请求直接丢弃
+pub fn take_manual_request(now: i64) -> bool {
+    let path = trigger_path();
+    let Ok(text) = std::fs::read_to_string(&path) else {
+        return false;
+    };
+    if let Err(e) = std::fs::remove_file(&path) {
+        log::warn!("[Network] 删除触发文件失败 {}: {e}", path.display());
+    }
+    text.trim()
+        .parse::<i64>()
+        .is_ok_and(|requested_at| now - requested_at <= TRIGGER_MAX_AGE_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(addr: &str, ssid: Option<&str>) -> NetworkFingerprint {
+        NetworkFingerprint {
+            local_addr: Some(addr.to_string()),
+            ssid: ssid.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn parses_ssid_from_each_platform_tool() {
+        assert_eq!(
+            parse_ssid("Current Wi-Fi Network: Office 5G\n").as_deref(),
+            Some("Office 5G")
+        );
+        assert_eq!(
+            parse_ssid("no:Neighbor\nyes:Home\n").as_deref(),
+            Some("Home")
+        );
+        let netsh = "    Name                   : Wi-Fi\n    \
+                     BSSID                  : aa:bb:cc:dd:ee:ff\n    \
+                     SSID                   : Cafe\n";
+        assert_eq!(parse_ssid(netsh).as_deref(), Some("Cafe"));
+        assert_eq!(
+            parse_ssid("You are not associated with an AirPort network."),
+            None
+        );
+    }
+
+    #[test]
+    fn rechecks_once_after_the_network_settles() {
+        let mut watcher = NetworkWatcher::default();
+        let home = fingerprint("192.168.1.5", Some("Home"));
+        let vpn = fingerprint("10.8.0.2", Some("Home"));
+
+        assert_eq!(watcher.poll(home.clone(), false, 0), None);
+        assert_eq!(watcher.poll(home, false, 15), None);
+        // VPN 连接中：出口地址变化，等下一次采样确认
+        assert_eq!(watcher.poll(vpn.clone(), false, 30), None);
+        // 断网的中间状态不影响判断
+        assert_eq!(watcher.poll(NetworkFingerprint::default(), false, 45), None);
+        assert_eq!(
+            watcher.poll(vpn.clone(), false, 60),
+            Some(NetworkChangeReason::Interface)
+        );
+        assert_eq!(watcher.poll(vpn, false, 75), None);
+    }
+
+    #[test]
+    fn cooldown_delays_automatic_but_not_manual_rechecks() {
+        let mut watcher = NetworkWatcher::default();
+        let office = fingerprint("10.0.0.7", Some("Office"));
+        let guest = fingerprint("10.0.0.7", Some("Guest"));
+
+        assert_eq!(
+            watcher.poll(office.clone(), true, 0),
+            Some(NetworkChangeReason::Manual)
+        );
+        assert_eq!(watcher.poll(guest.clone(), false, 15), None);
+        assert_eq!(watcher.poll(guest.clone(), false, 30), None);
+        assert_eq!(
+            watcher.poll(guest.clone(), false, 60),
+            Some(NetworkChangeReason::Ssid)
+        );
+        assert_eq!(
+            watcher.poll(guest, true, 65),
+            Some(NetworkChangeReason::Manual)
+        );
+    }
+}
@@ -0,0 +1,550 @@
+//! 外部通知渠道
+//!
+//! 预算提醒、供应商熔断、自动同步失败除了在界面内提示，还可以推送到用户在
+//! `settings.json` 中登记的渠道：Bark、Telegram Bot、ntfy、SMTP 邮件和通用 webhook。
+//!
+//! - 每个渠道按事件类型订阅，未勾选任何事件时接收全部事件；
+//! - 推送在后台任务中进行，失败只记录日志，绝不影响触发通知的业务流程；
+//! - 同一对象的同类事件在冷却期内只推送一次，避免熔断反复开合时刷屏。
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use base64::Engine;
+use serde::Serialize;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::error::AppError;
+use crate::settings::{
+    NotificationChannel, NotificationChannelKind, NotificationEvent, SmtpSecurity,
+};
+
+const DEFAULT_BARK_SERVER: &str = "https://api.day.app";
+const DEFAULT_NTFY_SERVER: &str = "https://ntfy.sh";
+
+/// 单个渠道的发送超时
+const SEND_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// 同一事件键的推送冷却时间
+const DEDUP_COOLDOWN: Duration = Duration::from_secs(10 * 60);
+
+/// 一条通知
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub title: String,
+    pub body: String,
+    pub timestamp: i64,
+    /// 去重键（例如 `claude:provider-id`），为空时不去重
+    #[serde(skip)]
+    dedup_key: Option<String>,
+}
+
+impl Notification {
+    pub fn new(
+        event: NotificationEvent,
+        title: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Self {
+        Self {
+            event,
+            title: title.into(),
+            body: body.into(),
+            timestamp: chrono::Utc::now().timestamp(),
+            dedup_key: None,
+        }
+    }
+
+    pub fn with_dedup_key(mut self, key: impl Into<String>) -> Self {
+        self.dedup_key = Some(key.into());
+        self
+    }
+}
+
+/// 按界面语言选择通知文案（未设置语言时与界面默认一致使用中文）
+pub fn localized_text(zh: impl Into<String>, en: impl Into<String>) -> String {
+    let zh_ui = crate::settings::get_settings()
+        .language
+        .as_deref()
+        .is_none_or(|language| language.starts_with("zh"));
+    if zh_ui {
+        zh.into()
+    } else {
+        en.into()
+    }
+}
+
+/// (事件, 去重键) -> 上次推送时间
+static LAST_SENT: LazyLock<Mutex<HashMap<(NotificationEvent, String), Instant>>> =
+    LazyLock::new(Default::default);
+
+fn channel_matches(channel: &NotificationChannel, event: NotificationEvent) -> bool {
+    channel.enabled && (channel.events.is_empty() || channel.events.contains(&event))
+}
+
+fn should_send(notification: &Notification, now: Instant) -> bool {
+    let Some(key) = &notification.dedup_key else {
+        return true;
+    };
+    let mut last_sent = LAST_SENT.lock().unwrap_or_else(|e| e.into_inner());
+    let key = (notification.event, key.clone());
+    match last_sent.get(&key) {
+        Some(at) if now.duration_since(*at) < DEDUP_COOLDOWN => false,
+        _ => {
+            last_sent.insert(key, now);
+            true
+        }
+    }
+}
+
+pub struct NotifierService;
+
+impl NotifierService {
+    /// 推送到订阅了该事件的全部渠道（后台执行，立即返回）
+    pub fn dispatch(notification: Notification) {
+        let channels: Vec<NotificationChannel> = crate::settings::notification_channels()
+            .into_iter()
+            .filter(|channel| channel_matches(channel, notification.event))
+            .collect();
+        if channels.is_empty() || !should_send(&notification, Instant::now()) {
+            return;
+        }
+
+        tauri::async_runtime::spawn(async move {
+            for channel in &channels {
+                match Self::send(channel, &notification).await {
+                    Ok(()) => log::debug!("[Notifier] 已推送到渠道 {}", channel.id),
+                    Err(e) => log::warn!("[Notifier] 推送到渠道 {} 失败: {e}", channel.id),
+                }
+            }
+        });
+    }
+
+    /// 向指定渠道发送一条测试通知（设置页"测试"按钮）
+    pub async fn send_test(channel: &NotificationChannel) -> Result<(), AppError> {
+        let notification = Notification::new(
+            channel
+                .events
+                .first()
+                .copied()
+                .unwrap_or(NotificationEvent::ProviderDown),
+            "CC Switch",
+            "This is a test notification from CC Switch.",
+        );
+        Self::send(channel, &notification).await
+    }
+
+    async fn send(
+        channel: &NotificationChannel,
+        notification: &Notification,
+    ) -> Result<(), AppError> {
+        if crate::settings::offline_mode() {
+            return Err(AppError::Message(
+                "Offline mode is enabled; notifications are not sent".to_string(),
+            ));
+        }
+        match tokio::time::timeout(SEND_TIMEOUT, send_to_channel(&channel.kind, notification)).await
+        {
+            Ok(result) => result,
+            Err(_) => Err(AppError::Message(format!(
+                "通知发送超时（{} 秒）",
+                SEND_TIMEOUT.as_secs()
+            ))),
+        }
+    }
+}
+
+async fn send_to_channel(
+    kind: &NotificationChannelKind,
+    notification: &Notification,
+) -> Result<(), AppError> {
+    let client =
+        || crate::proxy::http_client::get_auxiliary("notification").map_err(AppError::Message);
+    let request = match kind {
+        NotificationChannelKind::Bark {
+            server_url,
+            device_key,
+        } => {
+            let server = server_url.as_deref().unwrap_or(DEFAULT_BARK_SERVER);
+            client()?
+                .post(format!("{}/push", server.trim_end_matches('/')))
+                .json(&serde_json::json!({
+                    "device_key": device_key,
+                    "title": notification.title,
+                    "body": notification.body,
+                    "group": "CC Switch",
+                }))
+        }
+        NotificationChannelKind::Telegram { bot_token, chat_id } => client()?
+            .post(format!(
+                "https://api.telegram.org/bot{bot_token}/sendMessage"
+            ))
+            .json(&serde_json::json!({
+                "chat_id": chat_id,
+                "text": format!("{}\n{}", notification.title, notification.body),
+                "disable_web_page_preview": true,
+            })),
+        NotificationChannelKind::Ntfy {
+            server_url,
+            topic,
+            token,
+        } => {
+            // JSON 发布方式支持非 ASCII 标题（Header 方式需要额外编码）
+            let server = server_url.as_deref().unwrap_or(DEFAULT_NTFY_SERVER);
+            let request = client()?
+                .post(server.trim_end_matches('/'))
+                .json(&serde_json::json!({
+                    "topic": topic,
+                    "title": notification.title,
+                    "message": notification.body,
+                }));
+            match token {
+                Some(token) => request.bearer_auth(token),
+                None => request,
+            }
+        }
+        NotificationChannelKind::Webhook { url } => client()?.post(url).json(notification),
+        NotificationChannelKind::Email {
+            host,
+            port,
+            security,
+            username,
+            password,
+            from,
+            to,
+        } => {
+            let smtp = SmtpTarget {
+                host,
+                port: port.unwrap_or(match security {
+                    SmtpSecurity::Tls => 465,
+                    SmtpSecurity::Starttls => 587,
+                    SmtpSecurity::None => 25,
+                }),
+                security: *security,
+                credentials: username
+                    .as_deref()
+                    .map(|user| (user, password.as_deref().unwrap_or_default())),
+            };
+            let message = build_email_message(from, to, notification);
+            return send_email(&smtp, from, to, &message)
+                .await
+                .map_err(|e| AppError::Message(format!("SMTP 发送失败: {e}")));
+        }
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AppError::Message(format!("通知请求失败: {e}")))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(AppError::Message(format!(
+            "通知渠道返回状态码 {status}: {}",
+            body.chars().take(200).collect::<String>()
+        )));
+    }
+    Ok(())
+}
+
+struct SmtpTarget<'a> {
+    host: &'a str,
+    port: u16,
+    security: SmtpSecurity,
+    credentials: Option<(&'a str, &'a str)>,
+}
+
+/// RFC 2047 编码的 UTF-8 头部
+fn encode_header(value: &str) -> String {
+    if value.is_ascii() {
+        return value.to_string();
+    }
+    format!(
+        "=?UTF-8?B?{}?=",
+        base64::engine::general_purpose::STANDARD.encode(value)
+    )
+}
+
+/// 构造完整邮件（正文 base64 编码，无需处理点转义与 8bit 传输）
+fn build_email_message(from: &str, to: &[String], notification: &Notification) -> String {
+    let body = base64::engine::general_purpose::STANDARD.encode(&notification.body);
+    let wrapped = body
+        .as_bytes()
+        .chunks(76)
+        .map(|line| String::from_utf8_lossy(line).into_owned())
+        .collect::<Vec<_>>()
+        .join("\r\n");
+    format!(
+        "From: {from}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nMIME-Version: 1.0\r\n\
+         Content-Type: text/plain; charset=UTF-8\r\nContent-Transfer-Encoding: base64\r\n\r\n{wrapped}\r\n",
+        to.join(", "),
+        encode_header(&notification.title),
+        chrono::Local::now().to_rfc2822(),
+    )
+}
+
+/// 发送一条 SMTP 命令（`None` 表示只读取应答）并检查应答码
+async fn smtp_step<S>(
+    stream: &mut BufReader<S>,
+    command: Option<&str>,
+    label: &str,
+    expected: &[u16],
+) -> Result<(), String>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    if let Some(command) = command {
+        let io = stream.get_mut();
+        io.write_all(command.as_bytes())
+            .await
+            .map_err(|e| format!("{label}: {e}"))?;
+        io.write_all(b"\r\n")
+            .await
+            .map_err(|e| format!("{label}: {e}"))?;
+        io.flush().await.map_err(|e| format!("{label}: {e}"))?;
+    }
+
+    // 多行应答以 "250-" 续行，以 "250 " 结束
+    let mut reply = String::new();
+    loop {
+        let mut line = String::new();
+        let read = stream
+            .read_line(&mut line)
+            .await
+            .map_err(|e| format!("{label}: {e}"))?;
+        if read == 0 {
+            return Err(format!("{label}: connection closed"));
+        }
+        reply.push_str(&line);
+        if line.as_bytes().get(3) != Some(&b'-') {
+            break;
+        }
+    }
+    let code = reply.get(..3).and_then(|code| code.parse::<u16>().ok());
+    match code {
+        Some(code) if expected.contains(&code) => Ok(()),
+        _ => Err(format!("{label}: {}", reply.trim())),
+    }
+}
+
+async fn smtp_session<S>(
+    mut stream: BufReader<S>,
+    target: &SmtpTarget<'_>,
+    greeted: bool,
+    from: &str,
+    to: &[String],
+    message: &str,
+) -> Result<(), String>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    if !greeted {
+        smtp_step(&mut stream, None, "greeting", &[220]).await?;
+    }
+    smtp_step(&mut stream, Some("EHLO cc-switch"), "EHLO", &[250]).await?;
+    if let Some((user, password)) = target.credentials {
+        let token =
+            base64::engine::general_purpose::STANDARD.encode(format!("\0{user}\0{password}"));
+        smtp_step(
+            &mut stream,
+            Some(&format!("AUTH PLAIN {token}")),
+            "AUTH",
+            &[235],
+        )
+        .await?;
+    }
+    smtp_step(
+        &mut stream,
+        Some(&format!("MAIL FROM:<{from}>")),
+        "MAIL FROM",
+        &[250],
+    )
+    .await?;
+    for recipient in to {
+        smtp_step(
+            &mut stream,
+            Some(&format!("RCPT TO:<{recipient}>")),
+            "RCPT TO",
+            &[250, 251],
+        )
+        .await?;
+    }
+    smtp_step(&mut stream, Some("DATA"), "DATA", &[354]).await?;
+    smtp_step(&mut stream, Some(&format!("{message}.")), "message", &[250]).await?;
+    let _ = smtp_step(&mut stream, Some("QUIT"), "QUIT", &[221]).await;
+    Ok(())
+}
+
+async fn send_email(
+    target: &SmtpTarget<'_>,
+    from: &str,
+    to: &[String],
+    message: &str,
+) -> Result<(), String> {
+    if to.is_empty() {
+        return Err("no recipients".to_string());
+    }
+    let tcp = tokio::net::TcpStream::connect((target.host, target.port))
+        .await
+        .map_err(|e| format!("connect {}:{}: {e}", target.host, target.port))?;
+    let server_name = || {
+        rustls::pki_types::ServerName::try_from(target.host.to_string())
+            .map_err(|e| format!("invalid server name: {e}"))
+    };
+    let connector = crate::proxy::hyper_client::global_tls_connector();
+
+    match target.security {
+        SmtpSecurity::None => {
+            smtp_session(BufReader::new(tcp), target, false, from, to, message).await
+        }
+        SmtpSecurity::Tls => {
+            let tls = connector
+                .connect(server_name()?, tcp)
+                .await
+                .map_err(|e| format!("TLS handshake: {e}"))?;
+            smtp_session(BufReader::new(tls), target, false, from, to, message).await
+        }
+        SmtpSecurity::Starttls => {
+            let mut plain = BufReader::new(tcp);
+            smtp_step(&mut plain, None, "greeting", &[220]).await?;
+            smtp_step(&mut plain, Some("EHLO cc-switch"), "EHLO", &[250]).await?;
+            smtp_step(&mut plain, Some("STARTTLS"), "STARTTLS", &[220]).await?;
+            let tls = connector
+                .connect(server_name()?, plain.into_inner())
+                .await
+                .map_err(|e| format!("TLS handshake: {e}"))?;
+            smtp_session(BufReader::new(tls), target, true, from, to, message).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(events: Vec<NotificationEvent>) -> NotificationChannel {
+        NotificationChannel {
+            id: "c".to_string(),
+            enabled: true,
+            events,
+            kind: NotificationChannelKind::Webhook {
+                url: "http://127.0.0.1/hook".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn channels_filter_by_subscribed_events() {
+        assert!(channel_matches(
+            &channel(vec![]),
+            NotificationEvent::SyncFailed
+        ));
+        let budget_only = channel(vec![NotificationEvent::BudgetAlert]);
+        assert!(channel_matches(
+            &budget_only,
+            NotificationEvent::BudgetAlert
+        ));
+        assert!(!channel_matches(
+            &budget_only,
+            NotificationEvent::ProviderDown
+        ));
+
+        let mut disabled = channel(vec![]);
+        disabled.enabled = false;
+        assert!(!channel_matches(&disabled, NotificationEvent::BudgetAlert));
+    }
+
+    #[test]
+    fn dedup_key_suppresses_repeats_within_cooldown() {
+        let now = Instant::now();
+        let notification = Notification::new(NotificationEvent::ProviderDown, "down", "")
+            .with_dedup_key("notifier-test:p1");
+        assert!(should_send(&notification, now));
+        assert!(!should_send(&notification, now + Duration::from_secs(60)));
+        assert!(should_send(&notification, now + DEDUP_COOLDOWN));
+
+        let unkeyed = Notification::new(NotificationEvent::ProviderDown, "down", "");
+        assert!(should_send(&unkeyed, now));
+        assert!(should_send(&unkeyed, now));
+    }
+
+    #[test]
+    fn email_message_encodes_non_ascii_subject_and_body() {
+        let notification =
+            Notification::new(NotificationEvent::BudgetAlert, "预算提醒", "已用 80%");
+        let message = build_email_message(
+            "bot@example.com",
+            &["me@example.com".to_string()],
+            &notification,
+        );
+        assert!(message.contains("Subject: =?UTF-8?B?"));
+        assert!(message.contains("To: me@example.com\r\n"));
+        assert!(message.ends_with("\r\n"));
+        let body = message.split("\r\n\r\n").nth(1).unwrap().trim();
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(body)
+            .unwrap();
+        assert_eq!(String::from_utf8(decoded).unwrap(), "已用 80%");
+    }
+
+    #[tokio::test]
+    async fn smtp_session_speaks_the_expected_dialogue() {
+        let (client, server) = tokio::io::duplex(4096);
+        let server = tokio::spawn(async move {
+            let mut server = BufReader::new(server);
+            let mut transcript = Vec::new();
+            server.get_mut().write_all(b"220 ready\r\n").await.unwrap();
+            loop {
+                let mut line = String::new();
+                if server.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                let reply: &[u8] = match line.as_str() {
+                    "EHLO cc-switch" => b"250-localhost\r\n250 AUTH PLAIN\r\n",
+                    "DATA" => b"354 go ahead\r\n",
+                    "." => b"250 queued\r\n",
+                    "QUIT" => b"221 bye\r\n",
+                    l if l.starts_with("AUTH PLAIN ") => b"235 ok\r\n",
+                    l if l.starts_with("MAIL FROM") || l.starts_with("RCPT TO") => b"250 ok\r\n",
+                    _ => b"",
+                };
+                transcript.push(line);
+                if !reply.is_empty() {
+                    server.get_mut().write_all(reply).await.unwrap();
+                }
+                if transcript.last().map(String::as_str) == Some("QUIT") {
+                    break;
+                }
+            }
+            transcript
+        });
+
+        let target = SmtpTarget {
+            host: "localhost",
+            port: 25,
+            security: SmtpSecurity::None,
+            credentials: Some(("user", "secret")),
+        };
+        smtp_session(
+            BufReader::new(client),
+            &target,
+            false,
+            "bot@example.com",
+            &["me@example.com".to_string()],
+            "Subject: hi\r\n\r\nhello\r\n",
+        )
+        .await
+        .unwrap();
+
+        let transcript = server.await.unwrap();
+        assert_eq!(transcript[0], "EHLO cc-switch");
+        assert!(transcript[1].starts_with("AUTH PLAIN "));
+        assert_eq!(transcript[2], "MAIL FROM:<bot@example.com>");
+        assert_eq!(transcript[3], "RCPT TO:<me@example.com>");
+        assert_eq!(transcript[4], "DATA");
+        assert_eq!(transcript.last().map(String::as_str), Some("QUIT"));
+    }
+}
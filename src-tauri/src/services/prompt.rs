@@ -1,9 +1,10 @@
 use indexmap::IndexMap;
+use serde::Serialize;
 
 use crate::app_config::AppType;
 use crate::config::write_text_file;
 use crate::error::AppError;
-use crate::prompt::Prompt;
+use crate::prompt::{Prompt, PromptVersion};
 use crate::prompt_files::prompt_file_path;
 use crate::store::AppState;
 
@@ -15,6 +16,29 @@ fn get_unix_timestamp() -> Result<i64, AppError> {
         .map_err(|e| AppError::Message(format!("Failed to get system time: {e}")))
 }
 
+/// 差异预览的单行
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptDiffLine {
+    /// `same` / `added` / `removed`
+    pub kind: &'static str,
+    pub text: String,
+}
+
+/// 部署某个版本前的差异预览：当前文件 → 目标版本
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptDiff {
+    pub version: i64,
+    pub target_path: String,
+    pub added: usize,
+    pub removed: usize,
+    pub lines: Vec<PromptDiffLine>,
+}
+
+/// 超过该行数时不再逐行比对，直接整体替换（LCS 为 O(n·m)）
+const DIFF_MAX_LINES: usize = 4000;
+
 pub struct PromptService;
 
 impl PromptService {
@@ -71,10 +95,19 @@ impl PromptService {
     }
 
     pub fn enable_prompt(state: &AppState, app: AppType, id: &str) -> Result<(), AppError> {
-        // 回填当前 live 文件内容到已启用的提示词，或创建备份
         let target_path = prompt_file_path(&app)?;
+        Self::backfill_live_content(state, &app, &target_path)?;
+        Self::activate(state, &app, id, &target_path)
+    }
+
+    /// 回填当前 live 文件内容到已启用的提示词，或创建备份
+    fn backfill_live_content(
+        state: &AppState,
+        app: &AppType,
+        target_path: &std::path::Path,
+    ) -> Result<(), AppError> {
         if target_path.exists() {
-            if let Ok(live_content) = std::fs::read_to_string(&target_path) {
+            if let Ok(live_content) = std::fs::read_to_string(target_path) {
                 if !live_content.trim().is_empty() {
                     let mut prompts = state.db.get_prompts(app.as_str())?;
 
@@ -120,7 +153,16 @@ impl PromptService {
             }
         }
 
-        // 启用目标提示词并写入文件
+        Ok(())
+    }
+
+    /// 启用目标提示词（其余全部禁用）并写入文件
+    fn activate(
+        state: &AppState,
+        app: &AppType,
+        id: &str,
+        target_path: &std::path::Path,
+    ) -> Result<(), AppError> {
         let mut prompts = state.db.get_prompts(app.as_str())?;
 
         for prompt in prompts.values_mut() {
@@ -129,7 +171,7 @@ impl PromptService {
 
         if let Some(prompt) = prompts.get_mut(id) {
             prompt.enabled = true;
-            write_text_file(&target_path, &prompt.content)?; // 原子写入
+            write_text_file(target_path, &prompt.content)?; // 原子写入
             state.db.save_prompt(app.as_str(), prompt)?;
         } else {
             return Err(AppError::InvalidInput(format!("提示词 {id} 不存在")));
@@ -239,4 +281,170 @@ impl PromptService {
         log::info!("自动导入完成: {}", app.as_str());
         Ok(1)
     }
+
+    /// 获取提示词的历史版本（新版本在前）
+    pub fn list_versions(
+        state: &AppState,
+        app: AppType,
+        id: &str,
+    ) -> Result<Vec<PromptVersion>, AppError> {
+        state.db.get_prompt_versions(app.as_str(), id)
+    }
+
+    /// 预览把指定版本部署到目标应用时，对其提示词文件造成的改动
+    pub fn preview_version(
+        state: &AppState,
+        app: AppType,
+        id: &str,
+        version: i64,
+        target: Option<AppType>,
+    ) -> Result<PromptDiff, AppError> {
+        let selected = Self::load_version(state, &app, id, version)?;
+        let target_path = prompt_file_path(&target.unwrap_or(app))?;
+        let current = if target_path.exists() {
+            std::fs::read_to_string(&target_path).map_err(|e| AppError::io(&target_path, e))?
+        } else {
+            String::new()
+        };
+
+        let lines = diff_lines(&current, &selected.content);
+        Ok(PromptDiff {
+            version,
+            target_path: target_path.to_string_lossy().to_string(),
+            added: lines.iter().filter(|l| l.kind == "added").count(),
+            removed: lines.iter().filter(|l| l.kind == "removed").count(),
+            lines,
+        })
+    }
+
+    /// 部署（或回滚到）指定版本：写入目标应用的提示词文件并将其设为启用
+    ///
+    /// 目标应用与来源不同时，会在目标应用下创建/更新同 id 的提示词，
+    /// 同一份提示词因此可以分别部署到 CLAUDE.md、AGENTS.md、GEMINI.md。
+    pub fn deploy_version(
+        state: &AppState,
+        app: AppType,
+        id: &str,
+        version: i64,
+        target: Option<AppType>,
+    ) -> Result<(), AppError> {
+        let selected = Self::load_version(state, &app, id, version)?;
+        let source = state
+            .db
+            .get_prompts(app.as_str())?
+            .shift_remove(id)
+            .ok_or_else(|| AppError::InvalidInput(format!("提示词 {id} 不存在")))?;
+        let target = target.unwrap_or_else(|| app.clone());
+        let target_path = prompt_file_path(&target)?;
+        // 先保住用户对 live 文件的手动修改，再覆盖
+        Self::backfill_live_content(state, &target, &target_path)?;
+
+        let timestamp = get_unix_timestamp()?;
+        let existing = state.db.get_prompts(target.as_str())?.shift_remove(id);
+        let prompt = Prompt {
+            content: selected.content,
+            updated_at: Some(timestamp),
+            enabled: existing.as_ref().is_some_and(|p| p.enabled),
+            created_at: existing
+                .as_ref()
+                .and_then(|p| p.created_at)
+                .or(Some(timestamp)),
+            ..source
+        };
+        state.db.save_prompt(target.as_str(), &prompt)?;
+        log::info!(
+            "部署提示词 {id} v{version}: {} -> {}",
+            app.as_str(),
+            target.as_str()
+        );
+        Self::activate(state, &target, id, &target_path)
+    }
+
+    fn load_version(
+        state: &AppState,
+        app: &AppType,
+        id: &str,
+        version: i64,
+    ) -> Result<PromptVersion, AppError> {
+        state
+            .db
+            .get_prompt_version(app.as_str(), id, version)?
+            .ok_or_else(|| AppError::InvalidInput(format!("提示词 {id} 的版本 {version} 不存在")))
+    }
+}
+
+/// 基于最长公共子序列的逐行差异
+fn diff_lines(old: &str, new: &str) -> Vec<PromptDiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let line = |kind, text: &str| PromptDiffLine {
+        kind,
+        text: text.to_string(),
+    };
+
+    if old.len() > DIFF_MAX_LINES || new.len() > DIFF_MAX_LINES {
+        return old
+            .iter()
+            .map(|text| line("removed", text))
+            .chain(new.iter().map(|text| line("added", text)))
+            .collect();
+    }
+
+    // lcs[i][j] = old[i..] 与 new[j..] 的最长公共子序列长度
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::with_capacity(old.len().max(new.len()));
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            out.push(line("same", old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push(line("removed", old[i]));
+            i += 1;
+        } else {
+            out.push(line("added", new[j]));
+            j += 1;
+        }
+    }
+    out.extend(old[i..].iter().map(|text| line("removed", text)));
+    out.extend(new[j..].iter().map(|text| line("added", text)));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lines_marks_changes() {
+        let diff = diff_lines("a\nb\nc", "a\nc\nd");
+        let kinds: Vec<_> = diff.iter().map(|l| (l.kind, l.text.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("same", "a"),
+                ("removed", "b"),
+                ("same", "c"),
+                ("added", "d")
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_lines_from_empty_file_is_all_added() {
+        let diff = diff_lines("", "x\ny");
+        assert!(diff.iter().all(|l| l.kind == "added"));
+        assert_eq!(diff.len(), 2);
+    }
 }
@@ -0,0 +1,361 @@
+//! 中转注入 / 截断系统提示词的启发式检测
+//!
+//! 代理在转发 Claude 请求前粗略估算提示词 token 数，响应返回后与上游计费的输入 token
+//! （input + cache_read + cache_creation）对比。同一供应商最近若干次请求的比值中位数：
+//!
+//! - 明显偏高且每次多出上千 token：疑似中转在系统提示词前后注入了额外内容；
+//! - 明显偏低：疑似中转截断了系统提示词或历史消息。
+//!
+//! 只统计 Anthropic 原生格式的供应商（OpenAI 兼容上游使用不同的分词器，比值没有参考意义），
+//! 含图片 / 文档的请求与过短的请求不计入样本。估算本身有 ±30% 的误差，阈值留足余量，
+//! 结果只作为信任提示，不影响路由。状态仅保存在内存中，重启后重新采样。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::provider::Provider;
+use crate::proxy::providers::get_claude_api_format;
+use crate::proxy::usage::TokenUsage;
+
+/// 前端监听的事件名
+pub const EVENT_PROMPT_INTEGRITY_ALERT: &str = "provider-prompt-integrity-alert";
+
+/// 每个供应商保留的样本数
+const WINDOW_SIZE: usize = 20;
+
+/// 给出判定所需的最少样本数
+const MIN_SAMPLES: usize = 8;
+
+/// 估算低于该值的请求不计入样本（短请求的固定开销占比过大）
+const MIN_ESTIMATED_TOKENS: u64 = 1_000;
+
+/// 计费 / 估算比值高于该值且多出的 token 超过 [`INJECTION_MIN_EXTRA_TOKENS`] 时判定为注入
+const INJECTION_RATIO: f64 = 1.6;
+const INJECTION_MIN_EXTRA_TOKENS: i64 = 1_000;
+
+/// 计费 / 估算比值低于该值时判定为截断
+const TRUNCATION_RATIO: f64 = 0.55;
+
+/// 每条消息 / 工具定义的固定开销（角色标记、分隔符等）
+const PER_ITEM_OVERHEAD: u64 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptIntegrityStatus {
+    Normal,
+    /// 计费明显多于发送内容
+    Injected,
+    /// 计费明显少于发送内容
+    Truncated,
+}
+
+/// 单个供应商的检测结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptIntegritySignal {
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub status: PromptIntegrityStatus,
+    pub samples: usize,
+    /// 计费 / 估算比值的中位数
+    pub median_ratio: f64,
+    /// 每次请求多出（负数为缺少）的 token 数中位数
+    pub median_extra_tokens: i64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    estimated: u64,
+    billed: u64,
+}
+
+#[derive(Debug, Default)]
+struct ProviderSamples {
+    provider_name: String,
+    samples: VecDeque<Sample>,
+    /// 已提醒过的状态，状态变化后才再次提醒
+    alerted: Option<PromptIntegrityStatus>,
+}
+
+static SAMPLES: LazyLock<Mutex<HashMap<(String, String), ProviderSamples>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 估算 Anthropic Messages 请求的输入 token 数；含图片 / 文档时返回 `None`
+///
+/// CJK 字符按 1 token / 字，其余字符按 4 字符 / token。
+pub fn estimate_prompt_tokens(body: &Value) -> Option<u64> {
+    let mut counter = TokenCounter::default();
+    if let Some(system) = body.get("system") {
+        counter.add_content(system)?;
+    }
+    for message in body.get("messages")?.as_array()? {
+        counter.items += 1;
+        counter.add_content(message.get("content").unwrap_or(&Value::Null))?;
+    }
+    for tool in body
+        .get("tools")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        counter.items += 1;
+        counter.add_json(tool);
+    }
+    Some(counter.total())
+}
+
+#[derive(Default)]
+struct TokenCounter {
+    cjk_chars: u64,
+    other_chars: u64,
+    items: u64,
+}
+
+impl TokenCounter {
+    fn add_text(&mut self, text: &str) {
+        for c in text.chars() {
+            if is_cjk(c) {
+                self.cjk_chars += 1;
+            } else {
+                self.other_chars += 1;
+            }
+        }
+    }
+
+    fn add_json(&mut self, value: &Value) {
+        self.add_text(&value.to_string());
+    }
+
+    /// 字符串或内容块数组；遇到图片 / 文档返回 `None`
+    fn add_content(&mut self, content: &Value) -> Option<()> {
+        match content {
+            Value::String(text) => self.add_text(text),
+            Value::Array(blocks) => {
+                for block in blocks {
+                    match block.get("type").and_then(Value::as_str) {
+                        Some("image" | "document") => return None,
+                        Some("text") => self.add_text(
+                            block
+                                .get("text")
+                                .and_then(Value::as_str)
+                                .unwrap_or_default(),
+                        ),
+                        Some("thinking") => self.add_text(
+                            block
+                                .get("thinking")
+                                .and_then(Value::as_str)
+                                .unwrap_or_default(),
+                        ),
+                        Some("tool_use") => {
+                            self.add_text(block.get("name").and_then(Value::as_str).unwrap_or(""));
+                            self.add_json(block.get("input").unwrap_or(&Value::Null));
+                        }
+                        Some("tool_result") => {
+                            self.add_content(block.get("content").unwrap_or(&Value::Null))?
+                        }
+                        _ => self.add_json(block),
+                    }
+                }
+            }
+            Value::Null => {}
+            other => self.add_json(other),
+        }
+        Some(())
+    }
+
+    fn total(&self) -> u64 {
+        self.cjk_chars + self.other_chars.div_ceil(4) + self.items * PER_ITEM_OVERHEAD
+    }
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF // 平假名 / 片假名
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xAC00..=0xD7AF // 韩文
+        | 0xF900..=0xFAFF
+        | 0xFF00..=0xFFEF)
+}
+
+fn median<T: Copy + PartialOrd>(mut values: Vec<T>) -> Option<T> {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    values.get(values.len() / 2).copied()
+}
+
+fn evaluate(samples: &VecDeque<Sample>) -> (PromptIntegrityStatus, f64, i64) {
+    let ratio = median(
+        samples
+            .iter()
+            .map(|s| s.billed as f64 / s.estimated as f64)
+            .collect(),
+    )
+    .unwrap_or(1.0);
+    let extra = median(
+        samples
+            .iter()
+            .map(|s| s.billed as i64 - s.estimated as i64)
+            .collect(),
+    )
+    .unwrap_or(0);
+    let status = if samples.len() < MIN_SAMPLES {
+        PromptIntegrityStatus::Normal
+    } else if ratio >= INJECTION_RATIO && extra >= INJECTION_MIN_EXTRA_TOKENS {
+        PromptIntegrityStatus::Injected
+    } else if ratio <= TRUNCATION_RATIO {
+        PromptIntegrityStatus::Truncated
+    } else {
+        PromptIntegrityStatus::Normal
+    };
+    (status, ratio, extra)
+}
+
+pub struct PromptIntegrityService;
+
+impl PromptIntegrityService {
+    /// 代理拿到上游计费用量后调用；状态首次变为可疑时向前端发出提醒
+    pub fn observe(app_type: &str, provider: &Provider, estimated: u64, usage: &TokenUsage) {
+        if app_type != "claude"
+            || estimated < MIN_ESTIMATED_TOKENS
+            || get_claude_api_format(provider) != "anthropic"
+        {
+            return;
+        }
+        let billed = u64::from(usage.input_tokens)
+            + u64::from(usage.cache_read_tokens)
+            + u64::from(usage.cache_creation_tokens);
+        if billed == 0 {
+            return;
+        }
+
+        let signal = {
+            let Ok(mut all) = SAMPLES.lock() else {
+                return;
+            };
+            let entry = all
+                .entry((app_type.to_string(), provider.id.clone()))
+                .or_default();
+            entry.provider_name = provider.name.clone();
+            entry.samples.push_back(Sample { estimated, billed });
+            if entry.samples.len() > WINDOW_SIZE {
+                entry.samples.pop_front();
+            }
+            let (status, ratio, extra) = evaluate(&entry.samples);
+            if status == PromptIntegrityStatus::Normal {
+                entry.alerted = None;
+                return;
+            }
+            if entry.alerted == Some(status) {
+                return;
+            }
+            entry.alerted = Some(status);
+            PromptIntegritySignal {
+                app_type: app_type.to_string(),
+                provider_id: provider.id.clone(),
+                provider_name: provider.name.clone(),
+                status,
+                samples: entry.samples.len(),
+                median_ratio: ratio,
+                median_extra_tokens: extra,
+            }
+        };
+        log::warn!(
+            "[PromptIntegrity] 供应商 {} 疑似{}系统提示词：计费/估算中位数 {:.2}，每次 {:+} tokens",
+            signal.provider_name,
+            match signal.status {
+                PromptIntegrityStatus::Injected => "注入",
+                _ => "截断",
+            },
+            signal.median_ratio,
+            signal.median_extra_tokens
+        );
+        crate::usage_events::emit(EVENT_PROMPT_INTEGRITY_ALERT, signal);
+    }
+
+    /// 当前所有已采样供应商的检测结果（样本不足的也会返回，状态为 normal）
+    pub fn signals(app_type: Option<&str>) -> Vec<PromptIntegritySignal> {
+        let Ok(all) = SAMPLES.lock() else {
+            return Vec::new();
+        };
+        let mut signals: Vec<_> = all
+            .iter()
+            .filter(|((app, _), _)| app_type.is_none_or(|wanted| wanted == app))
+            .map(|((app, provider_id), entry)| {
+                let (status, ratio, extra) = evaluate(&entry.samples);
+                PromptIntegritySignal {
+                    app_type: app.clone(),
+                    provider_id: provider_id.clone(),
+                    provider_name: entry.provider_name.clone(),
+                    status,
+                    samples: entry.samples.len(),
+                    median_ratio: ratio,
+                    median_extra_tokens: extra,
+                }
+            })
+            .collect();
+        signals.sort_by(|a, b| a.provider_name.cmp(&b.provider_name));
+        signals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn samples(pairs: &[(u64, u64)]) -> VecDeque<Sample> {
+        pairs
+            .iter()
+            .map(|&(estimated, billed)| Sample { estimated, billed })
+            .collect()
+    }
+
+    #[test]
+    fn estimate_counts_system_messages_and_tools() {
+        let body = json!({
+            "system": [{ "type": "text", "text": "a".repeat(400) }],
+            "messages": [
+                { "role": "user", "content": "你好世界" },
+                { "role": "assistant", "content": [
+                    { "type": "tool_use", "id": "t1", "name": "read", "input": {} }
+                ]},
+                { "role": "user", "content": [
+                    { "type": "tool_result", "tool_use_id": "t1", "content": "b".repeat(40) }
+                ]}
+            ],
+            "tools": [{ "name": "read" }]
+        });
+        // 100 (system) + 4 (CJK) + ceil(("read{}" + 40 + tool json) / 4) + 4 × 4 overhead
+        let estimate = estimate_prompt_tokens(&body).unwrap();
+        assert!((125..=140).contains(&estimate), "{estimate}");
+
+        let with_image = json!({
+            "messages": [{ "role": "user", "content": [
+                { "type": "image", "source": { "type": "base64", "data": "xx" } }
+            ]}]
+        });
+        assert_eq!(estimate_prompt_tokens(&with_image), None);
+    }
+
+    #[test]
+    fn evaluate_flags_injection_and_truncation_after_enough_samples() {
+        let injected = samples(&[(5_000, 9_000); MIN_SAMPLES]);
+        assert_eq!(evaluate(&injected).0, PromptIntegrityStatus::Injected);
+        assert_eq!(evaluate(&injected).2, 4_000);
+
+        let truncated = samples(&[(20_000, 8_000); MIN_SAMPLES]);
+        assert_eq!(evaluate(&truncated).0, PromptIntegrityStatus::Truncated);
+
+        // 估算误差范围内
+        let normal = samples(&[(20_000, 24_000); MIN_SAMPLES]);
+        assert_eq!(evaluate(&normal).0, PromptIntegrityStatus::Normal);
+
+        // 样本不足时不下结论
+        let few = samples(&[(5_000, 9_000); MIN_SAMPLES - 1]);
+        assert_eq!(evaluate(&few).0, PromptIntegrityStatus::Normal);
+    }
+}
@@ -0,0 +1,335 @@
+//! 通用配置迁移助手
+//!
+//! 早期只有一份按应用共享的通用配置片段，不少用户把模型、上下文窗口、令牌等
+//! 供应商专属的键也写了进去，结果每次切换都会被这份片段覆盖。迁移助手：
+//! 1. 用通用配置提取器得到片段中真正可共享的部分；
+//! 2. 以 [`extract_difference`] 求出被误共享的键；
+//! 3. 把这些键写回每个启用通用配置的供应商自身配置，片段只保留可共享部分。
+//!
+//! 迁移不改变任何供应商的最终生效配置。迁移前的片段与供应商快照存入数据库，
+//! 可一键撤销。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use toml_edit::Item;
+
+use super::config_merge::apply_common_config_keeping_provider_scoped;
+use super::live::{extract_difference, provider_uses_common_config};
+use super::ProviderService;
+use crate::app_config::AppType;
+use crate::codex_config_model::CodexConfig;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+fn backup_key(app_type: &AppType) -> String {
+    format!("common_config_migration_backup_{}", app_type.as_str())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigratedProvider {
+    pub id: String,
+    pub name: String,
+}
+
+/// 迁移分析 / 执行结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommonConfigMigrationReport {
+    pub app_type: String,
+    /// 被识别为供应商专属的键（点分路径）
+    pub flagged_keys: Vec<String>,
+    /// 将迁入各供应商的片段内容
+    pub moved_snippet: String,
+    /// 迁移后保留的通用配置片段
+    pub shared_snippet: String,
+    /// 会被写入专属键的供应商
+    pub providers: Vec<MigratedProvider>,
+    pub applied: bool,
+    /// 存在可撤销的迁移时为其时间戳（毫秒）
+    pub undo_available_since: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MigrationBackup {
+    snippet: Option<String>,
+    providers: Vec<Provider>,
+    migrated_at: i64,
+}
+
+fn supports_migration(app_type: &AppType) -> bool {
+    matches!(app_type, AppType::Claude | AppType::Codex | AppType::Gemini)
+}
+
+/// 提取器按"供应商配置"的形状工作，Codex / Gemini 的片段需先包一层
+fn shareable_part(app_type: &AppType, snippet: &str) -> Result<String, AppError> {
+    let settings = match app_type {
+        AppType::Codex => serde_json::json!({ "config": snippet }),
+        AppType::Gemini => serde_json::json!({ "env": parse_json(snippet)? }),
+        _ => parse_json(snippet)?,
+    };
+    let shareable =
+        ProviderService::extract_common_config_snippet_from_settings(app_type.clone(), &settings)?;
+    Ok(if shareable.trim() == "{}" {
+        String::new()
+    } else {
+        shareable
+    })
+}
+
+fn parse_json(snippet: &str) -> Result<Value, AppError> {
+    serde_json::from_str(snippet.trim())
+        .map_err(|e| AppError::Message(format!("Invalid common config: {e}")))
+}
+
+/// 列出差集中的叶子键（点分路径），用于报告
+fn flagged_keys(app_type: &AppType, moved: &str) -> Vec<String> {
+    fn walk_json(prefix: &str, value: &Value, out: &mut Vec<String>) {
+        match value {
+            Value::Object(map) if !map.is_empty() => {
+                for (key, child) in map {
+                    walk_json(&join(prefix, key), child, out);
+                }
+            }
+            _ => out.push(prefix.to_string()),
+        }
+    }
+    fn walk_toml(prefix: &str, item: &Item, out: &mut Vec<String>) {
+        match item.as_table_like() {
+            Some(table) if !table.is_empty() => {
+                for (key, child) in table.iter() {
+                    walk_toml(&join(prefix, key), child, out);
+                }
+            }
+            _ => out.push(prefix.to_string()),
+        }
+    }
+    fn join(prefix: &str, key: &str) -> String {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        }
+    }
+
+    let mut out = Vec::new();
+    if moved.trim().is_empty() {
+        return out;
+    }
+    match app_type {
+        AppType::Codex => {
+            if let Ok(config) = CodexConfig::parse(moved) {
+                walk_toml("", config.as_document().as_item(), &mut out);
+            }
+        }
+        _ => {
+            if let Ok(value) = serde_json::from_str::<Value>(moved) {
+                walk_json("", &value, &mut out);
+            }
+        }
+    }
+    out
+}
+
+fn load_backup(state: &AppState, app_type: &AppType) -> Result<Option<MigrationBackup>, AppError> {
+    let Some(raw) = state.db.get_setting(&backup_key(app_type))? else {
+        return Ok(None);
+    };
+    match serde_json::from_str(&raw) {
+        Ok(backup) => Ok(Some(backup)),
+        Err(e) => {
+            log::warn!("通用配置迁移备份已损坏（{}），忽略: {e}", app_type.as_str());
+            Ok(None)
+        }
+    }
+}
+
+struct MigrationPlan {
+    report: CommonConfigMigrationReport,
+    snippet: Option<String>,
+    targets: Vec<Provider>,
+}
+
+fn plan(state: &AppState, app_type: &AppType) -> Result<MigrationPlan, AppError> {
+    if !supports_migration(app_type) {
+        return Err(AppError::localized(
+            "commonConfig.migration.unsupported",
+            format!("{} 不支持通用配置迁移", app_type.as_str()),
+            format!(
+                "Common config migration is not supported for {}",
+                app_type.as_str()
+            ),
+        ));
+    }
+
+    let snippet = state
+        .db
+        .get_config_snippet(app_type.as_str())?
+        .filter(|value| !value.trim().is_empty());
+    let undo_available_since = load_backup(state, app_type)?.map(|backup| backup.migrated_at);
+
+    let (moved, shared, targets) = match snippet.as_deref() {
+        Some(text) => {
+            let shared = shareable_part(app_type, text)?;
+            let moved = extract_difference(app_type, text, &shared)?;
+            let targets: Vec<Provider> = if moved.is_empty() {
+                Vec::new()
+            } else {
+                state
+                    .db
+                    .get_all_providers(app_type.as_str())?
+                    .into_values()
+                    .filter(|provider| provider_uses_common_config(app_type, provider, Some(text)))
+                    .collect()
+            };
+            (moved, shared, targets)
+        }
+        None => (String::new(), String::new(), Vec::new()),
+    };
+
+    Ok(MigrationPlan {
+        report: CommonConfigMigrationReport {
+            app_type: app_type.as_str().to_string(),
+            flagged_keys: flagged_keys(app_type, &moved),
+            moved_snippet: moved,
+            shared_snippet: shared,
+            providers: targets
+                .iter()
+                .map(|provider| MigratedProvider {
+                    id: provider.id.clone(),
+                    name: provider.name.clone(),
+                })
+                .collect(),
+            applied: false,
+            undo_available_since,
+        },
+        snippet,
+        targets,
+    })
+}
+
+/// 分析当前通用配置片段，不做任何修改
+pub fn analyze_common_config_migration(
+    state: &AppState,
+    app_type: AppType,
+) -> Result<CommonConfigMigrationReport, AppError> {
+    Ok(plan(state, &app_type)?.report)
+}
+
+/// 执行迁移：专属键迁入各供应商，片段只保留可共享部分
+pub fn apply_common_config_migration(
+    state: &AppState,
+    app_type: AppType,
+) -> Result<CommonConfigMigrationReport, AppError> {
+    let MigrationPlan {
+        mut report,
+        snippet,
+        targets,
+    } = plan(state, &app_type)?;
+    if report.flagged_keys.is_empty() {
+        return Ok(report);
+    }
+
+    let migrated_at = chrono::Utc::now().timestamp_millis();
+    let backup = MigrationBackup {
+        snippet,
+        providers: targets.clone(),
+        migrated_at,
+    };
+    let backup_json =
+        serde_json::to_string(&backup).map_err(|source| AppError::JsonSerialize { source })?;
+    state.db.set_setting(&backup_key(&app_type), &backup_json)?;
+
+    for provider in targets {
+        let mut updated = provider;
+        // 按切换时的合并方式写回（供应商已设置的专属键不被覆盖），生效配置保持不变
+        updated.settings_config = apply_common_config_keeping_provider_scoped(
+            &app_type,
+            &updated.settings_config,
+            &report.moved_snippet,
+        )?;
+        updated
+            .meta
+            .get_or_insert_with(Default::default)
+            .common_config_enabled = Some(true);
+        state.db.save_provider(app_type.as_str(), &updated)?;
+    }
+
+    let shared = (!report.shared_snippet.trim().is_empty()).then(|| report.shared_snippet.clone());
+    state.db.set_config_snippet(app_type.as_str(), shared)?;
+    ProviderService::sync_current_provider_for_app(state, app_type.clone())?;
+
+    log::info!(
+        "通用配置迁移完成（{}）：{} 个键迁入 {} 个供应商",
+        app_type.as_str(),
+        report.flagged_keys.len(),
+        report.providers.len()
+    );
+    report.applied = true;
+    report.undo_available_since = Some(migrated_at);
+    Ok(report)
+}
+
+/// 撤销最近一次迁移：恢复迁移前的片段与供应商配置
+///
+/// 迁移后被删除的供应商不会被重新创建。
+pub fn undo_common_config_migration(
+    state: &AppState,
+    app_type: AppType,
+) -> Result<CommonConfigMigrationReport, AppError> {
+    let Some(backup) = load_backup(state, &app_type)? else {
+        return Err(AppError::localized(
+            "commonConfig.migration.no_backup",
+            "没有可撤销的通用配置迁移",
+            "There is no common config migration to undo",
+        ));
+    };
+
+    let existing = state.db.get_all_providers(app_type.as_str())?;
+    for provider in &backup.providers {
+        if existing.contains_key(&provider.id) {
+            state.db.save_provider(app_type.as_str(), provider)?;
+        }
+    }
+    state
+        .db
+        .set_config_snippet(app_type.as_str(), backup.snippet)?;
+    state.db.delete_setting(&backup_key(&app_type))?;
+    ProviderService::sync_current_provider_for_app(state, app_type.clone())?;
+
+    log::info!("已撤销通用配置迁移（{}）", app_type.as_str());
+    analyze_common_config_migration(state, app_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_provider_scoped_keys_in_snippet() {
+        let snippet = r#"{
+            "env": {
+                "ANTHROPIC_MODEL": "glm-4.6",
+                "CLAUDE_CODE_MAX_CONTEXT_TOKENS": "128000",
+                "DISABLE_TELEMETRY": "1"
+            },
+            "permissions": { "allow": ["Bash"] }
+        }"#;
+        let shared = shareable_part(&AppType::Claude, snippet).unwrap();
+        let moved = extract_difference(&AppType::Claude, snippet, &shared).unwrap();
+
+        assert_eq!(
+            flagged_keys(&AppType::Claude, &moved),
+            ["env.ANTHROPIC_MODEL", "env.CLAUDE_CODE_MAX_CONTEXT_TOKENS"]
+        );
+        assert!(shared.contains("DISABLE_TELEMETRY"));
+        assert!(!shared.contains("ANTHROPIC_MODEL"));
+
+        let toml = "model = \"gpt-5\"\napproval_policy = \"never\"\n";
+        let shared = shareable_part(&AppType::Codex, toml).unwrap();
+        let moved = extract_difference(&AppType::Codex, toml, &shared).unwrap();
+        assert_eq!(flagged_keys(&AppType::Codex, &moved), ["model"]);
+    }
+}
@@ -0,0 +1,395 @@
+//! 通用配置键分类表
+//!
+//! 通用配置片段会合并进所有启用它的供应商，所以只能放与供应商无关的设置。
+//! 这里把已知键分为三类：
+//! - **可共享**（hooks、permissions、statusLine、cleanupPeriodDays 等）；
+//! - **供应商专属**（端点、凭据、模型与上下文窗口等）：提取通用配置时剥离，
+//!   保存片段时拒绝，切换时以供应商自身的值为准；
+//! - **未归类**：按可共享处理，保持原有行为。
+//!
+//! 凭据一律按供应商专属处理，判定见 [`ProviderService::is_sensitive_config_key`]。
+
+use serde_json::Value;
+use toml_edit::DocumentMut;
+
+use super::live::apply_common_config_to_settings;
+use super::ProviderService;
+use crate::app_config::AppType;
+use crate::error::AppError;
+
+/// 通用配置键的归属
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyScope {
+    /// 可安全共享给所有供应商
+    Common,
+    /// 随供应商变化，只能写在供应商自身配置里
+    Provider,
+    /// 未在分类表中，按可共享处理
+    Unclassified,
+}
+
+/// Claude `settings.json` 顶层：可共享
+const CLAUDE_COMMON_TOP_LEVEL: &[&str] = &[
+    "hooks",
+    "permissions",
+    "statusLine",
+    "cleanupPeriodDays",
+    "includeCoAuthoredBy",
+    "enableAllProjectMcpServers",
+    "enabledPlugins",
+    "outputStyle",
+    "theme",
+];
+
+/// Claude `settings.json` 顶层：供应商专属
+const CLAUDE_PROVIDER_TOP_LEVEL: &[&str] = &[
+    "apiBaseUrl",
+    // 凭据脚本按供应商配置
+    "apiKeyHelper",
+    // Legacy model fields
+    "primaryModel",
+    "smallFastModel",
+];
+
+/// Claude `env`：可共享
+const CLAUDE_COMMON_ENV: &[&str] = &[
+    "DISABLE_TELEMETRY",
+    "DISABLE_AUTOUPDATER",
+    "DISABLE_ERROR_REPORTING",
+    "CLAUDE_CODE_DISABLE_NONESSENTIAL_TRAFFIC",
+    "CLAUDE_CODE_MAX_OUTPUT_TOKENS",
+    "MAX_THINKING_TOKENS",
+    "BASH_DEFAULT_TIMEOUT_MS",
+    "API_TIMEOUT_MS",
+    "ENABLE_TOOL_SEARCH",
+];
+
+/// Claude `env`：供应商专属的**非机密**字段（模型 + 端点 + 上下文窗口）
+const CLAUDE_PROVIDER_ENV: &[&str] = &[
+    "ANTHROPIC_BASE_URL",
+    "ANTHROPIC_MODEL",
+    "ANTHROPIC_REASONING_MODEL",  // legacy: 已废弃，但旧配置可能残留
+    "ANTHROPIC_SMALL_FAST_MODEL", // legacy
+    "ANTHROPIC_DEFAULT_HAIKU_MODEL",
+    "ANTHROPIC_DEFAULT_HAIKU_MODEL_NAME",
+    "ANTHROPIC_DEFAULT_OPUS_MODEL",
+    "ANTHROPIC_DEFAULT_OPUS_MODEL_NAME",
+    "ANTHROPIC_DEFAULT_SONNET_MODEL",
+    "ANTHROPIC_DEFAULT_SONNET_MODEL_NAME",
+    // Fable 是 v3.16.3 新增的第四档模型映射，与 haiku/sonnet/opus 同属供应商专属，
+    // 不得进入通用配置片段，否则会污染其它供应商（issue #4272）。
+    "ANTHROPIC_DEFAULT_FABLE_MODEL",
+    "ANTHROPIC_DEFAULT_FABLE_MODEL_NAME",
+    "CLAUDE_CODE_SUBAGENT_MODEL",
+    // Context limits follow the actual upstream model. Sharing these
+    // across providers can cap GPT/Kimi to the wrong window and make
+    // Claude Code compact too early or miss the upstream limit.
+    "CLAUDE_CODE_MAX_CONTEXT_TOKENS",
+    "CLAUDE_CODE_AUTO_COMPACT_WINDOW",
+];
+
+/// Codex `config.toml` 顶层：可共享
+const CODEX_COMMON_TOP_LEVEL: &[&str] = &[
+    "approval_policy",
+    "sandbox_mode",
+    "disable_response_storage",
+    "notify",
+    "tui",
+    "history",
+    "file_opener",
+    "shell_environment_policy",
+];
+
+/// Codex `config.toml` 顶层：供应商专属
+const CODEX_PROVIDER_TOP_LEVEL: &[&str] = &[
+    "model",
+    "model_provider",
+    // Legacy/alt formats might use a top-level base_url.
+    "base_url",
+    // wire_api 与 base_url 同属供应商路由语义：无 model_provider 时
+    // update_codex_toml_field / 前端 setCodexWireApi 都会把它落在顶层，
+    // 进了片段会改写其它供应商的协议选择（chat vs responses）。
+    "wire_api",
+    "model_providers",
+    // 上下文窗口跟随上游模型
+    "model_context_window",
+    "model_auto_compact_token_limit",
+    // cc-switch 写 live 时注入的产物：
+    // - experimental_bearer_token 正常写在 [model_providers.<id>] 内，但无活跃路由 /
+    //   内建保留 id / 路由表缺失三种 fallback 会落在顶层——共享等于泄漏 API 密钥。
+    "experimental_bearer_token",
+    // - model_catalog_json 指向按供应商生成的 catalog 投影文件（DB 为 SSOT）。
+    "model_catalog_json",
+];
+
+/// Gemini `.env`：供应商专属
+const GEMINI_PROVIDER_ENV: &[&str] = &["GOOGLE_GEMINI_BASE_URL", "GEMINI_API_KEY", "GEMINI_MODEL"];
+
+fn lookup(key: &str, common: &[&str], provider: &[&str]) -> KeyScope {
+    if provider.contains(&key) || ProviderService::is_sensitive_config_key(key) {
+        KeyScope::Provider
+    } else if common.contains(&key) {
+        KeyScope::Common
+    } else {
+        KeyScope::Unclassified
+    }
+}
+
+/// 按分类表判定一个键的归属
+///
+/// `path` 为片段中的键路径：Claude 的 env 变量写作 `["env", "ANTHROPIC_MODEL"]`，
+/// 其余取顶层键；Gemini 片段本身就是 env 映射。
+pub(crate) fn classify_key(app_type: &AppType, path: &[&str]) -> KeyScope {
+    match (app_type, path) {
+        (AppType::Claude, ["env", key, ..]) => lookup(key, CLAUDE_COMMON_ENV, CLAUDE_PROVIDER_ENV),
+        (AppType::Claude, [key, ..]) => {
+            lookup(key, CLAUDE_COMMON_TOP_LEVEL, CLAUDE_PROVIDER_TOP_LEVEL)
+        }
+        (AppType::Codex, [key, ..]) => {
+            lookup(key, CODEX_COMMON_TOP_LEVEL, CODEX_PROVIDER_TOP_LEVEL)
+        }
+        (AppType::Gemini, [key, ..]) => lookup(key, &[], GEMINI_PROVIDER_ENV),
+        _ => KeyScope::Unclassified,
+    }
+}
+
+pub(crate) fn is_provider_scoped(app_type: &AppType, path: &[&str]) -> bool {
+    classify_key(app_type, path) == KeyScope::Provider
+}
+
+/// 从 JSON 对象中剥离供应商专属键（Claude 额外处理 `env`），返回被剥离的键路径
+pub(crate) fn strip_provider_scoped_json(app_type: &AppType, config: &mut Value) -> Vec<String> {
+    let mut removed = Vec::new();
+    let Some(obj) = config.as_object_mut() else {
+        return removed;
+    };
+
+    if matches!(app_type, AppType::Claude) {
+        if let Some(env) = obj.get_mut("env").and_then(Value::as_object_mut) {
+            env.retain(|key, _| {
+                let scoped = is_provider_scoped(app_type, &["env", key.as_str()]);
+                if scoped {
+                    removed.push(format!("env.{key}"));
+                }
+                !scoped
+            });
+            if env.is_empty() {
+                obj.remove("env");
+            }
+        }
+    }
+    obj.retain(|key, _| {
+        if matches!(app_type, AppType::Claude) && key == "env" {
+            return true;
+        }
+        let scoped = is_provider_scoped(app_type, &[key.as_str()]);
+        if scoped {
+            removed.push(key.clone());
+        }
+        !scoped
+    });
+    removed
+}
+
+/// 从 Codex TOML 文档顶层剥离供应商专属键，返回被剥离的键
+pub(crate) fn strip_provider_scoped_toml(doc: &mut DocumentMut) -> Vec<String> {
+    let root = doc.as_table_mut();
+    let scoped: Vec<String> = root
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| is_provider_scoped(&AppType::Codex, &[key.as_str()]))
+        .collect();
+    for key in &scoped {
+        root.remove(key);
+    }
+    scoped
+}
+
+/// 列出片段中的供应商专属键；不支持分类的应用返回空
+pub(crate) fn provider_scoped_keys(app_type: &AppType, snippet: &str) -> Vec<String> {
+    let trimmed = snippet.trim();
+    if trimmed.is_empty() {
+        return Vec::new();
+    }
+    match app_type {
+        AppType::Claude | AppType::Gemini => serde_json::from_str::<Value>(trimmed)
+            .map(|mut value| strip_provider_scoped_json(app_type, &mut value))
+            .unwrap_or_default(),
+        AppType::Codex => trimmed
+            .parse::<DocumentMut>()
+            .map(|mut doc| strip_provider_scoped_toml(&mut doc))
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// 保存通用配置前的检查：片段不得包含供应商专属键
+pub(crate) fn ensure_common_config_shareable(
+    app_type: &AppType,
+    snippet: &str,
+) -> Result<(), AppError> {
+    let keys = provider_scoped_keys(app_type, snippet);
+    if keys.is_empty() {
+        return Ok(());
+    }
+    let keys = keys.join(", ");
+    Err(AppError::localized(
+        "commonConfig.provider_scoped_keys",
+        format!("通用配置不能包含供应商专属的键：{keys}（可用迁移助手移入各供应商）"),
+        format!("Provider-scoped keys are not allowed in the common config: {keys}"),
+    ))
+}
+
+/// 合并通用配置，但供应商自身已设置的专属键保持不变
+///
+/// 旧片段里可能还残留模型、端点等键；切换时它们不能覆盖供应商的值。
+/// 供应商没有设置的专属键照常由片段补上，与迁移前的生效配置一致。
+pub(crate) fn apply_common_config_keeping_provider_scoped(
+    app_type: &AppType,
+    settings: &Value,
+    snippet: &str,
+) -> Result<Value, AppError> {
+    let filtered = match app_type {
+        AppType::Claude | AppType::Gemini => {
+            match serde_json::from_str::<Value>(snippet.trim()) {
+                Ok(mut source) => {
+                    drop_json_keys_defined_by_provider(app_type, &mut source, settings);
+                    serde_json::to_string(&source)
+                        .map_err(|source| AppError::JsonSerialize { source })?
+                }
+                // 交给合并函数报告格式错误
+                Err(_) => snippet.to_string(),
+            }
+        }
+        AppType::Codex => match snippet.trim().parse::<DocumentMut>() {
+            Ok(mut source) => {
+                let own = settings
+                    .get("config")
+                    .and_then(Value::as_str)
+                    .and_then(|text| text.parse::<DocumentMut>().ok());
+                if let Some(own) = own {
+                    let root = source.as_table_mut();
+                    let defined: Vec<String> = root
+                        .iter()
+                        .map(|(key, _)| key.to_string())
+                        .filter(|key| {
+                            is_provider_scoped(app_type, &[key.as_str()]) && own.contains_key(key)
+                        })
+                        .collect();
+                    for key in &defined {
+                        root.remove(key);
+                    }
+                }
+                source.to_string()
+            }
+            Err(_) => snippet.to_string(),
+        },
+        _ => snippet.to_string(),
+    };
+    apply_common_config_to_settings(app_type, settings, &filtered)
+}
+
+fn drop_json_keys_defined_by_provider(app_type: &AppType, source: &mut Value, settings: &Value) {
+    let (own_env, source_env) = match app_type {
+        AppType::Claude => (settings.get("env"), source.get_mut("env")),
+        AppType::Gemini => (settings.get("env"), Some(&mut *source)),
+        _ => return,
+    };
+    if let (Some(own_env), Some(Value::Object(source_env))) = (own_env, source_env) {
+        source_env.retain(|key, _| {
+            let scoped = if matches!(app_type, AppType::Claude) {
+                is_provider_scoped(app_type, &["env", key.as_str()])
+            } else {
+                is_provider_scoped(app_type, &[key.as_str()])
+            };
+            !(scoped && own_env.get(key).is_some())
+        });
+    }
+    if matches!(app_type, AppType::Claude) {
+        if let Some(source) = source.as_object_mut() {
+            source.retain(|key, _| {
+                key == "env"
+                    || !(is_provider_scoped(app_type, &[key.as_str()])
+                        && settings.get(key).is_some())
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn classifies_known_keys() {
+        let claude = AppType::Claude;
+        assert_eq!(classify_key(&claude, &["hooks"]), KeyScope::Common);
+        assert_eq!(
+            classify_key(&claude, &["cleanupPeriodDays"]),
+            KeyScope::Common
+        );
+        assert_eq!(
+            classify_key(&claude, &["env", "ANTHROPIC_BASE_URL"]),
+            KeyScope::Provider
+        );
+        // 凭据按模式匹配归为供应商专属
+        assert_eq!(
+            classify_key(&claude, &["env", "OPENROUTER_API_KEY"]),
+            KeyScope::Provider
+        );
+        assert_eq!(
+            classify_key(&claude, &["env", "CLAUDE_CODE_MAX_OUTPUT_TOKENS"]),
+            KeyScope::Common
+        );
+        assert_eq!(classify_key(&claude, &["model"]), KeyScope::Unclassified);
+        assert_eq!(
+            classify_key(&AppType::Codex, &["model_providers", "relay"]),
+            KeyScope::Provider
+        );
+        assert_eq!(
+            classify_key(&AppType::Gemini, &["GEMINI_MODEL"]),
+            KeyScope::Provider
+        );
+
+        assert_eq!(
+            provider_scoped_keys(
+                &claude,
+                r#"{"env":{"ANTHROPIC_MODEL":"x","DISABLE_TELEMETRY":"1"},"hooks":{}}"#
+            ),
+            ["env.ANTHROPIC_MODEL"]
+        );
+        assert!(ensure_common_config_shareable(&AppType::Codex, "model = \"gpt-5\"\n").is_err());
+        assert!(
+            ensure_common_config_shareable(&AppType::Codex, "[tui]\nnotifications = true\n")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn provider_scoped_keys_defined_by_provider_win_on_merge() {
+        let snippet = r#"{"env":{"ANTHROPIC_MODEL":"shared-model","DISABLE_TELEMETRY":"1"}}"#;
+
+        let own = json!({"env": {"ANTHROPIC_MODEL": "own-model"}});
+        let merged =
+            apply_common_config_keeping_provider_scoped(&AppType::Claude, &own, snippet).unwrap();
+        assert_eq!(merged["env"]["ANTHROPIC_MODEL"], "own-model");
+        assert_eq!(merged["env"]["DISABLE_TELEMETRY"], "1");
+
+        let bare = json!({"env": {"ANTHROPIC_BASE_URL": "https://a.example"}});
+        let merged =
+            apply_common_config_keeping_provider_scoped(&AppType::Claude, &bare, snippet).unwrap();
+        assert_eq!(merged["env"]["ANTHROPIC_MODEL"], "shared-model");
+
+        let codex = json!({"config": "model = \"gpt-5\"\n"});
+        let merged = apply_common_config_keeping_provider_scoped(
+            &AppType::Codex,
+            &codex,
+            "model = \"o3\"\napproval_policy = \"never\"\n",
+        )
+        .unwrap();
+        let config = merged["config"].as_str().unwrap();
+        assert!(config.contains("model = \"gpt-5\""));
+        assert!(config.contains("approval_policy = \"never\""));
+    }
+}
@@ -0,0 +1,287 @@
+//! Provider duplication with variant overrides
+//!
+//! 复制一个供应商并按点分路径覆盖 `settings_config` 中的字段（如
+//! `env.ANTHROPIC_MODEL`），用于快速创建只有模型或端点不同的变体。
+//! 副本的 id 为 `<原 id>-copy`（冲突时追加 `-2`、`-3`…，与界面复制规则一致），
+//! 名称追加 ` (copy)`；钥匙串中的凭据会解析成明文后按新 id 重新保存，
+//! 删除原供应商不会影响副本。
+
+use std::collections::HashSet;
+
+use serde_json::{Map, Value};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::i18n;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+/// 一条覆盖：点分路径 → 新值
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigOverride {
+    pub path: String,
+    pub value: Value,
+}
+
+impl ConfigOverride {
+    /// 解析 `path=value`（字符串值）或 `path:=json`（JSON 值，如数字、布尔、对象）
+    pub fn parse(spec: &str) -> Result<Self, AppError> {
+        let invalid = || i18n::error("provider.duplicate.invalidOverride", &[("spec", spec)]);
+        let (path, raw) = spec.split_once('=').ok_or_else(invalid)?;
+        let (path, value) = match path.strip_suffix(':') {
+            Some(path) => (
+                path,
+                serde_json::from_str(raw).map_err(|e| {
+                    i18n::error(
+                        "provider.duplicate.invalidJson",
+                        &[("path", path), ("error", &e.to_string())],
+                    )
+                })?,
+            ),
+            None => (path, Value::String(raw.to_string())),
+        };
+        let path = path.trim();
+        if path.is_empty() || path.split('.').any(str::is_empty) {
+            return Err(invalid());
+        }
+        Ok(Self {
+            path: path.to_string(),
+            value,
+        })
+    }
+
+    /// 写入配置；缺失的中间对象自动创建，数组按数字下标访问
+    pub fn apply(&self, settings: &mut Value) -> Result<(), AppError> {
+        let conflict = |segment: &str| {
+            i18n::error(
+                "provider.duplicate.pathConflict",
+                &[("path", &self.path), ("segment", segment)],
+            )
+        };
+        let segments: Vec<&str> = self.path.split('.').collect();
+        let (last, parents) = segments.split_last().expect("validated non-empty path");
+        let mut node = settings;
+        for segment in parents {
+            if node.is_null() {
+                *node = Value::Object(Map::new());
+            }
+            node = match node {
+                Value::Object(map) => map
+                    .entry(segment.to_string())
+                    .or_insert_with(|| Value::Object(Map::new())),
+                Value::Array(items) => segment
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| items.get_mut(index))
+                    .ok_or_else(|| conflict(segment))?,
+                _ => return Err(conflict(segment)),
+            };
+        }
+        if node.is_null() {
+            *node = Value::Object(Map::new());
+        }
+        match node {
+            Value::Object(map) => {
+                map.insert(last.to_string(), self.value.clone());
+            }
+            Value::Array(items) => {
+                let slot = last
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| items.get_mut(index))
+                    .ok_or_else(|| conflict(last))?;
+                *slot = self.value.clone();
+            }
+            _ => return Err(conflict(last)),
+        }
+        Ok(())
+    }
+}
+
+/// `<id>-copy`，冲突时 `<id>-copy-2`、`<id>-copy-3`…
+pub(crate) fn copy_id(id: &str, taken: &HashSet<String>) -> String {
+    let base = format!("{id}-copy");
+    if !taken.contains(&base) {
+        return base;
+    }
+    (2..)
+        .map(|counter| format!("{base}-{counter}"))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded counter")
+}
+
+/// `<name> (copy)`，冲突时 `<name> (copy 2)`…；复制副本时不会叠加后缀
+pub(crate) fn copy_name(name: &str, taken: &HashSet<String>) -> String {
+    let base = strip_copy_suffix(name);
+    let first = format!("{base} (copy)");
+    if !taken.contains(&first) {
+        return first;
+    }
+    (2..)
+        .map(|counter| format!("{base} (copy {counter})"))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded counter")
+}
+
+fn strip_copy_suffix(name: &str) -> &str {
+    let Some(stem) = name.strip_suffix(')') else {
+        return name;
+    };
+    let Some((base, suffix)) = stem.rsplit_once(" (copy") else {
+        return name;
+    };
+    let suffix = suffix.trim();
+    if suffix.is_empty() || suffix.chars().all(|c| c.is_ascii_digit()) {
+        base
+    } else {
+        name
+    }
+}
+
+/// 累加模式应用 Live 配置中已存在的 id 同样不可占用
+fn live_ids(app_type: &AppType) -> Result<HashSet<String>, AppError> {
+    let providers = match app_type {
+        AppType::OpenCode => crate::opencode_config::get_providers()?,
+        AppType::OpenClaw => crate::openclaw_config::get_providers()?,
+        AppType::Hermes => crate::hermes_config::get_providers()?,
+        _ => return Ok(HashSet::new()),
+    };
+    Ok(providers.keys().cloned().collect())
+}
+
+/// 构造副本（不保存）：深拷贝配置与元数据，应用覆盖，生成不冲突的 id 与名称
+pub(crate) fn build_copy(
+    source: &Provider,
+    overrides: &[ConfigOverride],
+    taken_ids: &HashSet<String>,
+    taken_names: &HashSet<String>,
+) -> Result<Provider, AppError> {
+    let mut settings = crate::services::secrets::resolve_settings(&source.settings_config)?;
+    for item in overrides {
+        item.apply(&mut settings)?;
+    }
+    let mut copy = Provider::with_id(
+        copy_id(&source.id, taken_ids),
+        copy_name(&source.name, taken_names),
+        settings,
+        source.website_url.clone(),
+    );
+    copy.category = source.category.clone();
+    copy.notes = source.notes.clone();
+    copy.meta = source.meta.clone();
+    copy.icon = source.icon.clone();
+    copy.icon_color = source.icon_color.clone();
+    copy.created_at = Some(chrono::Utc::now().timestamp_millis());
+    Ok(copy)
+}
+
+pub fn duplicate(
+    state: &AppState,
+    app_type: &AppType,
+    source: &Provider,
+    overrides: &[ConfigOverride],
+) -> Result<Provider, AppError> {
+    let providers = state.db.get_all_providers(app_type.as_str())?;
+    let mut taken_ids: HashSet<String> = providers.keys().cloned().collect();
+    taken_ids.extend(live_ids(app_type)?);
+    let taken_names: HashSet<String> = providers.values().map(|p| p.name.clone()).collect();
+    build_copy(source, overrides, &taken_ids, &taken_names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn set(values: &[&str]) -> HashSet<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_string_and_json_overrides() {
+        let item = ConfigOverride::parse("env.ANTHROPIC_MODEL=glm-4.6").unwrap();
+        assert_eq!(item.path, "env.ANTHROPIC_MODEL");
+        assert_eq!(item.value, json!("glm-4.6"));
+
+        // 字符串值中的 `=` 原样保留
+        let item = ConfigOverride::parse("env.URL=https://a.example.com/?k=v").unwrap();
+        assert_eq!(item.value, json!("https://a.example.com/?k=v"));
+
+        let item = ConfigOverride::parse("env.API_TIMEOUT_MS:=600000").unwrap();
+        assert_eq!(item.value, json!(600000));
+
+        assert!(ConfigOverride::parse("env.MODEL").is_err());
+        assert!(ConfigOverride::parse("env..MODEL=x").is_err());
+        assert!(ConfigOverride::parse("env.FLAG:=yes").is_err());
+    }
+
+    #[test]
+    fn applies_overrides_creating_missing_objects() {
+        let mut settings = json!({"env": {"ANTHROPIC_MODEL": "a"}, "list": [1, 2], "x": 1});
+        for spec in [
+            "env.ANTHROPIC_MODEL=b",
+            "permissions.allow:=[]",
+            "list.1:=3",
+        ] {
+            ConfigOverride::parse(spec)
+                .unwrap()
+                .apply(&mut settings)
+                .unwrap();
+        }
+        assert_eq!(settings["env"]["ANTHROPIC_MODEL"], "b");
+        assert_eq!(settings["permissions"]["allow"], json!([]));
+        assert_eq!(settings["list"], json!([1, 3]));
+
+        let conflict = ConfigOverride::parse("x.y=1").unwrap();
+        assert!(conflict.apply(&mut settings).is_err());
+        let out_of_range = ConfigOverride::parse("list.5=1").unwrap();
+        assert!(out_of_range.apply(&mut settings).is_err());
+    }
+
+    #[test]
+    fn copy_names_and_ids_avoid_collisions() {
+        assert_eq!(copy_id("relay", &set(&["relay"])), "relay-copy");
+        assert_eq!(
+            copy_id("relay", &set(&["relay-copy", "relay-copy-2"])),
+            "relay-copy-3"
+        );
+        assert_eq!(copy_name("Relay", &set(&["Relay"])), "Relay (copy)");
+        assert_eq!(
+            copy_name("Relay", &set(&["Relay (copy)"])),
+            "Relay (copy 2)"
+        );
+        // 复制副本不叠加后缀
+        assert_eq!(
+            copy_name("Relay (copy 2)", &set(&["Relay (copy)", "Relay (copy 2)"])),
+            "Relay (copy 3)"
+        );
+        assert_eq!(copy_name("Relay (EU)", &set(&[])), "Relay (EU) (copy)");
+    }
+
+    #[test]
+    fn build_copy_deep_copies_and_overrides() {
+        let mut source = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({"env": {"ANTHROPIC_MODEL": "claude-sonnet-4-5"}}),
+            None,
+        );
+        source.category = Some("custom".to_string());
+        source.sort_index = Some(3);
+        source.in_failover_queue = true;
+
+        let overrides = [ConfigOverride::parse("env.ANTHROPIC_MODEL=glm-4.6").unwrap()];
+        let copy = build_copy(&source, &overrides, &set(&["relay"]), &set(&["Relay"])).unwrap();
+        assert_eq!(copy.id, "relay-copy");
+        assert_eq!(copy.name, "Relay (copy)");
+        assert_eq!(copy.settings_config["env"]["ANTHROPIC_MODEL"], "glm-4.6");
+        assert_eq!(
+            source.settings_config["env"]["ANTHROPIC_MODEL"],
+            "claude-sonnet-4-5"
+        );
+        assert_eq!(copy.category.as_deref(), Some("custom"));
+        // 序号由 add 追加到末尾；故障转移队列需要单独加入
+        assert_eq!(copy.sort_index, None);
+        assert!(!copy.in_failover_queue);
+    }
+}
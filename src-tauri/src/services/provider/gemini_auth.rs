@@ -2,7 +2,8 @@
 //!
 //! Detects whether a Gemini provider uses PackyCode API Key, Google OAuth, or generic API Key.
 
-use crate::error::AppError;
+use serde_json::Value;
+
 use crate::provider::Provider;
 
 /// Gemini authentication type enumeration
@@ -104,7 +105,8 @@ pub(crate) fn is_google_official_gemini(provider: &Provider) -> bool {
 ///
 /// # What it does
 ///
-/// Writes to **`~/.gemini/settings.json`** (Gemini client config).
+/// Updates the in-memory content of **`~/.gemini/settings.json`** (Gemini client
+/// config); the caller writes it together with `.env`.
 ///
 /// # Value set
 ///
@@ -126,17 +128,13 @@ pub(crate) fn is_google_official_gemini(provider: &Provider) -> bool {
 /// 4. After successful login, credentials saved in Gemini credential store
 /// 5. Subsequent requests auto-use saved credentials
 ///
-/// # Error handling
+/// # Non-official providers
 ///
-/// If provider is not Google Official, function returns `Ok(())` immediately without any operation.
-pub(crate) fn ensure_google_oauth_security_flag(provider: &Provider) -> Result<(), AppError> {
+/// If provider is not Google Official, `settings` is left untouched.
+pub(crate) fn ensure_google_oauth_security_flag(provider: &Provider, settings: &mut Value) {
     if !is_google_official_gemini(provider) {
-        return Ok(());
+        return;
     }
 
-    // Write to Gemini directory settings.json (~/.gemini/settings.json)
-    use crate::gemini_config::write_google_oauth_settings;
-    write_google_oauth_settings()?;
-
-    Ok(())
+    crate::gemini_config::apply_google_oauth_settings(settings);
 }
@@ -5,10 +5,10 @@
 use std::collections::HashMap;
 
 use serde_json::{json, Value};
-use toml_edit::{DocumentMut, Item, TableLike};
 
 use crate::app_config::AppType;
 use crate::codex_config::{get_codex_auth_path, get_codex_config_path};
+use crate::codex_config_model::CodexConfig;
 use crate::config::{delete_file, get_claude_settings_path, read_json_file, write_json_file};
 use crate::database::Database;
 use crate::error::AppError;
@@ -284,170 +284,6 @@ fn json_deep_remove(target: &mut Value, source: &Value) {
     }
 }
 
-fn toml_value_is_subset(target: &toml_edit::Value, source: &toml_edit::Value) -> bool {
-    match (target, source) {
-        (toml_edit::Value::String(target), toml_edit::Value::String(source)) => {
-            target.value() == source.value()
-        }
-        (toml_edit::Value::Integer(target), toml_edit::Value::Integer(source)) => {
-            target.value() == source.value()
-        }
-        (toml_edit::Value::Float(target), toml_edit::Value::Float(source)) => {
-            target.value() == source.value()
-        }
-        (toml_edit::Value::Boolean(target), toml_edit::Value::Boolean(source)) => {
-            target.value() == source.value()
-        }
-        (toml_edit::Value::Datetime(target), toml_edit::Value::Datetime(source)) => {
-            target.value() == source.value()
-        }
-        (toml_edit::Value::Array(target), toml_edit::Value::Array(source)) => {
-            toml_array_contains_subset(target, source)
-        }
-        (toml_edit::Value::InlineTable(target), toml_edit::Value::InlineTable(source)) => {
-            source.iter().all(|(key, source_item)| {
-                target
-                    .get(key)
-                    .is_some_and(|target_item| toml_value_is_subset(target_item, source_item))
-            })
-        }
-        _ => false,
-    }
-}
-
-fn toml_array_contains_subset(target: &toml_edit::Array, source: &toml_edit::Array) -> bool {
-    let mut matched = vec![false; target.len()];
-    let target_items: Vec<&toml_edit::Value> = target.iter().collect();
-
-    source.iter().all(|source_item| {
-        if let Some((index, _)) = target_items
-            .iter()
-            .enumerate()
-            .find(|(index, target_item)| {
-                !matched[*index] && toml_value_is_subset(target_item, source_item)
-            })
-        {
-            matched[index] = true;
-            true
-        } else {
-            false
-        }
-    })
-}
-
-fn toml_remove_array_items(target: &mut toml_edit::Array, source: &toml_edit::Array) {
-    for source_item in source.iter() {
-        let index = {
-            let target_items: Vec<&toml_edit::Value> = target.iter().collect();
-            target_items
-                .iter()
-                .enumerate()
-                .find(|(_, target_item)| toml_value_is_subset(target_item, source_item))
-                .map(|(index, _)| index)
-        };
-
-        if let Some(index) = index {
-            target.remove(index);
-        }
-    }
-}
-
-fn toml_item_is_subset(target: &Item, source: &Item) -> bool {
-    if let Some(source_table) = source.as_table_like() {
-        let Some(target_table) = target.as_table_like() else {
-            return false;
-        };
-        return source_table.iter().all(|(key, source_item)| {
-            target_table
-                .get(key)
-                .is_some_and(|target_item| toml_item_is_subset(target_item, source_item))
-        });
-    }
-
-    match (target.as_value(), source.as_value()) {
-        (Some(target_value), Some(source_value)) => {
-            toml_value_is_subset(target_value, source_value)
-        }
-        _ => false,
-    }
-}
-
-fn merge_toml_item(target: &mut Item, source: &Item) {
-    if let Some(source_table) = source.as_table_like() {
-        if let Some(target_table) = target.as_table_like_mut() {
-            merge_toml_table_like(target_table, source_table);
-            return;
-        }
-    }
-
-    *target = source.clone();
-}
-
-fn merge_toml_table_like(target: &mut dyn TableLike, source: &dyn TableLike) {
-    for (key, source_item) in source.iter() {
-        match target.get_mut(key) {
-            Some(target_item) => merge_toml_item(target_item, source_item),
-            None => {
-                target.insert(key, source_item.clone());
-            }
-        }
-    }
-}
-
-fn remove_toml_item(target: &mut Item, source: &Item) {
-    if let Some(source_table) = source.as_table_like() {
-        if let Some(target_table) = target.as_table_like_mut() {
-            remove_toml_table_like(target_table, source_table);
-            if target_table.is_empty() {
-                *target = Item::None;
-            }
-            return;
-        }
-    }
-
-    if let Some(source_value) = source.as_value() {
-        let mut remove_item = false;
-
-        if let Some(target_value) = target.as_value_mut() {
-            match (target_value, source_value) {
-                (toml_edit::Value::Array(target_arr), toml_edit::Value::Array(source_arr)) => {
-                    toml_remove_array_items(target_arr, source_arr);
-                    remove_item = target_arr.is_empty();
-                }
-                (target_value, source_value)
-                    if toml_value_is_subset(target_value, source_value) =>
-                {
-                    remove_item = true;
-                }
-                _ => {}
-            }
-        }
-
-        if remove_item {
-            *target = Item::None;
-        }
-    }
-}
-
-fn remove_toml_table_like(target: &mut dyn TableLike, source: &dyn TableLike) {
-    let keys: Vec<String> = source.iter().map(|(key, _)| key.to_string()).collect();
-
-    for key in keys {
-        let mut remove_key = false;
-        if let (Some(target_item), Some(source_item)) = (target.get_mut(&key), source.get(&key)) {
-            remove_toml_item(target_item, source_item);
-            remove_key = target_item.is_none()
-                || target_item
-                    .as_table_like()
-                    .is_some_and(|table_like| table_like.is_empty());
-        }
-
-        if remove_key {
-            target.remove(&key);
-        }
-    }
-}
-
 /// 前端表单勾选/取消"使用通用配置"时，对编辑器里的 config.toml 文本做
 /// 结构化合并/剥离。必须在后端用 toml_edit 做：前端 smol-toml 只能
 /// parse → merge → 整文档重序列化，注释全丢、键序重排，还会生成多余的
@@ -462,24 +298,18 @@ pub fn update_toml_common_config_snippet(
         return Ok(config_toml.to_string());
     }
 
-    let mut target_doc = if config_toml.trim().is_empty() {
-        DocumentMut::new()
-    } else {
-        config_toml
-            .parse::<DocumentMut>()
-            .map_err(|e| AppError::Message(format!("Invalid Codex config.toml: {e}")))?
-    };
-    let source_doc = trimmed
-        .parse::<DocumentMut>()
+    let mut target = CodexConfig::try_parse(config_toml)
+        .map_err(|e| AppError::Message(format!("Invalid Codex config.toml: {e}")))?;
+    let snippet = CodexConfig::try_parse(trimmed)
         .map_err(|e| AppError::Message(format!("Invalid Codex common config snippet: {e}")))?;
 
     if enabled {
-        merge_toml_table_like(target_doc.as_table_mut(), source_doc.as_table());
+        target.merge_snippet(&snippet);
     } else {
-        remove_toml_table_like(target_doc.as_table_mut(), source_doc.as_table());
+        target.remove_snippet(&snippet);
     }
 
-    Ok(target_doc.to_string())
+    Ok(target.to_string())
 }
 
 fn settings_contain_common_config(app_type: &AppType, settings: &Value, snippet: &str) -> bool {
@@ -499,16 +329,13 @@ fn settings_contain_common_config(app_type: &AppType, settings: &Value, snippet:
                 return false;
             }
 
-            let target_doc = match config_toml.parse::<DocumentMut>() {
-                Ok(doc) => doc,
-                Err(_) => return false,
-            };
-            let source_doc = match trimmed.parse::<DocumentMut>() {
-                Ok(doc) => doc,
-                Err(_) => return false,
-            };
-
-            toml_item_is_subset(target_doc.as_item(), source_doc.as_item())
+            match (
+                CodexConfig::try_parse(config_toml),
+                CodexConfig::try_parse(trimmed),
+            ) {
+                (Ok(target), Ok(snippet)) => target.contains_snippet(&snippet),
+                _ => false,
+            }
         }
         AppType::Gemini => match serde_json::from_str::<Value>(trimmed) {
             Ok(Value::Object(source_map)) => {
@@ -569,22 +396,18 @@ pub(crate) fn remove_common_config_from_settings(
         AppType::Codex => {
             let mut result = settings.clone();
             let config_toml = settings.get("config").and_then(Value::as_str).unwrap_or("");
-            let mut target_doc = if config_toml.trim().is_empty() {
-                DocumentMut::new()
-            } else {
-                config_toml.parse::<DocumentMut>().map_err(|e| {
-                    AppError::Message(format!(
-                        "Invalid Codex config.toml while removing common config: {e}"
-                    ))
-                })?
-            };
-            let source_doc = trimmed.parse::<DocumentMut>().map_err(|e| {
+            let mut target = CodexConfig::try_parse(config_toml).map_err(|e| {
+                AppError::Message(format!(
+                    "Invalid Codex config.toml while removing common config: {e}"
+                ))
+            })?;
+            let snippet = CodexConfig::try_parse(trimmed).map_err(|e| {
                 AppError::Message(format!("Invalid Codex common config snippet: {e}"))
             })?;
 
-            remove_toml_table_like(target_doc.as_table_mut(), source_doc.as_table());
+            target.remove_snippet(&snippet);
             if let Some(obj) = result.as_object_mut() {
-                obj.insert("config".to_string(), Value::String(target_doc.to_string()));
+                obj.insert("config".to_string(), Value::String(target.to_string()));
             }
             Ok(result)
         }
@@ -605,7 +428,57 @@ pub(crate) fn remove_common_config_from_settings(
     }
 }
 
-fn apply_common_config_to_settings(
+/// 求通用配置片段相对其可共享部分的差集（`snippet` 去掉 `shareable` 后剩下的内容）
+///
+/// 迁移助手用它找出被误放进通用配置的供应商专属键。返回值与片段同格式，
+/// 没有差异时为空字符串。
+pub(crate) fn extract_difference(
+    app_type: &AppType,
+    snippet: &str,
+    shareable: &str,
+) -> Result<String, AppError> {
+    let trimmed = snippet.trim();
+    if trimmed.is_empty() {
+        return Ok(String::new());
+    }
+
+    match app_type {
+        AppType::Claude | AppType::Gemini => {
+            let mut source = serde_json::from_str::<Value>(trimmed)
+                .map_err(|e| AppError::Message(format!("Invalid common config: {e}")))?;
+            if !shareable.trim().is_empty() {
+                let shared = serde_json::from_str::<Value>(shareable.trim()).map_err(|e| {
+                    AppError::Message(format!("Invalid shareable common config: {e}"))
+                })?;
+                json_deep_remove(&mut source, &shared);
+            }
+            if source.as_object().is_none_or(|obj| obj.is_empty()) {
+                return Ok(String::new());
+            }
+            serde_json::to_string_pretty(&source)
+                .map_err(|e| AppError::Message(format!("Serialization failed: {e}")))
+        }
+        AppType::Codex => {
+            let mut source = CodexConfig::try_parse(trimmed).map_err(|e| {
+                AppError::Message(format!("Invalid Codex common config snippet: {e}"))
+            })?;
+            if !shareable.trim().is_empty() {
+                let shared = CodexConfig::try_parse(shareable.trim()).map_err(|e| {
+                    AppError::Message(format!("Invalid shareable Codex common config: {e}"))
+                })?;
+                source.remove_snippet(&shared);
+            }
+            Ok(source.to_string().trim().to_string())
+        }
+        AppType::GrokBuild
+        | AppType::OpenCode
+        | AppType::OpenClaw
+        | AppType::Hermes
+        | AppType::ClaudeDesktop => Ok(String::new()),
+    }
+}
+
+pub(crate) fn apply_common_config_to_settings(
     app_type: &AppType,
     settings: &Value,
     snippet: &str,
@@ -626,22 +499,18 @@ fn apply_common_config_to_settings(
         AppType::Codex => {
             let mut result = settings.clone();
             let config_toml = settings.get("config").and_then(Value::as_str).unwrap_or("");
-            let mut target_doc = if config_toml.trim().is_empty() {
-                DocumentMut::new()
-            } else {
-                config_toml.parse::<DocumentMut>().map_err(|e| {
-                    AppError::Message(format!(
-                        "Invalid Codex config.toml while applying common config: {e}"
-                    ))
-                })?
-            };
-            let source_doc = trimmed.parse::<DocumentMut>().map_err(|e| {
+            let mut target = CodexConfig::try_parse(config_toml).map_err(|e| {
+                AppError::Message(format!(
+                    "Invalid Codex config.toml while applying common config: {e}"
+                ))
+            })?;
+            let snippet = CodexConfig::try_parse(trimmed).map_err(|e| {
                 AppError::Message(format!("Invalid Codex common config snippet: {e}"))
             })?;
 
-            merge_toml_table_like(target_doc.as_table_mut(), source_doc.as_table());
+            target.merge_snippet(&snippet);
             if let Some(obj) = result.as_object_mut() {
-                obj.insert("config".to_string(), Value::String(target_doc.to_string()));
+                obj.insert("config".to_string(), Value::String(target.to_string()));
             }
             Ok(result)
         }
@@ -674,7 +543,11 @@ pub(crate) fn build_effective_settings_with_common_config(
 
     if provider_uses_common_config(app_type, provider, snippet.as_deref()) {
         if let Some(snippet_text) = snippet.as_deref() {
-            match apply_common_config_to_settings(app_type, &effective_settings, snippet_text) {
+            match super::config_merge::apply_common_config_keeping_provider_scoped(
+                app_type,
+                &effective_settings,
+                snippet_text,
+            ) {
                 Ok(settings) => effective_settings = settings,
                 Err(err) => {
                     log::warn!(
@@ -692,7 +565,9 @@ pub(crate) fn build_effective_settings_with_common_config(
         apply_kimi_for_coding_context_defaults(&mut effective_settings, provider);
     }
 
-    Ok(effective_settings)
+    // 模型别名与钥匙串引用只存在于 DB，写入 live 的必须是真实模型名与密钥
+    crate::services::model_alias::resolve_settings_models(app_type, &mut effective_settings);
+    crate::services::secrets::resolve_settings(&effective_settings)
 }
 
 pub(crate) fn write_live_with_common_config(
@@ -722,6 +597,23 @@ pub(crate) fn strip_common_config_from_live_settings(
     app_type: &AppType,
     provider: &Provider,
     live_settings: Value,
+) -> Value {
+    let mut settings = strip_common_config_for_backfill(db, app_type, provider, live_settings);
+    // live 中是解析后的模型名与明文密钥，回填前换回别名与钥匙串引用
+    crate::services::model_alias::restore_aliases_for_backfill(
+        app_type,
+        &provider.settings_config,
+        &mut settings,
+    );
+    crate::services::secrets::restore_refs_for_backfill(&provider.settings_config, &mut settings);
+    settings
+}
+
+fn strip_common_config_for_backfill(
+    db: &Database,
+    app_type: &AppType,
+    provider: &Provider,
+    live_settings: Value,
 ) -> Value {
     let snippet = match db.get_config_snippet(app_type.as_str()) {
         Ok(snippet) => snippet,
@@ -983,29 +875,8 @@ impl LiveSnapshot {
                     delete_file(&config_path)?;
                 }
             }
-            LiveSnapshot::Gemini { env, .. } => {
-                use crate::gemini_config::{
-                    get_gemini_env_path, get_gemini_settings_path, write_gemini_env_atomic,
-                };
-                let path = get_gemini_env_path();
-                if let Some(env_map) = env {
-                    write_gemini_env_atomic(env_map)?;
-                } else if path.exists() {
-                    delete_file(&path)?;
-                }
-
-                let settings_path = get_gemini_settings_path();
-                match self {
-                    LiveSnapshot::Gemini {
-                        config: Some(cfg), ..
-                    } => {
-                        write_json_file(&settings_path, cfg)?;
-                    }
-                    LiveSnapshot::Gemini { config: None, .. } if settings_path.exists() => {
-                        delete_file(&settings_path)?;
-                    }
-                    _ => {}
-                }
+            LiveSnapshot::Gemini { env, config } => {
+                crate::gemini_config::write_gemini_live_files(env.as_ref(), config.as_ref())?;
             }
         }
         Ok(())
@@ -1017,7 +888,12 @@ pub(crate) fn write_live_snapshot(app_type: &AppType, provider: &Provider) -> Re
     match app_type {
         AppType::Claude => {
             let path = get_claude_settings_path();
-            let settings = sanitize_claude_settings_for_live(&provider.settings_config);
+            let mut settings = sanitize_claude_settings_for_live(&provider.settings_config);
+            let live = path
+                .exists()
+                .then(|| read_json_file::<Value>(&path).ok())
+                .flatten();
+            crate::claude_plugin_sync::sync_enabled_plugins(&mut settings, live.as_ref());
             write_json_file(&path, &settings)?;
         }
         AppType::ClaudeDesktop => {
@@ -1064,24 +940,8 @@ pub(crate) fn write_live_snapshot(app_type: &AppType, provider: &Provider) -> Re
             use crate::provider::OpenCodeProviderConfig;
 
             // Defensive check: if settings_config is a full config structure, extract provider fragment
-            let config_to_write = if let Some(obj) = provider.settings_config.as_object() {
-                // Detect full config structure (has $schema or top-level provider field)
-                if obj.contains_key("$schema") || obj.contains_key("provider") {
-                    log::warn!(
-                        "OpenCode provider '{}' has full config structure in settings_config, attempting to extract fragment",
-                        provider.id
-                    );
-                    // Try to extract from provider.{id}
-                    obj.get("provider")
-                        .and_then(|p| p.get(&provider.id))
-                        .cloned()
-                        .unwrap_or_else(|| provider.settings_config.clone())
-                } else {
-                    provider.settings_config.clone()
-                }
-            } else {
-                provider.settings_config.clone()
-            };
+            let config_to_write =
+                opencode_config::provider_fragment(&provider.id, &provider.settings_config).clone();
 
             // Convert settings_config to OpenCodeProviderConfig
             let opencode_config_result =
@@ -1533,7 +1393,7 @@ pub fn import_default_config(state: &AppState, app_type: AppType) -> Result<bool
 
     let mut provider = Provider::with_id(
         "default".to_string(),
-        "default".to_string(),
+        super::LIVE_IMPORT_PROVIDER_NAME.to_string(),
         settings_config,
         None,
     );
@@ -1606,8 +1466,8 @@ pub fn should_import_default_config_on_startup(
 /// Write Gemini live configuration with authentication handling
 pub(crate) fn write_gemini_live(provider: &Provider) -> Result<(), AppError> {
     use crate::gemini_config::{
-        get_gemini_settings_path, json_to_env, validate_gemini_settings_strict,
-        write_gemini_env_atomic,
+        apply_packycode_settings, get_gemini_settings_path, json_to_env,
+        validate_gemini_settings_strict, write_gemini_live_files,
     };
 
     // One-time auth type detection to avoid repeated detection
@@ -1655,33 +1515,31 @@ pub(crate) fn write_gemini_live(provider: &Provider) -> Result<(), AppError> {
         config_to_write = Some(read_json_file(&settings_path)?);
     }
 
-    match auth_type {
-        GeminiAuthType::GoogleOfficial => {
-            // Google Official uses OAuth, no API key validation needed.
-            // Write user's env vars as-is (e.g. GEMINI_MODEL, custom vars).
-            write_gemini_env_atomic(&env_map)?;
-        }
-        GeminiAuthType::Packycode | GeminiAuthType::Generic => {
-            // API Key mode -- require GEMINI_API_KEY
-            validate_gemini_settings_strict(&provider.settings_config)?;
-            write_gemini_env_atomic(&env_map)?;
-        }
-    }
-
-    if let Some(config_value) = config_to_write {
-        write_json_file(&settings_path, &config_value)?;
+    if matches!(
+        auth_type,
+        GeminiAuthType::Packycode | GeminiAuthType::Generic
+    ) {
+        // API Key mode -- require GEMINI_API_KEY
+        validate_gemini_settings_strict(&provider.settings_config)?;
     }
 
     // Set security.auth.selectedType based on auth type
     // - Google Official: OAuth mode
     // - All others: API Key mode
+    let mut settings = config_to_write.unwrap_or_else(|| json!({}));
     match auth_type {
-        GeminiAuthType::GoogleOfficial => ensure_google_oauth_security_flag(provider)?,
+        GeminiAuthType::GoogleOfficial => {
+            ensure_google_oauth_security_flag(provider, &mut settings)
+        }
         GeminiAuthType::Packycode | GeminiAuthType::Generic => {
-            crate::gemini_config::write_packycode_settings()?;
+            apply_packycode_settings(&mut settings);
         }
     }
 
+    // Google Official writes the user's env vars as-is (e.g. GEMINI_MODEL, custom vars).
+    // Both files are written together and rolled back together on failure.
+    write_gemini_live_files(Some(&env_map), Some(&settings))?;
+
     Ok(())
 }
 
@@ -1977,6 +1835,7 @@ pub fn remove_openclaw_provider_from_live(provider_id: &str) -> Result<(), AppEr
 mod tests {
     use super::*;
     use serde_json::json;
+    use toml_edit::DocumentMut;
 
     #[test]
     fn kimi_for_coding_effective_settings_backfill_256k_context() {
@@ -2333,7 +2192,7 @@ base_url = "https://a.example/v1"
 
     /// 合并时标量=片段覆盖供应商值（与 Claude 侧 deepMerge 一致）；
     /// 剥离按值匹配：用户改过的值不删（与 strip 路径的
-    /// toml_value_is_subset 语义一致）。
+    /// `CodexConfig::remove_snippet` 语义一致）。
     #[test]
     fn update_toml_common_config_snippet_scalar_override_and_value_matched_removal() {
         let snippet = "[tui]\nnotifications = true\n";
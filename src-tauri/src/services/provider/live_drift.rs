@@ -0,0 +1,244 @@
+//! live 配置漂移：检测在 CC Switch 之外对 live 配置的手动修改
+//!
+//! 用户在 CC Switch 运行时直接编辑 `~/.claude/settings.json` 等文件，数据库里
+//! 当前供应商的副本就会悄悄与 live 分叉，下次切换回来时改动会被覆盖。
+//! [`crate::config_watcher`] 发现文件变化后调用 [`detect_live_drift`]：
+//! 按切换回填的同一套规则（剥离通用配置、换回钥匙串引用）把 live 还原成
+//! 供应商配置，再用 [`extract_difference`] 双向求差。CC Switch 自己写入的
+//! live 还原后与数据库一致，不会误报。
+//!
+//! 用户可以选择把改动并入当前供应商（[`DriftResolution::Reimport`]），
+//! 或用当前供应商重新覆盖 live（[`DriftResolution::Overwrite`]）。
+
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::live::{
+    extract_difference, read_live_settings, strip_common_config_from_live_settings,
+    write_live_with_common_config,
+};
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+/// 当前供应商与 live 配置的差异
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveConfigDrift {
+    pub app: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    /// 只在 live 中出现（新增或被改动）的配置，与供应商配置同格式
+    pub live_only: String,
+    /// 只在供应商配置中出现（被删除或被改动）的配置
+    pub stored_only: String,
+    /// Codex 的 auth.json 是否被改动（含密钥，不展示内容）
+    pub auth_changed: bool,
+}
+
+/// 处理漂移的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriftResolution {
+    /// 把 live 中的改动回填到当前供应商
+    Reimport,
+    /// 用当前供应商重新写入 live
+    Overwrite,
+}
+
+impl FromStr for DriftResolution {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "reimport" => Ok(Self::Reimport),
+            "overwrite" => Ok(Self::Overwrite),
+            other => Err(AppError::InvalidInput(format!(
+                "Unknown drift resolution: {other}"
+            ))),
+        }
+    }
+}
+
+/// 参与漂移检测的应用：只有切换模式、且回填规则完整的应用
+fn supports_drift(app_type: &AppType) -> bool {
+    matches!(app_type, AppType::Claude | AppType::Codex | AppType::Gemini)
+}
+
+/// 双向求差：返回 (只在 live 中, 只在供应商中, auth 是否改动)
+fn diff_settings(
+    app_type: &AppType,
+    live: &Value,
+    stored: &Value,
+) -> Result<(String, String, bool), AppError> {
+    match app_type {
+        AppType::Codex => {
+            let live_config = live.get("config").and_then(Value::as_str).unwrap_or("");
+            let stored_config = stored.get("config").and_then(Value::as_str).unwrap_or("");
+            Ok((
+                extract_difference(app_type, live_config, stored_config)?,
+                extract_difference(app_type, stored_config, live_config)?,
+                live.get("auth") != stored.get("auth"),
+            ))
+        }
+        _ => {
+            let live_text = live.to_string();
+            let stored_text = stored.to_string();
+            Ok((
+                extract_difference(app_type, &live_text, &stored_text)?,
+                extract_difference(app_type, &stored_text, &live_text)?,
+                false,
+            ))
+        }
+    }
+}
+
+/// live 被代理接管（或接管中）时内容是占位配置，不做比较也不允许覆盖
+fn live_taken_over(state: &AppState, app_type: &AppType) -> bool {
+    let has_live_backup = futures::executor::block_on(state.db.get_live_backup(app_type.as_str()))
+        .ok()
+        .flatten()
+        .is_some();
+    has_live_backup
+        || state
+            .proxy_service
+            .detect_takeover_in_live_config_for_app(app_type)
+}
+
+fn current_provider(state: &AppState, app_type: &AppType) -> Result<Option<Provider>, AppError> {
+    let Some(id) = crate::settings::get_effective_current_provider(&state.db, app_type)? else {
+        return Ok(None);
+    };
+    state.db.get_provider_by_id(&id, app_type.as_str())
+}
+
+/// 检测当前供应商与 live 配置是否分叉；不支持的应用、无当前供应商、
+/// live 不可读或被代理接管时返回 None
+pub fn detect_live_drift(
+    state: &AppState,
+    app_type: &AppType,
+) -> Result<Option<LiveConfigDrift>, AppError> {
+    if !supports_drift(app_type) || live_taken_over(state, app_type) {
+        return Ok(None);
+    }
+    let Some(provider) = current_provider(state, app_type)? else {
+        return Ok(None);
+    };
+    let live = match read_live_settings(app_type.clone()) {
+        Ok(live) => live,
+        Err(e) => {
+            log::debug!("[LiveDrift] 读取 {} live 配置失败: {e}", app_type.as_str());
+            return Ok(None);
+        }
+    };
+
+    let restored =
+        strip_common_config_from_live_settings(state.db.as_ref(), app_type, &provider, live);
+    let (live_only, stored_only, auth_changed) =
+        diff_settings(app_type, &restored, &provider.settings_config)?;
+    if live_only.is_empty() && stored_only.is_empty() && !auth_changed {
+        return Ok(None);
+    }
+
+    Ok(Some(LiveConfigDrift {
+        app: app_type.as_str().to_string(),
+        provider_id: provider.id,
+        provider_name: provider.name,
+        live_only,
+        stored_only,
+        auth_changed,
+    }))
+}
+
+/// 按用户选择处理漂移
+pub fn resolve_live_drift(
+    state: &AppState,
+    app_type: &AppType,
+    resolution: DriftResolution,
+) -> Result<(), AppError> {
+    if !supports_drift(app_type) {
+        return Err(AppError::InvalidInput(format!(
+            "Live config drift is not supported for {}",
+            app_type.as_str()
+        )));
+    }
+    if live_taken_over(state, app_type) {
+        return Err(AppError::localized(
+            "provider.liveDrift.takenOver",
+            "live 配置正被代理接管，无法处理外部修改",
+            "The live config is taken over by the proxy; external edits cannot be resolved",
+        ));
+    }
+    let Some(mut provider) = current_provider(state, app_type)? else {
+        return Ok(());
+    };
+
+    match resolution {
+        DriftResolution::Reimport => {
+            let live = read_live_settings(app_type.clone())?;
+            provider.settings_config = strip_common_config_from_live_settings(
+                state.db.as_ref(),
+                app_type,
+                &provider,
+                live,
+            );
+            state.db.save_provider(app_type.as_str(), &provider)?;
+            log::info!(
+                "[LiveDrift] 已将 {} live 配置的外部修改并入供应商 '{}'",
+                app_type.as_str(),
+                provider.id
+            );
+        }
+        DriftResolution::Overwrite => {
+            write_live_with_common_config(state.db.as_ref(), app_type, &provider)?;
+            log::info!(
+                "[LiveDrift] 已用供应商 '{}' 覆盖 {} live 配置",
+                provider.id,
+                app_type.as_str()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn identical_settings_have_no_drift() {
+        let settings = json!({"env": {"ANTHROPIC_BASE_URL": "https://a.example.com"}});
+        let (live_only, stored_only, auth_changed) =
+            diff_settings(&AppType::Claude, &settings, &settings).unwrap();
+        assert!(live_only.is_empty() && stored_only.is_empty() && !auth_changed);
+    }
+
+    #[test]
+    fn edited_claude_value_shows_on_both_sides() {
+        let live = json!({"env": {"ANTHROPIC_MODEL": "opus", "ANTHROPIC_BASE_URL": "u"}});
+        let stored = json!({"env": {"ANTHROPIC_MODEL": "sonnet", "ANTHROPIC_BASE_URL": "u"}});
+        let (live_only, stored_only, _) = diff_settings(&AppType::Claude, &live, &stored).unwrap();
+        assert!(live_only.contains("opus") && !live_only.contains("ANTHROPIC_BASE_URL"));
+        assert!(stored_only.contains("sonnet"));
+    }
+
+    #[test]
+    fn codex_diffs_config_and_flags_auth() {
+        let live = json!({
+            "auth": {"OPENAI_API_KEY": "sk-new"},
+            "config": "model = \"gpt-5\"\nmodel_reasoning_effort = \"high\"\n"
+        });
+        let stored = json!({
+            "auth": {"OPENAI_API_KEY": "sk-old"},
+            "config": "model = \"gpt-5\"\n"
+        });
+        let (live_only, stored_only, auth_changed) =
+            diff_settings(&AppType::Codex, &live, &stored).unwrap();
+        assert_eq!(live_only, "model_reasoning_effort = \"high\"");
+        assert!(stored_only.is_empty());
+        assert!(auth_changed);
+    }
+}
@@ -2,9 +2,16 @@
 //!
 //! Handles provider CRUD operations, switching, and configuration management.
 
+mod common_config_migration;
+mod config_merge;
+mod duplicate;
 mod endpoints;
 mod gemini_auth;
 mod live;
+mod live_drift;
+mod onboarding;
+mod ordering;
+mod proxy_override;
 mod usage;
 
 use indexmap::IndexMap;
@@ -15,20 +22,33 @@ use serde_json::Value;
 use crate::app_config::AppType;
 use crate::database::{validate_cost_multiplier, validate_pricing_source};
 use crate::error::AppError;
+use crate::i18n;
 use crate::provider::{Provider, UsageResult};
 use crate::services::mcp::McpService;
-use crate::settings::CustomEndpoint;
+use crate::services::status_file::{self, StatusSource};
+use crate::services::switch_hooks::{SwitchHookContext, SwitchHookService};
+use crate::settings::{CustomEndpoint, SwitchHookEvent};
 use crate::store::AppState;
 
 // Re-export sub-module functions for external access
+pub use common_config_migration::{
+    analyze_common_config_migration, apply_common_config_migration, undo_common_config_migration,
+    CommonConfigMigrationReport, MigratedProvider,
+};
+pub use duplicate::ConfigOverride;
 pub use live::{
     import_default_config, import_hermes_providers_from_live, import_openclaw_providers_from_live,
     import_opencode_providers_from_live, read_live_settings,
     should_import_default_config_on_startup, sync_current_to_live,
     update_toml_common_config_snippet,
 };
+pub use live_drift::{detect_live_drift, resolve_live_drift, DriftResolution, LiveConfigDrift};
+pub use onboarding::{
+    scan_live_import_candidates, LiveImportCandidate, LiveImportOutcome, LIVE_IMPORT_PROVIDER_NAME,
+};
 
 // Internal re-exports (pub(crate))
+pub(crate) use config_merge::ensure_common_config_shareable;
 pub(crate) use live::sanitize_claude_settings_for_live;
 pub(crate) use live::{
     build_effective_settings_with_common_config, normalize_provider_common_config_for_storage,
@@ -468,6 +488,81 @@ mod tests {
         });
     }
 
+    #[test]
+    #[serial]
+    fn common_config_migration_moves_provider_keys_and_can_undo() {
+        with_test_home(|state, _| {
+            let snippet = r#"{"env":{"ANTHROPIC_MODEL":"glm-4.6","DISABLE_TELEMETRY":"1"}}"#;
+            state
+                .db
+                .set_config_snippet("claude", Some(snippet.to_string()))
+                .expect("set snippet");
+            let mut shared = Provider::with_id(
+                "shared".into(),
+                "Shared".into(),
+                json!({"env": {"ANTHROPIC_BASE_URL": "https://a.example"}}),
+                None,
+            );
+            shared.meta = Some(ProviderMeta {
+                common_config_enabled: Some(true),
+                ..Default::default()
+            });
+            let own = Provider::with_id(
+                "own".into(),
+                "Own".into(),
+                json!({"env": {"ANTHROPIC_MODEL": "kimi-k2"}}),
+                None,
+            );
+            state.db.save_provider("claude", &shared).expect("save");
+            state.db.save_provider("claude", &own).expect("save");
+
+            let report = apply_common_config_migration(state, AppType::Claude).expect("apply");
+            assert!(report.applied);
+            assert_eq!(report.flagged_keys, ["env.ANTHROPIC_MODEL"]);
+            assert_eq!(report.providers.len(), 1);
+
+            let migrated = state
+                .db
+                .get_provider_by_id("shared", "claude")
+                .expect("query")
+                .expect("provider");
+            assert_eq!(
+                migrated.settings_config["env"]["ANTHROPIC_MODEL"],
+                "glm-4.6"
+            );
+            let untouched = state
+                .db
+                .get_provider_by_id("own", "claude")
+                .expect("query")
+                .expect("provider");
+            assert_eq!(untouched.settings_config, own.settings_config);
+            let remaining = state
+                .db
+                .get_config_snippet("claude")
+                .expect("snippet")
+                .expect("snippet kept");
+            assert!(!remaining.contains("ANTHROPIC_MODEL"));
+            assert!(remaining.contains("DISABLE_TELEMETRY"));
+
+            let undone = undo_common_config_migration(state, AppType::Claude).expect("undo");
+            assert_eq!(undone.undo_available_since, None);
+            assert_eq!(
+                state
+                    .db
+                    .get_config_snippet("claude")
+                    .expect("snippet")
+                    .as_deref(),
+                Some(snippet)
+            );
+            let restored = state
+                .db
+                .get_provider_by_id("shared", "claude")
+                .expect("query")
+                .expect("provider");
+            assert_eq!(restored.settings_config, shared.settings_config);
+        });
+    }
+
     #[test]
     #[serial]
     fn update_preserves_usage_credentials_that_only_match_previous_config() {
@@ -1987,6 +2082,21 @@ impl ProviderService {
             .and_then(|meta| meta.live_config_managed)
     }
 
+    /// 开启钥匙串存储时，把本次保存的明文凭据移入钥匙串（已是引用的字段保持不变）
+    fn store_secrets_if_enabled(
+        app_type: &AppType,
+        provider: &mut Provider,
+    ) -> Result<(), AppError> {
+        if crate::settings::secrets_use_keychain() {
+            crate::services::secrets::store_provider_secrets(
+                app_type.as_str(),
+                &provider.id,
+                &mut provider.settings_config,
+            )?;
+        }
+        Ok(())
+    }
+
     fn set_provider_live_config_managed(provider: &mut Provider, managed: bool) {
         provider
             .meta
@@ -2091,15 +2201,26 @@ impl ProviderService {
         provider: Provider,
         add_to_live: bool,
     ) -> Result<bool, AppError> {
+        let _write_lock = crate::instance_lock::acquire("provider.add")?;
         let mut provider = provider;
         // Normalize Claude model keys
         Self::normalize_provider_if_claude(&app_type, &mut provider);
         Self::validate_provider_settings(&app_type, &provider)?;
         normalize_provider_common_config_for_storage(state.db.as_ref(), &app_type, &mut provider)?;
         Self::normalize_usage_script_credential_overrides(&app_type, &mut provider);
+        Self::store_secrets_if_enabled(&app_type, &mut provider)?;
         if app_type.is_additive_mode() {
             Self::set_provider_live_config_managed(&mut provider, add_to_live);
         }
+        // 追加到末尾并固定序号，已有供应商的编号不受影响
+        if provider.sort_index.is_none() {
+            ordering::ensure_sort_indices(state, &app_type)?;
+            let existing = state.db.get_all_providers(app_type.as_str())?;
+            provider.sort_index = existing
+                .get(&provider.id)
+                .and_then(|p| p.sort_index)
+                .or_else(|| Some(ordering::next_sort_index(existing.values())));
+        }
 
         // Save to database
         state.db.save_provider(app_type.as_str(), &provider)?;
@@ -2141,6 +2262,7 @@ impl ProviderService {
         original_id: Option<&str>,
         provider: Provider,
     ) -> Result<bool, AppError> {
+        let _write_lock = crate::instance_lock::acquire("provider.update")?;
         let mut provider = provider;
         let original_id = original_id.unwrap_or(provider.id.as_str()).to_string();
         let provider_id_changed = original_id != provider.id;
@@ -2152,6 +2274,7 @@ impl ProviderService {
         Self::validate_provider_settings(&app_type, &provider)?;
         normalize_provider_common_config_for_storage(state.db.as_ref(), &app_type, &mut provider)?;
         Self::normalize_usage_script_credential_overrides(&app_type, &mut provider);
+        Self::store_secrets_if_enabled(&app_type, &mut provider)?;
 
         if provider_id_changed {
             if !app_type.is_additive_mode() {
@@ -2366,6 +2489,7 @@ impl ProviderService {
     /// 同时检查本地 settings 和数据库的当前供应商，防止删除任一端正在使用的供应商。
     /// 对于累加模式应用（OpenCode, OpenClaw），可以随时删除任意供应商，同时从 live 配置中移除。
     pub fn delete(state: &AppState, app_type: AppType, id: &str) -> Result<(), AppError> {
+        let _write_lock = crate::instance_lock::acquire("provider.delete")?;
         // Additive mode apps - no current provider concept
         if app_type.is_additive_mode() {
             // Single DB read shared across all additive-mode sub-paths below.
@@ -2500,7 +2624,51 @@ impl ProviderService {
     ///    c. Update database is_current (as default for new devices)
     ///    d. Write target provider config to live files
     ///    e. Sync MCP configuration
+    ///
+    /// User-configured switch hooks run around the whole flow: pre-switch hooks
+    /// before validation, post-switch / switch-failed hooks after it finishes.
+    ///
+    /// The switch itself holds the cross-process write lock so a concurrently
+    /// running GUI / CLI cannot interleave its own live-config writes; hooks run
+    /// outside it so a slow pre-switch hook does not block other instances.
     pub fn switch(state: &AppState, app_type: AppType, id: &str) -> Result<SwitchResult, AppError> {
+        if crate::settings::switch_hooks().is_empty() {
+            return Self::switch_inner(state, app_type, id);
+        }
+
+        let hook_ctx = SwitchHookContext::new(state, &app_type, id);
+        SwitchHookService::dispatch(hook_ctx.clone());
+        let result = Self::switch_inner(state, app_type, id);
+        match &result {
+            Ok(_) => SwitchHookService::dispatch(hook_ctx.with_event(SwitchHookEvent::PostSwitch)),
+            Err(e) => SwitchHookService::dispatch(
+                hook_ctx
+                    .with_event(SwitchHookEvent::SwitchFailed)
+                    .with_error(e.to_string()),
+            ),
+        }
+        result
+    }
+
+    fn switch_inner(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+    ) -> Result<SwitchResult, AppError> {
+        let _write_lock = crate::instance_lock::acquire("provider.switch")?;
+        let result = Self::switch_by_mode(state, app_type.clone(), id)?;
+        if let Err(e) = state.db.touch_provider_active(app_type.as_str(), id) {
+            log::warn!("记录供应商 {id} 最近使用时间失败: {e}");
+        }
+        status_file::refresh(&state.db, StatusSource::Switch);
+        Ok(result)
+    }
+
+    fn switch_by_mode(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+    ) -> Result<SwitchResult, AppError> {
         // Check if provider exists
         let providers = state.db.get_all_providers(app_type.as_str())?;
         let _provider = providers
@@ -2523,6 +2691,30 @@ impl ProviderService {
             return Self::switch_normal(state, app_type, id, &providers);
         }
 
+        if !proxy_override::supports_proxy_override(&app_type) {
+            return Self::switch_with_takeover_check(state, app_type, id, &providers);
+        }
+
+        // Per-provider proxy override (meta.useLocalProxy): release or engage the
+        // app takeover around the switch. Both steps take the per-app switch lock
+        // themselves, so they must run outside switch_with_takeover_check.
+        proxy_override::release_before_switch(state, &app_type, _provider)?;
+        let mut result = Self::switch_with_takeover_check(state, app_type.clone(), id, &providers)?;
+        proxy_override::engage_after_switch(state, &app_type, _provider, &mut result);
+        Ok(result)
+    }
+
+    /// Switch flow for apps whose live config may be owned by proxy takeover
+    fn switch_with_takeover_check(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        providers: &indexmap::IndexMap<String, Provider>,
+    ) -> Result<SwitchResult, AppError> {
+        let _provider = providers
+            .get(id)
+            .ok_or_else(|| AppError::Message(format!("供应商 {id} 不存在")))?;
+
         // Provider switches and takeover toggles both mutate live config and the
         // restore backup. Serialize them per app, then decide from the locked
         // current state so a just-started takeover cannot be overwritten by a
@@ -2588,7 +2780,7 @@ impl ProviderService {
         }
 
         // Normal mode: full switch with Live config write
-        Self::switch_normal(state, app_type, id, &providers)
+        Self::switch_normal(state, app_type, id, providers)
     }
 
     /// Normal switch flow (non-proxy mode)
@@ -2749,6 +2941,7 @@ impl ProviderService {
 
     /// Sync current provider to live configuration (re-export)
     pub fn sync_current_to_live(state: &AppState) -> Result<(), AppError> {
+        let _write_lock = crate::instance_lock::acquire("provider.sync_live")?;
         sync_current_to_live(state)
     }
 
@@ -2756,6 +2949,7 @@ impl ProviderService {
         state: &AppState,
         app_type: AppType,
     ) -> Result<(), AppError> {
+        let _write_lock = crate::instance_lock::acquire("provider.sync_live")?;
         if app_type.is_additive_mode() {
             return sync_current_provider_for_app_to_live(state, &app_type);
         }
@@ -3029,7 +3223,7 @@ impl ProviderService {
     /// `OPENROUTER_API_KEY` / `GOOGLE_API_KEY` 等回退）、各类 `*_AUTH_TOKEN` /
     /// 单数 `*_TOKEN`、AWS Bedrock / Vertex 凭据、以及通用 secret / password /
     /// 私钥命名。
-    fn is_sensitive_config_key(name: &str) -> bool {
+    pub(crate) fn is_sensitive_config_key(name: &str) -> bool {
         let upper = name.to_ascii_uppercase();
 
         // 单数 `_TOKEN` 命中 AWS_SESSION_TOKEN 等，但**不**误伤复数 `_TOKENS`
@@ -3069,75 +3263,11 @@ impl ProviderService {
     }
 
     /// Extract common config for Claude (JSON format)
+    ///
+    /// 供应商专属键（模型、端点、上下文窗口与任何凭据）按 `config_merge` 分类表剥离。
     fn extract_claude_common_config(settings: &Value) -> Result<String, AppError> {
         let mut config = settings.clone();
-
-        // 供应商专属的**非机密**字段（模型 + 端点），不应共享。凭据/机密不在此列举，
-        // 改由 `is_sensitive_config_key`（模式匹配）统一剥离，新供应商的 `*_API_KEY`
-        // 等无需再手工补名单即可被覆盖。
-        const ENV_PROVIDER_SPECIFIC_EXCLUDES: &[&str] = &[
-            "ANTHROPIC_MODEL",
-            "ANTHROPIC_REASONING_MODEL", // legacy: 已废弃，但旧配置可能残留
-            "ANTHROPIC_DEFAULT_HAIKU_MODEL",
-            "ANTHROPIC_DEFAULT_HAIKU_MODEL_NAME",
-            "ANTHROPIC_DEFAULT_OPUS_MODEL",
-            "ANTHROPIC_DEFAULT_OPUS_MODEL_NAME",
-            "ANTHROPIC_DEFAULT_SONNET_MODEL",
-            "ANTHROPIC_DEFAULT_SONNET_MODEL_NAME",
-            // Fable 是 v3.16.3 新增的第四档模型映射，与 haiku/sonnet/opus 同属供应商专属，
-            // 不得进入通用配置片段，否则会污染其它供应商（issue #4272）。
-            "ANTHROPIC_DEFAULT_FABLE_MODEL",
-            "ANTHROPIC_DEFAULT_FABLE_MODEL_NAME",
-            "CLAUDE_CODE_SUBAGENT_MODEL",
-            // Context limits follow the actual upstream model. Sharing these
-            // across providers can cap GPT/Kimi to the wrong window and make
-            // Claude Code compact too early or miss the upstream limit.
-            "CLAUDE_CODE_MAX_CONTEXT_TOKENS",
-            "CLAUDE_CODE_AUTO_COMPACT_WINDOW",
-            "ANTHROPIC_BASE_URL",
-        ];
-
-        const TOP_LEVEL_EXCLUDES: &[&str] = &[
-            "apiBaseUrl",
-            // Legacy model fields
-            "primaryModel",
-            "smallFastModel",
-        ];
-
-        // Remove env fields: provider-specific (models/endpoint) + 任何凭据键。
-        if let Some(env) = config.get_mut("env").and_then(|v| v.as_object_mut()) {
-            let sensitive: Vec<String> = env
-                .keys()
-                .filter(|k| Self::is_sensitive_config_key(k))
-                .cloned()
-                .collect();
-            for key in ENV_PROVIDER_SPECIFIC_EXCLUDES {
-                env.remove(*key);
-            }
-            for key in &sensitive {
-                env.remove(key);
-            }
-            // If env is empty after removal, remove the env object itself
-            if env.is_empty() {
-                config.as_object_mut().map(|obj| obj.remove("env"));
-            }
-        }
-
-        // Remove top-level fields: legacy model fields + 任何凭据键
-        // （例如非标准的顶层 apiKey / api_key / *_TOKEN）。
-        if let Some(obj) = config.as_object_mut() {
-            let sensitive: Vec<String> = obj
-                .keys()
-                .filter(|k| Self::is_sensitive_config_key(k))
-                .cloned()
-                .collect();
-            for key in TOP_LEVEL_EXCLUDES {
-                obj.remove(*key);
-            }
-            for key in &sensitive {
-                obj.remove(key);
-            }
-        }
+        config_merge::strip_provider_scoped_json(&AppType::Claude, &mut config);
 
         // Check if result is empty
         if config.as_object().is_none_or(|obj| obj.is_empty()) {
@@ -3164,19 +3294,10 @@ impl ProviderService {
             .parse::<toml_edit::DocumentMut>()
             .map_err(|e| AppError::Message(format!("TOML parse error: {e}")))?;
 
-        // Remove provider-specific fields.
+        // Remove provider-specific fields（含整张 model_providers 表与注入的
+        // experimental_bearer_token / model_catalog_json），见 `config_merge` 分类表。
+        config_merge::strip_provider_scoped_toml(&mut doc);
         let root = doc.as_table_mut();
-        root.remove("model");
-        root.remove("model_provider");
-        // Legacy/alt formats might use a top-level base_url.
-        root.remove("base_url");
-        // wire_api 与 base_url 同属供应商路由语义：无 model_provider 时
-        // update_codex_toml_field / 前端 setCodexWireApi 都会把它落在顶层，
-        // 进了片段会改写其它供应商的协议选择（chat vs responses）。
-        root.remove("wire_api");
-
-        // Remove entire model_providers table (provider-specific configuration)
-        root.remove("model_providers");
 
         // MCP 服务器归 DB mcp_servers 表所有：进了共享片段会绕过按应用的
         // 启用状态被合并进所有勾选通用配置的供应商，且在通用配置编辑框里
@@ -3195,15 +3316,8 @@ impl ProviderService {
             }
         }
 
-        // cc-switch 写 live 时注入的产物一律不进共享片段：
-        // - experimental_bearer_token 正常写在 [model_providers.<id>] 内（上面
-        //   整表已剥），但无活跃路由 / 内建保留 id / 路由表缺失三种 fallback
-        //   会落在顶层——不剥等于把 API 密钥写进共享片段。
-        root.remove("experimental_bearer_token");
-        // - model_catalog_json 指向按供应商生成的 catalog 投影文件（DB 为 SSOT）。
-        root.remove("model_catalog_json");
-        // - web_search 只剥 cc-switch 注入的 "disabled" 哨兵；用户手设的其它值
-        //   属于可共享偏好，保留。
+        // cc-switch 注入的 web_search 只剥 "disabled" 哨兵；用户手设的其它值
+        // 属于可共享偏好，保留。
         if root
             .get(crate::codex_config::CODEX_WEB_SEARCH_FIELD)
             .and_then(|item| item.as_str())
@@ -3233,16 +3347,15 @@ impl ProviderService {
 
     /// Extract common config for Gemini (JSON format)
     ///
-    /// Extracts `.env` values while excluding provider-specific credentials:
-    /// - GOOGLE_GEMINI_BASE_URL
-    /// - GEMINI_API_KEY
+    /// Extracts `.env` values while excluding provider-scoped keys
+    /// (GOOGLE_GEMINI_BASE_URL, GEMINI_API_KEY, GEMINI_MODEL and any credential).
     fn extract_gemini_common_config(settings: &Value) -> Result<String, AppError> {
         let env = settings.get("env").and_then(|v| v.as_object());
 
         let mut snippet = serde_json::Map::new();
         if let Some(env) = env {
             for (key, value) in env {
-                if key == "GOOGLE_GEMINI_BASE_URL" || key == "GEMINI_API_KEY" {
+                if config_merge::is_provider_scoped(&AppType::Gemini, &[key.as_str()]) {
                     continue;
                 }
                 let Value::String(v) = value else {
@@ -3311,6 +3424,7 @@ impl ProviderService {
     ///
     /// Returns `Ok(true)` if imported, `Ok(false)` if skipped.
     pub fn import_default_config(state: &AppState, app_type: AppType) -> Result<bool, AppError> {
+        let _write_lock = crate::instance_lock::acquire("provider.import_default")?;
         import_default_config(state, app_type)
     }
 
@@ -3321,6 +3435,13 @@ impl ProviderService {
         should_import_default_config_on_startup(state, app_type)
     }
 
+    /// Scan existing CLI configs that can be imported on first run (re-export)
+    pub fn scan_live_import_candidates(
+        state: &AppState,
+    ) -> Result<Vec<LiveImportCandidate>, AppError> {
+        scan_live_import_candidates(state)
+    }
+
     /// Read current live settings (re-export)
     pub fn read_live_settings(app_type: AppType) -> Result<Value, AppError> {
         read_live_settings(app_type)
@@ -3371,6 +3492,7 @@ impl ProviderService {
         app_type: AppType,
         updates: Vec<ProviderSortUpdate>,
     ) -> Result<bool, AppError> {
+        let _write_lock = crate::instance_lock::acquire("provider.sort")?;
         let mut providers = state.db.get_all_providers(app_type.as_str())?;
 
         for update in updates {
@@ -3383,6 +3505,53 @@ impl ProviderService {
         Ok(true)
     }
 
+    /// Assign explicit sort indices to providers that lack one (re-export)
+    pub fn ensure_sort_indices(state: &AppState, app_type: &AppType) -> Result<usize, AppError> {
+        ordering::ensure_sort_indices(state, app_type)
+    }
+
+    /// 复制供应商并应用点分路径覆盖，副本追加到列表末尾（累加模式应用不写入 Live 配置）
+    pub fn duplicate(
+        state: &AppState,
+        app_type: AppType,
+        id: &str,
+        overrides: &[ConfigOverride],
+    ) -> Result<Provider, AppError> {
+        let source = state
+            .db
+            .get_provider_by_id(id, app_type.as_str())?
+            .ok_or_else(|| i18n::error("provider.notFound", &[("id", id)]))?;
+        let copy = duplicate::duplicate(state, &app_type, &source, overrides)?;
+        let copy_id = copy.id.clone();
+        Self::add(state, app_type.clone(), copy, false)?;
+        log::info!(
+            "[Provider] {} 已复制供应商 {id} → {copy_id}",
+            app_type.as_str()
+        );
+        state
+            .db
+            .get_provider_by_id(&copy_id, app_type.as_str())?
+            .ok_or_else(|| AppError::Message(format!("Provider {copy_id} was not saved")))
+    }
+
+    /// Move the given providers to the front and renumber contiguously (re-export)
+    pub fn reorder(
+        state: &AppState,
+        app_type: &AppType,
+        front: &[String],
+    ) -> Result<Vec<String>, AppError> {
+        let _write_lock = crate::instance_lock::acquire("provider.reorder")?;
+        ordering::reorder(state, app_type, front)
+    }
+
+    /// Find a provider by id or display number (re-export)
+    pub fn resolve_by_number<'a>(
+        providers: &'a IndexMap<String, Provider>,
+        target: &str,
+    ) -> Option<&'a Provider> {
+        ordering::resolve(providers, target)
+    }
+
     /// Query provider usage (re-export)
     pub async fn query_usage(
         state: &AppState,
@@ -3510,14 +3679,23 @@ impl ProviderService {
                 }
             }
             AppType::OpenCode => {
-                // OpenCode uses a different config structure: { npm, options, models }
-                // Basic validation - must be an object
-                if !provider.settings_config.is_object() {
-                    return Err(AppError::localized(
-                        "provider.opencode.settings.not_object",
-                        "OpenCode 配置必须是 JSON 对象",
-                        "OpenCode configuration must be a JSON object",
-                    ));
+                // OpenCode uses a different config structure: { npm, options, models }.
+                // OMO / OMO Slim entries store agent settings instead of a provider block.
+                if matches!(provider.category.as_deref(), Some("omo" | "omo-slim")) {
+                    if !provider.settings_config.is_object() {
+                        return Err(AppError::localized(
+                            "provider.opencode.settings.not_object",
+                            "OpenCode 配置必须是 JSON 对象",
+                            "OpenCode configuration must be a JSON object",
+                        ));
+                    }
+                } else {
+                    crate::opencode_config::validate_provider_fragment(
+                        crate::opencode_config::provider_fragment(
+                            &provider.id,
+                            &provider.settings_config,
+                        ),
+                    )?;
                 }
             }
             AppType::OpenClaw => {
@@ -3554,6 +3732,24 @@ impl ProviderService {
             if let Some(usage_script) = &meta.usage_script {
                 validate_usage_script(usage_script)?;
             }
+            if let Some(scheme) = meta.custom_auth() {
+                crate::proxy::custom_auth::validate(scheme).map_err(|e| {
+                    AppError::localized(
+                        "provider.custom_auth.invalid",
+                        format!("自定义鉴权配置无效: {e}"),
+                        format!("Invalid custom authentication: {e}"),
+                    )
+                })?;
+            }
+            for window in &meta.maintenance_windows {
+                crate::services::maintenance::validate_window(window).map_err(|e| {
+                    AppError::localized(
+                        "provider.maintenance_window.invalid",
+                        format!("维护时段配置无效: {e}"),
+                        format!("Invalid maintenance window: {e}"),
+                    )
+                })?;
+            }
         }
 
         Ok(())
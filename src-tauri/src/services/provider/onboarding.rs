@@ -0,0 +1,98 @@
+//! 首次使用引导：扫描已有 CLI 配置，供一键导入
+//!
+//! 新用户通常已经有可用的 `~/.claude/settings.json`、`~/.codex/auth.json + config.toml`、
+//! `~/.gemini/.env`。这里只负责"发现"，真正的导入复用 [`super::import_default_config`]，
+//! 导入出的供应商名为 [`LIVE_IMPORT_PROVIDER_NAME`]。
+
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::store::AppState;
+
+/// 从 live 配置导入的供应商名称
+pub const LIVE_IMPORT_PROVIDER_NAME: &str = "Imported from live config";
+
+/// 可导入的 live 配置
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveImportCandidate {
+    pub app: String,
+    /// 检测到的配置文件（仅列出存在的文件）
+    pub files: Vec<String>,
+    /// 该应用已有用户自建供应商，导入会被跳过
+    pub already_configured: bool,
+    /// live 配置当前被代理接管，不能导入
+    pub taken_over: bool,
+}
+
+/// 单个应用的导入结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveImportOutcome {
+    pub app: String,
+    pub imported: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl LiveImportCandidate {
+    pub fn importable(&self) -> bool {
+        !self.already_configured && !self.taken_over
+    }
+}
+
+/// 各应用参与首启导入的 live 文件
+fn live_config_files(app_type: &AppType) -> Vec<PathBuf> {
+    match app_type {
+        AppType::Claude => vec![crate::config::get_claude_settings_path()],
+        AppType::Codex => vec![
+            crate::codex_config::get_codex_auth_path(),
+            crate::codex_config::get_codex_config_path(),
+        ],
+        AppType::Gemini => vec![
+            crate::gemini_config::get_gemini_env_path(),
+            crate::gemini_config::get_gemini_settings_path(),
+        ],
+        AppType::GrokBuild => vec![crate::grok_config::get_grok_config_path()],
+        // Claude Desktop 有专门的兼容导入；累加模式应用走各自的 import_xxx_from_live
+        AppType::ClaudeDesktop | AppType::OpenCode | AppType::OpenClaw | AppType::Hermes => {
+            Vec::new()
+        }
+    }
+}
+
+/// 扫描本机已有的 CLI 配置
+///
+/// 只返回至少存在一个配置文件的应用；Gemini 必须存在 `.env`（与导入逻辑一致）。
+pub fn scan_live_import_candidates(state: &AppState) -> Result<Vec<LiveImportCandidate>, AppError> {
+    let mut candidates = Vec::new();
+
+    for app_type in AppType::all().filter(|t| !t.is_additive_mode()) {
+        let files: Vec<String> = live_config_files(&app_type)
+            .into_iter()
+            .filter(|path| path.exists())
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        if matches!(app_type, AppType::Gemini)
+            && !crate::gemini_config::get_gemini_env_path().exists()
+        {
+            continue;
+        }
+
+        candidates.push(LiveImportCandidate {
+            app: app_type.as_str().to_string(),
+            files,
+            already_configured: state.db.has_non_official_seed_provider(app_type.as_str())?,
+            taken_over: state
+                .proxy_service
+                .detect_takeover_in_live_config_for_app(&app_type),
+        });
+    }
+
+    Ok(candidates)
+}
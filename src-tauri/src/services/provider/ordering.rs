@@ -0,0 +1,156 @@
+//! Stable provider ordering
+//!
+//! 首页列表、`cc-switch provider list` 与 `provider use <编号>` 共用同一顺序：
+//! `sort_index` 升序，未设置的排在最后（按创建时间、id）。编号即 `sort_index + 1`，
+//! 新增供应商追加在末尾、删除后留下空号，编号只会因显式重排（拖拽、`reorder`）而改变。
+
+use indexmap::IndexMap;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+/// 下一个可用的序号（当前最大序号 + 1）
+pub(crate) fn next_sort_index<'a>(providers: impl IntoIterator<Item = &'a Provider>) -> usize {
+    providers
+        .into_iter()
+        .filter_map(|provider| provider.sort_index)
+        .max()
+        .map_or(0, |max| max + 1)
+}
+
+/// 为缺少 sort_index 的供应商补齐显式序号，返回补齐的数量
+///
+/// 未设置序号的供应商本就排在已设置的之后，依次追加在最大序号之后，
+/// 补齐前后显示顺序不变。
+pub fn ensure_sort_indices(state: &AppState, app_type: &AppType) -> Result<usize, AppError> {
+    let providers = state.db.get_all_providers(app_type.as_str())?;
+    let mut next = next_sort_index(providers.values());
+    let mut assigned = 0;
+    for mut provider in providers.into_values() {
+        if provider.sort_index.is_some() {
+            continue;
+        }
+        provider.sort_index = Some(next);
+        state.db.save_provider(app_type.as_str(), &provider)?;
+        next += 1;
+        assigned += 1;
+    }
+    Ok(assigned)
+}
+
+/// 按编号（`sort_index + 1`）或 id 查找供应商；同名 id 优先
+pub fn resolve<'a>(
+    providers: &'a IndexMap<String, Provider>,
+    target: &str,
+) -> Option<&'a Provider> {
+    if let Some(provider) = providers.get(target) {
+        return Some(provider);
+    }
+    let number: usize = target.strip_prefix('#').unwrap_or(target).parse().ok()?;
+    let index = number.checked_sub(1)?;
+    providers
+        .values()
+        .find(|provider| provider.sort_index == Some(index))
+}
+
+/// 计算重排后的顺序：`front` 中的供应商按给定顺序排在最前，其余保持原有相对顺序
+fn reordered_ids(current: &[String], front: &[String]) -> Result<Vec<String>, AppError> {
+    for (index, id) in front.iter().enumerate() {
+        if !current.contains(id) {
+            return Err(AppError::localized(
+                "provider.not_found",
+                format!("供应商不存在: {id}"),
+                format!("Provider not found: {id}"),
+            ));
+        }
+        if front[..index].contains(id) {
+            return Err(AppError::localized(
+                "provider.reorder.duplicate",
+                format!("供应商 {id} 重复出现"),
+                format!("Provider {id} is listed more than once"),
+            ));
+        }
+    }
+    Ok(front
+        .iter()
+        .chain(current.iter().filter(|id| !front.contains(id)))
+        .cloned()
+        .collect())
+}
+
+/// 显式重排：`front` 排在最前，其余保持相对顺序，序号重新从 0 连续编号（同时消除空号）
+///
+/// 返回重排后的 id 列表。
+pub fn reorder(
+    state: &AppState,
+    app_type: &AppType,
+    front: &[String],
+) -> Result<Vec<String>, AppError> {
+    let providers = state.db.get_all_providers(app_type.as_str())?;
+    let current: Vec<String> = providers.keys().cloned().collect();
+    let ordered = reordered_ids(&current, front)?;
+    for (index, id) in ordered.iter().enumerate() {
+        let Some(provider) = providers.get(id) else {
+            continue;
+        };
+        if provider.sort_index == Some(index) {
+            continue;
+        }
+        let mut provider = provider.clone();
+        provider.sort_index = Some(index);
+        state.db.save_provider(app_type.as_str(), &provider)?;
+    }
+    log::info!(
+        "[Provider] {} 供应商顺序已更新: {}",
+        app_type.as_str(),
+        ordered.join(" → ")
+    );
+    Ok(ordered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn provider(id: &str, sort_index: Option<usize>) -> Provider {
+        let mut provider = Provider::with_id(id.to_string(), id.to_uppercase(), json!({}), None);
+        provider.sort_index = sort_index;
+        provider
+    }
+
+    #[test]
+    fn numbers_follow_sort_index_and_survive_gaps() {
+        let providers: IndexMap<String, Provider> = [
+            provider("a", Some(0)),
+            provider("c", Some(2)),
+            provider("7", Some(3)),
+        ]
+        .into_iter()
+        .map(|p| (p.id.clone(), p))
+        .collect();
+
+        assert_eq!(next_sort_index(providers.values()), 4);
+        assert_eq!(resolve(&providers, "1").map(|p| p.id.as_str()), Some("a"));
+        assert_eq!(resolve(&providers, "#3").map(|p| p.id.as_str()), Some("c"));
+        // 删除 b 后编号 2 空缺，不会顺延到 c
+        assert!(resolve(&providers, "2").is_none());
+        assert!(resolve(&providers, "0").is_none());
+        // 数字 id 优先按 id 匹配
+        assert_eq!(resolve(&providers, "7").map(|p| p.id.as_str()), Some("7"));
+    }
+
+    #[test]
+    fn reorder_moves_listed_ids_to_front() {
+        let current: Vec<String> = ["a", "b", "c", "d"].map(String::from).to_vec();
+        assert_eq!(
+            reordered_ids(&current, &["c".to_string(), "a".to_string()]).unwrap(),
+            ["c", "a", "b", "d"]
+        );
+        assert_eq!(reordered_ids(&current, &[]).unwrap(), current);
+        assert!(reordered_ids(&current, &["x".to_string()]).is_err());
+        assert!(reordered_ids(&current, &["a".to_string(), "a".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! 供应商级代理覆盖（`meta.useLocalProxy`）
+//!
+//! 应用级接管是"全有或全无"：一旦开启，该应用所有供应商都经本地代理转发。
+//! 供应商级覆盖让只有打开开关的供应商走代理：
+//! - 切换到开启覆盖的供应商：先按普通流程写入 Live，再为该应用接管 Live
+//! - 切换到未开启的供应商：若接管是由覆盖发起的，先恢复 Live 并关闭接管，再直连写入
+//!
+//! 用户手动开启的应用级接管不受影响（数据库标记区分两种来源）。
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::store::AppState;
+
+use super::SwitchResult;
+
+/// 支持供应商级代理覆盖的应用（与 Live 接管支持范围一致）
+pub(crate) fn supports_proxy_override(app_type: &AppType) -> bool {
+    matches!(
+        app_type,
+        AppType::Claude | AppType::Codex | AppType::Gemini | AppType::GrokBuild
+    )
+}
+
+fn wants_local_proxy(provider: &Provider) -> bool {
+    provider
+        .meta
+        .as_ref()
+        .is_some_and(|meta| meta.uses_local_proxy())
+}
+
+/// 切换前：目标供应商不需要代理时，释放由覆盖发起的接管
+pub(crate) fn release_before_switch(
+    state: &AppState,
+    app_type: &AppType,
+    target: &Provider,
+) -> Result<(), AppError> {
+    let app = app_type.as_str();
+    if wants_local_proxy(target) || !state.db.is_provider_proxy_override_active(app)? {
+        return Ok(());
+    }
+
+    log::info!("供应商 {} 未开启代理覆盖，恢复 {app} 直连", target.id);
+    futures::executor::block_on(state.proxy_service.set_takeover_for_app(app, false))
+        .map_err(|e| AppError::Message(format!("关闭 {app} 代理覆盖失败: {e}")))?;
+    state.db.set_provider_proxy_override_active(app, false)
+}
+
+/// 切换后：目标供应商需要代理且应用尚未被接管时，为其接管 Live
+///
+/// 切换本身已经完成，接管失败只记为警告，不回滚切换。
+pub(crate) fn engage_after_switch(
+    state: &AppState,
+    app_type: &AppType,
+    target: &Provider,
+    result: &mut SwitchResult,
+) {
+    if !wants_local_proxy(target) {
+        return;
+    }
+    let app = app_type.as_str();
+    let already_taken_over = futures::executor::block_on(state.db.get_live_backup(app))
+        .ok()
+        .flatten()
+        .is_some()
+        || state
+            .proxy_service
+            .detect_takeover_in_live_config_for_app(app_type);
+    if already_taken_over {
+        return;
+    }
+
+    log::info!("供应商 {} 开启了代理覆盖，接管 {app} Live 配置", target.id);
+    let engaged = futures::executor::block_on(state.proxy_service.set_takeover_for_app(app, true))
+        .map_err(AppError::Message)
+        .and_then(|_| state.db.set_provider_proxy_override_active(app, true));
+    if let Err(e) = engaged {
+        log::warn!("为供应商 {} 开启代理覆盖失败: {e}", target.id);
+        result
+            .warnings
+            .push(format!("proxy_override_failed:{}", target.id));
+    }
+}
@@ -0,0 +1,215 @@
+//! 供应商"最近使用"追踪与闲置清理
+//!
+//! 最近使用时间取切换时间、经代理请求时间中较晚者；从未记录过的供应商以创建时间兜底。
+//! 当前供应商始终视为活跃。闲置过久的供应商可归档：完整配置写入
+//! `~/.cc-switch/archive/` 下的 JSON 文件后再从列表中删除，需要时可手动导入。
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::config::{get_app_config_dir, write_json_file};
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::provider::ProviderService;
+use crate::store::AppState;
+
+/// 默认闲置阈值（天）
+pub const DEFAULT_STALE_DAYS: i64 = 90;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderLastSeen {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub last_active_at: Option<i64>,
+    pub last_traffic_at: Option<i64>,
+    /// 最近使用时间（Unix 秒）；无任何记录时为 None
+    pub last_seen_at: Option<i64>,
+    /// 距最近使用的整天数；当前供应商为 None
+    pub idle_days: Option<i64>,
+    pub is_current: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveResult {
+    pub path: PathBuf,
+    pub archived: Vec<String>,
+}
+
+fn idle_days(last_seen_at: Option<i64>, now: i64) -> Option<i64> {
+    last_seen_at.map(|at| ((now - at) / SECONDS_PER_DAY).max(0))
+}
+
+pub struct ProviderActivityService;
+
+impl ProviderActivityService {
+    /// 列出某应用全部供应商的最近使用情况（按首页顺序）
+    pub fn list(state: &AppState, app_type: &AppType) -> Result<Vec<ProviderLastSeen>, AppError> {
+        let current = crate::settings::get_effective_current_provider(&state.db, app_type)?;
+        Self::list_at(
+            state,
+            app_type,
+            current.as_deref(),
+            chrono::Utc::now().timestamp(),
+        )
+    }
+
+    fn list_at(
+        state: &AppState,
+        app_type: &AppType,
+        current: Option<&str>,
+        now: i64,
+    ) -> Result<Vec<ProviderLastSeen>, AppError> {
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+        let mut activity = state.db.get_provider_activity(app_type.as_str())?;
+
+        Ok(providers
+            .values()
+            .map(|provider| {
+                let record = activity.remove(&provider.id).unwrap_or_default();
+                let last_seen_at = record
+                    .last_active_at
+                    .max(record.last_traffic_at)
+                    .or(provider.created_at.map(|ms| ms / 1000));
+                let is_current = current == Some(provider.id.as_str());
+                ProviderLastSeen {
+                    provider_id: provider.id.clone(),
+                    provider_name: provider.name.clone(),
+                    last_active_at: record.last_active_at,
+                    last_traffic_at: record.last_traffic_at,
+                    last_seen_at,
+                    idle_days: if is_current {
+                        None
+                    } else {
+                        idle_days(last_seen_at, now)
+                    },
+                    is_current,
+                }
+            })
+            .collect())
+    }
+
+    /// 闲置至少 `min_days` 天的供应商，最久未用的在前
+    ///
+    /// 没有任何时间记录的供应商无法判断，不列入。
+    pub fn stale(
+        state: &AppState,
+        app_type: &AppType,
+        min_days: i64,
+    ) -> Result<Vec<ProviderLastSeen>, AppError> {
+        let mut stale: Vec<_> = Self::list(state, app_type)?
+            .into_iter()
+            .filter(|item| item.idle_days.is_some_and(|days| days >= min_days))
+            .collect();
+        stale.sort_by_key(|item| item.last_seen_at);
+        Ok(stale)
+    }
+
+    /// 归档供应商：导出到归档文件后删除
+    ///
+    /// 当前供应商不能归档；任何一个 ID 无效时整体不执行。
+    pub fn archive(
+        state: &AppState,
+        app_type: AppType,
+        provider_ids: &[String],
+    ) -> Result<ArchiveResult, AppError> {
+        let providers = state.db.get_all_providers(app_type.as_str())?;
+        let current = crate::settings::get_effective_current_provider(&state.db, &app_type)?;
+
+        let mut selected: Vec<&Provider> = Vec::with_capacity(provider_ids.len());
+        for id in provider_ids {
+            if current.as_deref() == Some(id.as_str()) {
+                return Err(AppError::localized(
+                    "provider.archive.current",
+                    format!("不能归档当前使用中的供应商: {id}"),
+                    format!("Cannot archive the provider currently in use: {id}"),
+                ));
+            }
+            let provider = providers.get(id).ok_or_else(|| {
+                AppError::localized(
+                    "provider.not_found",
+                    format!("供应商不存在: {id}"),
+                    format!("Provider not found: {id}"),
+                )
+            })?;
+            selected.push(provider);
+        }
+
+        let path = get_app_config_dir().join("archive").join(format!(
+            "providers-{}-{}.json",
+            app_type.as_str(),
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        write_json_file(
+            &path,
+            &serde_json::json!({
+                "appType": app_type.as_str(),
+                "archivedAt": chrono::Utc::now().timestamp(),
+                "providers": selected,
+            }),
+        )?;
+
+        let mut archived = Vec::with_capacity(selected.len());
+        for provider in selected {
+            ProviderService::delete(state, app_type.clone(), &provider.id)?;
+            archived.push(provider.id.clone());
+        }
+        log::info!(
+            "已归档 {} 个 {} 供应商到 {}",
+            archived.len(),
+            app_type.as_str(),
+            path.display()
+        );
+        Ok(ArchiveResult { path, archived })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    #[test]
+    fn idle_days_prefer_latest_activity_and_skip_current() {
+        let db = Arc::new(Database::memory().expect("in-memory database"));
+        let state = AppState::new(db.clone());
+        let day = SECONDS_PER_DAY;
+        let now = 1_000 * day;
+
+        let mut old = Provider::with_id("old".into(), "Old".into(), json!({}), None);
+        old.created_at = Some((now - 200 * day) * 1000);
+        let mut used = Provider::with_id("used".into(), "Used".into(), json!({}), None);
+        used.created_at = Some((now - 300 * day) * 1000);
+        db.save_provider("claude", &old).unwrap();
+        db.save_provider("claude", &used).unwrap();
+        {
+            let conn = crate::database::lock_conn!(db.conn);
+            Database::touch_provider_traffic_on_conn(&conn, "claude", "used", now - 5 * day)
+                .unwrap();
+            Database::touch_provider_traffic_on_conn(&conn, "claude", "gone", now).unwrap();
+        }
+
+        let list =
+            ProviderActivityService::list_at(&state, &AppType::Claude, Some("used"), now).unwrap();
+        let old = list.iter().find(|item| item.provider_id == "old").unwrap();
+        assert_eq!(old.idle_days, Some(200));
+        assert_eq!(old.last_traffic_at, None);
+
+        let used = list.iter().find(|item| item.provider_id == "used").unwrap();
+        assert_eq!(used.last_seen_at, Some(now - 5 * day));
+        assert!(used.is_current);
+        assert_eq!(used.idle_days, None);
+        assert!(db
+            .get_provider_activity("claude")
+            .unwrap()
+            .get("gone")
+            .is_none());
+    }
+}
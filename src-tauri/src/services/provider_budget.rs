@@ -0,0 +1,423 @@
+//! 供应商预算：按日/按月的美元或 token 上限
+//!
+//! 预算存放在供应商 meta 中（`limitDailyUsd` / `limitMonthlyUsd` /
+//! `limitDailyTokens` / `limitMonthlyTokens` / `budgetHardLimit`）。
+//!
+//! - 代理每记录一次有用量的请求，重新核算该供应商的消费，首次跨过 80% / 100%
+//!   时向前端发送 `provider-budget-alert` 事件（同一周期内每档只提醒一次）；
+//! - 开启硬上限的供应商达到 100% 后不再参与路由，请求交给故障转移队列中的其他供应商。
+//!   判定结果缓存一分钟，调高预算后最迟一分钟恢复路由。
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::database::Database;
+use crate::provider::Provider;
+use crate::services::notifier::{localized_text, Notification, NotifierService};
+use crate::settings::NotificationEvent;
+
+/// 前端监听的事件名
+pub const EVENT_PROVIDER_BUDGET_ALERT: &str = "provider-budget-alert";
+
+/// 提醒阈值
+const WARNING_RATIO: f64 = 0.8;
+
+/// 硬上限判定缓存时长
+const EXHAUSTED_CACHE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    Daily,
+    Monthly,
+}
+
+impl BudgetPeriod {
+    /// 当前周期的标识（本地时间），用于同一周期内去重提醒
+    fn current_key(self) -> String {
+        let now = chrono::Local::now();
+        match self {
+            BudgetPeriod::Daily => now.format("%Y-%m-%d").to_string(),
+            BudgetPeriod::Monthly => now.format("%Y-%m").to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetMetric {
+    Usd,
+    Tokens,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetLevel {
+    /// 已用 80% 以上
+    Warning,
+    /// 已达上限
+    Exceeded,
+}
+
+/// 单项预算的消费情况
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BudgetCheck {
+    pub period: BudgetPeriod,
+    pub metric: BudgetMetric,
+    pub used: f64,
+    pub limit: f64,
+}
+
+impl BudgetCheck {
+    pub fn level(&self) -> Option<BudgetLevel> {
+        if self.limit <= 0.0 {
+            return None;
+        }
+        let ratio = self.used / self.limit;
+        if ratio >= 1.0 {
+            Some(BudgetLevel::Exceeded)
+        } else if ratio >= WARNING_RATIO {
+            Some(BudgetLevel::Warning)
+        } else {
+            None
+        }
+    }
+}
+
+/// 供应商预算设置与今日/本月消费
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BudgetUsage {
+    pub daily_usd: f64,
+    pub daily_usd_limit: Option<f64>,
+    pub monthly_usd: f64,
+    pub monthly_usd_limit: Option<f64>,
+    pub daily_tokens: u64,
+    pub daily_token_limit: Option<u64>,
+    pub monthly_tokens: u64,
+    pub monthly_token_limit: Option<u64>,
+    pub hard_limit: bool,
+}
+
+impl BudgetUsage {
+    /// 已配置的各项预算
+    pub fn checks(&self) -> Vec<BudgetCheck> {
+        let usd = |period, used, limit: Option<f64>| {
+            limit.map(|limit| BudgetCheck {
+                period,
+                metric: BudgetMetric::Usd,
+                used,
+                limit,
+            })
+        };
+        let tokens = |period, used: u64, limit: Option<u64>| {
+            limit.map(|limit| BudgetCheck {
+                period,
+                metric: BudgetMetric::Tokens,
+                used: used as f64,
+                limit: limit as f64,
+            })
+        };
+        [
+            usd(BudgetPeriod::Daily, self.daily_usd, self.daily_usd_limit),
+            usd(
+                BudgetPeriod::Monthly,
+                self.monthly_usd,
+                self.monthly_usd_limit,
+            ),
+            tokens(
+                BudgetPeriod::Daily,
+                self.daily_tokens,
+                self.daily_token_limit,
+            ),
+            tokens(
+                BudgetPeriod::Monthly,
+                self.monthly_tokens,
+                self.monthly_token_limit,
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// 指定周期内任一预算（美元或 token）已达上限
+    pub fn period_exceeded(&self, period: BudgetPeriod) -> bool {
+        self.checks()
+            .iter()
+            .any(|check| check.period == period && check.level() == Some(BudgetLevel::Exceeded))
+    }
+
+    /// 开启了硬上限且任一预算已达上限
+    pub fn is_exhausted(&self) -> bool {
+        self.hard_limit
+            && (self.period_exceeded(BudgetPeriod::Daily)
+                || self.period_exceeded(BudgetPeriod::Monthly))
+    }
+}
+
+/// 预算提醒事件
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderBudgetAlert {
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub period: BudgetPeriod,
+    pub metric: BudgetMetric,
+    pub level: BudgetLevel,
+    pub used: f64,
+    pub limit: f64,
+    pub hard_limit: bool,
+}
+
+type AlertKey = (String, String, BudgetPeriod, BudgetMetric);
+
+/// 每项预算在当前周期内已提醒到的最高档位
+static ALERTED: LazyLock<Mutex<HashMap<AlertKey, (String, BudgetLevel)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// (app_type, provider_id) -> (判定时间, 是否已达硬上限)
+static EXHAUSTED: LazyLock<Mutex<HashMap<(String, String), (Instant, bool)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub struct ProviderBudgetService;
+
+impl ProviderBudgetService {
+    /// 代理记录一次有用量的请求后调用：刷新硬上限缓存，跨档时发出提醒
+    pub fn on_usage_recorded(db: &Database, app_type: &str, provider_id: &str) {
+        let provider = match db.get_provider_by_id(provider_id, app_type) {
+            Ok(Some(provider)) => provider,
+            Ok(None) => return,
+            Err(e) => {
+                log::debug!("[Budget] 读取供应商失败: {e}");
+                return;
+            }
+        };
+        if !has_budget(&provider) {
+            return;
+        }
+        let usage = match db.provider_budget_usage(provider_id, app_type) {
+            Ok(usage) => usage,
+            Err(e) => {
+                log::warn!("[Budget] 核算供应商 {provider_id} 预算失败: {e}");
+                return;
+            }
+        };
+
+        if let Ok(mut cache) = EXHAUSTED.lock() {
+            cache.insert(
+                (app_type.to_string(), provider_id.to_string()),
+                (Instant::now(), usage.is_exhausted()),
+            );
+        }
+
+        for check in usage.checks() {
+            let Some(level) = check.level() else {
+                continue;
+            };
+            if !Self::should_alert(app_type, provider_id, &check, level) {
+                continue;
+            }
+            let alert = ProviderBudgetAlert {
+                app_type: app_type.to_string(),
+                provider_id: provider_id.to_string(),
+                provider_name: provider.name.clone(),
+                period: check.period,
+                metric: check.metric,
+                level,
+                used: check.used,
+                limit: check.limit,
+                hard_limit: usage.hard_limit,
+            };
+            log::info!(
+                "[Budget] 供应商 {} 的{:?}预算（{:?}）已用 {:.2}/{:.2}",
+                provider.name,
+                check.period,
+                check.metric,
+                check.used,
+                check.limit
+            );
+            Self::notify(&alert);
+            crate::usage_events::emit(EVENT_PROVIDER_BUDGET_ALERT, alert);
+        }
+    }
+
+    /// 推送到订阅了预算提醒的外部通知渠道（去重已由 `should_alert` 保证）
+    fn notify(alert: &ProviderBudgetAlert) {
+        let (zh_period, en_period) = match alert.period {
+            BudgetPeriod::Daily => ("今日", "daily"),
+            BudgetPeriod::Monthly => ("本月", "monthly"),
+        };
+        let fmt = crate::i18n::LocaleFormat::current();
+        let amount = |value: f64| match alert.metric {
+            BudgetMetric::Usd => fmt.usd(value),
+            BudgetMetric::Tokens => format!("{} tokens", fmt.integer(value.round() as i64)),
+        };
+        let (used, limit) = (amount(alert.used), amount(alert.limit));
+        let (title, body) = match alert.level {
+            BudgetLevel::Warning => (
+                localized_text("预算即将用尽", "Budget almost used up"),
+                localized_text(
+                    format!(
+                        "{}（{}）{zh_period}已用 {used} / {limit}",
+                        alert.provider_name, alert.app_type
+                    ),
+                    format!(
+                        "{} ({}) has used {used} of its {en_period} {limit} budget",
+                        alert.provider_name, alert.app_type
+                    ),
+                ),
+            ),
+            BudgetLevel::Exceeded => (
+                localized_text("预算已用尽", "Budget exceeded"),
+                localized_text(
+                    format!(
+                        "{}（{}）{zh_period}已用 {used}，超过上限 {limit}",
+                        alert.provider_name, alert.app_type
+                    ),
+                    format!(
+                        "{} ({}) has used {used}, over its {en_period} {limit} budget",
+                        alert.provider_name, alert.app_type
+                    ),
+                ),
+            ),
+        };
+        NotifierService::dispatch(Notification::new(
+            NotificationEvent::BudgetAlert,
+            title,
+            body,
+        ));
+    }
+
+    /// 同一周期内每档只提醒一次；进入新周期后重新计数
+    fn should_alert(
+        app_type: &str,
+        provider_id: &str,
+        check: &BudgetCheck,
+        level: BudgetLevel,
+    ) -> bool {
+        let Ok(mut alerted) = ALERTED.lock() else {
+            return false;
+        };
+        let key = (
+            app_type.to_string(),
+            provider_id.to_string(),
+            check.period,
+            check.metric,
+        );
+        let period_key = check.period.current_key();
+        match alerted.get(&key) {
+            Some((previous_period, previous_level))
+                if *previous_period == period_key && *previous_level >= level =>
+            {
+                false
+            }
+            _ => {
+                alerted.insert(key, (period_key, level));
+                true
+            }
+        }
+    }
+
+    /// 路由：移除已达硬上限的供应商，返回被移除的数量
+    pub fn remove_exhausted(db: &Database, app_type: &str, providers: &mut Vec<Provider>) -> usize {
+        let before = providers.len();
+        providers.retain(|provider| {
+            let exhausted = Self::is_exhausted(db, app_type, provider);
+            if exhausted {
+                log::info!("[Budget] 供应商 {} 已达预算硬上限，跳过", provider.name);
+            }
+            !exhausted
+        });
+        before - providers.len()
+    }
+
+    fn is_exhausted(db: &Database, app_type: &str, provider: &Provider) -> bool {
+        let hard_limit = provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.budget_hard_limit)
+            .unwrap_or(false);
+        if !hard_limit || !has_budget(provider) {
+            return false;
+        }
+
+        let key = (app_type.to_string(), provider.id.clone());
+        if let Some((at, exhausted)) = EXHAUSTED.lock().ok().and_then(|c| c.get(&key).copied()) {
+            if at.elapsed() < EXHAUSTED_CACHE_TTL {
+                return exhausted;
+            }
+        }
+        let exhausted = db
+            .provider_budget_usage(&provider.id, app_type)
+            .map(|usage| usage.is_exhausted())
+            .unwrap_or(false);
+        if let Ok(mut cache) = EXHAUSTED.lock() {
+            cache.insert(key, (Instant::now(), exhausted));
+        }
+        exhausted
+    }
+}
+
+fn has_budget(provider: &Provider) -> bool {
+    provider.meta.as_ref().is_some_and(|meta| {
+        meta.limit_daily_usd.is_some()
+            || meta.limit_monthly_usd.is_some()
+            || meta.limit_daily_tokens.is_some()
+            || meta.limit_monthly_tokens.is_some()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_follow_thresholds() {
+        let check = |used| BudgetCheck {
+            period: BudgetPeriod::Daily,
+            metric: BudgetMetric::Usd,
+            used,
+            limit: 10.0,
+        };
+        assert_eq!(check(7.9).level(), None);
+        assert_eq!(check(8.0).level(), Some(BudgetLevel::Warning));
+        assert_eq!(check(10.0).level(), Some(BudgetLevel::Exceeded));
+    }
+
+    #[test]
+    fn token_budget_counts_towards_exhaustion_only_with_hard_limit() {
+        let mut usage = BudgetUsage {
+            monthly_tokens: 1_000_000,
+            monthly_token_limit: Some(1_000_000),
+            daily_usd: 1.0,
+            daily_usd_limit: Some(5.0),
+            ..Default::default()
+        };
+        assert!(usage.period_exceeded(BudgetPeriod::Monthly));
+        assert!(!usage.period_exceeded(BudgetPeriod::Daily));
+        assert!(!usage.is_exhausted());
+
+        usage.hard_limit = true;
+        assert!(usage.is_exhausted());
+    }
+
+    #[test]
+    fn alerts_once_per_level_per_period() {
+        let check = BudgetCheck {
+            period: BudgetPeriod::Monthly,
+            metric: BudgetMetric::Tokens,
+            used: 90.0,
+            limit: 100.0,
+        };
+        let alert =
+            |level| ProviderBudgetService::should_alert("claude", "budget-test", &check, level);
+        assert!(alert(BudgetLevel::Warning));
+        assert!(!alert(BudgetLevel::Warning));
+        assert!(alert(BudgetLevel::Exceeded));
+        assert!(!alert(BudgetLevel::Warning));
+    }
+}
@@ -0,0 +1,343 @@
+//! 供应商变更公告：解析上游响应中的弃用 / 下线声明
+//!
+//! 上游在计划下线接口或模型时，通常会在响应头里提前声明：
+//!
+//! - `Deprecation`（RFC 9745，`@<unix 秒>`；旧草案为 `true` 或 HTTP-date）
+//! - `Sunset`（RFC 8594，HTTP-date，接口停止服务的时间）
+//! - `Link: <...>; rel="deprecation"` / `rel="sunset"`（迁移说明链接）
+//! - `Warning: 299 - "..."` 以及部分网关自定义的 `x-*-deprecation` 提示文本
+//!
+//! 代理每次收到上游响应后调用 [`ProviderNoticeService::observe`]：内容与上次相同
+//! 时只在内存里去重，变化时写入 `provider_notices` 表并发送 `provider-notice`
+//! 事件，前端据此展示「供应商宣布了变更」横幅。用户关闭后，同一公告不再提醒，
+//! 公告内容变化（例如新公布了下线时间）会重新提醒。
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use http::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use crate::database::Database;
+use crate::error::AppError;
+
+/// 前端监听的事件名
+pub const EVENT_PROVIDER_NOTICE: &str = "provider-notice";
+
+/// 内容未变时刷新 last_seen_at 的最小间隔（秒），避免每个请求都写库
+const TOUCH_INTERVAL_SECS: i64 = 60 * 60;
+
+/// 提示文本最大长度
+const MAX_MESSAGE_CHARS: usize = 300;
+
+/// 各网关自定义的弃用提示头
+const VENDOR_MESSAGE_HEADERS: &[&str] = &[
+    "x-deprecation-notice",
+    "x-deprecation-message",
+    "x-api-deprecation",
+    "x-api-warn",
+    "x-sunset-notice",
+];
+
+/// 从响应头解析出的弃用声明
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeprecationSignal {
+    /// 声明已弃用（未给出时间时也为 true）
+    pub deprecated: bool,
+    /// 弃用生效时间（unix 秒）
+    pub deprecated_at: Option<i64>,
+    /// 停止服务时间（unix 秒）
+    pub sunset_at: Option<i64>,
+    /// 迁移说明链接
+    pub link: Option<String>,
+    /// 上游给出的提示文本
+    pub message: Option<String>,
+}
+
+/// 已记录的供应商公告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderNotice {
+    pub app_type: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    #[serde(flatten)]
+    pub signal: DeprecationSignal,
+    pub first_seen_at: i64,
+    pub last_seen_at: i64,
+    pub dismissed: bool,
+}
+
+/// (app_type, provider_id) -> (最近一次声明, 最近写库时间)
+static OBSERVED: LazyLock<Mutex<HashMap<(String, String), (DeprecationSignal, i64)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// HTTP-date（IMF-fixdate，如 `Sun, 06 Nov 1994 08:49:37 GMT`）或 RFC 9745 的 `@<秒>`
+fn parse_http_time(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if let Some(seconds) = value.strip_prefix('@') {
+        return seconds.trim().parse().ok();
+    }
+    chrono::DateTime::parse_from_rfc2822(value)
+        .map(|dt| dt.timestamp())
+        .ok()
+}
+
+fn bounded(text: &str) -> Option<String> {
+    let text = text.trim().trim_matches('"').trim();
+    if text.is_empty() {
+        return None;
+    }
+    let mut bounded: String = text.chars().take(MAX_MESSAGE_CHARS).collect();
+    if text.chars().count() > MAX_MESSAGE_CHARS {
+        bounded.push('…');
+    }
+    Some(bounded)
+}
+
+/// 从 `Link` 头中取 rel 为 deprecation / sunset 的链接
+fn deprecation_link(value: &str) -> Option<String> {
+    value.split(',').find_map(|entry| {
+        let (target, params) = entry.trim().split_once(';')?;
+        let target = target.trim().strip_prefix('<')?.strip_suffix('>')?;
+        let is_deprecation = params.split(';').any(|param| {
+            let Some((key, rel)) = param.split_once('=') else {
+                return false;
+            };
+            key.trim().eq_ignore_ascii_case("rel")
+                && rel
+                    .trim()
+                    .trim_matches('"')
+                    .split_whitespace()
+                    .any(|rel| rel == "deprecation" || rel == "sunset")
+        });
+        (is_deprecation && target.starts_with("http")).then(|| target.to_string())
+    })
+}
+
+/// `Warning: 299 <agent> "<text>"` 中的提示文本（299 = 持久性杂项警告）
+fn warning_text(value: &str) -> Option<String> {
+    let rest = value.trim().strip_prefix("299")?;
+    let start = rest.find('"')?;
+    let end = rest[start + 1..].find('"')? + start + 1;
+    bounded(&rest[start + 1..end])
+}
+
+pub struct ProviderNoticeService;
+
+impl ProviderNoticeService {
+    /// 解析响应头；没有任何弃用声明时返回 None
+    pub fn parse_headers(headers: &HeaderMap) -> Option<DeprecationSignal> {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        let mut signal = DeprecationSignal::default();
+
+        if let Some(value) = header("deprecation") {
+            let value = value.trim();
+            if !value.eq_ignore_ascii_case("false") {
+                signal.deprecated = true;
+                signal.deprecated_at = parse_http_time(value);
+            }
+        }
+        if let Some(value) = header("sunset") {
+            signal.sunset_at = parse_http_time(value);
+        }
+        signal.link = headers
+            .get_all("link")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .find_map(deprecation_link);
+        signal.message = VENDOR_MESSAGE_HEADERS
+            .iter()
+            .find_map(|name| header(name).and_then(bounded))
+            .or_else(|| {
+                headers
+                    .get_all("warning")
+                    .iter()
+                    .filter_map(|v| v.to_str().ok())
+                    .find_map(warning_text)
+            });
+
+        let announced = signal.deprecated || signal.sunset_at.is_some() || signal.message.is_some();
+        announced.then_some(signal)
+    }
+
+    /// 记录一次观测：公告变化时落库并通知前端
+    pub fn observe(
+        db: &Database,
+        app_type: &str,
+        provider_id: &str,
+        provider_name: &str,
+        signal: DeprecationSignal,
+    ) {
+        let now = chrono::Utc::now().timestamp();
+        let key = (app_type.to_string(), provider_id.to_string());
+        if let Ok(observed) = OBSERVED.lock() {
+            if let Some((last, written_at)) = observed.get(&key) {
+                if *last == signal && now - written_at < TOUCH_INTERVAL_SECS {
+                    return;
+                }
+            }
+        }
+
+        let changed = match db.upsert_provider_notice(app_type, provider_id, &signal, now) {
+            Ok(changed) => changed,
+            Err(e) => {
+                log::debug!("[Notice] 记录供应商公告失败: {e}");
+                return;
+            }
+        };
+        if let Ok(mut observed) = OBSERVED.lock() {
+            observed.insert(key, (signal.clone(), now));
+        }
+        if !changed {
+            return;
+        }
+
+        log::warn!(
+            "[Notice] 供应商 {provider_name} ({app_type}) 宣布了接口变更: deprecated={}, sunset_at={:?}",
+            signal.deprecated,
+            signal.sunset_at
+        );
+        crate::usage_events::emit(
+            EVENT_PROVIDER_NOTICE,
+            ProviderNotice {
+                app_type: app_type.to_string(),
+                provider_id: provider_id.to_string(),
+                provider_name: provider_name.to_string(),
+                signal,
+                first_seen_at: now,
+                last_seen_at: now,
+                dismissed: false,
+            },
+        );
+    }
+
+    /// 未被关闭的公告
+    pub fn list_active(
+        db: &Database,
+        app_type: Option<&str>,
+    ) -> Result<Vec<ProviderNotice>, AppError> {
+        Ok(db
+            .list_provider_notices(app_type)?
+            .into_iter()
+            .filter(|notice| !notice.dismissed)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(
+                http::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                value.parse().unwrap(),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn parses_standard_deprecation_headers() {
+        let signal = ProviderNoticeService::parse_headers(&headers(&[
+            ("Deprecation", "@1688169599"),
+            ("Sunset", "Sun, 30 Jun 2024 23:59:59 GMT"),
+            (
+                "Link",
+                "<https://api.example.com/v2>; rel=\"successor-version\", <https://example.com/migrate>; rel=\"deprecation\"; type=\"text/html\"",
+            ),
+        ]))
+        .unwrap();
+        assert!(signal.deprecated);
+        assert_eq!(signal.deprecated_at, Some(1688169599));
+        assert_eq!(signal.sunset_at, Some(1719791999));
+        assert_eq!(signal.link.as_deref(), Some("https://example.com/migrate"));
+        assert_eq!(signal.message, None);
+    }
+
+    #[test]
+    fn parses_warning_and_vendor_messages() {
+        let signal = ProviderNoticeService::parse_headers(&headers(&[
+            ("Deprecation", "true"),
+            (
+                "Warning",
+                "299 - \"claude-2 will be retired on 2025-01-06\"",
+            ),
+        ]))
+        .unwrap();
+        assert!(signal.deprecated);
+        assert_eq!(signal.deprecated_at, None);
+        assert_eq!(
+            signal.message.as_deref(),
+            Some("claude-2 will be retired on 2025-01-06")
+        );
+
+        let vendor = ProviderNoticeService::parse_headers(&headers(&[(
+            "x-api-warn",
+            "This endpoint moves to /v2 next month",
+        )]))
+        .unwrap();
+        assert!(!vendor.deprecated);
+        assert_eq!(
+            vendor.message.as_deref(),
+            Some("This endpoint moves to /v2 next month")
+        );
+    }
+
+    #[test]
+    fn dismissed_notice_returns_when_content_changes() -> Result<(), AppError> {
+        let db = Database::memory()?;
+        db.save_provider(
+            "claude",
+            &crate::provider::Provider::with_id(
+                "p1".to_string(),
+                "Relay".to_string(),
+                serde_json::json!({}),
+                None,
+            ),
+        )?;
+        let mut signal = DeprecationSignal {
+            deprecated: true,
+            ..Default::default()
+        };
+
+        assert!(db.upsert_provider_notice("claude", "p1", &signal, 100)?);
+        assert!(!db.upsert_provider_notice("claude", "p1", &signal, 200)?);
+        let notices = ProviderNoticeService::list_active(&db, Some("claude"))?;
+        assert_eq!(notices.len(), 1);
+        assert_eq!(notices[0].provider_name, "Relay");
+        assert_eq!(
+            (notices[0].first_seen_at, notices[0].last_seen_at),
+            (100, 200)
+        );
+
+        db.dismiss_provider_notice("claude", "p1")?;
+        assert!(ProviderNoticeService::list_active(&db, Some("claude"))?.is_empty());
+        assert!(!db.upsert_provider_notice("claude", "p1", &signal, 300)?);
+        assert!(ProviderNoticeService::list_active(&db, None)?.is_empty());
+
+        // 新公布了下线时间：重新提醒
+        signal.sunset_at = Some(1_900_000_000);
+        assert!(db.upsert_provider_notice("claude", "p1", &signal, 400)?);
+        let notices = ProviderNoticeService::list_active(&db, None)?;
+        assert_eq!(notices.len(), 1);
+        assert_eq!(notices[0].signal.sunset_at, Some(1_900_000_000));
+        Ok(())
+    }
+
+    #[test]
+    fn ignores_unrelated_headers() {
+        assert_eq!(
+            ProviderNoticeService::parse_headers(&headers(&[
+                ("Deprecation", "false"),
+                ("Warning", "110 - \"Response is stale\""),
+                ("Link", "<https://example.com/next>; rel=\"next\""),
+            ])),
+            None
+        );
+    }
+}
@@ -0,0 +1,290 @@
+//! 供应商信任等级
+//!
+//! 信任等级有两个来源，按优先级：
+//!
+//! 1. 用户在供应商上的标注（`meta.trustLevel`）；
+//! 2. 信任策略文件 `~/.cc-switch/provider-trust.json`，按上游域名匹配：
+//!
+//! ```json
+//! {
+//!   "trusted": ["api.anthropic.com", "*.openai.com"],
+//!   "untrusted": ["*.cheap-relay.example"],
+//!   "default": "untrusted"
+//! }
+//! ```
+//!
+//! 不受信任的供应商在切换或加入故障转移队列时需要确认，经其转发的请求
+//! 在 `proxy_request_logs.untrusted` 中打标，便于把敏感仓库的流量挡在可疑中转之外。
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::config::get_app_config_dir;
+use crate::error::AppError;
+use crate::provider::{Provider, ProviderMeta, ProviderTrust};
+use crate::store::AppState;
+
+const POLICY_FILE: &str = "provider-trust.json";
+
+static URL_HOST: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"https?://([A-Za-z0-9.\-]+)"#).expect("valid host regex"));
+
+/// 信任策略文件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustPolicy {
+    #[serde(default)]
+    pub trusted: Vec<String>,
+    #[serde(default)]
+    pub untrusted: Vec<String>,
+    /// 未匹配任何规则时的等级；为空表示不判定
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<ProviderTrust>,
+}
+
+/// 判定来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrustSource {
+    /// 用户标注
+    Label,
+    /// 策略文件规则
+    Policy,
+    /// 未标注、策略也未覆盖
+    None,
+}
+
+/// 解析后的信任等级
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedTrust {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<ProviderTrust>,
+    pub source: TrustSource,
+    /// 命中的策略规则
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+}
+
+impl ResolvedTrust {
+    pub fn is_untrusted(&self) -> bool {
+        self.level == Some(ProviderTrust::Untrusted)
+    }
+}
+
+impl TrustPolicy {
+    pub fn path() -> PathBuf {
+        get_app_config_dir().join(POLICY_FILE)
+    }
+
+    /// 读取策略文件；文件不存在或解析失败时视为空策略
+    pub fn load() -> Self {
+        let path = Self::path();
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            log::warn!("[Trust] 信任策略文件解析失败 {}: {e}", path.display());
+            Self::default()
+        })
+    }
+
+    /// 按上游域名判定：untrusted 规则优先于 trusted 规则
+    fn evaluate(&self, hosts: &[String]) -> ResolvedTrust {
+        let matched = |rules: &[String]| {
+            rules
+                .iter()
+                .find(|rule| hosts.iter().any(|host| host_matches(rule, host)))
+                .cloned()
+        };
+        if let Some(rule) = matched(&self.untrusted) {
+            return policy(ProviderTrust::Untrusted, Some(rule));
+        }
+        if let Some(rule) = matched(&self.trusted) {
+            return policy(ProviderTrust::Trusted, Some(rule));
+        }
+        match self.default {
+            Some(level) if !hosts.is_empty() => policy(level, None),
+            _ => ResolvedTrust {
+                level: None,
+                source: TrustSource::None,
+                rule: None,
+            },
+        }
+    }
+}
+
+fn policy(level: ProviderTrust, rule: Option<String>) -> ResolvedTrust {
+    ResolvedTrust {
+        level: Some(level),
+        source: TrustSource::Policy,
+        rule,
+    }
+}
+
+/// `*.example.com` 匹配 example.com 及其子域名，其余规则精确匹配
+fn host_matches(rule: &str, host: &str) -> bool {
+    let rule = rule.trim().to_ascii_lowercase();
+    let host = host.to_ascii_lowercase();
+    match rule.strip_prefix("*.") {
+        Some(suffix) => host == suffix || host.ends_with(&format!(".{suffix}")),
+        None => host == rule,
+    }
+}
+
+/// 从供应商配置中提取所有上游域名（兼容 JSON 与内嵌的 TOML 文本）
+fn upstream_hosts(settings_config: &str) -> Vec<String> {
+    let mut hosts: Vec<String> = URL_HOST
+        .captures_iter(settings_config)
+        .map(|cap| cap[1].to_ascii_lowercase())
+        .collect();
+    hosts.sort();
+    hosts.dedup();
+    hosts
+}
+
+fn resolve_parts(
+    meta: Option<&ProviderMeta>,
+    settings_config: &str,
+    policy: &TrustPolicy,
+) -> ResolvedTrust {
+    if let Some(level) = meta.and_then(|m| m.trust_level) {
+        return ResolvedTrust {
+            level: Some(level),
+            source: TrustSource::Label,
+            rule: None,
+        };
+    }
+    policy.evaluate(&upstream_hosts(settings_config))
+}
+
+/// 解析单个供应商的信任等级
+pub fn resolve(provider: &Provider, policy: &TrustPolicy) -> ResolvedTrust {
+    resolve_parts(
+        provider.meta.as_ref(),
+        &provider.settings_config.to_string(),
+        policy,
+    )
+}
+
+/// 在已持有的数据库连接上判定供应商是否不受信任（供使用日志打标）
+pub fn is_untrusted_on_conn(conn: &Connection, app_type: &str, provider_id: &str) -> bool {
+    let row: Option<(String, Option<String>)> = conn
+        .query_row(
+            "SELECT settings_config, meta FROM providers WHERE id = ?1 AND app_type = ?2",
+            params![provider_id, app_type],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .unwrap_or_default();
+    let Some((settings_config, meta)) = row else {
+        return false;
+    };
+    let meta: Option<ProviderMeta> = meta.and_then(|m| serde_json::from_str(&m).ok());
+    resolve_parts(meta.as_ref(), &settings_config, &TrustPolicy::load()).is_untrusted()
+}
+
+pub struct ProviderTrustService;
+
+impl ProviderTrustService {
+    /// 列出某应用下所有供应商的信任等级
+    pub fn list(
+        state: &AppState,
+        app: AppType,
+    ) -> Result<HashMap<String, ResolvedTrust>, AppError> {
+        let policy = TrustPolicy::load();
+        Ok(state
+            .db
+            .get_all_providers(app.as_str())?
+            .iter()
+            .map(|(id, provider)| (id.clone(), resolve(provider, &policy)))
+            .collect())
+    }
+
+    /// 标注（或清除）供应商的信任等级
+    pub fn set_label(
+        state: &AppState,
+        app: AppType,
+        id: &str,
+        level: Option<ProviderTrust>,
+    ) -> Result<(), AppError> {
+        let mut provider = state
+            .db
+            .get_provider_by_id(id, app.as_str())?
+            .ok_or_else(|| AppError::InvalidInput(format!("供应商 {id} 不存在")))?;
+        provider
+            .meta
+            .get_or_insert_with(Default::default)
+            .trust_level = level;
+        state.db.save_provider(app.as_str(), &provider)
+    }
+
+    pub fn get_policy() -> TrustPolicy {
+        TrustPolicy::load()
+    }
+
+    pub fn save_policy(policy: &TrustPolicy) -> Result<(), AppError> {
+        let path = TrustPolicy::path();
+        let text = serde_json::to_string_pretty(policy)
+            .map_err(|e| AppError::Message(format!("序列化信任策略失败: {e}")))?;
+        crate::config::write_text_file(&path, &text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcard_rules_match_subdomains() {
+        assert!(host_matches("*.relay.example", "api.relay.example"));
+        assert!(host_matches("*.relay.example", "relay.example"));
+        assert!(!host_matches("*.relay.example", "badrelay.example"));
+        assert!(host_matches("API.anthropic.com", "api.anthropic.com"));
+    }
+
+    #[test]
+    fn label_overrides_policy_and_untrusted_rules_win() {
+        let policy = TrustPolicy {
+            trusted: vec!["*.example".to_string()],
+            untrusted: vec!["relay.example".to_string()],
+            default: None,
+        };
+        let settings = r#"{"env":{"ANTHROPIC_BASE_URL":"https://relay.example/v1"}}"#;
+
+        let resolved = resolve_parts(None, settings, &policy);
+        assert!(resolved.is_untrusted());
+        assert_eq!(resolved.source, TrustSource::Policy);
+        assert_eq!(resolved.rule.as_deref(), Some("relay.example"));
+
+        let meta = ProviderMeta {
+            trust_level: Some(ProviderTrust::Trusted),
+            ..Default::default()
+        };
+        let resolved = resolve_parts(Some(&meta), settings, &policy);
+        assert_eq!(resolved.level, Some(ProviderTrust::Trusted));
+        assert_eq!(resolved.source, TrustSource::Label);
+    }
+
+    #[test]
+    fn default_level_applies_only_to_unmatched_remote_hosts() {
+        let policy = TrustPolicy {
+            trusted: vec!["api.anthropic.com".to_string()],
+            untrusted: Vec::new(),
+            default: Some(ProviderTrust::Untrusted),
+        };
+        let codex = "model_provider = \"x\"\nbase_url = \"https://cheap.example/v1\"";
+        assert!(resolve_parts(None, codex, &policy).is_untrusted());
+        assert_eq!(
+            resolve_parts(None, "https://api.anthropic.com", &policy).level,
+            Some(ProviderTrust::Trusted)
+        );
+        assert_eq!(resolve_parts(None, "{}", &policy).level, None);
+    }
+}
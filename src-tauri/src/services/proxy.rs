@@ -3,9 +3,11 @@
 //! 提供代理服务器的启动、停止和配置管理
 
 use crate::app_config::AppType;
+use crate::codex_config_model::CodexConfig;
 use crate::config::{get_claude_settings_path, read_json_file, write_json_file};
 use crate::database::Database;
 use crate::provider::Provider;
+use crate::proxy::auth::{client_token, is_proxy_client_token, ProxyAuthConfig};
 use crate::proxy::server::ProxyServer;
 use crate::proxy::switch_lock::SwitchLockManager;
 use crate::proxy::types::*;
@@ -18,9 +20,6 @@ use std::sync::Arc;
 use tauri::Emitter;
 use tokio::sync::RwLock;
 
-/// 用于接管 Live 配置时的占位符（避免客户端提示缺少 key，同时不泄露真实 Token）
-const PROXY_TOKEN_PLACEHOLDER: &str = "PROXY_MANAGED";
-
 /// 代理接管模式下需要从 Claude Live 配置中移除的"模型覆盖"字段。
 ///
 /// 原因：接管模式下 `*_MODEL` 必须由 CC Switch 写成稳定的 Claude 角色别名，
@@ -176,16 +175,13 @@ impl ProxyService {
                 let mut replaced_any = false;
                 for key in token_keys {
                     if env.contains_key(key) {
-                        env.insert(key.to_string(), json!(PROXY_TOKEN_PLACEHOLDER));
+                        env.insert(key.to_string(), json!(client_token()));
                         replaced_any = true;
                     }
                 }
 
                 if !replaced_any {
-                    env.insert(
-                        "ANTHROPIC_AUTH_TOKEN".to_string(),
-                        json!(PROXY_TOKEN_PLACEHOLDER),
-                    );
+                    env.insert("ANTHROPIC_AUTH_TOKEN".to_string(), json!(client_token()));
                 }
             }
             ClaudeTakeoverAuthPolicy::ManagedAccount { keep_auth_token } => {
@@ -199,15 +195,9 @@ impl ProxyService {
                 //   settings（预设不含该键），且旧版接管已把存量用户 live 中的键删光。
                 // - Copilot 仅 API_KEY：避免与 /login 管理的 key 冲突（#1049）。
                 if keep_auth_token {
-                    env.insert(
-                        "ANTHROPIC_AUTH_TOKEN".to_string(),
-                        json!(PROXY_TOKEN_PLACEHOLDER),
-                    );
+                    env.insert("ANTHROPIC_AUTH_TOKEN".to_string(), json!(client_token()));
                 } else {
-                    env.insert(
-                        "ANTHROPIC_API_KEY".to_string(),
-                        json!(PROXY_TOKEN_PLACEHOLDER),
-                    );
+                    env.insert("ANTHROPIC_API_KEY".to_string(), json!(client_token()));
                 }
             }
         }
@@ -549,13 +539,18 @@ impl ProxyService {
             });
         }
 
-        // 4. 创建并启动服务器
+        // 4. 创建并启动服务器（先刷新写入客户端配置的凭据，供随后的接管使用）
+        if let Err(e) = crate::proxy::auth::reload_client_token(&self.db) {
+            log::warn!("加载代理鉴权配置失败: {e}");
+        }
         let app_handle = self.app_handle.read().await.clone();
         let server = ProxyServer::new(config.clone(), self.db.clone(), app_handle);
         let info = server
             .start()
             .await
             .map_err(|e| format!("启动代理服务器失败: {e}"))?;
+        // 发布监听端口，供系统代理检测识别自身
+        crate::proxy::http_client::set_proxy_port(info.port);
         if let Err(e) = self
             .persist_ephemeral_listen_port_if_needed(&config, info.port)
             .await
@@ -875,6 +870,11 @@ impl ProxyService {
             .await
             .map_err(|e| format!("删除 {app_type_str} Live 备份失败: {e}"))?;
 
+        // 供应商级代理覆盖发起的接管到此结束，清除来源标记
+        let _ = self
+            .db
+            .set_provider_proxy_override_active(app_type_str, false);
+
         // 3) 设置 proxy_config.enabled = false
         let mut updated_config = self
             .db
@@ -999,7 +999,7 @@ impl ProxyService {
                                     .map(|s| (key, s.trim()))
                             })
                             .filter(|(_, token)| {
-                                !token.is_empty() && *token != PROXY_TOKEN_PLACEHOLDER
+                                !token.is_empty() && !is_proxy_client_token(token)
                             });
 
                             if let Some((token_key, token)) = token_pair {
@@ -1091,7 +1091,7 @@ impl ProxyService {
                             .and_then(|v| v.get("OPENAI_API_KEY"))
                             .and_then(|v| v.as_str())
                             .map(|s| s.trim())
-                            .filter(|s| !s.is_empty() && *s != PROXY_TOKEN_PLACEHOLDER)
+                            .filter(|s| !s.is_empty() && !is_proxy_client_token(s))
                         {
                             if let Some(auth_obj) = provider
                                 .settings_config
@@ -1143,7 +1143,7 @@ impl ProxyService {
                             .and_then(|v| v.get("GEMINI_API_KEY"))
                             .and_then(|v| v.as_str())
                             .map(|s| s.trim())
-                            .filter(|s| !s.is_empty() && *s != PROXY_TOKEN_PLACEHOLDER)
+                            .filter(|s| !s.is_empty() && !is_proxy_client_token(s))
                         {
                             if let Some(env_obj) = provider
                                 .settings_config
@@ -1199,7 +1199,7 @@ impl ProxyService {
                         if let Some(token) =
                             crate::grok_config::extract_inline_api_key(live_config_toml)
                         {
-                            if !token.is_empty() && token != PROXY_TOKEN_PLACEHOLDER {
+                            if !token.is_empty() && !is_proxy_client_token(&token) {
                                 if let Some(provider_config) = provider
                                     .settings_config
                                     .get("config")
@@ -1259,11 +1259,14 @@ impl ProxyService {
 
     /// 停止代理服务器
     pub async fn stop(&self) -> Result<(), String> {
-        if let Some(server) = self.server.write().await.take() {
-            server
-                .stop()
-                .await
-                .map_err(|e| format!("停止代理服务器失败: {e}"))?;
+        // 排空期间保留 server，状态接口仍能报告进行中的请求；结束后再移除
+        let stopped = match self.server.read().await.as_ref() {
+            Some(server) => Some(server.stop().await),
+            None => None,
+        };
+        if let Some(result) = stopped {
+            self.server.write().await.take();
+            result.map_err(|e| format!("停止代理服务器失败: {e}"))?;
 
             // 停止时设置 proxy_enabled = false
             let mut global_config = self
@@ -1523,12 +1526,9 @@ impl ProxyService {
             .get("config")
             .and_then(Value::as_str)
             .ok_or_else(|| "Grok Build 配置缺少 config 字段".to_string())?;
-        let updated = crate::grok_config::apply_proxy_takeover(
-            config_toml,
-            proxy_base_url,
-            PROXY_TOKEN_PLACEHOLDER,
-        )
-        .map_err(|e| format!("更新 Grok Build 接管配置失败: {e}"))?;
+        let updated =
+            crate::grok_config::apply_proxy_takeover(config_toml, proxy_base_url, &client_token())
+                .map_err(|e| format!("更新 Grok Build 接管配置失败: {e}"))?;
         config["config"] = json!(updated);
         Ok(())
     }
@@ -1576,11 +1576,11 @@ impl ProxyService {
             if let Some(env) = live_config.get_mut("env").and_then(|v| v.as_object_mut()) {
                 env.insert("GOOGLE_GEMINI_BASE_URL".to_string(), json!(&proxy_url));
                 // 使用占位符，避免显示缺少 key 的警告
-                env.insert("GEMINI_API_KEY".to_string(), json!(PROXY_TOKEN_PLACEHOLDER));
+                env.insert("GEMINI_API_KEY".to_string(), json!(client_token()));
             } else {
                 live_config["env"] = json!({
                     "GOOGLE_GEMINI_BASE_URL": &proxy_url,
-                    "GEMINI_API_KEY": PROXY_TOKEN_PLACEHOLDER
+                    "GEMINI_API_KEY": client_token()
                 });
             }
             self.write_gemini_live(&live_config)?;
@@ -1604,7 +1604,59 @@ impl ProxyService {
     /// 接管指定应用的 Live 配置（严格模式：目标配置不存在则返回错误）
     async fn takeover_live_config_strict(&self, app_type: &AppType) -> Result<(), String> {
         let (proxy_url, proxy_codex_base_url) = self.build_proxy_urls().await?;
+        self.write_takeover_live_config(app_type, &proxy_url, proxy_codex_base_url, None)
+    }
+
+    /// 把指定应用的 Live 配置指向另一台机器上的代理（如 SSH 隧道的本地端口）
+    ///
+    /// 字段改写与本机接管一致，但不启动本机代理、不备份 Live；`token` 为远端
+    /// 开启监听鉴权时的访问令牌。恢复直连时把当前供应商重新写入 Live 即可。
+    pub fn point_live_config_at(
+        &self,
+        app_type: &AppType,
+        proxy_origin: &str,
+        token: Option<&str>,
+    ) -> Result<(), String> {
+        let _write_lock =
+            crate::instance_lock::acquire("tunnel.repoint").map_err(|e| e.to_string())?;
+        let proxy_url = proxy_origin.trim_end_matches('/').to_string();
+        let proxy_codex_base_url = format!("{proxy_url}/v1");
+        self.write_takeover_live_config(app_type, &proxy_url, proxy_codex_base_url, token)
+    }
+
+    /// 远端令牌替换本机写入的占位符 / 令牌
+    fn replace_client_token(value: &mut Value, token: &str) {
+        match value {
+            Value::String(text) => {
+                let local = client_token();
+                if text.contains(&local) {
+                    *text = text.replace(&local, token);
+                }
+            }
+            Value::Array(items) => items
+                .iter_mut()
+                .for_each(|item| Self::replace_client_token(item, token)),
+            Value::Object(map) => map
+                .values_mut()
+                .for_each(|item| Self::replace_client_token(item, token)),
+            _ => {}
+        }
+    }
+
+    fn write_takeover_live_config(
+        &self,
+        app_type: &AppType,
+        proxy_url: &str,
+        proxy_codex_base_url: &str,
+        token: Option<&str>,
+    ) -> Result<(), String> {
         let proxy_grok_base_url = format!("{}/grokbuild/v1", proxy_url.trim_end_matches('/'));
+        let with_token = |mut live_config: Value| {
+            if let Some(token) = token {
+                Self::replace_client_token(&mut live_config, token);
+            }
+            live_config
+        };
 
         match app_type {
             AppType::Claude => {
@@ -1614,10 +1666,10 @@ impl ProxyService {
                     self.claude_provider_with_effective_settings(&claude_provider)?;
                 Self::apply_claude_takeover_fields_for_provider(
                     &mut live_config,
-                    &proxy_url,
+                    proxy_url,
                     &claude_provider,
                 );
-                self.write_claude_live(&live_config)?;
+                self.write_claude_live(&with_token(live_config))?;
                 log::info!("Claude Live 配置已接管，代理地址: {proxy_url}");
             }
             AppType::Codex => {
@@ -1625,27 +1677,30 @@ impl ProxyService {
                 let codex_provider = self.require_current_provider_for_app(&AppType::Codex)?;
                 Self::apply_codex_takeover_fields_for_provider(
                     &mut live_config,
-                    &proxy_codex_base_url,
+                    proxy_codex_base_url,
                     &codex_provider,
                 )?;
 
-                self.write_codex_takeover_live_for_provider(&live_config, Some(&codex_provider))?;
+                self.write_codex_takeover_live_for_provider(
+                    &with_token(live_config),
+                    Some(&codex_provider),
+                )?;
                 log::info!("Codex Live 配置已接管，代理地址: {proxy_codex_base_url}");
             }
             AppType::Gemini => {
                 let mut live_config = self.read_gemini_live()?;
 
                 if let Some(env) = live_config.get_mut("env").and_then(|v| v.as_object_mut()) {
-                    env.insert("GOOGLE_GEMINI_BASE_URL".to_string(), json!(&proxy_url));
-                    env.insert("GEMINI_API_KEY".to_string(), json!(PROXY_TOKEN_PLACEHOLDER));
+                    env.insert("GOOGLE_GEMINI_BASE_URL".to_string(), json!(proxy_url));
+                    env.insert("GEMINI_API_KEY".to_string(), json!(client_token()));
                 } else {
                     live_config["env"] = json!({
-                        "GOOGLE_GEMINI_BASE_URL": &proxy_url,
-                        "GEMINI_API_KEY": PROXY_TOKEN_PLACEHOLDER
+                        "GOOGLE_GEMINI_BASE_URL": proxy_url,
+                        "GEMINI_API_KEY": client_token()
                     });
                 }
 
-                self.write_gemini_live(&live_config)?;
+                self.write_gemini_live(&with_token(live_config))?;
                 log::info!("Gemini Live 配置已接管，代理地址: {proxy_url}");
             }
             AppType::GrokBuild => {
@@ -1659,7 +1714,7 @@ impl ProxyService {
                     );
                 }
                 Self::apply_grok_takeover_fields(&mut live_config, &proxy_grok_base_url)?;
-                self.write_grok_live(&live_config)?;
+                self.write_grok_live(&with_token(live_config))?;
                 log::info!("Grok Build Live 配置已接管，代理地址: {proxy_grok_base_url}");
             }
             _ => return Err("该应用不支持代理功能".to_string()),
@@ -1716,11 +1771,11 @@ impl ProxyService {
                 if let Ok(mut live_config) = self.read_gemini_live() {
                     if let Some(env) = live_config.get_mut("env").and_then(|v| v.as_object_mut()) {
                         env.insert("GOOGLE_GEMINI_BASE_URL".to_string(), json!(&proxy_url));
-                        env.insert("GEMINI_API_KEY".to_string(), json!(PROXY_TOKEN_PLACEHOLDER));
+                        env.insert("GEMINI_API_KEY".to_string(), json!(client_token()));
                     } else {
                         live_config["env"] = json!({
                             "GOOGLE_GEMINI_BASE_URL": &proxy_url,
-                            "GEMINI_API_KEY": PROXY_TOKEN_PLACEHOLDER
+                            "GEMINI_API_KEY": client_token()
                         });
                     }
 
@@ -2079,7 +2134,11 @@ impl ProxyService {
             "OPENROUTER_API_KEY",
             "OPENAI_API_KEY",
         ] {
-            if env.get(key).and_then(|v| v.as_str()) == Some(PROXY_TOKEN_PLACEHOLDER) {
+            if env
+                .get(key)
+                .and_then(|v| v.as_str())
+                .is_some_and(is_proxy_client_token)
+            {
                 env.remove(key);
             }
         }
@@ -2101,7 +2160,10 @@ impl ProxyService {
         let mut config = self.read_codex_live()?;
 
         if let Some(auth) = config.get_mut("auth").and_then(|v| v.as_object_mut()) {
-            if auth.get("OPENAI_API_KEY").and_then(|v| v.as_str()) == Some(PROXY_TOKEN_PLACEHOLDER)
+            if auth
+                .get("OPENAI_API_KEY")
+                .and_then(|v| v.as_str())
+                .is_some_and(is_proxy_client_token)
             {
                 auth.remove("OPENAI_API_KEY");
             }
@@ -2111,7 +2173,7 @@ impl ProxyService {
             let updated = Self::remove_local_toml_base_url(cfg_str);
             let updated =
                 crate::codex_config::remove_codex_experimental_bearer_token_if(&updated, |token| {
-                    token == PROXY_TOKEN_PLACEHOLDER
+                    is_proxy_client_token(token)
                 })
                 .map_err(|e| format!("清理 Codex 接管占位符失败: {e}"))?;
             let updated = crate::codex_config::remove_codex_official_proxy_route(&updated)
@@ -2135,7 +2197,11 @@ impl ProxyService {
             return Ok(());
         };
 
-        if env.get("GEMINI_API_KEY").and_then(|v| v.as_str()) == Some(PROXY_TOKEN_PLACEHOLDER) {
+        if env
+            .get("GEMINI_API_KEY")
+            .and_then(|v| v.as_str())
+            .is_some_and(is_proxy_client_token)
+        {
             env.remove("GEMINI_API_KEY");
         }
 
@@ -2157,7 +2223,10 @@ impl ProxyService {
         let Some(config_toml) = config.get("config").and_then(Value::as_str) else {
             return Ok(());
         };
-        if !crate::grok_config::has_proxy_placeholder(config_toml, PROXY_TOKEN_PLACEHOLDER) {
+        if !crate::grok_config::has_proxy_placeholder(
+            config_toml,
+            crate::proxy::auth::PROXY_TOKEN_PLACEHOLDER,
+        ) {
             return Ok(());
         }
 
@@ -2243,7 +2312,11 @@ impl ProxyService {
             "OPENROUTER_API_KEY",
             "OPENAI_API_KEY",
         ] {
-            if env.get(key).and_then(|v| v.as_str()) == Some(PROXY_TOKEN_PLACEHOLDER) {
+            if env
+                .get(key)
+                .and_then(|v| v.as_str())
+                .is_some_and(is_proxy_client_token)
+            {
                 return true;
             }
         }
@@ -2257,7 +2330,7 @@ impl ProxyService {
             .and_then(|v| v.as_object())
             .and_then(|auth| auth.get("OPENAI_API_KEY"))
             .and_then(|v| v.as_str())
-            == Some(PROXY_TOKEN_PLACEHOLDER)
+            .is_some_and(is_proxy_client_token)
         {
             return true;
         }
@@ -2266,8 +2339,7 @@ impl ProxyService {
             .get("config")
             .and_then(|v| v.as_str())
             .and_then(crate::codex_config::extract_codex_experimental_bearer_token)
-            .as_deref()
-            == Some(PROXY_TOKEN_PLACEHOLDER)
+            .is_some_and(|token| is_proxy_client_token(&token))
     }
 
     fn is_codex_live_taken_over(config: &Value) -> bool {
@@ -2283,7 +2355,9 @@ impl ProxyService {
             Some(env) => env,
             None => return false,
         };
-        env.get("GEMINI_API_KEY").and_then(|v| v.as_str()) == Some(PROXY_TOKEN_PLACEHOLDER)
+        env.get("GEMINI_API_KEY")
+            .and_then(|v| v.as_str())
+            .is_some_and(is_proxy_client_token)
     }
 
     fn is_grok_live_taken_over(config: &Value) -> bool {
@@ -2291,7 +2365,10 @@ impl ProxyService {
             .get("config")
             .and_then(Value::as_str)
             .is_some_and(|config_toml| {
-                crate::grok_config::has_proxy_placeholder(config_toml, PROXY_TOKEN_PLACEHOLDER)
+                crate::grok_config::has_proxy_placeholder(
+                    config_toml,
+                    crate::proxy::auth::PROXY_TOKEN_PLACEHOLDER,
+                )
             })
     }
 
@@ -2592,10 +2669,25 @@ impl ProxyService {
             return Err(format!("更新当前供应商失败: {error}"));
         }
 
+        let mut draining = 0;
         if let Some(server) = self.server.read().await.as_ref() {
             server
                 .set_active_target(app_type_enum.as_str(), &provider.id, &provider.name)
                 .await;
+            // 进行中的请求不受切换影响，继续在原供应商上完成
+            if logical_target_changed {
+                let current = std::collections::BTreeMap::from([(
+                    app_type_enum.as_str().to_string(),
+                    provider.id.clone(),
+                )]);
+                draining = server.draining_count(&current);
+            }
+        }
+        if draining > 0 {
+            log::info!(
+                "[Drain] {app_type} 已切换到 {}，{draining} 个进行中的请求继续在原供应商上完成",
+                provider.name
+            );
         }
 
         Ok(HotSwitchOutcome {
@@ -2620,52 +2712,20 @@ impl ProxyService {
             .get("config")
             .and_then(|v| v.as_str())
             .unwrap_or("");
-        let mut target_doc = if target_config.trim().is_empty() {
-            toml_edit::DocumentMut::new()
-        } else {
-            target_config
-                .parse::<toml_edit::DocumentMut>()
-                .map_err(|e| format!("解析新的 config.toml 失败: {e}"))?
-        };
+        let mut target = CodexConfig::try_parse(target_config)
+            .map_err(|e| format!("解析新的 config.toml 失败: {e}"))?;
 
         let existing_config = existing_config
             .get("config")
             .and_then(|v| v.as_str())
             .unwrap_or("");
-        if existing_config.trim().is_empty() {
-            target_obj.insert("config".to_string(), json!(target_doc.to_string()));
-            return Ok(());
+        if !existing_config.trim().is_empty() {
+            let existing = CodexConfig::try_parse(existing_config)
+                .map_err(|e| format!("解析现有 config.toml 备份失败: {e}"))?;
+            target.merge_missing_mcp_servers(&existing);
         }
 
-        let existing_doc = existing_config
-            .parse::<toml_edit::DocumentMut>()
-            .map_err(|e| format!("解析现有 config.toml 备份失败: {e}"))?;
-
-        if let Some(existing_mcp_servers) = existing_doc.get("mcp_servers") {
-            match target_doc.get_mut("mcp_servers") {
-                Some(target_mcp_servers) => {
-                    if let (Some(target_table), Some(existing_table)) = (
-                        target_mcp_servers.as_table_like_mut(),
-                        existing_mcp_servers.as_table_like(),
-                    ) {
-                        for (server_id, server_item) in existing_table.iter() {
-                            if target_table.get(server_id).is_none() {
-                                target_table.insert(server_id, server_item.clone());
-                            }
-                        }
-                    } else {
-                        log::warn!(
-                            "config.toml contains a non-table mcp_servers section; skipping MCP merge"
-                        );
-                    }
-                }
-                None => {
-                    target_doc["mcp_servers"] = existing_mcp_servers.clone();
-                }
-            }
-        }
-
-        target_obj.insert("config".to_string(), json!(target_doc.to_string()));
+        target_obj.insert("config".to_string(), json!(target.to_string()));
         Ok(())
     }
 
@@ -2758,11 +2818,11 @@ impl ProxyService {
         }
 
         if let Some(auth) = settings.get_mut("auth").and_then(|v| v.as_object_mut()) {
-            auth.insert("OPENAI_API_KEY".to_string(), json!(PROXY_TOKEN_PLACEHOLDER));
+            auth.insert("OPENAI_API_KEY".to_string(), json!(client_token()));
         } else if let Some(root) = settings.as_object_mut() {
             root.insert(
                 "auth".to_string(),
-                json!({ "OPENAI_API_KEY": PROXY_TOKEN_PLACEHOLDER }),
+                json!({ "OPENAI_API_KEY": client_token() }),
             );
         }
     }
@@ -2864,8 +2924,10 @@ impl ProxyService {
                     config.get("auth"),
                     config.get("config").and_then(|v| v.as_str()),
                 ) {
-                    if auth.get("OPENAI_API_KEY").and_then(|v| v.as_str())
-                        == Some(PROXY_TOKEN_PLACEHOLDER)
+                    if auth
+                        .get("OPENAI_API_KEY")
+                        .and_then(|v| v.as_str())
+                        .is_some_and(is_proxy_client_token)
                     {
                         let live_config = crate::codex_config::prepare_codex_provider_live_config(
                             auth, config_str,
@@ -2898,7 +2960,9 @@ impl ProxyService {
     }
 
     fn codex_auth_has_proxy_placeholder(auth: &Value) -> bool {
-        auth.get("OPENAI_API_KEY").and_then(|v| v.as_str()) == Some(PROXY_TOKEN_PLACEHOLDER)
+        auth.get("OPENAI_API_KEY")
+            .and_then(|v| v.as_str())
+            .is_some_and(is_proxy_client_token)
     }
 
     fn write_codex_takeover_live_for_provider(
@@ -3055,6 +3119,107 @@ impl ProxyService {
         }
     }
 
+    /// 获取代理鉴权配置
+    pub fn get_auth_config(&self) -> Result<ProxyAuthConfig, String> {
+        self.db
+            .get_proxy_auth_config()
+            .map_err(|e| format!("获取代理鉴权配置失败: {e}"))
+    }
+
+    /// 开关代理鉴权，已接管的应用立即改写客户端凭据
+    pub async fn set_auth_enabled(&self, enabled: bool) -> Result<(), String> {
+        self.db
+            .save_proxy_auth_config(&ProxyAuthConfig { enabled })
+            .map_err(|e| format!("保存代理鉴权配置失败: {e}"))?;
+        self.reload_auth_and_refresh_takeover().await
+    }
+
+    pub fn list_auth_tokens(&self) -> Result<Vec<ProxyAuthToken>, String> {
+        self.db
+            .list_proxy_auth_tokens()
+            .map_err(|e| format!("读取代理访问令牌失败: {e}"))
+    }
+
+    /// 新建访问令牌（供手动配置的其他客户端使用，不写入 Live 配置）
+    pub async fn create_auth_token(&self, name: &str) -> Result<ProxyAuthToken, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("令牌名称不能为空".to_string());
+        }
+        let token = ProxyAuthToken {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            token: crate::proxy::auth::generate_token(),
+            created_at: chrono::Utc::now().timestamp(),
+            last_used_at: None,
+        };
+        self.db
+            .insert_proxy_auth_token(&token)
+            .map_err(|e| format!("保存代理访问令牌失败: {e}"))?;
+        self.reload_auth_and_refresh_takeover().await?;
+        Ok(token)
+    }
+
+    /// 轮换令牌值；若是写入客户端配置的令牌，已接管的应用同步更新
+    pub async fn rotate_auth_token(&self, id: &str) -> Result<(), String> {
+        let found = self
+            .db
+            .update_proxy_auth_token_secret(id, &crate::proxy::auth::generate_token())
+            .map_err(|e| format!("轮换代理访问令牌失败: {e}"))?;
+        if !found {
+            return Err(format!("代理访问令牌 {id} 不存在"));
+        }
+        self.reload_auth_and_refresh_takeover().await
+    }
+
+    /// 吊销令牌；吊销最后一个令牌时会自动生成新的默认令牌
+    pub async fn revoke_auth_token(&self, id: &str) -> Result<(), String> {
+        let found = self
+            .db
+            .delete_proxy_auth_token(id)
+            .map_err(|e| format!("吊销代理访问令牌失败: {e}"))?;
+        if !found {
+            return Err(format!("代理访问令牌 {id} 不存在"));
+        }
+        self.reload_auth_and_refresh_takeover().await
+    }
+
+    pub fn get_auth_token_usage(
+        &self,
+        since: Option<i64>,
+    ) -> Result<Vec<ProxyAuthTokenUsage>, String> {
+        self.db
+            .get_proxy_auth_token_usage(since)
+            .map_err(|e| format!("统计代理访问令牌用量失败: {e}"))
+    }
+
+    async fn reload_auth_and_refresh_takeover(&self) -> Result<(), String> {
+        let previous = client_token();
+        crate::proxy::auth::reload_client_token(&self.db)
+            .map_err(|e| format!("加载代理鉴权配置失败: {e}"))?;
+        if let Some(server) = self.server.read().await.as_ref() {
+            server
+                .reload_auth()
+                .map_err(|e| format!("加载代理鉴权配置失败: {e}"))?;
+        }
+        if client_token() == previous {
+            return Ok(());
+        }
+        let status = self.get_takeover_status().await?;
+        for (taken_over, app_type) in [
+            (status.claude, AppType::Claude),
+            (status.codex, AppType::Codex),
+            (status.gemini, AppType::Gemini),
+            (status.grokbuild, AppType::GrokBuild),
+        ] {
+            if taken_over {
+                self.takeover_live_config_best_effort(&app_type).await?;
+                log::info!("{} Live 配置已更新代理访问凭据", app_type.as_str());
+            }
+        }
+        Ok(())
+    }
+
     /// 获取代理配置
     pub async fn get_config(&self) -> Result<ProxyConfig, String> {
         self.db
@@ -3105,6 +3270,8 @@ impl ProxyService {
                 .start()
                 .await
                 .map_err(|e| format!("重启代理服务器失败: {e}"))?;
+            // 发布监听端口，供系统代理检测识别自身
+            crate::proxy::http_client::set_proxy_port(info.port);
             if let Err(e) = self
                 .persist_ephemeral_listen_port_if_needed(&new_config, info.port)
                 .await
@@ -3216,6 +3383,7 @@ impl ProxyService {
 mod tests {
     use super::*;
     use crate::provider::ProviderMeta;
+    use crate::proxy::auth::PROXY_TOKEN_PLACEHOLDER;
     use serial_test::serial;
     use std::env;
     use tempfile::TempDir;
@@ -3738,6 +3906,45 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn point_live_config_at_writes_tunnel_url_and_remote_token() {
+        let _home = TempHome::new();
+        crate::settings::reload_settings().expect("reload settings");
+
+        let db = Arc::new(Database::memory().expect("init db"));
+        let service = ProxyService::new(db.clone());
+        let provider = Provider::with_id(
+            "p1".to_string(),
+            "P1".to_string(),
+            json!({"env": {
+                "ANTHROPIC_BASE_URL": "https://api.example.com",
+                "ANTHROPIC_AUTH_TOKEN": "sk-real"
+            }}),
+            None,
+        );
+        db.save_provider("claude", &provider)
+            .expect("save provider");
+        db.set_current_provider("claude", "p1")
+            .expect("set current provider");
+        let path = get_claude_settings_path();
+        std::fs::create_dir_all(path.parent().unwrap()).expect("create claude dir");
+        write_json_file(&path, &provider.settings_config).expect("write live");
+
+        service
+            .point_live_config_at(
+                &AppType::Claude,
+                "http://127.0.0.1:25721/",
+                Some("PROXY_MANAGED-remote"),
+            )
+            .expect("point live config");
+
+        let live: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let env = live["env"].as_object().expect("env object");
+        assert_env_str(env, "ANTHROPIC_BASE_URL", Some("http://127.0.0.1:25721"));
+        assert_env_str(env, "ANTHROPIC_AUTH_TOKEN", Some("PROXY_MANAGED-remote"));
+    }
+
     #[tokio::test]
     #[serial]
     async fn start_with_takeover_ephemeral_port_writes_actual_live_url() {
@@ -1,6 +1,6 @@
 //! S3 HTTP transport layer.
 //!
-//! Low-level HTTP primitives for S3 operations (PUT, GET, HEAD).
+//! Low-level HTTP primitives for S3 operations (PUT, GET, HEAD, DELETE, ListObjectsV2).
 //! Implements AWS Signature Version 4 request signing.
 //! The sync protocol logic lives in the upcoming `s3_sync` module.
 
@@ -332,7 +332,7 @@ pub(crate) async fn test_connection(creds: &S3Credentials) -> Result<(), AppErro
         )
     })?;
 
-    let client = http_client::get();
+    let client = http_client::get_auxiliary("s3 sync").map_err(AppError::Message)?;
     let body_hash = sha256_hex(b"");
     let mut headers = reqwest::header::HeaderMap::new();
     sign_request(
@@ -374,7 +374,7 @@ pub(crate) async fn put_object(
         )
     })?;
 
-    let client = http_client::get();
+    let client = http_client::get_auxiliary("s3 sync").map_err(AppError::Message)?;
     let body_hash = sha256_hex(&bytes);
     let mut headers = reqwest::header::HeaderMap::new();
     headers.insert("content-type", content_type.parse().unwrap());
@@ -419,7 +419,7 @@ pub(crate) async fn get_object(
         )
     })?;
 
-    let client = http_client::get();
+    let client = http_client::get_auxiliary("s3 sync").map_err(AppError::Message)?;
     let body_hash = sha256_hex(b"");
     let mut headers = reqwest::header::HeaderMap::new();
     sign_request(
@@ -485,7 +485,7 @@ pub(crate) async fn head_object(
         )
     })?;
 
-    let client = http_client::get();
+    let client = http_client::get_auxiliary("s3 sync").map_err(AppError::Message)?;
     let body_hash = sha256_hex(b"");
     let mut headers = reqwest::header::HeaderMap::new();
     sign_request(
@@ -518,6 +518,93 @@ pub(crate) async fn head_object(
         .map(|s| s.to_string()))
 }
 
+/// Delete an S3 object. S3 reports success for missing keys as well.
+pub(crate) async fn delete_object(creds: &S3Credentials, key: &str) -> Result<(), AppError> {
+    let url_str = build_object_url(creds, key);
+    let url = Url::parse(&url_str).map_err(|e| {
+        AppError::localized(
+            "s3.url.invalid",
+            format!("S3 URL 无效: {e}"),
+            format!("Invalid S3 URL: {e}"),
+        )
+    })?;
+
+    let client = http_client::get_auxiliary("s3 sync").map_err(AppError::Message)?;
+    let body_hash = sha256_hex(b"");
+    let mut headers = reqwest::header::HeaderMap::new();
+    sign_request(
+        "DELETE",
+        &url,
+        &mut headers,
+        &body_hash,
+        creds,
+        chrono::Utc::now(),
+    );
+
+    let resp = client
+        .delete(url.as_str())
+        .headers(headers)
+        .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| s3_transport_error("s3.delete_failed", "DELETE 请求", "DELETE request", &e))?;
+
+    if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND {
+        return Ok(());
+    }
+    Err(s3_status_error("DELETE", resp.status(), &url_str))
+}
+
+/// List object keys under `prefix` via ListObjectsV2 (first page, up to 1000 keys).
+pub(crate) async fn list_objects(
+    creds: &S3Credentials,
+    prefix: &str,
+) -> Result<Vec<String>, AppError> {
+    let mut url = Url::parse(&build_bucket_url(creds)).map_err(|e| {
+        AppError::localized(
+            "s3.url.invalid",
+            format!("S3 URL 无效: {e}"),
+            format!("Invalid S3 URL: {e}"),
+        )
+    })?;
+    url.query_pairs_mut()
+        .append_pair("list-type", "2")
+        .append_pair("prefix", prefix.trim_start_matches('/'));
+    let url_str = url.to_string();
+
+    let client = http_client::get_auxiliary("s3 sync").map_err(AppError::Message)?;
+    let body_hash = sha256_hex(b"");
+    let mut headers = reqwest::header::HeaderMap::new();
+    sign_request(
+        "GET",
+        &url,
+        &mut headers,
+        &body_hash,
+        creds,
+        chrono::Utc::now(),
+    );
+
+    let resp = client
+        .get(url.as_str())
+        .headers(headers)
+        .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| s3_transport_error("s3.list_failed", "LIST 请求", "LIST request", &e))?;
+
+    if !resp.status().is_success() {
+        return Err(s3_status_error("LIST", resp.status(), &url_str));
+    }
+    let body = resp.text().await.map_err(|e| {
+        AppError::localized(
+            "s3.response_read_failed",
+            format!("读取 S3 响应失败: {e}"),
+            format!("Failed to read S3 response: {e}"),
+        )
+    })?;
+    Ok(super::sync_backend::xml_element_texts(&body, "Key"))
+}
+
 // ─── Tests ───────────────────────────────────────────────────
 
 #[cfg(test)]
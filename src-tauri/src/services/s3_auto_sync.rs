@@ -9,8 +9,9 @@ use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
 
 use crate::error::AppError;
+use crate::services::notifier::{localized_text, Notification, NotifierService};
 use crate::services::s3_sync;
-use crate::settings::{self, S3SyncSettings};
+use crate::settings::{self, NotificationEvent, S3SyncSettings};
 
 const AUTO_SYNC_DEBOUNCE_MS: u64 = 1000;
 pub(crate) const MAX_AUTO_SYNC_WAIT_MS: u64 = 10_000;
@@ -48,6 +49,7 @@ pub fn should_trigger_for_table(table: &str) -> bool {
             | "provider_endpoints"
             | "mcp_servers"
             | "prompts"
+            | "prompt_versions"
             | "skills"
             | "skill_repos"
             | "settings"
@@ -103,6 +105,16 @@ fn emit_auto_sync_status_updated(app: &AppHandle, status: &str, error: Option<&s
     }
 }
 
+fn notify_auto_sync_failed(error: &AppError) {
+    let notification = Notification::new(
+        NotificationEvent::SyncFailed,
+        localized_text("S3 自动同步失败", "S3 auto sync failed"),
+        error.to_string(),
+    )
+    .with_dedup_key("s3");
+    NotifierService::dispatch(notification);
+}
+
 async fn run_auto_sync_upload(
     db: &crate::database::Database,
     app: &AppHandle,
@@ -125,6 +137,7 @@ async fn run_auto_sync_upload(
         }
         Err(err) => {
             persist_auto_sync_error(&mut sync_settings, &err);
+            notify_auto_sync_failed(&err);
             emit_auto_sync_status_updated(app, "error", Some(&err.to_string()));
             Err(err)
         }
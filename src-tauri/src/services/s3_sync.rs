@@ -11,7 +11,9 @@ use chrono::Utc;
 use serde_json::Value;
 
 use crate::error::AppError;
+use crate::progress_events::ProgressReporter;
 use crate::services::s3::{self, S3Credentials};
+use crate::services::sync_backend::{delete_all, Encrypted, S3Backend, SyncBackend};
 use crate::settings::{update_s3_sync_status, S3SyncSettings, WebDavSyncStatus};
 
 use super::sync_protocol::{
@@ -20,6 +22,7 @@ use super::sync_protocol::{
     RemoteLayout, SyncManifest, DB_COMPAT_VERSION, MAX_MANIFEST_BYTES, MAX_SYNC_ARTIFACT_BYTES,
     PROTOCOL_VERSION, REMOTE_DB_SQL, REMOTE_MANIFEST, REMOTE_SKILLS_ZIP,
 };
+use super::usage_sync;
 
 // ─── Sync lock ───────────────────────────────────────────────
 
@@ -41,8 +44,7 @@ where
 /// Check S3 connectivity by issuing a HEAD request against the bucket.
 pub async fn check_connection(settings: &S3SyncSettings) -> Result<(), AppError> {
     settings.validate()?;
-    let creds = creds_for(settings);
-    s3::test_connection(&creds).await
+    s3::test_connection(&creds_for(settings)).await
 }
 
 /// Upload local snapshot (db + skills) to remote S3.
@@ -51,28 +53,53 @@ pub async fn upload(
     settings: &mut S3SyncSettings,
 ) -> Result<Value, AppError> {
     settings.validate()?;
-    let creds = creds_for(settings);
-
-    let snapshot = build_local_snapshot(db)?;
+    let progress = ProgressReporter::start("s3.upload");
+    let backend = backend_for(settings);
+
+    progress.step("snapshot", 0, 4, None);
+    let mut usage = if settings.sync_usage {
+        let previous = fetch_manifest(&backend)
+            .await?
+            .filter(|manifest| validate_manifest_compat(manifest, RemoteLayout::Current).is_ok());
+        Some(
+            usage_sync::plan_upload(
+                db,
+                &backend,
+                previous.as_ref(),
+                settings.status.usage_watermark,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+    let snapshot = build_local_snapshot(db, usage.as_ref())?;
 
     // Upload order: artifacts first, manifest last (best-effort consistency)
-    let db_key = s3_key(settings, REMOTE_DB_SQL);
-    s3::put_object(&creds, &db_key, snapshot.db_sql, "application/sql").await?;
-
-    let skills_key = s3_key(settings, REMOTE_SKILLS_ZIP);
-    s3::put_object(&creds, &skills_key, snapshot.skills_zip, "application/zip").await?;
-
-    let manifest_key = s3_key(settings, REMOTE_MANIFEST);
-    s3::put_object(
-        &creds,
-        &manifest_key,
-        snapshot.manifest_bytes,
-        "application/json",
-    )
-    .await?;
+    progress.step("uploading", 1, 4, Some(REMOTE_DB_SQL.to_string()));
+    backend
+        .put(REMOTE_DB_SQL, snapshot.db_sql, "application/sql")
+        .await?;
+
+    progress.step("uploading", 2, 4, Some(REMOTE_SKILLS_ZIP.to_string()));
+    backend
+        .put(REMOTE_SKILLS_ZIP, snapshot.skills_zip, "application/zip")
+        .await?;
+    if let Some(plan) = usage.as_mut() {
+        usage_sync::upload_files(&backend, plan).await?;
+    }
+
+    progress.step("uploading", 3, 4, Some(REMOTE_MANIFEST.to_string()));
+    backend
+        .put(REMOTE_MANIFEST, snapshot.manifest_bytes, "application/json")
+        .await?;
+    if let Some(plan) = usage.as_ref() {
+        usage_sync::delete_obsolete(&backend, plan).await;
+        settings.status.usage_watermark = plan.watermark;
+    }
 
     // Fetch etag (best-effort, don't fail the upload)
-    let etag = match s3::head_object(&creds, &manifest_key).await {
+    let etag = match backend.head_etag(REMOTE_MANIFEST).await {
         Ok(e) => e,
         Err(e) => {
             log::debug!("[S3] Failed to fetch ETag after upload: {e}");
@@ -86,6 +113,7 @@ pub async fn upload(
         etag,
         persist_sync_success,
     );
+    progress.finish(None);
     Ok(serde_json::json!({ "status": "uploaded" }))
 }
 
@@ -95,10 +123,12 @@ pub async fn download(
     settings: &mut S3SyncSettings,
 ) -> Result<Value, AppError> {
     settings.validate()?;
-    let creds = creds_for(settings);
+    let progress = ProgressReporter::start("s3.download");
+    let backend = backend_for(settings);
 
-    let manifest_key = s3_key(settings, REMOTE_MANIFEST);
-    let (manifest_bytes, etag) = s3::get_object(&creds, &manifest_key, MAX_MANIFEST_BYTES)
+    progress.step("manifest", 0, 4, None);
+    let (manifest_bytes, etag) = backend
+        .get(REMOTE_MANIFEST, MAX_MANIFEST_BYTES)
         .await?
         .ok_or_else(|| {
             localized(
@@ -117,26 +147,60 @@ pub async fn download(
     validate_manifest_compat(&manifest, RemoteLayout::Current)?;
 
     // Download and verify artifacts
-    let db_sql = download_and_verify(settings, &creds, REMOTE_DB_SQL, &manifest.artifacts).await?;
-    let skills_zip =
-        download_and_verify(settings, &creds, REMOTE_SKILLS_ZIP, &manifest.artifacts).await?;
+    progress.step("downloading", 1, 4, Some(REMOTE_DB_SQL.to_string()));
+    let db_sql = download_and_verify(&backend, REMOTE_DB_SQL, &manifest.artifacts).await?;
+    progress.step("downloading", 2, 4, Some(REMOTE_SKILLS_ZIP.to_string()));
+    let skills_zip = download_and_verify(&backend, REMOTE_SKILLS_ZIP, &manifest.artifacts).await?;
 
     // Apply snapshot
+    progress.step("applying", 3, 4, None);
     apply_snapshot(db, &db_sql, &skills_zip)?;
+    if settings.sync_usage {
+        match usage_sync::pull(db, &backend, &manifest, settings.status.usage_watermark).await {
+            Ok(watermark) => settings.status.usage_watermark = watermark,
+            Err(e) => log::warn!("[S3] Failed to merge remote usage data: {e}"),
+        }
+    }
 
     let manifest_hash = sha256_hex(&manifest_bytes);
     let _persisted =
         persist_sync_success_best_effort(settings, manifest_hash, etag, persist_sync_success);
+    progress.finish(None);
     Ok(serde_json::json!({ "status": "downloaded" }))
 }
 
+/// Download and verify only the remote database artifact, for selective restore.
+pub(crate) async fn download_database(
+    settings: &S3SyncSettings,
+) -> Result<(SyncManifest, Vec<u8>), AppError> {
+    settings.validate()?;
+    let backend = backend_for(settings);
+    let (manifest_bytes, _) = backend
+        .get(REMOTE_MANIFEST, MAX_MANIFEST_BYTES)
+        .await?
+        .ok_or_else(|| {
+            localized(
+                "s3.sync.remote_empty",
+                "远端没有可下载的同步数据",
+                "No downloadable sync data found on the remote.",
+            )
+        })?;
+    let manifest: SyncManifest =
+        serde_json::from_slice(&manifest_bytes).map_err(|e| AppError::Json {
+            path: REMOTE_MANIFEST.to_string(),
+            source: e,
+        })?;
+    validate_manifest_compat(&manifest, RemoteLayout::Current)?;
+    let db_sql = download_and_verify(&backend, REMOTE_DB_SQL, &manifest.artifacts).await?;
+    Ok((manifest, db_sql))
+}
+
 /// Fetch remote manifest info without downloading artifacts.
 pub async fn fetch_remote_info(settings: &S3SyncSettings) -> Result<Option<Value>, AppError> {
     settings.validate()?;
-    let creds = creds_for(settings);
-    let manifest_key = s3_key(settings, REMOTE_MANIFEST);
+    let backend = backend_for(settings);
 
-    let Some((bytes, _)) = s3::get_object(&creds, &manifest_key, MAX_MANIFEST_BYTES).await? else {
+    let Some((bytes, _)) = backend.get(REMOTE_MANIFEST, MAX_MANIFEST_BYTES).await? else {
         return Ok(None);
     };
 
@@ -157,12 +221,19 @@ pub async fn fetch_remote_info(settings: &S3SyncSettings) -> Result<Option<Value
         "compatible": compatible,
         "artifacts": manifest.artifacts.keys().collect::<Vec<_>>(),
         "layout": RemoteLayout::Current.as_str(),
-        "remotePath": s3_dir_display(settings),
+        "remotePath": backend.location(),
     });
 
     Ok(Some(payload))
 }
 
+/// Delete every object in the profile directory on the remote.
+pub async fn delete_remote(settings: &S3SyncSettings) -> Result<Value, AppError> {
+    settings.validate()?;
+    let deleted = delete_all(&backend_for(settings)).await?;
+    Ok(serde_json::json!({ "status": "deleted", "deleted": deleted }))
+}
+
 // ─── Sync status persistence ─────────────────────────────────
 
 fn persist_sync_success(
@@ -177,6 +248,7 @@ fn persist_sync_success(
         last_local_manifest_hash: Some(manifest_hash.clone()),
         last_remote_manifest_hash: Some(manifest_hash),
         last_remote_etag: etag,
+        usage_watermark: settings.status.usage_watermark,
     };
     settings.status = status.clone();
     update_s3_sync_status(status)
@@ -184,9 +256,20 @@ fn persist_sync_success(
 
 // ─── Download & verify ───────────────────────────────────────
 
+async fn fetch_manifest(backend: &impl SyncBackend) -> Result<Option<SyncManifest>, AppError> {
+    let Some((bytes, _)) = backend.get(REMOTE_MANIFEST, MAX_MANIFEST_BYTES).await? else {
+        return Ok(None);
+    };
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .map_err(|e| AppError::Json {
+            path: REMOTE_MANIFEST.to_string(),
+            source: e,
+        })
+}
+
 async fn download_and_verify(
-    settings: &S3SyncSettings,
-    creds: &S3Credentials,
+    backend: &impl SyncBackend,
     artifact_name: &str,
     artifacts: &BTreeMap<String, ArtifactMeta>,
 ) -> Result<Vec<u8>, AppError> {
@@ -199,8 +282,8 @@ async fn download_and_verify(
     })?;
     validate_artifact_size_limit(artifact_name, meta.size)?;
 
-    let key = s3_key(settings, artifact_name);
-    let (bytes, _) = s3::get_object(creds, &key, MAX_SYNC_ARTIFACT_BYTES as usize)
+    let (bytes, _) = backend
+        .get(artifact_name, MAX_SYNC_ARTIFACT_BYTES as usize)
         .await?
         .ok_or_else(|| {
             localized(
@@ -220,11 +303,9 @@ async fn download_and_verify(
 ///
 /// Format: `{remote_root}/v{PROTOCOL_VERSION}/db-v{DB_COMPAT_VERSION}/{profile}/{artifact}`
 /// Example: `cc-switch-sync/v2/db-v6/default/manifest.json`
+#[cfg(test)]
 fn s3_key(settings: &S3SyncSettings, artifact: &str) -> String {
-    format!(
-        "{}/v{}/db-v{}/{}/{}",
-        settings.remote_root, PROTOCOL_VERSION, DB_COMPAT_VERSION, settings.profile, artifact
-    )
+    backend_for(settings).inner().key(artifact)
 }
 
 fn s3_dir_display(settings: &S3SyncSettings) -> String {
@@ -234,6 +315,16 @@ fn s3_dir_display(settings: &S3SyncSettings) -> String {
     )
 }
 
+fn backend_for(settings: &S3SyncSettings) -> Encrypted<S3Backend> {
+    Encrypted::new(
+        S3Backend {
+            creds: creds_for(settings),
+            prefix: s3_dir_display(settings),
+        },
+        settings.encryption_passphrase(),
+    )
+}
+
 fn creds_for(settings: &S3SyncSettings) -> S3Credentials {
     S3Credentials {
         access_key_id: settings.access_key_id.clone(),
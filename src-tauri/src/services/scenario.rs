@@ -0,0 +1,543 @@
+//! 场景宏：把多步操作串成一个命名脚本
+//!
+//! 宏文件放在 `~/.cc-switch/macros/` 下（`*.yaml` / `*.yml` / `*.json`），文件名即宏名：
+//!
+//! ```yaml
+//! description: 切到公司中转并开启代理
+//! hotkey: CmdOrCtrl+Alt+1
+//! steps:
+//!   - action: switch-provider
+//!     app: claude
+//!     provider: work-relay        # 供应商 id 或名称
+//!   - action: set-proxy
+//!     enabled: true
+//!   - action: set-takeover
+//!     app: claude
+//!     enabled: true
+//!   - action: set-failover-queue
+//!     app: claude
+//!     providers: [work-relay, backup-relay]
+//!   - action: open-terminal
+//!     cwd: ~/projects/demo
+//! ```
+//!
+//! 可从命令行（`cc-switch run <宏>`）、托盘菜单与全局快捷键执行。步骤按顺序执行，
+//! 任一步失败即停止，已完成的步骤不回滚。代理相关步骤依赖 GUI 进程内的代理服务，
+//! 命令行执行到这类步骤会报错，而不是在即将退出的命令行进程里启动代理。
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use tauri::async_runtime::block_on;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::profile::{ProfileScope, ProfileService};
+use crate::services::{ProviderService, ProviderSortUpdate};
+use crate::store::AppState;
+
+/// 宏的单个步骤
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum MacroStep {
+    /// 切换供应商（累加模式应用为加入 Live 配置）
+    SwitchProvider { app: String, provider: String },
+    /// 启动 / 停止本地代理（停止时恢复 Live 配置）
+    SetProxy { enabled: bool },
+    /// 开启 / 关闭某应用的代理接管
+    SetTakeover { app: String, enabled: bool },
+    /// 以给定顺序重建故障转移队列
+    SetFailoverQueue { app: String, providers: Vec<String> },
+    /// 应用项目 Profile
+    ApplyProfile {
+        profile: String,
+        #[serde(default)]
+        scope: Option<ProfileScope>,
+    },
+    /// 以供应商配置打开终端（默认 Claude 当前供应商）
+    OpenTerminal {
+        #[serde(default)]
+        app: Option<String>,
+        #[serde(default)]
+        provider: Option<String>,
+        #[serde(default)]
+        cwd: Option<String>,
+    },
+}
+
+impl MacroStep {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::SwitchProvider { .. } => "switch-provider",
+            Self::SetProxy { .. } => "set-proxy",
+            Self::SetTakeover { .. } => "set-takeover",
+            Self::SetFailoverQueue { .. } => "set-failover-queue",
+            Self::ApplyProfile { .. } => "apply-profile",
+            Self::OpenTerminal { .. } => "open-terminal",
+        }
+    }
+}
+
+/// 一个场景宏
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioMacro {
+    /// 宏名（取自文件名）
+    #[serde(default, skip_deserializing)]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// 全局快捷键，如 `CmdOrCtrl+Alt+1`
+    #[serde(default)]
+    pub hotkey: Option<String>,
+    pub steps: Vec<MacroStep>,
+}
+
+/// 宏的执行环境
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MacroRuntime {
+    /// GUI 进程内（托盘、快捷键），代理服务可用
+    App,
+    /// 命令行进程，执行完即退出
+    Cli,
+}
+
+/// 执行结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MacroRunReport {
+    pub name: String,
+    /// 成功完成的步骤数
+    pub completed: usize,
+    pub total: usize,
+    pub warnings: Vec<String>,
+    /// 执行过的供应商切换 (app, provider_id)，GUI 据此通知前端刷新
+    pub switched: Vec<(String, String)>,
+    /// 失败步骤的错误（`None` 表示全部成功）
+    pub error: Option<String>,
+}
+
+pub struct MacroService;
+
+impl MacroService {
+    /// 宏文件目录
+    pub fn macros_dir() -> PathBuf {
+        crate::config::get_app_config_dir().join("macros")
+    }
+
+    fn macro_files(dir: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && matches!(
+                        path.extension().and_then(|ext| ext.to_str()),
+                        Some("yaml" | "yml" | "json")
+                    )
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn parse_file(path: &Path) -> Result<ScenarioMacro, AppError> {
+        let text = std::fs::read_to_string(path).map_err(|e| AppError::io(path, e))?;
+        let mut scenario: ScenarioMacro =
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                serde_json::from_str(&text).map_err(|e| e.to_string())
+            } else {
+                serde_yaml::from_str(&text).map_err(|e| e.to_string())
+            }
+            .map_err(|e| {
+                AppError::localized(
+                    "macro.parse_failed",
+                    format!("解析宏文件 {} 失败: {e}", path.display()),
+                    format!("Failed to parse macro file {}: {e}", path.display()),
+                )
+            })?;
+        scenario.name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default()
+            .to_string();
+        Ok(scenario)
+    }
+
+    /// 列出全部宏；无法解析的文件记录日志后跳过
+    pub fn list() -> Vec<ScenarioMacro> {
+        Self::macro_files(&Self::macros_dir())
+            .iter()
+            .filter_map(|path| match Self::parse_file(path) {
+                Ok(scenario) => Some(scenario),
+                Err(e) => {
+                    log::warn!("[Macro] {e}");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// 按名称加载宏（解析失败时返回错误）
+    pub fn load(name: &str) -> Result<ScenarioMacro, AppError> {
+        Self::macro_files(&Self::macros_dir())
+            .iter()
+            .find(|path| path.file_stem().and_then(|stem| stem.to_str()) == Some(name))
+            .map(|path| Self::parse_file(path))
+            .unwrap_or_else(|| {
+                Err(AppError::localized(
+                    "macro.not_found",
+                    format!("找不到宏 {name}（目录 {}）", Self::macros_dir().display()),
+                    format!(
+                        "Macro {name} not found (in {})",
+                        Self::macros_dir().display()
+                    ),
+                ))
+            })
+    }
+
+    /// 按顺序执行宏的全部步骤，遇到失败即停止
+    pub fn run(
+        state: &AppState,
+        scenario: &ScenarioMacro,
+        runtime: MacroRuntime,
+    ) -> MacroRunReport {
+        let mut report = MacroRunReport {
+            name: scenario.name.clone(),
+            total: scenario.steps.len(),
+            ..Default::default()
+        };
+        for (index, step) in scenario.steps.iter().enumerate() {
+            if let Err(e) = run_step(state, step, runtime, &mut report) {
+                let message = format!("#{} {}: {e}", index + 1, step.label());
+                log::warn!("[Macro] {} 执行失败 {message}", scenario.name);
+                report.error = Some(message);
+                return report;
+            }
+            report.completed += 1;
+        }
+        log::info!(
+            "[Macro] {} 执行完成（{} 步）",
+            scenario.name,
+            report.completed
+        );
+        report
+    }
+}
+
+/// 在 GUI 进程中后台执行宏（托盘、快捷键入口）
+pub fn spawn_in_app(app: &tauri::AppHandle, name: String) {
+    use tauri::{Emitter, Manager};
+
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(state) = app.try_state::<AppState>() else {
+            return;
+        };
+        let scenario = match MacroService::load(&name) {
+            Ok(scenario) => scenario,
+            Err(e) => {
+                log::error!("[Macro] {e}");
+                return;
+            }
+        };
+        let report = MacroService::run(state.inner(), &scenario, MacroRuntime::App);
+        for warning in &report.warnings {
+            log::warn!("[Macro] {name}: {warning}");
+        }
+        for (app_type, provider_id) in &report.switched {
+            let payload = serde_json::json!({ "appType": app_type, "providerId": provider_id });
+            if let Err(e) = app.emit("provider-switched", payload) {
+                log::error!("发射 provider-switched 事件失败: {e}");
+            }
+        }
+        crate::tray::refresh_tray_menu(&app);
+    });
+}
+
+/// 全局快捷键 id → 宏名
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+static HOTKEY_MACROS: std::sync::Mutex<Vec<(u32, String)>> = std::sync::Mutex::new(Vec::new());
+
+/// 按宏文件重新注册全局快捷键（先注销本应用注册过的全部快捷键）
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn register_hotkeys(app: &tauri::AppHandle) {
+    use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+    let shortcuts = app.global_shortcut();
+    if let Err(e) = shortcuts.unregister_all() {
+        log::warn!("[Macro] 注销快捷键失败: {e}");
+    }
+    let mut bindings = HOTKEY_MACROS.lock().unwrap_or_else(|e| e.into_inner());
+    bindings.clear();
+    for scenario in MacroService::list() {
+        let Some(hotkey) = scenario.hotkey.as_deref() else {
+            continue;
+        };
+        let shortcut = match Shortcut::from_str(hotkey) {
+            Ok(shortcut) => shortcut,
+            Err(e) => {
+                log::warn!("[Macro] {} 的快捷键 {hotkey} 无效: {e}", scenario.name);
+                continue;
+            }
+        };
+        match shortcuts.register(shortcut) {
+            Ok(()) => bindings.push((shortcut.id(), scenario.name)),
+            Err(e) => log::warn!("[Macro] 注册快捷键 {hotkey} 失败: {e}"),
+        }
+    }
+    log::info!("[Macro] 已注册 {} 个宏快捷键", bindings.len());
+}
+
+/// 全局快捷键回调：按下时执行绑定的宏
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+pub fn handle_hotkey(
+    app: &tauri::AppHandle,
+    shortcut: &tauri_plugin_global_shortcut::Shortcut,
+    event: tauri_plugin_global_shortcut::ShortcutEvent,
+) {
+    if event.state() != tauri_plugin_global_shortcut::ShortcutState::Pressed {
+        return;
+    }
+    let name = HOTKEY_MACROS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|(id, _)| *id == shortcut.id())
+        .map(|(_, name)| name.clone());
+    if let Some(name) = name {
+        log::info!("[Macro] 快捷键触发宏 {name}");
+        spawn_in_app(app, name);
+    }
+}
+
+fn parse_app(app: &str) -> Result<AppType, AppError> {
+    AppType::from_str(app)
+}
+
+fn require_app_runtime(runtime: MacroRuntime) -> Result<(), AppError> {
+    match runtime {
+        MacroRuntime::App => Ok(()),
+        MacroRuntime::Cli => Err(AppError::localized(
+            "macro.requires_app",
+            "该步骤需要在运行中的 CC Switch 中执行（托盘或快捷键）",
+            "This step must run inside the running CC Switch app (tray or hotkey)",
+        )),
+    }
+}
+
+/// 按 id 或名称查找供应商；名称重复时要求使用 id
+fn resolve_provider(state: &AppState, app_type: &AppType, key: &str) -> Result<String, AppError> {
+    let providers = state.db.get_all_providers(app_type.as_str())?;
+    if providers.contains_key(key) {
+        return Ok(key.to_string());
+    }
+    let matches: Vec<&String> = providers
+        .iter()
+        .filter(|(_, provider)| provider.name == key)
+        .map(|(id, _)| id)
+        .collect();
+    match matches.as_slice() {
+        [id] => Ok((*id).clone()),
+        [] => Err(AppError::localized(
+            "macro.provider_not_found",
+            format!("{} 中找不到供应商 {key}", app_type.as_str()),
+            format!("Provider {key} not found in {}", app_type.as_str()),
+        )),
+        _ => Err(AppError::localized(
+            "macro.provider_ambiguous",
+            format!(
+                "{} 中有多个名为 {key} 的供应商，请改用 id",
+                app_type.as_str()
+            ),
+            format!(
+                "Multiple providers named {key} in {}; use the id instead",
+                app_type.as_str()
+            ),
+        )),
+    }
+}
+
+/// 把 `queue` 中的供应商按给定顺序放回它们原先占据的排序位置，其余供应商位置不变
+fn reorder_for_queue(ordered_ids: &[String], queue: &[String]) -> Vec<String> {
+    let mut next = queue.iter();
+    ordered_ids
+        .iter()
+        .map(|id| {
+            if queue.contains(id) {
+                next.next().cloned().unwrap_or_else(|| id.clone())
+            } else {
+                id.clone()
+            }
+        })
+        .collect()
+}
+
+/// 执行单个步骤；调用方需在阻塞线程中执行（切换供应商等操作是同步的，
+/// 代理服务的异步接口在这里 `block_on`）
+fn run_step(
+    state: &AppState,
+    step: &MacroStep,
+    runtime: MacroRuntime,
+    report: &mut MacroRunReport,
+) -> Result<(), AppError> {
+    match step {
+        MacroStep::SwitchProvider { app, provider } => {
+            let app_type = parse_app(app)?;
+            let id = resolve_provider(state, &app_type, provider)?;
+            let result = ProviderService::switch(state, app_type.clone(), &id)?;
+            report.warnings.extend(result.warnings);
+            report.switched.push((app_type.as_str().to_string(), id));
+        }
+        MacroStep::SetProxy { enabled } => {
+            require_app_runtime(runtime)?;
+            let proxy = &state.proxy_service;
+            if *enabled {
+                if !block_on(proxy.is_running()) {
+                    block_on(proxy.start()).map_err(AppError::Message)?;
+                }
+            } else if block_on(proxy.is_running()) {
+                block_on(proxy.stop_with_restore()).map_err(AppError::Message)?;
+            }
+        }
+        MacroStep::SetTakeover { app, enabled } => {
+            require_app_runtime(runtime)?;
+            let app_type = parse_app(app)?;
+            block_on(
+                state
+                    .proxy_service
+                    .set_takeover_for_app(app_type.as_str(), *enabled),
+            )
+            .map_err(AppError::Message)?;
+        }
+        MacroStep::SetFailoverQueue { app, providers } => {
+            let app_type = parse_app(app)?;
+            let app_str = app_type.as_str();
+            let queue = providers
+                .iter()
+                .map(|key| resolve_provider(state, &app_type, key))
+                .collect::<Result<Vec<_>, _>>()?;
+            state.db.clear_failover_queue(app_str)?;
+            for id in &queue {
+                state.db.add_to_failover_queue(app_str, id)?;
+            }
+            let ordered_ids: Vec<String> =
+                state.db.get_all_providers(app_str)?.into_keys().collect();
+            let updates = reorder_for_queue(&ordered_ids, &queue)
+                .into_iter()
+                .enumerate()
+                .map(|(sort_index, id)| ProviderSortUpdate { id, sort_index })
+                .collect();
+            ProviderService::update_sort_order(state, app_type, updates)?;
+        }
+        MacroStep::ApplyProfile { profile, scope } => {
+            let scope = scope.unwrap_or(ProfileScope::Claude);
+            let profiles = state.db.get_all_profiles()?;
+            let id = profiles
+                .iter()
+                .find(|p| p.id == *profile || p.name == *profile)
+                .map(|p| p.id.clone())
+                .ok_or_else(|| {
+                    AppError::localized(
+                        "macro.profile_not_found",
+                        format!("找不到项目 {profile}"),
+                        format!("Profile {profile} not found"),
+                    )
+                })?;
+            let (warnings, should_stop_proxy) = ProfileService::apply(state, &id, scope)?;
+            report.warnings.extend(warnings);
+            if should_stop_proxy {
+                require_app_runtime(runtime)?;
+                block_on(state.proxy_service.stop()).map_err(AppError::Message)?;
+            }
+        }
+        MacroStep::OpenTerminal { app, provider, cwd } => {
+            let app_type = parse_app(app.as_deref().unwrap_or("claude"))?;
+            let id = match provider {
+                Some(key) => resolve_provider(state, &app_type, key)?,
+                None => ProviderService::current(state, app_type.clone())?,
+            };
+            let cwd = cwd.as_deref().map(expand_home);
+            crate::commands::launch_provider_terminal(state, &app_type, &id, cwd)
+                .map_err(AppError::Message)?;
+        }
+    }
+    Ok(())
+}
+
+/// 展开路径开头的 `~`
+fn expand_home(path: &str) -> String {
+    match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') || rest.starts_with('\\') => {
+            format!("{}{rest}", crate::config::get_home_dir().display())
+        }
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_yaml_steps_with_kebab_case_actions() {
+        let scenario: ScenarioMacro = serde_yaml::from_str(
+            r#"
+hotkey: CmdOrCtrl+Alt+1
+steps:
+  - action: switch-provider
+    app: claude
+    provider: Work Relay
+  - action: set-proxy
+    enabled: true
+  - action: apply-profile
+    profile: demo
+    scope: claude-desktop
+  - action: open-terminal
+"#,
+        )
+        .unwrap();
+        assert_eq!(scenario.hotkey.as_deref(), Some("CmdOrCtrl+Alt+1"));
+        assert_eq!(
+            scenario.steps[0],
+            MacroStep::SwitchProvider {
+                app: "claude".into(),
+                provider: "Work Relay".into()
+            }
+        );
+        assert_eq!(
+            scenario.steps[2],
+            MacroStep::ApplyProfile {
+                profile: "demo".into(),
+                scope: Some(ProfileScope::ClaudeDesktop)
+            }
+        );
+        assert!(serde_yaml::from_str::<ScenarioMacro>("steps:\n  - action: reboot\n").is_err());
+    }
+
+    #[test]
+    fn failover_reorder_keeps_other_providers_in_place() {
+        let ids: Vec<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+        let queue = vec!["d".to_string(), "b".to_string()];
+        assert_eq!(reorder_for_queue(&ids, &queue), vec!["a", "d", "c", "b"]);
+    }
+
+    #[test]
+    fn cli_runtime_stops_at_proxy_steps() {
+        let state = AppState::new(std::sync::Arc::new(
+            crate::database::Database::memory().unwrap(),
+        ));
+        let scenario = ScenarioMacro {
+            name: "m".into(),
+            description: None,
+            hotkey: None,
+            steps: vec![MacroStep::SetProxy { enabled: true }],
+        };
+        let report = MacroService::run(&state, &scenario, MacroRuntime::Cli);
+        assert_eq!(report.completed, 0);
+        assert!(report.error.unwrap().starts_with("#1 set-proxy"));
+    }
+}
@@ -0,0 +1,369 @@
+//! 供应商密钥的 OS 钥匙串存储
+//!
+//! 默认情况下 API Key 以明文保存在 SQLite 与各应用的 live 配置副本中。开启
+//! 钥匙串后端后，供应商配置里的凭据字段只保存引用（`keychain:<app>/<id><pointer>`），
+//! 真实密钥交给系统钥匙串（macOS Keychain / Windows 凭据管理器 / Linux
+//! Secret Service）保管：
+//! - 写入 live 配置、代理转发、用量查询时才解析引用
+//! - 切走时的 live 回填会把明文换回引用（live 中的密钥被修改过则同步写回钥匙串）
+//! - 凭据字段按 [`ProviderService::is_sensitive_config_key`] 判定；Codex 的 TOML
+//!   `config` 字符串整体保存，其中内嵌的密钥不参与迁移
+//!
+//! 删除供应商不会清理钥匙串条目：复制出的供应商可能共享同一个引用。
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+/// 引用前缀
+pub const SECRET_REF_PREFIX: &str = "keychain:";
+
+/// 钥匙串中的服务名
+const KEYRING_SERVICE: &str = "cc-switch";
+
+/// 密钥存取后端（测试中以内存实现替换系统钥匙串）
+trait SecretStore {
+    fn get(&self, account: &str) -> Result<Option<String>, AppError>;
+    fn set(&self, account: &str, secret: &str) -> Result<(), AppError>;
+}
+
+struct KeyringStore;
+
+fn keyring_error(e: keyring::Error) -> AppError {
+    AppError::localized(
+        "secrets.keychain_failed",
+        format!("访问系统钥匙串失败: {e}"),
+        format!("Failed to access the OS keychain: {e}"),
+    )
+}
+
+impl SecretStore for KeyringStore {
+    fn get(&self, account: &str) -> Result<Option<String>, AppError> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE, account).map_err(keyring_error)?;
+        match entry.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(keyring_error(e)),
+        }
+    }
+
+    fn set(&self, account: &str, secret: &str) -> Result<(), AppError> {
+        keyring::Entry::new(KEYRING_SERVICE, account)
+            .and_then(|entry| entry.set_password(secret))
+            .map_err(keyring_error)
+    }
+}
+
+/// 字符串是否为钥匙串引用
+pub fn is_secret_ref(value: &str) -> bool {
+    value.starts_with(SECRET_REF_PREFIX)
+}
+
+fn contains_secret_ref(value: &Value) -> bool {
+    match value {
+        Value::String(s) => is_secret_ref(s),
+        Value::Array(items) => items.iter().any(contains_secret_ref),
+        Value::Object(map) => map.values().any(contains_secret_ref),
+        _ => false,
+    }
+}
+
+/// JSON Pointer 片段转义（RFC 6901）
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// 收集凭据字段（键名命中敏感规则、值为非空明文字符串）的 JSON Pointer
+fn collect_plaintext_secrets(value: &Value, pointer: &str, out: &mut Vec<String>) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    for (key, child) in map {
+        let child_pointer = format!("{pointer}/{}", escape_pointer_token(key));
+        match child {
+            Value::String(s)
+                if !s.trim().is_empty()
+                    && !is_secret_ref(s)
+                    && ProviderService::is_sensitive_config_key(key) =>
+            {
+                out.push(child_pointer);
+            }
+            Value::Object(_) => collect_plaintext_secrets(child, &child_pointer, out),
+            _ => {}
+        }
+    }
+}
+
+fn move_to_store(
+    store: &dyn SecretStore,
+    app: &str,
+    provider_id: &str,
+    settings: &mut Value,
+) -> Result<usize, AppError> {
+    let mut pointers = Vec::new();
+    collect_plaintext_secrets(settings, "", &mut pointers);
+    for pointer in &pointers {
+        let account = format!("{app}/{provider_id}{pointer}");
+        if let Some(slot) = settings.pointer_mut(pointer) {
+            if let Some(secret) = slot.as_str() {
+                store.set(&account, secret)?;
+                *slot = Value::String(format!("{SECRET_REF_PREFIX}{account}"));
+            }
+        }
+    }
+    Ok(pointers.len())
+}
+
+fn resolve_with(store: &dyn SecretStore, value: &mut Value) -> Result<(), AppError> {
+    match value {
+        Value::String(s) => {
+            if let Some(account) = s.strip_prefix(SECRET_REF_PREFIX) {
+                let secret = store.get(account)?.ok_or_else(|| {
+                    AppError::localized(
+                        "secrets.missing",
+                        format!("钥匙串中找不到密钥 {account}，请重新填写 API Key"),
+                        format!(
+                            "Secret {account} is missing from the OS keychain; \
+                             please re-enter the API key"
+                        ),
+                    )
+                })?;
+                *s = secret;
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                resolve_with(store, item)?;
+            }
+        }
+        Value::Object(map) => {
+            for child in map.values_mut() {
+                resolve_with(store, child)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn restore_with(
+    store: &dyn SecretStore,
+    original: &Value,
+    pointer: &str,
+    updated: &mut Value,
+) -> Result<(), AppError> {
+    match original {
+        Value::String(reference) if is_secret_ref(reference) => {
+            let Some(slot) = updated.pointer_mut(pointer) else {
+                return Ok(());
+            };
+            let Some(live_secret) = slot.as_str().filter(|s| !is_secret_ref(s)) else {
+                return Ok(());
+            };
+            let account = &reference[SECRET_REF_PREFIX.len()..];
+            if store.get(account)?.as_deref() != Some(live_secret) {
+                store.set(account, live_secret)?;
+            }
+            *slot = Value::String(reference.clone());
+        }
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_pointer = format!("{pointer}/{}", escape_pointer_token(key));
+                restore_with(store, child, &child_pointer, updated)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 把供应商配置中的明文凭据移入钥匙串并替换为引用，返回迁移的字段数
+pub fn store_provider_secrets(
+    app: &str,
+    provider_id: &str,
+    settings: &mut Value,
+) -> Result<usize, AppError> {
+    move_to_store(&KeyringStore, app, provider_id, settings)
+}
+
+/// 凭据字段（敏感键名、非空明文）的 JSON Pointer 列表
+pub(crate) fn plaintext_secret_pointers(settings: &Value) -> Vec<String> {
+    let mut pointers = Vec::new();
+    collect_plaintext_secrets(settings, "", &mut pointers);
+    pointers
+}
+
+/// 返回解析了所有钥匙串引用的配置副本（无引用时不访问钥匙串）
+pub fn resolve_settings(settings: &Value) -> Result<Value, AppError> {
+    let mut resolved = settings.clone();
+    if contains_secret_ref(settings) {
+        resolve_with(&KeyringStore, &mut resolved)?;
+    }
+    Ok(resolved)
+}
+
+/// 解析单个字符串值；非引用原样返回，解析失败时记录日志并返回空串
+pub fn resolve_secret_lossy(value: &str) -> String {
+    let Some(account) = value.strip_prefix(SECRET_REF_PREFIX) else {
+        return value.to_string();
+    };
+    match KeyringStore.get(account) {
+        Ok(Some(secret)) => secret,
+        Ok(None) => {
+            log::warn!("[Secrets] 钥匙串中找不到 {account}");
+            String::new()
+        }
+        Err(e) => {
+            log::warn!("[Secrets] 读取 {account} 失败: {e}");
+            String::new()
+        }
+    }
+}
+
+/// live 回填：`original` 中为引用的字段，若回填值是明文则换回引用；
+/// 明文与钥匙串中的值不同（用户直接改了 live 配置）时同步写回钥匙串。
+///
+/// 失败只记录日志——宁可让这次回填留下明文，也不能阻断切换。
+pub fn restore_refs_for_backfill(original: &Value, updated: &mut Value) {
+    if !contains_secret_ref(original) {
+        return;
+    }
+    if let Err(e) = restore_with(&KeyringStore, original, "", updated) {
+        log::warn!("[Secrets] 回填时恢复钥匙串引用失败: {e}");
+    }
+}
+
+/// 迁移结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretsMigrationReport {
+    /// 有字段被迁移的供应商数
+    pub providers: usize,
+    /// 迁移的凭据字段总数
+    pub secrets: usize,
+    /// 迁移失败的供应商（`app/id: 错误`）
+    pub failed: Vec<String>,
+}
+
+/// 把所有应用下所有供应商的明文凭据迁移到钥匙串
+///
+/// 单个供应商失败不影响其余供应商；该供应商保持原样（仍为明文）。
+pub fn migrate_all(state: &AppState) -> Result<SecretsMigrationReport, AppError> {
+    let mut report = SecretsMigrationReport::default();
+    for app_type in AppType::all() {
+        let app = app_type.as_str();
+        for (id, provider) in state.db.get_all_providers(app)? {
+            let mut updated = provider.clone();
+            let moved = match store_provider_secrets(app, &id, &mut updated.settings_config) {
+                Ok(moved) => moved,
+                Err(e) => {
+                    report.failed.push(format!("{app}/{id}: {e}"));
+                    continue;
+                }
+            };
+            if moved == 0 {
+                continue;
+            }
+            if let Err(e) = state.db.save_provider(app, &updated) {
+                report.failed.push(format!("{app}/{id}: {e}"));
+                continue;
+            }
+            report.providers += 1;
+            report.secrets += moved;
+        }
+    }
+    log::info!(
+        "[Secrets] 已迁移 {} 个供应商的 {} 个凭据字段到钥匙串",
+        report.providers,
+        report.secrets
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MemoryStore(RefCell<HashMap<String, String>>);
+
+    impl SecretStore for MemoryStore {
+        fn get(&self, account: &str) -> Result<Option<String>, AppError> {
+            Ok(self.0.borrow().get(account).cloned())
+        }
+
+        fn set(&self, account: &str, secret: &str) -> Result<(), AppError> {
+            self.0
+                .borrow_mut()
+                .insert(account.to_string(), secret.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn migrate_then_resolve_round_trips() {
+        let store = MemoryStore::default();
+        let original = json!({
+            "env": {
+                "ANTHROPIC_AUTH_TOKEN": "sk-secret",
+                "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                "CLAUDE_CODE_MAX_OUTPUT_TOKENS": "32000",
+                "ANTHROPIC_API_KEY": ""
+            }
+        });
+        let mut settings = original.clone();
+        assert_eq!(
+            move_to_store(&store, "claude", "p1", &mut settings).unwrap(),
+            1
+        );
+        assert_eq!(
+            settings["env"]["ANTHROPIC_AUTH_TOKEN"],
+            "keychain:claude/p1/env/ANTHROPIC_AUTH_TOKEN"
+        );
+        assert_eq!(
+            settings["env"]["ANTHROPIC_BASE_URL"],
+            "https://relay.example.com"
+        );
+
+        // 已是引用的字段不会重复迁移
+        assert_eq!(
+            move_to_store(&store, "claude", "p1", &mut settings).unwrap(),
+            0
+        );
+
+        let mut resolved = settings.clone();
+        resolve_with(&store, &mut resolved).unwrap();
+        assert_eq!(resolved, original);
+    }
+
+    #[test]
+    fn resolve_fails_when_secret_is_missing() {
+        let mut settings = json!({ "apiKey": "keychain:openclaw/gone/apiKey" });
+        assert!(resolve_with(&MemoryStore::default(), &mut settings).is_err());
+    }
+
+    #[test]
+    fn backfill_restores_refs_and_syncs_edited_secret() {
+        let store = MemoryStore::default();
+        let mut stored = json!({ "auth": { "OPENAI_API_KEY": "old" }, "config": "" });
+        move_to_store(&store, "codex", "c1", &mut stored).unwrap();
+
+        let mut live = json!({ "auth": { "OPENAI_API_KEY": "rotated" }, "config": "" });
+        restore_with(&store, &stored, "", &mut live).unwrap();
+        assert_eq!(live, stored);
+        assert_eq!(
+            store
+                .get("codex/c1/auth/OPENAI_API_KEY")
+                .unwrap()
+                .as_deref(),
+            Some("rotated")
+        );
+    }
+}
@@ -0,0 +1,427 @@
+//! 供应商密钥与密码管理器互通
+//!
+//! 导出为 1Password / Bitwarden 可直接导入的 CSV / JSON，每个凭据字段一条登录项：
+//! 用户名固定为凭据定位串 `<app>/<provider_id><json_pointer>`（与钥匙串账户名一致），
+//! 密码为明文密钥。导入时只认这个定位串，因此条目可以在密码管理器里随意改名、移动，
+//! 轮换密钥后再导入即可回写到对应供应商。
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::services::secrets;
+use crate::services::ProviderService;
+use crate::store::AppState;
+
+/// Bitwarden CSV 表头（个人密码库格式）
+const BITWARDEN_CSV_HEADER: &str =
+    "folder,favorite,type,name,notes,fields,reprompt,login_uri,login_username,login_password,login_totp";
+
+/// 1Password CSV 表头（导入时按列名匹配）
+const ONEPASSWORD_CSV_HEADER: &str = "Title,Website,Username,Password,Notes";
+
+/// 导出条目所在的文件夹 / 备注
+const FOLDER_NAME: &str = "cc-switch";
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PasswordManagerFormat {
+    #[serde(rename = "bitwarden-json")]
+    BitwardenJson,
+    #[serde(rename = "bitwarden-csv")]
+    BitwardenCsv,
+    #[serde(rename = "1password-csv")]
+    OnePasswordCsv,
+}
+
+impl FromStr for PasswordManagerFormat {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "bitwarden-json" | "bitwarden" => Ok(Self::BitwardenJson),
+            "bitwarden-csv" => Ok(Self::BitwardenCsv),
+            "1password-csv" | "1password" => Ok(Self::OnePasswordCsv),
+            other => Err(AppError::InvalidInput(format!(
+                "不支持的导出格式: {other}（可选 bitwarden-json / bitwarden-csv / 1password-csv）"
+            ))),
+        }
+    }
+}
+
+/// 单个凭据字段
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SecretItem {
+    /// `<app>/<provider_id><json_pointer>`
+    account: String,
+    title: String,
+    website: Option<String>,
+    secret: String,
+}
+
+/// 导出结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretsExportReport {
+    pub path: String,
+    /// 导出的凭据字段数
+    pub items: usize,
+    /// 钥匙串读取失败而跳过的供应商（`app/id: 错误`）
+    pub skipped: Vec<String>,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretsImportReport {
+    /// 密钥有变化并已写回的字段数
+    pub updated: usize,
+    /// 与当前密钥相同的字段数
+    pub unchanged: usize,
+    /// 无法对应到现有供应商字段的条目（定位串或原因）
+    pub skipped: Vec<String>,
+}
+
+/// 导出所有供应商的凭据字段到 `path`
+pub fn export_to_file(
+    state: &AppState,
+    format: PasswordManagerFormat,
+    path: &str,
+) -> Result<SecretsExportReport, AppError> {
+    let mut report = SecretsExportReport {
+        path: path.to_string(),
+        ..Default::default()
+    };
+    let mut items = Vec::new();
+    for app_type in AppType::all() {
+        let app = app_type.as_str();
+        for (id, provider) in state.db.get_all_providers(app)? {
+            let settings = match secrets::resolve_settings(&provider.settings_config) {
+                Ok(settings) => settings,
+                Err(e) => {
+                    report.skipped.push(format!("{app}/{id}: {e}"));
+                    continue;
+                }
+            };
+            for pointer in secrets::plaintext_secret_pointers(&settings) {
+                let Some(secret) = settings.pointer(&pointer).and_then(Value::as_str) else {
+                    continue;
+                };
+                items.push(SecretItem {
+                    account: format!("{app}/{id}{pointer}"),
+                    title: format!("{} ({app})", provider.name),
+                    website: provider.website_url.clone().filter(|url| !url.is_empty()),
+                    secret: secret.to_string(),
+                });
+            }
+        }
+    }
+
+    let content = render(&items, format)?;
+    std::fs::write(path, content).map_err(|e| AppError::io(path, e))?;
+    report.items = items.len();
+    log::info!(
+        "[Secrets] 已导出 {} 个凭据字段到 {path}（{format:?}）",
+        report.items
+    );
+    Ok(report)
+}
+
+/// 从密码管理器导出的 CSV / JSON 回写密钥
+///
+/// 只更新定位串能对应到现有供应商字段、且密钥确有变化的条目；
+/// 写回走正常的供应商更新流程（开启钥匙串时自动存回钥匙串，当前供应商同步 live 配置）。
+pub fn import_from_content(
+    state: &AppState,
+    content: &str,
+) -> Result<SecretsImportReport, AppError> {
+    let mut report = SecretsImportReport::default();
+    for (account, secret) in parse_items(content)? {
+        let Some((app_type, provider_id, pointer)) = parse_account(&account) else {
+            continue;
+        };
+        let Some(mut provider) = state
+            .db
+            .get_provider_by_id(&provider_id, app_type.as_str())?
+        else {
+            report.skipped.push(account);
+            continue;
+        };
+        let Some(slot) = provider.settings_config.pointer_mut(&pointer) else {
+            report.skipped.push(account);
+            continue;
+        };
+        let Some(current) = slot.as_str() else {
+            report.skipped.push(account);
+            continue;
+        };
+        if secrets::resolve_secret_lossy(current) == secret {
+            report.unchanged += 1;
+            continue;
+        }
+        *slot = Value::String(secret);
+        if let Err(e) = ProviderService::update(state, app_type, None, provider) {
+            report.skipped.push(format!("{account}: {e}"));
+            continue;
+        }
+        report.updated += 1;
+    }
+    log::info!(
+        "[Secrets] 从密码管理器导入：更新 {}，未变 {}，跳过 {}",
+        report.updated,
+        report.unchanged,
+        report.skipped.len()
+    );
+    Ok(report)
+}
+
+fn render(items: &[SecretItem], format: PasswordManagerFormat) -> Result<String, AppError> {
+    match format {
+        PasswordManagerFormat::BitwardenJson => {
+            let folder_id = uuid::Uuid::new_v4().to_string();
+            let items: Vec<Value> = items
+                .iter()
+                .map(|item| {
+                    let uris: Vec<Value> = item
+                        .website
+                        .iter()
+                        .map(|uri| json!({ "match": null, "uri": uri }))
+                        .collect();
+                    json!({
+                        "id": uuid::Uuid::new_v4().to_string(),
+                        "folderId": folder_id,
+                        "type": 1,
+                        "reprompt": 0,
+                        "name": item.title,
+                        "notes": FOLDER_NAME,
+                        "favorite": false,
+                        "login": {
+                            "uris": uris,
+                            "username": item.account,
+                            "password": item.secret,
+                            "totp": null
+                        }
+                    })
+                })
+                .collect();
+            serde_json::to_string_pretty(&json!({
+                "encrypted": false,
+                "folders": [{ "id": folder_id, "name": FOLDER_NAME }],
+                "items": items
+            }))
+            .map_err(|e| AppError::JsonSerialize { source: e })
+        }
+        PasswordManagerFormat::BitwardenCsv => {
+            let mut out = format!("{BITWARDEN_CSV_HEADER}\n");
+            for item in items {
+                let row = [
+                    FOLDER_NAME,
+                    "",
+                    "login",
+                    &item.title,
+                    "",
+                    "",
+                    "0",
+                    item.website.as_deref().unwrap_or_default(),
+                    &item.account,
+                    &item.secret,
+                    "",
+                ];
+                out.push_str(&csv_row(&row));
+            }
+            Ok(out)
+        }
+        PasswordManagerFormat::OnePasswordCsv => {
+            let mut out = format!("{ONEPASSWORD_CSV_HEADER}\n");
+            for item in items {
+                let row = [
+                    item.title.as_str(),
+                    item.website.as_deref().unwrap_or_default(),
+                    &item.account,
+                    &item.secret,
+                    FOLDER_NAME,
+                ];
+                out.push_str(&csv_row(&row));
+            }
+            Ok(out)
+        }
+    }
+}
+
+/// 解析密码管理器导出，返回 (用户名, 密码)
+///
+/// JSON 按 Bitwarden 结构读取 `items[].login`；CSV 按表头中的
+/// `login_username` / `username` 与 `login_password` / `password` 列读取（不区分大小写）。
+fn parse_items(content: &str) -> Result<Vec<(String, String)>, AppError> {
+    let content = content.trim_start_matches('\u{feff}');
+    if content.trim_start().starts_with('{') {
+        let value: Value = serde_json::from_str(content)
+            .map_err(|e| AppError::InvalidInput(format!("无法解析 Bitwarden JSON: {e}")))?;
+        return Ok(value
+            .get("items")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|item| {
+                let login = item.get("login")?;
+                Some((
+                    login.get("username")?.as_str()?.to_string(),
+                    login.get("password")?.as_str()?.to_string(),
+                ))
+            })
+            .collect());
+    }
+
+    let mut rows = parse_csv(content).into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .unwrap_or_default()
+        .iter()
+        .map(|name| name.trim().to_ascii_lowercase())
+        .collect();
+    let column = |names: &[&str]| header.iter().position(|h| names.contains(&h.as_str()));
+    let (Some(user_col), Some(pass_col)) = (
+        column(&["login_username", "username"]),
+        column(&["login_password", "password"]),
+    ) else {
+        return Err(AppError::InvalidInput(
+            "CSV 缺少 username / password 列".to_string(),
+        ));
+    };
+    Ok(rows
+        .filter_map(|row| Some((row.get(user_col)?.clone(), row.get(pass_col)?.clone())))
+        .collect())
+}
+
+/// 拆分定位串 `<app>/<provider_id><json_pointer>`；不是本应用导出的条目返回 `None`
+fn parse_account(account: &str) -> Option<(AppType, String, String)> {
+    let (app, rest) = account.trim().split_once('/')?;
+    let app_type = AppType::from_str(app).ok()?;
+    let (provider_id, pointer) = rest.split_once('/')?;
+    if provider_id.is_empty() || pointer.is_empty() {
+        return None;
+    }
+    Some((app_type, provider_id.to_string(), format!("/{pointer}")))
+}
+
+/// CSV 字段转义：含逗号、引号或换行时加引号，引号双写
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_row(fields: &[&str]) -> String {
+    let mut row = fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push('\n');
+    row
+}
+
+/// 最小 RFC 4180 解析：支持引号字段、字段内换行与双写引号
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                let done = std::mem::take(&mut row);
+                if done.iter().any(|f| !f.is_empty()) {
+                    rows.push(done);
+                }
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    row.push(field);
+    if row.iter().any(|f| !f.is_empty()) {
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<SecretItem> {
+        vec![
+            SecretItem {
+                account: "claude/relay/env/ANTHROPIC_AUTH_TOKEN".to_string(),
+                title: "Relay, \"main\" (claude)".to_string(),
+                website: Some("https://relay.example.com".to_string()),
+                secret: "sk-a,b\"c".to_string(),
+            },
+            SecretItem {
+                account: "codex/c1/auth/OPENAI_API_KEY".to_string(),
+                title: "Codex (codex)".to_string(),
+                website: None,
+                secret: "sk-codex".to_string(),
+            },
+        ]
+    }
+
+    fn expected() -> Vec<(String, String)> {
+        items()
+            .into_iter()
+            .map(|item| (item.account, item.secret))
+            .collect()
+    }
+
+    #[test]
+    fn every_format_round_trips() {
+        for format in [
+            PasswordManagerFormat::BitwardenJson,
+            PasswordManagerFormat::BitwardenCsv,
+            PasswordManagerFormat::OnePasswordCsv,
+        ] {
+            let content = render(&items(), format).unwrap();
+            assert_eq!(parse_items(&content).unwrap(), expected(), "{format:?}");
+        }
+    }
+
+    #[test]
+    fn csv_import_matches_columns_by_name() {
+        // 1Password 重新导出时列顺序与大小写可能不同
+        let content = "\u{feff}Password,Notes,Username\r\n\"sk-1\",\"multi\nline\",claude/p1/env/ANTHROPIC_AUTH_TOKEN\r\n";
+        assert_eq!(
+            parse_items(content).unwrap(),
+            vec![(
+                "claude/p1/env/ANTHROPIC_AUTH_TOKEN".to_string(),
+                "sk-1".to_string()
+            )]
+        );
+        assert!(parse_items("title,website\nfoo,bar\n").is_err());
+    }
+
+    #[test]
+    fn parse_account_requires_known_app_and_pointer() {
+        let (app, id, pointer) = parse_account("codex/c1/auth/OPENAI_API_KEY").unwrap();
+        assert_eq!(app, AppType::Codex);
+        assert_eq!(id, "c1");
+        assert_eq!(pointer, "/auth/OPENAI_API_KEY");
+        assert!(parse_account("someone@example.com").is_none());
+        assert!(parse_account("unknown/p1/apiKey").is_none());
+        assert!(parse_account("claude/p1").is_none());
+    }
+}
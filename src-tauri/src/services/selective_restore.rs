@@ -0,0 +1,349 @@
+//! 从云端备份中选择性恢复
+//!
+//! 完整下载会用远端快照整体覆盖本地数据库。这里只下载 `db.sql`，载入独立的内存数据库后
+//! 列出其中的供应商、MCP 服务器与设置项，由用户挑选后合并到本地：
+//!
+//! - 供应商按 `(应用, id)` 覆盖或新增，新增的排在本地列表末尾；本地当前供应商保持不变；
+//! - MCP 服务器按 id 覆盖或新增；
+//! - 设置只恢复可在设备间共享的配置项（通用配置片段、代理调优等），不含设备本地状态。
+//!
+//! 本地未被选中的条目一律保留，合并前会先备份本地数据库。
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::database::Database;
+use crate::error::AppError;
+
+/// 可随备份恢复的设置项
+const RESTORABLE_SETTING_KEYS: &[&str] = &[
+    "rectifier_config",
+    "retry_config",
+    "drain_config",
+    "failback_watchdog_config",
+    "optimizer_config",
+    "copilot_optimizer_config",
+    "response_cache_config",
+    "log_config",
+    "stream_check_config",
+    "self_test_config",
+];
+
+/// 通用配置片段按应用存放：`common_config_<app>`
+const COMMON_CONFIG_PREFIX: &str = "common_config_";
+
+/// 备份中的一个供应商
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupProviderEntry {
+    pub app_type: String,
+    pub id: String,
+    pub name: String,
+    /// 在备份中是否为当前供应商
+    pub is_current: bool,
+    /// 本地已存在同 id 的供应商（恢复时会覆盖）
+    pub exists_locally: bool,
+}
+
+/// 备份中的一个 MCP 服务器
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupMcpEntry {
+    pub id: String,
+    pub name: String,
+    pub exists_locally: bool,
+}
+
+/// 备份内容概览
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupContents {
+    pub device_name: String,
+    pub created_at: String,
+    pub providers: Vec<BackupProviderEntry>,
+    pub mcp_servers: Vec<BackupMcpEntry>,
+    /// 备份中可恢复的设置项键名
+    pub settings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderRef {
+    pub app_type: String,
+    pub id: String,
+}
+
+/// 用户的恢复选择
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreSelection {
+    #[serde(default)]
+    pub providers: Vec<ProviderRef>,
+    /// 整个应用的所有供应商
+    #[serde(default)]
+    pub apps: Vec<String>,
+    #[serde(default)]
+    pub mcp_servers: Vec<String>,
+    #[serde(default)]
+    pub settings: bool,
+}
+
+impl RestoreSelection {
+    fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+            && self.apps.is_empty()
+            && self.mcp_servers.is_empty()
+            && !self.settings
+    }
+
+    fn includes_provider(&self, app_type: &str, id: &str) -> bool {
+        self.apps.iter().any(|app| app == app_type)
+            || self
+                .providers
+                .iter()
+                .any(|provider| provider.app_type == app_type && provider.id == id)
+    }
+}
+
+/// 恢复结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreSummary {
+    pub providers: usize,
+    pub mcp_servers: usize,
+    pub settings: usize,
+}
+
+fn is_restorable_setting(key: &str) -> bool {
+    if RESTORABLE_SETTING_KEYS.contains(&key) {
+        return true;
+    }
+    // 片段本身及其“已清空”标记；迁移标记等内部状态不恢复
+    key.strip_prefix(COMMON_CONFIG_PREFIX).is_some_and(|rest| {
+        let app = rest.strip_suffix("_cleared").unwrap_or(rest);
+        AppType::all().any(|app_type| app_type.as_str() == app)
+    })
+}
+
+fn restorable_settings(backup: &Database) -> Result<Vec<(String, String)>, AppError> {
+    Ok(backup
+        .get_all_settings()?
+        .into_iter()
+        .filter(|(key, _)| is_restorable_setting(key))
+        .collect())
+}
+
+/// 把下载的 `db.sql` 载入内存数据库
+pub(crate) fn open_backup(db_sql: &[u8]) -> Result<Database, AppError> {
+    let sql = std::str::from_utf8(db_sql).map_err(|e| {
+        AppError::localized(
+            "sync.sql_not_utf8",
+            format!("SQL 非 UTF-8: {e}"),
+            format!("SQL is not valid UTF-8: {e}"),
+        )
+    })?;
+    Database::open_sql_export(sql)
+}
+
+/// 列出备份中可恢复的条目，并标注本地是否已存在
+pub fn preview(
+    backup: &Database,
+    local: &Database,
+    device_name: String,
+    created_at: String,
+) -> Result<BackupContents, AppError> {
+    let mut providers = Vec::new();
+    for app_type in AppType::all() {
+        let app = app_type.as_str();
+        let current = backup.get_current_provider(app)?;
+        let local_ids: HashSet<String> = local.get_all_providers(app)?.into_keys().collect();
+        for (id, provider) in backup.get_all_providers(app)? {
+            providers.push(BackupProviderEntry {
+                app_type: app.to_string(),
+                is_current: current.as_deref() == Some(id.as_str()),
+                exists_locally: local_ids.contains(&id),
+                name: provider.name,
+                id,
+            });
+        }
+    }
+
+    let local_mcp = local.get_all_mcp_servers()?;
+    let mcp_servers = backup
+        .get_all_mcp_servers()?
+        .into_values()
+        .map(|server| BackupMcpEntry {
+            exists_locally: local_mcp.contains_key(&server.id),
+            id: server.id,
+            name: server.name,
+        })
+        .collect();
+
+    let settings = restorable_settings(backup)?
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+
+    Ok(BackupContents {
+        device_name,
+        created_at,
+        providers,
+        mcp_servers,
+        settings,
+    })
+}
+
+/// 把选中的条目合并到本地数据库
+pub fn apply(
+    backup: &Database,
+    local: &Database,
+    selection: &RestoreSelection,
+) -> Result<RestoreSummary, AppError> {
+    if selection.is_empty() {
+        return Err(AppError::localized(
+            "sync.restore.nothing_selected",
+            "未选择任何要恢复的内容",
+            "Nothing was selected to restore",
+        ));
+    }
+    if let Err(e) = local.backup_database_file() {
+        log::warn!("[Restore] 选择性恢复前备份本地数据库失败: {e}");
+    }
+
+    let mut summary = RestoreSummary::default();
+    for app_type in AppType::all() {
+        let app = app_type.as_str();
+        let local_providers = local.get_all_providers(app)?;
+        let mut next_index = local_providers
+            .values()
+            .filter_map(|provider| provider.sort_index)
+            .max()
+            .map_or(0, |max| max + 1);
+        for (id, mut provider) in backup.get_all_providers(app)? {
+            if !selection.includes_provider(app, &id) {
+                continue;
+            }
+            // 覆盖时沿用本地编号，新增的排在末尾
+            provider.sort_index = match local_providers.get(&id) {
+                Some(existing) => existing.sort_index,
+                None => {
+                    next_index += 1;
+                    Some(next_index - 1)
+                }
+            };
+            local.save_provider(app, &provider)?;
+            summary.providers += 1;
+        }
+    }
+
+    if !selection.mcp_servers.is_empty() {
+        for server in backup.get_all_mcp_servers()?.into_values() {
+            if selection.mcp_servers.contains(&server.id) {
+                local.save_mcp_server(&server)?;
+                summary.mcp_servers += 1;
+            }
+        }
+    }
+
+    if selection.settings {
+        for (key, value) in restorable_settings(backup)? {
+            local.set_setting(&key, &value)?;
+            summary.settings += 1;
+        }
+    }
+
+    log::info!(
+        "[Restore] 已从备份恢复 {} 个供应商、{} 个 MCP 服务器、{} 项设置",
+        summary.providers,
+        summary.mcp_servers,
+        summary.settings
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::Provider;
+    use serde_json::json;
+
+    fn provider(id: &str, name: &str, sort_index: Option<usize>) -> Provider {
+        let mut provider = Provider::with_id(id.to_string(), name.to_string(), json!({}), None);
+        provider.sort_index = sort_index;
+        provider
+    }
+
+    #[test]
+    fn merges_only_selected_providers_and_keeps_local_numbers() {
+        let backup = Database::memory().unwrap();
+        backup
+            .save_provider("claude", &provider("a", "Relay A (backup)", Some(0)))
+            .unwrap();
+        backup
+            .save_provider("claude", &provider("b", "Relay B", Some(1)))
+            .unwrap();
+        backup
+            .save_provider("codex", &provider("c", "Codex C", Some(0)))
+            .unwrap();
+        backup
+            .set_setting("common_config_claude", "{\"env\":{}}")
+            .unwrap();
+        backup.set_setting("proxy_takeover_claude", "true").unwrap();
+        backup
+            .set_setting("common_config_legacy_migrated_v1", "true")
+            .unwrap();
+
+        let local = Database::memory().unwrap();
+        local
+            .save_provider("claude", &provider("a", "Relay A", Some(3)))
+            .unwrap();
+        local
+            .save_provider("claude", &provider("z", "Local only", Some(4)))
+            .unwrap();
+
+        let contents = preview(&backup, &local, "laptop".into(), "now".into()).unwrap();
+        assert_eq!(contents.providers.len(), 3);
+        assert!(contents
+            .providers
+            .iter()
+            .any(|entry| entry.id == "a" && entry.exists_locally));
+        assert_eq!(contents.settings, ["common_config_claude"]);
+
+        let selection = RestoreSelection {
+            providers: vec![
+                ProviderRef {
+                    app_type: "claude".into(),
+                    id: "a".into(),
+                },
+                ProviderRef {
+                    app_type: "claude".into(),
+                    id: "b".into(),
+                },
+            ],
+            settings: true,
+            ..Default::default()
+        };
+        let summary = apply(&backup, &local, &selection).unwrap();
+        assert_eq!(summary.providers, 2);
+        assert_eq!(summary.settings, 1);
+
+        let claude = local.get_all_providers("claude").unwrap();
+        assert_eq!(claude["a"].name, "Relay A (backup)");
+        assert_eq!(claude["a"].sort_index, Some(3));
+        assert_eq!(claude["b"].sort_index, Some(5));
+        assert!(claude.contains_key("z"));
+        assert!(local.get_all_providers("codex").unwrap().is_empty());
+        assert!(local
+            .get_setting("proxy_takeover_claude")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn empty_selection_is_rejected() {
+        let db = Database::memory().unwrap();
+        assert!(apply(&db, &db, &RestoreSelection::default()).is_err());
+    }
+}
@@ -0,0 +1,461 @@
+//! 夜间自检：凌晨对当前供应商与故障转移队列做连通性检查，早晨给出摘要
+//!
+//! - 只检查每个应用的当前供应商（primary）与故障转移队列中的供应商，
+//!   官方供应商没有可探测的目标，直接跳过；
+//! - 检查复用 [`StreamCheckService`]，结果同样写入 `stream_check_logs`；
+//! - 最近一次报告保存在 settings 表，到 `summary_hour` 后由定时任务发出
+//!   `self-test-summary` 事件；若届时窗口未打开，前端启动时再补弹一次。
+
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Local, Timelike};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::app_config::AppType;
+use crate::commands::{resolve_copilot_base_url_override, CopilotAuthState};
+use crate::database::Database;
+use crate::error::AppError;
+use crate::progress_events::ProgressReporter;
+use crate::proxy::HttpErrorClass;
+use crate::services::stream_check::{HealthStatus, StreamCheckResult, StreamCheckService};
+
+/// 前端监听的事件名
+pub const EVENT_SELF_TEST_SUMMARY: &str = "self-test-summary";
+
+/// 夜间自检配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestConfig {
+    /// 是否启用夜间自检（默认关闭）
+    #[serde(default)]
+    pub enabled: bool,
+    /// 自检时段（本地时间，0-23 点），默认凌晨 4 点
+    #[serde(default = "default_run_hour")]
+    pub run_hour: u8,
+    /// 摘要提醒时间（本地时间，0-23 点），默认早上 9 点
+    #[serde(default = "default_summary_hour")]
+    pub summary_hour: u8,
+    /// 网络变化（VPN 开关、切换 Wi-Fi）后自动重新检查，默认开启
+    #[serde(default = "default_network_recheck")]
+    pub network_recheck: bool,
+}
+
+fn default_run_hour() -> u8 {
+    4
+}
+
+fn default_summary_hour() -> u8 {
+    9
+}
+
+fn default_network_recheck() -> bool {
+    true
+}
+
+impl Default for SelfTestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            run_hour: default_run_hour(),
+            summary_hour: default_summary_hour(),
+            network_recheck: default_network_recheck(),
+        }
+    }
+}
+
+/// 供应商在代理链路中的角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum SelfTestRole {
+    Primary,
+    /// 故障转移队列中的位置（从 1 开始）
+    Failover {
+        position: usize,
+    },
+}
+
+/// 单个供应商的自检结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestEntry {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub role: SelfTestRole,
+    pub result: StreamCheckResult,
+    /// 检查时供应商处于维护时段，失败不算异常
+    #[serde(default)]
+    pub in_maintenance: bool,
+}
+
+impl SelfTestEntry {
+    /// 可达但返回 5xx 也视为异常。
+    ///
+    /// 连通性探测不携带凭据，根路径返回 401/404 属于正常现象，不能据此判断
+    /// 鉴权失效；5xx 则说明网关或上游本身出了问题。
+    pub fn is_healthy(&self) -> bool {
+        self.in_maintenance
+            || (self.result.success && self.result.http_status.is_none_or(|status| status < 500))
+    }
+
+    /// 形如 `primary OK 320ms`、`failover #2 returning 502`
+    fn describe(&self) -> String {
+        let role = match self.role {
+            SelfTestRole::Primary => "primary".to_string(),
+            SelfTestRole::Failover { position } => format!("failover #{position}"),
+        };
+        let outcome = match (self.result.success, self.result.http_status) {
+            (true, Some(status)) if status >= 500 => format!("returning {status}"),
+            (true, _) => {
+                let slow = if self.result.status == HealthStatus::Degraded {
+                    " (slow)"
+                } else {
+                    ""
+                };
+                match self.result.response_time_ms {
+                    Some(ms) => format!("OK {ms}ms{slow}"),
+                    None => format!("OK{slow}"),
+                }
+            }
+            (false, _) => format!("failed: {}", self.result.message),
+        };
+        if self.in_maintenance {
+            return format!("{role} {outcome} (maintenance window)");
+        }
+        format!("{role} {outcome}")
+    }
+}
+
+/// 单个应用的自检结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestAppReport {
+    pub app_type: String,
+    pub entries: Vec<SelfTestEntry>,
+    /// 一行摘要，如 `Claude primary OK 320ms; failover #2 returning 502`
+    pub summary: String,
+}
+
+/// 一次夜间自检的完整报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestReport {
+    /// 本地日期（YYYY-MM-DD），用于判断当天是否已执行
+    pub run_date: String,
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub apps: Vec<SelfTestAppReport>,
+    /// 摘要是否已经提醒过用户
+    #[serde(default)]
+    pub notified: bool,
+}
+
+impl SelfTestReport {
+    pub fn failure_count(&self) -> usize {
+        self.apps
+            .iter()
+            .flat_map(|app| &app.entries)
+            .filter(|entry| !entry.is_healthy())
+            .count()
+    }
+
+    pub fn summary_lines(&self) -> Vec<String> {
+        self.apps.iter().map(|app| app.summary.clone()).collect()
+    }
+}
+
+fn app_label(app_type: &AppType) -> &'static str {
+    match app_type {
+        AppType::Claude => "Claude",
+        AppType::ClaudeDesktop => "Claude Desktop",
+        AppType::Codex => "Codex",
+        AppType::Gemini => "Gemini",
+        AppType::GrokBuild => "Grok Build",
+        AppType::OpenCode => "OpenCode",
+        AppType::OpenClaw => "OpenClaw",
+        AppType::Hermes => "Hermes",
+    }
+}
+
+fn summarize_app(app_type: &AppType, entries: &[SelfTestEntry]) -> String {
+    let parts: Vec<String> = entries.iter().map(SelfTestEntry::describe).collect();
+    format!("{} {}", app_label(app_type), parts.join("; "))
+}
+
+/// 防止定时任务与「立即自检」并发执行
+fn run_lock() -> &'static Mutex<()> {
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+}
+
+pub struct SelfTestService;
+
+impl SelfTestService {
+    /// 当前时间是否落在自检时段内，且今天尚未执行
+    pub fn is_run_due(
+        config: &SelfTestConfig,
+        last: Option<&SelfTestReport>,
+        now: DateTime<Local>,
+    ) -> bool {
+        config.enabled
+            && now.hour() == u32::from(config.run_hour)
+            && last.is_none_or(|report| report.run_date != local_date(now))
+    }
+
+    /// 当天的报告已到提醒时间且尚未提醒
+    pub fn is_summary_due(
+        config: &SelfTestConfig,
+        report: &SelfTestReport,
+        now: DateTime<Local>,
+    ) -> bool {
+        !report.notified
+            && report.run_date == local_date(now)
+            && now.hour() >= u32::from(config.summary_hour)
+    }
+
+    /// 对所有应用的当前供应商与故障转移队列执行一次连通性检查并保存报告
+    pub async fn run(
+        db: &Database,
+        copilot_state: &CopilotAuthState,
+    ) -> Result<SelfTestReport, AppError> {
+        let started = Local::now();
+        let apps = Self::check_proxy_targets(db, Some(copilot_state), "self_test.run").await?;
+
+        let report = SelfTestReport {
+            run_date: local_date(started),
+            started_at: started.timestamp(),
+            finished_at: chrono::Utc::now().timestamp(),
+            apps,
+            notified: false,
+        };
+        db.save_self_test_report(&report)?;
+        log::info!(
+            "[SelfTest] 自检完成（{} 个异常）: {}",
+            report.failure_count(),
+            report.summary_lines().join(" | ")
+        );
+        Ok(report)
+    }
+
+    /// 检查所有应用的当前供应商与故障转移队列，结果写入 `stream_check_logs`。
+    ///
+    /// 夜间自检与网络变化后的重新检查共用；`copilot_state` 为空时（命令行）
+    /// Copilot 供应商按配置中的 base_url 探测。
+    pub async fn check_proxy_targets(
+        db: &Database,
+        copilot_state: Option<&CopilotAuthState>,
+        progress_operation: &'static str,
+    ) -> Result<Vec<SelfTestAppReport>, AppError> {
+        let Ok(_guard) = run_lock().try_lock() else {
+            return Err(AppError::Message("自检正在进行中".to_string()));
+        };
+
+        let config = db.get_stream_check_config()?;
+        let targets = Self::collect_targets(db)?;
+        let total = targets.iter().map(|(_, items)| items.len()).sum::<usize>() as u64;
+
+        let progress = ProgressReporter::start(progress_operation);
+        let mut done = 0u64;
+        let mut failing = 0usize;
+        let mut apps = Vec::new();
+        for (app_type, items) in targets {
+            let mut entries = Vec::with_capacity(items.len());
+            for (role, provider) in items {
+                progress.step("testing", done, total, Some(provider.name.clone()));
+                let base_url_override = match copilot_state {
+                    Some(copilot_state) => {
+                        resolve_copilot_base_url_override(&provider, copilot_state).await?
+                    }
+                    None => None,
+                };
+                let result = StreamCheckService::check_with_retry(
+                    &app_type,
+                    &provider,
+                    &config,
+                    base_url_override,
+                )
+                .await
+                .unwrap_or_else(|e| StreamCheckResult {
+                    status: HealthStatus::Failed,
+                    success: false,
+                    message: e.to_string(),
+                    response_time_ms: None,
+                    http_status: None,
+                    model_used: String::new(),
+                    tested_at: chrono::Utc::now().timestamp(),
+                    retry_count: 0,
+                    error_category: Some(HttpErrorClass::Transform.as_str().to_string()),
+                    diagnostics: None,
+                });
+                let _ = db.save_stream_check_log(
+                    &provider.id,
+                    &provider.name,
+                    app_type.as_str(),
+                    &result,
+                );
+                let entry = SelfTestEntry {
+                    provider_id: provider.id.clone(),
+                    provider_name: provider.name.clone(),
+                    role,
+                    result,
+                    in_maintenance: crate::services::maintenance::is_in_maintenance(
+                        &provider,
+                        chrono::Utc::now(),
+                    ),
+                };
+                if !entry.is_healthy() {
+                    failing += 1;
+                }
+                entries.push(entry);
+                done += 1;
+            }
+            apps.push(SelfTestAppReport {
+                app_type: app_type.as_str().to_string(),
+                summary: summarize_app(&app_type, &entries),
+                entries,
+            });
+        }
+
+        progress.finish(Some(format!("{done} provider(s), {failing} failing")));
+        Ok(apps)
+    }
+
+    /// 每个应用按「当前供应商 → 故障转移队列」顺序收集检查目标；同一供应商只测一次
+    fn collect_targets(
+        db: &Database,
+    ) -> Result<Vec<(AppType, Vec<(SelfTestRole, crate::provider::Provider)>)>, AppError> {
+        let mut targets = Vec::new();
+        for app_type in AppType::all() {
+            let providers = db.get_all_providers(app_type.as_str())?;
+            let mut items = Vec::new();
+            let current_id = db.get_current_provider(app_type.as_str())?;
+            if let Some(provider) = current_id.as_ref().and_then(|id| providers.get(id)) {
+                items.push((SelfTestRole::Primary, provider.clone()));
+            }
+            for (index, item) in db
+                .get_failover_queue(app_type.as_str())?
+                .into_iter()
+                .enumerate()
+            {
+                if current_id.as_deref() == Some(item.provider_id.as_str()) {
+                    continue;
+                }
+                if let Some(provider) = providers.get(&item.provider_id) {
+                    items.push((
+                        SelfTestRole::Failover {
+                            position: index + 1,
+                        },
+                        provider.clone(),
+                    ));
+                }
+            }
+            // 官方供应商没有可探测的目标，不做未鉴权的官方端点探测
+            items.retain(|(_, provider)| provider.category.as_deref() != Some("official"));
+            if !items.is_empty() {
+                targets.push((app_type, items));
+            }
+        }
+        Ok(targets)
+    }
+}
+
+fn local_date(now: DateTime<Local>) -> String {
+    now.format("%Y-%m-%d").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(role: SelfTestRole, success: bool, http_status: Option<u16>) -> SelfTestEntry {
+        SelfTestEntry {
+            provider_id: "p".to_string(),
+            provider_name: "P".to_string(),
+            role,
+            result: StreamCheckResult {
+                status: if success {
+                    HealthStatus::Operational
+                } else {
+                    HealthStatus::Failed
+                },
+                success,
+                message: if success {
+                    "Reachable".to_string()
+                } else {
+                    "Request timeout".to_string()
+                },
+                response_time_ms: Some(320),
+                http_status,
+                model_used: String::new(),
+                tested_at: 0,
+                retry_count: 0,
+                error_category: None,
+                diagnostics: None,
+            },
+            in_maintenance: false,
+        }
+    }
+
+    #[test]
+    fn summary_reports_primary_and_failover_outcomes() {
+        let entries = vec![
+            entry(SelfTestRole::Primary, true, Some(200)),
+            entry(SelfTestRole::Failover { position: 2 }, true, Some(502)),
+            entry(SelfTestRole::Failover { position: 3 }, false, None),
+            entry(SelfTestRole::Failover { position: 4 }, true, Some(401)),
+        ];
+        assert_eq!(
+            summarize_app(&AppType::Claude, &entries),
+            "Claude primary OK 320ms; failover #2 returning 502; \
+             failover #3 failed: Request timeout; failover #4 OK 320ms"
+        );
+        assert!(entries[0].is_healthy());
+        assert!(!entries[1].is_healthy());
+        assert!(!entries[2].is_healthy());
+        assert!(entries[3].is_healthy());
+    }
+
+    #[test]
+    fn failures_inside_maintenance_window_are_not_alarms() {
+        let mut failing = entry(SelfTestRole::Failover { position: 1 }, true, Some(503));
+        failing.in_maintenance = true;
+        assert!(failing.is_healthy());
+        assert_eq!(
+            failing.describe(),
+            "failover #1 returning 503 (maintenance window)"
+        );
+    }
+
+    #[test]
+    fn schedule_runs_once_per_night_and_notifies_in_the_morning() {
+        let config = SelfTestConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let night = Local.with_ymd_and_hms(2026, 3, 2, 4, 10, 0).unwrap();
+        let morning = Local.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap();
+
+        assert!(SelfTestService::is_run_due(&config, None, night));
+        assert!(!SelfTestService::is_run_due(&config, None, morning));
+        assert!(!SelfTestService::is_run_due(
+            &SelfTestConfig::default(),
+            None,
+            night
+        ));
+
+        let mut report = SelfTestReport {
+            run_date: "2026-03-02".to_string(),
+            started_at: night.timestamp(),
+            finished_at: night.timestamp(),
+            apps: Vec::new(),
+            notified: false,
+        };
+        assert!(!SelfTestService::is_run_due(&config, Some(&report), night));
+        assert!(!SelfTestService::is_summary_due(&config, &report, night));
+        assert!(SelfTestService::is_summary_due(&config, &report, morning));
+
+        report.notified = true;
+        assert!(!SelfTestService::is_summary_due(&config, &report, morning));
+    }
+}
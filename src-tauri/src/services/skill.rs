@@ -19,6 +19,8 @@ use crate::app_config::{AppType, InstalledSkill, SkillApps, UnmanagedSkill};
 use crate::config::get_app_config_dir;
 use crate::database::Database;
 use crate::error::format_skill_error;
+use crate::i18n;
+use crate::progress_events::ProgressReporter;
 
 // ========== 数据结构 ==========
 
@@ -98,10 +100,41 @@ pub struct Skill {
     pub repo_branch: Option<String>,
 }
 
+/// 仓库来源类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillRepoSource {
+    /// GitHub 仓库（公开仓库走归档下载，私有仓库带令牌走 API zipball）
+    #[default]
+    Github,
+    /// GitLab 仓库（gitlab.com 或自建实例）
+    Gitlab,
+    /// 本地目录（企业内网共享盘、手动维护的目录等）
+    Local,
+}
+
+impl SkillRepoSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Github => "github",
+            Self::Gitlab => "gitlab",
+            Self::Local => "local",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "gitlab" => Self::Gitlab,
+            "local" => Self::Local,
+            _ => Self::Github,
+        }
+    }
+}
+
 /// 仓库配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SkillRepo {
-    /// GitHub 用户/组织名
+    /// GitHub/GitLab 用户/组织名（本地目录来源固定为 "local"）
     pub owner: String,
     /// 仓库名称
     pub name: String,
@@ -109,6 +142,97 @@ pub struct SkillRepo {
     pub branch: String,
     /// 是否启用
     pub enabled: bool,
+    /// 来源类型
+    #[serde(default)]
+    pub source: SkillRepoSource,
+    /// 本地目录路径（local），或自建 GitLab 实例地址（gitlab，缺省 https://gitlab.com）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// 私有仓库访问令牌（GitHub / GitLab Personal Access Token，可为钥匙串引用）
+    #[serde(
+        default,
+        rename = "accessToken",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub access_token: Option<String>,
+}
+
+const DEFAULT_GITLAB_URL: &str = "https://gitlab.com";
+
+impl SkillRepo {
+    /// 访问令牌（已解析钥匙串引用；空串视为未配置）
+    fn resolved_token(&self) -> Option<String> {
+        self.access_token
+            .as_deref()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .map(crate::services::secrets::resolve_secret_lossy)
+            .filter(|token| !token.is_empty())
+    }
+
+    /// 仓库内文档的网页地址；本地目录来源没有网页地址
+    fn doc_url(&self, branch: &str, doc_path: &str) -> Option<String> {
+        match self.source {
+            SkillRepoSource::Github => Some(SkillService::build_skill_doc_url(
+                &self.owner,
+                &self.name,
+                branch,
+                doc_path,
+            )),
+            SkillRepoSource::Gitlab => Some(format!(
+                "{}/{}/{}/-/blob/{branch}/{doc_path}",
+                self.gitlab_base_url(),
+                self.owner,
+                self.name
+            )),
+            SkillRepoSource::Local => None,
+        }
+    }
+
+    fn gitlab_base_url(&self) -> &str {
+        self.location
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .unwrap_or(DEFAULT_GITLAB_URL)
+            .trim_end_matches('/')
+    }
+
+    /// 指定分支的归档下载地址与鉴权头
+    fn archive_request(&self, branch: &str) -> (String, Option<(&'static str, String)>) {
+        let token = self.resolved_token();
+        match self.source {
+            SkillRepoSource::Gitlab => {
+                let base = self.gitlab_base_url();
+                let project: String = url::form_urlencoded::byte_serialize(
+                    format!("{}/{}", self.owner, self.name).as_bytes(),
+                )
+                .collect();
+                let sha: String = url::form_urlencoded::byte_serialize(branch.as_bytes()).collect();
+                (
+                    format!("{base}/api/v4/projects/{project}/repository/archive.zip?sha={sha}"),
+                    token.map(|token| ("PRIVATE-TOKEN", token)),
+                )
+            }
+            // 私有仓库的 codeload 归档不接受令牌，改走 API zipball（会重定向到带签名的下载地址）
+            _ => match token {
+                Some(token) => (
+                    format!(
+                        "https://api.github.com/repos/{}/{}/zipball/{branch}",
+                        self.owner, self.name
+                    ),
+                    Some(("Authorization", format!("Bearer {token}"))),
+                ),
+                None => (
+                    format!(
+                        "https://github.com/{}/{}/archive/refs/heads/{branch}.zip",
+                        self.owner, self.name
+                    ),
+                    None,
+                ),
+            },
+        }
+    }
 }
 
 /// 技能安装状态（旧版兼容）
@@ -140,24 +264,28 @@ impl Default for SkillStore {
                     name: "skills".to_string(),
                     branch: "main".to_string(),
                     enabled: true,
+                    ..Default::default()
                 },
                 SkillRepo {
                     owner: "ComposioHQ".to_string(),
                     name: "awesome-claude-skills".to_string(),
                     branch: "master".to_string(),
                     enabled: true,
+                    ..Default::default()
                 },
                 SkillRepo {
                     owner: "cexll".to_string(),
                     name: "myclaude".to_string(),
                     branch: "master".to_string(),
                     enabled: true,
+                    ..Default::default()
                 },
                 SkillRepo {
                     owner: "JimLiu".to_string(),
                     name: "baoyu-skills".to_string(),
                     branch: "main".to_string(),
                     enabled: true,
+                    ..Default::default()
                 },
             ],
         }
@@ -172,6 +300,29 @@ pub struct SkillUninstallResult {
     pub backup_path: Option<String>,
 }
 
+/// Skill 安装目标：全局应用目录或指定项目目录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SkillInstallTarget {
+    /// 应用全局 skills 目录（如 ~/.claude/skills/）
+    #[default]
+    Global,
+    /// 项目内的 skills 目录（如 <project>/.claude/skills/）
+    Project { path: String },
+}
+
+/// 项目级 Skill 安装记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSkillInstall {
+    /// 规范化后的项目绝对路径
+    pub project_path: String,
+    pub skill_id: String,
+    /// 应用类型（AppType::as_str）
+    pub app: String,
+    pub installed_at: i64,
+}
+
 /// Skill 更新检测结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -550,6 +701,41 @@ impl SkillService {
         })
     }
 
+    /// 规范化项目路径：必须是已存在的绝对目录
+    pub fn normalize_project_path(raw: &str) -> Result<PathBuf> {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Err(i18n::error("skill.projectPathEmpty", &[]).into());
+        }
+        let path = PathBuf::from(trimmed);
+        if !path.is_absolute() {
+            return Err(i18n::error("skill.projectPathNotAbsolute", &[("path", trimmed)]).into());
+        }
+        if !path.is_dir() {
+            return Err(i18n::error("skill.projectDirMissing", &[("path", trimmed)]).into());
+        }
+        Ok(path.canonicalize().unwrap_or(path))
+    }
+
+    /// 获取项目内指定应用的 skills 目录
+    ///
+    /// 仅支持会读取项目级 skills 的应用；其余应用返回错误。
+    pub fn get_project_skills_dir(project: &Path, app: &AppType) -> Result<PathBuf> {
+        let app_dir = match app {
+            AppType::Claude => ".claude",
+            AppType::Codex => ".codex",
+            AppType::Gemini => ".gemini",
+            AppType::GrokBuild => ".grok",
+            AppType::OpenCode => ".opencode",
+            AppType::ClaudeDesktop | AppType::OpenClaw | AppType::Hermes => {
+                return Err(
+                    i18n::error("skill.projectScopeUnsupported", &[("app", app.as_str())]).into(),
+                );
+            }
+        };
+        Ok(project.join(app_dir).join("skills"))
+    }
+
     // ========== 统一管理方法 ==========
 
     /// 获取所有已安装的 Skills
@@ -563,13 +749,44 @@ impl SkillService {
     /// 流程：
     /// 1. 下载到 SSOT 目录
     /// 2. 保存到数据库
-    /// 3. 同步到启用的应用目录
+    /// 3. 同步到启用的应用目录（或项目目录，见 [`SkillInstallTarget`]）
+    ///
+    /// 项目级安装不会启用全局应用开关，避免项目 Skill 污染全局目录。
+    /// 下载与解压进度以 `skill.install` 进度事件上报。
     pub async fn install(
         &self,
         db: &Arc<Database>,
         skill: &DiscoverableSkill,
         current_app: &AppType,
+        target: &SkillInstallTarget,
+    ) -> Result<InstalledSkill> {
+        let progress = ProgressReporter::start("skill.install");
+        let result = self
+            .install_with_progress(db, skill, current_app, target, &progress)
+            .await;
+        match &result {
+            Ok(installed) => progress.finish(Some(installed.name.clone())),
+            Err(e) => progress.fail(e),
+        }
+        result
+    }
+
+    async fn install_with_progress(
+        &self,
+        db: &Arc<Database>,
+        skill: &DiscoverableSkill,
+        current_app: &AppType,
+        target: &SkillInstallTarget,
+        progress: &ProgressReporter,
     ) -> Result<InstalledSkill> {
+        let project = match target {
+            SkillInstallTarget::Global => None,
+            SkillInstallTarget::Project { path } => {
+                let project = Self::normalize_project_path(path)?;
+                Self::get_project_skills_dir(&project, current_app)?;
+                Some(project)
+            }
+        };
         let ssot_dir = Self::get_ssot_dir()?;
 
         // 允许多级目录（如 a/b/c），但必须是安全的相对路径。
@@ -601,6 +818,10 @@ impl SkillService {
                     && existing.repo_name.as_deref() == Some(&skill.repo_name);
                 if same_repo {
                     // 同一仓库的同名 skill，返回现有记录（可能需要更新启用状态）
+                    if let Some(project) = project.as_deref() {
+                        Self::install_into_project(db, existing, project, current_app)?;
+                        return Ok(existing.clone());
+                    }
                     let mut updated = existing.clone();
                     updated.apps.set_enabled_for(current_app, true);
                     db.save_skill(&updated)?;
@@ -639,20 +860,15 @@ impl SkillService {
         let dest = ssot_dir.join(&install_name);
 
         let mut repo_branch = skill.repo_branch.clone();
+        let repo =
+            Self::configured_repo(db, &skill.repo_owner, &skill.repo_name, &skill.repo_branch);
 
         // 如果已存在则跳过下载
         if !dest.exists() {
-            let repo = SkillRepo {
-                owner: skill.repo_owner.clone(),
-                name: skill.repo_name.clone(),
-                branch: skill.repo_branch.clone(),
-                enabled: true,
-            };
-
             // 下载仓库
             let (temp_dir, used_branch) = timeout(
                 std::time::Duration::from_secs(60),
-                self.download_repo(&repo),
+                self.download_repo(&repo, Some(progress)),
             )
             .await
             .map_err(|_| {
@@ -725,12 +941,9 @@ impl SkillService {
             })
             .unwrap_or_else(|| format!("{}/SKILL.md", skill.directory.trim_end_matches('/')));
 
-        let readme_url = Some(Self::build_skill_doc_url(
-            &skill.repo_owner,
-            &skill.repo_name,
-            &repo_branch,
-            &doc_path,
-        ));
+        let readme_url = repo.doc_url(&repo_branch, &doc_path);
+
+        progress.phase("installing", None);
 
         // 创建 InstalledSkill 记录
         // 计算内容哈希
@@ -752,7 +965,11 @@ impl SkillService {
             repo_name: Some(skill.repo_name.clone()),
             repo_branch: Some(repo_branch),
             readme_url,
-            apps: SkillApps::only(current_app),
+            apps: if project.is_some() {
+                SkillApps::default()
+            } else {
+                SkillApps::only(current_app)
+            },
             installed_at: chrono::Utc::now().timestamp(),
             content_hash,
             updated_at: 0,
@@ -761,6 +978,11 @@ impl SkillService {
         // 保存到数据库
         db.save_skill(&installed_skill)?;
 
+        if let Some(project) = project.as_deref() {
+            Self::install_into_project(db, &installed_skill, project, current_app)?;
+            return Ok(installed_skill);
+        }
+
         // 同步到当前应用目录
         Self::sync_to_app_dir(&install_name, current_app)?;
 
@@ -783,7 +1005,7 @@ impl SkillService {
         // 获取 skill 信息
         let skill = db
             .get_installed_skill(id)?
-            .ok_or_else(|| anyhow!("Skill not found: {id}"))?;
+            .ok_or_else(|| i18n::error("skill.notFound", &[("id", id)]))?;
 
         let backup_path =
             Self::create_uninstall_backup(&skill)?.map(|path| path.to_string_lossy().to_string());
@@ -793,6 +1015,17 @@ impl SkillService {
             let _ = Self::remove_from_app(&skill.directory, &app);
         }
 
+        // 从所有项目目录删除（SSOT 删除后项目内的 symlink 会失效）
+        for install in db.get_project_installs_for_skill(id)? {
+            if let Err(e) = Self::remove_project_install(db, &skill, &install) {
+                log::warn!(
+                    "从项目 {} 删除 Skill {} 失败: {e}",
+                    install.project_path,
+                    skill.directory
+                );
+            }
+        }
+
         // 从 SSOT 删除
         let ssot_dir = Self::get_ssot_dir()?;
         let skill_path = ssot_dir.join(&skill.directory);
@@ -815,6 +1048,120 @@ impl SkillService {
         Ok(SkillUninstallResult { backup_path })
     }
 
+    // ========== 项目级安装 ==========
+
+    /// 将已存在于 SSOT 的 Skill 同步到项目目录并记录
+    fn install_into_project(
+        db: &Arc<Database>,
+        skill: &InstalledSkill,
+        project: &Path,
+        app: &AppType,
+    ) -> Result<()> {
+        let skills_dir = Self::get_project_skills_dir(project, app)?;
+        Self::sync_to_skills_dir(&skill.directory, &skills_dir)?;
+
+        let project_path = project.to_string_lossy().to_string();
+        db.save_project_skill_install(&ProjectSkillInstall {
+            project_path: project_path.clone(),
+            skill_id: skill.id.clone(),
+            app: app.as_str().to_string(),
+            installed_at: chrono::Utc::now().timestamp(),
+        })?;
+
+        log::info!(
+            "Skill {} 已安装到项目 {project_path}（{:?}）",
+            skill.name,
+            app
+        );
+        Ok(())
+    }
+
+    fn remove_project_install(
+        db: &Arc<Database>,
+        skill: &InstalledSkill,
+        install: &ProjectSkillInstall,
+    ) -> Result<()> {
+        if let Ok(app) = install.app.parse::<AppType>() {
+            let skills_dir = Self::get_project_skills_dir(Path::new(&install.project_path), &app)?;
+            let skill_path = skills_dir.join(&skill.directory);
+            if skill_path.exists() || Self::is_symlink(&skill_path) {
+                Self::remove_path(&skill_path)?;
+            }
+        }
+        db.delete_project_skill_install(&install.project_path, &install.skill_id, &install.app)?;
+        Ok(())
+    }
+
+    /// 从项目目录卸载 Skill（保留 SSOT 与全局启用状态）
+    ///
+    /// `app` 为 `None` 时移除该项目下所有应用的安装。
+    pub fn uninstall_from_project(
+        db: &Arc<Database>,
+        id: &str,
+        project_path: &str,
+        app: Option<&AppType>,
+    ) -> Result<usize> {
+        let skill = db
+            .get_installed_skill(id)?
+            .ok_or_else(|| i18n::error("skill.notFound", &[("id", id)]))?;
+        let project = Self::normalize_project_path(project_path)?;
+        let project_key = project.to_string_lossy().to_string();
+
+        let mut removed = 0;
+        for install in db.get_project_skill_installs(Some(&project_key))? {
+            if install.skill_id != skill.id {
+                continue;
+            }
+            if app.is_some_and(|app| app.as_str() != install.app) {
+                continue;
+            }
+            Self::remove_project_install(db, &skill, &install)?;
+            removed += 1;
+        }
+
+        log::info!(
+            "Skill {} 已从项目 {project_key} 卸载 {removed} 处",
+            skill.name
+        );
+        Ok(removed)
+    }
+
+    /// 列出项目级安装记录；`project_path` 为 `None` 时返回所有项目
+    pub fn list_project_installs(
+        db: &Arc<Database>,
+        project_path: Option<&str>,
+    ) -> Result<Vec<ProjectSkillInstall>> {
+        let project_key = project_path
+            .map(|path| Self::normalize_project_path(path).map(|p| p.to_string_lossy().to_string()))
+            .transpose()?;
+        Ok(db.get_project_skill_installs(project_key.as_deref())?)
+    }
+
+    /// 重新同步所有项目级安装（SSOT 内容或位置变化后调用）
+    fn resync_project_installs(db: &Arc<Database>, skill: &InstalledSkill) {
+        let installs = match db.get_project_installs_for_skill(&skill.id) {
+            Ok(installs) => installs,
+            Err(e) => {
+                log::warn!("读取 Skill {} 的项目安装记录失败: {e}", skill.directory);
+                return;
+            }
+        };
+        for install in installs {
+            let Ok(app) = install.app.parse::<AppType>() else {
+                continue;
+            };
+            let result = Self::get_project_skills_dir(Path::new(&install.project_path), &app)
+                .and_then(|dir| Self::sync_to_skills_dir(&skill.directory, &dir));
+            if let Err(e) = result {
+                log::warn!(
+                    "同步 Skill {} 到项目 {} 失败: {e}",
+                    skill.directory,
+                    install.project_path
+                );
+            }
+        }
+    }
+
     // ========== 更新检测 ==========
 
     /// 计算目录内容的 SHA-256 哈希
@@ -892,17 +1239,12 @@ impl SkillService {
         let ssot_dir = Self::get_ssot_dir()?;
 
         for ((owner, name, branch), group_skills) in &repo_groups {
-            let repo = SkillRepo {
-                owner: owner.clone(),
-                name: name.clone(),
-                branch: branch.clone(),
-                enabled: true,
-            };
+            let repo = Self::configured_repo(db, owner, name, branch);
 
             // 下载仓库 ZIP
             let (temp_dir, _used_branch) = match timeout(
                 std::time::Duration::from_secs(60),
-                self.download_repo(&repo),
+                self.download_repo(&repo, None),
             )
             .await
             {
@@ -985,7 +1327,7 @@ impl SkillService {
     pub async fn update_skill(&self, db: &Arc<Database>, skill_id: &str) -> Result<InstalledSkill> {
         let skill = db
             .get_installed_skill(skill_id)?
-            .ok_or_else(|| anyhow!("Skill not found: {skill_id}"))?;
+            .ok_or_else(|| i18n::error("skill.notFound", &[("id", skill_id)]))?;
 
         let (owner, name, branch) = match (&skill.repo_owner, &skill.repo_name) {
             (Some(o), Some(n)) => (
@@ -999,19 +1341,14 @@ impl SkillService {
             _ => return Err(anyhow!("Cannot update local skill: {skill_id}")),
         };
 
-        let repo = SkillRepo {
-            owner: owner.clone(),
-            name: name.clone(),
-            branch: branch.clone(),
-            enabled: true,
-        };
+        let repo = Self::configured_repo(db, &owner, &name, &branch);
 
         let ssot_dir = Self::get_ssot_dir()?;
 
         // 下载仓库
         let (temp_dir, used_branch) = timeout(
             std::time::Duration::from_secs(60),
-            self.download_repo(&repo),
+            self.download_repo(&repo, None),
         )
         .await
         .map_err(|_| {
@@ -1074,12 +1411,7 @@ impl SkillService {
             .as_deref()
             .and_then(Self::extract_doc_path_from_url)
             .unwrap_or_else(|| format!("{}/SKILL.md", skill.directory.trim_end_matches('/')));
-        let readme_url = Some(Self::build_skill_doc_url(
-            &owner,
-            &name,
-            &used_branch,
-            &doc_path,
-        ));
+        let readme_url = repo.doc_url(&used_branch, &doc_path);
 
         let updated_skill = InstalledSkill {
             id: skill.id.clone(),
@@ -1104,6 +1436,7 @@ impl SkillService {
                 log::warn!("同步更新后的 skill 到 {:?} 失败: {e}", app);
             }
         }
+        Self::resync_project_installs(db, &updated_skill);
 
         log::info!("Skill {} 更新成功", updated_skill.name);
         Ok(updated_skill)
@@ -1209,6 +1542,9 @@ impl SkillService {
         for app in AppType::all() {
             let _ = Self::sync_to_app(db, &app);
         }
+        for skill in skills.values() {
+            Self::resync_project_installs(db, skill);
+        }
 
         log::info!(
             "Skill 存储迁移完成: {} 迁移, {} 跳过, {} 错误",
@@ -1351,7 +1687,7 @@ impl SkillService {
         // 获取当前 skill
         let mut skill = db
             .get_installed_skill(id)?
-            .ok_or_else(|| anyhow!("Skill not found: {id}"))?;
+            .ok_or_else(|| i18n::error("skill.notFound", &[("id", id)]))?;
 
         // 更新状态
         skill.apps.set_enabled_for(app, enabled);
@@ -1581,15 +1917,20 @@ impl SkillService {
             return Ok(());
         }
 
+        let app_dir = Self::get_app_skills_dir(app)?;
+        Self::sync_to_skills_dir(directory, &app_dir)
+    }
+
+    /// 同步 Skill 到任意 skills 目录（应用全局目录或项目目录）
+    fn sync_to_skills_dir(directory: &str, skills_dir: &Path) -> Result<()> {
         let ssot_dir = Self::get_ssot_dir()?;
         let source = ssot_dir.join(directory);
 
         Self::validate_sync_source_dir(&source, directory)?;
 
-        let app_dir = Self::get_app_skills_dir(app)?;
-        fs::create_dir_all(&app_dir)?;
+        fs::create_dir_all(skills_dir)?;
 
-        let dest = app_dir.join(directory);
+        let dest = skills_dir.join(directory);
 
         let sync_method = Self::get_sync_method();
 
@@ -1597,7 +1938,10 @@ impl SkillService {
             SyncMethod::Auto => {
                 if dest.exists() && !Self::is_symlink(&dest) {
                     Self::replace_dest_with_copy(&source, &dest, directory)?;
-                    log::debug!("Skill {directory} 已通过复制同步到 {app:?}");
+                    log::debug!(
+                        "Skill {directory} 已通过复制同步到 {}",
+                        skills_dir.display()
+                    );
                     return Ok(());
                 }
 
@@ -1608,7 +1952,10 @@ impl SkillService {
                 // 优先尝试 symlink
                 match Self::create_symlink(&source, &dest) {
                     Ok(()) => {
-                        log::debug!("Skill {directory} 已通过 symlink 同步到 {app:?}");
+                        log::debug!(
+                            "Skill {directory} 已通过 symlink 同步到 {}",
+                            skills_dir.display()
+                        );
                         return Ok(());
                     }
                     Err(err) => {
@@ -1621,18 +1968,27 @@ impl SkillService {
                 }
                 // Fallback 到 copy
                 Self::replace_dest_with_copy(&source, &dest, directory)?;
-                log::debug!("Skill {directory} 已通过复制同步到 {app:?}");
+                log::debug!(
+                    "Skill {directory} 已通过复制同步到 {}",
+                    skills_dir.display()
+                );
             }
             SyncMethod::Symlink => {
                 if dest.exists() || Self::is_symlink(&dest) {
                     Self::remove_path(&dest)?;
                 }
                 Self::create_symlink(&source, &dest)?;
-                log::debug!("Skill {directory} 已通过 symlink 同步到 {app:?}");
+                log::debug!(
+                    "Skill {directory} 已通过 symlink 同步到 {}",
+                    skills_dir.display()
+                );
             }
             SyncMethod::Copy => {
                 Self::replace_dest_with_copy(&source, &dest, directory)?;
-                log::debug!("Skill {directory} 已通过复制同步到 {app:?}");
+                log::debug!(
+                    "Skill {directory} 已通过复制同步到 {}",
+                    skills_dir.display()
+                );
             }
         }
 
@@ -1665,7 +2021,7 @@ impl SkillService {
 
     fn validate_sync_source_dir(source: &Path, directory: &str) -> Result<()> {
         if !source.is_dir() {
-            return Err(anyhow!("Skill 不存在于 SSOT: {directory}"));
+            return Err(i18n::error("skill.missingInSsot", &[("directory", directory)]).into());
         }
 
         let manifest = source.join("SKILL.md");
@@ -1814,6 +2170,8 @@ impl SkillService {
     // ========== 发现功能（保留原有逻辑）==========
 
     /// 列出所有可发现的技能（从仓库获取）
+    ///
+    /// 各仓库并发索引，每完成一个仓库以 `skill.index` 进度事件上报。
     pub async fn discover_available(
         &self,
         repos: Vec<SkillRepo>,
@@ -1823,12 +2181,28 @@ impl SkillService {
         // 仅使用启用的仓库
         let enabled_repos: Vec<SkillRepo> = repos.into_iter().filter(|repo| repo.enabled).collect();
 
-        let fetch_tasks = enabled_repos
-            .iter()
-            .map(|repo| self.fetch_repo_skills(repo));
+        let progress = ProgressReporter::start("skill.index");
+        let total = enabled_repos.len() as u64;
+        let indexed = std::sync::atomic::AtomicU64::new(0);
+        let fetch_tasks = enabled_repos.iter().map(|repo| {
+            let progress = &progress;
+            let indexed = &indexed;
+            async move {
+                let result = self.fetch_repo_skills(repo).await;
+                let done = indexed.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+                progress.step(
+                    "indexing",
+                    done,
+                    total,
+                    Some(format!("{}/{}", repo.owner, repo.name)),
+                );
+                result
+            }
+        });
 
         let results: Vec<Result<Vec<DiscoverableSkill>>> =
             futures::future::join_all(fetch_tasks).await;
+        progress.finish(None);
 
         for (repo, result) in enabled_repos.into_iter().zip(results) {
             match result {
@@ -1913,20 +2287,22 @@ impl SkillService {
 
     /// 从仓库获取技能列表
     async fn fetch_repo_skills(&self, repo: &SkillRepo) -> Result<Vec<DiscoverableSkill>> {
-        let (temp_dir, resolved_branch) =
-            timeout(std::time::Duration::from_secs(60), self.download_repo(repo))
-                .await
-                .map_err(|_| {
-                    anyhow!(format_skill_error(
-                        "DOWNLOAD_TIMEOUT",
-                        &[
-                            ("owner", &repo.owner),
-                            ("name", &repo.name),
-                            ("timeout", "60")
-                        ],
-                        Some("checkNetwork"),
-                    ))
-                })??;
+        let (temp_dir, resolved_branch) = timeout(
+            std::time::Duration::from_secs(60),
+            self.download_repo(repo, None),
+        )
+        .await
+        .map_err(|_| {
+            anyhow!(format_skill_error(
+                "DOWNLOAD_TIMEOUT",
+                &[
+                    ("owner", &repo.owner),
+                    ("name", &repo.name),
+                    ("timeout", "60")
+                ],
+                Some("checkNetwork"),
+            ))
+        })??;
 
         let mut skills = Vec::new();
         let scan_dir = temp_dir.clone();
@@ -2002,12 +2378,7 @@ impl SkillService {
             name: meta.name.unwrap_or_else(|| directory.to_string()),
             description: meta.description.unwrap_or_default(),
             directory: directory.to_string(),
-            readme_url: Some(Self::build_skill_doc_url(
-                &repo.owner,
-                &repo.name,
-                &repo.branch,
-                doc_path,
-            )),
+            readme_url: repo.doc_url(&repo.branch, doc_path),
             repo_owner: repo.owner.clone(),
             repo_name: repo.name.clone(),
             repo_branch: repo.branch.clone(),
@@ -2196,12 +2567,56 @@ impl SkillService {
         });
     }
 
-    /// 下载仓库
-    async fn download_repo(&self, repo: &SkillRepo) -> Result<(PathBuf, String)> {
+    /// 按 owner/name 查找已配置的仓库（携带来源与令牌）；未配置时按公开 GitHub 仓库处理
+    fn configured_repo(db: &Arc<Database>, owner: &str, name: &str, branch: &str) -> SkillRepo {
+        let configured = db
+            .get_skill_repos()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|repo| repo.owner == owner && repo.name == name);
+        SkillRepo {
+            owner: owner.to_string(),
+            name: name.to_string(),
+            branch: branch.to_string(),
+            enabled: true,
+            ..configured.unwrap_or_default()
+        }
+    }
+
+    /// 本地目录来源：复制到临时目录，与下载来源共用后续扫描/安装/清理流程
+    fn copy_local_repo(repo: &SkillRepo, temp_path: &Path) -> Result<(PathBuf, String)> {
+        let source = repo
+            .location
+            .as_deref()
+            .map(str::trim)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .filter(|path| path.is_dir())
+            .ok_or_else(|| {
+                anyhow!(format_skill_error(
+                    "LOCAL_SOURCE_NOT_FOUND",
+                    &[("path", repo.location.as_deref().unwrap_or(""))],
+                    Some("checkRepoUrl"),
+                ))
+            })?;
+        Self::copy_dir_recursive(&source, temp_path)?;
+        Ok((temp_path.to_path_buf(), repo.branch.clone()))
+    }
+
+    /// 下载仓库（`progress` 用于上报下载与解压进度）
+    async fn download_repo(
+        &self,
+        repo: &SkillRepo,
+        progress: Option<&ProgressReporter>,
+    ) -> Result<(PathBuf, String)> {
         let temp_dir = tempfile::tempdir()?;
         let temp_path = temp_dir.path().to_path_buf();
         let _ = temp_dir.keep();
 
+        if repo.source == SkillRepoSource::Local {
+            return Self::copy_local_repo(repo, &temp_path);
+        }
+
         let mut branches = Vec::new();
         if !repo.branch.is_empty() && !repo.branch.eq_ignore_ascii_case("HEAD") {
             branches.push(repo.branch.as_str());
@@ -2215,12 +2630,12 @@ impl SkillService {
 
         let mut last_error = None;
         for branch in branches {
-            let url = format!(
-                "https://github.com/{}/{}/archive/refs/heads/{}.zip",
-                repo.owner, repo.name, branch
-            );
+            let (url, auth) = repo.archive_request(branch);
 
-            match self.download_and_extract(&url, &temp_path).await {
+            match self
+                .download_and_extract(&url, auth, &temp_path, progress)
+                .await
+            {
                 Ok(_) => {
                     return Ok((temp_path, branch.to_string()));
                 }
@@ -2231,13 +2646,24 @@ impl SkillService {
             }
         }
 
-        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("所有分支下载失败")))
+        Err(last_error.unwrap_or_else(|| i18n::error("skill.allBranchesFailed", &[]).into()))
     }
 
-    /// 下载并解压 ZIP
-    async fn download_and_extract(&self, url: &str, dest: &Path) -> Result<()> {
-        let client = crate::proxy::http_client::get();
-        let response = client.get(url).send().await?;
+    /// 下载并解压 ZIP（`auth` 为私有仓库的鉴权头）
+    async fn download_and_extract(
+        &self,
+        url: &str,
+        auth: Option<(&'static str, String)>,
+        dest: &Path,
+        progress: Option<&ProgressReporter>,
+    ) -> Result<()> {
+        let client =
+            crate::proxy::http_client::get_auxiliary("skills").map_err(anyhow::Error::msg)?;
+        let mut request = client.get(url);
+        if let Some((name, value)) = auth {
+            request = request.header(name, value);
+        }
+        let response = request.send().await?;
         if !response.status().is_success() {
             let status = response.status().as_u16().to_string();
             return Err(anyhow::anyhow!(format_skill_error(
@@ -2252,7 +2678,19 @@ impl SkillService {
             )));
         }
 
-        let bytes = response.bytes().await?;
+        let total = response.content_length().unwrap_or(0);
+        let mut response = response;
+        let mut bytes = Vec::with_capacity(total as usize);
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            if let Some(progress) = progress {
+                if total > 0 {
+                    progress.step("downloading", bytes.len() as u64, total, None);
+                } else {
+                    progress.phase("downloading", Some(format!("{} KB", bytes.len() / 1024)));
+                }
+            }
+        }
         let cursor = std::io::Cursor::new(bytes);
         let mut archive = zip::ZipArchive::new(cursor)?;
 
@@ -2271,7 +2709,11 @@ impl SkillService {
         // 第一遍：解压普通文件和目录，收集 symlink 条目
         let mut symlinks: Vec<(PathBuf, String)> = Vec::new();
 
+        let entries = archive.len() as u64;
         for i in 0..archive.len() {
+            if let Some(progress) = progress {
+                progress.step("extracting", i as u64 + 1, entries, None);
+            }
             let mut file = archive.by_index(i)?;
             let file_path = file.name().to_string();
 
@@ -2796,7 +3238,8 @@ impl SkillService {
         limit: usize,
         offset: usize,
     ) -> Result<SkillsShSearchResult> {
-        let client = crate::proxy::http_client::get();
+        let client =
+            crate::proxy::http_client::get_auxiliary("skills").map_err(anyhow::Error::msg)?;
 
         let url = url::Url::parse_with_params(
             "https://skills.sh/api/search",
@@ -2914,6 +3357,7 @@ fn save_repos_from_lock(
                     // 未知分支时使用 HEAD 语义，后续下载会回退到 main/master。
                     branch: info.branch.clone().unwrap_or_else(|| "HEAD".to_string()),
                     enabled: true,
+                    ..Default::default()
                 };
                 if let Err(e) = db.save_skill_repo(&skill_repo) {
                     log::warn!("保存 skill 仓库 {}/{} 失败: {}", info.owner, info.repo, e);
@@ -3092,6 +3536,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_project_skills_dir_maps_supported_apps_and_rejects_others() {
+        let project = Path::new("/work/repo");
+
+        assert_eq!(
+            SkillService::get_project_skills_dir(project, &AppType::Claude).expect("claude"),
+            project.join(".claude").join("skills")
+        );
+        assert_eq!(
+            SkillService::get_project_skills_dir(project, &AppType::Codex).expect("codex"),
+            project.join(".codex").join("skills")
+        );
+        assert!(SkillService::get_project_skills_dir(project, &AppType::Hermes).is_err());
+        assert!(SkillService::get_project_skills_dir(project, &AppType::OpenClaw).is_err());
+    }
+
+    #[test]
+    fn normalize_project_path_rejects_relative_and_missing_dirs() {
+        let temp = tempdir().expect("tempdir");
+
+        assert!(SkillService::normalize_project_path("relative/repo").is_err());
+        assert!(SkillService::normalize_project_path("   ").is_err());
+        assert!(SkillService::normalize_project_path(
+            &temp.path().join("missing").to_string_lossy()
+        )
+        .is_err());
+        assert!(SkillService::normalize_project_path(&temp.path().to_string_lossy()).is_ok());
+    }
+
+    #[test]
+    fn archive_request_picks_endpoint_by_source_and_token() {
+        let public = SkillRepo {
+            owner: "acme".to_string(),
+            name: "skills".to_string(),
+            branch: "main".to_string(),
+            enabled: true,
+            ..Default::default()
+        };
+        let (url, auth) = public.archive_request("main");
+        assert_eq!(
+            url,
+            "https://github.com/acme/skills/archive/refs/heads/main.zip"
+        );
+        assert!(auth.is_none());
+
+        let private = SkillRepo {
+            access_token: Some("ghp_x".to_string()),
+            ..public.clone()
+        };
+        let (url, auth) = private.archive_request("main");
+        assert_eq!(url, "https://api.github.com/repos/acme/skills/zipball/main");
+        assert_eq!(auth, Some(("Authorization", "Bearer ghp_x".to_string())));
+
+        let gitlab = SkillRepo {
+            source: SkillRepoSource::Gitlab,
+            location: Some("https://gitlab.corp.example/".to_string()),
+            access_token: Some("glpat".to_string()),
+            ..public
+        };
+        let (url, auth) = gitlab.archive_request("release/1");
+        assert_eq!(
+            url,
+            "https://gitlab.corp.example/api/v4/projects/acme%2Fskills/repository/archive.zip?sha=release%2F1"
+        );
+        assert_eq!(auth, Some(("PRIVATE-TOKEN", "glpat".to_string())));
+        assert_eq!(
+            gitlab.doc_url("main", "a/SKILL.md").as_deref(),
+            Some("https://gitlab.corp.example/acme/skills/-/blob/main/a/SKILL.md")
+        );
+    }
+
     #[test]
     fn resolve_skill_source_dir_returns_repo_root_for_root_level_skill() {
         let temp = tempdir().expect("tempdir");
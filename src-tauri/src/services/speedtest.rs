@@ -1,9 +1,16 @@
 use futures::future::join_all;
 use reqwest::{Client, Url};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
 use std::time::Instant;
 
+use crate::app_config::AppType;
+use crate::database::Database;
 use crate::error::AppError;
+use crate::provider::{HeaderTemplateContext, Provider};
+use crate::proxy::HttpErrorClass;
+use crate::services::curl_export::UpstreamTarget;
 
 const DEFAULT_TIMEOUT_SECS: u64 = 8;
 const MAX_TIMEOUT_SECS: u64 = 30;
@@ -16,6 +23,38 @@ pub struct EndpointLatency {
     pub latency: Option<u128>,
     pub status: Option<u16>,
     pub error: Option<String>,
+    /// 统一错误分类：请求失败或返回错误状态码时给出
+    #[serde(rename = "errorClass", skip_serializing_if = "Option::is_none")]
+    pub error_class: Option<HttpErrorClass>,
+}
+
+/// 按供应商配置测速：携带其鉴权头、自定义请求头并走其出站代理
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderSpeedtestOptions {
+    pub app: String,
+    pub provider_id: String,
+    /// 发送最小的真实补全请求并测量首字节时间（TTFB），而非对基础地址做 GET
+    #[serde(default)]
+    pub completion: bool,
+}
+
+/// 供应商 env 中可配置出站代理的变量，按优先级排列
+const PROXY_ENV_KEYS: [&str; 6] = [
+    "HTTPS_PROXY",
+    "https_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+];
+
+/// 一次测速要发送的请求
+#[derive(Clone, Default)]
+struct RequestPlan {
+    headers: Vec<(String, String)>,
+    /// Some 时 POST 最小补全请求到对应端点，计时到首个响应数据块
+    completion: Option<(String, Value)>,
 }
 
 /// 网络测速相关业务
@@ -26,6 +65,79 @@ impl SpeedtestService {
     pub async fn test_endpoints(
         urls: Vec<String>,
         timeout_secs: Option<u64>,
+    ) -> Result<Vec<EndpointLatency>, AppError> {
+        let timeout = Self::sanitize_timeout(timeout_secs);
+        Self::measure(
+            Self::build_client(),
+            None,
+            RequestPlan::default(),
+            urls,
+            timeout,
+        )
+        .await
+    }
+
+    /// 以供应商的身份测试一组候选地址
+    ///
+    /// 部分中转未带鉴权时直接拒绝或走不同的路由，裸 GET 测得的延迟并不代表真实请求；
+    /// 这里复用复制为 curl 的上游构造（鉴权头、自定义 UA），叠加本地代理请求头覆盖，
+    /// 并优先使用供应商 env 中的 `HTTPS_PROXY` / `ALL_PROXY`（支持 socks5）。
+    pub async fn test_provider_endpoints(
+        db: &Database,
+        options: &ProviderSpeedtestOptions,
+        urls: Vec<String>,
+        timeout_secs: Option<u64>,
+    ) -> Result<Vec<EndpointLatency>, AppError> {
+        let app_type = AppType::from_str(&options.app)?;
+        let provider = db
+            .get_provider_by_id(&options.provider_id, app_type.as_str())?
+            .ok_or_else(|| {
+                AppError::localized(
+                    "provider.not_found",
+                    format!("供应商不存在: {}", options.provider_id),
+                    format!("Provider not found: {}", options.provider_id),
+                )
+            })?;
+        let target = UpstreamTarget::resolve(&app_type, &provider, None)?;
+
+        let mut headers = target.headers(false)?;
+        if let Some(overrides) = provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.local_proxy_request_overrides.as_ref())
+        {
+            let model = crate::services::curl_export::configured_model(&app_type, &provider);
+            let template =
+                HeaderTemplateContext::new(app_type.as_str(), &provider, model.as_deref());
+            for (name, value) in overrides.rendered_headers(&template) {
+                headers.retain(|(existing, _)| !existing.eq_ignore_ascii_case(&name));
+                headers.push((name, value));
+            }
+        }
+
+        let meta = provider.meta.as_ref();
+        let client = crate::proxy::http_client::get_for_provider_via(
+            meta.and_then(|meta| meta.tls_options()),
+            meta.and_then(|meta| meta.timeouts())
+                .and_then(|timeouts| timeouts.connect_timeout()),
+            provider_proxy_url(&provider).as_deref(),
+        )
+        .map_err(AppError::Message)?;
+
+        let plan = RequestPlan {
+            headers,
+            completion: options.completion.then(|| target.minimal_request(true)),
+        };
+        let timeout = Self::sanitize_timeout(timeout_secs);
+        Self::measure(client, Some(&target), plan, urls, timeout).await
+    }
+
+    async fn measure(
+        client: Client,
+        target: Option<&UpstreamTarget>,
+        plan: RequestPlan,
+        urls: Vec<String>,
+        timeout: u64,
     ) -> Result<Vec<EndpointLatency>, AppError> {
         if urls.is_empty() {
             return Ok(vec![]);
@@ -43,6 +155,7 @@ impl SpeedtestService {
                     latency: None,
                     status: None,
                     error: Some("URL 不能为空".to_string()),
+                    error_class: Some(HttpErrorClass::Client),
                 });
                 continue;
             }
@@ -55,6 +168,7 @@ impl SpeedtestService {
                         latency: None,
                         status: None,
                         error: Some(format!("URL 无效: {err}")),
+                        error_class: Some(HttpErrorClass::Client),
                     });
                 }
             }
@@ -64,11 +178,17 @@ impl SpeedtestService {
             return Ok(results.into_iter().flatten().collect::<Vec<_>>());
         }
 
-        let timeout = Self::sanitize_timeout(timeout_secs);
-        let (client, request_timeout) = Self::build_client(timeout)?;
+        let request_timeout = std::time::Duration::from_secs(timeout);
 
         let tasks = valid_targets.into_iter().map(|(idx, trimmed, parsed_url)| {
             let client = client.clone();
+            let plan = &plan;
+            let completion_url = match (&plan.completion, target) {
+                (Some((endpoint, _)), Some(target)) => {
+                    Some(target.url_with_base(&trimmed, endpoint))
+                }
+                _ => None,
+            };
             async move {
                 // 先进行一次热身请求，忽略结果，仅用于复用连接/绕过首包惩罚。
                 let _ = client
@@ -77,15 +197,31 @@ impl SpeedtestService {
                     .send()
                     .await;
 
+                let mut request = match (&completion_url, &plan.completion) {
+                    (Some(url), Some((_, body))) => client.post(url.as_str()).json(body),
+                    _ => client.get(parsed_url),
+                };
+                for (name, value) in &plan.headers {
+                    request = request.header(name.as_str(), value.as_str());
+                }
+
                 // 第二次请求开始计时，并将其作为结果返回。
                 let start = Instant::now();
-                let latency = match client.get(parsed_url).timeout(request_timeout).send().await {
-                    Ok(resp) => EndpointLatency {
-                        url: trimmed,
-                        latency: Some(start.elapsed().as_millis()),
-                        status: Some(resp.status().as_u16()),
-                        error: None,
-                    },
+                let latency = match request.timeout(request_timeout).send().await {
+                    Ok(mut resp) => {
+                        let status = resp.status().as_u16();
+                        // 补全请求计到首个数据块，反映真实的首字节时间
+                        if completion_url.is_some() && resp.status().is_success() {
+                            let _ = resp.chunk().await;
+                        }
+                        EndpointLatency {
+                            url: trimmed,
+                            latency: Some(start.elapsed().as_millis()),
+                            status: Some(status),
+                            error: None,
+                            error_class: HttpErrorClass::from_status(status),
+                        }
+                    }
                     Err(err) => {
                         let status = err.status().map(|s| s.as_u16());
                         let error_message = if err.is_timeout() {
@@ -101,6 +237,7 @@ impl SpeedtestService {
                             latency: None,
                             status,
                             error: Some(error_message),
+                            error_class: Some(HttpErrorClass::from_reqwest_error(&err)),
                         }
                     }
                 };
@@ -116,11 +253,9 @@ impl SpeedtestService {
         Ok(results.into_iter().flatten().collect::<Vec<_>>())
     }
 
-    fn build_client(timeout_secs: u64) -> Result<(Client, std::time::Duration), AppError> {
-        // 使用全局 HTTP 客户端（已包含代理配置）
-        // 返回 timeout Duration 供请求级别使用
-        let timeout = std::time::Duration::from_secs(timeout_secs);
-        Ok((crate::proxy::http_client::get(), timeout))
+    fn build_client() -> Client {
+        // 使用全局 HTTP 客户端（已包含代理配置），超时在请求级别设置
+        crate::proxy::http_client::get()
     }
 
     fn sanitize_timeout(timeout_secs: Option<u64>) -> u64 {
@@ -129,6 +264,17 @@ impl SpeedtestService {
     }
 }
 
+/// 供应商 env 中配置的出站代理（Claude Code、Gemini CLI 等会读取这些变量）
+fn provider_proxy_url(provider: &Provider) -> Option<String> {
+    let env = provider.settings_config.get("env")?;
+    PROXY_ENV_KEYS
+        .iter()
+        .filter_map(|key| env.get(*key).and_then(Value::as_str))
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,6 +299,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn provider_proxy_prefers_https_then_all_proxy() {
+        let provider = |env: Value| {
+            Provider::with_id(
+                "p".to_string(),
+                "P".to_string(),
+                serde_json::json!({ "env": env }),
+                None,
+            )
+        };
+        assert_eq!(
+            provider_proxy_url(&provider(serde_json::json!({
+                "HTTP_PROXY": "http://127.0.0.1:8080",
+                "ALL_PROXY": "socks5://127.0.0.1:1080",
+            }))),
+            Some("socks5://127.0.0.1:1080".to_string())
+        );
+        assert_eq!(
+            provider_proxy_url(&provider(serde_json::json!({
+                "https_proxy": " http://proxy.corp:3128 ",
+                "ALL_PROXY": "socks5://127.0.0.1:1080",
+            }))),
+            Some("http://proxy.corp:3128".to_string())
+        );
+        assert_eq!(
+            provider_proxy_url(&provider(serde_json::json!({ "HTTPS_PROXY": "" }))),
+            None
+        );
+    }
+
     #[test]
     fn test_endpoints_handles_empty_list() {
         let result =
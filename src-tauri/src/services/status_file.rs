@@ -0,0 +1,240 @@
+//! 状态栏集成：把各应用的当前供应商写入 `~/.cc-switch/status.json`
+//!
+//! tmux / starship / ccline 等状态栏只需读取这个稳定路径的 JSON 文件即可显示
+//! 当前供应商、配置模型、最近实际发往上游的模型、端点与今日花费，无需访问数据库或代理。
+//! 文件在设置中开启后写入（先写临时文件再原子替换，读取方不会读到半个文件），
+//! 并在以下时机刷新：
+//!
+//! - 启动时与保存设置后；
+//! - 每次切换供应商（界面、托盘、CLI、deep link、场景宏）；
+//! - 代理故障转移自动切换后；
+//! - 写入新的用量日志后（与前端用量刷新共用 200ms 防抖）。
+//!
+//! 字段说明见 `docs/guides/statusline-integration-*.md`；新增字段只追加，
+//! 不兼容的变更会提升 `version`。`cc-switch status --watch` 会持续输出该文件的变化。
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use chrono::TimeZone;
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::AppType;
+use crate::config::get_app_config_dir;
+use crate::database::Database;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::usage_stats::UsageSummary;
+
+const STATUS_FILE: &str = "status.json";
+
+/// 文件格式版本；只在字段语义发生不兼容变化时提升
+pub const STATUS_FILE_VERSION: u32 = 1;
+
+/// 触发刷新的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusSource {
+    Startup,
+    Settings,
+    Switch,
+    Failover,
+    /// 写入新的用量日志
+    Usage,
+    /// `cc-switch status` 直接查询（不写文件）
+    Query,
+}
+
+/// 单个应用的当前供应商
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStatus {
+    pub provider_id: String,
+    pub provider_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// 最近一次请求实际发往上游的模型（经模型映射后；没有请求记录时缺省）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mapped_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// 该应用今日（本地时区）的花费，美元十进制字符串；今日没有用量时缺省
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub today_cost_usd: Option<String>,
+    #[serde(default)]
+    pub today_requests: u64,
+}
+
+/// status.json 内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusSnapshot {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    pub updated_at: i64,
+    pub source: StatusSource,
+    /// 应用 → 当前供应商；没有当前供应商的应用（累加模式应用等）不出现
+    pub apps: BTreeMap<String, AppStatus>,
+}
+
+fn default_version() -> u32 {
+    STATUS_FILE_VERSION
+}
+
+pub fn status_path() -> PathBuf {
+    get_app_config_dir().join(STATUS_FILE)
+}
+
+/// 单个应用的当前供应商；累加模式应用或尚未选择供应商时为 `None`
+pub fn app_status(db: &Database, app_type: &AppType) -> Result<Option<AppStatus>, AppError> {
+    if app_type.is_additive_mode() {
+        return Ok(None);
+    }
+    let Some(id) = crate::settings::get_effective_current_provider(db, app_type)? else {
+        return Ok(None);
+    };
+    let Some(provider) = db.get_provider_by_id(&id, app_type.as_str())? else {
+        return Ok(None);
+    };
+    let mut status = provider_status(app_type, provider);
+    status.mapped_model = db.latest_upstream_model(app_type.as_str(), &status.provider_id)?;
+    Ok(Some(status))
+}
+
+fn provider_status(app_type: &AppType, provider: Provider) -> AppStatus {
+    let endpoint = crate::proxy::providers::get_adapter(app_type)
+        .extract_base_url(&provider)
+        .ok()
+        .filter(|url| !url.is_empty());
+    AppStatus {
+        model: crate::services::curl_export::configured_model(app_type, &provider),
+        mapped_model: None,
+        endpoint,
+        today_cost_usd: None,
+        today_requests: 0,
+        provider_id: provider.id,
+        provider_name: provider.name,
+    }
+}
+
+/// 本地时区今日零点（Unix 秒）
+fn today_start() -> i64 {
+    let midnight = chrono::Local::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .expect("valid time");
+    chrono::Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|time| time.timestamp())
+        .unwrap_or_default()
+}
+
+/// 从数据库汇总当前状态
+pub fn snapshot(db: &Database, source: StatusSource) -> Result<StatusSnapshot, AppError> {
+    let today: BTreeMap<String, UsageSummary> = db
+        .get_usage_summary_by_app(Some(today_start()), None, None, None)?
+        .into_iter()
+        .map(|item| (item.app_type, item.summary))
+        .collect();
+    let mut apps = BTreeMap::new();
+    for app_type in AppType::all() {
+        if let Some(mut status) = app_status(db, &app_type)? {
+            if let Some(summary) = today.get(app_type.as_str()) {
+                status.today_cost_usd = Some(summary.total_cost.clone());
+                status.today_requests = summary.total_requests;
+            }
+            apps.insert(app_type.as_str().to_string(), status);
+        }
+    }
+    Ok(StatusSnapshot {
+        version: STATUS_FILE_VERSION,
+        updated_at: chrono::Utc::now().timestamp(),
+        source,
+        apps,
+    })
+}
+
+/// 读取已写入的状态文件
+pub fn read() -> Option<StatusSnapshot> {
+    let text = std::fs::read_to_string(status_path()).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+/// 开启状态文件时重新写入；失败只记录日志
+pub fn refresh(db: &Database, source: StatusSource) {
+    if !crate::settings::get_settings().status_file_enabled {
+        return;
+    }
+    let result = snapshot(db, source)
+        .and_then(|snapshot| crate::config::write_json_file(&status_path(), &snapshot));
+    if let Err(e) = result {
+        log::warn!("[StatusFile] 写入状态文件失败: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn status_includes_configured_model_and_endpoint() {
+        let provider = Provider::with_id(
+            "relay".to_string(),
+            "Relay".to_string(),
+            json!({
+                "env": {
+                    "ANTHROPIC_BASE_URL": "https://relay.example.com",
+                    "ANTHROPIC_AUTH_TOKEN": "sk-test",
+                    "ANTHROPIC_MODEL": "claude-sonnet-4-5"
+                }
+            }),
+            None,
+        );
+        let status = provider_status(&AppType::Claude, provider);
+        assert_eq!(status.provider_id, "relay");
+        assert_eq!(status.provider_name, "Relay");
+        assert_eq!(status.model.as_deref(), Some("claude-sonnet-4-5"));
+        assert_eq!(
+            status.endpoint.as_deref(),
+            Some("https://relay.example.com")
+        );
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_json() {
+        let mut apps = BTreeMap::new();
+        apps.insert(
+            "codex".to_string(),
+            AppStatus {
+                provider_id: "p".to_string(),
+                provider_name: "P".to_string(),
+                model: None,
+                mapped_model: None,
+                endpoint: Some("https://api.example.com/v1".to_string()),
+                today_cost_usd: Some("0.125000".to_string()),
+                today_requests: 3,
+            },
+        );
+        let snapshot = StatusSnapshot {
+            version: STATUS_FILE_VERSION,
+            updated_at: 1,
+            source: StatusSource::Failover,
+            apps,
+        };
+        let text = serde_json::to_string(&snapshot).unwrap();
+        assert!(text.contains("\"source\":\"failover\""));
+        assert!(!text.contains("model"));
+        assert!(text.contains("\"todayCostUsd\":\"0.125000\""));
+        assert_eq!(
+            serde_json::from_str::<StatusSnapshot>(&text).unwrap(),
+            snapshot
+        );
+
+        // 旧版本写出的文件没有新增字段，仍可读取
+        let legacy: StatusSnapshot =
+            serde_json::from_str(r#"{"updatedAt":1,"source":"switch","apps":{}}"#).unwrap();
+        assert_eq!(legacy.version, STATUS_FILE_VERSION);
+    }
+}
@@ -23,8 +23,10 @@ use std::time::Instant;
 
 use crate::app_config::AppType;
 use crate::error::AppError;
-use crate::provider::Provider;
+use crate::provider::{HeaderTemplateContext, Provider};
 use crate::proxy::providers::{get_adapter, ClaudeAdapter, ProviderAdapter};
+use crate::proxy::HttpErrorClass;
+use crate::services::connection_diag::{self, ConnectionDiagnosis};
 
 /// 健康状态枚举
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -73,9 +75,12 @@ pub struct StreamCheckResult {
     pub model_used: String,
     pub tested_at: i64,
     pub retry_count: u32,
-    /// 细粒度错误分类；连通性检查不再细分，恒为 None。
+    /// 统一错误分类（`HttpErrorClass`，如 `network` / `client`）；可达时为 None。
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_category: Option<String>,
+    /// 网络层失败时的分层诊断（DNS / TCP / TLS / HTTP）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<ConnectionDiagnosis>,
 }
 
 /// 连通性检查服务
@@ -127,6 +132,7 @@ impl StreamCheckService {
             tested_at: chrono::Utc::now().timestamp(),
             retry_count: config.max_retries,
             error_category: None,
+            diagnostics: None,
         }))
     }
 
@@ -143,17 +149,52 @@ impl StreamCheckService {
             None => Self::resolve_base_url(app_type, provider)?,
         };
 
-        let client = crate::proxy::http_client::get();
+        let meta = provider.meta.as_ref();
+        let client = crate::proxy::http_client::get_for_provider(
+            meta.and_then(|meta| meta.tls_options()),
+            meta.and_then(|meta| meta.timeouts())
+                .and_then(|timeouts| timeouts.connect_timeout()),
+        )
+        .map_err(AppError::Message)?;
         let timeout = std::time::Duration::from_secs(config.timeout_secs);
         let ua = Self::custom_user_agent(provider);
+        let custom_headers = Self::custom_headers(app_type, provider);
 
-        let result = Self::probe_reachability(&client, &base_url, timeout, ua).await;
+        let url = base_url.trim();
+        let (result, class) = if url.is_empty() {
+            (
+                Err(AppError::Message("base_url 为空".to_string())),
+                Some(HttpErrorClass::Transform),
+            )
+        } else if crate::proxy::mock_upstream::is_mock_url(url) {
+            // 内置模拟供应商没有网络端点，只模拟配置的延迟
+            if let Some(latency) = meta
+                .and_then(|meta| meta.mock.as_ref())
+                .and_then(|mock| mock.latency_ms)
+            {
+                tokio::time::sleep(std::time::Duration::from_millis(latency)).await;
+            }
+            (Ok(200), None)
+        } else {
+            let result = Self::probe_reachability(&client, url, timeout, ua, &custom_headers).await;
+            let class = result
+                .as_ref()
+                .err()
+                .map(HttpErrorClass::from_reqwest_error);
+            (result.map_err(Self::map_request_error), class)
+        };
         let response_time = start.elapsed().as_millis() as u64;
-        Ok(Self::build_result(
-            result,
-            response_time,
-            config.degraded_threshold_ms,
-        ))
+        let mut check = Self::build_result(result, response_time, config.degraded_threshold_ms);
+        check.error_category = class.map(|class| class.as_str().to_string());
+        // 网络层失败：逐层重放，指出具体卡在 DNS / TCP / TLS / HTTP 哪一层
+        if class == Some(HttpErrorClass::Network) {
+            let diagnosis = connection_diag::diagnose(&client, url, timeout).await;
+            if let Some(summary) = diagnosis.summary() {
+                check.message = summary;
+            }
+            check.diagnostics = Some(diagnosis);
+        }
+        Ok(check)
     }
 
     /// 解析供应商 `base_url`。
@@ -200,14 +241,10 @@ impl StreamCheckService {
         base_url: &str,
         timeout: std::time::Duration,
         custom_ua: Option<HeaderValue>,
-    ) -> Result<u16, AppError> {
-        let url = base_url.trim();
-        if url.is_empty() {
-            return Err(AppError::Message("base_url 为空".to_string()));
-        }
-
+        custom_headers: &[(String, String)],
+    ) -> Result<u16, reqwest::Error> {
         let mut req = client
-            .get(url)
+            .get(base_url)
             .timeout(timeout)
             .header("accept", "*/*")
             .header("accept-encoding", "identity");
@@ -215,11 +252,12 @@ impl StreamCheckService {
         if let Some(ua) = custom_ua {
             req = req.header("user-agent", ua);
         }
-
-        match req.send().await {
-            Ok(resp) => Ok(resp.status().as_u16()),
-            Err(e) => Err(Self::map_request_error(e)),
+        // 租户 ID 等自定义请求头：缺少时部分中转直接拒绝，检测结果会与真实请求不一致
+        for (name, value) in custom_headers {
+            req = req.header(name.as_str(), value.as_str());
         }
+
+        req.send().await.map(|resp| resp.status().as_u16())
     }
 
     /// 将探测原始结果包装成 `StreamCheckResult`。
@@ -240,6 +278,7 @@ impl StreamCheckService {
                 tested_at,
                 retry_count: 0,
                 error_category: None,
+                diagnostics: None,
             },
             Err(e) => StreamCheckResult {
                 status: HealthStatus::Failed,
@@ -251,6 +290,7 @@ impl StreamCheckService {
                 tested_at,
                 retry_count: 0,
                 error_category: None,
+                diagnostics: None,
             },
         }
     }
@@ -278,13 +318,42 @@ impl StreamCheckService {
         }
     }
 
-    /// Provider 级自定义 User-Agent（`meta.customUserAgent`），与转发路径共用单一口径：
-    /// trim、空串视为未设置、非法值静默忽略（返回 `None`）。
+    /// 按供应商的客户端标识策略（`meta.clientIdentity`）发送的 User-Agent，与转发路径共用
+    /// 单一口径：自定义 UA 空串视为未设置、非法值静默忽略（返回 `None`）。
     fn custom_user_agent(provider: &Provider) -> Option<HeaderValue> {
         provider
             .meta
             .as_ref()
-            .and_then(|meta| meta.custom_user_agent_header().ok().flatten())
+            .and_then(|meta| meta.identity_user_agent())
+    }
+
+    /// 供应商配置的自定义请求头（`meta.localProxyRequestOverrides.headers`），
+    /// 与转发路径一样渲染模板变量并跳过受保护的请求头；名称或值非法的项忽略。
+    fn custom_headers(app_type: &AppType, provider: &Provider) -> Vec<(String, String)> {
+        let Some(overrides) = provider
+            .meta
+            .as_ref()
+            .and_then(|meta| meta.local_proxy_request_overrides.as_ref())
+        else {
+            return Vec::new();
+        };
+        let model = crate::services::curl_export::configured_model(app_type, provider);
+        let template = HeaderTemplateContext::new(app_type.as_str(), provider, model.as_deref());
+        overrides
+            .rendered_headers(&template)
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let name = reqwest::header::HeaderName::from_bytes(
+                    name.trim().to_ascii_lowercase().as_bytes(),
+                )
+                .ok()?;
+                if crate::proxy::is_protected_local_proxy_override_header(&name) {
+                    return None;
+                }
+                HeaderValue::from_str(&value).ok()?;
+                Some((name.as_str().to_string(), value))
+            })
+            .collect()
     }
 
     // ===== 各应用 base_url 提取（settings_config 结构互不相同）=====
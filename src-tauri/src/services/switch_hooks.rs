@@ -0,0 +1,304 @@
+//! 供应商切换钩子
+//!
+//! 在切换前（pre-switch）、切换成功后（post-switch）、切换失败（switch-failed）时
+//! 执行用户在设置中登记的本地命令或 HTTP webhook，例如发送通知、刷新 tmux 状态栏。
+//!
+//! - 命令通过环境变量 `CC_SWITCH_*` 获取事件详情，webhook 以 JSON POST 相同内容
+//! - pre-switch 钩子会在切换前等待完成（受超时约束），其余钩子在后台执行
+//! - 钩子失败只记录日志，绝不影响切换本身
+
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::settings::{SwitchHook, SwitchHookAction, SwitchHookEvent};
+use crate::store::AppState;
+
+const DEFAULT_HOOK_TIMEOUT_SECS: u64 = 10;
+
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// 钩子事件详情
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchHookContext {
+    pub event: SwitchHookEvent,
+    pub app: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_provider_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_provider_name: Option<String>,
+    pub to_provider_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_provider_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SwitchHookContext {
+    /// 以切换前的当前供应商为 from、目标供应商为 to 构建上下文
+    pub fn new(state: &AppState, app_type: &AppType, to_provider_id: &str) -> Self {
+        let app = app_type.as_str();
+        let provider_name = |id: &str| {
+            state
+                .db
+                .get_provider_by_id(id, app)
+                .ok()
+                .flatten()
+                .map(|provider| provider.name)
+        };
+        let from_provider_id = crate::settings::get_effective_current_provider(&state.db, app_type)
+            .ok()
+            .flatten();
+
+        Self {
+            event: SwitchHookEvent::PreSwitch,
+            app: app.to_string(),
+            from_provider_name: from_provider_id.as_deref().and_then(provider_name),
+            from_provider_id,
+            to_provider_id: to_provider_id.to_string(),
+            to_provider_name: provider_name(to_provider_id),
+            error: None,
+        }
+    }
+
+    pub fn with_event(&self, event: SwitchHookEvent) -> Self {
+        Self {
+            event,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+
+    fn env_vars(&self) -> Vec<(&'static str, String)> {
+        let mut vars = vec![
+            ("CC_SWITCH_EVENT", self.event.as_str().to_string()),
+            ("CC_SWITCH_APP", self.app.clone()),
+            ("CC_SWITCH_TO_PROVIDER_ID", self.to_provider_id.clone()),
+        ];
+        let optional = [
+            ("CC_SWITCH_FROM_PROVIDER_ID", &self.from_provider_id),
+            ("CC_SWITCH_FROM_PROVIDER_NAME", &self.from_provider_name),
+            ("CC_SWITCH_TO_PROVIDER_NAME", &self.to_provider_name),
+            ("CC_SWITCH_ERROR", &self.error),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                vars.push((key, value.clone()));
+            }
+        }
+        vars
+    }
+}
+
+fn hook_matches(hook: &SwitchHook, ctx: &SwitchHookContext) -> bool {
+    hook.enabled
+        && hook.events.contains(&ctx.event)
+        && (hook.apps.is_empty() || hook.apps.iter().any(|app| app == &ctx.app))
+}
+
+fn hook_timeout(hook: &SwitchHook) -> Duration {
+    Duration::from_secs(
+        hook.timeout_secs
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_HOOK_TIMEOUT_SECS),
+    )
+}
+
+fn shell_command(command: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        use std::os::windows::process::CommandExt;
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]).creation_flags(CREATE_NO_WINDOW);
+        cmd
+    };
+    #[cfg(not(target_os = "windows"))]
+    let mut cmd = {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    cmd
+}
+
+fn run_command(command: &str, ctx: &SwitchHookContext, timeout: Duration) -> Result<(), AppError> {
+    let mut child = shell_command(command)
+        .envs(ctx.env_vars())
+        .spawn()
+        .map_err(|e| AppError::Message(format!("启动钩子命令失败: {e}")))?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => {
+                return Err(AppError::Message(format!("钩子命令退出码异常: {status}")))
+            }
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(AppError::Message(format!(
+                    "钩子命令超时（{} 秒）",
+                    timeout.as_secs()
+                )));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(AppError::Message(format!("等待钩子命令失败: {e}"))),
+        }
+    }
+}
+
+fn run_webhook(url: &str, ctx: &SwitchHookContext, timeout: Duration) -> Result<(), AppError> {
+    let request = crate::proxy::http_client::get_auxiliary("switch hook webhook")
+        .map_err(AppError::Message)?
+        .post(url)
+        .timeout(timeout)
+        .json(ctx)
+        .send();
+    let response = tauri::async_runtime::block_on(request)
+        .map_err(|e| AppError::Message(format!("webhook 请求失败: {e}")))?;
+    if !response.status().is_success() {
+        return Err(AppError::Message(format!(
+            "webhook 返回状态码 {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
+fn run_hook(hook: &SwitchHook, ctx: &SwitchHookContext) {
+    let timeout = hook_timeout(hook);
+    let result = match &hook.action {
+        SwitchHookAction::Command { command } => run_command(command, ctx, timeout),
+        SwitchHookAction::Webhook { url } => run_webhook(url, ctx, timeout),
+    };
+    match result {
+        Ok(()) => log::debug!("[SwitchHook] {} ({}) 执行完成", hook.id, ctx.event.as_str()),
+        Err(e) => log::warn!(
+            "[SwitchHook] {} ({}) 执行失败: {e}",
+            hook.id,
+            ctx.event.as_str()
+        ),
+    }
+}
+
+pub struct SwitchHookService;
+
+impl SwitchHookService {
+    /// 执行匹配事件的钩子
+    ///
+    /// 钩子在独立线程中运行（webhook 需要阻塞等待异步请求，不能占用调用方的 runtime）；
+    /// pre-switch 钩子会等待全部完成后才返回。
+    pub fn dispatch(ctx: SwitchHookContext) {
+        let hooks: Vec<SwitchHook> = crate::settings::switch_hooks()
+            .into_iter()
+            .filter(|hook| hook_matches(hook, &ctx))
+            .collect();
+        if hooks.is_empty() {
+            return;
+        }
+
+        let wait = ctx.event == SwitchHookEvent::PreSwitch;
+        let handle = std::thread::spawn(move || {
+            for hook in &hooks {
+                run_hook(hook, &ctx);
+            }
+        });
+        if wait && handle.join().is_err() {
+            log::warn!("[SwitchHook] pre-switch 钩子线程异常退出");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(event: SwitchHookEvent) -> SwitchHookContext {
+        SwitchHookContext {
+            event,
+            app: "claude".to_string(),
+            from_provider_id: Some("old".to_string()),
+            from_provider_name: None,
+            to_provider_id: "new".to_string(),
+            to_provider_name: Some("New".to_string()),
+            error: None,
+        }
+    }
+
+    fn hook(apps: &[&str], events: &[SwitchHookEvent]) -> SwitchHook {
+        SwitchHook {
+            id: "h".to_string(),
+            enabled: true,
+            apps: apps.iter().map(|app| app.to_string()).collect(),
+            events: events.to_vec(),
+            action: SwitchHookAction::Command {
+                command: "true".to_string(),
+            },
+            timeout_secs: None,
+        }
+    }
+
+    #[test]
+    fn hook_matching_respects_app_and_event_filters() {
+        let ctx = context(SwitchHookEvent::PostSwitch);
+        assert!(hook_matches(
+            &hook(&[], &[SwitchHookEvent::PostSwitch]),
+            &ctx
+        ));
+        assert!(hook_matches(
+            &hook(&["claude"], &[SwitchHookEvent::PostSwitch]),
+            &ctx
+        ));
+        assert!(!hook_matches(
+            &hook(&["codex"], &[SwitchHookEvent::PostSwitch]),
+            &ctx
+        ));
+        assert!(!hook_matches(
+            &hook(&[], &[SwitchHookEvent::PreSwitch]),
+            &ctx
+        ));
+    }
+
+    #[test]
+    fn env_vars_skip_missing_fields() {
+        let vars = context(SwitchHookEvent::SwitchFailed)
+            .with_error("boom")
+            .env_vars();
+        let get = |key: &str| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(get("CC_SWITCH_EVENT"), Some("switch-failed"));
+        assert_eq!(get("CC_SWITCH_FROM_PROVIDER_ID"), Some("old"));
+        assert_eq!(get("CC_SWITCH_FROM_PROVIDER_NAME"), None);
+        assert_eq!(get("CC_SWITCH_ERROR"), Some("boom"));
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn command_hooks_time_out_and_report_exit_status() {
+        let ctx = context(SwitchHookEvent::PostSwitch);
+        assert!(run_command(
+            "test \"$CC_SWITCH_TO_PROVIDER_ID\" = new",
+            &ctx,
+            Duration::from_secs(5)
+        )
+        .is_ok());
+        assert!(run_command("exit 3", &ctx, Duration::from_secs(5)).is_err());
+        assert!(run_command("sleep 5", &ctx, Duration::from_millis(100)).is_err());
+    }
+}
@@ -0,0 +1,438 @@
+//! Storage backends for cross-device sync.
+//!
+//! [`SyncBackend`] abstracts the remote blob store (upload / download / list /
+//! delete of backup files inside one profile directory) so the manifest-based
+//! protocol in [`super::sync_protocol`] can run unchanged over WebDAV servers
+//! (Nextcloud, 坚果云, …) and S3-compatible object stores (AWS, MinIO, R2, …).
+//!
+//! [`Encrypted`] wraps any backend and seals every blob with a passphrase
+//! before it leaves the machine, so self-hosted storage never sees plaintext.
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::num::NonZeroU32;
+
+use crate::error::AppError;
+use crate::services::s3::{self, S3Credentials};
+use crate::services::webdav::{self, WebDavAuth};
+
+use super::sync_protocol::{localized, REMOTE_MANIFEST};
+
+// ─── Backend trait ───────────────────────────────────────────
+
+/// A remote directory holding sync blobs, addressed by file name.
+#[allow(async_fn_in_trait)]
+pub(crate) trait SyncBackend {
+    /// Human-readable remote directory, shown in the UI.
+    fn location(&self) -> String;
+
+    /// Make sure the remote directory exists before uploading.
+    async fn prepare(&self) -> Result<(), AppError>;
+
+    async fn put(&self, name: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), AppError>;
+
+    /// Returns `None` when the blob does not exist.
+    async fn get(
+        &self,
+        name: &str,
+        max_bytes: usize,
+    ) -> Result<Option<(Vec<u8>, Option<String>)>, AppError>;
+
+    async fn head_etag(&self, name: &str) -> Result<Option<String>, AppError>;
+
+    /// Names of the blobs stored directly in this directory.
+    async fn list(&self) -> Result<Vec<String>, AppError>;
+
+    /// Delete a blob. Deleting a missing blob is not an error.
+    async fn delete(&self, name: &str) -> Result<(), AppError>;
+}
+
+/// Delete every blob in the backend directory and return how many were removed.
+///
+/// The manifest goes first so an interrupted delete never leaves a manifest
+/// pointing at missing artifacts.
+pub(crate) async fn delete_all(backend: &impl SyncBackend) -> Result<usize, AppError> {
+    let mut names = backend.list().await?;
+    names.sort_by_key(|name| name != REMOTE_MANIFEST);
+    for name in &names {
+        backend.delete(name).await?;
+    }
+    Ok(names.len())
+}
+
+// ─── WebDAV ──────────────────────────────────────────────────
+
+pub(crate) struct WebDavBackend {
+    pub base_url: String,
+    pub auth: WebDavAuth,
+    /// Path segments of the profile directory below `base_url`.
+    pub dir: Vec<String>,
+}
+
+impl WebDavBackend {
+    fn file_url(&self, name: &str) -> Result<String, AppError> {
+        let mut segs = self.dir.clone();
+        segs.extend(webdav::path_segments(name).map(str::to_string));
+        webdav::build_remote_url(&self.base_url, &segs)
+    }
+}
+
+impl SyncBackend for WebDavBackend {
+    fn location(&self) -> String {
+        format!("/{}", self.dir.join("/"))
+    }
+
+    async fn prepare(&self) -> Result<(), AppError> {
+        webdav::ensure_remote_directories(&self.base_url, &self.dir, &self.auth).await
+    }
+
+    async fn put(&self, name: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), AppError> {
+        webdav::put_bytes(&self.file_url(name)?, &self.auth, bytes, content_type).await
+    }
+
+    async fn get(
+        &self,
+        name: &str,
+        max_bytes: usize,
+    ) -> Result<Option<(Vec<u8>, Option<String>)>, AppError> {
+        webdav::get_bytes(&self.file_url(name)?, &self.auth, max_bytes).await
+    }
+
+    async fn head_etag(&self, name: &str) -> Result<Option<String>, AppError> {
+        webdav::head_etag(&self.file_url(name)?, &self.auth).await
+    }
+
+    async fn list(&self) -> Result<Vec<String>, AppError> {
+        let dir_url = webdav::build_remote_url(&self.base_url, &self.dir)?;
+        webdav::list_files(&dir_url, &self.auth).await
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), AppError> {
+        webdav::delete(&self.file_url(name)?, &self.auth).await
+    }
+}
+
+// ─── S3 ──────────────────────────────────────────────────────
+
+pub(crate) struct S3Backend {
+    pub creds: S3Credentials,
+    /// Key prefix of the profile directory, without trailing `/`.
+    pub prefix: String,
+}
+
+impl S3Backend {
+    pub fn key(&self, name: &str) -> String {
+        format!("{}/{}", self.prefix, name)
+    }
+}
+
+impl SyncBackend for S3Backend {
+    fn location(&self) -> String {
+        self.prefix.clone()
+    }
+
+    async fn prepare(&self) -> Result<(), AppError> {
+        // Object stores have no directories.
+        Ok(())
+    }
+
+    async fn put(&self, name: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), AppError> {
+        s3::put_object(&self.creds, &self.key(name), bytes, content_type).await
+    }
+
+    async fn get(
+        &self,
+        name: &str,
+        max_bytes: usize,
+    ) -> Result<Option<(Vec<u8>, Option<String>)>, AppError> {
+        s3::get_object(&self.creds, &self.key(name), max_bytes).await
+    }
+
+    async fn head_etag(&self, name: &str) -> Result<Option<String>, AppError> {
+        s3::head_object(&self.creds, &self.key(name)).await
+    }
+
+    async fn list(&self) -> Result<Vec<String>, AppError> {
+        let dir = format!("{}/", self.prefix);
+        let keys = s3::list_objects(&self.creds, &dir).await?;
+        Ok(keys
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&dir).map(str::to_string))
+            .filter(|name| !name.is_empty() && !name.contains('/'))
+            .collect())
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), AppError> {
+        s3::delete_object(&self.creds, &self.key(name)).await
+    }
+}
+
+// ─── Encryption ──────────────────────────────────────────────
+
+/// Header identifying a passphrase-encrypted blob.
+const ENCRYPTED_MAGIC: &[u8; 8] = b"CCSENC1\0";
+const SALT_LEN: usize = 16;
+const TAG_LEN: usize = 16;
+/// Bytes added to every blob by [`encrypt_blob`].
+pub(crate) const ENCRYPTION_OVERHEAD: usize =
+    ENCRYPTED_MAGIC.len() + SALT_LEN + NONCE_LEN + TAG_LEN;
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+/// Backend decorator that encrypts blobs on upload and decrypts on download.
+///
+/// With `passphrase == None` it passes blobs through, but still refuses to
+/// hand back encrypted blobs so a missing passphrase fails loudly.
+pub(crate) struct Encrypted<B> {
+    inner: B,
+    passphrase: Option<String>,
+}
+
+impl<B: SyncBackend> Encrypted<B> {
+    pub fn new(inner: B, passphrase: Option<&str>) -> Self {
+        Self {
+            inner,
+            passphrase: passphrase.map(str::to_string),
+        }
+    }
+
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+}
+
+impl<B: SyncBackend> SyncBackend for Encrypted<B> {
+    fn location(&self) -> String {
+        self.inner.location()
+    }
+
+    async fn prepare(&self) -> Result<(), AppError> {
+        self.inner.prepare().await
+    }
+
+    async fn put(&self, name: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), AppError> {
+        match &self.passphrase {
+            Some(passphrase) => {
+                let sealed = encrypt_blob(passphrase, &bytes)?;
+                self.inner
+                    .put(name, sealed, "application/octet-stream")
+                    .await
+            }
+            None => self.inner.put(name, bytes, content_type).await,
+        }
+    }
+
+    async fn get(
+        &self,
+        name: &str,
+        max_bytes: usize,
+    ) -> Result<Option<(Vec<u8>, Option<String>)>, AppError> {
+        let Some((bytes, etag)) = self
+            .inner
+            .get(name, max_bytes.saturating_add(ENCRYPTION_OVERHEAD))
+            .await?
+        else {
+            return Ok(None);
+        };
+        let plain = match (&self.passphrase, is_encrypted(&bytes)) {
+            (Some(passphrase), true) => decrypt_blob(passphrase, &bytes)?,
+            (None, false) => bytes,
+            (None, true) => {
+                return Err(localized(
+                    "sync.encryption.passphrase_missing",
+                    "远端备份已加密，请先在同步设置中启用加密并填写口令",
+                    "The remote backup is encrypted. Enable encryption and enter the passphrase in sync settings.",
+                ))
+            }
+            (Some(_), false) => {
+                return Err(localized(
+                    "sync.encryption.remote_plaintext",
+                    "远端备份未加密：请重新上传，或关闭备份加密后再下载",
+                    "The remote backup is not encrypted. Upload again, or disable backup encryption to download it.",
+                ))
+            }
+        };
+        Ok(Some((plain, etag)))
+    }
+
+    async fn head_etag(&self, name: &str) -> Result<Option<String>, AppError> {
+        self.inner.head_etag(name).await
+    }
+
+    async fn list(&self) -> Result<Vec<String>, AppError> {
+        self.inner.list().await
+    }
+
+    async fn delete(&self, name: &str) -> Result<(), AppError> {
+        self.inner.delete(name).await
+    }
+}
+
+fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(ENCRYPTED_MAGIC)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<LessSafeKey, AppError> {
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(PBKDF2_ITERATIONS).expect("non-zero iterations"),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let unbound = UnboundKey::new(&AES_256_GCM, &key).map_err(|_| encryption_failed())?;
+    Ok(LessSafeKey::new(unbound))
+}
+
+/// Layout: `magic | salt(16) | nonce(12) | ciphertext | tag(16)`.
+pub(crate) fn encrypt_blob(passphrase: &str, plain: &[u8]) -> Result<Vec<u8>, AppError> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt).map_err(|_| encryption_failed())?;
+    rng.fill(&mut nonce).map_err(|_| encryption_failed())?;
+
+    let key = derive_key(passphrase, &salt)?;
+    let mut in_out = plain.to_vec();
+    key.seal_in_place_append_tag(
+        Nonce::assume_unique_for_key(nonce),
+        Aad::from(ENCRYPTED_MAGIC),
+        &mut in_out,
+    )
+    .map_err(|_| encryption_failed())?;
+
+    let mut out = Vec::with_capacity(ENCRYPTION_OVERHEAD + plain.len());
+    out.extend_from_slice(ENCRYPTED_MAGIC);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&in_out);
+    Ok(out)
+}
+
+pub(crate) fn decrypt_blob(passphrase: &str, sealed: &[u8]) -> Result<Vec<u8>, AppError> {
+    if !is_encrypted(sealed) || sealed.len() < ENCRYPTION_OVERHEAD {
+        return Err(decryption_failed());
+    }
+    let rest = &sealed[ENCRYPTED_MAGIC.len()..];
+    let (salt, rest) = rest.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into().map_err(|_| decryption_failed())?;
+
+    let key = derive_key(passphrase, salt)?;
+    let mut in_out = ciphertext.to_vec();
+    let plain_len = key
+        .open_in_place(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(ENCRYPTED_MAGIC),
+            &mut in_out,
+        )
+        .map_err(|_| decryption_failed())?
+        .len();
+    in_out.truncate(plain_len);
+    Ok(in_out)
+}
+
+fn encryption_failed() -> AppError {
+    localized(
+        "sync.encryption.failed",
+        "加密备份失败",
+        "Failed to encrypt backup.",
+    )
+}
+
+fn decryption_failed() -> AppError {
+    localized(
+        "sync.encryption.decrypt_failed",
+        "解密远端备份失败：口令错误或文件已损坏",
+        "Failed to decrypt remote backup: wrong passphrase or corrupted file.",
+    )
+}
+
+// ─── XML helpers ─────────────────────────────────────────────
+
+/// Collect the text of every `<local_name>` element, ignoring namespace
+/// prefixes (`<d:href>`, `<D:href>`, `<href>`) and decoding XML entities.
+///
+/// Good enough for PROPFIND multistatus and ListObjectsV2 responses, which
+/// never nest the elements we look for.
+pub(crate) fn xml_element_texts(body: &str, local_name: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = body;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with('/') || tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        let name = tag.split_whitespace().next().unwrap_or_default();
+        let local = name.rsplit(':').next().unwrap_or(name);
+        if local != local_name || tag.ends_with('/') {
+            continue;
+        }
+        let Some(close) = rest.find("</") else {
+            break;
+        };
+        out.push(decode_xml_entities(rest[..close].trim()));
+        rest = &rest[close..];
+    }
+    out
+}
+
+fn decode_xml_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// ─── Tests ───────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_blob_roundtrips_and_rejects_wrong_passphrase() {
+        let sealed = encrypt_blob("correct horse", b"INSERT INTO providers").unwrap();
+        assert!(is_encrypted(&sealed));
+        assert_eq!(sealed.len(), ENCRYPTION_OVERHEAD + 21);
+        assert_eq!(
+            decrypt_blob("correct horse", &sealed).unwrap(),
+            b"INSERT INTO providers"
+        );
+        assert!(decrypt_blob("battery staple", &sealed).is_err());
+    }
+
+    #[test]
+    fn decrypt_blob_rejects_tampered_or_plain_data() {
+        let mut sealed = encrypt_blob("pw", b"payload").unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x01;
+        assert!(decrypt_blob("pw", &sealed).is_err());
+        assert!(decrypt_blob("pw", b"{\"format\":\"cc-switch\"}").is_err());
+    }
+
+    #[test]
+    fn xml_element_texts_handles_prefixes_and_entities() {
+        let body = r#"<?xml version="1.0"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Contents><Key>root/v2/db-v6/default/db.sql</Key><Size>10</Size></Contents>
+  <Contents><Key>root/v2/db-v6/default/a&amp;b.zip</Key><Size>20</Size></Contents>
+  <s3:Key/>
+</ListBucketResult>"#;
+        assert_eq!(
+            xml_element_texts(body, "Key"),
+            vec![
+                "root/v2/db-v6/default/db.sql".to_string(),
+                "root/v2/db-v6/default/a&b.zip".to_string(),
+            ]
+        );
+        assert_eq!(xml_element_texts(body, "Size"), vec!["10", "20"]);
+    }
+}
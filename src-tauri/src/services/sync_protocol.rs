@@ -1,6 +1,7 @@
 //! Transport-agnostic sync protocol layer.
 //!
-//! Shared by WebDAV, S3, and future transports. Artifact set: `db.sql` + `skills.zip`.
+//! Shared by WebDAV, S3, and future transports. Artifact set: `db.sql` + `skills.zip`,
+//! plus the optional usage ledger files maintained by [`super::usage_sync`].
 
 use std::collections::BTreeMap;
 use std::fs;
@@ -13,6 +14,8 @@ use tempfile::tempdir;
 
 use crate::error::AppError;
 
+use super::usage_sync::UsagePlan;
+
 // Re-export archive functions for use by transport layers.
 pub(crate) use super::webdav_sync::archive::{
     backup_current_skills, restore_skills_from_backup, restore_skills_zip, zip_skills_ssot,
@@ -70,6 +73,30 @@ pub(crate) struct SyncManifest {
     pub created_at: String,
     pub artifacts: BTreeMap<String, ArtifactMeta>,
     pub snapshot_id: String,
+    /// 用量明细账本；旧版本客户端忽略该字段，上传时会丢弃账本
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<UsageLedger>,
+}
+
+/// 用量明细文件清单：一个压缩后的基线加上按时间追加的增量，文件均列在 `artifacts` 中
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct UsageLedger {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deltas: Vec<String>,
+}
+
+impl UsageLedger {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.base.is_none() && self.deltas.is_empty()
+    }
+
+    /// 基线在前、增量按追加顺序
+    pub(crate) fn files(&self) -> impl Iterator<Item = &String> {
+        self.base.iter().chain(self.deltas.iter())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,6 +131,7 @@ impl RemoteLayout {
 
 pub(crate) fn build_local_snapshot(
     db: &crate::database::Database,
+    usage: Option<&UsagePlan>,
 ) -> Result<LocalSnapshot, AppError> {
     // Export database to SQL string
     let sql_string = db.export_sql_string_for_sync()?;
@@ -138,6 +166,9 @@ pub(crate) fn build_local_snapshot(
             size: skills_zip.len() as u64,
         },
     );
+    if let Some(plan) = usage {
+        artifacts.extend(plan.artifacts.clone());
+    }
 
     let snapshot_id = compute_snapshot_id(&artifacts);
     let manifest = SyncManifest {
@@ -148,6 +179,9 @@ pub(crate) fn build_local_snapshot(
         created_at: Utc::now().to_rfc3339(),
         artifacts,
         snapshot_id,
+        usage: usage
+            .map(|plan| plan.ledger.clone())
+            .filter(|ledger| !ledger.is_empty()),
     };
     let manifest_bytes =
         serde_json::to_vec_pretty(&manifest).map_err(|e| AppError::JsonSerialize { source: e })?;
@@ -493,6 +527,7 @@ mod tests {
             created_at: "2026-02-12T00:00:00Z".to_string(),
             artifacts,
             snapshot_id: "snap-1".to_string(),
+            usage: None,
         }
     }
 
@@ -592,6 +627,30 @@ mod tests {
             value.get("deviceId").is_none(),
             "manifest should not contain deviceId"
         );
+        assert!(value.get("usage").is_none());
+    }
+
+    #[test]
+    fn manifest_usage_ledger_round_trips_and_defaults_to_none() {
+        let mut manifest =
+            manifest_with(PROTOCOL_FORMAT, PROTOCOL_VERSION, Some(DB_COMPAT_VERSION));
+        manifest.usage = Some(UsageLedger {
+            base: Some("usage-base-a.jsonl.gz".to_string()),
+            deltas: vec!["usage-delta-b.jsonl.gz".to_string()],
+        });
+        let bytes = serde_json::to_vec(&manifest).unwrap();
+        let parsed: SyncManifest = serde_json::from_slice(&bytes).unwrap();
+        let ledger = parsed.usage.unwrap();
+        assert_eq!(
+            ledger.files().collect::<Vec<_>>(),
+            vec!["usage-base-a.jsonl.gz", "usage-delta-b.jsonl.gz"]
+        );
+
+        // 旧版本上传的 manifest 没有账本
+        let mut legacy = serde_json::to_value(&manifest).unwrap();
+        legacy.as_object_mut().unwrap().remove("usage");
+        let parsed: SyncManifest = serde_json::from_value(legacy).unwrap();
+        assert!(parsed.usage.is_none());
     }
 
     #[test]
@@ -0,0 +1,191 @@
+//! 本地功能使用统计（Telemetry）
+//!
+//! 完全本地、默认关闭：
+//! - 仅记录子系统级别的使用计数（如「启动代理」「安装 Skill」），不含任何供应商、
+//!   密钥、URL、路径等可识别信息
+//! - 数据保存在 `~/.cc-switch/telemetry.json`，不随数据库云同步
+//! - 不会主动上传；用户可导出报告后自行决定是否提交给维护者
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::config::{get_app_config_dir, read_json_file, write_json_file};
+use crate::error::AppError;
+
+/// 可计数的功能事件
+///
+/// 新增事件时只追加，不要重命名已有事件（已导出的报告依赖稳定的 key）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetryEvent {
+    ProviderSwitch,
+    ProxyStart,
+    SkillInstall,
+    McpUpsert,
+    PromptEnable,
+    StreamCheck,
+    WebDavUpload,
+    WebDavDownload,
+    S3Upload,
+    S3Download,
+    ConfigExport,
+    ConfigImport,
+}
+
+impl TelemetryEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ProviderSwitch => "provider.switch",
+            Self::ProxyStart => "proxy.start",
+            Self::SkillInstall => "skills.install",
+            Self::McpUpsert => "mcp.upsert",
+            Self::PromptEnable => "prompts.enable",
+            Self::StreamCheck => "stream_check.run",
+            Self::WebDavUpload => "sync.webdav.upload",
+            Self::WebDavDownload => "sync.webdav.download",
+            Self::S3Upload => "sync.s3.upload",
+            Self::S3Download => "sync.s3.download",
+            Self::ConfigExport => "config.export",
+            Self::ConfigImport => "config.import",
+        }
+    }
+}
+
+/// 本地聚合的计数快照
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetrySnapshot {
+    /// 首次记录时间（Unix 秒）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<i64>,
+    /// 最近一次记录时间（Unix 秒）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_updated: Option<i64>,
+    /// 事件 key -> 累计次数
+    #[serde(default)]
+    pub counters: BTreeMap<String, u64>,
+}
+
+/// 导出的报告（供用户手动提交）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetryReport {
+    pub app_version: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub generated_at: i64,
+    #[serde(flatten)]
+    pub snapshot: TelemetrySnapshot,
+}
+
+static TELEMETRY_LOCK: Mutex<()> = Mutex::new(());
+
+fn telemetry_path() -> PathBuf {
+    get_app_config_dir().join("telemetry.json")
+}
+
+fn load_snapshot(path: &Path) -> TelemetrySnapshot {
+    if !path.exists() {
+        return TelemetrySnapshot::default();
+    }
+    read_json_file(path).unwrap_or_else(|e| {
+        log::warn!("[Telemetry] 读取统计文件失败，将重新开始计数: {e}");
+        TelemetrySnapshot::default()
+    })
+}
+
+pub struct TelemetryService;
+
+impl TelemetryService {
+    /// 记录一次功能使用；未开启统计时直接返回
+    ///
+    /// 统计失败只记日志，绝不影响调用方的业务流程。
+    pub fn record(event: TelemetryEvent) {
+        if !crate::settings::usage_telemetry_enabled() {
+            return;
+        }
+        if let Err(e) = Self::increment(&telemetry_path(), event, Utc::now().timestamp()) {
+            log::debug!("[Telemetry] 记录 {} 失败: {e}", event.as_str());
+        }
+    }
+
+    fn increment(path: &Path, event: TelemetryEvent, now: i64) -> Result<(), AppError> {
+        let _guard = TELEMETRY_LOCK.lock()?;
+        let mut snapshot = load_snapshot(path);
+        *snapshot
+            .counters
+            .entry(event.as_str().to_string())
+            .or_insert(0) += 1;
+        snapshot.since.get_or_insert(now);
+        snapshot.last_updated = Some(now);
+        write_json_file(path, &snapshot)
+    }
+
+    /// 读取当前计数
+    pub fn snapshot() -> Result<TelemetrySnapshot, AppError> {
+        let _guard = TELEMETRY_LOCK.lock()?;
+        Ok(load_snapshot(&telemetry_path()))
+    }
+
+    /// 清空所有计数
+    pub fn reset() -> Result<(), AppError> {
+        let _guard = TELEMETRY_LOCK.lock()?;
+        let path = telemetry_path();
+        if path.exists() {
+            std::fs::remove_file(&path).map_err(|e| AppError::io(&path, e))?;
+        }
+        Ok(())
+    }
+
+    /// 生成可手动提交的报告
+    pub fn report() -> Result<TelemetryReport, AppError> {
+        Ok(TelemetryReport {
+            app_version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            generated_at: Utc::now().timestamp(),
+            snapshot: Self::snapshot()?,
+        })
+    }
+
+    /// 导出报告到指定文件
+    pub fn export_report(target: &Path) -> Result<TelemetryReport, AppError> {
+        let report = Self::report()?;
+        write_json_file(target, &report)?;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn increment_aggregates_counters_and_keeps_first_timestamp() {
+        let temp = tempdir().expect("tempdir");
+        let path = temp.path().join("telemetry.json");
+
+        TelemetryService::increment(&path, TelemetryEvent::ProxyStart, 100).expect("first");
+        TelemetryService::increment(&path, TelemetryEvent::ProxyStart, 200).expect("second");
+        TelemetryService::increment(&path, TelemetryEvent::SkillInstall, 300).expect("third");
+
+        let snapshot = load_snapshot(&path);
+        assert_eq!(snapshot.counters.get("proxy.start"), Some(&2));
+        assert_eq!(snapshot.counters.get("skills.install"), Some(&1));
+        assert_eq!(snapshot.since, Some(100));
+        assert_eq!(snapshot.last_updated, Some(300));
+    }
+
+    #[test]
+    fn load_snapshot_recovers_from_corrupt_file() {
+        let temp = tempdir().expect("tempdir");
+        let path = temp.path().join("telemetry.json");
+        std::fs::write(&path, "not json").expect("write corrupt file");
+
+        let snapshot = load_snapshot(&path);
+        assert!(snapshot.counters.is_empty());
+    }
+}
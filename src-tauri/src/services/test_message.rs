@@ -0,0 +1,442 @@
+//! 发送测试消息：向供应商发一条真实的用户消息并解析结构化结果
+//!
+//! 与连通性检查（`stream_check`，只看能否到达）和能力探测（`capability_probe`，
+//! 只看状态码）不同，这里会读完响应并提取：实际使用的模型、停止原因、
+//! token 用量、总耗时与首 token 耗时，同时保留原始响应体便于排查。
+//!
+//! 流式模式下每收到一段文本就通过 [`EVENT_TEST_MESSAGE_CHUNK`] 推给前端，
+//! 界面可以像真实对话一样逐字显示。请求地址与鉴权头复用 [`UpstreamTarget`]，
+//! 与真实转发一致。
+
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::app_config::AppType;
+use crate::error::AppError;
+use crate::provider::Provider;
+use crate::services::curl_export::{UpstreamTarget, WireFormat};
+
+/// 流式预览片段的事件名
+pub const EVENT_TEST_MESSAGE_CHUNK: &str = "provider-test-message-chunk";
+
+const TEST_MESSAGE_TIMEOUT: Duration = Duration::from_secs(60);
+const TEST_MESSAGE_MAX_TOKENS: u32 = 256;
+/// 原始响应体最多保留的字节数
+const RAW_BODY_LIMIT: usize = 64 * 1024;
+const DEFAULT_TEST_MESSAGE: &str = "Hi! Please reply with a short greeting.";
+
+/// 测试消息参数
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMessageOptions {
+    /// 消息内容；为空时使用默认问候语
+    #[serde(default)]
+    pub message: Option<String>,
+    /// 模型；为空时取供应商配置的默认模型
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub stream: bool,
+    /// 前端生成的请求 ID，用于匹配流式预览事件
+    #[serde(default)]
+    pub request_id: Option<String>,
+}
+
+/// token 用量
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMessageUsage {
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+}
+
+/// 测试消息结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMessageResult {
+    pub success: bool,
+    pub http_status: Option<u16>,
+    /// 请求的模型
+    pub requested_model: String,
+    /// 响应中声明的实际模型
+    pub model: Option<String>,
+    pub stop_reason: Option<String>,
+    pub usage: Option<TestMessageUsage>,
+    /// 回复文本
+    pub text: String,
+    pub latency_ms: u64,
+    /// 流式模式下首段文本到达的耗时
+    pub first_token_ms: Option<u64>,
+    /// 原始响应体（过长时截断）
+    pub raw_body: String,
+    pub error: Option<String>,
+}
+
+/// 流式预览片段
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestMessageChunk {
+    pub request_id: String,
+    pub delta: String,
+}
+
+/// 从完整响应或流式事件中累积结构化结果
+#[derive(Debug, Default)]
+struct PreviewAccumulator {
+    text: String,
+    model: Option<String>,
+    stop_reason: Option<String>,
+    usage: TestMessageUsage,
+}
+
+fn str_at(value: &Value, pointer: &str) -> Option<String> {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+fn u64_at(value: &Value, pointer: &str) -> Option<u64> {
+    value.pointer(pointer).and_then(Value::as_u64)
+}
+
+impl PreviewAccumulator {
+    /// 应用一个 JSON 对象（完整响应或单个 SSE 事件），返回其中新增的文本
+    fn apply(&mut self, format: WireFormat, value: &Value) -> String {
+        let mut delta = String::new();
+        match format {
+            WireFormat::Anthropic => {
+                // 完整响应：content[].text；流式：content_block_delta.delta.text
+                if let Some(blocks) = value.get("content").and_then(Value::as_array) {
+                    for block in blocks {
+                        if let Some(text) = block.get("text").and_then(Value::as_str) {
+                            delta.push_str(text);
+                        }
+                    }
+                }
+                if let Some(text) = str_at(value, "/delta/text") {
+                    delta.push_str(&text);
+                }
+                let message = value.get("message").unwrap_or(value);
+                self.model = str_at(message, "/model").or(self.model.take());
+                self.stop_reason = str_at(value, "/stop_reason")
+                    .or_else(|| str_at(value, "/delta/stop_reason"))
+                    .or(self.stop_reason.take());
+                let usage = message.get("usage").or_else(|| value.get("usage"));
+                if let Some(usage) = usage {
+                    self.usage.input_tokens =
+                        u64_at(usage, "/input_tokens").or(self.usage.input_tokens);
+                    self.usage.output_tokens =
+                        u64_at(usage, "/output_tokens").or(self.usage.output_tokens);
+                }
+            }
+            WireFormat::OpenAiChat => {
+                if let Some(text) = str_at(value, "/choices/0/message/content")
+                    .or_else(|| str_at(value, "/choices/0/delta/content"))
+                {
+                    delta.push_str(&text);
+                }
+                self.model = str_at(value, "/model").or(self.model.take());
+                self.stop_reason =
+                    str_at(value, "/choices/0/finish_reason").or(self.stop_reason.take());
+                if let Some(usage) = value.get("usage").filter(|u| u.is_object()) {
+                    self.usage.input_tokens = u64_at(usage, "/prompt_tokens");
+                    self.usage.output_tokens = u64_at(usage, "/completion_tokens");
+                }
+            }
+            WireFormat::OpenAiResponses => {
+                if value.get("type").and_then(Value::as_str) == Some("response.output_text.delta") {
+                    if let Some(text) = str_at(value, "/delta") {
+                        delta.push_str(&text);
+                    }
+                }
+                // 完整响应本身，或 response.completed 事件里的 response
+                let response = value.get("response").unwrap_or(value);
+                if let Some(output) = response.get("output").and_then(Value::as_array) {
+                    // 流式时文本已由 delta 累积，只有完整响应才从 output 取
+                    if value.get("response").is_none() {
+                        for item in output {
+                            for part in item
+                                .get("content")
+                                .and_then(Value::as_array)
+                                .into_iter()
+                                .flatten()
+                            {
+                                if let Some(text) = part.get("text").and_then(Value::as_str) {
+                                    delta.push_str(text);
+                                }
+                            }
+                        }
+                    }
+                }
+                self.model = str_at(response, "/model").or(self.model.take());
+                self.stop_reason = str_at(response, "/incomplete_details/reason")
+                    .or_else(|| str_at(response, "/status"))
+                    .or(self.stop_reason.take());
+                if let Some(usage) = response.get("usage").filter(|u| u.is_object()) {
+                    self.usage.input_tokens = u64_at(usage, "/input_tokens");
+                    self.usage.output_tokens = u64_at(usage, "/output_tokens");
+                }
+            }
+            WireFormat::Gemini => {
+                for part in value
+                    .pointer("/candidates/0/content/parts")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                {
+                    if let Some(text) = part.get("text").and_then(Value::as_str) {
+                        delta.push_str(text);
+                    }
+                }
+                self.model = str_at(value, "/modelVersion").or(self.model.take());
+                self.stop_reason =
+                    str_at(value, "/candidates/0/finishReason").or(self.stop_reason.take());
+                if let Some(usage) = value.get("usageMetadata") {
+                    self.usage.input_tokens =
+                        u64_at(usage, "/promptTokenCount").or(self.usage.input_tokens);
+                    self.usage.output_tokens =
+                        u64_at(usage, "/candidatesTokenCount").or(self.usage.output_tokens);
+                }
+            }
+        }
+        self.text.push_str(&delta);
+        delta
+    }
+
+    fn usage(&self) -> Option<TestMessageUsage> {
+        (self.usage != TestMessageUsage::default()).then(|| self.usage.clone())
+    }
+}
+
+/// 从一段 SSE 文本中取出 `data:` 行的 JSON
+fn sse_data_values(lines: &str) -> impl Iterator<Item = Value> + '_ {
+    lines
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("data:"))
+        .map(str::trim)
+        .filter(|data| !data.is_empty() && *data != "[DONE]")
+        .filter_map(|data| serde_json::from_str(data).ok())
+}
+
+fn truncate_raw(raw: &mut String) {
+    if raw.len() > RAW_BODY_LIMIT {
+        let mut end = RAW_BODY_LIMIT;
+        while !raw.is_char_boundary(end) {
+            end -= 1;
+        }
+        raw.truncate(end);
+        raw.push_str("\n…(truncated)");
+    }
+}
+
+pub struct TestMessageService;
+
+impl TestMessageService {
+    /// 发送测试消息；`on_chunk` 在流式模式下每收到一段文本调用一次
+    pub async fn send(
+        app_type: &AppType,
+        provider: &Provider,
+        options: &TestMessageOptions,
+        on_chunk: impl Fn(&str),
+    ) -> Result<TestMessageResult, AppError> {
+        let target = UpstreamTarget::resolve(app_type, provider, options.model.as_deref())?;
+        let meta = provider.meta.as_ref();
+        let client = crate::proxy::http_client::get_for_provider(
+            meta.and_then(|meta| meta.tls_options()),
+            meta.and_then(|meta| meta.timeouts())
+                .and_then(|timeouts| timeouts.connect_timeout()),
+        )
+        .map_err(AppError::Message)?;
+
+        let message = options
+            .message
+            .as_deref()
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+            .unwrap_or(DEFAULT_TEST_MESSAGE);
+        let (endpoint, mut body) =
+            target.message_request(message, TEST_MESSAGE_MAX_TOKENS, options.stream);
+        if options.stream && target.format == WireFormat::OpenAiChat {
+            // 否则流式响应不带 usage
+            body["stream_options"] = json!({ "include_usage": true });
+        }
+
+        let prepared = target.prepare(&endpoint, &body, false)?;
+        let mut request = client
+            .post(&prepared.url)
+            .timeout(TEST_MESSAGE_TIMEOUT)
+            .body(prepared.body);
+        for (name, value) in prepared.headers {
+            request = request.header(name, value);
+        }
+
+        let start = Instant::now();
+        let mut result = TestMessageResult {
+            success: false,
+            http_status: None,
+            requested_model: target.model.clone(),
+            model: None,
+            stop_reason: None,
+            usage: None,
+            text: String::new(),
+            latency_ms: 0,
+            first_token_ms: None,
+            raw_body: String::new(),
+            error: None,
+        };
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                result.latency_ms = start.elapsed().as_millis() as u64;
+                result.error = Some(e.to_string());
+                return Ok(result);
+            }
+        };
+        let status = response.status();
+        result.http_status = Some(status.as_u16());
+        let is_sse = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.contains("text/event-stream"));
+
+        let mut acc = PreviewAccumulator::default();
+        let mut raw = String::new();
+        if status.is_success() && is_sse {
+            let mut stream = response.bytes_stream();
+            let mut pending = String::new();
+            while let Some(chunk) = stream.next().await {
+                let chunk = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        result.error = Some(e.to_string());
+                        break;
+                    }
+                };
+                let text = String::from_utf8_lossy(&chunk);
+                raw.push_str(&text);
+                pending.push_str(&text);
+                // 只处理完整的行，半行留到下一块
+                let Some(cut) = pending.rfind('\n') else {
+                    continue;
+                };
+                let complete: String = pending.drain(..=cut).collect();
+                for value in sse_data_values(&complete) {
+                    let delta = acc.apply(target.format, &value);
+                    if !delta.is_empty() {
+                        result
+                            .first_token_ms
+                            .get_or_insert(start.elapsed().as_millis() as u64);
+                        on_chunk(&delta);
+                    }
+                }
+            }
+            for value in sse_data_values(&pending) {
+                let delta = acc.apply(target.format, &value);
+                if !delta.is_empty() {
+                    on_chunk(&delta);
+                }
+            }
+        } else {
+            raw = response.text().await.unwrap_or_default();
+            if status.is_success() {
+                match serde_json::from_str::<Value>(&raw) {
+                    Ok(value) => {
+                        acc.apply(target.format, &value);
+                    }
+                    Err(e) => result.error = Some(format!("Invalid JSON response: {e}")),
+                }
+            }
+        }
+
+        result.latency_ms = start.elapsed().as_millis() as u64;
+        result.success = status.is_success() && result.error.is_none();
+        if !status.is_success() {
+            result.error = Some(format!("HTTP {}", status.as_u16()));
+        }
+        result.usage = acc.usage();
+        result.model = acc.model;
+        result.stop_reason = acc.stop_reason;
+        result.text = acc.text;
+        truncate_raw(&mut raw);
+        result.raw_body = raw;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anthropic_stream_events_accumulate_preview() {
+        let sse = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"model\":\"claude-sonnet-4-5\",\"usage\":{\"input_tokens\":12,\"output_tokens\":1}}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"Hel\"}}\n\n",
+            "data: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"lo\"}}\n\n",
+            "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\"},\"usage\":{\"output_tokens\":5}}\n\n",
+        );
+        let mut acc = PreviewAccumulator::default();
+        let deltas: Vec<String> = sse_data_values(sse)
+            .map(|value| acc.apply(WireFormat::Anthropic, &value))
+            .filter(|delta| !delta.is_empty())
+            .collect();
+        assert_eq!(deltas, vec!["Hel", "lo"]);
+        assert_eq!(acc.text, "Hello");
+        assert_eq!(acc.model.as_deref(), Some("claude-sonnet-4-5"));
+        assert_eq!(acc.stop_reason.as_deref(), Some("end_turn"));
+        assert_eq!(
+            acc.usage(),
+            Some(TestMessageUsage {
+                input_tokens: Some(12),
+                output_tokens: Some(5),
+            })
+        );
+    }
+
+    #[test]
+    fn openai_chat_full_response_is_parsed() {
+        let body = json!({
+            "model": "gpt-5-2025-08-07",
+            "choices": [{"message": {"content": "Hi there"}, "finish_reason": "stop"}],
+            "usage": {"prompt_tokens": 9, "completion_tokens": 3}
+        });
+        let mut acc = PreviewAccumulator::default();
+        acc.apply(WireFormat::OpenAiChat, &body);
+        assert_eq!(acc.text, "Hi there");
+        assert_eq!(acc.model.as_deref(), Some("gpt-5-2025-08-07"));
+        assert_eq!(acc.stop_reason.as_deref(), Some("stop"));
+        assert_eq!(acc.usage().unwrap().output_tokens, Some(3));
+    }
+
+    #[test]
+    fn responses_stream_does_not_double_count_completed_output() {
+        let mut acc = PreviewAccumulator::default();
+        acc.apply(
+            WireFormat::OpenAiResponses,
+            &json!({"type": "response.output_text.delta", "delta": "Hey"}),
+        );
+        acc.apply(
+            WireFormat::OpenAiResponses,
+            &json!({
+                "type": "response.completed",
+                "response": {
+                    "model": "gpt-5",
+                    "status": "completed",
+                    "output": [{"content": [{"type": "output_text", "text": "Hey"}]}],
+                    "usage": {"input_tokens": 4, "output_tokens": 2}
+                }
+            }),
+        );
+        assert_eq!(acc.text, "Hey");
+        assert_eq!(acc.stop_reason.as_deref(), Some("completed"));
+        assert_eq!(acc.usage().unwrap().input_tokens, Some(4));
+    }
+}
@@ -0,0 +1,202 @@
+//! SSH port-forward helper for remote proxies
+//!
+//! cc-switch 代理跑在远程开发机上时，代理默认只监听远端的 127.0.0.1，笔记本需要一条
+//! SSH 本地端口转发才能使用。这里生成对应的 `ssh -N -L` 命令，可选地托管 ssh 子进程，
+//! 并通过代理的 `/health`（无需鉴权）确认隧道已经打通。
+
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+use crate::i18n;
+
+/// 远端代理的默认监听端口（与代理配置默认值一致）
+pub const DEFAULT_REMOTE_PORT: u16 = 15721;
+
+const HEALTH_TIMEOUT: Duration = Duration::from_secs(3);
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TunnelSpec {
+    /// `user@host` 或 `~/.ssh/config` 中的主机别名
+    pub destination: String,
+    pub ssh_port: Option<u16>,
+    pub identity: Option<String>,
+    pub local_port: u16,
+    pub remote_port: u16,
+}
+
+impl TunnelSpec {
+    /// 本地端口默认与远端端口相同
+    pub fn new(destination: &str, remote_port: u16) -> Result<Self, AppError> {
+        let destination = destination.trim();
+        // 以 `-` 开头会被 ssh 当成参数解析
+        if destination.is_empty() || destination.starts_with('-') {
+            return Err(i18n::error(
+                "tunnel.invalidDestination",
+                &[("destination", destination)],
+            ));
+        }
+        Ok(Self {
+            destination: destination.to_string(),
+            ssh_port: None,
+            identity: None,
+            local_port: remote_port,
+            remote_port,
+        })
+    }
+
+    /// 传给 ssh 的参数：只做转发不开 shell，转发失败（端口被占用）时直接退出，
+    /// 并定期发送保活包，避免空闲时被 NAT 断开
+    pub fn ssh_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "-N".to_string(),
+            "-o".to_string(),
+            "ExitOnForwardFailure=yes".to_string(),
+            "-o".to_string(),
+            "ServerAliveInterval=30".to_string(),
+            "-L".to_string(),
+            format!(
+                "127.0.0.1:{}:127.0.0.1:{}",
+                self.local_port, self.remote_port
+            ),
+        ];
+        if let Some(port) = self.ssh_port {
+            args.extend(["-p".to_string(), port.to_string()]);
+        }
+        if let Some(identity) = &self.identity {
+            args.extend(["-i".to_string(), identity.clone()]);
+        }
+        args.push(self.destination.clone());
+        args
+    }
+
+    /// 可直接粘贴到终端的完整命令
+    pub fn command_line(&self) -> String {
+        std::iter::once("ssh".to_string())
+            .chain(self.ssh_args().iter().map(|arg| shell_arg(arg)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// 隧道在本机的代理地址
+    pub fn local_origin(&self) -> String {
+        format!("http://127.0.0.1:{}", self.local_port)
+    }
+}
+
+/// POSIX shell 转义：只含安全字符时原样输出
+fn shell_arg(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "@%+=:,./-_~".contains(c));
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+fn unhealthy(port: u16, detail: impl std::fmt::Display) -> AppError {
+    i18n::error(
+        "tunnel.unhealthy",
+        &[("port", &port.to_string()), ("detail", &detail.to_string())],
+    )
+}
+
+/// 请求隧道本地端口的 `/health`
+///
+/// 直连回环地址，不走全局代理与系统代理。
+pub async fn check_health(local_port: u16) -> Result<(), AppError> {
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(HEALTH_TIMEOUT)
+        .build()
+        .map_err(|e| unhealthy(local_port, e))?;
+    let response = client
+        .get(format!("http://127.0.0.1:{local_port}/health"))
+        .send()
+        .await
+        .map_err(|e| unhealthy(local_port, e))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(unhealthy(local_port, format!("HTTP {status}")));
+    }
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| unhealthy(local_port, e))?;
+    if body.get("status").and_then(|v| v.as_str()) != Some("healthy") {
+        return Err(unhealthy(local_port, body));
+    }
+    Ok(())
+}
+
+/// 启动 ssh 子进程；stdin 置空，密码 / 口令提示仍会通过终端交互
+pub fn spawn(spec: &TunnelSpec) -> Result<Child, AppError> {
+    Command::new("ssh")
+        .args(spec.ssh_args())
+        .stdin(Stdio::null())
+        .spawn()
+        .map_err(|e| i18n::error("tunnel.spawnFailed", &[("error", &e.to_string())]))
+}
+
+/// 等待隧道可用；ssh 提前退出（认证失败、端口被占用等）时立即返回错误
+pub async fn wait_until_healthy(
+    child: &mut Child,
+    local_port: u16,
+    timeout: Duration,
+) -> Result<(), AppError> {
+    let started = Instant::now();
+    loop {
+        if let Ok(Some(status)) = child.try_wait() {
+            return Err(i18n::error(
+                "tunnel.sshExited",
+                &[("status", &status.to_string())],
+            ));
+        }
+        match check_health(local_port).await {
+            Ok(()) => return Ok(()),
+            Err(e) if started.elapsed() >= timeout => return Err(e),
+            Err(_) => tokio::time::sleep(HEALTH_POLL_INTERVAL).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_forward_command() {
+        let mut spec = TunnelSpec::new("dev@build-box", DEFAULT_REMOTE_PORT).unwrap();
+        assert_eq!(
+            spec.command_line(),
+            "ssh -N -o ExitOnForwardFailure=yes -o ServerAliveInterval=30 \
+             -L 127.0.0.1:15721:127.0.0.1:15721 dev@build-box"
+        );
+
+        spec.local_port = 25721;
+        spec.ssh_port = Some(2222);
+        spec.identity = Some("/home/dev/.ssh/my key".to_string());
+        let command = spec.command_line();
+        assert!(command.contains("-L 127.0.0.1:25721:127.0.0.1:15721"));
+        assert!(command.ends_with("-p 2222 -i '/home/dev/.ssh/my key' dev@build-box"));
+        assert_eq!(spec.local_origin(), "http://127.0.0.1:25721");
+    }
+
+    #[test]
+    fn rejects_option_like_destination() {
+        assert!(TunnelSpec::new("-oProxyCommand=x", 15721).is_err());
+        assert!(TunnelSpec::new("  ", 15721).is_err());
+    }
+
+    #[tokio::test]
+    async fn health_check_fails_on_closed_port() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+        assert!(check_health(port).await.is_err());
+    }
+}
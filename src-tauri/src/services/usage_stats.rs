@@ -5,6 +5,7 @@
 use crate::database::{lock_conn, Database};
 use crate::error::AppError;
 use crate::proxy::usage::calculator::ModelPricing;
+use crate::services::provider_budget::{BudgetPeriod, BudgetUsage};
 use crate::services::sql_helpers::{
     fresh_input_sql, INPUT_TOKEN_SEMANTICS_FRESH, INPUT_TOKEN_SEMANTICS_TOTAL,
 };
@@ -98,6 +99,25 @@ pub struct ModelStats {
     pub avg_cost_per_request: String,
 }
 
+/// 同一工作负载下单个供应商的表现
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadProviderStats {
+    /// 工作负载指纹，如 `sonnet:m`
+    pub workload: String,
+    pub model_class: String,
+    pub size_bucket: String,
+    pub provider_id: String,
+    pub provider_name: String,
+    pub request_count: u64,
+    pub success_rate: f32,
+    pub avg_latency_ms: u64,
+    /// 仅统计流式请求
+    pub avg_first_token_ms: Option<u64>,
+    pub avg_cost_per_request: String,
+    pub avg_output_tokens: u64,
+}
+
 /// 请求日志过滤器
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -157,17 +177,25 @@ pub struct RequestLogDetail {
     /// 写入时实际用于计价的模型名。None = v11 前的历史行，"" = 未计价的错误行。
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pricing_model: Option<String>,
+    /// 上游重试次数（同供应商瞬时故障重试 + 故障转移）
+    pub retry_count: u32,
+    /// 是否经不受信任的供应商转发（写入时判定）
+    pub untrusted: bool,
+    /// 别名解析 / 模型映射后实际发往上游的模型（会话导入行与 v27 前无映射的行等于 model）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mapped_model: Option<String>,
 }
 
-/// 把 26 列的查询结果映射为 `RequestLogDetail`。
+/// 把 29 列的查询结果映射为 `RequestLogDetail`。
 ///
-/// 调用方的 SELECT **必须**按以下顺序返回 26 列：
+/// 调用方的 SELECT **必须**按以下顺序返回 29 列：
 /// `request_id, provider_id, provider_name, app_type, model, request_model,
 ///  cost_multiplier, input_tokens, output_tokens, cache_read_tokens,
 ///  cache_creation_tokens, input_cost_usd, output_cost_usd, cache_read_cost_usd,
 ///  cache_creation_cost_usd, total_cost_usd, is_streaming, latency_ms,
 ///  first_token_ms, duration_ms, status_code, error_message, created_at,
-///  data_source, pricing_model, input_token_semantics`
+///  data_source, pricing_model, input_token_semantics, retry_count, untrusted,
+///  mapped_model`
 ///
 /// 不需要 provider_name 时（如 backfill）SELECT `NULL AS provider_name` 占位即可。
 fn row_to_request_log_detail(row: &rusqlite::Row<'_>) -> rusqlite::Result<RequestLogDetail> {
@@ -200,6 +228,9 @@ fn row_to_request_log_detail(row: &rusqlite::Row<'_>) -> rusqlite::Result<Reques
         data_source: row.get(23)?,
         pricing_model: row.get(24)?,
         input_token_semantics: row.get::<_, i64>(25)?,
+        retry_count: row.get::<_, i64>(26)? as u32,
+        untrusted: row.get::<_, i64>(27)? != 0,
+        mapped_model: row.get(28)?,
     })
 }
 
@@ -1533,7 +1564,113 @@ impl Database {
         Ok(stats)
     }
 
+    /// 按工作负载比较供应商
+    ///
+    /// 只比较同一工作负载指纹（模型档位 + 请求体量）下的请求，且只返回至少
+    /// 两个供应商都承接过的工作负载，避免流量结构不同导致的误判。指纹在代理
+    /// 记录日志时写入，已聚合进日汇总的历史数据与会话日志不参与比较。
+    pub fn get_workload_comparison(
+        &self,
+        start_date: Option<i64>,
+        end_date: Option<i64>,
+        app_type: Option<&str>,
+    ) -> Result<Vec<WorkloadProviderStats>, AppError> {
+        let conn = lock_conn!(self.conn);
+
+        let mut conditions = vec!["l.workload IS NOT NULL".to_string()];
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        if let Some(start) = start_date {
+            conditions.push("l.created_at >= ?".to_string());
+            params.push(Box::new(start));
+        }
+        if let Some(end) = end_date {
+            conditions.push("l.created_at <= ?".to_string());
+            params.push(Box::new(end));
+        }
+        if let Some(at) = app_type {
+            conditions.push(format!("{} = ?", folded_app_type_sql("l.app_type")));
+            params.push(Box::new(at.to_string()));
+        }
+
+        let pname = provider_name_coalesce("l", "p");
+        let sql = format!(
+            "SELECT l.workload, l.provider_id, {pname} as provider_name,
+                COUNT(*) as request_count,
+                COALESCE(SUM(CASE WHEN l.status_code >= 200 AND l.status_code < 300 THEN 1 ELSE 0 END), 0),
+                COALESCE(AVG(l.latency_ms), 0),
+                AVG(CASE WHEN l.is_streaming = 1 THEN l.first_token_ms END),
+                COALESCE(AVG(CAST(l.total_cost_usd AS REAL)), 0),
+                COALESCE(AVG(l.output_tokens), 0)
+            FROM proxy_request_logs l
+            LEFT JOIN providers p ON l.provider_id = p.id AND l.app_type = p.app_type
+            WHERE {}
+            GROUP BY l.workload, l.provider_id, l.app_type
+            ORDER BY l.workload, COALESCE(AVG(l.latency_ms), 0)",
+            conditions.join(" AND ")
+        );
+
+        let mut stmt = conn.prepare(&sql)?;
+        let param_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let rows = stmt.query_map(param_refs.as_slice(), |row| {
+            let workload: String = row.get(0)?;
+            let request_count: i64 = row.get(3)?;
+            let success_count: i64 = row.get(4)?;
+            let (model_class, size_bucket) =
+                crate::proxy::usage::workload::split_fingerprint(&workload);
+            Ok(WorkloadProviderStats {
+                model_class: model_class.to_string(),
+                size_bucket: size_bucket.to_string(),
+                provider_id: row.get(1)?,
+                provider_name: row.get(2)?,
+                request_count: request_count as u64,
+                success_rate: if request_count > 0 {
+                    (success_count as f32 / request_count as f32) * 100.0
+                } else {
+                    0.0
+                },
+                avg_latency_ms: row.get::<_, f64>(5)? as u64,
+                avg_first_token_ms: row.get::<_, Option<f64>>(6)?.map(|v| v as u64),
+                avg_cost_per_request: format!("{:.6}", row.get::<_, f64>(7)?),
+                avg_output_tokens: row.get::<_, f64>(8)? as u64,
+                workload,
+            })
+        })?;
+
+        let mut stats = Vec::new();
+        for row in rows {
+            stats.push(row?);
+        }
+
+        let mut providers_per_workload: HashMap<String, usize> = HashMap::new();
+        for stat in &stats {
+            *providers_per_workload
+                .entry(stat.workload.clone())
+                .or_default() += 1;
+        }
+        stats.retain(|stat| providers_per_workload[&stat.workload] >= 2);
+
+        Ok(stats)
+    }
+
     /// 获取请求日志列表（分页）
+    /// 该供应商最近一次请求实际发往上游的模型（经模型映射后）
+    pub fn latest_upstream_model(
+        &self,
+        app_type: &str,
+        provider_id: &str,
+    ) -> Result<Option<String>, AppError> {
+        let conn = lock_conn!(self.conn);
+        conn.query_row(
+            "SELECT model FROM proxy_request_logs
+             WHERE app_type = ?1 AND provider_id = ?2 AND model != ''
+             ORDER BY created_at DESC LIMIT 1",
+            params![app_type, provider_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| AppError::Database(e.to_string()))
+    }
+
     pub fn get_request_logs(
         &self,
         filters: &LogFilters,
@@ -1604,7 +1741,8 @@ impl Database {
                     l.input_cost_usd, l.output_cost_usd, l.cache_read_cost_usd, l.cache_creation_cost_usd, l.total_cost_usd,
                     l.is_streaming, l.latency_ms, l.first_token_ms, l.duration_ms,
                     l.status_code, l.error_message, l.created_at, l.data_source, l.pricing_model,
-                    l.input_token_semantics
+                    l.input_token_semantics, l.retry_count, l.untrusted,
+                    COALESCE(l.mapped_model, l.model) AS mapped_model
              FROM proxy_request_logs l
              LEFT JOIN providers p ON l.provider_id = p.id AND l.app_type = p.app_type
              {where_clause}
@@ -1648,7 +1786,8 @@ impl Database {
                     input_cost_usd, output_cost_usd, cache_read_cost_usd, cache_creation_cost_usd, total_cost_usd,
                     is_streaming, latency_ms, first_token_ms, duration_ms,
                     status_code, error_message, created_at, l.data_source, l.pricing_model,
-                    l.input_token_semantics
+                    l.input_token_semantics, l.retry_count, l.untrusted,
+                    COALESCE(l.mapped_model, l.model) AS mapped_model
              FROM proxy_request_logs l
              LEFT JOIN providers p ON l.provider_id = p.id AND l.app_type = p.app_type
              WHERE l.request_id = ?"
@@ -1672,10 +1811,36 @@ impl Database {
         provider_id: &str,
         app_type: &str,
     ) -> Result<ProviderLimitStatus, AppError> {
+        let usage = self.provider_budget_usage(provider_id, app_type)?;
+
+        Ok(ProviderLimitStatus {
+            provider_id: provider_id.to_string(),
+            daily_usage: format!("{:.6}", usage.daily_usd),
+            daily_limit: usage.daily_usd_limit.map(|l| format!("{l:.2}")),
+            daily_exceeded: usage.period_exceeded(BudgetPeriod::Daily),
+            monthly_usage: format!("{:.6}", usage.monthly_usd),
+            monthly_limit: usage.monthly_usd_limit.map(|l| format!("{l:.2}")),
+            monthly_exceeded: usage.period_exceeded(BudgetPeriod::Monthly),
+            daily_tokens: usage.daily_tokens,
+            daily_token_limit: usage.daily_token_limit,
+            monthly_tokens: usage.monthly_tokens,
+            monthly_token_limit: usage.monthly_token_limit,
+            hard_limit: usage.hard_limit,
+        })
+    }
+
+    /// 读取 Provider 预算设置及今日/本月消费（美元与 token）
+    ///
+    /// token 口径为输入 + 输出，不含缓存读写。
+    pub fn provider_budget_usage(
+        &self,
+        provider_id: &str,
+        app_type: &str,
+    ) -> Result<BudgetUsage, AppError> {
         let conn = lock_conn!(self.conn);
 
         // 获取 provider 的限额设置
-        let (limit_daily, limit_monthly) = conn
+        let meta = conn
             .query_row(
                 "SELECT meta FROM providers WHERE id = ? AND app_type = ?",
                 params![provider_id, app_type],
@@ -1686,72 +1851,65 @@ impl Database {
             )
             .ok()
             .and_then(|meta_str| serde_json::from_str::<serde_json::Value>(&meta_str).ok())
-            .map(|meta| {
-                let daily = meta
-                    .get("limitDailyUsd")
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| s.parse::<f64>().ok());
-                let monthly = meta
-                    .get("limitMonthlyUsd")
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| s.parse::<f64>().ok());
-                (daily, monthly)
-            })
-            .unwrap_or((None, None));
+            .unwrap_or_default();
+        let usd_limit = |key: &str| {
+            meta.get(key)
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse::<f64>().ok())
+        };
+        let token_limit = |key: &str| meta.get(key).and_then(|v| v.as_u64());
 
         // 计算今日使用量 (detail logs + rollup)
-        let daily_usage: f64 = conn
+        let (daily_usd, daily_tokens): (f64, i64) = conn
             .query_row(
-                "SELECT COALESCE(SUM(cost), 0) FROM (
-                    SELECT CAST(total_cost_usd AS REAL) as cost
+                "SELECT COALESCE(SUM(cost), 0), COALESCE(SUM(tokens), 0) FROM (
+                    SELECT CAST(total_cost_usd AS REAL) as cost, input_tokens + output_tokens as tokens
                     FROM proxy_request_logs
                     WHERE provider_id = ? AND app_type = ?
                       AND date(datetime(created_at, 'unixepoch', 'localtime')) = date('now', 'localtime')
                     UNION ALL
-                    SELECT CAST(total_cost_usd AS REAL)
+                    SELECT CAST(total_cost_usd AS REAL), input_tokens + output_tokens
                     FROM usage_daily_rollups
                     WHERE provider_id = ? AND app_type = ?
                       AND date = date('now', 'localtime')
                 )",
                 params![provider_id, app_type, provider_id, app_type],
-                |row| row.get(0),
+                |row| Ok((row.get(0)?, row.get(1)?)),
             )
-            .unwrap_or(0.0);
+            .unwrap_or((0.0, 0));
 
         // 计算本月使用量 (detail logs + rollup)
-        let monthly_usage: f64 = conn
+        let (monthly_usd, monthly_tokens): (f64, i64) = conn
             .query_row(
-                "SELECT COALESCE(SUM(cost), 0) FROM (
-                    SELECT CAST(total_cost_usd AS REAL) as cost
+                "SELECT COALESCE(SUM(cost), 0), COALESCE(SUM(tokens), 0) FROM (
+                    SELECT CAST(total_cost_usd AS REAL) as cost, input_tokens + output_tokens as tokens
                     FROM proxy_request_logs
                     WHERE provider_id = ? AND app_type = ?
                       AND strftime('%Y-%m', datetime(created_at, 'unixepoch', 'localtime')) = strftime('%Y-%m', 'now', 'localtime')
                     UNION ALL
-                    SELECT CAST(total_cost_usd AS REAL)
+                    SELECT CAST(total_cost_usd AS REAL), input_tokens + output_tokens
                     FROM usage_daily_rollups
                     WHERE provider_id = ? AND app_type = ?
                       AND strftime('%Y-%m', date) = strftime('%Y-%m', 'now', 'localtime')
                 )",
                 params![provider_id, app_type, provider_id, app_type],
-                |row| row.get(0),
+                |row| Ok((row.get(0)?, row.get(1)?)),
             )
-            .unwrap_or(0.0);
-
-        let daily_exceeded = limit_daily
-            .map(|limit| daily_usage >= limit)
-            .unwrap_or(false);
-        let monthly_exceeded = limit_monthly
-            .map(|limit| monthly_usage >= limit)
-            .unwrap_or(false);
-
-        Ok(ProviderLimitStatus {
-            provider_id: provider_id.to_string(),
-            daily_usage: format!("{daily_usage:.6}"),
-            daily_limit: limit_daily.map(|l| format!("{l:.2}")),
-            daily_exceeded,
-            monthly_usage: format!("{monthly_usage:.6}"),
-            monthly_limit: limit_monthly.map(|l| format!("{l:.2}")),
-            monthly_exceeded,
+            .unwrap_or((0.0, 0));
+
+        Ok(BudgetUsage {
+            daily_usd,
+            daily_usd_limit: usd_limit("limitDailyUsd"),
+            monthly_usd,
+            monthly_usd_limit: usd_limit("limitMonthlyUsd"),
+            daily_tokens: daily_tokens.max(0) as u64,
+            daily_token_limit: token_limit("limitDailyTokens"),
+            monthly_tokens: monthly_tokens.max(0) as u64,
+            monthly_token_limit: token_limit("limitMonthlyTokens"),
+            hard_limit: meta
+                .get("budgetHardLimit")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
         })
     }
 }
@@ -1767,6 +1925,12 @@ pub struct ProviderLimitStatus {
     pub monthly_usage: String,
     pub monthly_limit: Option<String>,
     pub monthly_exceeded: bool,
+    pub daily_tokens: u64,
+    pub daily_token_limit: Option<u64>,
+    pub monthly_tokens: u64,
+    pub monthly_token_limit: Option<u64>,
+    /// 达到上限后代理是否停止路由到该供应商
+    pub hard_limit: bool,
 }
 
 #[derive(Clone)]
@@ -1804,7 +1968,8 @@ impl Database {
                         input_cost_usd, output_cost_usd, cache_read_cost_usd,
                         cache_creation_cost_usd, total_cost_usd, is_streaming, latency_ms,
                         first_token_ms, duration_ms, status_code, error_message, created_at,
-                        data_source, pricing_model, input_token_semantics
+                        data_source, pricing_model, input_token_semantics, retry_count,
+                        untrusted, COALESCE(mapped_model, model) AS mapped_model
              FROM proxy_request_logs
              WHERE CAST(total_cost_usd AS REAL) <= 0
                AND (input_tokens > 0 OR output_tokens > 0
@@ -2128,7 +2293,8 @@ fn query_model_pricing_prefix(
 }
 
 fn model_pricing_candidates(model_id: &str) -> Vec<String> {
-    let cleaned = clean_model_id_for_pricing(model_id);
+    // 日志中记录的可能是客户端请求的模型别名，按别名表解析后再查价
+    let cleaned = clean_model_id_for_pricing(&crate::services::model_alias::resolve(model_id));
     if is_placeholder_pricing_model(&cleaned) {
         return Vec::new();
     }
@@ -3759,6 +3925,49 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_get_workload_comparison_only_keeps_shared_workloads() -> Result<(), AppError> {
+        let db = Database::memory()?;
+
+        {
+            let conn = lock_conn!(db.conn);
+            let rows = [
+                ("a1", "p1", "sonnet:m", 1000, "0.02", 200),
+                ("a2", "p1", "sonnet:m", 3000, "0.04", 500),
+                ("b1", "p2", "sonnet:m", 800, "0.01", 200),
+                // 只有 p1 跑过的工作负载不参与比较
+                ("a3", "p1", "haiku:xs", 100, "0.001", 200),
+            ];
+            for (request_id, provider_id, workload, latency, cost, status) in rows {
+                conn.execute(
+                    "INSERT INTO proxy_request_logs (
+                        request_id, provider_id, app_type, model,
+                        input_tokens, output_tokens, total_cost_usd,
+                        latency_ms, status_code, created_at, workload
+                    ) VALUES (?, ?, 'claude', 'claude-sonnet-4-5', 10000, 100, ?, ?, ?, 1000, ?)",
+                    params![request_id, provider_id, cost, latency, status, workload],
+                )?;
+            }
+        }
+
+        let stats = db.get_workload_comparison(None, None, Some("claude"))?;
+        assert_eq!(stats.len(), 2);
+        assert!(stats.iter().all(|s| s.workload == "sonnet:m"));
+        assert_eq!(
+            (stats[0].model_class.as_str(), stats[0].size_bucket.as_str()),
+            ("sonnet", "m")
+        );
+        // 按平均延迟升序
+        assert_eq!(stats[0].provider_id, "p2");
+        assert_eq!(stats[1].provider_id, "p1");
+        assert_eq!(stats[1].request_count, 2);
+        assert_eq!(stats[1].avg_latency_ms, 2000);
+        assert_eq!(stats[1].success_rate, 50.0);
+        assert_eq!(stats[1].avg_cost_per_request, "0.030000");
+
+        Ok(())
+    }
+
     #[test]
     fn test_get_provider_stats_labels_opencode_session_provider() -> Result<(), AppError> {
         let db = Database::memory()?;
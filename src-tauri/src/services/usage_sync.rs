@@ -0,0 +1,466 @@
+//! Differential usage sync.
+//!
+//! 用量明细不进 `db.sql`，而是以 gzip 压缩的 JSONL 文件单独上传：每次上传只追加
+//! 本机水位（明细表的插入序号 rowid）之后的新行（增量），增量累积到 [`COMPACT_AFTER_DELTAS`] 个时由上传方
+//! 合并成新的基线（压缩），同时丢弃超出明细保留期的行。下载时按 `request_id`
+//! 去重合并；保留期之外的统计由各设备自己的日汇总承担，只要设备在保留期内
+//! 同步过一次，长期统计就与其他设备一致。
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Write};
+
+use chrono::Utc;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::Value;
+
+use crate::database::{detail_retention_cutoff, Database, UsageRow};
+use crate::error::AppError;
+use crate::i18n;
+
+use super::sync_backend::SyncBackend;
+use super::sync_protocol::{
+    sha256_hex, validate_artifact_size_limit, verify_artifact, ArtifactMeta, SyncManifest,
+    UsageLedger, MAX_SYNC_ARTIFACT_BYTES,
+};
+
+/// 增量文件达到该数量时，下一次上传合并为新基线
+pub(crate) const COMPACT_AFTER_DELTAS: usize = 16;
+
+/// 一次上传对用量账本的改动
+#[derive(Debug, Default)]
+pub(crate) struct UsagePlan {
+    /// 上传后写入 manifest 的账本
+    pub ledger: UsageLedger,
+    /// 账本中全部文件的元数据（沿用的 + 新增的）
+    pub artifacts: BTreeMap<String, ArtifactMeta>,
+    /// 需要在 manifest 之前上传的新文件
+    pub uploads: Vec<(String, Vec<u8>)>,
+    /// manifest 上传成功后可删除的旧文件（被压缩进基线）
+    pub obsolete: Vec<String>,
+    /// 上传成功后保存的新水位（rowid）
+    pub watermark: Option<i64>,
+}
+
+fn created_at(row: &UsageRow) -> Option<i64> {
+    row.get("created_at").and_then(Value::as_i64)
+}
+
+pub(crate) fn encode(rows: &[UsageRow]) -> Result<Vec<u8>, AppError> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    for row in rows {
+        serde_json::to_writer(&mut encoder, row)
+            .map_err(|e| AppError::JsonSerialize { source: e })?;
+        encoder
+            .write_all(b"\n")
+            .map_err(|e| AppError::Message(e.to_string()))?;
+    }
+    encoder
+        .finish()
+        .map_err(|e| AppError::Message(e.to_string()))
+}
+
+pub(crate) fn decode(name: &str, bytes: &[u8]) -> Result<Vec<UsageRow>, AppError> {
+    let mut text = String::new();
+    GzDecoder::new(bytes)
+        .take(MAX_SYNC_ARTIFACT_BYTES)
+        .read_to_string(&mut text)
+        .map_err(|e| {
+            i18n::error(
+                "sync.usage.decodeFailed",
+                &[("name", name), ("error", &e.to_string())],
+            )
+        })?;
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|e| AppError::Json {
+                path: name.to_string(),
+                source: e,
+            })
+        })
+        .collect()
+}
+
+fn file_name(kind: &str, bytes: &[u8]) -> String {
+    let hash = sha256_hex(bytes);
+    format!(
+        "usage-{kind}-{}-{}.jsonl.gz",
+        Utc::now().format("%Y%m%dT%H%M%S"),
+        hash.get(..8).unwrap_or(&hash)
+    )
+}
+
+fn meta_of(bytes: &[u8]) -> ArtifactMeta {
+    ArtifactMeta {
+        sha256: sha256_hex(bytes),
+        size: bytes.len() as u64,
+    }
+}
+
+/// 远端 manifest 中仍然有效的账本；缺少元数据的文件视为丢失并剔除
+fn carried_ledger(
+    previous: Option<&SyncManifest>,
+) -> (UsageLedger, BTreeMap<String, ArtifactMeta>) {
+    let Some(manifest) = previous else {
+        return Default::default();
+    };
+    let Some(ledger) = manifest.usage.as_ref() else {
+        return Default::default();
+    };
+    let known = |name: &String| manifest.artifacts.contains_key(name);
+    let carried = UsageLedger {
+        base: ledger.base.clone().filter(known),
+        deltas: ledger
+            .deltas
+            .iter()
+            .filter(|n| known(*n))
+            .cloned()
+            .collect(),
+    };
+    let artifacts = carried
+        .files()
+        .filter_map(|name| Some((name.clone(), manifest.artifacts.get(name)?.clone())))
+        .collect();
+    (carried, artifacts)
+}
+
+async fn fetch(
+    backend: &impl SyncBackend,
+    name: &str,
+    meta: &ArtifactMeta,
+) -> Result<Vec<UsageRow>, AppError> {
+    validate_artifact_size_limit(name, meta.size)?;
+    let (bytes, _) = backend
+        .get(name, MAX_SYNC_ARTIFACT_BYTES as usize)
+        .await?
+        .ok_or_else(|| i18n::error("sync.usage.missingArtifact", &[("name", name)]))?;
+    verify_artifact(&bytes, name, meta)?;
+    decode(name, &bytes)
+}
+
+/// 合并多批明细：按 `request_id` 去重、丢弃保留期之前的行、按时间排序
+fn compact(batches: Vec<Vec<UsageRow>>, not_before: i64) -> Vec<UsageRow> {
+    let mut by_id: HashMap<String, UsageRow> = HashMap::new();
+    for row in batches.into_iter().flatten() {
+        let Some(id) = row.get("request_id").and_then(Value::as_str) else {
+            continue;
+        };
+        if created_at(&row).is_some_and(|ts| ts >= not_before) {
+            by_id.entry(id.to_string()).or_insert(row);
+        }
+    }
+    let mut rows: Vec<UsageRow> = by_id.into_values().collect();
+    rows.sort_by(|a, b| {
+        created_at(a).cmp(&created_at(b)).then_with(|| {
+            a.get("request_id")
+                .and_then(Value::as_str)
+                .cmp(&b.get("request_id").and_then(Value::as_str))
+        })
+    });
+    rows
+}
+
+/// 计算本次上传的账本改动
+///
+/// `previous` 为远端当前（且与本机兼容）的 manifest；远端没有账本时（首次上传、
+/// 被清空或被旧版本覆盖）忽略水位，从保留期起点全量上传一个基线。
+pub(crate) async fn plan_upload(
+    db: &Database,
+    backend: &impl SyncBackend,
+    previous: Option<&SyncManifest>,
+    watermark: Option<i64>,
+) -> Result<UsagePlan, AppError> {
+    let cutoff = detail_retention_cutoff()?;
+    let (mut ledger, mut artifacts) = carried_ledger(previous);
+    let after = match watermark {
+        Some(mark) if !ledger.is_empty() => mark,
+        _ => 0,
+    };
+    let export = db.usage_rows_after(after, cutoff)?;
+    let rows = export.rows;
+
+    let mut plan = UsagePlan {
+        watermark: Some(export.high_water),
+        ..Default::default()
+    };
+    if rows.is_empty() {
+        plan.ledger = ledger;
+        plan.artifacts = artifacts;
+        return Ok(plan);
+    }
+
+    if ledger.is_empty() || ledger.deltas.len() >= COMPACT_AFTER_DELTAS {
+        let mut batches = Vec::new();
+        for name in ledger.files() {
+            batches.push(fetch(backend, name, &artifacts[name]).await?);
+        }
+        batches.push(rows);
+        let bytes = encode(&compact(batches, cutoff))?;
+        let name = file_name("base", &bytes);
+        plan.obsolete = ledger.files().cloned().collect();
+        plan.artifacts.insert(name.clone(), meta_of(&bytes));
+        plan.ledger = UsageLedger {
+            base: Some(name.clone()),
+            deltas: Vec::new(),
+        };
+        plan.uploads.push((name, bytes));
+    } else {
+        let bytes = encode(&rows)?;
+        let name = file_name("delta", &bytes);
+        artifacts.insert(name.clone(), meta_of(&bytes));
+        ledger.deltas.push(name.clone());
+        plan.ledger = ledger;
+        plan.artifacts = artifacts;
+        plan.uploads.push((name, bytes));
+    }
+    Ok(plan)
+}
+
+/// 上传账本中的新文件（在 manifest 之前调用）
+pub(crate) async fn upload_files(
+    backend: &impl SyncBackend,
+    plan: &mut UsagePlan,
+) -> Result<(), AppError> {
+    for (name, bytes) in std::mem::take(&mut plan.uploads) {
+        backend.put(&name, bytes, "application/gzip").await?;
+    }
+    Ok(())
+}
+
+/// 删除已被压缩进基线的旧文件（在 manifest 之后调用，失败仅记录日志）
+pub(crate) async fn delete_obsolete(backend: &impl SyncBackend, plan: &UsagePlan) {
+    for name in &plan.obsolete {
+        if let Err(e) = backend.delete(name).await {
+            log::debug!("[UsageSync] Failed to delete compacted usage file {name}: {e}");
+        }
+    }
+}
+
+/// 把远端账本合并进本地，返回下载后应保存的水位
+///
+/// 合并前本机没有待上传的新行（水位正好是合并前的最大 rowid）时，水位推进到
+/// 合并后的最大 rowid，避免把刚下载的明细再作为增量传回去；否则保留原水位，
+/// 等下次上传一并补齐。
+pub(crate) async fn pull(
+    db: &Database,
+    backend: &impl SyncBackend,
+    manifest: &SyncManifest,
+    watermark: Option<i64>,
+) -> Result<Option<i64>, AppError> {
+    let Some(ledger) = manifest.usage.as_ref() else {
+        return Ok(watermark);
+    };
+    let cutoff = detail_retention_cutoff()?;
+
+    let mut rows = Vec::new();
+    for name in ledger.files() {
+        let meta = manifest
+            .artifacts
+            .get(name)
+            .ok_or_else(|| i18n::error("sync.usage.missingMeta", &[("name", name)]))?;
+        rows.extend(fetch(backend, name, meta).await?);
+    }
+    // 一次合并：检查待上传行与写入在同一事务内，期间新记录的请求不会被水位跳过
+    let merge = db.merge_usage_rows(&rows, cutoff)?;
+    log::info!(
+        "[UsageSync] Merged {} usage rows from remote",
+        merge.inserted
+    );
+
+    Ok(if watermark == Some(merge.high_water_before) {
+        Some(merge.high_water_after)
+    } else {
+        watermark
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::insert_test_log;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryBackend {
+        blobs: Mutex<HashMap<String, Vec<u8>>>,
+    }
+
+    impl SyncBackend for MemoryBackend {
+        fn location(&self) -> String {
+            "memory".to_string()
+        }
+
+        async fn prepare(&self) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        async fn put(&self, name: &str, bytes: Vec<u8>, _: &str) -> Result<(), AppError> {
+            self.blobs.lock().unwrap().insert(name.to_string(), bytes);
+            Ok(())
+        }
+
+        async fn get(
+            &self,
+            name: &str,
+            _: usize,
+        ) -> Result<Option<(Vec<u8>, Option<String>)>, AppError> {
+            Ok(self
+                .blobs
+                .lock()
+                .unwrap()
+                .get(name)
+                .map(|b| (b.clone(), None)))
+        }
+
+        async fn head_etag(&self, _: &str) -> Result<Option<String>, AppError> {
+            Ok(None)
+        }
+
+        async fn list(&self) -> Result<Vec<String>, AppError> {
+            Ok(self.blobs.lock().unwrap().keys().cloned().collect())
+        }
+
+        async fn delete(&self, name: &str) -> Result<(), AppError> {
+            self.blobs.lock().unwrap().remove(name);
+            Ok(())
+        }
+    }
+
+    /// 模拟一次上传：文件先行、manifest 最后、再清理旧文件
+    async fn upload(
+        db: &Database,
+        backend: &MemoryBackend,
+        previous: Option<&SyncManifest>,
+        watermark: Option<i64>,
+    ) -> (SyncManifest, Option<i64>) {
+        let mut plan = plan_upload(db, backend, previous, watermark).await.unwrap();
+        upload_files(backend, &mut plan).await.unwrap();
+        delete_obsolete(backend, &plan).await;
+        let manifest = SyncManifest {
+            format: String::new(),
+            version: 0,
+            db_compat_version: None,
+            device_name: String::new(),
+            created_at: String::new(),
+            artifacts: plan.artifacts.clone(),
+            snapshot_id: String::new(),
+            usage: Some(plan.ledger.clone()).filter(|l| !l.is_empty()),
+        };
+        (manifest, plan.watermark)
+    }
+
+    #[test]
+    fn encode_round_trips_rows() {
+        let db = Database::memory().unwrap();
+        insert_test_log(&db, "req-1", 1_000);
+        let rows = db.usage_rows_after(0, 0).unwrap().rows;
+        let bytes = encode(&rows).unwrap();
+        assert_eq!(decode("usage.jsonl.gz", &bytes).unwrap(), rows);
+        assert!(decode("usage.jsonl.gz", b"not gzip").is_err());
+    }
+
+    #[tokio::test]
+    async fn uploads_only_new_rows_as_deltas() {
+        let now = Utc::now().timestamp();
+        let db = Database::memory().unwrap();
+        let backend = MemoryBackend::default();
+        insert_test_log(&db, "req-1", now - 3_600);
+        insert_test_log(&db, "req-2", now - 1_800);
+
+        let (manifest, watermark) = upload(&db, &backend, None, None).await;
+        let ledger = manifest.usage.clone().unwrap();
+        assert!(ledger.base.is_some() && ledger.deltas.is_empty());
+        assert_eq!(watermark, Some(2));
+
+        // 没有新行时账本不变，也不产生新文件
+        let (same, _) = upload(&db, &backend, Some(&manifest), watermark).await;
+        assert_eq!(same.usage, manifest.usage);
+
+        insert_test_log(&db, "req-3", now - 60);
+        let (manifest, _) = upload(&db, &backend, Some(&manifest), watermark).await;
+        let ledger = manifest.usage.clone().unwrap();
+        assert_eq!(ledger.deltas.len(), 1);
+        let delta = &ledger.deltas[0];
+        let rows = decode(delta, &backend.blobs.lock().unwrap()[delta]).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["request_id"], "req-3");
+    }
+
+    #[tokio::test]
+    async fn uploads_rows_imported_with_timestamps_older_than_last_sync() {
+        let now = Utc::now().timestamp();
+        let db = Database::memory().unwrap();
+        let backend = MemoryBackend::default();
+        insert_test_log(&db, "proxy-1", now - 60);
+        let (manifest, watermark) = upload(&db, &backend, None, None).await;
+
+        // 会话日志导入的行带原始消息时间，早于上次同步的最新行
+        insert_test_log(&db, "session-1", now - 7_200);
+        let (manifest, _) = upload(&db, &backend, Some(&manifest), watermark).await;
+        let ledger = manifest.usage.clone().unwrap();
+        assert_eq!(ledger.deltas.len(), 1);
+        let delta = &ledger.deltas[0];
+        let rows = decode(delta, &backend.blobs.lock().unwrap()[delta]).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["request_id"], "session-1");
+    }
+
+    #[tokio::test]
+    async fn compacts_deltas_into_a_new_base() {
+        let now = Utc::now().timestamp();
+        let db = Database::memory().unwrap();
+        let backend = MemoryBackend::default();
+        insert_test_log(&db, "req-0", now - 10_000);
+        let (mut manifest, mut watermark) = upload(&db, &backend, None, None).await;
+        for i in 1..=COMPACT_AFTER_DELTAS {
+            insert_test_log(&db, &format!("req-{i}"), now - 10_000 + i as i64);
+            (manifest, watermark) = upload(&db, &backend, Some(&manifest), watermark).await;
+        }
+        assert_eq!(
+            manifest.usage.as_ref().unwrap().deltas.len(),
+            COMPACT_AFTER_DELTAS
+        );
+
+        insert_test_log(&db, "req-last", now - 100);
+        let (manifest, _) = upload(&db, &backend, Some(&manifest), watermark).await;
+        let ledger = manifest.usage.clone().unwrap();
+        assert!(ledger.deltas.is_empty());
+        let base = ledger.base.unwrap();
+        // 旧文件全部清理，只剩新基线
+        assert_eq!(backend.list().await.unwrap(), vec![base.clone()]);
+        let rows = decode(&base, &backend.blobs.lock().unwrap()[&base]).unwrap();
+        assert_eq!(rows.len(), COMPACT_AFTER_DELTAS + 2);
+    }
+
+    #[tokio::test]
+    async fn pull_merges_remote_rows_and_advances_watermark() {
+        let now = Utc::now().timestamp();
+        let backend = MemoryBackend::default();
+        let laptop = Database::memory().unwrap();
+        insert_test_log(&laptop, "laptop-1", now - 600);
+        let (manifest, _) = upload(&laptop, &backend, None, None).await;
+
+        // 本机没有待上传的行：合并后水位推进到最新行，下载的明细不会再传回去
+        let desktop = Database::memory().unwrap();
+        insert_test_log(&desktop, "desktop-1", now - 900);
+        let watermark = pull(&desktop, &backend, &manifest, Some(1)).await.unwrap();
+        assert_eq!(watermark, Some(2));
+        assert_eq!(desktop.usage_rows_after(0, 0).unwrap().rows.len(), 2);
+        assert_eq!(
+            desktop
+                .merge_usage_rows(&laptop.usage_rows_after(0, 0).unwrap().rows, 0)
+                .unwrap()
+                .inserted,
+            0
+        );
+
+        // 有尚未上传的行：保留原水位，下次上传一并补齐
+        let phone = Database::memory().unwrap();
+        insert_test_log(&phone, "phone-1", now - 300);
+        let watermark = pull(&phone, &backend, &manifest, Some(0)).await.unwrap();
+        assert_eq!(watermark, Some(0));
+        assert_eq!(pull(&phone, &backend, &manifest, None).await.unwrap(), None);
+    }
+}
@@ -1,6 +1,6 @@
 //! WebDAV HTTP transport layer.
 //!
-//! Low-level HTTP primitives for WebDAV operations (PUT, GET, HEAD, MKCOL, PROPFIND).
+//! Low-level HTTP primitives for WebDAV operations (PUT, GET, HEAD, DELETE, MKCOL, PROPFIND).
 //! The sync protocol logic lives in [`super::webdav_sync`].
 
 use reqwest::{Method, RequestBuilder, StatusCode, Url};
@@ -131,7 +131,7 @@ fn webdav_transport_error(
 /// Test WebDAV connectivity via PROPFIND Depth=0 on the base URL.
 pub async fn test_connection(base_url: &str, auth: &WebDavAuth) -> Result<(), AppError> {
     let url = parse_base_url(base_url)?;
-    let client = http_client::get();
+    let client = http_client::get_auxiliary("webdav sync").map_err(AppError::Message)?;
 
     let resp = apply_auth(
         client
@@ -170,7 +170,7 @@ pub async fn ensure_remote_directories(
     if segments.is_empty() {
         return Ok(());
     }
-    let client = http_client::get();
+    let client = http_client::get_auxiliary("webdav sync").map_err(AppError::Message)?;
 
     for depth in 1..=segments.len() {
         let prefix = &segments[..depth];
@@ -228,7 +228,7 @@ pub async fn put_bytes(
     bytes: Vec<u8>,
     content_type: &str,
 ) -> Result<(), AppError> {
-    let client = http_client::get();
+    let client = http_client::get_auxiliary("webdav sync").map_err(AppError::Message)?;
     let resp = apply_auth(
         client
             .put(url)
@@ -255,7 +255,7 @@ pub async fn get_bytes(
     auth: &WebDavAuth,
     max_bytes: usize,
 ) -> Result<Option<(Vec<u8>, Option<String>)>, AppError> {
-    let client = http_client::get();
+    let client = http_client::get_auxiliary("webdav sync").map_err(AppError::Message)?;
     let resp = apply_auth(
         client
             .get(url)
@@ -299,7 +299,7 @@ pub async fn get_bytes(
 
 /// HEAD request to retrieve the ETag. Returns `None` on 404.
 pub async fn head_etag(url: &str, auth: &WebDavAuth) -> Result<Option<String>, AppError> {
-    let client = http_client::get();
+    let client = http_client::get_auxiliary("webdav sync").map_err(AppError::Message)?;
     let resp = apply_auth(
         client
             .head(url)
@@ -325,6 +325,115 @@ pub async fn head_etag(url: &str, auth: &WebDavAuth) -> Result<Option<String>, A
         .map(|s| s.to_string()))
 }
 
+/// DELETE a remote resource. A missing resource (404) counts as deleted.
+pub async fn delete(url: &str, auth: &WebDavAuth) -> Result<(), AppError> {
+    let client = http_client::get_auxiliary("webdav sync").map_err(AppError::Message)?;
+    let resp = apply_auth(
+        client
+            .delete(url)
+            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+        auth,
+    )
+    .send()
+    .await
+    .map_err(|e| {
+        webdav_transport_error(
+            "webdav.delete_failed",
+            "DELETE 请求",
+            "DELETE request",
+            url,
+            &e,
+        )
+    })?;
+
+    if resp.status().is_success() || resp.status() == StatusCode::NOT_FOUND {
+        return Ok(());
+    }
+    Err(webdav_status_error("DELETE", resp.status(), url))
+}
+
+/// PROPFIND Depth=1 on a collection and return the names of its direct
+/// (non-collection) members. Returns an empty list when the collection is missing.
+pub async fn list_files(dir_url: &str, auth: &WebDavAuth) -> Result<Vec<String>, AppError> {
+    let dir_url = if dir_url.ends_with('/') {
+        dir_url.to_string()
+    } else {
+        format!("{dir_url}/")
+    };
+    let client = http_client::get_auxiliary("webdav sync").map_err(AppError::Message)?;
+    let resp = apply_auth(
+        client
+            .request(method_propfind(), &dir_url)
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml")
+            .body(
+                r#"<?xml version="1.0" encoding="utf-8"?><d:propfind xmlns:d="DAV:"><d:prop><d:resourcetype/></d:prop></d:propfind>"#,
+            )
+            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS)),
+        auth,
+    )
+    .send()
+    .await
+    .map_err(|e| {
+        webdav_transport_error(
+            "webdav.propfind_failed",
+            "PROPFIND 请求",
+            "PROPFIND request",
+            &dir_url,
+            &e,
+        )
+    })?;
+
+    if resp.status() == StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    if !(resp.status().is_success() || resp.status() == StatusCode::MULTI_STATUS) {
+        return Err(webdav_status_error("PROPFIND", resp.status(), &dir_url));
+    }
+    let body = resp.text().await.map_err(|e| {
+        AppError::localized(
+            "webdav.response_read_failed",
+            format!("读取 WebDAV 响应失败: {e}"),
+            format!("Failed to read WebDAV response: {e}"),
+        )
+    })?;
+    Ok(parse_propfind_file_names(&body))
+}
+
+/// Extract member file names from a PROPFIND multistatus body.
+///
+/// Collections (trailing `/` in href) — including the listed directory itself —
+/// are skipped. Hrefs are percent-decoded.
+fn parse_propfind_file_names(body: &str) -> Vec<String> {
+    super::sync_backend::xml_element_texts(body, "href")
+        .into_iter()
+        .filter(|href| !href.ends_with('/'))
+        .filter_map(|href| {
+            let name = href.rsplit('/').next()?;
+            let decoded = percent_decode(name);
+            (!decoded.is_empty()).then_some(decoded)
+        })
+        .collect()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Ok(byte) = u8::from_str_radix(&input[i + 1..i + 3], 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
 // ─── Internal helpers ────────────────────────────────────────
 
 /// PROPFIND Depth=0 to check if a remote resource exists.
@@ -466,6 +575,21 @@ mod tests {
         assert!(!url.contains("//cc"), "should not have double-slash");
     }
 
+    #[test]
+    fn parse_propfind_file_names_skips_collections_and_decodes() {
+        let body = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response><d:href>/dav/cc-switch-sync/backups/</d:href></d:response>
+  <d:response><d:href>/dav/cc-switch-sync/backups/db%20backup.sql</d:href></d:response>
+  <d:response><d:href>/dav/cc-switch-sync/backups/nested/</d:href></d:response>
+  <D:response xmlns:D="DAV:"><D:href>/dav/cc-switch-sync/backups/a&amp;b.zip</D:href></D:response>
+</d:multistatus>"#;
+        assert_eq!(
+            parse_propfind_file_names(body),
+            vec!["db backup.sql".to_string(), "a&b.zip".to_string()]
+        );
+    }
+
     #[test]
     fn is_jianguoyun_detects_correctly() {
         assert!(is_jianguoyun("https://dav.jianguoyun.com/dav"));
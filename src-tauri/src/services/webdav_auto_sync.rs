@@ -9,8 +9,9 @@ use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
 
 use crate::error::AppError;
+use crate::services::notifier::{localized_text, Notification, NotifierService};
 use crate::services::webdav_sync as webdav_sync_service;
-use crate::settings::{self, WebDavSyncSettings};
+use crate::settings::{self, NotificationEvent, WebDavSyncSettings};
 
 const AUTO_SYNC_DEBOUNCE_MS: u64 = 1000;
 pub(crate) const MAX_AUTO_SYNC_WAIT_MS: u64 = 10_000;
@@ -48,6 +49,7 @@ pub fn should_trigger_for_table(table: &str) -> bool {
             | "provider_endpoints"
             | "mcp_servers"
             | "prompts"
+            | "prompt_versions"
             | "skills"
             | "skill_repos"
             | "settings"
@@ -103,6 +105,16 @@ fn emit_auto_sync_status_updated(app: &AppHandle, status: &str, error: Option<&s
     }
 }
 
+fn notify_auto_sync_failed(error: &AppError) {
+    let notification = Notification::new(
+        NotificationEvent::SyncFailed,
+        localized_text("WebDAV 自动同步失败", "WebDAV auto sync failed"),
+        error.to_string(),
+    )
+    .with_dedup_key("webdav");
+    NotifierService::dispatch(notification);
+}
+
 async fn run_auto_sync_upload(
     db: &crate::database::Database,
     app: &AppHandle,
@@ -129,6 +141,7 @@ async fn run_auto_sync_upload(
         }
         Err(err) => {
             persist_auto_sync_error(&mut sync_settings, &err);
+            notify_auto_sync_failed(&err);
             emit_auto_sync_status_updated(app, "error", Some(&err.to_string()));
             Err(err)
         }
@@ -11,12 +11,11 @@ use chrono::Utc;
 use serde_json::Value;
 
 use crate::error::AppError;
-use crate::services::webdav::{
-    auth_from_credentials, build_remote_url, ensure_remote_directories, get_bytes, head_etag,
-    path_segments, put_bytes, test_connection, WebDavAuth,
-};
+use crate::progress_events::ProgressReporter;
+use crate::services::webdav::{auth_from_credentials, path_segments, test_connection};
 use crate::settings::{update_webdav_sync_status, WebDavSyncSettings, WebDavSyncStatus};
 
+use super::sync_backend::{delete_all, Encrypted, SyncBackend, WebDavBackend};
 use super::sync_protocol::{
     apply_snapshot, build_local_snapshot, effective_db_compat_version, localized,
     persist_sync_success_best_effort, sha256_hex, validate_artifact_size_limit,
@@ -24,6 +23,7 @@ use super::sync_protocol::{
     DB_COMPAT_VERSION, MAX_MANIFEST_BYTES, MAX_SYNC_ARTIFACT_BYTES, PROTOCOL_VERSION,
     REMOTE_DB_SQL, REMOTE_MANIFEST, REMOTE_SKILLS_ZIP,
 };
+use super::usage_sync;
 
 pub(crate) mod archive;
 
@@ -53,11 +53,9 @@ struct RemoteSnapshot {
 /// Check WebDAV connectivity and ensure remote directory structure.
 pub async fn check_connection(settings: &WebDavSyncSettings) -> Result<(), AppError> {
     settings.validate()?;
-    let auth = auth_for(settings);
-    test_connection(&settings.base_url, &auth).await?;
-    let dir_segs = remote_dir_segments(settings, RemoteLayout::Current);
-    ensure_remote_directories(&settings.base_url, &dir_segs, &auth).await?;
-    Ok(())
+    let backend = backend_for(settings, RemoteLayout::Current);
+    test_connection(&settings.base_url, &backend.inner().auth).await?;
+    backend.prepare().await
 }
 
 /// Upload local snapshot (db + skills) to remote.
@@ -66,30 +64,55 @@ pub async fn upload(
     settings: &mut WebDavSyncSettings,
 ) -> Result<Value, AppError> {
     settings.validate()?;
-    let auth = auth_for(settings);
-    let dir_segs = remote_dir_segments(settings, RemoteLayout::Current);
-    ensure_remote_directories(&settings.base_url, &dir_segs, &auth).await?;
-
-    let snapshot = build_local_snapshot(db)?;
+    let progress = ProgressReporter::start("webdav.upload");
+    let backend = backend_for(settings, RemoteLayout::Current);
+    backend.prepare().await?;
+
+    progress.step("snapshot", 0, 4, None);
+    let mut usage = if settings.sync_usage {
+        let previous = fetch_remote_snapshot(&backend, RemoteLayout::Current)
+            .await?
+            .map(|remote| remote.manifest)
+            .filter(|manifest| validate_manifest_compat(manifest, RemoteLayout::Current).is_ok());
+        Some(
+            usage_sync::plan_upload(
+                db,
+                &backend,
+                previous.as_ref(),
+                settings.status.usage_watermark,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+    let snapshot = build_local_snapshot(db, usage.as_ref())?;
 
     // Upload order: artifacts first, manifest last (best-effort consistency)
-    let db_url = remote_file_url(settings, RemoteLayout::Current, REMOTE_DB_SQL)?;
-    put_bytes(&db_url, &auth, snapshot.db_sql, "application/sql").await?;
-
-    let skills_url = remote_file_url(settings, RemoteLayout::Current, REMOTE_SKILLS_ZIP)?;
-    put_bytes(&skills_url, &auth, snapshot.skills_zip, "application/zip").await?;
-
-    let manifest_url = remote_file_url(settings, RemoteLayout::Current, REMOTE_MANIFEST)?;
-    put_bytes(
-        &manifest_url,
-        &auth,
-        snapshot.manifest_bytes,
-        "application/json",
-    )
-    .await?;
+    progress.step("uploading", 1, 4, Some(REMOTE_DB_SQL.to_string()));
+    backend
+        .put(REMOTE_DB_SQL, snapshot.db_sql, "application/sql")
+        .await?;
+
+    progress.step("uploading", 2, 4, Some(REMOTE_SKILLS_ZIP.to_string()));
+    backend
+        .put(REMOTE_SKILLS_ZIP, snapshot.skills_zip, "application/zip")
+        .await?;
+    if let Some(plan) = usage.as_mut() {
+        usage_sync::upload_files(&backend, plan).await?;
+    }
+
+    progress.step("uploading", 3, 4, Some(REMOTE_MANIFEST.to_string()));
+    backend
+        .put(REMOTE_MANIFEST, snapshot.manifest_bytes, "application/json")
+        .await?;
+    if let Some(plan) = usage.as_ref() {
+        usage_sync::delete_obsolete(&backend, plan).await;
+        settings.status.usage_watermark = plan.watermark;
+    }
 
     // Fetch etag (best-effort, don't fail the upload)
-    let etag = match head_etag(&manifest_url, &auth).await {
+    let etag = match backend.head_etag(REMOTE_MANIFEST).await {
         Ok(e) => e,
         Err(e) => {
             log::debug!("[WebDAV] Failed to fetch ETag after upload: {e}");
@@ -103,6 +126,7 @@ pub async fn upload(
         etag,
         persist_sync_success,
     );
+    progress.finish(None);
     Ok(serde_json::json!({ "status": "uploaded" }))
 }
 
@@ -112,39 +136,42 @@ pub async fn download(
     settings: &mut WebDavSyncSettings,
 ) -> Result<Value, AppError> {
     settings.validate()?;
-    let auth = auth_for(settings);
-    let snapshot = find_remote_snapshot(settings, &auth)
-        .await?
-        .ok_or_else(|| {
-            localized(
-                "webdav.sync.remote_empty",
-                "远端没有可下载的同步数据",
-                "No downloadable sync data found on the remote.",
-            )
-        })?;
+    let progress = ProgressReporter::start("webdav.download");
+    progress.step("manifest", 0, 4, None);
+    let snapshot = find_remote_snapshot(settings).await?.ok_or_else(|| {
+        localized(
+            "webdav.sync.remote_empty",
+            "远端没有可下载的同步数据",
+            "No downloadable sync data found on the remote.",
+        )
+    })?;
 
     validate_manifest_compat(&snapshot.manifest, snapshot.layout)?;
 
     // Download and verify artifacts
-    let db_sql = download_and_verify(
-        settings,
-        &auth,
-        snapshot.layout,
-        REMOTE_DB_SQL,
-        &snapshot.manifest.artifacts,
-    )
-    .await?;
-    let skills_zip = download_and_verify(
-        settings,
-        &auth,
-        snapshot.layout,
-        REMOTE_SKILLS_ZIP,
-        &snapshot.manifest.artifacts,
-    )
-    .await?;
+    progress.step("downloading", 1, 4, Some(REMOTE_DB_SQL.to_string()));
+    let backend = backend_for(settings, snapshot.layout);
+    let db_sql = download_and_verify(&backend, REMOTE_DB_SQL, &snapshot.manifest.artifacts).await?;
+    progress.step("downloading", 2, 4, Some(REMOTE_SKILLS_ZIP.to_string()));
+    let skills_zip =
+        download_and_verify(&backend, REMOTE_SKILLS_ZIP, &snapshot.manifest.artifacts).await?;
 
     // Apply snapshot
+    progress.step("applying", 3, 4, None);
     apply_snapshot(db, &db_sql, &skills_zip)?;
+    if settings.sync_usage {
+        match usage_sync::pull(
+            db,
+            &backend,
+            &snapshot.manifest,
+            settings.status.usage_watermark,
+        )
+        .await
+        {
+            Ok(watermark) => settings.status.usage_watermark = watermark,
+            Err(e) => log::warn!("[WebDAV] Failed to merge remote usage data: {e}"),
+        }
+    }
 
     let manifest_hash = sha256_hex(&snapshot.manifest_bytes);
     let _persisted = persist_sync_success_best_effort(
@@ -153,18 +180,36 @@ pub async fn download(
         snapshot.manifest_etag,
         persist_sync_success,
     );
+    progress.finish(None);
     Ok(serde_json::json!({
         "status": "downloaded",
         "sourceLayout": snapshot.layout.as_str(),
-        "sourcePath": remote_dir_display(settings, snapshot.layout),
+        "sourcePath": backend.location(),
     }))
 }
 
+/// Download and verify only the remote database artifact, for selective restore.
+pub(crate) async fn download_database(
+    settings: &WebDavSyncSettings,
+) -> Result<(SyncManifest, Vec<u8>), AppError> {
+    settings.validate()?;
+    let snapshot = find_remote_snapshot(settings).await?.ok_or_else(|| {
+        localized(
+            "webdav.sync.remote_empty",
+            "远端没有可下载的同步数据",
+            "No downloadable sync data found on the remote.",
+        )
+    })?;
+    validate_manifest_compat(&snapshot.manifest, snapshot.layout)?;
+    let backend = backend_for(settings, snapshot.layout);
+    let db_sql = download_and_verify(&backend, REMOTE_DB_SQL, &snapshot.manifest.artifacts).await?;
+    Ok((snapshot.manifest, db_sql))
+}
+
 /// Fetch remote manifest info without downloading artifacts.
 pub async fn fetch_remote_info(settings: &WebDavSyncSettings) -> Result<Option<Value>, AppError> {
     settings.validate()?;
-    let auth = auth_for(settings);
-    let Some(snapshot) = find_remote_snapshot(settings, &auth).await? else {
+    let Some(snapshot) = find_remote_snapshot(settings).await? else {
         return Ok(None);
     };
     let compatible = validate_manifest_compat(&snapshot.manifest, snapshot.layout).is_ok();
@@ -180,12 +225,20 @@ pub async fn fetch_remote_info(settings: &WebDavSyncSettings) -> Result<Option<V
         "compatible": compatible,
         "artifacts": snapshot.manifest.artifacts.keys().collect::<Vec<_>>(),
         "layout": snapshot.layout.as_str(),
-        "remotePath": remote_dir_display(settings, snapshot.layout),
+        "remotePath": backend_for(settings, snapshot.layout).location(),
     });
 
     Ok(Some(payload))
 }
 
+/// Delete every blob in the current-layout profile directory on the remote.
+pub async fn delete_remote(settings: &WebDavSyncSettings) -> Result<Value, AppError> {
+    settings.validate()?;
+    let backend = backend_for(settings, RemoteLayout::Current);
+    let deleted = delete_all(&backend).await?;
+    Ok(serde_json::json!({ "status": "deleted", "deleted": deleted }))
+}
+
 // ─── Sync status persistence ─────────────────────────────────
 
 fn persist_sync_success(
@@ -200,6 +253,7 @@ fn persist_sync_success(
         last_local_manifest_hash: Some(manifest_hash.clone()),
         last_remote_manifest_hash: Some(manifest_hash),
         last_remote_etag: etag,
+        usage_watermark: settings.status.usage_watermark,
     };
     settings.status = status.clone();
     update_webdav_sync_status(status)
@@ -207,22 +261,23 @@ fn persist_sync_success(
 
 async fn find_remote_snapshot(
     settings: &WebDavSyncSettings,
-    auth: &WebDavAuth,
 ) -> Result<Option<RemoteSnapshot>, AppError> {
-    if let Some(snapshot) = fetch_remote_snapshot(settings, auth, RemoteLayout::Current).await? {
-        return Ok(Some(snapshot));
+    for layout in [RemoteLayout::Current, RemoteLayout::Legacy] {
+        if let Some(snapshot) =
+            fetch_remote_snapshot(&backend_for(settings, layout), layout).await?
+        {
+            return Ok(Some(snapshot));
+        }
     }
-    fetch_remote_snapshot(settings, auth, RemoteLayout::Legacy).await
+    Ok(None)
 }
 
 async fn fetch_remote_snapshot(
-    settings: &WebDavSyncSettings,
-    auth: &WebDavAuth,
+    backend: &impl SyncBackend,
     layout: RemoteLayout,
 ) -> Result<Option<RemoteSnapshot>, AppError> {
-    let manifest_url = remote_file_url(settings, layout, REMOTE_MANIFEST)?;
     let Some((manifest_bytes, manifest_etag)) =
-        get_bytes(&manifest_url, auth, MAX_MANIFEST_BYTES).await?
+        backend.get(REMOTE_MANIFEST, MAX_MANIFEST_BYTES).await?
     else {
         return Ok(None);
     };
@@ -243,9 +298,7 @@ async fn fetch_remote_snapshot(
 // ─── Download & verify ───────────────────────────────────────
 
 async fn download_and_verify(
-    settings: &WebDavSyncSettings,
-    auth: &WebDavAuth,
-    layout: RemoteLayout,
+    backend: &impl SyncBackend,
     artifact_name: &str,
     artifacts: &BTreeMap<String, ArtifactMeta>,
 ) -> Result<Vec<u8>, AppError> {
@@ -258,8 +311,8 @@ async fn download_and_verify(
     })?;
     validate_artifact_size_limit(artifact_name, meta.size)?;
 
-    let url = remote_file_url(settings, layout, artifact_name)?;
-    let (bytes, _) = get_bytes(&url, auth, MAX_SYNC_ARTIFACT_BYTES as usize)
+    let (bytes, _) = backend
+        .get(artifact_name, MAX_SYNC_ARTIFACT_BYTES as usize)
         .await?
         .ok_or_else(|| {
             localized(
@@ -286,23 +339,15 @@ fn remote_dir_segments(settings: &WebDavSyncSettings, layout: RemoteLayout) -> V
     segs
 }
 
-fn remote_file_url(
-    settings: &WebDavSyncSettings,
-    layout: RemoteLayout,
-    file_name: &str,
-) -> Result<String, AppError> {
-    let mut segs = remote_dir_segments(settings, layout);
-    segs.extend(path_segments(file_name).map(str::to_string));
-    build_remote_url(&settings.base_url, &segs)
-}
-
-fn remote_dir_display(settings: &WebDavSyncSettings, layout: RemoteLayout) -> String {
-    let segs = remote_dir_segments(settings, layout);
-    format!("/{}", segs.join("/"))
-}
-
-fn auth_for(settings: &WebDavSyncSettings) -> WebDavAuth {
-    auth_from_credentials(&settings.username, &settings.password)
+fn backend_for(settings: &WebDavSyncSettings, layout: RemoteLayout) -> Encrypted<WebDavBackend> {
+    Encrypted::new(
+        WebDavBackend {
+            base_url: settings.base_url.clone(),
+            auth: auth_from_credentials(&settings.username, &settings.password),
+            dir: remote_dir_segments(settings, layout),
+        },
+        settings.encryption_passphrase(),
+    )
 }
 
 // ─── Tests ───────────────────────────────────────────────────
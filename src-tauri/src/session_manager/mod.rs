@@ -84,13 +84,112 @@ pub fn scan_sessions() -> Vec<SessionMeta> {
     sessions.extend(r6);
     sessions.extend(r7);
 
+    sort_by_activity(&mut sessions);
+    sessions
+}
+
+/// Filters for [`recent_sessions`]; every field is optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionQuery {
+    /// Only sessions of this tool (`claude`, `codex`, ...)
+    pub provider_id: Option<String>,
+    /// Only sessions whose project dir is this directory or below it
+    pub project_dir: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Scan sessions of a single tool, newest first.
+pub fn scan_sessions_for(provider_id: &str) -> Result<Vec<SessionMeta>, String> {
+    let mut sessions = match provider_id {
+        "codex" => codex::scan_sessions(),
+        "claude" => claude::scan_sessions(),
+        "opencode" => opencode::scan_sessions(),
+        "openclaw" => openclaw::scan_sessions(),
+        "gemini" => gemini::scan_sessions(),
+        "grokbuild" => grokbuild::scan_sessions(),
+        "hermes" => hermes::scan_sessions(),
+        _ => return Err(format!("Unsupported provider: {provider_id}")),
+    };
+    sort_by_activity(&mut sessions);
+    Ok(sessions)
+}
+
+pub fn recent_sessions(query: &SessionQuery) -> Result<Vec<SessionMeta>, String> {
+    let sessions = match query.provider_id.as_deref() {
+        Some(provider_id) => scan_sessions_for(provider_id)?,
+        None => scan_sessions(),
+    };
+    Ok(filter_sessions(sessions, query))
+}
+
+fn filter_sessions(sessions: Vec<SessionMeta>, query: &SessionQuery) -> Vec<SessionMeta> {
+    let project_dir = query.project_dir.as_deref().map(normalize_dir);
+    sessions
+        .into_iter()
+        .filter(
+            |session| match (&project_dir, session.project_dir.as_deref()) {
+                (None, _) => true,
+                (Some(wanted), Some(dir)) => Path::new(&normalize_dir(dir)).starts_with(wanted),
+                (Some(_), None) => false,
+            },
+        )
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect()
+}
+
+fn normalize_dir(dir: &str) -> String {
+    let path = Path::new(dir);
+    path.canonicalize()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| dir.trim_end_matches(['/', '\\']).to_string())
+}
+
+/// Find a session by full id or unique id prefix.
+pub fn find_session<'a>(
+    sessions: &'a [SessionMeta],
+    id_or_prefix: &str,
+) -> Result<&'a SessionMeta, String> {
+    if let Some(exact) = sessions.iter().find(|s| s.session_id == id_or_prefix) {
+        return Ok(exact);
+    }
+    let mut matches = sessions
+        .iter()
+        .filter(|s| !id_or_prefix.is_empty() && s.session_id.starts_with(id_or_prefix));
+    match (matches.next(), matches.next()) {
+        (Some(only), None) => Ok(only),
+        (Some(_), Some(_)) => Err(format!("Ambiguous session id prefix: {id_or_prefix}")),
+        (None, _) => Err(format!("Session not found: {id_or_prefix}")),
+    }
+}
+
+/// Resume command that can be pasted into any shell.
+///
+/// Claude Code only finds a session from the project directory it was started in,
+/// so the command changes into the session's project dir first.
+pub fn shell_resume_command(session: &SessionMeta) -> Option<String> {
+    let command = session.resume_command.as_deref()?;
+    let cd = if cfg!(windows) { "cd /d" } else { "cd" };
+    Some(match session.project_dir.as_deref() {
+        Some(dir) if !dir.is_empty() => format!("{cd} {} && {command}", shell_quote(dir)),
+        _ => command.to_string(),
+    })
+}
+
+fn shell_quote(value: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", value.replace('"', "\\\""))
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+fn sort_by_activity(sessions: &mut [SessionMeta]) {
     sessions.sort_by(|a, b| {
         let a_ts = a.last_active_at.or(a.created_at).unwrap_or(0);
         let b_ts = b.last_active_at.or(b.created_at).unwrap_or(0);
         b_ts.cmp(&a_ts)
     });
-
-    sessions
 }
 
 pub fn load_messages(provider_id: &str, source_path: &str) -> Result<Vec<SessionMessage>, String> {
@@ -269,6 +368,80 @@ mod tests {
         .expect("write source");
     }
 
+    fn meta(provider_id: &str, session_id: &str, project_dir: Option<&str>) -> SessionMeta {
+        SessionMeta {
+            provider_id: provider_id.to_string(),
+            session_id: session_id.to_string(),
+            title: None,
+            summary: None,
+            project_dir: project_dir.map(str::to_string),
+            created_at: None,
+            last_active_at: None,
+            source_path: None,
+            resume_command: Some(format!("claude --resume {session_id}")),
+        }
+    }
+
+    #[test]
+    fn find_session_accepts_unique_prefix() {
+        let sessions = vec![
+            meta("claude", "abc-111", None),
+            meta("claude", "abc-222", None),
+            meta("codex", "def-333", None),
+        ];
+        assert_eq!(
+            find_session(&sessions, "def").unwrap().session_id,
+            "def-333"
+        );
+        assert_eq!(
+            find_session(&sessions, "abc-222").unwrap().session_id,
+            "abc-222"
+        );
+        assert!(find_session(&sessions, "abc")
+            .unwrap_err()
+            .contains("Ambiguous"));
+        assert!(find_session(&sessions, "zzz").is_err());
+    }
+
+    #[test]
+    fn filter_sessions_by_project_dir_and_limit() {
+        let sessions = vec![
+            meta("claude", "s1", Some("/nonexistent/work/api")),
+            meta("claude", "s2", Some("/nonexistent/work/web/")),
+            meta("claude", "s3", Some("/nonexistent/other")),
+            meta("claude", "s4", None),
+        ];
+        let query = SessionQuery {
+            project_dir: Some("/nonexistent/work".to_string()),
+            ..Default::default()
+        };
+        let ids: Vec<_> = filter_sessions(sessions.clone(), &query)
+            .into_iter()
+            .map(|s| s.session_id)
+            .collect();
+        assert_eq!(ids, vec!["s1", "s2"]);
+
+        let query = SessionQuery {
+            limit: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(filter_sessions(sessions, &query).len(), 1);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn shell_resume_command_changes_into_project_dir() {
+        let session = meta("claude", "s1", Some("/work/it's here"));
+        assert_eq!(
+            shell_resume_command(&session).as_deref(),
+            Some("cd '/work/it'\\''s here' && claude --resume s1")
+        );
+        assert_eq!(
+            shell_resume_command(&meta("claude", "s2", None)).as_deref(),
+            Some("claude --resume s2")
+        );
+    }
+
     #[test]
     fn accepts_source_path_under_any_allowed_provider_root() {
         let active_root = tempdir().expect("active root");
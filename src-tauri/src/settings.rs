@@ -95,6 +95,9 @@ pub struct WebDavSyncStatus {
     pub last_local_manifest_hash: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_remote_manifest_hash: Option<String>,
+    /// 已上传到该远端的用量明细水位（明细表 rowid），增量同步从此之后导出
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_watermark: Option<i64>,
 }
 
 fn default_remote_root() -> String {
@@ -122,6 +125,14 @@ pub struct WebDavSyncSettings {
     pub remote_root: String,
     #[serde(default = "default_profile")]
     pub profile: String,
+    /// 上传前用口令加密备份文件（AES-256-GCM）
+    #[serde(default)]
+    pub encryption_enabled: bool,
+    #[serde(default)]
+    pub encryption_passphrase: String,
+    /// 同时同步用量明细（增量上传，定期压缩）
+    #[serde(default)]
+    pub sync_usage: bool,
     #[serde(default)]
     pub status: WebDavSyncStatus,
 }
@@ -136,6 +147,9 @@ impl Default for WebDavSyncSettings {
             password: String::new(),
             remote_root: default_remote_root(),
             profile: default_profile(),
+            encryption_enabled: false,
+            encryption_passphrase: String::new(),
+            sync_usage: false,
             status: WebDavSyncStatus::default(),
         }
     }
@@ -157,7 +171,13 @@ impl WebDavSyncSettings {
                 "WebDAV username is required.",
             ));
         }
-        Ok(())
+        validate_encryption_passphrase(self.encryption_enabled, &self.encryption_passphrase)
+    }
+
+    /// 已启用加密时返回口令
+    pub fn encryption_passphrase(&self) -> Option<&str> {
+        (self.encryption_enabled && !self.encryption_passphrase.is_empty())
+            .then_some(self.encryption_passphrase.as_str())
     }
 
     pub fn normalize(&mut self) {
@@ -179,6 +199,20 @@ impl WebDavSyncSettings {
     }
 }
 
+fn validate_encryption_passphrase(
+    enabled: bool,
+    passphrase: &str,
+) -> Result<(), crate::error::AppError> {
+    if enabled && passphrase.is_empty() {
+        return Err(crate::error::AppError::localized(
+            "sync.encryption.passphrase_required",
+            "已启用备份加密，请设置加密口令",
+            "Backup encryption is enabled but no passphrase is set.",
+        ));
+    }
+    Ok(())
+}
+
 /// S3 同步设置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -201,6 +235,14 @@ pub struct S3SyncSettings {
     pub remote_root: String,
     #[serde(default = "default_profile")]
     pub profile: String,
+    /// 上传前用口令加密备份文件（AES-256-GCM）
+    #[serde(default)]
+    pub encryption_enabled: bool,
+    #[serde(default)]
+    pub encryption_passphrase: String,
+    /// 同时同步用量明细（增量上传，定期压缩）
+    #[serde(default)]
+    pub sync_usage: bool,
     #[serde(default)]
     pub status: WebDavSyncStatus,
 }
@@ -217,6 +259,9 @@ impl Default for S3SyncSettings {
             endpoint: String::new(),
             remote_root: default_remote_root(),
             profile: default_profile(),
+            encryption_enabled: false,
+            encryption_passphrase: String::new(),
+            sync_usage: false,
             status: WebDavSyncStatus::default(),
         }
     }
@@ -252,7 +297,13 @@ impl S3SyncSettings {
                 "S3 Secret Access Key is required.",
             ));
         }
-        Ok(())
+        validate_encryption_passphrase(self.encryption_enabled, &self.encryption_passphrase)
+    }
+
+    /// 已启用加密时返回口令
+    pub fn encryption_passphrase(&self) -> Option<&str> {
+        (self.encryption_enabled && !self.encryption_passphrase.is_empty())
+            .then_some(self.encryption_passphrase.as_str())
     }
 
     pub fn normalize(&mut self) {
@@ -334,6 +385,158 @@ pub struct CodexOfficialHistoryUnifyMigration {
     pub codex_config_dir: Option<String>,
 }
 
+/// 供应商切换钩子的触发时机
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SwitchHookEvent {
+    PreSwitch,
+    PostSwitch,
+    SwitchFailed,
+}
+
+impl SwitchHookEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PreSwitch => "pre-switch",
+            Self::PostSwitch => "post-switch",
+            Self::SwitchFailed => "switch-failed",
+        }
+    }
+}
+
+/// 钩子动作：本地命令或 HTTP webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SwitchHookAction {
+    /// 通过系统 shell 执行（Unix: `sh -c`，Windows: `cmd /C`）
+    Command { command: String },
+    /// 以 JSON POST 事件详情
+    Webhook { url: String },
+}
+
+/// 供应商切换钩子（设备级，不随数据库同步）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchHook {
+    pub id: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 生效的应用；为空表示所有应用
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub apps: Vec<String>,
+    pub events: Vec<SwitchHookEvent>,
+    #[serde(flatten)]
+    pub action: SwitchHookAction,
+    /// 超时秒数（默认 10 秒）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+/// 模型别名（设备级）：供应商配置、命令行与代理请求中都可以用 `alias` 代替真实模型名
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelAlias {
+    pub alias: String,
+    pub model: String,
+}
+
+/// 通知事件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationEvent {
+    /// 供应商预算跨过 80% / 100%
+    BudgetAlert,
+    /// 供应商熔断（连续失败或错误率超限）
+    ProviderDown,
+    /// WebDAV / S3 自动同步失败
+    SyncFailed,
+    /// 看门狗自动切走或切回供应商
+    AutoSwitch,
+}
+
+/// SMTP 连接加密方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// 隐式 TLS（通常为 465 端口）
+    #[default]
+    Tls,
+    /// 明文连接后 STARTTLS 升级（通常为 587 端口）
+    Starttls,
+    /// 不加密（仅用于本机中继）
+    None,
+}
+
+/// 通知渠道
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum NotificationChannelKind {
+    /// Bark（iOS）；`server_url` 默认 `https://api.day.app`
+    #[serde(rename_all = "camelCase")]
+    Bark {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        server_url: Option<String>,
+        device_key: String,
+    },
+    /// Telegram Bot `sendMessage`
+    #[serde(rename_all = "camelCase")]
+    Telegram { bot_token: String, chat_id: String },
+    /// ntfy 主题；`server_url` 默认 `https://ntfy.sh`
+    #[serde(rename_all = "camelCase")]
+    Ntfy {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        server_url: Option<String>,
+        topic: String,
+        /// 受保护主题的访问令牌
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token: Option<String>,
+    },
+    /// SMTP 邮件
+    #[serde(rename_all = "camelCase")]
+    Email {
+        host: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        port: Option<u16>,
+        #[serde(default)]
+        security: SmtpSecurity,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        username: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password: Option<String>,
+        from: String,
+        to: Vec<String>,
+    },
+    /// 以 JSON POST 通知内容
+    Webhook { url: String },
+}
+
+/// 通知渠道配置（设备级，不随数据库同步）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationChannel {
+    pub id: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 订阅的事件；为空表示全部事件
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<NotificationEvent>,
+    #[serde(flatten)]
+    pub kind: NotificationChannelKind,
+}
+
+/// 出站连接的 IP 协议族偏好
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IpFamilyPreference {
+    /// 按系统解析顺序
+    #[default]
+    Auto,
+    /// 优先 IPv4
+    Ipv4,
+    /// 优先 IPv6
+    Ipv6,
+}
+
 /// 应用设置结构
 ///
 /// 存储设备级别设置，保存在本地 `~/.cc-switch/settings.json`，不随数据库同步。
@@ -354,6 +557,9 @@ pub struct AppSettings {
     /// 是否跳过 Claude Code 初次安装确认
     #[serde(default)]
     pub skip_claude_onboarding: bool,
+    /// 切换 Claude 供应商时把已安装插件合并进 settings.json 的 enabledPlugins（默认开启）
+    #[serde(default = "default_true")]
+    pub sync_claude_plugins: bool,
     /// 是否开机自启
     #[serde(default)]
     pub launch_on_startup: bool,
@@ -363,6 +569,28 @@ pub struct AppSettings {
     /// 是否在主页面启用本地代理功能（默认关闭）
     #[serde(default)]
     pub enable_local_proxy: bool,
+    /// 代理会话亲和：同一会话优先路由到上次成功的供应商，保持 prompt cache 命中（默认开启）
+    #[serde(default = "default_true")]
+    pub proxy_session_affinity: bool,
+    /// 会话亲和绑定的空闲过期时间（秒，默认 3600）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_session_affinity_ttl_secs: Option<u64>,
+    /// 代理运行时定期探测已接管应用的当前供应商，保持上游连接可复用（默认关闭）
+    #[serde(default)]
+    pub proxy_keep_warm: bool,
+    /// 出站连接（代理转发、端点测速、连通性检查）的 IP 协议族偏好
+    #[serde(default)]
+    pub network_ip_preference: IpFamilyPreference,
+    /// Happy Eyeballs：首选地址迟迟连不上时并行尝试其它地址（默认开启）。
+    /// 关闭且指定了协议族偏好时只连接该协议族的地址。
+    #[serde(default = "default_true")]
+    pub network_happy_eyeballs: bool,
+    /// 离线模式：除供应商端点外禁止一切出站请求（技能/模板市场、版本检查、云同步等）
+    #[serde(default)]
+    pub offline_mode: bool,
+    /// 新增/编辑供应商时把 API Key 存入系统钥匙串，配置中只保留引用（默认关闭）
+    #[serde(default)]
+    pub secrets_use_keychain: bool,
     /// User has confirmed the local proxy first-run notice
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub proxy_confirmed: Option<bool>,
@@ -386,6 +614,10 @@ pub struct AppSettings {
     /// providers. Opt-in: defaults to false.
     #[serde(default)]
     pub unify_codex_session_history: bool,
+    /// Opt-in local usage telemetry: feature-level counters aggregated in
+    /// `~/.cc-switch/telemetry.json`. Never uploaded automatically.
+    #[serde(default)]
+    pub usage_telemetry_enabled: bool,
     /// User opted in (via the enable dialog checkbox) to migrate existing
     /// official sessions ("openai" bucket) into the shared bucket. Persisted so
     /// a failed migration retries at startup; cleared when the toggle turns off.
@@ -485,6 +717,28 @@ pub struct AppSettings {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub preferred_terminal: Option<String>,
 
+    // ===== 供应商切换钩子 =====
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub switch_hooks: Vec<SwitchHook>,
+
+    // ===== 状态栏集成 =====
+    /// 切换供应商后写入 `~/.cc-switch/status.json`，供 tmux / starship 等状态栏读取
+    #[serde(default)]
+    pub status_file_enabled: bool,
+
+    // ===== 供应商图标 =====
+    /// 后台抓取并缓存供应商官网图标（默认关闭，不主动访问供应商官网）
+    #[serde(default)]
+    pub provider_favicons_enabled: bool,
+
+    // ===== 通知渠道 =====
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notification_channels: Vec<NotificationChannel>,
+
+    // ===== 模型别名 =====
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub model_aliases: Vec<ModelAlias>,
+
     // ===== 本机自动迁移状态 =====
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub local_migrations: Option<LocalMigrations>,
@@ -510,9 +764,17 @@ impl Default for AppSettings {
             use_app_window_controls: false,
             enable_claude_plugin_integration: false,
             skip_claude_onboarding: false,
+            sync_claude_plugins: true,
             launch_on_startup: false,
             silent_startup: false,
             enable_local_proxy: false,
+            proxy_session_affinity: true,
+            proxy_session_affinity_ttl_secs: None,
+            proxy_keep_warm: false,
+            network_ip_preference: IpFamilyPreference::Auto,
+            network_happy_eyeballs: true,
+            offline_mode: false,
+            secrets_use_keychain: false,
             proxy_confirmed: None,
             usage_confirmed: None,
             usage_dashboard_refresh_interval_ms: None,
@@ -520,6 +782,7 @@ impl Default for AppSettings {
             show_profile_switcher: true,
             preserve_codex_official_auth_on_switch: false,
             unify_codex_session_history: false,
+            usage_telemetry_enabled: false,
             unify_codex_migrate_existing: None,
             failover_confirmed: None,
             first_run_notice_confirmed: None,
@@ -549,13 +812,18 @@ impl Default for AppSettings {
             backup_interval_hours: None,
             backup_retain_count: None,
             preferred_terminal: None,
+            switch_hooks: Vec::new(),
+            status_file_enabled: false,
+            provider_favicons_enabled: false,
+            notification_channels: Vec::new(),
+            model_aliases: Vec::new(),
             local_migrations: None,
         }
     }
 }
 
 impl AppSettings {
-    fn settings_path() -> Option<PathBuf> {
+    pub(crate) fn settings_path() -> Option<PathBuf> {
         // settings.json 保留用于旧版本迁移和无数据库场景
         Some(
             crate::config::get_home_dir()
@@ -636,27 +904,31 @@ impl AppSettings {
         }
     }
 
+    /// 读取 `settings.json`，再应用 `settings.toml` 与环境变量覆盖（见 `settings_overrides`）
     fn load_from_file() -> Self {
+        let mut settings = crate::settings_overrides::apply(Self::load_json_value());
+        settings.normalize_paths();
+        settings
+    }
+
+    fn load_json_value() -> serde_json::Value {
+        let fallback = || serde_json::to_value(Self::default()).unwrap_or_default();
         let Some(path) = Self::settings_path() else {
-            return Self::default();
+            return fallback();
+        };
+        let Ok(content) = fs::read_to_string(&path) else {
+            return fallback();
         };
-        if let Ok(content) = fs::read_to_string(&path) {
-            match serde_json::from_str::<AppSettings>(&content) {
-                Ok(mut settings) => {
-                    settings.normalize_paths();
-                    settings
-                }
-                Err(err) => {
-                    log::warn!(
-                        "解析设置文件失败，将使用默认设置。路径: {}, 错误: {}",
-                        path.display(),
-                        err
-                    );
-                    Self::default()
-                }
+        match serde_json::from_str::<AppSettings>(&content) {
+            Ok(settings) => serde_json::to_value(settings).unwrap_or_else(|_| fallback()),
+            Err(err) => {
+                log::warn!(
+                    "解析设置文件失败，将使用默认设置。路径: {}, 错误: {}",
+                    path.display(),
+                    err
+                );
+                fallback()
             }
-        } else {
-            Self::default()
         }
     }
 }
@@ -672,8 +944,14 @@ fn save_settings_file(settings: &AppSettings) -> Result<(), AppError> {
         fs::create_dir_all(parent).map_err(|e| AppError::io(parent, e))?;
     }
 
-    let json = serde_json::to_string_pretty(&normalized)
-        .map_err(|e| AppError::JsonSerialize { source: e })?;
+    let mut value =
+        serde_json::to_value(&normalized).map_err(|e| AppError::JsonSerialize { source: e })?;
+    let on_disk = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+    crate::settings_overrides::restore_unmanaged(&mut value, on_disk.as_ref());
+    let json =
+        serde_json::to_string_pretty(&value).map_err(|e| AppError::JsonSerialize { source: e })?;
     #[cfg(unix)]
     {
         use std::fs::OpenOptions;
@@ -702,7 +980,16 @@ fn save_settings_file(settings: &AppSettings) -> Result<(), AppError> {
 static SETTINGS_STORE: OnceLock<RwLock<AppSettings>> = OnceLock::new();
 
 fn settings_store() -> &'static RwLock<AppSettings> {
-    SETTINGS_STORE.get_or_init(|| RwLock::new(AppSettings::load_from_file()))
+    SETTINGS_STORE.get_or_init(|| {
+        let settings = AppSettings::load_from_file();
+        crate::i18n::set_language(settings.language.as_deref());
+        RwLock::new(settings)
+    })
+}
+
+/// 预先读取设置文件并填充缓存（启动时与数据库初始化并行调用）
+pub fn preload_settings() {
+    let _ = settings_store();
 }
 
 fn resolve_override_path(raw: &str) -> PathBuf {
@@ -737,9 +1024,11 @@ pub fn get_settings_for_frontend() -> AppSettings {
     let mut settings = get_settings();
     if let Some(sync) = &mut settings.webdav_sync {
         sync.password.clear();
+        sync.encryption_passphrase.clear();
     }
     if let Some(s3) = &mut settings.s3_sync {
         s3.secret_access_key.clear();
+        s3.encryption_passphrase.clear();
     }
     settings.webdav_backup = None;
     settings
@@ -753,6 +1042,7 @@ pub fn update_settings(mut new_settings: AppSettings) -> Result<(), AppError> {
         log::warn!("设置锁已毒化，使用恢复值: {e}");
         e.into_inner()
     });
+    crate::i18n::set_language(new_settings.language.as_deref());
     *guard = new_settings;
     Ok(())
 }
@@ -769,6 +1059,7 @@ where
     mutator(&mut next);
     next.normalize_paths();
     save_settings_file(&next)?;
+    crate::i18n::set_language(next.language.as_deref());
     *guard = next;
     Ok(())
 }
@@ -873,6 +1164,7 @@ pub fn reload_settings() -> Result<(), AppError> {
         log::warn!("设置锁已毒化，使用恢复值: {e}");
         e.into_inner()
     });
+    crate::i18n::set_language(fresh_settings.language.as_deref());
     *guard = fresh_settings;
     Ok(())
 }
@@ -953,6 +1245,122 @@ pub fn unify_codex_session_history() -> bool {
         .unify_codex_session_history
 }
 
+pub fn usage_telemetry_enabled() -> bool {
+    settings_store()
+        .read()
+        .unwrap_or_else(|e| {
+            log::warn!("设置锁已毒化，使用恢复值: {e}");
+            e.into_inner()
+        })
+        .usage_telemetry_enabled
+}
+
+const DEFAULT_PROXY_SESSION_AFFINITY_TTL_SECS: u64 = 3600;
+
+/// 代理会话亲和的过期时间；关闭亲和时返回 None
+pub fn proxy_session_affinity_ttl() -> Option<std::time::Duration> {
+    let settings = settings_store().read().unwrap_or_else(|e| {
+        log::warn!("设置锁已毒化，使用恢复值: {e}");
+        e.into_inner()
+    });
+    if !settings.proxy_session_affinity {
+        return None;
+    }
+    let secs = settings
+        .proxy_session_affinity_ttl_secs
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_PROXY_SESSION_AFFINITY_TTL_SECS);
+    Some(std::time::Duration::from_secs(secs))
+}
+
+/// 是否为当前供应商保持上游连接
+pub fn proxy_keep_warm() -> bool {
+    settings_store()
+        .read()
+        .unwrap_or_else(|e| {
+            log::warn!("设置锁已毒化，使用恢复值: {e}");
+            e.into_inner()
+        })
+        .proxy_keep_warm
+}
+
+/// 已配置的供应商切换钩子
+pub fn switch_hooks() -> Vec<SwitchHook> {
+    settings_store()
+        .read()
+        .unwrap_or_else(|e| {
+            log::warn!("设置锁已毒化，使用恢复值: {e}");
+            e.into_inner()
+        })
+        .switch_hooks
+        .clone()
+}
+
+/// 已配置的通知渠道
+pub fn notification_channels() -> Vec<NotificationChannel> {
+    settings_store()
+        .read()
+        .unwrap_or_else(|e| {
+            log::warn!("设置锁已毒化，使用恢复值: {e}");
+            e.into_inner()
+        })
+        .notification_channels
+        .clone()
+}
+
+/// 模型别名表
+pub fn model_aliases() -> Vec<ModelAlias> {
+    settings_store()
+        .read()
+        .unwrap_or_else(|e| {
+            log::warn!("设置锁已毒化，使用恢复值: {e}");
+            e.into_inner()
+        })
+        .model_aliases
+        .clone()
+}
+
+/// 保存模型别名表
+pub fn set_model_aliases(aliases: Vec<ModelAlias>) -> Result<(), AppError> {
+    mutate_settings(|current| {
+        current.model_aliases = aliases;
+    })
+}
+
+/// 出站连接偏好：(协议族偏好, 是否启用 Happy Eyeballs)
+pub fn network_connection_preference() -> (IpFamilyPreference, bool) {
+    let settings = settings_store().read().unwrap_or_else(|e| {
+        log::warn!("设置锁已毒化，使用恢复值: {e}");
+        e.into_inner()
+    });
+    (
+        settings.network_ip_preference,
+        settings.network_happy_eyeballs,
+    )
+}
+
+/// 是否处于离线模式（仅允许访问供应商端点）
+pub fn offline_mode() -> bool {
+    settings_store()
+        .read()
+        .unwrap_or_else(|e| {
+            log::warn!("设置锁已毒化，使用恢复值: {e}");
+            e.into_inner()
+        })
+        .offline_mode
+}
+
+/// 是否把新保存的供应商密钥存入系统钥匙串
+pub fn secrets_use_keychain() -> bool {
+    settings_store()
+        .read()
+        .unwrap_or_else(|e| {
+            log::warn!("设置锁已毒化，使用恢复值: {e}");
+            e.into_inner()
+        })
+        .secrets_use_keychain
+}
+
 // ===== 当前供应商管理函数 =====
 
 /// 获取指定应用类型的当前供应商 ID（从本地 settings 读取）
@@ -0,0 +1,410 @@
+//! 无界面部署的设置覆盖：`~/.cc-switch/settings.toml` 与 `CC_SWITCH_*` 环境变量
+//!
+//! 设置仍以界面维护的 `settings.json` 为基础，按以下顺序逐层覆盖（后者优先）：
+//!
+//! 1. `settings.json`（界面保存）；
+//! 2. `settings.toml`：键名与 `settings.json` 相同，可设置任意字段，表（table）按字段深度合并；
+//!    另有 `[proxy]` 表设置本地代理监听地址 / 端口（`listenAddress` / `listenPort`）；
+//! 3. 环境变量：`CC_SWITCH_` + 字段名的大写下划线形式（如 `CC_SWITCH_CLAUDE_CONFIG_DIR`），
+//!    仅支持 [`ENV_KEYS`] 中的字段；代理端口 / 地址为 `CC_SWITCH_PROXY_PORT` / `CC_SWITCH_PROXY_ADDRESS`。
+//!
+//! 环境变量的值先按 JSON 解析（数字、布尔、`null`），失败时视为字符串。
+//! 类型不匹配的覆盖项会被忽略并记录警告，不影响其余设置。
+//! 被覆盖的字段在界面中仍可见，但界面保存时不会写回 `settings.json`，
+//! 移除覆盖后即恢复界面中原来的值。
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::settings::AppSettings;
+
+const SETTINGS_TOML: &str = "settings.toml";
+const ENV_PREFIX: &str = "CC_SWITCH_";
+const PROXY_TABLE: &str = "proxy";
+const ENV_PROXY_PORT: &str = "CC_SWITCH_PROXY_PORT";
+const ENV_PROXY_ADDRESS: &str = "CC_SWITCH_PROXY_ADDRESS";
+
+/// 可由环境变量覆盖的设置字段（`settings.json` 键名）
+pub const ENV_KEYS: &[&str] = &[
+    "language",
+    "claudeConfigDir",
+    "codexConfigDir",
+    "geminiConfigDir",
+    "grokConfigDir",
+    "opencodeConfigDir",
+    "openclawConfigDir",
+    "hermesConfigDir",
+    "showInTray",
+    "launchOnStartup",
+    "silentStartup",
+    "enableLocalProxy",
+    "proxySessionAffinity",
+    "proxyKeepWarm",
+    "networkIpPreference",
+    "offlineMode",
+    "secretsUseKeychain",
+    "usageTelemetryEnabled",
+    "statusFileEnabled",
+    "backupIntervalHours",
+    "backupRetainCount",
+    "preferredTerminal",
+];
+
+/// 覆盖来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverrideSource {
+    File,
+    Env,
+}
+
+/// 被覆盖的设置项（供界面提示"该设置由部署配置管理"）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedSetting {
+    /// 字段名；代理监听配置为 `proxy.listenAddress` / `proxy.listenPort`
+    pub key: String,
+    pub source: OverrideSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_var: Option<String>,
+}
+
+/// 本地代理监听覆盖
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProxyListenOverride {
+    pub address: Option<String>,
+    pub port: Option<u16>,
+}
+
+pub fn settings_toml_path() -> PathBuf {
+    crate::config::get_home_dir()
+        .join(".cc-switch")
+        .join(SETTINGS_TOML)
+}
+
+/// 字段名转环境变量名：`claudeConfigDir` → `CC_SWITCH_CLAUDE_CONFIG_DIR`
+fn env_var_name(key: &str) -> String {
+    let mut name = String::from(ENV_PREFIX);
+    for ch in key.chars() {
+        if ch.is_ascii_uppercase() {
+            name.push('_');
+        }
+        name.push(ch.to_ascii_uppercase());
+    }
+    name
+}
+
+fn read_toml(path: &Path) -> Option<Map<String, Value>> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let parsed = match toml::from_str::<toml::Table>(&text) {
+        Ok(table) => table,
+        Err(e) => {
+            log::warn!(
+                "[SettingsOverrides] 解析 {} 失败，已忽略: {e}",
+                path.display()
+            );
+            return None;
+        }
+    };
+    match serde_json::to_value(parsed) {
+        Ok(Value::Object(map)) => Some(map),
+        _ => None,
+    }
+}
+
+fn deep_merge(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                deep_merge(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+fn env_value(raw: &str) -> Vec<Value> {
+    let raw = raw.trim();
+    let mut candidates = Vec::new();
+    if let Ok(parsed) = serde_json::from_str::<Value>(raw) {
+        candidates.push(parsed);
+    }
+    candidates.push(Value::String(raw.to_string()));
+    candidates
+}
+
+/// 逐项应用覆盖，类型不匹配的项被丢弃
+fn apply_layer(
+    base: Value,
+    file: Option<Map<String, Value>>,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> (AppSettings, Vec<ManagedSetting>) {
+    let mut settings = serde_json::from_value::<AppSettings>(base.clone()).unwrap_or_default();
+    let mut merged = base;
+    let mut managed = Vec::new();
+
+    let mut try_apply = |key: &str, candidates: Vec<Value>| -> bool {
+        for candidate in candidates {
+            let mut next = merged.clone();
+            deep_merge(
+                &mut next,
+                Value::Object(Map::from_iter([(key.to_string(), candidate)])),
+            );
+            match serde_json::from_value::<AppSettings>(next.clone()) {
+                Ok(parsed) => {
+                    merged = next;
+                    settings = parsed;
+                    return true;
+                }
+                Err(e) => log::debug!("[SettingsOverrides] 覆盖项 {key} 类型不匹配: {e}"),
+            }
+        }
+        log::warn!("[SettingsOverrides] 覆盖项 {key} 的值无效，已忽略");
+        false
+    };
+
+    for (key, value) in file.unwrap_or_default() {
+        if key == PROXY_TABLE {
+            continue;
+        }
+        if try_apply(&key, vec![value]) {
+            managed.push(ManagedSetting {
+                key,
+                source: OverrideSource::File,
+                env_var: None,
+            });
+        }
+    }
+
+    for key in ENV_KEYS {
+        let var = env_var_name(key);
+        let Some(raw) = env(&var) else {
+            continue;
+        };
+        if try_apply(key, env_value(&raw)) {
+            managed.retain(|item| item.key != *key);
+            managed.push(ManagedSetting {
+                key: key.to_string(),
+                source: OverrideSource::Env,
+                env_var: Some(var),
+            });
+        }
+    }
+
+    (settings, managed)
+}
+
+fn system_env(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// 在 `settings.json` 的内容上应用 `settings.toml` 与环境变量覆盖
+pub(crate) fn apply(base: Value) -> AppSettings {
+    apply_layer(base, read_toml(&settings_toml_path()), &system_env).0
+}
+
+/// 当前生效的覆盖项（含代理监听）
+pub fn managed_settings() -> Vec<ManagedSetting> {
+    let base = serde_json::to_value(AppSettings::default()).unwrap_or(Value::Null);
+    let file = read_toml(&settings_toml_path());
+    let proxy_file = file.as_ref().and_then(|map| map.get(PROXY_TABLE)).cloned();
+    let mut managed = apply_layer(base, file, &system_env).1;
+    managed.extend(proxy_managed(proxy_file.as_ref(), &system_env));
+    managed
+}
+
+fn proxy_managed(
+    file: Option<&Value>,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Vec<ManagedSetting> {
+    let from_file = resolve_proxy(file, &|_| None);
+    let resolved = resolve_proxy(file, env);
+    let mut managed = Vec::new();
+    let mut push = |key: &str, present: bool, from_env: bool, var: &str| {
+        if present {
+            managed.push(ManagedSetting {
+                key: format!("{PROXY_TABLE}.{key}"),
+                source: if from_env {
+                    OverrideSource::Env
+                } else {
+                    OverrideSource::File
+                },
+                env_var: from_env.then(|| var.to_string()),
+            });
+        }
+    };
+    push(
+        "listenAddress",
+        resolved.address.is_some(),
+        resolved.address != from_file.address,
+        ENV_PROXY_ADDRESS,
+    );
+    push(
+        "listenPort",
+        resolved.port.is_some(),
+        resolved.port != from_file.port,
+        ENV_PROXY_PORT,
+    );
+    managed
+}
+
+fn resolve_proxy(
+    file: Option<&Value>,
+    env: &dyn Fn(&str) -> Option<String>,
+) -> ProxyListenOverride {
+    let mut result = ProxyListenOverride {
+        address: file
+            .and_then(|proxy| proxy.get("listenAddress"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        port: file
+            .and_then(|proxy| proxy.get("listenPort"))
+            .and_then(Value::as_u64)
+            .and_then(|port| u16::try_from(port).ok()),
+    };
+    if let Some(address) = env(ENV_PROXY_ADDRESS) {
+        result.address = Some(address.trim().to_string());
+    }
+    if let Some(port) = env(ENV_PROXY_PORT) {
+        match port.trim().parse::<u16>() {
+            Ok(port) => result.port = Some(port),
+            Err(_) => {
+                log::warn!("[SettingsOverrides] {ENV_PROXY_PORT}={port} 不是有效端口，已忽略")
+            }
+        }
+    }
+    result
+}
+
+/// 代理监听地址 / 端口覆盖，在读取代理配置时应用
+pub fn proxy_listen_override() -> ProxyListenOverride {
+    let file = read_toml(&settings_toml_path());
+    resolve_proxy(
+        file.as_ref().and_then(|map| map.get(PROXY_TABLE)),
+        &system_env,
+    )
+}
+
+pub fn apply_proxy_listen(address: &mut String, port: &mut u16) {
+    let overrides = proxy_listen_override();
+    if let Some(value) = overrides.address {
+        *address = value;
+    }
+    if let Some(value) = overrides.port {
+        *port = value;
+    }
+}
+
+/// 界面保存设置时，被覆盖的字段保留 `settings.json` 中原来的值，避免把覆盖值固化到文件
+pub(crate) fn restore_unmanaged(value: &mut Value, on_disk: Option<&Value>) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    for item in managed_settings() {
+        if item.key.starts_with("proxy.") {
+            continue;
+        }
+        match on_disk.and_then(|disk| disk.get(&item.key)) {
+            Some(original) => {
+                map.insert(item.key, original.clone());
+            }
+            None => {
+                map.remove(&item.key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn env_of(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    fn toml_table(text: &str) -> Map<String, Value> {
+        match serde_json::to_value(toml::from_str::<toml::Table>(text).unwrap()).unwrap() {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn env_var_names_follow_field_names() {
+        assert_eq!(
+            env_var_name("claudeConfigDir"),
+            "CC_SWITCH_CLAUDE_CONFIG_DIR"
+        );
+        assert_eq!(env_var_name("language"), "CC_SWITCH_LANGUAGE");
+    }
+
+    #[test]
+    fn env_overrides_file_which_overrides_json() {
+        let base = json!({ "language": "zh", "offlineMode": false });
+        let file = toml_table(
+            r#"
+            language = "ja"
+            backupIntervalHours = 12
+            "#,
+        );
+        let env = env_of(&[
+            ("CC_SWITCH_LANGUAGE", "en"),
+            ("CC_SWITCH_OFFLINE_MODE", "true"),
+            ("CC_SWITCH_CODEX_CONFIG_DIR", "/srv/codex"),
+        ]);
+        let (settings, managed) = apply_layer(base, Some(file), &env);
+
+        assert_eq!(settings.language.as_deref(), Some("en"));
+        assert!(settings.offline_mode);
+        assert_eq!(settings.backup_interval_hours, Some(12));
+        assert_eq!(settings.codex_config_dir.as_deref(), Some("/srv/codex"));
+
+        let language = managed.iter().find(|item| item.key == "language").unwrap();
+        assert_eq!(language.source, OverrideSource::Env);
+        assert_eq!(language.env_var.as_deref(), Some("CC_SWITCH_LANGUAGE"));
+        assert!(managed
+            .iter()
+            .any(|item| item.key == "backupIntervalHours" && item.source == OverrideSource::File));
+    }
+
+    #[test]
+    fn invalid_override_is_ignored_without_losing_other_settings() {
+        let base = json!({ "language": "zh", "showInTray": false });
+        let env = env_of(&[("CC_SWITCH_BACKUP_INTERVAL_HOURS", "daily")]);
+        let (settings, managed) = apply_layer(base, None, &env);
+
+        assert_eq!(settings.language.as_deref(), Some("zh"));
+        assert!(!settings.show_in_tray);
+        assert_eq!(settings.backup_interval_hours, None);
+        assert!(managed.is_empty());
+    }
+
+    #[test]
+    fn proxy_port_from_env_beats_file() {
+        let file = json!({ "listenAddress": "0.0.0.0", "listenPort": 16000 });
+        let env = env_of(&[("CC_SWITCH_PROXY_PORT", "17000")]);
+        assert_eq!(
+            resolve_proxy(Some(&file), &env),
+            ProxyListenOverride {
+                address: Some("0.0.0.0".to_string()),
+                port: Some(17000),
+            }
+        );
+
+        let managed = proxy_managed(Some(&file), &env);
+        assert_eq!(managed[0].source, OverrideSource::File);
+        assert_eq!(managed[1].key, "proxy.listenPort");
+        assert_eq!(managed[1].env_var.as_deref(), Some("CC_SWITCH_PROXY_PORT"));
+    }
+}
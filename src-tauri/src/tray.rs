@@ -59,6 +59,8 @@ pub struct TrayTexts {
     pub _auto_label: &'static str,
     pub projects_label: &'static str,
     pub no_project_label: &'static str,
+    pub macros_label: &'static str,
+    pub reload_macros_label: &'static str,
 }
 
 /// 将系统区域标识映射为托盘支持的语言码。
@@ -108,6 +110,8 @@ impl TrayTexts {
                 _auto_label: "Auto (Failover)",
                 projects_label: "Projects",
                 no_project_label: "No project",
+                macros_label: "Macros",
+                reload_macros_label: "Reload macros",
             },
             "ja" => Self {
                 show_main: "メインウィンドウを開く",
@@ -118,6 +122,8 @@ impl TrayTexts {
                 _auto_label: "自動 (フェイルオーバー)",
                 projects_label: "プロジェクト",
                 no_project_label: "プロジェクトを使用しない",
+                macros_label: "マクロ",
+                reload_macros_label: "マクロを再読み込み",
             },
             "zh-TW" => Self {
                 show_main: "開啟主介面",
@@ -128,6 +134,8 @@ impl TrayTexts {
                 _auto_label: "自動 (故障轉移)",
                 projects_label: "專案",
                 no_project_label: "不使用專案",
+                macros_label: "場景巨集",
+                reload_macros_label: "重新載入巨集",
             },
             _ => Self {
                 show_main: "打开主界面",
@@ -138,6 +146,8 @@ impl TrayTexts {
                 _auto_label: "自动 (故障转移)",
                 projects_label: "项目",
                 no_project_label: "不使用项目",
+                macros_label: "场景宏",
+                reload_macros_label: "重新加载宏",
             },
         }
     }
@@ -155,6 +165,8 @@ pub struct TrayAppSection {
 /// Auto 菜单项后缀
 pub const AUTO_SUFFIX: &str = "auto";
 pub const TRAY_ID: &str = "cc-switch";
+/// 场景宏菜单项 id 前缀（`macro_run_<宏名>`）
+const MACRO_PREFIX: &str = "macro_run_";
 
 pub const TRAY_SECTIONS: [TrayAppSection; 4] = [
     TrayAppSection {
@@ -610,6 +622,11 @@ fn handle_provider_click(
         // 切换供应商。需要本地路由的供应商也不在这里自动启动代理，
         // 由用户在页面/设置中手动开启。
         crate::services::ProviderService::switch(app_state.inner(), app_type.clone(), provider_id)?;
+        crate::services::endpoint_pool::EndpointPoolService::spawn_on_switch(
+            app,
+            app_type.clone(),
+            provider_id.to_string(),
+        );
 
         // 更新托盘菜单
         if let Ok(new_menu) = create_tray_menu(app, app_state.inner()) {
@@ -843,6 +860,44 @@ pub fn create_tray_menu(
         }
     }
 
+    // 场景宏子菜单：有宏文件时显示，末尾提供"重新加载"（同时重新注册快捷键）
+    let macros = crate::services::scenario::MacroService::list();
+    if !macros.is_empty() {
+        let mut macros_builder =
+            SubmenuBuilder::with_id(app, "submenu_macros", tray_texts.macros_label);
+        for scenario in &macros {
+            let label = match scenario.description.as_deref() {
+                Some(description) if !description.is_empty() => {
+                    format!("{} · {description}", scenario.name)
+                }
+                _ => scenario.name.clone(),
+            };
+            let item = MenuItem::with_id(
+                app,
+                format!("{MACRO_PREFIX}{}", scenario.name),
+                &label,
+                true,
+                None::<&str>,
+            )
+            .map_err(|e| AppError::Message(format!("创建宏菜单项失败: {e}")))?;
+            macros_builder = macros_builder.item(&item);
+        }
+        let reload_item = MenuItem::with_id(
+            app,
+            "macro_reload",
+            tray_texts.reload_macros_label,
+            true,
+            None::<&str>,
+        )
+        .map_err(|e| AppError::Message(format!("创建重新加载宏菜单失败: {e}")))?;
+        let macros_submenu = macros_builder
+            .separator()
+            .item(&reload_item)
+            .build()
+            .map_err(|e| AppError::Message(format!("构建宏子菜单失败: {e}")))?;
+        menu_builder = menu_builder.item(&macros_submenu).separator();
+    }
+
     let lightweight_item = CheckMenuItem::with_id(
         app,
         "lightweight_mode",
@@ -987,7 +1042,16 @@ pub fn handle_tray_menu_event(app: &tauri::AppHandle, event_id: &str) {
             log::info!("退出应用");
             app.exit(0);
         }
+        "macro_reload" => {
+            #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+            crate::services::scenario::register_hotkeys(app);
+            refresh_tray_menu(app);
+        }
         _ => {
+            if let Some(name) = event_id.strip_prefix(MACRO_PREFIX) {
+                crate::services::scenario::spawn_in_app(app, name.to_string());
+                return;
+            }
             if handle_profile_tray_event(app, event_id) {
                 return;
             }
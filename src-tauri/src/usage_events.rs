@@ -9,12 +9,13 @@
 //! - 200ms 防抖合并：流式响应等场景在短时间内可能写入多条日志，
 //!   合并成一次事件可避免前端连续 invalidate。
 //! - 不阻塞写入：通知失败仅记录 warn 日志，不向上传播错误。
+//! - 同一防抖任务顺带刷新状态栏文件（`status.json`）中的今日花费与实际模型。
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::OnceLock;
 use std::time::Duration;
 
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 
 /// 前端监听的事件名
 pub const EVENT_USAGE_LOG_RECORDED: &str = "usage-log-recorded";
@@ -39,6 +40,11 @@ pub fn init(handle: AppHandle) {
     }
 }
 
+/// 已注入的 AppHandle（写日志路径上需要触发切换等后续动作时使用）
+pub(crate) fn app_handle() -> Option<AppHandle> {
+    APP_HANDLE.get().cloned()
+}
+
 /// 通知前端有新的使用日志写入。
 ///
 /// 调用方**不**需要持有 AppHandle，可以从任意线程/任意写入路径调用。
@@ -67,9 +73,27 @@ pub fn notify_log_recorded() {
         if let Err(e) = handle.emit(EVENT_USAGE_LOG_RECORDED, ()) {
             log::warn!("emit {EVENT_USAGE_LOG_RECORDED} 失败: {e}");
         }
+        if let Some(state) = handle.try_state::<crate::store::AppState>() {
+            crate::services::status_file::refresh(
+                &state.db,
+                crate::services::status_file::StatusSource::Usage,
+            );
+        }
     });
 }
 
+/// 立即向前端发送一次用量相关事件（不防抖），如预算提醒。
+///
+/// AppHandle 未注入时静默放弃；发送失败仅记录 warn 日志。
+pub fn emit<S: serde::Serialize + Clone>(event: &str, payload: S) {
+    let Some(handle) = APP_HANDLE.get() else {
+        return;
+    };
+    if let Err(e) = handle.emit(event, payload) {
+        log::warn!("emit {event} 失败: {e}");
+    }
+}
+
 #[cfg(test)]
 thread_local! {
     static TEST_NOTIFY_COUNT: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
@@ -59,6 +59,7 @@ fn import_default_config_claude_persists_provider() {
         default_provider.settings_config, settings,
         "default provider should capture live settings"
     );
+    assert_eq!(default_provider.name, "Imported from live config");
 
     // 验证数据已持久化到数据库（v3.7.0+ 使用 SQLite 而非 config.json）
     let db_path = home.join(".cc-switch").join("cc-switch.db");
@@ -22,6 +22,61 @@ fn sanitize_provider_name(name: &str) -> String {
         .to_lowercase()
 }
 
+#[test]
+fn scan_live_import_candidates_reports_claude_until_imported() {
+    let _guard = test_mutex().lock().expect("acquire test mutex");
+    reset_test_fs();
+    let _home = ensure_test_home();
+
+    let settings_path = get_claude_settings_path();
+    if let Some(parent) = settings_path.parent() {
+        std::fs::create_dir_all(parent).expect("create claude settings dir");
+    }
+    let settings = json!({
+        "env": {
+            "ANTHROPIC_AUTH_TOKEN": "test-key",
+            "ANTHROPIC_BASE_URL": "https://api.test"
+        }
+    });
+    std::fs::write(
+        &settings_path,
+        serde_json::to_string_pretty(&settings).expect("serialize settings"),
+    )
+    .expect("seed claude settings.json");
+
+    let mut config = MultiAppConfig::default();
+    config.ensure_app(&AppType::Claude);
+    let state = create_test_state_with_config(&config).expect("create test state");
+
+    let candidates =
+        ProviderService::scan_live_import_candidates(&state).expect("scan live configs");
+    let claude = candidates
+        .iter()
+        .find(|c| c.app == "claude")
+        .expect("claude live config detected");
+    assert_eq!(
+        claude.files,
+        vec![settings_path.to_string_lossy().to_string()]
+    );
+    assert!(claude.importable());
+    assert!(
+        candidates.iter().all(|c| c.app != "codex"),
+        "apps without live config files should not be listed"
+    );
+
+    ProviderService::import_default_config(&state, AppType::Claude)
+        .expect("import default config succeeds");
+
+    let candidates =
+        ProviderService::scan_live_import_candidates(&state).expect("scan live configs");
+    let claude = candidates
+        .iter()
+        .find(|c| c.app == "claude")
+        .expect("claude live config still detected");
+    assert!(claude.already_configured);
+    assert!(!claude.importable());
+}
+
 #[test]
 fn migrate_legacy_common_config_usage_marks_historical_provider_enabled() {
     let _guard = test_mutex().lock().expect("acquire test mutex");
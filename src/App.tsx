@@ -44,6 +44,13 @@ import { hermesApi } from "@/lib/api/hermes";
 import { useProxyStatus } from "@/hooks/useProxyStatus";
 import { useAutoCompact } from "@/hooks/useAutoCompact";
 import { useUsageCacheBridge } from "@/hooks/useUsageCacheBridge";
+import { useSelfTestSummary } from "@/hooks/useSelfTestSummary";
+import { useNetworkRecheck } from "@/hooks/useNetworkRecheck";
+import { useProviderBudgetAlerts } from "@/hooks/useProviderBudgetAlerts";
+import { usePromptIntegrityAlerts } from "@/hooks/usePromptIntegrityAlerts";
+import { useProviderLatencySloAlerts } from "@/hooks/useProviderLatencySloAlerts";
+import { useProviderEndpointSelected } from "@/hooks/useProviderEndpointSelected";
+import { useProviderWatchdogSwitch } from "@/hooks/useProviderWatchdogSwitch";
 import { useTauriEvent } from "@/hooks/useTauriEvent";
 import { useLastValidValue } from "@/hooks/useLastValidValue";
 import { useScanUnmanagedSkills } from "@/hooks/useSkills";
@@ -60,12 +67,14 @@ import {
 import { AppSwitcher } from "@/components/AppSwitcher";
 import { ProfileSwitcher } from "@/components/profiles/ProfileSwitcher";
 import { ProviderList } from "@/components/providers/ProviderList";
+import { ProviderNoticeBanner } from "@/components/providers/ProviderNoticeBanner";
 import { AddProviderDialog } from "@/components/providers/AddProviderDialog";
 import { EditProviderDialog } from "@/components/providers/EditProviderDialog";
 import { ConfirmDialog } from "@/components/ConfirmDialog";
 import { SettingsPage } from "@/components/settings/SettingsPage";
 import { UpdateBadge } from "@/components/UpdateBadge";
 import { EnvWarningBanner } from "@/components/env/EnvWarningBanner";
+import { SafeModeBanner } from "@/components/SafeModeBanner";
 import { ProxyToggle } from "@/components/proxy/ProxyToggle";
 import { ClaudeDesktopRouteToggle } from "@/components/proxy/ClaudeDesktopRouteToggle";
 import { FailoverToggle } from "@/components/proxy/FailoverToggle";
@@ -79,6 +88,7 @@ import {
 } from "@/components/skills/SkillsPage";
 import UnifiedSkillsPanel from "@/components/skills/UnifiedSkillsPanel";
 import { DeepLinkImportDialog } from "@/components/DeepLinkImportDialog";
+import { LiveConfigDriftDialog } from "@/components/LiveConfigDriftDialog";
 import { FirstRunNoticeDialog } from "@/components/FirstRunNoticeDialog";
 import { AgentsPanel } from "@/components/agents/AgentsPanel";
 import { UniversalProviderPanel } from "@/components/universal";
@@ -241,6 +251,10 @@ function App() {
     provider: Provider;
     action: "remove" | "delete";
   } | null>(null);
+  // 切换到不受信任的供应商前的确认
+  const [untrustedSwitch, setUntrustedSwitch] = useState<Provider | null>(
+    null,
+  );
   const [envConflicts, setEnvConflicts] = useState<EnvConflict[]>([]);
   const [showEnvBanner, setShowEnvBanner] = useState(false);
 
@@ -251,6 +265,13 @@ function App() {
   const isToolbarCompact = useAutoCompact(toolbarRef);
 
   useUsageCacheBridge();
+  useSelfTestSummary();
+  useNetworkRecheck();
+  useProviderBudgetAlerts();
+  usePromptIntegrityAlerts();
+  useProviderLatencySloAlerts();
+  useProviderEndpointSelected();
+  useProviderWatchdogSwitch();
 
   const promptPanelRef = useRef<any>(null);
   const mcpPanelRef = useRef<any>(null);
@@ -378,6 +399,38 @@ function App() {
     };
   }, [activeApp, refetch]);
 
+  // live 配置或数据库被外部修改（编辑器、CLI、其它实例）时刷新供应商列表
+  useEffect(() => {
+    let unsubscribe: (() => void) | undefined;
+    let active = true;
+
+    const setupListener = async () => {
+      try {
+        const off = await providersApi.onConfigFilesChanged(async (event) => {
+          if (
+            event.scopes.includes("database") ||
+            event.scopes.some((scope) => scope === activeApp)
+          ) {
+            await refetch();
+          }
+        });
+        if (!active) {
+          off();
+          return;
+        }
+        unsubscribe = off;
+      } catch (error) {
+        console.error("[App] Failed to subscribe config watcher event", error);
+      }
+    };
+
+    void setupListener();
+    return () => {
+      active = false;
+      unsubscribe?.();
+    };
+  }, [activeApp, refetch]);
+
   useTauriEvent("universal-provider-synced", async () => {
     await queryClient.invalidateQueries({ queryKey: ["providers"] });
     try {
@@ -653,6 +706,20 @@ function App() {
     setEditingProvider(null);
   };
 
+  const handleSwitchProvider = async (provider: Provider) => {
+    const trust = await queryClient
+      .fetchQuery({
+        queryKey: ["providerTrust", activeApp],
+        queryFn: () => providersApi.getTrustLevels(activeApp),
+      })
+      .catch(() => undefined);
+    if (trust?.[provider.id]?.level === "untrusted") {
+      setUntrustedSwitch(provider);
+      return;
+    }
+    await switchProvider(provider);
+  };
+
   const handleConfirmAction = async () => {
     if (!confirmAction) return;
     const { provider, action } = confirmAction;
@@ -980,6 +1047,7 @@ function App() {
                     transition={{ duration: 0.15 }}
                     className="space-y-4"
                   >
+                    <ProviderNoticeBanner appId={activeApp} />
                     <ProviderList
                       providers={providers}
                       currentProviderId={currentProviderId}
@@ -990,7 +1058,9 @@ function App() {
                         isProxyRunning && isCurrentAppTakeoverActive
                       }
                       activeProviderId={activeProviderId}
-                      onSwitch={switchProvider}
+                      onSwitch={(provider) =>
+                        void handleSwitchProvider(provider)
+                      }
                       onEdit={(provider) => {
                         setEditingProvider(provider);
                       }}
@@ -1024,7 +1094,7 @@ function App() {
                         activeApp === "openclaw"
                           ? setAsDefaultModel
                           : activeApp === "hermes"
-                            ? switchProvider
+                            ? (provider) => void handleSwitchProvider(provider)
                             : undefined
                       }
                     />
@@ -1107,6 +1177,7 @@ function App() {
           )}
         </div>
       )}
+      <SafeModeBanner />
       {showEnvBanner && envConflicts.length > 0 && (
         <EnvWarningBanner
           conflicts={envConflicts}
@@ -1642,6 +1713,22 @@ function App() {
         onCancel={() => setConfirmAction(null)}
       />
 
+      <ConfirmDialog
+        isOpen={Boolean(untrustedSwitch)}
+        title={t("provider.trust.confirmTitle")}
+        message={t("provider.trust.confirmSwitch", {
+          name: untrustedSwitch?.name ?? "",
+        })}
+        confirmText={t("provider.trust.confirmButton")}
+        onConfirm={() => {
+          if (untrustedSwitch) {
+            void switchProvider(untrustedSwitch);
+          }
+          setUntrustedSwitch(null);
+        }}
+        onCancel={() => setUntrustedSwitch(null)}
+      />
+
       <ConfirmDialog
         isOpen={launchDashboardOpen}
         title={t("hermes.webui.launchConfirmTitle")}
@@ -1665,6 +1752,7 @@ function App() {
       />
 
       <DeepLinkImportDialog />
+      <LiveConfigDriftDialog />
       <FirstRunNoticeDialog />
     </div>
   );
@@ -0,0 +1,148 @@
+import { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useQueryClient } from "@tanstack/react-query";
+import { toast } from "sonner";
+import { FileWarning, Loader2 } from "lucide-react";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { Button } from "@/components/ui/button";
+import { useTauriEvent } from "@/hooks/useTauriEvent";
+import { providersApi } from "@/lib/api";
+import type { LiveConfigDrift, LiveDriftResolution } from "@/lib/api/providers";
+import { extractErrorMessage } from "@/utils/errorUtils";
+
+/**
+ * live 配置被外部编辑（如手改 ~/.claude/settings.json）后弹出：
+ * 展示与当前供应商的差异，让用户选择并入供应商或用供应商覆盖回去。
+ */
+export function LiveConfigDriftDialog() {
+  const { t } = useTranslation();
+  const queryClient = useQueryClient();
+  // 每个应用只保留最近一次差异，依次处理
+  const [pending, setPending] = useState<LiveConfigDrift[]>([]);
+  const [resolving, setResolving] = useState<LiveDriftResolution | null>(null);
+
+  useTauriEvent<LiveConfigDrift>("live-config-drift", (drift) => {
+    setPending((prev) => [
+      ...prev.filter((item) => item.app !== drift.app),
+      drift,
+    ]);
+  });
+
+  const drift = pending[0];
+
+  const dismiss = () => {
+    setPending((prev) => prev.slice(1));
+  };
+
+  const resolve = async (resolution: LiveDriftResolution) => {
+    if (!drift) return;
+    setResolving(resolution);
+    try {
+      await providersApi.resolveLiveConfigDrift(drift.app, resolution);
+      await queryClient.invalidateQueries({ queryKey: ["providers"] });
+      toast.success(
+        t(
+          resolution === "reimport"
+            ? "liveDrift.reimported"
+            : "liveDrift.overwritten",
+          { name: drift.providerName },
+        ),
+      );
+      dismiss();
+    } catch (error) {
+      toast.error(t("liveDrift.resolveFailed"), {
+        description: extractErrorMessage(error) || undefined,
+      });
+    } finally {
+      setResolving(null);
+    }
+  };
+
+  return (
+    <Dialog
+      open={drift != null}
+      onOpenChange={(open) => {
+        if (!open && resolving == null) dismiss();
+      }}
+    >
+      <DialogContent className="max-w-2xl" zIndex="top">
+        <DialogHeader>
+          <DialogTitle className="flex items-center gap-2">
+            <FileWarning className="h-5 w-5 text-yellow-500" />
+            {t("liveDrift.title")}
+          </DialogTitle>
+        </DialogHeader>
+        {drift && (
+          <div className="space-y-3 px-6 py-5">
+            <DialogDescription className="leading-relaxed">
+              {t("liveDrift.description", {
+                app: t(`apps.${drift.app}`),
+                name: drift.providerName,
+              })}
+            </DialogDescription>
+            {drift.liveOnly && (
+              <div className="space-y-1">
+                <div className="text-xs font-medium">
+                  {t("liveDrift.liveOnly")}
+                </div>
+                <pre className="max-h-48 overflow-auto rounded-md bg-muted p-3 font-mono text-xs">
+                  {drift.liveOnly}
+                </pre>
+              </div>
+            )}
+            {drift.storedOnly && (
+              <div className="space-y-1">
+                <div className="text-xs font-medium">
+                  {t("liveDrift.storedOnly")}
+                </div>
+                <pre className="max-h-48 overflow-auto rounded-md bg-muted p-3 font-mono text-xs">
+                  {drift.storedOnly}
+                </pre>
+              </div>
+            )}
+            {drift.authChanged && (
+              <p className="text-xs text-muted-foreground">
+                {t("liveDrift.authChanged")}
+              </p>
+            )}
+          </div>
+        )}
+        <DialogFooter>
+          <Button
+            variant="outline"
+            onClick={dismiss}
+            disabled={resolving != null}
+          >
+            {t("liveDrift.ignore")}
+          </Button>
+          <Button
+            variant="outline"
+            onClick={() => void resolve("overwrite")}
+            disabled={resolving != null}
+          >
+            {resolving === "overwrite" && (
+              <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+            )}
+            {t("liveDrift.overwrite")}
+          </Button>
+          <Button
+            onClick={() => void resolve("reimport")}
+            disabled={resolving != null}
+          >
+            {resolving === "reimport" && (
+              <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+            )}
+            {t("liveDrift.reimport")}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -15,6 +15,7 @@ interface ProviderIconProps {
   size?: number | string; // 尺寸
   className?: string;
   showFallback?: boolean; // 是否显示 fallback
+  fallbackUrl?: string; // 没有可用图标时优先于首字母显示（如缓存的站点图标）
 }
 
 export const ProviderIcon: React.FC<ProviderIconProps> = ({
@@ -24,6 +25,7 @@ export const ProviderIcon: React.FC<ProviderIconProps> = ({
   size = 32,
   className,
   showFallback = true,
+  fallbackUrl,
 }) => {
   // 获取内联 SVG 字符串
   const iconSvg = useMemo(() => {
@@ -38,8 +40,11 @@ export const ProviderIcon: React.FC<ProviderIconProps> = ({
     if (icon && isUrlIcon(icon)) {
       return getIconUrl(icon);
     }
+    if (!iconSvg && fallbackUrl) {
+      return fallbackUrl;
+    }
     return "";
-  }, [icon]);
+  }, [icon, iconSvg, fallbackUrl]);
 
   // 计算尺寸样式
   const sizeStyle = useMemo(() => {
@@ -0,0 +1,29 @@
+import { useQuery } from "@tanstack/react-query";
+import { useTranslation } from "react-i18next";
+import { ShieldAlert } from "lucide-react";
+import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert";
+import { settingsApi } from "@/lib/api";
+
+/** 安全模式提示：代理自动启动、后台同步与定时任务均已跳过 */
+export function SafeModeBanner() {
+  const { t } = useTranslation();
+  const { data: safeMode } = useQuery({
+    queryKey: ["safe-mode"],
+    queryFn: () => settingsApi.isSafeMode(),
+    staleTime: Infinity,
+  });
+
+  if (!safeMode) {
+    return null;
+  }
+
+  return (
+    <div className="px-6 pt-4">
+      <Alert className="border-amber-500/30 bg-amber-500/5">
+        <ShieldAlert className="h-4 w-4" />
+        <AlertTitle>{t("safeMode.title")}</AlertTitle>
+        <AlertDescription>{t("safeMode.description")}</AlertDescription>
+      </Alert>
+    </div>
+  );
+}
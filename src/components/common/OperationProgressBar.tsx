@@ -0,0 +1,46 @@
+import { useTranslation } from "react-i18next";
+import { cn } from "@/lib/utils";
+import type { OperationProgress } from "@/lib/api/progress";
+
+interface OperationProgressBarProps {
+  progress: OperationProgress;
+  className?: string;
+}
+
+/**
+ * 长时间操作的进度条
+ * percent 为 null 时显示不确定进度动画
+ */
+export function OperationProgressBar({
+  progress,
+  className,
+}: OperationProgressBarProps) {
+  const { t } = useTranslation();
+  const phase = t(`progress.phase.${progress.phase}`, {
+    defaultValue: progress.phase,
+  });
+
+  return (
+    <div className={cn("w-full space-y-1.5", className)}>
+      <div className="flex items-center justify-between gap-3 text-xs text-muted-foreground">
+        <span className="truncate">
+          {progress.message ? `${phase} · ${progress.message}` : phase}
+        </span>
+        {progress.percent !== null && <span>{progress.percent}%</span>}
+      </div>
+      <div className="h-1.5 w-full overflow-hidden rounded-full bg-muted">
+        <div
+          className={cn(
+            "h-full rounded-full bg-primary transition-[width] duration-200",
+            progress.percent === null && "w-1/3 animate-pulse",
+          )}
+          style={
+            progress.percent === null
+              ? undefined
+              : { width: `${progress.percent}%` }
+          }
+        />
+      </div>
+    </div>
+  );
+}
@@ -23,6 +23,7 @@ import { normalizeTomlText } from "@/utils/textNormalization";
 import { parseSmartMcpJson } from "@/utils/formatters";
 import { useMcpValidation } from "./useMcpValidation";
 import { useUpsertMcpServer } from "@/hooks/useMcp";
+import { mcpApi } from "@/lib/api";
 import { FullScreenPanel } from "@/components/common/FullScreenPanel";
 
 interface McpFormModalProps {
@@ -88,6 +89,11 @@ const McpFormModal: React.FC<McpFormModalProps> = ({
   });
 
   const isEditing = !!editingId;
+  // 新增的 stdio 服务器默认先校验能否启动，再写入各应用配置
+  const [verifyRuntime, setVerifyRuntime] = useState(!isEditing);
+  const [runtimeStep, setRuntimeStep] = useState<
+    "checking" | "preparing" | "verifying" | null
+  >(null);
 
   const hasAdditionalInfo = !!(
     initialData?.description ||
@@ -287,6 +293,38 @@ const McpFormModal: React.FC<McpFormModalProps> = ({
     }
   };
 
+  // 检测运行时、准备依赖并试启动；失败时返回原因
+  const verifyServerStarts = async (
+    spec: McpServerSpec,
+  ): Promise<string | null> => {
+    try {
+      setRuntimeStep("checking");
+      const check = await mcpApi.checkRuntime(spec);
+      if (!check.runtime.available) {
+        return t("mcp.runtime.missing", {
+          command: check.runtime.command,
+          url: check.installUrl ?? "",
+        });
+      }
+      if (check.prepareCommand) {
+        setRuntimeStep("preparing");
+        try {
+          await mcpApi.prepareRuntime(spec);
+        } catch (error) {
+          // 准备失败不阻断：启动时运行时会自行下载
+          console.warn("[MCP] prepare runtime failed", error);
+        }
+      }
+      setRuntimeStep("verifying");
+      const result = await mcpApi.verifyServer(spec);
+      return result.ok ? null : result.error || t("mcp.runtime.startFailed");
+    } catch (error) {
+      return extractErrorMessage(error) || t("mcp.runtime.startFailed");
+    } finally {
+      setRuntimeStep(null);
+    }
+  };
+
   const handleSubmit = async () => {
     const trimmedId = formId.trim();
     if (!trimmedId) {
@@ -362,12 +400,28 @@ const McpFormModal: React.FC<McpFormModalProps> = ({
       const nameTrimmed = (formName || trimmedId).trim();
       const finalName = nameTrimmed || trimmedId;
 
+      let apps = enabledApps;
+      let verifyFailure: string | null = null;
+      if (
+        verifyRuntime &&
+        (serverSpec.type ?? "stdio") === "stdio" &&
+        Object.values(enabledApps).some(Boolean)
+      ) {
+        verifyFailure = await verifyServerStarts(serverSpec);
+        if (verifyFailure) {
+          // 起不来的服务器只保存，不写入各应用配置
+          apps = Object.fromEntries(
+            Object.keys(enabledApps).map((app) => [app, false]),
+          ) as typeof enabledApps;
+        }
+      }
+
       const entry: McpServer = {
         ...(initialData ? { ...initialData } : {}),
         id: trimmedId,
         name: finalName,
         server: serverSpec,
-        apps: enabledApps,
+        apps,
       };
 
       const descriptionTrimmed = formDescription.trim();
@@ -402,7 +456,14 @@ const McpFormModal: React.FC<McpFormModalProps> = ({
       }
 
       await upsertMutation.mutateAsync(entry);
-      toast.success(t("common.success"), { closeButton: true });
+      if (verifyFailure) {
+        toast.warning(t("mcp.runtime.savedDisabled", { error: verifyFailure }), {
+          duration: 8000,
+          closeButton: true,
+        });
+      } else {
+        toast.success(t("common.success"), { closeButton: true });
+      }
       await onSave();
     } catch (error: any) {
       const detail = extractErrorMessage(error);
@@ -433,7 +494,9 @@ const McpFormModal: React.FC<McpFormModalProps> = ({
           >
             {isEditing ? <Save size={16} /> : <Plus size={16} />}
             {saving
-              ? t("common.saving")
+              ? runtimeStep
+                ? t(`mcp.runtime.${runtimeStep}`)
+                : t("common.saving")
               : isEditing
                 ? t("common.save")
                 : t("common.add")}
@@ -621,6 +684,29 @@ const McpFormModal: React.FC<McpFormModalProps> = ({
               </div>
             </div>
 
+            {!isEditing && (
+              <div className="flex items-start gap-2">
+                <Checkbox
+                  id="verify-runtime"
+                  checked={verifyRuntime}
+                  onCheckedChange={(checked) =>
+                    setVerifyRuntime(checked === true)
+                  }
+                />
+                <div className="space-y-0.5">
+                  <label
+                    htmlFor="verify-runtime"
+                    className="text-sm text-foreground cursor-pointer select-none"
+                  >
+                    {t("mcp.runtime.verifyBeforeEnable")}
+                  </label>
+                  <p className="text-xs text-muted-foreground">
+                    {t("mcp.runtime.verifyHint")}
+                  </p>
+                </div>
+              </div>
+            )}
+
             {/* 可折叠的附加信息按钮 */}
             <div>
               <button
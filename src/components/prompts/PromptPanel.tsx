@@ -6,6 +6,7 @@ import { usePromptActions } from "@/hooks/usePromptActions";
 import { useTauriEvent } from "@/hooks/useTauriEvent";
 import PromptListItem from "./PromptListItem";
 import PromptFormPanel from "./PromptFormPanel";
+import PromptVersionsDialog from "./PromptVersionsDialog";
 import { ConfirmDialog } from "../ConfirmDialog";
 
 interface PromptPanelProps {
@@ -23,6 +24,7 @@ const PromptPanel = React.forwardRef<PromptPanelHandle, PromptPanelProps>(
     const { t } = useTranslation();
     const [isFormOpen, setIsFormOpen] = useState(false);
     const [editingId, setEditingId] = useState<string | null>(null);
+    const [historyId, setHistoryId] = useState<string | null>(null);
     const [confirmDialog, setConfirmDialog] = useState<{
       isOpen: boolean;
       titleKey: string;
@@ -136,6 +138,7 @@ const PromptPanel = React.forwardRef<PromptPanelHandle, PromptPanelProps>(
                   prompt={prompt}
                   onToggle={toggleEnabled}
                   onEdit={handleEdit}
+                  onHistory={setHistoryId}
                   onDelete={handleDelete}
                 />
               ))}
@@ -153,6 +156,16 @@ const PromptPanel = React.forwardRef<PromptPanelHandle, PromptPanelProps>(
           />
         )}
 
+        {historyId && prompts[historyId] && (
+          <PromptVersionsDialog
+            appId={appId}
+            promptId={historyId}
+            promptName={prompts[historyId].name}
+            onDeployed={reload}
+            onClose={() => setHistoryId(null)}
+          />
+        )}
+
         {confirmDialog && (
           <ConfirmDialog
             isOpen={confirmDialog.isOpen}
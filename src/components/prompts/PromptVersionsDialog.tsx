@@ -0,0 +1,237 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { Loader2 } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import {
+  Dialog,
+  DialogContent,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import {
+  promptsApi,
+  type PromptDiff,
+  type PromptVersion,
+} from "@/lib/api/prompts";
+import type { AppId } from "@/lib/api";
+import { cn } from "@/lib/utils";
+
+/** 可部署的目标应用及其提示词文件 */
+const TARGET_FILES: Partial<Record<AppId, string>> = {
+  claude: "CLAUDE.md",
+  codex: "AGENTS.md",
+  gemini: "GEMINI.md",
+  grokbuild: "AGENTS.md",
+  opencode: "AGENTS.md",
+  hermes: "AGENTS.md",
+};
+
+interface PromptVersionsDialogProps {
+  appId: AppId;
+  promptId: string;
+  promptName: string;
+  onDeployed: () => void;
+  onClose: () => void;
+}
+
+const PromptVersionsDialog: React.FC<PromptVersionsDialogProps> = ({
+  appId,
+  promptId,
+  promptName,
+  onDeployed,
+  onClose,
+}) => {
+  const { t } = useTranslation();
+  const [versions, setVersions] = useState<PromptVersion[]>([]);
+  const [selected, setSelected] = useState<number | null>(null);
+  const [targetApp, setTargetApp] = useState<AppId>(appId);
+  const [diff, setDiff] = useState<PromptDiff | null>(null);
+  const [loadingDiff, setLoadingDiff] = useState(false);
+  const [deploying, setDeploying] = useState(false);
+
+  useEffect(() => {
+    promptsApi
+      .getVersions(appId, promptId)
+      .then((list) => {
+        setVersions(list);
+        setSelected(list[0]?.version ?? null);
+      })
+      .catch((error) => toast.error(String(error)));
+  }, [appId, promptId]);
+
+  useEffect(() => {
+    if (selected === null) return;
+    setLoadingDiff(true);
+    promptsApi
+      .previewVersion(appId, promptId, selected, targetApp)
+      .then(setDiff)
+      .catch((error) => {
+        setDiff(null);
+        toast.error(String(error));
+      })
+      .finally(() => setLoadingDiff(false));
+  }, [appId, promptId, selected, targetApp]);
+
+  const handleDeploy = async () => {
+    if (selected === null) return;
+    setDeploying(true);
+    try {
+      await promptsApi.deployVersion(appId, promptId, selected, targetApp);
+      toast.success(
+        t("prompts.versions.deploySuccess", {
+          version: selected,
+          file: TARGET_FILES[targetApp],
+          app: t(`apps.${targetApp}`),
+        }),
+        { closeButton: true },
+      );
+      onDeployed();
+      onClose();
+    } catch (error) {
+      toast.error(t("prompts.versions.deployFailed"), {
+        description: String(error),
+      });
+    } finally {
+      setDeploying(false);
+    }
+  };
+
+  const latest = versions[0]?.version;
+  const unchanged = diff !== null && diff.added === 0 && diff.removed === 0;
+
+  return (
+    <Dialog open onOpenChange={onClose}>
+      <DialogContent className="max-w-4xl max-h-[85vh] flex flex-col">
+        <DialogHeader>
+          <DialogTitle>
+            {t("prompts.versions.title", { name: promptName })}
+          </DialogTitle>
+        </DialogHeader>
+
+        <div className="flex flex-1 min-h-0 gap-4 px-6 py-4">
+          <div className="w-48 flex-shrink-0 space-y-1 overflow-y-auto">
+            {versions.map((item) => (
+              <button
+                key={item.version}
+                type="button"
+                onClick={() => setSelected(item.version)}
+                className={cn(
+                  "w-full rounded-lg px-3 py-2 text-left text-sm transition-colors",
+                  selected === item.version
+                    ? "bg-primary/10 text-primary"
+                    : "hover:bg-muted",
+                )}
+              >
+                <div className="font-medium">
+                  v{item.version}
+                  {item.version === latest && (
+                    <span className="ml-2 text-xs text-muted-foreground">
+                      {t("prompts.versions.latest")}
+                    </span>
+                  )}
+                </div>
+                <div className="text-xs text-muted-foreground">
+                  {new Date(item.createdAt * 1000).toLocaleString()}
+                </div>
+              </button>
+            ))}
+          </div>
+
+          <div className="flex flex-1 min-w-0 flex-col gap-3">
+            <div className="flex items-center justify-between gap-3">
+              <Select
+                value={targetApp}
+                onValueChange={(value) => setTargetApp(value as AppId)}
+              >
+                <SelectTrigger className="w-56">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {Object.entries(TARGET_FILES).map(([app, file]) => (
+                    <SelectItem key={app} value={app}>
+                      {t(`apps.${app}`)} · {file}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+              {diff && (
+                <span className="text-xs text-muted-foreground">
+                  {t("prompts.versions.diffStats", {
+                    added: diff.added,
+                    removed: diff.removed,
+                  })}
+                </span>
+              )}
+            </div>
+
+            <div className="flex-1 overflow-auto rounded-lg border border-border-default bg-muted/30 font-mono text-xs">
+              {loadingDiff ? (
+                <div className="flex justify-center py-8">
+                  <Loader2 className="h-4 w-4 animate-spin" />
+                </div>
+              ) : unchanged ? (
+                <div className="py-8 text-center text-muted-foreground">
+                  {t("prompts.versions.noChanges")}
+                </div>
+              ) : (
+                diff?.lines.map((line, index) => (
+                  <div
+                    key={index}
+                    className={cn(
+                      "whitespace-pre-wrap px-3",
+                      line.kind === "added" &&
+                        "bg-green-500/10 text-green-700 dark:text-green-400",
+                      line.kind === "removed" &&
+                        "bg-red-500/10 text-red-700 dark:text-red-400",
+                    )}
+                  >
+                    {line.kind === "added"
+                      ? "+ "
+                      : line.kind === "removed"
+                        ? "- "
+                        : "  "}
+                    {line.text}
+                  </div>
+                ))
+              )}
+            </div>
+            {diff && (
+              <p className="text-xs text-muted-foreground truncate">
+                {diff.targetPath}
+              </p>
+            )}
+          </div>
+        </div>
+
+        <DialogFooter>
+          <Button type="button" variant="outline" onClick={onClose}>
+            {t("common.cancel")}
+          </Button>
+          <Button
+            type="button"
+            onClick={() => void handleDeploy()}
+            disabled={selected === null || deploying}
+          >
+            {deploying && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+            {selected !== null && selected !== latest && targetApp === appId
+              ? t("prompts.versions.rollback", { version: selected })
+              : t("prompts.versions.deploy", {
+                  file: TARGET_FILES[targetApp],
+                })}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+};
+
+export default PromptVersionsDialog;
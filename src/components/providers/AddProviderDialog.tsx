@@ -1,13 +1,15 @@
 import { useCallback, useState } from "react";
 import { useTranslation } from "react-i18next";
-import { Plus } from "lucide-react";
+import { ClipboardPaste, Plus } from "lucide-react";
 import { toast } from "sonner";
+import { emit } from "@tauri-apps/api/event";
 import { Button } from "@/components/ui/button";
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
 import { FullScreenPanel } from "@/components/common/FullScreenPanel";
 import type { Provider, CustomEndpoint, UniversalProvider } from "@/types";
 import type { AppId } from "@/lib/api";
 import { universalProvidersApi } from "@/lib/api";
+import { deeplinkApi } from "@/lib/api/deeplink";
 import {
   ProviderForm,
   type ProviderFormValues,
@@ -319,12 +321,35 @@ export function AddProviderDialog({
     [appId, onSubmit, onOpenChange],
   );
 
+  // 粘贴即添加：识别剪贴板中的地址与 Key，交给深链接导入确认框
+  const handlePasteToAdd = useCallback(async () => {
+    try {
+      const text = await deeplinkApi.readClipboardText();
+      const pasted = await deeplinkApi.parseProviderPaste(text);
+      onOpenChange(false);
+      await emit("deeplink-import", pasted.request);
+    } catch (error) {
+      toast.error(t("provider.pasteToAddFailed"), {
+        description: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }, [onOpenChange, t]);
+
   const footer =
     !showUniversalTab || activeTab === "app-specific" ? (
       <>
         <span className="mr-auto min-w-0 text-xs text-muted-foreground truncate">
           {t("provider.addFooterHint")}
         </span>
+        <Button
+          variant="outline"
+          onClick={handlePasteToAdd}
+          title={t("provider.pasteToAddHint")}
+          className="border-border/20 hover:bg-accent hover:text-accent-foreground"
+        >
+          <ClipboardPaste className="h-4 w-4 mr-2" />
+          {t("provider.pasteToAdd")}
+        </Button>
         <Button
           variant="outline"
           onClick={() => onOpenChange(false)}
@@ -8,6 +8,8 @@ import {
   ProviderForm,
   type ProviderFormValues,
 } from "@/components/providers/forms/ProviderForm";
+import { ProviderCapabilityMatrix } from "@/components/providers/ProviderCapabilityMatrix";
+import { ProviderTestMessagePanel } from "@/components/providers/ProviderTestMessagePanel";
 import { openclawApi, providersApi, vscodeApi, type AppId } from "@/lib/api";
 
 interface EditProviderDialogProps {
@@ -221,6 +223,12 @@ export function EditProviderDialog({
     return null;
   }
 
+  // 与"复制为 curl"口径一致：官方供应商与累加模式应用无法直接构造上游请求
+  const canProbeCapabilities =
+    ["claude", "claude-desktop", "codex", "gemini", "grokbuild"].includes(
+      appId,
+    ) && provider.category !== "official";
+
   return (
     <FullScreenPanel
       isOpen={open}
@@ -249,6 +257,12 @@ export function EditProviderDialog({
         showButtons={false}
         isProxyTakeover={isProxyTakeover}
       />
+      {canProbeCapabilities && (
+        <>
+          <ProviderTestMessagePanel appId={appId} providerId={provider.id} />
+          <ProviderCapabilityMatrix appId={appId} providerId={provider.id} />
+        </>
+      )}
     </FullScreenPanel>
   );
 }
@@ -2,12 +2,15 @@ import {
   Activity,
   BarChart3,
   Check,
+  Code,
   Copy,
   Edit,
   Loader2,
   Minus,
   Play,
   Plus,
+  ShieldAlert,
+  ShieldCheck,
   Terminal,
   Trash2,
   Zap,
@@ -28,11 +31,15 @@ interface ProviderActionsProps {
   onEdit: () => void;
   onDuplicate: () => void;
   onTest?: () => void;
+  onCopyCurl?: () => void;
   onConfigureUsage?: () => void;
   onDelete: () => void;
   onRemoveFromConfig?: () => void;
   onDisableOmo?: () => void;
   onOpenTerminal?: () => void;
+  // 信任标注：isUntrusted 为当前判定结果，点击切换标注
+  isUntrusted?: boolean;
+  onToggleTrust?: () => void;
   isAutoFailoverEnabled?: boolean;
   isInFailoverQueue?: boolean;
   onToggleFailover?: (enabled: boolean) => void;
@@ -67,11 +74,14 @@ export function ProviderActions({
   onEdit,
   onDuplicate,
   onTest,
+  onCopyCurl,
   onConfigureUsage,
   onDelete,
   onRemoveFromConfig,
   onDisableOmo,
   onOpenTerminal,
+  isUntrusted = false,
+  onToggleTrust,
   isAutoFailoverEnabled = false,
   isInFailoverQueue = false,
   onToggleFailover,
@@ -323,6 +333,18 @@ export function ProviderActions({
           )}
         </Button>
 
+        {onCopyCurl && (
+          <Button
+            size="icon"
+            variant="ghost"
+            onClick={onCopyCurl}
+            title={t("provider.copyAsCurl")}
+            className={iconButtonClass}
+          >
+            <Code className="h-4 w-4" />
+          </Button>
+        )}
+
         <Button
           size="icon"
           variant="ghost"
@@ -352,6 +374,31 @@ export function ProviderActions({
           </Button>
         )}
 
+        {onToggleTrust && (
+          <Button
+            size="icon"
+            variant="ghost"
+            onClick={onToggleTrust}
+            title={
+              isUntrusted
+                ? t("provider.trust.markTrusted")
+                : t("provider.trust.markUntrusted")
+            }
+            className={cn(
+              iconButtonClass,
+              isUntrusted
+                ? "text-red-500 hover:text-emerald-600 dark:hover:text-emerald-400"
+                : "hover:text-red-500 dark:hover:text-red-400",
+            )}
+          >
+            {isUntrusted ? (
+              <ShieldAlert className="h-4 w-4" />
+            ) : (
+              <ShieldCheck className="h-4 w-4" />
+            )}
+          </Button>
+        )}
+
         <Button
           size="icon"
           variant="ghost"
@@ -0,0 +1,160 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { AlertTriangle, Loader2, Radar } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { cn } from "@/lib/utils";
+import { providersApi, type AppId } from "@/lib/api";
+import type {
+  CapabilityMatrix,
+  CapabilitySupport,
+  PromptIntegritySignal,
+} from "@/lib/api/providers";
+import { extractErrorMessage } from "@/utils/errorUtils";
+
+interface ProviderCapabilityMatrixProps {
+  appId: AppId;
+  providerId: string;
+}
+
+const CAPABILITIES = [
+  "streaming",
+  "tools",
+  "vision",
+  "cacheControl",
+  "context1m",
+] as const;
+
+const SUPPORT_CLASS: Record<CapabilitySupport, string> = {
+  supported: "text-emerald-600 dark:text-emerald-400",
+  unsupported: "text-red-600 dark:text-red-400",
+  unknown: "text-muted-foreground",
+};
+
+export function ProviderCapabilityMatrix({
+  appId,
+  providerId,
+}: ProviderCapabilityMatrixProps) {
+  const { t } = useTranslation();
+  const [matrix, setMatrix] = useState<CapabilityMatrix | null>(null);
+  const [isProbing, setIsProbing] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [integrity, setIntegrity] = useState<PromptIntegritySignal | null>(
+    null,
+  );
+
+  useEffect(() => {
+    providersApi
+      .getCapabilities(providerId, appId)
+      .then(setMatrix)
+      .catch((e) =>
+        console.error("[ProviderCapabilityMatrix] Failed to load", e),
+      );
+    providersApi
+      .getPromptIntegritySignals(appId)
+      .then((signals) =>
+        setIntegrity(
+          signals.find((signal) => signal.providerId === providerId) ?? null,
+        ),
+      )
+      .catch((e) =>
+        console.error("[ProviderCapabilityMatrix] Failed to load signals", e),
+      );
+  }, [appId, providerId]);
+
+  async function handleProbe() {
+    try {
+      setIsProbing(true);
+      setError(null);
+      setMatrix(await providersApi.probeCapabilities(providerId, appId));
+    } catch (e) {
+      setError(extractErrorMessage(e));
+    } finally {
+      setIsProbing(false);
+    }
+  }
+
+  return (
+    <div className="mt-6 space-y-3 rounded-lg border border-border/50 p-4">
+      <div className="flex items-center justify-between gap-4">
+        <div className="space-y-1">
+          <h4 className="text-sm font-medium">
+            {t("provider.capabilities.title")}
+          </h4>
+          <p className="text-xs text-muted-foreground">
+            {t("provider.capabilities.description")}
+          </p>
+        </div>
+        <Button
+          type="button"
+          variant="outline"
+          size="sm"
+          onClick={handleProbe}
+          disabled={isProbing}
+        >
+          {isProbing ? (
+            <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+          ) : (
+            <Radar className="mr-2 h-4 w-4" />
+          )}
+          {t("provider.capabilities.probe")}
+        </Button>
+      </div>
+
+      {error && (
+        <p className="text-xs text-red-600 dark:text-red-400">{error}</p>
+      )}
+
+      {integrity && integrity.status !== "normal" && (
+        <div className="flex items-start gap-2 rounded-md bg-amber-500/10 px-3 py-2 text-xs text-amber-700 dark:text-amber-300">
+          <AlertTriangle className="mt-0.5 h-3.5 w-3.5 shrink-0" />
+          <span>
+            {t(`provider.promptIntegrity.${integrity.status}`, {
+              ratio: integrity.medianRatio.toFixed(2),
+              tokens: Math.abs(integrity.medianExtraTokens).toLocaleString(),
+              samples: integrity.samples,
+            })}
+          </span>
+        </div>
+      )}
+
+      {matrix ? (
+        <div className="space-y-2 text-xs">
+          <div className="grid grid-cols-2 gap-2 md:grid-cols-3">
+            {CAPABILITIES.map((key) => (
+              <div
+                key={key}
+                className="flex items-center justify-between rounded-md bg-muted/40 px-2 py-1"
+              >
+                <span>{t(`provider.capabilities.${key}`)}</span>
+                <span
+                  className={cn("font-medium", SUPPORT_CLASS[matrix[key]])}
+                >
+                  {t(`provider.capabilities.support.${matrix[key]}`)}
+                </span>
+              </div>
+            ))}
+            <div className="flex items-center justify-between rounded-md bg-muted/40 px-2 py-1">
+              <span>{t("provider.capabilities.maxOutputTokens")}</span>
+              <span className="font-medium">
+                {matrix.maxOutputTokens?.toLocaleString() ??
+                  t("provider.capabilities.support.unknown")}
+              </span>
+            </div>
+          </div>
+          <div className="text-muted-foreground">
+            {t("provider.capabilities.probedAt", {
+              model: matrix.model,
+              time: new Date(matrix.probedAt * 1000).toLocaleString(),
+            })}
+          </div>
+        </div>
+      ) : (
+        !isProbing && (
+          <p className="text-xs text-muted-foreground">
+            {t("provider.capabilities.empty")}
+          </p>
+        )
+      )}
+    </div>
+  );
+}
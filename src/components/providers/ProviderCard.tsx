@@ -6,7 +6,8 @@ import type {
   DraggableSyntheticListeners,
 } from "@dnd-kit/core";
 import type { Provider } from "@/types";
-import type { AppId } from "@/lib/api";
+import { providersApi, type AppId } from "@/lib/api";
+import type { ResolvedTrust } from "@/lib/api/providers";
 import { cn } from "@/lib/utils";
 import { ProviderActions } from "@/components/providers/ProviderActions";
 import { ProviderIcon } from "@/components/ProviderIcon";
@@ -19,6 +20,11 @@ import { PROVIDER_TYPES, TEMPLATE_TYPES } from "@/config/constants";
 import { isHermesReadOnlyProvider } from "@/config/hermesProviderPresets";
 import { ProviderHealthBadge } from "@/components/providers/ProviderHealthBadge";
 import { FailoverPriorityBadge } from "@/components/providers/FailoverPriorityBadge";
+import {
+  STALE_BADGE_DAYS,
+  StaleProviderBadge,
+} from "@/components/providers/StaleProviderBadge";
+import { UntrustedProviderBadge } from "@/components/providers/UntrustedProviderBadge";
 import {
   extractCodexBaseUrl,
   extractCodexExperimentalBearerToken,
@@ -30,6 +36,7 @@ import {
 import { useProviderHealth } from "@/lib/query/failover";
 import { useUsageQuery } from "@/lib/query/queries";
 import { resolveProviderIcon } from "@/utils/providerIcon";
+import { useCopyAsCurl } from "@/hooks/useCopyAsCurl";
 
 interface DragHandleProps {
   attributes: DraggableAttributes;
@@ -64,6 +71,10 @@ interface ProviderCardProps {
   isInFailoverQueue?: boolean; // 是否在故障转移队列中
   onToggleFailover?: (enabled: boolean) => void; // 切换故障转移队列
   activeProviderId?: string; // 代理当前实际使用的供应商 ID（用于故障转移模式下标注绿色边框）
+  idleDays?: number | null; // 距最近使用的天数（当前供应商为 null）
+  trust?: ResolvedTrust; // 信任等级（用户标注或策略文件判定）
+  favicon?: string; // 已缓存的站点图标（data URL），未选择图标时显示
+  onToggleTrust?: () => void;
   // OpenClaw: default model
   isDefaultModel?: boolean;
   onSetAsDefault?: () => void;
@@ -163,6 +174,10 @@ export function ProviderCard({
   isInFailoverQueue = false,
   onToggleFailover,
   activeProviderId,
+  idleDays,
+  trust,
+  favicon,
+  onToggleTrust,
   // OpenClaw: default model
   isDefaultModel,
   onSetAsDefault,
@@ -230,6 +245,16 @@ export function ProviderCard({
     provider.meta?.providerType === PROVIDER_TYPES.CODEX_OAUTH;
   // xAI OAuth (SuperGrok 反代)：额度经自管 OAuth token 自动显示，与 codex_oauth 同构
   const isXaiOauth = provider.meta?.providerType === PROVIDER_TYPES.XAI_OAUTH;
+  // 托管 OAuth 供应商的令牌由代理动态换取，累加模式应用也没有统一的请求协议
+  const copyAsCurl = useCopyAsCurl();
+  const canCopyAsCurl =
+    ["claude", "claude-desktop", "codex", "gemini", "grokbuild"].includes(
+      appId,
+    ) &&
+    provider.category !== "official" &&
+    !isCopilot &&
+    !isCodexOauth &&
+    !isXaiOauth;
   // 统一权威谓词（详见 providerNeedsRouting）：以 providerType 为准，不受
   // apiFormat 被改动/缺省影响。此 badge 仅在 Codex 视图渲染，故加 appId 守卫。
   const codexNeedsRouting =
@@ -346,6 +371,7 @@ export function ProviderCard({
               )}
               name={provider.name}
               color={provider.iconColor}
+              fallbackUrl={favicon}
               size={20}
             />
           </div>
@@ -436,6 +462,14 @@ export function ProviderCard({
                   <FailoverPriorityBadge priority={failoverPriority} />
                 )}
 
+              {idleDays != null && idleDays >= STALE_BADGE_DAYS && (
+                <StaleProviderBadge idleDays={idleDays} />
+              )}
+
+              {trust?.level === "untrusted" && (
+                <UntrustedProviderBadge trust={trust} />
+              )}
+
               {provider.category === "third_party" &&
                 provider.meta?.isPartner && (
                   <span
@@ -579,6 +613,14 @@ export function ProviderCard({
                   ? () => onTest(provider)
                   : undefined
               }
+              onCopyCurl={
+                canCopyAsCurl
+                  ? () =>
+                      copyAsCurl((maskKey) =>
+                        providersApi.copyAsCurl(provider.id, appId, maskKey),
+                      )
+                  : undefined
+              }
               onConfigureUsage={
                 (isOfficial && !supportsOfficialSubscription) ||
                 isCopilot ||
@@ -597,6 +639,8 @@ export function ProviderCard({
               onOpenTerminal={
                 onOpenTerminal ? () => onOpenTerminal(provider) : undefined
               }
+              isUntrusted={trust?.level === "untrusted"}
+              onToggleTrust={onToggleTrust}
               isAutoFailoverEnabled={isAutoFailoverEnabled}
               isInFailoverQueue={isInFailoverQueue}
               onToggleFailover={onToggleFailover}
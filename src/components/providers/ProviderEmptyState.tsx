@@ -1,4 +1,4 @@
-import { Download, Users } from "lucide-react";
+import { Download, FolderInput, Users } from "lucide-react";
 import { useTranslation } from "react-i18next";
 import { Button } from "@/components/ui/button";
 import type { AppId } from "@/lib/api/types";
@@ -7,12 +7,19 @@ interface ProviderEmptyStateProps {
   appId: AppId;
   onCreate?: () => void;
   onImport?: () => void;
+  /** 本机已有 CLI 配置、可一键导入的应用（首次使用引导） */
+  liveImportApps?: AppId[];
+  onImportAllLive?: () => void;
+  isImportingAllLive?: boolean;
 }
 
 export function ProviderEmptyState({
   appId,
   onCreate,
   onImport,
+  liveImportApps = [],
+  onImportAllLive,
+  isImportingAllLive = false,
 }: ProviderEmptyStateProps) {
   const { t } = useTranslation();
   const showSnippetHint =
@@ -43,6 +50,16 @@ export function ProviderEmptyState({
               : t("provider.importCurrent")}
           </Button>
         )}
+        {onImportAllLive && liveImportApps.length > 0 && (
+          <Button
+            variant="outline"
+            onClick={onImportAllLive}
+            disabled={isImportingAllLive}
+          >
+            <FolderInput className="mr-2 h-4 w-4" />
+            {t("provider.importAllLive", { apps: liveImportApps.join(", ") })}
+          </Button>
+        )}
         {onCreate && (
           <Button variant={onImport ? "outline" : "default"} onClick={onCreate}>
             {t("provider.addProvider")}
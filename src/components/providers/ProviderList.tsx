@@ -19,7 +19,11 @@ import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { toast } from "sonner";
 import type { Provider } from "@/types";
 import type { AppId } from "@/lib/api";
-import { providersApi } from "@/lib/api/providers";
+import {
+  providersApi,
+  PROVIDER_FAVICONS_UPDATED_EVENT,
+  type ResolvedTrust,
+} from "@/lib/api/providers";
 import { extractErrorMessage } from "@/utils/errorUtils";
 import { useDragSort } from "@/hooks/useDragSort";
 import {
@@ -31,8 +35,10 @@ import {
   useHermesModelConfig,
 } from "@/hooks/useHermes";
 import { useStreamCheck } from "@/hooks/useStreamCheck";
+import { useTauriEvent } from "@/hooks/useTauriEvent";
 import { ProviderCard } from "@/components/providers/ProviderCard";
 import { ProviderEmptyState } from "@/components/providers/ProviderEmptyState";
+import { ConfirmDialog } from "@/components/ConfirmDialog";
 import {
   useAutoFailoverEnabled,
   useFailoverQueue,
@@ -104,6 +110,31 @@ export function ProviderList({
     enabled: appId === "opencode",
   });
 
+  // 最近使用情况，用于显示闲置徽章
+  const { data: lastSeen } = useQuery({
+    queryKey: ["providerLastSeen", appId],
+    queryFn: () => providersApi.getLastSeen(appId),
+    staleTime: 5 * 60 * 1000,
+  });
+  const idleDaysById = useMemo(
+    () =>
+      new Map(lastSeen?.map((item) => [item.providerId, item.idleDays]) ?? []),
+    [lastSeen],
+  );
+
+  // 信任等级，用于显示不受信任徽章并在加入故障转移队列前确认
+  const { data: trustById } = useQuery({
+    queryKey: ["providerTrust", appId],
+    queryFn: () => providersApi.getTrustLevels(appId),
+  });
+
+  // 已缓存的站点图标：没有手动选择图标的供应商以此代替首字母
+  const { data: favicons } = useQuery({
+    queryKey: ["providerFavicons", appId],
+    queryFn: () => providersApi.getFavicons(appId),
+    staleTime: 5 * 60 * 1000,
+  });
+
   // OpenClaw: 查询 live 配置中的供应商 ID 列表，用于判断 isInConfig
   const { data: openclawLiveIds } = useOpenClawLiveProviderIds(
     appId === "openclaw",
@@ -178,15 +209,24 @@ export function ProviderList({
     [isFailoverModeActive, failoverQueue],
   );
 
+  // 不受信任的供应商加入故障转移队列前需要确认
+  const [pendingFailoverId, setPendingFailoverId] = useState<string | null>(
+    null,
+  );
+
   const handleToggleFailover = useCallback(
     (providerId: string, enabled: boolean) => {
       if (enabled) {
+        if (trustById?.[providerId]?.level === "untrusted") {
+          setPendingFailoverId(providerId);
+          return;
+        }
         addToQueue.mutate({ appType: appId, providerId });
       } else {
         removeFromQueue.mutate({ appType: appId, providerId });
       }
     },
-    [appId, addToQueue, removeFromQueue],
+    [appId, addToQueue, removeFromQueue, trustById],
   );
 
   const [searchTerm, setSearchTerm] = useState("");
@@ -207,8 +247,39 @@ export function ProviderList({
     [checkProvider],
   );
 
-  // Import current live config as default provider
   const queryClient = useQueryClient();
+
+  useTauriEvent(PROVIDER_FAVICONS_UPDATED_EVENT, () => {
+    void queryClient.invalidateQueries({ queryKey: ["providerFavicons"] });
+  });
+
+  const trustMutation = useMutation({
+    mutationFn: ({
+      provider,
+      trust,
+    }: {
+      provider: Provider;
+      trust?: ResolvedTrust;
+    }) => {
+      // 不受信任时：用户标注直接清除，策略判定则标注为受信任以覆盖规则
+      const level =
+        trust?.level === "untrusted"
+          ? trust.source === "label"
+            ? null
+            : "trusted"
+          : "untrusted";
+      return providersApi.setTrust(appId, provider.id, level);
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["providerTrust", appId] });
+      queryClient.invalidateQueries({ queryKey: ["providers", appId] });
+    },
+    onError: (error: unknown) => {
+      toast.error(extractErrorMessage(error) || t("provider.trust.saveFailed"));
+    },
+  });
+
+  // Import current live config as default provider
   const importMutation = useMutation({
     mutationFn: async (): Promise<boolean> => {
       if (appId === "opencode") {
@@ -250,6 +321,53 @@ export function ProviderList({
     },
   });
 
+  // 首次使用引导：列表为空时扫描本机已有的 CLI 配置，提供一键导入全部
+  const isEmpty = !isLoading && sortedProviders.length === 0;
+  const { data: liveImportCandidates } = useQuery({
+    queryKey: ["liveImportCandidates"],
+    queryFn: () => providersApi.scanLiveImportCandidates(),
+    enabled: isEmpty,
+  });
+  const liveImportApps = useMemo(
+    () =>
+      (liveImportCandidates ?? [])
+        .filter(
+          (candidate) => !candidate.alreadyConfigured && !candidate.takenOver,
+        )
+        .map((candidate) => candidate.app),
+    [liveImportCandidates],
+  );
+
+  const importAllLiveMutation = useMutation({
+    mutationFn: () => providersApi.importLiveConfigs(liveImportApps),
+    onSuccess: (outcomes) => {
+      queryClient.invalidateQueries({ queryKey: ["providers"] });
+      queryClient.invalidateQueries({ queryKey: ["liveImportCandidates"] });
+      const imported = outcomes.filter((outcome) => outcome.imported);
+      const failed = outcomes.find((outcome) => outcome.error);
+      if (failed) {
+        toast.error(
+          t("provider.importAllLiveFailed", {
+            app: failed.app,
+            error: failed.error,
+          }),
+        );
+      }
+      if (imported.length > 0) {
+        toast.success(
+          t("provider.importAllLiveSuccess", {
+            apps: imported.map((outcome) => outcome.app).join(", "),
+          }),
+        );
+      } else if (!failed) {
+        toast.info(t("provider.noProviders"));
+      }
+    },
+    onError: (error: unknown) => {
+      toast.error(extractErrorMessage(error) || t("settings.importFailed"));
+    },
+  });
+
   useEffect(() => {
     const handleKeyDown = (event: KeyboardEvent) => {
       if (event.defaultPrevented) return;
@@ -370,6 +488,13 @@ export function ProviderList({
         appId={appId}
         onCreate={onCreate}
         onImport={() => importMutation.mutate()}
+        liveImportApps={liveImportApps}
+        onImportAllLive={
+          liveImportApps.length > 0
+            ? () => importAllLiveMutation.mutate()
+            : undefined
+        }
+        isImportingAllLive={importAllLiveMutation.isPending}
       />
     );
   }
@@ -431,6 +556,15 @@ export function ProviderList({
                   handleToggleFailover(provider.id, enabled)
                 }
                 activeProviderId={activeProviderId}
+                idleDays={idleDaysById.get(provider.id)}
+                trust={trustById?.[provider.id]}
+                favicon={favicons?.[provider.id]}
+                onToggleTrust={() =>
+                  trustMutation.mutate({
+                    provider,
+                    trust: trustById?.[provider.id],
+                  })
+                }
                 // OpenClaw: default model / Hermes: model.provider === provider.id
                 isDefaultModel={
                   appId === "hermes"
@@ -538,6 +672,28 @@ export function ProviderList({
       ) : (
         renderProviderList()
       )}
+
+      <ConfirmDialog
+        isOpen={pendingFailoverId !== null}
+        title={t("provider.trust.confirmTitle")}
+        message={t("provider.trust.confirmFailover", {
+          name: pendingFailoverId
+            ? (providers[pendingFailoverId]?.name ?? pendingFailoverId)
+            : "",
+        })}
+        confirmText={t("provider.trust.confirmButton")}
+        variant="destructive"
+        onConfirm={() => {
+          if (pendingFailoverId) {
+            addToQueue.mutate({
+              appType: appId,
+              providerId: pendingFailoverId,
+            });
+          }
+          setPendingFailoverId(null);
+        }}
+        onCancel={() => setPendingFailoverId(null)}
+      />
     </div>
   );
 }
@@ -568,6 +724,10 @@ interface SortableProviderCardProps {
   isInFailoverQueue: boolean;
   onToggleFailover: (enabled: boolean) => void;
   activeProviderId?: string;
+  idleDays?: number | null;
+  trust?: ResolvedTrust;
+  favicon?: string;
+  onToggleTrust?: () => void;
   // OpenClaw: default model
   isDefaultModel?: boolean;
   onSetAsDefault?: () => void;
@@ -599,6 +759,10 @@ function SortableProviderCard({
   isInFailoverQueue,
   onToggleFailover,
   activeProviderId,
+  idleDays,
+  trust,
+  favicon,
+  onToggleTrust,
   isDefaultModel,
   onSetAsDefault,
 }: SortableProviderCardProps) {
@@ -651,6 +815,10 @@ function SortableProviderCard({
         isInFailoverQueue={isInFailoverQueue}
         onToggleFailover={onToggleFailover}
         activeProviderId={activeProviderId}
+        idleDays={idleDays}
+        trust={trust}
+        favicon={favicon}
+        onToggleTrust={onToggleTrust}
         // OpenClaw: default model
         isDefaultModel={isDefaultModel}
         onSetAsDefault={onSetAsDefault}
@@ -0,0 +1,117 @@
+import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { useTranslation } from "react-i18next";
+import { ExternalLink, Megaphone, X } from "lucide-react";
+import { toast } from "sonner";
+import { Alert, AlertDescription, AlertTitle } from "@/components/ui/alert";
+import { Button } from "@/components/ui/button";
+import { providersApi, settingsApi, type AppId } from "@/lib/api";
+import {
+  PROVIDER_NOTICE_EVENT,
+  type ProviderNotice,
+} from "@/lib/api/providers";
+import { useTauriEvent } from "@/hooks/useTauriEvent";
+import { extractErrorMessage } from "@/utils/errorUtils";
+
+interface ProviderNoticeBannerProps {
+  appId: AppId;
+}
+
+/**
+ * 「供应商宣布了变更」横幅：代理从上游响应头中识别到弃用 / 下线声明后，
+ * 在供应商列表上方提示，用户关闭后同一公告不再出现
+ */
+export function ProviderNoticeBanner({ appId }: ProviderNoticeBannerProps) {
+  const { t, i18n } = useTranslation();
+  const queryClient = useQueryClient();
+  const queryKey = ["providerNotices", appId];
+  const { data: notices = [] } = useQuery({
+    queryKey,
+    queryFn: () => providersApi.getNotices(appId),
+  });
+
+  useTauriEvent<ProviderNotice>(PROVIDER_NOTICE_EVENT, (notice) => {
+    if (notice.appType === appId) {
+      void queryClient.invalidateQueries({ queryKey });
+    }
+  });
+
+  if (notices.length === 0) {
+    return null;
+  }
+
+  const formatDate = (seconds: number) =>
+    new Date(seconds * 1000).toLocaleDateString(i18n.language);
+
+  const handleDismiss = async (providerId: string) => {
+    try {
+      await providersApi.dismissNotice(providerId, appId);
+      await queryClient.invalidateQueries({ queryKey });
+    } catch (error) {
+      toast.error(extractErrorMessage(error));
+    }
+  };
+
+  return (
+    <Alert className="border-amber-500/30 bg-amber-500/5">
+      <Megaphone className="h-4 w-4 !text-amber-600" />
+      <AlertTitle>{t("providerNotice.title")}</AlertTitle>
+      <AlertDescription>
+        <ul className="mt-2 space-y-2 text-sm">
+          {notices.map((notice) => (
+            <li
+              key={notice.providerId}
+              className="flex items-start justify-between gap-3"
+            >
+              <div className="min-w-0 space-y-0.5">
+                <div className="font-medium">
+                  {notice.providerName}
+                  {notice.deprecated && (
+                    <span className="ml-2 text-xs text-amber-700 dark:text-amber-400">
+                      {notice.deprecatedAt
+                        ? t("providerNotice.deprecatedAt", {
+                            date: formatDate(notice.deprecatedAt),
+                          })
+                        : t("providerNotice.deprecated")}
+                    </span>
+                  )}
+                </div>
+                {notice.message && (
+                  <p className="break-words text-muted-foreground">
+                    {notice.message}
+                  </p>
+                )}
+                {notice.sunsetAt && (
+                  <p className="text-xs text-muted-foreground">
+                    {t("providerNotice.sunsetAt", {
+                      date: formatDate(notice.sunsetAt),
+                    })}
+                  </p>
+                )}
+                {notice.link && (
+                  <button
+                    type="button"
+                    className="inline-flex items-center gap-1 text-xs text-blue-600 hover:underline dark:text-blue-400"
+                    onClick={() => void settingsApi.openExternal(notice.link!)}
+                  >
+                    {t("providerNotice.learnMore")}
+                    <ExternalLink className="h-3 w-3" />
+                  </button>
+                )}
+              </div>
+              <Button
+                type="button"
+                variant="ghost"
+                size="icon"
+                className="h-6 w-6 shrink-0"
+                title={t("providerNotice.dismiss")}
+                onClick={() => void handleDismiss(notice.providerId)}
+              >
+                <X className="h-3.5 w-3.5" />
+              </Button>
+            </li>
+          ))}
+        </ul>
+      </AlertDescription>
+    </Alert>
+  );
+}
@@ -0,0 +1,198 @@
+import { useEffect, useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { Loader2, Send } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import { Textarea } from "@/components/ui/textarea";
+import { providersApi, type AppId } from "@/lib/api";
+import type { TestMessageResult } from "@/lib/api/providers";
+import { extractErrorMessage } from "@/utils/errorUtils";
+
+interface ProviderTestMessagePanelProps {
+  appId: AppId;
+  providerId: string;
+}
+
+/**
+ * 发送测试消息：展示回复预览（流式逐段显示）以及模型、停止原因、
+ * token 用量与耗时，原始响应体折叠在下方便于排查
+ */
+export function ProviderTestMessagePanel({
+  appId,
+  providerId,
+}: ProviderTestMessagePanelProps) {
+  const { t } = useTranslation();
+  const [message, setMessage] = useState("");
+  const [stream, setStream] = useState(true);
+  const [isSending, setIsSending] = useState(false);
+  const [preview, setPreview] = useState("");
+  const [result, setResult] = useState<TestMessageResult | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const requestIdRef = useRef<string | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    let active = true;
+    providersApi
+      .onTestMessageChunk((chunk) => {
+        if (chunk.requestId === requestIdRef.current) {
+          setPreview((prev) => prev + chunk.delta);
+        }
+      })
+      .then((off) => {
+        if (active) {
+          unlisten = off;
+        } else {
+          off();
+        }
+      })
+      .catch((e) =>
+        console.error("[ProviderTestMessagePanel] Failed to subscribe", e),
+      );
+    return () => {
+      active = false;
+      unlisten?.();
+    };
+  }, []);
+
+  async function handleSend() {
+    const requestId = crypto.randomUUID();
+    requestIdRef.current = requestId;
+    setIsSending(true);
+    setPreview("");
+    setResult(null);
+    setError(null);
+    try {
+      const next = await providersApi.sendTestMessage(providerId, appId, {
+        message: message.trim() || undefined,
+        stream,
+        requestId,
+      });
+      setResult(next);
+      setPreview(next.text);
+    } catch (e) {
+      setError(extractErrorMessage(e));
+    } finally {
+      requestIdRef.current = null;
+      setIsSending(false);
+    }
+  }
+
+  const stats = result
+    ? [
+        { key: "model", value: result.model ?? result.requestedModel },
+        { key: "stopReason", value: result.stopReason ?? "-" },
+        {
+          key: "usage",
+          value: result.usage
+            ? `${result.usage.inputTokens ?? "-"} / ${result.usage.outputTokens ?? "-"}`
+            : "-",
+        },
+        {
+          key: "latency",
+          value:
+            result.firstTokenMs != null
+              ? `${result.latencyMs} ms (${t("provider.testMessage.firstToken", { ms: result.firstTokenMs })})`
+              : `${result.latencyMs} ms`,
+        },
+      ]
+    : [];
+
+  return (
+    <div className="mt-6 space-y-3 rounded-lg border border-border/50 p-4">
+      <div className="space-y-1">
+        <h4 className="text-sm font-medium">
+          {t("provider.testMessage.title")}
+        </h4>
+        <p className="text-xs text-muted-foreground">
+          {t("provider.testMessage.description")}
+        </p>
+      </div>
+
+      <Textarea
+        value={message}
+        rows={2}
+        placeholder={t("provider.testMessage.placeholder")}
+        onChange={(e) => setMessage(e.target.value)}
+        disabled={isSending}
+      />
+
+      <div className="flex items-center justify-between gap-4">
+        <div className="flex items-center gap-2">
+          <Switch
+            id={`test-message-stream-${providerId}`}
+            checked={stream}
+            onCheckedChange={setStream}
+            disabled={isSending}
+          />
+          <Label
+            htmlFor={`test-message-stream-${providerId}`}
+            className="text-xs"
+          >
+            {t("provider.testMessage.stream")}
+          </Label>
+        </div>
+        <Button
+          type="button"
+          variant="outline"
+          size="sm"
+          onClick={handleSend}
+          disabled={isSending}
+        >
+          {isSending ? (
+            <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+          ) : (
+            <Send className="mr-2 h-4 w-4" />
+          )}
+          {t("provider.testMessage.send")}
+        </Button>
+      </div>
+
+      {error && (
+        <p className="text-xs text-red-600 dark:text-red-400">{error}</p>
+      )}
+
+      {(preview || isSending) && (
+        <div className="whitespace-pre-wrap rounded-md bg-muted/40 p-3 text-sm">
+          {preview || t("common.loading")}
+        </div>
+      )}
+
+      {result && (
+        <div className="space-y-2 text-xs">
+          {result.error && (
+            <p className="font-mono text-red-600 dark:text-red-400">
+              {result.error}
+            </p>
+          )}
+          <div className="grid grid-cols-2 gap-2">
+            {stats.map(({ key, value }) => (
+              <div
+                key={key}
+                className="flex items-center justify-between gap-2 rounded-md bg-muted/40 px-2 py-1"
+              >
+                <span>{t(`provider.testMessage.${key}`)}</span>
+                <span className="truncate font-mono" title={value}>
+                  {value}
+                </span>
+              </div>
+            ))}
+          </div>
+          {result.rawBody && (
+            <details>
+              <summary className="cursor-pointer text-muted-foreground">
+                {t("provider.testMessage.rawBody", {
+                  status: result.httpStatus ?? "-",
+                })}
+              </summary>
+              <pre className="mt-2 max-h-64 overflow-auto rounded-md bg-muted p-3 font-mono">
+                {result.rawBody}
+              </pre>
+            </details>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
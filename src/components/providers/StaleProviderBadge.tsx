@@ -0,0 +1,34 @@
+import { cn } from "@/lib/utils";
+import { useTranslation } from "react-i18next";
+
+/** 闲置达到该天数才显示徽章 */
+export const STALE_BADGE_DAYS = 30;
+
+interface StaleProviderBadgeProps {
+  idleDays: number;
+  className?: string;
+}
+
+/**
+ * 闲置徽章
+ * 供应商长期未被切换、也未经代理转发请求时提示可考虑归档
+ */
+export function StaleProviderBadge({
+  idleDays,
+  className,
+}: StaleProviderBadgeProps) {
+  const { t } = useTranslation();
+
+  return (
+    <span
+      className={cn(
+        "inline-flex items-center px-1.5 py-0.5 rounded text-[10px] font-medium",
+        "bg-muted text-muted-foreground",
+        className,
+      )}
+      title={t("provider.stale.tooltip", { days: idleDays })}
+    >
+      {t("provider.stale.badge", { days: idleDays })}
+    </span>
+  );
+}
@@ -0,0 +1,41 @@
+import { ShieldAlert } from "lucide-react";
+import { useTranslation } from "react-i18next";
+import { cn } from "@/lib/utils";
+import type { ResolvedTrust } from "@/lib/api/providers";
+
+interface UntrustedProviderBadgeProps {
+  trust: ResolvedTrust;
+  className?: string;
+}
+
+/**
+ * 不受信任徽章
+ * 悬停时说明判定来源：用户标注或策略文件中命中的规则
+ */
+export function UntrustedProviderBadge({
+  trust,
+  className,
+}: UntrustedProviderBadgeProps) {
+  const { t } = useTranslation();
+
+  const tooltip =
+    trust.source === "label"
+      ? t("provider.trust.labelTooltip")
+      : trust.rule
+        ? t("provider.trust.ruleTooltip", { rule: trust.rule })
+        : t("provider.trust.defaultTooltip");
+
+  return (
+    <span
+      className={cn(
+        "inline-flex items-center gap-0.5 px-1.5 py-0.5 rounded text-[10px] font-medium",
+        "bg-red-500/10 text-red-600 dark:text-red-400",
+        className,
+      )}
+      title={tooltip}
+    >
+      <ShieldAlert className="h-3 w-3" />
+      {t("provider.trust.untrusted")}
+    </span>
+  );
+}
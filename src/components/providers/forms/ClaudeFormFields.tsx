@@ -55,6 +55,8 @@ import type {
   ProviderCategory,
   ClaudeApiFormat,
   ClaudeApiKeyField,
+  ClientIdentity,
+  ModelAlias,
 } from "@/types";
 import {
   hasClaudeOneMMarker,
@@ -155,10 +157,15 @@ interface ClaudeFormFieldsProps {
   // Local proxy User-Agent override
   customUserAgent: string;
   onCustomUserAgentChange: (value: string) => void;
+  clientIdentity?: ClientIdentity;
+  onClientIdentityChange?: (identity: ClientIdentity) => void;
   localProxyHeadersOverride: string;
   onLocalProxyHeadersOverrideChange: (value: string) => void;
   localProxyBodyOverride: string;
   onLocalProxyBodyOverrideChange: (value: string) => void;
+
+  // 设置中的模型别名，作为模型输入候选
+  modelAliases?: ModelAlias[];
 }
 
 export function ClaudeFormFields({
@@ -220,10 +227,13 @@ export function ClaudeFormFields({
   onFullUrlChange,
   customUserAgent,
   onCustomUserAgentChange,
+  clientIdentity,
+  onClientIdentityChange,
   localProxyHeadersOverride,
   onLocalProxyHeadersOverrideChange,
   localProxyBodyOverride,
   onLocalProxyBodyOverrideChange,
+  modelAliases,
 }: ClaudeFormFieldsProps) {
   const { t } = useTranslation();
   const hasRequestOverrides = Boolean(
@@ -239,6 +249,7 @@ export function ClaudeFormFields({
     (!isXaiOauthPreset && apiFormat !== "anthropic") ||
     apiKeyField !== "ANTHROPIC_AUTH_TOKEN" ||
     customUserAgent ||
+    (clientIdentity && clientIdentity !== "passthrough") ||
     hasRequestOverrides
   );
   const [advancedExpanded, setAdvancedExpanded] = useState(
@@ -301,7 +312,14 @@ export function ClaudeFormFields({
     const modelsUrl = matchedPreset?.modelsUrl;
 
     setIsFetchingModels(true);
-    fetchModelsForConfig(baseUrl, apiKey, isFullUrl, modelsUrl, customUserAgent)
+    fetchModelsForConfig(
+      baseUrl,
+      apiKey,
+      isFullUrl,
+      modelsUrl,
+      customUserAgent,
+      clientIdentity,
+    )
       .then((models) => {
         setFetchedModels(models);
         showModelFetchResult(models.length);
@@ -311,7 +329,15 @@ export function ClaudeFormFields({
         showFetchModelsError(err, t);
       })
       .finally(() => setIsFetchingModels(false));
-  }, [baseUrl, apiKey, isFullUrl, customUserAgent, showModelFetchResult, t]);
+  }, [
+    baseUrl,
+    apiKey,
+    isFullUrl,
+    customUserAgent,
+    clientIdentity,
+    showModelFetchResult,
+    t,
+  ]);
 
   const handleFetchCopilotModels = useCallback(() => {
     if (!isCopilotAuthenticated) {
@@ -477,6 +503,7 @@ export function ClaudeFormFields({
           placeholder={placeholder}
           fetchedModels={codexOauthModels}
           isLoading={codexOauthModelsLoading}
+          aliases={modelAliases}
         />
       );
     }
@@ -490,6 +517,7 @@ export function ClaudeFormFields({
           placeholder={placeholder}
           fetchedModels={xaiOauthModels}
           isLoading={xaiOauthModelsLoading}
+          aliases={modelAliases}
         />
       );
     }
@@ -586,6 +614,7 @@ export function ClaudeFormFields({
         placeholder={placeholder}
         fetchedModels={fetchedModels}
         isLoading={isFetchingModels}
+        aliases={modelAliases}
       />
     );
   };
@@ -1108,6 +1137,8 @@ export function ClaudeFormFields({
               id="claude-custom-user-agent"
               value={customUserAgent}
               onChange={onCustomUserAgentChange}
+              identity={clientIdentity}
+              onIdentityChange={onClientIdentityChange}
             />
 
             <div className="border-t border-border-default pt-3">
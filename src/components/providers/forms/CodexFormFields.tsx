@@ -39,6 +39,7 @@ import { LocalProxyRequestOverridesField } from "./LocalProxyRequestOverridesFie
 import { cn } from "@/lib/utils";
 import type {
   ClaudeApiKeyField,
+  ClientIdentity,
   CodexApiFormat,
   CodexCatalogModel,
   CodexChatReasoning,
@@ -112,6 +113,8 @@ interface CodexFormFieldsProps {
   // Local proxy User-Agent override
   customUserAgent: string;
   onCustomUserAgentChange: (value: string) => void;
+  clientIdentity?: ClientIdentity;
+  onClientIdentityChange?: (identity: ClientIdentity) => void;
   localProxyHeadersOverride: string;
   onLocalProxyHeadersOverrideChange: (value: string) => void;
   localProxyBodyOverride: string;
@@ -206,6 +209,8 @@ export function CodexFormFields({
   speedTestEndpoints,
   customUserAgent,
   onCustomUserAgentChange,
+  clientIdentity,
+  onClientIdentityChange,
   localProxyHeadersOverride,
   onLocalProxyHeadersOverrideChange,
   localProxyBodyOverride,
@@ -250,6 +255,7 @@ export function CodexFormFields({
   );
   const hasAnyAdvancedValue =
     !!customUserAgent ||
+    (!!clientIdentity && clientIdentity !== "passthrough") ||
     hasRequestOverrides ||
     catalogModels.length > 0 ||
     apiFormat === "openai_responses" ||
@@ -381,6 +387,7 @@ export function CodexFormFields({
       isFullUrl,
       undefined,
       customUserAgent,
+      clientIdentity,
     )
       .then((models) => {
         if (seq !== fetchModelsSeqRef.current) return;
@@ -404,6 +411,7 @@ export function CodexFormFields({
     codexApiKey,
     isFullUrl,
     customUserAgent,
+    clientIdentity,
     isXaiOauthPreset,
     isXaiOauthAuthenticated,
     selectedXaiAccountId,
@@ -1072,6 +1080,8 @@ export function CodexFormFields({
                 id="codex-custom-user-agent"
                 value={customUserAgent}
                 onChange={onCustomUserAgentChange}
+                identity={clientIdentity}
+                onIdentityChange={onClientIdentityChange}
               />
               <div className="border-t border-border-default pt-3">
                 <LocalProxyRequestOverridesField
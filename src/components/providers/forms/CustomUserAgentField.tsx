@@ -3,6 +3,13 @@ import { ChevronDown } from "lucide-react";
 import { FormLabel } from "@/components/ui/form";
 import { Input } from "@/components/ui/input";
 import { Button } from "@/components/ui/button";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
 import {
   DropdownMenu,
   DropdownMenuContent,
@@ -11,16 +18,30 @@ import {
 } from "@/components/ui/dropdown-menu";
 import { isValidUserAgentHeader } from "@/lib/userAgent";
 import { USER_AGENT_PRESETS } from "@/config/userAgentPresets";
+import type { ClientIdentity } from "@/types";
+
+const CLIENT_IDENTITIES: ClientIdentity[] = [
+  "passthrough",
+  "claudeCode",
+  "ccSwitch",
+  "custom",
+];
 
 interface CustomUserAgentFieldProps {
   /** 输入框的 id（用于 label htmlFor）；两个表单需传入各自唯一值。 */
   id: string;
   value: string;
   onChange: (value: string) => void;
+  /** 客户端标识策略；不传 onIdentityChange 时只显示自定义 UA 输入框。 */
+  identity?: ClientIdentity;
+  onIdentityChange?: (identity: ClientIdentity) => void;
 }
 
 /**
- * 供应商级自定义 User-Agent 字段（Claude / Codex 表单共用）。
+ * 供应商级客户端标识 / 自定义 User-Agent 字段（Claude / Codex 表单共用）。
+ *
+ * 标识策略决定转发、检测、测速与获取模型时发送的 User-Agent：透传客户端自己的、
+ * 伪装 Claude Code、如实标识 cc-switch，或使用下方输入的自定义值。
  *
  * 含标签 + 输入框 + 右侧预设下拉菜单 + 实时合法性提示。校验口径与后端
  * `parse_custom_user_agent` 一致（见 `@/lib/userAgent`），非法时给非阻断红字提示
@@ -30,9 +51,69 @@ export function CustomUserAgentField({
   id,
   value,
   onChange,
+  identity,
+  onIdentityChange,
 }: CustomUserAgentFieldProps) {
   const { t } = useTranslation();
   const valid = isValidUserAgentHeader(value);
+  const showCustom = !onIdentityChange || identity === "custom";
+
+  return (
+    <div className="space-y-2">
+      {onIdentityChange && (
+        <div className="space-y-2">
+          <FormLabel htmlFor={`${id}-identity`}>
+            {t("providerForm.clientIdentity.label")}
+          </FormLabel>
+          <Select
+            value={identity ?? "passthrough"}
+            onValueChange={(next) => onIdentityChange(next as ClientIdentity)}
+          >
+            <SelectTrigger id={`${id}-identity`}>
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {CLIENT_IDENTITIES.map((option) => (
+                <SelectItem key={option} value={option}>
+                  {t(`providerForm.clientIdentity.${option}`)}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+          <p className="text-xs text-muted-foreground">
+            {t("providerForm.clientIdentity.hint")}
+          </p>
+        </div>
+      )}
+      {showCustom && (
+        <CustomUserAgentInput id={id} value={value} onChange={onChange} />
+      )}
+      {showCustom && !valid && (
+        <p className="text-xs text-destructive">
+          {t("providerForm.customUserAgentInvalid", {
+            defaultValue:
+              "User-Agent 不能包含控制字符（如换行符），否则将被忽略。",
+          })}
+        </p>
+      )}
+      {showCustom && valid && !onIdentityChange && (
+        <p className="text-xs text-muted-foreground">
+          {t("providerForm.customUserAgentHint", {
+            defaultValue:
+              "仅在开启本地路由/代理接管后生效，会替换转发到供应商 API 请求中的 User-Agent。",
+          })}
+        </p>
+      )}
+    </div>
+  );
+}
+
+function CustomUserAgentInput({
+  id,
+  value,
+  onChange,
+}: Pick<CustomUserAgentFieldProps, "id" | "value" | "onChange">) {
+  const { t } = useTranslation();
 
   return (
     <div className="space-y-2">
@@ -76,21 +157,6 @@ export function CustomUserAgentField({
           </DropdownMenuContent>
         </DropdownMenu>
       </div>
-      {valid ? (
-        <p className="text-xs text-muted-foreground">
-          {t("providerForm.customUserAgentHint", {
-            defaultValue:
-              "仅在开启本地路由/代理接管后生效，会替换转发到供应商 API 请求中的 User-Agent。",
-          })}
-        </p>
-      ) : (
-        <p className="text-xs text-destructive">
-          {t("providerForm.customUserAgentInvalid", {
-            defaultValue:
-              "User-Agent 不能包含控制字符（如换行符），否则将被忽略。",
-          })}
-        </p>
-      )}
     </div>
   );
 }
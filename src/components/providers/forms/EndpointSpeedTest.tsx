@@ -105,6 +105,8 @@ const EndpointSpeedTest: React.FC<EndpointSpeedTestProps> = ({
   const [isTesting, setIsTesting] = useState(false);
   const [lastError, setLastError] = useState<string | null>(null);
   const [isSaving, setIsSaving] = useState(false);
+  // 编辑模式：发送最小真实补全请求，测量首字节时间
+  const [measureCompletion, setMeasureCompletion] = useState(false);
 
   // 记录初始的自定义端点，用于对比变化
   const [initialCustomUrls, setInitialCustomUrls] = useState<Set<string>>(
@@ -329,8 +331,12 @@ const EndpointSpeedTest: React.FC<EndpointSpeedTestProps> = ({
     );
 
     try {
+      // 已保存的供应商按其鉴权头、自定义请求头与出站代理测速
       const results = await vscodeApi.testApiEndpoints(urls, {
         timeoutSecs: ENDPOINT_TIMEOUT_SECS[appId],
+        provider: providerId
+          ? { app: appId, providerId, completion: measureCompletion }
+          : undefined,
       });
 
       const resultMap = new Map(
@@ -380,7 +386,16 @@ const EndpointSpeedTest: React.FC<EndpointSpeedTestProps> = ({
     } finally {
       setIsTesting(false);
     }
-  }, [entries, autoSelect, appId, normalizedSelected, onChange, t]);
+  }, [
+    entries,
+    autoSelect,
+    appId,
+    providerId,
+    measureCompletion,
+    normalizedSelected,
+    onChange,
+    t,
+  ]);
 
   const handleSelect = useCallback(
     (url: string) => {
@@ -492,6 +507,22 @@ const EndpointSpeedTest: React.FC<EndpointSpeedTestProps> = ({
             {entries.length} {t("endpointTest.endpoints")}
           </div>
           <div className="flex items-center gap-3">
+            {isEditMode && (
+              <label
+                className="flex items-center gap-1.5 text-xs text-gray-600 dark:text-gray-400"
+                title={t("endpointTest.measureCompletionHint")}
+              >
+                <input
+                  type="checkbox"
+                  checked={measureCompletion}
+                  onChange={(event) => {
+                    setMeasureCompletion(event.target.checked);
+                  }}
+                  className="h-3.5 w-3.5 rounded border-border-default bg-background text-primary focus:ring-2 focus:ring-primary/20"
+                />
+                {t("endpointTest.measureCompletion")}
+              </label>
+            )}
             <label className="flex items-center gap-1.5 text-xs text-gray-600 dark:text-gray-400">
               <input
                 type="checkbox"
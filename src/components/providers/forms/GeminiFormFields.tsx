@@ -11,7 +11,7 @@ import {
   showFetchModelsError,
   type FetchedModel,
 } from "@/lib/api/model-fetch";
-import type { ProviderCategory } from "@/types";
+import type { ModelAlias, ProviderCategory } from "@/types";
 
 interface EndpointCandidate {
   url: string;
@@ -43,6 +43,7 @@ interface GeminiFormFieldsProps {
   shouldShowModelField: boolean;
   model: string;
   onModelChange: (value: string) => void;
+  modelAliases?: ModelAlias[];
 
   // Speed Test Endpoints
   speedTestEndpoints: EndpointCandidate[];
@@ -69,6 +70,7 @@ export function GeminiFormFields({
   shouldShowModelField,
   model,
   onModelChange,
+  modelAliases,
   speedTestEndpoints,
 }: GeminiFormFieldsProps) {
   const { t } = useTranslation();
@@ -188,6 +190,7 @@ export function GeminiFormFields({
             placeholder="gemini-3.5-flash"
             fetchedModels={fetchedModels}
             isLoading={isFetchingModels}
+            aliases={modelAliases}
           />
         </div>
       )}
@@ -6,6 +6,10 @@ import {
   parseHeaderOverrideJson,
 } from "@/lib/requestOverrides";
 
+// 请求头值支持的模板变量（由后端在发送前渲染）
+const HEADER_TEMPLATE_VARS =
+  "{{model}}, {{app_type}}, {{provider_id}}, {{provider_name}}";
+
 interface LocalProxyRequestOverridesFieldProps {
   headersJson: string;
   bodyJson: string;
@@ -49,10 +53,19 @@ export function LocalProxyRequestOverridesField({
           <Textarea
             value={headersJson}
             onChange={(event) => onHeadersJsonChange(event.target.value)}
-            placeholder={'{\n  "X-Provider": "cc-switch"\n}'}
+            placeholder={
+              '{\n  "X-Provider": "cc-switch",\n  "X-Model": "{{model}}"\n}'
+            }
             className="min-h-[132px] resize-y font-mono text-xs"
             aria-invalid={Boolean(headerError)}
           />
+          <p className="text-xs text-muted-foreground">
+            {t("providerForm.localProxyHeaderTemplatesHint", {
+              vars: HEADER_TEMPLATE_VARS,
+              defaultValue:
+                "值中可使用 {{vars}}；连通性检查与测速同样携带这些请求头。",
+            })}
+          </p>
           {headerError && (
             <p className="text-xs text-destructive">
               {t("providerForm.localProxyHeaderOverridesInvalidDetail", {
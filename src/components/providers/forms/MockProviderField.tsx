@@ -0,0 +1,134 @@
+import { useTranslation } from "react-i18next";
+import { FormLabel } from "@/components/ui/form";
+import { Input } from "@/components/ui/input";
+import { Textarea } from "@/components/ui/textarea";
+import type { MockProviderOptions } from "@/types";
+
+interface MockProviderFieldProps {
+  value: MockProviderOptions;
+  onChange: (value: MockProviderOptions) => void;
+}
+
+export const isMockBaseUrl = (url: string) =>
+  url.trim().toLowerCase().startsWith("mock://");
+
+const parseNumber = (raw: string) => {
+  const trimmed = raw.trim();
+  const parsed = Number(trimmed);
+  return trimmed === "" || !Number.isFinite(parsed) || parsed < 0
+    ? undefined
+    : parsed;
+};
+
+/**
+ * 内置模拟供应商（`mock://`）的应答配置。
+ *
+ * 请求由本地代理直接应答，因此需要开启代理接管或供应商级本地代理。
+ */
+export function MockProviderField({ value, onChange }: MockProviderFieldProps) {
+  const { t } = useTranslation();
+  const failurePercent =
+    value.failureRate === undefined
+      ? ""
+      : String(Math.round(value.failureRate * 100));
+
+  return (
+    <div className="space-y-2">
+      <FormLabel htmlFor="provider-mock-replies">
+        {t("providerForm.mockReplies", { defaultValue: "模拟回复" })}
+      </FormLabel>
+      <Textarea
+        id="provider-mock-replies"
+        value={(value.replies ?? []).join("\n")}
+        onChange={(e) =>
+          onChange({
+            ...value,
+            replies: e.target.value
+              .split("\n")
+              .filter((line) => line.trim() !== ""),
+          })
+        }
+        placeholder={t("providerForm.mockRepliesPlaceholder", {
+          defaultValue: "每行一条；留空则回显最后一条用户消息",
+        })}
+        rows={3}
+      />
+      <div className="grid grid-cols-3 gap-2">
+        <div className="space-y-1">
+          <FormLabel htmlFor="provider-mock-latency" className="text-xs">
+            {t("providerForm.mockLatency", { defaultValue: "延迟（毫秒）" })}
+          </FormLabel>
+          <Input
+            id="provider-mock-latency"
+            type="number"
+            min={0}
+            value={value.latencyMs ?? ""}
+            onChange={(e) => {
+              const parsed = parseNumber(e.target.value);
+              onChange({
+                ...value,
+                latencyMs:
+                  parsed === undefined ? undefined : Math.floor(parsed),
+              });
+            }}
+            placeholder="0"
+          />
+        </div>
+        <div className="space-y-1">
+          <FormLabel htmlFor="provider-mock-failure-rate" className="text-xs">
+            {t("providerForm.mockFailureRate", {
+              defaultValue: "失败率（%）",
+            })}
+          </FormLabel>
+          <Input
+            id="provider-mock-failure-rate"
+            type="number"
+            min={0}
+            max={100}
+            value={failurePercent}
+            onChange={(e) => {
+              const parsed = parseNumber(e.target.value);
+              onChange({
+                ...value,
+                failureRate:
+                  parsed === undefined
+                    ? undefined
+                    : Math.min(parsed, 100) / 100,
+              });
+            }}
+            placeholder="0"
+          />
+        </div>
+        <div className="space-y-1">
+          <FormLabel htmlFor="provider-mock-failure-status" className="text-xs">
+            {t("providerForm.mockFailureStatus", {
+              defaultValue: "失败状态码",
+            })}
+          </FormLabel>
+          <Input
+            id="provider-mock-failure-status"
+            type="number"
+            min={400}
+            max={599}
+            value={value.failureStatus ?? ""}
+            onChange={(e) => {
+              const parsed = parseNumber(e.target.value);
+              onChange({
+                ...value,
+                failureStatus:
+                  parsed === undefined ? undefined : Math.floor(parsed),
+              });
+            }}
+            placeholder="503"
+          />
+        </div>
+      </div>
+      <p className="text-xs text-muted-foreground">
+        {t("providerForm.mockHint", {
+          defaultValue:
+            "由本地代理直接应答，不消耗 token。需开启代理接管或该供应商的本地代理，可用于演示切换、故障转移与用量统计。",
+        })}
+      </p>
+    </div>
+  );
+}
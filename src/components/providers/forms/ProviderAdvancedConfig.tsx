@@ -1,6 +1,17 @@
 import { useTranslation } from "react-i18next";
 import { useState, useEffect } from "react";
-import { ChevronDown, ChevronRight, Coins } from "lucide-react";
+import {
+  ChevronDown,
+  ChevronRight,
+  Coins,
+  Gauge,
+  Network,
+  Plus,
+  Trash2,
+  Wallet,
+  Wrench,
+} from "lucide-react";
+import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
 import { Switch } from "@/components/ui/switch";
@@ -12,6 +23,11 @@ import {
   SelectValue,
 } from "@/components/ui/select";
 import { cn } from "@/lib/utils";
+import type {
+  MaintenanceWindow,
+  ProviderMeta,
+  SelectedEndpoint,
+} from "@/types";
 export type PricingModelSourceOption = "inherit" | "request" | "response";
 
 interface ProviderPricingConfig {
@@ -20,19 +36,213 @@ interface ProviderPricingConfig {
   pricingModelSource: PricingModelSourceOption;
 }
 
+export interface ProviderBudgetConfig {
+  dailyUsd?: string;
+  monthlyUsd?: string;
+  dailyTokens?: string;
+  monthlyTokens?: string;
+  hardLimit: boolean;
+}
+
 interface ProviderAdvancedConfigProps {
   pricingConfig: ProviderPricingConfig;
   onPricingConfigChange: (config: ProviderPricingConfig) => void;
+  budgetConfig: ProviderBudgetConfig;
+  onBudgetConfigChange: (config: ProviderBudgetConfig) => void;
+  maintenanceWindows: MaintenanceWindow[];
+  onMaintenanceWindowsChange: (windows: MaintenanceWindow[]) => void;
+  latencySloConfig: ProviderLatencySloConfig;
+  onLatencySloConfigChange: (config: ProviderLatencySloConfig) => void;
+  endpointPoolConfig: ProviderEndpointPoolConfig;
+  onEndpointPoolConfigChange: (config: ProviderEndpointPoolConfig) => void;
+  selectedEndpoint?: SelectedEndpoint;
+}
+
+export interface ProviderEndpointPoolConfig {
+  enabled: boolean;
+  onSwitch: boolean;
+  intervalMinutes?: string;
+}
+
+export const endpointPoolConfigFromMeta = (
+  meta?: ProviderMeta,
+): ProviderEndpointPoolConfig => ({
+  enabled: !!meta?.endpointPool,
+  onSwitch: meta?.endpointPool?.onSwitch ?? true,
+  intervalMinutes: meta?.endpointPool?.intervalMinutes
+    ? meta.endpointPool.intervalMinutes.toString()
+    : undefined,
+});
+
+/** 表单值 → meta 字段；未启用时不写入 */
+export const endpointPoolMetaFromConfig = (
+  config: ProviderEndpointPoolConfig,
+): ProviderMeta["endpointPool"] => {
+  if (!config.enabled) return undefined;
+  const interval = Number(config.intervalMinutes?.trim());
+  return {
+    onSwitch: config.onSwitch,
+    intervalMinutes: interval > 0 ? Math.round(interval) : undefined,
+  };
+};
+
+/** 间隔为空或正整数 */
+export const isValidEndpointPoolConfig = (
+  config: ProviderEndpointPoolConfig,
+) =>
+  !config.intervalMinutes?.trim() ||
+  (/^\d+$/.test(config.intervalMinutes.trim()) &&
+    Number(config.intervalMinutes) > 0);
+
+export interface ProviderLatencySloConfig {
+  thresholdMs?: string;
+  percentile?: string;
+  windowMinutes?: string;
+  demote: boolean;
 }
 
+export const latencySloConfigFromMeta = (
+  meta?: ProviderMeta,
+): ProviderLatencySloConfig => ({
+  thresholdMs: meta?.latencySlo?.thresholdMs?.toString(),
+  percentile: meta?.latencySlo?.percentile?.toString(),
+  windowMinutes: meta?.latencySlo?.windowMinutes?.toString(),
+  demote: meta?.latencySlo?.demote ?? false,
+});
+
+/** 表单值 → meta 字段；阈值留空或 0 表示不启用 */
+export const latencySloMetaFromConfig = (
+  config: ProviderLatencySloConfig,
+): ProviderMeta["latencySlo"] => {
+  const threshold = Number(config.thresholdMs?.trim());
+  if (!config.thresholdMs?.trim() || !(threshold > 0)) return undefined;
+  const optional = (value?: string) =>
+    value?.trim() && Number(value) > 0 ? Number(value) : undefined;
+  return {
+    thresholdMs: Math.round(threshold),
+    percentile: optional(config.percentile),
+    windowMinutes: optional(config.windowMinutes),
+    demote: config.demote || undefined,
+  };
+};
+
+/** 百分位 1–100、窗口为正整数；阈值为空时不校验 */
+export const isValidLatencySloConfig = (config: ProviderLatencySloConfig) => {
+  const integer = (value: string | undefined, max?: number) =>
+    !value?.trim() ||
+    (/^\d+$/.test(value.trim()) &&
+      Number(value) > 0 &&
+      (max === undefined || Number(value) <= max));
+  return (
+    integer(config.thresholdMs) &&
+    integer(config.percentile, 100) &&
+    integer(config.windowMinutes)
+  );
+};
+
+export const budgetConfigFromMeta = (
+  meta?: ProviderMeta,
+): ProviderBudgetConfig => ({
+  dailyUsd: meta?.limitDailyUsd,
+  monthlyUsd: meta?.limitMonthlyUsd,
+  dailyTokens: meta?.limitDailyTokens?.toString(),
+  monthlyTokens: meta?.limitMonthlyTokens?.toString(),
+  hardLimit: meta?.budgetHardLimit ?? false,
+});
+
+/** 表单值 → meta 字段；留空或 0 表示不限 */
+export const budgetMetaFromConfig = (
+  config: ProviderBudgetConfig,
+): Pick<
+  ProviderMeta,
+  | "limitDailyUsd"
+  | "limitMonthlyUsd"
+  | "limitDailyTokens"
+  | "limitMonthlyTokens"
+  | "budgetHardLimit"
+> => {
+  const usd = (value?: string) =>
+    value?.trim() && Number(value) > 0 ? value.trim() : undefined;
+  const tokens = (value?: string) =>
+    value?.trim() && Number(value) > 0 ? Number(value) : undefined;
+  const meta = {
+    limitDailyUsd: usd(config.dailyUsd),
+    limitMonthlyUsd: usd(config.monthlyUsd),
+    limitDailyTokens: tokens(config.dailyTokens),
+    limitMonthlyTokens: tokens(config.monthlyTokens),
+  };
+  const hasBudget = Object.values(meta).some((value) => value !== undefined);
+  return {
+    ...meta,
+    budgetHardLimit: hasBudget && config.hardLimit ? true : undefined,
+  };
+};
+
+const TIME_PATTERN = /^([01]\d|2[0-3]):[0-5]\d$/;
+
+/** 开始/结束时间均为 HH:MM 且不相同 */
+export const isValidMaintenanceWindow = (window: MaintenanceWindow) =>
+  TIME_PATTERN.test(window.start) &&
+  TIME_PATTERN.test(window.end) &&
+  window.start !== window.end;
+
+const WEEKDAYS = [1, 2, 3, 4, 5, 6, 7] as const;
+
+const LATENCY_SLO_FIELDS = [
+  { key: "thresholdMs", placeholder: "3000" },
+  { key: "percentile", placeholder: "95" },
+  { key: "windowMinutes", placeholder: "15" },
+] as const;
+
+const BUDGET_FIELDS = [
+  { key: "dailyUsd", step: "0.01", inputMode: "decimal" },
+  { key: "monthlyUsd", step: "0.01", inputMode: "decimal" },
+  { key: "dailyTokens", step: "1", inputMode: "numeric" },
+  { key: "monthlyTokens", step: "1", inputMode: "numeric" },
+] as const;
+
 export function ProviderAdvancedConfig({
   pricingConfig,
   onPricingConfigChange,
+  budgetConfig,
+  onBudgetConfigChange,
+  maintenanceWindows,
+  onMaintenanceWindowsChange,
+  latencySloConfig,
+  onLatencySloConfigChange,
+  endpointPoolConfig,
+  onEndpointPoolConfigChange,
+  selectedEndpoint,
 }: ProviderAdvancedConfigProps) {
   const { t } = useTranslation();
   const [isPricingConfigOpen, setIsPricingConfigOpen] = useState(
     pricingConfig.enabled,
   );
+  const hasBudget = BUDGET_FIELDS.some(({ key }) => !!budgetConfig[key]);
+  const [isBudgetOpen, setIsBudgetOpen] = useState(hasBudget);
+  const [isMaintenanceOpen, setIsMaintenanceOpen] = useState(
+    maintenanceWindows.length > 0,
+  );
+  const hasLatencySlo = !!latencySloConfig.thresholdMs?.trim();
+  const [isLatencySloOpen, setIsLatencySloOpen] = useState(hasLatencySlo);
+  const [isEndpointPoolOpen, setIsEndpointPoolOpen] = useState(
+    endpointPoolConfig.enabled,
+  );
+
+  const updateWindow = (index: number, patch: Partial<MaintenanceWindow>) =>
+    onMaintenanceWindowsChange(
+      maintenanceWindows.map((window, i) =>
+        i === index ? { ...window, ...patch } : window,
+      ),
+    );
+
+  const toggleDay = (index: number, day: number) => {
+    const days = maintenanceWindows[index].days ?? [];
+    const next = days.includes(day)
+      ? days.filter((d) => d !== day)
+      : [...days, day].sort();
+    updateWindow(index, { days: next.length > 0 ? next : undefined });
+  };
 
   useEffect(() => {
     setIsPricingConfigOpen(pricingConfig.enabled);
@@ -177,6 +387,401 @@ export function ProviderAdvancedConfig({
           </div>
         </div>
       </div>
+
+      {/* 预算 */}
+      <div className="rounded-lg border border-border/50 bg-muted/20">
+        <button
+          type="button"
+          className="flex w-full items-center justify-between p-4 hover:bg-muted/30 transition-colors"
+          onClick={() => setIsBudgetOpen(!isBudgetOpen)}
+        >
+          <div className="flex items-center gap-3">
+            <Wallet className="h-4 w-4 text-muted-foreground" />
+            <span className="font-medium">{t("provider.budget.title")}</span>
+          </div>
+          {isBudgetOpen ? (
+            <ChevronDown className="h-4 w-4 text-muted-foreground" />
+          ) : (
+            <ChevronRight className="h-4 w-4 text-muted-foreground" />
+          )}
+        </button>
+        <div
+          className={cn(
+            "overflow-hidden transition-all duration-200",
+            isBudgetOpen ? "max-h-[600px] opacity-100" : "max-h-0 opacity-0",
+          )}
+        >
+          <div className="border-t border-border/50 p-4 space-y-4">
+            <p className="text-sm text-muted-foreground">
+              {t("provider.budget.description")}
+            </p>
+            <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+              {BUDGET_FIELDS.map(({ key, step, inputMode }) => (
+                <div key={key} className="space-y-2">
+                  <Label htmlFor={`budget-${key}`}>
+                    {t(`provider.budget.${key}`)}
+                  </Label>
+                  <Input
+                    id={`budget-${key}`}
+                    type="number"
+                    step={step}
+                    min="0"
+                    inputMode={inputMode}
+                    value={budgetConfig[key] || ""}
+                    onChange={(e) =>
+                      onBudgetConfigChange({
+                        ...budgetConfig,
+                        [key]: e.target.value || undefined,
+                      })
+                    }
+                    placeholder={t("provider.budget.unlimited")}
+                  />
+                </div>
+              ))}
+            </div>
+            <div className="flex items-center justify-between gap-4">
+              <div className="space-y-1">
+                <Label htmlFor="budget-hard-limit">
+                  {t("provider.budget.hardLimit")}
+                </Label>
+                <p className="text-xs text-muted-foreground">
+                  {t("provider.budget.hardLimitHint")}
+                </p>
+              </div>
+              <Switch
+                id="budget-hard-limit"
+                checked={budgetConfig.hardLimit}
+                disabled={!hasBudget}
+                onCheckedChange={(checked) =>
+                  onBudgetConfigChange({ ...budgetConfig, hardLimit: checked })
+                }
+              />
+            </div>
+          </div>
+        </div>
+      </div>
+
+      {/* 维护时段 */}
+      <div className="rounded-lg border border-border/50 bg-muted/20">
+        <button
+          type="button"
+          className="flex w-full items-center justify-between p-4 hover:bg-muted/30 transition-colors"
+          onClick={() => setIsMaintenanceOpen(!isMaintenanceOpen)}
+        >
+          <div className="flex items-center gap-3">
+            <Wrench className="h-4 w-4 text-muted-foreground" />
+            <span className="font-medium">
+              {t("provider.maintenance.title")}
+            </span>
+          </div>
+          {isMaintenanceOpen ? (
+            <ChevronDown className="h-4 w-4 text-muted-foreground" />
+          ) : (
+            <ChevronRight className="h-4 w-4 text-muted-foreground" />
+          )}
+        </button>
+        <div
+          className={cn(
+            "overflow-hidden transition-all duration-200",
+            isMaintenanceOpen
+              ? "max-h-[1200px] opacity-100"
+              : "max-h-0 opacity-0",
+          )}
+        >
+          <div className="border-t border-border/50 p-4 space-y-4">
+            <p className="text-sm text-muted-foreground">
+              {t("provider.maintenance.description")}
+            </p>
+            {maintenanceWindows.map((window, index) => (
+              <div
+                key={index}
+                className="space-y-3 rounded-md border border-border/50 p-3"
+              >
+                <div className="grid grid-cols-1 md:grid-cols-[1fr_1fr_1.5fr_auto] gap-3 items-end">
+                  <div className="space-y-2">
+                    <Label htmlFor={`maintenance-start-${index}`}>
+                      {t("provider.maintenance.start")}
+                    </Label>
+                    <Input
+                      id={`maintenance-start-${index}`}
+                      type="time"
+                      value={window.start}
+                      onChange={(e) =>
+                        updateWindow(index, { start: e.target.value })
+                      }
+                    />
+                  </div>
+                  <div className="space-y-2">
+                    <Label htmlFor={`maintenance-end-${index}`}>
+                      {t("provider.maintenance.end")}
+                    </Label>
+                    <Input
+                      id={`maintenance-end-${index}`}
+                      type="time"
+                      value={window.end}
+                      onChange={(e) =>
+                        updateWindow(index, { end: e.target.value })
+                      }
+                    />
+                  </div>
+                  <div className="space-y-2">
+                    <Label htmlFor={`maintenance-tz-${index}`}>
+                      {t("provider.maintenance.timezone")}
+                    </Label>
+                    <Input
+                      id={`maintenance-tz-${index}`}
+                      value={window.timezone ?? ""}
+                      onChange={(e) =>
+                        updateWindow(index, {
+                          timezone: e.target.value || undefined,
+                        })
+                      }
+                      placeholder={t(
+                        "provider.maintenance.timezonePlaceholder",
+                      )}
+                    />
+                  </div>
+                  <Button
+                    type="button"
+                    variant="ghost"
+                    size="icon"
+                    aria-label={t("provider.maintenance.remove")}
+                    onClick={() =>
+                      onMaintenanceWindowsChange(
+                        maintenanceWindows.filter((_, i) => i !== index),
+                      )
+                    }
+                  >
+                    <Trash2 className="h-4 w-4" />
+                  </Button>
+                </div>
+                <div className="flex flex-wrap items-center gap-1">
+                  {WEEKDAYS.map((day) => {
+                    const active = (window.days ?? []).includes(day);
+                    return (
+                      <Button
+                        key={day}
+                        type="button"
+                        size="sm"
+                        variant={active ? "default" : "outline"}
+                        className="h-7 px-2 text-xs"
+                        onClick={() => toggleDay(index, day)}
+                      >
+                        {t(`provider.maintenance.weekday.${day}`)}
+                      </Button>
+                    );
+                  })}
+                  <span className="ml-2 text-xs text-muted-foreground">
+                    {(window.days ?? []).length === 0
+                      ? t("provider.maintenance.everyDay")
+                      : null}
+                  </span>
+                </div>
+              </div>
+            ))}
+            <Button
+              type="button"
+              variant="outline"
+              size="sm"
+              onClick={() =>
+                onMaintenanceWindowsChange([
+                  ...maintenanceWindows,
+                  { start: "03:00", end: "03:10" },
+                ])
+              }
+            >
+              <Plus className="mr-2 h-4 w-4" />
+              {t("provider.maintenance.add")}
+            </Button>
+          </div>
+        </div>
+      </div>
+
+      {/* 首字延迟 SLO */}
+      <div className="rounded-lg border border-border/50 bg-muted/20">
+        <button
+          type="button"
+          className="flex w-full items-center justify-between p-4 hover:bg-muted/30 transition-colors"
+          onClick={() => setIsLatencySloOpen(!isLatencySloOpen)}
+        >
+          <div className="flex items-center gap-3">
+            <Gauge className="h-4 w-4 text-muted-foreground" />
+            <span className="font-medium">
+              {t("provider.latencySlo.title")}
+            </span>
+          </div>
+          {isLatencySloOpen ? (
+            <ChevronDown className="h-4 w-4 text-muted-foreground" />
+          ) : (
+            <ChevronRight className="h-4 w-4 text-muted-foreground" />
+          )}
+        </button>
+        <div
+          className={cn(
+            "overflow-hidden transition-all duration-200",
+            isLatencySloOpen
+              ? "max-h-[600px] opacity-100"
+              : "max-h-0 opacity-0",
+          )}
+        >
+          <div className="border-t border-border/50 p-4 space-y-4">
+            <p className="text-sm text-muted-foreground">
+              {t("provider.latencySlo.description")}
+            </p>
+            <div className="grid grid-cols-1 md:grid-cols-3 gap-4">
+              {LATENCY_SLO_FIELDS.map(({ key, placeholder }) => (
+                <div key={key} className="space-y-2">
+                  <Label htmlFor={`latency-slo-${key}`}>
+                    {t(`provider.latencySlo.${key}`)}
+                  </Label>
+                  <Input
+                    id={`latency-slo-${key}`}
+                    type="number"
+                    step="1"
+                    min="1"
+                    inputMode="numeric"
+                    value={latencySloConfig[key] || ""}
+                    onChange={(e) =>
+                      onLatencySloConfigChange({
+                        ...latencySloConfig,
+                        [key]: e.target.value || undefined,
+                      })
+                    }
+                    placeholder={placeholder}
+                  />
+                </div>
+              ))}
+            </div>
+            <div className="flex items-center justify-between gap-4">
+              <div className="space-y-1">
+                <Label htmlFor="latency-slo-demote">
+                  {t("provider.latencySlo.demote")}
+                </Label>
+                <p className="text-xs text-muted-foreground">
+                  {t("provider.latencySlo.demoteHint")}
+                </p>
+              </div>
+              <Switch
+                id="latency-slo-demote"
+                checked={latencySloConfig.demote}
+                disabled={!hasLatencySlo}
+                onCheckedChange={(checked) =>
+                  onLatencySloConfigChange({
+                    ...latencySloConfig,
+                    demote: checked,
+                  })
+                }
+              />
+            </div>
+          </div>
+        </div>
+      </div>
+
+      {/* 候选端点池 */}
+      <div className="rounded-lg border border-border/50 bg-muted/20">
+        <button
+          type="button"
+          className="flex w-full items-center justify-between p-4 hover:bg-muted/30 transition-colors"
+          onClick={() => setIsEndpointPoolOpen(!isEndpointPoolOpen)}
+        >
+          <div className="flex items-center gap-3">
+            <Network className="h-4 w-4 text-muted-foreground" />
+            <span className="font-medium">
+              {t("provider.endpointPool.title")}
+            </span>
+          </div>
+          <div className="flex items-center gap-3">
+            <div
+              className="flex items-center gap-2"
+              onClick={(e) => e.stopPropagation()}
+            >
+              <Label
+                htmlFor="endpoint-pool-enabled"
+                className="text-sm text-muted-foreground"
+              >
+                {t("provider.endpointPool.enabled")}
+              </Label>
+              <Switch
+                id="endpoint-pool-enabled"
+                checked={endpointPoolConfig.enabled}
+                onCheckedChange={(checked) => {
+                  onEndpointPoolConfigChange({
+                    ...endpointPoolConfig,
+                    enabled: checked,
+                  });
+                  if (checked) setIsEndpointPoolOpen(true);
+                }}
+              />
+            </div>
+            {isEndpointPoolOpen ? (
+              <ChevronDown className="h-4 w-4 text-muted-foreground" />
+            ) : (
+              <ChevronRight className="h-4 w-4 text-muted-foreground" />
+            )}
+          </div>
+        </button>
+        <div
+          className={cn(
+            "overflow-hidden transition-all duration-200",
+            isEndpointPoolOpen
+              ? "max-h-[600px] opacity-100"
+              : "max-h-0 opacity-0",
+          )}
+        >
+          <div className="border-t border-border/50 p-4 space-y-4">
+            <p className="text-sm text-muted-foreground">
+              {t("provider.endpointPool.description")}
+            </p>
+            <div className="flex items-center justify-between gap-4">
+              <Label htmlFor="endpoint-pool-on-switch">
+                {t("provider.endpointPool.onSwitch")}
+              </Label>
+              <Switch
+                id="endpoint-pool-on-switch"
+                checked={endpointPoolConfig.onSwitch}
+                disabled={!endpointPoolConfig.enabled}
+                onCheckedChange={(checked) =>
+                  onEndpointPoolConfigChange({
+                    ...endpointPoolConfig,
+                    onSwitch: checked,
+                  })
+                }
+              />
+            </div>
+            <div className="space-y-2">
+              <Label htmlFor="endpoint-pool-interval">
+                {t("provider.endpointPool.intervalMinutes")}
+              </Label>
+              <Input
+                id="endpoint-pool-interval"
+                type="number"
+                step="1"
+                min="1"
+                inputMode="numeric"
+                value={endpointPoolConfig.intervalMinutes || ""}
+                disabled={!endpointPoolConfig.enabled}
+                onChange={(e) =>
+                  onEndpointPoolConfigChange({
+                    ...endpointPoolConfig,
+                    intervalMinutes: e.target.value || undefined,
+                  })
+                }
+                placeholder={t("provider.endpointPool.intervalPlaceholder")}
+              />
+            </div>
+            {selectedEndpoint && (
+              <p className="text-xs text-muted-foreground break-all">
+                {t("provider.endpointPool.lastSelected", {
+                  url: selectedEndpoint.url,
+                  latency: selectedEndpoint.latencyMs,
+                  count: selectedEndpoint.candidates,
+                  time: new Date(selectedEndpoint.selectedAt).toLocaleString(),
+                })}
+              </p>
+            )}
+          </div>
+        </div>
+      </div>
     </div>
   );
 }
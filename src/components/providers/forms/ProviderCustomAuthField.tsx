@@ -0,0 +1,186 @@
+import { useTranslation } from "react-i18next";
+import { FormLabel } from "@/components/ui/form";
+import { Input } from "@/components/ui/input";
+import { Textarea } from "@/components/ui/textarea";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import type { CustomAuthScheme, HmacAuthOptions } from "@/types";
+
+type AuthType = CustomAuthScheme["type"] | "default";
+
+const AUTH_TYPES: AuthType[] = ["default", "queryParam", "cookie", "hmac"];
+
+const DEFAULT_TEMPLATE = "{method}\n{path}\n{timestamp}\n{body_sha256}";
+
+interface ProviderCustomAuthFieldProps {
+  value?: CustomAuthScheme;
+  onChange: (value: CustomAuthScheme | undefined) => void;
+}
+
+const emptyScheme = (type: AuthType): CustomAuthScheme | undefined => {
+  switch (type) {
+    case "queryParam":
+      return { type, param: "key" };
+    case "cookie":
+      return { type, name: "" };
+    case "hmac":
+      return { type, secret: "" };
+    default:
+      return undefined;
+  }
+};
+
+/**
+ * 自定义鉴权方式：密钥放在查询参数 / Cookie 中，或对请求做 HMAC 签名。
+ *
+ * 配置后本地代理转发、测试消息、能力探测与复制为 curl 都改用该方式，
+ * 不再发送默认的 x-api-key / Authorization 头。
+ */
+export function ProviderCustomAuthField({
+  value,
+  onChange,
+}: ProviderCustomAuthFieldProps) {
+  const { t } = useTranslation();
+  const type: AuthType = value?.type ?? "default";
+
+  const updateHmac = (patch: Partial<HmacAuthOptions>) => {
+    if (value?.type === "hmac") {
+      onChange({ ...value, ...patch });
+    }
+  };
+
+  const hmacInput = (
+    key: keyof Omit<HmacAuthOptions, "secret" | "template" | "encoding">,
+    placeholder: string,
+  ) => (
+    <div className="space-y-1">
+      <FormLabel htmlFor={`provider-custom-auth-${key}`} className="text-xs">
+        {t(`providerForm.customAuth.${key}`)}
+      </FormLabel>
+      <Input
+        id={`provider-custom-auth-${key}`}
+        value={value?.type === "hmac" ? (value[key] ?? "") : ""}
+        onChange={(e) => updateHmac({ [key]: e.target.value })}
+        placeholder={placeholder}
+        autoComplete="off"
+      />
+    </div>
+  );
+
+  return (
+    <div className="space-y-2">
+      <FormLabel htmlFor="provider-custom-auth">
+        {t("providerForm.customAuth.label")}
+      </FormLabel>
+      <Select
+        value={type}
+        onValueChange={(next) => onChange(emptyScheme(next as AuthType))}
+      >
+        <SelectTrigger id="provider-custom-auth">
+          <SelectValue />
+        </SelectTrigger>
+        <SelectContent>
+          {AUTH_TYPES.map((option) => (
+            <SelectItem key={option} value={option}>
+              {t(`providerForm.customAuth.type.${option}`)}
+            </SelectItem>
+          ))}
+        </SelectContent>
+      </Select>
+      <p className="text-xs text-muted-foreground">
+        {t("providerForm.customAuth.hint")}
+      </p>
+
+      {value?.type === "queryParam" && (
+        <Input
+          value={value.param}
+          onChange={(e) => onChange({ ...value, param: e.target.value })}
+          placeholder="key"
+          autoComplete="off"
+        />
+      )}
+
+      {value?.type === "cookie" && (
+        <Input
+          value={value.name}
+          onChange={(e) => onChange({ ...value, name: e.target.value })}
+          placeholder="session"
+          autoComplete="off"
+        />
+      )}
+
+      {value?.type === "hmac" && (
+        <div className="space-y-3 rounded-md border border-border/50 p-3">
+          <div className="space-y-1">
+            <FormLabel
+              htmlFor="provider-custom-auth-secret"
+              className="text-xs"
+            >
+              {t("providerForm.customAuth.secret")}
+            </FormLabel>
+            <Input
+              id="provider-custom-auth-secret"
+              type="password"
+              value={value.secret}
+              onChange={(e) => updateHmac({ secret: e.target.value })}
+              autoComplete="off"
+            />
+          </div>
+          <div className="space-y-1">
+            <FormLabel
+              htmlFor="provider-custom-auth-template"
+              className="text-xs"
+            >
+              {t("providerForm.customAuth.template")}
+            </FormLabel>
+            <Textarea
+              id="provider-custom-auth-template"
+              rows={3}
+              className="font-mono text-xs"
+              value={value.template ?? ""}
+              onChange={(e) => updateHmac({ template: e.target.value })}
+              placeholder={DEFAULT_TEMPLATE}
+            />
+            <p className="text-xs text-muted-foreground">
+              {t("providerForm.customAuth.templateHint")}
+            </p>
+          </div>
+          <div className="grid grid-cols-2 gap-3">
+            {hmacInput("signatureHeader", "x-signature")}
+            {hmacInput("signaturePrefix", "HMAC-SHA256 ")}
+            {hmacInput("timestampHeader", "x-timestamp")}
+            {hmacInput("nonceHeader", "x-nonce")}
+            {hmacInput("keyHeader", "x-api-key")}
+            <div className="space-y-1">
+              <FormLabel
+                htmlFor="provider-custom-auth-encoding"
+                className="text-xs"
+              >
+                {t("providerForm.customAuth.encoding")}
+              </FormLabel>
+              <Select
+                value={value.encoding ?? "hex"}
+                onValueChange={(encoding) =>
+                  updateHmac({ encoding: encoding as "hex" | "base64" })
+                }
+              >
+                <SelectTrigger id="provider-custom-auth-encoding">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value="hex">hex</SelectItem>
+                  <SelectItem value="base64">base64</SelectItem>
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -23,6 +23,12 @@ import type {
   CodexChatReasoning,
   PromptCacheRoutingMode,
   ClaudeApiKeyField,
+  ProviderTimeouts,
+  MockProviderOptions,
+  ProviderTlsOptions,
+  MaintenanceWindow,
+  ClientIdentity,
+  CustomAuthScheme,
 } from "@/types";
 import {
   providerPresets,
@@ -80,11 +86,28 @@ import { ClaudeDesktopProviderForm } from "./ClaudeDesktopProviderForm";
 import { GrokBuildProviderForm } from "./GrokBuildProviderForm";
 import { CodexFormFields } from "./CodexFormFields";
 import { GeminiFormFields } from "./GeminiFormFields";
+import { ProviderTimeoutsField } from "./ProviderTimeoutsField";
+import { ProviderTextRepairField } from "./ProviderTextRepairField";
+import { MockProviderField, isMockBaseUrl } from "./MockProviderField";
+import { ProviderTlsField } from "./ProviderTlsField";
+import { ProviderCustomAuthField } from "./ProviderCustomAuthField";
 import { OmoFormFields } from "./OmoFormFields";
 import { parseOmoOtherFieldsObject } from "@/types/omo";
 import {
   ProviderAdvancedConfig,
+  budgetConfigFromMeta,
+  budgetMetaFromConfig,
+  endpointPoolConfigFromMeta,
+  endpointPoolMetaFromConfig,
+  isValidEndpointPoolConfig,
+  isValidLatencySloConfig,
+  latencySloConfigFromMeta,
+  latencySloMetaFromConfig,
+  isValidMaintenanceWindow,
   type PricingModelSourceOption,
+  type ProviderBudgetConfig,
+  type ProviderEndpointPoolConfig,
+  type ProviderLatencySloConfig,
 } from "./ProviderAdvancedConfig";
 import {
   useProviderCategory,
@@ -179,6 +202,68 @@ export const normalizeCodexCatalogModelsForSave = (
   return normalized;
 };
 
+const normalizeProviderTls = (
+  value: ProviderTlsOptions,
+): ProviderTlsOptions | undefined => {
+  const caCertPath = value.caCertPath?.trim();
+  const insecureSkipVerify = value.insecureSkipVerify === true;
+  if (!caCertPath && !insecureSkipVerify) {
+    return undefined;
+  }
+  return {
+    ...(caCertPath ? { caCertPath } : {}),
+    ...(insecureSkipVerify ? { insecureSkipVerify } : {}),
+  };
+};
+
+// 旧数据只有 customUserAgent 时视为自定义标识
+const clientIdentityFromMeta = (meta?: ProviderMeta): ClientIdentity =>
+  meta?.clientIdentity ?? (meta?.customUserAgent ? "custom" : "passthrough");
+
+const normalizeProviderTimeouts = (
+  value: ProviderTimeouts,
+): ProviderTimeouts | undefined => {
+  const entries = Object.entries(value).filter(
+    ([, secs]) => typeof secs === "number" && secs >= 0,
+  );
+  return entries.length > 0 ? Object.fromEntries(entries) : undefined;
+};
+
+// 去掉空白的可选项；名称 / 密钥为空时视为未配置
+const normalizeCustomAuth = (
+  value?: CustomAuthScheme,
+): CustomAuthScheme | undefined => {
+  switch (value?.type) {
+    case "queryParam":
+      return value.param.trim()
+        ? { type: "queryParam", param: value.param.trim() }
+        : undefined;
+    case "cookie":
+      return value.name.trim()
+        ? { type: "cookie", name: value.name.trim() }
+        : undefined;
+    case "hmac": {
+      if (!value.secret) {
+        return undefined;
+      }
+      const trimmed = (field?: string) => field?.trim() || undefined;
+      return {
+        type: "hmac",
+        secret: value.secret,
+        template: value.template || undefined,
+        signatureHeader: trimmed(value.signatureHeader),
+        signaturePrefix: value.signaturePrefix || undefined,
+        timestampHeader: trimmed(value.timestampHeader),
+        nonceHeader: trimmed(value.nonceHeader),
+        keyHeader: trimmed(value.keyHeader),
+        encoding: value.encoding === "base64" ? "base64" : undefined,
+      };
+    }
+    default:
+      return undefined;
+  }
+};
+
 const normalizeCodexChatReasoningForSave = (
   value?: CodexChatReasoning,
 ): CodexChatReasoning | undefined => {
@@ -332,6 +417,21 @@ function ProviderFormFull({
     ),
   }));
 
+  const [budgetConfig, setBudgetConfig] = useState<ProviderBudgetConfig>(
+    () => budgetConfigFromMeta(initialData?.meta),
+  );
+  const [maintenanceWindows, setMaintenanceWindows] = useState<
+    MaintenanceWindow[]
+  >(() => initialData?.meta?.maintenanceWindows ?? []);
+  const [latencySloConfig, setLatencySloConfig] =
+    useState<ProviderLatencySloConfig>(() =>
+      latencySloConfigFromMeta(initialData?.meta),
+    );
+  const [endpointPoolConfig, setEndpointPoolConfig] =
+    useState<ProviderEndpointPoolConfig>(() =>
+      endpointPoolConfigFromMeta(initialData?.meta),
+    );
+
   const { category } = useProviderCategory({
     appId,
     selectedPresetId,
@@ -341,6 +441,10 @@ function ProviderFormFull({
   const isOmoCategory = appId === "opencode" && category === "omo";
   const isOmoSlimCategory = appId === "opencode" && category === "omo-slim";
   const isAnyOmoCategory = isOmoCategory || isOmoSlimCategory;
+  // 自定义鉴权作用于本地代理转发（Claude / Codex / Gemini 适配器）
+  const supportsCustomAuth =
+    (appId === "claude" || appId === "codex" || appId === "gemini") &&
+    category !== "official";
 
   useEffect(() => {
     setSelectedPresetId(initialData ? null : "custom");
@@ -362,9 +466,17 @@ function ProviderFormFull({
         initialData?.meta?.pricingModelSource,
       ),
     });
+    setBudgetConfig(budgetConfigFromMeta(initialData?.meta));
+    setMaintenanceWindows(initialData?.meta?.maintenanceWindows ?? []);
+    setLatencySloConfig(latencySloConfigFromMeta(initialData?.meta));
+    setEndpointPoolConfig(endpointPoolConfigFromMeta(initialData?.meta));
     setCodexChatReasoning(initialData?.meta?.codexChatReasoning ?? {});
     setPromptCacheRouting(initialData?.meta?.promptCacheRouting ?? "auto");
     setCustomUserAgent(initialData?.meta?.customUserAgent ?? "");
+    setClientIdentity(clientIdentityFromMeta(initialData?.meta));
+    setProviderTls(initialData?.meta?.tls ?? {});
+    setCustomAuth(initialData?.meta?.customAuth);
+    setProviderTimeouts(initialData?.meta?.timeouts ?? {});
     setLocalProxyHeadersOverride(
       formatRequestOverrideObject(
         initialData?.meta?.localProxyRequestOverrides?.headers,
@@ -555,6 +667,24 @@ function ProviderFormFull({
   const [customUserAgent, setCustomUserAgent] = useState<string>(
     () => initialData?.meta?.customUserAgent ?? "",
   );
+  const [clientIdentity, setClientIdentity] = useState<ClientIdentity>(() =>
+    clientIdentityFromMeta(initialData?.meta),
+  );
+  const [providerTls, setProviderTls] = useState<ProviderTlsOptions>(
+    () => initialData?.meta?.tls ?? {},
+  );
+  const [providerTimeouts, setProviderTimeouts] = useState<ProviderTimeouts>(
+    () => initialData?.meta?.timeouts ?? {},
+  );
+  const [customAuth, setCustomAuth] = useState<CustomAuthScheme | undefined>(
+    () => initialData?.meta?.customAuth,
+  );
+  const [repairResponseText, setRepairResponseText] = useState<boolean>(
+    () => initialData?.meta?.repairResponseText ?? false,
+  );
+  const [mockOptions, setMockOptions] = useState<MockProviderOptions>(
+    () => initialData?.meta?.mock ?? {},
+  );
   const [localProxyHeadersOverride, setLocalProxyHeadersOverride] =
     useState<string>(() =>
       formatRequestOverrideObject(
@@ -1075,6 +1205,33 @@ function ProviderFormFull({
       return;
     }
 
+    const budgetUsd = [budgetConfig.dailyUsd, budgetConfig.monthlyUsd];
+    const budgetTokens = [budgetConfig.dailyTokens, budgetConfig.monthlyTokens];
+    if (
+      budgetUsd.some(
+        (v) => v?.trim() && !isNonNegativeDecimalString(v.trim()),
+      ) ||
+      budgetTokens.some((v) => v?.trim() && !/^\d+$/.test(v.trim()))
+    ) {
+      toast.error(t("provider.budget.invalid"));
+      return;
+    }
+
+    if (!maintenanceWindows.every(isValidMaintenanceWindow)) {
+      toast.error(t("provider.maintenance.invalid"));
+      return;
+    }
+
+    if (!isValidLatencySloConfig(latencySloConfig)) {
+      toast.error(t("provider.latencySlo.invalid"));
+      return;
+    }
+
+    if (!isValidEndpointPoolConfig(endpointPoolConfig)) {
+      toast.error(t("provider.endpointPool.invalid"));
+      return;
+    }
+
     // opencode / openclaw / hermes: providerKey 相关
     // A 类（空）归到 issues；B 类（正则不合法 / 重复 / 状态加载中）仍硬拒绝
     const keyPattern = /^[a-z0-9]+(-[a-z0-9]+)*$/;
@@ -1596,9 +1753,32 @@ function ProviderFormFull({
           ? promptCacheRouting
           : undefined,
       customUserAgent:
-        (appId === "claude" || appId === "codex") && category !== "official"
+        (appId === "claude" || appId === "codex") &&
+        category !== "official" &&
+        clientIdentity === "custom"
           ? customUserAgent.trim() || undefined
           : undefined,
+      clientIdentity:
+        (appId === "claude" || appId === "codex") &&
+        category !== "official" &&
+        clientIdentity !== "passthrough"
+          ? clientIdentity
+          : undefined,
+      tls:
+        category !== "official"
+          ? normalizeProviderTls(providerTls)
+          : undefined,
+      timeouts:
+        category !== "official"
+          ? normalizeProviderTimeouts(providerTimeouts)
+          : undefined,
+      customAuth: supportsCustomAuth
+        ? normalizeCustomAuth(customAuth)
+        : undefined,
+      repairResponseText:
+        category !== "official" && repairResponseText ? true : undefined,
+      mock:
+        appId === "claude" && isMockBaseUrl(baseUrl) ? mockOptions : undefined,
       localProxyRequestOverrides: shouldApplyLocalProxyRequestOverrides
         ? overridesResult.overrides
         : undefined,
@@ -1609,6 +1789,11 @@ function ProviderFormFull({
         pricingConfig.enabled && pricingConfig.pricingModelSource !== "inherit"
           ? pricingConfig.pricingModelSource
           : undefined,
+      ...budgetMetaFromConfig(budgetConfig),
+      maintenanceWindows:
+        maintenanceWindows.length > 0 ? maintenanceWindows : undefined,
+      latencySlo: latencySloMetaFromConfig(latencySloConfig),
+      endpointPool: endpointPoolMetaFromConfig(endpointPoolConfig),
       apiFormat:
         appId === "claude" && category !== "official"
           ? isXaiOauthProvider
@@ -2187,6 +2372,7 @@ function ProviderFormFull({
           {appId === "claude" && (
             <ClaudeFormFields
               providerId={providerId}
+              modelAliases={settingsData?.modelAliases}
               shouldShowApiKey={
                 (category !== "cloud_provider" ||
                   hasApiKeyField(form.getValues("settingsConfig"), "claude")) &&
@@ -2269,6 +2455,8 @@ function ProviderFormFull({
               onFullUrlChange={setLocalIsFullUrl}
               customUserAgent={customUserAgent}
               onCustomUserAgentChange={setCustomUserAgent}
+              clientIdentity={clientIdentity}
+              onClientIdentityChange={setClientIdentity}
               localProxyHeadersOverride={localProxyHeadersOverride}
               onLocalProxyHeadersOverrideChange={setLocalProxyHeadersOverride}
               localProxyBodyOverride={localProxyBodyOverride}
@@ -2324,6 +2512,8 @@ function ProviderFormFull({
               speedTestEndpoints={speedTestEndpoints}
               customUserAgent={customUserAgent}
               onCustomUserAgentChange={setCustomUserAgent}
+              clientIdentity={clientIdentity}
+              onClientIdentityChange={setClientIdentity}
               localProxyHeadersOverride={localProxyHeadersOverride}
               onLocalProxyHeadersOverrideChange={setLocalProxyHeadersOverride}
               localProxyBodyOverride={localProxyBodyOverride}
@@ -2334,6 +2524,7 @@ function ProviderFormFull({
           {appId === "gemini" && (
             <GeminiFormFields
               providerId={providerId}
+              modelAliases={settingsData?.modelAliases}
               shouldShowApiKey={shouldShowApiKey(
                 form.getValues("settingsConfig"),
                 isEditMode,
@@ -2360,6 +2551,35 @@ function ProviderFormFull({
             />
           )}
 
+          {category !== "official" && !isAnyOmoCategory && (
+            <ProviderTlsField value={providerTls} onChange={setProviderTls} />
+          )}
+
+          {supportsCustomAuth && !isAnyOmoCategory && (
+            <ProviderCustomAuthField
+              value={customAuth}
+              onChange={setCustomAuth}
+            />
+          )}
+
+          {category !== "official" && !isAnyOmoCategory && (
+            <ProviderTimeoutsField
+              value={providerTimeouts}
+              onChange={setProviderTimeouts}
+            />
+          )}
+
+          {appId === "claude" && isMockBaseUrl(baseUrl) && (
+            <MockProviderField value={mockOptions} onChange={setMockOptions} />
+          )}
+
+          {category !== "official" && !isAnyOmoCategory && (
+            <ProviderTextRepairField
+              checked={repairResponseText}
+              onChange={setRepairResponseText}
+            />
+          )}
+
           {appId === "opencode" && !isAnyOmoCategory && (
             <OpenCodeFormFields
               npm={opencodeForm.opencodeNpm}
@@ -2602,6 +2822,15 @@ function ProviderFormFull({
               <ProviderAdvancedConfig
                 pricingConfig={pricingConfig}
                 onPricingConfigChange={setPricingConfig}
+                budgetConfig={budgetConfig}
+                onBudgetConfigChange={setBudgetConfig}
+                maintenanceWindows={maintenanceWindows}
+                onMaintenanceWindowsChange={setMaintenanceWindows}
+                latencySloConfig={latencySloConfig}
+                onLatencySloConfigChange={setLatencySloConfig}
+                endpointPoolConfig={endpointPoolConfig}
+                onEndpointPoolConfigChange={setEndpointPoolConfig}
+                selectedEndpoint={initialData?.meta?.selectedEndpoint}
               />
             )}
 
@@ -0,0 +1,41 @@
+import { useTranslation } from "react-i18next";
+import { FormLabel } from "@/components/ui/form";
+import { Switch } from "@/components/ui/switch";
+
+interface ProviderTextRepairFieldProps {
+  checked: boolean;
+  onChange: (checked: boolean) => void;
+}
+
+/**
+ * 响应编码修复开关。仅在本地代理转发时生效，修复乱码、双重转义与 GBK 编码的中日韩文本。
+ */
+export function ProviderTextRepairField({
+  checked,
+  onChange,
+}: ProviderTextRepairFieldProps) {
+  const { t } = useTranslation();
+
+  return (
+    <div className="flex items-center justify-between gap-4">
+      <div className="space-y-0.5">
+        <FormLabel htmlFor="provider-repair-response-text">
+          {t("providerForm.repairResponseText", {
+            defaultValue: "修复响应中的中文乱码",
+          })}
+        </FormLabel>
+        <p className="text-xs text-muted-foreground">
+          {t("providerForm.repairResponseTextHint", {
+            defaultValue:
+              "中转返回乱码、\\uXXXX 转义或 GBK 编码的文本时开启。仅作用于本地代理转发，修复时会记录日志。",
+          })}
+        </p>
+      </div>
+      <Switch
+        id="provider-repair-response-text"
+        checked={checked}
+        onCheckedChange={onChange}
+      />
+    </div>
+  );
+}
@@ -0,0 +1,87 @@
+import { useTranslation } from "react-i18next";
+import { FormLabel } from "@/components/ui/form";
+import { Input } from "@/components/ui/input";
+import type { ProviderTimeouts } from "@/types";
+
+interface ProviderTimeoutsFieldProps {
+  value: ProviderTimeouts;
+  onChange: (value: ProviderTimeouts) => void;
+}
+
+const FIELDS = [
+  {
+    key: "connectTimeoutSecs",
+    labelKey: "providerForm.timeoutConnect",
+    defaultLabel: "连接超时",
+  },
+  {
+    key: "totalTimeoutSecs",
+    labelKey: "providerForm.timeoutTotal",
+    defaultLabel: "非流式总超时",
+  },
+  {
+    key: "streamIdleTimeoutSecs",
+    labelKey: "providerForm.timeoutStreamIdle",
+    defaultLabel: "流式静默期超时",
+  },
+] as const;
+
+/**
+ * 供应商级超时覆盖（秒）。留空沿用代理设置中的应用级超时，0 表示不限制。
+ *
+ * 后端在本地代理转发中使用全部三项，连通性检查使用连接超时。
+ */
+export function ProviderTimeoutsField({
+  value,
+  onChange,
+}: ProviderTimeoutsFieldProps) {
+  const { t } = useTranslation();
+
+  const handleChange = (key: keyof ProviderTimeouts, raw: string) => {
+    const trimmed = raw.trim();
+    const parsed = Number(trimmed);
+    onChange({
+      ...value,
+      [key]:
+        trimmed === "" || !Number.isFinite(parsed) || parsed < 0
+          ? undefined
+          : Math.floor(parsed),
+    });
+  };
+
+  return (
+    <div className="space-y-2">
+      <FormLabel>
+        {t("providerForm.timeouts", { defaultValue: "超时覆盖（秒）" })}
+      </FormLabel>
+      <div className="grid grid-cols-3 gap-2">
+        {FIELDS.map((field) => (
+          <div key={field.key} className="space-y-1">
+            <label
+              htmlFor={`provider-timeout-${field.key}`}
+              className="text-xs text-muted-foreground"
+            >
+              {t(field.labelKey, { defaultValue: field.defaultLabel })}
+            </label>
+            <Input
+              id={`provider-timeout-${field.key}`}
+              type="number"
+              min={0}
+              value={value[field.key] ?? ""}
+              onChange={(e) => handleChange(field.key, e.target.value)}
+              placeholder={t("providerForm.timeoutInherit", {
+                defaultValue: "沿用全局",
+              })}
+            />
+          </div>
+        ))}
+      </div>
+      <p className="text-xs text-muted-foreground">
+        {t("providerForm.timeoutsHint", {
+          defaultValue:
+            "留空沿用代理设置，0 表示不限制。慢速推理模型可放宽总超时与静默期，不稳定的中转可缩短连接超时以尽快故障转移。",
+        })}
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1,92 @@
+import { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { FormLabel } from "@/components/ui/form";
+import { Input } from "@/components/ui/input";
+import { Switch } from "@/components/ui/switch";
+import { ConfirmDialog } from "@/components/ConfirmDialog";
+import type { ProviderTlsOptions } from "@/types";
+
+interface ProviderTlsFieldProps {
+  value: ProviderTlsOptions;
+  onChange: (value: ProviderTlsOptions) => void;
+}
+
+/**
+ * 供应商级 TLS 选项：自定义根证书路径 + 跳过证书校验。
+ *
+ * 跳过校验必须经确认对话框明确同意后才会开启；关闭无需确认。
+ * 后端在连通性检查与代理转发两条路径上共用同一份配置。
+ */
+export function ProviderTlsField({ value, onChange }: ProviderTlsFieldProps) {
+  const { t } = useTranslation();
+  const [confirmOpen, setConfirmOpen] = useState(false);
+
+  return (
+    <div className="space-y-2">
+      <FormLabel htmlFor="provider-tls-ca">
+        {t("providerForm.tlsCaCertPath", {
+          defaultValue: "自定义根证书（CA）路径",
+        })}
+      </FormLabel>
+      <Input
+        id="provider-tls-ca"
+        type="text"
+        value={value.caCertPath ?? ""}
+        onChange={(e) => onChange({ ...value, caCertPath: e.target.value })}
+        placeholder="/path/to/private-ca.pem"
+        autoComplete="off"
+      />
+      <p className="text-xs text-muted-foreground">
+        {t("providerForm.tlsCaCertPathHint", {
+          defaultValue:
+            "自建中转使用私有 CA 时填写（PEM 或 DER）。连通性检查与本地代理转发都会信任该证书。",
+        })}
+      </p>
+      <div className="flex items-center justify-between gap-4">
+        <div className="space-y-0.5">
+          <FormLabel htmlFor="provider-tls-insecure">
+            {t("providerForm.tlsInsecure", {
+              defaultValue: "跳过证书校验",
+            })}
+          </FormLabel>
+          {value.insecureSkipVerify && (
+            <p className="text-xs text-destructive">
+              {t("providerForm.tlsInsecureActive", {
+                defaultValue: "已关闭证书校验，连接可能被中间人劫持。",
+              })}
+            </p>
+          )}
+        </div>
+        <Switch
+          id="provider-tls-insecure"
+          checked={value.insecureSkipVerify ?? false}
+          onCheckedChange={(checked) => {
+            if (checked) {
+              setConfirmOpen(true);
+            } else {
+              onChange({ ...value, insecureSkipVerify: false });
+            }
+          }}
+        />
+      </div>
+      <ConfirmDialog
+        isOpen={confirmOpen}
+        title={t("providerForm.tlsInsecureConfirmTitle", {
+          defaultValue: "跳过 TLS 证书校验？",
+        })}
+        message={t("providerForm.tlsInsecureConfirmMessage", {
+          defaultValue:
+            "关闭校验后，任何人都可以冒充该供应商并截获 API Key 与对话内容。仅在排查证书问题时临时开启，优先使用自定义根证书。",
+        })}
+        confirmText={t("providerForm.tlsInsecureConfirm", {
+          defaultValue: "我了解风险，仍然开启",
+        })}
+        onConfirm={() => {
+          setConfirmOpen(false);
+          onChange({ ...value, insecureSkipVerify: true });
+        }}
+        onCancel={() => setConfirmOpen(false)}
+      />
+    </div>
+  );
+}
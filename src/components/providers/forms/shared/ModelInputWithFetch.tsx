@@ -11,6 +11,7 @@ import {
 } from "@/components/ui/dropdown-menu";
 import { ChevronDown, Download, Loader2 } from "lucide-react";
 import type { FetchedModel } from "@/lib/api/model-fetch";
+import type { ModelAlias } from "@/types";
 
 interface ModelInputWithFetchProps {
   id: string;
@@ -21,6 +22,19 @@ interface ModelInputWithFetchProps {
   isLoading: boolean;
   /** 传入时显示获取按钮；不传时只在有数据后显示下拉 */
   onFetch?: () => void;
+  /** 设置中的模型别名：作为输入候选，并提示别名解析后的模型 */
+  aliases?: ModelAlias[];
+}
+
+/** 与后端一致：不区分大小写，保留 `[1m]` 后缀 */
+function resolveAlias(aliases: ModelAlias[], value: string): string | null {
+  const name = value.trim();
+  const suffix = /\[1m\]$/i.test(name) ? name.slice(-4) : "";
+  const base = suffix ? name.slice(0, -4).trim() : name;
+  const entry = aliases.find(
+    (item) => item.alias.trim().toLowerCase() === base.toLowerCase(),
+  );
+  return entry ? `${entry.model.trim()}${suffix}` : null;
 }
 
 export function ModelInputWithFetch({
@@ -31,116 +45,144 @@ export function ModelInputWithFetch({
   fetchedModels,
   isLoading,
   onFetch,
+  aliases = [],
 }: ModelInputWithFetchProps) {
   const { t } = useTranslation();
+  const field = renderField();
+  if (aliases.length === 0) return field;
+
+  const resolved = resolveAlias(aliases, value);
+  return (
+    <div className="space-y-1">
+      {field}
+      <datalist id={`${id}-aliases`}>
+        {aliases.map((entry) => (
+          <option key={entry.alias} value={entry.alias}>
+            {entry.model}
+          </option>
+        ))}
+      </datalist>
+      {resolved && (
+        <p className="text-xs text-muted-foreground">
+          {t("providerForm.modelAliasResolved", { model: resolved })}
+        </p>
+      )}
+    </div>
+  );
+
+  function renderField() {
+    // 有模型数据: Input + DropdownMenu
+    if (fetchedModels.length > 0) {
+      const grouped: Record<string, FetchedModel[]> = {};
+      for (const model of fetchedModels) {
+        const vendor = model.ownedBy || "Other";
+        if (!grouped[vendor]) grouped[vendor] = [];
+        grouped[vendor].push(model);
+      }
+      const vendors = Object.keys(grouped).sort();
 
-  // 有模型数据: Input + DropdownMenu
-  if (fetchedModels.length > 0) {
-    const grouped: Record<string, FetchedModel[]> = {};
-    for (const model of fetchedModels) {
-      const vendor = model.ownedBy || "Other";
-      if (!grouped[vendor]) grouped[vendor] = [];
-      grouped[vendor].push(model);
+      return (
+        <div className="flex gap-1">
+          <Input
+            id={id}
+            type="text"
+            value={value}
+            onChange={(e) => onChange(e.target.value)}
+            placeholder={placeholder}
+            autoComplete="off"
+            list={aliases.length > 0 ? `${id}-aliases` : undefined}
+            className="flex-1"
+          />
+          <DropdownMenu>
+            <DropdownMenuTrigger asChild>
+              <Button variant="outline" size="icon" className="shrink-0">
+                <ChevronDown className="h-4 w-4" />
+              </Button>
+            </DropdownMenuTrigger>
+            <DropdownMenuContent
+              align="end"
+              className="max-h-64 overflow-y-auto z-[200]"
+            >
+              {vendors.map((vendor, vi) => (
+                <div key={vendor}>
+                  {vi > 0 && <DropdownMenuSeparator />}
+                  <DropdownMenuLabel>{vendor}</DropdownMenuLabel>
+                  {grouped[vendor].map((model) => (
+                    <DropdownMenuItem
+                      key={model.id}
+                      onSelect={() => onChange(model.id)}
+                    >
+                      {model.id}
+                    </DropdownMenuItem>
+                  ))}
+                </div>
+              ))}
+            </DropdownMenuContent>
+          </DropdownMenu>
+        </div>
+      );
     }
-    const vendors = Object.keys(grouped).sort();
 
-    return (
-      <div className="flex gap-1">
-        <Input
-          id={id}
-          type="text"
-          value={value}
-          onChange={(e) => onChange(e.target.value)}
-          placeholder={placeholder}
-          autoComplete="off"
-          className="flex-1"
-        />
-        <DropdownMenu>
-          <DropdownMenuTrigger asChild>
-            <Button variant="outline" size="icon" className="shrink-0">
-              <ChevronDown className="h-4 w-4" />
-            </Button>
-          </DropdownMenuTrigger>
-          <DropdownMenuContent
-            align="end"
-            className="max-h-64 overflow-y-auto z-[200]"
-          >
-            {vendors.map((vendor, vi) => (
-              <div key={vendor}>
-                {vi > 0 && <DropdownMenuSeparator />}
-                <DropdownMenuLabel>{vendor}</DropdownMenuLabel>
-                {grouped[vendor].map((model) => (
-                  <DropdownMenuItem
-                    key={model.id}
-                    onSelect={() => onChange(model.id)}
-                  >
-                    {model.id}
-                  </DropdownMenuItem>
-                ))}
-              </div>
-            ))}
-          </DropdownMenuContent>
-        </DropdownMenu>
-      </div>
-    );
-  }
+    // 加载中: Input + Spinner
+    if (isLoading) {
+      return (
+        <div className="flex gap-1">
+          <Input
+            id={id}
+            type="text"
+            value={value}
+            onChange={(e) => onChange(e.target.value)}
+            placeholder={placeholder}
+            autoComplete="off"
+            list={aliases.length > 0 ? `${id}-aliases` : undefined}
+            className="flex-1"
+          />
+          <Button variant="outline" size="icon" className="shrink-0" disabled>
+            <Loader2 className="h-4 w-4 animate-spin" />
+          </Button>
+        </div>
+      );
+    }
 
-  // 加载中: Input + Spinner
-  if (isLoading) {
-    return (
-      <div className="flex gap-1">
-        <Input
-          id={id}
-          type="text"
-          value={value}
-          onChange={(e) => onChange(e.target.value)}
-          placeholder={placeholder}
-          autoComplete="off"
-          className="flex-1"
-        />
-        <Button variant="outline" size="icon" className="shrink-0" disabled>
-          <Loader2 className="h-4 w-4 animate-spin" />
-        </Button>
-      </div>
-    );
-  }
+    // 有 onFetch: Input + 获取按钮
+    if (onFetch) {
+      return (
+        <div className="flex gap-1">
+          <Input
+            id={id}
+            type="text"
+            value={value}
+            onChange={(e) => onChange(e.target.value)}
+            placeholder={placeholder}
+            autoComplete="off"
+            list={aliases.length > 0 ? `${id}-aliases` : undefined}
+            className="flex-1"
+          />
+          <Button
+            variant="outline"
+            size="icon"
+            className="shrink-0"
+            type="button"
+            onClick={onFetch}
+            title={t("providerForm.fetchModels")}
+          >
+            <Download className="h-4 w-4" />
+          </Button>
+        </div>
+      );
+    }
 
-  // 有 onFetch: Input + 获取按钮
-  if (onFetch) {
+    // 无 onFetch: 纯 Input
     return (
-      <div className="flex gap-1">
-        <Input
-          id={id}
-          type="text"
-          value={value}
-          onChange={(e) => onChange(e.target.value)}
-          placeholder={placeholder}
-          autoComplete="off"
-          className="flex-1"
-        />
-        <Button
-          variant="outline"
-          size="icon"
-          className="shrink-0"
-          type="button"
-          onClick={onFetch}
-          title={t("providerForm.fetchModels")}
-        >
-          <Download className="h-4 w-4" />
-        </Button>
-      </div>
+      <Input
+        id={id}
+        type="text"
+        value={value}
+        onChange={(e) => onChange(e.target.value)}
+        placeholder={placeholder}
+        autoComplete="off"
+        list={aliases.length > 0 ? `${id}-aliases` : undefined}
+      />
     );
   }
-
-  // 无 onFetch: 纯 Input
-  return (
-    <Input
-      id={id}
-      type="text"
-      value={value}
-      onChange={(e) => onChange(e.target.value)}
-      placeholder={placeholder}
-      autoComplete="off"
-    />
-  );
 }
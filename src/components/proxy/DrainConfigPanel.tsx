@@ -0,0 +1,109 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { Save, Loader2 } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import { settingsApi, type DrainConfig } from "@/lib/api/settings";
+
+const TIMEOUT_RANGE = { min: 1, max: 600 };
+
+/**
+ * 优雅排空配置（全局）
+ * 停止代理时不再接受新请求，进行中的请求（含流式响应）在原供应商上完成，
+ * 超时后强制关闭；切换供应商本身不会中断进行中的请求
+ */
+export function DrainConfigPanel() {
+  const { t } = useTranslation();
+  const [config, setConfig] = useState<DrainConfig | null>(null);
+  // 使用字符串状态以支持完全清空数字输入框
+  const [timeout, setTimeoutValue] = useState("30");
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    settingsApi
+      .getDrainConfig()
+      .then((loaded) => {
+        setConfig(loaded);
+        setTimeoutValue(String(loaded.timeoutSecs));
+      })
+      .catch((e) => console.error("Failed to load drain config:", e));
+  }, []);
+
+  const save = async (next: DrainConfig) => {
+    setSaving(true);
+    try {
+      await settingsApi.setDrainConfig(next);
+      setConfig(next);
+      toast.success(t("proxy.drain.saved"), { closeButton: true });
+    } catch (e) {
+      toast.error(String(e));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleSave = () => {
+    if (!config) return;
+    const value = Number(timeout.trim());
+    const { min, max } = TIMEOUT_RANGE;
+    if (!Number.isInteger(value) || value < min || value > max) {
+      toast.error(
+        t("proxy.autoFailover.validationFailed", {
+          fields: `${t("proxy.drain.timeoutSecs")}: ${min}-${max}`,
+        }),
+      );
+      return;
+    }
+    void save({ ...config, timeoutSecs: value });
+  };
+
+  if (!config) return null;
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between">
+        <div className="space-y-0.5">
+          <Label>{t("proxy.drain.enabled")}</Label>
+          <p className="text-xs text-muted-foreground">
+            {t("proxy.drain.enabledDescription")}
+          </p>
+        </div>
+        <Switch
+          checked={config.enabled}
+          disabled={saving}
+          onCheckedChange={(checked) => save({ ...config, enabled: checked })}
+        />
+      </div>
+
+      <div className="flex items-end gap-3">
+        <div className="flex-1 space-y-1.5">
+          <Label htmlFor="drain-timeout">{t("proxy.drain.timeoutSecs")}</Label>
+          <Input
+            id="drain-timeout"
+            type="number"
+            min={TIMEOUT_RANGE.min}
+            max={TIMEOUT_RANGE.max}
+            value={timeout}
+            disabled={!config.enabled || saving}
+            onChange={(e) => setTimeoutValue(e.target.value)}
+          />
+        </div>
+        <Button
+          size="sm"
+          onClick={handleSave}
+          disabled={!config.enabled || saving}
+        >
+          {saving ? (
+            <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+          ) : (
+            <Save className="mr-2 h-4 w-4" />
+          )}
+          {t("common.save")}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,186 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { Save, Loader2 } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import { settingsApi, type FailbackWatchdogConfig } from "@/lib/api/settings";
+
+type NumericField =
+  | "windowSecs"
+  | "minFailures"
+  | "errorRatePercent"
+  | "cooloffSecs";
+
+const RANGES: Record<NumericField, { min: number; max: number }> = {
+  windowSecs: { min: 10, max: 3600 },
+  minFailures: { min: 1, max: 1000 },
+  errorRatePercent: { min: 1, max: 100 },
+  cooloffSecs: { min: 30, max: 86400 },
+};
+
+const FIELDS = Object.keys(RANGES) as NumericField[];
+
+/**
+ * 自动切走 / 切回看门狗（全局）
+ * 当前供应商在统计窗口内持续返回所选状态码时切到故障转移队列中的下一个供应商，
+ * 冷却期结束后切回原供应商
+ */
+export function FailbackWatchdogPanel() {
+  const { t } = useTranslation();
+  const [config, setConfig] = useState<FailbackWatchdogConfig | null>(null);
+  // 使用字符串状态以支持完全清空数字输入框
+  const [values, setValues] = useState<Record<NumericField, string>>({
+    windowSecs: "",
+    minFailures: "",
+    errorRatePercent: "",
+    cooloffSecs: "",
+  });
+  const [statusCodes, setStatusCodes] = useState("");
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    settingsApi
+      .getFailbackWatchdogConfig()
+      .then((loaded) => {
+        setConfig(loaded);
+        setValues({
+          windowSecs: String(loaded.windowSecs),
+          minFailures: String(loaded.minFailures),
+          errorRatePercent: String(loaded.errorRatePercent),
+          cooloffSecs: String(loaded.cooloffSecs),
+        });
+        setStatusCodes(loaded.statusCodes.join(", "));
+      })
+      .catch((e) => console.error("Failed to load watchdog config:", e));
+  }, []);
+
+  const save = async (next: FailbackWatchdogConfig) => {
+    setSaving(true);
+    try {
+      await settingsApi.setFailbackWatchdogConfig(next);
+      setConfig(next);
+      toast.success(t("proxy.watchdog.saved"), { closeButton: true });
+    } catch (e) {
+      toast.error(String(e));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleSave = () => {
+    if (!config) return;
+    const errors: string[] = [];
+    const parsed = { ...config };
+    for (const field of FIELDS) {
+      const value = Number(values[field].trim());
+      const { min, max } = RANGES[field];
+      if (!Number.isInteger(value) || value < min || value > max) {
+        errors.push(`${t(`proxy.watchdog.${field}`)}: ${min}-${max}`);
+      } else {
+        parsed[field] = value;
+      }
+    }
+    const codes = statusCodes.split(/[\s,]+/).filter(Boolean).map(Number);
+    if (
+      codes.length === 0 ||
+      codes.some((code) => !Number.isInteger(code) || code < 100 || code > 599)
+    ) {
+      errors.push(`${t("proxy.watchdog.statusCodes")}: 100-599`);
+    } else {
+      parsed.statusCodes = codes;
+    }
+    if (errors.length > 0) {
+      toast.error(
+        t("proxy.autoFailover.validationFailed", { fields: errors.join("; ") }),
+      );
+      return;
+    }
+    void save(parsed);
+  };
+
+  if (!config) return null;
+
+  const disabled = !config.enabled || saving;
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between">
+        <div className="space-y-0.5">
+          <Label>{t("proxy.watchdog.enabled")}</Label>
+          <p className="text-xs text-muted-foreground">
+            {t("proxy.watchdog.enabledDescription")}
+          </p>
+        </div>
+        <Switch
+          checked={config.enabled}
+          disabled={saving}
+          onCheckedChange={(checked) => save({ ...config, enabled: checked })}
+        />
+      </div>
+
+      <div className="grid grid-cols-2 gap-3">
+        {FIELDS.map((field) => (
+          <div key={field} className="space-y-1.5">
+            <Label htmlFor={`watchdog-${field}`}>
+              {t(`proxy.watchdog.${field}`)}
+            </Label>
+            <Input
+              id={`watchdog-${field}`}
+              type="number"
+              min={RANGES[field].min}
+              max={RANGES[field].max}
+              value={values[field]}
+              disabled={disabled}
+              onChange={(e) =>
+                setValues((prev) => ({ ...prev, [field]: e.target.value }))
+              }
+            />
+          </div>
+        ))}
+      </div>
+
+      <div className="space-y-1.5">
+        <Label htmlFor="watchdog-status-codes">
+          {t("proxy.watchdog.statusCodes")}
+        </Label>
+        <Input
+          id="watchdog-status-codes"
+          value={statusCodes}
+          disabled={disabled}
+          placeholder="401, 403, 429"
+          onChange={(e) => setStatusCodes(e.target.value)}
+        />
+      </div>
+
+      <div className="flex items-center justify-between">
+        <div className="space-y-0.5">
+          <Label>{t("proxy.watchdog.switchBack")}</Label>
+          <p className="text-xs text-muted-foreground">
+            {t("proxy.watchdog.switchBackDescription")}
+          </p>
+        </div>
+        <Switch
+          checked={config.switchBack}
+          disabled={disabled}
+          onCheckedChange={(checked) =>
+            setConfig({ ...config, switchBack: checked })
+          }
+        />
+      </div>
+
+      <div className="flex justify-end">
+        <Button size="sm" onClick={handleSave} disabled={disabled}>
+          {saving ? (
+            <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+          ) : (
+            <Save className="mr-2 h-4 w-4" />
+          )}
+          {t("common.save")}
+        </Button>
+      </div>
+    </div>
+  );
+}
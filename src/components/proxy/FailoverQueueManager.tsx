@@ -3,13 +3,21 @@
  *
  * 允许用户管理代理模式下的故障转移队列，支持：
  * - 添加/移除供应商
- * - 队列顺序基于首页供应商列表的 sort_index
+ * - 调整顺序（队列顺序即首页供应商列表的 sort_index，队列外的供应商位置不变）
  */
 
 import { useState } from "react";
 import { useTranslation } from "react-i18next";
 import { toast } from "sonner";
-import { Plus, Trash2, Loader2, Info, AlertTriangle } from "lucide-react";
+import {
+  Plus,
+  Trash2,
+  Loader2,
+  Info,
+  AlertTriangle,
+  ArrowUp,
+  ArrowDown,
+} from "lucide-react";
 import { Button } from "@/components/ui/button";
 import { Switch } from "@/components/ui/switch";
 import { Alert, AlertDescription } from "@/components/ui/alert";
@@ -28,6 +36,7 @@ import {
   useAvailableProvidersForFailover,
   useAddToFailoverQueue,
   useRemoveFromFailoverQueue,
+  useReorderFailoverQueue,
   useAutoFailoverEnabled,
   useSetAutoFailoverEnabled,
 } from "@/lib/query/failover";
@@ -60,6 +69,7 @@ export function FailoverQueueManager({
   // Mutations
   const addToQueue = useAddToFailoverQueue();
   const removeFromQueue = useRemoveFromFailoverQueue();
+  const reorderQueue = useReorderFailoverQueue();
 
   // 切换故障转移开关
   const handleToggleFailover = (enabled: boolean) => {
@@ -104,6 +114,28 @@ export function FailoverQueueManager({
     }
   };
 
+  // 与相邻项交换位置
+  const handleMove = async (index: number, offset: -1 | 1) => {
+    if (!queue) return;
+    const target = index + offset;
+    if (target < 0 || target >= queue.length) return;
+
+    const providerIds = queue.map((item) => item.providerId);
+    [providerIds[index], providerIds[target]] = [
+      providerIds[target],
+      providerIds[index],
+    ];
+    try {
+      await reorderQueue.mutateAsync({ appType, providerIds });
+    } catch (error) {
+      toast.error(
+        t("proxy.failoverQueue.reorderFailed", "调整顺序失败") +
+          ": " +
+          String(error),
+      );
+    }
+  };
+
   if (isQueueLoading) {
     return (
       <div className="flex items-center justify-center p-8">
@@ -230,9 +262,12 @@ export function FailoverQueueManager({
               key={item.providerId}
               item={item}
               index={index}
+              total={queue.length}
               disabled={disabled}
               onRemove={handleRemoveProvider}
+              onMove={handleMove}
               isRemoving={removeFromQueue.isPending}
+              isReordering={reorderQueue.isPending}
             />
           ))}
         </div>
@@ -243,7 +278,7 @@ export function FailoverQueueManager({
         <p className="text-xs text-muted-foreground">
           {t(
             "proxy.failoverQueue.orderHint",
-            "队列顺序与首页供应商列表顺序一致，可在首页拖拽调整顺序。",
+            "使用箭头调整顺序。队列顺序与首页供应商列表顺序一致，队列外的供应商位置不变。",
           )}
         </p>
       )}
@@ -254,17 +289,23 @@ export function FailoverQueueManager({
 interface QueueItemProps {
   item: FailoverQueueItem;
   index: number;
+  total: number;
   disabled: boolean;
   onRemove: (providerId: string) => void;
+  onMove: (index: number, offset: -1 | 1) => void;
   isRemoving: boolean;
+  isReordering: boolean;
 }
 
 function QueueItem({
   item,
   index,
+  total,
   disabled,
   onRemove,
+  onMove,
   isRemoving,
+  isReordering,
 }: QueueItemProps) {
   const { t } = useTranslation();
 
@@ -288,9 +329,36 @@ function QueueItem({
               ({item.providerNotes})
             </span>
           )}
+          {!item.healthy && (
+            <span className="ml-2 px-1.5 py-0.5 text-xs rounded bg-destructive/10 text-destructive">
+              {t("proxy.failoverQueue.unhealthy", "不健康")}
+            </span>
+          )}
         </span>
       </div>
 
+      {/* 排序按钮 */}
+      <Button
+        variant="ghost"
+        size="icon"
+        className="h-8 w-8 text-muted-foreground"
+        onClick={() => onMove(index, -1)}
+        disabled={disabled || isReordering || index === 0}
+        aria-label={t("proxy.failoverQueue.moveUp", "上移")}
+      >
+        <ArrowUp className="h-4 w-4" />
+      </Button>
+      <Button
+        variant="ghost"
+        size="icon"
+        className="h-8 w-8 text-muted-foreground"
+        onClick={() => onMove(index, 1)}
+        disabled={disabled || isReordering || index === total - 1}
+        aria-label={t("proxy.failoverQueue.moveDown", "下移")}
+      >
+        <ArrowDown className="h-4 w-4" />
+      </Button>
+
       {/* 删除按钮 */}
       <Button
         variant="ghost"
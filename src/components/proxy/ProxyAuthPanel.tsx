@@ -0,0 +1,228 @@
+import { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { toast } from "sonner";
+import { Copy, KeyRound, Plus, RefreshCw, Trash2 } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import { ConfirmDialog } from "@/components/ConfirmDialog";
+import { proxyApi } from "@/lib/api/proxy";
+import type { ProxyAuthToken } from "@/types/proxy";
+import { extractErrorMessage } from "@/utils/errorUtils";
+
+/** 用量统计窗口：最近 30 天 */
+const USAGE_WINDOW_SECS = 30 * 24 * 60 * 60;
+
+function maskToken(token: string): string {
+  return token.length > 24
+    ? `${token.slice(0, 14)}…${token.slice(-4)}`
+    : token;
+}
+
+/**
+ * 本地代理鉴权：开关 + 访问令牌列表（含按令牌的用量）。
+ * 第一个令牌会写入被接管应用的 Live 配置。
+ */
+export function ProxyAuthPanel() {
+  const { t } = useTranslation();
+  const queryClient = useQueryClient();
+  const [newName, setNewName] = useState("");
+  const [pendingRevoke, setPendingRevoke] = useState<ProxyAuthToken | null>(
+    null,
+  );
+
+  const { data: config } = useQuery({
+    queryKey: ["proxyAuthConfig"],
+    queryFn: () => proxyApi.getAuthConfig(),
+  });
+  const { data: tokens = [] } = useQuery({
+    queryKey: ["proxyAuthTokens"],
+    queryFn: () => proxyApi.listAuthTokens(),
+  });
+  const { data: usage = [] } = useQuery({
+    queryKey: ["proxyAuthTokenUsage"],
+    queryFn: () =>
+      proxyApi.getAuthTokenUsage(
+        Math.floor(Date.now() / 1000) - USAGE_WINDOW_SECS,
+      ),
+    enabled: config?.enabled ?? false,
+  });
+
+  const invalidate = () => {
+    void queryClient.invalidateQueries({ queryKey: ["proxyAuthConfig"] });
+    void queryClient.invalidateQueries({ queryKey: ["proxyAuthTokens"] });
+    void queryClient.invalidateQueries({ queryKey: ["proxyAuthTokenUsage"] });
+  };
+  const onError = (error: unknown) =>
+    toast.error(t("proxy.auth.failed"), {
+      description: extractErrorMessage(error),
+    });
+
+  const toggleMutation = useMutation({
+    mutationFn: (enabled: boolean) => proxyApi.setAuthEnabled(enabled),
+    onSuccess: (_, enabled) => {
+      invalidate();
+      toast.success(
+        enabled ? t("proxy.auth.enabled") : t("proxy.auth.disabled"),
+        { closeButton: true },
+      );
+    },
+    onError,
+  });
+  const createMutation = useMutation({
+    mutationFn: (name: string) => proxyApi.createAuthToken(name),
+    onSuccess: () => {
+      setNewName("");
+      invalidate();
+    },
+    onError,
+  });
+  const rotateMutation = useMutation({
+    mutationFn: (id: string) => proxyApi.rotateAuthToken(id),
+    onSuccess: () => {
+      invalidate();
+      toast.success(t("proxy.auth.rotated"), { closeButton: true });
+    },
+    onError,
+  });
+  const revokeMutation = useMutation({
+    mutationFn: (id: string) => proxyApi.revokeAuthToken(id),
+    onSuccess: invalidate,
+    onError,
+  });
+
+  const handleCopy = async (token: string) => {
+    try {
+      await navigator.clipboard.writeText(token);
+      toast.success(t("proxy.auth.copied"));
+    } catch (error) {
+      onError(error);
+    }
+  };
+
+  const enabled = config?.enabled ?? false;
+
+  return (
+    <div className="space-y-3 rounded-md border border-border bg-background/60 px-3 py-3">
+      <div className="flex items-center justify-between">
+        <div className="space-y-0.5">
+          <Label className="flex items-center gap-1.5 text-sm font-medium">
+            <KeyRound className="h-4 w-4" />
+            {t("proxy.auth.title")}
+          </Label>
+          <p className="text-xs text-muted-foreground">
+            {t("proxy.auth.description")}
+          </p>
+        </div>
+        <Switch
+          checked={enabled}
+          onCheckedChange={(checked) => toggleMutation.mutate(checked)}
+          disabled={toggleMutation.isPending}
+        />
+      </div>
+
+      {enabled && (
+        <div className="space-y-2 border-t border-border pt-3">
+          {tokens.map((token, index) => {
+            const stats = usage.find((item) => item.tokenId === token.id);
+            return (
+              <div
+                key={token.id}
+                className="flex items-center justify-between gap-3 rounded-md border border-border px-3 py-2"
+              >
+                <div className="min-w-0 space-y-0.5">
+                  <div className="flex items-center gap-2 text-sm font-medium">
+                    {token.name}
+                    {index === 0 && (
+                      <span className="text-xs font-normal text-muted-foreground">
+                        {t("proxy.auth.clientToken")}
+                      </span>
+                    )}
+                  </div>
+                  <p className="truncate font-mono text-xs text-muted-foreground">
+                    {maskToken(token.token)}
+                  </p>
+                  <p className="text-xs text-muted-foreground">
+                    {t("proxy.auth.usage", {
+                      requests: stats?.requestCount ?? 0,
+                      tokens: (stats?.totalTokens ?? 0).toLocaleString(),
+                      cost: Number(stats?.totalCost ?? 0).toFixed(4),
+                    })}
+                    {token.lastUsedAt &&
+                      ` · ${t("proxy.auth.lastUsed", {
+                        time: new Date(
+                          token.lastUsedAt * 1000,
+                        ).toLocaleString(),
+                      })}`}
+                  </p>
+                </div>
+                <div className="flex flex-shrink-0 items-center gap-1">
+                  <Button
+                    size="icon"
+                    variant="ghost"
+                    title={t("proxy.auth.copy")}
+                    onClick={() => void handleCopy(token.token)}
+                  >
+                    <Copy className="h-4 w-4" />
+                  </Button>
+                  <Button
+                    size="icon"
+                    variant="ghost"
+                    title={t("proxy.auth.rotate")}
+                    disabled={rotateMutation.isPending}
+                    onClick={() => rotateMutation.mutate(token.id)}
+                  >
+                    <RefreshCw className="h-4 w-4" />
+                  </Button>
+                  <Button
+                    size="icon"
+                    variant="ghost"
+                    title={t("proxy.auth.revoke")}
+                    onClick={() => setPendingRevoke(token)}
+                  >
+                    <Trash2 className="h-4 w-4" />
+                  </Button>
+                </div>
+              </div>
+            );
+          })}
+
+          <div className="flex items-center gap-2">
+            <Input
+              value={newName}
+              onChange={(event) => setNewName(event.target.value)}
+              placeholder={t("proxy.auth.namePlaceholder")}
+              className="h-8"
+            />
+            <Button
+              size="sm"
+              variant="outline"
+              disabled={!newName.trim() || createMutation.isPending}
+              onClick={() => createMutation.mutate(newName.trim())}
+            >
+              <Plus className="mr-1 h-4 w-4" />
+              {t("proxy.auth.create")}
+            </Button>
+          </div>
+        </div>
+      )}
+
+      <ConfirmDialog
+        isOpen={pendingRevoke !== null}
+        title={t("proxy.auth.revokeTitle")}
+        message={t("proxy.auth.revokeMessage", {
+          name: pendingRevoke?.name ?? "",
+        })}
+        confirmText={t("proxy.auth.revoke")}
+        variant="destructive"
+        onConfirm={() => {
+          if (pendingRevoke) revokeMutation.mutate(pendingRevoke.id);
+          setPendingRevoke(null);
+        }}
+        onCancel={() => setPendingRevoke(null)}
+      />
+    </div>
+  );
+}
@@ -30,6 +30,7 @@ import type { ProxyStatus } from "@/types/proxy";
 import { useTranslation } from "react-i18next";
 import { AnimatePresence, motion } from "framer-motion";
 import { extractErrorMessage } from "@/utils/errorUtils";
+import { ProxyAuthPanel } from "./ProxyAuthPanel";
 
 interface ProxyPanelProps {
   enableLocalProxy: boolean;
@@ -390,6 +391,21 @@ export function ProxyPanel({
                     })}
                   </p>
                 )}
+                {status.in_flight
+                  ?.filter((group) => group.draining)
+                  .map((group) => (
+                    <p
+                      key={`${group.app_type}-${group.provider_id}`}
+                      className="text-xs text-amber-600 dark:text-amber-400"
+                    >
+                      {t("proxy.drain.inFlight", {
+                        app: group.app_type,
+                        provider: group.provider_name,
+                        count: group.count,
+                        seconds: group.oldest_secs,
+                      })}
+                    </p>
+                  ))}
               </div>
 
               {/* [5] Logging toggle */}
@@ -620,6 +636,9 @@ export function ProxyPanel({
             </div>
           </div>
         )}
+
+        {/* [9] Proxy auth — always visible */}
+        <ProxyAuthPanel />
       </section>
     </>
   );
@@ -0,0 +1,159 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { Save, Loader2 } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import { settingsApi, type RetryConfig } from "@/lib/api/settings";
+
+const RANGES = {
+  sameProviderRetries: { min: 0, max: 5 },
+  backoffBaseMs: { min: 50, max: 5000 },
+  budgetMs: { min: 1000, max: 120000 },
+};
+
+type NumericField = keyof typeof RANGES;
+
+/**
+ * 请求内重试配置（全局）
+ * 连接错误、502/503/504、流式首包前失败时先按退避重试同一供应商，再走故障转移；
+ * 默认只重放 GET/HEAD，POST 补全请求需显式开启（可能重复计费）
+ */
+export function RetryConfigPanel() {
+  const { t } = useTranslation();
+  const [config, setConfig] = useState<RetryConfig | null>(null);
+  // 使用字符串状态以支持完全清空数字输入框
+  const [form, setForm] = useState<Record<NumericField, string>>({
+    sameProviderRetries: "1",
+    backoffBaseMs: "250",
+    budgetMs: "10000",
+  });
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    settingsApi
+      .getRetryConfig()
+      .then((loaded) => {
+        setConfig(loaded);
+        setForm({
+          sameProviderRetries: String(loaded.sameProviderRetries),
+          backoffBaseMs: String(loaded.backoffBaseMs),
+          budgetMs: String(loaded.budgetMs),
+        });
+      })
+      .catch((e) => console.error("Failed to load retry config:", e));
+  }, []);
+
+  const save = async (next: RetryConfig) => {
+    setSaving(true);
+    try {
+      await settingsApi.setRetryConfig(next);
+      setConfig(next);
+      toast.success(t("proxy.retry.saved"), { closeButton: true });
+    } catch (e) {
+      toast.error(String(e));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleSave = () => {
+    if (!config) return;
+    const parsed = {} as Record<NumericField, number>;
+    const invalid: string[] = [];
+    for (const field of Object.keys(RANGES) as NumericField[]) {
+      const value = Number(form[field].trim());
+      const { min, max } = RANGES[field];
+      if (!Number.isInteger(value) || value < min || value > max) {
+        invalid.push(`${t(`proxy.retry.${field}`)}: ${min}-${max}`);
+      }
+      parsed[field] = value;
+    }
+    if (invalid.length > 0) {
+      toast.error(
+        t("proxy.autoFailover.validationFailed", {
+          fields: invalid.join("; "),
+        }),
+      );
+      return;
+    }
+    void save({ ...config, ...parsed });
+  };
+
+  if (!config) return null;
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between">
+        <div className="space-y-0.5">
+          <Label>{t("proxy.retry.enabled")}</Label>
+          <p className="text-xs text-muted-foreground">
+            {t("proxy.retry.enabledDescription")}
+          </p>
+        </div>
+        <Switch
+          checked={config.enabled}
+          disabled={saving}
+          onCheckedChange={(checked) => save({ ...config, enabled: checked })}
+        />
+      </div>
+
+      <div className="flex items-center justify-between">
+        <div className="space-y-0.5">
+          <Label>{t("proxy.retry.retryNonIdempotent")}</Label>
+          <p className="text-xs text-muted-foreground">
+            {t("proxy.retry.retryNonIdempotentDescription")}
+          </p>
+        </div>
+        <Switch
+          checked={config.retryNonIdempotent}
+          disabled={!config.enabled || saving}
+          onCheckedChange={(checked) =>
+            save({ ...config, retryNonIdempotent: checked })
+          }
+        />
+      </div>
+
+      <div className="grid grid-cols-1 gap-4 md:grid-cols-3">
+        {(Object.keys(RANGES) as NumericField[]).map((field) => (
+          <div key={field} className="space-y-1.5">
+            <Label htmlFor={`retry-${field}`}>
+              {t(`proxy.retry.${field}`)}
+            </Label>
+            <Input
+              id={`retry-${field}`}
+              type="number"
+              min={RANGES[field].min}
+              max={RANGES[field].max}
+              value={form[field]}
+              disabled={!config.enabled || saving}
+              onChange={(e) =>
+                setForm((prev) => ({ ...prev, [field]: e.target.value }))
+              }
+            />
+          </div>
+        ))}
+      </div>
+      <p className="text-xs text-muted-foreground">
+        {t("proxy.retry.hint")}
+      </p>
+
+      <div className="flex justify-end">
+        <Button
+          size="sm"
+          onClick={handleSave}
+          disabled={!config.enabled || saving}
+        >
+          {saving ? (
+            <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+          ) : (
+            <Save className="mr-2 h-4 w-4" />
+          )}
+          {t("common.save")}
+        </Button>
+      </div>
+    </div>
+  );
+}
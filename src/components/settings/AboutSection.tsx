@@ -57,6 +57,10 @@ interface ToolVersion {
   installed_but_broken: boolean;
   env_type: "windows" | "wsl" | "macos" | "linux" | "unknown";
   wsl_distro: string | null;
+  path: string | null;
+  install_method: string | null;
+  min_supported_version: string | null;
+  below_min_supported: boolean;
 }
 
 const TOOL_NAMES = [
@@ -1025,13 +1029,14 @@ export function AboutSection({ isPortable }: AboutSectionProps) {
             // 已安装却跑不起来（如 Node 版本不达标）：用它区分卡片文案与按钮，避免把
             // "装了跑不起来"误判成"未安装"而给出无用的安装按钮（重装同一版本解决不了）。
             const installedButBroken = Boolean(tool?.installed_but_broken);
+            const belowMinSupported = Boolean(tool?.below_min_supported);
             // loading 和 broken 都没有可执行动作；其余按是否已装/是否过期选择。
             const action: ToolLifecycleAction | null =
               isToolVersionLoading || installedButBroken
                 ? null
                 : !tool?.version
                   ? "install"
-                  : isOutdated
+                  : isOutdated || belowMinSupported
                     ? "update"
                     : null;
             const runningAction = toolActions[toolName];
@@ -1068,7 +1073,11 @@ export function AboutSection({ isPortable }: AboutSectionProps) {
                   {isToolVersionLoading ? (
                     <Loader2 className="mt-1 h-4 w-4 animate-spin text-muted-foreground" />
                   ) : tool?.version ? (
-                    isOutdated ? (
+                    belowMinSupported ? (
+                      <span className="mt-1 shrink-0 rounded-full border border-red-500/20 bg-red-500/10 px-1.5 py-0.5 text-[10px] text-red-600 dark:text-red-400">
+                        {t("settings.unsupportedVersionShort")}
+                      </span>
+                    ) : isOutdated ? (
                       <span className="mt-1 shrink-0 rounded-full border border-yellow-500/20 bg-yellow-500/10 px-1.5 py-0.5 text-[10px] text-yellow-600 dark:text-yellow-400">
                         {t("settings.updateAvailableShort")}
                       </span>
@@ -1108,6 +1117,26 @@ export function AboutSection({ isPortable }: AboutSectionProps) {
                         : tool?.latest_version || t("common.unknown")}
                     </span>
                   </div>
+                  {!isToolVersionLoading && tool?.install_method && (
+                    <div className="flex items-center justify-between gap-3">
+                      <span className="text-muted-foreground">
+                        {t("settings.installMethod")}
+                      </span>
+                      <span
+                        className="min-w-0 truncate font-mono text-foreground"
+                        title={tool.path ?? undefined}
+                      >
+                        {tool.install_method}
+                      </span>
+                    </div>
+                  )}
+                  {!isToolVersionLoading && belowMinSupported && (
+                    <div className="text-[11px] text-red-600 dark:text-red-400">
+                      {t("settings.belowMinSupported", {
+                        version: tool?.min_supported_version,
+                      })}
+                    </div>
+                  )}
                   {!isToolVersionLoading && !tool?.version && tool?.error && (
                     <div className="truncate text-[11px] text-muted-foreground">
                       {tool.error}
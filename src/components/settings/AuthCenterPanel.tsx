@@ -6,6 +6,7 @@ import { CopilotAuthSection } from "@/components/providers/forms/CopilotAuthSect
 import { CodexOAuthSection } from "@/components/providers/forms/CodexOAuthSection";
 import { XaiOAuthSection } from "@/components/providers/forms/XaiOAuthSection";
 import { ProviderIcon } from "@/components/ProviderIcon";
+import { CopilotProvisionPanel } from "./CopilotProvisionPanel";
 
 export function AuthCenterPanel() {
   const { t } = useTranslation();
@@ -52,6 +53,7 @@ export function AuthCenterPanel() {
         </div>
 
         <CopilotAuthSection />
+        <CopilotProvisionPanel />
       </section>
 
       <section className="rounded-xl border border-border/60 bg-card/60 p-6">
@@ -0,0 +1,181 @@
+import { useCallback, useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { Loader2, Undo2, Wand2 } from "lucide-react";
+import { toast } from "sonner";
+import { Button } from "@/components/ui/button";
+import { configApi } from "@/lib/api";
+import type {
+  CommonConfigMigrationApp,
+  CommonConfigMigrationReport,
+} from "@/lib/api/config";
+
+const APPS: CommonConfigMigrationApp[] = ["claude", "codex", "gemini"];
+
+/**
+ * 通用配置迁移助手：找出被误放进通用配置的供应商专属键，
+ * 迁入各供应商自身配置；迁移可撤销。
+ */
+export function CommonConfigMigrationSection() {
+  const { t } = useTranslation();
+  const [appType, setAppType] = useState<CommonConfigMigrationApp>("claude");
+  const [report, setReport] = useState<CommonConfigMigrationReport | null>(
+    null,
+  );
+  const [busy, setBusy] = useState<"analyze" | "apply" | "undo" | null>(null);
+
+  const analyze = useCallback(
+    async (app: CommonConfigMigrationApp) => {
+      setBusy("analyze");
+      try {
+        setReport(await configApi.analyzeCommonConfigMigration(app));
+      } catch (error) {
+        console.error("Failed to analyze common config:", error);
+        setReport(null);
+        toast.error(
+          t("settings.commonConfigMigration.analyzeFailed", {
+            error: String(error),
+          }),
+        );
+      } finally {
+        setBusy(null);
+      }
+    },
+    [t],
+  );
+
+  useEffect(() => {
+    void analyze(appType);
+  }, [analyze, appType]);
+
+  const handleApply = async () => {
+    setBusy("apply");
+    try {
+      const result = await configApi.applyCommonConfigMigration(appType);
+      setReport(result);
+      toast.success(
+        t("settings.commonConfigMigration.applied", {
+          keys: result.flaggedKeys.length,
+          providers: result.providers.length,
+        }),
+      );
+    } catch (error) {
+      console.error("Failed to migrate common config:", error);
+      toast.error(
+        t("settings.commonConfigMigration.applyFailed", {
+          error: String(error),
+        }),
+      );
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleUndo = async () => {
+    setBusy("undo");
+    try {
+      setReport(await configApi.undoCommonConfigMigration(appType));
+      toast.success(t("settings.commonConfigMigration.undone"));
+    } catch (error) {
+      console.error("Failed to undo common config migration:", error);
+      toast.error(
+        t("settings.commonConfigMigration.undoFailed", {
+          error: String(error),
+        }),
+      );
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const flagged = report?.flaggedKeys ?? [];
+  const canApply = !report?.applied && flagged.length > 0;
+
+  return (
+    <section className="space-y-4">
+      <p className="text-sm text-muted-foreground">
+        {t("settings.commonConfigMigration.description")}
+      </p>
+
+      <div className="flex gap-2">
+        {APPS.map((app) => (
+          <Button
+            key={app}
+            size="sm"
+            variant={app === appType ? "default" : "outline"}
+            disabled={busy !== null}
+            onClick={() => setAppType(app)}
+          >
+            {t(`apps.${app}`)}
+          </Button>
+        ))}
+      </div>
+
+      {busy === "analyze" ? (
+        <div className="flex items-center gap-2 text-sm text-muted-foreground">
+          <Loader2 className="h-4 w-4 animate-spin" />
+          {t("settings.commonConfigMigration.analyzing")}
+        </div>
+      ) : report && flagged.length === 0 ? (
+        <p className="text-sm text-muted-foreground">
+          {t("settings.commonConfigMigration.clean")}
+        </p>
+      ) : (
+        report && (
+          <div className="space-y-3 rounded-lg border border-border/40 p-3">
+            <div className="space-y-1">
+              <p className="text-sm font-medium">
+                {report.applied
+                  ? t("settings.commonConfigMigration.movedKeys")
+                  : t("settings.commonConfigMigration.flaggedKeys")}
+              </p>
+              <ul className="space-y-0.5 text-xs font-mono">
+                {flagged.map((key) => (
+                  <li key={key}>{key}</li>
+                ))}
+              </ul>
+            </div>
+            <p className="text-xs text-muted-foreground">
+              {report.providers.length > 0
+                ? t("settings.commonConfigMigration.providers", {
+                    names: report.providers.map((p) => p.name).join(", "),
+                  })
+                : t("settings.commonConfigMigration.noProviders")}
+            </p>
+          </div>
+        )
+      )}
+
+      <div className="flex items-center gap-2">
+        <Button
+          size="sm"
+          disabled={!canApply || busy !== null}
+          onClick={() => void handleApply()}
+        >
+          {busy === "apply" ? (
+            <Loader2 className="h-3.5 w-3.5 mr-1.5 animate-spin" />
+          ) : (
+            <Wand2 className="h-3.5 w-3.5 mr-1.5" />
+          )}
+          {t("settings.commonConfigMigration.apply")}
+        </Button>
+        {report?.undoAvailableSince != null && (
+          <Button
+            size="sm"
+            variant="outline"
+            disabled={busy !== null}
+            onClick={() => void handleUndo()}
+          >
+            {busy === "undo" ? (
+              <Loader2 className="h-3.5 w-3.5 mr-1.5 animate-spin" />
+            ) : (
+              <Undo2 className="h-3.5 w-3.5 mr-1.5" />
+            )}
+            {t("settings.commonConfigMigration.undo", {
+              time: new Date(report.undoAvailableSince).toLocaleString(),
+            })}
+          </Button>
+        )}
+      </div>
+    </section>
+  );
+}
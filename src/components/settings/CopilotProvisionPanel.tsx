@@ -0,0 +1,188 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useQueryClient } from "@tanstack/react-query";
+import { toast } from "sonner";
+import { Loader2, Wand2 } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Checkbox } from "@/components/ui/checkbox";
+import { Label } from "@/components/ui/label";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { copilotApi, type CopilotModel } from "@/lib/api";
+import { useCopilotAuth } from "@/components/providers/forms/hooks/useCopilotAuth";
+
+const TARGET_APPS = ["claude", "codex"] as const;
+
+/**
+ * 从已认证的 Copilot 账号一键生成 Claude / Codex 供应商。
+ * 生成的供应商会随账号的模型列表自动更新模型配置。
+ */
+export function CopilotProvisionPanel() {
+  const { t } = useTranslation();
+  const queryClient = useQueryClient();
+  const { accounts, defaultAccountId } = useCopilotAuth();
+  const [accountId, setAccountId] = useState<string | null>(null);
+  const [models, setModels] = useState<CopilotModel[]>([]);
+  const [model, setModel] = useState("");
+  const [apps, setApps] = useState<Set<string>>(new Set(["claude"]));
+  const [loadingModels, setLoadingModels] = useState(false);
+  const [provisioning, setProvisioning] = useState(false);
+
+  const activeAccountId =
+    accountId ?? defaultAccountId ?? accounts[0]?.id ?? null;
+
+  useEffect(() => {
+    if (!activeAccountId) return;
+    setLoadingModels(true);
+    copilotApi
+      .copilotGetModelsForAccount(activeAccountId)
+      .then((list) => {
+        const pickable = list.filter((item) => item.model_picker_enabled);
+        const next = pickable.length > 0 ? pickable : list;
+        setModels(next);
+        setModel((current) =>
+          next.some((item) => item.id === current) ? current : "",
+        );
+      })
+      .catch((error) => {
+        console.error("Failed to load Copilot models:", error);
+        setModels([]);
+      })
+      .finally(() => setLoadingModels(false));
+  }, [activeAccountId]);
+
+  if (accounts.length === 0) return null;
+
+  const toggleApp = (app: string, checked: boolean) => {
+    const next = new Set(apps);
+    if (checked) {
+      next.add(app);
+    } else {
+      next.delete(app);
+    }
+    setApps(next);
+  };
+
+  const handleProvision = async () => {
+    if (!activeAccountId || !model) return;
+    setProvisioning(true);
+    try {
+      const result = await copilotApi.copilotProvisionProviders(
+        activeAccountId,
+        model,
+        [...apps],
+      );
+      for (const app of TARGET_APPS) {
+        await queryClient.invalidateQueries({ queryKey: ["providers", app] });
+      }
+      toast.success(
+        t("copilotProvision.success", {
+          names: result.map((item) => item.name).join(", "),
+          count: result.length,
+        }),
+      );
+    } catch (error) {
+      toast.error(
+        t("copilotProvision.failed", {
+          error: (error as Error)?.message ?? String(error),
+        }),
+      );
+    } finally {
+      setProvisioning(false);
+    }
+  };
+
+  return (
+    <div className="mt-6 space-y-4 border-t border-border/60 pt-4">
+      <div className="space-y-1">
+        <h5 className="text-sm font-medium">{t("copilotProvision.title")}</h5>
+        <p className="text-xs text-muted-foreground">
+          {t("copilotProvision.description")}
+        </p>
+      </div>
+
+      <div className="grid grid-cols-2 gap-4">
+        <div className="space-y-1.5">
+          <Label>{t("copilotProvision.account")}</Label>
+          <Select
+            value={activeAccountId ?? undefined}
+            onValueChange={setAccountId}
+          >
+            <SelectTrigger>
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {accounts.map((account) => (
+                <SelectItem key={account.id} value={account.id}>
+                  {account.login}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+        <div className="space-y-1.5">
+          <Label>{t("copilotProvision.model")}</Label>
+          <Select
+            value={model || undefined}
+            onValueChange={setModel}
+            disabled={loadingModels || models.length === 0}
+          >
+            <SelectTrigger>
+              <SelectValue
+                placeholder={
+                  loadingModels
+                    ? t("copilotProvision.loadingModels")
+                    : t("copilotProvision.selectModel")
+                }
+              />
+            </SelectTrigger>
+            <SelectContent>
+              {models.map((item) => (
+                <SelectItem key={item.id} value={item.id}>
+                  {item.name} ({item.vendor})
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+      </div>
+
+      <div className="flex items-center justify-between gap-4">
+        <div className="flex items-center gap-4">
+          {TARGET_APPS.map((app) => (
+            <div key={app} className="flex items-center gap-2">
+              <Checkbox
+                id={`copilot-provision-${app}`}
+                checked={apps.has(app)}
+                onCheckedChange={(checked) => toggleApp(app, checked === true)}
+              />
+              <Label
+                htmlFor={`copilot-provision-${app}`}
+                className="font-normal"
+              >
+                {t(`apps.${app}`)}
+              </Label>
+            </div>
+          ))}
+        </div>
+        <Button
+          size="sm"
+          onClick={() => void handleProvision()}
+          disabled={!model || apps.size === 0 || provisioning}
+        >
+          {provisioning ? (
+            <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+          ) : (
+            <Wand2 className="mr-2 h-4 w-4" />
+          )}
+          {t("copilotProvision.generate")}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,43 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { Lock } from "lucide-react";
+import { Alert, AlertDescription } from "@/components/ui/alert";
+import { settingsApi } from "@/lib/api";
+import type { ManagedSetting } from "@/types";
+
+/**
+ * 提示哪些设置由 ~/.cc-switch/settings.toml 或 CC_SWITCH_* 环境变量管理，
+ * 这些项在界面中的修改不会生效。
+ */
+export function ManagedSettingsNotice() {
+  const { t } = useTranslation();
+  const [managed, setManaged] = useState<ManagedSetting[]>([]);
+
+  useEffect(() => {
+    settingsApi
+      .getManagedSettings()
+      .then(setManaged)
+      .catch((error) => {
+        console.warn("[ManagedSettingsNotice] load failed", error);
+      });
+  }, []);
+
+  if (managed.length === 0) return null;
+
+  const keys = managed
+    .map((item) => (item.envVar ? `${item.key} (${item.envVar})` : item.key))
+    .join(", ");
+
+  return (
+    <Alert>
+      <Lock className="h-4 w-4" />
+      <AlertDescription>
+        {t("settings.managedSettings", {
+          keys,
+          defaultValue:
+            "以下设置由 settings.toml 或环境变量管理，界面中的修改不会生效：{{keys}}",
+        })}
+      </AlertDescription>
+    </Alert>
+  );
+}
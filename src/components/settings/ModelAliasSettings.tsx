@@ -0,0 +1,137 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { Plus, Tags, Trash2 } from "lucide-react";
+import { toast } from "sonner";
+import type { SettingsFormState } from "@/hooks/useSettings";
+import type { ModelAlias } from "@/types";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+
+interface ModelAliasSettingsProps {
+  settings: SettingsFormState;
+  onChange: (updates: Partial<SettingsFormState>) => void;
+}
+
+/** 校验与后端一致：别名非空、不含空白、不重复，目标不能是另一个别名 */
+function findProblem(aliases: ModelAlias[]): string | null {
+  const names = aliases.map((entry) => entry.alias.trim().toLowerCase());
+  for (const [index, entry] of aliases.entries()) {
+    const alias = entry.alias.trim();
+    const model = entry.model.trim();
+    if (!alias || !model || /\s/.test(alias)) return "invalid";
+    if (names.indexOf(alias.toLowerCase()) !== index) return "duplicate";
+    const target = model.replace(/\[1m\]$/i, "").toLowerCase();
+    if (names.includes(target)) return "chain";
+  }
+  return null;
+}
+
+/**
+ * 模型别名表：供应商配置里可以写 `main`、`fast` 等别名，
+ * 写入 Live 配置、代理转发与用量查价时解析为真实模型名。
+ */
+export function ModelAliasSettings({
+  settings,
+  onChange,
+}: ModelAliasSettingsProps) {
+  const { t } = useTranslation();
+  const saved = settings.modelAliases ?? [];
+  const [rows, setRows] = useState<ModelAlias[]>(saved);
+
+  useEffect(() => {
+    setRows(settings.modelAliases ?? []);
+  }, [settings.modelAliases]);
+
+  const commit = (next: ModelAlias[]) => {
+    // 未填写完整的行先留在本地，两栏都填好后再保存
+    const complete = next.filter(
+      (entry) => entry.alias.trim() && entry.model.trim(),
+    );
+    const trimmed = complete.map((entry) => ({
+      alias: entry.alias.trim(),
+      model: entry.model.trim(),
+    }));
+    if (JSON.stringify(trimmed) === JSON.stringify(saved)) return;
+    const problem = findProblem(trimmed);
+    if (problem) {
+      toast.error(t(`settings.modelAliases.errors.${problem}`));
+      return;
+    }
+    onChange({ modelAliases: trimmed });
+  };
+
+  const updateRow = (index: number, patch: Partial<ModelAlias>) => {
+    setRows((current) =>
+      current.map((entry, i) => (i === index ? { ...entry, ...patch } : entry)),
+    );
+  };
+
+  const removeRow = (index: number) => {
+    const next = rows.filter((_, i) => i !== index);
+    setRows(next);
+    commit(next);
+  };
+
+  return (
+    <section className="space-y-4">
+      <div className="flex items-center gap-2 pb-2 border-b border-border/40">
+        <Tags className="h-4 w-4 text-primary" />
+        <h3 className="text-sm font-medium">
+          {t("settings.modelAliases.title")}
+        </h3>
+      </div>
+      <p className="text-xs text-muted-foreground">
+        {t("settings.modelAliases.description")}
+      </p>
+
+      <div className="space-y-2">
+        {rows.map((entry, index) => (
+          <div key={index} className="flex items-center gap-2">
+            <Input
+              value={entry.alias}
+              placeholder={t("settings.modelAliases.aliasPlaceholder")}
+              aria-label={t("settings.modelAliases.alias")}
+              className="w-40 font-mono text-xs"
+              onChange={(event) =>
+                updateRow(index, { alias: event.target.value })
+              }
+              onBlur={() => commit(rows)}
+            />
+            <span className="text-muted-foreground">→</span>
+            <Input
+              value={entry.model}
+              placeholder={t("settings.modelAliases.modelPlaceholder")}
+              aria-label={t("settings.modelAliases.model")}
+              className="flex-1 font-mono text-xs"
+              onChange={(event) =>
+                updateRow(index, { model: event.target.value })
+              }
+              onBlur={() => commit(rows)}
+            />
+            <Button
+              type="button"
+              variant="ghost"
+              size="icon"
+              aria-label={t("settings.modelAliases.remove")}
+              onClick={() => removeRow(index)}
+            >
+              <Trash2 className="h-4 w-4" />
+            </Button>
+          </div>
+        ))}
+      </div>
+
+      <Button
+        type="button"
+        variant="outline"
+        size="sm"
+        onClick={() =>
+          setRows((current) => [...current, { alias: "", model: "" }])
+        }
+      >
+        <Plus className="mr-1 h-4 w-4" />
+        {t("settings.modelAliases.add")}
+      </Button>
+    </section>
+  );
+}
@@ -1,5 +1,15 @@
 import { useState } from "react";
-import { Server, Activity, Zap, Globe, ShieldAlert } from "lucide-react";
+import {
+  Server,
+  Activity,
+  Zap,
+  Globe,
+  ShieldAlert,
+  Network,
+  WifiOff,
+  Flame,
+  DatabaseZap,
+} from "lucide-react";
 import { motion } from "framer-motion";
 import { useTranslation } from "react-i18next";
 import {
@@ -13,10 +23,22 @@ import { Badge } from "@/components/ui/badge";
 import { ProxyPanel } from "@/components/proxy";
 import { AutoFailoverConfigPanel } from "@/components/proxy/AutoFailoverConfigPanel";
 import { FailoverQueueManager } from "@/components/proxy/FailoverQueueManager";
+import { RetryConfigPanel } from "@/components/proxy/RetryConfigPanel";
+import { DrainConfigPanel } from "@/components/proxy/DrainConfigPanel";
+import { FailbackWatchdogPanel } from "@/components/proxy/FailbackWatchdogPanel";
 import { RectifierConfigPanel } from "@/components/settings/RectifierConfigPanel";
+import { ResponseCacheConfigPanel } from "@/components/settings/ResponseCacheConfigPanel";
 import { GlobalProxySettings } from "@/components/settings/GlobalProxySettings";
 import { ConfirmDialog } from "@/components/ConfirmDialog";
 import { ToggleRow } from "@/components/ui/toggle-row";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import type { IpFamilyPreference } from "@/types";
 import { useProxyStatus } from "@/hooks/useProxyStatus";
 import type { SettingsFormState } from "@/hooks/useSettings";
 
@@ -161,6 +183,42 @@ export function ProxyTabContent({
                 onCheckedChange={handleFailoverToggleChange}
               />
 
+              <div className="space-y-4">
+                <div>
+                  <h4 className="text-sm font-semibold">
+                    {t("proxy.retry.title")}
+                  </h4>
+                  <p className="text-xs text-muted-foreground">
+                    {t("proxy.retry.description")}
+                  </p>
+                </div>
+                <RetryConfigPanel />
+              </div>
+
+              <div className="space-y-4">
+                <div>
+                  <h4 className="text-sm font-semibold">
+                    {t("proxy.drain.title")}
+                  </h4>
+                  <p className="text-xs text-muted-foreground">
+                    {t("proxy.drain.description")}
+                  </p>
+                </div>
+                <DrainConfigPanel />
+              </div>
+
+              <div className="space-y-4">
+                <div>
+                  <h4 className="text-sm font-semibold">
+                    {t("proxy.watchdog.title")}
+                  </h4>
+                  <p className="text-xs text-muted-foreground">
+                    {t("proxy.watchdog.description")}
+                  </p>
+                </div>
+                <FailbackWatchdogPanel />
+              </div>
+
               {!isRunning && (
                 <div className="p-4 rounded-lg bg-yellow-500/10 border border-yellow-500/20">
                   <p className="text-sm text-yellow-600 dark:text-yellow-400">
@@ -237,6 +295,29 @@ export function ProxyTabContent({
           </AccordionContent>
         </AccordionItem>
 
+        {/* Response Cache */}
+        <AccordionItem
+          value="responseCache"
+          className="rounded-xl glass-card overflow-hidden"
+        >
+          <AccordionTrigger className="px-6 py-4 hover:no-underline hover:bg-muted/50 data-[state=open]:bg-muted/50">
+            <div className="flex items-center gap-3">
+              <DatabaseZap className="h-5 w-5 text-emerald-500" />
+              <div className="text-left">
+                <h3 className="text-base font-semibold">
+                  {t("settings.advanced.responseCache.title")}
+                </h3>
+                <p className="text-sm text-muted-foreground font-normal">
+                  {t("settings.advanced.responseCache.description")}
+                </p>
+              </div>
+            </div>
+          </AccordionTrigger>
+          <AccordionContent className="px-6 pb-6 pt-4 border-t border-border/50">
+            <ResponseCacheConfigPanel />
+          </AccordionContent>
+        </AccordionItem>
+
         {/* Global Outbound Proxy */}
         <AccordionItem
           value="globalProxy"
@@ -257,6 +338,72 @@ export function ProxyTabContent({
           </AccordionTrigger>
           <AccordionContent className="px-6 pb-6 pt-4 border-t border-border/50">
             <GlobalProxySettings />
+            <div className="mt-4 space-y-3 border-t border-border/50 pt-4">
+              <div className="flex items-center justify-between gap-4">
+                <div className="space-y-0.5">
+                  <p className="text-sm font-medium">
+                    {t("settings.advanced.network.ipPreference")}
+                  </p>
+                  <p className="text-xs text-muted-foreground">
+                    {t("settings.advanced.network.ipPreferenceDescription")}
+                  </p>
+                </div>
+                <Select
+                  value={settings?.networkIpPreference ?? "auto"}
+                  onValueChange={(value) =>
+                    void onAutoSave({
+                      networkIpPreference: value as IpFamilyPreference,
+                    })
+                  }
+                >
+                  <SelectTrigger className="w-[140px]">
+                    <SelectValue />
+                  </SelectTrigger>
+                  <SelectContent>
+                    <SelectItem value="auto">
+                      {t("settings.advanced.network.ipAuto")}
+                    </SelectItem>
+                    <SelectItem value="ipv4">
+                      {t("settings.advanced.network.ipv4")}
+                    </SelectItem>
+                    <SelectItem value="ipv6">
+                      {t("settings.advanced.network.ipv6")}
+                    </SelectItem>
+                  </SelectContent>
+                </Select>
+              </div>
+              <ToggleRow
+                icon={<Network className="h-4 w-4 text-cyan-500" />}
+                title={t("settings.advanced.network.happyEyeballs")}
+                description={t(
+                  "settings.advanced.network.happyEyeballsDescription",
+                )}
+                checked={settings?.networkHappyEyeballs ?? true}
+                onCheckedChange={(checked) =>
+                  void onAutoSave({ networkHappyEyeballs: checked })
+                }
+              />
+              <ToggleRow
+                icon={<Flame className="h-4 w-4 text-amber-500" />}
+                title={t("settings.advanced.network.keepWarm")}
+                description={t("settings.advanced.network.keepWarmDescription")}
+                checked={settings?.proxyKeepWarm ?? false}
+                onCheckedChange={(checked) =>
+                  void onAutoSave({ proxyKeepWarm: checked })
+                }
+              />
+              <ToggleRow
+                icon={<WifiOff className="h-4 w-4 text-orange-500" />}
+                title={t("settings.advanced.network.offlineMode")}
+                description={t(
+                  "settings.advanced.network.offlineModeDescription",
+                )}
+                checked={settings?.offlineMode ?? false}
+                onCheckedChange={(checked) =>
+                  void onAutoSave({ offlineMode: checked })
+                }
+              />
+            </div>
           </AccordionContent>
         </AccordionItem>
       </Accordion>
@@ -0,0 +1,133 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { Switch } from "@/components/ui/switch";
+import { Label } from "@/components/ui/label";
+import { Input } from "@/components/ui/input";
+import { Button } from "@/components/ui/button";
+import {
+  settingsApi,
+  type ResponseCacheConfig,
+  type ResponseCacheStats,
+} from "@/lib/api/settings";
+
+export function ResponseCacheConfigPanel() {
+  const { t } = useTranslation();
+  const [config, setConfig] = useState<ResponseCacheConfig>({
+    enabled: false,
+    ttlSeconds: 300,
+    maxEntries: 200,
+  });
+  const [stats, setStats] = useState<ResponseCacheStats | null>(null);
+  const [isLoading, setIsLoading] = useState(true);
+
+  const refreshStats = () =>
+    settingsApi
+      .getResponseCacheStats()
+      .then(setStats)
+      .catch((e) => console.error("Failed to load response cache stats:", e));
+
+  useEffect(() => {
+    settingsApi
+      .getResponseCacheConfig()
+      .then(setConfig)
+      .catch((e) => console.error("Failed to load response cache config:", e))
+      .finally(() => setIsLoading(false));
+    void refreshStats();
+  }, []);
+
+  const handleChange = async (updates: Partial<ResponseCacheConfig>) => {
+    const newConfig = { ...config, ...updates };
+    setConfig(newConfig);
+    try {
+      await settingsApi.setResponseCacheConfig(newConfig);
+      if (!newConfig.enabled) void refreshStats();
+    } catch (e) {
+      console.error("Failed to save response cache config:", e);
+      toast.error(String(e));
+      setConfig(config);
+    }
+  };
+
+  const handleClear = async () => {
+    try {
+      await settingsApi.clearResponseCache();
+      await refreshStats();
+      toast.success(t("settings.advanced.responseCache.cleared"));
+    } catch (e) {
+      toast.error(String(e));
+    }
+  };
+
+  if (isLoading) return null;
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between">
+        <div className="space-y-0.5">
+          <Label>{t("settings.advanced.responseCache.enabled")}</Label>
+          <p className="text-xs text-muted-foreground">
+            {t("settings.advanced.responseCache.enabledDescription")}
+          </p>
+        </div>
+        <Switch
+          checked={config.enabled}
+          onCheckedChange={(checked) => handleChange({ enabled: checked })}
+        />
+      </div>
+
+      <div className="grid grid-cols-2 gap-4 pl-4">
+        <div className="space-y-1.5">
+          <Label htmlFor="response-cache-ttl">
+            {t("settings.advanced.responseCache.ttlSeconds")}
+          </Label>
+          <Input
+            id="response-cache-ttl"
+            type="number"
+            min={1}
+            defaultValue={config.ttlSeconds}
+            disabled={!config.enabled}
+            onBlur={(e) => {
+              const value = Number(e.target.value);
+              if (value > 0 && value !== config.ttlSeconds) {
+                void handleChange({ ttlSeconds: value });
+              }
+            }}
+          />
+        </div>
+        <div className="space-y-1.5">
+          <Label htmlFor="response-cache-max">
+            {t("settings.advanced.responseCache.maxEntries")}
+          </Label>
+          <Input
+            id="response-cache-max"
+            type="number"
+            min={1}
+            defaultValue={config.maxEntries}
+            disabled={!config.enabled}
+            onBlur={(e) => {
+              const value = Number(e.target.value);
+              if (value > 0 && value !== config.maxEntries) {
+                void handleChange({ maxEntries: value });
+              }
+            }}
+          />
+        </div>
+      </div>
+
+      {stats && (
+        <div className="flex items-center justify-between rounded-lg bg-muted/40 px-3 py-2 text-xs text-muted-foreground">
+          <span>{t("settings.advanced.responseCache.stats", stats)}</span>
+          <Button
+            variant="ghost"
+            size="sm"
+            className="h-7 px-2 text-xs"
+            onClick={() => void handleClear()}
+          >
+            {t("settings.advanced.responseCache.clear")}
+          </Button>
+        </div>
+      )}
+    </div>
+  );
+}
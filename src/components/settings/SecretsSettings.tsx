@@ -0,0 +1,222 @@
+import { useRef, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { Download, KeyRound, Loader2, Upload } from "lucide-react";
+import { toast } from "sonner";
+import type { SettingsFormState } from "@/hooks/useSettings";
+import { ToggleRow } from "@/components/ui/toggle-row";
+import { Button } from "@/components/ui/button";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { settingsApi } from "@/lib/api";
+import type { PasswordManagerFormat } from "@/lib/api/settings";
+
+const PASSWORD_MANAGER_FORMATS: Array<{
+  value: PasswordManagerFormat;
+  labelKey: string;
+  fileName: string;
+}> = [
+  {
+    value: "1password-csv",
+    labelKey: "settings.secrets.formats.onePasswordCsv",
+    fileName: "cc-switch-secrets-1password.csv",
+  },
+  {
+    value: "bitwarden-json",
+    labelKey: "settings.secrets.formats.bitwardenJson",
+    fileName: "cc-switch-secrets-bitwarden.json",
+  },
+  {
+    value: "bitwarden-csv",
+    labelKey: "settings.secrets.formats.bitwardenCsv",
+    fileName: "cc-switch-secrets-bitwarden.csv",
+  },
+];
+
+interface SecretsSettingsProps {
+  settings: SettingsFormState;
+  onChange: (updates: Partial<SettingsFormState>) => void;
+}
+
+/**
+ * API Key 存储位置：开启后新保存的密钥进入系统钥匙串，
+ * 配置中只保留 `keychain:` 引用；已有明文密钥需手动迁移一次。
+ */
+export function SecretsSettings({ settings, onChange }: SecretsSettingsProps) {
+  const { t } = useTranslation();
+  const enabled = settings.secretsUseKeychain ?? false;
+  const [migrating, setMigrating] = useState(false);
+  const [format, setFormat] = useState<PasswordManagerFormat>("1password-csv");
+  const importInputRef = useRef<HTMLInputElement>(null);
+
+  const handleMigrate = async () => {
+    setMigrating(true);
+    try {
+      const report = await settingsApi.migrateSecretsToKeychain();
+      if (report.failed.length > 0) {
+        console.error("Failed to migrate some secrets:", report.failed);
+        toast.warning(
+          t("settings.secrets.migratePartial", {
+            providers: report.providers,
+            failed: report.failed.length,
+          }),
+        );
+      } else {
+        toast.success(
+          t("settings.secrets.migrateSuccess", {
+            providers: report.providers,
+            secrets: report.secrets,
+          }),
+        );
+      }
+    } catch (error) {
+      console.error("Failed to migrate secrets:", error);
+      toast.error(
+        t("settings.secrets.migrateFailed", { error: String(error) }),
+      );
+    } finally {
+      setMigrating(false);
+    }
+  };
+
+  const handleExport = async () => {
+    try {
+      const fileName =
+        PASSWORD_MANAGER_FORMATS.find((item) => item.value === format)
+          ?.fileName ?? "cc-switch-secrets.csv";
+      const filePath = await settingsApi.saveFileDialog(fileName);
+      if (!filePath) return;
+      const report = await settingsApi.exportSecretsForPasswordManager(
+        format,
+        filePath,
+      );
+      if (report.skipped.length > 0) {
+        console.warn("Skipped providers during export:", report.skipped);
+      }
+      toast.success(
+        t("settings.secrets.exportSuccess", {
+          items: report.items,
+          skipped: report.skipped.length,
+        }),
+      );
+    } catch (error) {
+      console.error("Failed to export secrets:", error);
+      toast.error(t("settings.secrets.exportFailed", { error: String(error) }));
+    }
+  };
+
+  const handleImportFile = async (file: File | undefined) => {
+    if (!file) return;
+    try {
+      const report = await settingsApi.importSecretsFromPasswordManager(
+        await file.text(),
+      );
+      if (report.skipped.length > 0) {
+        console.warn("Skipped password manager items:", report.skipped);
+      }
+      toast.success(
+        t("settings.secrets.importSuccess", {
+          updated: report.updated,
+          unchanged: report.unchanged,
+          skipped: report.skipped.length,
+        }),
+      );
+    } catch (error) {
+      console.error("Failed to import secrets:", error);
+      toast.error(t("settings.secrets.importFailed", { error: String(error) }));
+    } finally {
+      if (importInputRef.current) importInputRef.current.value = "";
+    }
+  };
+
+  return (
+    <section className="space-y-4">
+      <div className="flex items-center gap-2 pb-2 border-b border-border/40">
+        <KeyRound className="h-4 w-4 text-primary" />
+        <h3 className="text-sm font-medium">{t("settings.secrets.title")}</h3>
+      </div>
+
+      <ToggleRow
+        icon={<KeyRound className="h-4 w-4 text-amber-500" />}
+        title={t("settings.secrets.useKeychain")}
+        description={t("settings.secrets.useKeychainDescription")}
+        checked={enabled}
+        onCheckedChange={(value) => onChange({ secretsUseKeychain: value })}
+      />
+
+      {enabled && (
+        <div className="flex items-center justify-between gap-4 rounded-lg border border-border/40 p-3">
+          <p className="text-xs text-muted-foreground">
+            {t("settings.secrets.migrateDescription")}
+          </p>
+          <Button
+            variant="outline"
+            size="sm"
+            disabled={migrating}
+            onClick={() => void handleMigrate()}
+          >
+            {migrating && (
+              <Loader2 className="h-3.5 w-3.5 mr-1.5 animate-spin" />
+            )}
+            {t("settings.secrets.migrate")}
+          </Button>
+        </div>
+      )}
+
+      <div className="space-y-3 rounded-lg border border-border/40 p-3">
+        <div className="space-y-1">
+          <p className="text-sm font-medium">
+            {t("settings.secrets.passwordManager")}
+          </p>
+          <p className="text-xs text-muted-foreground">
+            {t("settings.secrets.passwordManagerDescription")}
+          </p>
+        </div>
+        <div className="flex flex-wrap items-center gap-2">
+          <Select
+            value={format}
+            onValueChange={(value) => setFormat(value as PasswordManagerFormat)}
+          >
+            <SelectTrigger className="h-8 w-[180px] text-xs">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {PASSWORD_MANAGER_FORMATS.map((item) => (
+                <SelectItem key={item.value} value={item.value}>
+                  {t(item.labelKey)}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={() => void handleExport()}
+          >
+            <Download className="h-3.5 w-3.5 mr-1.5" />
+            {t("settings.secrets.export")}
+          </Button>
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={() => importInputRef.current?.click()}
+          >
+            <Upload className="h-3.5 w-3.5 mr-1.5" />
+            {t("settings.secrets.import")}
+          </Button>
+          <input
+            ref={importInputRef}
+            type="file"
+            accept=".csv,.json"
+            className="hidden"
+            onChange={(e) => void handleImportFile(e.target.files?.[0])}
+          />
+        </div>
+      </div>
+    </section>
+  );
+}
@@ -0,0 +1,288 @@
+import { useEffect, useMemo, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { useQueryClient } from "@tanstack/react-query";
+import { toast } from "sonner";
+import { Loader2 } from "lucide-react";
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+} from "@/components/ui/dialog";
+import { Button } from "@/components/ui/button";
+import { Checkbox } from "@/components/ui/checkbox";
+import { Label } from "@/components/ui/label";
+import { settingsApi } from "@/lib/api";
+import type { BackupContents, BackupProviderEntry } from "@/types";
+
+export type RestoreSource = "webdav" | "s3";
+
+interface SelectiveRestoreDialogProps {
+  source: RestoreSource | null;
+  onClose: () => void;
+}
+
+const providerKey = (entry: { appType: string; id: string }) =>
+  `${entry.appType}/${entry.id}`;
+
+/**
+ * 从云端备份中挑选供应商 / MCP 服务器 / 设置恢复，合并到本地而非整体覆盖。
+ */
+export function SelectiveRestoreDialog({
+  source,
+  onClose,
+}: SelectiveRestoreDialogProps) {
+  const { t } = useTranslation();
+  const queryClient = useQueryClient();
+  const [contents, setContents] = useState<BackupContents | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [restoring, setRestoring] = useState(false);
+  const [providers, setProviders] = useState<Set<string>>(new Set());
+  const [mcpServers, setMcpServers] = useState<Set<string>>(new Set());
+  const [settings, setSettings] = useState(false);
+
+  useEffect(() => {
+    if (!source) return;
+    setContents(null);
+    setProviders(new Set());
+    setMcpServers(new Set());
+    setSettings(false);
+    setLoading(true);
+    const preview =
+      source === "webdav"
+        ? settingsApi.webdavSyncPreviewRestore()
+        : settingsApi.s3SyncPreviewRestore();
+    preview
+      .then(setContents)
+      .catch((error) => {
+        toast.error(
+          t("settings.selectiveRestore.previewFailed", {
+            error: (error as Error)?.message ?? String(error),
+          }),
+        );
+        onClose();
+      })
+      .finally(() => setLoading(false));
+  }, [source, onClose, t]);
+
+  const groups = useMemo(() => {
+    const map = new Map<string, BackupProviderEntry[]>();
+    for (const entry of contents?.providers ?? []) {
+      map.set(entry.appType, [...(map.get(entry.appType) ?? []), entry]);
+    }
+    return [...map.entries()];
+  }, [contents]);
+
+  const toggle = (
+    set: Set<string>,
+    update: (next: Set<string>) => void,
+    keys: string[],
+    checked: boolean,
+  ) => {
+    const next = new Set(set);
+    keys.forEach((key) => (checked ? next.add(key) : next.delete(key)));
+    update(next);
+  };
+
+  const nothingSelected =
+    providers.size === 0 && mcpServers.size === 0 && !settings;
+
+  const handleRestore = async () => {
+    if (!source || !contents) return;
+    setRestoring(true);
+    try {
+      const selection = {
+        providers: contents.providers
+          .filter((entry) => providers.has(providerKey(entry)))
+          .map(({ appType, id }) => ({ appType, id })),
+        mcpServers: [...mcpServers],
+        settings,
+      };
+      const summary =
+        source === "webdav"
+          ? await settingsApi.webdavSyncRestoreSelected(selection)
+          : await settingsApi.s3SyncRestoreSelected(selection);
+      toast.success(t("settings.selectiveRestore.success", summary));
+      if (summary.warning) toast.warning(summary.warning);
+      await queryClient.invalidateQueries();
+      onClose();
+    } catch (error) {
+      toast.error(
+        t("settings.selectiveRestore.failed", {
+          error: (error as Error)?.message ?? String(error),
+        }),
+      );
+    } finally {
+      setRestoring(false);
+    }
+  };
+
+  return (
+    <Dialog
+      open={source !== null}
+      onOpenChange={(open) => {
+        if (!open) onClose();
+      }}
+    >
+      <DialogContent className="max-w-lg" zIndex="alert">
+        <DialogHeader>
+          <DialogTitle>{t("settings.selectiveRestore.title")}</DialogTitle>
+          <DialogDescription>
+            {contents
+              ? t("settings.selectiveRestore.description", {
+                  device: contents.deviceName,
+                  time: new Date(contents.createdAt).toLocaleString(),
+                })
+              : t("settings.selectiveRestore.loading")}
+          </DialogDescription>
+        </DialogHeader>
+
+        {loading && (
+          <div className="flex justify-center py-8">
+            <Loader2 className="h-5 w-5 animate-spin text-muted-foreground" />
+          </div>
+        )}
+
+        {contents && (
+          <div className="max-h-[55vh] space-y-4 overflow-y-auto px-6 py-2 text-sm">
+            {groups.map(([appType, entries]) => {
+              const keys = entries.map(providerKey);
+              const selected = keys.filter((key) => providers.has(key)).length;
+              return (
+                <div key={appType} className="space-y-2">
+                  <div className="flex items-center gap-2 font-medium">
+                    <Checkbox
+                      id={`restore-app-${appType}`}
+                      checked={
+                        selected === 0
+                          ? false
+                          : selected === keys.length
+                            ? true
+                            : "indeterminate"
+                      }
+                      onCheckedChange={(checked) =>
+                        toggle(providers, setProviders, keys, checked === true)
+                      }
+                    />
+                    <Label htmlFor={`restore-app-${appType}`}>
+                      {t(`apps.${appType}`, { defaultValue: appType })}
+                    </Label>
+                  </div>
+                  <div className="space-y-1.5 pl-6">
+                    {entries.map((entry) => {
+                      const key = providerKey(entry);
+                      return (
+                        <div key={key} className="flex items-center gap-2">
+                          <Checkbox
+                            id={`restore-provider-${key}`}
+                            checked={providers.has(key)}
+                            onCheckedChange={(checked) =>
+                              toggle(
+                                providers,
+                                setProviders,
+                                [key],
+                                checked === true,
+                              )
+                            }
+                          />
+                          <Label
+                            htmlFor={`restore-provider-${key}`}
+                            className="font-normal"
+                          >
+                            {entry.name}
+                          </Label>
+                          {entry.isCurrent && (
+                            <span className="text-xs text-muted-foreground">
+                              {t("settings.selectiveRestore.current")}
+                            </span>
+                          )}
+                          {entry.existsLocally && (
+                            <span className="text-xs text-amber-600 dark:text-amber-400">
+                              {t("settings.selectiveRestore.overwrite")}
+                            </span>
+                          )}
+                        </div>
+                      );
+                    })}
+                  </div>
+                </div>
+              );
+            })}
+
+            {contents.mcpServers.length > 0 && (
+              <div className="space-y-2">
+                <div className="font-medium">
+                  {t("settings.selectiveRestore.mcpServers")}
+                </div>
+                <div className="space-y-1.5 pl-6">
+                  {contents.mcpServers.map((server) => (
+                    <div key={server.id} className="flex items-center gap-2">
+                      <Checkbox
+                        id={`restore-mcp-${server.id}`}
+                        checked={mcpServers.has(server.id)}
+                        onCheckedChange={(checked) =>
+                          toggle(
+                            mcpServers,
+                            setMcpServers,
+                            [server.id],
+                            checked === true,
+                          )
+                        }
+                      />
+                      <Label
+                        htmlFor={`restore-mcp-${server.id}`}
+                        className="font-normal"
+                      >
+                        {server.name}
+                      </Label>
+                      {server.existsLocally && (
+                        <span className="text-xs text-amber-600 dark:text-amber-400">
+                          {t("settings.selectiveRestore.overwrite")}
+                        </span>
+                      )}
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
+
+            {contents.settings.length > 0 && (
+              <div className="flex items-start gap-2">
+                <Checkbox
+                  id="restore-settings"
+                  checked={settings}
+                  onCheckedChange={(checked) => setSettings(checked === true)}
+                />
+                <div className="space-y-1">
+                  <Label htmlFor="restore-settings">
+                    {t("settings.selectiveRestore.settings")}
+                  </Label>
+                  <p className="text-xs text-muted-foreground">
+                    {t("settings.selectiveRestore.settingsHint", {
+                      count: contents.settings.length,
+                    })}
+                  </p>
+                </div>
+              </div>
+            )}
+          </div>
+        )}
+
+        <DialogFooter>
+          <Button variant="outline" onClick={onClose} disabled={restoring}>
+            {t("common.cancel")}
+          </Button>
+          <Button
+            onClick={handleRestore}
+            disabled={!contents || nothingSelected || restoring}
+          >
+            {restoring && <Loader2 className="mr-2 h-4 w-4 animate-spin" />}
+            {t("settings.selectiveRestore.confirm")}
+          </Button>
+        </DialogFooter>
+      </DialogContent>
+    </Dialog>
+  );
+}
@@ -16,6 +16,7 @@ import {
   ScrollText,
   HardDriveDownload,
   FlaskConical,
+  Layers,
 } from "lucide-react";
 import { toast } from "sonner";
 import {
@@ -48,10 +49,16 @@ import { WebdavSyncSection } from "@/components/settings/WebdavSyncSection";
 import { AboutSection } from "@/components/settings/AboutSection";
 import { ProxyTabContent } from "@/components/settings/ProxyTabContent";
 import { ConnectivityCheckConfigPanel } from "@/components/usage/ConnectivityCheckConfigPanel";
+import { NightlySelfTestPanel } from "@/components/usage/NightlySelfTestPanel";
 import { UsageDashboard } from "@/components/usage/UsageDashboard";
 import { LogConfigPanel } from "@/components/settings/LogConfigPanel";
 import { AuthCenterPanel } from "@/components/settings/AuthCenterPanel";
 import { CodexAuthSettings } from "@/components/settings/CodexAuthSettings";
+import { TelemetrySettings } from "@/components/settings/TelemetrySettings";
+import { SecretsSettings } from "@/components/settings/SecretsSettings";
+import { ModelAliasSettings } from "@/components/settings/ModelAliasSettings";
+import { CommonConfigMigrationSection } from "@/components/settings/CommonConfigMigrationSection";
+import { ManagedSettingsNotice } from "@/components/settings/ManagedSettingsNotice";
 import { useInstalledSkills } from "@/hooks/useSkills";
 import { useSettings } from "@/hooks/useSettings";
 import { useImportExport } from "@/hooks/useImportExport";
@@ -252,6 +259,7 @@ export function SettingsPage({
                     transition={{ duration: 0.3 }}
                     className="space-y-6"
                   >
+                    <ManagedSettingsNotice />
                     <LanguageSettings
                       value={settings.language}
                       onChange={(lang) => handleAutoSave({ language: lang })}
@@ -282,6 +290,18 @@ export function SettingsPage({
                       settings={settings}
                       onChange={handleAutoSave}
                     />
+                    <SecretsSettings
+                      settings={settings}
+                      onChange={handleAutoSave}
+                    />
+                    <ModelAliasSettings
+                      settings={settings}
+                      onChange={handleAutoSave}
+                    />
+                    <TelemetrySettings
+                      settings={settings}
+                      onChange={handleAutoSave}
+                    />
                     <TerminalSettings
                       value={settings.preferredTerminal}
                       onChange={(terminal) =>
@@ -395,6 +415,28 @@ export function SettingsPage({
                         </AccordionContent>
                       </AccordionItem>
 
+                      <AccordionItem
+                        value="commonConfigMigration"
+                        className="rounded-xl glass-card overflow-hidden"
+                      >
+                        <AccordionTrigger className="px-6 py-4 hover:no-underline hover:bg-muted/50 data-[state=open]:bg-muted/50">
+                          <div className="flex items-center gap-3">
+                            <Layers className="h-5 w-5 text-violet-500" />
+                            <div className="text-left">
+                              <h3 className="text-base font-semibold">
+                                {t("settings.commonConfigMigration.title")}
+                              </h3>
+                              <p className="text-sm text-muted-foreground font-normal">
+                                {t("settings.commonConfigMigration.subtitle")}
+                              </p>
+                            </div>
+                          </div>
+                        </AccordionTrigger>
+                        <AccordionContent className="px-6 pb-6 pt-4 border-t border-border/50">
+                          <CommonConfigMigrationSection />
+                        </AccordionContent>
+                      </AccordionItem>
+
                       <AccordionItem
                         value="backup"
                         className="rounded-xl glass-card overflow-hidden"
@@ -476,6 +518,9 @@ export function SettingsPage({
                         </AccordionTrigger>
                         <AccordionContent className="px-6 pb-6 pt-4 border-t border-border/50">
                           <ConnectivityCheckConfigPanel />
+                          <div className="mt-6 pt-6 border-t border-border/50">
+                            <NightlySelfTestPanel />
+                          </div>
                         </AccordionContent>
                       </AccordionItem>
 
@@ -0,0 +1,117 @@
+import { useCallback, useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { BarChart3, Download, RotateCcw } from "lucide-react";
+import { toast } from "sonner";
+import type { SettingsFormState } from "@/hooks/useSettings";
+import { ToggleRow } from "@/components/ui/toggle-row";
+import { Button } from "@/components/ui/button";
+import { settingsApi } from "@/lib/api";
+import type { TelemetrySnapshot } from "@/lib/api/settings";
+
+interface TelemetrySettingsProps {
+  settings: SettingsFormState;
+  onChange: (updates: Partial<SettingsFormState>) => void;
+}
+
+export function TelemetrySettings({
+  settings,
+  onChange,
+}: TelemetrySettingsProps) {
+  const { t } = useTranslation();
+  const enabled = settings.usageTelemetryEnabled ?? false;
+  const [snapshot, setSnapshot] = useState<TelemetrySnapshot | null>(null);
+
+  const refresh = useCallback(() => {
+    void settingsApi
+      .getTelemetrySnapshot()
+      .then(setSnapshot)
+      .catch((error) => {
+        console.error("Failed to load telemetry snapshot:", error);
+      });
+  }, []);
+
+  useEffect(() => {
+    if (enabled) refresh();
+  }, [enabled, refresh]);
+
+  const handleExport = async () => {
+    try {
+      const filePath = await settingsApi.saveFileDialog(
+        "cc-switch-usage-report.json",
+      );
+      if (!filePath) return;
+      await settingsApi.exportTelemetryReport(filePath);
+      toast.success(t("settings.telemetry.exportSuccess"));
+    } catch (error) {
+      console.error("Failed to export telemetry report:", error);
+      toast.error(t("settings.telemetry.exportFailed"));
+    }
+  };
+
+  const handleReset = async () => {
+    try {
+      await settingsApi.resetTelemetry();
+      refresh();
+      toast.success(t("settings.telemetry.resetSuccess"));
+    } catch (error) {
+      console.error("Failed to reset telemetry:", error);
+      toast.error(t("settings.telemetry.resetFailed"));
+    }
+  };
+
+  const counters = Object.entries(snapshot?.counters ?? {});
+
+  return (
+    <section className="space-y-4">
+      <div className="flex items-center gap-2 pb-2 border-b border-border/40">
+        <BarChart3 className="h-4 w-4 text-primary" />
+        <h3 className="text-sm font-medium">{t("settings.telemetry.title")}</h3>
+      </div>
+
+      <ToggleRow
+        icon={<BarChart3 className="h-4 w-4 text-violet-500" />}
+        title={t("settings.telemetry.enable")}
+        description={t("settings.telemetry.enableDescription")}
+        checked={enabled}
+        onCheckedChange={(value) => onChange({ usageTelemetryEnabled: value })}
+      />
+
+      {enabled && (
+        <div className="space-y-3 rounded-lg border border-border/40 p-3">
+          {counters.length === 0 ? (
+            <p className="text-xs text-muted-foreground">
+              {t("settings.telemetry.empty")}
+            </p>
+          ) : (
+            <ul className="space-y-1 text-xs">
+              {counters.map(([key, count]) => (
+                <li key={key} className="flex justify-between font-mono">
+                  <span className="text-muted-foreground">{key}</span>
+                  <span>{count}</span>
+                </li>
+              ))}
+            </ul>
+          )}
+          <div className="flex gap-2">
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => void handleExport()}
+            >
+              <Download className="h-3.5 w-3.5 mr-1.5" />
+              {t("settings.telemetry.export")}
+            </Button>
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => void handleReset()}
+            >
+              <RotateCcw className="h-3.5 w-3.5 mr-1.5" />
+              {t("settings.telemetry.reset")}
+            </Button>
+          </div>
+        </div>
+      )}
+    </section>
+  );
+}
@@ -34,6 +34,10 @@ import {
 } from "@/components/ui/dialog";
 import { settingsApi } from "@/lib/api";
 import { ConfirmDialog } from "@/components/ConfirmDialog";
+import {
+  SelectiveRestoreDialog,
+  type RestoreSource,
+} from "./SelectiveRestoreDialog";
 import type { SettingsFormState } from "@/hooks/useSettings";
 import type {
   RemoteSnapshotInfo,
@@ -229,6 +233,67 @@ function ActionButton({
   );
 }
 
+// ─── SyncEncryptionFields ───────────────────────────────────
+
+/** Backup encryption toggle + passphrase, shared by WebDAV and S3 forms. */
+function SyncEncryptionFields({
+  enabled,
+  passphrase,
+  hasSavedPassphrase,
+  disabled,
+  onEnabledChange,
+  onPassphraseChange,
+}: {
+  enabled: boolean;
+  passphrase: string;
+  hasSavedPassphrase: boolean;
+  disabled?: boolean;
+  onEnabledChange: (checked: boolean) => void;
+  onPassphraseChange: (value: string) => void;
+}) {
+  const { t } = useTranslation();
+  return (
+    <>
+      <div className="flex items-start gap-4">
+        <label className="w-40 text-xs font-medium text-foreground shrink-0">
+          {t("settings.syncEncryption.enabled")}
+          <span className="block text-[10px] font-normal text-muted-foreground">
+            {t("settings.syncEncryption.hint")}
+          </span>
+        </label>
+        <div className="pt-1">
+          <Switch
+            checked={enabled}
+            onCheckedChange={onEnabledChange}
+            aria-label={t("settings.syncEncryption.enabled")}
+            disabled={disabled}
+          />
+        </div>
+      </div>
+      {enabled && (
+        <div className="flex items-center gap-4">
+          <label className="w-40 text-xs font-medium text-foreground shrink-0">
+            {t("settings.syncEncryption.passphrase")}
+          </label>
+          <Input
+            type="password"
+            value={passphrase}
+            onChange={(e) => onPassphraseChange(e.target.value)}
+            placeholder={
+              hasSavedPassphrase
+                ? t("settings.syncEncryption.passphraseKeepPlaceholder")
+                : t("settings.syncEncryption.passphrasePlaceholder")
+            }
+            className="text-xs flex-1"
+            autoComplete="new-password"
+            disabled={disabled}
+          />
+        </div>
+      )}
+    </>
+  );
+}
+
 // ─── Main component ─────────────────────────────────────────
 
 export function WebdavSyncSection({
@@ -270,6 +335,10 @@ export function WebdavSyncSection({
     remoteRoot: config?.remoteRoot ?? "cc-switch-sync",
     profile: config?.profile ?? "default",
     autoSync: config?.autoSync ?? false,
+    encryptionEnabled: config?.encryptionEnabled ?? false,
+    syncUsage: config?.syncUsage ?? false,
+    // 口令从不下发给前端，留空保存表示沿用已保存的口令
+    encryptionPassphrase: "",
   }));
 
   // ─── S3 form state ─────────────────────────────────────────
@@ -289,6 +358,11 @@ export function WebdavSyncSection({
   const [s3Profile, setS3Profile] = useState(s3Config?.profile ?? "default");
   const [s3AutoSync, setS3AutoSync] = useState(s3Config?.autoSync ?? false);
   const [s3Enabled, setS3Enabled] = useState(s3Config?.enabled ?? false);
+  const [s3EncryptionEnabled, setS3EncryptionEnabled] = useState(
+    s3Config?.encryptionEnabled ?? false,
+  );
+  const [s3EncryptionPassphrase, setS3EncryptionPassphrase] = useState("");
+  const [s3SyncUsage, setS3SyncUsage] = useState(s3Config?.syncUsage ?? false);
   const [s3SecretTouched, setS3SecretTouched] = useState(false);
   const [s3Dirty, setS3Dirty] = useState(false);
   const [s3JustSaved, setS3JustSaved] = useState(false);
@@ -314,6 +388,10 @@ export function WebdavSyncSection({
   const [dialogType, setDialogType] = useState<DialogType>(null);
   const [remoteInfo, setRemoteInfo] = useState<RemoteSnapshotInfo | null>(null);
   const [showAutoSyncConfirm, setShowAutoSyncConfirm] = useState(false);
+  const [restoreSource, setRestoreSource] = useState<RestoreSource | null>(
+    null,
+  );
+  const closeSelectiveRestore = useCallback(() => setRestoreSource(null), []);
 
   const closeDialog = useCallback(() => {
     setDialogType(null);
@@ -366,6 +444,9 @@ export function WebdavSyncSection({
         remoteRoot: nextRemoteRoot,
         profile: nextProfile,
         autoSync: config.autoSync ?? false,
+        encryptionEnabled: config.encryptionEnabled ?? false,
+        syncUsage: config.syncUsage ?? false,
+        encryptionPassphrase: "",
       };
     });
     setPasswordTouched(false);
@@ -384,6 +465,9 @@ export function WebdavSyncSection({
     setS3Profile(s3Config.profile ?? "default");
     setS3AutoSync(s3Config.autoSync ?? false);
     setS3Enabled(s3Config.enabled ?? false);
+    setS3EncryptionEnabled(s3Config.encryptionEnabled ?? false);
+    setS3EncryptionPassphrase("");
+    setS3SyncUsage(s3Config.syncUsage ?? false);
     setS3SecretTouched(false);
   }, [s3Config, s3Dirty]);
 
@@ -400,6 +484,26 @@ export function WebdavSyncSection({
     }
   }, []);
 
+  const handleEncryptionChange = useCallback((checked: boolean) => {
+    setForm((prev) => ({ ...prev, encryptionEnabled: checked }));
+    setDirty(true);
+    setJustSaved(false);
+    if (justSavedTimerRef.current) {
+      clearTimeout(justSavedTimerRef.current);
+      justSavedTimerRef.current = null;
+    }
+  }, []);
+
+  const handleSyncUsageChange = useCallback((checked: boolean) => {
+    setForm((prev) => ({ ...prev, syncUsage: checked }));
+    setDirty(true);
+    setJustSaved(false);
+    if (justSavedTimerRef.current) {
+      clearTimeout(justSavedTimerRef.current);
+      justSavedTimerRef.current = null;
+    }
+  }, []);
+
   const handlePresetChange = useCallback((id: string) => {
     setPresetId(id);
     const preset = WEBDAV_PRESETS.find((p) => p.id === id);
@@ -464,6 +568,9 @@ export function WebdavSyncSection({
       remoteRoot: form.remoteRoot.trim() || "cc-switch-sync",
       profile: form.profile.trim() || "default",
       autoSync: form.autoSync,
+      encryptionEnabled: form.encryptionEnabled,
+      encryptionPassphrase: form.encryptionPassphrase,
+      syncUsage: form.syncUsage,
     };
   }, [form, passwordTouched]);
 
@@ -679,6 +786,9 @@ export function WebdavSyncSection({
       endpoint: s3Endpoint.trim() || undefined,
       remoteRoot: s3RemoteRoot.trim() || "cc-switch-sync",
       profile: s3Profile.trim() || "default",
+      encryptionEnabled: s3EncryptionEnabled,
+      encryptionPassphrase: s3EncryptionPassphrase,
+      syncUsage: s3SyncUsage,
     };
   }, [
     s3Enabled,
@@ -690,6 +800,9 @@ export function WebdavSyncSection({
     s3Endpoint,
     s3RemoteRoot,
     s3Profile,
+    s3EncryptionEnabled,
+    s3EncryptionPassphrase,
+    s3SyncUsage,
   ]);
 
   // ─── S3 Handlers ──────────────────────────────────────────
@@ -1122,6 +1235,34 @@ export function WebdavSyncSection({
                 />
               </div>
             </div>
+
+            <div className="flex items-start gap-4">
+              <label className="w-40 text-xs font-medium text-foreground shrink-0">
+                {t("settings.webdavSync.syncUsage")}
+                <span className="block text-[10px] font-normal text-muted-foreground">
+                  {t("settings.webdavSync.syncUsageHint")}
+                </span>
+              </label>
+              <div className="pt-1">
+                <Switch
+                  checked={form.syncUsage}
+                  onCheckedChange={handleSyncUsageChange}
+                  aria-label={t("settings.webdavSync.syncUsage")}
+                  disabled={isLoading}
+                />
+              </div>
+            </div>
+
+            <SyncEncryptionFields
+              enabled={form.encryptionEnabled}
+              passphrase={form.encryptionPassphrase}
+              hasSavedPassphrase={!!config?.encryptionEnabled}
+              disabled={isLoading}
+              onEnabledChange={handleEncryptionChange}
+              onPassphraseChange={(value) =>
+                updateField("encryptionPassphrase", value)
+              }
+            />
           </div>
 
           {/* Last sync time */}
@@ -1413,6 +1554,42 @@ export function WebdavSyncSection({
               </div>
             </div>
 
+            {/* Usage sync toggle */}
+            <div className="flex items-start gap-4">
+              <label className="w-40 text-xs font-medium text-foreground shrink-0">
+                {t("settings.s3Sync.syncUsage")}
+                <span className="block text-[10px] font-normal text-muted-foreground">
+                  {t("settings.s3Sync.syncUsageHint")}
+                </span>
+              </label>
+              <div className="pt-1">
+                <Switch
+                  checked={s3SyncUsage}
+                  onCheckedChange={(checked) => {
+                    setS3SyncUsage(checked);
+                    markS3Dirty();
+                  }}
+                  aria-label={t("settings.s3Sync.syncUsage")}
+                  disabled={isS3Loading}
+                />
+              </div>
+            </div>
+
+            <SyncEncryptionFields
+              enabled={s3EncryptionEnabled}
+              passphrase={s3EncryptionPassphrase}
+              hasSavedPassphrase={!!s3Config?.encryptionEnabled}
+              disabled={isS3Loading}
+              onEnabledChange={(checked) => {
+                setS3EncryptionEnabled(checked);
+                markS3Dirty();
+              }}
+              onPassphraseChange={(value) => {
+                setS3EncryptionPassphrase(value);
+                markS3Dirty();
+              }}
+            />
+
             {/* Enabled toggle */}
             <div className="flex items-start gap-4">
               <label className="w-40 text-xs font-medium text-foreground shrink-0">
@@ -1693,6 +1870,15 @@ export function WebdavSyncSection({
             <Button variant="outline" onClick={closeDialog}>
               {t("common.cancel")}
             </Button>
+            <Button
+              variant="outline"
+              onClick={() => {
+                closeDialog();
+                setRestoreSource("webdav");
+              }}
+            >
+              {t("settings.selectiveRestore.open")}
+            </Button>
             <Button variant="destructive" onClick={handleDownloadConfirm}>
               {t("settings.webdavSync.confirmDownload.confirm")}
             </Button>
@@ -1812,6 +1998,15 @@ export function WebdavSyncSection({
             <Button variant="outline" onClick={closeS3Dialog}>
               {t("common.cancel")}
             </Button>
+            <Button
+              variant="outline"
+              onClick={() => {
+                closeS3Dialog();
+                setRestoreSource("s3");
+              }}
+            >
+              {t("settings.selectiveRestore.open")}
+            </Button>
             <Button variant="destructive" onClick={handleS3DownloadConfirm}>
               {t("settings.s3Sync.confirmDownload.confirm")}
             </Button>
@@ -1819,6 +2014,11 @@ export function WebdavSyncSection({
         </DialogContent>
       </Dialog>
 
+      <SelectiveRestoreDialog
+        source={restoreSource}
+        onClose={closeSelectiveRestore}
+      />
+
       {/* ─── Mutual exclusion confirmation dialog ────────── */}
       <Dialog
         open={dialogType === "mutual_exclusion"}
@@ -1,6 +1,14 @@
 import { useTranslation } from "react-i18next";
 import type { SettingsFormState } from "@/hooks/useSettings";
-import { AppWindow, MonitorUp, Power, EyeOff } from "lucide-react";
+import {
+  AppWindow,
+  MonitorUp,
+  Power,
+  EyeOff,
+  Puzzle,
+  PanelBottom,
+  ImageIcon,
+} from "lucide-react";
 import { ToggleRow } from "@/components/ui/toggle-row";
 import { AnimatePresence, motion } from "framer-motion";
 import { isLinux } from "@/lib/platform";
@@ -67,6 +75,14 @@ export function WindowSettings({ settings, onChange }: WindowSettingsProps) {
           onCheckedChange={(value) => onChange({ skipClaudeOnboarding: value })}
         />
 
+        <ToggleRow
+          icon={<Puzzle className="h-4 w-4 text-violet-500" />}
+          title={t("settings.syncClaudePlugins")}
+          description={t("settings.syncClaudePluginsDescription")}
+          checked={settings.syncClaudePlugins ?? true}
+          onCheckedChange={(value) => onChange({ syncClaudePlugins: value })}
+        />
+
         <ToggleRow
           icon={<AppWindow className="h-4 w-4 text-blue-500" />}
           title={t("settings.minimizeToTray")}
@@ -88,6 +104,24 @@ export function WindowSettings({ settings, onChange }: WindowSettingsProps) {
             }
           />
         )}
+
+        <ToggleRow
+          icon={<PanelBottom className="h-4 w-4 text-teal-500" />}
+          title={t("settings.statusFile")}
+          description={t("settings.statusFileDescription")}
+          checked={!!settings.statusFileEnabled}
+          onCheckedChange={(value) => onChange({ statusFileEnabled: value })}
+        />
+
+        <ToggleRow
+          icon={<ImageIcon className="h-4 w-4 text-pink-500" />}
+          title={t("settings.providerFavicons")}
+          description={t("settings.providerFaviconsDescription")}
+          checked={!!settings.providerFaviconsEnabled}
+          onCheckedChange={(value) =>
+            onChange({ providerFaviconsEnabled: value })
+          }
+        />
       </div>
     </section>
   );
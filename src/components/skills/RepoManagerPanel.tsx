@@ -3,10 +3,23 @@ import { useTranslation } from "react-i18next";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
-import { Trash2, ExternalLink, Plus } from "lucide-react";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { Trash2, ExternalLink, Plus, FolderOpen } from "lucide-react";
 import { settingsApi } from "@/lib/api";
 import { FullScreenPanel } from "@/components/common/FullScreenPanel";
-import type { DiscoverableSkill, SkillRepo } from "@/lib/api/skills";
+import type {
+  DiscoverableSkill,
+  SkillRepo,
+  SkillRepoSource,
+} from "@/lib/api/skills";
+
+const DEFAULT_GITLAB_URL = "https://gitlab.com";
 
 interface RepoManagerPanelProps {
   repos: SkillRepo[];
@@ -24,8 +37,10 @@ export function RepoManagerPanel({
   onClose,
 }: RepoManagerPanelProps) {
   const { t } = useTranslation();
+  const [source, setSource] = useState<SkillRepoSource>("github");
   const [repoUrl, setRepoUrl] = useState("");
   const [branch, setBranch] = useState("");
+  const [accessToken, setAccessToken] = useState("");
   const [error, setError] = useState("");
 
   const getSkillCount = (repo: SkillRepo) =>
@@ -51,33 +66,93 @@ export function RepoManagerPanel({
     return null;
   };
 
+  // GitLab 支持子组：https://gitlab.example.com/group/sub/project → owner = group/sub
+  const parseGitlabUrl = (
+    url: string,
+  ): { owner: string; name: string; location: string } | null => {
+    const cleaned = url.trim().replace(/\.git$/, "").replace(/\/+$/, "");
+    let location = DEFAULT_GITLAB_URL;
+    let path = cleaned;
+    const match = cleaned.match(/^(https?:\/\/[^/]+)\/(.+)$/);
+    if (match) {
+      location = match[1];
+      path = match[2];
+    }
+    const parts = path.split("/").filter(Boolean);
+    if (parts.length < 2) return null;
+    return {
+      owner: parts.slice(0, -1).join("/"),
+      name: parts[parts.length - 1],
+      location,
+    };
+  };
+
+  const parseLocalPath = (
+    path: string,
+  ): { owner: string; name: string; location: string } | null => {
+    const trimmed = path.trim().replace(/[\\/]+$/, "");
+    const name = trimmed.split(/[\\/]/).pop();
+    if (!trimmed || !name) return null;
+    return { owner: "local", name, location: trimmed };
+  };
+
+  const buildRepo = (): SkillRepo | null => {
+    const common = {
+      branch: branch || "main",
+      enabled: true,
+      source,
+      accessToken: accessToken.trim() || undefined,
+    };
+    if (source === "local") {
+      const parsed = parseLocalPath(repoUrl);
+      return parsed ? { ...common, ...parsed, accessToken: undefined } : null;
+    }
+    if (source === "gitlab") {
+      const parsed = parseGitlabUrl(repoUrl);
+      return parsed ? { ...common, ...parsed } : null;
+    }
+    const parsed = parseRepoUrl(repoUrl);
+    return parsed ? { ...common, ...parsed } : null;
+  };
+
+  const handlePickDirectory = async () => {
+    const dir = await settingsApi.pickDirectory(repoUrl || undefined);
+    if (dir) setRepoUrl(dir);
+  };
+
   const handleAdd = async () => {
     setError("");
 
-    const parsed = parseRepoUrl(repoUrl);
-    if (!parsed) {
-      setError(t("skills.repo.invalidUrl"));
+    const repo = buildRepo();
+    if (!repo) {
+      setError(
+        t(
+          source === "local"
+            ? "skills.repo.invalidPath"
+            : "skills.repo.invalidUrl",
+        ),
+      );
       return;
     }
 
     try {
-      await onAdd({
-        owner: parsed.owner,
-        name: parsed.name,
-        branch: branch || "main",
-        enabled: true,
-      });
+      await onAdd(repo);
 
       setRepoUrl("");
       setBranch("");
+      setAccessToken("");
     } catch (e) {
       setError(e instanceof Error ? e.message : t("skills.repo.addFailed"));
     }
   };
 
-  const handleOpenRepo = async (owner: string, name: string) => {
+  const handleOpenRepo = async (repo: SkillRepo) => {
+    const base =
+      repo.source === "gitlab"
+        ? (repo.location || DEFAULT_GITLAB_URL).replace(/\/+$/, "")
+        : "https://github.com";
     try {
-      await settingsApi.openExternal(`https://github.com/${owner}/${name}`);
+      await settingsApi.openExternal(`${base}/${repo.owner}/${repo.name}`);
     } catch (error) {
       console.error("Failed to open URL:", error);
     }
@@ -96,29 +171,89 @@ export function RepoManagerPanel({
         </h3>
         <div className="space-y-4">
           <div>
-            <Label htmlFor="repo-url" className="text-foreground">
-              {t("skills.repo.url")}
+            <Label htmlFor="repo-source" className="text-foreground">
+              {t("skills.repo.source")}
             </Label>
-            <Input
-              id="repo-url"
-              placeholder={t("skills.repo.urlPlaceholder")}
-              value={repoUrl}
-              onChange={(e) => setRepoUrl(e.target.value)}
-              className="mt-2"
-            />
+            <Select
+              value={source}
+              onValueChange={(value) => setSource(value as SkillRepoSource)}
+            >
+              <SelectTrigger id="repo-source" className="mt-2">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="github">GitHub</SelectItem>
+                <SelectItem value="gitlab">GitLab</SelectItem>
+                <SelectItem value="local">
+                  {t("skills.repo.sourceLocal")}
+                </SelectItem>
+              </SelectContent>
+            </Select>
           </div>
           <div>
-            <Label htmlFor="branch" className="text-foreground">
-              {t("skills.repo.branch")}
+            <Label htmlFor="repo-url" className="text-foreground">
+              {t(
+                source === "local"
+                  ? "skills.repo.localPath"
+                  : "skills.repo.url",
+              )}
             </Label>
-            <Input
-              id="branch"
-              placeholder={t("skills.repo.branchPlaceholder")}
-              value={branch}
-              onChange={(e) => setBranch(e.target.value)}
-              className="mt-2"
-            />
+            <div className="mt-2 flex gap-2">
+              <Input
+                id="repo-url"
+                placeholder={t(
+                  source === "gitlab"
+                    ? "skills.repo.gitlabUrlPlaceholder"
+                    : source === "local"
+                      ? "skills.repo.localPathPlaceholder"
+                      : "skills.repo.urlPlaceholder",
+                )}
+                value={repoUrl}
+                onChange={(e) => setRepoUrl(e.target.value)}
+              />
+              {source === "local" && (
+                <Button
+                  variant="outline"
+                  size="icon"
+                  type="button"
+                  onClick={() => void handlePickDirectory()}
+                  title={t("skills.repo.browse")}
+                >
+                  <FolderOpen className="h-4 w-4" />
+                </Button>
+              )}
+            </div>
           </div>
+          {source !== "local" && (
+            <>
+              <div>
+                <Label htmlFor="branch" className="text-foreground">
+                  {t("skills.repo.branch")}
+                </Label>
+                <Input
+                  id="branch"
+                  placeholder={t("skills.repo.branchPlaceholder")}
+                  value={branch}
+                  onChange={(e) => setBranch(e.target.value)}
+                  className="mt-2"
+                />
+              </div>
+              <div>
+                <Label htmlFor="access-token" className="text-foreground">
+                  {t("skills.repo.accessToken")}
+                </Label>
+                <Input
+                  id="access-token"
+                  type="password"
+                  autoComplete="off"
+                  placeholder={t("skills.repo.accessTokenPlaceholder")}
+                  value={accessToken}
+                  onChange={(e) => setAccessToken(e.target.value)}
+                  className="mt-2"
+                />
+              </div>
+            </>
+          )}
           {error && (
             <p className="text-sm text-red-600 dark:text-red-400">{error}</p>
           )}
@@ -156,7 +291,9 @@ export function RepoManagerPanel({
                     {repo.owner}/{repo.name}
                   </div>
                   <div className="mt-1 text-xs text-muted-foreground">
-                    {t("skills.repo.branch")}: {repo.branch || "main"}
+                    {repo.source === "local"
+                      ? repo.location
+                      : `${t("skills.repo.branch")}: ${repo.branch || "main"}`}
                     <span className="ml-3 inline-flex items-center rounded-full border border-border-default px-2 py-0.5 text-[11px]">
                       {t("skills.repo.skillCount", {
                         count: getSkillCount(repo),
@@ -165,16 +302,18 @@ export function RepoManagerPanel({
                   </div>
                 </div>
                 <div className="flex gap-2">
-                  <Button
-                    variant="ghost"
-                    size="icon"
-                    type="button"
-                    onClick={() => handleOpenRepo(repo.owner, repo.name)}
-                    title={t("common.view", { defaultValue: "查看" })}
-                    className="hover:bg-black/5 dark:hover:bg-white/5"
-                  >
-                    <ExternalLink className="h-4 w-4" />
-                  </Button>
+                  {repo.source !== "local" && (
+                    <Button
+                      variant="ghost"
+                      size="icon"
+                      type="button"
+                      onClick={() => handleOpenRepo(repo)}
+                      title={t("common.view", { defaultValue: "查看" })}
+                      className="hover:bg-black/5 dark:hover:bg-white/5"
+                    >
+                      <ExternalLink className="h-4 w-4" />
+                    </Button>
+                  )}
                   <Button
                     variant="ghost"
                     size="icon"
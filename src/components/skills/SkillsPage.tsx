@@ -25,6 +25,9 @@ import {
 import { toast } from "sonner";
 import { SkillCard } from "./SkillCard";
 import { RepoManagerPanel } from "./RepoManagerPanel";
+import { OperationProgressBar } from "@/components/common/OperationProgressBar";
+import { useOperationProgress } from "@/hooks/useOperationProgress";
+import { useLiveReload } from "@/hooks/useLiveReload";
 import {
   useDiscoverableSkills,
   useInstalledSkills,
@@ -62,6 +65,9 @@ type SkillsPageHeaderAction = {
   execute: (page: SkillsPageHandle | null) => void;
 };
 
+/** 技能页关心的后端进度事件 */
+const SKILL_PROGRESS_OPERATIONS = ["skill.index", "skill.install"] as const;
+
 const SKILLS_PAGE_HEADER_ACTIONS: readonly SkillsPageHeaderAction[] = [
   {
     key: "refresh-repos",
@@ -121,6 +127,13 @@ export const SkillsPage = forwardRef<SkillsPageHandle, SkillsPageProps>(
     } = useDiscoverableSkills();
     const { data: installedSkills } = useInstalledSkills();
     const { data: repos = [], refetch: refetchRepos } = useSkillRepos();
+    const operationProgress = useOperationProgress(SKILL_PROGRESS_OPERATIONS);
+    const indexProgress =
+      operationProgress?.operation === "skill.index" ? operationProgress : null;
+    const installProgress =
+      operationProgress?.operation === "skill.install"
+        ? operationProgress
+        : null;
 
     // skills.sh 搜索
     const {
@@ -153,6 +166,7 @@ export const SkillsPage = forwardRef<SkillsPageHandle, SkillsPageProps>(
 
     // Mutations
     const installMutation = useInstallSkill();
+    const { offerReload } = useLiveReload();
     const addRepoMutation = useAddSkillRepo();
     const removeRepoMutation = useRemoveSkillRepo();
 
@@ -251,13 +265,21 @@ export const SkillsPage = forwardRef<SkillsPageHandle, SkillsPageProps>(
       }
 
       try {
-        await installMutation.mutateAsync({
+        const installed = await installMutation.mutateAsync({
           skill,
           currentApp,
         });
-        toast.success(t("skills.installSuccess", { name: skill.name }), {
-          closeButton: true,
-        });
+        if (installed.apps.claude) {
+          offerReload(
+            t("skills.installSuccess", { name: skill.name }),
+            "skill",
+            installed.directory,
+          );
+        } else {
+          toast.success(t("skills.installSuccess", { name: skill.name }), {
+            closeButton: true,
+          });
+        }
       } catch (error) {
         const errorMessage =
           error instanceof Error ? error.message : String(error);
@@ -530,8 +552,14 @@ export const SkillsPage = forwardRef<SkillsPageHandle, SkillsPageProps>(
             {effectiveSource === "repos" ? (
               /* ===== 仓库模式 ===== */
               loading ? (
-                <div className="flex items-center justify-center h-64">
+                <div className="flex flex-col items-center justify-center h-64 gap-4">
                   <RefreshCw className="h-8 w-8 animate-spin text-muted-foreground" />
+                  {indexProgress && (
+                    <OperationProgressBar
+                      progress={indexProgress}
+                      className="max-w-sm"
+                    />
+                  )}
                 </div>
               ) : skills.length === 0 ? (
                 <div className="flex flex-col items-center justify-center h-64 text-center">
@@ -559,16 +587,24 @@ export const SkillsPage = forwardRef<SkillsPageHandle, SkillsPageProps>(
                   </p>
                 </div>
               ) : (
-                <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4">
-                  {filteredSkills.map((skill) => (
-                    <SkillCard
-                      key={skill.key}
-                      skill={skill}
-                      onInstall={handleInstall}
-                      onUninstall={handleUninstall}
+                <>
+                  {installProgress && (
+                    <OperationProgressBar
+                      progress={installProgress}
+                      className="mb-4"
                     />
-                  ))}
-                </div>
+                  )}
+                  <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4">
+                    {filteredSkills.map((skill) => (
+                      <SkillCard
+                        key={skill.key}
+                        skill={skill}
+                        onInstall={handleInstall}
+                        onUninstall={handleUninstall}
+                      />
+                    ))}
+                  </div>
+                </>
               )
             ) : (
               /* ===== skills.sh 模式 ===== */
@@ -35,6 +35,7 @@ import { SKILLS_APP_IDS } from "@/config/appConfig";
 import { AppCountBar } from "@/components/common/AppCountBar";
 import { AppToggleGroup } from "@/components/common/AppToggleGroup";
 import { ListItemRow } from "@/components/common/ListItemRow";
+import { useLiveReload } from "@/hooks/useLiveReload";
 import {
   Dialog,
   DialogContent,
@@ -101,6 +102,7 @@ const UnifiedSkillsPanel = React.forwardRef<
     isFetching: isCheckingUpdates,
   } = useCheckSkillUpdates();
   const updateSkillMutation = useUpdateSkill();
+  const { offerReload } = useLiveReload();
   const [isUpdatingAll, setIsUpdatingAll] = useState(false);
 
   const updatesMap = useMemo(() => {
@@ -248,9 +250,17 @@ const UnifiedSkillsPanel = React.forwardRef<
   const handleUpdateSkill = async (skill: InstalledSkill) => {
     try {
       const updated = await updateSkillMutation.mutateAsync(skill.id);
-      toast.success(t("skills.updateSuccess", { name: updated.name }), {
-        closeButton: true,
-      });
+      if (updated.apps.claude) {
+        offerReload(
+          t("skills.updateSuccess", { name: updated.name }),
+          "skill",
+          updated.directory,
+        );
+      } else {
+        toast.success(t("skills.updateSuccess", { name: updated.name }), {
+          closeButton: true,
+        });
+      }
     } catch (error) {
       toast.error(t("skills.updateFailed"), { description: String(error) });
     }
@@ -0,0 +1,248 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { Calculator, Loader2 } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "@/components/ui/table";
+import { cn } from "@/lib/utils";
+import { providersApi } from "@/lib/api";
+import { usageApi } from "@/lib/api/usage";
+import type { Provider } from "@/types";
+import type { AppTypeFilter, CostSimulation } from "@/types/usage";
+import { extractErrorMessage } from "@/utils/errorUtils";
+import { fmtUsd } from "./format";
+
+/** 走代理、有模型映射与倍率的应用 */
+const SIMULATION_APPS = ["claude", "codex", "gemini"] as const;
+type SimulationApp = (typeof SIMULATION_APPS)[number];
+
+interface CostSimulationPanelProps {
+  appType: AppTypeFilter;
+}
+
+function initialApp(appType: AppTypeFilter): SimulationApp {
+  return (SIMULATION_APPS as ReadonlyArray<string>).includes(appType)
+    ? (appType as SimulationApp)
+    : "claude";
+}
+
+export function CostSimulationPanel({ appType }: CostSimulationPanelProps) {
+  const { t } = useTranslation();
+  const [app, setApp] = useState<SimulationApp>(() => initialApp(appType));
+  const [providers, setProviders] = useState<Provider[]>([]);
+  const [providerId, setProviderId] = useState<string>("");
+  const [days, setDays] = useState("7");
+  const [result, setResult] = useState<CostSimulation | null>(null);
+  const [isRunning, setIsRunning] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setApp(initialApp(appType));
+  }, [appType]);
+
+  useEffect(() => {
+    setProviderId("");
+    setResult(null);
+    providersApi
+      .getAll(app)
+      .then((map) => setProviders(Object.values(map)))
+      .catch((e) => console.error("[CostSimulationPanel] Failed to load", e));
+  }, [app]);
+
+  const parsedDays = Number.parseInt(days, 10);
+  const canRun = providerId !== "" && parsedDays > 0 && !isRunning;
+
+  async function handleSimulate() {
+    try {
+      setIsRunning(true);
+      setError(null);
+      setResult(
+        await usageApi.simulateProviderCost(app, providerId, parsedDays),
+      );
+    } catch (e) {
+      setError(extractErrorMessage(e));
+    } finally {
+      setIsRunning(false);
+    }
+  }
+
+  const delta = result ? Number.parseFloat(result.deltaUsd) : 0;
+
+  return (
+    <div className="space-y-4 rounded-lg border border-border/50 bg-card/40 p-4 backdrop-blur-sm">
+      <div className="space-y-1">
+        <h4 className="text-sm font-medium">{t("usage.simulation.title")}</h4>
+        <p className="text-xs text-muted-foreground">
+          {t("usage.simulation.description")}
+        </p>
+      </div>
+
+      <div className="flex flex-wrap items-center gap-2">
+        <Select value={app} onValueChange={(v) => setApp(v as SimulationApp)}>
+          <SelectTrigger className="h-9 w-[110px] text-xs">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            {SIMULATION_APPS.map((item) => (
+              <SelectItem key={item} value={item}>
+                {t(`apps.${item}`)}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+        <Select value={providerId} onValueChange={setProviderId}>
+          <SelectTrigger className="h-9 w-[200px] text-xs [&>span]:min-w-0 [&>span]:truncate">
+            <SelectValue placeholder={t("usage.simulation.selectProvider")} />
+          </SelectTrigger>
+          <SelectContent className="max-w-[280px]">
+            {providers.map((provider) => (
+              <SelectItem key={provider.id} value={provider.id}>
+                {provider.name}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+        <div className="flex items-center gap-1 text-xs text-muted-foreground">
+          <Input
+            type="number"
+            min={1}
+            value={days}
+            onChange={(e) => setDays(e.target.value)}
+            className="h-9 w-[72px] text-xs"
+          />
+          {t("usage.simulation.days")}
+        </div>
+        <Button
+          type="button"
+          size="sm"
+          onClick={handleSimulate}
+          disabled={!canRun}
+        >
+          {isRunning ? (
+            <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+          ) : (
+            <Calculator className="mr-2 h-4 w-4" />
+          )}
+          {t("usage.simulation.run")}
+        </Button>
+      </div>
+
+      {error && (
+        <p className="text-xs text-red-600 dark:text-red-400">{error}</p>
+      )}
+
+      {result && (
+        <div className="space-y-3">
+          <div className="grid grid-cols-3 gap-2 text-xs">
+            <div className="rounded-md bg-muted/40 px-3 py-2">
+              <div className="text-muted-foreground">
+                {t("usage.simulation.actual")}
+              </div>
+              <div className="text-base font-semibold">
+                {fmtUsd(result.actualCostUsd, 4)}
+              </div>
+            </div>
+            <div className="rounded-md bg-muted/40 px-3 py-2">
+              <div className="text-muted-foreground">
+                {t("usage.simulation.simulated", {
+                  provider: result.providerName,
+                  multiplier: result.costMultiplier,
+                })}
+              </div>
+              <div className="text-base font-semibold">
+                {fmtUsd(result.simulatedCostUsd, 4)}
+              </div>
+            </div>
+            <div className="rounded-md bg-muted/40 px-3 py-2">
+              <div className="text-muted-foreground">
+                {t("usage.simulation.delta")}
+              </div>
+              <div
+                className={cn(
+                  "text-base font-semibold",
+                  delta < 0 && "text-emerald-600 dark:text-emerald-400",
+                  delta > 0 && "text-red-600 dark:text-red-400",
+                )}
+              >
+                {delta > 0 ? "+" : delta < 0 ? "-" : ""}
+                {fmtUsd(Math.abs(delta), 4)}
+              </div>
+            </div>
+          </div>
+
+          {result.rows.length === 0 ? (
+            <p className="text-xs text-muted-foreground">
+              {t("usage.simulation.empty", { days: result.days })}
+            </p>
+          ) : (
+            <Table>
+              <TableHeader>
+                <TableRow>
+                  <TableHead>{t("usage.simulation.requestedModel")}</TableHead>
+                  <TableHead>{t("usage.simulation.simulatedModel")}</TableHead>
+                  <TableHead className="text-right">
+                    {t("usage.requests")}
+                  </TableHead>
+                  <TableHead className="text-right">
+                    {t("usage.simulation.actual")}
+                  </TableHead>
+                  <TableHead className="text-right">
+                    {t("usage.simulation.simulatedShort")}
+                  </TableHead>
+                </TableRow>
+              </TableHeader>
+              <TableBody>
+                {result.rows.map((row) => (
+                  <TableRow key={`${row.requestedModel}|${row.actualModel}`}>
+                    <TableCell className="font-mono text-xs">
+                      {row.requestedModel}
+                    </TableCell>
+                    <TableCell className="font-mono text-xs">
+                      {row.simulatedModel}
+                      {!row.priced && (
+                        <span className="ml-2 text-amber-600 dark:text-amber-400">
+                          {t("usage.simulation.noPricing")}
+                        </span>
+                      )}
+                    </TableCell>
+                    <TableCell className="text-right">
+                      {row.requestCount.toLocaleString()}
+                    </TableCell>
+                    <TableCell className="text-right">
+                      {fmtUsd(row.actualCostUsd, 4)}
+                    </TableCell>
+                    <TableCell className="text-right">
+                      {fmtUsd(row.simulatedCostUsd, 4)}
+                    </TableCell>
+                  </TableRow>
+                ))}
+              </TableBody>
+            </Table>
+          )}
+
+          {result.unpricedModels.length > 0 && (
+            <p className="text-xs text-amber-600 dark:text-amber-400">
+              {t("usage.simulation.unpriced", {
+                models: result.unpricedModels.join(", "),
+              })}
+            </p>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,197 @@
+import { useState, useEffect } from "react";
+import { useTranslation } from "react-i18next";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Switch } from "@/components/ui/switch";
+import { Loader2, Play, Save } from "lucide-react";
+import { toast } from "sonner";
+import {
+  getSelfTestConfig,
+  getSelfTestReport,
+  isSelfTestEntryHealthy,
+  runSelfTestNow,
+  saveSelfTestConfig,
+  type SelfTestReport,
+} from "@/lib/api/connectivity-check";
+
+const clampHour = (val: string, defaultVal: number) => {
+  const n = parseInt(val);
+  return isNaN(n) ? defaultVal : Math.min(23, Math.max(0, n));
+};
+
+export function NightlySelfTestPanel() {
+  const { t } = useTranslation();
+  const [isLoading, setIsLoading] = useState(true);
+  const [isSaving, setIsSaving] = useState(false);
+  const [isRunning, setIsRunning] = useState(false);
+  const [enabled, setEnabled] = useState(false);
+  const [networkRecheck, setNetworkRecheck] = useState(true);
+  // 使用字符串状态以支持完全清空数字输入框
+  const [hours, setHours] = useState({ runHour: "4", summaryHour: "9" });
+  const [report, setReport] = useState<SelfTestReport | null>(null);
+
+  useEffect(() => {
+    void (async () => {
+      try {
+        const [config, lastReport] = await Promise.all([
+          getSelfTestConfig(),
+          getSelfTestReport(),
+        ]);
+        setEnabled(config.enabled);
+        setNetworkRecheck(config.networkRecheck);
+        setHours({
+          runHour: String(config.runHour),
+          summaryHour: String(config.summaryHour),
+        });
+        setReport(lastReport);
+      } catch (e) {
+        console.error("[NightlySelfTestPanel] Failed to load config", e);
+      } finally {
+        setIsLoading(false);
+      }
+    })();
+  }, []);
+
+  async function handleSave() {
+    try {
+      setIsSaving(true);
+      await saveSelfTestConfig({
+        enabled,
+        runHour: clampHour(hours.runHour, 4),
+        summaryHour: clampHour(hours.summaryHour, 9),
+        networkRecheck,
+      });
+      toast.success(t("streamCheck.configSaved"), { closeButton: true });
+    } catch (e) {
+      toast.error(t("streamCheck.configSaveFailed") + ": " + String(e));
+    } finally {
+      setIsSaving(false);
+    }
+  }
+
+  async function handleRunNow() {
+    try {
+      setIsRunning(true);
+      setReport(await runSelfTestNow());
+    } catch (e) {
+      toast.error(t("streamCheck.selfTest.runFailed", { error: String(e) }));
+    } finally {
+      setIsRunning(false);
+    }
+  }
+
+  if (isLoading) {
+    return (
+      <div className="flex items-center justify-center p-4">
+        <Loader2 className="h-6 w-6 animate-spin text-muted-foreground" />
+      </div>
+    );
+  }
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between gap-4">
+        <div className="space-y-1">
+          <h4 className="text-sm font-medium">
+            {t("streamCheck.selfTest.title")}
+          </h4>
+          <p className="text-xs text-muted-foreground">
+            {t("streamCheck.selfTest.description")}
+          </p>
+        </div>
+        <Switch checked={enabled} onCheckedChange={setEnabled} />
+      </div>
+
+      <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+        <div className="space-y-2">
+          <Label htmlFor="selfTestRunHour">
+            {t("streamCheck.selfTest.runHour")}
+          </Label>
+          <Input
+            id="selfTestRunHour"
+            type="number"
+            min={0}
+            max={23}
+            value={hours.runHour}
+            disabled={!enabled}
+            onChange={(e) => setHours({ ...hours, runHour: e.target.value })}
+          />
+        </div>
+        <div className="space-y-2">
+          <Label htmlFor="selfTestSummaryHour">
+            {t("streamCheck.selfTest.summaryHour")}
+          </Label>
+          <Input
+            id="selfTestSummaryHour"
+            type="number"
+            min={0}
+            max={23}
+            value={hours.summaryHour}
+            disabled={!enabled}
+            onChange={(e) =>
+              setHours({ ...hours, summaryHour: e.target.value })
+            }
+          />
+        </div>
+      </div>
+
+      <div className="flex items-center justify-between gap-4">
+        <div className="space-y-1">
+          <h4 className="text-sm font-medium">
+            {t("streamCheck.networkRecheck.title")}
+          </h4>
+          <p className="text-xs text-muted-foreground">
+            {t("streamCheck.networkRecheck.description")}
+          </p>
+        </div>
+        <Switch checked={networkRecheck} onCheckedChange={setNetworkRecheck} />
+      </div>
+
+      {report && (
+        <div className="rounded-md border border-border/50 p-3 space-y-1 text-xs">
+          <div className="text-muted-foreground">
+            {t("streamCheck.selfTest.lastRun", {
+              time: new Date(report.finishedAt * 1000).toLocaleString(),
+            })}
+          </div>
+          {report.apps.length === 0 ? (
+            <div>{t("streamCheck.selfTest.noTargets")}</div>
+          ) : (
+            report.apps.map((app) => (
+              <div
+                key={app.appType}
+                className={
+                  app.entries.every(isSelfTestEntryHealthy)
+                    ? "text-emerald-600 dark:text-emerald-400"
+                    : "text-red-600 dark:text-red-400"
+                }
+              >
+                {app.summary}
+              </div>
+            ))
+          )}
+        </div>
+      )}
+
+      <div className="flex justify-end gap-2">
+        <Button variant="outline" onClick={handleRunNow} disabled={isRunning}>
+          {isRunning ? (
+            <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+          ) : (
+            <Play className="mr-2 h-4 w-4" />
+          )}
+          {t("streamCheck.selfTest.runNow")}
+        </Button>
+        <Button onClick={handleSave} disabled={isSaving}>
+          {isSaving ? (
+            <Loader2 className="mr-2 h-4 w-4 animate-spin" />
+          ) : (
+            <Save className="mr-2 h-4 w-4" />
+          )}
+          {t("common.save")}
+        </Button>
+      </div>
+    </div>
+  );
+}
@@ -1,4 +1,6 @@
 import { useTranslation } from "react-i18next";
+import { Terminal } from "lucide-react";
+import { Button } from "@/components/ui/button";
 import {
   Dialog,
   DialogContent,
@@ -6,6 +8,8 @@ import {
   DialogTitle,
 } from "@/components/ui/dialog";
 import { useRequestDetail } from "@/lib/query/usage";
+import { usageApi } from "@/lib/api/usage";
+import { useCopyAsCurl } from "@/hooks/useCopyAsCurl";
 import { getFreshInputTokens, isUnpricedUsage } from "@/types/usage";
 
 interface RequestDetailPanelProps {
@@ -19,6 +23,7 @@ export function RequestDetailPanel({
 }: RequestDetailPanelProps) {
   const { t, i18n } = useTranslation();
   const { data: request, isLoading } = useRequestDetail(requestId);
+  const copyAsCurl = useCopyAsCurl();
   const dateLocale =
     i18n.language === "zh"
       ? "zh-CN"
@@ -60,8 +65,20 @@ export function RequestDetailPanel({
   return (
     <Dialog open onOpenChange={onClose}>
       <DialogContent className="max-w-2xl max-h-[80vh] overflow-y-auto">
-        <DialogHeader>
+        <DialogHeader className="flex-row items-center justify-between gap-2 pr-8">
           <DialogTitle>{t("usage.requestDetail", "请求详情")}</DialogTitle>
+          <Button
+            size="sm"
+            variant="outline"
+            onClick={() =>
+              copyAsCurl((maskKey) =>
+                usageApi.copyRequestAsCurl(request.requestId, maskKey),
+              )
+            }
+          >
+            <Terminal className="mr-1 h-4 w-4" />
+            {t("provider.copyAsCurl")}
+          </Button>
         </DialogHeader>
 
         <div className="space-y-4">
@@ -98,6 +115,14 @@ export function RequestDetailPanel({
                   <span className="ml-2 font-mono text-xs text-muted-foreground">
                     {request.providerId}
                   </span>
+                  {request.untrusted && (
+                    <span
+                      className="ml-2 rounded bg-red-500/10 px-1.5 py-0.5 text-[10px] font-medium text-red-600 dark:text-red-400"
+                      title={t("usage.untrustedTooltip")}
+                    >
+                      {t("usage.untrusted")}
+                    </span>
+                  )}
                 </dd>
               </div>
               <div>
@@ -122,6 +147,18 @@ export function RequestDetailPanel({
                       </dd>
                     </>
                   )}
+                {request.mappedModel &&
+                  request.mappedModel !== request.model &&
+                  request.mappedModel !== request.requestModel && (
+                    <>
+                      <dt className="mt-1 text-muted-foreground">
+                        {t("usage.mappedModel", "映射模型")}
+                      </dt>
+                      <dd className="font-mono text-xs">
+                        {request.mappedModel}
+                      </dd>
+                    </>
+                  )}
                 {request.pricingModel &&
                   request.pricingModel !== request.model && (
                     <>
@@ -305,6 +342,12 @@ export function RequestDetailPanel({
                 </dt>
                 <dd className="font-mono">{request.latencyMs}ms</dd>
               </div>
+              <div>
+                <dt className="text-muted-foreground">
+                  {t("usage.retries")}
+                </dt>
+                <dd className="font-mono">{request.retryCount}</dd>
+              </div>
             </dl>
           </div>
 
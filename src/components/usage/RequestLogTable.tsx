@@ -24,7 +24,7 @@ import {
   type LogFilters,
   type UsageRangeSelection,
 } from "@/types/usage";
-import { ChevronLeft, ChevronRight } from "lucide-react";
+import { ChevronLeft, ChevronRight, ShieldAlert } from "lucide-react";
 import { UsageDateRangePicker } from "./UsageDateRangePicker";
 import {
   fmtInt,
@@ -211,6 +211,14 @@ export function RequestLogTable({
                         </TableCell>
                         <TableCell className="text-center">
                           {log.providerName || t("usage.unknownProvider")}
+                          {log.untrusted && (
+                            <span
+                              className="ml-1 inline-flex align-middle text-red-500"
+                              title={t("usage.untrustedTooltip")}
+                            >
+                              <ShieldAlert className="h-3.5 w-3.5" />
+                            </span>
+                          )}
                         </TableCell>
                         <TableCell className="text-center font-mono text-xs max-w-[200px]">
                           <div
@@ -308,6 +316,16 @@ export function RequestLogTable({
                           >
                             {log.statusCode}
                           </span>
+                          {log.retryCount > 0 && (
+                            <div
+                              className="text-[11px] text-muted-foreground"
+                              title={t("usage.retryCountTooltip", {
+                                count: log.retryCount,
+                              })}
+                            >
+                              {t("usage.retryCount", { count: log.retryCount })}
+                            </div>
+                          )}
                         </TableCell>
                         <TableCell className="text-center text-xs text-muted-foreground">
                           {log.dataSource || "proxy"}
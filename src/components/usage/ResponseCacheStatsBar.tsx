@@ -0,0 +1,54 @@
+import { useTranslation } from "react-i18next";
+import { useQuery } from "@tanstack/react-query";
+import { DatabaseZap } from "lucide-react";
+import { settingsApi } from "@/lib/api/settings";
+import { usageKeys } from "@/lib/query/usage";
+
+interface ResponseCacheStatsBarProps {
+  refreshIntervalMs: number;
+}
+
+/**
+ * 代理响应缓存的命中统计（自程序启动起）；未开启且无记录时不显示。
+ */
+export function ResponseCacheStatsBar({
+  refreshIntervalMs,
+}: ResponseCacheStatsBarProps) {
+  const { t } = useTranslation();
+
+  const { data } = useQuery({
+    queryKey: [...usageKeys.all, "response-cache"],
+    queryFn: async () => {
+      const [config, stats] = await Promise.all([
+        settingsApi.getResponseCacheConfig(),
+        settingsApi.getResponseCacheStats(),
+      ]);
+      return { enabled: config.enabled, ...stats };
+    },
+    refetchInterval: refreshIntervalMs > 0 ? refreshIntervalMs : false,
+    refetchIntervalInBackground: false,
+  });
+
+  const lookups = (data?.hits ?? 0) + (data?.misses ?? 0);
+  if (!data || (!data.enabled && lookups === 0)) {
+    return null;
+  }
+
+  const hitRate = lookups > 0 ? Math.round((data.hits / lookups) * 100) : 0;
+
+  return (
+    <div className="flex items-center gap-3 text-xs text-muted-foreground bg-muted/30 rounded-lg px-4 py-2">
+      <DatabaseZap className="h-3.5 w-3.5 text-emerald-500" />
+      <span className="font-medium text-foreground/70">
+        {t("usage.responseCache.title")}
+      </span>
+      <span>
+        {t("usage.responseCache.summary", {
+          hits: data.hits.toLocaleString(),
+          misses: data.misses.toLocaleString(),
+          rate: hitRate,
+        })}
+      </span>
+    </div>
+  );
+}
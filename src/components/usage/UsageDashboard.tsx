@@ -1,10 +1,13 @@
 import { useEffect, useMemo, useState } from "react";
 import { useTranslation } from "react-i18next";
 import { UsageHero } from "./UsageHero";
+import { ResponseCacheStatsBar } from "./ResponseCacheStatsBar";
 import { UsageTrendChart } from "./UsageTrendChart";
 import { RequestLogTable } from "./RequestLogTable";
 import { ProviderStatsTable } from "./ProviderStatsTable";
 import { ModelStatsTable } from "./ModelStatsTable";
+import { WorkloadComparisonTable } from "./WorkloadComparisonTable";
+import { CostSimulationPanel } from "./CostSimulationPanel";
 import {
   KNOWN_APP_TYPES,
   type AppType,
@@ -20,7 +23,10 @@ import {
   Coins,
   LayoutGrid,
   DatabaseBackup,
+  Download,
   Loader2,
+  Calculator,
+  Scale,
 } from "lucide-react";
 import { ProviderIcon } from "@/components/ProviderIcon";
 import {
@@ -48,6 +54,14 @@ import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
 import { Button } from "@/components/ui/button";
 import { ConfirmDialog } from "@/components/ConfirmDialog";
 import { usageApi } from "@/lib/api/usage";
+import { settingsApi } from "@/lib/api/settings";
+import {
+  DropdownMenu,
+  DropdownMenuContent,
+  DropdownMenuItem,
+  DropdownMenuTrigger,
+} from "@/components/ui/dropdown-menu";
+import type { UsageExportFormat } from "@/types/usage";
 import { toast } from "sonner";
 
 const APP_FILTER_OPTIONS: AppTypeFilter[] = ["all", ...KNOWN_APP_TYPES];
@@ -196,6 +210,25 @@ export function UsageDashboard({
     return `${startStr} - ${endStr}`;
   }, [locale, range, resolvedRange.endDate, resolvedRange.startDate, t]);
 
+  const exportUsage = async (format: UsageExportFormat) => {
+    try {
+      const filePath = await settingsApi.saveFileDialog(
+        `cc-switch-usage.${format}`,
+      );
+      if (!filePath) return;
+      await usageApi.exportUsage(
+        format,
+        resolvedRange.startDate,
+        resolvedRange.endDate,
+        appType === "all" ? undefined : appType,
+        filePath,
+      );
+      toast.success(t("usage.export.success", { path: filePath }));
+    } catch (error) {
+      toast.error(t("usage.export.failed", { error: String(error) }));
+    }
+  };
+
   // 顶栏下拉的选项池：Provider 列表只跟应用/时间范围走（不受自身选中值影响），
   // 模型列表随所选 Provider 级联。两者都只列当前范围内真实有数据的条目。
   // refetchInterval 必须跟随面板的刷新设置——未筛选时这两个查询与统计表共享
@@ -363,6 +396,28 @@ export function UsageDashboard({
               triggerLabel={rangeLabel}
               onApply={(nextRange) => setRange(nextRange)}
             />
+
+            <DropdownMenu>
+              <DropdownMenuTrigger asChild>
+                <Button
+                  variant="outline"
+                  size="icon"
+                  className="h-9 w-9 shrink-0"
+                  title={t("usage.export.title")}
+                  aria-label={t("usage.export.title")}
+                >
+                  <Download className="h-3.5 w-3.5" />
+                </Button>
+              </DropdownMenuTrigger>
+              <DropdownMenuContent align="end">
+                <DropdownMenuItem onSelect={() => void exportUsage("csv")}>
+                  {t("usage.export.csv")}
+                </DropdownMenuItem>
+                <DropdownMenuItem onSelect={() => void exportUsage("json")}>
+                  {t("usage.export.json")}
+                </DropdownMenuItem>
+              </DropdownMenuContent>
+            </DropdownMenu>
           </div>
         </div>
       </div>
@@ -375,6 +430,8 @@ export function UsageDashboard({
         refreshIntervalMs={refreshIntervalMs}
       />
 
+      <ResponseCacheStatsBar refreshIntervalMs={refreshIntervalMs} />
+
       <UsageTrendChart
         range={range}
         rangeLabel={rangeLabel}
@@ -400,6 +457,14 @@ export function UsageDashboard({
                 <BarChart3 className="h-4 w-4" />
                 {t("usage.modelStats")}
               </TabsTrigger>
+              <TabsTrigger value="workloads" className="gap-2">
+                <Scale className="h-4 w-4" />
+                {t("usage.workload.tab")}
+              </TabsTrigger>
+              <TabsTrigger value="simulate" className="gap-2">
+                <Calculator className="h-4 w-4" />
+                {t("usage.simulation.tab")}
+              </TabsTrigger>
             </TabsList>
           </div>
 
@@ -439,6 +504,18 @@ export function UsageDashboard({
                 refreshIntervalMs={refreshIntervalMs}
               />
             </TabsContent>
+
+            <TabsContent value="workloads" className="mt-0">
+              <WorkloadComparisonTable
+                range={range}
+                appType={appType}
+                refreshIntervalMs={refreshIntervalMs}
+              />
+            </TabsContent>
+
+            <TabsContent value="simulate" className="mt-0">
+              <CostSimulationPanel appType={appType} />
+            </TabsContent>
           </motion.div>
         </Tabs>
       </div>
@@ -0,0 +1,146 @@
+import { Fragment, useMemo } from "react";
+import { useTranslation } from "react-i18next";
+import {
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeader,
+  TableRow,
+} from "@/components/ui/table";
+import { useWorkloadComparison } from "@/lib/query/usage";
+import { cn } from "@/lib/utils";
+import { fmtUsd } from "./format";
+import type { UsageRangeSelection, WorkloadProviderStats } from "@/types/usage";
+
+interface WorkloadComparisonTableProps {
+  range: UsageRangeSelection;
+  appType?: string;
+  refreshIntervalMs: number;
+}
+
+/**
+ * 同负载供应商对比：按「模型档位 + 请求体量」分组，只比较同组内的供应商，
+ * 每组中延迟最低、单次成本最低的一项高亮
+ */
+export function WorkloadComparisonTable({
+  range,
+  appType,
+  refreshIntervalMs,
+}: WorkloadComparisonTableProps) {
+  const { t } = useTranslation();
+  const { data: stats, isLoading } = useWorkloadComparison(range, appType, {
+    refetchInterval: refreshIntervalMs > 0 ? refreshIntervalMs : false,
+  });
+
+  const groups = useMemo(() => {
+    const byWorkload = new Map<string, WorkloadProviderStats[]>();
+    for (const stat of stats ?? []) {
+      const rows = byWorkload.get(stat.workload) ?? [];
+      rows.push(stat);
+      byWorkload.set(stat.workload, rows);
+    }
+    return Array.from(byWorkload.values()).map((rows) => ({
+      rows,
+      fastest: Math.min(...rows.map((row) => row.avgLatencyMs)),
+      cheapest: Math.min(...rows.map((row) => Number(row.avgCostPerRequest))),
+    }));
+  }, [stats]);
+
+  if (isLoading) {
+    return <div className="h-[400px] animate-pulse rounded bg-gray-100" />;
+  }
+
+  return (
+    <div className="space-y-2">
+      <p className="text-xs text-muted-foreground">
+        {t("usage.workload.description")}
+      </p>
+      <div className="rounded-lg border border-border/50 bg-card/40 backdrop-blur-sm overflow-hidden">
+        <Table>
+          <TableHeader>
+            <TableRow>
+              <TableHead>{t("usage.provider", "Provider")}</TableHead>
+              <TableHead className="text-right">
+                {t("usage.requests", "请求数")}
+              </TableHead>
+              <TableHead className="text-right">
+                {t("usage.avgLatency", "平均延迟")}
+              </TableHead>
+              <TableHead className="text-right">
+                {t("usage.workload.avgFirstToken")}
+              </TableHead>
+              <TableHead className="text-right">
+                {t("usage.workload.avgCost")}
+              </TableHead>
+              <TableHead className="text-right">
+                {t("usage.successRate", "成功率")}
+              </TableHead>
+            </TableRow>
+          </TableHeader>
+          <TableBody>
+            {groups.length === 0 ? (
+              <TableRow>
+                <TableCell
+                  colSpan={6}
+                  className="text-center text-muted-foreground"
+                >
+                  {t("usage.workload.empty")}
+                </TableCell>
+              </TableRow>
+            ) : (
+              groups.map(({ rows, fastest, cheapest }) => (
+                <Fragment key={rows[0].workload}>
+                  <TableRow className="bg-muted/40 hover:bg-muted/40">
+                    <TableCell colSpan={6} className="text-xs font-medium">
+                      {t("usage.workload.group", {
+                        model: rows[0].modelClass,
+                        size: t(`usage.workload.bucket.${rows[0].sizeBucket}`),
+                      })}
+                    </TableCell>
+                  </TableRow>
+                  {rows.map((stat) => (
+                    <TableRow key={`${stat.workload}-${stat.providerId}`}>
+                      <TableCell className="font-medium">
+                        {stat.providerName}
+                      </TableCell>
+                      <TableCell className="text-right">
+                        {stat.requestCount.toLocaleString()}
+                      </TableCell>
+                      <TableCell
+                        className={cn(
+                          "text-right",
+                          stat.avgLatencyMs === fastest &&
+                            "font-semibold text-green-600 dark:text-green-400",
+                        )}
+                      >
+                        {stat.avgLatencyMs}ms
+                      </TableCell>
+                      <TableCell className="text-right">
+                        {stat.avgFirstTokenMs != null
+                          ? `${stat.avgFirstTokenMs}ms`
+                          : "-"}
+                      </TableCell>
+                      <TableCell
+                        className={cn(
+                          "text-right",
+                          Number(stat.avgCostPerRequest) === cheapest &&
+                            "font-semibold text-green-600 dark:text-green-400",
+                        )}
+                      >
+                        {fmtUsd(stat.avgCostPerRequest, 4)}
+                      </TableCell>
+                      <TableCell className="text-right">
+                        {stat.successRate.toFixed(1)}%
+                      </TableCell>
+                    </TableRow>
+                  ))}
+                </Fragment>
+              ))
+            )}
+          </TableBody>
+        </Table>
+      </div>
+    </div>
+  );
+}
@@ -1431,4 +1431,17 @@ export const providerPresets: ProviderPreset[] = [
     icon: "aws",
     iconColor: "#FF9900",
   },
+  {
+    // 内置模拟供应商：由本地代理直接应答，不消耗 token，需开启本地代理
+    name: "Mock (Demo)",
+    nameKey: "providerForm.presets.mock",
+    websiteUrl: "",
+    settingsConfig: {
+      env: {
+        ANTHROPIC_BASE_URL: "mock://echo",
+        ANTHROPIC_AUTH_TOKEN: "mock",
+      },
+    },
+    category: "custom",
+  },
 ];
@@ -8,6 +8,7 @@ import React, {
 } from "react";
 import type { UpdateInfo } from "../lib/updater";
 import { checkForUpdate } from "../lib/updater";
+import { settingsApi } from "../lib/api";
 
 interface UpdateContextValue {
   // 更新状态
@@ -118,8 +119,12 @@ export function UpdateProvider({ children }: { children: React.ReactNode }) {
   // 应用启动时自动检查更新
   useEffect(() => {
     // 延迟1秒后检查，避免影响启动体验
+    // 离线模式下不自动检查（手动检查仍可用）
     const timer = setTimeout(() => {
-      checkUpdate().catch(console.error);
+      settingsApi
+        .get()
+        .then((settings) => (settings.offlineMode ? false : checkUpdate()))
+        .catch(console.error);
     }, 1000);
 
     return () => clearTimeout(timer);
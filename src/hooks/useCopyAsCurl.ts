@@ -0,0 +1,45 @@
+import { useCallback } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { copyText } from "@/lib/clipboard";
+import { extractErrorMessage } from "@/utils/errorUtils";
+
+/**
+ * 复制为 curl：默认复制遮蔽密钥的版本（便于贴到 issue），
+ * toast 中提供「包含密钥」操作复制可直接运行的完整命令。
+ */
+export function useCopyAsCurl() {
+  const { t } = useTranslation();
+
+  return useCallback(
+    async (generate: (maskKey: boolean) => Promise<string>) => {
+      try {
+        await copyText(await generate(true));
+        toast.success(t("provider.copyAsCurlSuccess"), {
+          action: {
+            label: t("provider.copyAsCurlIncludeKey"),
+            onClick: async () => {
+              try {
+                await copyText(await generate(false));
+                toast.success(t("provider.copyAsCurlWithKeySuccess"));
+              } catch (error) {
+                toast.error(
+                  t("provider.copyAsCurlFailed", {
+                    error: extractErrorMessage(error),
+                  }),
+                );
+              }
+            },
+          },
+        });
+      } catch (error) {
+        toast.error(
+          t("provider.copyAsCurlFailed", {
+            error: extractErrorMessage(error),
+          }),
+        );
+      }
+    },
+    [t],
+  );
+}
@@ -12,34 +12,17 @@ import { toast } from "sonner";
 import { useTranslation } from "react-i18next";
 import type { Provider } from "@/types";
 import { providersApi, type AppId } from "@/lib/api";
+import { compareProviderOrder } from "@/utils/providerOrder";
 
 export function useDragSort(providers: Record<string, Provider>, appId: AppId) {
   const queryClient = useQueryClient();
-  const { t, i18n } = useTranslation();
+  const { t } = useTranslation();
 
-  const sortedProviders = useMemo(() => {
-    const locale =
-      i18n.language === "zh"
-        ? "zh-CN"
-        : i18n.language === "zh-TW"
-          ? "zh-TW"
-          : "en-US";
-    return Object.values(providers).sort((a, b) => {
-      if (a.sortIndex !== undefined && b.sortIndex !== undefined) {
-        return a.sortIndex - b.sortIndex;
-      }
-      if (a.sortIndex !== undefined) return -1;
-      if (b.sortIndex !== undefined) return 1;
-
-      const timeA = a.createdAt ?? 0;
-      const timeB = b.createdAt ?? 0;
-      if (timeA && timeB && timeA !== timeB) {
-        return timeA - timeB;
-      }
-
-      return a.name.localeCompare(b.name, locale);
-    });
-  }, [providers, i18n.language]);
+  // 与后端及 CLI 编号（`cc-switch provider list`）保持同一顺序
+  const sortedProviders = useMemo(
+    () => Object.values(providers).sort(compareProviderOrder),
+    [providers],
+  );
 
   const sensors = useSensors(
     useSensor(PointerSensor, {
@@ -0,0 +1,104 @@
+import { useCallback } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  sessionsApi,
+  type LiveReloadReport,
+  type ReloadKind,
+} from "@/lib/api/sessions";
+
+/** 热加载需要会话进入下一轮对话，稍后再检查一次 */
+const RECHECK_DELAY_MS = 15000;
+
+/**
+ * 安装 / 更新 Skill、启用 Prompt 后，提示把变更推送到正在运行的 Claude 会话，
+ * 并报告会话是否已加载。
+ */
+export function useLiveReload() {
+  const { t } = useTranslation();
+
+  const report = useCallback(
+    (result: LiveReloadReport) => {
+      const count = (status: string) =>
+        result.sessions.filter((session) => session.status === status).length;
+      const loaded = count("loaded") + count("pickedUp");
+      const pending = count("pending");
+      const restart = result.sessions.filter(
+        (session) => session.status === "restartRequired",
+      );
+
+      if (result.sessions.length === 0) {
+        toast.info(t("liveReload.noSessions"), { closeButton: true });
+        return;
+      }
+      if (restart.length > 0) {
+        toast.warning(
+          t("liveReload.restartRequired", { count: restart.length }),
+          {
+            description: restart
+              .map((session) => session.resumeCommand)
+              .filter(Boolean)
+              .join("\n"),
+            duration: 15000,
+            closeButton: true,
+          },
+        );
+      }
+      if (pending > 0) {
+        toast.info(t("liveReload.pending", { count: pending }), {
+          closeButton: true,
+        });
+      }
+      if (loaded > 0) {
+        toast.success(t("liveReload.loaded", { count: loaded }), {
+          closeButton: true,
+        });
+      }
+    },
+    [t],
+  );
+
+  const reload = useCallback(
+    async (kind: ReloadKind, name: string) => {
+      try {
+        const result = await sessionsApi.triggerLiveReload(kind, name);
+        report(result);
+        if (!result.sessions.some((session) => session.status === "pending")) {
+          return;
+        }
+        setTimeout(() => {
+          sessionsApi
+            .checkLiveReload(kind, name, result.triggeredAt)
+            .then((next) => {
+              if (next.sessions.some((s) => s.status === "pickedUp")) {
+                report(next);
+              }
+            })
+            .catch((error) =>
+              console.error("Failed to check live reload:", error),
+            );
+        }, RECHECK_DELAY_MS);
+      } catch (error) {
+        toast.error(t("liveReload.failed"), { description: String(error) });
+      }
+    },
+    [report, t],
+  );
+
+  /** 在成功提示中附带"推送到运行中的会话"按钮 */
+  const offerReload = useCallback(
+    (message: string, kind: ReloadKind, name: string) => {
+      toast.success(message, {
+        closeButton: true,
+        duration: 10000,
+        action: {
+          label: t("liveReload.action"),
+          onClick: () => void reload(kind, name),
+        },
+      });
+    },
+    [reload, t],
+  );
+
+  return { reload, offerReload };
+}
@@ -0,0 +1,40 @@
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  NETWORK_RECHECK_EVENT,
+  isSelfTestEntryHealthy,
+  type NetworkRecheckReport,
+} from "@/lib/api/connectivity-check";
+import { useTauriEvent } from "./useTauriEvent";
+
+/**
+ * 网络变化（VPN 开关、切换 Wi-Fi 或 `cc-switch network-changed`）后，
+ * 后端重新检查当前供应商与故障转移队列并 emit `network-recheck`，这里弹出摘要。
+ */
+export function useNetworkRecheck() {
+  const { t } = useTranslation();
+
+  useTauriEvent<NetworkRecheckReport>(NETWORK_RECHECK_EVENT, (report) => {
+    if (report.apps.length === 0) return;
+    const failing = report.apps
+      .flatMap((app) => app.entries)
+      .filter((entry) => !isSelfTestEntryHealthy(entry)).length;
+    const title =
+      failing > 0
+        ? t("streamCheck.networkRecheck.summaryFailing", { failing })
+        : t("streamCheck.networkRecheck.summaryOk");
+    const options = {
+      description: [
+        t(`streamCheck.networkRecheck.reason.${report.reason}`),
+        ...report.apps.map((app) => app.summary),
+      ].join("\n"),
+      duration: failing > 0 ? Infinity : 8000,
+      closeButton: true,
+    };
+    if (failing > 0) {
+      toast.warning(title, options);
+    } else {
+      toast.success(title, options);
+    }
+  });
+}
@@ -0,0 +1,36 @@
+import { useCallback, useState } from "react";
+import { useTauriEvent } from "./useTauriEvent";
+import {
+  OPERATION_PROGRESS_EVENT,
+  isProgressFinished,
+  type OperationProgress,
+} from "@/lib/api/progress";
+
+/**
+ * 跟踪指定类型操作的最新进度
+ *
+ * 返回最近一次仍在进行中的操作进度；操作结束（done / failed）后返回 null，
+ * 调用方据此在进度条与原有内容之间切换。
+ */
+export function useOperationProgress(
+  operations: readonly string[],
+): OperationProgress | null {
+  const [progress, setProgress] = useState<OperationProgress | null>(null);
+  const key = operations.join(",");
+
+  const handler = useCallback(
+    (event: OperationProgress) => {
+      if (!key.split(",").includes(event.operation)) return;
+      setProgress((current) => {
+        if (isProgressFinished(event)) {
+          return current?.id === event.id ? null : current;
+        }
+        return event;
+      });
+    },
+    [key],
+  );
+
+  useTauriEvent<OperationProgress>(OPERATION_PROGRESS_EVENT, handler);
+  return progress;
+}
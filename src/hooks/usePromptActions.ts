@@ -2,9 +2,11 @@ import { useState, useCallback } from "react";
 import { useTranslation } from "react-i18next";
 import { toast } from "sonner";
 import { promptsApi, type Prompt, type AppId } from "@/lib/api";
+import { useLiveReload } from "@/hooks/useLiveReload";
 
 export function usePromptActions(appId: AppId) {
   const { t } = useTranslation();
+  const { offerReload } = useLiveReload();
   const [prompts, setPrompts] = useState<Record<string, Prompt>>({});
   const [loading, setLoading] = useState(false);
   const [currentFileContent, setCurrentFileContent] = useState<string | null>(
@@ -59,18 +61,30 @@ export function usePromptActions(appId: AppId) {
     [appId, reload, t],
   );
 
+  // Claude 的 CLAUDE.md 只在会话启动时读取，启用后提示推送到运行中的会话
+  const notifyEnabled = useCallback(
+    (id: string) => {
+      if (appId === "claude") {
+        offerReload(t("prompts.enableSuccess"), "prompt", id);
+      } else {
+        toast.success(t("prompts.enableSuccess"), { closeButton: true });
+      }
+    },
+    [appId, offerReload, t],
+  );
+
   const enablePrompt = useCallback(
     async (id: string) => {
       try {
         await promptsApi.enablePrompt(appId, id);
         await reload();
-        toast.success(t("prompts.enableSuccess"), { closeButton: true });
+        notifyEnabled(id);
       } catch (error) {
         toast.error(t("prompts.enableFailed"));
         throw error;
       }
     },
-    [appId, reload, t],
+    [appId, notifyEnabled, reload, t],
   );
 
   const toggleEnabled = useCallback(
@@ -104,7 +118,7 @@ export function usePromptActions(appId: AppId) {
       try {
         if (enabled) {
           await promptsApi.enablePrompt(appId, id);
-          toast.success(t("prompts.enableSuccess"), { closeButton: true });
+          notifyEnabled(id);
         } else {
           // 禁用提示词 - 需要后端支持
           await promptsApi.upsertPrompt(appId, id, {
@@ -123,7 +137,7 @@ export function usePromptActions(appId: AppId) {
         throw error;
       }
     },
-    [appId, prompts, reload, t],
+    [appId, notifyEnabled, prompts, reload, t],
   );
 
   const importFromFile = useCallback(async () => {
@@ -0,0 +1,33 @@
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  PROMPT_INTEGRITY_ALERT_EVENT,
+  type PromptIntegritySignal,
+} from "@/lib/api/providers";
+import { useTauriEvent } from "./useTauriEvent";
+
+/**
+ * 中转系统提示词检测：代理发现某供应商计费的输入 token 与实际发送内容长期不符时，
+ * 后端 emit `provider-prompt-integrity-alert`，这里提示一次（状态变化后才会再次提示）。
+ */
+export function usePromptIntegrityAlerts() {
+  const { t } = useTranslation();
+
+  useTauriEvent<PromptIntegritySignal>(
+    PROMPT_INTEGRITY_ALERT_EVENT,
+    (signal) => {
+      toast.warning(
+        t("provider.promptIntegrity.alertTitle", { name: signal.providerName }),
+        {
+          description: t(`provider.promptIntegrity.${signal.status}`, {
+            ratio: signal.medianRatio.toFixed(2),
+            tokens: Math.abs(signal.medianExtraTokens).toLocaleString(),
+            samples: signal.samples,
+          }),
+          duration: Infinity,
+          closeButton: true,
+        },
+      );
+    },
+  );
+}
@@ -0,0 +1,48 @@
+import { useQueryClient } from "@tanstack/react-query";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { usageKeys } from "@/lib/query/usage";
+import {
+  PROVIDER_BUDGET_ALERT_EVENT,
+  type ProviderBudgetAlert,
+} from "@/types/usage";
+import { useTauriEvent } from "./useTauriEvent";
+
+/**
+ * 供应商预算提醒：代理记录用量后首次跨过 80% / 100% 时，后端 emit
+ * `provider-budget-alert`，这里弹出提示并刷新该供应商的额度查询。
+ */
+export function useProviderBudgetAlerts() {
+  const { t } = useTranslation();
+  const queryClient = useQueryClient();
+
+  useTauriEvent<ProviderBudgetAlert>(PROVIDER_BUDGET_ALERT_EVENT, (alert) => {
+    void queryClient.invalidateQueries({
+      queryKey: usageKeys.limits(alert.providerId, alert.appType),
+    });
+
+    const format = (value: number) =>
+      alert.metric === "usd"
+        ? `$${value.toFixed(2)}`
+        : Math.round(value).toLocaleString();
+    const params = {
+      name: alert.providerName,
+      period: t(`provider.budget.period.${alert.period}`),
+      used: format(alert.used),
+      limit: format(alert.limit),
+    };
+    if (alert.level === "exceeded") {
+      toast.error(t("provider.budget.alertExceeded", params), {
+        description: alert.hardLimit
+          ? t("provider.budget.alertHardLimit")
+          : undefined,
+        duration: Infinity,
+        closeButton: true,
+      });
+    } else {
+      toast.warning(t("provider.budget.alertWarning", params), {
+        closeButton: true,
+      });
+    }
+  });
+}
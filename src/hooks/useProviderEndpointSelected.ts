@@ -0,0 +1,34 @@
+import { useQueryClient } from "@tanstack/react-query";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  PROVIDER_ENDPOINT_SELECTED_EVENT,
+  type ProviderEndpointSelected,
+} from "@/types/usage";
+import { useTauriEvent } from "./useTauriEvent";
+
+/**
+ * 候选端点池：后端测速后切换到更快的端点时 emit `provider-endpoint-selected`，
+ * 这里刷新供应商列表并提示新端点。
+ */
+export function useProviderEndpointSelected() {
+  const { t } = useTranslation();
+  const queryClient = useQueryClient();
+
+  useTauriEvent<ProviderEndpointSelected>(
+    PROVIDER_ENDPOINT_SELECTED_EVENT,
+    (event) => {
+      void queryClient.invalidateQueries({
+        queryKey: ["providers", event.appType],
+      });
+      toast.info(
+        t("provider.endpointPool.switched", {
+          name: event.providerName,
+          url: event.url,
+          latency: event.latencyMs,
+        }),
+        { description: event.previousUrl, closeButton: true },
+      );
+    },
+  );
+}
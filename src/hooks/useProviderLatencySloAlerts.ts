@@ -0,0 +1,41 @@
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  PROVIDER_LATENCY_SLO_EVENT,
+  type ProviderLatencySloAlert,
+} from "@/types/usage";
+import { useTauriEvent } from "./useTauriEvent";
+
+/**
+ * 首字延迟 SLO：滚动窗口内的 TTFB 百分位超过阈值或恢复时，后端 emit
+ * `provider-latency-slo`，这里提示一次（状态变化后才会再次提示）。
+ */
+export function useProviderLatencySloAlerts() {
+  const { t } = useTranslation();
+
+  useTauriEvent<ProviderLatencySloAlert>(
+    PROVIDER_LATENCY_SLO_EVENT,
+    (alert) => {
+      const params = {
+        name: alert.providerName,
+        percentile: alert.percentile,
+        observed: alert.observedMs,
+        threshold: alert.thresholdMs,
+        minutes: alert.windowMinutes,
+      };
+      if (alert.violated) {
+        toast.warning(t("provider.latencySlo.alertViolated", params), {
+          description: alert.demoted
+            ? t("provider.latencySlo.alertDemoted")
+            : undefined,
+          duration: Infinity,
+          closeButton: true,
+        });
+      } else {
+        toast.success(t("provider.latencySlo.alertRecovered", params), {
+          closeButton: true,
+        });
+      }
+    },
+  );
+}
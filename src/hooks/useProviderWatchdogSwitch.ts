@@ -0,0 +1,38 @@
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  PROVIDER_WATCHDOG_SWITCH_EVENT,
+  type ProviderWatchdogSwitch,
+} from "@/types/usage";
+import { useTauriEvent } from "./useTauriEvent";
+
+/**
+ * 看门狗自动切换：当前供应商持续返回鉴权 / 限流错误被切走、或冷却后切回时，
+ * 后端 emit `provider-watchdog-switch`（列表刷新由 `provider-switched` 处理）。
+ */
+export function useProviderWatchdogSwitch() {
+  const { t } = useTranslation();
+
+  useTauriEvent<ProviderWatchdogSwitch>(
+    PROVIDER_WATCHDOG_SWITCH_EVENT,
+    (event) => {
+      const params = {
+        app: event.appType,
+        from: event.fromProviderName,
+        to: event.toProviderName,
+        failures: event.failures,
+        requests: event.requests,
+      };
+      if (event.reason === "failing") {
+        toast.warning(t("proxy.watchdog.switchedAway", params), {
+          duration: Infinity,
+          closeButton: true,
+        });
+      } else {
+        toast.success(t("proxy.watchdog.switchedBack", params), {
+          closeButton: true,
+        });
+      }
+    },
+  );
+}
@@ -0,0 +1,53 @@
+import { useEffect } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  SELF_TEST_SUMMARY_EVENT,
+  isSelfTestEntryHealthy,
+  takePendingSelfTestSummary,
+  type SelfTestReport,
+} from "@/lib/api/connectivity-check";
+import { useTauriEvent } from "./useTauriEvent";
+
+/**
+ * 夜间自检的早晨摘要：后端到提醒时间后 emit `self-test-summary`；
+ * 若当时窗口未打开，启动时通过 `take_pending_self_test_summary` 补弹一次。
+ */
+export function useSelfTestSummary() {
+  const { t } = useTranslation();
+
+  const showSummary = (report: SelfTestReport) => {
+    if (report.apps.length === 0) return;
+    const failing = report.apps
+      .flatMap((app) => app.entries)
+      .filter((entry) => !isSelfTestEntryHealthy(entry)).length;
+    const description = report.apps.map((app) => app.summary).join("\n");
+    const options = {
+      description,
+      duration: failing > 0 ? Infinity : 10000,
+      closeButton: true,
+    };
+    if (failing > 0) {
+      toast.warning(
+        t("streamCheck.selfTest.summaryFailing", { failing }),
+        options,
+      );
+    } else {
+      toast.success(t("streamCheck.selfTest.summaryOk"), options);
+    }
+  };
+
+  useTauriEvent<SelfTestReport>(SELF_TEST_SUMMARY_EVENT, showSummary);
+
+  useEffect(() => {
+    void takePendingSelfTestSummary()
+      .then((report) => {
+        if (report) showSummary(report);
+      })
+      .catch((error) => {
+        console.error("[SelfTest] Failed to load pending summary", error);
+      });
+    // 只在启动时补弹一次
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+}
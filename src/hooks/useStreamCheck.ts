@@ -3,6 +3,7 @@ import { toast } from "sonner";
 import { useTranslation } from "react-i18next";
 import {
   streamCheckProvider,
+  type ConnectionDiagnosis,
   type StreamCheckResult,
 } from "@/lib/api/connectivity-check";
 import type { AppId } from "@/lib/api";
@@ -18,6 +19,22 @@ export function useStreamCheck(appId: AppId) {
   const { t } = useTranslation();
   const [checkingIds, setCheckingIds] = useState<Set<string>>(new Set());
 
+  /** 分层诊断轨迹，例如 "DNS ✓ 12ms → TCP ✓ 40ms → TLS ✗ 310ms" */
+  const formatDiagnosis = useCallback(
+    (diagnosis: ConnectionDiagnosis) => {
+      const trail = diagnosis.steps
+        .map(
+          (step) =>
+            `${t(`streamCheck.diag.${step.stage}`)} ${step.ok ? "✓" : "✗"} ${step.durationMs}ms`,
+        )
+        .join(" → ");
+      return diagnosis.viaProxy
+        ? `${trail}\n${t("streamCheck.diag.viaProxy", { proxy: diagnosis.viaProxy })}`
+        : trail;
+    },
+    [t],
+  );
+
   const checkProvider = useCallback(
     async (
       providerId: string,
@@ -54,10 +71,14 @@ export function useStreamCheck(appId: AppId) {
               defaultValue: `${providerName} 无法连通: ${result.message}`,
             }),
             {
-              description: t("streamCheck.unreachableHint", {
-                defaultValue:
-                  "无法建立连接（DNS / 连接 / TLS / 超时）。请检查 base_url 与网络。",
-              }),
+              description: result.diagnostics
+                ? formatDiagnosis(result.diagnostics)
+                : result.errorCategory
+                  ? t(`errorClass.${result.errorCategory}`)
+                  : t("streamCheck.unreachableHint", {
+                      defaultValue:
+                        "无法建立连接（DNS / 连接 / TLS / 超时）。请检查 base_url 与网络。",
+                    }),
               duration: 8000,
               closeButton: true,
             },
@@ -82,7 +103,7 @@ export function useStreamCheck(appId: AppId) {
         });
       }
     },
-    [appId, t],
+    [appId, formatDiagnosis, t],
   );
 
   const isChecking = useCallback(
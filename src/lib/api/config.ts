@@ -98,3 +98,67 @@ export async function extractCommonConfigSnippet(
 
   return invoke<string>("extract_common_config_snippet", args);
 }
+
+export type CommonConfigMigrationApp = "claude" | "codex" | "gemini";
+
+export interface CommonConfigMigrationReport {
+  appType: CommonConfigMigrationApp;
+  flaggedKeys: string[];
+  movedSnippet: string;
+  sharedSnippet: string;
+  providers: { id: string; name: string }[];
+  applied: boolean;
+  undoAvailableSince: number | null;
+}
+
+/**
+ * 分析通用配置片段中被误共享的供应商专属键（模型、令牌、上下文窗口等）
+ * @param appType - 应用类型（claude/codex/gemini）
+ * @returns 迁移报告（不做任何修改）
+ */
+export async function analyzeCommonConfigMigration(
+  appType: CommonConfigMigrationApp,
+): Promise<CommonConfigMigrationReport> {
+  return invoke("analyze_common_config_migration", { appType });
+}
+
+/**
+ * 把误共享的键迁入各供应商自身配置，片段只保留可共享部分
+ */
+export async function applyCommonConfigMigration(
+  appType: CommonConfigMigrationApp,
+): Promise<CommonConfigMigrationReport> {
+  return invoke("apply_common_config_migration", { appType });
+}
+
+/**
+ * 撤销最近一次通用配置迁移
+ */
+export async function undoCommonConfigMigration(
+  appType: CommonConfigMigrationApp,
+): Promise<CommonConfigMigrationReport> {
+  return invoke("undo_common_config_migration", { appType });
+}
+
+export type GeminiDrift =
+  | { kind: "staleTempFile"; path: string }
+  | { kind: "invalidSettingsJson"; error: string }
+  | { kind: "invalidEnv"; error: string }
+  | { kind: "authTypeMismatch"; expected: string; actual: string | null };
+
+export interface GeminiRepairReport {
+  drifts: GeminiDrift[];
+  repaired: boolean;
+  backups: string[];
+}
+
+/**
+ * 检查并修复 Gemini .env 与 settings.json 之间的漂移
+ * @param dryRun - 为 true 时只检查不修改
+ * @returns 发现的漂移、是否已修复以及备份文件路径
+ */
+export async function repairGeminiConfig(
+  dryRun = false,
+): Promise<GeminiRepairReport> {
+  return invoke<GeminiRepairReport>("repair_gemini_config", { dryRun });
+}
@@ -6,6 +6,15 @@ import type { AppId } from "./types";
 
 export type HealthStatus = "operational" | "degraded" | "failed";
 
+/** 统一 HTTP 错误分类（与代理故障转移、端点测速共用） */
+export type HttpErrorClass =
+  | "auth"
+  | "quota"
+  | "network"
+  | "upstream"
+  | "transform"
+  | "client";
+
 export interface StreamCheckConfig {
   /** 单次探测超时（秒） */
   timeoutSecs: number;
@@ -15,6 +24,25 @@ export interface StreamCheckConfig {
   degradedThresholdMs: number;
 }
 
+/** 分层诊断层级 */
+export type DiagStage = "dns" | "tcp" | "tls" | "http";
+
+export interface DiagStep {
+  stage: DiagStage;
+  ok: boolean;
+  durationMs: number;
+  detail: string;
+}
+
+/** 网络层失败时的分层诊断（DNS → TCP → TLS → HTTP HEAD） */
+export interface ConnectionDiagnosis {
+  host: string;
+  port: number;
+  steps: DiagStep[];
+  /** 全局出站代理；存在时前三层不代表真实请求路径 */
+  viaProxy?: string;
+}
+
 export interface StreamCheckResult {
   status: HealthStatus;
   success: boolean;
@@ -23,6 +51,8 @@ export interface StreamCheckResult {
   httpStatus?: number;
   testedAt: number;
   retryCount: number;
+  errorCategory?: HttpErrorClass;
+  diagnostics?: ConnectionDiagnosis;
 }
 
 // ===== 连通性检查 API =====
@@ -62,3 +92,91 @@ export async function saveStreamCheckConfig(
 ): Promise<void> {
   return invoke("save_stream_check_config", { config });
 }
+
+// ===== 夜间自检 =====
+
+export interface SelfTestConfig {
+  enabled: boolean;
+  /** 自检时段（本地时间，0-23 点） */
+  runHour: number;
+  /** 摘要提醒时间（本地时间，0-23 点） */
+  summaryHour: number;
+  /** 网络变化（VPN 开关、切换 Wi-Fi）后自动重新检查 */
+  networkRecheck: boolean;
+}
+
+export type SelfTestRole =
+  | { kind: "primary" }
+  | { kind: "failover"; position: number };
+
+export interface SelfTestEntry {
+  providerId: string;
+  providerName: string;
+  role: SelfTestRole;
+  result: StreamCheckResult;
+  /** 检查时处于维护时段，失败不算异常 */
+  inMaintenance?: boolean;
+}
+
+export interface SelfTestAppReport {
+  appType: AppId;
+  entries: SelfTestEntry[];
+  /** 一行摘要，如 "Claude primary OK 320ms; failover #2 returning 502" */
+  summary: string;
+}
+
+export interface SelfTestReport {
+  runDate: string;
+  startedAt: number;
+  finishedAt: number;
+  apps: SelfTestAppReport[];
+  notified: boolean;
+}
+
+export const SELF_TEST_SUMMARY_EVENT = "self-test-summary";
+
+/** 网络变化后的重新检查结果 */
+export interface NetworkRecheckReport {
+  reason: "interface" | "ssid" | "manual";
+  fingerprint: { localAddr?: string; ssid?: string };
+  checkedAt: number;
+  apps: SelfTestAppReport[];
+}
+
+export const NETWORK_RECHECK_EVENT = "network-recheck";
+
+/** 与后端 `SelfTestEntry::is_healthy` 保持一致：探测不带凭据，只有 5xx 算异常 */
+export function isSelfTestEntryHealthy(entry: SelfTestEntry): boolean {
+  return (
+    !!entry.inMaintenance ||
+    (entry.result.success && (entry.result.httpStatus ?? 0) < 500)
+  );
+}
+
+export async function getSelfTestConfig(): Promise<SelfTestConfig> {
+  return invoke("get_self_test_config");
+}
+
+export async function saveSelfTestConfig(
+  config: SelfTestConfig,
+): Promise<void> {
+  return invoke("save_self_test_config", { config });
+}
+
+export async function getSelfTestReport(): Promise<SelfTestReport | null> {
+  return invoke("get_self_test_report");
+}
+
+/**
+ * 立即执行一次自检（不受启用开关与时段限制）
+ */
+export async function runSelfTestNow(): Promise<SelfTestReport> {
+  return invoke("run_self_test_now");
+}
+
+/**
+ * 领取待提醒的自检摘要（提醒时间窗口未打开时由启动流程补弹）
+ */
+export async function takePendingSelfTestSummary(): Promise<SelfTestReport | null> {
+  return invoke("take_pending_self_test_summary");
+}
@@ -256,3 +256,38 @@ export async function copilotGetUsageForAccount(
     accountId,
   });
 }
+
+export interface ProvisionedProvider {
+  appType: string;
+  providerId: string;
+  name: string;
+  created: boolean;
+}
+
+/**
+ * 为已认证账号生成 Claude / Codex 供应商（已生成过则更新）
+ *
+ * @param accountId - GitHub 用户 ID
+ * @param model - 选定的 Copilot 模型
+ * @param apps - 目标应用（claude / codex）
+ */
+export async function copilotProvisionProviders(
+  accountId: string,
+  model: string,
+  apps: string[],
+): Promise<ProvisionedProvider[]> {
+  return invoke<ProvisionedProvider[]>("copilot_provision_providers", {
+    accountId,
+    model,
+    apps,
+  });
+}
+
+/**
+ * 按最新模型列表刷新自动生成的供应商
+ *
+ * @returns 更新的供应商数量
+ */
+export async function copilotSyncProvisionedProviders(): Promise<number> {
+  return invoke<number>("copilot_sync_provisioned_providers");
+}
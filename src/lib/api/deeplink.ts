@@ -49,6 +49,14 @@ export interface DeepLinkImportRequest {
   usageAutoInterval?: number;
 }
 
+// "粘贴即添加"识别结果
+export interface PastedProvider {
+  request: DeepLinkImportRequest;
+  // Claude 鉴权方式：Bearer（ANTHROPIC_AUTH_TOKEN）或 x-api-key（ANTHROPIC_API_KEY）
+  authStyle: "authToken" | "apiKey";
+  format: "deeplink" | "json" | "env" | "text";
+}
+
 export interface McpImportResult {
   importedCount: number;
   importedIds: string[];
@@ -79,6 +87,22 @@ export const deeplinkApi = {
     return invoke("parse_deeplink", { url });
   },
 
+  /**
+   * Recognize a provider in pasted text (welcome message, JSON, export lines)
+   * @param text Clipboard text
+   * @returns Import request for the deep link confirmation dialog
+   */
+  parseProviderPaste: async (text: string): Promise<PastedProvider> => {
+    return invoke("parse_provider_paste", { text });
+  },
+
+  /**
+   * Read plain text from the system clipboard
+   */
+  readClipboardText: async (): Promise<string> => {
+    return invoke("read_clipboard_text");
+  },
+
   /**
    * Merge configuration from Base64/URL into a deep link request
    * This is used to show the complete configuration in the confirmation dialog
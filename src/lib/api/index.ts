@@ -22,6 +22,7 @@ export type { Profile, ProfilePayload, ProfilesResponse } from "./profiles";
 export type {
   CopilotDeviceCodeResponse,
   CopilotAuthStatus,
+  CopilotModel,
   GitHubAccount,
 } from "./copilot";
 export type {
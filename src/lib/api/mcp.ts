@@ -8,6 +8,22 @@ import type {
 } from "@/types";
 import type { AppId } from "./types";
 
+export interface McpRuntimeCheck {
+  kind: "node" | "python" | "docker" | "other";
+  runtime: { command: string; available: boolean; version?: string | null };
+  package?: string | null;
+  prepareCommand?: string | null;
+  installUrl?: string | null;
+}
+
+export interface McpStartResult {
+  ok: boolean;
+  serverName?: string | null;
+  serverVersion?: string | null;
+  error?: string | null;
+  elapsedMs: number;
+}
+
 export const mcpApi = {
   async getStatus(): Promise<McpStatus> {
     return await invoke("get_claude_mcp_status");
@@ -126,4 +142,28 @@ export const mcpApi = {
   async importFromApps(): Promise<number> {
     return await invoke("import_mcp_from_apps");
   },
+
+  /**
+   * 检测 stdio 服务器依赖的运行时（npx / uvx / docker）
+   */
+  async checkRuntime(spec: McpServerSpec): Promise<McpRuntimeCheck> {
+    return await invoke("check_mcp_runtime", { spec });
+  },
+
+  /**
+   * 预下载 npm 包或拉取镜像，返回执行的命令（无需准备时为 null）
+   */
+  async prepareRuntime(spec: McpServerSpec): Promise<string | null> {
+    return await invoke("prepare_mcp_runtime", { spec });
+  },
+
+  /**
+   * 试启动服务器并完成 initialize 握手
+   */
+  async verifyServer(
+    spec: McpServerSpec,
+    timeoutSecs?: number,
+  ): Promise<McpStartResult> {
+    return await invoke("verify_mcp_server", { spec, timeoutSecs });
+  },
 };
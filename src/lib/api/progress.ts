@@ -0,0 +1,29 @@
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+
+export const OPERATION_PROGRESS_EVENT = "operation-progress";
+
+/**
+ * 长时间操作的进度事件（与后端 `progress_events::OperationProgress` 对应）
+ * phase 为 `done` / `failed` 时表示操作结束
+ */
+export interface OperationProgress {
+  id: string;
+  /** 操作类型，如 `skill.install`、`skill.index`、`webdav.upload`、`backup` */
+  operation: string;
+  phase: string;
+  /** 0-100；无法估算时为 null，显示不确定进度 */
+  percent: number | null;
+  message: string | null;
+}
+
+export function isProgressFinished(progress: OperationProgress): boolean {
+  return progress.phase === "done" || progress.phase === "failed";
+}
+
+export async function onOperationProgress(
+  handler: (progress: OperationProgress) => void,
+): Promise<UnlistenFn> {
+  return await listen<OperationProgress>(OPERATION_PROGRESS_EVENT, (event) => {
+    handler(event.payload);
+  });
+}
@@ -11,6 +11,25 @@ export interface Prompt {
   updatedAt?: number;
 }
 
+export interface PromptVersion {
+  version: number;
+  content: string;
+  createdAt: number;
+}
+
+export interface PromptDiffLine {
+  kind: "same" | "added" | "removed";
+  text: string;
+}
+
+export interface PromptDiff {
+  version: number;
+  targetPath: string;
+  added: number;
+  removed: number;
+  lines: PromptDiffLine[];
+}
+
 export const promptsApi = {
   async getPrompts(app: AppId): Promise<Record<string, Prompt>> {
     return await invoke("get_prompts", { app });
@@ -35,4 +54,36 @@ export const promptsApi = {
   async getCurrentFileContent(app: AppId): Promise<string | null> {
     return await invoke("get_current_prompt_file_content", { app });
   },
+
+  async getVersions(app: AppId, id: string): Promise<PromptVersion[]> {
+    return await invoke("get_prompt_versions", { app, id });
+  },
+
+  async previewVersion(
+    app: AppId,
+    id: string,
+    version: number,
+    targetApp?: AppId,
+  ): Promise<PromptDiff> {
+    return await invoke("preview_prompt_version", {
+      app,
+      id,
+      version,
+      targetApp,
+    });
+  },
+
+  async deployVersion(
+    app: AppId,
+    id: string,
+    version: number,
+    targetApp?: AppId,
+  ): Promise<void> {
+    return await invoke("deploy_prompt_version", {
+      app,
+      id,
+      version,
+      targetApp,
+    });
+  },
 };
@@ -2,6 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
   Provider,
+  ProviderTrust,
   UniversalProvider,
   UniversalProvidersMap,
 } from "@/types";
@@ -17,10 +18,118 @@ export interface ProviderSwitchEvent {
   providerId: string;
 }
 
+export type ConfigWatchScope = "claude" | "codex" | "gemini" | "database";
+
+export interface ConfigFilesChangedEvent {
+  scopes: ConfigWatchScope[];
+  paths: string[];
+}
+
+export interface LiveConfigDrift {
+  app: AppId;
+  providerId: string;
+  providerName: string;
+  /** 只在 live 中出现（新增或被改动）的配置 */
+  liveOnly: string;
+  /** 只在供应商配置中出现（被删除或被改动）的配置 */
+  storedOnly: string;
+  authChanged: boolean;
+}
+
+export type LiveDriftResolution = "reimport" | "overwrite";
+
+export const PROVIDER_NOTICE_EVENT = "provider-notice";
+
+/** 后台刷新供应商站点图标后发出 */
+export const PROVIDER_FAVICONS_UPDATED_EVENT = "provider-favicons-updated";
+
+/** 上游通过 Deprecation / Sunset 等响应头宣布的变更 */
+export interface ProviderNotice {
+  appType: string;
+  providerId: string;
+  providerName: string;
+  deprecated: boolean;
+  /** 弃用生效时间（unix 秒） */
+  deprecatedAt?: number | null;
+  /** 停止服务时间（unix 秒） */
+  sunsetAt?: number | null;
+  link?: string | null;
+  message?: string | null;
+  firstSeenAt: number;
+  lastSeenAt: number;
+  dismissed: boolean;
+}
+
+export interface TestMessageOptions {
+  message?: string;
+  model?: string;
+  stream?: boolean;
+  requestId?: string;
+}
+
+export interface TestMessageResult {
+  success: boolean;
+  httpStatus: number | null;
+  requestedModel: string;
+  model: string | null;
+  stopReason: string | null;
+  usage: { inputTokens: number | null; outputTokens: number | null } | null;
+  text: string;
+  latencyMs: number;
+  firstTokenMs: number | null;
+  rawBody: string;
+  error: string | null;
+}
+
+export interface TestMessageChunk {
+  requestId: string;
+  delta: string;
+}
+
+export type CapabilitySupport = "supported" | "unsupported" | "unknown";
+
+export const PROMPT_INTEGRITY_ALERT_EVENT = "provider-prompt-integrity-alert";
+
+/** 中转注入 / 截断系统提示词的检测结果 */
+export interface PromptIntegritySignal {
+  appType: string;
+  providerId: string;
+  providerName: string;
+  status: "normal" | "injected" | "truncated";
+  samples: number;
+  medianRatio: number;
+  medianExtraTokens: number;
+}
+
+export interface CapabilityMatrix {
+  model: string;
+  streaming: CapabilitySupport;
+  tools: CapabilitySupport;
+  vision: CapabilitySupport;
+  cacheControl: CapabilitySupport;
+  context1m: CapabilitySupport;
+  maxOutputTokens?: number | null;
+  probedAt: number;
+}
+
 export interface SwitchResult {
   warnings: string[];
 }
 
+/** 首次使用时检测到的已有 CLI 配置 */
+export interface LiveImportCandidate {
+  app: AppId;
+  files: string[];
+  alreadyConfigured: boolean;
+  takenOver: boolean;
+}
+
+export interface LiveImportOutcome {
+  app: AppId;
+  imported: boolean;
+  error?: string;
+}
+
 export interface OpenTerminalOptions {
   cwd?: string;
 }
@@ -40,6 +149,38 @@ export interface ClaudeDesktopStatus {
   gatewayTokenConfigured: boolean;
 }
 
+/** 供应商最近使用情况（时间均为 Unix 秒） */
+export interface ProviderLastSeen {
+  providerId: string;
+  providerName: string;
+  lastActiveAt?: number | null;
+  lastTrafficAt?: number | null;
+  lastSeenAt?: number | null;
+  /** 距最近使用的整天数；当前供应商为 null */
+  idleDays?: number | null;
+  isCurrent: boolean;
+}
+
+/** 解析后的信任等级：source 为 label（用户标注）/ policy（策略文件）/ none */
+export interface ResolvedTrust {
+  level?: ProviderTrust;
+  source: "label" | "policy" | "none";
+  /** 命中的策略规则 */
+  rule?: string;
+}
+
+/** 信任策略文件（~/.cc-switch/provider-trust.json），按上游域名匹配 */
+export interface TrustPolicy {
+  trusted: string[];
+  untrusted: string[];
+  default?: ProviderTrust;
+}
+
+export interface ArchiveProvidersResult {
+  path: string;
+  archived: string[];
+}
+
 export interface ClaudeDesktopDefaultRoute {
   routeId: string;
   envKey: string;
@@ -83,6 +224,113 @@ export const providersApi = {
    * Remove provider from live config only (for additive mode apps like OpenCode)
    * Does NOT delete from database - provider remains in the list
    */
+  async getLastSeen(appId: AppId): Promise<ProviderLastSeen[]> {
+    return await invoke("get_provider_last_seen", { app: appId });
+  },
+
+  /** 已缓存的站点图标（id → data URL），不触发网络请求 */
+  async getFavicons(appId: AppId): Promise<Record<string, string>> {
+    return await invoke("get_provider_favicons", { app: appId });
+  },
+
+  async getTrustLevels(appId: AppId): Promise<Record<string, ResolvedTrust>> {
+    return await invoke("get_provider_trust_levels", { app: appId });
+  },
+
+  /** level 为 null 时清除标注，回退到策略文件判定 */
+  async setTrust(
+    appId: AppId,
+    id: string,
+    level: ProviderTrust | null,
+  ): Promise<boolean> {
+    return await invoke("set_provider_trust", { app: appId, id, level });
+  },
+
+  async getTrustPolicy(): Promise<TrustPolicy> {
+    return await invoke("get_provider_trust_policy");
+  },
+
+  async saveTrustPolicy(policy: TrustPolicy): Promise<boolean> {
+    return await invoke("save_provider_trust_policy", { policy });
+  },
+
+  async getStale(appId: AppId, days?: number): Promise<ProviderLastSeen[]> {
+    return await invoke("get_stale_providers", { app: appId, days });
+  },
+
+  /** 导出到归档文件后删除；当前供应商不能归档 */
+  async archive(ids: string[], appId: AppId): Promise<ArchiveProvidersResult> {
+    return await invoke("archive_providers", { ids, app: appId });
+  },
+
+  /** 生成携带鉴权头与最小请求体的 curl 命令 */
+  async copyAsCurl(
+    id: string,
+    appId: AppId,
+    maskKey: boolean,
+    model?: string,
+  ): Promise<string> {
+    return await invoke("copy_provider_as_curl", {
+      id,
+      app: appId,
+      model,
+      maskKey,
+    });
+  },
+
+  async probeCapabilities(
+    id: string,
+    appId: AppId,
+    model?: string,
+  ): Promise<CapabilityMatrix> {
+    return await invoke("probe_provider_capabilities", {
+      id,
+      app: appId,
+      model,
+    });
+  },
+
+  async sendTestMessage(
+    id: string,
+    appId: AppId,
+    options?: TestMessageOptions,
+  ): Promise<TestMessageResult> {
+    return await invoke("send_test_message_to_provider", {
+      id,
+      app: appId,
+      options,
+    });
+  },
+
+  async onTestMessageChunk(
+    handler: (chunk: TestMessageChunk) => void,
+  ): Promise<UnlistenFn> {
+    return await listen("provider-test-message-chunk", (event) => {
+      handler(event.payload as TestMessageChunk);
+    });
+  },
+
+  async getCapabilities(
+    id: string,
+    appId: AppId,
+  ): Promise<CapabilityMatrix | null> {
+    return await invoke("get_provider_capabilities", { id, app: appId });
+  },
+
+  async getPromptIntegritySignals(
+    appId?: AppId,
+  ): Promise<PromptIntegritySignal[]> {
+    return await invoke("get_prompt_integrity_signals", { app: appId });
+  },
+
+  async getNotices(appId?: AppId): Promise<ProviderNotice[]> {
+    return await invoke("get_provider_notices", { app: appId });
+  },
+
+  async dismissNotice(id: string, appId: AppId): Promise<void> {
+    return await invoke("dismiss_provider_notice", { id, app: appId });
+  },
+
   async removeFromLiveConfig(id: string, appId: AppId): Promise<boolean> {
     return await invoke("remove_provider_from_live_config", { id, app: appId });
   },
@@ -95,6 +343,28 @@ export const providersApi = {
     return await invoke("import_default_config", { app: appId });
   },
 
+  async scanLiveImportCandidates(): Promise<LiveImportCandidate[]> {
+    return await invoke("scan_live_import_candidates");
+  },
+
+  async importLiveConfigs(apps?: AppId[]): Promise<LiveImportOutcome[]> {
+    return await invoke("import_live_configs", { apps });
+  },
+
+  async getLiveConfigDrift(appId: AppId): Promise<LiveConfigDrift | null> {
+    return await invoke("get_live_config_drift", { app: appId });
+  },
+
+  async resolveLiveConfigDrift(
+    appId: AppId,
+    resolution: LiveDriftResolution,
+  ): Promise<boolean> {
+    return await invoke("resolve_live_config_drift", {
+      app: appId,
+      resolution,
+    });
+  },
+
   async importClaudeDesktopFromClaude(): Promise<number> {
     return await invoke("import_claude_desktop_providers_from_claude");
   },
@@ -139,6 +409,14 @@ export const providersApi = {
     });
   },
 
+  async onConfigFilesChanged(
+    handler: (event: ConfigFilesChangedEvent) => void,
+  ): Promise<UnlistenFn> {
+    return await listen("config-files-changed", (event) => {
+      handler(event.payload as ConfigFilesChangedEvent);
+    });
+  },
+
   /**
    * 打开指定提供商的终端
    * 任何提供商都可以打开终端，不受是否为当前激活提供商的限制
@@ -3,9 +3,16 @@ import type {
   ProxyConfig,
   ProxyStatus,
   ProxyServerInfo,
+  ProxyBenchOptions,
+  ProxyBenchReport,
+  ProxyLogLevel,
+  ProxyLogRule,
   ProxyTakeoverStatus,
   GlobalProxyConfig,
   AppProxyConfig,
+  ProxyAuthConfig,
+  ProxyAuthToken,
+  ProxyAuthTokenUsage,
 } from "@/types/proxy";
 
 export const proxyApi = {
@@ -16,6 +23,25 @@ export const proxyApi = {
     return invoke("start_proxy_server");
   },
 
+  // 代理压测（隔离实例 + mock 上游，不影响正在运行的代理）
+  async runProxyBench(options?: ProxyBenchOptions): Promise<ProxyBenchReport> {
+    return invoke("run_proxy_bench", { options });
+  },
+
+  // 获取按路由 / 供应商设置的日志级别
+  async getProxyLogRules(): Promise<ProxyLogRule[]> {
+    return invoke("get_proxy_log_rules");
+  },
+
+  // 设置日志级别（即时生效）；providerId 为空作用于整个路由，level 为 null 移除规则
+  async setProxyLogLevel(
+    app: string,
+    providerId: string | null,
+    level: ProxyLogLevel | null,
+  ): Promise<ProxyLogRule[]> {
+    return invoke("set_proxy_log_level", { app, providerId, level });
+  },
+
   // 停止代理服务器并恢复配置
   async stopProxyWithRestore(): Promise<void> {
     return invoke("stop_proxy_with_restore");
@@ -93,6 +119,43 @@ export const proxyApi = {
     return invoke("update_proxy_config_for_app", { config });
   },
 
+  // ========== 代理鉴权 API ==========
+
+  // 获取代理鉴权配置
+  async getAuthConfig(): Promise<ProxyAuthConfig> {
+    return invoke("get_proxy_auth_config");
+  },
+
+  // 开关代理鉴权（已接管的应用会立即改写客户端凭据）
+  async setAuthEnabled(enabled: boolean): Promise<void> {
+    return invoke("set_proxy_auth_enabled", { enabled });
+  },
+
+  // 列出访问令牌
+  async listAuthTokens(): Promise<ProxyAuthToken[]> {
+    return invoke("list_proxy_auth_tokens");
+  },
+
+  // 新建访问令牌
+  async createAuthToken(name: string): Promise<ProxyAuthToken> {
+    return invoke("create_proxy_auth_token", { name });
+  },
+
+  // 轮换访问令牌
+  async rotateAuthToken(id: string): Promise<void> {
+    return invoke("rotate_proxy_auth_token", { id });
+  },
+
+  // 吊销访问令牌
+  async revokeAuthToken(id: string): Promise<void> {
+    return invoke("revoke_proxy_auth_token", { id });
+  },
+
+  // 按令牌汇总用量
+  async getAuthTokenUsage(since?: number): Promise<ProxyAuthTokenUsage[]> {
+    return invoke("get_proxy_auth_token_usage", { since });
+  },
+
   // ========== 计费默认配置 API ==========
 
   // 获取默认成本倍率
@@ -12,11 +12,46 @@ export interface DeleteSessionResult extends DeleteSessionOptions {
   error?: string;
 }
 
+export interface SessionQuery {
+  providerId?: string;
+  projectDir?: string;
+  limit?: number;
+}
+
+export type ReloadKind = "skill" | "command" | "prompt";
+
+export type SessionReloadStatus =
+  | "loaded"
+  | "pickedUp"
+  | "pending"
+  | "restartRequired";
+
+export interface SessionReloadState {
+  sessionId: string;
+  title?: string;
+  projectDir?: string;
+  status: SessionReloadStatus;
+  resumeCommand?: string;
+}
+
+export interface LiveReloadReport {
+  kind: ReloadKind;
+  name: string;
+  path: string;
+  triggeredAt: number;
+  hotReload: boolean;
+  sessions: SessionReloadState[];
+}
+
 export const sessionsApi = {
   async list(): Promise<SessionMeta[]> {
     return await invoke("list_sessions");
   },
 
+  async listRecent(query?: SessionQuery): Promise<SessionMeta[]> {
+    return await invoke("list_recent_sessions", { query });
+  },
+
   async getMessages(
     providerId: string,
     sourcePath: string,
@@ -51,4 +86,19 @@ export const sessionsApi = {
       customConfig,
     });
   },
+
+  async triggerLiveReload(
+    kind: ReloadKind,
+    name: string,
+  ): Promise<LiveReloadReport> {
+    return await invoke("trigger_live_reload", { kind, name });
+  },
+
+  async checkLiveReload(
+    kind: ReloadKind,
+    name: string,
+    triggeredAt: number,
+  ): Promise<LiveReloadReport> {
+    return await invoke("check_live_reload", { kind, name, triggeredAt });
+  },
 };
@@ -4,6 +4,11 @@ import type {
   WebDavSyncSettings,
   S3SyncSettings,
   RemoteSnapshotInfo,
+  BackupContents,
+  RestoreSelection,
+  RestoreSummary,
+  NotificationChannel,
+  ManagedSetting,
 } from "@/types";
 import type { AppId } from "./types";
 
@@ -26,10 +31,56 @@ export interface CodexUnifyHistoryRestoreResult {
   skippedReason?: string;
 }
 
+/** 本地聚合的功能使用计数 */
+export interface TelemetrySnapshot {
+  since?: number;
+  lastUpdated?: number;
+  counters: Record<string, number>;
+}
+
+/** 导出的使用统计报告 */
+export interface TelemetryReport extends TelemetrySnapshot {
+  appVersion: string;
+  os: string;
+  arch: string;
+  generatedAt: number;
+}
+
+/** 明文 API Key 迁移到系统钥匙串的结果 */
+export interface SecretsMigrationReport {
+  providers: number;
+  secrets: number;
+  failed: string[];
+}
+
+export type PasswordManagerFormat =
+  | "bitwarden-json"
+  | "bitwarden-csv"
+  | "1password-csv";
+
+/** 导出到密码管理器文件的结果 */
+export interface SecretsExportReport {
+  path: string;
+  items: number;
+  skipped: string[];
+}
+
+/** 从密码管理器回写密钥的结果 */
+export interface SecretsImportReport {
+  updated: number;
+  unchanged: number;
+  skipped: string[];
+}
+
 export interface WebDavSyncResult {
   status: string;
 }
 
+export interface RemoteDeleteResult {
+  status: "deleted";
+  deleted: number;
+}
+
 export const settingsApi = {
   async get(): Promise<Settings> {
     return await invoke("get_settings");
@@ -39,6 +90,11 @@ export const settingsApi = {
     return await invoke("save_settings", { settings });
   },
 
+  /** settings.toml / 环境变量覆盖的设置项，界面修改这些项不会生效 */
+  async getManagedSettings(): Promise<ManagedSetting[]> {
+    return await invoke("get_managed_settings");
+  },
+
   /** 是否存在统一 Codex 会话历史的迁移备份（关闭弹窗据此显示"恢复备份"勾选） */
   async hasCodexUnifyHistoryBackup(): Promise<boolean> {
     return await invoke("has_codex_unify_history_backup");
@@ -65,6 +121,11 @@ export const settingsApi = {
     return await invoke("is_portable_mode");
   },
 
+  /** 是否以安全模式（--safe-mode）启动 */
+  async isSafeMode(): Promise<boolean> {
+    return await invoke("is_safe_mode");
+  },
+
   async getConfigDir(appId: AppId): Promise<string> {
     return await invoke("get_config_dir", { app: appId });
   },
@@ -132,6 +193,42 @@ export const settingsApi = {
     return await invoke("import_config_from_file", { filePath });
   },
 
+  // ─── Usage telemetry (local, opt-in) ─────────────────────
+
+  async getTelemetrySnapshot(): Promise<TelemetrySnapshot> {
+    return await invoke("get_telemetry_snapshot");
+  },
+
+  async resetTelemetry(): Promise<boolean> {
+    return await invoke("reset_telemetry");
+  },
+
+  async exportTelemetryReport(filePath: string): Promise<TelemetryReport> {
+    return await invoke("export_telemetry_report", { filePath });
+  },
+
+  // ─── Secrets (OS keychain) ───────────────────────────────
+
+  async migrateSecretsToKeychain(): Promise<SecretsMigrationReport> {
+    return await invoke("migrate_secrets_to_keychain");
+  },
+
+  async exportSecretsForPasswordManager(
+    format: PasswordManagerFormat,
+    filePath: string,
+  ): Promise<SecretsExportReport> {
+    return await invoke("export_secrets_for_password_manager", {
+      format,
+      filePath,
+    });
+  },
+
+  async importSecretsFromPasswordManager(
+    content: string,
+  ): Promise<SecretsImportReport> {
+    return await invoke("import_secrets_from_password_manager", { content });
+  },
+
   // ─── WebDAV sync ──────────────────────────────────────────
 
   async webdavTestConnection(
@@ -152,6 +249,16 @@ export const settingsApi = {
     return await invoke("webdav_sync_download");
   },
 
+  async webdavSyncPreviewRestore(): Promise<BackupContents> {
+    return await invoke("webdav_sync_preview_restore");
+  },
+
+  async webdavSyncRestoreSelected(
+    selection: RestoreSelection,
+  ): Promise<RestoreSummary> {
+    return await invoke("webdav_sync_restore_selected", { selection });
+  },
+
   async webdavSyncSaveSettings(
     settings: WebDavSyncSettings,
     passwordTouched = false,
@@ -168,6 +275,10 @@ export const settingsApi = {
     return await invoke("webdav_sync_fetch_remote_info");
   },
 
+  async webdavSyncDeleteRemote(): Promise<RemoteDeleteResult> {
+    return await invoke("webdav_sync_delete_remote");
+  },
+
   // ===== S3 Sync API =====
 
   async s3TestConnection(
@@ -188,6 +299,16 @@ export const settingsApi = {
     return await invoke("s3_sync_download");
   },
 
+  async s3SyncPreviewRestore(): Promise<BackupContents> {
+    return await invoke("s3_sync_preview_restore");
+  },
+
+  async s3SyncRestoreSelected(
+    selection: RestoreSelection,
+  ): Promise<RestoreSummary> {
+    return await invoke("s3_sync_restore_selected", { selection });
+  },
+
   async s3SyncSaveSettings(
     settings: S3SyncSettings,
     passwordTouched: boolean,
@@ -202,6 +323,10 @@ export const settingsApi = {
     return await invoke("s3_sync_fetch_remote_info");
   },
 
+  async s3SyncDeleteRemote(): Promise<RemoteDeleteResult> {
+    return await invoke("s3_sync_delete_remote");
+  },
+
   async syncCurrentProvidersLive(): Promise<void> {
     const result = (await invoke("sync_current_providers_live")) as {
       success?: boolean;
@@ -233,6 +358,12 @@ export const settingsApi = {
     return await invoke("get_auto_launch_status");
   },
 
+  async testNotificationChannel(
+    channel: NotificationChannel,
+  ): Promise<boolean> {
+    return await invoke("test_notification_channel", { channel });
+  },
+
   async getToolVersions(
     tools?: string[],
     wslShellByTool?: Record<
@@ -248,6 +379,10 @@ export const settingsApi = {
       installed_but_broken: boolean;
       env_type: "windows" | "wsl" | "macos" | "linux" | "unknown";
       wsl_distro: string | null;
+      path: string | null;
+      install_method: string | null;
+      min_supported_version: string | null;
+      below_min_supported: boolean;
     }>
   > {
     return await invoke("get_tool_versions", { tools, wslShellByTool });
@@ -284,6 +419,32 @@ export const settingsApi = {
     return await invoke("set_rectifier_config", { config });
   },
 
+  async getRetryConfig(): Promise<RetryConfig> {
+    return await invoke("get_retry_config");
+  },
+
+  async setRetryConfig(config: RetryConfig): Promise<boolean> {
+    return await invoke("set_retry_config", { config });
+  },
+
+  async getDrainConfig(): Promise<DrainConfig> {
+    return await invoke("get_drain_config");
+  },
+
+  async setDrainConfig(config: DrainConfig): Promise<boolean> {
+    return await invoke("set_drain_config", { config });
+  },
+
+  async getFailbackWatchdogConfig(): Promise<FailbackWatchdogConfig> {
+    return await invoke("get_failback_watchdog_config");
+  },
+
+  async setFailbackWatchdogConfig(
+    config: FailbackWatchdogConfig,
+  ): Promise<boolean> {
+    return await invoke("set_failback_watchdog_config", { config });
+  },
+
   async getOptimizerConfig(): Promise<OptimizerConfig> {
     return await invoke("get_optimizer_config");
   },
@@ -292,6 +453,22 @@ export const settingsApi = {
     return await invoke("set_optimizer_config", { config });
   },
 
+  async getResponseCacheConfig(): Promise<ResponseCacheConfig> {
+    return await invoke("get_response_cache_config");
+  },
+
+  async setResponseCacheConfig(config: ResponseCacheConfig): Promise<boolean> {
+    return await invoke("set_response_cache_config", { config });
+  },
+
+  async getResponseCacheStats(): Promise<ResponseCacheStats> {
+    return await invoke("get_response_cache_stats");
+  },
+
+  async clearResponseCache(): Promise<boolean> {
+    return await invoke("clear_response_cache");
+  },
+
   async getLogConfig(): Promise<LogConfig> {
     return await invoke("get_log_config");
   },
@@ -329,12 +506,51 @@ export interface RectifierConfig {
   requestMediaHeuristic: boolean;
 }
 
+/** 请求内重试配置：瞬时故障先退避重试同一供应商，再走故障转移 */
+export interface RetryConfig {
+  enabled: boolean;
+  sameProviderRetries: number;
+  backoffBaseMs: number;
+  backoffMaxMs: number;
+  budgetMs: number;
+  retryNonIdempotent: boolean;
+}
+
+/** 优雅排空配置：停止代理时等待进行中的请求在原供应商上完成 */
+export interface DrainConfig {
+  enabled: boolean;
+  timeoutSecs: number;
+}
+
+/** 看门狗：当前供应商持续返回鉴权 / 限流错误时自动切走，冷却后切回 */
+export interface FailbackWatchdogConfig {
+  enabled: boolean;
+  windowSecs: number;
+  minFailures: number;
+  errorRatePercent: number;
+  statusCodes: number[];
+  cooloffSecs: number;
+  switchBack: boolean;
+}
+
 export interface OptimizerConfig {
   enabled: boolean;
   thinkingOptimizer: boolean;
   cacheInjection: boolean;
 }
 
+export interface ResponseCacheConfig {
+  enabled: boolean;
+  ttlSeconds: number;
+  maxEntries: number;
+}
+
+export interface ResponseCacheStats {
+  hits: number;
+  misses: number;
+  entries: number;
+}
+
 export interface LogConfig {
   enabled: boolean;
   level: "error" | "warn" | "info" | "debug" | "trace";
@@ -51,6 +51,19 @@ export interface SkillBackupEntry {
   skill: InstalledSkill;
 }
 
+/** Skill 安装目标：全局应用目录或项目目录 */
+export type SkillInstallTarget =
+  | { type: "global" }
+  | { type: "project"; path: string };
+
+/** 项目级 Skill 安装记录 */
+export interface ProjectSkillInstall {
+  projectPath: string;
+  skillId: string;
+  app: AppType;
+  installedAt: number;
+}
+
 /** 可发现的 Skill（来自仓库） */
 export interface DiscoverableSkill {
   key: string;
@@ -126,11 +139,19 @@ export interface SkillsShSearchResult {
 }
 
 /** 仓库配置 */
+/** 仓库来源：GitHub（默认）、GitLab（含自建实例）、本地目录 */
+export type SkillRepoSource = "github" | "gitlab" | "local";
+
 export interface SkillRepo {
   owner: string;
   name: string;
   branch: string;
   enabled: boolean;
+  source?: SkillRepoSource;
+  /** 本地目录路径（local）或 GitLab 实例地址（gitlab） */
+  location?: string;
+  /** 私有仓库访问令牌 */
+  accessToken?: string;
 }
 
 // ========== API ==========
@@ -157,13 +178,33 @@ export const skillsApi = {
   async installUnified(
     skill: DiscoverableSkill,
     currentApp: AppId,
+    target?: SkillInstallTarget,
   ): Promise<InstalledSkill> {
-    return await invoke("install_skill_unified", { skill, currentApp });
+    return await invoke("install_skill_unified", {
+      skill,
+      currentApp,
+      target,
+    });
+  },
+
+  /** 卸载 Skill（统一卸载；项目目标仅移除该项目内的安装） */
+  async uninstallUnified(
+    id: string,
+    target?: SkillInstallTarget,
+    currentApp?: AppId,
+  ): Promise<SkillUninstallResult> {
+    return await invoke("uninstall_skill_unified", {
+      id,
+      target,
+      currentApp,
+    });
   },
 
-  /** 卸载 Skill（统一卸载） */
-  async uninstallUnified(id: string): Promise<SkillUninstallResult> {
-    return await invoke("uninstall_skill_unified", { id });
+  /** 获取项目级 Skill 安装记录 */
+  async getProjectInstalls(
+    projectPath?: string,
+  ): Promise<ProjectSkillInstall[]> {
+    return await invoke("get_project_skill_installs", { projectPath });
   },
 
   /** 从备份恢复 Skill */
@@ -5,6 +5,7 @@ import type {
   DailyStats,
   ProviderStats,
   ModelStats,
+  WorkloadProviderStats,
   RequestLog,
   LogFilters,
   ModelPricing,
@@ -12,6 +13,8 @@ import type {
   PaginatedLogs,
   SessionSyncResult,
   DataSourceSummary,
+  UsageExportFormat,
+  CostSimulation,
 } from "@/types/usage";
 import type { UsageResult } from "@/types";
 import type { AppId } from "./types";
@@ -126,6 +129,14 @@ export const usageApi = {
     });
   },
 
+  getWorkloadComparison: async (
+    startDate?: number,
+    endDate?: number,
+    appType?: string,
+  ): Promise<WorkloadProviderStats[]> => {
+    return invoke("get_workload_comparison", { startDate, endDate, appType });
+  },
+
   getRequestLogs: async (
     filters: LogFilters,
     page: number = 0,
@@ -142,6 +153,13 @@ export const usageApi = {
     return invoke("get_request_detail", { requestId });
   },
 
+  copyRequestAsCurl: async (
+    requestId: string,
+    maskKey: boolean,
+  ): Promise<string> => {
+    return invoke("copy_request_as_curl", { requestId, maskKey });
+  },
+
   getModelPricing: async (): Promise<ModelPricing[]> => {
     return invoke("get_model_pricing");
   },
@@ -187,4 +205,30 @@ export const usageApi = {
   getDataSourceBreakdown: async (): Promise<DataSourceSummary[]> => {
     return invoke("get_usage_data_sources");
   },
+
+  // 导出用量明细；传 filePath 时写入文件并返回路径，否则返回导出文本
+  exportUsage: async (
+    format: UsageExportFormat,
+    startDate?: number,
+    endDate?: number,
+    appType?: string,
+    filePath?: string,
+  ): Promise<string> => {
+    return invoke("export_usage", {
+      format,
+      startDate,
+      endDate,
+      appType,
+      filePath,
+    });
+  },
+
+  // 模拟最近 days 天的流量改走指定供应商时的花费
+  simulateProviderCost: async (
+    appType: string,
+    providerId: string,
+    days: number,
+  ): Promise<CostSimulation> => {
+    return invoke("simulate_provider_cost", { appType, providerId, days });
+  },
 };
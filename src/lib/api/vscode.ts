@@ -1,12 +1,22 @@
 import { invoke } from "@tauri-apps/api/core";
 import type { CustomEndpoint } from "@/types";
 import type { AppId } from "./types";
+import type { HttpErrorClass } from "./connectivity-check";
 
 export interface EndpointLatencyResult {
   url: string;
   latency: number | null;
   status?: number;
   error?: string;
+  errorClass?: HttpErrorClass;
+}
+
+/** 以供应商身份测速：携带其鉴权头、自定义请求头并走其出站代理 */
+export interface ProviderSpeedtestOptions {
+  app: AppId;
+  providerId: string;
+  /** 发送最小真实补全请求并测首字节时间，而非对地址做 GET */
+  completion?: boolean;
 }
 
 export const vscodeApi = {
@@ -16,11 +26,12 @@ export const vscodeApi = {
 
   async testApiEndpoints(
     urls: string[],
-    options?: { timeoutSecs?: number },
+    options?: { timeoutSecs?: number; provider?: ProviderSpeedtestOptions },
   ): Promise<EndpointLatencyResult[]> {
     return await invoke("test_api_endpoints", {
       urls,
       timeoutSecs: options?.timeoutSecs,
+      provider: options?.provider,
     });
   },
 
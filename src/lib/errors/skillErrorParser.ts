@@ -39,6 +39,7 @@ function getErrorI18nKey(code: string): string {
     EMPTY_ARCHIVE: "skills.error.emptyArchive",
     GET_HOME_DIR_FAILED: "skills.error.getHomeDirFailed",
     NO_SKILLS_IN_ZIP: "skills.error.noSkillsInZip",
+    LOCAL_SOURCE_NOT_FOUND: "skills.error.localSourceNotFound",
   };
 
   return mapping[code] || "skills.error.unknownError";
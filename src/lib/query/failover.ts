@@ -181,6 +181,30 @@ export function useRemoveFromFailoverQueue() {
   });
 }
 
+/**
+ * 调整故障转移队列顺序
+ */
+export function useReorderFailoverQueue() {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({
+      appType,
+      providerIds,
+    }: {
+      appType: string;
+      providerIds: string[];
+    }) => failoverApi.reorderFailoverQueue(appType, providerIds),
+    onSuccess: (queue, variables) => {
+      queryClient.setQueryData(["failoverQueue", variables.appType], queue);
+      // 队列顺序即首页列表顺序
+      queryClient.invalidateQueries({
+        queryKey: ["providers", variables.appType],
+      });
+    },
+  });
+}
+
 // ========== 自动故障转移开关 Hooks ==========
 
 /**
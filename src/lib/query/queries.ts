@@ -20,25 +20,13 @@ import type {
 } from "@/types";
 import { usageKeys } from "@/lib/query/usage";
 import { extractErrorMessage } from "@/utils/errorUtils";
+import { compareProviderOrder } from "@/utils/providerOrder";
 
 const sortProviders = (
   providers: Record<string, Provider>,
 ): Record<string, Provider> => {
   const sortedEntries = Object.values(providers)
-    .sort((a, b) => {
-      const indexA = a.sortIndex ?? Number.MAX_SAFE_INTEGER;
-      const indexB = b.sortIndex ?? Number.MAX_SAFE_INTEGER;
-      if (indexA !== indexB) {
-        return indexA - indexB;
-      }
-
-      const timeA = a.createdAt ?? 0;
-      const timeB = b.createdAt ?? 0;
-      if (timeA === timeB) {
-        return a.name.localeCompare(b.name, "zh-CN");
-      }
-      return timeA - timeB;
-    })
+    .sort(compareProviderOrder)
     .map((provider) => [provider.id, provider] as const);
 
   return Object.fromEntries(sortedEntries);
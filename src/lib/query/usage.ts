@@ -125,6 +125,22 @@ export const usageKeys = {
       filters?.providerName ?? null,
       filters?.model ?? null,
     ] as const,
+  workloadComparison: (
+    preset: UsageRangeSelection["preset"],
+    customStartDate: number | undefined,
+    customEndDate: number | undefined,
+    appType?: string,
+    liveEndTime?: boolean,
+  ) =>
+    [
+      ...usageKeys.all,
+      "workload-comparison",
+      preset,
+      customStartDate ?? 0,
+      customEndDate ?? 0,
+      liveEndTime ?? false,
+      appType ?? null,
+    ] as const,
   logs: (key: RequestLogsKey, page: number, pageSize: number) =>
     [
       ...usageKeys.all,
@@ -273,6 +289,33 @@ export function useProviderStats(
   });
 }
 
+export function useWorkloadComparison(
+  range: UsageRangeSelection,
+  appType?: string,
+  options?: UsageQueryOptions,
+) {
+  const effective = normalizeScopeFilters({ appType });
+  return useQuery({
+    queryKey: usageKeys.workloadComparison(
+      range.preset,
+      range.customStartDate,
+      range.customEndDate,
+      effective.appType,
+      range.liveEndTime,
+    ),
+    queryFn: () => {
+      const { startDate, endDate } = resolveUsageRange(range);
+      return usageApi.getWorkloadComparison(
+        startDate,
+        endDate,
+        effective.appType,
+      );
+    },
+    refetchInterval: options?.refetchInterval ?? DEFAULT_REFETCH_INTERVAL_MS,
+    refetchIntervalInBackground: options?.refetchIntervalInBackground ?? false,
+  });
+}
+
 export function useModelStats(
   range: UsageRangeSelection,
   filters?: UsageScopeFilters,
@@ -169,6 +169,56 @@ export interface LocalProxyRequestOverrides {
 }
 
 // 供应商元数据（字段名与后端一致，保持 snake_case）
+export interface ProviderTlsOptions {
+  // 额外信任的根证书文件路径（PEM 或 DER）
+  caCertPath?: string;
+  // 跳过证书校验（危险，需用户确认后开启）
+  insecureSkipVerify?: boolean;
+}
+
+// 供应商级超时覆盖（秒）：留空沿用应用级配置，0 表示不限制
+export interface ProviderTimeouts {
+  connectTimeoutSecs?: number;
+  totalTimeoutSecs?: number;
+  streamIdleTimeoutSecs?: number;
+}
+
+// HMAC 签名鉴权：签名原文由 template 渲染，支持 {method} {path} {query}
+// {timestamp} {nonce} {body_sha256} {api_key} 占位符
+export interface HmacAuthOptions {
+  secret: string;
+  template?: string;
+  signatureHeader?: string;
+  signaturePrefix?: string;
+  timestampHeader?: string;
+  nonceHeader?: string;
+  // 携带 API Key 的请求头，默认 x-api-key；空字符串表示不发送
+  keyHeader?: string;
+  encoding?: "hex" | "base64";
+}
+
+// 自定义鉴权方式（替代默认的 x-api-key / Bearer）
+export type CustomAuthScheme =
+  | { type: "queryParam"; param: string }
+  | { type: "cookie"; name: string }
+  | ({ type: "hmac" } & HmacAuthOptions);
+
+// 内置模拟供应商（base_url 为 mock://）的应答配置
+export interface MockProviderOptions {
+  // 为空时回显；一条时固定回复；多条时轮流回复
+  replies?: string[];
+  latencyMs?: number;
+  // 0 ~ 1
+  failureRate?: number;
+  failureStatus?: number;
+}
+
+// 客户端标识策略：透传客户端 UA / 伪装 Claude Code / 如实标识 cc-switch / 自定义 UA
+export type ClientIdentity = "passthrough" | "claudeCode" | "ccSwitch" | "custom";
+
+// 供应商信任等级
+export type ProviderTrust = "trusted" | "untrusted";
+
 export interface ProviderMeta {
   // 自定义端点：以 URL 为键，值为端点信息
   custom_endpoints?: Record<string, CustomEndpoint>;
@@ -190,6 +240,22 @@ export interface ProviderMeta {
   costMultiplier?: string;
   // 供应商计费模式来源
   pricingModelSource?: string;
+  // 预算：每日/每月消费上限（美元，字符串保存精度）
+  limitDailyUsd?: string;
+  limitMonthlyUsd?: string;
+  // 预算：每日/每月 token 上限（输入 + 输出）
+  limitDailyTokens?: number;
+  limitMonthlyTokens?: number;
+  // 达到预算上限后代理停止路由到该供应商
+  budgetHardLimit?: boolean;
+  // 维护时段：时段内代理优先路由到其他供应商，健康检查不计失败
+  maintenanceWindows?: MaintenanceWindow[];
+  // 首字延迟 SLO：违反时提醒，可选在故障转移中后移
+  latencySlo?: LatencySlo;
+  // 候选端点池：基础地址 + 自定义端点中自动选择最快的可用端点
+  endpointPool?: EndpointPool;
+  // 端点池最近一次的选择结果（后端写入）
+  selectedEndpoint?: SelectedEndpoint;
   // API 格式（Claude / Codex 供应商使用）
   // - "anthropic": 原生 Anthropic Messages API 格式，直接透传
   // - "openai_chat": OpenAI Chat Completions 格式，需要格式转换
@@ -225,12 +291,30 @@ export interface ProviderMeta {
   maxOutputTokens?: number;
   // Custom User-Agent for local proxy routing. Only applied by the local proxy.
   customUserAgent?: string;
+  // 发往上游的客户端标识策略（转发、检测、测速、获取模型共用）；未设置时有自定义 UA 即视为 custom
+  clientIdentity?: ClientIdentity;
   // Local proxy request overrides. Only applied by the local proxy after route transforms.
   localProxyRequestOverrides?: LocalProxyRequestOverrides;
+  // 供应商级 TLS 选项（私有 CA / 跳过证书校验），作用于连通性检查与代理转发
+  tls?: ProviderTlsOptions;
+  // 供应商级超时覆盖（连接 / 非流式总超时 / 流式静默期）
+  timeouts?: ProviderTimeouts;
+  // 自定义鉴权方式（查询参数 / Cookie / HMAC 签名），作用于代理转发与测试请求
+  customAuth?: CustomAuthScheme;
+  // 修复中转弄坏的中日韩响应文本（乱码 / 双重转义 / GBK），仅本地代理转发生效
+  repairResponseText?: boolean;
+  // 内置模拟供应商的应答配置，仅 base_url 为 mock:// 时生效
+  mock?: MockProviderOptions;
+  // 供应商级代理覆盖：切换到该供应商时仅为它接管 Live 并经本地代理转发
+  useLocalProxy?: boolean;
   // 供应商类型（用于识别 Copilot 等特殊供应商）
   providerType?: string;
   // GitHub Copilot 关联账号 ID（旧字段，保留兼容读取）
   githubAccountId?: string;
+  // 由 Copilot 账号自动生成的供应商所选模型（随账号模型列表自动更新）
+  copilotModel?: string;
+  // 信任标注：不受信任的供应商在切换/加入故障转移时需要确认
+  trustLevel?: ProviderTrust;
 }
 
 // Skill 同步方式
@@ -296,6 +380,46 @@ export interface WebDavSyncStatus {
   lastRemoteManifestHash?: string | null;
 }
 
+// 供应商维护时段（如中转站每天 03:00–03:10 重启）
+export interface MaintenanceWindow {
+  // HH:MM
+  start: string;
+  // HH:MM，早于 start 表示跨越午夜
+  end: string;
+  // "local"（默认）、"UTC"、"+08:00"、"UTC+8" 或 "CST" 等常见缩写
+  timezone?: string;
+  // 1 = 周一 … 7 = 周日，为空表示每天
+  days?: number[];
+}
+
+// 首字延迟 SLO（如 p95 TTFB < 3000ms），代理按滚动窗口评估
+export interface LatencySlo {
+  // 百分位（1–100），默认 95
+  percentile?: number;
+  thresholdMs: number;
+  // 滚动窗口（分钟），默认 15
+  windowMinutes?: number;
+  // 窗口内样本数达到该值才评估，默认 10
+  minSamples?: number;
+  // 违反期间在故障转移队列中后移
+  demote?: boolean;
+}
+
+export interface EndpointPool {
+  // 切换到该供应商后测速选择，默认 true
+  onSwitch?: boolean;
+  // 作为当前供应商时定期重新选择的间隔（分钟），0 表示不定期
+  intervalMinutes?: number;
+}
+
+export interface SelectedEndpoint {
+  url: string;
+  latencyMs: number;
+  // 毫秒时间戳
+  selectedAt: number;
+  candidates: number;
+}
+
 // WebDAV 同步配置
 export interface WebDavSyncSettings {
   enabled?: boolean;
@@ -305,6 +429,11 @@ export interface WebDavSyncSettings {
   password?: string;
   remoteRoot?: string;
   profile?: string;
+  encryptionEnabled?: boolean;
+  // 后端读取时总是清空，留空保存表示沿用现有口令
+  encryptionPassphrase?: string;
+  // 增量同步用量明细
+  syncUsage?: boolean;
   status?: WebDavSyncStatus;
 }
 
@@ -319,6 +448,11 @@ export interface S3SyncSettings {
   endpoint?: string;
   remoteRoot?: string;
   profile?: string;
+  encryptionEnabled?: boolean;
+  // 后端读取时总是清空，留空保存表示沿用现有口令
+  encryptionPassphrase?: string;
+  // 增量同步用量明细
+  syncUsage?: boolean;
   status?: WebDavSyncStatus;
 }
 
@@ -338,8 +472,104 @@ export interface RemoteSnapshotInfo {
   remotePath: string;
 }
 
+// 云端备份中的条目（选择性恢复）
+export interface BackupProviderEntry {
+  appType: string;
+  id: string;
+  name: string;
+  isCurrent: boolean;
+  existsLocally: boolean;
+}
+
+export interface BackupMcpEntry {
+  id: string;
+  name: string;
+  existsLocally: boolean;
+}
+
+export interface BackupContents {
+  deviceName: string;
+  createdAt: string;
+  providers: BackupProviderEntry[];
+  mcpServers: BackupMcpEntry[];
+  settings: string[];
+}
+
+export interface RestoreSelection {
+  providers: { appType: string; id: string }[];
+  apps?: string[];
+  mcpServers: string[];
+  settings: boolean;
+}
+
+export interface RestoreSummary {
+  providers: number;
+  mcpServers: number;
+  settings: number;
+  warning?: string;
+}
+
+// 供应商切换钩子
+export type SwitchHookEvent = "pre-switch" | "post-switch" | "switch-failed";
+
+export type SwitchHook = {
+  id: string;
+  enabled?: boolean;
+  // 为空表示所有应用
+  apps?: string[];
+  events: SwitchHookEvent[];
+  timeoutSecs?: number;
+} & ({ type: "command"; command: string } | { type: "webhook"; url: string });
+
+// 外部通知渠道
+export type NotificationEvent =
+  | "budget-alert"
+  | "provider-down"
+  | "sync-failed"
+  | "auto-switch";
+
+export type SmtpSecurity = "tls" | "starttls" | "none";
+
+export type NotificationChannel = {
+  id: string;
+  enabled?: boolean;
+  // 为空表示接收全部事件
+  events?: NotificationEvent[];
+} & (
+  | { type: "bark"; serverUrl?: string; deviceKey: string }
+  | { type: "telegram"; botToken: string; chatId: string }
+  | { type: "ntfy"; serverUrl?: string; topic: string; token?: string }
+  | {
+      type: "email";
+      host: string;
+      port?: number;
+      security?: SmtpSecurity;
+      username?: string;
+      password?: string;
+      from: string;
+      to: string[];
+    }
+  | { type: "webhook"; url: string }
+);
+
+// 模型别名：供应商配置、代理请求中可写 alias，写入 Live / 转发前解析为 model
+export type ModelAlias = {
+  alias: string;
+  model: string;
+};
+
+// 由 ~/.cc-switch/settings.toml 或 CC_SWITCH_* 环境变量覆盖的设置项
+export interface ManagedSetting {
+  // settings.json 键名；代理监听为 proxy.listenAddress / proxy.listenPort
+  key: string;
+  source: "file" | "env";
+  envVar?: string;
+}
+
 // 应用设置类型（用于设置对话框与 Tauri API）
 // 存储在本地 ~/.cc-switch/settings.json，不随数据库同步
+export type IpFamilyPreference = "auto" | "ipv4" | "ipv6";
+
 export interface Settings {
   // ===== 设备级 UI 设置 =====
   // 是否在系统托盘（macOS 菜单栏）显示图标
@@ -352,12 +582,28 @@ export interface Settings {
   enableClaudePluginIntegration?: boolean;
   // 跳过 Claude Code 初次安装确认（写入 ~/.claude.json 的 hasCompletedOnboarding）
   skipClaudeOnboarding?: boolean;
+  // 切换供应商时同步已安装插件到 settings.json 的 enabledPlugins（默认开启）
+  syncClaudePlugins?: boolean;
   // 是否开机自启
   launchOnStartup?: boolean;
   // 静默启动（程序启动时不显示主窗口）
   silentStartup?: boolean;
   // 是否启用主页面本地代理功能（默认关闭）
   enableLocalProxy?: boolean;
+  // 代理会话亲和：同一会话优先命中上次成功的供应商（默认开启）
+  proxySessionAffinity?: boolean;
+  // 会话亲和绑定的空闲过期时间（秒，默认 3600）
+  proxySessionAffinityTtlSecs?: number;
+  // 代理运行时为已接管应用的当前供应商保持上游连接（默认关闭）
+  proxyKeepWarm?: boolean;
+  // 出站连接的 IP 协议族偏好（代理转发、端点测速、连通性检查）
+  networkIpPreference?: IpFamilyPreference;
+  // Happy Eyeballs：首选地址迟迟连不上时并行尝试其它地址（默认开启）
+  networkHappyEyeballs?: boolean;
+  // 离线模式：除供应商端点外禁止一切出站请求（技能市场、版本检查、云同步、自动更新等）
+  offlineMode?: boolean;
+  // 新增/编辑供应商时把 API Key 存入系统钥匙串，配置中只保留引用
+  secretsUseKeychain?: boolean;
   // User has confirmed the local proxy first-run notice
   proxyConfirmed?: boolean;
   // User has confirmed the usage query first-run notice
@@ -372,6 +618,8 @@ export interface Settings {
   // Run official Codex under the shared "custom" provider id so future
   // sessions share one resume-history bucket with third-party providers
   unifyCodexSessionHistory?: boolean;
+  // Opt-in local usage telemetry (feature counters, never uploaded automatically)
+  usageTelemetryEnabled?: boolean;
   // User opted in (enable dialog checkbox) to migrate existing official sessions
   unifyCodexMigrateExisting?: boolean;
   // User has confirmed the failover toggle first-run notice
@@ -439,6 +687,23 @@ export interface Settings {
   // Linux: "gnome-terminal" | "konsole" | "xfce4-terminal" | "alacritty" | "kitty" | "ghostty"
   preferredTerminal?: string;
 
+  // ===== 供应商切换钩子 =====
+  switchHooks?: SwitchHook[];
+
+  // ===== 状态栏集成 =====
+  // 切换后写入 ~/.cc-switch/status.json，供 tmux / starship 等读取
+  statusFileEnabled?: boolean;
+
+  // ===== 供应商图标 =====
+  // 后台抓取并缓存供应商官网图标（默认关闭）
+  providerFaviconsEnabled?: boolean;
+
+  // ===== 通知渠道 =====
+  notificationChannels?: NotificationChannel[];
+
+  // ===== 模型别名 =====
+  modelAliases?: ModelAlias[];
+
   // ===== 本机自动迁移状态 =====
   localMigrations?: {
     codexThirdPartyHistoryProviderBucketV1?: {
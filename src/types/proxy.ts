@@ -27,6 +27,9 @@ export interface ProxyStatus {
   last_error: string | null;
   failover_count: number;
   active_targets?: ActiveTarget[];
+  /** 正在停止并等待进行中的请求完成 */
+  draining?: boolean;
+  in_flight?: InFlightGroup[];
 }
 
 export interface ActiveTarget {
@@ -35,6 +38,52 @@ export interface ActiveTarget {
   provider_id: string;
 }
 
+/** 按应用与供应商分组的进行中请求 */
+export interface InFlightGroup {
+  app_type: string;
+  provider_id: string | null;
+  provider_name: string | null;
+  count: number;
+  oldest_secs: number;
+  /** 供应商已被切走：这些请求正在原供应商上排空 */
+  draining: boolean;
+}
+
+export interface ProxyBenchOptions {
+  concurrency?: number;
+  requests?: number;
+  upstreamDelayMs?: number;
+}
+
+export interface ProxyBenchReport {
+  requests: number;
+  concurrency: number;
+  succeeded: number;
+  failed: number;
+  totalMs: number;
+  throughputRps: number;
+  latencyMs: {
+    min: number;
+    mean: number;
+    p50: number;
+    p90: number;
+    p99: number;
+    max: number;
+  };
+}
+
+export type ProxyLogLevel =
+  | "silent"
+  | "summary"
+  | "headers"
+  | "full-body-redacted";
+
+export interface ProxyLogRule {
+  app: string;
+  providerId?: string;
+  level: ProxyLogLevel;
+}
+
 export interface ProxyServerInfo {
   address: string;
   port: number;
@@ -114,6 +163,8 @@ export interface FailoverQueueItem {
   providerName: string;
   providerNotes?: string;
   sortIndex?: number;
+  // 持久化的健康状态，没有记录时为 true
+  healthy: boolean;
 }
 
 // 全局代理配置（统一字段，三行镜像）
@@ -124,6 +175,28 @@ export interface GlobalProxyConfig {
   enableLogging: boolean;
 }
 
+// 本地代理鉴权配置
+export interface ProxyAuthConfig {
+  enabled: boolean;
+}
+
+// 本地代理访问令牌（第一个令牌写入被接管应用的 Live 配置）
+export interface ProxyAuthToken {
+  id: string;
+  name: string;
+  token: string;
+  createdAt: number;
+  lastUsedAt?: number;
+}
+
+// 按令牌汇总的用量
+export interface ProxyAuthTokenUsage {
+  tokenId: string;
+  requestCount: number;
+  totalTokens: number;
+  totalCost: string;
+}
+
 // 应用级代理配置（每个 app 独立）
 export interface AppProxyConfig {
   appType: string;
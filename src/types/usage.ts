@@ -14,6 +14,8 @@ export interface RequestLog {
   appType: string;
   model: string;
   requestModel?: string;
+  /** 别名解析 / 模型映射后实际发往上游的模型 */
+  mappedModel?: string;
   /** 写入时实际用于计价的模型名；路由接管 + request 计价模式下可能与 model 不同 */
   pricingModel?: string;
   costMultiplier: string;
@@ -34,6 +36,10 @@ export interface RequestLog {
   errorMessage?: string;
   createdAt: number;
   dataSource?: string;
+  /** 上游重试次数（同供应商瞬时故障重试 + 故障转移） */
+  retryCount: number;
+  /** 经不受信任的供应商转发 */
+  untrusted: boolean;
 }
 
 export interface SessionSyncResult {
@@ -45,6 +51,37 @@ export interface SessionSyncResult {
   errors: string[];
 }
 
+export type UsageExportFormat = "csv" | "json";
+
+export interface CostSimulationRow {
+  requestedModel: string;
+  actualModel: string;
+  simulatedModel: string;
+  requestCount: number;
+  inputTokens: number;
+  outputTokens: number;
+  cacheReadTokens: number;
+  cacheCreationTokens: number;
+  actualCostUsd: string;
+  simulatedCostUsd: string;
+  priced: boolean;
+}
+
+export interface CostSimulation {
+  appType: string;
+  providerId: string;
+  providerName: string;
+  days: number;
+  costMultiplier: string;
+  requestCount: number;
+  actualCostUsd: string;
+  simulatedCostUsd: string;
+  /** 模拟 - 实际；负数表示切换后更便宜 */
+  deltaUsd: string;
+  rows: CostSimulationRow[];
+  unpricedModels: string[];
+}
+
 export interface DataSourceSummary {
   dataSource: string;
   requestCount: number;
@@ -115,6 +152,20 @@ export interface ModelStats {
   avgCostPerRequest: string;
 }
 
+export interface WorkloadProviderStats {
+  workload: string;
+  modelClass: string;
+  sizeBucket: string;
+  providerId: string;
+  providerName: string;
+  requestCount: number;
+  successRate: number;
+  avgLatencyMs: number;
+  avgFirstTokenMs?: number | null;
+  avgCostPerRequest: string;
+  avgOutputTokens: number;
+}
+
 export interface LogFilters {
   appType?: string;
   providerName?: string;
@@ -146,6 +197,63 @@ export interface ProviderLimitStatus {
   monthlyUsage: string;
   monthlyLimit?: string;
   monthlyExceeded: boolean;
+  dailyTokens: number;
+  dailyTokenLimit?: number;
+  monthlyTokens: number;
+  monthlyTokenLimit?: number;
+  hardLimit: boolean;
+}
+
+export const PROVIDER_BUDGET_ALERT_EVENT = "provider-budget-alert";
+
+export interface ProviderBudgetAlert {
+  appType: string;
+  providerId: string;
+  providerName: string;
+  period: "daily" | "monthly";
+  metric: "usd" | "tokens";
+  level: "warning" | "exceeded";
+  used: number;
+  limit: number;
+  hardLimit: boolean;
+}
+
+export const PROVIDER_LATENCY_SLO_EVENT = "provider-latency-slo";
+
+export interface ProviderLatencySloAlert {
+  appType: string;
+  providerId: string;
+  providerName: string;
+  violated: boolean;
+  percentile: number;
+  thresholdMs: number;
+  observedMs: number;
+  windowMinutes: number;
+  demoted: boolean;
+}
+
+export const PROVIDER_WATCHDOG_SWITCH_EVENT = "provider-watchdog-switch";
+
+export interface ProviderWatchdogSwitch {
+  appType: string;
+  fromProviderId: string;
+  fromProviderName: string;
+  toProviderId: string;
+  toProviderName: string;
+  reason: "failing" | "cooloffElapsed";
+  failures: number;
+  requests: number;
+}
+
+export const PROVIDER_ENDPOINT_SELECTED_EVENT = "provider-endpoint-selected";
+
+export interface ProviderEndpointSelected {
+  appType: string;
+  providerId: string;
+  providerName: string;
+  previousUrl: string;
+  url: string;
+  latencyMs: number;
 }
 
 export type UsageRangePreset = "today" | "1d" | "7d" | "14d" | "30d" | "custom";
@@ -0,0 +1,22 @@
+import type { Provider } from "@/types";
+
+/**
+ * 供应商显示顺序，与数据库查询（`ORDER BY COALESCE(sort_index, 999999),
+ * created_at, id`）及 CLI 编号保持一致：sortIndex 升序，未设置的排在最后，
+ * 其次按创建时间、id。不按名称排序，避免显示语言影响顺序。
+ */
+export function compareProviderOrder(a: Provider, b: Provider): number {
+  const indexA = a.sortIndex ?? Number.MAX_SAFE_INTEGER;
+  const indexB = b.sortIndex ?? Number.MAX_SAFE_INTEGER;
+  if (indexA !== indexB) {
+    return indexA - indexB;
+  }
+
+  const timeA = a.createdAt ?? 0;
+  const timeB = b.createdAt ?? 0;
+  if (timeA !== timeB) {
+    return timeA - timeB;
+  }
+
+  return a.id < b.id ? -1 : a.id > b.id ? 1 : 0;
+}
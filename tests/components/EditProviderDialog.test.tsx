@@ -38,6 +38,10 @@ vi.mock("@/components/common/FullScreenPanel", () => ({
     ) : null,
 }));
 
+vi.mock("@/components/providers/ProviderCapabilityMatrix", () => ({
+  ProviderCapabilityMatrix: () => null,
+}));
+
 vi.mock("@/components/providers/forms/ProviderForm", () => ({
   ProviderForm: ({
     initialData,
@@ -1,9 +1,11 @@
-import { render, screen, fireEvent } from "@testing-library/react";
+import { render, screen, fireEvent, waitFor } from "@testing-library/react";
 import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
 import { describe, it, expect, vi, beforeEach } from "vitest";
+import { http, HttpResponse } from "msw";
 import type { ReactElement } from "react";
 import type { Provider } from "@/types";
 import { ProviderList } from "@/components/providers/ProviderList";
+import { server } from "../msw/server";
 
 const useDragSortMock = vi.fn();
 const useSortableMock = vi.fn();
@@ -193,6 +195,55 @@ describe("ProviderList Component", () => {
     expect(handleCreate).toHaveBeenCalledTimes(1);
   });
 
+  it("offers to import existing CLI configs from the empty state", async () => {
+    let importedApps: unknown;
+    server.use(
+      http.post("http://tauri.local/scan_live_import_candidates", () =>
+        HttpResponse.json([
+          {
+            app: "claude",
+            files: ["~/.claude/settings.json"],
+            alreadyConfigured: false,
+            takenOver: false,
+          },
+          {
+            app: "codex",
+            files: ["~/.codex/config.toml"],
+            alreadyConfigured: true,
+            takenOver: false,
+          },
+        ]),
+      ),
+      http.post(
+        "http://tauri.local/import_live_configs",
+        async ({ request }) => {
+          importedApps = ((await request.json()) as { apps: unknown }).apps;
+          return HttpResponse.json([{ app: "claude", imported: true }]);
+        },
+      ),
+    );
+
+    renderWithQueryClient(
+      <ProviderList
+        providers={{}}
+        currentProviderId=""
+        appId="claude"
+        onSwitch={vi.fn()}
+        onEdit={vi.fn()}
+        onDelete={vi.fn()}
+        onDuplicate={vi.fn()}
+        onOpenWebsite={vi.fn()}
+      />,
+    );
+
+    const importAllButton = await screen.findByRole("button", {
+      name: "provider.importAllLive",
+    });
+    fireEvent.click(importAllButton);
+
+    await waitFor(() => expect(importedApps).toEqual(["claude"]));
+  });
+
   it("should render in order returned by useDragSort and pass through action callbacks", () => {
     const providerA = createProvider({ id: "a", name: "A" });
     const providerB = createProvider({ id: "b", name: "B" });
@@ -65,6 +65,10 @@ vi.mock("@/components/usage/ModelStatsTable", () => ({
   ModelStatsTable: () => <div data-testid="model-stats-table" />,
 }));
 
+vi.mock("@/components/usage/CostSimulationPanel", () => ({
+  CostSimulationPanel: () => <div data-testid="cost-simulation-panel" />,
+}));
+
 vi.mock("@/components/usage/PricingConfigPanel", () => ({
   PricingConfigPanel: () => <div data-testid="pricing-config-panel" />,
 }));
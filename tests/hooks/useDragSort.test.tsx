@@ -72,7 +72,7 @@ describe("useDragSort", () => {
     consoleErrorSpy.mockRestore();
   });
 
-  it("should sort providers by sortIndex, createdAt, and name", () => {
+  it("should sort providers by sortIndex, createdAt, and id", () => {
     const { wrapper } = createWrapper();
 
     const { result } = renderHook(() => useDragSort(mockProviders, "claude"), {
@@ -123,6 +123,10 @@ export const handlers = [
     return success(true);
   }),
 
+  http.post(`${TAURI_ENDPOINT}/scan_live_import_candidates`, () => success([])),
+
+  http.post(`${TAURI_ENDPOINT}/import_live_configs`, () => success([])),
+
   http.post(`${TAURI_ENDPOINT}/open_external`, () => success(true)),
 
   http.post(`${TAURI_ENDPOINT}/list_sessions`, () => success(listSessions())),